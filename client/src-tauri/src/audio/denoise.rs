@@ -0,0 +1,67 @@
+//! Noise Suppression
+//!
+//! RNNoise (through the `nnnoiseless` port) applied to captured audio before
+//! it is Opus-encoded. RNNoise works on mono 10 ms frames at 48 kHz, so each
+//! channel of an interleaved capture frame is denoised by its own state.
+
+use nnnoiseless::DenoiseState;
+
+/// RNNoise expects samples in the 16-bit range.
+const SAMPLE_SCALE: f32 = 32767.0;
+
+/// Per-channel RNNoise state for interleaved capture frames.
+pub struct NoiseSuppressor {
+    channels: usize,
+    states: Vec<Box<DenoiseState<'static>>>,
+    input: [f32; DenoiseState::FRAME_SIZE],
+    output: [f32; DenoiseState::FRAME_SIZE],
+}
+
+impl NoiseSuppressor {
+    /// Create a suppressor for frames with `channels` interleaved channels.
+    pub fn new(channels: usize) -> Self {
+        Self {
+            channels,
+            states: (0..channels).map(|_| DenoiseState::new()).collect(),
+            input: [0.0; DenoiseState::FRAME_SIZE],
+            output: [0.0; DenoiseState::FRAME_SIZE],
+        }
+    }
+
+    /// Denoise an interleaved frame in place.
+    ///
+    /// Samples past the last whole 10 ms block are left unchanged; capture
+    /// frames are 20 ms, so there are none.
+    pub fn process(&mut self, frame: &mut [f32]) {
+        let block = DenoiseState::FRAME_SIZE * self.channels;
+
+        for chunk in frame.chunks_exact_mut(block) {
+            for (channel, state) in self.states.iter_mut().enumerate() {
+                for (i, sample) in self.input.iter_mut().enumerate() {
+                    *sample = chunk[i * self.channels + channel] * SAMPLE_SCALE;
+                }
+                state.process_frame(&mut self.output, &self.input);
+                for (i, sample) in self.output.iter().enumerate() {
+                    chunk[i * self.channels + channel] = sample / SAMPLE_SCALE;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audio::{CHANNELS, FRAME_SIZE};
+
+    #[test]
+    fn test_process_keeps_frame_size_and_silence() {
+        let mut suppressor = NoiseSuppressor::new(CHANNELS as usize);
+        let mut frame = vec![0.0; FRAME_SIZE * CHANNELS as usize];
+
+        suppressor.process(&mut frame);
+
+        assert_eq!(frame.len(), FRAME_SIZE * CHANNELS as usize);
+        assert!(frame.iter().all(|s| s.abs() < 1e-3));
+    }
+}
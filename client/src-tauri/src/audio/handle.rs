@@ -12,7 +12,9 @@ use opus::{Channels as OpusChannels, Decoder, Encoder};
 use tokio::sync::mpsc;
 use tracing::{debug, error, info, warn};
 
-use super::{AudioDevice, AudioDeviceList, AudioError, CHANNELS, FRAME_SIZE, SAMPLE_RATE};
+use super::{
+    AudioDevice, AudioDeviceList, AudioError, NoiseSuppressor, CHANNELS, FRAME_SIZE, SAMPLE_RATE,
+};
 
 /// Audio handle that can be safely shared across threads
 pub struct AudioHandle {
@@ -25,6 +27,9 @@ pub struct AudioHandle {
     /// Deafened state (atomic for thread-safe access)
     deafened: Arc<AtomicBool>,
 
+    /// Whether captured audio is denoised (read by the capture task per frame)
+    noise_suppression: Arc<AtomicBool>,
+
     /// Microphone test level (0-100)
     mic_test_level: Arc<AtomicU8>,
 
@@ -63,6 +68,7 @@ impl AudioHandle {
             host: Arc::new(host),
             muted: Arc::new(AtomicBool::new(false)),
             deafened: Arc::new(AtomicBool::new(false)),
+            noise_suppression: Arc::new(AtomicBool::new(true)),
             mic_test_level: Arc::new(AtomicU8::new(0)),
             capture_control: None,
             playback_control: None,
@@ -174,6 +180,7 @@ impl AudioHandle {
 
         let device = self.get_device(self.input_device_name.as_deref(), true)?;
         let muted = self.muted.clone();
+        let noise_suppression = self.noise_suppression.clone();
 
         // Create control channel
         let (control_tx, mut control_rx) = mpsc::channel::<CaptureControl>(1);
@@ -181,7 +188,7 @@ impl AudioHandle {
 
         // Spawn capture task that owns the Stream
         tokio::task::spawn_blocking(move || {
-            run_capture_task(device, muted, output_tx, &mut control_rx);
+            run_capture_task(device, muted, noise_suppression, output_tx, &mut control_rx);
         });
 
         info!("Audio capture started");
@@ -253,6 +260,17 @@ impl AudioHandle {
         self.deafened.load(Ordering::Relaxed)
     }
 
+    /// Enable or disable noise suppression; applies from the next captured frame
+    pub fn set_noise_suppression(&self, enabled: bool) {
+        self.noise_suppression.store(enabled, Ordering::Relaxed);
+        debug!("Noise suppression: {}", enabled);
+    }
+
+    /// Get noise suppression state
+    pub fn is_noise_suppression_enabled(&self) -> bool {
+        self.noise_suppression.load(Ordering::Relaxed)
+    }
+
     /// Start microphone test
     pub async fn start_mic_test(&mut self, device_id: Option<String>) -> Result<(), AudioError> {
         // Stop existing test if running
@@ -306,6 +324,7 @@ impl AudioHandle {
 fn run_capture_task(
     device: Device,
     muted: Arc<AtomicBool>,
+    noise_suppression: Arc<AtomicBool>,
     output_tx: mpsc::Sender<Vec<u8>>,
     control_rx: &mut mpsc::Receiver<CaptureControl>,
 ) {
@@ -330,6 +349,7 @@ fn run_capture_task(
         FRAME_SIZE * CHANNELS as usize * 2,
     )));
     let frame_samples = FRAME_SIZE * CHANNELS as usize;
+    let mut suppressor = NoiseSuppressor::new(CHANNELS as usize);
 
     let encoder_clone = encoder;
     let sample_buffer_clone = sample_buffer;
@@ -347,7 +367,10 @@ fn run_capture_task(
             buffer.extend_from_slice(data);
 
             while buffer.len() >= frame_samples {
-                let frame: Vec<f32> = buffer.drain(..frame_samples).collect();
+                let mut frame: Vec<f32> = buffer.drain(..frame_samples).collect();
+                if noise_suppression.load(Ordering::Relaxed) {
+                    suppressor.process(&mut frame);
+                }
 
                 let samples_i16: Vec<i16> = frame
                     .iter()
@@ -1,14 +1,17 @@
 //! Audio Input/Output
 //!
-//! Handles audio capture, playback, encoding/decoding with cpal and opus.
+//! Handles audio capture, playback, encoding/decoding with cpal and opus,
+//! with optional RNNoise noise suppression on captured audio.
 //!
 //! This module provides a Send + Sync audio handle that moves non-thread-safe
 //! `cpal::Stream` objects into background tasks.
 
 use thiserror::Error;
 
+mod denoise;
 mod handle;
 
+pub use denoise::NoiseSuppressor;
 pub use handle::AudioHandle;
 
 /// Audio configuration constants
@@ -99,4 +102,13 @@ mod tests {
         handle.set_deafened(false);
         assert!(!handle.is_deafened());
     }
+
+    #[test]
+    fn test_noise_suppression_state() {
+        let handle = AudioHandle::new().unwrap();
+        assert!(handle.is_noise_suppression_enabled());
+
+        handle.set_noise_suppression(false);
+        assert!(!handle.is_noise_suppression_enabled());
+    }
 }
@@ -8,6 +8,7 @@ use tauri::{command, State};
 use tracing::{debug, error};
 use url::form_urlencoded;
 
+use crate::network::http;
 use crate::AppState;
 
 // ============================================================================
@@ -56,13 +57,14 @@ pub struct GuildSummary {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AuditLogEntry {
     pub id: String,
-    pub actor_id: String,
+    pub actor_id: Option<String>,
     pub actor_username: Option<String>,
     pub action: String,
     pub target_type: Option<String>,
     pub target_id: Option<String>,
     pub details: Option<serde_json::Value>,
     pub ip_address: Option<String>,
+    pub user_agent: Option<String>,
     pub created_at: String,
 }
 
@@ -138,22 +140,19 @@ pub async fn check_admin_status(state: State<'_, AppState>) -> Result<AdminStatu
 
     debug!("Checking admin status");
 
-    let response = state
-        .http
-        .get(format!("{server_url}/api/admin/status"))
-        .header("Authorization", format!("Bearer {token}"))
-        .send()
-        .await
-        .map_err(|e| {
-            error!("Failed to check admin status: {}", e);
-            format!("Connection failed: {e}")
-        })?;
+    let response = http::send_idempotent(
+        state
+            .http
+            .get(format!("{server_url}/api/admin/status"))
+            .header("Authorization", format!("Bearer {token}")),
+    )
+    .await?;
 
     if !response.status().is_success() {
         let status = response.status();
         let body = response.text().await.unwrap_or_default();
         error!("Failed to check admin status: {} - {}", status, body);
-        return Err(format!("Failed to check admin status: {status}"));
+        return Err(http::status_error("Failed to check admin status", status));
     }
 
     let json: AdminStatusJson = response
@@ -188,22 +187,19 @@ pub async fn get_admin_stats(state: State<'_, AppState>) -> Result<AdminStats, S
     debug!("Fetching admin stats");
 
     // Get user count from users endpoint
-    let users_response = state
-        .http
-        .get(format!("{server_url}/api/admin/users?limit=1"))
-        .header("Authorization", format!("Bearer {token}"))
-        .send()
-        .await
-        .map_err(|e| {
-            error!("Failed to fetch user stats: {}", e);
-            format!("Connection failed: {e}")
-        })?;
+    let users_response = http::send_idempotent(
+        state
+            .http
+            .get(format!("{server_url}/api/admin/users?limit=1"))
+            .header("Authorization", format!("Bearer {token}")),
+    )
+    .await?;
 
     if !users_response.status().is_success() {
         let status = users_response.status();
         let body = users_response.text().await.unwrap_or_default();
         error!("Failed to fetch user stats: {} - {}", status, body);
-        return Err(format!("Failed to fetch admin stats: {status}"));
+        return Err(http::status_error("Failed to fetch admin stats", status));
     }
 
     let users_data: PaginatedResponse<UserSummary> = users_response
@@ -212,22 +208,19 @@ pub async fn get_admin_stats(state: State<'_, AppState>) -> Result<AdminStats, S
         .map_err(|e| format!("Invalid response: {e}"))?;
 
     // Get guild count from guilds endpoint
-    let guilds_response = state
-        .http
-        .get(format!("{server_url}/api/admin/guilds?limit=1"))
-        .header("Authorization", format!("Bearer {token}"))
-        .send()
-        .await
-        .map_err(|e| {
-            error!("Failed to fetch guild stats: {}", e);
-            format!("Connection failed: {e}")
-        })?;
+    let guilds_response = http::send_idempotent(
+        state
+            .http
+            .get(format!("{server_url}/api/admin/guilds?limit=1"))
+            .header("Authorization", format!("Bearer {token}")),
+    )
+    .await?;
 
     if !guilds_response.status().is_success() {
         let status = guilds_response.status();
         let body = guilds_response.text().await.unwrap_or_default();
         error!("Failed to fetch guild stats: {} - {}", status, body);
-        return Err(format!("Failed to fetch admin stats: {status}"));
+        return Err(http::status_error("Failed to fetch admin stats", status));
     }
 
     let guilds_data: PaginatedResponse<GuildSummary> = guilds_response
@@ -268,24 +261,21 @@ pub async fn admin_list_users(
 
     debug!("Fetching users (limit={}, offset={})", limit, offset);
 
-    let response = state
-        .http
-        .get(format!(
-            "{server_url}/api/admin/users?limit={limit}&offset={offset}"
-        ))
-        .header("Authorization", format!("Bearer {token}"))
-        .send()
-        .await
-        .map_err(|e| {
-            error!("Failed to fetch users: {}", e);
-            format!("Connection failed: {e}")
-        })?;
+    let response = http::send_idempotent(
+        state
+            .http
+            .get(format!(
+                "{server_url}/api/admin/users?limit={limit}&offset={offset}"
+            ))
+            .header("Authorization", format!("Bearer {token}")),
+    )
+    .await?;
 
     if !response.status().is_success() {
         let status = response.status();
         let body = response.text().await.unwrap_or_default();
         error!("Failed to fetch users: {} - {}", status, body);
-        return Err(format!("Failed to fetch users: {status}"));
+        return Err(http::status_error("Failed to fetch users", status));
     }
 
     let users: PaginatedResponse<UserSummary> = response
@@ -324,23 +314,20 @@ pub async fn admin_ban_user(
         "expires_at": expires_at,
     });
 
-    let response = state
-        .http
-        .post(format!("{server_url}/api/admin/users/{user_id}/ban"))
-        .header("Authorization", format!("Bearer {token}"))
-        .json(&body)
-        .send()
-        .await
-        .map_err(|e| {
-            error!("Failed to ban user: {}", e);
-            format!("Connection failed: {e}")
-        })?;
+    let response = http::send(
+        state
+            .http
+            .post(format!("{server_url}/api/admin/users/{user_id}/ban"))
+            .header("Authorization", format!("Bearer {token}"))
+            .json(&body),
+    )
+    .await?;
 
     if !response.status().is_success() {
         let status = response.status();
         let body = response.text().await.unwrap_or_default();
         error!("Failed to ban user: {} - {}", status, body);
-        return Err(format!("Failed to ban user: {status}"));
+        return Err(http::status_error("Failed to ban user", status));
     }
 
     let result: BanResponse = response
@@ -368,22 +355,19 @@ pub async fn admin_unban_user(
 
     debug!("Unbanning user {}", user_id);
 
-    let response = state
-        .http
-        .delete(format!("{server_url}/api/admin/users/{user_id}/ban"))
-        .header("Authorization", format!("Bearer {token}"))
-        .send()
-        .await
-        .map_err(|e| {
-            error!("Failed to unban user: {}", e);
-            format!("Connection failed: {e}")
-        })?;
+    let response = http::send(
+        state
+            .http
+            .delete(format!("{server_url}/api/admin/users/{user_id}/ban"))
+            .header("Authorization", format!("Bearer {token}")),
+    )
+    .await?;
 
     if !response.status().is_success() {
         let status = response.status();
         let body = response.text().await.unwrap_or_default();
         error!("Failed to unban user: {} - {}", status, body);
-        return Err(format!("Failed to unban user: {status}"));
+        return Err(http::status_error("Failed to unban user", status));
     }
 
     let result: BanResponse = response
@@ -419,24 +403,21 @@ pub async fn admin_list_guilds(
 
     debug!("Fetching guilds (limit={}, offset={})", limit, offset);
 
-    let response = state
-        .http
-        .get(format!(
-            "{server_url}/api/admin/guilds?limit={limit}&offset={offset}"
-        ))
-        .header("Authorization", format!("Bearer {token}"))
-        .send()
-        .await
-        .map_err(|e| {
-            error!("Failed to fetch guilds: {}", e);
-            format!("Connection failed: {e}")
-        })?;
+    let response = http::send_idempotent(
+        state
+            .http
+            .get(format!(
+                "{server_url}/api/admin/guilds?limit={limit}&offset={offset}"
+            ))
+            .header("Authorization", format!("Bearer {token}")),
+    )
+    .await?;
 
     if !response.status().is_success() {
         let status = response.status();
         let body = response.text().await.unwrap_or_default();
         error!("Failed to fetch guilds: {} - {}", status, body);
-        return Err(format!("Failed to fetch guilds: {status}"));
+        return Err(http::status_error("Failed to fetch guilds", status));
     }
 
     let guilds: PaginatedResponse<GuildSummary> = response
@@ -473,23 +454,20 @@ pub async fn admin_suspend_guild(
         "reason": reason,
     });
 
-    let response = state
-        .http
-        .post(format!("{server_url}/api/admin/guilds/{guild_id}/suspend"))
-        .header("Authorization", format!("Bearer {token}"))
-        .json(&body)
-        .send()
-        .await
-        .map_err(|e| {
-            error!("Failed to suspend guild: {}", e);
-            format!("Connection failed: {e}")
-        })?;
+    let response = http::send(
+        state
+            .http
+            .post(format!("{server_url}/api/admin/guilds/{guild_id}/suspend"))
+            .header("Authorization", format!("Bearer {token}"))
+            .json(&body),
+    )
+    .await?;
 
     if !response.status().is_success() {
         let status = response.status();
         let body = response.text().await.unwrap_or_default();
         error!("Failed to suspend guild: {} - {}", status, body);
-        return Err(format!("Failed to suspend guild: {status}"));
+        return Err(http::status_error("Failed to suspend guild", status));
     }
 
     let result: SuspendResponse = response
@@ -517,22 +495,19 @@ pub async fn admin_unsuspend_guild(
 
     debug!("Unsuspending guild {}", guild_id);
 
-    let response = state
-        .http
-        .delete(format!("{server_url}/api/admin/guilds/{guild_id}/suspend"))
-        .header("Authorization", format!("Bearer {token}"))
-        .send()
-        .await
-        .map_err(|e| {
-            error!("Failed to unsuspend guild: {}", e);
-            format!("Connection failed: {e}")
-        })?;
+    let response = http::send(
+        state
+            .http
+            .delete(format!("{server_url}/api/admin/guilds/{guild_id}/suspend"))
+            .header("Authorization", format!("Bearer {token}")),
+    )
+    .await?;
 
     if !response.status().is_success() {
         let status = response.status();
         let body = response.text().await.unwrap_or_default();
         error!("Failed to unsuspend guild: {} - {}", status, body);
-        return Err(format!("Failed to unsuspend guild: {status}"));
+        return Err(http::status_error("Failed to unsuspend guild", status));
     }
 
     let result: SuspendResponse = response
@@ -564,22 +539,19 @@ pub async fn admin_delete_user(
 
     debug!("Deleting user {}", user_id);
 
-    let response = state
-        .http
-        .delete(format!("{server_url}/api/admin/users/{user_id}"))
-        .header("Authorization", format!("Bearer {token}"))
-        .send()
-        .await
-        .map_err(|e| {
-            error!("Failed to delete user: {}", e);
-            format!("Connection failed: {e}")
-        })?;
+    let response = http::send(
+        state
+            .http
+            .delete(format!("{server_url}/api/admin/users/{user_id}"))
+            .header("Authorization", format!("Bearer {token}")),
+    )
+    .await?;
 
     if !response.status().is_success() {
         let status = response.status();
         let body = response.text().await.unwrap_or_default();
         error!("Failed to delete user: {} - {}", status, body);
-        return Err(format!("Failed to delete user: {status}"));
+        return Err(http::status_error("Failed to delete user", status));
     }
 
     let result: DeleteResponse = response
@@ -607,22 +579,19 @@ pub async fn admin_delete_guild(
 
     debug!("Deleting guild {}", guild_id);
 
-    let response = state
-        .http
-        .delete(format!("{server_url}/api/admin/guilds/{guild_id}"))
-        .header("Authorization", format!("Bearer {token}"))
-        .send()
-        .await
-        .map_err(|e| {
-            error!("Failed to delete guild: {}", e);
-            format!("Connection failed: {e}")
-        })?;
+    let response = http::send(
+        state
+            .http
+            .delete(format!("{server_url}/api/admin/guilds/{guild_id}"))
+            .header("Authorization", format!("Bearer {token}")),
+    )
+    .await?;
 
     if !response.status().is_success() {
         let status = response.status();
         let body = response.text().await.unwrap_or_default();
         error!("Failed to delete guild: {} - {}", status, body);
-        return Err(format!("Failed to delete guild: {status}"));
+        return Err(http::status_error("Failed to delete guild", status));
     }
 
     let result: DeleteResponse = response
@@ -669,22 +638,19 @@ pub async fn admin_get_audit_log(
         url.push_str(&encoded);
     }
 
-    let response = state
-        .http
-        .get(url)
-        .header("Authorization", format!("Bearer {token}"))
-        .send()
-        .await
-        .map_err(|e| {
-            error!("Failed to fetch audit log: {}", e);
-            format!("Connection failed: {e}")
-        })?;
+    let response = http::send_idempotent(
+        state
+            .http
+            .get(url)
+            .header("Authorization", format!("Bearer {token}")),
+    )
+    .await?;
 
     if !response.status().is_success() {
         let status = response.status();
         let body = response.text().await.unwrap_or_default();
         error!("Failed to fetch audit log: {} - {}", status, body);
-        return Err(format!("Failed to fetch audit log: {status}"));
+        return Err(http::status_error("Failed to fetch audit log", status));
     }
 
     let entries: PaginatedResponse<AuditLogEntry> = response
@@ -704,10 +670,12 @@ pub async fn admin_get_audit_log(
 // Session Elevation Commands
 // ============================================================================
 
-/// Elevate admin session.
+/// Elevate admin session by re-confirming the password or a TOTP code.
 #[command]
 pub async fn admin_elevate(
     state: State<'_, AppState>,
+    password: Option<String>,
+    mfa_code: Option<String>,
     reason: Option<String>,
 ) -> Result<ElevateResponse, String> {
     let (server_url, token) = {
@@ -721,20 +689,19 @@ pub async fn admin_elevate(
     debug!("Elevating admin session");
 
     let body = serde_json::json!({
+        "password": password,
+        "mfa_code": mfa_code,
         "reason": reason,
     });
 
-    let response = state
-        .http
-        .post(format!("{server_url}/api/admin/elevate"))
-        .header("Authorization", format!("Bearer {token}"))
-        .json(&body)
-        .send()
-        .await
-        .map_err(|e| {
-            error!("Failed to elevate session: {}", e);
-            format!("Connection failed: {e}")
-        })?;
+    let response = http::send(
+        state
+            .http
+            .post(format!("{server_url}/api/admin/elevate"))
+            .header("Authorization", format!("Bearer {token}"))
+            .json(&body),
+    )
+    .await?;
 
     if !response.status().is_success() {
         let status = response.status();
@@ -748,7 +715,7 @@ pub async fn admin_elevate(
             }
         }
 
-        return Err(format!("Failed to elevate session: {status}"));
+        return Err(http::status_error("Failed to elevate session", status));
     }
 
     let result: ElevateResponse = response
@@ -777,6 +744,8 @@ async fn read_auth(state: &AppState) -> Result<(String, String), String> {
 
 const VALID_RANGES: &[&str] = &["1h", "6h", "24h", "7d", "30d"];
 const VALID_SORTS: &[&str] = &["latency", "errors"];
+const VALID_CONSUMER_TYPES: &[&str] = &["user", "guild"];
+const VALID_CONSUMER_SORTS: &[&str] = &["requests", "ws_events", "voice_minutes"];
 const VALID_LEVELS: &[&str] = &["ERROR", "WARN", "INFO", "DEBUG", "TRACE"];
 const VALID_TRACE_STATUSES: &[&str] = &["error", "slow"];
 
@@ -813,19 +782,19 @@ pub async fn admin_obs_summary(state: State<'_, AppState>) -> Result<serde_json:
     let (server_url, token) = read_auth(&state).await?;
     debug!("Fetching observability summary");
 
-    let response = state
-        .http
-        .get(format!("{server_url}/api/admin/observability/summary"))
-        .header("Authorization", format!("Bearer {token}"))
-        .send()
-        .await
-        .map_err(|e| format!("Connection failed: {e}"))?;
+    let response = http::send_idempotent(
+        state
+            .http
+            .get(format!("{server_url}/api/admin/observability/summary"))
+            .header("Authorization", format!("Bearer {token}")),
+    )
+    .await?;
 
     if !response.status().is_success() {
         let status = response.status();
         let body = response.text().await.unwrap_or_default();
         error!("Failed to fetch obs summary: {} - {}", status, body);
-        return Err(format!("Failed to fetch obs summary: {status}"));
+        return Err(http::status_error("Failed to fetch obs summary", status));
     }
 
     response
@@ -864,21 +833,21 @@ pub async fn admin_obs_trends(
         p
     };
 
-    let response = state
-        .http
-        .get(format!(
-            "{server_url}/api/admin/observability/trends?{params}"
-        ))
-        .header("Authorization", format!("Bearer {token}"))
-        .send()
-        .await
-        .map_err(|e| format!("Connection failed: {e}"))?;
+    let response = http::send_idempotent(
+        state
+            .http
+            .get(format!(
+                "{server_url}/api/admin/observability/trends?{params}"
+            ))
+            .header("Authorization", format!("Bearer {token}")),
+    )
+    .await?;
 
     if !response.status().is_success() {
         let status = response.status();
         let body = response.text().await.unwrap_or_default();
         error!("Failed to fetch obs trends: {} - {}", status, body);
-        return Err(format!("Failed to fetch obs trends: {status}"));
+        return Err(http::status_error("Failed to fetch obs trends", status));
     }
 
     response
@@ -906,21 +875,21 @@ pub async fn admin_obs_top_routes(
         ("limit", limit.map(|l| l.to_string())),
     ]);
 
-    let response = state
-        .http
-        .get(format!(
-            "{server_url}/api/admin/observability/top-routes?{params}"
-        ))
-        .header("Authorization", format!("Bearer {token}"))
-        .send()
-        .await
-        .map_err(|e| format!("Connection failed: {e}"))?;
+    let response = http::send_idempotent(
+        state
+            .http
+            .get(format!(
+                "{server_url}/api/admin/observability/top-routes?{params}"
+            ))
+            .header("Authorization", format!("Bearer {token}")),
+    )
+    .await?;
 
     if !response.status().is_success() {
         let status = response.status();
         let body = response.text().await.unwrap_or_default();
         error!("Failed to fetch top routes: {} - {}", status, body);
-        return Err(format!("Failed to fetch top routes: {status}"));
+        return Err(http::status_error("Failed to fetch top routes", status));
     }
 
     response
@@ -945,21 +914,136 @@ pub async fn admin_obs_top_errors(
         ("limit", limit.map(|l| l.to_string())),
     ]);
 
-    let response = state
-        .http
-        .get(format!(
-            "{server_url}/api/admin/observability/top-errors?{params}"
-        ))
-        .header("Authorization", format!("Bearer {token}"))
-        .send()
-        .await
-        .map_err(|e| format!("Connection failed: {e}"))?;
+    let response = http::send_idempotent(
+        state
+            .http
+            .get(format!(
+                "{server_url}/api/admin/observability/top-errors?{params}"
+            ))
+            .header("Authorization", format!("Bearer {token}")),
+    )
+    .await?;
 
     if !response.status().is_success() {
         let status = response.status();
         let body = response.text().await.unwrap_or_default();
         error!("Failed to fetch top errors: {} - {}", status, body);
-        return Err(format!("Failed to fetch top errors: {status}"));
+        return Err(http::status_error("Failed to fetch top errors", status));
+    }
+
+    response
+        .json::<serde_json::Value>()
+        .await
+        .map_err(|e| format!("Invalid response: {e}"))
+}
+
+/// Fetch the users or guilds generating the most load.
+#[command]
+pub async fn admin_obs_top_consumers(
+    state: State<'_, AppState>,
+    range: String,
+    consumer_type: Option<String>,
+    sort: Option<String>,
+    limit: Option<i64>,
+) -> Result<serde_json::Value, String> {
+    validate_optional(Some(range.as_str()), VALID_RANGES, "range")?;
+    validate_optional(consumer_type.as_deref(), VALID_CONSUMER_TYPES, "type")?;
+    validate_optional(sort.as_deref(), VALID_CONSUMER_SORTS, "sort")?;
+    let (server_url, token) = read_auth(&state).await?;
+    debug!("Fetching top consumers (range={})", range);
+
+    let params = build_query(&[
+        ("range", Some(range)),
+        ("type", consumer_type),
+        ("sort", sort),
+        ("limit", limit.map(|l| l.to_string())),
+    ]);
+
+    let response = http::send_idempotent(
+        state
+            .http
+            .get(format!(
+                "{server_url}/api/admin/observability/top-consumers?{params}"
+            ))
+            .header("Authorization", format!("Bearer {token}")),
+    )
+    .await?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+        error!("Failed to fetch top consumers: {} - {}", status, body);
+        return Err(http::status_error("Failed to fetch top consumers", status));
+    }
+
+    response
+        .json::<serde_json::Value>()
+        .await
+        .map_err(|e| format!("Invalid response: {e}"))
+}
+
+/// Fetch the WebSocket disconnect breakdown.
+#[command]
+pub async fn admin_obs_ws_disconnects(
+    state: State<'_, AppState>,
+    range: String,
+) -> Result<serde_json::Value, String> {
+    validate_optional(Some(range.as_str()), VALID_RANGES, "range")?;
+    let (server_url, token) = read_auth(&state).await?;
+    debug!("Fetching WS disconnects (range={})", range);
+
+    let params = build_query(&[("range", Some(range))]);
+
+    let response = http::send_idempotent(
+        state
+            .http
+            .get(format!(
+                "{server_url}/api/admin/observability/ws-disconnects?{params}"
+            ))
+            .header("Authorization", format!("Bearer {token}")),
+    )
+    .await?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+        error!("Failed to fetch WS disconnects: {} - {}", status, body);
+        return Err(http::status_error("Failed to fetch WS disconnects", status));
+    }
+
+    response
+        .json::<serde_json::Value>()
+        .await
+        .map_err(|e| format!("Invalid response: {e}"))
+}
+
+/// Fetch the report queue SLA metrics.
+#[command]
+pub async fn admin_obs_report_sla(
+    state: State<'_, AppState>,
+    range: String,
+) -> Result<serde_json::Value, String> {
+    validate_optional(Some(range.as_str()), VALID_RANGES, "range")?;
+    let (server_url, token) = read_auth(&state).await?;
+    debug!("Fetching report SLA (range={})", range);
+
+    let params = build_query(&[("range", Some(range))]);
+
+    let response = http::send_idempotent(
+        state
+            .http
+            .get(format!(
+                "{server_url}/api/admin/observability/report-sla?{params}"
+            ))
+            .header("Authorization", format!("Bearer {token}")),
+    )
+    .await?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+        error!("Failed to fetch report SLA: {} - {}", status, body);
+        return Err(http::status_error("Failed to fetch report SLA", status));
     }
 
     response
@@ -996,19 +1080,19 @@ pub async fn admin_obs_logs(
         format!("{server_url}/api/admin/observability/logs?{params}")
     };
 
-    let response = state
-        .http
-        .get(url)
-        .header("Authorization", format!("Bearer {token}"))
-        .send()
-        .await
-        .map_err(|e| format!("Connection failed: {e}"))?;
+    let response = http::send_idempotent(
+        state
+            .http
+            .get(url)
+            .header("Authorization", format!("Bearer {token}")),
+    )
+    .await?;
 
     if !response.status().is_success() {
         let status = response.status();
         let body = response.text().await.unwrap_or_default();
         error!("Failed to fetch obs logs: {} - {}", status, body);
-        return Err(format!("Failed to fetch obs logs: {status}"));
+        return Err(http::status_error("Failed to fetch obs logs", status));
     }
 
     response
@@ -1046,13 +1130,13 @@ pub async fn admin_obs_traces(
         format!("{server_url}/api/admin/observability/traces?{params}")
     };
 
-    let response = state
-        .http
-        .get(url)
-        .header("Authorization", format!("Bearer {token}"))
-        .send()
-        .await
-        .map_err(|e| format!("Connection failed: {e}"))?;
+    let response = http::send_idempotent(
+        state
+            .http
+            .get(url)
+            .header("Authorization", format!("Bearer {token}")),
+    )
+    .await?;
 
     if !response.status().is_success() {
         let status_code = response.status();
@@ -1073,19 +1157,19 @@ pub async fn admin_obs_links(state: State<'_, AppState>) -> Result<serde_json::V
     let (server_url, token) = read_auth(&state).await?;
     debug!("Fetching observability links");
 
-    let response = state
-        .http
-        .get(format!("{server_url}/api/admin/observability/links"))
-        .header("Authorization", format!("Bearer {token}"))
-        .send()
-        .await
-        .map_err(|e| format!("Connection failed: {e}"))?;
+    let response = http::send_idempotent(
+        state
+            .http
+            .get(format!("{server_url}/api/admin/observability/links"))
+            .header("Authorization", format!("Bearer {token}")),
+    )
+    .await?;
 
     if !response.status().is_success() {
         let status = response.status();
         let body = response.text().await.unwrap_or_default();
         error!("Failed to fetch obs links: {} - {}", status, body);
-        return Err(format!("Failed to fetch obs links: {status}"));
+        return Err(http::status_error("Failed to fetch obs links", status));
     }
 
     response
@@ -1107,22 +1191,19 @@ pub async fn admin_de_elevate(state: State<'_, AppState>) -> Result<DeElevateRes
 
     debug!("De-elevating admin session");
 
-    let response = state
-        .http
-        .delete(format!("{server_url}/api/admin/elevate"))
-        .header("Authorization", format!("Bearer {token}"))
-        .send()
-        .await
-        .map_err(|e| {
-            error!("Failed to de-elevate session: {}", e);
-            format!("Connection failed: {e}")
-        })?;
+    let response = http::send(
+        state
+            .http
+            .delete(format!("{server_url}/api/admin/elevate"))
+            .header("Authorization", format!("Bearer {token}")),
+    )
+    .await?;
 
     if !response.status().is_success() {
         let status = response.status();
         let body = response.text().await.unwrap_or_default();
         error!("Failed to de-elevate session: {} - {}", status, body);
-        return Err(format!("Failed to de-elevate session: {status}"));
+        return Err(http::status_error("Failed to de-elevate session", status));
     }
 
     let result: DeElevateResponse = response
@@ -1,13 +1,32 @@
 //! Authentication Commands
 
 use std::collections::HashMap;
+use std::time::Duration;
 
+use reqwest::{Client as HttpClient, StatusCode};
 use serde::{Deserialize, Serialize};
-use tauri::{command, State};
-use tracing::{debug, error, info};
+use tauri::{command, AppHandle, Emitter, Manager, State};
+use tokio::sync::Mutex;
+use tracing::{debug, error, info, warn};
 
+use crate::credentials::{self, StoredSession};
+use crate::network::http;
+use crate::passkey;
 use crate::{AppState, User, UserStatus};
 
+/// Refresh the access token this long before it expires.
+const REFRESH_MARGIN: Duration = Duration::from_secs(60);
+
+/// Shortest wait before a scheduled refresh.
+const MIN_REFRESH_DELAY: Duration = Duration::from_secs(10);
+
+/// Wait before retrying a refresh that could not reach the server.
+const REFRESH_RETRY_DELAY: Duration = Duration::from_secs(30);
+
+/// Serializes refreshes: the server rotates refresh tokens, so two concurrent
+/// refreshes with the same token would invalidate each other.
+static REFRESH_LOCK: Mutex<()> = Mutex::const_new(());
+
 /// Login request from frontend.
 #[derive(Deserialize)]
 pub struct LoginRequest {
@@ -15,6 +34,8 @@ pub struct LoginRequest {
     pub username: String,
     pub password: String,
     pub mfa_code: Option<String>,
+    /// Passkey assertion answering `MFA_REQUIRED` instead of `mfa_code`.
+    pub passkey: Option<serde_json::Value>,
 }
 
 impl std::fmt::Debug for LoginRequest {
@@ -24,6 +45,7 @@ impl std::fmt::Debug for LoginRequest {
             .field("username", &self.username)
             .field("password", &"[REDACTED]")
             .field("mfa_code", &self.mfa_code.as_ref().map(|_| "[REDACTED]"))
+            .field("passkey", &self.passkey.as_ref().map(|_| "[REDACTED]"))
             .finish()
     }
 }
@@ -36,6 +58,7 @@ pub struct RegisterRequest {
     pub email: Option<String>,
     pub password: String,
     pub display_name: Option<String>,
+    pub registration_code: Option<String>,
 }
 
 /// Token response from server.
@@ -55,6 +78,8 @@ struct UserResponse {
     username: String,
     display_name: String,
     email: Option<String>,
+    #[serde(default)]
+    email_verified: bool,
     avatar_url: Option<String>,
     status: String,
     mfa_enabled: bool,
@@ -68,6 +93,7 @@ impl From<UserResponse> for User {
             display_name: r.display_name,
             avatar_url: r.avatar_url,
             email: r.email,
+            email_verified: r.email_verified,
             mfa_enabled: r.mfa_enabled,
             status: match r.status.as_str() {
                 "online" => UserStatus::Online,
@@ -81,7 +107,11 @@ impl From<UserResponse> for User {
 
 /// Login with username and password.
 #[command]
-pub async fn login(state: State<'_, AppState>, request: LoginRequest) -> Result<User, String> {
+pub async fn login(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    request: LoginRequest,
+) -> Result<User, String> {
     info!("Attempting login for user: {}", request.username);
 
     let server_url = request.server_url.trim_end_matches('/');
@@ -94,17 +124,17 @@ pub async fn login(state: State<'_, AppState>, request: LoginRequest) -> Result<
     if let Some(ref code) = request.mfa_code {
         login_body["mfa_code"] = serde_json::json!(code);
     }
+    if let Some(ref passkey) = request.passkey {
+        login_body["passkey"] = passkey.clone();
+    }
 
-    let response = state
-        .http
-        .post(format!("{server_url}/auth/login"))
-        .json(&login_body)
-        .send()
-        .await
-        .map_err(|e| {
-            error!("Login request failed: {}", e);
-            format!("Connection failed: {e}")
-        })?;
+    let response = http::send(
+        state
+            .http
+            .post(format!("{server_url}/auth/login"))
+            .json(&login_body),
+    )
+    .await?;
 
     if !response.status().is_success() {
         let status = response.status();
@@ -134,40 +164,8 @@ pub async fn login(state: State<'_, AppState>, request: LoginRequest) -> Result<
 
     debug!("Login successful, fetching user info");
 
-    // Fetch user info with the new token
-    let user_response = state
-        .http
-        .get(format!("{server_url}/auth/me"))
-        .header("Authorization", format!("Bearer {}", tokens.access_token))
-        .send()
-        .await
-        .map_err(|e| format!("Failed to fetch user info: {e}"))?;
-
-    if !user_response.status().is_success() {
-        return Err("Failed to fetch user info".to_string());
-    }
-
-    let user_data: UserResponse = user_response
-        .json()
-        .await
-        .map_err(|e| format!("Invalid user response: {e}"))?;
-
-    let user: User = user_data.into();
-
-    // Store auth state
-    {
-        let mut auth = state.auth.write().await;
-        auth.access_token = Some(tokens.access_token.clone());
-        auth.refresh_token = Some(tokens.refresh_token.clone());
-        auth.server_url = Some(server_url.to_string());
-        auth.user = Some(user.clone());
-    }
-
-    // Store refresh token securely in keyring
-    if let Err(e) = store_refresh_token(server_url, &tokens.refresh_token) {
-        error!("Failed to store refresh token: {}", e);
-        // Continue anyway - user is still logged in for this session
-    }
+    let user = fetch_user(&state.http, server_url, &tokens.access_token).await?;
+    start_session(&app, &state, server_url, &tokens, user.clone()).await;
 
     info!("User {} logged in successfully", user.username);
     Ok(user)
@@ -176,6 +174,7 @@ pub async fn login(state: State<'_, AppState>, request: LoginRequest) -> Result<
 /// Register a new user.
 #[command]
 pub async fn register(
+    app: AppHandle,
     state: State<'_, AppState>,
     request: RegisterRequest,
 ) -> Result<User, String> {
@@ -184,21 +183,22 @@ pub async fn register(
     let server_url = request.server_url.trim_end_matches('/');
 
     // Send register request to server
-    let response = state
-        .http
-        .post(format!("{server_url}/auth/register"))
-        .json(&serde_json::json!({
+    let response = http::send(state.http.post(format!("{server_url}/auth/register")).json(
+        &serde_json::json!({
             "username": request.username,
             "email": request.email,
             "password": request.password,
-            "display_name": request.display_name
-        }))
-        .send()
-        .await
-        .map_err(|e| {
-            error!("Registration request failed: {}", e);
-            format!("Connection failed: {e}")
-        })?;
+            "display_name": request.display_name,
+            "registration_code": request.registration_code
+        }),
+    ))
+    .await?;
+
+    // Under the "approval" policy the account waits for an admin, no session
+    if response.status() == StatusCode::ACCEPTED {
+        info!("Registration of {} is waiting for approval", request.username);
+        return Err("REGISTRATION_PENDING_APPROVAL".to_string());
+    }
 
     if !response.status().is_success() {
         let status = response.status();
@@ -206,6 +206,11 @@ pub async fn register(
         error!("Registration failed with status {}: {}", status, body);
         return Err(if status.as_u16() == 409 {
             "Username or email already exists".to_string()
+        } else if status.as_u16() == 403 {
+            serde_json::from_str::<serde_json::Value>(&body)
+                .ok()
+                .and_then(|error| error["message"].as_str().map(String::from))
+                .unwrap_or_else(|| "Registration is not allowed".to_string())
         } else if status.as_u16() == 400 {
             // Try to extract validation error
             if let Ok(error) = serde_json::from_str::<serde_json::Value>(&body) {
@@ -228,39 +233,8 @@ pub async fn register(
 
     debug!("Registration successful, fetching user info");
 
-    // Fetch user info with the new token
-    let user_response = state
-        .http
-        .get(format!("{server_url}/auth/me"))
-        .header("Authorization", format!("Bearer {}", tokens.access_token))
-        .send()
-        .await
-        .map_err(|e| format!("Failed to fetch user info: {e}"))?;
-
-    if !user_response.status().is_success() {
-        return Err("Failed to fetch user info".to_string());
-    }
-
-    let user_data: UserResponse = user_response
-        .json()
-        .await
-        .map_err(|e| format!("Invalid user response: {e}"))?;
-
-    let user: User = user_data.into();
-
-    // Store auth state
-    {
-        let mut auth = state.auth.write().await;
-        auth.access_token = Some(tokens.access_token.clone());
-        auth.refresh_token = Some(tokens.refresh_token.clone());
-        auth.server_url = Some(server_url.to_string());
-        auth.user = Some(user.clone());
-    }
-
-    // Store refresh token securely
-    if let Err(e) = store_refresh_token(server_url, &tokens.refresh_token) {
-        error!("Failed to store refresh token: {}", e);
-    }
+    let user = fetch_user(&state.http, server_url, &tokens.access_token).await?;
+    start_session(&app, &state, server_url, &tokens, user.clone()).await;
 
     info!("User {} registered successfully", user.username);
     Ok(user)
@@ -286,27 +260,31 @@ pub async fn logout(state: State<'_, AppState>) -> Result<(), String> {
             .await;
     }
 
-    // Clear auth state
-    {
-        let mut auth = state.auth.write().await;
-        auth.access_token = None;
-        auth.refresh_token = None;
-        auth.user = None;
-        // Keep server_url for potential re-login
-    }
+    end_session(&state).await;
+    state.notifications.clear_channel_cache().await;
+    state.guild_cache.clear();
 
-    // Clear stored credentials
     if let Some(url) = server_url {
-        let _ = clear_refresh_token(&url);
+        credentials::clear_legacy(&url);
     }
 
     info!("Logged out successfully");
     Ok(())
 }
 
-/// Get the current authenticated user.
+/// Get the current authenticated user, restoring the session stored in the
+/// keychain if there is none in memory.
+///
+/// `legacy_server_url` is the server an earlier build may have stored a
+/// refresh token for; it is only used when no current session is stored.
+/// Returns an error (keeping the stored session) when the server cannot be
+/// reached, and `None` when the stored session was rejected.
 #[command]
-pub async fn get_current_user(state: State<'_, AppState>) -> Result<Option<User>, String> {
+pub async fn get_current_user(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    legacy_server_url: Option<String>,
+) -> Result<Option<User>, String> {
     // Check if we have a user in memory
     {
         let auth = state.auth.read().await;
@@ -315,9 +293,50 @@ pub async fn get_current_user(state: State<'_, AppState>) -> Result<Option<User>
         }
     }
 
-    // Try to restore session from stored credentials
-    // For now, return None - session restoration will be implemented with keyring
-    Ok(None)
+    let Some(stored) = credentials::load().or_else(|| {
+        legacy_server_url
+            .as_deref()
+            .and_then(credentials::migrate_legacy)
+    }) else {
+        return Ok(None);
+    };
+
+    info!("Restoring session for {}", stored.server_url);
+    let tokens = {
+        let _guard = REFRESH_LOCK.lock().await;
+        match refresh_tokens(&state.http, &stored.server_url, &stored.refresh_token).await {
+            Ok(tokens) => tokens,
+            Err(RefreshError::Rejected) => {
+                info!("Stored session was rejected by the server");
+                credentials::clear();
+                return Ok(None);
+            }
+            Err(RefreshError::Unavailable(e)) => return Err(e),
+        }
+    };
+    // The old refresh token is spent; keep the new one even if the rest fails
+    remember(&stored.server_url, &tokens.refresh_token);
+
+    let user = fetch_user(&state.http, &stored.server_url, &tokens.access_token).await?;
+    start_session(&app, &state, &stored.server_url, &tokens, user.clone()).await;
+
+    info!("Session restored for user {}", user.username);
+    Ok(Some(user))
+}
+
+/// Refresh the access token now.
+///
+/// Returns `false` and signs out if the server rejected the session.
+#[command]
+pub async fn refresh_session(state: State<'_, AppState>) -> Result<bool, String> {
+    match refresh_current_session(&state).await {
+        Ok(_) => Ok(true),
+        Err(RefreshError::Rejected) => {
+            end_session(&state).await;
+            Ok(false)
+        }
+        Err(RefreshError::Unavailable(e)) => Err(e),
+    }
 }
 
 /// Get auth info for fetch-based operations (e.g., file uploads).
@@ -350,7 +369,7 @@ pub struct OidcLoginResult {
 /// 4. Waits for the OIDC callback on the localhost listener
 /// 5. Extracts tokens from the callback query params and returns them
 #[command]
-pub async fn oidc_authorize(
+pub async fn login_with_oidc(
     state: State<'_, AppState>,
     app_handle: tauri::AppHandle,
     server_url: String,
@@ -524,66 +543,298 @@ pub async fn oidc_authorize(
         .and_then(|s| s.parse().ok())
         .unwrap_or(false);
 
-    // Fetch user info with the new token
-    let user_response = state
-        .http
-        .get(format!("{server_url}/auth/me"))
-        .header("Authorization", format!("Bearer {access_token}"))
-        .send()
+    let user = fetch_user(&state.http, server_url, &access_token).await?;
+    let tokens = TokenResponse {
+        access_token: access_token.clone(),
+        refresh_token: refresh_token.clone(),
+        expires_in,
+        token_type: "Bearer".to_string(),
+    };
+    start_session(&app_handle, &state, server_url, &tokens, user.clone()).await;
+
+    info!("OIDC login successful for user: {}", user.username);
+    Ok(OidcLoginResult {
+        access_token,
+        refresh_token,
+        expires_in,
+        setup_required,
+    })
+}
+
+/// A device link request started by this client.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct DeviceLinkStarted {
+    pub code: String,
+    pub secret: String,
+    pub expires_in: u64,
+}
+
+/// Start signing in through a device that is already logged in. The code is
+/// shown as a QR code for that device to approve; the secret stays here.
+#[command]
+pub async fn start_device_link(
+    state: State<'_, AppState>,
+    server_url: String,
+) -> Result<DeviceLinkStarted, String> {
+    let server_url = server_url.trim_end_matches('/');
+
+    let response = http::send(
+        state
+            .http
+            .post(format!("{server_url}/auth/device-link"))
+            .json(&serde_json::json!({})),
+    )
+    .await?;
+
+    if !response.status().is_success() {
+        return Err(format!(
+            "Could not start device link: {}",
+            response.status()
+        ));
+    }
+
+    response
+        .json()
         .await
-        .map_err(|e| format!("Failed to fetch user info: {e}"))?;
+        .map_err(|e| format!("Invalid response from server: {e}"))
+}
 
-    if !user_response.status().is_success() {
-        return Err("Failed to fetch user info after OIDC login".to_string());
+/// Check whether a device link request was approved, and sign in if it was.
+///
+/// Returns `None` while the request is still waiting for approval.
+#[command]
+pub async fn claim_device_link(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    server_url: String,
+    code: String,
+    secret: String,
+) -> Result<Option<User>, String> {
+    let server_url = server_url.trim_end_matches('/');
+
+    let response = http::send(
+        state
+            .http
+            .post(format!("{server_url}/auth/device-link/{code}/claim"))
+            .json(&serde_json::json!({ "secret": secret })),
+    )
+    .await?;
+
+    match response.status() {
+        StatusCode::ACCEPTED => return Ok(None),
+        StatusCode::NOT_FOUND => {
+            return Err("The link request expired or was rejected".to_string());
+        }
+        status if !status.is_success() => {
+            return Err(format!("Device link failed: {status}"));
+        }
+        _ => {}
     }
 
-    let user_data: UserResponse = user_response
+    let tokens: TokenResponse = response.json().await.map_err(|e| {
+        error!("Failed to parse device link response: {}", e);
+        format!("Invalid response from server: {e}")
+    })?;
+
+    let user = fetch_user(&state.http, server_url, &tokens.access_token).await?;
+    start_session(&app, &state, server_url, &tokens, user.clone()).await;
+
+    info!("User {} signed in through a linked device", user.username);
+    Ok(Some(user))
+}
+
+// Session helpers
+
+/// Why a token refresh failed.
+enum RefreshError {
+    /// The server rejected the refresh token; the session is over.
+    Rejected,
+    /// The server could not be reached or failed; worth retrying.
+    Unavailable(String),
+}
+
+/// Fetch the signed-in user's profile.
+async fn fetch_user(
+    http_client: &HttpClient,
+    server_url: &str,
+    access_token: &str,
+) -> Result<User, String> {
+    let response = http::send_idempotent(
+        http_client
+            .get(format!("{server_url}/auth/me"))
+            .header("Authorization", format!("Bearer {access_token}")),
+    )
+    .await?;
+
+    if !response.status().is_success() {
+        return Err(http::status_error(
+            "Failed to fetch user info",
+            response.status(),
+        ));
+    }
+
+    let user_data: UserResponse = response
         .json()
         .await
         .map_err(|e| format!("Invalid user response: {e}"))?;
+    Ok(user_data.into())
+}
+
+/// Exchange a refresh token for new tokens. Callers hold `REFRESH_LOCK`.
+async fn refresh_tokens(
+    http_client: &HttpClient,
+    server_url: &str,
+    refresh_token: &str,
+) -> Result<TokenResponse, RefreshError> {
+    let response = http::send(
+        http_client
+            .post(format!("{server_url}/auth/refresh"))
+            .json(&serde_json::json!({ "refresh_token": refresh_token })),
+    )
+    .await
+    .map_err(RefreshError::Unavailable)?;
+
+    let status = response.status();
+    if matches!(status, StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN) {
+        return Err(RefreshError::Rejected);
+    }
+    if !status.is_success() {
+        return Err(RefreshError::Unavailable(http::status_error(
+            "Token refresh failed",
+            status,
+        )));
+    }
+
+    response
+        .json()
+        .await
+        .map_err(|e| RefreshError::Unavailable(format!("Invalid refresh response: {e}")))
+}
 
-    let user: User = user_data.into();
+/// Refresh the signed-in session's tokens. Returns the new access token's
+/// lifetime in seconds.
+async fn refresh_current_session(state: &AppState) -> Result<u64, RefreshError> {
+    let _guard = REFRESH_LOCK.lock().await;
 
-    // Store auth state
+    let (server_url, refresh_token) = {
+        let auth = state.auth.read().await;
+        match (&auth.server_url, &auth.refresh_token) {
+            (Some(url), Some(token)) => (url.clone(), token.clone()),
+            _ => return Err(RefreshError::Rejected),
+        }
+    };
+
+    let tokens = refresh_tokens(&state.http, &server_url, &refresh_token).await?;
     {
         let mut auth = state.auth.write().await;
-        auth.access_token = Some(access_token.clone());
-        auth.refresh_token = Some(refresh_token.clone());
-        auth.server_url = Some(server_url.to_string());
-        auth.user = Some(user.clone());
-    }
-
-    // Store refresh token securely
-    if let Err(e) = store_refresh_token(server_url, &refresh_token) {
-        error!("Failed to store OIDC refresh token: {}", e);
+        if auth.refresh_token.as_deref() != Some(refresh_token.as_str()) {
+            // Signed out or in again while refreshing; that session owns
+            // the state now
+            return Err(RefreshError::Unavailable(
+                "Session changed during refresh".to_string(),
+            ));
+        }
+        auth.access_token = Some(tokens.access_token);
+        auth.refresh_token = Some(tokens.refresh_token.clone());
     }
+    remember(&server_url, &tokens.refresh_token);
 
-    info!("OIDC login successful for user: {}", user.username);
-    Ok(OidcLoginResult {
-        access_token,
-        refresh_token,
-        expires_in,
-        setup_required,
-    })
+    debug!("Access token refreshed");
+    Ok(tokens.expires_in)
 }
 
-// Keyring helpers
+/// Persist the session to the keychain so it survives a restart.
+fn remember(server_url: &str, refresh_token: &str) {
+    let session = StoredSession {
+        server_url: server_url.to_string(),
+        refresh_token: refresh_token.to_string(),
+    };
+    if let Err(e) = credentials::save(&session) {
+        // The user stays signed in for this run
+        error!("Failed to store session in keychain: {}", e);
+    }
+}
 
-const KEYRING_SERVICE: &str = "voicechat";
+/// Sign a session in: keep the tokens in memory, persist the refresh token
+/// and schedule access token refreshes.
+async fn start_session(
+    app: &AppHandle,
+    state: &AppState,
+    server_url: &str,
+    tokens: &TokenResponse,
+    user: User,
+) {
+    let refresh_task = spawn_token_refresh(app.clone(), tokens.expires_in);
+    {
+        let mut auth = state.auth.write().await;
+        if let Some(previous) = auth.refresh_task.replace(refresh_task) {
+            previous.abort();
+        }
+        auth.access_token = Some(tokens.access_token.clone());
+        auth.refresh_token = Some(tokens.refresh_token.clone());
+        auth.server_url = Some(server_url.to_string());
+        auth.user = Some(user);
+    }
+    remember(server_url, &tokens.refresh_token);
+}
 
-fn keyring_user(server_url: &str) -> String {
-    // Use server URL as keyring username to support multiple servers
-    format!("refresh_token:{server_url}")
+/// Clear the session from memory and the keychain.
+async fn end_session(state: &AppState) {
+    {
+        let mut auth = state.auth.write().await;
+        if let Some(task) = auth.refresh_task.take() {
+            task.abort();
+        }
+        auth.access_token = None;
+        auth.refresh_token = None;
+        auth.user = None;
+        // Keep server_url for potential re-login
+    }
+    credentials::clear();
 }
 
-fn store_refresh_token(server_url: &str, token: &str) -> Result<(), keyring::Error> {
-    let entry = keyring::Entry::new(KEYRING_SERVICE, &keyring_user(server_url))?;
-    entry.set_password(token)
+/// How long to wait before refreshing an access token valid for `expires_in`
+/// seconds.
+fn refresh_delay(expires_in: u64) -> Duration {
+    Duration::from_secs(expires_in)
+        .saturating_sub(REFRESH_MARGIN)
+        .max(MIN_REFRESH_DELAY)
 }
 
-fn clear_refresh_token(server_url: &str) -> Result<(), keyring::Error> {
-    let entry = keyring::Entry::new(KEYRING_SERVICE, &keyring_user(server_url))?;
-    entry.delete_password()
+/// Keep the access token fresh until the session ends. Emits
+/// `session-expired` if the server rejects the refresh token.
+fn spawn_token_refresh(app: AppHandle, expires_in: u64) -> tokio::task::AbortHandle {
+    tokio::spawn(async move {
+        let mut delay = refresh_delay(expires_in);
+        loop {
+            tokio::time::sleep(delay).await;
+            let state = app.state::<AppState>();
+            delay = match refresh_current_session(&state).await {
+                Ok(expires_in) => refresh_delay(expires_in),
+                Err(RefreshError::Unavailable(e)) => {
+                    warn!(error = %e, "Token refresh failed, retrying");
+                    REFRESH_RETRY_DELAY
+                }
+                Err(RefreshError::Rejected) => break,
+            };
+        }
+
+        warn!("Session expired");
+        let state = app.state::<AppState>();
+        {
+            let mut auth = state.auth.write().await;
+            // This task is ending; drop its handle without aborting it
+            auth.refresh_task = None;
+            auth.access_token = None;
+            auth.refresh_token = None;
+            auth.user = None;
+        }
+        credentials::clear();
+        if let Err(e) = app.emit("session-expired", ()) {
+            warn!("Failed to emit session-expired event: {}", e);
+        }
+    })
+    .abort_handle()
 }
 
 // ============================================================================
@@ -635,13 +886,13 @@ async fn get_auth_context(state: &AppState) -> Result<(String, String), String>
 pub async fn mfa_setup(state: State<'_, AppState>) -> Result<MfaSetupResponse, String> {
     let (server_url, token) = get_auth_context(&state).await?;
 
-    let response = state
-        .http
-        .post(format!("{server_url}/auth/mfa/setup"))
-        .header("Authorization", format!("Bearer {token}"))
-        .send()
-        .await
-        .map_err(|e| format!("MFA setup request failed: {e}"))?;
+    let response = http::send(
+        state
+            .http
+            .post(format!("{server_url}/auth/mfa/setup"))
+            .header("Authorization", format!("Bearer {token}")),
+    )
+    .await?;
 
     if !response.status().is_success() {
         let body = response.text().await.unwrap_or_default();
@@ -662,14 +913,14 @@ pub async fn mfa_verify(
 ) -> Result<MfaVerifyResponse, String> {
     let (server_url, token) = get_auth_context(&state).await?;
 
-    let response = state
-        .http
-        .post(format!("{server_url}/auth/mfa/verify"))
-        .header("Authorization", format!("Bearer {token}"))
-        .json(&serde_json::json!({ "code": code }))
-        .send()
-        .await
-        .map_err(|e| format!("MFA verify request failed: {e}"))?;
+    let response = http::send(
+        state
+            .http
+            .post(format!("{server_url}/auth/mfa/verify"))
+            .header("Authorization", format!("Bearer {token}"))
+            .json(&serde_json::json!({ "code": code })),
+    )
+    .await?;
 
     if !response.status().is_success() {
         let body = response.text().await.unwrap_or_default();
@@ -687,14 +938,14 @@ pub async fn mfa_verify(
 pub async fn mfa_disable(state: State<'_, AppState>, code: String) -> Result<(), String> {
     let (server_url, token) = get_auth_context(&state).await?;
 
-    let response = state
-        .http
-        .post(format!("{server_url}/auth/mfa/disable"))
-        .header("Authorization", format!("Bearer {token}"))
-        .json(&serde_json::json!({ "code": code }))
-        .send()
-        .await
-        .map_err(|e| format!("MFA disable request failed: {e}"))?;
+    let response = http::send(
+        state
+            .http
+            .post(format!("{server_url}/auth/mfa/disable"))
+            .header("Authorization", format!("Bearer {token}"))
+            .json(&serde_json::json!({ "code": code })),
+    )
+    .await?;
 
     if !response.status().is_success() {
         let body = response.text().await.unwrap_or_default();
@@ -719,13 +970,13 @@ pub async fn mfa_generate_backup_codes(
 ) -> Result<MfaBackupCodesResponse, String> {
     let (server_url, token) = get_auth_context(&state).await?;
 
-    let response = state
-        .http
-        .post(format!("{server_url}/auth/mfa/backup-codes"))
-        .header("Authorization", format!("Bearer {token}"))
-        .send()
-        .await
-        .map_err(|e| format!("Backup codes request failed: {e}"))?;
+    let response = http::send(
+        state
+            .http
+            .post(format!("{server_url}/auth/mfa/backup-codes"))
+            .header("Authorization", format!("Bearer {token}")),
+    )
+    .await?;
 
     if !response.status().is_success() {
         let body = response.text().await.unwrap_or_default();
@@ -745,13 +996,13 @@ pub async fn mfa_backup_code_count(
 ) -> Result<MfaBackupCodeCountResponse, String> {
     let (server_url, token) = get_auth_context(&state).await?;
 
-    let response = state
-        .http
-        .get(format!("{server_url}/auth/mfa/backup-codes/count"))
-        .header("Authorization", format!("Bearer {token}"))
-        .send()
-        .await
-        .map_err(|e| format!("Backup code count request failed: {e}"))?;
+    let response = http::send_idempotent(
+        state
+            .http
+            .get(format!("{server_url}/auth/mfa/backup-codes/count"))
+            .header("Authorization", format!("Bearer {token}")),
+    )
+    .await?;
 
     if !response.status().is_success() {
         let body = response.text().await.unwrap_or_default();
@@ -763,3 +1014,245 @@ pub async fn mfa_backup_code_count(
         .await
         .map_err(|e| format!("Invalid backup code count response: {e}"))
 }
+
+// Passkeys
+
+/// A registered passkey.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct PasskeyInfo {
+    pub id: String,
+    pub name: String,
+    pub created_at: String,
+    pub last_used_at: Option<String>,
+}
+
+/// Start signing in with a passkey. Returns the server's assertion challenge
+/// (`challenge_id` and `options`), also used to answer `MFA_REQUIRED`.
+#[command]
+pub async fn passkey_login_start(
+    state: State<'_, AppState>,
+    server_url: String,
+    username: String,
+) -> Result<serde_json::Value, String> {
+    let server_url = server_url.trim_end_matches('/');
+
+    let response = http::send(
+        state
+            .http
+            .post(format!("{server_url}/auth/webauthn/login/start"))
+            .json(&serde_json::json!({ "username": username })),
+    )
+    .await?;
+
+    if !response.status().is_success() {
+        return Err(match response.status() {
+            StatusCode::UNAUTHORIZED => "No passkey is registered for this account".to_string(),
+            StatusCode::SERVICE_UNAVAILABLE => {
+                "Passkeys are not enabled on this server".to_string()
+            }
+            status => http::status_error("Passkey sign-in failed", status),
+        });
+    }
+
+    response
+        .json()
+        .await
+        .map_err(|e| format!("Invalid response from server: {e}"))
+}
+
+/// Finish signing in with a signed passkey assertion.
+#[command]
+pub async fn passkey_login_finish(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    server_url: String,
+    assertion: serde_json::Value,
+) -> Result<User, String> {
+    let server_url = server_url.trim_end_matches('/');
+
+    let response = http::send(
+        state
+            .http
+            .post(format!("{server_url}/auth/webauthn/login/finish"))
+            .json(&assertion),
+    )
+    .await?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        error!("Passkey sign-in failed with status {}", status);
+        return Err(if status == StatusCode::UNAUTHORIZED {
+            "The passkey was not accepted".to_string()
+        } else {
+            http::status_error("Passkey sign-in failed", status)
+        });
+    }
+
+    let tokens: TokenResponse = response.json().await.map_err(|e| {
+        error!("Failed to parse token response: {}", e);
+        format!("Invalid response from server: {e}")
+    })?;
+
+    let user = fetch_user(&state.http, server_url, &tokens.access_token).await?;
+    start_session(&app, &state, server_url, &tokens, user.clone()).await;
+
+    info!("User {} signed in with a passkey", user.username);
+    Ok(user)
+}
+
+/// List the current user's passkeys.
+#[command]
+pub async fn passkey_list(state: State<'_, AppState>) -> Result<Vec<PasskeyInfo>, String> {
+    let (server_url, token) = get_auth_context(&state).await?;
+
+    let response = http::send_idempotent(
+        state
+            .http
+            .get(format!("{server_url}/auth/webauthn/credentials"))
+            .header("Authorization", format!("Bearer {token}")),
+    )
+    .await?;
+
+    if !response.status().is_success() {
+        return Err(http::status_error(
+            "Failed to list passkeys",
+            response.status(),
+        ));
+    }
+
+    response
+        .json()
+        .await
+        .map_err(|e| format!("Invalid passkey list response: {e}"))
+}
+
+/// Start registering a passkey. Re-authenticates with the current password,
+/// an MFA code, or on accounts with neither a provider `reauth_token` or a
+/// `passkey` assertion.
+#[command]
+pub async fn passkey_register_start(
+    state: State<'_, AppState>,
+    current_password: Option<String>,
+    mfa_code: Option<String>,
+    reauth_token: Option<String>,
+    passkey: Option<serde_json::Value>,
+) -> Result<serde_json::Value, String> {
+    let (server_url, token) = get_auth_context(&state).await?;
+
+    let response = http::send(
+        state
+            .http
+            .post(format!("{server_url}/auth/webauthn/register/start"))
+            .header("Authorization", format!("Bearer {token}"))
+            .json(&serde_json::json!({
+                "current_password": current_password,
+                "mfa_code": mfa_code,
+                "reauth_token": reauth_token,
+                "passkey": passkey,
+            })),
+    )
+    .await?;
+
+    if !response.status().is_success() {
+        let body = response.text().await.unwrap_or_default();
+        return Err(format!("Passkey registration failed: {body}"));
+    }
+
+    response
+        .json()
+        .await
+        .map_err(|e| format!("Invalid passkey registration response: {e}"))
+}
+
+/// Finish registering a passkey with the authenticator's credential.
+#[command]
+pub async fn passkey_register_finish(
+    state: State<'_, AppState>,
+    challenge_id: String,
+    name: String,
+    credential: serde_json::Value,
+) -> Result<PasskeyInfo, String> {
+    let (server_url, token) = get_auth_context(&state).await?;
+
+    let response = http::send(
+        state
+            .http
+            .post(format!("{server_url}/auth/webauthn/register/finish"))
+            .header("Authorization", format!("Bearer {token}"))
+            .json(&serde_json::json!({
+                "challenge_id": challenge_id,
+                "name": name,
+                "credential": credential,
+            })),
+    )
+    .await?;
+
+    if !response.status().is_success() {
+        let body = response.text().await.unwrap_or_default();
+        return Err(format!("Passkey registration failed: {body}"));
+    }
+
+    response
+        .json()
+        .await
+        .map_err(|e| format!("Invalid passkey registration response: {e}"))
+}
+
+/// Remove one of the current user's passkeys.
+#[command]
+pub async fn passkey_delete(state: State<'_, AppState>, id: String) -> Result<(), String> {
+    let (server_url, token) = get_auth_context(&state).await?;
+
+    let response = http::send(
+        state
+            .http
+            .delete(format!("{server_url}/auth/webauthn/credentials/{id}"))
+            .header("Authorization", format!("Bearer {token}")),
+    )
+    .await?;
+
+    if !response.status().is_success() {
+        let body = response.text().await.unwrap_or_default();
+        return Err(format!("Failed to remove passkey: {body}"));
+    }
+
+    Ok(())
+}
+
+/// Create a passkey with the OS authenticator from the server's creation
+/// options. Fails with `PASSKEY_UNSUPPORTED` where the webview should run
+/// the ceremony instead.
+#[command]
+pub async fn passkey_create(
+    state: State<'_, AppState>,
+    options: serde_json::Value,
+) -> Result<serde_json::Value, String> {
+    let (server_url, _) = get_auth_context(&state).await?;
+    passkey::register(&server_url, options).await
+}
+
+/// Sign an assertion challenge with the OS authenticator. Fails with
+/// `PASSKEY_UNSUPPORTED` where the webview should run the ceremony instead.
+#[command]
+pub async fn passkey_get(
+    server_url: String,
+    options: serde_json::Value,
+) -> Result<serde_json::Value, String> {
+    passkey::authenticate(server_url.trim_end_matches('/'), options).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_refresh_delay_leaves_margin_before_expiry() {
+        assert_eq!(refresh_delay(900), Duration::from_secs(840));
+    }
+
+    #[test]
+    fn test_refresh_delay_has_floor_for_short_lifetimes() {
+        assert_eq!(refresh_delay(30), MIN_REFRESH_DELAY);
+        assert_eq!(refresh_delay(0), MIN_REFRESH_DELAY);
+    }
+}
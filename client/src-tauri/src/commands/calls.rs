@@ -8,6 +8,7 @@ use serde::{Deserialize, Serialize};
 use tauri::{command, State};
 use tracing::{debug, error, info};
 
+use crate::network::http;
 use crate::AppState;
 
 // ============================================================================
@@ -67,22 +68,19 @@ pub async fn start_dm_call(
 
     info!("Starting call in DM: {}", channel_id);
 
-    let response = state
-        .http
-        .post(format!("{server_url}/api/dm/{channel_id}/call/start"))
-        .header("Authorization", format!("Bearer {token}"))
-        .send()
-        .await
-        .map_err(|e| {
-            error!("Failed to start call: {}", e);
-            format!("Connection failed: {e}")
-        })?;
+    let response = http::send(
+        state
+            .http
+            .post(format!("{server_url}/api/dm/{channel_id}/call/start"))
+            .header("Authorization", format!("Bearer {token}")),
+    )
+    .await?;
 
     if !response.status().is_success() {
         let status = response.status();
         let body = response.text().await.unwrap_or_default();
         error!("Failed to start call: {} - {}", status, body);
-        return Err(format!("Failed to start call: {status}"));
+        return Err(http::status_error("Failed to start call", status));
     }
 
     let call_state: CallStateResponse = response
@@ -112,22 +110,19 @@ pub async fn join_dm_call(
 
     info!("Joining call in DM: {}", channel_id);
 
-    let response = state
-        .http
-        .post(format!("{server_url}/api/dm/{channel_id}/call/join"))
-        .header("Authorization", format!("Bearer {token}"))
-        .send()
-        .await
-        .map_err(|e| {
-            error!("Failed to join call: {}", e);
-            format!("Connection failed: {e}")
-        })?;
+    let response = http::send(
+        state
+            .http
+            .post(format!("{server_url}/api/dm/{channel_id}/call/join"))
+            .header("Authorization", format!("Bearer {token}")),
+    )
+    .await?;
 
     if !response.status().is_success() {
         let status = response.status();
         let body = response.text().await.unwrap_or_default();
         error!("Failed to join call: {} - {}", status, body);
-        return Err(format!("Failed to join call: {status}"));
+        return Err(http::status_error("Failed to join call", status));
     }
 
     let call_state: CallStateResponse = response
@@ -157,16 +152,13 @@ pub async fn decline_dm_call(
 
     info!("Declining call in DM: {}", channel_id);
 
-    let response = state
-        .http
-        .post(format!("{server_url}/api/dm/{channel_id}/call/decline"))
-        .header("Authorization", format!("Bearer {token}"))
-        .send()
-        .await
-        .map_err(|e| {
-            error!("Failed to decline call: {}", e);
-            format!("Connection failed: {e}")
-        })?;
+    let response = http::send(
+        state
+            .http
+            .post(format!("{server_url}/api/dm/{channel_id}/call/decline"))
+            .header("Authorization", format!("Bearer {token}")),
+    )
+    .await?;
 
     // 404 means call already ended, which is fine
     if response.status().as_u16() == 404 {
@@ -185,7 +177,7 @@ pub async fn decline_dm_call(
         let status = response.status();
         let body = response.text().await.unwrap_or_default();
         error!("Failed to decline call: {} - {}", status, body);
-        return Err(format!("Failed to decline call: {status}"));
+        return Err(http::status_error("Failed to decline call", status));
     }
 
     let call_state: CallStateResponse = response
@@ -215,16 +207,13 @@ pub async fn leave_dm_call(
 
     info!("Leaving call in DM: {}", channel_id);
 
-    let response = state
-        .http
-        .post(format!("{server_url}/api/dm/{channel_id}/call/leave"))
-        .header("Authorization", format!("Bearer {token}"))
-        .send()
-        .await
-        .map_err(|e| {
-            error!("Failed to leave call: {}", e);
-            format!("Connection failed: {e}")
-        })?;
+    let response = http::send(
+        state
+            .http
+            .post(format!("{server_url}/api/dm/{channel_id}/call/leave"))
+            .header("Authorization", format!("Bearer {token}")),
+    )
+    .await?;
 
     // 404 means call already ended, which is fine
     if response.status().as_u16() == 404 {
@@ -243,7 +232,7 @@ pub async fn leave_dm_call(
         let status = response.status();
         let body = response.text().await.unwrap_or_default();
         error!("Failed to leave call: {} - {}", status, body);
-        return Err(format!("Failed to leave call: {status}"));
+        return Err(http::status_error("Failed to leave call", status));
     }
 
     let call_state: CallStateResponse = response
@@ -271,16 +260,13 @@ pub async fn get_dm_call(
 
     debug!("Getting call state for DM: {}", channel_id);
 
-    let response = state
-        .http
-        .get(format!("{server_url}/api/dm/{channel_id}/call"))
-        .header("Authorization", format!("Bearer {token}"))
-        .send()
-        .await
-        .map_err(|e| {
-            error!("Failed to get call state: {}", e);
-            format!("Connection failed: {e}")
-        })?;
+    let response = http::send_idempotent(
+        state
+            .http
+            .get(format!("{server_url}/api/dm/{channel_id}/call"))
+            .header("Authorization", format!("Bearer {token}")),
+    )
+    .await?;
 
     // 404 means no active call
     if response.status().as_u16() == 404 {
@@ -291,7 +277,7 @@ pub async fn get_dm_call(
         let status = response.status();
         let body = response.text().await.unwrap_or_default();
         error!("Failed to get call state: {} - {}", status, body);
-        return Err(format!("Failed to get call state: {status}"));
+        return Err(http::status_error("Failed to get call state", status));
     }
 
     let call_state: CallStateResponse = response
@@ -4,6 +4,7 @@ use serde::{Deserialize, Serialize};
 use tauri::{command, State};
 use tracing::{debug, error};
 
+use crate::network::http;
 use crate::{AppState, UserStatus};
 
 /// Channel type.
@@ -79,21 +80,18 @@ pub async fn get_channels(state: State<'_, AppState>) -> Result<Vec<Channel>, St
 
     debug!("Fetching channels from {}", server_url);
 
-    let response = state
-        .http
-        .get(format!("{server_url}/api/channels"))
-        .header("Authorization", format!("Bearer {token}"))
-        .send()
-        .await
-        .map_err(|e| {
-            error!("Failed to fetch channels: {}", e);
-            format!("Connection failed: {e}")
-        })?;
+    let response = http::send_idempotent(
+        state
+            .http
+            .get(format!("{server_url}/api/channels"))
+            .header("Authorization", format!("Bearer {token}")),
+    )
+    .await?;
 
     if !response.status().is_success() {
         let status = response.status();
         error!("Failed to fetch channels: {}", status);
-        return Err(format!("Failed to fetch channels: {status}"));
+        return Err(http::status_error("Failed to fetch channels", status));
     }
 
     let channels: Vec<Channel> = response
@@ -137,21 +135,18 @@ pub async fn get_messages(
         url = format!("{}?{}", url, params.join("&"));
     }
 
-    let response = state
-        .http
-        .get(&url)
-        .header("Authorization", format!("Bearer {token}"))
-        .send()
-        .await
-        .map_err(|e| {
-            error!("Failed to fetch messages: {}", e);
-            format!("Connection failed: {e}")
-        })?;
+    let response = http::send_idempotent(
+        state
+            .http
+            .get(&url)
+            .header("Authorization", format!("Bearer {token}")),
+    )
+    .await?;
 
     if !response.status().is_success() {
         let status = response.status();
         error!("Failed to fetch messages: {}", status);
-        return Err(format!("Failed to fetch messages: {status}"));
+        return Err(http::status_error("Failed to fetch messages", status));
     }
 
     let messages: Vec<Message> = response
@@ -180,26 +175,23 @@ pub async fn send_message(
 
     debug!("Sending message to channel {}", channel_id);
 
-    let response = state
-        .http
-        .post(format!("{server_url}/api/messages/channel/{channel_id}"))
-        .header("Authorization", format!("Bearer {token}"))
-        .json(&serde_json::json!({
-            "content": content,
-            "encrypted": false
-        }))
-        .send()
-        .await
-        .map_err(|e| {
-            error!("Failed to send message: {}", e);
-            format!("Connection failed: {e}")
-        })?;
+    let response = http::send(
+        state
+            .http
+            .post(format!("{server_url}/api/messages/channel/{channel_id}"))
+            .header("Authorization", format!("Bearer {token}"))
+            .json(&serde_json::json!({
+                "content": content,
+                "encrypted": false
+            })),
+    )
+    .await?;
 
     if !response.status().is_success() {
         let status = response.status();
         let body = response.text().await.unwrap_or_default();
         error!("Failed to send message: {} - {}", status, body);
-        return Err(format!("Failed to send message: {status}"));
+        return Err(http::status_error("Failed to send message", status));
     }
 
     let message: Message = response
@@ -228,25 +220,22 @@ pub async fn edit_message(
 
     debug!("Editing message {}", message_id);
 
-    let response = state
-        .http
-        .patch(format!("{server_url}/api/messages/{message_id}"))
-        .header("Authorization", format!("Bearer {token}"))
-        .json(&serde_json::json!({
-            "content": content
-        }))
-        .send()
-        .await
-        .map_err(|e| {
-            error!("Failed to edit message: {}", e);
-            format!("Connection failed: {e}")
-        })?;
+    let response = http::send(
+        state
+            .http
+            .patch(format!("{server_url}/api/messages/{message_id}"))
+            .header("Authorization", format!("Bearer {token}"))
+            .json(&serde_json::json!({
+                "content": content
+            })),
+    )
+    .await?;
 
     if !response.status().is_success() {
         let status = response.status();
         let body = response.text().await.unwrap_or_default();
         error!("Failed to edit message: {} - {}", status, body);
-        return Err(format!("Failed to edit message: {status}"));
+        return Err(http::status_error("Failed to edit message", status));
     }
 
     let message: Message = response
@@ -289,21 +278,18 @@ pub async fn get_thread_replies(
         url = format!("{}?{}", url, params.join("&"));
     }
 
-    let response = state
-        .http
-        .get(&url)
-        .header("Authorization", format!("Bearer {token}"))
-        .send()
-        .await
-        .map_err(|e| {
-            error!("Failed to fetch thread replies: {}", e);
-            format!("Connection failed: {e}")
-        })?;
+    let response = http::send_idempotent(
+        state
+            .http
+            .get(&url)
+            .header("Authorization", format!("Bearer {token}")),
+    )
+    .await?;
 
     if !response.status().is_success() {
         let status = response.status();
         error!("Failed to fetch thread replies: {}", status);
-        return Err(format!("Failed to fetch thread replies: {status}"));
+        return Err(http::status_error("Failed to fetch thread replies", status));
     }
 
     let body: serde_json::Value = response
@@ -348,23 +334,20 @@ pub async fn send_thread_reply(
         body["nonce"] = serde_json::Value::String(n);
     }
 
-    let response = state
-        .http
-        .post(format!("{server_url}/api/messages/channel/{channel_id}"))
-        .header("Authorization", format!("Bearer {token}"))
-        .json(&body)
-        .send()
-        .await
-        .map_err(|e| {
-            error!("Failed to send thread reply: {}", e);
-            format!("Connection failed: {e}")
-        })?;
+    let response = http::send(
+        state
+            .http
+            .post(format!("{server_url}/api/messages/channel/{channel_id}"))
+            .header("Authorization", format!("Bearer {token}"))
+            .json(&body),
+    )
+    .await?;
 
     if !response.status().is_success() {
         let status = response.status();
         let err_body = response.text().await.unwrap_or_default();
         error!("Failed to send thread reply: {} - {}", status, err_body);
-        return Err(format!("Failed to send thread reply: {status}"));
+        return Err(http::status_error("Failed to send thread reply", status));
     }
 
     let message: serde_json::Value = response
@@ -389,21 +372,18 @@ pub async fn mark_thread_read(state: State<'_, AppState>, parent_id: String) ->
 
     debug!("Marking thread {} as read", parent_id);
 
-    let response = state
-        .http
-        .post(format!("{server_url}/api/messages/{parent_id}/thread/read"))
-        .header("Authorization", format!("Bearer {token}"))
-        .send()
-        .await
-        .map_err(|e| {
-            error!("Failed to mark thread read: {}", e);
-            format!("Connection failed: {e}")
-        })?;
+    let response = http::send(
+        state
+            .http
+            .post(format!("{server_url}/api/messages/{parent_id}/thread/read"))
+            .header("Authorization", format!("Bearer {token}")),
+    )
+    .await?;
 
     if !response.status().is_success() {
         let status = response.status();
         error!("Failed to mark thread read: {}", status);
-        return Err(format!("Failed to mark thread read: {status}"));
+        return Err(http::status_error("Failed to mark thread read", status));
     }
 
     debug!("Thread {} marked as read", parent_id);
@@ -15,6 +15,7 @@ use vc_crypto::olm::EncryptedMessage;
 use vc_crypto::{EncryptedBackup, RecoveryKey};
 
 use crate::crypto::{ClaimedPrekey, CryptoManager, PrekeyForUpload, PrekeyInfo};
+use crate::network::http;
 use crate::AppState;
 
 /// Recovery key formatted for display (4-char chunks).
@@ -272,12 +273,8 @@ pub async fn get_server_settings(state: State<'_, AppState>) -> Result<ServerSet
     let auth = state.auth.read().await;
     let server_url = auth.server_url.as_ref().ok_or("Not connected")?;
 
-    let response = state
-        .http
-        .get(format!("{server_url}/api/settings"))
-        .send()
-        .await
-        .map_err(|e| format!("Request failed: {e}"))?;
+    let response =
+        http::send_idempotent(state.http.get(format!("{server_url}/api/settings"))).await?;
 
     if !response.status().is_success() {
         return Err(format!("Server error: {}", response.status()));
@@ -298,13 +295,13 @@ pub async fn get_backup_status(state: State<'_, AppState>) -> Result<BackupStatu
     let server_url = auth.server_url.as_ref().ok_or("Not connected")?;
     let token = auth.access_token.as_ref().ok_or("Not authenticated")?;
 
-    let response = state
-        .http
-        .get(format!("{server_url}/api/keys/backup/status"))
-        .bearer_auth(token)
-        .send()
-        .await
-        .map_err(|e| format!("Request failed: {e}"))?;
+    let response = http::send_idempotent(
+        state
+            .http
+            .get(format!("{server_url}/api/keys/backup/status"))
+            .bearer_auth(token),
+    )
+    .await?;
 
     if !response.status().is_success() {
         return Err(format!("Server error: {}", response.status()));
@@ -386,14 +383,14 @@ pub async fn create_backup(
     let server_url = auth.server_url.as_ref().ok_or("Not connected")?;
     let token = auth.access_token.as_ref().ok_or("Not authenticated")?;
 
-    let response = state
-        .http
-        .post(format!("{server_url}/api/keys/backup"))
-        .bearer_auth(token)
-        .json(&request)
-        .send()
-        .await
-        .map_err(|e| format!("Upload failed: {e}"))?;
+    let response = http::send(
+        state
+            .http
+            .post(format!("{server_url}/api/keys/backup"))
+            .bearer_auth(token)
+            .json(&request),
+    )
+    .await?;
 
     if !response.status().is_success() {
         let body = response.text().await.unwrap_or_default();
@@ -430,13 +427,13 @@ pub async fn restore_backup(
     let server_url = auth.server_url.as_ref().ok_or("Not connected")?;
     let token = auth.access_token.as_ref().ok_or("Not authenticated")?;
 
-    let response = state
-        .http
-        .get(format!("{server_url}/api/keys/backup"))
-        .bearer_auth(token)
-        .send()
-        .await
-        .map_err(|e| format!("Download failed: {e}"))?;
+    let response = http::send_idempotent(
+        state
+            .http
+            .get(format!("{server_url}/api/keys/backup"))
+            .bearer_auth(token),
+    )
+    .await?;
 
     if response.status().as_u16() == 404 {
         return Err("No backup found".to_string());
@@ -0,0 +1,93 @@
+//! Draft Sync Commands
+//!
+//! Tauri commands for syncing message drafts with the server.
+
+use tauri::{command, State};
+use tracing::{debug, error};
+
+use crate::network::http;
+use crate::AppState;
+
+/// Fetch the user's drafts from the server.
+///
+/// Returns `{ drafts: [{ channel_id, content, updated_at }] }`.
+#[command]
+pub async fn fetch_drafts(state: State<'_, AppState>) -> Result<serde_json::Value, String> {
+    let (server_url, token) = {
+        let auth = state.auth.read().await;
+        (auth.server_url.clone(), auth.access_token.clone())
+    };
+
+    let server_url = server_url.ok_or("Not authenticated")?;
+    let token = token.ok_or("Not authenticated")?;
+
+    debug!("Fetching drafts from server");
+
+    let response = http::send_idempotent(
+        state
+            .http
+            .get(format!("{server_url}/api/me/drafts"))
+            .header("Authorization", format!("Bearer {token}")),
+    )
+    .await?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        error!("Failed to fetch drafts: {}", status);
+        return Err(http::status_error("Failed to fetch drafts", status));
+    }
+
+    let drafts: serde_json::Value = response
+        .json()
+        .await
+        .map_err(|e| format!("Invalid response: {e}"))?;
+
+    debug!("Drafts fetched successfully");
+    Ok(drafts)
+}
+
+/// Save (or clear, with empty content) a channel draft on the server.
+///
+/// `updated_at` is when the draft was edited locally. Returns the draft the
+/// server keeps, which is a newer one from another device if this edit lost.
+#[command]
+pub async fn save_draft(
+    state: State<'_, AppState>,
+    channel_id: String,
+    content: String,
+    updated_at: String,
+) -> Result<serde_json::Value, String> {
+    let (server_url, token) = {
+        let auth = state.auth.read().await;
+        (auth.server_url.clone(), auth.access_token.clone())
+    };
+
+    let server_url = server_url.ok_or("Not authenticated")?;
+    let token = token.ok_or("Not authenticated")?;
+
+    debug!("Saving draft for channel {}", channel_id);
+
+    // Saves carry their edit time, so a retried request cannot overwrite a newer draft
+    let response = http::send_idempotent(
+        state
+            .http
+            .put(format!("{server_url}/api/me/drafts/{channel_id}"))
+            .header("Authorization", format!("Bearer {token}"))
+            .json(&serde_json::json!({ "content": content, "updated_at": updated_at })),
+    )
+    .await?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+        error!("Failed to save draft: {} - {}", status, body);
+        return Err(http::status_error("Failed to save draft", status));
+    }
+
+    let draft: serde_json::Value = response
+        .json()
+        .await
+        .map_err(|e| format!("Invalid response: {e}"))?;
+
+    Ok(draft)
+}
@@ -6,6 +6,7 @@ use serde::{Deserialize, Serialize};
 use tauri::{command, State};
 use tracing::{debug, error};
 
+use crate::network::http;
 use crate::AppState;
 
 /// A favorite channel.
@@ -48,21 +49,18 @@ pub async fn fetch_favorites(state: State<'_, AppState>) -> Result<Vec<FavoriteC
 
     debug!("Fetching favorites from server");
 
-    let response = state
-        .http
-        .get(format!("{server_url}/api/me/favorites"))
-        .header("Authorization", format!("Bearer {token}"))
-        .send()
-        .await
-        .map_err(|e| {
-            error!("Failed to fetch favorites: {}", e);
-            format!("Connection failed: {e}")
-        })?;
+    let response = http::send_idempotent(
+        state
+            .http
+            .get(format!("{server_url}/api/me/favorites"))
+            .header("Authorization", format!("Bearer {token}")),
+    )
+    .await?;
 
     if !response.status().is_success() {
         let status = response.status();
         error!("Failed to fetch favorites: {}", status);
-        return Err(format!("Failed to fetch favorites: {status}"));
+        return Err(http::status_error("Failed to fetch favorites", status));
     }
 
     let data: FavoritesResponse = response
@@ -90,22 +88,19 @@ pub async fn add_favorite(
 
     debug!("Adding favorite: channel_id={}", channel_id);
 
-    let response = state
-        .http
-        .post(format!("{server_url}/api/me/favorites/{channel_id}"))
-        .header("Authorization", format!("Bearer {token}"))
-        .send()
-        .await
-        .map_err(|e| {
-            error!("Failed to add favorite: {}", e);
-            format!("Connection failed: {e}")
-        })?;
+    let response = http::send(
+        state
+            .http
+            .post(format!("{server_url}/api/me/favorites/{channel_id}"))
+            .header("Authorization", format!("Bearer {token}")),
+    )
+    .await?;
 
     if !response.status().is_success() {
         let status = response.status();
         let body = response.text().await.unwrap_or_default();
         error!("Failed to add favorite: {} - {}", status, body);
-        return Err(format!("Failed to add favorite: {status}"));
+        return Err(http::status_error("Failed to add favorite", status));
     }
 
     let favorite: Favorite = response
@@ -130,21 +125,18 @@ pub async fn remove_favorite(state: State<'_, AppState>, channel_id: String) ->
 
     debug!("Removing favorite: channel_id={}", channel_id);
 
-    let response = state
-        .http
-        .delete(format!("{server_url}/api/me/favorites/{channel_id}"))
-        .header("Authorization", format!("Bearer {token}"))
-        .send()
-        .await
-        .map_err(|e| {
-            error!("Failed to remove favorite: {}", e);
-            format!("Connection failed: {e}")
-        })?;
+    let response = http::send(
+        state
+            .http
+            .delete(format!("{server_url}/api/me/favorites/{channel_id}"))
+            .header("Authorization", format!("Bearer {token}")),
+    )
+    .await?;
 
     if !response.status().is_success() {
         let status = response.status();
         error!("Failed to remove favorite: {}", status);
-        return Err(format!("Failed to remove favorite: {status}"));
+        return Err(http::status_error("Failed to remove favorite", status));
     }
 
     debug!("Favorite removed: channel_id={}", channel_id);
@@ -172,22 +164,19 @@ pub async fn reorder_favorite_channels(
         guild_id
     );
 
-    let response = state
-        .http
-        .put(format!("{server_url}/api/me/favorites/reorder"))
-        .header("Authorization", format!("Bearer {token}"))
-        .json(&serde_json::json!({ "guild_id": guild_id, "channel_ids": channel_ids }))
-        .send()
-        .await
-        .map_err(|e| {
-            error!("Failed to reorder favorites: {}", e);
-            format!("Connection failed: {e}")
-        })?;
+    let response = http::send(
+        state
+            .http
+            .put(format!("{server_url}/api/me/favorites/reorder"))
+            .header("Authorization", format!("Bearer {token}"))
+            .json(&serde_json::json!({ "guild_id": guild_id, "channel_ids": channel_ids })),
+    )
+    .await?;
 
     if !response.status().is_success() {
         let status = response.status();
         error!("Failed to reorder favorites: {}", status);
-        return Err(format!("Failed to reorder favorites: {status}"));
+        return Err(http::status_error("Failed to reorder favorites", status));
     }
 
     debug!("Favorites reordered successfully");
@@ -210,22 +199,22 @@ pub async fn reorder_favorite_guilds(
 
     debug!("Reordering {} favorite guilds", guild_ids.len());
 
-    let response = state
-        .http
-        .put(format!("{server_url}/api/me/favorites/reorder-guilds"))
-        .header("Authorization", format!("Bearer {token}"))
-        .json(&serde_json::json!({ "guild_ids": guild_ids }))
-        .send()
-        .await
-        .map_err(|e| {
-            error!("Failed to reorder favorite guilds: {}", e);
-            format!("Connection failed: {e}")
-        })?;
+    let response = http::send(
+        state
+            .http
+            .put(format!("{server_url}/api/me/favorites/reorder-guilds"))
+            .header("Authorization", format!("Bearer {token}"))
+            .json(&serde_json::json!({ "guild_ids": guild_ids })),
+    )
+    .await?;
 
     if !response.status().is_success() {
         let status = response.status();
         error!("Failed to reorder favorite guilds: {}", status);
-        return Err(format!("Failed to reorder favorite guilds: {status}"));
+        return Err(http::status_error(
+            "Failed to reorder favorite guilds",
+            status,
+        ));
     }
 
     debug!("Favorite guilds reordered successfully");
@@ -0,0 +1,345 @@
+//! Friends Tauri Commands
+//!
+//! Friend requests, friend list and blocking.
+
+use serde::{Deserialize, Serialize};
+use tauri::{command, State};
+use tracing::{debug, error};
+
+use crate::network::http;
+use crate::AppState;
+
+/// A friend, pending request or blocked user.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Friend {
+    pub user_id: String,
+    pub username: String,
+    pub display_name: String,
+    pub avatar_url: Option<String>,
+    pub status_message: Option<String>,
+    pub is_online: bool,
+    pub friendship_id: String,
+    pub friendship_status: String,
+    pub created_at: String,
+}
+
+/// A friendship record.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Friendship {
+    pub id: String,
+    pub requester_id: String,
+    pub addressee_id: String,
+    pub status: String,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+/// Fetch the current user's friends.
+#[command]
+pub async fn fetch_friends(state: State<'_, AppState>) -> Result<Vec<Friend>, String> {
+    let (server_url, token) = {
+        let auth = state.auth.read().await;
+        (auth.server_url.clone(), auth.access_token.clone())
+    };
+
+    let server_url = server_url.ok_or("Not authenticated")?;
+    let token = token.ok_or("Not authenticated")?;
+
+    debug!("Fetching friends");
+
+    let response = http::send_idempotent(
+        state
+            .http
+            .get(format!("{server_url}/api/friends"))
+            .header("Authorization", format!("Bearer {token}")),
+    )
+    .await?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        error!("Failed to fetch friends: {}", status);
+        return Err(http::status_error("Failed to fetch friends", status));
+    }
+
+    response
+        .json()
+        .await
+        .map_err(|e| format!("Invalid response: {e}"))
+}
+
+/// Fetch incoming and outgoing pending friend requests.
+#[command]
+pub async fn fetch_pending_friends(state: State<'_, AppState>) -> Result<Vec<Friend>, String> {
+    let (server_url, token) = {
+        let auth = state.auth.read().await;
+        (auth.server_url.clone(), auth.access_token.clone())
+    };
+
+    let server_url = server_url.ok_or("Not authenticated")?;
+    let token = token.ok_or("Not authenticated")?;
+
+    debug!("Fetching pending friend requests");
+
+    let response = http::send_idempotent(
+        state
+            .http
+            .get(format!("{server_url}/api/friends/pending"))
+            .header("Authorization", format!("Bearer {token}")),
+    )
+    .await?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        error!("Failed to fetch pending friend requests: {}", status);
+        return Err(http::status_error(
+            "Failed to fetch pending friend requests",
+            status,
+        ));
+    }
+
+    response
+        .json()
+        .await
+        .map_err(|e| format!("Invalid response: {e}"))
+}
+
+/// Fetch the users blocked by the current user.
+#[command]
+pub async fn fetch_blocked_users(state: State<'_, AppState>) -> Result<Vec<Friend>, String> {
+    let (server_url, token) = {
+        let auth = state.auth.read().await;
+        (auth.server_url.clone(), auth.access_token.clone())
+    };
+
+    let server_url = server_url.ok_or("Not authenticated")?;
+    let token = token.ok_or("Not authenticated")?;
+
+    debug!("Fetching blocked users");
+
+    let response = http::send_idempotent(
+        state
+            .http
+            .get(format!("{server_url}/api/friends/blocked"))
+            .header("Authorization", format!("Bearer {token}")),
+    )
+    .await?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        error!("Failed to fetch blocked users: {}", status);
+        return Err(http::status_error("Failed to fetch blocked users", status));
+    }
+
+    response
+        .json()
+        .await
+        .map_err(|e| format!("Invalid response: {e}"))
+}
+
+/// Send a friend request by username.
+#[command]
+pub async fn send_friend_request(
+    state: State<'_, AppState>,
+    username: String,
+) -> Result<Friendship, String> {
+    let (server_url, token) = {
+        let auth = state.auth.read().await;
+        (auth.server_url.clone(), auth.access_token.clone())
+    };
+
+    let server_url = server_url.ok_or("Not authenticated")?;
+    let token = token.ok_or("Not authenticated")?;
+
+    debug!("Sending friend request: username={}", username);
+
+    let response = http::send(
+        state
+            .http
+            .post(format!("{server_url}/api/friends/request"))
+            .header("Authorization", format!("Bearer {token}"))
+            .json(&serde_json::json!({ "username": username })),
+    )
+    .await?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        error!("Failed to send friend request: {}", status);
+        return Err(http::status_error("Failed to send friend request", status));
+    }
+
+    response
+        .json()
+        .await
+        .map_err(|e| format!("Invalid response: {e}"))
+}
+
+/// Accept an incoming friend request.
+#[command]
+pub async fn accept_friend_request(
+    state: State<'_, AppState>,
+    friendship_id: String,
+) -> Result<Friendship, String> {
+    let (server_url, token) = {
+        let auth = state.auth.read().await;
+        (auth.server_url.clone(), auth.access_token.clone())
+    };
+
+    let server_url = server_url.ok_or("Not authenticated")?;
+    let token = token.ok_or("Not authenticated")?;
+
+    debug!("Accepting friend request: friendship_id={}", friendship_id);
+
+    let response = http::send(
+        state
+            .http
+            .post(format!("{server_url}/api/friends/{friendship_id}/accept"))
+            .header("Authorization", format!("Bearer {token}")),
+    )
+    .await?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        error!("Failed to accept friend request: {}", status);
+        return Err(http::status_error(
+            "Failed to accept friend request",
+            status,
+        ));
+    }
+
+    response
+        .json()
+        .await
+        .map_err(|e| format!("Invalid response: {e}"))
+}
+
+/// Decline an incoming friend request.
+#[command]
+pub async fn decline_friend_request(
+    state: State<'_, AppState>,
+    friendship_id: String,
+) -> Result<(), String> {
+    let (server_url, token) = {
+        let auth = state.auth.read().await;
+        (auth.server_url.clone(), auth.access_token.clone())
+    };
+
+    let server_url = server_url.ok_or("Not authenticated")?;
+    let token = token.ok_or("Not authenticated")?;
+
+    debug!("Declining friend request: friendship_id={}", friendship_id);
+
+    let response = http::send(
+        state
+            .http
+            .post(format!("{server_url}/api/friends/{friendship_id}/reject"))
+            .header("Authorization", format!("Bearer {token}")),
+    )
+    .await?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        error!("Failed to decline friend request: {}", status);
+        return Err(http::status_error(
+            "Failed to decline friend request",
+            status,
+        ));
+    }
+
+    Ok(())
+}
+
+/// Remove a friend.
+#[command]
+pub async fn remove_friend(
+    state: State<'_, AppState>,
+    friendship_id: String,
+) -> Result<(), String> {
+    let (server_url, token) = {
+        let auth = state.auth.read().await;
+        (auth.server_url.clone(), auth.access_token.clone())
+    };
+
+    let server_url = server_url.ok_or("Not authenticated")?;
+    let token = token.ok_or("Not authenticated")?;
+
+    debug!("Removing friend: friendship_id={}", friendship_id);
+
+    let response = http::send(
+        state
+            .http
+            .delete(format!("{server_url}/api/friends/{friendship_id}"))
+            .header("Authorization", format!("Bearer {token}")),
+    )
+    .await?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        error!("Failed to remove friend: {}", status);
+        return Err(http::status_error("Failed to remove friend", status));
+    }
+
+    Ok(())
+}
+
+/// Block a user.
+#[command]
+pub async fn block_user(state: State<'_, AppState>, user_id: String) -> Result<Friendship, String> {
+    let (server_url, token) = {
+        let auth = state.auth.read().await;
+        (auth.server_url.clone(), auth.access_token.clone())
+    };
+
+    let server_url = server_url.ok_or("Not authenticated")?;
+    let token = token.ok_or("Not authenticated")?;
+
+    debug!("Blocking user: user_id={}", user_id);
+
+    let response = http::send(
+        state
+            .http
+            .post(format!("{server_url}/api/friends/{user_id}/block"))
+            .header("Authorization", format!("Bearer {token}")),
+    )
+    .await?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        error!("Failed to block user: {}", status);
+        return Err(http::status_error("Failed to block user", status));
+    }
+
+    response
+        .json()
+        .await
+        .map_err(|e| format!("Invalid response: {e}"))
+}
+
+/// Unblock a user.
+#[command]
+pub async fn unblock_user(state: State<'_, AppState>, user_id: String) -> Result<(), String> {
+    let (server_url, token) = {
+        let auth = state.auth.read().await;
+        (auth.server_url.clone(), auth.access_token.clone())
+    };
+
+    let server_url = server_url.ok_or("Not authenticated")?;
+    let token = token.ok_or("Not authenticated")?;
+
+    debug!("Unblocking user: user_id={}", user_id);
+
+    let response = http::send(
+        state
+            .http
+            .delete(format!("{server_url}/api/friends/{user_id}/block"))
+            .header("Authorization", format!("Bearer {token}")),
+    )
+    .await?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        error!("Failed to unblock user: {}", status);
+        return Err(http::status_error("Failed to unblock user", status));
+    }
+
+    Ok(())
+}
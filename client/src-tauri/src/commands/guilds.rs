@@ -0,0 +1,107 @@
+//! Guild Commands
+//!
+//! Guild, channel and member lists served from the guild cache, which keeps
+//! them current from WebSocket events. A list is fetched from the server the
+//! first time it is queried in a session.
+
+use serde_json::Value;
+use tauri::{command, State};
+use tracing::{debug, error, warn};
+
+use crate::guild_cache::CacheKey;
+use crate::network::http;
+use crate::AppState;
+
+/// Serve a list from the cache, fetching it from `path` when needed.
+///
+/// Falls back to the list saved by an earlier session when the server cannot
+/// be reached.
+async fn cached_list(state: &AppState, key: CacheKey, path: &str) -> Result<Vec<Value>, String> {
+    if let Some(list) = state.guild_cache.get(&key) {
+        return Ok(list);
+    }
+
+    let (server_url, token) = {
+        let auth = state.auth.read().await;
+        (auth.server_url.clone(), auth.access_token.clone())
+    };
+
+    let server_url = server_url.ok_or("Not authenticated")?;
+    let token = token.ok_or("Not authenticated")?;
+
+    debug!("Fetching {} for guild cache", path);
+
+    let fetched = async {
+        let response = http::send_idempotent(
+            state
+                .http
+                .get(format!("{server_url}{path}"))
+                .header("Authorization", format!("Bearer {token}")),
+        )
+        .await?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            error!("Failed to fetch {}: {}", path, status);
+            return Err(http::status_error("Failed to fetch guild data", status));
+        }
+
+        response
+            .json::<Vec<Value>>()
+            .await
+            .map_err(|e| format!("Invalid response: {e}"))
+    }
+    .await;
+
+    match fetched {
+        Ok(list) => {
+            state.guild_cache.store(key, list.clone());
+            Ok(list)
+        }
+        Err(e) => match state.guild_cache.get_stale(&key) {
+            Some(list) => {
+                warn!("Serving saved guild data for {}: {}", path, e);
+                Ok(list)
+            }
+            None => Err(e),
+        },
+    }
+}
+
+/// Get the guilds the user is a member of.
+#[command]
+pub async fn get_guilds(state: State<'_, AppState>) -> Result<Vec<Value>, String> {
+    cached_list(&state, CacheKey::Guilds, "/api/guilds").await
+}
+
+/// Get a guild's channels, with unread counts.
+#[command]
+pub async fn get_guild_channels(
+    state: State<'_, AppState>,
+    guild_id: String,
+) -> Result<Vec<Value>, String> {
+    let path = format!("/api/guilds/{guild_id}/channels");
+    cached_list(&state, CacheKey::Channels(guild_id), &path).await
+}
+
+/// Get a guild's members.
+#[command]
+pub async fn get_guild_members(
+    state: State<'_, AppState>,
+    guild_id: String,
+) -> Result<Vec<Value>, String> {
+    let path = format!("/api/guilds/{guild_id}/members");
+    cached_list(&state, CacheKey::Members(guild_id), &path).await
+}
+
+/// Refetch a guild's lists (or all lists) on their next query.
+///
+/// For changes the server does not announce, such as joining or leaving a guild.
+#[command]
+pub async fn invalidate_guild_cache(
+    state: State<'_, AppState>,
+    guild_id: Option<String>,
+) -> Result<(), String> {
+    state.guild_cache.invalidate(guild_id.as_deref());
+    Ok(())
+}
@@ -8,7 +8,12 @@ pub mod calls;
 pub mod chat;
 pub mod clipboard;
 pub mod crypto;
+pub mod drafts;
 pub mod favorites;
+pub mod friends;
+pub mod guilds;
+pub mod network;
+pub mod notifications;
 pub mod pages;
 pub mod pins;
 pub mod preferences;
@@ -0,0 +1,15 @@
+//! Network Commands
+
+use tauri::command;
+
+use crate::network::connectivity::{self, NetworkState};
+
+/// Get whether the server is currently reachable.
+///
+/// Changes are pushed as `network-state` events; this gives the initial value.
+#[command]
+pub fn get_network_state() -> NetworkState {
+    NetworkState {
+        online: connectivity::is_online(),
+    }
+}
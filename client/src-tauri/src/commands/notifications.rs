@@ -0,0 +1,42 @@
+//! Notification Commands
+//!
+//! Settings for native desktop notifications. The frontend pushes these from
+//! synced preferences; they are also saved locally so rules apply before the
+//! preferences have loaded.
+
+use tauri::{command, AppHandle, State};
+use tracing::debug;
+
+use crate::notifications::{self, NotificationSettings};
+use crate::AppState;
+
+/// Get the current desktop notification settings.
+#[command]
+pub async fn get_notification_settings(
+    state: State<'_, AppState>,
+) -> Result<NotificationSettings, String> {
+    Ok(state.notifications.settings().await)
+}
+
+/// Replace the desktop notification settings and save them to disk.
+#[command]
+pub async fn set_notification_settings(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    settings: NotificationSettings,
+) -> Result<(), String> {
+    debug!(
+        muted_guilds = settings.muted_guilds.len(),
+        dnd_windows = settings.dnd_windows.len(),
+        "Updating notification settings"
+    );
+
+    let path = notifications::settings_path(&app)?;
+    let to_save = settings.clone();
+    tokio::task::spawn_blocking(move || notifications::save_settings(&path, &to_save))
+        .await
+        .map_err(|e| format!("Failed to save notification settings: {e}"))??;
+
+    state.notifications.set_settings(settings).await;
+    Ok(())
+}
@@ -6,6 +6,7 @@ use serde::{Deserialize, Serialize};
 use tauri::{command, State};
 use tracing::{debug, error};
 
+use crate::network::http;
 use crate::AppState;
 
 /// Validate that a string is safe for use as a URL path segment.
@@ -75,21 +76,18 @@ pub async fn list_platform_pages(state: State<'_, AppState>) -> Result<Vec<PageL
 
     debug!("Fetching platform pages");
 
-    let response = state
-        .http
-        .get(format!("{server_url}/api/pages"))
-        .header("Authorization", format!("Bearer {token}"))
-        .send()
-        .await
-        .map_err(|e| {
-            error!("Failed to fetch platform pages: {}", e);
-            format!("Connection failed: {e}")
-        })?;
+    let response = http::send_idempotent(
+        state
+            .http
+            .get(format!("{server_url}/api/pages"))
+            .header("Authorization", format!("Bearer {token}")),
+    )
+    .await?;
 
     if !response.status().is_success() {
         let status = response.status();
         error!("Failed to fetch platform pages: {}", status);
-        return Err(format!("Failed to fetch platform pages: {status}"));
+        return Err(http::status_error("Failed to fetch platform pages", status));
     }
 
     let pages: Vec<PageListItem> = response
@@ -116,21 +114,18 @@ pub async fn get_platform_page(state: State<'_, AppState>, slug: String) -> Resu
 
     debug!("Fetching platform page: {}", slug);
 
-    let response = state
-        .http
-        .get(format!("{server_url}/api/pages/by-slug/{slug}"))
-        .header("Authorization", format!("Bearer {token}"))
-        .send()
-        .await
-        .map_err(|e| {
-            error!("Failed to fetch platform page: {}", e);
-            format!("Connection failed: {e}")
-        })?;
+    let response = http::send_idempotent(
+        state
+            .http
+            .get(format!("{server_url}/api/pages/by-slug/{slug}"))
+            .header("Authorization", format!("Bearer {token}")),
+    )
+    .await?;
 
     if !response.status().is_success() {
         let status = response.status();
         error!("Failed to fetch platform page: {}", status);
-        return Err(format!("Failed to fetch platform page: {status}"));
+        return Err(http::status_error("Failed to fetch platform page", status));
     }
 
     let page: Page = response
@@ -172,28 +167,25 @@ pub async fn create_platform_page(
 
     debug!("Creating platform page: {}", title);
 
-    let response = state
-        .http
-        .post(format!("{server_url}/api/pages"))
-        .header("Authorization", format!("Bearer {token}"))
-        .json(&serde_json::json!({
-            "title": title,
-            "slug": slug,
-            "content": content,
-            "requires_acceptance": requires_acceptance,
-        }))
-        .send()
-        .await
-        .map_err(|e| {
-            error!("Failed to create platform page: {}", e);
-            format!("Connection failed: {e}")
-        })?;
+    let response = http::send(
+        state
+            .http
+            .post(format!("{server_url}/api/pages"))
+            .header("Authorization", format!("Bearer {token}"))
+            .json(&serde_json::json!({
+                "title": title,
+                "slug": slug,
+                "content": content,
+                "requires_acceptance": requires_acceptance,
+            })),
+    )
+    .await?;
 
     if !response.status().is_success() {
         let status = response.status();
         let body = response.text().await.unwrap_or_default();
         error!("Failed to create platform page: {} - {}", status, body);
-        return Err(format!("Failed to create platform page: {status}"));
+        return Err(http::status_error("Failed to create platform page", status));
     }
 
     let page: Page = response
@@ -237,28 +229,25 @@ pub async fn update_platform_page(
 
     debug!("Updating platform page: {}", page_id);
 
-    let response = state
-        .http
-        .patch(format!("{server_url}/api/pages/{page_id}"))
-        .header("Authorization", format!("Bearer {token}"))
-        .json(&serde_json::json!({
-            "title": title,
-            "slug": slug,
-            "content": content,
-            "requires_acceptance": requires_acceptance,
-        }))
-        .send()
-        .await
-        .map_err(|e| {
-            error!("Failed to update platform page: {}", e);
-            format!("Connection failed: {e}")
-        })?;
+    let response = http::send(
+        state
+            .http
+            .patch(format!("{server_url}/api/pages/{page_id}"))
+            .header("Authorization", format!("Bearer {token}"))
+            .json(&serde_json::json!({
+                "title": title,
+                "slug": slug,
+                "content": content,
+                "requires_acceptance": requires_acceptance,
+            })),
+    )
+    .await?;
 
     if !response.status().is_success() {
         let status = response.status();
         let body = response.text().await.unwrap_or_default();
         error!("Failed to update platform page: {} - {}", status, body);
-        return Err(format!("Failed to update platform page: {status}"));
+        return Err(http::status_error("Failed to update platform page", status));
     }
 
     let page: Page = response
@@ -288,21 +277,18 @@ pub async fn delete_platform_page(
 
     debug!("Deleting platform page: {}", page_id);
 
-    let response = state
-        .http
-        .delete(format!("{server_url}/api/pages/{page_id}"))
-        .header("Authorization", format!("Bearer {token}"))
-        .send()
-        .await
-        .map_err(|e| {
-            error!("Failed to delete platform page: {}", e);
-            format!("Connection failed: {e}")
-        })?;
+    let response = http::send(
+        state
+            .http
+            .delete(format!("{server_url}/api/pages/{page_id}"))
+            .header("Authorization", format!("Bearer {token}")),
+    )
+    .await?;
 
     if !response.status().is_success() {
         let status = response.status();
         error!("Failed to delete platform page: {}", status);
-        return Err(format!("Failed to delete platform page: {status}"));
+        return Err(http::status_error("Failed to delete platform page", status));
     }
 
     debug!("Deleted platform page: {}", page_id);
@@ -325,24 +311,24 @@ pub async fn reorder_platform_pages(
 
     debug!("Reordering platform pages");
 
-    let response = state
-        .http
-        .post(format!("{server_url}/api/pages/reorder"))
-        .header("Authorization", format!("Bearer {token}"))
-        .json(&serde_json::json!({
-            "page_ids": page_ids,
-        }))
-        .send()
-        .await
-        .map_err(|e| {
-            error!("Failed to reorder platform pages: {}", e);
-            format!("Connection failed: {e}")
-        })?;
+    let response = http::send(
+        state
+            .http
+            .post(format!("{server_url}/api/pages/reorder"))
+            .header("Authorization", format!("Bearer {token}"))
+            .json(&serde_json::json!({
+                "page_ids": page_ids,
+            })),
+    )
+    .await?;
 
     if !response.status().is_success() {
         let status = response.status();
         error!("Failed to reorder platform pages: {}", status);
-        return Err(format!("Failed to reorder platform pages: {status}"));
+        return Err(http::status_error(
+            "Failed to reorder platform pages",
+            status,
+        ));
     }
 
     debug!("Reordered platform pages");
@@ -371,21 +357,18 @@ pub async fn list_guild_pages(
 
     debug!("Fetching guild pages for: {}", guild_id);
 
-    let response = state
-        .http
-        .get(format!("{server_url}/api/guilds/{guild_id}/pages"))
-        .header("Authorization", format!("Bearer {token}"))
-        .send()
-        .await
-        .map_err(|e| {
-            error!("Failed to fetch guild pages: {}", e);
-            format!("Connection failed: {e}")
-        })?;
+    let response = http::send_idempotent(
+        state
+            .http
+            .get(format!("{server_url}/api/guilds/{guild_id}/pages"))
+            .header("Authorization", format!("Bearer {token}")),
+    )
+    .await?;
 
     if !response.status().is_success() {
         let status = response.status();
         error!("Failed to fetch guild pages: {}", status);
-        return Err(format!("Failed to fetch guild pages: {status}"));
+        return Err(http::status_error("Failed to fetch guild pages", status));
     }
 
     let pages: Vec<PageListItem> = response
@@ -417,23 +400,20 @@ pub async fn get_guild_page(
 
     debug!("Fetching guild page: {}/{}", guild_id, slug);
 
-    let response = state
-        .http
-        .get(format!(
-            "{server_url}/api/guilds/{guild_id}/pages/by-slug/{slug}"
-        ))
-        .header("Authorization", format!("Bearer {token}"))
-        .send()
-        .await
-        .map_err(|e| {
-            error!("Failed to fetch guild page: {}", e);
-            format!("Connection failed: {e}")
-        })?;
+    let response = http::send_idempotent(
+        state
+            .http
+            .get(format!(
+                "{server_url}/api/guilds/{guild_id}/pages/by-slug/{slug}"
+            ))
+            .header("Authorization", format!("Bearer {token}")),
+    )
+    .await?;
 
     if !response.status().is_success() {
         let status = response.status();
         error!("Failed to fetch guild page: {}", status);
-        return Err(format!("Failed to fetch guild page: {status}"));
+        return Err(http::status_error("Failed to fetch guild page", status));
     }
 
     let page: Page = response
@@ -476,29 +456,26 @@ pub async fn create_guild_page(
         ));
     }
 
-    let response = state
-        .http
-        .post(format!("{server_url}/api/guilds/{guild_id}/pages"))
-        .header("Authorization", format!("Bearer {token}"))
-        .json(&serde_json::json!({
-            "title": title,
-            "slug": slug,
-            "content": content,
-            "requires_acceptance": requires_acceptance,
-            "category_id": category_id,
-        }))
-        .send()
-        .await
-        .map_err(|e| {
-            error!("Failed to create guild page: {}", e);
-            format!("Connection failed: {e}")
-        })?;
+    let response = http::send(
+        state
+            .http
+            .post(format!("{server_url}/api/guilds/{guild_id}/pages"))
+            .header("Authorization", format!("Bearer {token}"))
+            .json(&serde_json::json!({
+                "title": title,
+                "slug": slug,
+                "content": content,
+                "requires_acceptance": requires_acceptance,
+                "category_id": category_id,
+            })),
+    )
+    .await?;
 
     if !response.status().is_success() {
         let status = response.status();
         let body = response.text().await.unwrap_or_default();
         error!("Failed to create guild page: {} - {}", status, body);
-        return Err(format!("Failed to create guild page: {status}"));
+        return Err(http::status_error("Failed to create guild page", status));
     }
 
     let page: Page = response
@@ -558,25 +535,22 @@ pub async fn update_guild_page(
         body["category_id"] = serde_json::json!(cat);
     }
 
-    let response = state
-        .http
-        .patch(format!(
-            "{server_url}/api/guilds/{guild_id}/pages/{page_id}"
-        ))
-        .header("Authorization", format!("Bearer {token}"))
-        .json(&body)
-        .send()
-        .await
-        .map_err(|e| {
-            error!("Failed to update guild page: {}", e);
-            format!("Connection failed: {e}")
-        })?;
+    let response = http::send(
+        state
+            .http
+            .patch(format!(
+                "{server_url}/api/guilds/{guild_id}/pages/{page_id}"
+            ))
+            .header("Authorization", format!("Bearer {token}"))
+            .json(&body),
+    )
+    .await?;
 
     if !response.status().is_success() {
         let status = response.status();
         let body = response.text().await.unwrap_or_default();
         error!("Failed to update guild page: {} - {}", status, body);
-        return Err(format!("Failed to update guild page: {status}"));
+        return Err(http::status_error("Failed to update guild page", status));
     }
 
     let page: Page = response
@@ -608,23 +582,20 @@ pub async fn delete_guild_page(
 
     debug!("Deleting guild page: {} in {}", page_id, guild_id);
 
-    let response = state
-        .http
-        .delete(format!(
-            "{server_url}/api/guilds/{guild_id}/pages/{page_id}"
-        ))
-        .header("Authorization", format!("Bearer {token}"))
-        .send()
-        .await
-        .map_err(|e| {
-            error!("Failed to delete guild page: {}", e);
-            format!("Connection failed: {e}")
-        })?;
+    let response = http::send(
+        state
+            .http
+            .delete(format!(
+                "{server_url}/api/guilds/{guild_id}/pages/{page_id}"
+            ))
+            .header("Authorization", format!("Bearer {token}")),
+    )
+    .await?;
 
     if !response.status().is_success() {
         let status = response.status();
         error!("Failed to delete guild page: {}", status);
-        return Err(format!("Failed to delete guild page: {status}"));
+        return Err(http::status_error("Failed to delete guild page", status));
     }
 
     debug!("Deleted guild page: {}", page_id);
@@ -650,24 +621,21 @@ pub async fn reorder_guild_pages(
 
     debug!("Reordering guild pages in: {}", guild_id);
 
-    let response = state
-        .http
-        .post(format!("{server_url}/api/guilds/{guild_id}/pages/reorder"))
-        .header("Authorization", format!("Bearer {token}"))
-        .json(&serde_json::json!({
-            "page_ids": page_ids,
-        }))
-        .send()
-        .await
-        .map_err(|e| {
-            error!("Failed to reorder guild pages: {}", e);
-            format!("Connection failed: {e}")
-        })?;
+    let response = http::send(
+        state
+            .http
+            .post(format!("{server_url}/api/guilds/{guild_id}/pages/reorder"))
+            .header("Authorization", format!("Bearer {token}"))
+            .json(&serde_json::json!({
+                "page_ids": page_ids,
+            })),
+    )
+    .await?;
 
     if !response.status().is_success() {
         let status = response.status();
         error!("Failed to reorder guild pages: {}", status);
-        return Err(format!("Failed to reorder guild pages: {status}"));
+        return Err(http::status_error("Failed to reorder guild pages", status));
     }
 
     debug!("Reordered guild pages in: {}", guild_id);
@@ -693,21 +661,18 @@ pub async fn accept_page(state: State<'_, AppState>, page_id: String) -> Result<
 
     debug!("Accepting page: {}", page_id);
 
-    let response = state
-        .http
-        .post(format!("{server_url}/api/pages/{page_id}/accept"))
-        .header("Authorization", format!("Bearer {token}"))
-        .send()
-        .await
-        .map_err(|e| {
-            error!("Failed to accept page: {}", e);
-            format!("Connection failed: {e}")
-        })?;
+    let response = http::send(
+        state
+            .http
+            .post(format!("{server_url}/api/pages/{page_id}/accept"))
+            .header("Authorization", format!("Bearer {token}")),
+    )
+    .await?;
 
     if !response.status().is_success() {
         let status = response.status();
         error!("Failed to accept page: {}", status);
-        return Err(format!("Failed to accept page: {status}"));
+        return Err(http::status_error("Failed to accept page", status));
     }
 
     debug!("Accepted page: {}", page_id);
@@ -729,21 +694,21 @@ pub async fn get_pending_acceptance(
 
     debug!("Fetching pending acceptance pages");
 
-    let response = state
-        .http
-        .get(format!("{server_url}/api/pages/pending-acceptance"))
-        .header("Authorization", format!("Bearer {token}"))
-        .send()
-        .await
-        .map_err(|e| {
-            error!("Failed to fetch pending acceptance: {}", e);
-            format!("Connection failed: {e}")
-        })?;
+    let response = http::send_idempotent(
+        state
+            .http
+            .get(format!("{server_url}/api/pages/pending-acceptance"))
+            .header("Authorization", format!("Bearer {token}")),
+    )
+    .await?;
 
     if !response.status().is_success() {
         let status = response.status();
         error!("Failed to fetch pending acceptance: {}", status);
-        return Err(format!("Failed to fetch pending acceptance: {status}"));
+        return Err(http::status_error(
+            "Failed to fetch pending acceptance",
+            status,
+        ));
     }
 
     let pages: Vec<PageListItem> = response
@@ -807,23 +772,20 @@ pub async fn list_page_revisions(
         page_id, guild_id
     );
 
-    let response = state
-        .http
-        .get(format!(
-            "{server_url}/api/guilds/{guild_id}/pages/{page_id}/revisions"
-        ))
-        .header("Authorization", format!("Bearer {token}"))
-        .send()
-        .await
-        .map_err(|e| {
-            error!("Failed to fetch page revisions: {}", e);
-            format!("Connection failed: {e}")
-        })?;
+    let response = http::send_idempotent(
+        state
+            .http
+            .get(format!(
+                "{server_url}/api/guilds/{guild_id}/pages/{page_id}/revisions"
+            ))
+            .header("Authorization", format!("Bearer {token}")),
+    )
+    .await?;
 
     if !response.status().is_success() {
         let status = response.status();
         error!("Failed to fetch page revisions: {}", status);
-        return Err(format!("Failed to fetch page revisions: {status}"));
+        return Err(http::status_error("Failed to fetch page revisions", status));
     }
 
     let revisions: Vec<RevisionListItem> = response
@@ -863,23 +825,20 @@ pub async fn get_page_revision(
         revision_number, page_id, guild_id
     );
 
-    let response = state
-        .http
-        .get(format!(
-            "{server_url}/api/guilds/{guild_id}/pages/{page_id}/revisions/{revision_number}"
-        ))
-        .header("Authorization", format!("Bearer {token}"))
-        .send()
-        .await
-        .map_err(|e| {
-            error!("Failed to fetch page revision: {}", e);
-            format!("Connection failed: {e}")
-        })?;
+    let response = http::send_idempotent(
+        state
+            .http
+            .get(format!(
+                "{server_url}/api/guilds/{guild_id}/pages/{page_id}/revisions/{revision_number}"
+            ))
+            .header("Authorization", format!("Bearer {token}")),
+    )
+    .await?;
 
     if !response.status().is_success() {
         let status = response.status();
         error!("Failed to fetch page revision: {}", status);
-        return Err(format!("Failed to fetch page revision: {status}"));
+        return Err(http::status_error("Failed to fetch page revision", status));
     }
 
     let revision: PageRevision = response
@@ -915,24 +874,24 @@ pub async fn restore_page_revision(
         page_id, revision_number, guild_id
     );
 
-    let response = state
-        .http
-        .post(format!(
-            "{server_url}/api/guilds/{guild_id}/pages/{page_id}/revisions/{revision_number}/restore"
-        ))
-        .header("Authorization", format!("Bearer {token}"))
-        .send()
-        .await
-        .map_err(|e| {
-            error!("Failed to restore page revision: {}", e);
-            format!("Connection failed: {e}")
-        })?;
+    let response = http::send(
+        state
+            .http
+            .post(format!(
+                "{server_url}/api/guilds/{guild_id}/pages/{page_id}/revisions/{revision_number}/restore"
+            ))
+            .header("Authorization", format!("Bearer {token}"))
+    )
+    .await?;
 
     if !response.status().is_success() {
         let status = response.status();
         let body = response.text().await.unwrap_or_default();
         error!("Failed to restore page revision: {} - {}", status, body);
-        return Err(format!("Failed to restore page revision: {status}"));
+        return Err(http::status_error(
+            "Failed to restore page revision",
+            status,
+        ));
     }
 
     let page: Page = response
@@ -976,23 +935,23 @@ pub async fn list_page_categories(
 
     debug!("Fetching page categories for guild: {}", guild_id);
 
-    let response = state
-        .http
-        .get(format!(
-            "{server_url}/api/guilds/{guild_id}/page-categories"
-        ))
-        .header("Authorization", format!("Bearer {token}"))
-        .send()
-        .await
-        .map_err(|e| {
-            error!("Failed to fetch page categories: {}", e);
-            format!("Connection failed: {e}")
-        })?;
+    let response = http::send_idempotent(
+        state
+            .http
+            .get(format!(
+                "{server_url}/api/guilds/{guild_id}/page-categories"
+            ))
+            .header("Authorization", format!("Bearer {token}")),
+    )
+    .await?;
 
     if !response.status().is_success() {
         let status = response.status();
         error!("Failed to fetch page categories: {}", status);
-        return Err(format!("Failed to fetch page categories: {status}"));
+        return Err(http::status_error(
+            "Failed to fetch page categories",
+            status,
+        ));
     }
 
     let categories: Vec<PageCategory> = response
@@ -1027,27 +986,24 @@ pub async fn create_page_category(
 
     debug!("Creating page category '{}' in guild: {}", name, guild_id);
 
-    let response = state
-        .http
-        .post(format!(
-            "{server_url}/api/guilds/{guild_id}/page-categories"
-        ))
-        .header("Authorization", format!("Bearer {token}"))
-        .json(&serde_json::json!({
-            "name": name,
-        }))
-        .send()
-        .await
-        .map_err(|e| {
-            error!("Failed to create page category: {}", e);
-            format!("Connection failed: {e}")
-        })?;
+    let response = http::send(
+        state
+            .http
+            .post(format!(
+                "{server_url}/api/guilds/{guild_id}/page-categories"
+            ))
+            .header("Authorization", format!("Bearer {token}"))
+            .json(&serde_json::json!({
+                "name": name,
+            })),
+    )
+    .await?;
 
     if !response.status().is_success() {
         let status = response.status();
         let body = response.text().await.unwrap_or_default();
         error!("Failed to create page category: {} - {}", status, body);
-        return Err(format!("Failed to create page category: {status}"));
+        return Err(http::status_error("Failed to create page category", status));
     }
 
     let category: PageCategory = response
@@ -1083,27 +1039,24 @@ pub async fn update_page_category(
         category_id, guild_id
     );
 
-    let response = state
-        .http
-        .patch(format!(
-            "{server_url}/api/guilds/{guild_id}/page-categories/{category_id}"
-        ))
-        .header("Authorization", format!("Bearer {token}"))
-        .json(&serde_json::json!({
-            "name": name,
-        }))
-        .send()
-        .await
-        .map_err(|e| {
-            error!("Failed to update page category: {}", e);
-            format!("Connection failed: {e}")
-        })?;
+    let response = http::send(
+        state
+            .http
+            .patch(format!(
+                "{server_url}/api/guilds/{guild_id}/page-categories/{category_id}"
+            ))
+            .header("Authorization", format!("Bearer {token}"))
+            .json(&serde_json::json!({
+                "name": name,
+            })),
+    )
+    .await?;
 
     if !response.status().is_success() {
         let status = response.status();
         let body = response.text().await.unwrap_or_default();
         error!("Failed to update page category: {} - {}", status, body);
-        return Err(format!("Failed to update page category: {status}"));
+        return Err(http::status_error("Failed to update page category", status));
     }
 
     let category: PageCategory = response
@@ -1138,23 +1091,20 @@ pub async fn delete_page_category(
         category_id, guild_id
     );
 
-    let response = state
-        .http
-        .delete(format!(
-            "{server_url}/api/guilds/{guild_id}/page-categories/{category_id}"
-        ))
-        .header("Authorization", format!("Bearer {token}"))
-        .send()
-        .await
-        .map_err(|e| {
-            error!("Failed to delete page category: {}", e);
-            format!("Connection failed: {e}")
-        })?;
+    let response = http::send(
+        state
+            .http
+            .delete(format!(
+                "{server_url}/api/guilds/{guild_id}/page-categories/{category_id}"
+            ))
+            .header("Authorization", format!("Bearer {token}")),
+    )
+    .await?;
 
     if !response.status().is_success() {
         let status = response.status();
         error!("Failed to delete page category: {}", status);
-        return Err(format!("Failed to delete page category: {status}"));
+        return Err(http::status_error("Failed to delete page category", status));
     }
 
     debug!("Deleted page category: {}", category_id);
@@ -1180,26 +1130,26 @@ pub async fn reorder_page_categories(
 
     debug!("Reordering page categories in guild: {}", guild_id);
 
-    let response = state
-        .http
-        .post(format!(
-            "{server_url}/api/guilds/{guild_id}/page-categories/reorder"
-        ))
-        .header("Authorization", format!("Bearer {token}"))
-        .json(&serde_json::json!({
-            "category_ids": category_ids,
-        }))
-        .send()
-        .await
-        .map_err(|e| {
-            error!("Failed to reorder page categories: {}", e);
-            format!("Connection failed: {e}")
-        })?;
+    let response = http::send(
+        state
+            .http
+            .post(format!(
+                "{server_url}/api/guilds/{guild_id}/page-categories/reorder"
+            ))
+            .header("Authorization", format!("Bearer {token}"))
+            .json(&serde_json::json!({
+                "category_ids": category_ids,
+            })),
+    )
+    .await?;
 
     if !response.status().is_success() {
         let status = response.status();
         error!("Failed to reorder page categories: {}", status);
-        return Err(format!("Failed to reorder page categories: {status}"));
+        return Err(http::status_error(
+            "Failed to reorder page categories",
+            status,
+        ));
     }
 
     debug!("Reordered page categories in guild: {}", guild_id);
@@ -6,6 +6,7 @@ use serde::{Deserialize, Serialize};
 use tauri::{command, State};
 use tracing::{debug, error};
 
+use crate::network::http;
 use crate::AppState;
 
 /// A pin item.
@@ -51,21 +52,18 @@ pub async fn fetch_pins(state: State<'_, AppState>) -> Result<Vec<Pin>, String>
 
     debug!("Fetching pins from server");
 
-    let response = state
-        .http
-        .get(format!("{server_url}/api/me/pins"))
-        .header("Authorization", format!("Bearer {token}"))
-        .send()
-        .await
-        .map_err(|e| {
-            error!("Failed to fetch pins: {}", e);
-            format!("Connection failed: {e}")
-        })?;
+    let response = http::send_idempotent(
+        state
+            .http
+            .get(format!("{server_url}/api/me/pins"))
+            .header("Authorization", format!("Bearer {token}")),
+    )
+    .await?;
 
     if !response.status().is_success() {
         let status = response.status();
         error!("Failed to fetch pins: {}", status);
-        return Err(format!("Failed to fetch pins: {status}"));
+        return Err(http::status_error("Failed to fetch pins", status));
     }
 
     let pins: Vec<Pin> = response
@@ -93,23 +91,20 @@ pub async fn create_pin(
 
     debug!("Creating pin: type={}", request.pin_type);
 
-    let response = state
-        .http
-        .post(format!("{server_url}/api/me/pins"))
-        .header("Authorization", format!("Bearer {token}"))
-        .json(&request)
-        .send()
-        .await
-        .map_err(|e| {
-            error!("Failed to create pin: {}", e);
-            format!("Connection failed: {e}")
-        })?;
+    let response = http::send(
+        state
+            .http
+            .post(format!("{server_url}/api/me/pins"))
+            .header("Authorization", format!("Bearer {token}"))
+            .json(&request),
+    )
+    .await?;
 
     if !response.status().is_success() {
         let status = response.status();
         let body = response.text().await.unwrap_or_default();
         error!("Failed to create pin: {} - {}", status, body);
-        return Err(format!("Failed to create pin: {status}"));
+        return Err(http::status_error("Failed to create pin", status));
     }
 
     let pin: Pin = response
@@ -138,23 +133,20 @@ pub async fn update_pin(
 
     debug!("Updating pin: id={}", pin_id);
 
-    let response = state
-        .http
-        .put(format!("{server_url}/api/me/pins/{pin_id}"))
-        .header("Authorization", format!("Bearer {token}"))
-        .json(&request)
-        .send()
-        .await
-        .map_err(|e| {
-            error!("Failed to update pin: {}", e);
-            format!("Connection failed: {e}")
-        })?;
+    let response = http::send(
+        state
+            .http
+            .put(format!("{server_url}/api/me/pins/{pin_id}"))
+            .header("Authorization", format!("Bearer {token}"))
+            .json(&request),
+    )
+    .await?;
 
     if !response.status().is_success() {
         let status = response.status();
         let body = response.text().await.unwrap_or_default();
         error!("Failed to update pin: {} - {}", status, body);
-        return Err(format!("Failed to update pin: {status}"));
+        return Err(http::status_error("Failed to update pin", status));
     }
 
     let pin: Pin = response
@@ -179,21 +171,18 @@ pub async fn delete_pin(state: State<'_, AppState>, pin_id: String) -> Result<()
 
     debug!("Deleting pin: id={}", pin_id);
 
-    let response = state
-        .http
-        .delete(format!("{server_url}/api/me/pins/{pin_id}"))
-        .header("Authorization", format!("Bearer {token}"))
-        .send()
-        .await
-        .map_err(|e| {
-            error!("Failed to delete pin: {}", e);
-            format!("Connection failed: {e}")
-        })?;
+    let response = http::send(
+        state
+            .http
+            .delete(format!("{server_url}/api/me/pins/{pin_id}"))
+            .header("Authorization", format!("Bearer {token}")),
+    )
+    .await?;
 
     if !response.status().is_success() {
         let status = response.status();
         error!("Failed to delete pin: {}", status);
-        return Err(format!("Failed to delete pin: {status}"));
+        return Err(http::status_error("Failed to delete pin", status));
     }
 
     debug!("Pin deleted: id={}", pin_id);
@@ -213,22 +202,19 @@ pub async fn reorder_pins(state: State<'_, AppState>, pin_ids: Vec<String>) -> R
 
     debug!("Reordering {} pins", pin_ids.len());
 
-    let response = state
-        .http
-        .put(format!("{server_url}/api/me/pins/reorder"))
-        .header("Authorization", format!("Bearer {token}"))
-        .json(&serde_json::json!({ "pin_ids": pin_ids }))
-        .send()
-        .await
-        .map_err(|e| {
-            error!("Failed to reorder pins: {}", e);
-            format!("Connection failed: {e}")
-        })?;
+    let response = http::send(
+        state
+            .http
+            .put(format!("{server_url}/api/me/pins/reorder"))
+            .header("Authorization", format!("Bearer {token}"))
+            .json(&serde_json::json!({ "pin_ids": pin_ids })),
+    )
+    .await?;
 
     if !response.status().is_success() {
         let status = response.status();
         error!("Failed to reorder pins: {}", status);
-        return Err(format!("Failed to reorder pins: {status}"));
+        return Err(http::status_error("Failed to reorder pins", status));
     }
 
     debug!("Pins reordered successfully");
@@ -5,6 +5,7 @@
 use tauri::{command, State};
 use tracing::{debug, error};
 
+use crate::network::http;
 use crate::AppState;
 
 /// Fetch user preferences from the server.
@@ -22,21 +23,18 @@ pub async fn fetch_preferences(state: State<'_, AppState>) -> Result<serde_json:
 
     debug!("Fetching preferences from server");
 
-    let response = state
-        .http
-        .get(format!("{server_url}/api/me/preferences"))
-        .header("Authorization", format!("Bearer {token}"))
-        .send()
-        .await
-        .map_err(|e| {
-            error!("Failed to fetch preferences: {}", e);
-            format!("Connection failed: {e}")
-        })?;
+    let response = http::send_idempotent(
+        state
+            .http
+            .get(format!("{server_url}/api/me/preferences"))
+            .header("Authorization", format!("Bearer {token}")),
+    )
+    .await?;
 
     if !response.status().is_success() {
         let status = response.status();
         error!("Failed to fetch preferences: {}", status);
-        return Err(format!("Failed to fetch preferences: {status}"));
+        return Err(http::status_error("Failed to fetch preferences", status));
     }
 
     let preferences: serde_json::Value = response
@@ -66,23 +64,20 @@ pub async fn update_preferences(
 
     debug!("Updating preferences on server");
 
-    let response = state
-        .http
-        .put(format!("{server_url}/api/me/preferences"))
-        .header("Authorization", format!("Bearer {token}"))
-        .json(&serde_json::json!({ "preferences": preferences }))
-        .send()
-        .await
-        .map_err(|e| {
-            error!("Failed to update preferences: {}", e);
-            format!("Connection failed: {e}")
-        })?;
+    let response = http::send(
+        state
+            .http
+            .put(format!("{server_url}/api/me/preferences"))
+            .header("Authorization", format!("Bearer {token}"))
+            .json(&serde_json::json!({ "preferences": preferences })),
+    )
+    .await?;
 
     if !response.status().is_success() {
         let status = response.status();
         let body = response.text().await.unwrap_or_default();
         error!("Failed to update preferences: {} - {}", status, body);
-        return Err(format!("Failed to update preferences: {status}"));
+        return Err(http::status_error("Failed to update preferences", status));
     }
 
     let result: serde_json::Value = response
@@ -8,6 +8,7 @@ use serde::{Deserialize, Serialize};
 use tauri::{command, State};
 use tracing::{debug, error};
 
+use crate::network::http;
 use crate::AppState;
 
 // ============================================================================
@@ -97,22 +98,19 @@ pub async fn get_guild_roles(
 
     debug!("Fetching roles for guild {}", guild_id);
 
-    let response = state
-        .http
-        .get(format!("{server_url}/api/guilds/{guild_id}/roles"))
-        .header("Authorization", format!("Bearer {token}"))
-        .send()
-        .await
-        .map_err(|e| {
-            error!("Failed to fetch roles: {}", e);
-            format!("Connection failed: {e}")
-        })?;
+    let response = http::send_idempotent(
+        state
+            .http
+            .get(format!("{server_url}/api/guilds/{guild_id}/roles"))
+            .header("Authorization", format!("Bearer {token}")),
+    )
+    .await?;
 
     if !response.status().is_success() {
         let status = response.status();
         let body = response.text().await.unwrap_or_default();
         error!("Failed to fetch roles: {} - {}", status, body);
-        return Err(format!("Failed to fetch roles: {status}"));
+        return Err(http::status_error("Failed to fetch roles", status));
     }
 
     let roles: Vec<GuildRole> = response
@@ -141,23 +139,20 @@ pub async fn create_guild_role(
 
     debug!("Creating role '{}' in guild {}", request.name, guild_id);
 
-    let response = state
-        .http
-        .post(format!("{server_url}/api/guilds/{guild_id}/roles"))
-        .header("Authorization", format!("Bearer {token}"))
-        .json(&request)
-        .send()
-        .await
-        .map_err(|e| {
-            error!("Failed to create role: {}", e);
-            format!("Connection failed: {e}")
-        })?;
+    let response = http::send(
+        state
+            .http
+            .post(format!("{server_url}/api/guilds/{guild_id}/roles"))
+            .header("Authorization", format!("Bearer {token}"))
+            .json(&request),
+    )
+    .await?;
 
     if !response.status().is_success() {
         let status = response.status();
         let body = response.text().await.unwrap_or_default();
         error!("Failed to create role: {} - {}", status, body);
-        return Err(format!("Failed to create role: {status}"));
+        return Err(http::status_error("Failed to create role", status));
     }
 
     let role: GuildRole = response
@@ -187,25 +182,22 @@ pub async fn update_guild_role(
 
     debug!("Updating role {} in guild {}", role_id, guild_id);
 
-    let response = state
-        .http
-        .patch(format!(
-            "{server_url}/api/guilds/{guild_id}/roles/{role_id}"
-        ))
-        .header("Authorization", format!("Bearer {token}"))
-        .json(&request)
-        .send()
-        .await
-        .map_err(|e| {
-            error!("Failed to update role: {}", e);
-            format!("Connection failed: {e}")
-        })?;
+    let response = http::send(
+        state
+            .http
+            .patch(format!(
+                "{server_url}/api/guilds/{guild_id}/roles/{role_id}"
+            ))
+            .header("Authorization", format!("Bearer {token}"))
+            .json(&request),
+    )
+    .await?;
 
     if !response.status().is_success() {
         let status = response.status();
         let body = response.text().await.unwrap_or_default();
         error!("Failed to update role: {} - {}", status, body);
-        return Err(format!("Failed to update role: {status}"));
+        return Err(http::status_error("Failed to update role", status));
     }
 
     let role: GuildRole = response
@@ -234,24 +226,21 @@ pub async fn delete_guild_role(
 
     debug!("Deleting role {} from guild {}", role_id, guild_id);
 
-    let response = state
-        .http
-        .delete(format!(
-            "{server_url}/api/guilds/{guild_id}/roles/{role_id}"
-        ))
-        .header("Authorization", format!("Bearer {token}"))
-        .send()
-        .await
-        .map_err(|e| {
-            error!("Failed to delete role: {}", e);
-            format!("Connection failed: {e}")
-        })?;
+    let response = http::send(
+        state
+            .http
+            .delete(format!(
+                "{server_url}/api/guilds/{guild_id}/roles/{role_id}"
+            ))
+            .header("Authorization", format!("Bearer {token}")),
+    )
+    .await?;
 
     if !response.status().is_success() {
         let status = response.status();
         let body = response.text().await.unwrap_or_default();
         error!("Failed to delete role: {} - {}", status, body);
-        return Err(format!("Failed to delete role: {status}"));
+        return Err(http::status_error("Failed to delete role", status));
     }
 
     let result: DeleteRoleResponse = response
@@ -284,22 +273,19 @@ pub async fn get_guild_member_roles(
 
     debug!("Fetching member roles for guild {}", guild_id);
 
-    let response = state
-        .http
-        .get(format!("{server_url}/api/guilds/{guild_id}/member-roles"))
-        .header("Authorization", format!("Bearer {token}"))
-        .send()
-        .await
-        .map_err(|e| {
-            error!("Failed to fetch member roles: {}", e);
-            format!("Connection failed: {e}")
-        })?;
+    let response = http::send_idempotent(
+        state
+            .http
+            .get(format!("{server_url}/api/guilds/{guild_id}/member-roles"))
+            .header("Authorization", format!("Bearer {token}")),
+    )
+    .await?;
 
     if !response.status().is_success() {
         let status = response.status();
         let body = response.text().await.unwrap_or_default();
         error!("Failed to fetch member roles: {} - {}", status, body);
-        return Err(format!("Failed to fetch member roles: {status}"));
+        return Err(http::status_error("Failed to fetch member roles", status));
     }
 
     let member_roles: HashMap<String, Vec<String>> = response
@@ -336,24 +322,21 @@ pub async fn assign_member_role(
         role_id, user_id, guild_id
     );
 
-    let response = state
-        .http
-        .post(format!(
-            "{server_url}/api/guilds/{guild_id}/members/{user_id}/roles/{role_id}"
-        ))
-        .header("Authorization", format!("Bearer {token}"))
-        .send()
-        .await
-        .map_err(|e| {
-            error!("Failed to assign role: {}", e);
-            format!("Connection failed: {e}")
-        })?;
+    let response = http::send(
+        state
+            .http
+            .post(format!(
+                "{server_url}/api/guilds/{guild_id}/members/{user_id}/roles/{role_id}"
+            ))
+            .header("Authorization", format!("Bearer {token}")),
+    )
+    .await?;
 
     if !response.status().is_success() {
         let status = response.status();
         let body = response.text().await.unwrap_or_default();
         error!("Failed to assign role: {} - {}", status, body);
-        return Err(format!("Failed to assign role: {status}"));
+        return Err(http::status_error("Failed to assign role", status));
     }
 
     let result: RoleAssignmentResponse = response
@@ -386,24 +369,21 @@ pub async fn remove_member_role(
         role_id, user_id, guild_id
     );
 
-    let response = state
-        .http
-        .delete(format!(
-            "{server_url}/api/guilds/{guild_id}/members/{user_id}/roles/{role_id}"
-        ))
-        .header("Authorization", format!("Bearer {token}"))
-        .send()
-        .await
-        .map_err(|e| {
-            error!("Failed to remove role: {}", e);
-            format!("Connection failed: {e}")
-        })?;
+    let response = http::send(
+        state
+            .http
+            .delete(format!(
+                "{server_url}/api/guilds/{guild_id}/members/{user_id}/roles/{role_id}"
+            ))
+            .header("Authorization", format!("Bearer {token}")),
+    )
+    .await?;
 
     if !response.status().is_success() {
         let status = response.status();
         let body = response.text().await.unwrap_or_default();
         error!("Failed to remove role: {} - {}", status, body);
-        return Err(format!("Failed to remove role: {status}"));
+        return Err(http::status_error("Failed to remove role", status));
     }
 
     let result: RoleAssignmentResponse = response
@@ -435,22 +415,22 @@ pub async fn get_channel_overrides(
 
     debug!("Fetching overrides for channel {}", channel_id);
 
-    let response = state
-        .http
-        .get(format!("{server_url}/api/channels/{channel_id}/overrides"))
-        .header("Authorization", format!("Bearer {token}"))
-        .send()
-        .await
-        .map_err(|e| {
-            error!("Failed to fetch channel overrides: {}", e);
-            format!("Connection failed: {e}")
-        })?;
+    let response = http::send_idempotent(
+        state
+            .http
+            .get(format!("{server_url}/api/channels/{channel_id}/overrides"))
+            .header("Authorization", format!("Bearer {token}")),
+    )
+    .await?;
 
     if !response.status().is_success() {
         let status = response.status();
         let body = response.text().await.unwrap_or_default();
         error!("Failed to fetch channel overrides: {} - {}", status, body);
-        return Err(format!("Failed to fetch channel overrides: {status}"));
+        return Err(http::status_error(
+            "Failed to fetch channel overrides",
+            status,
+        ));
     }
 
     let overrides: Vec<ChannelOverride> = response
@@ -487,25 +467,22 @@ pub async fn set_channel_override(
         role_id, channel_id
     );
 
-    let response = state
-        .http
-        .put(format!(
-            "{server_url}/api/channels/{channel_id}/overrides/{role_id}"
-        ))
-        .header("Authorization", format!("Bearer {token}"))
-        .json(&request)
-        .send()
-        .await
-        .map_err(|e| {
-            error!("Failed to set channel override: {}", e);
-            format!("Connection failed: {e}")
-        })?;
+    let response = http::send(
+        state
+            .http
+            .put(format!(
+                "{server_url}/api/channels/{channel_id}/overrides/{role_id}"
+            ))
+            .header("Authorization", format!("Bearer {token}"))
+            .json(&request),
+    )
+    .await?;
 
     if !response.status().is_success() {
         let status = response.status();
         let body = response.text().await.unwrap_or_default();
         error!("Failed to set channel override: {} - {}", status, body);
-        return Err(format!("Failed to set channel override: {status}"));
+        return Err(http::status_error("Failed to set channel override", status));
     }
 
     let override_result: ChannelOverride = response
@@ -540,24 +517,24 @@ pub async fn delete_channel_override(
         role_id, channel_id
     );
 
-    let response = state
-        .http
-        .delete(format!(
-            "{server_url}/api/channels/{channel_id}/overrides/{role_id}"
-        ))
-        .header("Authorization", format!("Bearer {token}"))
-        .send()
-        .await
-        .map_err(|e| {
-            error!("Failed to delete channel override: {}", e);
-            format!("Connection failed: {e}")
-        })?;
+    let response = http::send(
+        state
+            .http
+            .delete(format!(
+                "{server_url}/api/channels/{channel_id}/overrides/{role_id}"
+            ))
+            .header("Authorization", format!("Bearer {token}")),
+    )
+    .await?;
 
     if !response.status().is_success() {
         let status = response.status();
         let body = response.text().await.unwrap_or_default();
         error!("Failed to delete channel override: {} - {}", status, body);
-        return Err(format!("Failed to delete channel override: {status}"));
+        return Err(http::status_error(
+            "Failed to delete channel override",
+            status,
+        ));
     }
 
     debug!(
@@ -1,4 +1,4 @@
-//! Sound playback commands for notification sounds.
+//! Sound playback commands for notification sounds and spoken announcements.
 
 use std::io::Cursor;
 use std::thread;
@@ -83,6 +83,14 @@ pub fn get_available_sounds() -> Vec<String> {
     ]
 }
 
+/// Speak a short announcement through the OS text-to-speech engine.
+///
+/// Announcements are queued behind any that are still being spoken.
+#[command]
+pub fn speak_event(text: String) -> Result<(), String> {
+    crate::speech::speak(&text)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
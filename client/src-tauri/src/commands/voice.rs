@@ -2,6 +2,7 @@
 //!
 //! Tauri commands for voice chat functionality.
 
+use std::collections::HashMap;
 use std::sync::atomic::{AtomicU16, AtomicU32, Ordering};
 use std::sync::Arc;
 
@@ -21,9 +22,13 @@ use crate::AppState;
 ///
 /// Initializes audio pipeline and WebRTC, sends `VoiceJoin` to server.
 /// Server will respond with `VoiceOffer` which should be handled by `handle_voice_offer`.
+/// `node_rtts` carries the frontend's media node latency probes, if any, and
+/// `noise_suppression` the user's setting, which is reported to the server.
 #[command]
 pub async fn join_voice(
     channel_id: String,
+    node_rtts: Option<HashMap<String, u32>>,
+    noise_suppression: Option<bool>,
     state: State<'_, AppState>,
     app: AppHandle,
 ) -> Result<(), String> {
@@ -40,6 +45,11 @@ pub async fn join_voice(
         return Err("Already in a voice channel. Leave first.".into());
     }
 
+    if let Some(enabled) = noise_suppression {
+        voice_state.audio.set_noise_suppression(enabled);
+    }
+    let noise_suppression = voice_state.audio.is_noise_suppression_enabled();
+
     // Default ICE servers (can be configured from server later)
     let ice_servers = vec![IceServerConfig::default()];
 
@@ -100,7 +110,11 @@ pub async fn join_voice(
     let ws = state.websocket.read().await;
     if let Some(ws_manager) = ws.as_ref() {
         ws_manager
-            .send(ClientEvent::VoiceJoin { channel_id })
+            .send(ClientEvent::VoiceJoin {
+                channel_id,
+                node_rtts: node_rtts.unwrap_or_default(),
+                noise_suppression,
+            })
             .await
             .map_err(|e| format!("Failed to send VoiceJoin: {e}"))?;
     } else {
@@ -306,6 +320,26 @@ pub async fn set_deafen(deafened: bool, state: State<'_, AppState>) -> Result<()
     Ok(())
 }
 
+/// Enable or disable noise suppression on captured audio.
+///
+/// Takes effect immediately; the server learns the setting at the next join.
+#[command]
+pub async fn set_noise_suppression(
+    enabled: bool,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    debug!("Setting noise suppression: {}", enabled);
+
+    state.ensure_voice().await?;
+
+    let voice = state.voice.read().await;
+    let voice_state = voice.as_ref().ok_or("Voice not initialized")?;
+
+    voice_state.audio.set_noise_suppression(enabled);
+
+    Ok(())
+}
+
 /// Start microphone test (local only, no server connection).
 #[command]
 pub async fn start_mic_test(
@@ -83,6 +83,12 @@ pub async fn ws_ping(state: State<'_, AppState>) -> Result<(), String> {
     send_event(&state, ClientEvent::Ping).await
 }
 
+/// Request a server clock reading; the reply arrives as `ws:time_sync`.
+#[command]
+pub async fn ws_time_sync(state: State<'_, AppState>, client_time: i64) -> Result<(), String> {
+    send_event(&state, ClientEvent::TimeSync { client_time }).await
+}
+
 /// Send activity update to server via WebSocket.
 #[command]
 pub async fn ws_send_activity(
@@ -93,6 +99,57 @@ pub async fn ws_send_activity(
     send_event(&state, ClientEvent::SetActivity { activity }).await
 }
 
+/// Set the user's status. Accepts the frontend names (`idle`, `dnd`,
+/// `invisible`) and maps them to the server's.
+#[command]
+pub async fn update_status(state: State<'_, AppState>, status: String) -> Result<(), String> {
+    let status = match status.as_str() {
+        "idle" => "away",
+        "dnd" => "busy",
+        "invisible" | "offline" => "offline",
+        _ => "online",
+    };
+    send_event(
+        &state,
+        ClientEvent::SetStatus {
+            status: status.to_string(),
+        },
+    )
+    .await
+}
+
+/// Tell the server the user is active, so they are not shown as away.
+#[command]
+pub async fn ws_presence_ping(state: State<'_, AppState>) -> Result<(), String> {
+    send_event(&state, ClientEvent::PresencePing).await
+}
+
+/// Request a chunk of a guild's member list; the reply arrives as
+/// `ws:guild_member_chunk`.
+#[command]
+pub async fn ws_request_guild_members(
+    state: State<'_, AppState>,
+    guild_id: String,
+    role_id: Option<String>,
+    online_only: Option<bool>,
+    after: Option<String>,
+    limit: Option<i64>,
+    nonce: Option<String>,
+) -> Result<(), String> {
+    send_event(
+        &state,
+        ClientEvent::RequestGuildMembers {
+            guild_id,
+            role_id,
+            online_only: online_only.unwrap_or(false),
+            after,
+            limit,
+            nonce,
+        },
+    )
+    .await
+}
+
 /// Helper to send an event.
 async fn send_event(state: &State<'_, AppState>, event: ClientEvent) -> Result<(), String> {
     let ws = state.websocket.read().await;
@@ -0,0 +1,167 @@
+//! Stored Credentials
+//!
+//! What is needed to sign back in after a restart — the server URL and the
+//! refresh token — is kept as a single OS keychain entry (Keychain on macOS,
+//! Credential Manager on Windows, Secret Service on Linux). Nothing
+//! credential-bearing is written to the app data directory, and access tokens
+//! only ever live in memory.
+//!
+//! Earlier builds stored one refresh token per server under the `voicechat`
+//! service. [`migrate_legacy`] moves such an entry into the current one.
+
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+
+/// Keychain service name.
+const SERVICE: &str = "kaiku";
+
+/// Keychain account holding the session.
+const ACCOUNT: &str = "session";
+
+/// Service name used by earlier builds.
+const LEGACY_SERVICE: &str = "voicechat";
+
+/// A session that can be resumed after a restart.
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StoredSession {
+    pub server_url: String,
+    pub refresh_token: String,
+}
+
+impl std::fmt::Debug for StoredSession {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StoredSession")
+            .field("server_url", &self.server_url)
+            .field("refresh_token", &"[REDACTED]")
+            .finish()
+    }
+}
+
+fn entry() -> Result<keyring::Entry, keyring::Error> {
+    keyring::Entry::new(SERVICE, ACCOUNT)
+}
+
+fn legacy_entry(server_url: &str) -> Result<keyring::Entry, keyring::Error> {
+    keyring::Entry::new(
+        LEGACY_SERVICE,
+        &format!("refresh_token:{}", server_url.trim_end_matches('/')),
+    )
+}
+
+fn encode(session: &StoredSession) -> String {
+    serde_json::json!({
+        "server_url": session.server_url,
+        "refresh_token": session.refresh_token,
+    })
+    .to_string()
+}
+
+fn decode(secret: &str) -> Option<StoredSession> {
+    serde_json::from_str::<StoredSession>(secret)
+        .ok()
+        .filter(|s| !s.server_url.is_empty() && !s.refresh_token.is_empty())
+}
+
+/// Store the session, replacing any previous one.
+pub fn save(session: &StoredSession) -> Result<(), keyring::Error> {
+    entry()?.set_password(&encode(session))
+}
+
+/// Load the stored session, if any.
+pub fn load() -> Option<StoredSession> {
+    match entry().and_then(|e| e.get_password()) {
+        Ok(secret) => {
+            let session = decode(&secret);
+            if session.is_none() {
+                warn!("Stored session is unreadable, discarding it");
+                clear();
+            }
+            session
+        }
+        Err(keyring::Error::NoEntry) => None,
+        Err(e) => {
+            warn!(error = %e, "Failed to read stored session from keychain");
+            None
+        }
+    }
+}
+
+/// Remove the stored session.
+pub fn clear() {
+    match entry().and_then(|e| e.delete_password()) {
+        Ok(()) | Err(keyring::Error::NoEntry) => {}
+        Err(e) => warn!(error = %e, "Failed to remove stored session from keychain"),
+    }
+}
+
+/// Remove an earlier build's refresh token for `server_url`.
+pub fn clear_legacy(server_url: &str) {
+    match legacy_entry(server_url).and_then(|e| e.delete_password()) {
+        Ok(()) | Err(keyring::Error::NoEntry) => {}
+        Err(e) => warn!(error = %e, "Failed to remove legacy refresh token from keychain"),
+    }
+}
+
+/// Move an earlier build's refresh token for `server_url` into the current
+/// entry and return it as a session.
+///
+/// If the new entry cannot be written, the legacy one is kept and the
+/// session is still returned, so signing in works for this run.
+pub fn migrate_legacy(server_url: &str) -> Option<StoredSession> {
+    let refresh_token = match legacy_entry(server_url).and_then(|e| e.get_password()) {
+        Ok(token) if !token.is_empty() => token,
+        Ok(_) | Err(keyring::Error::NoEntry) => return None,
+        Err(e) => {
+            warn!(error = %e, "Failed to read legacy refresh token from keychain");
+            return None;
+        }
+    };
+
+    let session = StoredSession {
+        server_url: server_url.trim_end_matches('/').to_string(),
+        refresh_token,
+    };
+    match save(&session) {
+        Ok(()) => {
+            clear_legacy(server_url);
+            info!("Migrated stored refresh token to the current keychain entry");
+        }
+        Err(e) => warn!(error = %e, "Failed to migrate legacy refresh token"),
+    }
+    Some(session)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn session() -> StoredSession {
+        StoredSession {
+            server_url: "https://chat.example.com".to_string(),
+            refresh_token: "refresh-secret".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_session_round_trips_through_secret() {
+        assert_eq!(decode(&encode(&session())), Some(session()));
+    }
+
+    #[test]
+    fn test_decode_rejects_unusable_secrets() {
+        assert_eq!(decode("not json"), None);
+        // A bare token, as stored by earlier builds
+        assert_eq!(decode("refresh-secret"), None);
+        assert_eq!(
+            decode(r#"{"server_url":"https://chat.example.com","refresh_token":""}"#),
+            None
+        );
+    }
+
+    #[test]
+    fn test_debug_redacts_refresh_token() {
+        let debug = format!("{:?}", session());
+        assert!(debug.contains("chat.example.com"));
+        assert!(!debug.contains("refresh-secret"));
+    }
+}
@@ -0,0 +1,502 @@
+//! Guild Membership Cache
+//!
+//! Keeps the user's guild list and, per guild, its channels and members so
+//! the frontend can query them without refetching full lists on every
+//! navigation. Lists are fetched over HTTP on first use, kept current from
+//! WebSocket events and saved to disk.
+//!
+//! Lists loaded from disk are refetched once per session and only served
+//! as-is while the server cannot be reached. When a resume fails, events may
+//! have been missed, so every list is refetched on its next query.
+
+use std::collections::{HashMap, HashSet};
+use std::io::ErrorKind;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tauri::{AppHandle, Manager};
+use tracing::warn;
+
+use crate::network::websocket::ServerEvent;
+use crate::AppState;
+
+/// A cached list.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum CacheKey {
+    Guilds,
+    Channels(String),
+    Members(String),
+}
+
+/// Cached lists, as saved to disk.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct CacheData {
+    /// User the lists belong to.
+    user_id: Option<String>,
+    guilds: Option<Vec<Value>>,
+    channels: HashMap<String, Vec<Value>>,
+    members: HashMap<String, Vec<Value>>,
+}
+
+#[derive(Debug, Default)]
+struct Inner {
+    data: CacheData,
+    /// Lists fetched from the server during this session.
+    synced: HashSet<CacheKey>,
+    /// Bumped on every change, so an older snapshot never overwrites a newer one.
+    generation: u64,
+}
+
+/// Guilds, channels and members of the signed-in user.
+#[derive(Debug, Default)]
+pub struct GuildCache {
+    inner: Mutex<Inner>,
+    path: Option<PathBuf>,
+    /// Generation of the last snapshot written to disk.
+    saved: Arc<Mutex<u64>>,
+}
+
+impl GuildCache {
+    /// Load the cache saved by the last session.
+    pub fn load(app: &AppHandle) -> Self {
+        let path = cache_path(app)
+            .inspect_err(|e| warn!("Guild cache will not be saved: {e}"))
+            .ok();
+        let data = path
+            .as_ref()
+            .and_then(|path| match std::fs::read_to_string(path) {
+                Ok(contents) => serde_json::from_str(&contents)
+                    .inspect_err(|e| warn!("Corrupt guild cache file, starting empty: {e}"))
+                    .ok(),
+                Err(e) if e.kind() == ErrorKind::NotFound => None,
+                Err(e) => {
+                    warn!("Failed to read guild cache file, starting empty: {e}");
+                    None
+                }
+            })
+            .unwrap_or_default();
+
+        Self {
+            inner: Mutex::new(Inner {
+                data,
+                ..Inner::default()
+            }),
+            path,
+            saved: Arc::default(),
+        }
+    }
+
+    /// A list fetched during this session.
+    pub fn get(&self, key: &CacheKey) -> Option<Vec<Value>> {
+        let inner = self.lock();
+        if !inner.synced.contains(key) {
+            return None;
+        }
+        inner.data.list(key).cloned()
+    }
+
+    /// A list as last known, even from a previous session.
+    pub fn get_stale(&self, key: &CacheKey) -> Option<Vec<Value>> {
+        self.lock().data.list(key).cloned()
+    }
+
+    /// Store a list fetched from the server.
+    pub fn store(&self, key: CacheKey, list: Vec<Value>) {
+        let mut inner = self.lock();
+        match &key {
+            CacheKey::Guilds => inner.data.guilds = Some(list),
+            CacheKey::Channels(guild_id) => {
+                inner.data.channels.insert(guild_id.clone(), list);
+            }
+            CacheKey::Members(guild_id) => {
+                inner.data.members.insert(guild_id.clone(), list);
+            }
+        }
+        inner.synced.insert(key);
+        self.changed(inner);
+    }
+
+    /// Refetch the guild list and a guild's lists, or every list, on their
+    /// next query.
+    pub fn invalidate(&self, guild_id: Option<&str>) {
+        let mut inner = self.lock();
+        match guild_id {
+            Some(guild_id) => inner.synced.retain(|key| match key {
+                CacheKey::Guilds => false,
+                CacheKey::Channels(id) | CacheKey::Members(id) => id != guild_id,
+            }),
+            None => inner.synced.clear(),
+        }
+    }
+
+    /// Forget everything (e.g. on logout).
+    pub fn clear(&self) {
+        let mut inner = self.lock();
+        inner.data = CacheData::default();
+        inner.synced.clear();
+        self.changed(inner);
+    }
+
+    /// Keep the cache current with a WebSocket event.
+    pub fn apply(&self, event: &ServerEvent) {
+        let mut inner = self.lock();
+        match event {
+            ServerEvent::Ready { user_id, .. } => {
+                if inner.data.user_id.as_ref() != Some(user_id) {
+                    inner.data = CacheData {
+                        user_id: Some(user_id.clone()),
+                        ..CacheData::default()
+                    };
+                    inner.synced.clear();
+                    self.changed(inner);
+                }
+            }
+            ServerEvent::ResumeFailed { .. } => inner.synced.clear(),
+            _ => {
+                if inner.data.apply(event) {
+                    self.changed(inner);
+                }
+            }
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Inner> {
+        self.inner
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+
+    /// Save a snapshot in the background.
+    fn changed(&self, mut inner: std::sync::MutexGuard<'_, Inner>) {
+        inner.generation += 1;
+        let Some(path) = self.path.clone() else {
+            return;
+        };
+        let generation = inner.generation;
+        let json = match serde_json::to_string(&inner.data) {
+            Ok(json) => json,
+            Err(e) => {
+                warn!("Failed to serialize guild cache: {e}");
+                return;
+            }
+        };
+        drop(inner);
+
+        let saved = self.saved.clone();
+        tauri::async_runtime::spawn_blocking(move || {
+            let mut saved = saved
+                .lock()
+                .unwrap_or_else(std::sync::PoisonError::into_inner);
+            if *saved >= generation {
+                return;
+            }
+            if let Err(e) = std::fs::write(&path, json) {
+                warn!("Failed to write guild cache file: {e}");
+                return;
+            }
+            *saved = generation;
+        });
+    }
+}
+
+impl CacheData {
+    fn list(&self, key: &CacheKey) -> Option<&Vec<Value>> {
+        match key {
+            CacheKey::Guilds => self.guilds.as_ref(),
+            CacheKey::Channels(guild_id) => self.channels.get(guild_id),
+            CacheKey::Members(guild_id) => self.members.get(guild_id),
+        }
+    }
+
+    fn guild_mut(&mut self, guild_id: &str) -> Option<&mut Value> {
+        find_by(self.guilds.as_mut()?, "id", guild_id)
+    }
+
+    fn channel_mut(&mut self, channel_id: &str) -> Option<&mut Value> {
+        self.channels
+            .values_mut()
+            .find_map(|channels| find_by(channels, "id", channel_id))
+    }
+
+    fn member_mut(&mut self, guild_id: &str, user_id: &str) -> Option<&mut Value> {
+        find_by(self.members.get_mut(guild_id)?, "user_id", user_id)
+    }
+
+    /// Apply an event; returns whether anything changed.
+    fn apply(&mut self, event: &ServerEvent) -> bool {
+        match event {
+            ServerEvent::GuildUpdate { guild_id, guild } => self
+                .guild_mut(guild_id)
+                .is_some_and(|cached| merge(cached, guild, false)),
+            ServerEvent::Patch {
+                entity_type,
+                entity_id,
+                diff,
+            } => match entity_type.as_str() {
+                "guild" => self
+                    .guild_mut(entity_id)
+                    .is_some_and(|cached| merge(cached, diff, false)),
+                "channel" => self
+                    .channel_mut(entity_id)
+                    .is_some_and(|cached| merge(cached, diff, false)),
+                "member" => {
+                    let Some(guild_id) = diff.get("guild_id").and_then(Value::as_str) else {
+                        return false;
+                    };
+                    let Some(updates) = diff.get("updates") else {
+                        return false;
+                    };
+                    self.member_mut(guild_id, entity_id)
+                        .is_some_and(|cached| merge(cached, updates, false))
+                }
+                // Profile changes show up in every guild the user shares
+                "user" => self
+                    .members
+                    .values_mut()
+                    .filter_map(|members| find_by(members, "user_id", entity_id))
+                    .fold(false, |changed, cached| {
+                        merge(cached, diff, true) || changed
+                    }),
+                _ => false,
+            },
+            ServerEvent::ChannelPositionsUpdate {
+                guild_id, channels, ..
+            } => {
+                let Some(cached) = self.channels.get_mut(guild_id) else {
+                    return false;
+                };
+                let mut changed = false;
+                for placement in channels {
+                    let Some(id) = placement.get("id").and_then(Value::as_str) else {
+                        continue;
+                    };
+                    if let Some(channel) = find_by(cached, "id", id) {
+                        changed |= merge(channel, &placement_fields(placement), false);
+                    }
+                }
+                changed
+            }
+            ServerEvent::MemberTimeoutUpdate {
+                guild_id,
+                user_id,
+                timeout_until,
+            } => self.member_mut(guild_id, user_id).is_some_and(|cached| {
+                merge(
+                    cached,
+                    &serde_json::json!({ "timeout_until": timeout_until }),
+                    false,
+                )
+            }),
+            ServerEvent::MessageNew {
+                channel_id,
+                message,
+            } => {
+                let author_id = message.pointer("/author/id").and_then(Value::as_str);
+                if author_id.is_some() && author_id == self.user_id.as_deref() {
+                    return false;
+                }
+                let Some(count) = self
+                    .channel_mut(channel_id)
+                    .and_then(|channel| channel.get_mut("unread_count"))
+                else {
+                    return false;
+                };
+                *count = Value::from(count.as_u64().unwrap_or(0) + 1);
+                true
+            }
+            ServerEvent::ChannelRead { channel_id } => {
+                self.channel_mut(channel_id).is_some_and(|cached| {
+                    merge(cached, &serde_json::json!({ "unread_count": 0 }), true)
+                })
+            }
+            ServerEvent::AdminGuildDeleted { guild_id, .. } => {
+                let before = self.guilds.as_ref().map_or(0, Vec::len);
+                if let Some(guilds) = &mut self.guilds {
+                    guilds.retain(|g| g.get("id").and_then(Value::as_str) != Some(guild_id));
+                }
+                let removed = self.guilds.as_ref().map_or(0, Vec::len) != before;
+                let channels = self.channels.remove(guild_id).is_some();
+                let members = self.members.remove(guild_id).is_some();
+                removed || channels || members
+            }
+            _ => false,
+        }
+    }
+}
+
+/// The entry of `list` whose `field` is `id`.
+fn find_by<'a>(list: &'a mut [Value], field: &str, id: &str) -> Option<&'a mut Value> {
+    list.iter_mut()
+        .find(|entry| entry.get(field).and_then(Value::as_str) == Some(id))
+}
+
+/// Position fields of a channel placement; `category_id` is `null` when omitted.
+fn placement_fields(placement: &Value) -> Value {
+    serde_json::json!({
+        "position": placement.get("position").cloned().unwrap_or(Value::Null),
+        "category_id": placement.get("category_id").cloned().unwrap_or(Value::Null),
+    })
+}
+
+/// Copy the fields of `diff` into `target`; returns whether anything changed.
+///
+/// With `existing_only`, fields `target` does not have are skipped.
+fn merge(target: &mut Value, diff: &Value, existing_only: bool) -> bool {
+    let (Some(target), Some(diff)) = (target.as_object_mut(), diff.as_object()) else {
+        return false;
+    };
+    let mut changed = false;
+    for (key, value) in diff {
+        match target.get_mut(key) {
+            Some(current) if current == value => {}
+            Some(current) => {
+                *current = value.clone();
+                changed = true;
+            }
+            None if existing_only => {}
+            None => {
+                target.insert(key.clone(), value.clone());
+                changed = true;
+            }
+        }
+    }
+    changed
+}
+
+/// Path of the cache file.
+fn cache_path(app: &AppHandle) -> Result<PathBuf, String> {
+    let app_data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data dir: {e}"))?;
+
+    std::fs::create_dir_all(&app_data_dir)
+        .map_err(|e| format!("Failed to create app data directory: {e}"))?;
+
+    Ok(app_data_dir.join("guild_cache.json"))
+}
+
+/// Keep the cache current with a received WebSocket event.
+pub fn handle_server_event(app: &AppHandle, event: &ServerEvent) {
+    app.state::<AppState>().guild_cache.apply(event);
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn cache() -> CacheData {
+        CacheData {
+            user_id: Some("me".to_string()),
+            guilds: Some(vec![json!({ "id": "g1", "name": "Guild" })]),
+            channels: HashMap::from([(
+                "g1".to_string(),
+                vec![json!({ "id": "c1", "position": 0, "category_id": null, "unread_count": 0 })],
+            )]),
+            members: HashMap::from([(
+                "g1".to_string(),
+                vec![json!({ "user_id": "u1", "display_name": "Old", "nickname": null })],
+            )]),
+        }
+    }
+
+    fn message_new(author_id: &str) -> ServerEvent {
+        ServerEvent::MessageNew {
+            channel_id: "c1".to_string(),
+            message: json!({ "author": { "id": author_id } }),
+        }
+    }
+
+    #[test]
+    fn test_applies_guild_and_member_changes() {
+        let mut data = cache();
+
+        assert!(data.apply(&ServerEvent::GuildUpdate {
+            guild_id: "g1".to_string(),
+            guild: json!({ "id": "g1", "name": "Renamed" }),
+        }));
+        assert_eq!(data.guilds.as_ref().unwrap()[0]["name"], "Renamed");
+
+        // User patches only touch fields the member entry has
+        assert!(data.apply(&ServerEvent::Patch {
+            entity_type: "user".to_string(),
+            entity_id: "u1".to_string(),
+            diff: json!({ "display_name": "New", "email": "x@example.com" }),
+        }));
+        let member = &data.members["g1"][0];
+        assert_eq!(member["display_name"], "New");
+        assert!(member.get("email").is_none());
+
+        assert!(data.apply(&ServerEvent::Patch {
+            entity_type: "member".to_string(),
+            entity_id: "u1".to_string(),
+            diff: json!({ "guild_id": "g1", "updates": { "nickname": "Nick" } }),
+        }));
+        assert_eq!(data.members["g1"][0]["nickname"], "Nick");
+
+        // Unknown guilds are ignored
+        assert!(!data.apply(&ServerEvent::GuildUpdate {
+            guild_id: "g2".to_string(),
+            guild: json!({ "id": "g2" }),
+        }));
+
+        assert!(data.apply(&ServerEvent::AdminGuildDeleted {
+            guild_id: "g1".to_string(),
+            guild_name: "Renamed".to_string(),
+        }));
+        assert!(data.guilds.as_ref().unwrap().is_empty());
+        assert!(data.channels.is_empty() && data.members.is_empty());
+    }
+
+    #[test]
+    fn test_applies_channel_changes() {
+        let mut data = cache();
+
+        assert!(data.apply(&ServerEvent::ChannelPositionsUpdate {
+            guild_id: "g1".to_string(),
+            channels: vec![json!({ "id": "c1", "position": 3, "category_id": "cat" })],
+            categories: vec![],
+        }));
+        assert_eq!(data.channels["g1"][0]["position"], 3);
+        assert_eq!(data.channels["g1"][0]["category_id"], "cat");
+
+        // Own messages do not count as unread
+        assert!(!data.apply(&message_new("me")));
+        assert!(data.apply(&message_new("u1")));
+        assert!(data.apply(&message_new("u1")));
+        assert_eq!(data.channels["g1"][0]["unread_count"], 2);
+
+        assert!(data.apply(&ServerEvent::ChannelRead {
+            channel_id: "c1".to_string(),
+        }));
+        assert_eq!(data.channels["g1"][0]["unread_count"], 0);
+    }
+
+    #[test]
+    fn test_failed_resume_requires_refetch() {
+        let cache = GuildCache::default();
+        cache.apply(&ServerEvent::Ready {
+            user_id: "me".to_string(),
+            session_id: None,
+        });
+        cache.store(CacheKey::Guilds, vec![json!({ "id": "g1" })]);
+        assert!(cache.get(&CacheKey::Guilds).is_some());
+
+        cache.apply(&ServerEvent::ResumeFailed {
+            reason: "expired".to_string(),
+        });
+        assert!(cache.get(&CacheKey::Guilds).is_none());
+        assert!(cache.get_stale(&CacheKey::Guilds).is_some());
+
+        // Another account never sees the previous user's lists
+        cache.apply(&ServerEvent::Ready {
+            user_id: "other".to_string(),
+            session_id: None,
+        });
+        assert!(cache.get_stale(&CacheKey::Guilds).is_none());
+    }
+}
@@ -5,9 +5,14 @@
 mod audio;
 mod capture;
 mod commands;
+mod credentials;
 mod crypto;
+mod guild_cache;
 mod network;
+mod notifications;
+mod passkey;
 mod presence;
+mod speech;
 mod video;
 mod webrtc;
 
@@ -18,7 +23,9 @@ use commands::clipboard::ClipboardGuard;
 use commands::screen_share::ScreenSharePipeline;
 use commands::settings::UiState;
 use commands::webcam::WebcamPipeline;
+use guild_cache::GuildCache;
 use network::WebSocketManager;
+use notifications::{NotificationSettings, NotificationState};
 use reqwest::Client as HttpClient;
 use serde::{Deserialize, Serialize};
 use tauri::Manager;
@@ -52,6 +59,7 @@ pub fn run() {
 
     tauri::Builder::default()
         .plugin(tauri_plugin_shell::init())
+        .plugin(tauri_plugin_notification::init())
         .setup(|app| {
             // Initialize logging
             tracing_subscriber::fmt()
@@ -63,8 +71,10 @@ pub fn run() {
 
             tracing::info!("Kaiku Client starting");
 
-            // Store app state
-            app.manage(AppState::new());
+            // Store app state, with notification rules and guild lists saved by the last session
+            let notification_settings = notifications::load_settings(app.handle());
+            let guild_cache = GuildCache::load(app.handle());
+            app.manage(AppState::new(notification_settings, guild_cache));
 
             // Store clipboard guard
             app.manage(Arc::new(ClipboardGuard::new()));
@@ -72,6 +82,9 @@ pub fn run() {
             // Start presence polling service
             presence::start_presence_service(app.handle().clone());
 
+            // Watch server reachability for the frontend's offline indicator
+            network::connectivity::start_connectivity_watcher(app.handle().clone());
+
             Ok(())
         })
         .invoke_handler(tauri::generate_handler![
@@ -79,14 +92,25 @@ pub fn run() {
             commands::auth::login,
             commands::auth::logout,
             commands::auth::get_current_user,
+            commands::auth::refresh_session,
             commands::auth::get_auth_info,
             commands::auth::register,
-            commands::auth::oidc_authorize,
+            commands::auth::login_with_oidc,
+            commands::auth::start_device_link,
+            commands::auth::claim_device_link,
             commands::auth::mfa_setup,
             commands::auth::mfa_verify,
             commands::auth::mfa_disable,
             commands::auth::mfa_generate_backup_codes,
             commands::auth::mfa_backup_code_count,
+            commands::auth::passkey_login_start,
+            commands::auth::passkey_login_finish,
+            commands::auth::passkey_list,
+            commands::auth::passkey_register_start,
+            commands::auth::passkey_register_finish,
+            commands::auth::passkey_delete,
+            commands::auth::passkey_create,
+            commands::auth::passkey_get,
             // Chat commands
             commands::chat::get_channels,
             commands::chat::get_messages,
@@ -95,11 +119,17 @@ pub fn run() {
             commands::chat::get_thread_replies,
             commands::chat::send_thread_reply,
             commands::chat::mark_thread_read,
+            // Guild commands
+            commands::guilds::get_guilds,
+            commands::guilds::get_guild_channels,
+            commands::guilds::get_guild_members,
+            commands::guilds::invalidate_guild_cache,
             // Voice commands
             commands::voice::join_voice,
             commands::voice::leave_voice,
             commands::voice::set_mute,
             commands::voice::set_deafen,
+            commands::voice::set_noise_suppression,
             commands::voice::handle_voice_offer,
             commands::voice::handle_voice_ice_candidate,
             commands::voice::start_mic_test,
@@ -133,7 +163,13 @@ pub fn run() {
             commands::websocket::ws_typing,
             commands::websocket::ws_stop_typing,
             commands::websocket::ws_ping,
+            commands::websocket::ws_time_sync,
             commands::websocket::ws_send_activity,
+            commands::websocket::ws_presence_ping,
+            commands::websocket::ws_request_guild_members,
+            commands::websocket::update_status,
+            // Network commands
+            commands::network::get_network_state,
             // Pages commands
             commands::pages::list_platform_pages,
             commands::pages::get_platform_page,
@@ -187,6 +223,9 @@ pub fn run() {
             commands::admin::admin_obs_trends,
             commands::admin::admin_obs_top_routes,
             commands::admin::admin_obs_top_errors,
+            commands::admin::admin_obs_top_consumers,
+            commands::admin::admin_obs_ws_disconnects,
+            commands::admin::admin_obs_report_sla,
             commands::admin::admin_obs_logs,
             commands::admin::admin_obs_traces,
             commands::admin::admin_obs_links,
@@ -219,6 +258,10 @@ pub fn run() {
             // Sound commands
             commands::sound::play_sound,
             commands::sound::get_available_sounds,
+            commands::sound::speak_event,
+            // Notification commands
+            commands::notifications::get_notification_settings,
+            commands::notifications::set_notification_settings,
             // Clipboard commands
             commands::clipboard::secure_copy,
             commands::clipboard::secure_paste,
@@ -236,7 +279,19 @@ pub fn run() {
             // Preferences commands
             commands::preferences::fetch_preferences,
             commands::preferences::update_preferences,
+            // Draft sync commands
+            commands::drafts::fetch_drafts,
+            commands::drafts::save_draft,
             // Pins commands
+            commands::friends::fetch_friends,
+            commands::friends::fetch_pending_friends,
+            commands::friends::fetch_blocked_users,
+            commands::friends::send_friend_request,
+            commands::friends::accept_friend_request,
+            commands::friends::decline_friend_request,
+            commands::friends::remove_friend,
+            commands::friends::block_user,
+            commands::friends::unblock_user,
             commands::pins::fetch_pins,
             commands::pins::create_pin,
             commands::pins::update_pin,
@@ -273,6 +328,8 @@ pub struct User {
     pub avatar_url: Option<String>,
     pub status: UserStatus,
     pub email: Option<String>,
+    #[serde(default)]
+    pub email_verified: bool,
     pub mfa_enabled: bool,
 }
 
@@ -283,6 +340,8 @@ pub struct AuthState {
     pub refresh_token: Option<String>,
     pub user: Option<User>,
     pub server_url: Option<String>,
+    /// Background task keeping the access token fresh.
+    pub refresh_task: Option<tokio::task::AbortHandle>,
 }
 
 /// Voice connection state.
@@ -331,10 +390,14 @@ pub struct AppState {
     pub crypto: Arc<Mutex<Option<crypto::CryptoManager>>>,
     /// Cached UI state (category collapse). Lazy-loaded from disk on first access.
     pub ui_state: Arc<Mutex<Option<UiState>>>,
+    /// Desktop notification rules and channel lookup cache.
+    pub notifications: Arc<NotificationState>,
+    /// Guilds, channels and members kept current from WebSocket events.
+    pub guild_cache: Arc<GuildCache>,
 }
 
 impl AppState {
-    fn new() -> Self {
+    fn new(notification_settings: NotificationSettings, guild_cache: GuildCache) -> Self {
         let http = HttpClient::builder()
             .timeout(std::time::Duration::from_secs(30))
             .build()
@@ -347,6 +410,8 @@ impl AppState {
             voice: Arc::new(RwLock::new(None)),
             crypto: Arc::new(Mutex::new(None)),
             ui_state: Arc::new(Mutex::new(None)),
+            notifications: Arc::new(NotificationState::new(notification_settings)),
+            guild_cache: Arc::new(guild_cache),
         }
     }
 
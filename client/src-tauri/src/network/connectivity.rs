@@ -0,0 +1,102 @@
+//! Connectivity Watcher
+//!
+//! Tracks whether the server is reachable and emits `network-state` to the
+//! frontend when that changes. Requests report their outcome as they finish;
+//! the watcher also probes the server's health endpoint, more often while
+//! offline, so recovery is noticed without user action.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager};
+use tracing::info;
+
+use crate::AppState;
+
+/// Event emitted when reachability changes.
+pub const NETWORK_STATE_EVENT: &str = "network-state";
+
+/// Probe interval while the server is reachable.
+const ONLINE_PROBE_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Probe interval while the server is unreachable.
+const OFFLINE_PROBE_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Timeout for a single health probe.
+const PROBE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Whether the server was reachable at the last check.
+static ONLINE: AtomicBool = AtomicBool::new(true);
+
+/// Handle used to emit events; set when the watcher starts.
+static APP: OnceLock<AppHandle> = OnceLock::new();
+
+/// Payload of the `network-state` event.
+#[derive(Debug, Clone, Serialize)]
+pub struct NetworkState {
+    pub online: bool,
+}
+
+/// Whether the server was reachable at the last check.
+pub fn is_online() -> bool {
+    ONLINE.load(Ordering::SeqCst)
+}
+
+/// Record that a request reached the server.
+pub fn report_reachable() {
+    set_online(true);
+}
+
+/// Record that a request could not reach the server.
+pub fn report_unreachable() {
+    set_online(false);
+}
+
+fn set_online(online: bool) {
+    if ONLINE.swap(online, Ordering::SeqCst) == online {
+        return;
+    }
+
+    info!(online, "Network state changed");
+    if let Some(app) = APP.get() {
+        let _ = app.emit(NETWORK_STATE_EVENT, NetworkState { online });
+    }
+}
+
+/// Start the background connectivity watcher.
+pub fn start_connectivity_watcher(app: AppHandle) {
+    if APP.set(app.clone()).is_err() {
+        return; // Already running
+    }
+
+    tauri::async_runtime::spawn(async move {
+        loop {
+            let delay = if is_online() {
+                ONLINE_PROBE_INTERVAL
+            } else {
+                OFFLINE_PROBE_INTERVAL
+            };
+            tokio::time::sleep(delay).await;
+
+            let state = app.state::<AppState>();
+            // Nothing to probe until the user has picked a server
+            let Some(server_url) = state.server_url().await else {
+                continue;
+            };
+
+            match state
+                .http
+                .get(format!("{server_url}/health"))
+                .timeout(PROBE_TIMEOUT)
+                .send()
+                .await
+            {
+                Ok(_) => report_reachable(),
+                Err(e) if e.is_connect() || e.is_timeout() => report_unreachable(),
+                Err(_) => {}
+            }
+        }
+    });
+}
@@ -0,0 +1,137 @@
+//! HTTP Request Wrapper
+//!
+//! Shared send path for command HTTP calls. Errors use stable prefixes so the
+//! frontend can tell a lost connection (`Offline: ...`) from a failing server
+//! (`Server error: ...`). Idempotent requests are retried a bounded number of
+//! times with exponential backoff.
+
+use std::time::Duration;
+
+use reqwest::{RequestBuilder, Response, StatusCode};
+use tracing::warn;
+
+use super::connectivity;
+
+/// Prefix of errors returned when the server could not be reached.
+pub const OFFLINE_PREFIX: &str = "Offline";
+
+/// Prefix of errors returned for 5xx responses.
+pub const SERVER_ERROR_PREFIX: &str = "Server error";
+
+/// Attempts made for idempotent requests, including the first.
+const MAX_ATTEMPTS: u32 = 3;
+
+/// Delay before the first retry; doubles for each further retry.
+const INITIAL_BACKOFF: Duration = Duration::from_millis(250);
+
+/// Send a request once.
+///
+/// Use for anything that is not safe to repeat (POST, PATCH, DELETE, ...).
+pub async fn send(request: RequestBuilder) -> Result<Response, String> {
+    match request.send().await {
+        Ok(response) => {
+            connectivity::report_reachable();
+            Ok(response)
+        }
+        Err(e) => Err(transport_error(&e)),
+    }
+}
+
+/// Send an idempotent request (GET), retrying transport failures and
+/// gateway errors (502/503/504).
+///
+/// The last response is returned as-is once retries are exhausted, so callers
+/// handle its status like any other.
+pub async fn send_idempotent(request: RequestBuilder) -> Result<Response, String> {
+    let mut attempt = 1;
+    let mut backoff = INITIAL_BACKOFF;
+
+    loop {
+        let Some(current) = request.try_clone() else {
+            // Streaming bodies cannot be replayed
+            return send(request).await;
+        };
+        let retries_left = attempt < MAX_ATTEMPTS;
+
+        match current.send().await {
+            Ok(response) if retries_left && is_retryable_status(response.status()) => {
+                warn!(status = %response.status(), attempt, "Retrying request");
+            }
+            Ok(response) => {
+                connectivity::report_reachable();
+                return Ok(response);
+            }
+            Err(e) if retries_left && is_transient(&e) => {
+                warn!(error = %e, attempt, "Retrying request");
+            }
+            Err(e) => return Err(transport_error(&e)),
+        }
+
+        tokio::time::sleep(backoff).await;
+        backoff *= 2;
+        attempt += 1;
+    }
+}
+
+/// Error message for an unsuccessful response status.
+///
+/// 5xx statuses get the `Server error` prefix.
+pub fn status_error(context: &str, status: StatusCode) -> String {
+    if status.is_server_error() {
+        format!("{SERVER_ERROR_PREFIX}: {context}: {status}")
+    } else {
+        format!("{context}: {status}")
+    }
+}
+
+/// Whether a response status is worth retrying.
+fn is_retryable_status(status: StatusCode) -> bool {
+    matches!(
+        status,
+        StatusCode::BAD_GATEWAY | StatusCode::SERVICE_UNAVAILABLE | StatusCode::GATEWAY_TIMEOUT
+    )
+}
+
+/// Whether a transport error means the server could not be reached.
+fn is_transient(e: &reqwest::Error) -> bool {
+    e.is_connect() || e.is_timeout()
+}
+
+/// Map a transport error to a frontend message, updating connectivity.
+fn transport_error(e: &reqwest::Error) -> String {
+    if is_transient(e) {
+        connectivity::report_unreachable();
+        warn!(error = %e, "Server unreachable");
+        format!("{OFFLINE_PREFIX}: {e}")
+    } else {
+        warn!(error = %e, "Request failed");
+        format!("Request failed: {e}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_status_error_marks_server_errors() {
+        assert_eq!(
+            status_error("Failed to fetch pins", StatusCode::SERVICE_UNAVAILABLE),
+            "Server error: Failed to fetch pins: 503 Service Unavailable"
+        );
+        assert_eq!(
+            status_error("Failed to fetch pins", StatusCode::NOT_FOUND),
+            "Failed to fetch pins: 404 Not Found"
+        );
+    }
+
+    #[test]
+    fn test_only_gateway_errors_are_retried() {
+        assert!(is_retryable_status(StatusCode::BAD_GATEWAY));
+        assert!(is_retryable_status(StatusCode::SERVICE_UNAVAILABLE));
+        assert!(is_retryable_status(StatusCode::GATEWAY_TIMEOUT));
+        assert!(!is_retryable_status(StatusCode::INTERNAL_SERVER_ERROR));
+        assert!(!is_retryable_status(StatusCode::TOO_MANY_REQUESTS));
+        assert!(!is_retryable_status(StatusCode::NOT_FOUND));
+    }
+}
@@ -1,5 +1,7 @@
 //! Network Layer
 
+pub mod connectivity;
+pub mod http;
 pub mod websocket;
 
 pub use websocket::{ClientEvent, ConnectionStatus, WebSocketManager};
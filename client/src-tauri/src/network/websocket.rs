@@ -2,6 +2,7 @@
 //!
 //! Manages real-time connection to the server with automatic reconnection.
 
+use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::Duration;
 
@@ -18,6 +19,9 @@ use tracing::{debug, error, info, warn};
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum ClientEvent {
     Ping,
+    TimeSync {
+        client_time: i64,
+    },
     Subscribe {
         channel_id: String,
     },
@@ -32,6 +36,12 @@ pub enum ClientEvent {
     },
     VoiceJoin {
         channel_id: String,
+        /// Measured RTT in milliseconds to each media node, keyed by node ID.
+        #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+        node_rtts: HashMap<String, u32>,
+        /// Whether captured audio is noise-suppressed, for connection diagnostics.
+        #[serde(default)]
+        noise_suppression: bool,
     },
     VoiceLeave {
         channel_id: String,
@@ -53,6 +63,26 @@ pub enum ClientEvent {
     SetActivity {
         activity: Option<serde_json::Value>,
     },
+    SetStatus {
+        status: String,
+    },
+    PresencePing,
+    RequestGuildMembers {
+        guild_id: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        role_id: Option<String>,
+        online_only: bool,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        after: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        limit: Option<i64>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        nonce: Option<String>,
+    },
+    Resume {
+        session_id: String,
+        last_seq: u64,
+    },
 }
 
 /// Server events received from the server.
@@ -61,14 +91,43 @@ pub enum ClientEvent {
 pub enum ServerEvent {
     Ready {
         user_id: String,
+        #[serde(default)]
+        session_id: Option<String>,
+        #[serde(default)]
+        profile: Option<serde_json::Value>,
+    },
+    Resumed {
+        session_id: String,
+        last_seq: u64,
+        replayed: u64,
+    },
+    ResumeFailed {
+        reason: String,
+    },
+    SessionRevoked {
+        reason: String,
+    },
+    NewLoginNetwork {
+        ip: String,
+        user_agent: Option<String>,
+        method: String,
     },
     Pong,
+    TimeSync {
+        server_time: i64,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        client_time: Option<i64>,
+    },
     Subscribed {
         channel_id: String,
     },
     Unsubscribed {
         channel_id: String,
     },
+    SubscriptionEvicted {
+        channel_id: String,
+        limit: usize,
+    },
     MessageNew {
         channel_id: String,
         message: serde_json::Value,
@@ -83,6 +142,16 @@ pub enum ServerEvent {
         channel_id: String,
         message_id: String,
     },
+    AttachmentProcessed {
+        channel_id: String,
+        message_id: String,
+        attachment: serde_json::Value,
+    },
+    MessageEmbedsUpdate {
+        channel_id: String,
+        message_id: String,
+        embeds: serde_json::Value,
+    },
     TypingStart {
         channel_id: String,
         user_id: String,
@@ -110,10 +179,35 @@ pub enum ServerEvent {
     VoiceUserJoined {
         channel_id: String,
         user_id: String,
+        #[serde(default)]
+        username: String,
+        #[serde(default)]
+        display_name: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        name_pronunciation: Option<String>,
+        #[serde(default)]
+        listener: bool,
+    },
+    VoiceListenerPromoted {
+        channel_id: String,
+        user_id: String,
+        promoted_by: String,
+    },
+    VoiceHandRaised {
+        channel_id: String,
+        user_id: String,
+    },
+    VoiceHandLowered {
+        channel_id: String,
+        user_id: String,
     },
     VoiceUserLeft {
         channel_id: String,
         user_id: String,
+        #[serde(default)]
+        display_name: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        name_pronunciation: Option<String>,
     },
     VoiceUserMuted {
         channel_id: String,
@@ -214,12 +308,76 @@ pub enum ServerEvent {
         packet_loss: f64,
         jitter: f64,
         quality: f64,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        concealment_events: Option<f64>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        jitter_buffer_delay: Option<f64>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        audio_level: Option<f64>,
+    },
+    VoiceQualityWarning {
+        channel_id: String,
+        packet_loss: f64,
+        audio_bitrate: u32,
+    },
+    VoiceQualityRecovered {
+        channel_id: String,
+    },
+    // Guild profile events
+    GuildUpdate {
+        guild_id: String,
+        guild: serde_json::Value,
     },
     // Guild emoji events
+    GuildMemberChunk {
+        guild_id: String,
+        members: Vec<serde_json::Value>,
+        #[serde(default)]
+        next_cursor: Option<String>,
+        #[serde(default)]
+        nonce: Option<String>,
+    },
     GuildEmojiUpdated {
         guild_id: String,
         emojis: Vec<serde_json::Value>,
     },
+    // Guild supporter perk events
+    GuildPerksUpdate {
+        guild_id: String,
+        perks: serde_json::Value,
+    },
+    // Guild role events
+    RoleCreate {
+        guild_id: String,
+        role: serde_json::Value,
+    },
+    RoleUpdate {
+        guild_id: String,
+        role: serde_json::Value,
+    },
+    RoleDelete {
+        guild_id: String,
+        role_id: String,
+    },
+    RolesReorder {
+        guild_id: String,
+        roles: Vec<serde_json::Value>,
+    },
+    ChannelPositionsUpdate {
+        guild_id: String,
+        channels: Vec<serde_json::Value>,
+        categories: Vec<serde_json::Value>,
+    },
+    MemberRolesUpdate {
+        guild_id: String,
+        user_id: String,
+        role_ids: Vec<String>,
+    },
+    MemberTimeoutUpdate {
+        guild_id: String,
+        user_id: String,
+        timeout_until: Option<String>,
+    },
     // Admin delete events
     AdminUserDeleted {
         user_id: String,
@@ -288,6 +446,10 @@ pub enum ServerEvent {
     UserUnblocked {
         user_id: String,
     },
+    RelationshipUpdate {
+        user_id: String,
+        relationship: Option<String>,
+    },
     // Thread events
     ThreadReplyNew {
         channel_id: String,
@@ -305,11 +467,50 @@ pub enum ServerEvent {
         thread_parent_id: String,
         last_read_message_id: Option<String>,
     },
+    MessageHighlight {
+        channel_id: String,
+        message_id: String,
+        keyword: String,
+    },
     // Preferences sync
     PreferencesUpdated {
         preferences: serde_json::Value,
         updated_at: String,
     },
+    // Draft sync
+    DraftUpdate {
+        channel_id: String,
+        content: String,
+        updated_at: String,
+    },
+    // Data export
+    DataExportReady {
+        job_id: String,
+        download_url: String,
+        download_url_expires_at: String,
+        file_size_bytes: i64,
+    },
+    DataExportFailed {
+        job_id: String,
+        error_message: String,
+    },
+    // Discovery review
+    GuildDiscoveryReviewed {
+        guild_id: String,
+        guild_name: String,
+        status: String,
+        reason: Option<String>,
+    },
+    // Guild suspension
+    GuildSuspended {
+        guild_id: String,
+        guild_name: String,
+        reason: String,
+    },
+    GuildUnsuspended {
+        guild_id: String,
+        guild_name: String,
+    },
     // State sync
     Patch {
         entity_type: String,
@@ -318,6 +519,77 @@ pub enum ServerEvent {
     },
 }
 
+/// Resume state carried across reconnects.
+///
+/// Pub/sub events carry a `seq` numbered within the connection's session.
+/// After a reconnect the client sends `resume` before anything else and,
+/// until the server answers, keeps counting the new connection's own session
+/// in case the resume fails.
+#[derive(Debug, Default)]
+struct SessionTracker {
+    /// Session and last received `seq`.
+    current: Option<(String, u64)>,
+    /// The new connection's session while a resume is pending.
+    fresh: Option<(String, u64)>,
+    resuming: bool,
+}
+
+/// The `seq` field of a server message.
+#[derive(Deserialize)]
+struct Sequenced {
+    seq: Option<u64>,
+}
+
+impl SessionTracker {
+    /// The `resume` to send on a new connection, if there is a session.
+    fn resume_event(&mut self) -> Option<ClientEvent> {
+        let (session_id, last_seq) = self.current.clone()?;
+        self.resuming = true;
+        self.fresh = None;
+        Some(ClientEvent::Resume {
+            session_id,
+            last_seq,
+        })
+    }
+
+    /// Track a received message.
+    fn observe(&mut self, event: Option<&ServerEvent>, seq: Option<u64>) {
+        match event {
+            Some(ServerEvent::Ready { session_id, .. }) => {
+                let session = session_id.clone().map(|id| (id, 0));
+                if self.resuming {
+                    self.fresh = session;
+                } else {
+                    self.current = session;
+                }
+            }
+            Some(ServerEvent::Resumed {
+                session_id,
+                last_seq,
+                ..
+            }) => {
+                self.current = Some((session_id.clone(), *last_seq));
+                self.fresh = None;
+                self.resuming = false;
+            }
+            Some(ServerEvent::ResumeFailed { .. }) => {
+                self.current = self.fresh.take();
+                self.resuming = false;
+            }
+            _ => {
+                let session = if self.resuming {
+                    &mut self.fresh
+                } else {
+                    &mut self.current
+                };
+                if let (Some((_, last_seq)), Some(seq)) = (session, seq) {
+                    *last_seq = seq;
+                }
+            }
+        }
+    }
+}
+
 /// Connection status.
 #[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "snake_case")]
@@ -394,6 +666,7 @@ async fn connection_loop(
 ) {
     let mut attempt = 0u32;
     let max_backoff = Duration::from_secs(30);
+    let mut session = SessionTracker::default();
 
     loop {
         // Check for shutdown
@@ -430,6 +703,16 @@ async fn connection_loop(
                 // Split the stream
                 let (mut write, mut read) = ws_stream.split();
 
+                // Resuming must be the first message on the connection
+                if let Some(resume) = session.resume_event() {
+                    if let Ok(json) = serde_json::to_string(&resume) {
+                        debug!("Resuming session");
+                        if let Err(e) = write.send(Message::Text(json.into())).await {
+                            warn!("Failed to send resume: {}", e);
+                        }
+                    }
+                }
+
                 // Handle messages until disconnected
                 loop {
                     tokio::select! {
@@ -437,7 +720,11 @@ async fn connection_loop(
                         msg = read.next() => {
                             match msg {
                                 Some(Ok(Message::Text(text))) => {
-                                    handle_server_message(&app, &text);
+                                    let event = handle_server_message(&app, &text);
+                                    let seq = serde_json::from_str::<Sequenced>(&text)
+                                        .ok()
+                                        .and_then(|s| s.seq);
+                                    session.observe(event.as_ref(), seq);
                                 }
                                 Some(Ok(Message::Ping(data))) => {
                                     if let Err(e) = write.send(Message::Pong(data)).await {
@@ -518,8 +805,8 @@ fn build_ws_url(server_url: &str, token: &str) -> String {
     format!("{}/ws?token={}", base.trim_end_matches('/'), token)
 }
 
-/// Handle a message from the server.
-fn handle_server_message(app: &AppHandle, text: &str) {
+/// Handle a message from the server, returning the parsed event.
+fn handle_server_message(app: &AppHandle, text: &str) -> Option<ServerEvent> {
     match serde_json::from_str::<ServerEvent>(text) {
         Ok(event) => {
             debug!("Received: {:?}", event);
@@ -527,12 +814,20 @@ fn handle_server_message(app: &AppHandle, text: &str) {
             // Emit the event to the frontend
             let event_name = match &event {
                 ServerEvent::Ready { .. } => "ws:ready",
+                ServerEvent::Resumed { .. } => "ws:resumed",
+                ServerEvent::ResumeFailed { .. } => "ws:resume_failed",
+                ServerEvent::SessionRevoked { .. } => "ws:session_revoked",
+                ServerEvent::NewLoginNetwork { .. } => "ws:new_login_network",
                 ServerEvent::Pong => "ws:pong",
+                ServerEvent::TimeSync { .. } => "ws:time_sync",
                 ServerEvent::Subscribed { .. } => "ws:subscribed",
                 ServerEvent::Unsubscribed { .. } => "ws:unsubscribed",
+                ServerEvent::SubscriptionEvicted { .. } => "ws:subscription_evicted",
                 ServerEvent::MessageNew { .. } => "ws:message_new",
                 ServerEvent::MessageEdit { .. } => "ws:message_edit",
                 ServerEvent::MessageDelete { .. } => "ws:message_delete",
+                ServerEvent::AttachmentProcessed { .. } => "ws:attachment_processed",
+                ServerEvent::MessageEmbedsUpdate { .. } => "ws:message_embeds_update",
                 ServerEvent::TypingStart { .. } => "ws:typing_start",
                 ServerEvent::TypingStop { .. } => "ws:typing_stop",
                 ServerEvent::PresenceUpdate { .. } => "ws:presence_update",
@@ -540,6 +835,9 @@ fn handle_server_message(app: &AppHandle, text: &str) {
                 ServerEvent::VoiceOffer { .. } => "ws:voice_offer",
                 ServerEvent::VoiceIceCandidate { .. } => "ws:voice_ice_candidate",
                 ServerEvent::VoiceUserJoined { .. } => "ws:voice_user_joined",
+                ServerEvent::VoiceListenerPromoted { .. } => "ws:voice_listener_promoted",
+                ServerEvent::VoiceHandRaised { .. } => "ws:voice_hand_raised",
+                ServerEvent::VoiceHandLowered { .. } => "ws:voice_hand_lowered",
                 ServerEvent::VoiceUserLeft { .. } => "ws:voice_user_left",
                 ServerEvent::VoiceUserMuted { .. } => "ws:voice_user_muted",
                 ServerEvent::VoiceUserUnmuted { .. } => "ws:voice_user_unmuted",
@@ -566,8 +864,20 @@ fn handle_server_message(app: &AppHandle, text: &str) {
                 ServerEvent::ReactionRemove { .. } => "ws:reaction_remove",
                 // Voice stats
                 ServerEvent::VoiceUserStats { .. } => "ws:voice_user_stats",
+                ServerEvent::VoiceQualityWarning { .. } => "ws:voice_quality_warning",
+                ServerEvent::VoiceQualityRecovered { .. } => "ws:voice_quality_recovered",
+                ServerEvent::GuildUpdate { .. } => "ws:guild_update",
                 // Guild emoji events
+                ServerEvent::GuildMemberChunk { .. } => "ws:guild_member_chunk",
                 ServerEvent::GuildEmojiUpdated { .. } => "ws:guild_emoji_updated",
+                ServerEvent::GuildPerksUpdate { .. } => "ws:guild_perks_update",
+                ServerEvent::RoleCreate { .. } => "ws:role_create",
+                ServerEvent::RoleUpdate { .. } => "ws:role_update",
+                ServerEvent::RoleDelete { .. } => "ws:role_delete",
+                ServerEvent::RolesReorder { .. } => "ws:roles_reorder",
+                ServerEvent::ChannelPositionsUpdate { .. } => "ws:channel_positions_update",
+                ServerEvent::MemberRolesUpdate { .. } => "ws:member_roles_update",
+                ServerEvent::MemberTimeoutUpdate { .. } => "ws:member_timeout_update",
                 // Admin delete events
                 ServerEvent::AdminUserDeleted { .. } => "ws:admin_user_deleted",
                 ServerEvent::AdminGuildDeleted { .. } => "ws:admin_guild_deleted",
@@ -587,12 +897,23 @@ fn handle_server_message(app: &AppHandle, text: &str) {
                 // Block events
                 ServerEvent::UserBlocked { .. } => "ws:user_blocked",
                 ServerEvent::UserUnblocked { .. } => "ws:user_unblocked",
+                ServerEvent::RelationshipUpdate { .. } => "ws:relationship_update",
                 // Thread events
                 ServerEvent::ThreadReplyNew { .. } => "ws:thread_reply_new",
                 ServerEvent::ThreadReplyDelete { .. } => "ws:thread_reply_delete",
                 ServerEvent::ThreadRead { .. } => "ws:thread_read",
+                ServerEvent::MessageHighlight { .. } => "ws:message_highlight",
                 // Preferences sync
                 ServerEvent::PreferencesUpdated { .. } => "ws:preferences_updated",
+                // Draft sync
+                ServerEvent::DraftUpdate { .. } => "ws:draft_update",
+                // Data export
+                ServerEvent::DataExportReady { .. } => "ws:data_export_ready",
+                ServerEvent::DataExportFailed { .. } => "ws:data_export_failed",
+                // Discovery review
+                ServerEvent::GuildDiscoveryReviewed { .. } => "ws:guild_discovery_reviewed",
+                ServerEvent::GuildSuspended { .. } => "ws:guild_suspended",
+                ServerEvent::GuildUnsuspended { .. } => "ws:guild_unsuspended",
                 // State sync
                 ServerEvent::Patch { .. } => "ws:patch",
             };
@@ -600,9 +921,91 @@ fn handle_server_message(app: &AppHandle, text: &str) {
             if let Err(e) = app.emit(event_name, &event) {
                 error!("Failed to emit event: {}", e);
             }
+
+            crate::guild_cache::handle_server_event(app, &event);
+            crate::notifications::handle_server_event(app, &event);
+            crate::speech::handle_server_event(app, &event);
+            Some(event)
         }
         Err(e) => {
             warn!("Failed to parse server message: {} - {}", e, text);
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ready(session_id: &str) -> ServerEvent {
+        ServerEvent::Ready {
+            user_id: "user".to_string(),
+            session_id: Some(session_id.to_string()),
+            profile: None,
+        }
+    }
+
+    #[test]
+    fn test_tracks_last_seq_of_current_session() {
+        let mut session = SessionTracker::default();
+        assert!(session.resume_event().is_none());
+
+        session.observe(Some(&ready("a")), None);
+        session.observe(Some(&ServerEvent::Pong), None);
+        session.observe(None, Some(5));
+        assert_eq!(session.current, Some(("a".to_string(), 5)));
+
+        match session.resume_event() {
+            Some(ClientEvent::Resume {
+                session_id,
+                last_seq,
+            }) => {
+                assert_eq!(session_id, "a");
+                assert_eq!(last_seq, 5);
+            }
+            other => panic!("expected resume, got {other:?}"),
         }
     }
+
+    #[test]
+    fn test_resumed_continues_old_session() {
+        let mut session = SessionTracker::default();
+        session.observe(Some(&ready("a")), None);
+        session.observe(None, Some(2));
+
+        session.resume_event();
+        session.observe(Some(&ready("b")), None);
+        session.observe(None, Some(1));
+        session.observe(None, Some(3)); // replayed from "a"
+        session.observe(
+            Some(&ServerEvent::Resumed {
+                session_id: "a".to_string(),
+                last_seq: 3,
+                replayed: 1,
+            }),
+            None,
+        );
+        session.observe(None, Some(4));
+        assert_eq!(session.current, Some(("a".to_string(), 4)));
+    }
+
+    #[test]
+    fn test_failed_resume_switches_to_new_session() {
+        let mut session = SessionTracker::default();
+        session.observe(Some(&ready("a")), None);
+        session.observe(None, Some(9));
+
+        session.resume_event();
+        session.observe(Some(&ready("b")), None);
+        session.observe(None, Some(1));
+        session.observe(
+            Some(&ServerEvent::ResumeFailed {
+                reason: "too_old".to_string(),
+            }),
+            None,
+        );
+        session.observe(None, Some(2));
+        assert_eq!(session.current, Some(("b".to_string(), 2)));
+    }
 }
@@ -0,0 +1,11 @@
+//! Native Desktop Notifications
+//!
+//! Raises OS notifications for mentions, direct messages, and incoming calls
+//! received over the WebSocket, filtered by per-guild/per-channel mute rules
+//! and do-not-disturb windows.
+
+mod rules;
+mod service;
+
+pub use rules::*;
+pub use service::*;
@@ -0,0 +1,251 @@
+//! Notification settings and the rules that decide whether to notify.
+
+use std::collections::{HashMap, HashSet};
+
+use chrono::{Datelike, NaiveDateTime, Timelike};
+use serde::{Deserialize, Serialize};
+
+/// Per-channel notification level (mirrors the synced `channel_notifications` preference).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChannelLevel {
+    /// Every message.
+    All,
+    /// Only messages that mention the user.
+    #[default]
+    Mentions,
+    /// Nothing.
+    Muted,
+}
+
+/// A recurring do-not-disturb window in local time.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DndWindow {
+    /// Start time, `"HH:MM"`.
+    pub start_time: String,
+    /// End time, `"HH:MM"`. Earlier than `start_time` for overnight windows.
+    pub end_time: String,
+    /// Days the window starts on (0 = Sunday). Empty means every day.
+    #[serde(default)]
+    pub days: Vec<u8>,
+}
+
+/// Parse `"HH:MM"` into minutes since midnight.
+fn parse_minutes(time: &str) -> Option<u32> {
+    let (hours, minutes) = time.split_once(':')?;
+    let (hours, minutes): (u32, u32) = (hours.parse().ok()?, minutes.parse().ok()?);
+    (hours < 24 && minutes < 60).then_some(hours * 60 + minutes)
+}
+
+impl DndWindow {
+    fn applies_on(&self, day: u32) -> bool {
+        self.days.is_empty() || self.days.iter().any(|&d| u32::from(d) == day)
+    }
+
+    /// Whether `now` (local time) falls inside this window.
+    ///
+    /// An overnight window belongs to the day it starts on, so a Friday
+    /// 22:00-07:00 window also covers early Saturday morning.
+    pub fn contains(&self, now: NaiveDateTime) -> bool {
+        let (Some(start), Some(end)) = (
+            parse_minutes(&self.start_time),
+            parse_minutes(&self.end_time),
+        ) else {
+            return false;
+        };
+        let minute = now.hour() * 60 + now.minute();
+        let today = now.weekday().num_days_from_sunday();
+        let yesterday = (today + 6) % 7;
+
+        if start <= end {
+            start <= minute && minute < end && self.applies_on(today)
+        } else {
+            (minute >= start && self.applies_on(today))
+                || (minute < end && self.applies_on(yesterday))
+        }
+    }
+}
+
+/// Desktop notification settings, pushed by the frontend from synced preferences.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NotificationSettings {
+    /// Master switch for native notifications.
+    pub enabled: bool,
+    /// Notify for mentions in guild channels.
+    pub mentions: bool,
+    /// Notify for direct messages.
+    pub direct_messages: bool,
+    /// Notify for incoming calls.
+    pub incoming_calls: bool,
+    /// Guilds whose channels never notify.
+    pub muted_guilds: HashSet<String>,
+    /// Per-channel levels; channels not listed use [`ChannelLevel::Mentions`].
+    pub channel_levels: HashMap<String, ChannelLevel>,
+    /// Recurring windows during which nothing notifies.
+    pub dnd_windows: Vec<DndWindow>,
+    /// Speak voice joins and leaves in the current call (see `speech`).
+    pub announce_voice_presence: bool,
+}
+
+impl Default for NotificationSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            mentions: true,
+            direct_messages: true,
+            incoming_calls: true,
+            muted_guilds: HashSet::new(),
+            channel_levels: HashMap::new(),
+            dnd_windows: Vec::new(),
+            announce_voice_presence: false,
+        }
+    }
+}
+
+/// What happened, as far as the notification rules are concerned.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NotificationKind {
+    /// A message in a guild channel.
+    GuildMessage { mentioned: bool },
+    /// A message in a DM or group DM.
+    DirectMessage,
+    /// Someone started a call in a DM.
+    IncomingCall,
+}
+
+impl NotificationSettings {
+    /// Whether a do-not-disturb window is active at `now` (local time).
+    pub fn in_dnd(&self, now: NaiveDateTime) -> bool {
+        self.dnd_windows.iter().any(|w| w.contains(now))
+    }
+
+    /// Decide whether an event in `channel_id` (of `guild_id`, if any) should notify.
+    pub fn should_notify(
+        &self,
+        kind: NotificationKind,
+        channel_id: &str,
+        guild_id: Option<&str>,
+        now: NaiveDateTime,
+    ) -> bool {
+        if !self.enabled || self.in_dnd(now) {
+            return false;
+        }
+        if guild_id.is_some_and(|g| self.muted_guilds.contains(g)) {
+            return false;
+        }
+        let level = self
+            .channel_levels
+            .get(channel_id)
+            .copied()
+            .unwrap_or_default();
+        if level == ChannelLevel::Muted {
+            return false;
+        }
+
+        match kind {
+            NotificationKind::GuildMessage { mentioned } => {
+                (mentioned && self.mentions) || level == ChannelLevel::All
+            }
+            NotificationKind::DirectMessage => self.direct_messages,
+            NotificationKind::IncomingCall => self.incoming_calls,
+        }
+    }
+}
+
+/// Whether `content` mentions `username` (`@username`, case-insensitive, whole word).
+pub fn mentions_user(content: &str, username: &str) -> bool {
+    let needle = format!("@{}", username.to_lowercase());
+    let haystack = content.to_lowercase();
+    haystack.match_indices(&needle).any(|(idx, _)| {
+        haystack[idx + needle.len()..]
+            .chars()
+            .next()
+            .is_none_or(|c| !(c.is_alphanumeric() || c == '_'))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+
+    use super::*;
+
+    /// 2026-03-06 is a Friday.
+    fn friday_at(hour: u32, minute: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2026, 3, 6)
+            .unwrap()
+            .and_hms_opt(hour, minute, 0)
+            .unwrap()
+    }
+
+    fn window(start: &str, end: &str, days: &[u8]) -> DndWindow {
+        DndWindow {
+            start_time: start.into(),
+            end_time: end.into(),
+            days: days.to_vec(),
+        }
+    }
+
+    #[test]
+    fn test_dnd_window_same_day() {
+        let w = window("09:00", "17:00", &[]);
+        assert!(w.contains(friday_at(9, 0)));
+        assert!(w.contains(friday_at(16, 59)));
+        assert!(!w.contains(friday_at(17, 0)));
+        assert!(!w.contains(friday_at(8, 59)));
+    }
+
+    #[test]
+    fn test_dnd_window_overnight_belongs_to_start_day() {
+        // Thursday night only (4 = Thursday)
+        let w = window("22:00", "07:00", &[4]);
+        assert!(w.contains(friday_at(6, 30))); // Thursday's window, Friday morning
+        assert!(!w.contains(friday_at(23, 0))); // Friday night is not covered
+        let every_day = window("22:00", "07:00", &[]);
+        assert!(every_day.contains(friday_at(23, 0)));
+    }
+
+    #[test]
+    fn test_dnd_window_rejects_invalid_times() {
+        assert!(!window("25:00", "07:00", &[]).contains(friday_at(1, 0)));
+        assert!(!window("bad", "07:00", &[]).contains(friday_at(1, 0)));
+    }
+
+    #[test]
+    fn test_should_notify_levels_and_mutes() {
+        let mut settings = NotificationSettings::default();
+        let now = friday_at(12, 0);
+        let mention = NotificationKind::GuildMessage { mentioned: true };
+        let plain = NotificationKind::GuildMessage { mentioned: false };
+
+        assert!(settings.should_notify(mention, "c1", Some("g1"), now));
+        assert!(!settings.should_notify(plain, "c1", Some("g1"), now));
+        assert!(settings.should_notify(NotificationKind::DirectMessage, "dm", None, now));
+
+        settings
+            .channel_levels
+            .insert("c1".into(), ChannelLevel::All);
+        assert!(settings.should_notify(plain, "c1", Some("g1"), now));
+
+        settings.muted_guilds.insert("g1".into());
+        assert!(!settings.should_notify(mention, "c1", Some("g1"), now));
+
+        settings
+            .channel_levels
+            .insert("dm".into(), ChannelLevel::Muted);
+        assert!(!settings.should_notify(NotificationKind::IncomingCall, "dm", None, now));
+
+        settings.dnd_windows.push(window("11:00", "13:00", &[]));
+        assert!(!settings.should_notify(NotificationKind::DirectMessage, "dm2", None, now));
+    }
+
+    #[test]
+    fn test_mentions_user() {
+        assert!(mentions_user("hey @Alice, look", "alice"));
+        assert!(mentions_user("@alice", "alice"));
+        assert!(!mentions_user("@alice_bot hi", "alice"));
+        assert!(!mentions_user("alice without at", "alice"));
+        assert!(mentions_user("@alicex @alice", "alice"));
+    }
+}
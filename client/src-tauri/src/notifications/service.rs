@@ -0,0 +1,316 @@
+//! Turns WebSocket events into native OS notifications.
+
+use std::collections::HashMap;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+use tauri::{AppHandle, Manager};
+use tauri_plugin_notification::NotificationExt;
+use tokio::sync::RwLock;
+use tracing::{debug, warn};
+
+use super::rules::{mentions_user, NotificationKind, NotificationSettings};
+use crate::network::http;
+use crate::network::websocket::ServerEvent;
+use crate::AppState;
+
+/// Longest message preview shown in a notification body.
+const MAX_BODY_CHARS: usize = 140;
+
+/// What the notification rules need to know about a channel.
+#[derive(Debug, Clone)]
+struct ChannelInfo {
+    name: String,
+    guild_id: Option<String>,
+}
+
+/// Subset of `GET /api/channels/{id}` used here.
+#[derive(Debug, Deserialize)]
+struct ChannelResponse {
+    name: String,
+    guild_id: Option<String>,
+}
+
+/// Subset of a `message_new` payload used here.
+#[derive(Debug, Deserialize)]
+struct MessagePayload {
+    author: MessageAuthor,
+    content: String,
+    #[serde(default)]
+    encrypted: bool,
+    mention_type: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct MessageAuthor {
+    id: String,
+    display_name: String,
+}
+
+/// Notification settings and the channel lookup cache.
+#[derive(Debug, Default)]
+pub struct NotificationState {
+    settings: RwLock<NotificationSettings>,
+    channels: RwLock<HashMap<String, ChannelInfo>>,
+}
+
+impl NotificationState {
+    /// Create the state with previously saved settings.
+    pub fn new(settings: NotificationSettings) -> Self {
+        Self {
+            settings: RwLock::new(settings),
+            channels: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Current settings.
+    pub async fn settings(&self) -> NotificationSettings {
+        self.settings.read().await.clone()
+    }
+
+    /// Replace the settings.
+    pub async fn set_settings(&self, settings: NotificationSettings) {
+        *self.settings.write().await = settings;
+    }
+
+    /// Forget cached channel info (e.g. on logout).
+    pub async fn clear_channel_cache(&self) {
+        self.channels.write().await.clear();
+    }
+}
+
+// ============================================================================
+// Persistence
+// ============================================================================
+
+/// Path of the locally saved settings, so notifications follow the user's
+/// rules before the frontend has synced preferences.
+pub fn settings_path(app: &AppHandle) -> Result<PathBuf, String> {
+    let app_data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data dir: {e}"))?;
+
+    std::fs::create_dir_all(&app_data_dir)
+        .map_err(|e| format!("Failed to create app data directory: {e}"))?;
+
+    Ok(app_data_dir.join("notification_settings.json"))
+}
+
+/// Load saved settings, falling back to defaults.
+pub fn load_settings(app: &AppHandle) -> NotificationSettings {
+    let Ok(path) = settings_path(app) else {
+        return NotificationSettings::default();
+    };
+    match std::fs::read_to_string(&path) {
+        Ok(contents) => serde_json::from_str(&contents).unwrap_or_else(|e| {
+            warn!("Corrupt notification settings file, using defaults: {e}");
+            NotificationSettings::default()
+        }),
+        Err(e) if e.kind() == ErrorKind::NotFound => NotificationSettings::default(),
+        Err(e) => {
+            warn!("Failed to read notification settings file, using defaults: {e}");
+            NotificationSettings::default()
+        }
+    }
+}
+
+/// Save settings to disk.
+pub fn save_settings(path: &Path, settings: &NotificationSettings) -> Result<(), String> {
+    let json = serde_json::to_string_pretty(settings)
+        .map_err(|e| format!("Failed to serialize notification settings: {e}"))?;
+    std::fs::write(path, json)
+        .map_err(|e| format!("Failed to write notification settings file: {e}"))
+}
+
+// ============================================================================
+// Event Handling
+// ============================================================================
+
+/// Raise a native notification for the event if the user's rules allow it.
+///
+/// Only new messages and incoming calls can notify. Nothing is shown while
+/// the main window is focused; the frontend handles that case with sounds.
+pub fn handle_server_event(app: &AppHandle, event: &ServerEvent) {
+    let candidate = match event {
+        ServerEvent::MessageNew {
+            channel_id,
+            message,
+        } => Candidate::Message {
+            channel_id: channel_id.clone(),
+            message: message.clone(),
+        },
+        ServerEvent::IncomingCall {
+            channel_id,
+            initiator_name,
+            ..
+        } => Candidate::Call {
+            channel_id: channel_id.clone(),
+            initiator_name: initiator_name.clone(),
+        },
+        _ => return,
+    };
+
+    if main_window_focused(app) {
+        return;
+    }
+
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        if let Err(e) = notify(&app, candidate).await {
+            debug!("Notification skipped: {e}");
+        }
+    });
+}
+
+enum Candidate {
+    Message {
+        channel_id: String,
+        message: serde_json::Value,
+    },
+    Call {
+        channel_id: String,
+        initiator_name: String,
+    },
+}
+
+fn main_window_focused(app: &AppHandle) -> bool {
+    app.get_webview_window("main")
+        .and_then(|w| w.is_focused().ok())
+        .unwrap_or(false)
+}
+
+async fn notify(app: &AppHandle, candidate: Candidate) -> Result<(), String> {
+    let state = app.state::<AppState>();
+    let (user_id, username) = {
+        let auth = state.auth.read().await;
+        let user = auth.user.as_ref().ok_or("Not authenticated")?;
+        (user.id.clone(), user.username.clone())
+    };
+    let now = chrono::Local::now().naive_local();
+
+    let (channel_id, guild_id, kind, title, body) = match candidate {
+        Candidate::Message {
+            channel_id,
+            message,
+        } => {
+            let message: MessagePayload =
+                serde_json::from_value(message).map_err(|e| format!("Invalid message: {e}"))?;
+            if message.author.id == user_id {
+                return Ok(());
+            }
+            let channel = channel_info(&state, &channel_id).await?;
+            let body = if message.encrypted {
+                "New encrypted message".to_string()
+            } else {
+                truncate(&message.content, MAX_BODY_CHARS)
+            };
+            let author = message.author.display_name;
+
+            if channel.guild_id.is_none() {
+                (
+                    channel_id,
+                    None,
+                    NotificationKind::DirectMessage,
+                    author,
+                    body,
+                )
+            } else {
+                let mentioned = !message.encrypted
+                    && (matches!(message.mention_type.as_deref(), Some("everyone" | "here"))
+                        || mentions_user(&message.content, &username));
+                let title = if mentioned {
+                    format!("{author} mentioned you in #{}", channel.name)
+                } else {
+                    format!("{author} in #{}", channel.name)
+                };
+                (
+                    channel_id,
+                    channel.guild_id,
+                    NotificationKind::GuildMessage { mentioned },
+                    title,
+                    body,
+                )
+            }
+        }
+        Candidate::Call {
+            channel_id,
+            initiator_name,
+        } => (
+            channel_id,
+            None,
+            NotificationKind::IncomingCall,
+            "Incoming call".to_string(),
+            format!("{initiator_name} is calling you"),
+        ),
+    };
+
+    let allowed = state.notifications.settings.read().await.should_notify(
+        kind,
+        &channel_id,
+        guild_id.as_deref(),
+        now,
+    );
+    if !allowed {
+        return Ok(());
+    }
+
+    app.notification()
+        .builder()
+        .title(title)
+        .body(body)
+        .show()
+        .map_err(|e| format!("Failed to show notification: {e}"))
+}
+
+/// Look up a channel's name and guild, caching the result.
+async fn channel_info(state: &AppState, channel_id: &str) -> Result<ChannelInfo, String> {
+    if let Some(info) = state.notifications.channels.read().await.get(channel_id) {
+        return Ok(info.clone());
+    }
+
+    let (server_url, token) = {
+        let auth = state.auth.read().await;
+        (auth.server_url.clone(), auth.access_token.clone())
+    };
+    let server_url = server_url.ok_or("Not authenticated")?;
+    let token = token.ok_or("Not authenticated")?;
+
+    let response = http::send_idempotent(
+        state
+            .http
+            .get(format!("{server_url}/api/channels/{channel_id}"))
+            .header("Authorization", format!("Bearer {token}")),
+    )
+    .await?;
+    if !response.status().is_success() {
+        return Err(http::status_error(
+            "Failed to fetch channel",
+            response.status(),
+        ));
+    }
+    let channel: ChannelResponse = response
+        .json()
+        .await
+        .map_err(|e| format!("Invalid response: {e}"))?;
+
+    let info = ChannelInfo {
+        name: channel.name,
+        guild_id: channel.guild_id,
+    };
+    state
+        .notifications
+        .channels
+        .write()
+        .await
+        .insert(channel_id.to_string(), info.clone());
+    Ok(info)
+}
+
+fn truncate(text: &str, max_chars: usize) -> String {
+    match text.char_indices().nth(max_chars) {
+        Some((idx, _)) => format!("{}…", &text[..idx]),
+        None => text.to_string(),
+    }
+}
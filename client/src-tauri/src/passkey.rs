@@ -0,0 +1,91 @@
+//! OS Authenticator Bridge
+//!
+//! Runs WebAuthn ceremonies for passkey registration and sign-in against the
+//! operating system's authenticator. On Windows that is Windows Hello, which
+//! also brokers security keys and phone passkeys. Elsewhere the webview
+//! talks to the platform authenticator itself, so the ceremonies return
+//! [`UNSUPPORTED`] and the frontend falls back to `navigator.credentials`.
+//!
+//! Options and credentials are passed through as the JSON the server sends
+//! and expects, so the commands don't need to know the WebAuthn types.
+
+use serde_json::Value;
+
+/// Error returned when there is no OS authenticator to bridge to.
+pub const UNSUPPORTED: &str = "PASSKEY_UNSUPPORTED";
+
+/// Create a credential from the server's creation options.
+///
+/// `origin` must match the relying party origin configured on the server.
+#[cfg_attr(not(windows), allow(clippy::unused_async))]
+pub async fn register(origin: &str, options: Value) -> Result<Value, String> {
+    #[cfg(windows)]
+    {
+        windows::register(origin, options).await
+    }
+    #[cfg(not(windows))]
+    {
+        let _ = (origin, options);
+        Err(UNSUPPORTED.to_string())
+    }
+}
+
+/// Sign the server's assertion challenge with one of the account's passkeys.
+///
+/// `origin` must match the relying party origin configured on the server.
+#[cfg_attr(not(windows), allow(clippy::unused_async))]
+pub async fn authenticate(origin: &str, options: Value) -> Result<Value, String> {
+    #[cfg(windows)]
+    {
+        windows::authenticate(origin, options).await
+    }
+    #[cfg(not(windows))]
+    {
+        let _ = (origin, options);
+        Err(UNSUPPORTED.to_string())
+    }
+}
+
+#[cfg(windows)]
+mod windows {
+    use serde_json::Value;
+    use url::Url;
+    use webauthn_authenticator_rs::win10::Win10;
+    use webauthn_authenticator_rs::WebauthnAuthenticator;
+    use webauthn_rs_proto::{CreationChallengeResponse, RequestChallengeResponse};
+
+    fn parse_origin(origin: &str) -> Result<Url, String> {
+        Url::parse(origin).map_err(|e| format!("Invalid server URL: {e}"))
+    }
+
+    pub async fn register(origin: &str, options: Value) -> Result<Value, String> {
+        let origin = parse_origin(origin)?;
+        let options: CreationChallengeResponse =
+            serde_json::from_value(options).map_err(|e| format!("Invalid passkey options: {e}"))?;
+
+        // The Windows Hello prompt blocks until the user answers it
+        let credential = tokio::task::spawn_blocking(move || {
+            WebauthnAuthenticator::new(Win10::default()).do_registration(origin, options)
+        })
+        .await
+        .map_err(|e| format!("Passkey prompt failed: {e}"))?
+        .map_err(|e| format!("Passkey registration failed: {e:?}"))?;
+
+        serde_json::to_value(credential).map_err(|e| format!("Invalid passkey credential: {e}"))
+    }
+
+    pub async fn authenticate(origin: &str, options: Value) -> Result<Value, String> {
+        let origin = parse_origin(origin)?;
+        let options: RequestChallengeResponse =
+            serde_json::from_value(options).map_err(|e| format!("Invalid passkey options: {e}"))?;
+
+        let assertion = tokio::task::spawn_blocking(move || {
+            WebauthnAuthenticator::new(Win10::default()).do_authentication(origin, options)
+        })
+        .await
+        .map_err(|e| format!("Passkey prompt failed: {e}"))?
+        .map_err(|e| format!("Passkey sign-in failed: {e:?}"))?;
+
+        serde_json::to_value(assertion).map_err(|e| format!("Invalid passkey assertion: {e}"))
+    }
+}
@@ -0,0 +1,228 @@
+//! Spoken Announcements
+//!
+//! Reads short announcements through the OS text-to-speech engine, for
+//! visually-impaired users. Voice channel joins and leaves in the user's
+//! current call are announced when `announce_voice_presence` is enabled in the
+//! desktop notification settings, using the participant's name pronunciation
+//! when they set one.
+//!
+//! Announcements are queued and spoken one at a time by a dedicated thread:
+//! `say` on macOS, `System.Speech` through `PowerShell` on Windows, and
+//! `spd-say` (falling back to `espeak`) on Linux.
+
+use std::process::Command;
+use std::sync::mpsc::{self, Sender};
+use std::sync::{Mutex, OnceLock};
+
+use tauri::{AppHandle, Manager};
+use tracing::{debug, warn};
+
+use crate::network::websocket::ServerEvent;
+use crate::AppState;
+
+/// Longest announcement spoken, in characters.
+const MAX_ANNOUNCEMENT_CHARS: usize = 200;
+
+static QUEUE: OnceLock<Mutex<Sender<String>>> = OnceLock::new();
+
+/// Queue `text` to be spoken after earlier announcements.
+pub fn speak(text: &str) -> Result<(), String> {
+    let text = sanitize(text);
+    if text.is_empty() {
+        return Ok(());
+    }
+
+    let queue = QUEUE.get_or_init(|| {
+        let (tx, rx) = mpsc::channel::<String>();
+        std::thread::spawn(move || {
+            for text in rx {
+                if let Err(e) = speak_blocking(&text) {
+                    warn!("Failed to speak announcement: {e}");
+                }
+            }
+        });
+        Mutex::new(tx)
+    });
+    queue
+        .lock()
+        .map_err(|_| "Speech queue unavailable".to_string())?
+        .send(text)
+        .map_err(|_| "Speech queue closed".to_string())
+}
+
+/// Single line of plain text that cannot be mistaken for a command-line option.
+fn sanitize(text: &str) -> String {
+    let text: String = text
+        .chars()
+        .map(|c| if c.is_control() { ' ' } else { c })
+        .take(MAX_ANNOUNCEMENT_CHARS)
+        .collect();
+    text.trim().trim_start_matches('-').trim().to_string()
+}
+
+#[cfg(target_os = "macos")]
+fn speak_blocking(text: &str) -> Result<(), String> {
+    run(Command::new("say").arg(text))
+}
+
+#[cfg(target_os = "windows")]
+fn speak_blocking(text: &str) -> Result<(), String> {
+    // Passed through the environment so the text is never parsed as script
+    run(Command::new("powershell")
+        .args([
+            "-NoProfile",
+            "-NonInteractive",
+            "-Command",
+            "Add-Type -AssemblyName System.Speech; \
+             (New-Object System.Speech.Synthesis.SpeechSynthesizer).Speak($env:KAIKU_SPEECH_TEXT)",
+        ])
+        .env("KAIKU_SPEECH_TEXT", text))
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+fn speak_blocking(text: &str) -> Result<(), String> {
+    run(Command::new("spd-say").args(["--wait", text]))
+        .or_else(|_| run(Command::new("espeak").arg(text)))
+}
+
+fn run(command: &mut Command) -> Result<(), String> {
+    let status = command
+        .status()
+        .map_err(|e| format!("Failed to start text-to-speech: {e}"))?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(format!("Text-to-speech exited with {status}"))
+    }
+}
+
+/// Announcement for a voice event in the user's current call, if any.
+fn announcement(event: &ServerEvent, voice_channel_id: &str, own_user_id: &str) -> Option<String> {
+    let (channel_id, user_id, display_name, name_pronunciation, action) = match event {
+        ServerEvent::VoiceUserJoined {
+            channel_id,
+            user_id,
+            display_name,
+            name_pronunciation,
+            ..
+        } => (
+            channel_id,
+            user_id,
+            display_name,
+            name_pronunciation,
+            "joined",
+        ),
+        ServerEvent::VoiceUserLeft {
+            channel_id,
+            user_id,
+            display_name,
+            name_pronunciation,
+        } => (
+            channel_id,
+            user_id,
+            display_name,
+            name_pronunciation,
+            "left",
+        ),
+        _ => return None,
+    };
+    if channel_id != voice_channel_id || user_id == own_user_id {
+        return None;
+    }
+
+    let name = name_pronunciation
+        .as_deref()
+        .filter(|name| !name.trim().is_empty())
+        .unwrap_or(display_name);
+    if name.trim().is_empty() {
+        return Some(format!("Someone {action}"));
+    }
+    Some(format!("{name} {action}"))
+}
+
+/// Announce voice joins and leaves in the user's current call.
+pub fn handle_server_event(app: &AppHandle, event: &ServerEvent) {
+    if !matches!(
+        event,
+        ServerEvent::VoiceUserJoined { .. } | ServerEvent::VoiceUserLeft { .. }
+    ) {
+        return;
+    }
+
+    let app = app.clone();
+    let event = event.clone();
+    tauri::async_runtime::spawn(async move {
+        let state = app.state::<AppState>();
+        if !state.notifications.settings().await.announce_voice_presence {
+            return;
+        }
+        let Some(voice_channel_id) = state
+            .voice
+            .read()
+            .await
+            .as_ref()
+            .and_then(|voice| voice.channel_id.clone())
+        else {
+            return;
+        };
+        let Some(own_user_id) = state.auth.read().await.user.as_ref().map(|u| u.id.clone()) else {
+            return;
+        };
+
+        if let Some(text) = announcement(&event, &voice_channel_id, &own_user_id) {
+            debug!("Announcing: {text}");
+            if let Err(e) = speak(&text) {
+                warn!("Failed to queue announcement: {e}");
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn joined(channel_id: &str, user_id: &str, pronunciation: Option<&str>) -> ServerEvent {
+        ServerEvent::VoiceUserJoined {
+            channel_id: channel_id.to_string(),
+            user_id: user_id.to_string(),
+            username: "siobhan".to_string(),
+            display_name: "Siobhan".to_string(),
+            name_pronunciation: pronunciation.map(str::to_string),
+            listener: false,
+        }
+    }
+
+    #[test]
+    fn test_announcement() {
+        assert_eq!(
+            announcement(&joined("c1", "u1", Some("shi-vawn")), "c1", "me").as_deref(),
+            Some("shi-vawn joined")
+        );
+        assert_eq!(
+            announcement(&joined("c1", "u1", None), "c1", "me").as_deref(),
+            Some("Siobhan joined")
+        );
+        let left = ServerEvent::VoiceUserLeft {
+            channel_id: "c1".to_string(),
+            user_id: "u1".to_string(),
+            display_name: String::new(),
+            name_pronunciation: None,
+        };
+        assert_eq!(
+            announcement(&left, "c1", "me").as_deref(),
+            Some("Someone left")
+        );
+
+        // Other calls and the user's own join are not announced
+        assert!(announcement(&joined("c2", "u1", None), "c1", "me").is_none());
+        assert!(announcement(&joined("c1", "me", None), "c1", "me").is_none());
+    }
+
+    #[test]
+    fn test_sanitize() {
+        assert_eq!(sanitize("--output x\njoined"), "output x joined");
+        assert_eq!(sanitize("  Ann joined "), "Ann joined");
+        assert_eq!(sanitize(&"a".repeat(500)).len(), MAX_ANNOUNCEMENT_CHARS);
+    }
+}
@@ -50,6 +50,7 @@ import { onShowBlockConfirm, onShowReport } from "./lib/contextMenuBuilders";
 
 import { fetchUploadLimits } from "./lib/tauri";
 import { initDrafts } from "./stores/drafts";
+import { initNetworkState } from "./stores/network";
 
 // Global modal state
 const [blockTarget, setBlockTarget] = createSignal<{
@@ -79,6 +80,9 @@ onShowReport((target) =>
 const Layout: Component<ParentProps> = (props) => {
   onMount(() => {
     initDrafts();
+    initNetworkState().catch((err) =>
+      console.warn("[App] Failed to start network state tracking:", err),
+    );
     // Fetch upload size limits from server (non-blocking)
     fetchUploadLimits().catch((err) =>
       console.warn("[App] Failed to fetch upload limits:", err),
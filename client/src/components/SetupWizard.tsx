@@ -19,7 +19,7 @@ import { getAccessToken } from "@/lib/tauri";
 // Setup config interface (matches server API)
 interface SetupConfig {
   server_name: string;
-  registration_policy: "open" | "invite_only" | "closed";
+  registration_policy: "open" | "invite_only" | "approval" | "closed";
   terms_url?: string;
   privacy_url?: string;
 }
@@ -182,7 +182,7 @@ const SetupWizard: Component = () => {
   // Form state
   const [serverName, setServerName] = createSignal("");
   const [registrationPolicy, setRegistrationPolicy] = createSignal<
-    "open" | "invite_only" | "closed"
+    "open" | "invite_only" | "approval" | "closed"
   >("open");
   const [termsUrl, setTermsUrl] = createSignal("");
   const [privacyUrl, setPrivacyUrl] = createSignal("");
@@ -339,7 +339,11 @@ const SetupWizard: Component = () => {
                 value={registrationPolicy()}
                 onChange={(e) =>
                   setRegistrationPolicy(
-                    e.currentTarget.value as "open" | "invite_only" | "closed",
+                    e.currentTarget.value as
+                      | "open"
+                      | "invite_only"
+                      | "approval"
+                      | "closed",
                   )
                 }
                 disabled={isLoading()}
@@ -349,6 +353,9 @@ const SetupWizard: Component = () => {
                 <option value="invite_only">
                   Invite Only - Requires invite code
                 </option>
+                <option value="approval">
+                  Approval - Admins approve new accounts
+                </option>
                 <option value="closed">Closed - Registration disabled</option>
               </select>
               <p class="mt-1 text-xs text-text-muted">
@@ -32,6 +32,7 @@ import {
   deElevateSession,
   getElevationTimeRemaining,
 } from "@/stores/admin";
+import { authState } from "@/stores/auth";
 
 interface AdminQuickModalProps {
   onClose: () => void;
@@ -60,9 +61,22 @@ const AdminQuickModal: Component<AdminQuickModalProps> = (props) => {
     }
   });
 
+  // Step-up credential: authenticator code when MFA is enabled, else password
+  const hasMfa = () => authState.user?.mfa_enabled ?? false;
+  const [useMfa, setUseMfa] = createSignal(hasMfa());
+  const [credential, setCredential] = createSignal("");
+
   // Handle elevation
-  const handleElevate = async () => {
-    await elevateSession();
+  const handleElevate = async (e: Event) => {
+    e.preventDefault();
+    const value = credential().trim();
+    if (!value) return;
+    const elevated = await elevateSession(
+      useMfa() ? { mfa_code: value } : { password: value },
+    );
+    if (elevated) {
+      setCredential("");
+    }
   };
 
   // Handle de-elevation
@@ -141,15 +155,48 @@ const AdminQuickModal: Component<AdminQuickModalProps> = (props) => {
                       <Shield class="w-4 h-4" />
                       <span class="text-sm font-medium">Session not elevated</span>
                     </div>
-                    <button
-                      onClick={handleElevate}
-                      disabled={adminState.isElevating}
-                      class="w-full px-4 py-2 rounded-lg bg-accent-primary text-white font-medium transition-colors hover:bg-accent-primary/90 disabled:opacity-50 disabled:cursor-not-allowed"
-                    >
-                      {adminState.isElevating
-                        ? "Elevating..."
-                        : "Elevate Session"}
-                    </button>
+                    <form onSubmit={handleElevate} class="space-y-3">
+                      <input
+                        type={useMfa() ? "text" : "password"}
+                        inputMode={useMfa() ? "numeric" : undefined}
+                        autocomplete={
+                          useMfa() ? "one-time-code" : "current-password"
+                        }
+                        value={credential()}
+                        onInput={(e) => setCredential(e.currentTarget.value)}
+                        placeholder={
+                          useMfa()
+                            ? "Authenticator code"
+                            : "Confirm your password"
+                        }
+                        class="w-full px-3 py-2 rounded-lg bg-white/5 border border-white/10 text-sm text-text-primary placeholder-text-secondary/50 focus:outline-none focus:border-accent-primary"
+                      />
+                      <Show when={hasMfa()}>
+                        <button
+                          type="button"
+                          onClick={() => {
+                            setUseMfa(!useMfa());
+                            setCredential("");
+                          }}
+                          class="text-xs text-text-secondary hover:text-text-primary transition-colors"
+                        >
+                          {useMfa()
+                            ? "Use password instead"
+                            : "Use authenticator code instead"}
+                        </button>
+                      </Show>
+                      <button
+                        type="submit"
+                        disabled={
+                          adminState.isElevating || !credential().trim()
+                        }
+                        class="w-full px-4 py-2 rounded-lg bg-accent-primary text-white font-medium transition-colors hover:bg-accent-primary/90 disabled:opacity-50 disabled:cursor-not-allowed"
+                      >
+                        {adminState.isElevating
+                          ? "Elevating..."
+                          : "Elevate Session"}
+                      </button>
+                    </form>
                   </div>
                 }
               >
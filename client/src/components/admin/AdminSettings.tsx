@@ -25,7 +25,29 @@ import {
   adminUpdateOidcProvider,
   adminDeleteOidcProvider,
 } from "@/lib/tauri";
-import type { AuthMethodsConfig, AdminOidcProvider } from "@/lib/types";
+import type {
+  AuthMethodsConfig,
+  AdminOidcProvider,
+  OidcProvisioning,
+} from "@/lib/types";
+
+const PROVISIONING_OPTIONS: {
+  value: OidcProvisioning;
+  label: string;
+  desc: string;
+}[] = [
+  {
+    value: "registration_policy",
+    label: "Follow Policy",
+    desc: "Create accounts if registration is open",
+  },
+  {
+    value: "always",
+    label: "Always",
+    desc: "Create accounts even when closed",
+  },
+  { value: "never", label: "Never", desc: "Only linked accounts sign in" },
+];
 import { adminState } from "@/stores/admin";
 
 function providerIcon(hint: string | null) {
@@ -75,6 +97,8 @@ const AdminSettings: Component = () => {
     client_secret: "",
     issuer_url: "",
     scopes: "openid profile email",
+    provisioning: "registration_policy" as OidcProvisioning,
+    link_by_email: false,
     preset: "" as "" | "github" | "google" | "custom",
   });
 
@@ -197,6 +221,8 @@ const AdminSettings: Component = () => {
         client_secret: addForm.client_secret,
         issuer_url: addForm.issuer_url || undefined,
         scopes: addForm.scopes || undefined,
+        provisioning: addForm.provisioning,
+        link_by_email: addForm.link_by_email,
       });
       setShowAddProvider(false);
       setAddForm({
@@ -207,6 +233,8 @@ const AdminSettings: Component = () => {
         client_secret: "",
         issuer_url: "",
         scopes: "openid profile email",
+        provisioning: "registration_policy",
+        link_by_email: false,
         preset: "",
       });
       await loadSettings();
@@ -384,7 +412,12 @@ const AdminSettings: Component = () => {
                   {
                     value: "invite_only",
                     label: "Invite Only",
-                    desc: "Requires invitation",
+                    desc: "Requires a registration code",
+                  },
+                  {
+                    value: "approval",
+                    label: "Approval",
+                    desc: "Admins approve new accounts",
                   },
                   {
                     value: "closed",
@@ -459,7 +492,15 @@ const AdminSettings: Component = () => {
                               {provider.display_name}
                             </div>
                             <div class="text-xs text-text-muted">
-                              {provider.slug} &middot; {provider.provider_type}
+                              {provider.slug} &middot; {provider.provider_type}{" "}
+                              &middot; new accounts:{" "}
+                              {PROVISIONING_OPTIONS.find(
+                                (o) => o.value === provider.provisioning,
+                              )?.label ?? provider.provisioning}
+                              <Show when={provider.link_by_email}>
+                                {" "}
+                                &middot; links by email
+                              </Show>
                             </div>
                           </div>
                         </div>
@@ -676,6 +717,55 @@ const AdminSettings: Component = () => {
                   />
                 </div>
 
+                <div>
+                  <label class="block text-xs text-text-muted mb-1">
+                    New Accounts
+                  </label>
+                  <div class="flex gap-2">
+                    <For each={PROVISIONING_OPTIONS}>
+                      {(option) => (
+                        <button
+                          type="button"
+                          onClick={() =>
+                            setAddForm("provisioning", option.value)
+                          }
+                          class="flex-1 p-2 rounded-lg border text-left transition-colors"
+                          classList={{
+                            "border-accent-primary bg-accent-primary/10":
+                              addForm.provisioning === option.value,
+                            "border-white/10 bg-white/5 hover:bg-white/10":
+                              addForm.provisioning !== option.value,
+                          }}
+                        >
+                          <div class="text-xs font-medium text-text-primary">
+                            {option.label}
+                          </div>
+                          <div class="text-xs text-text-muted">
+                            {option.desc}
+                          </div>
+                        </button>
+                      )}
+                    </For>
+                  </div>
+                </div>
+
+                <label class="flex items-start gap-2 text-sm text-text-primary">
+                  <input
+                    type="checkbox"
+                    class="mt-0.5"
+                    checked={addForm.link_by_email}
+                    onChange={(e) =>
+                      setAddForm("link_by_email", e.currentTarget.checked)
+                    }
+                  />
+                  <span>
+                    Link to existing accounts by email
+                    <span class="block text-xs text-text-muted">
+                      Only when the provider reports the email as verified.
+                    </span>
+                  </span>
+                </label>
+
                 <div class="flex gap-2 pt-2">
                   <button
                     onClick={handleCreateProvider}
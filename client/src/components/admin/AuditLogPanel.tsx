@@ -44,6 +44,15 @@ const ACTION_TYPES = [
   { value: "admin.session.elevated", label: "Session Elevated" },
   { value: "admin.session.de_elevated", label: "Session De-elevated" },
   { value: "admin.announcements.create", label: "Announcement Created" },
+  { value: "admin.settings.update", label: "Server Settings Updated" },
+  { value: "admin.config.reload", label: "Configuration Reloaded" },
+  { value: "auth.login", label: "Sign-in" },
+  { value: "auth.login_failed", label: "Failed Sign-in" },
+  { value: "auth.login.new_network", label: "New Network Sign-in" },
+  { value: "auth.mfa.enabled", label: "MFA Enabled" },
+  { value: "auth.mfa.disabled", label: "MFA Disabled" },
+  { value: "auth.email.verified", label: "Email Verified" },
+  { value: "setup.completed", label: "Setup Completed" },
 ];
 
 const AuditLogPanel: Component = () => {
@@ -150,7 +159,7 @@ const AuditLogPanel: Component = () => {
 
     // Capitalize first letter and format
     const formattedAction =
-      lastPart.charAt(0).toUpperCase() + lastPart.slice(1);
+      lastPart.charAt(0).toUpperCase() + lastPart.slice(1).replace(/_/g, " ");
 
     // Add target context if available
     if (targetPart === "users") {
@@ -162,6 +171,9 @@ const AuditLogPanel: Component = () => {
     if (targetPart === "session") {
       return `Session ${formattedAction}`;
     }
+    if (targetPart === "mfa") {
+      return `MFA ${formattedAction}`;
+    }
 
     return formattedAction;
   };
@@ -369,7 +381,8 @@ const AuditLogPanel: Component = () => {
 
                     {/* Actor */}
                     <div class="flex items-center text-sm text-text-primary truncate">
-                      {entry.actor_username || truncateId(entry.actor_id)}
+                      {entry.actor_username ||
+                        (entry.actor_id ? truncateId(entry.actor_id) : "-")}
                     </div>
 
                     {/* Target */}
@@ -387,7 +400,10 @@ const AuditLogPanel: Component = () => {
                     </div>
 
                     {/* IP Address */}
-                    <div class="flex items-center text-sm text-text-secondary font-mono truncate">
+                    <div
+                      class="flex items-center text-sm text-text-secondary font-mono truncate"
+                      title={entry.user_agent ?? undefined}
+                    >
                       {entry.ip_address || "-"}
                     </div>
 
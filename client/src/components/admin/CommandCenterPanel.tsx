@@ -35,12 +35,20 @@ import {
   loadObsTrends,
   loadObsTopRoutes,
   loadObsTopErrors,
+  loadObsTopConsumers,
+  loadObsWsDisconnects,
+  loadObsReportSla,
   loadObsLogs,
   loadObsTraces,
   loadObsLinks,
   setObsTimeRange,
 } from "@/stores/admin";
-import type { ObsTimeRange } from "@/lib/types";
+import type {
+  ObsTimeRange,
+  TopConsumersSort,
+  TopConsumersType,
+  WsDisconnectCause,
+} from "@/lib/types";
 import TableRowSkeleton from "./TableRowSkeleton";
 
 // ============================================================================
@@ -70,6 +78,12 @@ function formatUptime(seconds: number): string {
   return `${mins}m`;
 }
 
+function formatDuration(seconds: number | null): string {
+  if (seconds == null) return "-";
+  if (seconds < 60) return `${seconds.toFixed(0)}s`;
+  return formatUptime(seconds);
+}
+
 function formatTimestamp(iso: string): string {
   const d = new Date(iso);
   return d.toLocaleTimeString(undefined, {
@@ -340,6 +354,16 @@ const DurationBadge: Component<{ ms: number }> = (props) => {
   );
 };
 
+const DISCONNECT_CAUSE_LABELS: Record<WsDisconnectCause, string> = {
+  client_close: "Client closed",
+  connection_lost: "Connection lost",
+  timeout: "Idle timeout",
+  rate_limited: "Rate limited",
+  auth_expired: "Token expired",
+  server_shutdown: "Server shutdown",
+  session_revoked: "Session revoked",
+};
+
 // ============================================================================
 // Main Component
 // ============================================================================
@@ -348,6 +372,10 @@ const CommandCenterPanel: Component = () => {
   const [routeSort, setRouteSort] = createSignal<"latency" | "errors">(
     "latency",
   );
+  const [consumerType, setConsumerType] =
+    createSignal<TopConsumersType>("user");
+  const [consumerSort, setConsumerSort] =
+    createSignal<TopConsumersSort>("requests");
   const [logsLevel, setLogsLevel] = createSignal<string>("ERROR");
   const [logsDomain, setLogsDomain] = createSignal<string>("");
   const [logsSearch, setLogsSearch] = createSignal<string>("");
@@ -361,6 +389,9 @@ const CommandCenterPanel: Component = () => {
     loadObsTrends();
     loadObsTopRoutes();
     loadObsTopErrors();
+    loadObsTopConsumers();
+    loadObsWsDisconnects();
+    loadObsReportSla();
     loadObsLogs(true, "ERROR");
     loadObsTraces(true, "error");
     loadObsLinks();
@@ -388,6 +419,9 @@ const CommandCenterPanel: Component = () => {
     loadObsTrends(range);
     loadObsTopRoutes(range);
     loadObsTopErrors(range);
+    loadObsTopConsumers(range, consumerType(), consumerSort());
+    loadObsWsDisconnects(range);
+    loadObsReportSla(range);
   };
 
   // Manual refresh
@@ -396,6 +430,9 @@ const CommandCenterPanel: Component = () => {
     loadObsTrends();
     loadObsTopRoutes(undefined, routeSort());
     loadObsTopErrors();
+    loadObsTopConsumers(undefined, consumerType(), consumerSort());
+    loadObsWsDisconnects();
+    loadObsReportSla();
     loadObsLogs(true, logsLevel() || undefined, logsDomain() || undefined, logsSearch() || undefined);
     loadObsTraces(true, tracesStatus() || undefined, tracesDomain() || undefined);
   };
@@ -406,6 +443,17 @@ const CommandCenterPanel: Component = () => {
     loadObsTopRoutes(undefined, sort);
   };
 
+  // Top consumers filter handlers
+  const handleConsumerTypeChange = (type: TopConsumersType) => {
+    setConsumerType(type);
+    loadObsTopConsumers(undefined, type, consumerSort());
+  };
+
+  const handleConsumerSortChange = (sort: TopConsumersSort) => {
+    setConsumerSort(sort);
+    loadObsTopConsumers(undefined, consumerType(), sort);
+  };
+
   // Logs filter handlers
   const handleLogsLevelChange = (level: string) => {
     setLogsLevel(level);
@@ -756,6 +804,268 @@ const CommandCenterPanel: Component = () => {
           </section>
         </div>
 
+        {/* Top Consumers */}
+        <section class="rounded-xl bg-white/5 border border-white/10 overflow-hidden">
+          <div class="flex items-center justify-between p-4 border-b border-white/10">
+            <h3 class="text-sm font-semibold text-text-primary">
+              Top Consumers
+            </h3>
+            <div class="flex items-center gap-3">
+              <div class="flex gap-1">
+                <For
+                  each={
+                    [
+                      ["user", "Users"],
+                      ["guild", "Guilds"],
+                    ] as const
+                  }
+                >
+                  {([value, label]) => (
+                    <button
+                      onClick={() => handleConsumerTypeChange(value)}
+                      class="px-2 py-0.5 text-xs rounded transition-colors"
+                      classList={{
+                        "bg-accent-primary/20 text-accent-primary":
+                          consumerType() === value,
+                        "text-text-secondary hover:text-text-primary":
+                          consumerType() !== value,
+                      }}
+                    >
+                      {label}
+                    </button>
+                  )}
+                </For>
+              </div>
+              <select
+                value={consumerSort()}
+                onChange={(e) =>
+                  handleConsumerSortChange(
+                    e.currentTarget.value as TopConsumersSort,
+                  )
+                }
+                class="px-2 py-1 text-xs rounded-md bg-white/5 border border-white/10 text-text-primary"
+              >
+                <option value="requests">Requests</option>
+                <option value="ws_events">WS Events</option>
+                <option value="voice_minutes">Voice Minutes</option>
+              </select>
+            </div>
+          </div>
+          <Show
+            when={!adminState.isObsTopConsumersLoading}
+            fallback={<TableRowSkeleton columns={4} rows={5} />}
+          >
+            <Show
+              when={
+                adminState.obsTopConsumers &&
+                adminState.obsTopConsumers.consumers.length > 0
+              }
+              fallback={
+                <div class="p-8 text-center text-text-secondary text-sm">
+                  No usage data available
+                </div>
+              }
+            >
+              {/* Header */}
+              <div class="grid grid-cols-[1fr_100px_100px_100px] gap-2 px-4 py-2 text-xs font-medium text-text-secondary border-b border-white/5">
+                <span>{consumerType() === "user" ? "User" : "Guild"}</span>
+                <span class="text-right">Requests</span>
+                <span class="text-right">WS Events</span>
+                <span class="text-right">Voice Min</span>
+              </div>
+              <For each={adminState.obsTopConsumers!.consumers}>
+                {(consumer) => (
+                  <div class="grid grid-cols-[1fr_100px_100px_100px] gap-2 px-4 py-2 text-xs border-b border-white/5 hover:bg-white/3">
+                    <span
+                      class="text-text-primary truncate"
+                      title={consumer.id}
+                    >
+                      {consumer.name ?? consumer.id}
+                    </span>
+                    <span class="text-right text-text-secondary">
+                      {consumer.http_requests.toLocaleString()}
+                    </span>
+                    <span class="text-right text-text-secondary">
+                      {consumer.ws_events.toLocaleString()}
+                    </span>
+                    <span class="text-right text-text-secondary">
+                      {consumer.voice_minutes.toFixed(0)}
+                    </span>
+                  </div>
+                )}
+              </For>
+            </Show>
+          </Show>
+        </section>
+
+        {/* WebSocket Disconnects */}
+        <section class="rounded-xl bg-white/5 border border-white/10 overflow-hidden">
+          <div class="flex items-center justify-between p-4 border-b border-white/10">
+            <h3 class="text-sm font-semibold text-text-primary">
+              WebSocket Disconnects
+            </h3>
+            <Show when={adminState.obsWsDisconnects}>
+              <span class="text-xs text-text-secondary">
+                {adminState.obsWsDisconnects!.total.toLocaleString()} total
+              </span>
+            </Show>
+          </div>
+          <Show
+            when={!adminState.isObsWsDisconnectsLoading}
+            fallback={<TableRowSkeleton columns={3} rows={4} />}
+          >
+            <Show
+              when={
+                adminState.obsWsDisconnects &&
+                adminState.obsWsDisconnects.total > 0
+              }
+              fallback={
+                <div class="p-8 text-center text-text-secondary text-sm">
+                  No disconnects recorded
+                </div>
+              }
+            >
+              <div class="grid grid-cols-2 gap-4 p-4">
+                {/* By cause */}
+                <div>
+                  <div class="grid grid-cols-[1fr_80px_100px] gap-2 pb-2 text-xs font-medium text-text-secondary border-b border-white/5">
+                    <span>Cause</span>
+                    <span class="text-right">Count</span>
+                    <span class="text-right">Avg Session</span>
+                  </div>
+                  <For each={adminState.obsWsDisconnects!.by_cause}>
+                    {(entry) => (
+                      <div class="grid grid-cols-[1fr_80px_100px] gap-2 py-1.5 text-xs border-b border-white/5">
+                        <span class="text-text-primary">
+                          {DISCONNECT_CAUSE_LABELS[entry.cause] ?? entry.cause}
+                        </span>
+                        <span class="text-right text-text-secondary">
+                          {entry.count.toLocaleString()}
+                        </span>
+                        <span class="text-right text-text-secondary">
+                          {entry.avg_session_secs == null
+                            ? "-"
+                            : entry.avg_session_secs < 60
+                              ? `${entry.avg_session_secs.toFixed(0)}s`
+                              : formatUptime(entry.avg_session_secs)}
+                        </span>
+                      </div>
+                    )}
+                  </For>
+                  <div class="flex flex-wrap gap-1.5 pt-3">
+                    <For each={adminState.obsWsDisconnects!.by_close_code}>
+                      {(entry) => (
+                        <span class="px-2 py-0.5 text-xs rounded bg-white/5 text-text-secondary font-mono">
+                          {entry.close_code ?? "none"}: {entry.count}
+                        </span>
+                      )}
+                    </For>
+                  </div>
+                </div>
+
+                {/* Top users */}
+                <div>
+                  <div class="grid grid-cols-[1fr_80px] gap-2 pb-2 text-xs font-medium text-text-secondary border-b border-white/5">
+                    <span>Most Disconnects</span>
+                    <span class="text-right">Count</span>
+                  </div>
+                  <For each={adminState.obsWsDisconnects!.top_users}>
+                    {(user) => (
+                      <div class="grid grid-cols-[1fr_80px] gap-2 py-1.5 text-xs border-b border-white/5">
+                        <span
+                          class="text-text-primary truncate"
+                          title={user.user_id}
+                        >
+                          {user.username ?? user.user_id}
+                        </span>
+                        <span class="text-right text-text-secondary">
+                          {user.count.toLocaleString()}
+                        </span>
+                      </div>
+                    )}
+                  </For>
+                </div>
+              </div>
+            </Show>
+          </Show>
+        </section>
+
+        {/* Report Queue SLA */}
+        <section class="rounded-xl bg-white/5 border border-white/10 overflow-hidden">
+          <div class="flex items-center justify-between p-4 border-b border-white/10">
+            <h3 class="text-sm font-semibold text-text-primary">
+              Report Queue
+            </h3>
+            <Show when={adminState.obsReportSla}>
+              <span class="text-xs text-text-secondary">
+                SLA {formatDuration(adminState.obsReportSla!.sla_secs)}
+              </span>
+            </Show>
+          </div>
+          <Show
+            when={!adminState.isObsReportSlaLoading}
+            fallback={<TableRowSkeleton columns={4} rows={2} />}
+          >
+            <Show
+              when={adminState.obsReportSla}
+              fallback={
+                <div class="p-8 text-center text-text-secondary text-sm">
+                  No report data
+                </div>
+              }
+            >
+              {(sla) => (
+                <div class="grid grid-cols-4 gap-4 p-4 text-xs">
+                  <div>
+                    <div class="text-text-secondary">Open / In review</div>
+                    <div class="text-lg font-semibold text-text-primary">
+                      {sla().backlog.open} / {sla().backlog.in_review}
+                    </div>
+                    <div class="text-text-secondary">
+                      Oldest open{" "}
+                      {formatDuration(sla().backlog.oldest_open_secs)}
+                    </div>
+                  </div>
+                  <div>
+                    <div class="text-text-secondary">Past SLA</div>
+                    <div
+                      class="text-lg font-semibold"
+                      classList={{
+                        "text-status-error": sla().backlog.breaching > 0,
+                        "text-text-primary": sla().backlog.breaching === 0,
+                      }}
+                    >
+                      {sla().backlog.breaching}
+                    </div>
+                    <div class="text-text-secondary">
+                      {sla().closed_within_sla} of{" "}
+                      {sla().time_to_resolve.count} closed in SLA
+                    </div>
+                  </div>
+                  <div>
+                    <div class="text-text-secondary">Time to claim</div>
+                    <div class="text-lg font-semibold text-text-primary">
+                      {formatDuration(sla().time_to_claim.p50_secs)}
+                    </div>
+                    <div class="text-text-secondary">
+                      p90 {formatDuration(sla().time_to_claim.p90_secs)}
+                    </div>
+                  </div>
+                  <div>
+                    <div class="text-text-secondary">Time to resolve</div>
+                    <div class="text-lg font-semibold text-text-primary">
+                      {formatDuration(sla().time_to_resolve.p50_secs)}
+                    </div>
+                    <div class="text-text-secondary">
+                      p90 {formatDuration(sla().time_to_resolve.p90_secs)}
+                    </div>
+                  </div>
+                </div>
+              )}
+            </Show>
+          </Show>
+        </section>
+
         {/* Logs Section */}
         <section class="rounded-xl bg-white/5 border border-white/10 overflow-hidden">
           <div class="flex items-center justify-between p-4 border-b border-white/10">
@@ -816,6 +816,7 @@ const GuildsPanel: Component = () => {
                   onInput={(e) => setSuspendReason(e.currentTarget.value)}
                   placeholder="Enter reason..."
                   rows={3}
+                  maxLength={500}
                   class="w-full px-3 py-2 rounded-lg bg-white/5 border border-white/10 text-text-primary placeholder-text-secondary/50 focus:outline-none focus:border-accent-primary text-sm resize-none"
                 />
               </div>
@@ -2,6 +2,7 @@
  * ReportsPanel - Report management panel for admin dashboard
  *
  * Provides report listing with filter by status/category, claim, and resolve functionality.
+ * The details dialog shows internal notes and message evidence snapshots.
  * Actions require session elevation (two-tier privilege model).
  */
 
@@ -19,11 +20,14 @@ import {
   ChevronRight,
   Loader2,
   UserCheck,
+  UserX,
   CheckCircle,
   XCircle,
+  FileText,
 } from "lucide-solid";
 import * as tauri from "@/lib/tauri";
 import { adminState } from "@/stores/admin";
+import { currentUser } from "@/stores/auth";
 import { showToast } from "@/components/ui/Toast";
 import Skeleton from "@/components/ui/Skeleton";
 
@@ -63,6 +67,14 @@ const ReportsPanel: Component = () => {
   const [resolveNote, setResolveNote] = createSignal("");
   const [actionLoading, setActionLoading] = createSignal(false);
 
+  // Details dialog state
+  const [detailReport, setDetailReport] =
+    createSignal<tauri.AdminReportResponse | null>(null);
+  const [notes, setNotes] = createSignal<tauri.ReportNote[]>([]);
+  const [evidence, setEvidence] = createSignal<tauri.ReportEvidence[]>([]);
+  const [detailLoading, setDetailLoading] = createSignal(false);
+  const [noteDraft, setNoteDraft] = createSignal("");
+
   const totalPages = createMemo(() =>
     Math.max(1, Math.ceil(total() / PAGE_SIZE)),
   );
@@ -137,6 +149,71 @@ const ReportsPanel: Component = () => {
     }
   };
 
+  const handleUnclaim = async (report: tauri.AdminReportResponse) => {
+    const force = report.assigned_admin_id !== currentUser()?.id;
+    if (force && !confirm("Release another admin's claim on this report?")) {
+      return;
+    }
+    setActionLoading(true);
+    try {
+      await tauri.adminUnclaimReport(report.id, force);
+      showToast({ type: "success", title: "Claim released", duration: 3000 });
+      await loadReports();
+      await loadStats();
+    } catch (err) {
+      showToast({
+        type: "error",
+        title: "Failed to release claim",
+        message: err instanceof Error ? err.message : undefined,
+        duration: 8000,
+      });
+    } finally {
+      setActionLoading(false);
+    }
+  };
+
+  const openDetails = async (report: tauri.AdminReportResponse) => {
+    setDetailReport(report);
+    setNotes([]);
+    setEvidence([]);
+    setNoteDraft("");
+    setDetailLoading(true);
+    try {
+      const [n, e] = await Promise.all([
+        tauri.adminListReportNotes(report.id),
+        tauri.adminListReportEvidence(report.id),
+      ]);
+      setNotes(n);
+      setEvidence(e);
+    } catch (err) {
+      console.error("[Admin] Failed to load report details:", err);
+    } finally {
+      setDetailLoading(false);
+    }
+  };
+
+  const handleAddNote = async () => {
+    const report = detailReport();
+    const body = noteDraft().trim();
+    if (!report || !body) return;
+
+    setActionLoading(true);
+    try {
+      const note = await tauri.adminAddReportNote(report.id, body);
+      setNotes([...notes(), note]);
+      setNoteDraft("");
+    } catch (err) {
+      showToast({
+        type: "error",
+        title: "Failed to add note",
+        message: err instanceof Error ? err.message : undefined,
+        duration: 8000,
+      });
+    } finally {
+      setActionLoading(false);
+    }
+  };
+
   const handleResolve = async () => {
     const id = resolveReportId();
     if (!id) return;
@@ -302,6 +379,14 @@ const ReportsPanel: Component = () => {
                       </td>
                       <td class="px-4 py-3 text-right">
                         <div class="flex items-center justify-end gap-1">
+                          <button
+                            onClick={() => openDetails(report)}
+                            disabled={!adminState.isElevated}
+                            title="Notes & evidence"
+                            class="p-1.5 rounded-lg text-text-secondary hover:text-text-primary hover:bg-white/10 transition-colors disabled:opacity-30"
+                          >
+                            <FileText class="w-4 h-4" />
+                          </button>
                           <Show when={report.status === "pending"}>
                             <button
                               onClick={() => handleClaim(report.id)}
@@ -314,6 +399,18 @@ const ReportsPanel: Component = () => {
                               <UserCheck class="w-4 h-4" />
                             </button>
                           </Show>
+                          <Show when={report.status === "reviewing"}>
+                            <button
+                              onClick={() => handleUnclaim(report)}
+                              disabled={
+                                !adminState.isElevated || actionLoading()
+                              }
+                              title="Release claim"
+                              class="p-1.5 rounded-lg text-text-secondary hover:text-status-warning hover:bg-status-warning/10 transition-colors disabled:opacity-30"
+                            >
+                              <UserX class="w-4 h-4" />
+                            </button>
+                          </Show>
                           <Show
                             when={
                               report.status === "pending" ||
@@ -371,6 +468,127 @@ const ReportsPanel: Component = () => {
         </div>
       </Show>
 
+      {/* Details Dialog */}
+      <Show when={detailReport()}>
+        <div class="fixed inset-0 z-50 flex items-center justify-center">
+          <div
+            class="absolute inset-0 bg-black/60 backdrop-blur-sm"
+            onClick={() => setDetailReport(null)}
+          />
+          <div
+            class="relative rounded-xl border border-white/10 w-[560px] max-h-[80vh] flex flex-col shadow-2xl"
+            style="background-color: var(--color-surface-layer1)"
+          >
+            <div class="flex items-center justify-between px-5 py-4 border-b border-white/10">
+              <h3 class="text-lg font-bold text-text-primary">
+                Report Details
+              </h3>
+              <button
+                onClick={() => setDetailReport(null)}
+                class="p-1.5 text-text-secondary hover:text-text-primary hover:bg-white/10 rounded-lg transition-colors"
+              >
+                <XCircle class="w-5 h-5" />
+              </button>
+            </div>
+            <div class="p-5 space-y-5 overflow-y-auto">
+              <Show
+                when={!detailLoading()}
+                fallback={
+                  <div class="flex items-center justify-center p-6">
+                    <Loader2 class="w-5 h-5 text-text-secondary animate-spin" />
+                  </div>
+                }
+              >
+                <div class="space-y-2">
+                  <h4 class="text-sm font-medium text-text-secondary">
+                    Evidence
+                  </h4>
+                  <Show
+                    when={evidence().length > 0}
+                    fallback={
+                      <p class="text-xs text-text-secondary">
+                        No messages linked.
+                      </p>
+                    }
+                  >
+                    <For each={evidence()}>
+                      {(item) => (
+                        <div class="p-3 rounded-lg bg-white/5 border border-white/10 space-y-1">
+                          <div class="flex items-center justify-between text-xs text-text-secondary">
+                            <span>
+                              {item.author_username ?? "Deleted user"} ·{" "}
+                              {formatDate(item.message_created_at)}
+                            </span>
+                            <Show when={!item.message_id}>
+                              <span class="text-status-warning">
+                                Message deleted
+                              </span>
+                            </Show>
+                          </div>
+                          <p class="text-sm text-text-primary whitespace-pre-wrap break-words">
+                            {item.encrypted
+                              ? "[End-to-end encrypted]"
+                              : item.content}
+                          </p>
+                          <For each={item.attachments}>
+                            {(a) => (
+                              <div class="text-xs text-text-secondary font-mono">
+                                {a.filename}
+                              </div>
+                            )}
+                          </For>
+                        </div>
+                      )}
+                    </For>
+                  </Show>
+                </div>
+
+                <div class="space-y-2">
+                  <h4 class="text-sm font-medium text-text-secondary">
+                    Internal Notes
+                  </h4>
+                  <For
+                    each={notes()}
+                    fallback={
+                      <p class="text-xs text-text-secondary">No notes yet.</p>
+                    }
+                  >
+                    {(note) => (
+                      <div class="p-3 rounded-lg bg-white/5 border border-white/10">
+                        <div class="text-xs text-text-secondary">
+                          {note.author_username ?? "Deleted user"} ·{" "}
+                          {formatDate(note.created_at)}
+                        </div>
+                        <p class="text-sm text-text-primary whitespace-pre-wrap break-words">
+                          {note.body}
+                        </p>
+                      </div>
+                    )}
+                  </For>
+                  <textarea
+                    value={noteDraft()}
+                    onInput={(e) => setNoteDraft(e.currentTarget.value)}
+                    placeholder="Add a note (only visible to admins)..."
+                    maxLength={2000}
+                    class="w-full px-3 py-2 rounded-lg bg-white/5 border border-white/10 text-text-primary placeholder-text-secondary/50 focus:outline-none focus:border-accent-primary resize-none text-sm"
+                    rows={2}
+                  />
+                  <div class="flex justify-end">
+                    <button
+                      onClick={handleAddNote}
+                      disabled={actionLoading() || !noteDraft().trim()}
+                      class="px-4 py-2 rounded-lg bg-accent-primary text-white text-sm font-medium transition-colors hover:bg-accent-primary/90 disabled:opacity-50"
+                    >
+                      Add Note
+                    </button>
+                  </div>
+                </div>
+              </Show>
+            </div>
+          </div>
+        </div>
+      </Show>
+
       {/* Resolve Dialog */}
       <Show when={resolveReportId()}>
         <div class="fixed inset-0 z-50 flex items-center justify-center">
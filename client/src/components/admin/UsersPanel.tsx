@@ -1,7 +1,8 @@
 /**
  * UsersPanel - User management panel for admin dashboard
  *
- * Provides user listing with search, pagination, and ban/unban functionality.
+ * Provides user listing with search, pagination, ban/unban, suspension,
+ * forced logout, password resets and system admin grants.
  * Actions require session elevation (two-tier privilege model).
  */
 
@@ -29,6 +30,11 @@ import {
   Square,
   CheckSquare,
   Trash2,
+  PauseCircle,
+  LogOut,
+  KeyRound,
+  Shield,
+  ShieldOff,
 } from "lucide-solid";
 import {
   adminState,
@@ -37,6 +43,11 @@ import {
   loadUserDetails,
   banUser,
   unbanUser,
+  suspendUser,
+  unsuspendUser,
+  revokeUserSessions,
+  sendUserPasswordReset,
+  setUserSystemAdmin,
   deleteUser,
   searchUsers,
   toggleUserSelection,
@@ -59,6 +70,8 @@ const UsersPanel: Component = () => {
   const [searchQuery, setSearchQuery] = createSignal("");
   const [banReason, setBanReason] = createSignal("");
   const [showBanDialog, setShowBanDialog] = createSignal(false);
+  const [suspendReason, setSuspendReason] = createSignal("");
+  const [showSuspendDialog, setShowSuspendDialog] = createSignal(false);
   const [showBulkBanDialog, setShowBulkBanDialog] = createSignal(false);
   const [bulkBanReason, setBulkBanReason] = createSignal("");
   const [showDeleteDialog, setShowDeleteDialog] = createSignal(false);
@@ -205,6 +218,117 @@ const UsersPanel: Component = () => {
     }
   };
 
+  // Handle suspend action
+  const handleSuspend = async () => {
+    const user = selectedUser();
+    if (!user || !suspendReason().trim()) return;
+
+    setActionLoading(true);
+    try {
+      const success = await suspendUser(user.id, suspendReason().trim());
+      if (success) {
+        setShowSuspendDialog(false);
+        setSuspendReason("");
+        showToast({
+          type: "success",
+          title: "User suspended",
+          message: `@${user.username} has been suspended and signed out`,
+          duration: 3000,
+        });
+      }
+    } finally {
+      setActionLoading(false);
+    }
+  };
+
+  // Handle unsuspend action
+  const handleUnsuspend = async () => {
+    const user = selectedUser();
+    if (!user) return;
+
+    setActionLoading(true);
+    try {
+      const success = await unsuspendUser(user.id);
+      if (success) {
+        showToast({
+          type: "success",
+          title: "Suspension lifted",
+          message: `@${user.username} can sign in again`,
+          duration: 3000,
+        });
+      }
+    } finally {
+      setActionLoading(false);
+    }
+  };
+
+  // Handle forced logout
+  const handleRevokeSessions = async () => {
+    const user = selectedUser();
+    if (!user) return;
+
+    setActionLoading(true);
+    try {
+      const revoked = await revokeUserSessions(user.id);
+      if (revoked !== null) {
+        showToast({
+          type: "success",
+          title: "Sessions revoked",
+          message: `@${user.username} has been signed out everywhere`,
+          duration: 3000,
+        });
+      }
+    } finally {
+      setActionLoading(false);
+    }
+  };
+
+  // Handle password reset email
+  const handlePasswordReset = async () => {
+    const user = selectedUser();
+    if (!user) return;
+
+    setActionLoading(true);
+    try {
+      const success = await sendUserPasswordReset(user.id);
+      if (success) {
+        showToast({
+          type: "success",
+          title: "Password reset sent",
+          message: `A reset code was emailed to @${user.username}`,
+          duration: 3000,
+        });
+      }
+    } finally {
+      setActionLoading(false);
+    }
+  };
+
+  // Handle system admin grant/revoke
+  const handleToggleSystemAdmin = async () => {
+    const user = selectedUser();
+    const details = adminState.selectedUserDetails;
+    if (!user || !details) return;
+
+    const grant = !details.is_system_admin;
+    setActionLoading(true);
+    try {
+      const success = await setUserSystemAdmin(user.id, grant);
+      if (success) {
+        showToast({
+          type: "success",
+          title: grant ? "Admin granted" : "Admin revoked",
+          message: grant
+            ? `@${user.username} is now a system admin`
+            : `@${user.username} is no longer a system admin`,
+          duration: 3000,
+        });
+      }
+    } finally {
+      setActionLoading(false);
+    }
+  };
+
   // Handle delete action
   const handleDelete = async () => {
     const user = selectedUser();
@@ -481,10 +605,20 @@ const UsersPanel: Component = () => {
                     <Show
                       when={user.is_banned}
                       fallback={
-                        <span class="inline-flex items-center gap-1 px-2 py-0.5 rounded-full text-xs font-medium bg-status-success/20 text-status-success">
-                          <CheckCircle class="w-3 h-3" />
-                          Active
-                        </span>
+                        <Show
+                          when={user.suspended_at}
+                          fallback={
+                            <span class="inline-flex items-center gap-1 px-2 py-0.5 rounded-full text-xs font-medium bg-status-success/20 text-status-success">
+                              <CheckCircle class="w-3 h-3" />
+                              Active
+                            </span>
+                          }
+                        >
+                          <span class="inline-flex items-center gap-1 px-2 py-0.5 rounded-full text-xs font-medium bg-status-warning/20 text-status-warning">
+                            <PauseCircle class="w-3 h-3" />
+                            Suspended
+                          </span>
+                        </Show>
                       }
                     >
                       <span class="inline-flex items-center gap-1 px-2 py-0.5 rounded-full text-xs font-medium bg-status-error/20 text-status-error">
@@ -590,10 +724,20 @@ const UsersPanel: Component = () => {
                     <Show
                       when={user().is_banned}
                       fallback={
-                        <span class="inline-flex items-center gap-1 px-2 py-0.5 rounded-full text-xs font-medium bg-status-success/20 text-status-success">
-                          <CheckCircle class="w-3 h-3" />
-                          Active
-                        </span>
+                        <Show
+                          when={user().suspended_at}
+                          fallback={
+                            <span class="inline-flex items-center gap-1 px-2 py-0.5 rounded-full text-xs font-medium bg-status-success/20 text-status-success">
+                              <CheckCircle class="w-3 h-3" />
+                              Active
+                            </span>
+                          }
+                        >
+                          <span class="inline-flex items-center gap-1 px-2 py-0.5 rounded-full text-xs font-medium bg-status-warning/20 text-status-warning">
+                            <PauseCircle class="w-3 h-3" />
+                            Suspended
+                          </span>
+                        </Show>
                       }
                     >
                       <span class="inline-flex items-center gap-1 px-2 py-0.5 rounded-full text-xs font-medium bg-status-error/20 text-status-error">
@@ -604,6 +748,27 @@ const UsersPanel: Component = () => {
                   </div>
                 </div>
 
+                {/* Suspension - from user details */}
+                <Show when={adminState.selectedUserDetails?.suspended_at}>
+                  {(suspendedAt) => (
+                    <div class="space-y-1">
+                      <div class="text-xs font-medium text-text-secondary uppercase tracking-wide">
+                        Suspended
+                      </div>
+                      <div class="text-sm text-text-primary">
+                        {formatDate(suspendedAt())}
+                      </div>
+                      <Show
+                        when={adminState.selectedUserDetails?.suspension_reason}
+                      >
+                        <div class="text-xs text-text-secondary break-words">
+                          {adminState.selectedUserDetails?.suspension_reason}
+                        </div>
+                      </Show>
+                    </div>
+                  )}
+                </Show>
+
                 {/* Last Login - from user details */}
                 <div class="space-y-1">
                   <div class="text-xs font-medium text-text-secondary uppercase tracking-wide">
@@ -624,6 +789,29 @@ const UsersPanel: Component = () => {
                   </div>
                 </div>
 
+                {/* Username History - from user details */}
+                <Show
+                  when={adminState.selectedUserDetails?.username_history.length}
+                >
+                  <div class="space-y-1">
+                    <div class="text-xs font-medium text-text-secondary uppercase tracking-wide">
+                      Previous Usernames
+                    </div>
+                    <For each={adminState.selectedUserDetails?.username_history}>
+                      {(change) => (
+                        <div class="flex items-center justify-between gap-2 text-sm">
+                          <span class="text-text-primary font-mono truncate">
+                            @{change.old_username}
+                          </span>
+                          <span class="text-xs text-text-secondary flex-shrink-0">
+                            until {formatDate(change.changed_at)}
+                          </span>
+                        </div>
+                      )}
+                    </For>
+                  </div>
+                </Show>
+
                 {/* Guild Memberships - from user details */}
                 <div class="space-y-2">
                   <div class="text-xs font-medium text-text-secondary uppercase tracking-wide">
@@ -710,6 +898,70 @@ const UsersPanel: Component = () => {
                   </button>
                 </Show>
 
+                <Show
+                  when={user().suspended_at}
+                  fallback={
+                    <button
+                      onClick={() => setShowSuspendDialog(true)}
+                      disabled={!adminState.isElevated || actionLoading()}
+                      class="w-full flex items-center justify-center gap-2 px-4 py-2 rounded-lg border border-status-warning/50 text-status-warning font-medium transition-colors hover:bg-status-warning/10 disabled:opacity-50 disabled:cursor-not-allowed"
+                    >
+                      <PauseCircle class="w-4 h-4" />
+                      Suspend User
+                    </button>
+                  }
+                >
+                  <button
+                    onClick={handleUnsuspend}
+                    disabled={!adminState.isElevated || actionLoading()}
+                    class="w-full flex items-center justify-center gap-2 px-4 py-2 rounded-lg border border-status-success/50 text-status-success font-medium transition-colors hover:bg-status-success/10 disabled:opacity-50 disabled:cursor-not-allowed"
+                  >
+                    <CheckCircle class="w-4 h-4" />
+                    Lift Suspension
+                  </button>
+                </Show>
+
+                <button
+                  onClick={handleRevokeSessions}
+                  disabled={!adminState.isElevated || actionLoading()}
+                  class="w-full flex items-center justify-center gap-2 px-4 py-2 rounded-lg bg-white/10 text-text-primary font-medium transition-colors hover:bg-white/20 disabled:opacity-50 disabled:cursor-not-allowed"
+                >
+                  <LogOut class="w-4 h-4" />
+                  Force Logout
+                </button>
+
+                <button
+                  onClick={handlePasswordReset}
+                  disabled={!adminState.isElevated || actionLoading()}
+                  class="w-full flex items-center justify-center gap-2 px-4 py-2 rounded-lg bg-white/10 text-text-primary font-medium transition-colors hover:bg-white/20 disabled:opacity-50 disabled:cursor-not-allowed"
+                >
+                  <KeyRound class="w-4 h-4" />
+                  Send Password Reset
+                </button>
+
+                <Show when={adminState.selectedUserDetails}>
+                  {(details) => (
+                    <button
+                      onClick={handleToggleSystemAdmin}
+                      disabled={!adminState.isElevated || actionLoading()}
+                      class="w-full flex items-center justify-center gap-2 px-4 py-2 rounded-lg bg-white/10 text-text-primary font-medium transition-colors hover:bg-white/20 disabled:opacity-50 disabled:cursor-not-allowed"
+                    >
+                      <Show
+                        when={details().is_system_admin}
+                        fallback={
+                          <>
+                            <Shield class="w-4 h-4" />
+                            Grant System Admin
+                          </>
+                        }
+                      >
+                        <ShieldOff class="w-4 h-4" />
+                        Revoke System Admin
+                      </Show>
+                    </button>
+                  )}
+                </Show>
+
                 <button
                   onClick={() => setShowDeleteDialog(true)}
                   disabled={!adminState.isElevated || actionLoading()}
@@ -785,6 +1037,69 @@ const UsersPanel: Component = () => {
         </div>
       </Show>
 
+      {/* Suspend Dialog */}
+      <Show when={showSuspendDialog()}>
+        <div class="fixed inset-0 z-50 flex items-center justify-center">
+          {/* Backdrop */}
+          <div
+            class="absolute inset-0 bg-black/60 backdrop-blur-sm"
+            onClick={() => setShowSuspendDialog(false)}
+          />
+
+          {/* Dialog */}
+          <div
+            class="relative rounded-xl border border-white/10 w-[400px] shadow-2xl animate-[fadeIn_0.15s_ease-out]"
+            style="background-color: var(--color-surface-layer1)"
+          >
+            <div class="p-5 space-y-4">
+              <h3 class="text-lg font-bold text-text-primary">Suspend User</h3>
+
+              <p class="text-sm text-text-secondary">
+                Suspend{" "}
+                <span class="font-medium text-text-primary">
+                  @{selectedUser()?.username}
+                </span>
+                ? They will be signed out everywhere and unable to sign in
+                until the suspension is lifted.
+              </p>
+
+              <div class="space-y-2">
+                <label class="text-sm font-medium text-text-secondary">
+                  Reason for suspension
+                </label>
+                <textarea
+                  value={suspendReason()}
+                  onInput={(e) => setSuspendReason(e.currentTarget.value)}
+                  placeholder="Enter reason..."
+                  rows={3}
+                  maxLength={500}
+                  class="w-full px-3 py-2 rounded-lg bg-white/5 border border-white/10 text-text-primary placeholder-text-secondary/50 focus:outline-none focus:border-accent-primary text-sm resize-none"
+                />
+              </div>
+
+              <div class="flex gap-3 pt-2">
+                <button
+                  onClick={() => {
+                    setShowSuspendDialog(false);
+                    setSuspendReason("");
+                  }}
+                  class="flex-1 px-4 py-2 rounded-lg bg-white/10 text-text-primary font-medium transition-colors hover:bg-white/20"
+                >
+                  Cancel
+                </button>
+                <button
+                  onClick={handleSuspend}
+                  disabled={!suspendReason().trim() || actionLoading()}
+                  class="flex-1 px-4 py-2 rounded-lg bg-status-warning text-white font-medium transition-colors hover:bg-status-warning/90 disabled:opacity-50 disabled:cursor-not-allowed"
+                >
+                  {actionLoading() ? "Suspending..." : "Confirm Suspension"}
+                </button>
+              </div>
+            </div>
+          </div>
+        </div>
+      </Show>
+
       {/* Bulk Ban Dialog */}
       <Show when={showBulkBanDialog()}>
         <div class="fixed inset-0 z-50 flex items-center justify-center">
@@ -0,0 +1,147 @@
+/**
+ * Device Link Login
+ *
+ * Signs in through a device that is already logged in: shows a QR code for
+ * that device to approve and polls until it does.
+ */
+
+import { Component, createSignal, onCleanup, onMount, Show } from "solid-js";
+import QRCode from "qrcode";
+import { Smartphone } from "lucide-solid";
+import { startDeviceLink, type DeviceLinkStarted } from "@/lib/tauri";
+import { deviceLinkUri, formatDeviceLinkCode } from "@/lib/deviceLink";
+import { loginWithDeviceLink } from "@/stores/auth";
+
+const POLL_INTERVAL_MS = 3000;
+
+interface DeviceLinkLoginProps {
+  serverUrl: string;
+  onSignedIn: () => void;
+  onCancel: () => void;
+}
+
+const DeviceLinkLogin: Component<DeviceLinkLoginProps> = (props) => {
+  const [link, setLink] = createSignal<DeviceLinkStarted | null>(null);
+  const [qrDataUrl, setQrDataUrl] = createSignal("");
+  const [secondsLeft, setSecondsLeft] = createSignal(0);
+  const [error, setError] = createSignal("");
+
+  let pollTimer: ReturnType<typeof setInterval> | undefined;
+  let countdownTimer: ReturnType<typeof setInterval> | undefined;
+  let polling = false;
+
+  const stop = () => {
+    clearInterval(pollTimer);
+    clearInterval(countdownTimer);
+  };
+
+  const poll = async () => {
+    const current = link();
+    if (!current || polling) return;
+    polling = true;
+    try {
+      const user = await loginWithDeviceLink(
+        props.serverUrl,
+        current.code,
+        current.secret,
+      );
+      if (user) {
+        stop();
+        props.onSignedIn();
+      }
+    } catch (err) {
+      stop();
+      setLink(null);
+      setError(err instanceof Error ? err.message : String(err));
+    } finally {
+      polling = false;
+    }
+  };
+
+  const start = async () => {
+    stop();
+    setError("");
+    setLink(null);
+    try {
+      const started = await startDeviceLink(props.serverUrl);
+      // Generated locally so the code is never sent to a third party
+      setQrDataUrl(
+        await QRCode.toDataURL(deviceLinkUri(started.code), {
+          width: 200,
+          margin: 1,
+        }),
+      );
+      setLink(started);
+      setSecondsLeft(started.expires_in);
+
+      pollTimer = setInterval(poll, POLL_INTERVAL_MS);
+      countdownTimer = setInterval(() => {
+        const left = secondsLeft() - 1;
+        setSecondsLeft(left);
+        if (left <= 0) {
+          stop();
+          setLink(null);
+          setError("The code expired. Generate a new one to try again.");
+        }
+      }, 1000);
+    } catch (err) {
+      setError(err instanceof Error ? err.message : String(err));
+    }
+  };
+
+  onMount(start);
+  onCleanup(stop);
+
+  return (
+    <div class="space-y-4">
+      <div class="flex items-center gap-3 p-3 bg-accent-primary/10 border border-accent-primary/20 rounded-lg">
+        <Smartphone class="w-5 h-5 text-accent-primary flex-shrink-0" />
+        <p class="text-sm text-text-secondary">
+          On a device where you are logged in, open Settings → Security →
+          Link a Device and scan this code or type it in.
+        </p>
+      </div>
+
+      <Show when={link()}>
+        {(current) => (
+          <div class="flex flex-col items-center gap-3">
+            <img
+              src={qrDataUrl()}
+              alt="Device link QR code"
+              class="w-48 h-48 rounded-lg bg-white p-2"
+              data-testid="device-link-qr"
+            />
+            <p class="font-mono text-lg tracking-widest text-text-primary">
+              {formatDeviceLinkCode(current().code)}
+            </p>
+            <p class="text-xs text-text-muted">
+              Waiting for approval · expires in {secondsLeft()}s
+            </p>
+          </div>
+        )}
+      </Show>
+
+      <Show when={error()}>
+        <div
+          class="p-3 rounded-md text-sm"
+          style="background-color: var(--color-error-bg); border: 1px solid var(--color-error-border); color: var(--color-error-text)"
+        >
+          {error()}
+        </div>
+        <button type="button" class="btn-primary w-full" onClick={start}>
+          Generate a new code
+        </button>
+      </Show>
+
+      <button
+        type="button"
+        onClick={props.onCancel}
+        class="w-full text-sm text-text-secondary hover:text-text-primary transition-colors"
+      >
+        Back to login
+      </button>
+    </div>
+  );
+};
+
+export default DeviceLinkLogin;
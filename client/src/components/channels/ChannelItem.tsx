@@ -22,6 +22,7 @@ import {
   Copy,
 } from "lucide-solid";
 import type { ChannelWithUnread } from "@/lib/types";
+import { isVoiceChannelType } from "@/lib/utils";
 import { isInChannel, getParticipants, voiceState } from "@/stores/voice";
 import { authState } from "@/stores/auth";
 import { isChannelMuted, setChannelNotificationLevel } from "@/stores/sound";
@@ -45,7 +46,7 @@ interface ChannelItemProps {
 }
 
 const ChannelItem: Component<ChannelItemProps> = (props) => {
-  const isVoice = () => props.channel.channel_type === "voice";
+  const isVoice = () => isVoiceChannelType(props.channel.channel_type);
   const isConnected = () => isInChannel(props.channel.id);
   // Also show as "active" when connecting to this channel
   const isConnecting = () =>
@@ -48,6 +48,7 @@ import { joinVoice, leaveVoice, isInChannel } from "@/stores/voice";
 import { memberHasPermission } from "@/stores/permissions";
 import { PermissionBits } from "@/lib/permissionConstants";
 import type { ChannelWithUnread, ChannelCategory } from "@/lib/types";
+import { isVoiceChannelType } from "@/lib/utils";
 import { showToast } from "@/components/ui/Toast";
 import CategoryHeader from "./CategoryHeader";
 import ChannelItem from "./ChannelItem";
@@ -376,7 +377,7 @@ const ChannelList: Component = () => {
     channel: ChannelWithUnread,
     categoryId: string | null,
   ) => {
-    const isVoice = channel.channel_type === "voice";
+    const isVoice = isVoiceChannelType(channel.channel_type);
     const draggable = canManageChannels();
 
     return (
@@ -5,7 +5,7 @@
  */
 
 import { Component, createSignal, Show } from "solid-js";
-import { X, Hash, Mic } from "lucide-solid";
+import { X, Hash, Mic, Radio } from "lucide-solid";
 import { createChannel } from "@/stores/channels";
 import { Portal } from "solid-js/web";
 import { showToast } from "@/components/ui/Toast";
@@ -21,7 +21,9 @@ interface CreateChannelModalProps {
 
 const CreateChannelModal: Component<CreateChannelModalProps> = (props) => {
   const [name, setName] = createSignal("");
-  const [channelType, setChannelType] = createSignal<"text" | "voice">(
+  const [channelType, setChannelType] = createSignal<
+    "text" | "voice" | "stage"
+  >(
     props.initialType || "text",
   );
   const [isCreating, setIsCreating] = createSignal(false);
@@ -143,6 +145,30 @@ const CreateChannelModal: Component<CreateChannelModalProps> = (props) => {
                       </div>
                     </div>
                   </button>
+                  <button
+                    type="button"
+                    onClick={() => setChannelType("stage")}
+                    class="flex-1 flex items-center gap-3 p-4 rounded-xl border-2 transition-all"
+                    classList={{
+                      "border-accent-primary bg-accent-primary/10":
+                        channelType() === "stage",
+                      "border-white/10 hover:border-white/20":
+                        channelType() !== "stage",
+                    }}
+                  >
+                    <div
+                      class="w-10 h-10 rounded-lg flex items-center justify-center"
+                      style="background-color: var(--color-surface-layer2)"
+                    >
+                      <Radio size={20} class="text-text-primary" />
+                    </div>
+                    <div class="text-left">
+                      <div class="font-semibold text-text-primary">Stage</div>
+                      <div class="text-xs text-text-secondary">
+                        Speakers talk, others raise a hand
+                      </div>
+                    </div>
+                  </button>
                 </div>
               </div>
 
@@ -24,7 +24,9 @@ const PAGE_SIZE = 12;
 
 const DiscoveryView: Component = () => {
   const [query, setQuery] = createSignal("");
-  const [sort, setSort] = createSignal<"members" | "newest">("members");
+  const [sort, setSort] = createSignal<"members" | "trending" | "newest">(
+    "members",
+  );
   const [guilds, setGuilds] = createSignal<DiscoverableGuild[]>([]);
   const [total, setTotal] = createSignal(0);
   const [offset, setOffset] = createSignal(0);
@@ -144,6 +146,21 @@ const DiscoveryView: Component = () => {
             >
               Popular
             </button>
+            <button
+              onClick={() => {
+                setSort("trending");
+                setOffset(0);
+              }}
+              class="px-3 py-2 transition-colors"
+              aria-pressed={sort() === "trending"}
+              classList={{
+                "bg-accent-primary text-white": sort() === "trending",
+                "bg-surface-layer2 text-text-secondary hover:text-text-primary":
+                  sort() !== "trending",
+              }}
+            >
+              Trending
+            </button>
             <button
               onClick={() => {
                 setSort("newest");
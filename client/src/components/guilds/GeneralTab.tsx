@@ -1,5 +1,6 @@
 /**
- * GeneralTab - General guild settings (threads, discovery, tags, banner)
+ * GeneralTab - General guild settings (icon and banner, threads, system and
+ * rules channels, discovery, tags)
  */
 
 import {
@@ -7,12 +8,28 @@ import {
   createSignal,
   createMemo,
   For,
+  Match,
   Show,
+  Switch,
   onMount,
 } from "solid-js";
-import { X } from "lucide-solid";
-import { getGuildSettings, updateGuildSettings } from "@/lib/tauri";
+import { Upload, X } from "lucide-solid";
+import {
+  getGuildSettings,
+  getUploadLimitText,
+  updateGuildSettings,
+} from "@/lib/tauri";
+import type { GuildSettings } from "@/lib/types";
 import { showToast } from "@/components/ui/Toast";
+import { authState } from "@/stores/auth";
+import { channelsState } from "@/stores/channels";
+import {
+  guildsState,
+  isGuildOwner,
+  removeGuildImage,
+  updateGuildChannels,
+  uploadGuildImage,
+} from "@/stores/guilds";
 
 interface GeneralTabProps {
   guildId: string;
@@ -20,36 +37,36 @@ interface GeneralTabProps {
 
 const TAG_REGEX = /^[a-zA-Z0-9-]+$/;
 const MAX_TAGS = 5;
+const IMAGE_TYPES = "image/png,image/jpeg,image/gif,image/webp";
 
 const GeneralTab: Component<GeneralTabProps> = (props) => {
   const [threadsEnabled, setThreadsEnabled] = createSignal(true);
   const [discoverable, setDiscoverable] = createSignal(false);
+  const [discoveryReview, setDiscoveryReview] = createSignal<
+    Pick<GuildSettings, "discovery_status" | "discovery_rejection_reason">
+  >({ discovery_status: "none", discovery_rejection_reason: null });
   const [tags, setTags] = createSignal<string[]>([]);
   const [tagInput, setTagInput] = createSignal("");
-  const [bannerUrl, setBannerUrl] = createSignal("");
   const [loading, setLoading] = createSignal(true);
   const [savingCount, setSavingCount] = createSignal(0);
   const saving = () => savingCount() > 0;
-  const [bannerLoadError, setBannerLoadError] = createSignal(false);
 
-  const trimmedBannerUrl = createMemo(() => bannerUrl().trim());
-  const isValidBannerUrl = createMemo(() => {
-    const url = trimmedBannerUrl();
-    if (!url) return false;
-    try {
-      return new URL(url).protocol === "https:";
-    } catch {
-      return false;
-    }
-  });
+  const guild = () => guildsState.guilds.find((g) => g.id === props.guildId);
+  const isOwner = () =>
+    isGuildOwner(props.guildId, authState.user?.id || "");
+  const textChannels = createMemo(() =>
+    channelsState.channels.filter(
+      (c) => c.guild_id === props.guildId && c.channel_type === "text",
+    ),
+  );
 
   onMount(async () => {
     try {
       const settings = await getGuildSettings(props.guildId);
       setThreadsEnabled(settings.threads_enabled);
       setDiscoverable(settings.discoverable);
+      setDiscoveryReview(settings);
       setTags(settings.tags ?? []);
-      setBannerUrl(settings.banner_url ?? "");
     } catch (err) {
       console.error("Failed to load guild settings:", err);
       showToast({
@@ -68,7 +85,7 @@ const GeneralTab: Component<GeneralTabProps> = (props) => {
   ) => {
     setSavingCount((c) => c + 1);
     try {
-      await updateGuildSettings(props.guildId, patch);
+      return await updateGuildSettings(props.guildId, patch);
     } catch (err) {
       console.error("Failed to update guild settings:", err);
       showToast({
@@ -83,6 +100,26 @@ const GeneralTab: Component<GeneralTabProps> = (props) => {
     }
   };
 
+  const handleChannelChange = async (
+    field: "system_channel_id" | "rules_channel_id",
+    value: string,
+  ) => {
+    setSavingCount((c) => c + 1);
+    try {
+      await updateGuildChannels(props.guildId, { [field]: value || null });
+    } catch (err) {
+      console.error("Failed to update guild channels:", err);
+      showToast({
+        type: "error",
+        title: "Update Failed",
+        message: "Could not update the channel.",
+        duration: 8000,
+      });
+    } finally {
+      setSavingCount((c) => c - 1);
+    }
+  };
+
   const handleToggleThreads = async () => {
     const newValue = !threadsEnabled();
     try {
@@ -96,8 +133,9 @@ const GeneralTab: Component<GeneralTabProps> = (props) => {
   const handleToggleDiscoverable = async () => {
     const newValue = !discoverable();
     try {
-      await saveSetting({ discoverable: newValue });
+      const settings = await saveSetting({ discoverable: newValue });
       setDiscoverable(newValue);
+      setDiscoveryReview(settings);
     } catch (_: unknown) {
       // error already shown by saveSetting
     }
@@ -166,30 +204,123 @@ const GeneralTab: Component<GeneralTabProps> = (props) => {
     }
   };
 
-  const handleBannerSave = async () => {
-    const url = bannerUrl().trim() || null;
-    if (url && !isValidBannerUrl()) {
+  const handleImageSelect = async (kind: "icon" | "banner", e: Event) => {
+    const input = e.currentTarget as HTMLInputElement;
+    const file = input.files?.[0];
+    input.value = "";
+    if (!file) return;
+
+    setSavingCount((c) => c + 1);
+    try {
+      await uploadGuildImage(props.guildId, kind, file);
+    } catch (err) {
+      console.error(`Failed to upload guild ${kind}:`, err);
       showToast({
         type: "error",
-        title: "Invalid URL",
-        message: "Banner URL must use HTTPS.",
+        title: "Upload Failed",
+        message: err instanceof Error ? err.message : `Could not upload ${kind}.`,
+        duration: 8000,
       });
-      return;
+    } finally {
+      setSavingCount((c) => c - 1);
     }
+  };
+
+  const handleImageRemove = async (kind: "icon" | "banner") => {
+    setSavingCount((c) => c + 1);
     try {
-      await saveSetting({ banner_url: url });
+      await removeGuildImage(props.guildId, kind);
+    } catch (err) {
+      console.error(`Failed to remove guild ${kind}:`, err);
       showToast({
-        type: "success",
-        title: "Saved",
-        message: "Banner URL updated.",
+        type: "error",
+        title: "Update Failed",
+        message: `Could not remove ${kind}.`,
+        duration: 8000,
       });
-    } catch (_: unknown) {
-      // error already shown
+    } finally {
+      setSavingCount((c) => c - 1);
     }
   };
 
   return (
     <div class="p-6 space-y-6">
+      {/* Icon & Banner */}
+      <div>
+        <h3 class="text-sm font-semibold text-text-primary uppercase tracking-wide mb-4">
+          Icon &amp; Banner
+        </h3>
+        <div class="p-4 bg-surface-layer2 rounded-xl border border-white/5 space-y-4">
+          <div class="text-xs text-text-secondary">
+            PNG, JPEG, GIF or WebP up to {getUploadLimitText("avatar")}. Icons
+            must be at least 128×128 and are cropped square; banners must be
+            at least 600×200 and are cropped to 3:1.
+          </div>
+          <For
+            each={
+              [
+                { kind: "icon", label: "Server Icon" },
+                { kind: "banner", label: "Banner" },
+              ] as const
+            }
+          >
+            {(option) => {
+              const url = () =>
+                option.kind === "icon" ? guild()?.icon_url : guild()?.banner_url;
+              return (
+                <div class="flex items-center gap-4">
+                  <div
+                    class="flex-shrink-0 overflow-hidden bg-surface-layer1 border border-white/5"
+                    classList={{
+                      "w-16 h-16 rounded-2xl": option.kind === "icon",
+                      "w-48 h-16 rounded-lg": option.kind === "banner",
+                    }}
+                  >
+                    <Show when={url()}>
+                      <img
+                        src={url()!}
+                        alt={option.label}
+                        class="w-full h-full object-cover"
+                      />
+                    </Show>
+                  </div>
+                  <div class="flex-1">
+                    <div class="text-sm font-medium text-text-primary">
+                      {option.label}
+                    </div>
+                    <div class="flex items-center gap-2 mt-2">
+                      <label
+                        class="flex items-center gap-1.5 px-3 py-1.5 text-xs font-medium rounded-lg bg-accent-primary text-white hover:bg-accent-hover transition-colors cursor-pointer"
+                        classList={{ "opacity-50 pointer-events-none": saving() }}
+                      >
+                        <Upload class="w-3.5 h-3.5" />
+                        Upload
+                        <input
+                          type="file"
+                          accept={IMAGE_TYPES}
+                          class="hidden"
+                          onChange={(e) => handleImageSelect(option.kind, e)}
+                          disabled={saving()}
+                        />
+                      </label>
+                      <Show when={url()}>
+                        <button
+                          onClick={() => handleImageRemove(option.kind)}
+                          disabled={saving()}
+                          class="px-3 py-1.5 text-xs font-medium rounded-lg bg-white/5 text-text-secondary hover:text-text-primary disabled:opacity-50 transition-colors"
+                        >
+                          Remove
+                        </button>
+                      </Show>
+                    </div>
+                  </div>
+                </div>
+              );
+            }}
+          </For>
+        </div>
+      </div>
+
       <div>
         <h3 class="text-sm font-semibold text-text-primary uppercase tracking-wide mb-4">
           General
@@ -230,6 +361,60 @@ const GeneralTab: Component<GeneralTabProps> = (props) => {
         </div>
       </div>
 
+      {/* Channels Section (owner only, like other guild profile fields) */}
+      <Show when={isOwner()}>
+        <div>
+          <h3 class="text-sm font-semibold text-text-primary uppercase tracking-wide mb-4">
+            Channels
+          </h3>
+          <div class="p-4 bg-surface-layer2 rounded-xl border border-white/5 space-y-4">
+            <For
+              each={
+                [
+                  {
+                    field: "system_channel_id",
+                    label: "System Channel",
+                    desc: "Join announcements are posted here, and members land here when opening the server.",
+                  },
+                  {
+                    field: "rules_channel_id",
+                    label: "Rules Channel",
+                    desc: "Shown first to members who just joined.",
+                  },
+                ] as const
+              }
+            >
+              {(option) => (
+                <div>
+                  <label class="text-sm font-medium text-text-primary block">
+                    {option.label}
+                  </label>
+                  <div class="text-xs text-text-secondary mt-1 mb-2">
+                    {option.desc}
+                  </div>
+                  <select
+                    value={guild()?.[option.field] ?? ""}
+                    onChange={(e) =>
+                      handleChannelChange(option.field, e.currentTarget.value)
+                    }
+                    disabled={saving()}
+                    class="w-full px-3 py-2 rounded-lg border border-white/10 text-text-primary disabled:opacity-50"
+                    style="background-color: var(--color-surface-layer1)"
+                  >
+                    <option value="">None</option>
+                    <For each={textChannels()}>
+                      {(channel) => (
+                        <option value={channel.id}>#{channel.name}</option>
+                      )}
+                    </For>
+                  </select>
+                </div>
+              )}
+            </For>
+          </div>
+        </div>
+      </Show>
+
       {/* Discovery Section */}
       <div>
         <h3 class="text-sm font-semibold text-text-primary uppercase tracking-wide mb-4">
@@ -246,6 +431,31 @@ const GeneralTab: Component<GeneralTabProps> = (props) => {
               Allow this server to appear in the public server browser. Anyone
               can find and join without an invite code.
             </div>
+            <Show when={discoverable()}>
+              <Switch>
+                <Match when={discoveryReview().discovery_status === "pending"}>
+                  <div class="text-xs text-text-secondary mt-1">
+                    Waiting for review before the server is listed.
+                  </div>
+                </Match>
+                <Match
+                  when={
+                    discoveryReview().discovery_status === "rejected" ||
+                    discoveryReview().discovery_status === "delisted"
+                  }
+                >
+                  <div class="text-xs text-accent-danger mt-1">
+                    {discoveryReview().discovery_status === "rejected"
+                      ? "Listing rejected"
+                      : "Removed from discovery"}
+                    {discoveryReview().discovery_rejection_reason
+                      ? `: ${discoveryReview().discovery_rejection_reason}`
+                      : ""}
+                    . Turn discovery off and on again to resubmit.
+                  </div>
+                </Match>
+              </Switch>
+            </Show>
           </div>
           <button
             onClick={handleToggleDiscoverable}
@@ -321,55 +531,6 @@ const GeneralTab: Component<GeneralTabProps> = (props) => {
               </div>
             </Show>
           </div>
-
-          {/* Banner URL */}
-          <div class="mt-4 p-4 bg-surface-layer2 rounded-xl border border-white/5">
-            <div class="text-sm font-medium text-text-primary mb-1">
-              Banner Image
-            </div>
-            <div class="text-xs text-text-secondary mb-3">
-              URL to a banner image displayed on your server's discovery card.
-            </div>
-            <div class="flex items-center gap-2">
-              <input
-                type="url"
-                placeholder="https://example.com/banner.png"
-                value={bannerUrl()}
-                onInput={(e) => {
-                  setBannerUrl(e.currentTarget.value);
-                  setBannerLoadError(false);
-                }}
-                class="flex-1 px-3 py-1.5 text-sm rounded-lg bg-surface-layer1 border border-white/5 text-text-primary placeholder-text-secondary focus:outline-none focus:border-accent-primary/50"
-              />
-              <button
-                onClick={handleBannerSave}
-                disabled={saving()}
-                class="px-3 py-1.5 text-xs font-medium rounded-lg bg-accent-primary text-white hover:bg-accent-hover disabled:opacity-50 transition-colors"
-              >
-                Save
-              </button>
-            </div>
-            <Show when={isValidBannerUrl()}>
-              <div class="mt-3 h-20 rounded-lg overflow-hidden border border-white/5">
-                <Show
-                  when={!bannerLoadError()}
-                  fallback={
-                    <div class="flex items-center justify-center h-full text-xs text-text-secondary">
-                      Image failed to load
-                    </div>
-                  }
-                >
-                  <img
-                    src={trimmedBannerUrl()}
-                    alt="Banner preview"
-                    class="w-full h-full object-cover"
-                    onError={() => setBannerLoadError(true)}
-                    onLoad={() => setBannerLoadError(false)}
-                  />
-                </Show>
-              </div>
-            </Show>
-          </div>
         </Show>
       </div>
     </div>
@@ -1,5 +1,5 @@
 /**
- * InvitesTab - Invite management for guild owners
+ * InvitesTab - Invite management for members with MANAGE_INVITES
  */
 
 import { Component, createSignal, For, Show, onMount } from "solid-js";
@@ -26,8 +26,18 @@ const EXPIRY_OPTIONS: { value: InviteExpiry; label: string }[] = [
   { value: "never", label: "Never" },
 ];
 
+const MAX_USES_OPTIONS: { value: number | null; label: string }[] = [
+  { value: null, label: "No limit" },
+  { value: 1, label: "1 use" },
+  { value: 5, label: "5 uses" },
+  { value: 10, label: "10 uses" },
+  { value: 25, label: "25 uses" },
+  { value: 100, label: "100 uses" },
+];
+
 const InvitesTab: Component<InvitesTabProps> = (props) => {
   const [expiresIn, setExpiresIn] = createSignal<InviteExpiry>("7d");
+  const [maxUses, setMaxUses] = createSignal<number | null>(null);
   const [isCreating, setIsCreating] = createSignal(false);
   const [copiedCode, setCopiedCode] = createSignal<string | null>(null);
   const [deletingCode, setDeletingCode] = createSignal<string | null>(null);
@@ -41,7 +51,7 @@ const InvitesTab: Component<InvitesTabProps> = (props) => {
   const handleCreate = async () => {
     setIsCreating(true);
     try {
-      await createInvite(props.guildId, expiresIn());
+      await createInvite(props.guildId, expiresIn(), maxUses());
     } catch (err) {
       console.error("Failed to create invite:", err);
     } finally {
@@ -117,6 +127,25 @@ const InvitesTab: Component<InvitesTabProps> = (props) => {
               </For>
             </select>
           </div>
+          <div class="flex-1">
+            <label class="text-xs text-text-secondary mb-1 block">
+              Max uses
+            </label>
+            <select
+              value={maxUses() ?? ""}
+              onChange={(e) =>
+                setMaxUses(
+                  e.currentTarget.value ? Number(e.currentTarget.value) : null,
+                )
+              }
+              class="w-full px-3 py-2 rounded-lg border border-white/10 text-text-primary"
+              style="background-color: var(--color-surface-layer2)"
+            >
+              <For each={MAX_USES_OPTIONS}>
+                {(opt) => <option value={opt.value ?? ""}>{opt.label}</option>}
+              </For>
+            </select>
+          </div>
           <button
             data-testid="create-invite-button"
             onClick={handleCreate}
@@ -156,7 +185,9 @@ const InvitesTab: Component<InvitesTabProps> = (props) => {
                     </code>
                     <div class="text-xs text-text-secondary mt-1">
                       {formatExpiry(invite.expires_at)} &bull;{" "}
-                      {invite.use_count} use{invite.use_count !== 1 ? "s" : ""}
+                      {invite.max_uses !== null
+                        ? `${invite.use_count} / ${invite.max_uses} uses`
+                        : `${invite.use_count} use${invite.use_count !== 1 ? "s" : ""}`}
                     </div>
                   </div>
                   <div class="flex items-center gap-2 ml-3">
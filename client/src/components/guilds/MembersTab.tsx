@@ -11,11 +11,12 @@ import {
   onMount,
 } from "solid-js";
 import { createVirtualizer } from "@/lib/virtualizer";
-import { Search, Crown } from "lucide-solid";
+import { Search, Crown, Clock } from "lucide-solid";
 import {
   guildsState,
   loadGuildMembers,
   getGuildMembers,
+  isMemberTimedOut,
 } from "@/stores/guilds";
 import {
   loadGuildRoles,
@@ -195,6 +196,7 @@ const MembersTab: Component<MembersTabProps> = (props) => {
                           <div
                             data-testid={`members-tab-row-${m().username}`}
                             class="flex items-center gap-3 p-3 rounded-lg hover:bg-white/5 transition-colors group"
+                            classList={{ "opacity-50": isMemberTimedOut(m()) }}
                             onContextMenu={(e) =>
                               showUserContextMenu(e, {
                                 id: m().user_id,
@@ -207,7 +209,7 @@ const MembersTab: Component<MembersTabProps> = (props) => {
                             <div class="relative flex-shrink-0">
                               <div class="w-10 h-10 rounded-full bg-accent-primary/20 flex items-center justify-center">
                                 <Show
-                                  when={m().avatar_url}
+                                  when={m().guild_avatar_url || m().avatar_url}
                                   fallback={
                                     <span class="text-sm font-semibold text-accent-primary">
                                       {m().display_name.charAt(0).toUpperCase()}
@@ -215,7 +217,7 @@ const MembersTab: Component<MembersTabProps> = (props) => {
                                   }
                                 >
                                   <img
-                                    src={m().avatar_url!}
+                                    src={(m().guild_avatar_url || m().avatar_url)!}
                                     alt={m().display_name}
                                     class="w-10 h-10 rounded-full object-cover"
                                   />
@@ -244,6 +246,13 @@ const MembersTab: Component<MembersTabProps> = (props) => {
                                     <Crown class="w-4 h-4 text-yellow-500" />
                                   </span>
                                 </Show>
+                                <Show when={isMemberTimedOut(m())}>
+                                  <span
+                                    title={`Timed out until ${new Date(m().timeout_until!).toLocaleString()}`}
+                                  >
+                                    <Clock class="w-4 h-4 text-text-secondary" />
+                                  </span>
+                                </Show>
                               </div>
                               <div class="text-sm text-text-secondary">
                                 @{m().username}
@@ -2,7 +2,8 @@
  * SafetyTab - Content filter configuration for guild settings
  *
  * Provides:
- * 1. Built-in category toggles (Slurs, Hate Speech, Spam, Abusive Language)
+ * 1. Built-in category toggles (Slurs, Hate Speech, Spam, Abusive Language,
+ *    Links with a link mode)
  * 2. Custom pattern management (keywords + regex)
  * 3. Filter test panel (dry-run)
  * 4. Moderation action log
@@ -31,6 +32,7 @@ import type {
   GuildFilterPattern,
   FilterCategory,
   FilterAction,
+  LinkFilterMode,
   ModerationAction,
   TestFilterResponse,
 } from "@/lib/api/filters";
@@ -56,6 +58,10 @@ const CATEGORY_INFO: Record<string, { label: string; description: string }> = {
     label: "Abusive Language",
     description: "Filters abusive and threatening language",
   },
+  links: {
+    label: "Links",
+    description: "Filters links, invites to other communities, or unlisted domains",
+  },
 };
 
 const ALL_CATEGORIES: FilterCategory[] = [
@@ -63,8 +69,15 @@ const ALL_CATEGORIES: FilterCategory[] = [
   "hate_speech",
   "spam",
   "abusive_language",
+  "links",
 ];
 
+type LocalConfig = {
+  enabled: boolean;
+  action: FilterAction;
+  link_mode?: LinkFilterMode;
+};
+
 const SafetyTab: Component<SafetyTabProps> = (props) => {
   // Category configs
   const [configs, setConfigs] = createSignal<GuildFilterConfig[]>([]);
@@ -73,7 +86,7 @@ const SafetyTab: Component<SafetyTabProps> = (props) => {
 
   // Local state for toggles (before save)
   const [localConfigs, setLocalConfigs] = createSignal<
-    Record<string, { enabled: boolean; action: FilterAction }>
+    Record<string, LocalConfig>
   >({});
 
   // Custom patterns
@@ -115,13 +128,14 @@ const SafetyTab: Component<SafetyTabProps> = (props) => {
       setConfigs(data);
 
       // Initialize local state from server data
-      const local: Record<string, { enabled: boolean; action: FilterAction }> =
-        {};
+      const local: Record<string, LocalConfig> = {};
       for (const cat of ALL_CATEGORIES) {
         const existing = data.find((c) => c.category === cat);
         local[cat] = {
           enabled: existing?.enabled ?? false,
           action: existing?.action ?? "block",
+          link_mode:
+            cat === "links" ? (existing?.link_mode ?? "invites") : undefined,
         };
       }
       setLocalConfigs(local);
@@ -151,6 +165,7 @@ const SafetyTab: Component<SafetyTabProps> = (props) => {
         category: cat,
         enabled: localConfigs()[cat]?.enabled ?? false,
         action: localConfigs()[cat]?.action ?? ("block" as FilterAction),
+        link_mode: localConfigs()[cat]?.link_mode,
       }));
       const updated = await updateFilterConfigs(props.guildId, entries);
       setConfigs(updated);
@@ -175,6 +190,13 @@ const SafetyTab: Component<SafetyTabProps> = (props) => {
     }));
   };
 
+  const setLinkMode = (linkMode: LinkFilterMode) => {
+    setLocalConfigs((prev) => ({
+      ...prev,
+      links: { ...prev.links, link_mode: linkMode },
+    }));
+  };
+
   const handleAddPattern = async () => {
     if (!newPattern().trim()) return;
     setAddingPattern(true);
@@ -257,7 +279,9 @@ const SafetyTab: Component<SafetyTabProps> = (props) => {
       if (!server && local.enabled) return true;
       if (
         server &&
-        (server.enabled !== local.enabled || server.action !== local.action)
+        (server.enabled !== local.enabled ||
+          server.action !== local.action ||
+          (server.link_mode ?? undefined) !== local.link_mode)
       )
         return true;
     }
@@ -371,6 +395,19 @@ const SafetyTab: Component<SafetyTabProps> = (props) => {
                       </p>
                     </div>
                     <div class="flex items-center gap-3">
+                      <Show when={cat === "links"}>
+                        <select
+                          value={local().link_mode ?? "invites"}
+                          onChange={(e) =>
+                            setLinkMode(e.currentTarget.value as LinkFilterMode)
+                          }
+                          class="text-sm px-2 py-1 rounded-lg border border-white/10 bg-transparent text-text-primary"
+                        >
+                          <option value="invites">External Invites</option>
+                          <option value="allowlist">Unlisted Domains</option>
+                          <option value="all">All Links</option>
+                        </select>
+                      </Show>
                       <select
                         value={local().action}
                         onChange={(e) =>
@@ -1,6 +1,7 @@
 import { Component, For, Show, createSignal, onMount } from "solid-js";
 import { getGuildUsage } from "@/lib/tauri";
 import type { GuildUsageStats } from "@/lib/types";
+import { getGuildPerks, loadGuildPerks } from "@/stores/guilds";
 
 interface UsageTabProps {
   guildId: string;
@@ -15,7 +16,10 @@ const UsageTab: Component<UsageTabProps> = (props) => {
     setLoading(true);
     setError(null);
     try {
-      const data = await getGuildUsage(props.guildId);
+      const [data] = await Promise.all([
+        getGuildUsage(props.guildId),
+        loadGuildPerks(props.guildId),
+      ]);
       setUsage(data);
     } catch (err) {
       console.error("Failed to load guild usage stats:", err);
@@ -25,6 +29,10 @@ const UsageTab: Component<UsageTabProps> = (props) => {
     }
   });
 
+  const perks = () => getGuildPerks(props.guildId);
+
+  const formatMiB = (bytes: number) => `${Math.round(bytes / (1024 * 1024))} MB`;
+
   const rows = () => {
     const data = usage();
     if (!data) return [];
@@ -51,6 +59,29 @@ const UsageTab: Component<UsageTabProps> = (props) => {
 
       <Show when={error()}>{(msg) => <p class="text-sm text-red-400">{msg()}</p>}</Show>
 
+      <Show when={!loading() && perks()}>
+        {(p) => (
+          <div
+            data-testid="guild-perks"
+            class="p-4 rounded-xl border border-white/10 bg-surface-layer2 text-sm"
+          >
+            <div class="flex items-center justify-between">
+              <span class="text-text-primary font-medium">Supporter Tier {p().tier}</span>
+              <span class="text-text-secondary">
+                {p().supporter_count} supporter{p().supporter_count === 1 ? "" : "s"}
+                <Show when={p().next_tier_at !== null}>
+                  {" "}· next tier at {p().next_tier_at}
+                </Show>
+              </span>
+            </div>
+            <p class="text-xs text-text-secondary mt-1">
+              Uploads up to {formatMiB(p().max_upload_size)} · Voice up to{" "}
+              {Math.round(p().voice_bitrate / 1000)} kbps
+            </p>
+          </div>
+        )}
+      </Show>
+
       <Show when={!loading() && usage()}>
         <div class="grid grid-cols-1 md:grid-cols-2 gap-3">
           <For each={rows()}>
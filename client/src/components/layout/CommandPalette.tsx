@@ -31,6 +31,7 @@ import { channelsState, selectChannel } from "@/stores/channels";
 import { setShowGlobalSearch } from "@/stores/search";
 import { toggleMute, toggleDeafen, voiceState } from "@/stores/voice";
 import type { Channel } from "@/lib/types";
+import { isVoiceChannelType } from "@/lib/utils";
 
 interface CommandItem {
   id: string;
@@ -56,7 +57,7 @@ const CommandPalette: Component = () => {
         id: channel.id,
         type: "channel",
         label: `# ${channel.name}`,
-        icon: channel.channel_type === "voice" ? Volume2 : Hash,
+        icon: isVoiceChannelType(channel.channel_type) ? Volume2 : Hash,
         action: () => {
           selectChannel(channel.id);
           setIsOpen(false);
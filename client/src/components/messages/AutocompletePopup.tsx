@@ -3,14 +3,17 @@
  *
  * Data wrapper for PopupList that provides user, emoji, channel, and command autocomplete.
  * Handles data fetching and formatting for @user, :emoji:, #channel, and /command suggestions.
+ * Guild @user suggestions come from the server's autocomplete endpoint, with
+ * locally loaded members as the fallback.
  */
 
-import { Component, createMemo } from "solid-js";
+import { Component, createMemo, createResource } from "solid-js";
 import PopupList, { type PopupListItem } from "@/components/ui/PopupList";
 import Avatar from "@/components/ui/Avatar";
 import { Hash, Terminal } from "lucide-solid";
 import { searchEmojis } from "@/lib/emojiData";
 import { emojiState } from "@/stores/emoji";
+import { guildAutocomplete } from "@/lib/tauri";
 import type { GuildMember } from "@/lib/types";
 import type { ChannelWithUnread } from "@/lib/types";
 import type { GuildCommand } from "@/lib/api/bots";
@@ -48,10 +51,49 @@ interface AutocompletePopupProps {
 }
 
 const AutocompletePopup: Component<AutocompletePopupProps> = (props) => {
+  // Server-ranked member suggestions for guilds (covers members not loaded locally)
+  const [serverUsers] = createResource(
+    () =>
+      props.type === "user" && props.guildId
+        ? { guildId: props.guildId, query: props.query }
+        : false,
+    async ({ guildId, query }) => {
+      try {
+        const response = await guildAutocomplete(guildId, query, "user", 8);
+        return response.results;
+      } catch {
+        // Fall back to filtering locally loaded members
+        return undefined;
+      }
+    },
+  );
+
   // Get user suggestions
   const userItems = createMemo((): PopupListItem[] => {
     if (props.type !== "user") return [];
 
+    const ranked = props.guildId ? serverUsers.latest : undefined;
+    if (ranked) {
+      return ranked.flatMap((item) =>
+        item.type === "user"
+          ? [
+              {
+                id: item.id,
+                label: item.display_name,
+                description: `@${item.username}`,
+                icon: (
+                  <Avatar
+                    src={item.avatar_url}
+                    alt={item.display_name}
+                    size="sm"
+                  />
+                ),
+              },
+            ]
+          : [],
+      );
+    }
+
     const query = props.query.toLowerCase();
     let users: Array<{
       user_id: string;
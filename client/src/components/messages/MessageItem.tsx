@@ -19,8 +19,10 @@ import {
   Hash,
   Trash2,
   Flag,
+  Forward,
   MessageSquareMore,
   Pencil,
+  ShieldAlert,
 } from "lucide-solid";
 import type { Message } from "@/lib/types";
 import { formatTimestamp } from "@/lib/utils";
@@ -649,6 +651,20 @@ const MessageItem: Component<MessageItemProps> = (props) => {
           </div>
         </Show>
 
+        <Show when={props.message.forwarded_from}>
+          {(origin) => (
+            <div class="flex items-center gap-1 text-xs text-text-secondary mb-0.5">
+              <Forward class="w-3 h-3" />
+              <span>
+                Forwarded
+                {origin().author ? ` from ${origin().author!.display_name}` : ""}
+                {" · "}
+                {formatTimestamp(origin().created_at)}
+              </span>
+            </div>
+          )}
+        </Show>
+
         <Show
           when={!isBeingEdited()}
           fallback={
@@ -711,106 +727,147 @@ const MessageItem: Component<MessageItemProps> = (props) => {
             {props.message.attachments.map((attachment) => (
               <div class="group/attachment relative">
                 <Show
-                  when={isImage(attachment.mime_type)}
+                  when={!attachment.scan_status}
                   fallback={
-                    <button
-                      type="button"
-                      class="flex items-center gap-3 px-4 py-3 bg-surface-layer2 rounded-xl hover:bg-surface-highlight transition-all duration-200 border border-white/5 max-w-sm cursor-pointer text-left"
-                      onClick={async () => {
-                        try {
-                          const url = await fetchSignedUrl(attachment.id);
-                          window.open(url, "_blank");
-                        } catch (err) {
-                          console.error("Failed to get signed URL:", err);
-                          showToast({
-                            type: "error",
-                            title: "Download Failed",
-                            message: "Could not get download link.",
-                            duration: 8000,
-                          });
-                        }
-                      }}
-                    >
-                      <div class="p-2 bg-surface-base rounded-lg text-accent-primary">
-                        <File class="w-6 h-6" />
+                    <div class="flex items-center gap-3 px-4 py-3 bg-surface-layer2 rounded-xl border border-white/5 max-w-sm">
+                      <div class="p-2 bg-surface-base rounded-lg text-text-secondary">
+                        <ShieldAlert class="w-6 h-6" />
                       </div>
                       <div class="flex-1 min-w-0">
                         <div class="text-sm font-medium text-text-primary truncate">
                           {attachment.filename}
                         </div>
                         <div class="text-xs text-text-secondary">
-                          {formatFileSize(attachment.size)}
+                          {attachment.scan_status === "pending"
+                            ? "Scanning for malware…"
+                            : "Held by malware scan"}
                         </div>
                       </div>
-                      <Download class="w-4 h-4 text-text-secondary opacity-0 group-hover/attachment:opacity-100 transition-opacity" />
-                    </button>
+                    </div>
                   }
                 >
-                  {(() => {
-                    const variant = attachment.thumbnail_url
-                      ? "thumbnail"
-                      : undefined;
-                    const [imgSrc] = createResource(
-                      () => attachment.id,
-                      async (id) => {
-                        try {
-                          return await fetchSignedUrl(id, variant);
-                        } catch (err) {
-                          console.error("Failed to load image attachment:", id, err);
-                          throw err;
-                        }
-                      },
-                    );
-
-                    return (
-                      <div
-                        class="relative rounded-xl overflow-hidden border border-white/5 bg-surface-layer2 max-w-md"
-                        style={
-                          attachment.width && attachment.height
-                            ? {
-                                "aspect-ratio": `${attachment.width} / ${attachment.height}`,
-                                "max-height": "320px",
-                              }
-                            : { "max-height": "320px" }
-                        }
+                  <Show
+                    when={isImage(attachment.mime_type)}
+                    fallback={
+                      <button
+                        type="button"
+                        class="flex items-center gap-3 px-4 py-3 bg-surface-layer2 rounded-xl hover:bg-surface-highlight transition-all duration-200 border border-white/5 max-w-sm cursor-pointer text-left"
+                        onClick={async () => {
+                          try {
+                            const url = await fetchSignedUrl(attachment.id);
+                            window.open(url, "_blank");
+                          } catch (err) {
+                            console.error("Failed to get signed URL:", err);
+                            showToast({
+                              type: "error",
+                              title: "Download Failed",
+                              message: "Could not get download link.",
+                              duration: 8000,
+                            });
+                          }
+                        }}
                       >
-                        {/* Blurhash placeholder (visible while image loads) */}
-                        <Show when={attachment.blurhash}>
-                          <BlurhashPlaceholder
-                            hash={attachment.blurhash!}
-                            width={attachment.width ?? 32}
-                            height={attachment.height ?? 32}
-                            class="absolute inset-0 w-full h-full"
-                          />
-                        </Show>
-
-                        {/* Error fallback when signed URL fetch fails */}
-                        <Show when={imgSrc.error}>
-                          <div class="absolute inset-0 flex items-center justify-center text-text-secondary text-sm">
-                            Failed to load image
+                        <div class="p-2 bg-surface-base rounded-lg text-accent-primary">
+                          <File class="w-6 h-6" />
+                        </div>
+                        <div class="flex-1 min-w-0">
+                          <div class="text-sm font-medium text-text-primary truncate">
+                            {attachment.filename}
                           </div>
-                        </Show>
-
-                        {/* Actual image — loads signed URL, fades in over placeholder */}
-                        <Show when={imgSrc()}>
-                          <img
-                            src={imgSrc()}
-                            alt={attachment.filename}
-                            class="relative w-full h-full object-contain block opacity-0 transition-opacity duration-300"
-                            loading="lazy"
-                            onLoad={(e) => {
-                              (
-                                e.target as HTMLImageElement
-                              ).classList.remove("opacity-0");
-                              (e.target as HTMLImageElement).classList.add(
-                                "opacity-100",
-                              );
-                            }}
+                          <div class="text-xs text-text-secondary">
+                            {formatFileSize(attachment.size)}
+                          </div>
+                        </div>
+                        <Download class="w-4 h-4 text-text-secondary opacity-0 group-hover/attachment:opacity-100 transition-opacity" />
+                      </button>
+                    }
+                  >
+                    {(() => {
+                      const variant = attachment.thumbnail_url
+                        ? "thumbnail"
+                        : undefined;
+                      const [imgSrc] = createResource(
+                        () => attachment.id,
+                        async (id) => {
+                          try {
+                            return await fetchSignedUrl(id, variant);
+                          } catch (err) {
+                            console.error("Failed to load image attachment:", id, err);
+                            throw err;
+                          }
+                        },
+                      );
+
+                      return (
+                        <div
+                          class="relative rounded-xl overflow-hidden border border-white/5 bg-surface-layer2 max-w-md"
+                          style={
+                            attachment.width && attachment.height
+                              ? {
+                                  "aspect-ratio": `${attachment.width} / ${attachment.height}`,
+                                  "max-height": "320px",
+                                }
+                              : { "max-height": "320px" }
+                          }
+                        >
+                          {/* Blurhash placeholder (visible while image loads) */}
+                          <Show when={attachment.blurhash}>
+                            <BlurhashPlaceholder
+                              hash={attachment.blurhash!}
+                              width={attachment.width ?? 32}
+                              height={attachment.height ?? 32}
+                              class="absolute inset-0 w-full h-full"
+                            />
+                          </Show>
+
+                          {/* Error fallback when signed URL fetch fails */}
+                          <Show when={imgSrc.error}>
+                            <div class="absolute inset-0 flex items-center justify-center text-text-secondary text-sm">
+                              Failed to load image
+                            </div>
+                          </Show>
+
+                          {/* Actual image — loads signed URL, fades in over placeholder */}
+                          <Show when={imgSrc()}>
+                            <img
+                              src={imgSrc()}
+                              alt={attachment.filename}
+                              class="relative w-full h-full object-contain block opacity-0 transition-opacity duration-300"
+                              loading="lazy"
+                              onLoad={(e) => {
+                                (
+                                  e.target as HTMLImageElement
+                                ).classList.remove("opacity-0");
+                                (e.target as HTMLImageElement).classList.add(
+                                  "opacity-100",
+                                );
+                              }}
+                              onClick={async () => {
+                                try {
+                                  const url = await fetchSignedUrl(
+                                    attachment.id,
+                                  );
+                                  window.open(url, "_blank");
+                                } catch (err) {
+                                  console.error("Failed to get signed URL:", err);
+                                  showToast({
+                                    type: "error",
+                                    title: "Download Failed",
+                                    message: "Could not get download link.",
+                                    duration: 8000,
+                                  });
+                                }
+                              }}
+                              style={{ cursor: "pointer" }}
+                            />
+                          </Show>
+                          <button
+                            type="button"
+                            class="absolute top-2 right-2 p-1.5 bg-black/50 hover:bg-black/70 rounded-lg text-white opacity-0 group-hover/attachment:opacity-100 transition-opacity backdrop-blur-sm cursor-pointer"
+                            title="Download original"
                             onClick={async () => {
                               try {
-                                const url = await fetchSignedUrl(
-                                  attachment.id,
-                                );
+                                const url = await fetchSignedUrl(attachment.id);
                                 window.open(url, "_blank");
                               } catch (err) {
                                 console.error("Failed to get signed URL:", err);
@@ -822,39 +879,60 @@ const MessageItem: Component<MessageItemProps> = (props) => {
                                 });
                               }
                             }}
-                            style={{ cursor: "pointer" }}
-                          />
-                        </Show>
-                        <button
-                          type="button"
-                          class="absolute top-2 right-2 p-1.5 bg-black/50 hover:bg-black/70 rounded-lg text-white opacity-0 group-hover/attachment:opacity-100 transition-opacity backdrop-blur-sm cursor-pointer"
-                          title="Download original"
-                          onClick={async () => {
-                            try {
-                              const url = await fetchSignedUrl(attachment.id);
-                              window.open(url, "_blank");
-                            } catch (err) {
-                              console.error("Failed to get signed URL:", err);
-                              showToast({
-                                type: "error",
-                                title: "Download Failed",
-                                message: "Could not get download link.",
-                                duration: 8000,
-                              });
-                            }
-                          }}
-                        >
-                          <Download class="w-4 h-4" />
-                        </button>
-                      </div>
-                    );
-                  })()}
+                          >
+                            <Download class="w-4 h-4" />
+                          </button>
+                        </div>
+                      );
+                    })()}
+                  </Show>
                 </Show>
               </div>
             ))}
           </div>
         </Show>
 
+        {/* Link previews */}
+        <Show when={(props.message.embeds?.length ?? 0) > 0}>
+          <div class="mt-2 flex flex-col gap-2">
+            <For each={props.message.embeds}>
+              {(embed) => (
+                <a
+                  href={embed.url}
+                  target="_blank"
+                  rel="noopener noreferrer"
+                  class="flex gap-3 max-w-md p-3 bg-surface-layer2 rounded-xl border-l-4 border-accent-primary hover:bg-surface-highlight transition-colors"
+                >
+                  <div class="flex-1 min-w-0">
+                    <Show when={embed.site_name}>
+                      <div class="text-xs text-text-secondary truncate">{embed.site_name}</div>
+                    </Show>
+                    <Show when={embed.title}>
+                      <div class="text-sm font-semibold text-accent-primary line-clamp-2">
+                        {embed.title}
+                      </div>
+                    </Show>
+                    <Show when={embed.description}>
+                      <div class="mt-1 text-xs text-text-secondary line-clamp-3">
+                        {embed.description}
+                      </div>
+                    </Show>
+                  </div>
+                  <Show when={embed.image_url}>
+                    <img
+                      src={embed.image_url}
+                      alt=""
+                      loading="lazy"
+                      referrerpolicy="no-referrer"
+                      class="w-20 h-20 rounded-lg object-cover flex-shrink-0"
+                    />
+                  </Show>
+                </a>
+              )}
+            </For>
+          </div>
+        </Show>
+
         {/* Reactions */}
         <Show when={hasReactions()}>
           <ReactionBar
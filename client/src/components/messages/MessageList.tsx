@@ -18,6 +18,7 @@ import {
 } from "lucide-solid";
 import flokiHappy from "@/assets/emotes/floki_emote_1.png";
 import MessageItem from "./MessageItem";
+import SystemMessageItem from "./SystemMessageItem";
 import {
   messagesState,
   setMessagesState,
@@ -26,7 +27,7 @@ import {
   hasMoreMessages,
 } from "@/stores/messages";
 import { areThreadsEnabled } from "@/stores/guilds";
-import { shouldGroupWithPrevious } from "@/lib/utils";
+import { isSystemMessage, shouldGroupWithPrevious } from "@/lib/utils";
 
 interface MessageListProps {
   channelId: string;
@@ -63,14 +64,15 @@ const MessageList: Component<MessageListProps> = (props) => {
     const msgs = messages();
     return msgs.map((message, idx) => {
       const prev = idx > 0 ? msgs[idx - 1] : null;
-      const isCompact = prev
-        ? shouldGroupWithPrevious(
-            message.created_at,
-            prev.created_at,
-            message.author.id,
-            prev.author.id,
-          )
-        : false;
+      const isCompact =
+        prev && !isSystemMessage(message) && !isSystemMessage(prev)
+          ? shouldGroupWithPrevious(
+              message.created_at,
+              prev.created_at,
+              message.author.id,
+              prev.author.id,
+            )
+          : false;
       return { message, isCompact };
     });
   });
@@ -396,6 +398,9 @@ const MessageList: Component<MessageListProps> = (props) => {
                 >
                   {(() => {
                     const data = item();
+                    if (data && isSystemMessage(data.message)) {
+                      return <SystemMessageItem message={data.message} />;
+                    }
                     return data ? (
                       <MessageItem
                         message={data.message}
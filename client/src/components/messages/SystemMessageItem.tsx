@@ -0,0 +1,63 @@
+/**
+ * System Message Item
+ *
+ * Compact, non-interactive row for server-generated messages
+ * (member joins, calls, voice activity, automod actions, ...).
+ */
+
+import { Component } from "solid-js";
+import { Dynamic } from "solid-js/web";
+import { Phone, Pin, ShieldAlert, Sparkles, UserPlus, Volume2, Info } from "lucide-solid";
+import type { Message, MessageType } from "@/lib/types";
+import { formatTimestamp } from "@/lib/utils";
+import { showUserContextMenu } from "@/lib/contextMenuBuilders";
+
+interface SystemMessageItemProps {
+  message: Message;
+}
+
+const ICONS: Partial<Record<MessageType, typeof Info>> = {
+  system_join: UserPlus,
+  system_pin: Pin,
+  system_call: Phone,
+  system_boost: Sparkles,
+  system_voice: Volume2,
+  system_automod: ShieldAlert,
+};
+
+const SystemMessageItem: Component<SystemMessageItemProps> = (props) => {
+  const author = () => props.message.author;
+  const icon = () =>
+    (props.message.message_type && ICONS[props.message.message_type]) ?? Info;
+
+  return (
+    <div
+      data-testid="system-message-item"
+      class="flex items-center gap-4 px-4 py-1 mt-2 text-sm text-text-secondary"
+    >
+      <div class="w-10 flex-shrink-0 flex justify-center">
+        <Dynamic component={icon()} class="w-4 h-4" />
+      </div>
+      <div class="flex-1 min-w-0">
+        <span
+          class="font-semibold text-text-primary hover:underline cursor-pointer"
+          onContextMenu={(e: MouseEvent) => {
+            showUserContextMenu(e, {
+              id: author().id,
+              username: author().username,
+              display_name: author().display_name,
+            });
+          }}
+        >
+          {author().display_name}
+        </span>{" "}
+        <span>{props.message.content}</span>
+        <span class="ml-2 text-xs">
+          {formatTimestamp(props.message.created_at)}
+        </span>
+      </div>
+    </div>
+  );
+};
+
+export default SystemMessageItem;
@@ -12,6 +12,12 @@ const AccountSettings: Component = () => {
   const [isUploading, setIsUploading] = createSignal(false);
   const [error, setError] = createSignal<string | null>(null);
   const [isPasswordModalOpen, setIsPasswordModalOpen] = createSignal(false);
+  const [newUsername, setNewUsername] = createSignal("");
+  const [isChangingUsername, setIsChangingUsername] = createSignal(false);
+  const [usernameError, setUsernameError] = createSignal<string | null>(null);
+  const [verificationCode, setVerificationCode] = createSignal("");
+  const [isVerifying, setIsVerifying] = createSignal(false);
+  const [isResending, setIsResending] = createSignal(false);
   let fileInput: HTMLInputElement | undefined;
 
   const handleFileChange = async (e: Event) => {
@@ -63,6 +69,88 @@ const AccountSettings: Component = () => {
     }
   };
 
+  const handleUsernameSubmit = async (e: Event) => {
+    e.preventDefault();
+    const username = newUsername().trim();
+    if (!username || isChangingUsername()) return;
+
+    setUsernameError(null);
+    setIsChangingUsername(true);
+    try {
+      const result = await tauri.changeUsername(username);
+      updateUser({ username: result.username });
+      setNewUsername("");
+      showToast({
+        type: "success",
+        title: "Username Changed",
+        message:
+          `You are now @${result.username}. Mentions of ` +
+          `@${result.previous_username} reach you until ` +
+          `${new Date(result.previous_username_held_until).toLocaleDateString()}.`,
+        duration: 5000,
+      });
+    } catch (err) {
+      setUsernameError(
+        err instanceof Error ? err.message : "Failed to change username",
+      );
+    } finally {
+      setIsChangingUsername(false);
+    }
+  };
+
+  const handleResendVerification = async () => {
+    setIsResending(true);
+    try {
+      await tauri.resendEmailVerification();
+      showToast({
+        type: "success",
+        title: "Verification Email Sent",
+        message: `Check ${user()?.email} for your verification code.`,
+        duration: 5000,
+      });
+    } catch (err) {
+      showToast({
+        type: "error",
+        title: "Could Not Send Email",
+        message:
+          err instanceof Error
+            ? err.message
+            : "Failed to send verification email",
+        duration: 8000,
+      });
+    } finally {
+      setIsResending(false);
+    }
+  };
+
+  const handleVerifySubmit = async (e: Event) => {
+    e.preventDefault();
+    const code = verificationCode().trim();
+    if (!code || isVerifying()) return;
+
+    setIsVerifying(true);
+    try {
+      await tauri.verifyEmail(code);
+      updateUser({ email_verified: true });
+      setVerificationCode("");
+      showToast({
+        type: "success",
+        title: "Email Verified",
+        message: "Your email address has been verified.",
+        duration: 3000,
+      });
+    } catch (err) {
+      showToast({
+        type: "error",
+        title: "Verification Failed",
+        message: err instanceof Error ? err.message : "Invalid or expired code",
+        duration: 8000,
+      });
+    } finally {
+      setIsVerifying(false);
+    }
+  };
+
   return (
     <div class="space-y-6">
       <div>
@@ -128,6 +216,17 @@ const AccountSettings: Component = () => {
               <span class="text-text-primary">
                 {user()?.email || "Not set"}
               </span>
+              <Show when={user()?.email}>
+                <span
+                  class="text-xs px-1.5 py-0.5 rounded"
+                  classList={{
+                    "bg-success/20 text-success": !!user()?.email_verified,
+                    "bg-warning/20 text-warning": !user()?.email_verified,
+                  }}
+                >
+                  {user()?.email_verified ? "Verified" : "Unverified"}
+                </span>
+              </Show>
             </div>
             <div class="flex items-center gap-2 text-sm">
               <span class="text-text-secondary w-20">User ID:</span>
@@ -145,6 +244,76 @@ const AccountSettings: Component = () => {
         </div>
       </Show>
 
+      <Show when={user()?.email && !user()?.email_verified}>
+        <div class="pt-4 border-t border-white/5">
+          <h4 class="text-sm font-semibold text-text-secondary uppercase tracking-wide mb-2">
+            Verify Email
+          </h4>
+          <p class="text-xs text-text-secondary mb-3">
+            Enter the code we sent to {user()?.email}.
+          </p>
+          <form class="flex gap-2" onSubmit={handleVerifySubmit}>
+            <input
+              type="text"
+              class="input-field flex-1 font-mono"
+              placeholder="Verification code"
+              value={verificationCode()}
+              onInput={(e) => setVerificationCode(e.currentTarget.value)}
+              disabled={isVerifying()}
+            />
+            <button
+              type="submit"
+              class="btn-primary"
+              disabled={isVerifying() || !verificationCode().trim()}
+            >
+              {isVerifying() ? "Verifying..." : "Verify"}
+            </button>
+            <button
+              type="button"
+              class="btn-secondary"
+              onClick={handleResendVerification}
+              disabled={isResending()}
+            >
+              {isResending() ? "Sending..." : "Resend Code"}
+            </button>
+          </form>
+        </div>
+      </Show>
+
+      <div class="pt-4 border-t border-white/5">
+        <h4 class="text-sm font-semibold text-text-secondary uppercase tracking-wide mb-4">
+          Username
+        </h4>
+        <form class="flex gap-2" onSubmit={handleUsernameSubmit}>
+          <input
+            type="text"
+            class="input-field flex-1 font-mono"
+            placeholder={user()?.username}
+            value={newUsername()}
+            onInput={(e) => setNewUsername(e.currentTarget.value)}
+            disabled={isChangingUsername()}
+            maxLength={32}
+          />
+          <button
+            type="submit"
+            class="btn-primary"
+            disabled={isChangingUsername() || !newUsername().trim()}
+          >
+            {isChangingUsername() ? "Saving..." : "Change Username"}
+          </button>
+        </form>
+        <p class="text-xs text-text-secondary mt-2">
+          You can change your username once every 30 days. Mentions of your
+          old username keep reaching you for 14 days, and nobody else can
+          take it in that time.
+        </p>
+        <Show when={usernameError()}>
+          <div class="mt-3 p-3 rounded-lg bg-error-bg border border-error-border text-error-text text-sm">
+            {usernameError()}
+          </div>
+        </Show>
+      </div>
+
       <div class="pt-4 border-t border-white/5">
         <h4 class="text-sm font-semibold text-text-secondary uppercase tracking-wide mb-4">
           Password & Authentication
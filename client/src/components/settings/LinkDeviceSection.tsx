@@ -0,0 +1,254 @@
+/**
+ * Link Device Section
+ *
+ * Approves a new device that is signing in with a link code: scanned from its
+ * QR code where the browser can read barcodes, or typed in.
+ */
+
+import { Component, createSignal, onCleanup, Show } from "solid-js";
+import { Camera, Smartphone } from "lucide-solid";
+import {
+  approveDeviceLink,
+  getDeviceLink,
+  rejectDeviceLink,
+  type DeviceLinkDetails,
+} from "@/lib/tauri";
+import { formatDeviceLinkCode, parseDeviceLinkCode } from "@/lib/deviceLink";
+import { showToast } from "@/components/ui/Toast";
+
+// Shape of the BarcodeDetector API (Chromium-based browsers and webviews)
+interface DetectedBarcode {
+  rawValue: string;
+}
+interface BarcodeDetectorLike {
+  detect(source: HTMLVideoElement): Promise<DetectedBarcode[]>;
+}
+type BarcodeDetectorConstructor = new (options: {
+  formats: string[];
+}) => BarcodeDetectorLike;
+
+const barcodeDetector = (
+  window as unknown as { BarcodeDetector?: BarcodeDetectorConstructor }
+).BarcodeDetector;
+
+const LinkDeviceSection: Component = () => {
+  const [codeInput, setCodeInput] = createSignal("");
+  const [request, setRequest] = createSignal<DeviceLinkDetails | null>(null);
+  const [error, setError] = createSignal("");
+  const [isBusy, setIsBusy] = createSignal(false);
+  const [isScanning, setIsScanning] = createSignal(false);
+
+  let video: HTMLVideoElement | undefined;
+  let stream: MediaStream | undefined;
+  let scanTimer: ReturnType<typeof setInterval> | undefined;
+
+  const stopScanning = () => {
+    clearInterval(scanTimer);
+    stream?.getTracks().forEach((track) => track.stop());
+    stream = undefined;
+    setIsScanning(false);
+  };
+
+  onCleanup(stopScanning);
+
+  const lookUp = async (input: string) => {
+    const code = parseDeviceLinkCode(input);
+    if (!code) {
+      setError("That is not a valid link code.");
+      return;
+    }
+    setIsBusy(true);
+    setError("");
+    try {
+      setRequest(await getDeviceLink(code));
+    } catch (err) {
+      setError(err instanceof Error ? err.message : String(err));
+    } finally {
+      setIsBusy(false);
+    }
+  };
+
+  const startScanning = async () => {
+    if (!barcodeDetector) return;
+    setError("");
+    try {
+      stream = await navigator.mediaDevices.getUserMedia({
+        video: { facingMode: "environment" },
+      });
+    } catch {
+      setError("Could not open the camera.");
+      return;
+    }
+    setIsScanning(true);
+    if (video) {
+      video.srcObject = stream;
+      await video.play();
+    }
+
+    const detector = new barcodeDetector({ formats: ["qr_code"] });
+    scanTimer = setInterval(async () => {
+      if (!video) return;
+      const codes = await detector.detect(video).catch(() => []);
+      const scanned = codes.find((c) => parseDeviceLinkCode(c.rawValue));
+      if (scanned) {
+        stopScanning();
+        setCodeInput(scanned.rawValue);
+        await lookUp(scanned.rawValue);
+      }
+    }, 300);
+  };
+
+  const finish = () => {
+    setRequest(null);
+    setCodeInput("");
+  };
+
+  const handleApprove = async () => {
+    const current = request();
+    if (!current) return;
+    setIsBusy(true);
+    try {
+      await approveDeviceLink(current.code);
+      finish();
+      showToast({
+        type: "success",
+        title: "Device Linked",
+        message: "The new device is now signed in to your account.",
+        duration: 5000,
+      });
+    } catch (err) {
+      setError(err instanceof Error ? err.message : String(err));
+    } finally {
+      setIsBusy(false);
+    }
+  };
+
+  const handleReject = async () => {
+    const current = request();
+    if (!current) return;
+    setIsBusy(true);
+    try {
+      await rejectDeviceLink(current.code);
+      finish();
+    } catch (err) {
+      setError(err instanceof Error ? err.message : String(err));
+    } finally {
+      setIsBusy(false);
+    }
+  };
+
+  return (
+    <div class="pt-6 border-t border-white/10">
+      <div class="flex items-center gap-3 mb-4">
+        <Smartphone class="w-5 h-5 text-text-secondary" />
+        <h3 class="text-lg font-semibold text-text-primary">Link a Device</h3>
+      </div>
+
+      <div class="bg-surface-base rounded-xl p-4 space-y-4">
+        <p class="text-sm text-text-secondary">
+          Sign in on a new device without your password: choose "Sign in with
+          another device" there, then scan or enter the code it shows.
+        </p>
+
+        <Show
+          when={request()}
+          fallback={
+            <form
+              class="flex gap-2"
+              onSubmit={(e) => {
+                e.preventDefault();
+                lookUp(codeInput());
+              }}
+            >
+              <input
+                type="text"
+                class="input-field flex-1 font-mono tracking-widest uppercase"
+                placeholder="ABCDE-12345"
+                value={codeInput()}
+                onInput={(e) => setCodeInput(e.currentTarget.value)}
+                disabled={isBusy()}
+                maxLength={40}
+              />
+              <button
+                type="submit"
+                class="btn-primary"
+                disabled={isBusy() || !codeInput().trim()}
+              >
+                Continue
+              </button>
+              <Show when={barcodeDetector}>
+                <button
+                  type="button"
+                  class="btn-secondary flex items-center gap-2"
+                  onClick={() =>
+                    isScanning() ? stopScanning() : startScanning()
+                  }
+                  disabled={isBusy()}
+                >
+                  <Camera class="w-4 h-4" />
+                  {isScanning() ? "Stop" : "Scan"}
+                </button>
+              </Show>
+            </form>
+          }
+        >
+          {(current) => (
+            <div class="space-y-3">
+              <div class="p-3 bg-white/5 rounded-lg space-y-1 text-sm">
+                <p class="text-text-primary font-mono tracking-widest">
+                  {formatDeviceLinkCode(current().code)}
+                </p>
+                <p class="text-text-secondary">
+                  {current().user_agent ?? "Unknown device"}
+                </p>
+                <p class="text-text-muted">
+                  From {current().ip_address} ·{" "}
+                  {new Date(current().created_at).toLocaleTimeString()}
+                </p>
+              </div>
+              <p class="text-xs text-text-muted">
+                Only approve a device you are holding. It will be signed in to
+                your account.
+              </p>
+              <div class="flex gap-2">
+                <button
+                  type="button"
+                  class="btn-primary flex-1"
+                  onClick={handleApprove}
+                  disabled={isBusy()}
+                >
+                  Approve
+                </button>
+                <button
+                  type="button"
+                  class="btn-secondary flex-1"
+                  onClick={handleReject}
+                  disabled={isBusy()}
+                >
+                  Reject
+                </button>
+              </div>
+            </div>
+          )}
+        </Show>
+
+        <Show when={isScanning()}>
+          <video
+            ref={video}
+            class="w-full max-w-xs rounded-lg"
+            muted
+            playsinline
+          />
+        </Show>
+
+        <Show when={error()}>
+          <div class="p-3 rounded-lg bg-error-bg border border-error-border text-error-text text-sm">
+            {error()}
+          </div>
+        </Show>
+      </div>
+    </div>
+  );
+};
+
+export default LinkDeviceSection;
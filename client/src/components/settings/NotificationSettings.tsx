@@ -2,10 +2,12 @@
  * Notification Settings
  *
  * Sound notification settings with sound selection, volume control, and test button.
+ * In the desktop app, also native notification toggles, muted servers, and
+ * do-not-disturb windows.
  */
 
-import { Component, For, createSignal, createMemo } from "solid-js";
-import { Check, Volume2, Play, Moon, Clock } from "lucide-solid";
+import { Component, For, Show, createSignal, createMemo } from "solid-js";
+import { Check, Volume2, Play, Moon, Clock, Bell, Plus, X } from "lucide-solid";
 import {
   soundSettings,
   setSoundEnabled,
@@ -19,6 +21,26 @@ import {
 } from "@/stores/sound";
 import { AVAILABLE_SOUNDS, type SoundInfo } from "@/lib/sound/types";
 import { testSound } from "@/lib/sound";
+import {
+  preferences,
+  updateDesktopNotificationSetting,
+} from "@/stores/preferences";
+import { guildsState } from "@/stores/guilds";
+import {
+  DEFAULT_DESKTOP_NOTIFICATION_PREFERENCES,
+  type DesktopNotificationPreferences,
+  type DndWindow,
+} from "@/lib/types";
+
+const isTauri = typeof window !== "undefined" && "__TAURI__" in window;
+
+const DAY_LABELS = ["Sun", "Mon", "Tue", "Wed", "Thu", "Fri", "Sat"];
+
+const CHECKBOX_CLASS =
+  "w-5 h-5 rounded border-2 border-white/30 bg-transparent checked:bg-accent-primary checked:border-accent-primary transition-colors cursor-pointer accent-accent-primary";
+
+const TIME_INPUT_CLASS =
+  "px-3 py-1.5 rounded-lg bg-surface-highlight border border-white/10 text-text-primary text-sm focus:outline-none focus:border-accent-primary transition-colors";
 
 const NotificationSettings: Component = () => {
   const [isTesting, setIsTesting] = createSignal(false);
@@ -38,6 +60,51 @@ const NotificationSettings: Component = () => {
     };
   });
 
+  const desktop = (): DesktopNotificationPreferences => ({
+    ...DEFAULT_DESKTOP_NOTIFICATION_PREFERENCES,
+    ...preferences().desktop_notifications,
+  });
+
+  const toggleMutedGuild = (guildId: string, muted: boolean) => {
+    const others = desktop().muted_guilds.filter((id) => id !== guildId);
+    updateDesktopNotificationSetting(
+      "muted_guilds",
+      muted ? [...others, guildId] : others,
+    );
+  };
+
+  const updateDndWindow = (index: number, patch: Partial<DndWindow>) => {
+    updateDesktopNotificationSetting(
+      "dnd_windows",
+      desktop().dnd_windows.map((w, i) =>
+        i === index ? { ...w, ...patch } : w,
+      ),
+    );
+  };
+
+  const addDndWindow = () => {
+    updateDesktopNotificationSetting("dnd_windows", [
+      ...desktop().dnd_windows,
+      { start_time: "22:00", end_time: "08:00", days: [] },
+    ]);
+  };
+
+  const removeDndWindow = (index: number) => {
+    updateDesktopNotificationSetting(
+      "dnd_windows",
+      desktop().dnd_windows.filter((_, i) => i !== index),
+    );
+  };
+
+  const toggleDndDay = (index: number, day: number) => {
+    const days = desktop().dnd_windows[index]?.days ?? [];
+    updateDndWindow(index, {
+      days: days.includes(day)
+        ? days.filter((d) => d !== day)
+        : [...days, day].sort(),
+    });
+  };
+
   const handleTestSound = async () => {
     if (isTesting()) return;
     setIsTesting(true);
@@ -257,6 +324,213 @@ const NotificationSettings: Component = () => {
         </div>
       </div>
 
+      {/* Desktop notifications (native OS notifications, desktop app only) */}
+      <Show when={isTauri}>
+        <div>
+          <h3 class="text-lg font-semibold mb-4 text-text-primary flex items-center gap-2">
+            <Bell class="w-5 h-5" />
+            Desktop Notifications
+          </h3>
+
+          <p class="text-sm text-text-secondary mb-4">
+            Show system notifications while Kaiku is in the background
+          </p>
+
+          <label class="flex items-center gap-3 cursor-pointer mb-4">
+            <input
+              type="checkbox"
+              checked={desktop().enabled}
+              onChange={(e) =>
+                updateDesktopNotificationSetting(
+                  "enabled",
+                  e.currentTarget.checked,
+                )
+              }
+              class={CHECKBOX_CLASS}
+            />
+            <span class="text-text-primary">Enable desktop notifications</span>
+          </label>
+
+          <label class="flex items-center gap-3 cursor-pointer">
+            <input
+              type="checkbox"
+              checked={desktop().announce_voice_presence}
+              onChange={(e) =>
+                updateDesktopNotificationSetting(
+                  "announce_voice_presence",
+                  e.currentTarget.checked,
+                )
+              }
+              class={CHECKBOX_CLASS}
+            />
+            <span class="text-text-primary">
+              Read out who joins or leaves my voice call
+            </span>
+          </label>
+
+          <div
+            class="space-y-6"
+            classList={{
+              "opacity-50 pointer-events-none": !desktop().enabled,
+            }}
+          >
+            <div class="space-y-3">
+              <label class="flex items-center gap-3 cursor-pointer">
+                <input
+                  type="checkbox"
+                  checked={desktop().mentions}
+                  onChange={(e) =>
+                    updateDesktopNotificationSetting(
+                      "mentions",
+                      e.currentTarget.checked,
+                    )
+                  }
+                  class={CHECKBOX_CLASS}
+                />
+                <span class="text-text-primary">Mentions</span>
+              </label>
+              <label class="flex items-center gap-3 cursor-pointer">
+                <input
+                  type="checkbox"
+                  checked={desktop().direct_messages}
+                  onChange={(e) =>
+                    updateDesktopNotificationSetting(
+                      "direct_messages",
+                      e.currentTarget.checked,
+                    )
+                  }
+                  class={CHECKBOX_CLASS}
+                />
+                <span class="text-text-primary">Direct messages</span>
+              </label>
+              <label class="flex items-center gap-3 cursor-pointer">
+                <input
+                  type="checkbox"
+                  checked={desktop().incoming_calls}
+                  onChange={(e) =>
+                    updateDesktopNotificationSetting(
+                      "incoming_calls",
+                      e.currentTarget.checked,
+                    )
+                  }
+                  class={CHECKBOX_CLASS}
+                />
+                <span class="text-text-primary">Incoming calls</span>
+              </label>
+            </div>
+
+            {/* Muted servers */}
+            <Show when={guildsState.guilds.length > 0}>
+              <div>
+                <h4 class="text-base font-medium mb-3 text-text-primary">
+                  Muted Servers
+                </h4>
+                <div class="space-y-2">
+                  <For each={guildsState.guilds}>
+                    {(guild) => (
+                      <label class="flex items-center gap-3 cursor-pointer">
+                        <input
+                          type="checkbox"
+                          checked={desktop().muted_guilds.includes(guild.id)}
+                          onChange={(e) =>
+                            toggleMutedGuild(guild.id, e.currentTarget.checked)
+                          }
+                          class={CHECKBOX_CLASS}
+                        />
+                        <span class="text-text-primary">{guild.name}</span>
+                      </label>
+                    )}
+                  </For>
+                </div>
+              </div>
+            </Show>
+
+            {/* Do-not-disturb windows */}
+            <div>
+              <h4 class="text-base font-medium mb-1 text-text-primary">
+                Do Not Disturb Schedule
+              </h4>
+              <p class="text-sm text-text-secondary mb-3">
+                No desktop notifications during these times. Leave all days
+                unselected to repeat every day.
+              </p>
+
+              <div class="space-y-3">
+                <For each={desktop().dnd_windows}>
+                  {(window, index) => (
+                    <div class="p-3 rounded-xl border border-white/10 space-y-3">
+                      <div class="flex items-center gap-4">
+                        <div class="flex items-center gap-2">
+                          <Clock class="w-4 h-4 text-text-secondary" />
+                          <span class="text-sm text-text-secondary">From</span>
+                          <input
+                            type="time"
+                            value={window.start_time}
+                            onChange={(e) =>
+                              updateDndWindow(index(), {
+                                start_time: e.currentTarget.value,
+                              })
+                            }
+                            class={TIME_INPUT_CLASS}
+                          />
+                        </div>
+                        <div class="flex items-center gap-2">
+                          <span class="text-sm text-text-secondary">To</span>
+                          <input
+                            type="time"
+                            value={window.end_time}
+                            onChange={(e) =>
+                              updateDndWindow(index(), {
+                                end_time: e.currentTarget.value,
+                              })
+                            }
+                            class={TIME_INPUT_CLASS}
+                          />
+                        </div>
+                        <button
+                          onClick={() => removeDndWindow(index())}
+                          class="ml-auto p-1.5 rounded-lg text-text-secondary hover:text-text-primary hover:bg-white/10 transition-colors"
+                          title="Remove schedule"
+                        >
+                          <X class="w-4 h-4" />
+                        </button>
+                      </div>
+
+                      <div class="flex gap-1.5">
+                        <For each={DAY_LABELS}>
+                          {(label, day) => (
+                            <button
+                              onClick={() => toggleDndDay(index(), day())}
+                              class="px-2 py-1 rounded-md text-xs font-medium transition-colors"
+                              classList={{
+                                "bg-accent-primary text-white":
+                                  window.days.includes(day()),
+                                "bg-surface-highlight text-text-secondary hover:bg-white/10":
+                                  !window.days.includes(day()),
+                              }}
+                            >
+                              {label}
+                            </button>
+                          )}
+                        </For>
+                      </div>
+                    </div>
+                  )}
+                </For>
+
+                <button
+                  onClick={addDndWindow}
+                  class="flex items-center gap-2 px-3 py-1.5 rounded-lg bg-surface-highlight hover:bg-white/10 text-text-primary text-sm font-medium transition-colors"
+                >
+                  <Plus class="w-4 h-4" />
+                  Add schedule
+                </button>
+              </div>
+            </div>
+          </div>
+        </div>
+      </Show>
+
       {/* Info text */}
       <p class="text-xs text-text-muted">
         Sounds will only play for messages from others, and respect per-channel
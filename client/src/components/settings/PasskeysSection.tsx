@@ -0,0 +1,246 @@
+/**
+ * Passkeys Section
+ *
+ * Lists the account's passkeys and registers new ones after
+ * re-authentication. Passkeys sign in on their own and, with two-factor
+ * authentication enabled, can stand in for an authenticator code. Accounts
+ * without password or MFA confirm with a fresh provider or passkey sign-in.
+ */
+
+import { Component, createResource, createSignal, For, Show } from "solid-js";
+import { Fingerprint } from "lucide-solid";
+import {
+  deletePasskey,
+  fetchServerSettings,
+  getAuthMethods,
+  getServerUrl,
+  listPasskeys,
+  type Reauthentication,
+} from "@/lib/tauri";
+import { confirmWithPasskey, confirmWithProvider } from "@/lib/reauth";
+import { passkeysSupported, registerPasskey } from "@/lib/webauthn";
+import { authState } from "@/stores/auth";
+import { showToast } from "@/components/ui/Toast";
+
+const PasskeysSection: Component = () => {
+  const [settings] = createResource(() =>
+    fetchServerSettings(getServerUrl()),
+  );
+  const enabled = () =>
+    !!settings()?.passkeys_enabled && passkeysSupported();
+
+  const [passkeys, { mutate }] = createResource(enabled, listPasskeys);
+  const [methods] = createResource(enabled, getAuthMethods);
+
+  const [name, setName] = createSignal("");
+  const [currentPassword, setCurrentPassword] = createSignal("");
+  const [mfaCode, setMfaCode] = createSignal("");
+  const [freshProof, setFreshProof] = createSignal<Reauthentication | null>(
+    null,
+  );
+  const [error, setError] = createSignal("");
+  const [isBusy, setIsBusy] = createSignal(false);
+
+  const hasPassword = () => methods()?.has_password ?? false;
+  const needsMfaCode = () =>
+    !hasPassword() && (authState.user?.mfa_enabled ?? false);
+  const needsFreshSignIn = () =>
+    !hasPassword() && !(authState.user?.mfa_enabled ?? false);
+
+  const providerName = (slug: string) =>
+    settings()?.oidc_providers.find((p) => p.slug === slug)?.display_name ??
+    slug;
+
+  const run = async (action: () => Promise<void>) => {
+    setIsBusy(true);
+    setError("");
+    try {
+      await action();
+    } catch (err) {
+      setError(err instanceof Error ? err.message : String(err));
+    } finally {
+      setIsBusy(false);
+    }
+  };
+
+  const confirm = (proof: () => Promise<Reauthentication>) =>
+    run(async () => {
+      setFreshProof(await proof());
+    });
+
+  const handleAdd = (e: Event) => {
+    e.preventDefault();
+    run(async () => {
+      const passkey = await registerPasskey(name().trim(), {
+        current_password: hasPassword() ? currentPassword() : undefined,
+        mfa_code: needsMfaCode() ? mfaCode() : undefined,
+        ...(needsFreshSignIn() ? freshProof() : null),
+      });
+      mutate((list) => [...(list ?? []), passkey]);
+      setName("");
+      setCurrentPassword("");
+      setMfaCode("");
+      setFreshProof(null);
+      showToast({
+        type: "success",
+        title: "Passkey Added",
+        message: "You can now sign in with this passkey.",
+        duration: 5000,
+      });
+    });
+  };
+
+  const handleRemove = (id: string) =>
+    run(async () => {
+      await deletePasskey(id);
+      mutate((list) => list?.filter((p) => p.id !== id));
+    });
+
+  return (
+    <Show when={enabled()}>
+      <div class="pt-6 border-t border-white/10">
+        <div class="flex items-center gap-3 mb-4">
+          <Fingerprint class="w-5 h-5 text-text-secondary" />
+          <h3 class="text-lg font-semibold text-text-primary">Passkeys</h3>
+        </div>
+
+        <div class="bg-surface-base rounded-xl p-4 space-y-4">
+          <p class="text-sm text-text-secondary">
+            Sign in with your fingerprint, face, device PIN or a security key.
+            With two-factor authentication enabled, a passkey also works
+            instead of an authenticator code.
+          </p>
+
+          <Show when={(passkeys()?.length ?? 0) > 0}>
+            <div class="space-y-2">
+              <For each={passkeys()}>
+                {(passkey) => (
+                  <div class="flex items-center justify-between p-3 bg-white/5 rounded-lg">
+                    <div class="text-sm">
+                      <p class="text-text-primary">{passkey.name}</p>
+                      <p class="text-text-muted text-xs">
+                        Added {new Date(passkey.created_at).toLocaleDateString()}
+                        <Show when={passkey.last_used_at}>
+                          {(used) =>
+                            ` · last used ${new Date(used()).toLocaleDateString()}`
+                          }
+                        </Show>
+                      </p>
+                    </div>
+                    <button
+                      type="button"
+                      class="btn-secondary text-sm"
+                      onClick={() => handleRemove(passkey.id)}
+                      disabled={isBusy()}
+                    >
+                      Remove
+                    </button>
+                  </div>
+                )}
+              </For>
+            </div>
+          </Show>
+
+          <form class="space-y-2" onSubmit={handleAdd}>
+            <Show when={needsFreshSignIn()}>
+              <Show
+                when={!freshProof()}
+                fallback={
+                  <p class="text-sm text-text-secondary">
+                    Identity confirmed. Add the passkey within 5 minutes.
+                  </p>
+                }
+              >
+                <p class="text-sm text-text-secondary">
+                  Sign in again to confirm:
+                </p>
+                <div class="flex flex-wrap gap-2">
+                  <For each={methods()?.identities}>
+                    {(identity) => (
+                      <button
+                        type="button"
+                        class="btn-secondary text-sm"
+                        onClick={() =>
+                          confirm(() => confirmWithProvider(identity.provider))
+                        }
+                        disabled={isBusy()}
+                      >
+                        Confirm with {providerName(identity.provider)}
+                      </button>
+                    )}
+                  </For>
+                  <Show when={(passkeys()?.length ?? 0) > 0}>
+                    <button
+                      type="button"
+                      class="btn-secondary text-sm"
+                      onClick={() =>
+                        confirm(() =>
+                          confirmWithPasskey(authState.user?.username ?? ""),
+                        )
+                      }
+                      disabled={isBusy()}
+                    >
+                      Confirm with passkey
+                    </button>
+                  </Show>
+                </div>
+              </Show>
+            </Show>
+            <Show
+              when={hasPassword()}
+              fallback={
+                <Show when={needsMfaCode()}>
+                  <input
+                    type="text"
+                    inputmode="numeric"
+                    class="input-field w-full"
+                    placeholder="MFA code to confirm"
+                    value={mfaCode()}
+                    onInput={(e) => setMfaCode(e.currentTarget.value)}
+                    disabled={isBusy()}
+                  />
+                </Show>
+              }
+            >
+              <input
+                type="password"
+                class="input-field w-full"
+                placeholder="Current password to confirm"
+                value={currentPassword()}
+                onInput={(e) => setCurrentPassword(e.currentTarget.value)}
+                disabled={isBusy()}
+                autocomplete="current-password"
+              />
+            </Show>
+            <div class="flex gap-2">
+              <input
+                type="text"
+                class="input-field flex-1"
+                placeholder="Passkey name (e.g. Laptop)"
+                value={name()}
+                onInput={(e) => setName(e.currentTarget.value)}
+                disabled={isBusy()}
+                maxLength={64}
+              />
+              <button
+                type="submit"
+                class="btn-primary"
+                disabled={isBusy() || !name().trim()}
+              >
+                Add Passkey
+              </button>
+            </div>
+          </form>
+
+          <Show when={error()}>
+            <div class="p-3 rounded-lg bg-error-bg border border-error-border text-error-text text-sm">
+              {error()}
+            </div>
+          </Show>
+        </div>
+      </div>
+    </Show>
+  );
+};
+
+export default PasskeysSection;
@@ -1,7 +1,8 @@
 /**
  * Security Settings
  *
- * Shows E2EE backup status, MFA (TOTP) management, and clipboard protection settings.
+ * Shows E2EE backup status, MFA (TOTP) management, linked sign-in methods,
+ * device linking, and clipboard protection settings.
  */
 
 import {
@@ -40,6 +41,10 @@ import { authState } from "@/stores/auth";
 import { updateUser } from "@/stores/auth";
 import { showToast } from "@/components/ui/Toast";
 
+import LinkDeviceSection from "./LinkDeviceSection";
+import PasskeysSection from "./PasskeysSection";
+import SignInMethodsSection from "./SignInMethodsSection";
+
 const MfaSetupModal = lazy(() => import("./MfaSetupModal"));
 const BackupCodesDisplay = lazy(() => import("./BackupCodesDisplay"));
 
@@ -518,6 +523,15 @@ const SecuritySettings: Component<SecuritySettingsProps> = (props) => {
         </div>
       </Show>
 
+      {/* Sign-in Methods Section */}
+      <SignInMethodsSection />
+
+      {/* Passkeys Section */}
+      <PasskeysSection />
+
+      {/* Link a Device Section */}
+      <LinkDeviceSection />
+
       {/* Clipboard Protection Section */}
       <div class="pt-6 border-t border-white/10">
         <div class="flex items-center gap-3 mb-4">
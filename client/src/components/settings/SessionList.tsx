@@ -18,6 +18,8 @@ interface SessionSummary {
   avg_loss: number | null;
   avg_jitter: number | null;
   worst_quality: number | null;
+  /** Null for sessions recorded before clients reported it. */
+  noise_suppression: boolean | null;
 }
 
 interface SessionListResponse {
@@ -99,6 +101,11 @@ export const SessionList: Component = () => {
                   {formatTime(session.started_at)} -{" "}
                   {formatTime(session.ended_at)} (
                   {formatDuration(session.started_at, session.ended_at)})
+                  <Show when={session.noise_suppression !== null}>
+                    {" "}
+                    · Noise suppression{" "}
+                    {session.noise_suppression ? "on" : "off"}
+                  </Show>
                 </div>
               </div>
               <div class="text-right text-xs text-text-secondary">
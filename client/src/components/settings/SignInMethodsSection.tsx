@@ -0,0 +1,344 @@
+/**
+ * Sign-in Methods Section
+ *
+ * Lists the password and OIDC identities linked to the account, links new
+ * ones after re-authentication, and removes them while another usable
+ * method remains. Accounts without password or MFA re-authenticate by
+ * signing in again through a linked provider or with a passkey.
+ */
+
+import {
+  Component,
+  createResource,
+  createSignal,
+  For,
+  onCleanup,
+  Show,
+} from "solid-js";
+import { KeyRound, Link2 } from "lucide-solid";
+import {
+  fetchServerSettings,
+  getAuthMethods,
+  getServerUrl,
+  linkAuthMethod,
+  unlinkAuthMethod,
+  type Reauthentication,
+} from "@/lib/tauri";
+import { confirmWithPasskey, confirmWithProvider } from "@/lib/reauth";
+import type { OidcProvider } from "@/lib/types";
+import { authState } from "@/stores/auth";
+import { showToast } from "@/components/ui/Toast";
+
+const SignInMethodsSection: Component = () => {
+  const [methods, { mutate, refetch }] = createResource(getAuthMethods);
+  const [settings] = createResource(() =>
+    fetchServerSettings(getServerUrl()),
+  );
+
+  const [currentPassword, setCurrentPassword] = createSignal("");
+  const [mfaCode, setMfaCode] = createSignal("");
+  const [newPassword, setNewPassword] = createSignal("");
+  const [freshProof, setFreshProof] = createSignal<Reauthentication | null>(
+    null,
+  );
+  const [error, setError] = createSignal("");
+  const [isBusy, setIsBusy] = createSignal(false);
+
+  let messageHandler: ((event: MessageEvent) => void) | undefined;
+  onCleanup(() => {
+    if (messageHandler) window.removeEventListener("message", messageHandler);
+  });
+
+  const hasPassword = () => methods()?.has_password ?? false;
+  const needsMfaCode = () =>
+    !hasPassword() && (authState.user?.mfa_enabled ?? false);
+  const needsFreshSignIn = () =>
+    !hasPassword() && !(authState.user?.mfa_enabled ?? false);
+
+  const providerName = (slug: string) =>
+    settings()?.oidc_providers.find((p) => p.slug === slug)?.display_name ??
+    slug;
+
+  const unlinkedProviders = (): OidcProvider[] => {
+    const s = settings();
+    if (!s?.oidc_enabled) return [];
+    const linked = new Set(methods()?.identities.map((m) => m.provider));
+    return s.oidc_providers.filter((p) => !linked.has(p.slug));
+  };
+
+  const reauth = (): Reauthentication => ({
+    current_password: hasPassword() ? currentPassword() : undefined,
+    mfa_code: needsMfaCode() ? mfaCode() : undefined,
+    ...(needsFreshSignIn() ? freshProof() : null),
+  });
+
+  const run = async (action: () => Promise<void>) => {
+    setIsBusy(true);
+    setError("");
+    try {
+      await action();
+    } catch (err) {
+      setError(err instanceof Error ? err.message : String(err));
+    } finally {
+      setIsBusy(false);
+    }
+  };
+
+  const confirm = (proof: () => Promise<Reauthentication>) =>
+    run(async () => {
+      setFreshProof(await proof());
+    });
+
+  const handleAddPassword = (e: Event) => {
+    e.preventDefault();
+    run(async () => {
+      const result = await linkAuthMethod({
+        method: "local",
+        new_password: newPassword(),
+        ...reauth(),
+      });
+      if (result.methods) mutate(result.methods);
+      setNewPassword("");
+      setMfaCode("");
+      setFreshProof(null);
+      showToast({
+        type: "success",
+        title: "Password Added",
+        message: "You can now also sign in with your username and password.",
+        duration: 5000,
+      });
+    });
+  };
+
+  const handleLinkProvider = (provider: OidcProvider) =>
+    run(async () => {
+      const result = await linkAuthMethod({
+        method: "oidc",
+        provider: provider.slug,
+        ...reauth(),
+      });
+      if (!result.authorize_url) return;
+      setCurrentPassword("");
+      setMfaCode("");
+      setFreshProof(null);
+
+      // The callback page reports the outcome from the provider popup
+      const expectedOrigin = new URL(getServerUrl()).origin;
+      if (messageHandler) {
+        window.removeEventListener("message", messageHandler);
+      }
+      const handler = (event: MessageEvent) => {
+        if (event.origin !== expectedOrigin) return;
+        if (event.data?.type !== "oidc-link") return;
+        window.removeEventListener("message", handler);
+        messageHandler = undefined;
+        if (event.data.error) {
+          setError(event.data.error);
+          return;
+        }
+        refetch();
+        showToast({
+          type: "success",
+          title: "Account Linked",
+          message: `You can now sign in with ${provider.display_name}.`,
+          duration: 5000,
+        });
+      };
+      messageHandler = handler;
+      window.addEventListener("message", handler);
+      window.open(result.authorize_url, "oidc-link", "width=600,height=700");
+    });
+
+  const handleUnlink = (id: string) =>
+    run(async () => {
+      mutate(await unlinkAuthMethod(id, reauth()));
+      setCurrentPassword("");
+      setMfaCode("");
+      setFreshProof(null);
+    });
+
+  return (
+    <div class="pt-6 border-t border-white/10">
+      <div class="flex items-center gap-3 mb-4">
+        <Link2 class="w-5 h-5 text-text-secondary" />
+        <h3 class="text-lg font-semibold text-text-primary">
+          Sign-in Methods
+        </h3>
+      </div>
+
+      <div class="bg-surface-base rounded-xl p-4 space-y-4">
+        <Show when={needsFreshSignIn()}>
+          <Show
+            when={!freshProof()}
+            fallback={
+              <p class="text-sm text-text-secondary">
+                Identity confirmed. Make your change within 5 minutes.
+              </p>
+            }
+          >
+            <p class="text-sm text-text-secondary">
+              Sign in again to confirm changes:
+            </p>
+            <div class="flex flex-wrap gap-2">
+              <For each={methods()?.identities}>
+                {(identity) => (
+                  <button
+                    type="button"
+                    class="btn-secondary text-sm"
+                    onClick={() =>
+                      confirm(() => confirmWithProvider(identity.provider))
+                    }
+                    disabled={isBusy()}
+                  >
+                    Confirm with {providerName(identity.provider)}
+                  </button>
+                )}
+              </For>
+              <Show when={settings()?.passkeys_enabled}>
+                <button
+                  type="button"
+                  class="btn-secondary text-sm"
+                  onClick={() =>
+                    confirm(() =>
+                      confirmWithPasskey(authState.user?.username ?? ""),
+                    )
+                  }
+                  disabled={isBusy()}
+                >
+                  Confirm with passkey
+                </button>
+              </Show>
+            </div>
+          </Show>
+        </Show>
+
+        <Show
+          when={hasPassword()}
+          fallback={
+            <Show when={needsMfaCode()}>
+              <input
+                type="text"
+                inputmode="numeric"
+                class="input-field w-full"
+                placeholder="MFA code to confirm changes"
+                value={mfaCode()}
+                onInput={(e) => setMfaCode(e.currentTarget.value)}
+                disabled={isBusy()}
+              />
+            </Show>
+          }
+        >
+          <input
+            type="password"
+            class="input-field w-full"
+            placeholder="Current password to confirm changes"
+            value={currentPassword()}
+            onInput={(e) => setCurrentPassword(e.currentTarget.value)}
+            disabled={isBusy()}
+            autocomplete="current-password"
+          />
+        </Show>
+
+        <div class="space-y-2">
+          <div class="flex items-center justify-between p-3 bg-white/5 rounded-lg">
+            <div class="flex items-center gap-3">
+              <KeyRound class="w-4 h-4 text-text-secondary" />
+              <span class="text-sm text-text-primary">Password</span>
+            </div>
+            <Show
+              when={hasPassword()}
+              fallback={
+                <span class="text-xs text-text-muted">Not set</span>
+              }
+            >
+              <button
+                type="button"
+                class="btn-secondary text-sm"
+                onClick={() => handleUnlink("local")}
+                disabled={isBusy()}
+              >
+                Remove
+              </button>
+            </Show>
+          </div>
+
+          <For each={methods()?.identities}>
+            {(identity) => (
+              <div class="flex items-center justify-between p-3 bg-white/5 rounded-lg">
+                <div class="text-sm">
+                  <p class="text-text-primary">
+                    {providerName(identity.provider)}
+                  </p>
+                  <p class="text-text-muted text-xs">
+                    Linked {new Date(identity.created_at).toLocaleDateString()}
+                    <Show when={identity.last_used_at}>
+                      {(used) =>
+                        ` · last used ${new Date(used()).toLocaleDateString()}`
+                      }
+                    </Show>
+                  </p>
+                </div>
+                <button
+                  type="button"
+                  class="btn-secondary text-sm"
+                  onClick={() => handleUnlink(identity.id)}
+                  disabled={isBusy()}
+                >
+                  Remove
+                </button>
+              </div>
+            )}
+          </For>
+        </div>
+
+        <Show when={!hasPassword() && settings()?.auth_methods.local}>
+          <form class="flex gap-2" onSubmit={handleAddPassword}>
+            <input
+              type="password"
+              class="input-field flex-1"
+              placeholder="New password (8+ characters)"
+              value={newPassword()}
+              onInput={(e) => setNewPassword(e.currentTarget.value)}
+              disabled={isBusy()}
+              autocomplete="new-password"
+              minLength={8}
+              maxLength={128}
+            />
+            <button
+              type="submit"
+              class="btn-primary"
+              disabled={isBusy() || newPassword().length < 8}
+            >
+              Add Password
+            </button>
+          </form>
+        </Show>
+
+        <Show when={unlinkedProviders().length > 0}>
+          <div class="flex flex-wrap gap-2">
+            <For each={unlinkedProviders()}>
+              {(provider) => (
+                <button
+                  type="button"
+                  class="btn-secondary text-sm"
+                  onClick={() => handleLinkProvider(provider)}
+                  disabled={isBusy()}
+                >
+                  Link {provider.display_name}
+                </button>
+              )}
+            </For>
+          </div>
+        </Show>
+
+        <Show when={error()}>
+          <div class="p-3 rounded-lg bg-error-bg border border-error-border text-error-text text-sm">
+            {error()}
+          </div>
+        </Show>
+      </div>
+    </div>
+  );
+};
+
+export default SignInMethodsSection;
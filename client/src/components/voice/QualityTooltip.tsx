@@ -1,11 +1,20 @@
-import { Component } from "solid-js";
+import { Component, Show } from "solid-js";
 import type { ConnectionMetrics } from "../../lib/webrtc/types";
 import type { QualityLevel } from "../../lib/types";
+import type { IssueSource } from "../../stores/voice";
 
 interface QualityTooltipProps {
   metrics: ConnectionMetrics;
+  /** Likely origin of problems with this participant (remote tooltips only). */
+  issueSource?: IssueSource | null;
 }
 
+const issueSourceLabels: Record<IssueSource, string> = {
+  local: "Your connection",
+  remote: "Their connection",
+  both: "Both connections",
+};
+
 // Map semantic quality levels to user-friendly labels
 const qualityLabels: Record<QualityLevel, string> = {
   good: "Excellent",
@@ -19,6 +28,8 @@ const thresholds = {
   latency: { warning: 100, poor: 200 },
   packetLoss: { warning: 1, poor: 3 },
   jitter: { warning: 30, poor: 50 },
+  concealmentEvents: { warning: 5, poor: 15 },
+  jitterBufferDelay: { warning: 80, poor: 150 },
 };
 
 function getMetricStatus(
@@ -36,6 +47,10 @@ export const QualityTooltip: Component<QualityTooltipProps> = (props) => {
   const lossStatus = () =>
     getMetricStatus(props.metrics.packetLoss, "packetLoss");
   const jitterStatus = () => getMetricStatus(props.metrics.jitter, "jitter");
+  const concealmentStatus = () =>
+    getMetricStatus(props.metrics.concealmentEvents ?? 0, "concealmentEvents");
+  const jitterBufferStatus = () =>
+    getMetricStatus(props.metrics.jitterBufferDelay ?? 0, "jitterBufferDelay");
 
   const statusIcon = (status: "ok" | "warning" | "critical") => {
     switch (status) {
@@ -120,6 +135,46 @@ export const QualityTooltip: Component<QualityTooltipProps> = (props) => {
             </span>
           </span>
         </div>
+
+        <Show when={props.metrics.concealmentEvents !== undefined}>
+          <div class="flex justify-between items-center">
+            <span class="text-text-secondary">Concealment:</span>
+            <span class="flex items-center gap-1">
+              <span
+                class={
+                  concealmentStatus() !== "ok"
+                    ? "font-medium text-text-primary"
+                    : "text-text-secondary"
+                }
+              >
+                {props.metrics.concealmentEvents}
+              </span>
+              <span class={statusColor(concealmentStatus())}>
+                {statusIcon(concealmentStatus())}
+              </span>
+            </span>
+          </div>
+        </Show>
+
+        <Show when={props.metrics.jitterBufferDelay !== undefined}>
+          <div class="flex justify-between items-center">
+            <span class="text-text-secondary">Jitter Buffer:</span>
+            <span class="flex items-center gap-1">
+              <span
+                class={
+                  jitterBufferStatus() !== "ok"
+                    ? "font-medium text-text-primary"
+                    : "text-text-secondary"
+                }
+              >
+                {props.metrics.jitterBufferDelay}ms
+              </span>
+              <span class={statusColor(jitterBufferStatus())}>
+                {statusIcon(jitterBufferStatus())}
+              </span>
+            </span>
+          </div>
+        </Show>
       </div>
 
       <div class="border-t border-surface-layer1 my-2" />
@@ -130,6 +185,14 @@ export const QualityTooltip: Component<QualityTooltipProps> = (props) => {
           {qualityLabels[props.metrics.quality]}
         </span>
       </div>
+      <Show when={props.issueSource}>
+        {(source) => (
+          <div class="text-xs text-text-secondary mt-1">
+            Likely cause:{" "}
+            <span class="text-text-primary">{issueSourceLabels[source()]}</span>
+          </div>
+        )}
+      </Show>
     </div>
   );
 };
@@ -6,14 +6,29 @@ import {
   onMount,
   onCleanup,
 } from "solid-js";
-import { PhoneOff, Signal, MonitorUp } from "lucide-solid";
+import {
+  PhoneOff,
+  Signal,
+  MonitorUp,
+  MessageSquare,
+  Hand,
+  Volume2,
+} from "lucide-solid";
 import {
   voiceState,
   leaveVoice,
   getParticipants,
   getLocalMetrics,
+  setHandRaised,
+  promoteListener,
+  setUserGain,
 } from "@/stores/voice";
-import { getChannel } from "@/stores/channels";
+import { getVoiceUserGain } from "@/stores/preferences";
+import { getChannel, openVoiceChat } from "@/stores/channels";
+import { authState } from "@/stores/auth";
+import { isGuildOwner } from "@/stores/guilds";
+import { memberHasPermission } from "@/stores/permissions";
+import { PermissionBits } from "@/lib/permissionConstants";
 import { viewUserShare } from "@/stores/screenShareViewer";
 import { formatElapsedTime } from "@/lib/utils";
 import { QualityIndicator } from "./QualityIndicator";
@@ -37,6 +52,39 @@ const VoicePanel: Component = () => {
     leaveVoice();
   };
 
+  const handleOpenChat = () => {
+    if (voiceState.channelId) openVoiceChat(voiceState.channelId);
+  };
+
+  // Participant whose volume is being adjusted
+  const [gainUserId, setGainUserId] = createSignal<string | null>(null);
+
+  const toggleGainControl = (userId: string) => {
+    if (userId === authState.user?.id) return;
+    setGainUserId((current) => (current === userId ? null : userId));
+  };
+
+  // Listeners (a stage's audience) raise a hand; moderators let them speak
+  const self = () =>
+    participants().find((p) => p.user_id === authState.user?.id);
+
+  const canPromote = () => {
+    const guildId = channel()?.guild_id;
+    const userId = authState.user?.id;
+    if (!guildId || !userId) return false;
+
+    const isOwner = isGuildOwner(guildId, userId);
+    return (
+      isOwner ||
+      memberHasPermission(
+        guildId,
+        userId,
+        isOwner,
+        PermissionBits.VOICE_MUTE_OTHERS,
+      )
+    );
+  };
+
   const isConnected = () => voiceState.state === "connected";
   const isConnecting = () => voiceState.state === "connecting";
 
@@ -114,6 +162,29 @@ const VoicePanel: Component = () => {
               </div>
             </div>
           </div>
+          <Show when={self()?.listener}>
+            <button
+              data-testid="voice-raise-hand"
+              onClick={() => setHandRaised(!self()?.hand_raised)}
+              class="p-1.5 hover:bg-white/10 rounded transition-colors"
+              classList={{
+                "text-accent-primary": !!self()?.hand_raised,
+                "text-text-secondary hover:text-text-primary":
+                  !self()?.hand_raised,
+              }}
+              title={self()?.hand_raised ? "Lower hand" : "Raise hand to speak"}
+            >
+              <Hand class="w-4 h-4" />
+            </button>
+          </Show>
+          <button
+            data-testid="voice-chat"
+            onClick={handleOpenChat}
+            class="p-1.5 text-text-secondary hover:text-text-primary hover:bg-white/10 rounded transition-colors"
+            title="Open voice chat"
+          >
+            <MessageSquare class="w-4 h-4" />
+          </button>
           <button
             data-testid="voice-disconnect"
             onClick={handleDisconnect}
@@ -135,7 +206,8 @@ const VoicePanel: Component = () => {
                         ? "bg-accent-success/20 text-accent-success border border-accent-success/30 shadow-[0_0_8px_rgba(163,190,140,0.3)]"
                         : "bg-white/5 text-text-secondary border border-transparent"
                       } ${participant.muted ? "opacity-50" : "transition-all duration-200"}`}
-                    title={participant.muted ? "Muted" : undefined}
+                    title={participant.muted ? "Muted" : "Adjust volume"}
+                    onClick={() => toggleGainControl(participant.user_id)}
                   >
                     <div class="w-4 h-4 rounded-full bg-accent-primary/20 flex items-center justify-center text-accent-primary">
                       {/* Using first letter as avatar fallback for simplicity */}
@@ -144,6 +216,19 @@ const VoicePanel: Component = () => {
                     <span class="truncate max-w-20">
                       {participant.user_id.slice(0, 8)}
                     </span>
+                    <Show when={participant.hand_raised}>
+                      <button
+                        onClick={(e) => {
+                          e.stopPropagation();
+                          if (canPromote()) promoteListener(participant.user_id);
+                        }}
+                        disabled={!canPromote()}
+                        class="p-0.5 rounded transition-colors enabled:hover:bg-accent-primary/30"
+                        title={canPromote() ? "Let them speak" : "Hand raised"}
+                      >
+                        <Hand class="w-3 h-3 text-accent-primary" />
+                      </button>
+                    </Show>
                     {participant.screen_sharing && (
                       <button
                         onClick={(e) => {
@@ -160,6 +245,28 @@ const VoicePanel: Component = () => {
                 )}
               </For>
             </div>
+            <Show when={gainUserId()}>
+              {(userId) => (
+                <label class="flex items-center gap-2 mt-2 text-xs text-text-secondary">
+                  <Volume2 class="w-3.5 h-3.5 shrink-0" />
+                  <input
+                    type="range"
+                    min="0"
+                    max="200"
+                    step="5"
+                    value={Math.round(getVoiceUserGain(userId()) * 100)}
+                    onChange={(e) =>
+                      setUserGain(userId(), Number(e.currentTarget.value) / 100)
+                    }
+                    class="flex-1 accent-accent-primary"
+                    aria-label={`Volume for ${userId().slice(0, 8)}`}
+                  />
+                  <span class="w-9 text-right">
+                    {Math.round(getVoiceUserGain(userId()) * 100)}%
+                  </span>
+                </label>
+              )}
+            </Show>
           </div>
         </Show>
 
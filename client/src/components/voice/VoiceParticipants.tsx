@@ -4,6 +4,7 @@ import {
   voiceState,
   getLocalMetrics,
   getParticipantMetrics,
+  getIssueSource,
 } from "@/stores/voice";
 import { authState } from "@/stores/auth";
 import { QualityIndicator } from "./QualityIndicator";
@@ -118,6 +119,9 @@ const VoiceParticipants: Component<Props> = (props) => {
                 jitter: m.jitter,
                 quality: m.quality,
                 timestamp: Date.now(),
+                concealmentEvents: m.concealmentEvents,
+                jitterBufferDelay: m.jitterBufferDelay,
+                audioLevel: m.audioLevel,
               };
             };
 
@@ -139,7 +143,10 @@ const VoiceParticipants: Component<Props> = (props) => {
                   />
                   <Show when={showTooltip() && metricsForTooltip()}>
                     <div class="absolute bottom-full right-0 mb-2 z-50">
-                      <QualityTooltip metrics={metricsForTooltip()!} />
+                      <QualityTooltip
+                        metrics={metricsForTooltip()!}
+                        issueSource={getIssueSource(participant.user_id)}
+                      />
                     </div>
                   </Show>
                 </div>
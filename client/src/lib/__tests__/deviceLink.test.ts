@@ -0,0 +1,27 @@
+import { describe, it, expect } from "vitest";
+import {
+  deviceLinkUri,
+  formatDeviceLinkCode,
+  parseDeviceLinkCode,
+} from "../deviceLink";
+
+describe("deviceLink", () => {
+  it("round-trips a code through its QR payload", () => {
+    expect(parseDeviceLinkCode(deviceLinkUri("ABCDE12345"))).toBe(
+      "ABCDE12345",
+    );
+  });
+
+  it("accepts hand-typed codes", () => {
+    expect(parseDeviceLinkCode(formatDeviceLinkCode("ABCDE12345"))).toBe(
+      "ABCDE12345",
+    );
+    expect(parseDeviceLinkCode(" abcde 12345 ")).toBe("ABCDE12345");
+  });
+
+  it("rejects anything else", () => {
+    expect(parseDeviceLinkCode("ABCDE1234")).toBeNull();
+    expect(parseDeviceLinkCode("ABCDE1234U")).toBeNull();
+    expect(parseDeviceLinkCode("https://example.com/ABCDE12345")).toBeNull();
+  });
+});
@@ -0,0 +1,45 @@
+import { describe, it, expect, beforeEach } from "vitest";
+import {
+  getClockOffset,
+  recordTimeSync,
+  resetClockSync,
+} from "../serverClock";
+
+describe("serverClock", () => {
+  beforeEach(() => {
+    resetClockSync();
+  });
+
+  it("uses unsolicited readings until an echoed sample arrives", () => {
+    recordTimeSync(10_000, undefined, 4_000);
+    expect(getClockOffset()).toBe(6_000);
+
+    recordTimeSync(10_200, 4_000, 4_100);
+    expect(getClockOffset()).toBe(6_150);
+
+    recordTimeSync(20_000, undefined, 5_000);
+    expect(getClockOffset()).toBe(6_150);
+  });
+
+  it("corrects for half the round trip", () => {
+    // Server is 1s ahead; request took 50ms each way
+    recordTimeSync(2_050, 1_000, 1_100);
+    expect(getClockOffset()).toBe(1_000);
+  });
+
+  it("keeps the sample with the lowest round trip", () => {
+    recordTimeSync(2_000, 1_000, 1_200);
+    expect(getClockOffset()).toBe(900);
+
+    recordTimeSync(3_010, 2_000, 2_020);
+    expect(getClockOffset()).toBe(1_000);
+
+    recordTimeSync(9_999, 3_000, 3_500);
+    expect(getClockOffset()).toBe(1_000);
+  });
+
+  it("ignores samples with a negative round trip", () => {
+    recordTimeSync(2_000, 1_500, 1_000);
+    expect(getClockOffset()).toBe(0);
+  });
+});
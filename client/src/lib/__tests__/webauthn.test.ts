@@ -0,0 +1,50 @@
+import { describe, it, expect } from "vitest";
+import {
+  base64UrlToBuffer,
+  bufferToBase64Url,
+  toCreationOptions,
+  toRequestOptions,
+} from "../webauthn";
+
+function bytes(buffer: BufferSource | undefined): number[] {
+  return Array.from(new Uint8Array(buffer as ArrayBuffer));
+}
+
+describe("webauthn", () => {
+  it("round-trips base64url without padding", () => {
+    const encoded = bufferToBase64Url(new Uint8Array([251, 255, 191, 0]).buffer);
+    expect(encoded).toBe("-_-_AA");
+    expect(bytes(base64UrlToBuffer(encoded))).toEqual([251, 255, 191, 0]);
+  });
+
+  it("decodes binary fields of creation options", () => {
+    const options = toCreationOptions({
+      publicKey: {
+        rp: { id: "chat.example.com", name: "Kaiku" },
+        user: { id: "AQID", name: "alice", displayName: "Alice" },
+        challenge: "BAUG",
+        pubKeyCredParams: [{ type: "public-key", alg: -7 }],
+        excludeCredentials: [{ type: "public-key", id: "Bwg" }],
+      },
+    });
+    expect(bytes(options.user.id)).toEqual([1, 2, 3]);
+    expect(options.user.name).toBe("alice");
+    expect(bytes(options.challenge)).toEqual([4, 5, 6]);
+    expect(bytes(options.excludeCredentials?.[0].id)).toEqual([7, 8]);
+    expect(options.rp.id).toBe("chat.example.com");
+  });
+
+  it("decodes binary fields of request options", () => {
+    const options = toRequestOptions({
+      publicKey: {
+        challenge: "BAUG",
+        rpId: "chat.example.com",
+        allowCredentials: [{ type: "public-key", id: "Bwg" }],
+        userVerification: "required",
+      },
+    });
+    expect(bytes(options.challenge)).toEqual([4, 5, 6]);
+    expect(bytes(options.allowCredentials?.[0].id)).toEqual([7, 8]);
+    expect(options.userVerification).toBe("required");
+  });
+});
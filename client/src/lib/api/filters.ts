@@ -1,7 +1,8 @@
 /**
  * Content Filter API
  *
- * Guild content filter configuration, custom patterns, moderation log.
+ * Guild content filter configuration, custom patterns, link domains,
+ * channel language rules, moderation log.
  */
 
 import { getAccessToken } from "../tauri";
@@ -17,8 +18,13 @@ export type FilterCategory =
   | "hate_speech"
   | "spam"
   | "abusive_language"
-  | "custom";
+  | "custom"
+  | "language"
+  | "links";
 export type FilterAction = "block" | "log" | "warn";
+/** Which links the `links` category matches. */
+export type LinkFilterMode = "all" | "invites" | "allowlist";
+export type LinkDomainList = "allow" | "deny";
 
 export interface GuildFilterConfig {
   id: string;
@@ -26,6 +32,8 @@ export interface GuildFilterConfig {
   category: FilterCategory;
   enabled: boolean;
   action: FilterAction;
+  /** Only set for the `links` category. */
+  link_mode?: LinkFilterMode;
   created_at: string;
   updated_at: string;
 }
@@ -42,6 +50,93 @@ export interface GuildFilterPattern {
   updated_at: string;
 }
 
+export interface GuildLinkDomain {
+  id: string;
+  guild_id: string;
+  /** Lowercase host; also covers its subdomains. */
+  domain: string;
+  list: LinkDomainList;
+  created_by: string;
+  created_at: string;
+}
+
+export interface ChannelLanguageRule {
+  id: string;
+  guild_id: string;
+  channel_id: string;
+  /** ISO 639-3 codes, e.g. "eng", "deu". */
+  allowed_languages: string[];
+  action: FilterAction;
+  enabled: boolean;
+  created_at: string;
+  updated_at: string;
+}
+
+export interface SetLanguageRuleRequest {
+  allowed_languages: string[];
+  action?: FilterAction;
+  enabled?: boolean;
+}
+
+/** Obfuscation normalization stages applied before keyword matching. */
+export interface FilterNormalization {
+  /** Drop zero-width characters and diacritics. */
+  strip_invisible: boolean;
+  /** Fold look-alike letters (Cyrillic, fullwidth, styled Unicode) to ASCII. */
+  fold_confusables: boolean;
+  /** Map leetspeak digits and symbols to letters ("b@dw0rd"). */
+  substitute_leetspeak: boolean;
+}
+
+export type EscalationAction = "warn" | "timeout" | "ban";
+
+/** Automod policy applied after repeated filter violations. */
+export interface EscalationPolicy {
+  id: string;
+  guild_id: string;
+  name: string;
+  enabled: boolean;
+  /** Filter violations needed within the window. */
+  threshold: number;
+  window_seconds: number;
+  action: EscalationAction;
+  /** Only set for the `timeout` action. */
+  timeout_seconds?: number;
+  /** Channel that receives a notice when the policy fires. */
+  notify_channel_id?: string;
+  created_by?: string;
+  created_at: string;
+  updated_at: string;
+}
+
+export interface EscalationPolicyRequest {
+  name: string;
+  enabled?: boolean;
+  threshold: number;
+  window_seconds: number;
+  action: EscalationAction;
+  /** Required for the `timeout` action. */
+  timeout_seconds?: number;
+  notify_channel_id?: string;
+}
+
+export interface PolicyEvaluation {
+  policy_id: string;
+  name: string;
+  enabled: boolean;
+  action: EscalationAction;
+  threshold: number;
+  violations: number;
+}
+
+export interface EscalationEvaluation {
+  user_id: string;
+  /** Owners and moderators are never escalated. */
+  exempt: boolean;
+  policies: PolicyEvaluation[];
+  triggered_policy_id?: string;
+}
+
 export interface ModerationAction {
   id: string;
   guild_id: string;
@@ -66,6 +161,8 @@ export interface FilterConfigEntry {
   category: FilterCategory;
   enabled: boolean;
   action: FilterAction;
+  /** Only valid for the `links` category (default "invites"). */
+  link_mode?: LinkFilterMode;
 }
 
 export interface CreatePatternRequest {
@@ -235,6 +332,323 @@ export async function deleteCustomPattern(
   }
 }
 
+/**
+ * List the link allowlist and denylist for a guild.
+ */
+export async function listLinkDomains(
+  guildId: string,
+): Promise<GuildLinkDomain[]> {
+  const token = getAccessToken();
+  const response = await fetch(
+    `${API_BASE}/api/guilds/${guildId}/filters/domains`,
+    {
+      headers: { Authorization: `Bearer ${token}` },
+    },
+  );
+
+  if (!response.ok) {
+    throw new Error("Failed to load link domains");
+  }
+
+  return response.json();
+}
+
+/**
+ * Add a domain to the link allowlist or denylist.
+ */
+export async function addLinkDomain(
+  guildId: string,
+  domain: string,
+  list: LinkDomainList,
+): Promise<GuildLinkDomain> {
+  const token = getAccessToken();
+  const response = await fetch(
+    `${API_BASE}/api/guilds/${guildId}/filters/domains`,
+    {
+      method: "POST",
+      headers: {
+        "Content-Type": "application/json",
+        Authorization: `Bearer ${token}`,
+      },
+      body: JSON.stringify({ domain, list }),
+    },
+  );
+
+  if (!response.ok) {
+    const error = await response.text();
+    throw new Error(error || "Failed to add link domain");
+  }
+
+  return response.json();
+}
+
+/**
+ * Remove a domain from the link allowlist or denylist.
+ */
+export async function deleteLinkDomain(
+  guildId: string,
+  domainId: string,
+): Promise<void> {
+  const token = getAccessToken();
+  const response = await fetch(
+    `${API_BASE}/api/guilds/${guildId}/filters/domains/${domainId}`,
+    {
+      method: "DELETE",
+      headers: { Authorization: `Bearer ${token}` },
+    },
+  );
+
+  if (!response.ok) {
+    throw new Error("Failed to delete link domain");
+  }
+}
+
+/**
+ * List channel language rules for a guild.
+ */
+export async function listLanguageRules(
+  guildId: string,
+): Promise<ChannelLanguageRule[]> {
+  const token = getAccessToken();
+  const response = await fetch(
+    `${API_BASE}/api/guilds/${guildId}/filters/languages`,
+    {
+      headers: { Authorization: `Bearer ${token}` },
+    },
+  );
+
+  if (!response.ok) {
+    throw new Error("Failed to load language rules");
+  }
+
+  return response.json();
+}
+
+/**
+ * Create or replace a channel's language rule.
+ */
+export async function setLanguageRule(
+  guildId: string,
+  channelId: string,
+  data: SetLanguageRuleRequest,
+): Promise<ChannelLanguageRule> {
+  const token = getAccessToken();
+  const response = await fetch(
+    `${API_BASE}/api/guilds/${guildId}/filters/languages/${channelId}`,
+    {
+      method: "PUT",
+      headers: {
+        "Content-Type": "application/json",
+        Authorization: `Bearer ${token}`,
+      },
+      body: JSON.stringify(data),
+    },
+  );
+
+  if (!response.ok) {
+    const error = await response.text();
+    throw new Error(error || "Failed to save language rule");
+  }
+
+  return response.json();
+}
+
+/**
+ * Delete a channel's language rule.
+ */
+export async function deleteLanguageRule(
+  guildId: string,
+  channelId: string,
+): Promise<void> {
+  const token = getAccessToken();
+  const response = await fetch(
+    `${API_BASE}/api/guilds/${guildId}/filters/languages/${channelId}`,
+    {
+      method: "DELETE",
+      headers: { Authorization: `Bearer ${token}` },
+    },
+  );
+
+  if (!response.ok) {
+    throw new Error("Failed to delete language rule");
+  }
+}
+
+/**
+ * Get a guild's obfuscation normalization settings.
+ */
+export async function getFilterNormalization(
+  guildId: string,
+): Promise<FilterNormalization> {
+  const token = getAccessToken();
+  const response = await fetch(
+    `${API_BASE}/api/guilds/${guildId}/filters/normalization`,
+    {
+      headers: { Authorization: `Bearer ${token}` },
+    },
+  );
+
+  if (!response.ok) {
+    throw new Error("Failed to load normalization settings");
+  }
+
+  return response.json();
+}
+
+/**
+ * Replace a guild's obfuscation normalization settings.
+ */
+export async function updateFilterNormalization(
+  guildId: string,
+  data: FilterNormalization,
+): Promise<FilterNormalization> {
+  const token = getAccessToken();
+  const response = await fetch(
+    `${API_BASE}/api/guilds/${guildId}/filters/normalization`,
+    {
+      method: "PUT",
+      headers: {
+        "Content-Type": "application/json",
+        Authorization: `Bearer ${token}`,
+      },
+      body: JSON.stringify(data),
+    },
+  );
+
+  if (!response.ok) {
+    const error = await response.text();
+    throw new Error(error || "Failed to save normalization settings");
+  }
+
+  return response.json();
+}
+
+/**
+ * List automod escalation policies for a guild.
+ */
+export async function listEscalationPolicies(
+  guildId: string,
+): Promise<EscalationPolicy[]> {
+  const token = getAccessToken();
+  const response = await fetch(
+    `${API_BASE}/api/guilds/${guildId}/filters/escalations`,
+    {
+      headers: { Authorization: `Bearer ${token}` },
+    },
+  );
+
+  if (!response.ok) {
+    throw new Error("Failed to load escalation policies");
+  }
+
+  return response.json();
+}
+
+/**
+ * Create an automod escalation policy.
+ */
+export async function createEscalationPolicy(
+  guildId: string,
+  request: EscalationPolicyRequest,
+): Promise<EscalationPolicy> {
+  const token = getAccessToken();
+  const response = await fetch(
+    `${API_BASE}/api/guilds/${guildId}/filters/escalations`,
+    {
+      method: "POST",
+      headers: {
+        "Content-Type": "application/json",
+        Authorization: `Bearer ${token}`,
+      },
+      body: JSON.stringify(request),
+    },
+  );
+
+  if (!response.ok) {
+    const error = await response.text();
+    throw new Error(error || "Failed to create escalation policy");
+  }
+
+  return response.json();
+}
+
+/**
+ * Replace an automod escalation policy.
+ */
+export async function updateEscalationPolicy(
+  guildId: string,
+  policyId: string,
+  request: EscalationPolicyRequest,
+): Promise<EscalationPolicy> {
+  const token = getAccessToken();
+  const response = await fetch(
+    `${API_BASE}/api/guilds/${guildId}/filters/escalations/${policyId}`,
+    {
+      method: "PUT",
+      headers: {
+        "Content-Type": "application/json",
+        Authorization: `Bearer ${token}`,
+      },
+      body: JSON.stringify(request),
+    },
+  );
+
+  if (!response.ok) {
+    const error = await response.text();
+    throw new Error(error || "Failed to update escalation policy");
+  }
+
+  return response.json();
+}
+
+/**
+ * Delete an automod escalation policy.
+ */
+export async function deleteEscalationPolicy(
+  guildId: string,
+  policyId: string,
+): Promise<void> {
+  const token = getAccessToken();
+  const response = await fetch(
+    `${API_BASE}/api/guilds/${guildId}/filters/escalations/${policyId}`,
+    {
+      method: "DELETE",
+      headers: { Authorization: `Bearer ${token}` },
+    },
+  );
+
+  if (!response.ok) {
+    throw new Error("Failed to delete escalation policy");
+  }
+}
+
+/**
+ * Dry-run the escalation policies for a member.
+ */
+export async function evaluateEscalation(
+  guildId: string,
+  userId: string,
+): Promise<EscalationEvaluation> {
+  const token = getAccessToken();
+  const response = await fetch(
+    `${API_BASE}/api/guilds/${guildId}/filters/escalations/evaluate`,
+    {
+      method: "POST",
+      headers: {
+        "Content-Type": "application/json",
+        Authorization: `Bearer ${token}`,
+      },
+      body: JSON.stringify({ user_id: userId }),
+    },
+  );
+
+  if (!response.ok) {
+    throw new Error("Failed to evaluate escalation policies");
+  }
+
+  return response.json();
+}
+
 /**
  * List moderation log entries (paginated).
  */
@@ -260,10 +674,13 @@ export async function listModerationLog(
 
 /**
  * Test content against active filters (dry-run).
+ *
+ * Pass `channelId` to also apply that channel's language rule.
  */
 export async function testFilter(
   guildId: string,
   content: string,
+  channelId?: string,
 ): Promise<TestFilterResponse> {
   const token = getAccessToken();
   const response = await fetch(
@@ -274,7 +691,7 @@ export async function testFilter(
         "Content-Type": "application/json",
         Authorization: `Bearer ${token}`,
       },
-      body: JSON.stringify({ content }),
+      body: JSON.stringify({ content, channel_id: channelId }),
     },
   );
 
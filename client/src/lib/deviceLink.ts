@@ -0,0 +1,35 @@
+/**
+ * Device Link Codes
+ *
+ * A client signing in through another device shows its link code as a QR
+ * code (`kaiku://link-device/<code>`) and as text (`ABCDE-12345`). The
+ * approving device accepts either form, scanned or typed.
+ */
+
+const URI_PREFIX = "kaiku://link-device/";
+
+// Crockford base32, matching the server's code alphabet
+const CODE_PATTERN = /^[0-9A-HJKMNP-TV-Z]{10}$/;
+
+/** The QR code payload for a link code. */
+export function deviceLinkUri(code: string): string {
+  return `${URI_PREFIX}${code}`;
+}
+
+/** Split a link code in two halves for reading it aloud or typing it. */
+export function formatDeviceLinkCode(code: string): string {
+  return `${code.slice(0, 5)}-${code.slice(5)}`;
+}
+
+/**
+ * Extract a link code from a scanned QR payload or a hand-typed code.
+ * Returns null if the input is neither.
+ */
+export function parseDeviceLinkCode(input: string): string | null {
+  let text = input.trim();
+  if (text.toLowerCase().startsWith(URI_PREFIX)) {
+    text = text.slice(URI_PREFIX.length);
+  }
+  const code = text.replace(/[\s-]/g, "").toUpperCase();
+  return CODE_PATTERN.test(code) ? code : null;
+}
@@ -47,6 +47,15 @@ export const PermissionBits = {
 
   // Channel access (bit 24)
   VIEW_CHANNEL: 1 << 24,
+
+  // Recording (bit 25)
+  RECORD_VOICE: 1 << 25,
+
+  // Voice chat (bit 26)
+  VOICE_TEXT_CHAT: 1 << 26,
+
+  // Member profiles (bit 27)
+  MANAGE_NICKNAMES: 1 << 27,
 } as const;
 
 export type PermissionBit =
@@ -181,6 +190,23 @@ export const PERMISSIONS: PermissionDefinition[] = [
     category: "voice",
     forbiddenForEveryone: true,
   },
+  {
+    key: "VOICE_TEXT_CHAT",
+    bit: PermissionBits.VOICE_TEXT_CHAT,
+    name: "Voice Channel Chat",
+    description: "Allows sending messages in a voice channel's text chat",
+    category: "voice",
+    forbiddenForEveryone: false,
+  },
+  {
+    key: "RECORD_VOICE",
+    bit: PermissionBits.RECORD_VOICE,
+    name: "Record Voice",
+    description:
+      "Allows starting server-side recordings of voice channels (participants must consent)",
+    category: "voice",
+    forbiddenForEveryone: true,
+  },
 
   // Moderation permissions
   {
@@ -207,6 +233,15 @@ export const PERMISSIONS: PermissionDefinition[] = [
     category: "moderation",
     forbiddenForEveryone: true,
   },
+  {
+    key: "MANAGE_NICKNAMES",
+    bit: PermissionBits.MANAGE_NICKNAMES,
+    name: "Manage Nicknames",
+    description:
+      "Allows changing other members' server nicknames and resetting their server avatars",
+    category: "moderation",
+    forbiddenForEveryone: true,
+  },
   {
     key: "BAN_MEMBERS",
     bit: PermissionBits.BAN_MEMBERS,
@@ -354,6 +389,7 @@ export const EVERYONE_DEFAULT =
   PermissionBits.ADD_REACTIONS |
   PermissionBits.VOICE_CONNECT |
   PermissionBits.VOICE_SPEAK |
+  PermissionBits.VOICE_TEXT_CHAT |
   PermissionBits.CREATE_INVITE;
 
 export const MODERATOR_DEFAULT =
@@ -364,6 +400,7 @@ export const MODERATOR_DEFAULT =
   PermissionBits.MANAGE_MESSAGES |
   PermissionBits.TIMEOUT_MEMBERS |
   PermissionBits.KICK_MEMBERS |
+  PermissionBits.MANAGE_NICKNAMES |
   PermissionBits.VIEW_AUDIT_LOG |
   PermissionBits.MANAGE_INVITES |
   PermissionBits.MENTION_EVERYONE;
@@ -372,7 +409,8 @@ export const OFFICER_DEFAULT =
   MODERATOR_DEFAULT |
   PermissionBits.BAN_MEMBERS |
   PermissionBits.MANAGE_CHANNELS |
-  PermissionBits.MANAGE_PAGES;
+  PermissionBits.MANAGE_PAGES |
+  PermissionBits.RECORD_VOICE;
 
 // Permissions that @everyone can never have
 export const EVERYONE_FORBIDDEN =
@@ -390,7 +428,9 @@ export const EVERYONE_FORBIDDEN =
   PermissionBits.TRANSFER_OWNERSHIP |
   PermissionBits.MANAGE_INVITES |
   PermissionBits.MANAGE_PAGES |
-  PermissionBits.MENTION_EVERYONE;
+  PermissionBits.MENTION_EVERYONE |
+  PermissionBits.RECORD_VOICE |
+  PermissionBits.MANAGE_NICKNAMES;
 
 // Check if a permission is valid for @everyone role
 export function isValidForEveryone(permissions: number): boolean {
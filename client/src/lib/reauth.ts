@@ -0,0 +1,47 @@
+/**
+ * Fresh Sign-in Proofs
+ *
+ * Accounts without a password or MFA confirm changes to their sign-in
+ * methods by signing in again: through a linked OIDC provider, whose popup
+ * posts back a single-use `reauth_token`, or with one of their passkeys.
+ */
+
+import {
+  getServerUrl,
+  startOidcReauth,
+  type Reauthentication,
+} from "@/lib/tauri";
+import { passkeyAssertion } from "@/lib/webauthn";
+
+/**
+ * Sign in again through `provider` in a popup and resolve with the proof
+ * once the callback page reports back.
+ */
+export async function confirmWithProvider(
+  provider: string,
+): Promise<Reauthentication> {
+  const { authorize_url } = await startOidcReauth(provider);
+  const expectedOrigin = new URL(getServerUrl()).origin;
+
+  return new Promise((resolve, reject) => {
+    const handler = (event: MessageEvent) => {
+      if (event.origin !== expectedOrigin) return;
+      if (event.data?.type !== "oidc-reauth") return;
+      window.removeEventListener("message", handler);
+      if (event.data.error) {
+        reject(new Error(event.data.error));
+      } else {
+        resolve({ reauth_token: event.data.reauth_token });
+      }
+    };
+    window.addEventListener("message", handler);
+    window.open(authorize_url, "oidc-reauth", "width=600,height=700");
+  });
+}
+
+/** Sign a fresh challenge with one of `username`'s passkeys. */
+export async function confirmWithPasskey(
+  username: string,
+): Promise<Reauthentication> {
+  return { passkey: await passkeyAssertion(getServerUrl(), username) };
+}
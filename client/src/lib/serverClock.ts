@@ -0,0 +1,59 @@
+/**
+ * Server Clock
+ *
+ * Estimates the offset between the local clock and the server clock from
+ * `time_sync` WebSocket events, so server timestamps can be compared with
+ * "now" on machines whose clock is wrong.
+ */
+
+// Offset to add to Date.now() to get server time (ms)
+let offsetMs = 0;
+// Round trip of the sample the offset came from; Infinity for unsolicited readings
+let sampleRttMs = Infinity;
+
+/**
+ * Record a `time_sync` event.
+ *
+ * Readings that echo our `client_time` are corrected for half the round trip
+ * and preferred over unsolicited ones; among those, the lowest round trip wins.
+ */
+export function recordTimeSync(
+  serverTime: number,
+  clientTime: number | undefined,
+  receivedAt: number = Date.now(),
+): void {
+  if (clientTime === undefined) {
+    if (sampleRttMs === Infinity) {
+      offsetMs = serverTime - receivedAt;
+    }
+    return;
+  }
+
+  const rtt = receivedAt - clientTime;
+  if (rtt < 0 || rtt > sampleRttMs) return;
+
+  sampleRttMs = rtt;
+  offsetMs = serverTime - (clientTime + rtt / 2);
+}
+
+/**
+ * Forget previous samples (call when a new connection is established).
+ */
+export function resetClockSync(): void {
+  offsetMs = 0;
+  sampleRttMs = Infinity;
+}
+
+/**
+ * Estimated server clock minus local clock, in milliseconds.
+ */
+export function getClockOffset(): number {
+  return offsetMs;
+}
+
+/**
+ * Current time on the server clock (Unix milliseconds).
+ */
+export function serverNow(): number {
+  return Date.now() + offsetMs;
+}
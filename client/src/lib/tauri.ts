@@ -15,6 +15,7 @@ import type {
   GuildMember,
   GuildInvite,
   InviteResponse,
+  InvitePreview,
   InviteExpiry,
   Friend,
   Friendship,
@@ -35,10 +36,16 @@ import type {
   AdminStatus,
   UserSummary,
   GuildSummary,
+  DiscoveryQueueEntry,
+  PendingRegistration,
+  RegistrationCode,
+  CreateRegistrationCodeRequest,
   AuditLogEntry,
   PaginatedResponse,
+  ElevateCredentials,
   ElevateResponse,
   UserDetailsResponse,
+  UserSuspensionResponse,
   GuildDetailsResponse,
   BulkBanResponse,
   BulkSuspendResponse,
@@ -54,7 +61,11 @@ import type {
   SearchResponse,
   SearchFilters,
   GlobalSearchResponse,
+  AutocompleteType,
+  AutocompleteResponse,
   PaginatedMessages,
+  PaginatedChannelAttachments,
+  AttachmentKind,
   Pin,
   CreatePinRequest,
   UpdatePinRequest,
@@ -64,10 +75,13 @@ import type {
   AuthSettingsResponse,
   AuthMethodsConfig,
   AdminOidcProvider,
+  OidcProvisioning,
   UiState,
   GuildSettings,
   GuildUsageStats,
+  GuildPerks,
   DiscoverResponse,
+  RecommendedGuildsResponse,
   JoinDiscoverableResponse,
   PageRevision,
   RevisionListItem,
@@ -76,11 +90,19 @@ import type {
   TrendsResponse,
   TopRoutesResponse,
   TopErrorsResponse,
+  TopConsumersType,
+  TopConsumersSort,
+  TopConsumersResponse,
+  WsDisconnectBreakdown,
+  ReportSlaMetrics,
   LogsResponse,
   TracesResponse,
   ObsLinksResponse,
   ObsTimeRange,
   CustomStatus,
+  NetworkState,
+  GuildOnboarding,
+  WelcomeChannel,
 } from "./types";
 
 // Re-export types for convenience
@@ -95,6 +117,7 @@ export type {
   GuildMember,
   GuildInvite,
   InviteResponse,
+  InvitePreview,
   InviteExpiry,
   Friend,
   Friendship,
@@ -115,10 +138,16 @@ export type {
   AdminStatus,
   UserSummary,
   GuildSummary,
+  DiscoveryQueueEntry,
+  PendingRegistration,
+  RegistrationCode,
+  CreateRegistrationCodeRequest,
   AuditLogEntry,
   PaginatedResponse,
+  ElevateCredentials,
   ElevateResponse,
   UserDetailsResponse,
+  UserSuspensionResponse,
   GuildDetailsResponse,
   BulkBanResponse,
   BulkSuspendResponse,
@@ -134,6 +163,8 @@ export type {
   SearchResponse,
   SearchFilters,
   GlobalSearchResponse,
+  AutocompleteType,
+  AutocompleteResponse,
   Pin,
   CreatePinRequest,
   UpdatePinRequest,
@@ -143,9 +174,12 @@ export type {
   AuthSettingsResponse,
   AuthMethodsConfig,
   AdminOidcProvider,
+  OidcProvisioning,
   GuildSettings,
   GuildUsageStats,
+  GuildPerks,
   DiscoverResponse,
+  RecommendedGuildsResponse,
   JoinDiscoverableResponse,
   PageRevision,
   RevisionListItem,
@@ -425,35 +459,30 @@ function scheduleTokenRefresh() {
 
 /**
  * Refresh the access token. Browser mode sends a credentialed request so the
- * server reads the HttpOnly cookie; Tauri mode sends the refresh token in the
- * request body.
+ * server reads the HttpOnly cookie; in Tauri mode the backend holds the
+ * refresh token (in the OS keychain) and refreshes on its own schedule.
  */
 export async function refreshAccessToken(): Promise<boolean> {
+  if (isTauri) {
+    try {
+      const { invoke } = await import("@tauri-apps/api/core");
+      return await invoke<boolean>("refresh_session");
+    } catch (error) {
+      console.error("[Auth] Token refresh error:", error);
+      return false;
+    }
+  }
+
   try {
     console.log("[Auth] Refreshing access token...");
 
     const baseUrl = browserState.serverUrl.replace(/\/+$/, "");
 
-    // Tauri clients send refresh_token in body; browser sends nothing
-    // (server reads the HttpOnly cookie automatically).
-    const fetchOptions: RequestInit = {
+    // The server reads the refresh token from the HttpOnly cookie
+    const response = await fetch(`${baseUrl}/auth/refresh`, {
       method: "POST",
       credentials: "include",
-    };
-    if (isTauri) {
-      const refreshToken = browserState.refreshToken;
-      if (!refreshToken) {
-        clearBrowserTokens();
-        return false;
-      }
-
-      fetchOptions.headers = { "Content-Type": "application/json" };
-      fetchOptions.body = JSON.stringify({
-        refresh_token: refreshToken,
-      });
-    }
-
-    const response = await fetch(`${baseUrl}/auth/refresh`, fetchOptions);
+    });
 
     if (!response.ok) {
       console.error("[Auth] Token refresh failed:", response.status);
@@ -482,14 +511,6 @@ export async function refreshAccessToken(): Promise<boolean> {
     // Store access token in memory; browser mode relies on HttpOnly cookie
     // for the refresh token so we don't keep it in JS-accessible memory.
     browserState.accessToken = data.access_token;
-    if (isTauri) {
-      if (!data.refresh_token) {
-        console.error("[Auth] Token refresh returned empty refresh token");
-        clearBrowserTokens();
-        return false;
-      }
-      browserState.refreshToken = data.refresh_token;
-    }
     browserState.tokenExpiresAt = Date.now() + data.expires_in * 1000;
 
     console.log("[Auth] Token refreshed successfully");
@@ -553,6 +574,27 @@ class HttpError extends Error {
   }
 }
 
+/** Broad category of a failed command, for choosing what to show the user. */
+export type CommandErrorKind = "offline" | "server" | "client";
+
+/**
+ * Classify an error thrown by a command.
+ *
+ * Tauri commands prefix unreachable-server errors with `Offline:` and 5xx
+ * responses with `Server error:`; browser mode throws the same prefixes for
+ * failed fetches and `HttpError` for error responses.
+ */
+export function commandErrorKind(error: unknown): CommandErrorKind {
+  if (error instanceof HttpError) {
+    return error.status >= 500 ? "server" : "client";
+  }
+  const message =
+    error instanceof Error ? error.message : String(error ?? "");
+  if (message.startsWith("Offline:")) return "offline";
+  if (message.startsWith("Server error:")) return "server";
+  return "client";
+}
+
 // HTTP helper for browser mode
 async function httpRequest<T>(
   method: string,
@@ -594,12 +636,20 @@ async function httpRequest<T>(
     headers: JSON.stringify(logHeaders),
   });
 
-  const response = await fetch(`${baseUrl}${cleanPath}`, {
-    method,
-    headers,
-    body: body ? JSON.stringify(body) : undefined,
-    credentials: "include",
-  });
+  let response: Response;
+  try {
+    response = await fetch(`${baseUrl}${cleanPath}`, {
+      method,
+      headers,
+      body: body ? JSON.stringify(body) : undefined,
+      credentials: "include",
+    });
+  } catch (fetchError) {
+    // fetch() only rejects when no response arrived
+    const reason =
+      fetchError instanceof Error ? fetchError.message : String(fetchError);
+    throw new Error(`Offline: ${reason}`);
+  }
 
   if (!response.ok) {
     let errorMessage = `HTTP ${response.status}: ${response.statusText}`;
@@ -665,6 +715,7 @@ export async function login(
   username: string,
   password: string,
   mfaCode?: string,
+  passkey?: PasskeyAssertion,
 ): Promise<AuthResult> {
   if (isTauri) {
     const { invoke } = await import("@tauri-apps/api/core");
@@ -674,6 +725,7 @@ export async function login(
         username,
         password,
         mfa_code: mfaCode ?? null,
+        passkey: passkey ?? null,
       },
     });
   }
@@ -687,6 +739,9 @@ export async function login(
   if (mfaCode) {
     body.mfa_code = mfaCode;
   }
+  if (passkey) {
+    body.passkey = passkey;
+  }
 
   const response = await httpRequest<AuthResponse>("POST", "/auth/login", body);
 
@@ -782,12 +837,36 @@ export async function updateCustomStatus(
   }
 }
 
+/**
+ * Set how the user's display name is pronounced in screen-reader
+ * announcements (null or empty clears it).
+ */
+export async function updateNamePronunciation(
+  pronunciation: string | null,
+): Promise<void> {
+  await httpRequest("POST", "/auth/me", {
+    name_pronunciation: pronunciation,
+  });
+}
+
+/**
+ * Speak a short announcement through the OS text-to-speech engine.
+ * Desktop only; a no-op in the browser.
+ */
+export async function speakEvent(text: string): Promise<void> {
+  if (isTauri) {
+    const { invoke } = await import("@tauri-apps/api/core");
+    await invoke("speak_event", { text });
+  }
+}
+
 export async function register(
   serverUrl: string,
   username: string,
   password: string,
   email?: string,
   displayName?: string,
+  registrationCode?: string,
 ): Promise<AuthResult> {
   if (isTauri) {
     const { invoke } = await import("@tauri-apps/api/core");
@@ -798,6 +877,7 @@ export async function register(
         email,
         password,
         display_name: displayName,
+        registration_code: registrationCode,
       },
     });
   }
@@ -807,13 +887,21 @@ export async function register(
   localStorage.setItem("serverUrl", serverUrl);
   setSessionRestoreBlocked(false);
 
-  const response = await httpRequest<AuthResponse>("POST", "/auth/register", {
+  const response = await httpRequest<
+    AuthResponse | { pending_approval: true }
+  >("POST", "/auth/register", {
     username,
     password,
     email,
     display_name: displayName,
+    registration_code: registrationCode,
   });
 
+  // Under the "approval" policy the account waits for an admin, no session
+  if ("pending_approval" in response) {
+    throw new Error("REGISTRATION_PENDING_APPROVAL");
+  }
+
   // Store access token in memory; browser relies on HttpOnly cookie for refresh
   browserState.accessToken = response.access_token;
   if (isTauri) {
@@ -886,7 +974,12 @@ export async function logout(): Promise<void> {
 export async function getCurrentUser(): Promise<User | null> {
   if (isTauri) {
     const { invoke } = await import("@tauri-apps/api/core");
-    return invoke("get_current_user");
+    // Lets the backend migrate a refresh token stored by earlier builds
+    const legacyServerUrl =
+      localStorage.getItem("serverUrl") ||
+      import.meta.env.VITE_SERVER_URL ||
+      null;
+    return invoke("get_current_user", { legacyServerUrl });
   }
 
   // Browser mode - check if we have a token
@@ -959,6 +1052,139 @@ export async function updatePassword(current_password: string, new_password: str
   return httpRequest("POST", "/auth/me/password", { current_password, new_password });
 }
 
+/** Email a new verification code to the current user's address. */
+export async function resendEmailVerification(): Promise<void> {
+  await httpRequest("POST", "/auth/email/verification");
+}
+
+/** Verify the current user's email with a code from a verification email. */
+export async function verifyEmail(token: string): Promise<void> {
+  await httpRequest("POST", "/auth/email/verify", { token });
+}
+
+export interface ChangeUsernameResponse {
+  username: string;
+  previous_username: string;
+  /** Until when @mentions of the previous username still reach this user. */
+  previous_username_held_until: string;
+  /** Earliest time the username can be changed again. */
+  next_change_at: string;
+}
+
+/**
+ * Change the current user's username.
+ *
+ * Limited to one change per cooldown period; the previous username stays
+ * held for this user for a while so mentions of it keep working.
+ */
+export async function changeUsername(
+  username: string,
+): Promise<ChangeUsernameResponse> {
+  return httpRequest<ChangeUsernameResponse>("PATCH", "/api/me/username", {
+    username,
+  });
+}
+
+export interface LinkedIdentity {
+  id: string;
+  method: "oidc" | "ldap";
+  /** Provider slug, e.g. the OIDC provider. */
+  provider: string;
+  created_at: string;
+  last_used_at: string | null;
+}
+
+export interface AuthMethodsResponse {
+  has_password: boolean;
+  identities: LinkedIdentity[];
+}
+
+/**
+ * Proof that the user still controls the account, required to change its
+ * sign-in methods. Accounts without password or MFA send a `reauth_token`
+ * from {@link startOidcReauth} or a passkey assertion.
+ */
+export interface Reauthentication {
+  /** Required when the account has a password. */
+  current_password?: string;
+  /** Required for password-less accounts with MFA enabled. */
+  mfa_code?: string;
+  /** Token from a fresh sign-in through a linked provider. */
+  reauth_token?: string;
+  /** Assertion from one of the account's passkeys. */
+  passkey?: PasskeyAssertion;
+}
+
+export interface LinkAuthMethodRequest extends Reauthentication {
+  method: "local" | "oidc";
+  /** Password to add (for `local`). */
+  new_password?: string;
+  /** Provider slug (for `oidc`). */
+  provider?: string;
+}
+
+export interface LinkAuthMethodResponse {
+  /** Provider authorization URL to open (OIDC). */
+  authorize_url: string | null;
+  /** Sign-in methods after linking (password). */
+  methods: AuthMethodsResponse | null;
+}
+
+/**
+ * List the sign-in methods linked to the current account.
+ */
+export async function getAuthMethods(): Promise<AuthMethodsResponse> {
+  return httpRequest<AuthMethodsResponse>("GET", "/api/me/auth-methods");
+}
+
+/**
+ * Link a password or an OIDC identity to the current account.
+ *
+ * OIDC links return an `authorize_url`; the identity is linked once the
+ * provider redirects back, which the callback page reports with an
+ * `oidc-link` postMessage.
+ */
+export async function linkAuthMethod(
+  request: LinkAuthMethodRequest,
+): Promise<LinkAuthMethodResponse> {
+  return httpRequest<LinkAuthMethodResponse>(
+    "POST",
+    "/api/me/auth-methods/link",
+    request,
+  );
+}
+
+/**
+ * Unlink a sign-in method: `"local"` for the password, or an identity ID.
+ * Requires re-authentication as for linking. The server refuses to remove
+ * the last usable method.
+ */
+export async function unlinkAuthMethod(
+  id: string,
+  reauth: Reauthentication,
+): Promise<AuthMethodsResponse> {
+  return httpRequest<AuthMethodsResponse>(
+    "DELETE",
+    `/api/me/auth-methods/${encodeURIComponent(id)}`,
+    reauth,
+  );
+}
+
+/**
+ * Start a fresh sign-in through a linked provider, for accounts without
+ * password or MFA. The callback page posts an `oidc-reauth` message with a
+ * single-use `reauth_token`.
+ */
+export async function startOidcReauth(
+  provider: string,
+): Promise<{ authorize_url: string }> {
+  return httpRequest<{ authorize_url: string }>(
+    "POST",
+    "/api/me/auth-methods/reauth",
+    { provider },
+  );
+}
+
 // ============================================================================
 // MFA Commands
 // ============================================================================
@@ -1119,6 +1345,70 @@ export async function uploadAvatar(file: File): Promise<User> {
   }
 }
 
+export interface UpdateProfileCustomizationRequest {
+  /** Markdown, at most 1000 characters; `null` clears it. */
+  about_me?: string | null;
+  /** At most 40 characters; `null` clears them. */
+  pronouns?: string | null;
+  /** `#rrggbb`; `null` clears it. */
+  accent_color?: string | null;
+}
+
+/**
+ * Update the current user's about-me text, pronouns and accent colour.
+ */
+export async function updateProfileCustomization(
+  request: UpdateProfileCustomizationRequest,
+): Promise<User> {
+  return httpRequest<User>("PATCH", "/api/me/profile", request);
+}
+
+/**
+ * Upload a profile banner. Limited to the avatar size.
+ */
+export async function uploadBanner(file: File): Promise<User> {
+  const error = validateFileSize(file, "avatar");
+  if (error) {
+    throw new Error(error);
+  }
+
+  const { token, baseUrl } = await getUploadAuth();
+
+  const headers: Record<string, string> = {};
+  if (token) {
+    headers["Authorization"] = `Bearer ${token}`;
+  }
+
+  const formData = new FormData();
+  formData.append("banner", file);
+
+  const response = await fetch(`${baseUrl}/api/me/profile/banner`, {
+    method: "POST",
+    headers,
+    body: formData,
+  });
+
+  if (!response.ok) {
+    let errorMessage = `Upload failed (HTTP ${response.status})`;
+    try {
+      const errorBody = await response.json();
+      errorMessage = errorBody.message || errorBody.error || errorMessage;
+    } catch {
+      errorMessage = response.statusText || errorMessage;
+    }
+    throw new Error(errorMessage);
+  }
+
+  return response.json();
+}
+
+/**
+ * Remove the current user's profile banner.
+ */
+export async function removeBanner(): Promise<User> {
+  return httpRequest<User>("DELETE", "/api/me/profile/banner");
+}
+
 // Chat Commands
 
 export async function getChannels(): Promise<Channel[]> {
@@ -1132,7 +1422,7 @@ export async function getChannels(): Promise<Channel[]> {
 
 export async function createChannel(
   name: string,
-  channelType: "text" | "voice",
+  channelType: "text" | "voice" | "stage",
   guildId?: string,
   topic?: string,
   categoryId?: string,
@@ -1178,6 +1468,22 @@ export async function getMessages(
   );
 }
 
+export async function getChannelAttachments(
+  channelId: string,
+  options?: { before?: string; kind?: AttachmentKind; limit?: number },
+): Promise<PaginatedChannelAttachments> {
+  const params = new URLSearchParams();
+  if (options?.before) params.set("before", options.before);
+  if (options?.kind) params.set("kind", options.kind);
+  if (options?.limit) params.set("limit", options.limit.toString());
+  const query = params.toString();
+
+  return httpRequest<PaginatedChannelAttachments>(
+    "GET",
+    `/api/channels/${channelId}/attachments${query ? `?${query}` : ""}`,
+  );
+}
+
 export async function sendMessage(
   channelId: string,
   content: string,
@@ -1449,6 +1755,18 @@ export async function getGuilds(): Promise<Guild[]> {
   return httpRequest<Guild[]>("GET", "/api/guilds");
 }
 
+/**
+ * Make the desktop guild cache refetch the guild list and a guild's lists
+ * (or every list), for changes the server does not announce over WebSocket.
+ * No-op in the browser, which does not cache.
+ */
+export async function invalidateGuildCache(guildId?: string): Promise<void> {
+  if (isTauri) {
+    const { invoke } = await import("@tauri-apps/api/core");
+    await invoke("invalidate_guild_cache", { guildId });
+  }
+}
+
 export async function getGuild(guildId: string): Promise<Guild> {
   if (isTauri) {
     const { invoke } = await import("@tauri-apps/api/core");
@@ -1474,7 +1792,6 @@ export async function updateGuild(
   guildId: string,
   name?: string,
   description?: string,
-  icon_url?: string,
 ): Promise<Guild> {
   if (isTauri) {
     const { invoke } = await import("@tauri-apps/api/core");
@@ -1482,14 +1799,113 @@ export async function updateGuild(
       guildId,
       name,
       description,
-      iconUrl: icon_url,
     });
   }
 
   return httpRequest<Guild>("PATCH", `/api/guilds/${guildId}`, {
     name,
     description,
-    icon_url,
+  });
+}
+
+/**
+ * Upload a guild icon or banner (requires MANAGE_GUILD). The server converts
+ * it to WebP renditions and returns the updated guild.
+ */
+export async function uploadGuildImage(
+  guildId: string,
+  kind: "icon" | "banner",
+  file: File,
+): Promise<Guild> {
+  // Frontend validation (guild images share the avatar limit)
+  const validationError = validateFileSize(file, "avatar");
+  if (validationError) {
+    console.warn(
+      "[uploadGuildImage] Frontend validation failed:",
+      validationError,
+    );
+    throw new Error(validationError);
+  }
+
+  const { token, baseUrl } = await getUploadAuth();
+
+  const headers: Record<string, string> = {};
+  if (token) {
+    headers["Authorization"] = `Bearer ${token}`;
+  }
+
+  const formData = new FormData();
+  formData.append("file", file);
+
+  const response = await fetch(`${baseUrl}/api/guilds/${guildId}/${kind}`, {
+    method: "POST",
+    headers,
+    body: formData,
+  });
+
+  if (!response.ok) {
+    let errorMessage = `Upload failed (HTTP ${response.status})`;
+
+    try {
+      const errorBody = await response.json();
+      errorMessage = errorBody.message || errorBody.error || errorMessage;
+    } catch (parseError) {
+      console.warn(
+        "[uploadGuildImage] Failed to parse error response:",
+        parseError,
+      );
+      errorMessage = response.statusText || errorMessage;
+    }
+
+    console.error("[uploadGuildImage] Upload failed:", {
+      status: response.status,
+      error: errorMessage,
+      guildId,
+      kind,
+      fileSize: file.size,
+      fileName: file.name,
+    });
+
+    throw new Error(errorMessage);
+  }
+
+  try {
+    return await response.json();
+  } catch (parseError) {
+    console.error(
+      "[uploadGuildImage] Failed to parse success response:",
+      parseError,
+    );
+    throw new Error("Server returned invalid response");
+  }
+}
+
+/**
+ * Remove a guild icon or banner (requires MANAGE_GUILD).
+ */
+export async function removeGuildImage(
+  guildId: string,
+  kind: "icon" | "banner",
+): Promise<Guild> {
+  return fetchApi<Guild>(`/api/guilds/${guildId}/${kind}`, {
+    method: "DELETE",
+  });
+}
+
+/**
+ * Designate the guild's system and rules channels (owner only).
+ * `null` clears a designation; omitted fields are left unchanged.
+ */
+export async function updateGuildChannels(
+  guildId: string,
+  channels: {
+    system_channel_id?: string | null;
+    rules_channel_id?: string | null;
+  },
+): Promise<Guild> {
+  return fetchApi<Guild>(`/api/guilds/${guildId}`, {
+    method: "PATCH",
+    body: channels,
   });
 }
 
@@ -1558,6 +1974,13 @@ export async function getGuildUsage(guildId: string): Promise<GuildUsageStats> {
   return fetchApi<GuildUsageStats>(`/api/guilds/${guildId}/usage`);
 }
 
+/**
+ * Get guild supporter perks (tier, emoji slots, upload size, voice bitrate).
+ */
+export async function getGuildPerks(guildId: string): Promise<GuildPerks> {
+  return fetchApi<GuildPerks>(`/api/guilds/${guildId}/perks`);
+}
+
 /**
  * Update guild settings (requires MANAGE_GUILD).
  */
@@ -1567,7 +1990,8 @@ export async function updateGuildSettings(
     threads_enabled?: boolean;
     discoverable?: boolean;
     tags?: string[];
-    banner_url?: string | null;
+    voice_log_channel_id?: string | null;
+    voice_region?: string | null;
   },
 ): Promise<GuildSettings> {
   return fetchApi<GuildSettings>(`/api/guilds/${guildId}/settings`, {
@@ -1582,7 +2006,7 @@ export async function updateGuildSettings(
 export async function discoverGuilds(params?: {
   q?: string;
   tags?: string[];
-  sort?: "members" | "newest";
+  sort?: "members" | "newest" | "trending";
   limit?: number;
   offset?: number;
 }): Promise<DiscoverResponse> {
@@ -1598,6 +2022,32 @@ export async function discoverGuilds(params?: {
   );
 }
 
+/**
+ * Discoverable guilds sharing tags with the user's guilds (requires auth).
+ */
+export async function getRecommendedGuilds(
+  limit?: number,
+): Promise<RecommendedGuildsResponse> {
+  const qs = limit != null ? `?limit=${limit}` : "";
+  return fetchApi<RecommendedGuildsResponse>(
+    `/api/discover/guilds/recommended${qs}`,
+  );
+}
+
+/**
+ * Report a discovery listing (requires auth). Enough reports delist the guild
+ * until an admin reviews it again.
+ */
+export async function reportDiscoveryListing(
+  guildId: string,
+  reason: string,
+): Promise<void> {
+  await fetchApi<void>(`/api/discover/guilds/${guildId}/report`, {
+    method: "POST",
+    body: { reason },
+  });
+}
+
 /**
  * Join a discoverable guild (requires auth).
  */
@@ -1660,12 +2110,32 @@ export async function searchGuildMessages(
 }
 
 /**
- * Search messages in DM channels using full-text search.
+ * Suggest guild members, channels, or roles for a mention prefix.
  */
-export async function searchDMMessages(
+export async function guildAutocomplete(
+  guildId: string,
   query: string,
-  limit: number = 25,
-  offset: number = 0,
+  type: AutocompleteType,
+  limit: number = 10,
+): Promise<AutocompleteResponse> {
+  const params = new URLSearchParams({
+    q: query,
+    type,
+    limit: limit.toString(),
+  });
+  return httpRequest<AutocompleteResponse>(
+    "GET",
+    `/api/guilds/${guildId}/autocomplete?${params}`,
+  );
+}
+
+/**
+ * Search messages in DM channels using full-text search.
+ */
+export async function searchDMMessages(
+  query: string,
+  limit: number = 25,
+  offset: number = 0,
   filters?: SearchFilters,
 ): Promise<SearchResponse> {
   const params = new URLSearchParams({
@@ -1707,7 +2177,7 @@ export async function searchGlobalMessages(
 // Guild Invite Commands
 
 /**
- * Get invites for a guild (owner only)
+ * Get invites for a guild (requires MANAGE_INVITES)
  */
 export async function getGuildInvites(guildId: string): Promise<GuildInvite[]> {
   if (isTauri) {
@@ -1719,24 +2189,26 @@ export async function getGuildInvites(guildId: string): Promise<GuildInvite[]> {
 }
 
 /**
- * Create a new invite for a guild (owner only)
+ * Create a new invite for a guild (requires CREATE_INVITE)
  */
 export async function createGuildInvite(
   guildId: string,
   expiresIn: InviteExpiry = "7d",
+  maxUses: number | null = null,
 ): Promise<GuildInvite> {
   if (isTauri) {
     const { invoke } = await import("@tauri-apps/api/core");
-    return invoke("create_guild_invite", { guildId, expiresIn });
+    return invoke("create_guild_invite", { guildId, expiresIn, maxUses });
   }
 
   return httpRequest<GuildInvite>("POST", `/api/guilds/${guildId}/invites`, {
     expires_in: expiresIn,
+    max_uses: maxUses,
   });
 }
 
 /**
- * Delete/revoke an invite (owner only)
+ * Delete/revoke an invite (requires MANAGE_INVITES, or being its creator)
  */
 export async function deleteGuildInvite(
   guildId: string,
@@ -1750,6 +2222,13 @@ export async function deleteGuildInvite(
   await httpRequest<void>("DELETE", `/api/guilds/${guildId}/invites/${code}`);
 }
 
+/**
+ * Preview an invite (works without being logged in)
+ */
+export async function getInvitePreview(code: string): Promise<InvitePreview> {
+  return fetchApi<InvitePreview>(`/api/invites/${code}`);
+}
+
 /**
  * Join a guild via invite code
  */
@@ -1777,6 +2256,123 @@ export async function kickGuildMember(
   await httpRequest<void>("DELETE", `/api/guilds/${guildId}/members/${userId}`);
 }
 
+export interface UpdateGuildMemberRequest {
+  /** Guild nickname (max 32 characters); `null` or blank clears it. */
+  nickname?: string | null;
+  /** Only `null` is accepted: resets the guild avatar. */
+  guild_avatar_url?: null;
+}
+
+/**
+ * Change a member's guild nickname or reset their guild avatar.
+ * Changing other members needs MANAGE_NICKNAMES.
+ */
+export async function updateGuildMember(
+  guildId: string,
+  userId: string,
+  request: UpdateGuildMemberRequest,
+): Promise<GuildMember> {
+  return httpRequest<GuildMember>(
+    "PATCH",
+    `/api/guilds/${guildId}/members/${userId}`,
+    request,
+  );
+}
+
+export interface UpdateGuildOnboardingRequest {
+  enabled: boolean;
+  welcome_description: string | null;
+  welcome_channels: WelcomeChannel[];
+  /** Guild page that requires acceptance; `null` disables the rules gate. */
+  rules_page_id: string | null;
+  default_role_ids: string[];
+}
+
+/**
+ * Get a guild's onboarding (welcome screen, rules gate, default roles).
+ */
+export async function getGuildOnboarding(
+  guildId: string,
+): Promise<GuildOnboarding> {
+  return httpRequest<GuildOnboarding>(
+    "GET",
+    `/api/guilds/${guildId}/onboarding`,
+  );
+}
+
+/**
+ * Replace a guild's onboarding (requires MANAGE_GUILD; default roles also
+ * need MANAGE_ROLES).
+ */
+export async function updateGuildOnboarding(
+  guildId: string,
+  request: UpdateGuildOnboardingRequest,
+): Promise<GuildOnboarding> {
+  return httpRequest<GuildOnboarding>(
+    "PUT",
+    `/api/guilds/${guildId}/onboarding`,
+    request,
+  );
+}
+
+/**
+ * Complete onboarding: accepts the rules page and assigns the default roles.
+ * Returns the member's role IDs afterwards.
+ */
+export async function completeGuildOnboarding(
+  guildId: string,
+): Promise<{ role_ids: string[] }> {
+  return httpRequest<{ role_ids: string[] }>(
+    "POST",
+    `/api/guilds/${guildId}/onboarding/complete`,
+  );
+}
+
+/**
+ * Upload the current user's avatar for one guild.
+ */
+export async function uploadGuildMemberAvatar(
+  guildId: string,
+  file: File,
+): Promise<GuildMember> {
+  const error = validateFileSize(file, "avatar");
+  if (error) {
+    throw new Error(error);
+  }
+
+  const { token, baseUrl } = await getUploadAuth();
+
+  const headers: Record<string, string> = {};
+  if (token) {
+    headers["Authorization"] = `Bearer ${token}`;
+  }
+
+  const formData = new FormData();
+  formData.append("file", file);
+
+  const response = await fetch(
+    `${baseUrl}/api/guilds/${guildId}/members/@me/avatar`,
+    {
+      method: "POST",
+      headers,
+      body: formData,
+    },
+  );
+
+  if (!response.ok) {
+    let errorMessage = `Upload failed (HTTP ${response.status})`;
+    try {
+      const errorBody = await response.json();
+      errorMessage = errorBody.message || errorBody.error || errorMessage;
+    } catch {
+      errorMessage = response.statusText || errorMessage;
+    }
+    throw new Error(errorMessage);
+  }
+
+  return response.json();
+}
+
 // Guild Category Commands
 
 /**
@@ -2027,18 +2623,38 @@ export async function reorderGuildChannels(
 // Friends Commands
 
 export async function getFriends(): Promise<Friend[]> {
+  if (isTauri) {
+    const { invoke } = await import("@tauri-apps/api/core");
+    return invoke("fetch_friends");
+  }
+
   return httpRequest<Friend[]>("GET", "/api/friends");
 }
 
 export async function getPendingFriends(): Promise<Friend[]> {
+  if (isTauri) {
+    const { invoke } = await import("@tauri-apps/api/core");
+    return invoke("fetch_pending_friends");
+  }
+
   return httpRequest<Friend[]>("GET", "/api/friends/pending");
 }
 
 export async function getBlockedFriends(): Promise<Friend[]> {
+  if (isTauri) {
+    const { invoke } = await import("@tauri-apps/api/core");
+    return invoke("fetch_blocked_users");
+  }
+
   return httpRequest<Friend[]>("GET", "/api/friends/blocked");
 }
 
 export async function sendFriendRequest(username: string): Promise<Friendship> {
+  if (isTauri) {
+    const { invoke } = await import("@tauri-apps/api/core");
+    return invoke("send_friend_request", { username });
+  }
+
   return httpRequest<Friendship>("POST", "/api/friends/request", { username });
 }
 
@@ -2095,22 +2711,47 @@ export async function reorderPins(pinIds: string[]): Promise<void> {
 export async function acceptFriendRequest(
   friendshipId: string,
 ): Promise<Friendship> {
+  if (isTauri) {
+    const { invoke } = await import("@tauri-apps/api/core");
+    return invoke("accept_friend_request", { friendship_id: friendshipId });
+  }
+
   return httpRequest<Friendship>("POST", `/api/friends/${friendshipId}/accept`);
 }
 
 export async function rejectFriendRequest(friendshipId: string): Promise<void> {
+  if (isTauri) {
+    const { invoke } = await import("@tauri-apps/api/core");
+    return invoke("decline_friend_request", { friendship_id: friendshipId });
+  }
+
   await httpRequest<void>("POST", `/api/friends/${friendshipId}/reject`);
 }
 
 export async function removeFriend(friendshipId: string): Promise<void> {
+  if (isTauri) {
+    const { invoke } = await import("@tauri-apps/api/core");
+    return invoke("remove_friend", { friendship_id: friendshipId });
+  }
+
   await httpRequest<void>("DELETE", `/api/friends/${friendshipId}`);
 }
 
 export async function blockUser(userId: string): Promise<Friendship> {
+  if (isTauri) {
+    const { invoke } = await import("@tauri-apps/api/core");
+    return invoke("block_user", { user_id: userId });
+  }
+
   return httpRequest<Friendship>("POST", `/api/friends/${userId}/block`);
 }
 
 export async function unblockUser(userId: string): Promise<void> {
+  if (isTauri) {
+    const { invoke } = await import("@tauri-apps/api/core");
+    return invoke("unblock_user", { user_id: userId });
+  }
+
   await httpRequest<void>("DELETE", `/api/friends/${userId}/block`);
 }
 
@@ -2159,6 +2800,8 @@ export interface AdminReportResponse {
   description: string | null;
   status: string;
   assigned_admin_id: string | null;
+  claimed_at: string | null;
+  resolved_by: string | null;
   resolution_action: string | null;
   resolution_note: string | null;
   resolved_at: string | null;
@@ -2215,6 +2858,16 @@ export async function adminClaimReport(
   );
 }
 
+export async function adminUnclaimReport(
+  reportId: string,
+  force = false,
+): Promise<AdminReportResponse> {
+  return httpRequest<AdminReportResponse>(
+    "DELETE",
+    `/api/admin/reports/${reportId}/claim${force ? "?force=true" : ""}`,
+  );
+}
+
 export async function adminResolveReport(
   reportId: string,
   resolution_action: string,
@@ -2234,6 +2887,185 @@ export async function adminGetReportStats(): Promise<ReportStatsResponse> {
   return httpRequest<ReportStatsResponse>("GET", "/api/admin/reports/stats");
 }
 
+export interface ReportNote {
+  id: string;
+  report_id: string;
+  author_id: string | null;
+  author_username: string | null;
+  body: string;
+  created_at: string;
+}
+
+export interface ReportEvidence {
+  id: string;
+  report_id: string;
+  message_id: string | null;
+  channel_id: string;
+  author_id: string | null;
+  author_username: string | null;
+  content: string;
+  encrypted: boolean;
+  attachments: {
+    id: string;
+    filename: string;
+    mime_type: string;
+    size_bytes: number;
+  }[];
+  message_created_at: string;
+  edited_at: string | null;
+  added_by: string | null;
+  captured_at: string;
+}
+
+export async function adminListReportNotes(
+  reportId: string,
+): Promise<ReportNote[]> {
+  return httpRequest<ReportNote[]>(
+    "GET",
+    `/api/admin/reports/${reportId}/notes`,
+  );
+}
+
+export async function adminAddReportNote(
+  reportId: string,
+  body: string,
+): Promise<ReportNote> {
+  return httpRequest<ReportNote>(
+    "POST",
+    `/api/admin/reports/${reportId}/notes`,
+    { body },
+  );
+}
+
+export async function adminListReportEvidence(
+  reportId: string,
+): Promise<ReportEvidence[]> {
+  return httpRequest<ReportEvidence[]>(
+    "GET",
+    `/api/admin/reports/${reportId}/evidence`,
+  );
+}
+
+export async function adminAddReportEvidence(
+  reportId: string,
+  messageId: string,
+): Promise<ReportEvidence> {
+  return httpRequest<ReportEvidence>(
+    "POST",
+    `/api/admin/reports/${reportId}/evidence`,
+    { message_id: messageId },
+  );
+}
+
+/**
+ * List guilds waiting for discovery review, oldest submission first (admin only).
+ */
+export async function adminListDiscoveryQueue(
+  limit: number,
+  offset: number,
+): Promise<PaginatedResponse<DiscoveryQueueEntry>> {
+  return httpRequest<PaginatedResponse<DiscoveryQueueEntry>>(
+    "GET",
+    `/api/admin/discovery/queue?limit=${limit}&offset=${offset}`,
+  );
+}
+
+/**
+ * Approve a queued guild for discovery (requires elevation).
+ */
+export async function adminApproveDiscovery(
+  guildId: string,
+): Promise<{ guild_id: string; status: string }> {
+  return httpRequest<{ guild_id: string; status: string }>(
+    "POST",
+    `/api/admin/discovery/queue/${guildId}/approve`,
+  );
+}
+
+/**
+ * Reject a queued guild; the reason is sent to its owner (requires elevation).
+ */
+export async function adminRejectDiscovery(
+  guildId: string,
+  reason: string,
+): Promise<{ guild_id: string; status: string }> {
+  return httpRequest<{ guild_id: string; status: string }>(
+    "POST",
+    `/api/admin/discovery/queue/${guildId}/reject`,
+    { reason },
+  );
+}
+
+/**
+ * List accounts waiting for registration approval, oldest first (admin only).
+ */
+export async function adminListPendingRegistrations(
+  limit: number,
+  offset: number,
+): Promise<PaginatedResponse<PendingRegistration>> {
+  return httpRequest<PaginatedResponse<PendingRegistration>>(
+    "GET",
+    `/api/admin/registrations?limit=${limit}&offset=${offset}`,
+  );
+}
+
+/**
+ * Approve a pending account so it can sign in (requires elevation).
+ */
+export async function adminApproveRegistration(
+  userId: string,
+): Promise<{ user_id: string; status: string }> {
+  return httpRequest<{ user_id: string; status: string }>(
+    "POST",
+    `/api/admin/registrations/${userId}/approve`,
+  );
+}
+
+/**
+ * Reject a pending account, deleting it (requires elevation).
+ */
+export async function adminRejectRegistration(
+  userId: string,
+): Promise<{ user_id: string; status: string }> {
+  return httpRequest<{ user_id: string; status: string }>(
+    "POST",
+    `/api/admin/registrations/${userId}/reject`,
+  );
+}
+
+/**
+ * List registration codes, newest first (admin only).
+ */
+export async function adminListRegistrationCodes(
+  limit: number,
+  offset: number,
+): Promise<PaginatedResponse<RegistrationCode>> {
+  return httpRequest<PaginatedResponse<RegistrationCode>>(
+    "GET",
+    `/api/admin/registration-codes?limit=${limit}&offset=${offset}`,
+  );
+}
+
+/**
+ * Mint a registration code for invite-only registration (requires elevation).
+ */
+export async function adminCreateRegistrationCode(
+  request: CreateRegistrationCodeRequest,
+): Promise<RegistrationCode> {
+  return httpRequest<RegistrationCode>(
+    "POST",
+    "/api/admin/registration-codes",
+    request,
+  );
+}
+
+/**
+ * Delete a registration code (requires elevation).
+ */
+export async function adminDeleteRegistrationCode(id: string): Promise<void> {
+  await httpRequest<void>("DELETE", `/api/admin/registration-codes/${id}`);
+}
+
 // DM Commands
 
 export interface DMIconResponse {
@@ -2467,6 +3299,28 @@ export async function leaveDMCall(
   );
 }
 
+// Voice Region Commands
+
+export interface VoiceMediaNode {
+  id: string;
+  /** Human-readable region label, e.g. "eu-central". */
+  region: string;
+  /** URL to request when measuring round-trip time to this node. */
+  probe_url: string;
+}
+
+export interface VoiceRegionsResponse {
+  /** Empty on single-node deployments. */
+  nodes: VoiceMediaNode[];
+}
+
+/**
+ * List the SFU media nodes the client can probe for latency-based placement.
+ */
+export async function getVoiceRegions(): Promise<VoiceRegionsResponse> {
+  return httpRequest<VoiceRegionsResponse>("GET", "/api/voice/regions");
+}
+
 // Voice Commands (browser mode stubs - voice requires Tauri)
 
 export async function joinVoice(channelId: string): Promise<void> {
@@ -2643,50 +3497,124 @@ export async function wsStatus(): Promise<ConnectionStatus> {
 export async function wsSubscribe(channelId: string): Promise<void> {
   if (isTauri) {
     const { invoke } = await import("@tauri-apps/api/core");
-    return invoke("ws_subscribe", { channelId });
+    return invoke("ws_subscribe", { channelId });
+  }
+
+  browserWs?.send(JSON.stringify({ type: "subscribe", channel_id: channelId }));
+}
+
+export async function wsUnsubscribe(channelId: string): Promise<void> {
+  if (isTauri) {
+    const { invoke } = await import("@tauri-apps/api/core");
+    return invoke("ws_unsubscribe", { channelId });
+  }
+
+  browserWs?.send(
+    JSON.stringify({ type: "unsubscribe", channel_id: channelId }),
+  );
+}
+
+export async function wsTyping(channelId: string): Promise<void> {
+  if (isTauri) {
+    const { invoke } = await import("@tauri-apps/api/core");
+    return invoke("ws_typing", { channelId });
+  }
+
+  browserWs?.send(JSON.stringify({ type: "typing", channel_id: channelId }));
+}
+
+export async function wsStopTyping(channelId: string): Promise<void> {
+  if (isTauri) {
+    const { invoke } = await import("@tauri-apps/api/core");
+    return invoke("ws_stop_typing", { channelId });
+  }
+
+  browserWs?.send(
+    JSON.stringify({ type: "stop_typing", channel_id: channelId }),
+  );
+}
+
+export async function wsPing(): Promise<void> {
+  if (isTauri) {
+    const { invoke } = await import("@tauri-apps/api/core");
+    return invoke("ws_ping");
   }
 
-  browserWs?.send(JSON.stringify({ type: "subscribe", channel_id: channelId }));
+  browserWs?.send(JSON.stringify({ type: "ping" }));
 }
 
-export async function wsUnsubscribe(channelId: string): Promise<void> {
+/**
+ * Request a server clock reading (answered with a `time_sync` event).
+ */
+export async function wsTimeSync(): Promise<void> {
+  const clientTime = Date.now();
   if (isTauri) {
     const { invoke } = await import("@tauri-apps/api/core");
-    return invoke("ws_unsubscribe", { channelId });
+    return invoke("ws_time_sync", { clientTime });
   }
 
   browserWs?.send(
-    JSON.stringify({ type: "unsubscribe", channel_id: channelId }),
+    JSON.stringify({ type: "time_sync", client_time: clientTime }),
   );
 }
 
-export async function wsTyping(channelId: string): Promise<void> {
+/** Filters and paging for a member list chunk request. */
+export interface GuildMemberChunkOptions {
+  roleId?: string;
+  onlineOnly?: boolean;
+  after?: string;
+  limit?: number;
+  nonce?: string;
+}
+
+/**
+ * Request a chunk of a guild's member list (answered with a
+ * `guild_member_chunk` event).
+ */
+export async function wsRequestGuildMembers(
+  guildId: string,
+  options: GuildMemberChunkOptions = {},
+): Promise<void> {
   if (isTauri) {
     const { invoke } = await import("@tauri-apps/api/core");
-    return invoke("ws_typing", { channelId });
+    return invoke("ws_request_guild_members", { guildId, ...options });
   }
 
-  browserWs?.send(JSON.stringify({ type: "typing", channel_id: channelId }));
+  browserWs?.send(
+    JSON.stringify({
+      type: "request_guild_members",
+      guild_id: guildId,
+      role_id: options.roleId,
+      online_only: options.onlineOnly ?? false,
+      after: options.after,
+      limit: options.limit,
+      nonce: options.nonce,
+    }),
+  );
 }
 
-export async function wsStopTyping(channelId: string): Promise<void> {
+/**
+ * Tell the server the user is active, so it keeps them out of idle.
+ */
+export async function wsPresencePing(): Promise<void> {
   if (isTauri) {
     const { invoke } = await import("@tauri-apps/api/core");
-    return invoke("ws_stop_typing", { channelId });
+    return invoke("ws_presence_ping");
   }
 
-  browserWs?.send(
-    JSON.stringify({ type: "stop_typing", channel_id: channelId }),
-  );
+  browserWs?.send(JSON.stringify({ type: "presence_ping" }));
 }
 
-export async function wsPing(): Promise<void> {
+/**
+ * Get whether the server is currently reachable.
+ */
+export async function getNetworkState(): Promise<NetworkState> {
   if (isTauri) {
     const { invoke } = await import("@tauri-apps/api/core");
-    return invoke("ws_ping");
+    return invoke("get_network_state");
   }
 
-  browserWs?.send(JSON.stringify({ type: "ping" }));
+  return { online: navigator.onLine };
 }
 
 // Export browser WebSocket for event handling
@@ -3286,6 +4214,19 @@ export async function deleteGuildRole(
   );
 }
 
+/**
+ * Reorder all non-default roles of a guild (highest rank first).
+ */
+export async function reorderGuildRoles(
+  guildId: string,
+  roleIds: string[],
+): Promise<GuildRole[]> {
+  return fetchApi<GuildRole[]>(`/api/guilds/${guildId}/roles/reorder`, {
+    method: "POST",
+    body: { role_ids: roleIds },
+  });
+}
+
 /**
  * Get all member role assignments for a guild.
  * Returns a map of user_id -> list of role_ids.
@@ -3579,19 +4520,23 @@ export async function adminGetAuditLog(
 }
 
 /**
- * Elevate admin session.
+ * Elevate admin session by re-confirming the password or a TOTP code.
  */
 export async function adminElevate(
+  credentials: ElevateCredentials,
   reason?: string,
 ): Promise<ElevateResponse> {
   if (isTauri) {
     const { invoke } = await import("@tauri-apps/api/core");
     return invoke<ElevateResponse>("admin_elevate", {
+      password: credentials.password,
+      mfaCode: credentials.mfa_code,
       reason,
     });
   }
 
   return httpRequest<ElevateResponse>("POST", "/api/admin/elevate", {
+    ...credentials,
     reason,
   });
 }
@@ -3649,6 +4594,64 @@ export async function adminUnbanUser(
   );
 }
 
+/**
+ * Suspend a user account (requires elevation). Signs the user out everywhere.
+ */
+export async function adminSuspendUser(
+  userId: string,
+  reason: string,
+): Promise<UserSuspensionResponse> {
+  return httpRequest<UserSuspensionResponse>(
+    "POST",
+    `/api/admin/users/${userId}/suspend`,
+    { reason },
+  );
+}
+
+/**
+ * Lift a user's suspension (requires elevation).
+ */
+export async function adminUnsuspendUser(
+  userId: string,
+): Promise<UserSuspensionResponse> {
+  return httpRequest<UserSuspensionResponse>(
+    "DELETE",
+    `/api/admin/users/${userId}/suspend`,
+  );
+}
+
+/**
+ * Revoke all of a user's sessions (requires elevation).
+ */
+export async function adminRevokeUserSessions(
+  userId: string,
+): Promise<{ user_id: string; sessions_revoked: number }> {
+  return httpRequest<{ user_id: string; sessions_revoked: number }>(
+    "POST",
+    `/api/admin/users/${userId}/revoke-sessions`,
+  );
+}
+
+/**
+ * Email a user a password reset code (requires elevation).
+ */
+export async function adminSendPasswordReset(userId: string): Promise<void> {
+  await httpRequest<void>("POST", `/api/admin/users/${userId}/password-reset`);
+}
+
+/**
+ * Grant or revoke system admin (requires elevation).
+ */
+export async function adminSetSystemAdmin(
+  userId: string,
+  isAdmin: boolean,
+): Promise<{ user_id: string; is_admin: boolean }> {
+  return httpRequest<{ user_id: string; is_admin: boolean }>(
+    isAdmin ? "POST" : "DELETE",
+    `/api/admin/users/${userId}/system-admin`,
+  );
+}
+
 /**
  * Suspend a guild (requires elevation).
  */
@@ -4248,7 +5251,7 @@ export async function oidcAuthorize(
 
   if (isTauri) {
     const { invoke } = await import("@tauri-apps/api/core");
-    const tokens = await invoke<OidcLoginResult>("oidc_authorize", {
+    const tokens = await invoke<OidcLoginResult>("login_with_oidc", {
       serverUrl: baseUrl,
       providerSlug,
     });
@@ -4272,6 +5275,13 @@ export async function oidcCompleteLogin(
 ): Promise<void> {
   const baseUrl = serverUrl.replace(/\/+$/, "");
 
+  if (isTauri) {
+    // login_with_oidc already started the session in the backend, which
+    // keeps the tokens and refreshes them
+    localStorage.setItem("serverUrl", baseUrl);
+    return;
+  }
+
   // Store tokens; browser relies on HttpOnly cookie for refresh
   browserState.serverUrl = baseUrl;
   browserState.accessToken = accessToken;
@@ -4289,6 +5299,262 @@ export async function oidcCompleteLogin(
   scheduleTokenRefresh();
 }
 
+// ============================================================================
+// Device Linking (QR login)
+// ============================================================================
+
+/** A link request started by a client that wants to sign in. */
+export interface DeviceLinkStarted {
+  code: string;
+  secret: string;
+  expires_in: number;
+}
+
+/** A pending link request, as shown to the device about to approve it. */
+export interface DeviceLinkDetails {
+  code: string;
+  user_agent: string | null;
+  ip_address: string;
+  created_at: string;
+  accepts_key_transfer: boolean;
+}
+
+interface DeviceLinkSession extends AuthResponse {
+  key_transfer: string | null;
+}
+
+/**
+ * Start signing in through a device that is already logged in.
+ * The returned code is shown as a QR code; the secret never leaves this client.
+ */
+export async function startDeviceLink(
+  serverUrl: string,
+): Promise<DeviceLinkStarted> {
+  if (isTauri) {
+    const { invoke } = await import("@tauri-apps/api/core");
+    return invoke<DeviceLinkStarted>("start_device_link", { serverUrl });
+  }
+
+  browserState.serverUrl = serverUrl;
+  return httpRequest<DeviceLinkStarted>("POST", "/auth/device-link", {});
+}
+
+/**
+ * Claim the session of a link request once another device approved it.
+ * Returns null while the request is still waiting for approval.
+ */
+export async function claimDeviceLink(
+  serverUrl: string,
+  code: string,
+  secret: string,
+): Promise<AuthResult | null> {
+  if (isTauri) {
+    const { invoke } = await import("@tauri-apps/api/core");
+    const user = await invoke<User | null>("claim_device_link", {
+      serverUrl,
+      code,
+      secret,
+    });
+    return user ? { user, setup_required: false } : null;
+  }
+
+  browserState.serverUrl = serverUrl;
+  const session = await httpRequest<DeviceLinkSession | null>(
+    "POST",
+    `/auth/device-link/${encodeURIComponent(code)}/claim`,
+    { secret },
+  );
+  if (!session) return null;
+
+  // Browser relies on the HttpOnly cookie for refresh, as after a login
+  localStorage.setItem("serverUrl", serverUrl);
+  setSessionRestoreBlocked(false);
+  browserState.accessToken = session.access_token;
+  browserState.tokenExpiresAt = Date.now() + session.expires_in * 1000;
+  scheduleTokenRefresh();
+
+  const user = await httpRequest<User>("GET", "/auth/me");
+  return { user, setup_required: session.setup_required };
+}
+
+// ============================================================================
+// Passkeys
+// ============================================================================
+
+export interface PasskeyInfo {
+  id: string;
+  name: string;
+  created_at: string;
+  last_used_at: string | null;
+}
+
+/** A WebAuthn challenge; `options` is handed to the authenticator as-is. */
+export interface PasskeyChallenge {
+  challenge_id: string;
+  options: unknown;
+}
+
+/** A signed assertion: finishes a passkey sign-in or answers MFA_REQUIRED. */
+export interface PasskeyAssertion {
+  challenge_id: string;
+  credential: unknown;
+}
+
+/**
+ * Request an assertion challenge for the account's passkeys.
+ */
+export async function startPasskeyLogin(
+  serverUrl: string,
+  username: string,
+): Promise<PasskeyChallenge> {
+  if (isTauri) {
+    const { invoke } = await import("@tauri-apps/api/core");
+    return invoke<PasskeyChallenge>("passkey_login_start", {
+      serverUrl,
+      username,
+    });
+  }
+
+  browserState.serverUrl = serverUrl;
+  return httpRequest<PasskeyChallenge>("POST", "/auth/webauthn/login/start", {
+    username,
+  });
+}
+
+/**
+ * Sign in with a signed passkey assertion.
+ */
+export async function finishPasskeyLogin(
+  serverUrl: string,
+  assertion: PasskeyAssertion,
+): Promise<AuthResult> {
+  if (isTauri) {
+    const { invoke } = await import("@tauri-apps/api/core");
+    const user = await invoke<User>("passkey_login_finish", {
+      serverUrl,
+      assertion,
+    });
+    return { user, setup_required: false };
+  }
+
+  browserState.serverUrl = serverUrl;
+  const response = await httpRequest<AuthResponse>(
+    "POST",
+    "/auth/webauthn/login/finish",
+    assertion,
+  );
+
+  // Browser relies on the HttpOnly cookie for refresh, as after a login
+  localStorage.setItem("serverUrl", serverUrl);
+  setSessionRestoreBlocked(false);
+  browserState.accessToken = response.access_token;
+  browserState.tokenExpiresAt = Date.now() + response.expires_in * 1000;
+  scheduleTokenRefresh();
+
+  const user = await httpRequest<User>("GET", "/auth/me");
+  return { user, setup_required: response.setup_required };
+}
+
+/** List the current user's passkeys. */
+export async function listPasskeys(): Promise<PasskeyInfo[]> {
+  if (isTauri) {
+    const { invoke } = await import("@tauri-apps/api/core");
+    return invoke<PasskeyInfo[]>("passkey_list");
+  }
+  return httpRequest<PasskeyInfo[]>("GET", "/auth/webauthn/credentials");
+}
+
+/**
+ * Request a registration challenge. Requires the current password, an MFA
+ * code on password-less accounts with MFA enabled, or otherwise a fresh
+ * sign-in proof (`reauth_token` or `passkey`).
+ */
+export async function startPasskeyRegistration(
+  reauth: Reauthentication,
+): Promise<PasskeyChallenge> {
+  if (isTauri) {
+    const { invoke } = await import("@tauri-apps/api/core");
+    return invoke<PasskeyChallenge>("passkey_register_start", {
+      currentPassword: reauth.current_password ?? null,
+      mfaCode: reauth.mfa_code ?? null,
+      reauthToken: reauth.reauth_token ?? null,
+      passkey: reauth.passkey ?? null,
+    });
+  }
+  return httpRequest<PasskeyChallenge>(
+    "POST",
+    "/auth/webauthn/register/start",
+    reauth,
+  );
+}
+
+/** Store the authenticator's new credential under `name`. */
+export async function finishPasskeyRegistration(
+  challengeId: string,
+  name: string,
+  credential: unknown,
+): Promise<PasskeyInfo> {
+  if (isTauri) {
+    const { invoke } = await import("@tauri-apps/api/core");
+    return invoke<PasskeyInfo>("passkey_register_finish", {
+      challengeId,
+      name,
+      credential,
+    });
+  }
+  return httpRequest<PasskeyInfo>("POST", "/auth/webauthn/register/finish", {
+    challenge_id: challengeId,
+    name,
+    credential,
+  });
+}
+
+/**
+ * Remove a passkey. The server refuses to remove the last usable sign-in
+ * method.
+ */
+export async function deletePasskey(id: string): Promise<void> {
+  if (isTauri) {
+    const { invoke } = await import("@tauri-apps/api/core");
+    return invoke("passkey_delete", { id });
+  }
+  await httpRequest<void>(
+    "DELETE",
+    `/auth/webauthn/credentials/${encodeURIComponent(id)}`,
+  );
+}
+
+/**
+ * Look up a link request before approving it from this device.
+ */
+export async function getDeviceLink(code: string): Promise<DeviceLinkDetails> {
+  return httpRequest<DeviceLinkDetails>(
+    "GET",
+    `/auth/device-link/${encodeURIComponent(code)}`,
+  );
+}
+
+/**
+ * Approve a link request, signing the other device in as the current user.
+ */
+export async function approveDeviceLink(code: string): Promise<void> {
+  await httpRequest<void>(
+    "POST",
+    `/auth/device-link/${encodeURIComponent(code)}/approve`,
+    {},
+  );
+}
+
+/**
+ * Reject a link request.
+ */
+export async function rejectDeviceLink(code: string): Promise<void> {
+  await httpRequest<void>(
+    "DELETE",
+    `/auth/device-link/${encodeURIComponent(code)}`,
+  );
+}
+
 // ============================================================================
 // Admin Auth Settings & OIDC Provider Management
 // ============================================================================
@@ -4348,6 +5614,8 @@ export async function adminCreateOidcProvider(body: {
   client_id: string;
   client_secret: string;
   scopes?: string;
+  provisioning?: OidcProvisioning;
+  link_by_email?: boolean;
 }): Promise<AdminOidcProvider> {
   if (isTauri) {
     const { invoke } = await import("@tauri-apps/api/core");
@@ -4376,6 +5644,8 @@ export async function adminUpdateOidcProvider(
     client_secret?: string;
     scopes: string;
     enabled: boolean;
+    provisioning?: OidcProvisioning;
+    link_by_email?: boolean;
   },
 ): Promise<AdminOidcProvider> {
   if (isTauri) {
@@ -4480,6 +5750,58 @@ export async function adminObsTopErrors(
   );
 }
 
+export async function adminObsTopConsumers(
+  range: ObsTimeRange,
+  type?: TopConsumersType,
+  sort?: TopConsumersSort,
+  limit?: number,
+): Promise<TopConsumersResponse> {
+  if (isTauri) {
+    const { invoke } = await import("@tauri-apps/api/core");
+    return invoke<TopConsumersResponse>("admin_obs_top_consumers", {
+      range,
+      consumerType: type,
+      sort,
+      limit,
+    });
+  }
+  const params = new URLSearchParams();
+  params.set("range", range);
+  if (type) params.set("type", type);
+  if (sort) params.set("sort", sort);
+  if (limit) params.set("limit", String(limit));
+  return httpRequest<TopConsumersResponse>(
+    "GET",
+    `/api/admin/observability/top-consumers?${params.toString()}`,
+  );
+}
+
+export async function adminObsWsDisconnects(
+  range: ObsTimeRange,
+): Promise<WsDisconnectBreakdown> {
+  if (isTauri) {
+    const { invoke } = await import("@tauri-apps/api/core");
+    return invoke<WsDisconnectBreakdown>("admin_obs_ws_disconnects", { range });
+  }
+  return httpRequest<WsDisconnectBreakdown>(
+    "GET",
+    `/api/admin/observability/ws-disconnects?range=${range}`,
+  );
+}
+
+export async function adminObsReportSla(
+  range: ObsTimeRange,
+): Promise<ReportSlaMetrics> {
+  if (isTauri) {
+    const { invoke } = await import("@tauri-apps/api/core");
+    return invoke<ReportSlaMetrics>("admin_obs_report_sla", { range });
+  }
+  return httpRequest<ReportSlaMetrics>(
+    "GET",
+    `/api/admin/observability/report-sla?range=${range}`,
+  );
+}
+
 export async function adminObsLogs(
   level?: string,
   domain?: string,
@@ -89,11 +89,19 @@ export interface UserProfile {
   username: string;
   display_name: string;
   avatar_url: string | null;
+  banner_url?: string | null;
+  /** Markdown; raw HTML is stripped by the server. */
+  about_me?: string | null;
+  pronouns?: string | null;
+  /** `#rrggbb` */
+  accent_color?: string | null;
   status: UserStatus;
 }
 
 export interface User extends UserProfile {
   email: string | null;
+  /** Whether `email` has been confirmed with a verification code. */
+  email_verified?: boolean;
   mfa_enabled: boolean;
   created_at: string;
   status_message?: string | null;
@@ -113,6 +121,10 @@ export interface Guild {
   banner_url: string | null;
   plan: string;
   created_at: string;
+  /** Text channel for join announcements; members land here by default. */
+  system_channel_id: string | null;
+  /** Text channel shown first to members who just joined. */
+  rules_channel_id: string | null;
 }
 
 export interface UsageStat {
@@ -131,11 +143,28 @@ export interface GuildUsageStats {
   pages: UsageStat;
 }
 
+export interface GuildPerks {
+  guild_id: string;
+  tier: number;
+  supporter_count: number;
+  next_tier_at: number | null;
+  emoji_slots: number;
+  max_upload_size: number;
+  voice_bitrate: number;
+}
+
 export interface GuildSettings {
   threads_enabled: boolean;
   discoverable: boolean;
   tags: string[];
   banner_url: string | null;
+  voice_log_channel_id: string | null;
+  /** Preferred voice region for new voice rooms (null = no preference). */
+  voice_region: string | null;
+  /** Only "approved" guilds are listed in discovery. */
+  discovery_status: "none" | "pending" | "approved" | "rejected" | "delisted";
+  /** Why the listing was rejected or delisted. */
+  discovery_rejection_reason: string | null;
 }
 
 export interface DiscoverableGuild {
@@ -156,10 +185,17 @@ export interface DiscoverResponse {
   offset: number;
 }
 
+/** Guilds recommended from the tags of the user's current guilds. */
+export interface RecommendedGuildsResponse {
+  guilds: DiscoverableGuild[];
+}
+
 export interface JoinDiscoverableResponse {
   guild_id: string;
   guild_name: string;
   already_member: boolean;
+  /** The guild's onboarding, when enabled. */
+  onboarding?: GuildOnboarding;
 }
 
 export interface GuildMember {
@@ -168,9 +204,17 @@ export interface GuildMember {
   display_name: string;
   avatar_url: string | null;
   nickname: string | null;
+  /** Guild-specific avatar, shown instead of avatar_url in this guild. */
+  guild_avatar_url?: string | null;
   joined_at: string;
   status: "online" | "idle" | "offline";
   last_seen_at: string | null;
+  /** Active timeout end (null = not timed out) */
+  timeout_until?: string | null;
+  /** Assigned roles (excluding @everyone), for grouping the member list. */
+  role_ids?: string[];
+  /** Joined behind the rules gate and has not completed onboarding yet. */
+  pending?: boolean;
 }
 
 export interface GuildInvite {
@@ -180,6 +224,8 @@ export interface GuildInvite {
   created_by: string;
   expires_at: string | null;
   use_count: number;
+  /** Maximum number of uses (null = unlimited) */
+  max_uses: number | null;
   created_at: string;
 }
 
@@ -190,14 +236,48 @@ export interface InviteResponse {
   guild_name: string;
   expires_at: string | null;
   use_count: number;
+  max_uses: number | null;
   created_at: string;
+  /** The guild's onboarding, when enabled. */
+  onboarding?: GuildOnboarding;
+}
+
+/** A channel highlighted on the welcome screen. */
+export interface WelcomeChannel {
+  channel_id: string;
+  description: string;
+}
+
+/** Welcome screen, rules gate and roles for new members of a guild. */
+export interface GuildOnboarding {
+  guild_id: string;
+  enabled: boolean;
+  welcome_description: string | null;
+  welcome_channels: WelcomeChannel[];
+  /** Guild page new members must accept before they can post. */
+  rules_page_id: string | null;
+  /** Roles assigned when a member completes onboarding. */
+  default_role_ids: string[];
+}
+
+/** Public invite preview (available without logging in) */
+export interface InvitePreview {
+  code: string;
+  guild_id: string;
+  guild_name: string;
+  guild_icon_url: string | null;
+  guild_description: string | null;
+  member_count: number;
+  expires_at: string | null;
+  /** Remaining uses (null = unlimited) */
+  uses_remaining: number | null;
 }
 
 export type InviteExpiry = "30m" | "1h" | "1d" | "7d" | "never";
 
 // Channel Types
 
-export type ChannelType = "text" | "voice" | "dm";
+export type ChannelType = "text" | "voice" | "stage" | "dm";
 
 export interface Channel {
   id: string;
@@ -209,6 +289,8 @@ export interface Channel {
   icon_url: string | null;
   user_limit: number | null;
   position: number;
+  /** Days messages are kept before being purged (null = forever). */
+  retention_days?: number | null;
   created_at: string;
 }
 
@@ -246,6 +328,8 @@ export interface Attachment {
   blurhash?: string;
   thumbnail_url?: string;
   medium_url?: string;
+  /** Set while the malware scanner holds the file back from the channel. */
+  scan_status?: "pending" | "quarantined" | "failed";
 }
 
 export interface Reaction {
@@ -265,10 +349,21 @@ export interface GuildEmoji {
   created_at: string;
 }
 
+/** Message kind. System messages are generated by the server for lifecycle events. */
+export type MessageType =
+  | "default"
+  | "system_join"
+  | "system_pin"
+  | "system_call"
+  | "system_boost"
+  | "system_voice"
+  | "system_automod";
+
 export interface Message {
   id: string;
   channel_id: string;
   author: UserProfile;
+  message_type?: MessageType;
   content: string;
   encrypted: boolean;
   attachments: Attachment[];
@@ -281,6 +376,46 @@ export interface Message {
   mention_type: "direct" | "everyone" | "here" | null;
   reactions?: Reaction[];
   thread_info?: ThreadInfo;
+  embeds?: LinkEmbed[];
+  /** Catalog reference of a system message (server-side Fluent message id and arguments). */
+  system_text?: LocalizedText;
+  /** Locale `content` is rendered in (system messages only). */
+  content_locale?: string;
+  /** Origin of a forwarded message. */
+  forwarded_from?: ForwardedFrom;
+  /** Guild custom emojis referenced as `:name:` in `content`. */
+  emojis?: MessageEmoji[];
+}
+
+/** Custom emoji resolved from a `:name:` token in a message. */
+export interface MessageEmoji {
+  id: string;
+  name: string;
+  url: string;
+  animated: boolean;
+}
+
+/** Where a forwarded message was originally posted. Ids are null once deleted. */
+export interface ForwardedFrom {
+  message_id: string | null;
+  channel_id: string | null;
+  /** Null for webhook posts and deleted accounts. */
+  author: UserProfile | null;
+  created_at: string;
+}
+
+/** Reference to a server catalog message; lets clients render it with their own translations. */
+export interface LocalizedText {
+  id: string;
+  args?: Record<string, string | number>;
+}
+
+export interface LinkEmbed {
+  url: string;
+  title?: string;
+  description?: string;
+  site_name?: string;
+  image_url?: string;
 }
 
 export interface ThreadInfo {
@@ -299,6 +434,22 @@ export interface PaginatedMessages {
   next_cursor: string | null;
 }
 
+export type AttachmentKind = "image" | "video" | "audio" | "file";
+
+/** Channel gallery entry (attachment plus the message it came from). */
+export interface ChannelAttachment extends Attachment {
+  kind: AttachmentKind;
+  message_id: string;
+  uploader: UserProfile | null;
+  created_at: string;
+}
+
+export interface PaginatedChannelAttachments {
+  items: ChannelAttachment[];
+  has_more: boolean;
+  next_cursor: string | null;
+}
+
 // Search Types
 
 export interface SearchAuthor {
@@ -335,6 +486,26 @@ export interface SearchFilters {
   sort?: "relevance" | "date";
 }
 
+// Mention Autocomplete Types
+
+export type AutocompleteType = "user" | "channel" | "role";
+
+export type AutocompleteItem =
+  | {
+      type: "user";
+      id: string;
+      username: string;
+      display_name: string;
+      nickname: string | null;
+      avatar_url: string | null;
+    }
+  | { type: "channel"; id: string; name: string; channel_type: ChannelType }
+  | { type: "role"; id: string; name: string; color: string | null };
+
+export interface AutocompleteResponse {
+  results: AutocompleteItem[];
+}
+
 // Global Search Types
 
 export interface GlobalSearchSource {
@@ -356,6 +527,8 @@ export interface GlobalSearchResponse {
 
 // Voice Types
 
+export type VoiceActivityMode = "vad" | "ptt";
+
 export interface VoiceParticipant {
   user_id: string;
   username?: string;
@@ -364,6 +537,11 @@ export interface VoiceParticipant {
   speaking: boolean;
   screen_sharing: boolean;
   webcam_active?: boolean;
+  activity_mode?: VoiceActivityMode;
+  /** Joined receive-only (no VOICE_SPEAK) until a moderator promotes them. */
+  listener?: boolean;
+  /** Listener asking a moderator to let them speak. */
+  hand_raised?: boolean;
 }
 
 export interface WebcamServerInfo {
@@ -408,37 +586,121 @@ export interface TokenResponse {
 
 export type ClientEvent =
   | { type: "ping" }
+  | { type: "time_sync"; client_time: number }
   | { type: "subscribe"; channel_id: string }
   | { type: "unsubscribe"; channel_id: string }
   | { type: "typing"; channel_id: string }
   | { type: "stop_typing"; channel_id: string }
-  | { type: "voice_join"; channel_id: string }
+  | {
+      type: "voice_join";
+      channel_id: string;
+      /** Measured RTT in ms to each media node, keyed by node ID. */
+      node_rtts?: Record<string, number>;
+      /** Whether captured audio is noise-suppressed (for diagnostics). */
+      noise_suppression?: boolean;
+    }
   | { type: "voice_leave"; channel_id: string }
   | { type: "voice_answer"; channel_id: string; sdp: string }
   | { type: "voice_ice_candidate"; channel_id: string; candidate: string }
   | { type: "voice_mute"; channel_id: string }
   | { type: "voice_unmute"; channel_id: string }
+  | {
+      type: "voice_stats";
+      channel_id: string;
+      session_id: string;
+      latency: number;
+      packet_loss: number;
+      jitter: number;
+      quality: number;
+      timestamp: number;
+      concealment_events?: number;
+      jitter_buffer_delay?: number;
+      audio_level?: number;
+    }
+  | { type: "voice_set_activity_mode"; channel_id: string; mode: VoiceActivityMode }
+  | { type: "voice_promote_listener"; channel_id: string; user_id: string }
+  | { type: "voice_raise_hand"; channel_id: string }
+  | { type: "voice_lower_hand"; channel_id: string }
+  | { type: "voice_set_user_gain"; target_user: string; gain: number }
   // Webcam events
   | { type: "voice_webcam_start"; channel_id: string; quality: string }
   | { type: "voice_webcam_stop"; channel_id: string }
   // Admin events
   | { type: "admin_subscribe" }
-  | { type: "admin_unsubscribe" };
+  | { type: "admin_unsubscribe" }
+  // Session resume (first message on a reconnect)
+  | { type: "resume"; session_id: string; last_seq: number };
+
+/** New placement of a channel after a bulk reorder. */
+export interface ChannelPlacement {
+  id: string;
+  position: number;
+  category_id: string | null;
+}
+
+/** New placement of a category after a bulk reorder. */
+export interface CategoryPlacement {
+  id: string;
+  position: number;
+  parent_id: string | null;
+}
 
 export type ServerEvent =
-  | { type: "ready"; user_id: string }
+  | {
+      type: "ready";
+      user_id: string;
+      session_id?: string;
+      profile?: UserProfile;
+    }
+  | {
+      type: "resumed";
+      session_id: string;
+      last_seq: number;
+      replayed: number;
+    }
+  | { type: "resume_failed"; reason: string }
+  | { type: "session_revoked"; reason: "suspended" | "revoked" }
+  | {
+      type: "new_login_network";
+      ip: string;
+      user_agent: string | null;
+      method: "password" | "passkey" | "oidc" | "device_link";
+    }
   | { type: "pong" }
+  | { type: "time_sync"; server_time: number; client_time?: number }
   | { type: "subscribed"; channel_id: string }
   | { type: "unsubscribed"; channel_id: string }
+  | { type: "subscription_evicted"; channel_id: string; limit: number }
   | { type: "message_new"; channel_id: string; message: Message }
   | {
       type: "message_edit";
       channel_id: string;
       message_id: string;
       content: string;
+      emojis?: MessageEmoji[];
       edited_at: string;
     }
   | { type: "message_delete"; channel_id: string; message_id: string }
+  | {
+      type: "message_pinned";
+      channel_id: string;
+      message_id: string;
+      pinned_by: string;
+      pinned_at: string;
+    }
+  | { type: "message_unpinned"; channel_id: string; message_id: string }
+  | {
+      type: "attachment_processed";
+      channel_id: string;
+      message_id: string;
+      attachment: Attachment;
+    }
+  | {
+      type: "message_embeds_update";
+      channel_id: string;
+      message_id: string;
+      embeds: LinkEmbed[];
+    }
   | { type: "typing_start"; channel_id: string; user_id: string }
   | { type: "typing_stop"; channel_id: string; user_id: string }
   | { type: "presence_update"; user_id: string; status: UserStatus }
@@ -451,16 +713,44 @@ export type ServerEvent =
       user_id: string;
       username: string;
       display_name: string;
+      /** Phonetic spelling of the display name, for screen readers */
+      name_pronunciation?: string;
+      listener?: boolean;
+    }
+  | {
+      type: "voice_user_left";
+      channel_id: string;
+      user_id: string;
+      display_name?: string;
+      name_pronunciation?: string;
     }
-  | { type: "voice_user_left"; channel_id: string; user_id: string }
   | { type: "voice_user_muted"; channel_id: string; user_id: string }
   | { type: "voice_user_unmuted"; channel_id: string; user_id: string }
+  | { type: "voice_speaking"; channel_id: string; user_id: string; speaking: boolean }
+  | {
+      type: "voice_listener_promoted";
+      channel_id: string;
+      user_id: string;
+      promoted_by: string;
+    }
+  | { type: "voice_hand_raised"; channel_id: string; user_id: string }
+  | { type: "voice_hand_lowered"; channel_id: string; user_id: string }
+  | {
+      type: "voice_activity_mode_changed";
+      channel_id: string;
+      user_id: string;
+      mode: VoiceActivityMode;
+    }
   | {
       type: "voice_room_state";
       channel_id: string;
       participants: VoiceParticipant[];
       screen_shares?: ScreenShareServerInfo[];
       webcams?: WebcamServerInfo[];
+      /** Media node the channel is placed on (multi-node deployments only). */
+      media_node?: string;
+      /** Public IP of the node hosting the channel (multi-node deployments only). */
+      media_address?: string;
     }
   | { type: "voice_error"; code: string; message: string }
   // Screen share events
@@ -532,7 +822,17 @@ export type ServerEvent =
       packet_loss: number;
       jitter: number;
       quality: number;
+      concealment_events?: number;
+      jitter_buffer_delay?: number;
+      audio_level?: number;
     }
+  | {
+      type: "voice_quality_warning";
+      channel_id: string;
+      packet_loss: number;
+      audio_bitrate: number;
+    }
+  | { type: "voice_quality_recovered"; channel_id: string }
   // Admin events
   | { type: "admin_user_banned"; user_id: string; username: string }
   | { type: "admin_user_unbanned"; user_id: string; username: string }
@@ -542,6 +842,7 @@ export type ServerEvent =
   | { type: "admin_guild_deleted"; guild_id: string; guild_name: string }
   // DM read sync event
   | { type: "dm_read"; channel_id: string }
+  | { type: "dm_state_update"; channel_id: string; state: DMConversationState }
   // Guild channel read sync event
   | { type: "channel_read"; channel_id: string; last_read_message_id?: string }
   // Preferences events
@@ -550,6 +851,46 @@ export type ServerEvent =
       preferences: Partial<UserPreferences>;
       updated_at: string;
     }
+  // Draft sync events
+  | {
+      type: "draft_update";
+      channel_id: string;
+      content: string;
+      updated_at: string;
+    }
+  // Data export events
+  | {
+      type: "data_export_ready";
+      job_id: string;
+      download_url: string;
+      download_url_expires_at: string;
+      file_size_bytes: number;
+    }
+  | {
+      type: "data_export_failed";
+      job_id: string;
+      error_message: string;
+    }
+  // Discovery review (sent to the guild owner)
+  | {
+      type: "guild_discovery_reviewed";
+      guild_id: string;
+      guild_name: string;
+      status: "approved" | "rejected" | "delisted";
+      reason: string | null;
+    }
+  // Guild suspension (sent to the guild owner)
+  | {
+      type: "guild_suspended";
+      guild_id: string;
+      guild_name: string;
+      reason: string;
+    }
+  | {
+      type: "guild_unsuspended";
+      guild_id: string;
+      guild_name: string;
+    }
   // Reaction events
   | {
       type: "reaction_add";
@@ -565,8 +906,40 @@ export type ServerEvent =
       user_id: string;
       emoji: string;
     }
+  // Guild profile events
+  | { type: "guild_update"; guild_id: string; guild: Guild }
+  | {
+      type: "guild_member_chunk";
+      guild_id: string;
+      members: GuildMember[];
+      next_cursor?: string;
+      nonce?: string;
+    }
   // Guild emoji events
   | { type: "guild_emoji_updated"; guild_id: string; emojis: GuildEmoji[] }
+  | { type: "guild_perks_update"; guild_id: string; perks: GuildPerks }
+  | { type: "role_create"; guild_id: string; role: GuildRole }
+  | { type: "role_update"; guild_id: string; role: GuildRole }
+  | { type: "role_delete"; guild_id: string; role_id: string }
+  | { type: "roles_reorder"; guild_id: string; roles: GuildRole[] }
+  | {
+      type: "channel_positions_update";
+      guild_id: string;
+      channels: ChannelPlacement[];
+      categories: CategoryPlacement[];
+    }
+  | {
+      type: "member_roles_update";
+      guild_id: string;
+      user_id: string;
+      role_ids: string[];
+    }
+  | {
+      type: "member_timeout_update";
+      guild_id: string;
+      user_id: string;
+      timeout_until: string | null;
+    }
   // Friend events
   | {
       type: "friend_request_received";
@@ -594,6 +967,11 @@ export type ServerEvent =
   // Block events
   | { type: "user_blocked"; user_id: string }
   | { type: "user_unblocked"; user_id: string }
+  | {
+      type: "relationship_update";
+      user_id: string;
+      relationship: Relationship | null;
+    }
   // Admin report events
   | {
       type: "admin_report_created";
@@ -622,6 +1000,12 @@ export type ServerEvent =
       thread_parent_id: string;
       last_read_message_id: string | null;
     }
+  | {
+      type: "message_highlight";
+      channel_id: string;
+      message_id: string;
+      keyword: string;
+    }
   // State sync events
   | {
       type: "patch";
@@ -728,6 +1112,38 @@ export interface FocusState {
   triggering_category: FocusTriggerCategory | null;
 }
 
+// Desktop Notification Types
+
+/** Recurring do-not-disturb window in local time. */
+export interface DndWindow {
+  start_time: string; // "HH:MM" format
+  end_time: string; // earlier than start_time for overnight windows
+  days: number[]; // days the window starts on (0 = Sunday); empty = every day
+}
+
+/** Native OS notification settings (desktop app only). */
+export interface DesktopNotificationPreferences {
+  enabled: boolean;
+  mentions: boolean;
+  direct_messages: boolean;
+  incoming_calls: boolean;
+  muted_guilds: string[];
+  dnd_windows: DndWindow[];
+  /** Speak voice joins and leaves in the current call (desktop only) */
+  announce_voice_presence: boolean;
+}
+
+export const DEFAULT_DESKTOP_NOTIFICATION_PREFERENCES: DesktopNotificationPreferences =
+  {
+    enabled: true,
+    mentions: true,
+    direct_messages: true,
+    incoming_calls: true,
+    muted_guilds: [],
+    dnd_windows: [],
+    announce_voice_presence: false,
+  };
+
 // User Preferences (synced across devices)
 export interface UserPreferences {
   // Theme
@@ -754,6 +1170,15 @@ export interface UserPreferences {
   // Per-channel notification levels
   channel_notifications: Record<string, "all" | "mentions" | "muted">;
 
+  // Words that notify like an @mention in guild channels
+  highlight_keywords: string[];
+
+  // Per-user voice volume, by user ID (0-2, missing = 1)
+  voice_user_gains: Record<string, number>;
+
+  // Native desktop notifications (mute rules and do-not-disturb windows)
+  desktop_notifications: DesktopNotificationPreferences;
+
   // Home sidebar section collapse states
   home_sidebar: {
     collapsed: {
@@ -788,6 +1213,13 @@ export interface StoredPreferences {
 
 export type FriendshipStatus = "pending" | "accepted" | "blocked";
 
+/** The current user's relationship to another user. */
+export type Relationship =
+  | "friend"
+  | "incoming_request"
+  | "outgoing_request"
+  | "blocked";
+
 export interface Friendship {
   id: string;
   requester_id: string;
@@ -849,6 +1281,19 @@ export interface DMListItem {
   participants: DMParticipant[];
   last_message: LastMessagePreview | null;
   unread_count: number;
+  /** How the user has organized the conversation. */
+  state?: DMConversationState;
+}
+
+/** Per-user DM organization, set with `PATCH /api/dm/{id}/state`. */
+export interface DMConversationState {
+  /** Hidden from the DM list until a new message arrives. */
+  hidden: boolean;
+  archived: boolean;
+  /** Pinned to the top of the DM list. */
+  pinned: boolean;
+  /** Marked unread; stays unread until the conversation is read. */
+  unread_kept: boolean;
 }
 
 // Pages Types
@@ -1077,6 +1522,8 @@ export interface UserSummary {
   avatar_url: string | null;
   created_at: string;
   is_banned: boolean;
+  /** When a system admin suspended the account (null if not suspended). */
+  suspended_at: string | null;
 }
 
 export interface GuildSummary {
@@ -1089,15 +1536,65 @@ export interface GuildSummary {
   suspended_at: string | null;
 }
 
+/** A guild waiting for discovery review. */
+export interface DiscoveryQueueEntry {
+  guild_id: string;
+  name: string;
+  description: string | null;
+  icon_url: string | null;
+  tags: string[];
+  owner_id: string;
+  owner_username: string;
+  member_count: number;
+  /** "pending" (submitted by the owner) or "delisted" (by user reports). */
+  status: "pending" | "delisted";
+  submitted_at: string | null;
+  /** Reports against the listing since its last review. */
+  report_count: number;
+}
+
+/** An account waiting for admin approval (registration policy "approval"). */
+export interface PendingRegistration {
+  user_id: string;
+  username: string;
+  display_name: string;
+  email: string | null;
+  registered_at: string;
+}
+
+/** A registration code for the "invite_only" registration policy. */
+export interface RegistrationCode {
+  id: string;
+  code: string;
+  note: string | null;
+  created_by: string | null;
+  /** Null when unlimited. */
+  max_uses: number | null;
+  use_count: number;
+  /** Null when the code never expires. */
+  expires_at: string | null;
+  created_at: string;
+}
+
+export interface CreateRegistrationCodeRequest {
+  note?: string;
+  /** 1-1000; unlimited if omitted. */
+  max_uses?: number;
+  /** 1-8760; never expires if omitted. */
+  expires_in_hours?: number;
+}
+
 export interface AuditLogEntry {
   id: string;
-  actor_id: string;
+  /** Null for failed sign-ins with an unknown username or deleted actors */
+  actor_id: string | null;
   actor_username: string | null;
   action: string;
   target_type: string | null;
   target_id: string | null;
   details: Record<string, unknown> | null;
   ip_address: string | null;
+  user_agent: string | null;
   created_at: string;
 }
 
@@ -1108,6 +1605,12 @@ export interface PaginatedResponse<T> {
   offset: number;
 }
 
+/** Step-up credentials for `POST /api/admin/elevate`; exactly one is sent. */
+export interface ElevateCredentials {
+  password?: string;
+  mfa_code?: string;
+}
+
 export interface ElevateResponse {
   elevated: boolean;
   expires_at: string;
@@ -1124,6 +1627,12 @@ export interface UserGuildMembership {
   is_owner: boolean;
 }
 
+export interface UsernameChange {
+  old_username: string;
+  new_username: string;
+  changed_at: string;
+}
+
 export interface UserDetailsResponse {
   id: string;
   username: string;
@@ -1132,9 +1641,21 @@ export interface UserDetailsResponse {
   avatar_url: string | null;
   created_at: string;
   is_banned: boolean;
+  suspended_at: string | null;
+  suspension_reason: string | null;
+  is_system_admin: boolean;
   last_login: string | null;
   guild_count: number;
   guilds: UserGuildMembership[];
+  /** Past usernames, newest change first. */
+  username_history: UsernameChange[];
+}
+
+export interface UserSuspensionResponse {
+  user_id: string;
+  suspended: boolean;
+  suspended_at: string | null;
+  reason: string | null;
 }
 
 // Guild Detail Types
@@ -1213,6 +1734,8 @@ export interface ServerSettings {
   oidc_enabled: boolean;
   oidc_providers: OidcProvider[];
   auth_methods: AuthMethodsConfig;
+  /** Whether passkeys can be registered and used to sign in. */
+  passkeys_enabled: boolean;
   registration_policy: string;
 }
 
@@ -1223,6 +1746,9 @@ export interface AuthSettingsResponse {
 }
 
 /** Admin OIDC provider (full detail, secrets masked). */
+/** What happens when an identity with no linked account signs in. */
+export type OidcProvisioning = "registration_policy" | "always" | "never";
+
 export interface AdminOidcProvider {
   id: string;
   slug: string;
@@ -1238,6 +1764,9 @@ export interface AdminOidcProvider {
   enabled: boolean;
   position: number;
   created_at: string;
+  provisioning: OidcProvisioning;
+  /** Link unlinked identities to the account with the same verified email. */
+  link_by_email: boolean;
 }
 
 // E2EE Types
@@ -1379,6 +1908,64 @@ export interface TopErrorsResponse {
   error_categories: ErrorCategoryEntry[];
 }
 
+export type TopConsumersType = "user" | "guild";
+
+export type TopConsumersSort = "requests" | "ws_events" | "voice_minutes";
+
+export interface TopConsumerEntry {
+  id: string;
+  name: string | null;
+  http_requests: number;
+  ws_events: number;
+  voice_minutes: number;
+}
+
+export interface TopConsumersResponse {
+  consumers: TopConsumerEntry[];
+}
+
+export type WsDisconnectCause =
+  | "client_close"
+  | "connection_lost"
+  | "timeout"
+  | "rate_limited"
+  | "auth_expired"
+  | "server_shutdown"
+  | "session_revoked";
+
+export interface WsDisconnectBreakdown {
+  total: number;
+  by_cause: {
+    cause: WsDisconnectCause;
+    count: number;
+    avg_session_secs: number | null;
+  }[];
+  by_close_code: { close_code: number | null; count: number }[];
+  bucket_secs: number;
+  timeline: { bucket: string; cause: WsDisconnectCause; count: number }[];
+  top_users: { user_id: string; username: string | null; count: number }[];
+}
+
+export interface ReportSlaPercentiles {
+  count: number;
+  p50_secs: number | null;
+  p90_secs: number | null;
+}
+
+export interface ReportSlaMetrics {
+  sla_secs: number;
+  backlog: {
+    open: number;
+    in_review: number;
+    oldest_open_secs: number | null;
+    breaching: number;
+  };
+  created: number;
+  time_to_claim: ReportSlaPercentiles;
+  time_to_resolve: ReportSlaPercentiles;
+  closed_within_sla: number;
+}
+
 export interface ObsLogEvent {
   id: string;
   ts: string;
@@ -1444,3 +2031,10 @@ export interface CallStateResponse {
   ended_at?: string;
   capabilities?: string[];
 }
+
+// Network State Types
+
+/** Whether the server is reachable (`network-state` event payload). */
+export interface NetworkState {
+  online: boolean;
+}
@@ -2,6 +2,8 @@
  * Utility Functions
  */
 
+import type { ChannelType, Message } from "./types";
+
 /**
  * Format a timestamp for display.
  * Shows time only for today, date and time for older messages.
@@ -97,6 +99,20 @@ export function shouldGroupWithPrevious(
   return diffMins < 5;
 }
 
+/**
+ * Whether a message was generated by the server (join, call, voice activity, ...).
+ */
+export function isSystemMessage(message: Pick<Message, "message_type">): boolean {
+  return !!message.message_type && message.message_type !== "default";
+}
+
+/**
+ * Whether members join a channel of this type through voice (voice or stage).
+ */
+export function isVoiceChannelType(channelType: ChannelType): boolean {
+  return channelType === "voice" || channelType === "stage";
+}
+
 /**
  * Format elapsed time in MM:SS format.
  * Used for voice connection duration timers.
@@ -0,0 +1,192 @@
+/**
+ * Passkey Ceremonies
+ *
+ * Runs WebAuthn registration and assertion ceremonies for the options the
+ * server returns. The desktop app hands them to the OS authenticator through
+ * the `passkey_create` / `passkey_get` commands; where that reports
+ * `PASSKEY_UNSUPPORTED`, and in the browser, `navigator.credentials` runs
+ * them instead. Binary fields cross the wire as base64url.
+ */
+
+import type {
+  PasskeyAssertion,
+  PasskeyChallenge,
+  Reauthentication,
+} from "@/lib/tauri";
+
+const isTauri = typeof window !== "undefined" && "__TAURI__" in window;
+
+/** Error the desktop bridge returns where the webview should take over. */
+const UNSUPPORTED = "PASSKEY_UNSUPPORTED";
+
+type Json = Record<string, unknown>;
+
+export function base64UrlToBuffer(value: string): ArrayBuffer {
+  const base64 = value.replace(/-/g, "+").replace(/_/g, "/");
+  const padded = base64 + "=".repeat((4 - (base64.length % 4)) % 4);
+  const binary = atob(padded);
+  const bytes = new Uint8Array(binary.length);
+  for (let i = 0; i < binary.length; i++) {
+    bytes[i] = binary.charCodeAt(i);
+  }
+  return bytes.buffer;
+}
+
+export function bufferToBase64Url(buffer: ArrayBuffer): string {
+  const bytes = new Uint8Array(buffer);
+  let binary = "";
+  for (const byte of bytes) {
+    binary += String.fromCharCode(byte);
+  }
+  return btoa(binary).replace(/\+/g, "-").replace(/\//g, "_").replace(/=+$/, "");
+}
+
+function decodeDescriptors(
+  descriptors: unknown,
+): PublicKeyCredentialDescriptor[] | undefined {
+  if (!Array.isArray(descriptors)) return undefined;
+  return descriptors.map((d: Json) => ({
+    ...(d as unknown as PublicKeyCredentialDescriptor),
+    id: base64UrlToBuffer(d.id as string),
+  }));
+}
+
+/** Decode the server's creation options for `navigator.credentials.create()`. */
+export function toCreationOptions(
+  options: unknown,
+): PublicKeyCredentialCreationOptions {
+  const publicKey = (options as { publicKey: Json }).publicKey;
+  const user = publicKey.user as Json;
+  return {
+    ...(publicKey as unknown as PublicKeyCredentialCreationOptions),
+    challenge: base64UrlToBuffer(publicKey.challenge as string),
+    user: {
+      ...(user as unknown as PublicKeyCredentialUserEntity),
+      id: base64UrlToBuffer(user.id as string),
+    },
+    excludeCredentials: decodeDescriptors(publicKey.excludeCredentials),
+  };
+}
+
+/** Decode the server's request options for `navigator.credentials.get()`. */
+export function toRequestOptions(
+  options: unknown,
+): PublicKeyCredentialRequestOptions {
+  const publicKey = (options as { publicKey: Json }).publicKey;
+  return {
+    ...(publicKey as unknown as PublicKeyCredentialRequestOptions),
+    challenge: base64UrlToBuffer(publicKey.challenge as string),
+    allowCredentials: decodeDescriptors(publicKey.allowCredentials),
+  };
+}
+
+async function webviewCreate(options: unknown): Promise<Json> {
+  const credential = (await navigator.credentials.create({
+    publicKey: toCreationOptions(options),
+  })) as PublicKeyCredential | null;
+  if (!credential) throw new Error("Passkey registration was cancelled");
+
+  const response = credential.response as AuthenticatorAttestationResponse;
+  return {
+    id: credential.id,
+    rawId: bufferToBase64Url(credential.rawId),
+    type: credential.type,
+    extensions: credential.getClientExtensionResults(),
+    response: {
+      attestationObject: bufferToBase64Url(response.attestationObject),
+      clientDataJSON: bufferToBase64Url(response.clientDataJSON),
+      transports: response.getTransports?.(),
+    },
+  };
+}
+
+async function webviewGet(options: unknown): Promise<Json> {
+  const credential = (await navigator.credentials.get({
+    publicKey: toRequestOptions(options),
+  })) as PublicKeyCredential | null;
+  if (!credential) throw new Error("Passkey sign-in was cancelled");
+
+  const response = credential.response as AuthenticatorAssertionResponse;
+  return {
+    id: credential.id,
+    rawId: bufferToBase64Url(credential.rawId),
+    type: credential.type,
+    extensions: credential.getClientExtensionResults(),
+    response: {
+      authenticatorData: bufferToBase64Url(response.authenticatorData),
+      clientDataJSON: bufferToBase64Url(response.clientDataJSON),
+      signature: bufferToBase64Url(response.signature),
+      userHandle: response.userHandle
+        ? bufferToBase64Url(response.userHandle)
+        : null,
+    },
+  };
+}
+
+function isUnsupported(err: unknown): boolean {
+  return (err instanceof Error ? err.message : String(err)) === UNSUPPORTED;
+}
+
+/** Create a credential, preferring the OS authenticator on desktop. */
+async function createCredential(options: unknown): Promise<Json> {
+  if (isTauri) {
+    const { invoke } = await import("@tauri-apps/api/core");
+    try {
+      return await invoke<Json>("passkey_create", { options });
+    } catch (err) {
+      if (!isUnsupported(err)) throw err;
+    }
+  }
+  return webviewCreate(options);
+}
+
+/** Sign an assertion challenge, preferring the OS authenticator on desktop. */
+async function getAssertion(serverUrl: string, options: unknown): Promise<Json> {
+  if (isTauri) {
+    const { invoke } = await import("@tauri-apps/api/core");
+    try {
+      return await invoke<Json>("passkey_get", { serverUrl, options });
+    } catch (err) {
+      if (!isUnsupported(err)) throw err;
+    }
+  }
+  return webviewGet(options);
+}
+
+/** Whether this client can run passkey ceremonies at all. */
+export function passkeysSupported(): boolean {
+  return isTauri || typeof window.PublicKeyCredential !== "undefined";
+}
+
+/**
+ * Sign a fresh challenge for `username` with one of their passkeys.
+ * Finishes a passkey sign-in, or answers MFA_REQUIRED in place of a code.
+ */
+export async function passkeyAssertion(
+  serverUrl: string,
+  username: string,
+): Promise<PasskeyAssertion> {
+  const { startPasskeyLogin } = await import("@/lib/tauri");
+  const challenge: PasskeyChallenge = await startPasskeyLogin(serverUrl, username);
+  return {
+    challenge_id: challenge.challenge_id,
+    credential: await getAssertion(serverUrl, challenge.options),
+  };
+}
+
+/**
+ * Register a new passkey for the signed-in account. Re-authenticates with
+ * the current password, an MFA code, or a fresh sign-in proof on accounts
+ * with neither.
+ */
+export async function registerPasskey(
+  name: string,
+  reauth: Reauthentication,
+) {
+  const { startPasskeyRegistration, finishPasskeyRegistration } = await import(
+    "@/lib/tauri"
+  );
+  const challenge = await startPasskeyRegistration(reauth);
+  const credential = await createCredential(challenge.options);
+  return finishPasskeyRegistration(challenge.challenge_id, name, credential);
+}
@@ -19,6 +19,7 @@ import type {
   QualityLevel,
 } from "./types";
 import * as Sentry from "@sentry/browser";
+import { measureNodeRtts } from "./regions";
 
 /** HTMLAudioElement extended with the non-standard setSinkId API (Chrome/Edge). */
 type AudioElementWithSinkId = HTMLAudioElement & {
@@ -37,6 +38,12 @@ export class BrowserVoiceAdapter implements VoiceAdapter {
   private localStream: MediaStream | null = null;
   private remoteStreams = new Map<string, MediaStream>();
 
+  // Remote audio playback, through one gain node per user
+  private playbackContext: AudioContext | null = null;
+  private playbackElements: HTMLAudioElement[] = [];
+  private userGainNodes = new Map<string, GainNode>();
+  private userGains = new Map<string, number>();
+
   // Screen share state
   private screenShareStream: MediaStream | null = null;
   private screenShareTrack: RTCRtpSender | null = null;
@@ -67,6 +74,9 @@ export class BrowserVoiceAdapter implements VoiceAdapter {
   private prevStats: {
     lost: number;
     received: number;
+    concealmentEvents: number;
+    jitterBufferDelay: number; // cumulative seconds
+    jitterBufferEmitted: number;
     timestamp: number;
   } | null = null;
 
@@ -170,12 +180,17 @@ export class BrowserVoiceAdapter implements VoiceAdapter {
         throw new Error("WebSocket connection timeout. Status: " + status.type);
       }
 
+      // Report media node latency so the server can place the channel
+      const nodeRtts = await measureNodeRtts();
+
       console.log("[BrowserVoiceAdapter] WebSocket ready, sending voice_join");
 
       // Send voice_join message to server
       await wsSend({
         type: "voice_join",
         channel_id: channelId,
+        node_rtts: nodeRtts,
+        noise_suppression: this.noiseSuppression,
       });
 
       console.log("[BrowserVoiceAdapter] Waiting for offer from server");
@@ -265,6 +280,14 @@ export class BrowserVoiceAdapter implements VoiceAdapter {
     return { ok: true, value: undefined };
   }
 
+  setUserGain(userId: string, gain: number): void {
+    this.userGains.set(userId, gain);
+    const gainNode = this.userGainNodes.get(userId);
+    if (gainNode) {
+      gainNode.gain.value = gain;
+    }
+  }
+
   async setNoiseSuppression(enabled: boolean): Promise<VoiceResult<void>> {
     console.log(`[BrowserVoiceAdapter] Set noise suppression: ${enabled}`);
     this.noiseSuppression = enabled;
@@ -452,6 +475,10 @@ export class BrowserVoiceAdapter implements VoiceAdapter {
       let jitter = 0;
       let totalLost = 0;
       let totalReceived = 0;
+      let totalConcealmentEvents = 0;
+      let totalJitterBufferDelay = 0;
+      let totalJitterBufferEmitted = 0;
+      let audioLevel: number | undefined;
 
       stats.forEach((report) => {
         if (report.type === "candidate-pair" && report.state === "succeeded") {
@@ -461,11 +488,19 @@ export class BrowserVoiceAdapter implements VoiceAdapter {
           totalLost += report.packetsLost ?? 0;
           totalReceived += report.packetsReceived ?? 0;
           jitter = Math.max(jitter, (report.jitter ?? 0) * 1000);
+          totalConcealmentEvents += report.concealmentEvents ?? 0;
+          totalJitterBufferDelay += report.jitterBufferDelay ?? 0;
+          totalJitterBufferEmitted += report.jitterBufferEmittedCount ?? 0;
+        }
+        if (report.type === "media-source" && report.kind === "audio") {
+          audioLevel = report.audioLevel;
         }
       });
 
-      // Calculate delta packet loss since last sample
+      // Calculate delta packet loss and playout stats since last sample
       let packetLoss = 0;
+      let concealmentEvents: number | undefined;
+      let jitterBufferDelay: number | undefined;
       const now = Date.now();
 
       if (this.prevStats) {
@@ -476,11 +511,29 @@ export class BrowserVoiceAdapter implements VoiceAdapter {
         if (deltaTotal > 0) {
           packetLoss = (deltaLost / deltaTotal) * 100;
         }
+
+        // Counters restart when tracks are replaced; skip negative deltas
+        const deltaConcealment =
+          totalConcealmentEvents - this.prevStats.concealmentEvents;
+        if (deltaConcealment >= 0) {
+          concealmentEvents = deltaConcealment;
+        }
+
+        const deltaEmitted =
+          totalJitterBufferEmitted - this.prevStats.jitterBufferEmitted;
+        const deltaDelay =
+          totalJitterBufferDelay - this.prevStats.jitterBufferDelay;
+        if (deltaEmitted > 0 && deltaDelay >= 0) {
+          jitterBufferDelay = Math.round((deltaDelay / deltaEmitted) * 1000);
+        }
       }
 
       this.prevStats = {
         lost: totalLost,
         received: totalReceived,
+        concealmentEvents: totalConcealmentEvents,
+        jitterBufferDelay: totalJitterBufferDelay,
+        jitterBufferEmitted: totalJitterBufferEmitted,
         timestamp: now,
       };
 
@@ -490,6 +543,12 @@ export class BrowserVoiceAdapter implements VoiceAdapter {
         jitter: Math.round(jitter),
         quality: this.calculateQuality(latency, packetLoss, jitter),
         timestamp: now,
+        concealmentEvents,
+        jitterBufferDelay,
+        audioLevel:
+          audioLevel === undefined
+            ? undefined
+            : Math.round(audioLevel * 1000) / 1000,
       };
     } catch (err) {
       console.warn("Failed to extract metrics:", err);
@@ -1095,6 +1154,7 @@ export class BrowserVoiceAdapter implements VoiceAdapter {
       } else {
         // Audio track = voice (Microphone) or screen audio (ScreenAudio)
         this.remoteStreams.set(userId, stream);
+        this.playRemoteAudio(userId, stream);
 
         const remoteTrack: RemoteTrack = {
           trackId: track.id,
@@ -1114,6 +1174,30 @@ export class BrowserVoiceAdapter implements VoiceAdapter {
     };
   }
 
+  /** Play a remote audio stream through the sender's gain node. */
+  private playRemoteAudio(userId: string, stream: MediaStream) {
+    if (!this.playbackContext) {
+      this.playbackContext = new AudioContext();
+    }
+
+    let gainNode = this.userGainNodes.get(userId);
+    if (!gainNode) {
+      gainNode = this.playbackContext.createGain();
+      gainNode.gain.value = this.userGains.get(userId) ?? 1;
+      gainNode.connect(this.playbackContext.destination);
+      this.userGainNodes.set(userId, gainNode);
+    }
+    this.playbackContext.createMediaStreamSource(stream).connect(gainNode);
+
+    // Chrome only feeds remote WebRTC audio to Web Audio while a media
+    // element plays the stream, so a muted one is kept alongside
+    const element = new Audio();
+    element.muted = true;
+    element.srcObject = stream;
+    element.play().catch(() => {});
+    this.playbackElements.push(element);
+  }
+
   private startVAD() {
     if (!this.localStream) return;
 
@@ -1191,6 +1275,15 @@ export class BrowserVoiceAdapter implements VoiceAdapter {
     // Clear remote streams
     this.remoteStreams.clear();
 
+    // Stop remote audio playback
+    this.playbackElements.forEach((element) => {
+      element.srcObject = null;
+    });
+    this.playbackElements = [];
+    this.userGainNodes.clear();
+    this.playbackContext?.close();
+    this.playbackContext = null;
+
     // Stop mic test if running
     this.stopMicTest();
   }
@@ -0,0 +1,76 @@
+/**
+ * Media Node Latency Probing
+ *
+ * Measures round-trip time to each SFU media node listed by the server so
+ * `voice_join` can report it and the server can place the channel on the
+ * node closest to its participants.
+ */
+
+import type { VoiceMediaNode } from "@/lib/tauri";
+
+/** Probes per node; the fastest one is kept so connection setup doesn't skew it. */
+const PROBE_ATTEMPTS = 3;
+/** Probes slower than this count as failed. */
+const PROBE_TIMEOUT_MS = 1000;
+
+async function probeOnce(url: string): Promise<number | null> {
+  const controller = new AbortController();
+  const timer = setTimeout(() => controller.abort(), PROBE_TIMEOUT_MS);
+  const start = performance.now();
+  try {
+    await fetch(url, {
+      cache: "no-store",
+      mode: "no-cors",
+      signal: controller.signal,
+    });
+    return performance.now() - start;
+  } catch {
+    return null;
+  } finally {
+    clearTimeout(timer);
+  }
+}
+
+/**
+ * Measure RTT to a single node in milliseconds, or `null` if every probe failed.
+ */
+export async function probeNode(node: VoiceMediaNode): Promise<number | null> {
+  let best: number | null = null;
+  for (let i = 0; i < PROBE_ATTEMPTS; i++) {
+    const rtt = await probeOnce(node.probe_url);
+    if (rtt !== null && (best === null || rtt < best)) {
+      best = rtt;
+    }
+  }
+  return best === null ? null : Math.round(best);
+}
+
+/**
+ * Measure RTT to every media node, keyed by node ID.
+ *
+ * Returns an empty map on single-node deployments or if the node list
+ * can't be fetched. Nodes whose probes all fail are left out.
+ */
+export async function measureNodeRtts(): Promise<Record<string, number>> {
+  const { getVoiceRegions } = await import("@/lib/tauri");
+
+  let nodes: VoiceMediaNode[];
+  try {
+    nodes = (await getVoiceRegions()).nodes;
+  } catch (err) {
+    console.warn("[VoiceRegions] Failed to fetch media nodes:", err);
+    return {};
+  }
+
+  const results = await Promise.all(
+    nodes.map(async (node) => [node.id, await probeNode(node)] as const),
+  );
+
+  const rtts: Record<string, number> = {};
+  for (const [id, rtt] of results) {
+    if (rtt !== null) {
+      rtts[id] = rtt;
+    }
+  }
+  return rtts;
+}
@@ -19,13 +19,14 @@ import type {
   CaptureSource,
 } from "./types";
 import * as Sentry from "@sentry/browser";
+import { measureNodeRtts } from "./regions";
 
 export class TauriVoiceAdapter implements VoiceAdapter {
   private state: VoiceConnectionState = "disconnected";
   private channelId: string | null = null;
   private muted = false;
   private deafened = false;
-  private noiseSuppression = false;
+  private noiseSuppression = true;
   private screenSharing = false;
   private webcamActive = false;
 
@@ -48,7 +49,12 @@ export class TauriVoiceAdapter implements VoiceAdapter {
     console.log(`[TauriVoiceAdapter] Joining channel: ${channelId}`);
 
     try {
-      await invoke("join_voice", { channelId });
+      const nodeRtts = await measureNodeRtts();
+      await invoke("join_voice", {
+        channelId,
+        nodeRtts,
+        noiseSuppression: this.noiseSuppression,
+      });
       this.channelId = channelId;
       this.setState("connecting");
       return { ok: true, value: undefined };
@@ -123,13 +129,7 @@ export class TauriVoiceAdapter implements VoiceAdapter {
       this.noiseSuppression = enabled;
       return { ok: true, value: undefined };
     } catch (err) {
-      // Noise suppression might not be implemented in Tauri backend yet
-      // Fall back to just storing the state locally
-      console.warn(
-        "[TauriVoiceAdapter] Noise suppression not implemented in backend",
-      );
-      this.noiseSuppression = enabled;
-      return { ok: true, value: undefined };
+      return { ok: false, error: this.mapTauriError(err) };
     }
   }
 
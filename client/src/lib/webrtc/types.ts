@@ -170,6 +170,9 @@ export interface ConnectionMetrics {
   jitter: number; // ms
   quality: QualityLevel;
   timestamp: number;
+  concealmentEvents?: number; // received audio, since last sample
+  jitterBufferDelay?: number; // received audio, ms
+  audioLevel?: number; // outgoing mic, 0-1
 }
 
 /**
@@ -181,6 +184,9 @@ export interface ParticipantMetrics {
   packetLoss: number;
   jitter: number;
   quality: QualityLevel;
+  concealmentEvents?: number;
+  jitterBufferDelay?: number;
+  audioLevel?: number;
 }
 
 /**
@@ -198,6 +204,8 @@ export interface VoiceAdapter {
   setMute(muted: boolean): Promise<VoiceResult<void>>;
   setDeafen(deafened: boolean): Promise<VoiceResult<void>>;
   setNoiseSuppression(enabled: boolean): Promise<VoiceResult<void>>;
+  /** Scale a remote user's audio (0-2, 1 = unchanged). Not supported by native playback. */
+  setUserGain?(userId: string, gain: number): void;
 
   // Signaling (called by WebSocket store)
   handleOffer(channelId: string, sdp: string): Promise<VoiceResult<string>>; // Returns answer SDP
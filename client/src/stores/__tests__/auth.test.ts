@@ -30,6 +30,7 @@ vi.mock("@/stores/preferences", () => ({
 vi.mock("@/stores/drafts", () => ({
   clearAllDrafts: vi.fn(),
   cleanupDrafts: vi.fn(),
+  syncDrafts: vi.fn(() => Promise.resolve()),
 }));
 
 import * as tauri from "@/lib/tauri";
@@ -158,6 +158,22 @@ describe("channels store", () => {
       expect(subscribeChannel).not.toHaveBeenCalledWith("v1"); // voice channels not subscribed
     });
 
+    it("selects the landing channel when given", async () => {
+      const channels: ChannelWithUnread[] = [
+        createChannelWithUnread({ id: "t1", position: 0 }),
+        createChannelWithUnread({ id: "rules", position: 1 }),
+      ];
+      vi.mocked(tauri.getGuildChannels).mockResolvedValue(channels);
+      vi.mocked(waitForConnection).mockResolvedValue(false);
+
+      await loadChannelsForGuild("guild-1", "rules");
+      expect(channelsState.selectedChannelId).toBe("rules");
+
+      // A designation that no longer exists falls back to the first text channel
+      await loadChannelsForGuild("guild-1", "deleted");
+      expect(channelsState.selectedChannelId).toBe("t1");
+    });
+
     it("clears selection when no text channels", async () => {
       const channels: ChannelWithUnread[] = [
         createChannelWithUnread({ id: "v1", channel_type: "voice" }),
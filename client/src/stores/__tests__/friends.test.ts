@@ -33,6 +33,7 @@ import {
   unblockUser,
   handleUserBlocked,
   handleUserUnblocked,
+  handleRelationshipUpdate,
   getOnlineFriends,
 } from "../friends";
 
@@ -320,6 +321,36 @@ describe("friends store", () => {
     });
   });
 
+  describe("handleRelationshipUpdate (WS handler)", () => {
+    it("drops the user from every list when the relationship ends", () => {
+      setFriendsState({
+        friends: [createFriend({ user_id: "user-1" })],
+        pendingRequests: [
+          createFriend({ user_id: "user-2", friendship_status: "pending" }),
+        ],
+      });
+
+      handleRelationshipUpdate("user-1", null);
+
+      expect(friendsState.friends).toEqual([]);
+      expect(friendsState.pendingRequests).toHaveLength(1);
+    });
+
+    it("moves an accepted request to the friends list", () => {
+      setFriendsState({
+        pendingRequests: [
+          createFriend({ user_id: "user-1", friendship_status: "pending" }),
+        ],
+      });
+      vi.mocked(tauri.getFriends).mockResolvedValue([]);
+
+      handleRelationshipUpdate("user-1", "friend");
+
+      expect(friendsState.pendingRequests).toEqual([]);
+      expect(tauri.getFriends).toHaveBeenCalled();
+    });
+  });
+
   describe("getOnlineFriends", () => {
     it("returns only online friends", () => {
       setFriendsState({
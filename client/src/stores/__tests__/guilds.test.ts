@@ -6,14 +6,18 @@ vi.mock("@/lib/tauri", () => ({
   getGuildChannels: vi.fn(),
   createGuild: vi.fn(),
   updateGuild: vi.fn(),
+  uploadGuildImage: vi.fn(),
+  removeGuildImage: vi.fn(),
   deleteGuild: vi.fn(),
   joinGuild: vi.fn(),
   leaveGuild: vi.fn(),
+  invalidateGuildCache: vi.fn(),
   getGuildInvites: vi.fn(),
   createGuildInvite: vi.fn(),
   deleteGuildInvite: vi.fn(),
   joinViaInvite: vi.fn(),
   kickGuildMember: vi.fn(),
+  wsRequestGuildMembers: vi.fn(),
 }));
 
 vi.mock("@/components/ui/Toast", () => ({
@@ -44,9 +48,14 @@ import {
   setGuildsState,
   loadGuilds,
   loadGuildMembers,
+  requestGuildMemberChunk,
+  handleGuildMemberChunk,
   getActiveGuild,
   createGuild,
   updateGuild,
+  uploadGuildImage,
+  removeGuildImage,
+  handleGuildUpdate,
   deleteGuild,
   joinGuild,
   leaveGuild,
@@ -54,6 +63,8 @@ import {
   createInvite,
   deleteInvite,
   kickMember,
+  setMemberTimeout,
+  isMemberTimedOut,
   isGuildOwner,
   patchGuild,
   areThreadsEnabled,
@@ -62,6 +73,7 @@ import {
   clearGuildUnread,
   getGuildIdForChannel,
   joinViaInviteCode,
+  landingChannelId,
   selectGuild,
   selectHome,
 } from "../guilds";
@@ -79,6 +91,8 @@ function createGuildObj(overrides: Partial<Guild> = {}): Guild {
     banner_url: null,
     plan: "free",
     created_at: "2025-01-01T00:00:00Z",
+    system_channel_id: null,
+    rules_channel_id: null,
     ...overrides,
   };
 }
@@ -105,6 +119,7 @@ function createInviteObj(overrides: Partial<GuildInvite> = {}): GuildInvite {
     created_by: "owner-1",
     expires_at: null,
     use_count: 0,
+    max_uses: null,
     created_at: "2025-01-01T00:00:00Z",
     ...overrides,
   };
@@ -117,6 +132,7 @@ describe("guilds store", () => {
       guilds: [],
       activeGuildId: null,
       members: {},
+      memberCursors: {},
       invites: {},
       guildChannels: {},
       guildUnreadCounts: {},
@@ -176,6 +192,39 @@ describe("guilds store", () => {
     });
   });
 
+  describe("member list chunks", () => {
+    it("merges chunks by user and records the cursor", () => {
+      setGuildsState("members", "guild-1", [
+        createMember({ user_id: "u1", nickname: null }),
+      ]);
+
+      handleGuildMemberChunk(
+        "guild-1",
+        [
+          createMember({ user_id: "u1", nickname: "Renamed" }),
+          createMember({ user_id: "u2" }),
+        ],
+        "u2",
+      );
+
+      const members = guildsState.members["guild-1"];
+      expect(members.map((m) => m.user_id)).toEqual(["u1", "u2"]);
+      expect(members[0].nickname).toBe("Renamed");
+      expect(guildsState.memberCursors["guild-1"]).toBe("u2");
+    });
+
+    it("continues from the last cursor", async () => {
+      handleGuildMemberChunk("guild-1", [createMember({ user_id: "u5" })], "u5");
+
+      await requestGuildMemberChunk("guild-1", { onlineOnly: true });
+
+      expect(tauri.wsRequestGuildMembers).toHaveBeenCalledWith("guild-1", {
+        onlineOnly: true,
+        after: "u5",
+      });
+    });
+  });
+
   describe("getActiveGuild", () => {
     it("returns null when no guild active", () => {
       expect(getActiveGuild()).toBeNull();
@@ -212,6 +261,52 @@ describe("guilds store", () => {
     });
   });
 
+  describe("guild images", () => {
+    it("stores the guild returned by an upload", async () => {
+      setGuildsState({ guilds: [createGuildObj({ id: "g1" })] });
+      const file = new File(["x"], "icon.png", { type: "image/png" });
+      vi.mocked(tauri.uploadGuildImage).mockResolvedValue(
+        createGuildObj({ id: "g1", icon_url: "/kaiku/guilds/g1/icons/a/256.webp" }),
+      );
+
+      await uploadGuildImage("g1", "icon", file);
+
+      expect(tauri.uploadGuildImage).toHaveBeenCalledWith("g1", "icon", file);
+      expect(guildsState.guilds[0].icon_url).toBe(
+        "/kaiku/guilds/g1/icons/a/256.webp",
+      );
+    });
+
+    it("clears a removed banner", async () => {
+      setGuildsState({
+        guilds: [createGuildObj({ id: "g1", banner_url: "/b/960.webp" })],
+      });
+      vi.mocked(tauri.removeGuildImage).mockResolvedValue(
+        createGuildObj({ id: "g1", banner_url: null }),
+      );
+
+      await removeGuildImage("g1", "banner");
+
+      expect(guildsState.guilds[0].banner_url).toBeNull();
+    });
+  });
+
+  describe("handleGuildUpdate", () => {
+    it("replaces the guild's fields", () => {
+      setGuildsState({
+        guilds: [createGuildObj({ id: "g1" }), createGuildObj({ id: "g2" })],
+      });
+
+      handleGuildUpdate(
+        createGuildObj({ id: "g2", name: "Renamed", icon_url: "/i/256.webp" }),
+      );
+
+      expect(guildsState.guilds[0].name).toBe("Test Guild");
+      expect(guildsState.guilds[1].name).toBe("Renamed");
+      expect(guildsState.guilds[1].icon_url).toBe("/i/256.webp");
+    });
+  });
+
   describe("deleteGuild", () => {
     it("removes guild from store", async () => {
       setGuildsState({ guilds: [createGuildObj({ id: "g1" })] });
@@ -282,6 +377,16 @@ describe("guilds store", () => {
       expect(result.code).toBe("NEW");
       expect(guildsState.invites["guild-1"]).toHaveLength(1);
     });
+
+    it("forwards the use limit", async () => {
+      vi.mocked(tauri.createGuildInvite).mockResolvedValue(
+        createInviteObj({ max_uses: 5 }),
+      );
+
+      await createInvite("guild-1", "1d", 5);
+
+      expect(tauri.createGuildInvite).toHaveBeenCalledWith("guild-1", "1d", 5);
+    });
   });
 
   describe("deleteInvite", () => {
@@ -306,6 +411,33 @@ describe("guilds store", () => {
     });
   });
 
+  describe("setMemberTimeout", () => {
+    it("updates only the matching member", () => {
+      setGuildsState("members", "guild-1", [
+        createMember({ user_id: "u1" }),
+        createMember({ user_id: "u2" }),
+      ]);
+      const until = new Date(Date.now() + 60_000).toISOString();
+
+      setMemberTimeout("guild-1", "u1", until);
+
+      const [first, second] = guildsState.members["guild-1"];
+      expect(first.timeout_until).toBe(until);
+      expect(isMemberTimedOut(first)).toBe(true);
+      expect(isMemberTimedOut(second)).toBe(false);
+
+      setMemberTimeout("guild-1", "u1", null);
+      expect(isMemberTimedOut(guildsState.members["guild-1"][0])).toBe(false);
+    });
+
+    it("treats an expired timeout as lifted", () => {
+      const member = createMember({
+        timeout_until: new Date(Date.now() - 1000).toISOString(),
+      });
+      expect(isMemberTimedOut(member)).toBe(false);
+    });
+  });
+
   describe("isGuildOwner", () => {
     it("returns true for owner", () => {
       setGuildsState({
@@ -432,6 +564,25 @@ describe("guilds store", () => {
     });
   });
 
+  describe("landingChannelId", () => {
+    it("prefers the rules channel right after joining", () => {
+      const guild = createGuildObj({
+        system_channel_id: "general",
+        rules_channel_id: "rules",
+      });
+
+      expect(landingChannelId(guild, true)).toBe("rules");
+      expect(landingChannelId(guild, false)).toBe("general");
+    });
+
+    it("falls back to the system channel, then none", () => {
+      expect(
+        landingChannelId(createGuildObj({ system_channel_id: "general" }), true),
+      ).toBe("general");
+      expect(landingChannelId(createGuildObj(), true)).toBeNull();
+    });
+  });
+
   describe("selectHome", () => {
     it("clears active guild", async () => {
       setGuildsState({ activeGuildId: "g1" });
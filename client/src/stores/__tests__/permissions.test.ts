@@ -7,6 +7,7 @@ vi.mock("@/lib/tauri", () => ({
   createGuildRole: vi.fn(),
   updateGuildRole: vi.fn(),
   deleteGuildRole: vi.fn(),
+  reorderGuildRoles: vi.fn(),
   getChannelOverrides: vi.fn(),
   setChannelOverride: vi.fn(),
   deleteChannelOverride: vi.fn(),
@@ -93,28 +94,47 @@ describe("permissions store", () => {
   describe("reorderRole", () => {
     beforeEach(async () => {
       vi.mocked(tauri.getGuildRoles).mockResolvedValue([...mockRoles]);
-      vi.mocked(tauri.updateGuildRole).mockResolvedValue(mockRoles[0]);
+      vi.mocked(tauri.reorderGuildRoles).mockResolvedValue([...mockRoles]);
       await loadGuildRoles("guild1");
     });
 
     it("should not reorder @everyone role", async () => {
       await reorderRole("guild1", "everyone", 0);
 
-      expect(tauri.updateGuildRole).not.toHaveBeenCalled();
+      expect(tauri.reorderGuildRoles).not.toHaveBeenCalled();
     });
 
     it("should optimistically update local state", async () => {
       // Move Mod (position 1) to position 0 (Admin's position)
       await reorderRole("guild1", "role2", 0);
 
-      // Verify optimistic update happened
-      expect(tauri.updateGuildRole).toHaveBeenCalledWith("guild1", "role2", {
-        position: 0,
-      });
+      // Full order is sent, with the moved role first
+      expect(tauri.reorderGuildRoles).toHaveBeenCalledWith("guild1", [
+        "role2",
+        "role1",
+        "role3",
+      ]);
+    });
+
+    it("should hand out existing position slots when moving down", async () => {
+      vi.mocked(tauri.reorderGuildRoles).mockImplementation(
+        () => new Promise(() => {}),
+      );
+
+      // Move Admin (position 0) to Member's position (2)
+      void reorderRole("guild1", "role1", 2);
+
+      const roles = getGuildRoles("guild1");
+      expect(roles.map((r) => [r.id, r.position])).toEqual([
+        ["role2", 0],
+        ["role3", 1],
+        ["role1", 2],
+        ["everyone", 3],
+      ]);
     });
 
     it("should revert on API failure", async () => {
-      vi.mocked(tauri.updateGuildRole).mockRejectedValue(
+      vi.mocked(tauri.reorderGuildRoles).mockRejectedValue(
         new Error("API Error"),
       );
       vi.mocked(tauri.getGuildRoles).mockResolvedValue([...mockRoles]);
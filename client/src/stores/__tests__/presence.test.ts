@@ -3,6 +3,7 @@ import { beforeEach, describe, expect, it, vi } from "vitest";
 vi.mock("@/lib/tauri", () => ({
   updateStatus: vi.fn(),
   updateCustomStatus: vi.fn(),
+  wsPresencePing: vi.fn().mockResolvedValue(undefined),
 }));
 
 vi.mock("@/lib/idleDetector", () => ({
@@ -33,7 +34,11 @@ vi.mock("@/stores/friends", () => ({
   setFriendsState: vi.fn(),
 }));
 
-import { updateCustomStatus, updateStatus } from "@/lib/tauri";
+import {
+  updateCustomStatus,
+  updateStatus,
+  wsPresencePing,
+} from "@/lib/tauri";
 import { startIdleDetection, stopIdleDetection } from "@/lib/idleDetector";
 import { currentUser } from "@/stores/auth";
 import { setFriendsState } from "@/stores/friends";
@@ -298,6 +303,25 @@ describe("presence store", () => {
 
       expect(startIdleDetection).toHaveBeenCalledWith(expect.any(Function), 5);
     });
+
+    it("pings the server while active and stops when idle", () => {
+      vi.useFakeTimers();
+      vi.mocked(wsPresencePing).mockClear();
+      initIdleDetection();
+      expect(wsPresencePing).toHaveBeenCalledTimes(1);
+
+      vi.advanceTimersByTime(60_000);
+      expect(wsPresencePing).toHaveBeenCalledTimes(2);
+
+      const onIdleChange = vi.mocked(startIdleDetection).mock.calls.at(-1)![0];
+      onIdleChange(true);
+      vi.advanceTimersByTime(120_000);
+      expect(wsPresencePing).toHaveBeenCalledTimes(2);
+      expect(updateStatus).not.toHaveBeenCalledWith("idle");
+
+      stopIdleDetectionCleanup();
+      vi.useRealTimers();
+    });
   });
 
   describe("stopIdleDetectionCleanup", () => {
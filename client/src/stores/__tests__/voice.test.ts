@@ -58,6 +58,7 @@ import {
   getLocalMetrics,
   getParticipantMetrics,
   handleVoiceUserStats,
+  getIssueSource,
 } from "../voice";
 
 describe("voice store", () => {
@@ -211,6 +212,82 @@ describe("voice store", () => {
 
       expect(getParticipantMetrics("u1")).toBeUndefined();
     });
+
+    it("keeps playout stats when reported", () => {
+      setVoiceState({ state: "connected", channelId: "ch-1" });
+
+      handleVoiceUserStats({
+        channel_id: "ch-1",
+        user_id: "u1",
+        latency: 25,
+        packet_loss: 0,
+        jitter: 3,
+        quality: 3,
+        concealment_events: 12,
+        jitter_buffer_delay: 80,
+        audio_level: 0.4,
+      });
+
+      const metrics = getParticipantMetrics("u1");
+      expect(metrics?.concealmentEvents).toBe(12);
+      expect(metrics?.jitterBufferDelay).toBe(80);
+      expect(metrics?.audioLevel).toBe(0.4);
+    });
+  });
+
+  describe("getIssueSource", () => {
+    const localMetrics = (quality: "good" | "poor", concealmentEvents = 0) => ({
+      latency: 20,
+      packetLoss: 0,
+      jitter: 2,
+      quality,
+      timestamp: Date.now(),
+      concealmentEvents,
+    });
+
+    const reportRemote = (quality: number, concealment_events = 0) =>
+      handleVoiceUserStats({
+        channel_id: "ch-1",
+        user_id: "u1",
+        latency: 20,
+        packet_loss: 0,
+        jitter: 2,
+        quality,
+        concealment_events,
+      });
+
+    it("blames the remote side when only their stats are degraded", () => {
+      setVoiceState({
+        state: "connected",
+        channelId: "ch-1",
+        localMetrics: localMetrics("good"),
+      });
+      reportRemote(1);
+
+      expect(getIssueSource("u1")).toBe("remote");
+    });
+
+    it("blames the local side when we conceal audio but they look fine", () => {
+      setVoiceState({
+        state: "connected",
+        channelId: "ch-1",
+        localMetrics: localMetrics("good", 20),
+      });
+      reportRemote(3);
+
+      expect(getIssueSource("u1")).toBe("local");
+    });
+
+    it("returns null when both sides are healthy", () => {
+      setVoiceState({
+        state: "connected",
+        channelId: "ch-1",
+        localMetrics: localMetrics("good"),
+      });
+      reportRemote(3);
+
+      expect(getIssueSource("u1")).toBeNull();
+    });
   });
 
   describe("setSpeaking", () => {
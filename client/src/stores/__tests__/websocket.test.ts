@@ -7,6 +7,7 @@ vi.mock("@/lib/tauri", () => ({
   wsUnsubscribe: vi.fn(),
   wsTyping: vi.fn(),
   wsStopTyping: vi.fn(),
+  wsTimeSync: vi.fn(() => Promise.resolve()),
   getBrowserWebSocket: vi.fn(),
   wsStatus: vi.fn(),
   wsSend: vi.fn(),
@@ -57,6 +58,7 @@ vi.mock("@/stores/friends", () => ({
   loadPendingRequests: vi.fn(),
   handleUserBlocked: vi.fn(),
   handleUserUnblocked: vi.fn(),
+  handleRelationshipUpdate: vi.fn(),
 }));
 
 vi.mock("@/stores/channels", () => ({
@@ -16,10 +16,16 @@ import type {
   GuildDetailsResponse,
   BulkBanResponse,
   BulkSuspendResponse,
+  ElevateCredentials,
   ObservabilitySummary,
   TrendsResponse,
   TopRoutesResponse,
   TopErrorsResponse,
+  TopConsumersResponse,
+  TopConsumersType,
+  TopConsumersSort,
+  WsDisconnectBreakdown,
+  ReportSlaMetrics,
   ObsLogEvent,
   ObsTraceEntry,
   ObsLinksResponse,
@@ -90,6 +96,9 @@ interface AdminStoreState {
   obsTrends: TrendsResponse | null;
   obsTopRoutes: TopRoutesResponse | null;
   obsTopErrors: TopErrorsResponse | null;
+  obsTopConsumers: TopConsumersResponse | null;
+  obsWsDisconnects: WsDisconnectBreakdown | null;
+  obsReportSla: ReportSlaMetrics | null;
   obsLogs: ObsLogEvent[];
   obsLogsCursor: string | null;
   obsLogsHasMore: boolean;
@@ -115,6 +124,9 @@ interface AdminStoreState {
   isObsTrendsLoading: boolean;
   isObsTopRoutesLoading: boolean;
   isObsTopErrorsLoading: boolean;
+  isObsTopConsumersLoading: boolean;
+  isObsWsDisconnectsLoading: boolean;
+  isObsReportSlaLoading: boolean;
   isObsLogsLoading: boolean;
   isObsTracesLoading: boolean;
   isObsLinksLoading: boolean;
@@ -168,6 +180,9 @@ const [adminState, setAdminState] = createStore<AdminStoreState>({
   obsTrends: null,
   obsTopRoutes: null,
   obsTopErrors: null,
+  obsTopConsumers: null,
+  obsWsDisconnects: null,
+  obsReportSla: null,
   obsLogs: [],
   obsLogsCursor: null,
   obsLogsHasMore: false,
@@ -193,6 +208,9 @@ const [adminState, setAdminState] = createStore<AdminStoreState>({
   isObsTrendsLoading: false,
   isObsTopRoutesLoading: false,
   isObsTopErrorsLoading: false,
+  isObsTopConsumersLoading: false,
+  isObsWsDisconnectsLoading: false,
+  isObsReportSlaLoading: false,
   isObsLogsLoading: false,
   isObsTracesLoading: false,
   isObsLinksLoading: false,
@@ -297,15 +315,16 @@ export async function loadAdminStats(): Promise<void> {
 }
 
 /**
- * Elevate admin session
+ * Elevate admin session after re-confirming the password or a TOTP code
  */
 export async function elevateSession(
+  credentials: ElevateCredentials,
   reason?: string,
 ): Promise<boolean> {
   setAdminState({ isElevating: true, error: null });
 
   try {
-    const response = await tauri.adminElevate(reason);
+    const response = await tauri.adminElevate(credentials, reason);
     setAdminState({
       isElevated: response.elevated,
       elevationExpiresAt: response.expires_at,
@@ -534,6 +553,126 @@ export async function unbanUser(userId: string): Promise<boolean> {
   }
 }
 
+/**
+ * Apply a suspension change to the user list and the open detail view
+ */
+function setUserSuspension(
+  userId: string,
+  suspendedAt: string | null,
+  reason: string | null,
+): void {
+  setAdminState("users", (users) =>
+    users.map((u) =>
+      u.id === userId ? { ...u, suspended_at: suspendedAt } : u,
+    ),
+  );
+  if (adminState.selectedUserDetails?.id === userId) {
+    setAdminState("selectedUserDetails", {
+      ...adminState.selectedUserDetails,
+      suspended_at: suspendedAt,
+      suspension_reason: reason,
+    });
+  }
+}
+
+/**
+ * Suspend a user account
+ */
+export async function suspendUser(
+  userId: string,
+  reason: string,
+): Promise<boolean> {
+  try {
+    const result = await tauri.adminSuspendUser(userId, reason);
+    setUserSuspension(userId, result.suspended_at, result.reason);
+    return true;
+  } catch (err) {
+    console.error("[Admin] Failed to suspend user:", err);
+    setAdminState({
+      error: err instanceof Error ? err.message : "Failed to suspend user",
+    });
+    return false;
+  }
+}
+
+/**
+ * Lift a user's suspension
+ */
+export async function unsuspendUser(userId: string): Promise<boolean> {
+  try {
+    await tauri.adminUnsuspendUser(userId);
+    setUserSuspension(userId, null, null);
+    return true;
+  } catch (err) {
+    console.error("[Admin] Failed to unsuspend user:", err);
+    setAdminState({
+      error: err instanceof Error ? err.message : "Failed to unsuspend user",
+    });
+    return false;
+  }
+}
+
+/**
+ * Sign a user out everywhere. Returns the number of revoked sessions.
+ */
+export async function revokeUserSessions(
+  userId: string,
+): Promise<number | null> {
+  try {
+    const result = await tauri.adminRevokeUserSessions(userId);
+    return result.sessions_revoked;
+  } catch (err) {
+    console.error("[Admin] Failed to revoke sessions:", err);
+    setAdminState({
+      error: err instanceof Error ? err.message : "Failed to revoke sessions",
+    });
+    return null;
+  }
+}
+
+/**
+ * Email a user a password reset code
+ */
+export async function sendUserPasswordReset(userId: string): Promise<boolean> {
+  try {
+    await tauri.adminSendPasswordReset(userId);
+    return true;
+  } catch (err) {
+    console.error("[Admin] Failed to send password reset:", err);
+    setAdminState({
+      error:
+        err instanceof Error ? err.message : "Failed to send password reset",
+    });
+    return false;
+  }
+}
+
+/**
+ * Grant or revoke system admin
+ */
+export async function setUserSystemAdmin(
+  userId: string,
+  isAdmin: boolean,
+): Promise<boolean> {
+  try {
+    const result = await tauri.adminSetSystemAdmin(userId, isAdmin);
+    if (adminState.selectedUserDetails?.id === userId) {
+      setAdminState("selectedUserDetails", {
+        ...adminState.selectedUserDetails,
+        is_system_admin: result.is_admin,
+      });
+    }
+    return true;
+  } catch (err) {
+    console.error("[Admin] Failed to update system admin:", err);
+    setAdminState({
+      error:
+        err instanceof Error ? err.message : "Failed to update system admin",
+    });
+    return false;
+  }
+}
+
 /**
  * Select a user in the list
  */
@@ -1117,6 +1256,9 @@ export function resetAdminState(): void {
     obsTrends: null,
     obsTopRoutes: null,
     obsTopErrors: null,
+  obsTopConsumers: null,
+  obsWsDisconnects: null,
+  obsReportSla: null,
     obsLogs: [],
     obsLogsCursor: null,
     obsLogsHasMore: false,
@@ -1140,6 +1282,9 @@ export function resetAdminState(): void {
     isObsTrendsLoading: false,
     isObsTopRoutesLoading: false,
     isObsTopErrorsLoading: false,
+  isObsTopConsumersLoading: false,
+  isObsWsDisconnectsLoading: false,
+  isObsReportSlaLoading: false,
     isObsLogsLoading: false,
     isObsTracesLoading: false,
     isObsLinksLoading: false,
@@ -1527,6 +1672,54 @@ export async function loadObsTopErrors(range?: ObsTimeRange): Promise<void> {
   }
 }
 
+export async function loadObsTopConsumers(
+  range?: ObsTimeRange,
+  type?: TopConsumersType,
+  sort?: TopConsumersSort,
+): Promise<void> {
+  const r = range ?? adminState.obsTimeRange;
+  setAdminState({ isObsTopConsumersLoading: true });
+  try {
+    const consumers = await tauri.adminObsTopConsumers(r, type, sort);
+    setAdminState({
+      obsTopConsumers: consumers,
+      isObsTopConsumersLoading: false,
+    });
+  } catch (err) {
+    console.error("[Admin] Failed to load obs top consumers:", err);
+    setAdminState({ isObsTopConsumersLoading: false });
+  }
+}
+
+export async function loadObsWsDisconnects(
+  range?: ObsTimeRange,
+): Promise<void> {
+  const r = range ?? adminState.obsTimeRange;
+  setAdminState({ isObsWsDisconnectsLoading: true });
+  try {
+    const breakdown = await tauri.adminObsWsDisconnects(r);
+    setAdminState({
+      obsWsDisconnects: breakdown,
+      isObsWsDisconnectsLoading: false,
+    });
+  } catch (err) {
+    console.error("[Admin] Failed to load obs WS disconnects:", err);
+    setAdminState({ isObsWsDisconnectsLoading: false });
+  }
+}
+
+export async function loadObsReportSla(range?: ObsTimeRange): Promise<void> {
+  const r = range ?? adminState.obsTimeRange;
+  setAdminState({ isObsReportSlaLoading: true });
+  try {
+    const sla = await tauri.adminObsReportSla(r);
+    setAdminState({ obsReportSla: sla, isObsReportSlaLoading: false });
+  } catch (err) {
+    console.error("[Admin] Failed to load obs report SLA:", err);
+    setAdminState({ isObsReportSlaLoading: false });
+  }
+}
+
 export async function loadObsLogs(
   reset: boolean = false,
   level?: string,
@@ -7,6 +7,7 @@
 import { createStore } from "solid-js/store";
 import type { User } from "@/lib/types";
 import * as tauri from "@/lib/tauri";
+import { passkeyAssertion } from "@/lib/webauthn";
 import {
   initWebSocket,
   connect as wsConnect,
@@ -20,7 +21,9 @@ import {
   stopIdleDetectionCleanup,
 } from "./presence";
 import { initPreferences } from "./preferences";
-import { clearAllDrafts, cleanupDrafts } from "./drafts";
+import { clearAllDrafts, cleanupDrafts, syncDrafts } from "./drafts";
+
+const isTauri = typeof window !== "undefined" && "__TAURI__" in window;
 
 // Auth state interface
 interface AuthState {
@@ -113,6 +116,20 @@ function registerSessionExpiredListener() {
   });
 
   sessionExpiredListenerRegistered = true;
+
+  // The Tauri backend refreshes tokens itself and reports a rejected session
+  // as a Tauri event; route it through the same handler.
+  if (isTauri) {
+    void import("@tauri-apps/api/event")
+      .then(({ listen }) =>
+        listen("session-expired", () => {
+          window.dispatchEvent(new CustomEvent("kaiku:session-expired"));
+        }),
+      )
+      .catch((err) => {
+        console.error("[Auth] Failed to listen for session expiry:", err);
+      });
+  }
 }
 
 // Register immediately — the listener checks auth state internally
@@ -155,6 +172,9 @@ export async function initAuth(): Promise<void> {
               "Real-time messaging temporarily unavailable. Reconnecting...",
             );
           }),
+        syncDrafts().catch((draftErr) => {
+          console.error("[Auth] Draft sync failed:", draftErr);
+        }),
         initPreferences()
           .then(() => console.log("[Auth] Preferences initialized after session restore"))
           .catch((prefErr) => {
@@ -186,11 +206,18 @@ export async function login(
   username: string,
   password: string,
   mfaCode?: string,
+  passkey?: tauri.PasskeyAssertion,
 ): Promise<User> {
   setAuthState({ isLoading: true, error: null });
 
   try {
-    const result = await tauri.login(serverUrl, username, password, mfaCode);
+    const result = await tauri.login(
+      serverUrl,
+      username,
+      password,
+      mfaCode,
+      passkey,
+    );
     setAuthState({
       user: result.user,
       serverUrl,
@@ -216,6 +243,9 @@ export async function login(
           error: "Real-time messaging temporarily unavailable. Reconnecting...",
         });
       }),
+      syncDrafts().catch((draftErr) => {
+        console.error("[Auth] Draft sync failed:", draftErr);
+      }),
       initPreferences()
         .then(() => console.log("[Auth] Preferences initialized after login"))
         .catch((prefErr) => {
@@ -255,6 +285,7 @@ export async function register(
   password: string,
   email?: string,
   displayName?: string,
+  registrationCode?: string,
 ): Promise<User> {
   setAuthState({ isLoading: true, error: null });
 
@@ -265,6 +296,7 @@ export async function register(
       password,
       email,
       displayName,
+      registrationCode,
     );
     setAuthState({
       user: result.user,
@@ -284,6 +316,9 @@ export async function register(
       wsConnect().catch((wsErr) => {
         console.error("WebSocket connection failed:", wsErr);
       }),
+      syncDrafts().catch((draftErr) => {
+        console.error("[Auth] Draft sync failed:", draftErr);
+      }),
       initPreferences()
         .then(() => console.log("[Auth] Preferences initialized after registration"))
         .catch((prefErr) => {
@@ -297,6 +332,13 @@ export async function register(
     return result.user;
   } catch (err) {
     const error = err instanceof Error ? err.message : String(err);
+
+    // The account exists but waits for an admin; not an error to display
+    if (error === "REGISTRATION_PENDING_APPROVAL") {
+      setAuthState({ isLoading: false, error: null });
+      throw new Error("REGISTRATION_PENDING_APPROVAL");
+    }
+
     setAuthState({ isLoading: false, error });
     throw new Error(error);
   }
@@ -352,6 +394,9 @@ export async function loginWithOidc(
           error: "Real-time messaging temporarily unavailable. Reconnecting...",
         });
       }),
+      syncDrafts().catch((draftErr) => {
+        console.error("[Auth] Draft sync failed:", draftErr);
+      }),
       initPreferences().catch((prefErr) => {
         console.error("[Auth] Preferences initialization failed:", prefErr);
       }),
@@ -365,6 +410,84 @@ export async function loginWithOidc(
   }
 }
 
+/**
+ * Finish signing in through another device: claim the approved link request.
+ * Returns null while the request is still waiting for approval.
+ */
+export async function loginWithDeviceLink(
+  serverUrl: string,
+  code: string,
+  secret: string,
+): Promise<User | null> {
+  const result = await tauri.claimDeviceLink(serverUrl, code, secret);
+  if (!result) return null;
+  return completeSignIn(serverUrl, result);
+}
+
+/**
+ * Sign in with one of the account's passkeys instead of a password.
+ */
+export async function loginWithPasskey(
+  serverUrl: string,
+  username: string,
+): Promise<User> {
+  setAuthState({ isLoading: true, error: null });
+
+  try {
+    const assertion = await passkeyAssertion(serverUrl, username);
+    const result = await tauri.finishPasskeyLogin(serverUrl, assertion);
+    return await completeSignIn(serverUrl, result);
+  } catch (err) {
+    const error = err instanceof Error ? err.message : String(err);
+    setAuthState({ isLoading: false, error });
+    throw new Error(error);
+  }
+}
+
+/**
+ * Take over a session issued outside the password login and start the
+ * same services a login does.
+ */
+async function completeSignIn(
+  serverUrl: string,
+  result: tauri.AuthResult,
+): Promise<User> {
+  setAuthState({
+    user: result.user,
+    serverUrl,
+    isLoading: false,
+    isInitialized: true,
+    error: null,
+    setupRequired: result.setup_required,
+    mfaRequired: false,
+    sessionExpired: false,
+  });
+
+  // Initialize WebSocket listeners and presence in parallel (independent)
+  await Promise.all([initWebSocket(), initPresence()]);
+  registerWebSocketReconnectListener();
+
+  // Connect WebSocket and sync preferences in parallel (independent)
+  await Promise.all([
+    wsConnect().catch((wsErr) => {
+      console.error("WebSocket connection failed:", wsErr);
+      setAuthState({
+        error: "Real-time messaging temporarily unavailable. Reconnecting...",
+      });
+    }),
+    syncDrafts().catch((draftErr) => {
+      console.error("[Auth] Draft sync failed:", draftErr);
+    }),
+    initPreferences().catch((prefErr) => {
+      console.error("[Auth] Preferences initialization failed:", prefErr);
+    }),
+  ]);
+
+  initIdleDetection();
+
+  return result.user;
+}
+
 /**
  * Logout and clear session.
  */
@@ -7,7 +7,7 @@
  */
 
 import { createStore, reconcile } from "solid-js/store";
-import type { ChannelCategory } from "@/lib/types";
+import type { CategoryPlacement, ChannelCategory } from "@/lib/types";
 import * as tauri from "@/lib/tauri";
 import { showToast } from "@/components/ui/Toast";
 
@@ -338,6 +338,29 @@ export async function reorderCategories(
   }
 }
 
+/**
+ * Apply category moves from a bulk reorder (channel_positions_update).
+ */
+export function handleCategoryPositionsUpdateEvent(
+  guildId: string,
+  placements: CategoryPlacement[],
+): void {
+  const categories = categoriesState.categories[guildId];
+  if (!categories || placements.length === 0) return;
+
+  const byId = new Map(placements.map((p) => [p.id, p]));
+  setCategoriesState(
+    "categories",
+    guildId,
+    categories.map((cat) => {
+      const placement = byId.get(cat.id);
+      return placement
+        ? { ...cat, position: placement.position, parent_id: placement.parent_id }
+        : cat;
+    }),
+  );
+}
+
 /**
  * Check if a category is a subcategory (has a parent).
  */
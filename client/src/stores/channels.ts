@@ -5,7 +5,8 @@
  */
 
 import { createStore } from "solid-js/store";
-import type { ChannelWithUnread } from "@/lib/types";
+import type { ChannelPlacement, ChannelWithUnread } from "@/lib/types";
+import { isVoiceChannelType } from "@/lib/utils";
 import * as tauri from "@/lib/tauri";
 import { subscribeChannel, waitForConnection } from "@/stores/websocket";
 import { showToast } from "@/components/ui/Toast";
@@ -38,7 +39,7 @@ export const textChannels = () =>
 
 export const voiceChannels = () =>
   channelsState.channels
-    .filter((c) => c.channel_type === "voice")
+    .filter((c) => isVoiceChannelType(c.channel_type))
     .sort((a, b) => a.position - b.position);
 
 // Actions
@@ -82,7 +83,10 @@ export async function loadChannels(): Promise<void> {
  * Load channels for a specific guild.
  * This replaces the current channel list with the guild's channels.
  */
-export async function loadChannelsForGuild(guildId: string): Promise<void> {
+export async function loadChannelsForGuild(
+  guildId: string,
+  landingChannelId?: string | null,
+): Promise<void> {
   setChannelsState({ isLoading: true, error: null });
 
   try {
@@ -93,9 +97,13 @@ export async function loadChannelsForGuild(guildId: string): Promise<void> {
       error: null,
     });
 
-    // Auto-select first text channel in this guild
+    // Auto-select the landing channel, or the first text channel in this guild
     if (channels.length > 0) {
-      const firstText = channels.find((c) => c.channel_type === "text");
+      const landing = channels.find(
+        (c) => c.id === landingChannelId && c.channel_type === "text",
+      );
+      const firstText =
+        landing ?? channels.find((c) => c.channel_type === "text");
       if (firstText) {
         setChannelsState({ selectedChannelId: firstText.id });
       } else {
@@ -196,6 +204,15 @@ export function selectChannel(channelId: string): void {
   setChannelsState({ selectedChannelId: channelId });
 }
 
+/**
+ * Open a voice channel's text chat in the message view, subscribing to it
+ * like a text channel.
+ */
+export async function openVoiceChat(channelId: string): Promise<void> {
+  await subscribeChannel(channelId);
+  selectChannel(channelId);
+}
+
 /**
  * Clear channel selection.
  */
@@ -315,7 +332,7 @@ export function handleChannelReadEvent(channelId: string): void {
  */
 export async function createChannel(
   name: string,
-  channelType: "text" | "voice",
+  channelType: "text" | "voice" | "stage",
   guildId?: string,
   topic?: string,
   categoryId?: string,
@@ -489,5 +506,26 @@ export async function moveChannelToCategory(
   }
 }
 
+/**
+ * Apply channel moves from a bulk reorder (channel_positions_update).
+ */
+export function handleChannelPositionsUpdateEvent(
+  placements: ChannelPlacement[],
+): void {
+  const byId = new Map(placements.map((p) => [p.id, p]));
+  setChannelsState("channels", (channels) =>
+    channels.map((c) => {
+      const placement = byId.get(c.id);
+      return placement
+        ? {
+            ...c,
+            position: placement.position,
+            category_id: placement.category_id,
+          }
+        : c;
+    }),
+  );
+}
+
 // Export the store for reading
 export { channelsState, setChannelsState };
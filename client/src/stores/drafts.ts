@@ -6,6 +6,8 @@
  * - Debounced save (300ms) with beforeunload flush
  * - LRU eviction at 50 drafts
  * - Skips E2EE channels (no plaintext leak)
+ * - Syncs with the server so drafts follow the user across devices; the
+ *   most recent edit wins
  */
 
 import { createSignal } from "solid-js";
@@ -17,6 +19,9 @@ import { createSignal } from "solid-js";
 const STORAGE_KEY = "vc:drafts";
 const DEBOUNCE_MS = 300;
 const MAX_DRAFTS = 50;
+const SYNC_DEBOUNCE_MS = 1500;
+
+const isTauri = typeof window !== "undefined" && "__TAURI__" in window;
 
 // ============================================================================
 // Types
@@ -29,6 +34,13 @@ interface DraftEntry {
 
 type DraftsMap = Record<string, DraftEntry>;
 
+/** Draft as stored on the server (empty content = cleared). */
+export interface ServerDraft {
+  channel_id: string;
+  content: string;
+  updated_at: string;
+}
+
 // ============================================================================
 // Signals
 // ============================================================================
@@ -42,6 +54,16 @@ const [drafts, setDrafts] = createSignal<DraftsMap>({});
 let saveTimer: ReturnType<typeof setTimeout> | null = null;
 let isDirty = false;
 
+// ============================================================================
+// Server Sync State
+// ============================================================================
+
+/** Edits waiting to be pushed, keyed by channel (empty content = cleared). */
+const pendingSync = new Map<string, DraftEntry>();
+/** When drafts were cleared locally, so older server copies are ignored. */
+const clearedAt = new Map<string, number>();
+let syncTimer: ReturnType<typeof setTimeout> | null = null;
+
 // ============================================================================
 // localStorage Functions
 // ============================================================================
@@ -128,6 +150,153 @@ function evictOldest(data: DraftsMap): DraftsMap {
   return Object.fromEntries(kept);
 }
 
+// ============================================================================
+// Server Sync
+// ============================================================================
+
+/**
+ * Fetch drafts from server.
+ * Uses Tauri invoke when available, falls back to HTTP API.
+ */
+async function fetchServerDrafts(): Promise<ServerDraft[]> {
+  if (isTauri) {
+    const { invoke } = await import("@tauri-apps/api/core");
+    const response = await invoke<{ drafts: ServerDraft[] }>("fetch_drafts");
+    return response.drafts;
+  }
+
+  const { fetchApi } = await import("@/lib/tauri");
+  const response = await fetchApi<{ drafts: ServerDraft[] }>("/api/me/drafts");
+  return response.drafts;
+}
+
+/**
+ * Push one draft to the server.
+ * Returns the draft the server keeps (a newer one if this edit lost).
+ */
+async function pushDraft(
+  channelId: string,
+  entry: DraftEntry,
+): Promise<ServerDraft> {
+  const updatedAt = new Date(entry.updatedAt).toISOString();
+
+  if (isTauri) {
+    const { invoke } = await import("@tauri-apps/api/core");
+    return invoke<ServerDraft>("save_draft", {
+      channelId,
+      content: entry.content,
+      updatedAt,
+    });
+  }
+
+  const { fetchApi } = await import("@/lib/tauri");
+  return fetchApi<ServerDraft>(`/api/me/drafts/${channelId}`, {
+    method: "PUT",
+    body: { content: entry.content, updated_at: updatedAt },
+  });
+}
+
+/**
+ * Apply a draft from the server if it is newer than the local state.
+ */
+function applyServerDraft(draft: ServerDraft): void {
+  const serverTime = Date.parse(draft.updated_at);
+  const local = drafts()[draft.channel_id];
+  const pending = pendingSync.get(draft.channel_id);
+
+  const localTime = Math.max(
+    local?.updatedAt ?? 0,
+    pending?.updatedAt ?? 0,
+    clearedAt.get(draft.channel_id) ?? 0,
+  );
+  if (serverTime <= localTime) return;
+
+  setDrafts((prev) => {
+    const updated = { ...prev };
+    if (draft.content.trim()) {
+      updated[draft.channel_id] = {
+        content: draft.content,
+        updatedAt: serverTime,
+      };
+      return evictOldest(updated);
+    }
+    delete updated[draft.channel_id];
+    return updated;
+  });
+  if (!draft.content.trim()) {
+    clearedAt.set(draft.channel_id, serverTime);
+  }
+
+  isDirty = true;
+  flushSave();
+}
+
+/**
+ * Push all queued edits to the server.
+ */
+async function flushSync(): Promise<void> {
+  if (syncTimer) {
+    clearTimeout(syncTimer);
+    syncTimer = null;
+  }
+
+  const batch = [...pendingSync.entries()];
+  pendingSync.clear();
+
+  await Promise.all(
+    batch.map(async ([channelId, entry]) => {
+      try {
+        applyServerDraft(await pushDraft(channelId, entry));
+      } catch (e) {
+        // Drafts stay local; the next edit or login sync retries
+        console.warn("[Drafts] Failed to sync draft:", channelId, e);
+      }
+    }),
+  );
+}
+
+/**
+ * Queue a draft edit for the server (debounced).
+ */
+function queueSync(channelId: string, entry: DraftEntry): void {
+  pendingSync.set(channelId, entry);
+
+  if (syncTimer) clearTimeout(syncTimer);
+  syncTimer = setTimeout(() => {
+    syncTimer = null;
+    void flushSync();
+  }, SYNC_DEBOUNCE_MS);
+}
+
+/**
+ * Reconcile local drafts with the server (called after login).
+ * Newer server drafts replace local ones; local drafts the server has
+ * not seen (e.g. typed offline) are pushed.
+ */
+export async function syncDrafts(): Promise<void> {
+  const serverDrafts = await fetchServerDrafts();
+  const serverTimes = new Map<string, number>();
+
+  for (const draft of serverDrafts) {
+    serverTimes.set(draft.channel_id, Date.parse(draft.updated_at));
+    applyServerDraft(draft);
+  }
+
+  for (const [channelId, entry] of Object.entries(drafts())) {
+    if (entry.updatedAt > (serverTimes.get(channelId) ?? 0)) {
+      pendingSync.set(channelId, entry);
+    }
+  }
+  await flushSync();
+}
+
+/**
+ * Handle WebSocket draft_update event from another device.
+ */
+export function handleDraftUpdate(event: ServerDraft): void {
+  applyServerDraft(event);
+}
+
 // ============================================================================
 // Initialization
 // ============================================================================
@@ -176,6 +345,7 @@ export function saveDraft(
   if (isE2EE) return;
 
   const trimmed = content.trim();
+  const updatedAt = Date.now();
 
   setDrafts((prev) => {
     let updated = { ...prev };
@@ -187,7 +357,7 @@ export function saveDraft(
       // Update or add draft
       updated[channelId] = {
         content,
-        updatedAt: Date.now(),
+        updatedAt,
       };
 
       // Apply LRU eviction
@@ -198,6 +368,10 @@ export function saveDraft(
   });
 
   isDirty = true;
+  if (!trimmed) {
+    clearedAt.set(channelId, updatedAt);
+  }
+  queueSync(channelId, { content: trimmed ? content : "", updatedAt });
 
   // Debounced save
   if (saveTimer) clearTimeout(saveTimer);
@@ -211,6 +385,9 @@ export function saveDraft(
  * Clear draft for a specific channel.
  */
 export function clearDraft(channelId: string): void {
+  const hadDraft = channelId in drafts() || pendingSync.has(channelId);
+  const updatedAt = Date.now();
+
   setDrafts((prev) => {
     const updated = { ...prev };
     delete updated[channelId];
@@ -218,6 +395,11 @@ export function clearDraft(channelId: string): void {
   });
 
   isDirty = true;
+  clearedAt.set(channelId, updatedAt);
+  // Only drafts that may have reached the server need clearing there
+  if (hadDraft) {
+    queueSync(channelId, { content: "", updatedAt });
+  }
 
   // Immediate save for clear (user sent message)
   flushSave();
@@ -227,6 +409,9 @@ export function clearDraft(channelId: string): void {
  * Clear all drafts (called on logout).
  */
 export function clearAllDrafts(): void {
+  // Drafts stay on the server for the next login; just drop local state
+  pendingSync.clear();
+  clearedAt.clear();
   setDrafts({});
   isDirty = true;
   flushSave();
@@ -245,6 +430,10 @@ export function cleanupDrafts(): void {
     clearTimeout(saveTimer);
     saveTimer = null;
   }
+  if (syncTimer) {
+    clearTimeout(syncTimer);
+    syncTimer = null;
+  }
 }
 
 // ============================================================================
@@ -5,7 +5,7 @@
  */
 
 import { createStore } from "solid-js/store";
-import type { Friend } from "@/lib/types";
+import type { Friend, Relationship } from "@/lib/types";
 import * as tauri from "@/lib/tauri";
 import { showToast } from "@/components/ui/Toast";
 
@@ -266,6 +266,41 @@ export function handleUserUnblocked(userId: string): void {
   });
 }
 
+/**
+ * Handle RelationshipUpdate event from WebSocket
+ */
+export function handleRelationshipUpdate(
+  userId: string,
+  relationship: Relationship | null,
+): void {
+  switch (relationship) {
+    case "friend":
+      setFriendsState({
+        pendingRequests: friendsState.pendingRequests.filter(
+          (f) => f.user_id !== userId,
+        ),
+      });
+      loadFriends();
+      break;
+    case "incoming_request":
+    case "outgoing_request":
+      loadPendingRequests();
+      break;
+    case "blocked":
+      // Handled by UserBlocked
+      break;
+    case null:
+      setFriendsState({
+        friends: friendsState.friends.filter((f) => f.user_id !== userId),
+        pendingRequests: friendsState.pendingRequests.filter(
+          (f) => f.user_id !== userId,
+        ),
+        blocked: friendsState.blocked.filter((f) => f.user_id !== userId),
+      });
+      break;
+  }
+}
+
 /**
  * Get online friends
  */
@@ -5,9 +5,16 @@
  */
 
 import { createStore } from "solid-js/store";
-import type { Guild, GuildMember, GuildInvite, Channel } from "@/lib/types";
+import type {
+  Guild,
+  GuildMember,
+  GuildInvite,
+  GuildPerks,
+  Channel,
+} from "@/lib/types";
 import * as tauri from "@/lib/tauri";
 import { showToast } from "@/components/ui/Toast";
+import { serverNow } from "@/lib/serverClock";
 
 /**
  * Guild store state
@@ -19,7 +26,9 @@ interface GuildStoreState {
   activeGuildId: string | null;
   // Members of the active guild
   members: Record<string, GuildMember[]>;
-  // Invites for guilds (owner only)
+  // Next member list chunk cursor per guild (null once the last chunk arrived)
+  memberCursors: Record<string, string | null>;
+  // Invites for guilds (members with MANAGE_INVITES)
   invites: Record<string, GuildInvite[]>;
   // Channels of the active guild
   guildChannels: Record<string, Channel[]>;
@@ -27,6 +36,8 @@ interface GuildStoreState {
   guildUnreadCounts: Record<string, number>;
   // Map channel IDs to guild IDs (for routing WebSocket events)
   channelGuildMap: Record<string, string>;
+  // Supporter perks per guild
+  guildPerks: Record<string, GuildPerks>;
   // Loading states
   isLoading: boolean;
   isMembersLoading: boolean;
@@ -43,10 +54,12 @@ const [guildsState, setGuildsState] = createStore<GuildStoreState>({
   guilds: [],
   activeGuildId: null,
   members: {},
+  memberCursors: {},
   invites: {},
   guildChannels: {},
   guildUnreadCounts: {},
   channelGuildMap: {},
+  guildPerks: {},
   isLoading: false,
   isMembersLoading: false,
   isInvitesLoading: false,
@@ -119,7 +132,10 @@ export async function loadGuildChannels(guildId: string): Promise<void> {
  * Select/activate a guild
  * This will trigger channel list reload scoped to guild
  */
-export async function selectGuild(guildId: string): Promise<void> {
+export async function selectGuild(
+  guildId: string,
+  options: { justJoined?: boolean } = {},
+): Promise<void> {
   const previousGuildId = guildsState.activeGuildId;
   setGuildsState({ activeGuildId: guildId });
   // Clear guild-level unread badge when entering the guild
@@ -127,7 +143,11 @@ export async function selectGuild(guildId: string): Promise<void> {
 
   // Load channels for this guild (this will update the channels store)
   const { loadChannelsForGuild } = await import("./channels");
-  await loadChannelsForGuild(guildId);
+  const guild = guildsState.guilds.find((g) => g.id === guildId);
+  await loadChannelsForGuild(
+    guildId,
+    guild ? landingChannelId(guild, options.justJoined ?? false) : null,
+  );
 
   // Load guild members
   await loadGuildMembers(guildId);
@@ -150,6 +170,68 @@ export async function selectGuild(guildId: string): Promise<void> {
   }
 }
 
+/**
+ * Channel to open when entering a guild: its rules channel right after
+ * joining, otherwise its system channel. `null` falls back to the first text
+ * channel.
+ */
+export function landingChannelId(
+  guild: Guild,
+  justJoined: boolean,
+): string | null {
+  if (justJoined && guild.rules_channel_id) {
+    return guild.rules_channel_id;
+  }
+  return guild.system_channel_id;
+}
+
+/**
+ * Designate the guild's system and rules channels.
+ */
+export async function updateGuildChannels(
+  guildId: string,
+  channels: Parameters<typeof tauri.updateGuildChannels>[1],
+): Promise<Guild> {
+  const updated = await tauri.updateGuildChannels(guildId, channels);
+  setGuildsState("guilds", (g) => g.id === guildId, updated);
+  return updated;
+}
+
+/**
+ * Upload a new guild icon or banner.
+ */
+export async function uploadGuildImage(
+  guildId: string,
+  kind: "icon" | "banner",
+  file: File,
+): Promise<Guild> {
+  const updated = await tauri.uploadGuildImage(guildId, kind, file);
+  setGuildsState("guilds", (g) => g.id === guildId, updated);
+  return updated;
+}
+
+/**
+ * Remove the guild icon or banner.
+ */
+export async function removeGuildImage(
+  guildId: string,
+  kind: "icon" | "banner",
+): Promise<Guild> {
+  const updated = await tauri.removeGuildImage(guildId, kind);
+  setGuildsState("guilds", (g) => g.id === guildId, updated);
+  return updated;
+}
+
+/**
+ * Apply a guild update received over WebSocket.
+ */
+export function handleGuildUpdate(guild: Guild): void {
+  setGuildsState("guilds", (g) => g.id === guild.id, (prev) => ({
+    ...prev,
+    ...guild,
+  }));
+}
+
 /**
  * Select "Home" view (no guild selected)
  * This shows DMs, mentions, and cross-server activity
@@ -217,6 +299,7 @@ export async function createGuild(
   description?: string,
 ): Promise<Guild> {
   const guild = await tauri.createGuild(name, description);
+  await tauri.invalidateGuildCache(guild.id);
   setGuildsState("guilds", (prev) => [...prev, guild]);
   return guild;
 }
@@ -228,9 +311,8 @@ export async function updateGuild(
   guildId: string,
   name?: string,
   description?: string,
-  icon_url?: string,
 ): Promise<Guild> {
-  const updated = await tauri.updateGuild(guildId, name, description, icon_url);
+  const updated = await tauri.updateGuild(guildId, name, description);
   setGuildsState("guilds", (g) => g.id === guildId, updated);
   return updated;
 }
@@ -240,6 +322,7 @@ export async function updateGuild(
  */
 export async function deleteGuild(guildId: string): Promise<void> {
   await tauri.deleteGuild(guildId);
+  await tauri.invalidateGuildCache(guildId);
   setGuildsState("guilds", (prev) => prev.filter((g) => g.id !== guildId));
 
   // If the deleted guild was active, select home
@@ -256,6 +339,7 @@ export async function joinGuild(
   inviteCode: string,
 ): Promise<void> {
   await tauri.joinGuild(guildId, inviteCode);
+  await tauri.invalidateGuildCache(guildId);
   await loadGuilds(); // Reload guilds to include the newly joined one
 }
 
@@ -264,6 +348,7 @@ export async function joinGuild(
  */
 export async function leaveGuild(guildId: string): Promise<void> {
   await tauri.leaveGuild(guildId);
+  await tauri.invalidateGuildCache(guildId);
   setGuildsState("guilds", (prev) => prev.filter((g) => g.id !== guildId));
 
   // If the left guild was active, select home
@@ -277,7 +362,7 @@ export async function leaveGuild(guildId: string): Promise<void> {
 // ============================================================================
 
 /**
- * Load invites for a guild (owner only)
+ * Load invites for a guild (requires MANAGE_INVITES)
  */
 export async function loadGuildInvites(guildId: string): Promise<void> {
   setGuildsState({ isInvitesLoading: true });
@@ -298,8 +383,9 @@ export async function loadGuildInvites(guildId: string): Promise<void> {
 export async function createInvite(
   guildId: string,
   expiresIn: tauri.InviteExpiry = "7d",
+  maxUses: number | null = null,
 ): Promise<tauri.GuildInvite> {
-  const invite = await tauri.createGuildInvite(guildId, expiresIn);
+  const invite = await tauri.createGuildInvite(guildId, expiresIn, maxUses);
   setGuildsState("invites", guildId, (prev) => [invite, ...(prev || [])]);
   return invite;
 }
@@ -323,11 +409,12 @@ export async function deleteInvite(
 export async function joinViaInviteCode(code: string): Promise<void> {
   // Join first — if this fails, the error is genuine
   const response = await tauri.joinViaInvite(code);
+  await tauri.invalidateGuildCache(response.guild_id);
 
   // Post-join UI setup — join already succeeded at this point
   try {
     await loadGuilds();
-    await selectGuild(response.guild_id);
+    await selectGuild(response.guild_id, { justJoined: true });
   } catch (err) {
     console.error("Post-join UI setup failed (join succeeded):", err);
     showToast({
@@ -340,6 +427,37 @@ export async function joinViaInviteCode(code: string): Promise<void> {
   }
 }
 
+/**
+ * Request the next chunk of a guild's member list over the WebSocket.
+ * Chunks arrive as `guild_member_chunk` events (see `handleGuildMemberChunk`).
+ */
+export async function requestGuildMemberChunk(
+  guildId: string,
+  options: tauri.GuildMemberChunkOptions = {},
+): Promise<void> {
+  const after =
+    options.after ?? guildsState.memberCursors[guildId] ?? undefined;
+  await tauri.wsRequestGuildMembers(guildId, { ...options, after });
+}
+
+/**
+ * Merge a member list chunk into the loaded members (called from WebSocket handler).
+ */
+export function handleGuildMemberChunk(
+  guildId: string,
+  members: GuildMember[],
+  nextCursor: string | null,
+): void {
+  setGuildsState("members", guildId, (prev) => {
+    const merged = new Map((prev || []).map((m) => [m.user_id, m]));
+    for (const member of members) {
+      merged.set(member.user_id, member);
+    }
+    return [...merged.values()];
+  });
+  setGuildsState("memberCursors", guildId, nextCursor);
+}
+
 /**
  * Kick a member from a guild
  */
@@ -353,6 +471,29 @@ export async function kickMember(
   );
 }
 
+/**
+ * Update a member's timeout (called from WebSocket handler).
+ */
+export function setMemberTimeout(
+  guildId: string,
+  userId: string,
+  timeoutUntil: string | null,
+): void {
+  setGuildsState("members", guildId, (prev) =>
+    (prev || []).map((m) =>
+      m.user_id === userId ? { ...m, timeout_until: timeoutUntil } : m,
+    ),
+  );
+}
+
+/**
+ * Check if a member's timeout is still running.
+ */
+export function isMemberTimedOut(member: GuildMember): boolean {
+  if (!member.timeout_until) return false;
+  return new Date(member.timeout_until).getTime() > serverNow();
+}
+
 /**
  * Get invites for a guild
  */
@@ -419,6 +560,32 @@ export function patchGuild(
   }
 }
 
+/**
+ * Load supporter perks for a guild.
+ */
+export async function loadGuildPerks(guildId: string): Promise<void> {
+  try {
+    const perks = await tauri.getGuildPerks(guildId);
+    setGuildsState("guildPerks", guildId, perks);
+  } catch (err) {
+    console.error(`Failed to load perks for guild ${guildId}:`, err);
+  }
+}
+
+/**
+ * Get cached supporter perks for a guild.
+ */
+export function getGuildPerks(guildId: string): GuildPerks | undefined {
+  return guildsState.guildPerks[guildId];
+}
+
+/**
+ * Store updated perks (called from WebSocket handler).
+ */
+export function setGuildPerks(guildId: string, perks: GuildPerks): void {
+  setGuildsState("guildPerks", guildId, perks);
+}
+
 /**
  * Check if threads are enabled for a guild.
  */
@@ -49,6 +49,8 @@ export function patchMember(
     "display_name",
     "avatar_url",
     "nickname",
+    "guild_avatar_url",
+    "pending",
     "status",
     "last_seen_at",
   ];
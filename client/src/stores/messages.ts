@@ -7,7 +7,7 @@
 
 import { createSignal } from "solid-js";
 import { createStore } from "solid-js/store";
-import type { Message, ClaimedPrekeyInput, DMListItem, E2EEContent, MegolmE2EEContent } from "@/lib/types";
+import type { Attachment, LinkEmbed, Message, ClaimedPrekeyInput, DMListItem, E2EEContent, MegolmE2EEContent } from "@/lib/types";
 import * as tauri from "@/lib/tauri";
 import { e2eeStore } from "@/stores/e2ee";
 import { showToast } from "@/components/ui/Toast";
@@ -406,6 +406,38 @@ export function removeMessage(channelId: string, messageId: string): void {
   }
 }
 
+/**
+ * Replace an attachment once server-side image processing has finished.
+ */
+export function updateAttachment(
+  channelId: string,
+  messageId: string,
+  attachment: Attachment,
+): void {
+  const messages = messagesState.byChannel[channelId];
+  if (!messages) return;
+  const index = messages.findIndex((m) => m.id === messageId);
+  if (index === -1) return;
+  setMessagesState("byChannel", channelId, index, "attachments", (attachments) =>
+    attachments.map((a) => (a.id === attachment.id ? attachment : a)),
+  );
+}
+
+/**
+ * Replace a message's link previews once the server has unfurled its URLs.
+ */
+export function updateMessageEmbeds(
+  channelId: string,
+  messageId: string,
+  embeds: LinkEmbed[],
+): void {
+  const messages = messagesState.byChannel[channelId];
+  if (!messages) return;
+  const index = messages.findIndex((m) => m.id === messageId);
+  if (index === -1) return;
+  setMessagesState("byChannel", channelId, index, "embeds", embeds);
+}
+
 /**
  * Get messages for a channel.
  */
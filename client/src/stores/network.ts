@@ -0,0 +1,75 @@
+/**
+ * Network Store
+ *
+ * Tracks whether the server is reachable. The desktop app follows the
+ * `network-state` events from the Tauri connectivity watcher; browser mode
+ * follows the window's online/offline events.
+ */
+
+import { createSignal } from "solid-js";
+import { getNetworkState } from "@/lib/tauri";
+import type { NetworkState } from "@/lib/types";
+
+const isTauri = typeof window !== "undefined" && "__TAURI__" in window;
+
+type UnlistenFn = () => void;
+
+// ============================================================================
+// State
+// ============================================================================
+
+const [online, setOnline] = createSignal(true);
+
+let unlisten: UnlistenFn | null = null;
+
+/**
+ * Whether the server is currently reachable.
+ */
+export const isOnline = online;
+
+// ============================================================================
+// Lifecycle
+// ============================================================================
+
+function handleBrowserOnline(): void {
+  setOnline(true);
+}
+
+function handleBrowserOffline(): void {
+  setOnline(false);
+}
+
+/**
+ * Start following network state changes. Safe to call more than once.
+ */
+export async function initNetworkState(): Promise<void> {
+  if (unlisten) return;
+
+  if (isTauri) {
+    const { listen } = await import("@tauri-apps/api/event");
+    unlisten = await listen<NetworkState>("network-state", (event) => {
+      setOnline(event.payload.online);
+    });
+  } else {
+    window.addEventListener("online", handleBrowserOnline);
+    window.addEventListener("offline", handleBrowserOffline);
+    unlisten = () => {
+      window.removeEventListener("online", handleBrowserOnline);
+      window.removeEventListener("offline", handleBrowserOffline);
+    };
+  }
+
+  try {
+    setOnline((await getNetworkState()).online);
+  } catch (e) {
+    console.warn("[Network] Failed to get initial network state:", e);
+  }
+}
+
+/**
+ * Stop following network state changes.
+ */
+export function cleanupNetworkState(): void {
+  unlisten?.();
+  unlisten = null;
+}
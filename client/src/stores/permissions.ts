@@ -92,6 +92,17 @@ export function getEveryoneRole(guildId: string): GuildRole | undefined {
   return getGuildRoles(guildId).find((r) => r.is_default);
 }
 
+/**
+ * Insert or replace a role in the local store, keeping position order.
+ */
+function upsertRole(guildId: string, role: GuildRole): void {
+  setPermissionsState("roles", guildId, (prev) => {
+    const roles = (prev || []).filter((r) => r.id !== role.id);
+    roles.push(role);
+    return roles.sort((a, b) => a.position - b.position);
+  });
+}
+
 /**
  * Create a new role
  */
@@ -100,10 +111,7 @@ export async function createRole(
   request: CreateRoleRequest,
 ): Promise<GuildRole> {
   const role = await tauri.createGuildRole(guildId, request);
-  setPermissionsState("roles", guildId, (prev) => {
-    const roles = [...(prev || []), role];
-    return roles.sort((a, b) => a.position - b.position);
-  });
+  upsertRole(guildId, role);
   return role;
 }
 
@@ -116,10 +124,7 @@ export async function updateRole(
   request: UpdateRoleRequest,
 ): Promise<GuildRole> {
   const updated = await tauri.updateGuildRole(guildId, roleId, request);
-  setPermissionsState("roles", guildId, (prev) => {
-    const roles = (prev || []).map((r) => (r.id === roleId ? updated : r));
-    return roles.sort((a, b) => a.position - b.position);
-  });
+  upsertRole(guildId, updated);
   return updated;
 }
 
@@ -131,60 +136,50 @@ export async function deleteRole(
   roleId: string,
 ): Promise<void> {
   await tauri.deleteGuildRole(guildId, roleId);
-  setPermissionsState("roles", guildId, (prev) =>
-    (prev || []).filter((r) => r.id !== roleId),
-  );
+  handleRoleDeleteEvent(guildId, roleId);
 }
 
 /**
- * Reorder a role to a new position.
- * Moves the role and adjusts other roles' positions accordingly.
+ * Reorder a role to the position currently held by another role.
+ * The server keeps the existing position slots and hands them out in the
+ * new order, so the same is done here for the optimistic update.
  */
 export async function reorderRole(
   guildId: string,
   roleId: string,
   newPosition: number,
 ): Promise<void> {
-  // Optimistically update the UI first
   const roles = getGuildRoles(guildId);
-  const roleIndex = roles.findIndex((r) => r.id === roleId);
-  if (roleIndex === -1) return;
-
-  const role = roles[roleIndex];
-  const oldPosition = role.position;
+  const role = roles.find((r) => r.id === roleId);
 
   // Can't reorder the @everyone role (always at the bottom)
-  if (role.is_default) return;
-
-  // Create new array with updated positions
-  const updatedRoles = roles.map((r) => {
-    if (r.id === roleId) {
-      return { ...r, position: newPosition };
-    }
-    // Shift roles between old and new position
-    if (oldPosition < newPosition) {
-      // Moving down: shift roles in between up
-      if (r.position > oldPosition && r.position <= newPosition) {
-        return { ...r, position: r.position - 1 };
-      }
-    } else {
-      // Moving up: shift roles in between down
-      if (r.position >= newPosition && r.position < oldPosition) {
-        return { ...r, position: r.position + 1 };
-      }
-    }
-    return r;
-  });
+  if (!role || role.is_default) return;
 
-  // Sort by position
-  updatedRoles.sort((a, b) => a.position - b.position);
+  const ranked = roles.filter((r) => !r.is_default);
+  const slots = ranked.map((r) => r.position);
+  const ordered = ranked.filter((r) => r.id !== roleId);
+  const targetIndex = ranked.findIndex((r) => r.position >= newPosition);
+  ordered.splice(targetIndex === -1 ? ordered.length : targetIndex, 0, role);
 
   // Update local state optimistically
-  setPermissionsState("roles", guildId, updatedRoles);
+  const positions = new Map(ordered.map((r, i) => [r.id, slots[i]]));
+  setPermissionsState(
+    "roles",
+    guildId,
+    roles
+      .map((r) => {
+        const position = positions.get(r.id);
+        return position === undefined ? r : { ...r, position };
+      })
+      .sort((a, b) => a.position - b.position),
+  );
 
   try {
-    // Send update to server
-    await tauri.updateGuildRole(guildId, roleId, { position: newPosition });
+    const updated = await tauri.reorderGuildRoles(
+      guildId,
+      ordered.map((r) => r.id),
+    );
+    handleRolesReorderEvent(guildId, updated);
   } catch (err) {
     console.error("[Permissions] Failed to reorder role:", err);
     // Revert on failure by reloading
@@ -193,6 +188,64 @@ export async function reorderRole(
   }
 }
 
+// ============================================================================
+// Role WebSocket Event Handlers
+// ============================================================================
+
+/**
+ * Handle a created or updated role (role_create / role_update).
+ */
+export function handleRoleUpdateEvent(guildId: string, role: GuildRole): void {
+  if (!permissionsState.roles[guildId]) return;
+  upsertRole(guildId, role);
+}
+
+/**
+ * Handle a deleted role (role_delete).
+ */
+export function handleRoleDeleteEvent(guildId: string, roleId: string): void {
+  if (permissionsState.roles[guildId]) {
+    setPermissionsState("roles", guildId, (prev) =>
+      (prev || []).filter((r) => r.id !== roleId),
+    );
+  }
+
+  const assignments = permissionsState.memberRoles[guildId];
+  if (assignments) {
+    for (const userId of Object.keys(assignments)) {
+      setPermissionsState("memberRoles", guildId, userId, (prev) =>
+        (prev || []).filter((id) => id !== roleId),
+      );
+    }
+  }
+}
+
+/**
+ * Handle a role reorder (roles_reorder).
+ */
+export function handleRolesReorderEvent(
+  guildId: string,
+  roles: GuildRole[],
+): void {
+  setPermissionsState(
+    "roles",
+    guildId,
+    [...roles].sort((a, b) => a.position - b.position),
+  );
+}
+
+/**
+ * Handle a member's role assignments changing (member_roles_update).
+ */
+export function handleMemberRolesUpdateEvent(
+  guildId: string,
+  userId: string,
+  roleIds: string[],
+): void {
+  if (!permissionsState.memberRoles[guildId]) return;
+  setPermissionsState("memberRoles", guildId, userId, roleIds);
+}
+
 // ============================================================================
 // Member Role Functions
 // ============================================================================
@@ -2,8 +2,10 @@
  * Preferences Store
  *
  * Manages user preferences with cross-device sync via server and localStorage fallback.
- * Preferences include theme, sound settings, quiet hours, connection display, and
- * per-channel notification levels.
+ * Preferences include theme, sound settings, quiet hours, connection display,
+ * per-channel notification levels, and desktop notification rules. In the
+ * desktop app, notification rules are also pushed to the Rust backend, which
+ * raises native notifications.
  */
 
 import { createSignal } from "solid-js";
@@ -13,8 +15,13 @@ import type {
   StoredPreferences,
   FocusMode,
   FocusPreferences,
+  DesktopNotificationPreferences,
+} from "@/lib/types";
+import {
+  DEFAULT_DESKTOP_NOTIFICATION_PREFERENCES,
+  DEFAULT_DISPLAY_PREFERENCES,
+  THEME_NAMES,
 } from "@/lib/types";
-import { DEFAULT_DISPLAY_PREFERENCES, THEME_NAMES } from "@/lib/types";
 
 // ============================================================================
 // Constants
@@ -95,6 +102,9 @@ export const DEFAULT_PREFERENCES: UserPreferences = {
     show_notifications: true,
   },
   channel_notifications: {},
+  highlight_keywords: [],
+  voice_user_gains: {},
+  desktop_notifications: DEFAULT_DESKTOP_NOTIFICATION_PREFERENCES,
   home_sidebar: {
     collapsed: {
       unread: false,
@@ -170,13 +180,47 @@ function getInitialPreferencesState(): {
 
 const initialPreferencesState = getInitialPreferencesState();
 
-const [preferences, setPreferences] =
+const [preferences, setPreferencesSignal] =
   createSignal<UserPreferences>(initialPreferencesState.preferences);
 const [lastUpdated, setLastUpdated] =
   createSignal<string>(initialPreferencesState.updatedAt);
 const [isSyncing, setIsSyncing] = createSignal(false);
 const [isInitialized, setIsInitialized] = createSignal(false);
 
+// ============================================================================
+// Desktop Notification Sync
+// ============================================================================
+
+let lastDesktopNotificationSettings: string | null = null;
+
+/**
+ * Push notification rules to the Tauri backend when they change.
+ */
+function syncDesktopNotificationSettings(prefs: UserPreferences): void {
+  if (!isTauri) return;
+
+  const settings = {
+    ...DEFAULT_DESKTOP_NOTIFICATION_PREFERENCES,
+    ...prefs.desktop_notifications,
+    channel_levels: prefs.channel_notifications,
+  };
+  const serialized = JSON.stringify(settings);
+  if (serialized === lastDesktopNotificationSettings) return;
+  lastDesktopNotificationSettings = serialized;
+
+  import("@tauri-apps/api/core")
+    .then(({ invoke }) => invoke("set_notification_settings", { settings }))
+    .catch((e) => {
+      lastDesktopNotificationSettings = null;
+      console.error("[Preferences] Failed to sync notification settings:", e);
+    });
+}
+
+function setPreferences(prefs: UserPreferences): void {
+  setPreferencesSignal(prefs);
+  syncDesktopNotificationSettings(prefs);
+}
+
 // ============================================================================
 // Debounce Timer
 // ============================================================================
@@ -485,6 +529,7 @@ export function handlePreferencesUpdated(event: {
     setLastUpdated(event.updated_at);
     saveToLocalStorage(merged, event.updated_at);
     console.log("[Preferences] Applied update from another device");
+    import("@/stores/voice").then(({ applyUserGains }) => applyUserGains());
   } else {
     console.log("[Preferences] Ignored older update from server");
   }
@@ -514,6 +559,33 @@ export function setChannelNotificationLevel(
   updatePreference("channel_notifications", updatedNotifications);
 }
 
+/**
+ * Set the words that notify like an @mention.
+ */
+export function setHighlightKeywords(keywords: string[]): void {
+  updatePreference("highlight_keywords", keywords);
+}
+
+/**
+ * Get how loud a user plays in voice channels (1 = unchanged).
+ */
+export function getVoiceUserGain(userId: string): number {
+  return preferences().voice_user_gains?.[userId] ?? 1;
+}
+
+/**
+ * Update a desktop notification setting.
+ */
+export function updateDesktopNotificationSetting<
+  K extends keyof DesktopNotificationPreferences,
+>(key: K, value: DesktopNotificationPreferences[K]): void {
+  const current = {
+    ...DEFAULT_DESKTOP_NOTIFICATION_PREFERENCES,
+    ...preferences().desktop_notifications,
+  };
+  updatePreference("desktop_notifications", { ...current, [key]: value });
+}
+
 /**
  * Check if currently in quiet hours.
  */
@@ -18,7 +18,11 @@ import {
   stopIdleDetection,
   setIdleTimeout,
 } from "@/lib/idleDetector";
-import { updateCustomStatus, updateStatus } from "@/lib/tauri";
+import {
+  updateCustomStatus,
+  updateStatus,
+  wsPresencePing,
+} from "@/lib/tauri";
 import { preferences } from "./preferences";
 import { currentUser, updateUser } from "./auth";
 import { setFriendsState } from "./friends";
@@ -247,27 +251,64 @@ export function getMyStatus(): UserStatus {
   return getUserStatus(user.id);
 }
 
+/** How often an active client reports activity to the server. */
+const PRESENCE_PING_INTERVAL_MS = 60_000;
+
+let presencePingTimer: ReturnType<typeof setInterval> | null = null;
+
+function sendPresencePing(): void {
+  wsPresencePing().catch((e) =>
+    console.warn("[Presence] Failed to send activity ping:", e),
+  );
+}
+
+function startPresencePings(): void {
+  stopPresencePings();
+  sendPresencePing();
+  presencePingTimer = setInterval(sendPresencePing, PRESENCE_PING_INTERVAL_MS);
+}
+
+function stopPresencePings(): void {
+  if (presencePingTimer) {
+    clearInterval(presencePingTimer);
+    presencePingTimer = null;
+  }
+}
+
 /**
  * Initialize idle detection.
- * Automatically sets user to 'idle' after configured timeout of inactivity.
- * Restores previous status when user becomes active again.
+ * Reports activity to the server while the user is active; the server shows
+ * them as away once none of their devices has pinged for a while.
+ * Locally shows 'idle' after the configured timeout of inactivity and
+ * restores the previous status when the user becomes active again.
  */
 export function initIdleDetection(): void {
   const timeout = preferences().display?.idle_timeout_minutes ?? 5;
 
   startIdleDetection((isIdle) => {
+    const user = currentUser();
     const currentStatus = getMyStatus();
 
+    if (isIdle) {
+      stopPresencePings();
+    } else {
+      startPresencePings();
+    }
+
+    if (!user) return;
+
     if (isIdle && currentStatus === "online") {
-      // User went idle while online - save status and switch to idle
+      // User went idle while online - save status and show idle
       previousStatus = "online";
       wasManuallySetIdle = false;
-      setMyStatus("idle");
+      updateUserPresence(user.id, "idle");
     } else if (!isIdle && currentStatus === "idle" && !wasManuallySetIdle) {
       // User became active while auto-idle - restore previous status
-      setMyStatus(previousStatus);
+      updateUserPresence(user.id, previousStatus);
     }
   }, timeout);
+
+  startPresencePings();
 }
 
 /**
@@ -275,6 +316,7 @@ export function initIdleDetection(): void {
  */
 export function stopIdleDetectionCleanup(): void {
   stopIdleDetection();
+  stopPresencePings();
 }
 
 /**
@@ -378,6 +420,10 @@ export function patchUser(userId: string, diff: Record<string, unknown>): void {
         "username",
         "display_name",
         "avatar_url",
+        "banner_url",
+        "about_me",
+        "pronouns",
+        "accent_color",
         "email",
         "mfa_enabled",
       ];
@@ -16,6 +16,7 @@ import {
 import type { ScreenShareInfo, ScreenShareQuality } from "@/lib/webrtc/types";
 import type { VoiceParticipant, WebcamServerInfo } from "@/lib/types";
 import { channelsState } from "@/stores/channels";
+import { getVoiceUserGain } from "@/stores/preferences";
 import * as tauri from "@/lib/tauri";
 import { showToast, dismissToast } from "@/components/ui/Toast";
 
@@ -246,7 +247,7 @@ function startMetricsLoop(): void {
         const sessionId = voiceState.sessionId;
         if (sessionId && voiceState.channelId) {
           tauri.wsSend({
-            type: "VoiceStats",
+            type: "voice_stats",
             channel_id: voiceState.channelId,
             session_id: sessionId,
             latency: metrics.latency,
@@ -254,6 +255,9 @@ function startMetricsLoop(): void {
             jitter: metrics.jitter,
             quality: qualityToNumber(metrics.quality),
             timestamp: metrics.timestamp,
+            concealment_events: metrics.concealmentEvents,
+            jitter_buffer_delay: metrics.jitterBufferDelay,
+            audio_level: metrics.audioLevel,
           });
         }
       } else {
@@ -432,6 +436,9 @@ export async function joinVoice(channelId: string): Promise<void> {
       onSpeakingChange: (speaking) => {
         setVoiceState({ speaking });
       },
+      onRemoteTrack: (track) => {
+        adapter.setUserGain?.(track.userId, getVoiceUserGain(track.userId));
+      },
       onScreenShareTrack: (userId, track) => {
         console.log("[Voice] Screen share track received:", userId);
         // Import and call viewer store
@@ -589,6 +596,58 @@ export async function setDeafen(deafened: boolean): Promise<void> {
   }
 }
 
+/**
+ * Promote a listener in the current voice channel so they can speak.
+ * Requires VOICE_MUTE_OTHERS.
+ */
+export async function promoteListener(userId: string): Promise<void> {
+  if (!voiceState.channelId) return;
+
+  const { wsSend } = await import("@/lib/tauri");
+  await wsSend({
+    type: "voice_promote_listener",
+    channel_id: voiceState.channelId,
+    user_id: userId,
+  });
+}
+
+/**
+ * Raise or lower the user's hand to ask the moderators to let them speak.
+ * Only listeners can raise a hand; a moderator answers with promoteListener.
+ */
+export async function setHandRaised(raised: boolean): Promise<void> {
+  if (!voiceState.channelId) return;
+
+  const { wsSend } = await import("@/lib/tauri");
+  await wsSend({
+    type: raised ? "voice_raise_hand" : "voice_lower_hand",
+    channel_id: voiceState.channelId,
+  });
+}
+
+/**
+ * Set how loud a user plays for this user (0-2, 1 = unchanged).
+ * The server saves it in the synced preferences.
+ */
+export async function setUserGain(userId: string, gain: number): Promise<void> {
+  getVoiceAdapter()?.setUserGain?.(userId, gain);
+
+  const { wsSend } = await import("@/lib/tauri");
+  await wsSend({ type: "voice_set_user_gain", target_user: userId, gain });
+}
+
+/**
+ * Apply the saved per-user volumes to the current call's participants.
+ */
+export function applyUserGains(): void {
+  const adapter = getVoiceAdapter();
+  if (!adapter?.setUserGain) return;
+
+  for (const userId of Object.keys(voiceState.participants)) {
+    adapter.setUserGain(userId, getVoiceUserGain(userId));
+  }
+}
+
 /**
  * Set speaking state (temporary for testing until VAD is implemented).
  * @phase1 - Backend needs to implement Voice Activity Detection (VAD)
@@ -861,6 +920,40 @@ export function getParticipantMetrics(
   return voiceState.participantMetrics.get(userId);
 }
 
+/** Concealment events per 3s sample above which received audio is choppy. */
+const CONCEALMENT_WARNING_EVENTS = 5;
+
+/** Where a degraded connection to a remote participant most likely originates. */
+export type IssueSource = "local" | "remote" | "both";
+
+function isDegraded(
+  metrics: Pick<ConnectionMetrics, "quality" | "concealmentEvents">,
+): boolean {
+  return (
+    metrics.quality === "poor" ||
+    metrics.quality === "warning" ||
+    (metrics.concealmentEvents ?? 0) > CONCEALMENT_WARNING_EVENTS
+  );
+}
+
+/**
+ * Work out whether audio problems with a remote participant come from our
+ * connection, theirs, or both, using each side's network and playout stats.
+ * Returns null when neither side looks degraded or local stats are missing.
+ */
+export function getIssueSource(userId: string): IssueSource | null {
+  const local = voiceState.localMetrics;
+  const remote = voiceState.participantMetrics.get(userId);
+  if (!local || local === "unknown" || !remote) return null;
+
+  const localDegraded = isDegraded(local);
+  const remoteDegraded = isDegraded(remote);
+  if (localDegraded && remoteDegraded) return "both";
+  if (localDegraded) return "local";
+  if (remoteDegraded) return "remote";
+  return null;
+}
+
 /**
  * Handle incoming voice_user_stats event from server.
  * Updates participant metrics in the store.
@@ -872,6 +965,9 @@ export function handleVoiceUserStats(data: {
   packet_loss: number;
   jitter: number;
   quality: number;
+  concealment_events?: number;
+  jitter_buffer_delay?: number;
+  audio_level?: number;
 }): void {
   const { channel_id, user_id, latency, packet_loss, jitter, quality } = data;
 
@@ -885,6 +981,9 @@ export function handleVoiceUserStats(data: {
     packetLoss: packet_loss,
     jitter,
     quality: numberToQuality(quality),
+    concealmentEvents: data.concealment_events,
+    jitterBufferDelay: data.jitter_buffer_delay,
+    audioLevel: data.audio_level,
   });
   setVoiceState("participantMetrics", newMetrics);
 }
@@ -8,15 +8,30 @@ import { createStore } from "solid-js/store";
 import * as tauri from "@/lib/tauri";
 import type {
   Activity,
+  Attachment,
+  CategoryPlacement,
+  ChannelPlacement,
+  Guild,
+  GuildMember,
+  GuildPerks,
+  GuildRole,
+  LinkEmbed,
   Message,
+  MessageEmoji,
+  Relationship,
   ServerEvent,
   ThreadInfo,
+  UserProfile,
   UserStatus,
+  VoiceParticipant,
 } from "@/lib/types";
 import { updateUserActivity, updateUserPresence } from "./presence";
 import {
   addMessage,
+  loadInitialMessages,
   removeMessage,
+  updateAttachment,
+  updateMessageEmbeds,
   messagesState,
   setMessagesState,
 } from "./messages";
@@ -31,6 +46,7 @@ import {
   threadsState,
 } from "./threads";
 import { handlePreferencesUpdated } from "./preferences";
+import { handleDraftUpdate, type ServerDraft } from "./drafts";
 import {
   receiveIncomingCall,
   callConnected,
@@ -45,25 +61,41 @@ import {
   loadPendingRequests,
   handleUserBlocked,
   handleUserUnblocked,
+  handleRelationshipUpdate,
 } from "./friends";
 import { playNotification } from "@/lib/sound";
+import { recordTimeSync, resetClockSync } from "@/lib/serverClock";
 import {
   getChannel,
   channelsState,
+  handleChannelPositionsUpdateEvent,
   handleChannelReadEvent,
   incrementUnreadCount,
 } from "./channels";
-import { currentUser } from "./auth";
+import { handleCategoryPositionsUpdateEvent } from "./categories";
+import { currentUser, logout, updateUser } from "./auth";
 import {
   guildsState,
   getGuildIdForChannel,
+  handleGuildMemberChunk,
+  handleGuildUpdate,
   incrementGuildUnread,
+  loadGuilds,
+  setGuildPerks,
+  setMemberTimeout,
 } from "./guilds";
+import {
+  handleMemberRolesUpdateEvent,
+  handleRoleDeleteEvent,
+  handleRolesReorderEvent,
+  handleRoleUpdateEvent,
+} from "./permissions";
 import type { MentionType, SoundEventType } from "@/lib/sound/types";
 import {
   dmsState,
   handleDMReadEvent,
   handleDMNameUpdated,
+  loadDMs,
   updateDMLastMessage,
 } from "./dms";
 
@@ -111,7 +143,9 @@ let unlisteners: UnlistenFn[] = [];
 
 // Typing debounce timers
 const typingTimers: Record<string, NodeJS.Timeout> = {};
-const TYPING_TIMEOUT = 5000; // 5 seconds
+// Fallback only: the server re-announces ongoing typing every 5s and sends
+// TypingStop when an indicator expires.
+const TYPING_TIMEOUT = 10000; // 10 seconds
 
 // Track connection start time for WS connect duration
 let connectStartTime = 0;
@@ -168,6 +202,232 @@ function handleMessageNotification(message: Message): void {
   });
 }
 
+/**
+ * Handle a server clock reading.
+ *
+ * The server sends an unsolicited reading after every (re)connect; follow it
+ * with a round-trip request for a latency-corrected offset.
+ */
+function handleTimeSync(serverTime: number, clientTime?: number): void {
+  if (clientTime === undefined) {
+    resetClockSync();
+    recordTimeSync(serverTime, undefined);
+    tauri.wsTimeSync().catch((err) => {
+      console.warn("[WebSocket] Failed to request time sync:", err);
+    });
+    return;
+  }
+  recordTimeSync(serverTime, clientTime);
+}
+
+/**
+ * Warn the user while the server caps their bitrate for sustained packet loss.
+ */
+async function handleVoiceQualityEvent(
+  event:
+    | {
+        type: "voice_quality_warning";
+        channel_id: string;
+        packet_loss: number;
+        audio_bitrate: number;
+      }
+    | { type: "voice_quality_recovered"; channel_id: string },
+): Promise<void> {
+  const { showToast, dismissToast } = await import("@/components/ui/Toast");
+  const id = `voice-quality-${event.channel_id}`;
+  if (event.type === "voice_quality_recovered") {
+    dismissToast(id);
+    return;
+  }
+  showToast({
+    type: "warning",
+    title: "Unstable connection",
+    message: `${event.packet_loss.toFixed(1)}% packet loss. Your audio quality was lowered until your connection recovers.`,
+    duration: 0,
+    id,
+  });
+}
+
+/**
+ * Tell the user their data export finished, with a download button when ready.
+ */
+async function handleDataExportEvent(
+  event:
+    | { type: "data_export_ready"; job_id: string; download_url: string }
+    | { type: "data_export_failed"; job_id: string; error_message: string },
+): Promise<void> {
+  const { showToast } = await import("@/components/ui/Toast");
+  if (event.type === "data_export_ready") {
+    const url = event.download_url;
+    showToast({
+      type: "success",
+      title: "Data export ready",
+      message: "Your data archive is ready to download.",
+      duration: 0,
+      id: `data-export-${event.job_id}`,
+      action: { label: "Download", onClick: () => window.open(url, "_blank") },
+    });
+  } else {
+    showToast({
+      type: "error",
+      title: "Data export failed",
+      message: event.error_message,
+      duration: 8000,
+      id: `data-export-${event.job_id}`,
+    });
+  }
+}
+
+/**
+ * Tell a guild owner that a system admin suspended or restored their server.
+ */
+async function handleGuildSuspension(event: {
+  guild_id: string;
+  guild_name: string;
+  reason?: string;
+}): Promise<void> {
+  const { showToast } = await import("@/components/ui/Toast");
+  const id = `guild-suspension-${event.guild_id}`;
+  if (event.reason !== undefined) {
+    showToast({
+      type: "error",
+      title: "Server suspended",
+      message: `${event.guild_name}: ${event.reason}`,
+      duration: 0,
+      id,
+    });
+  } else {
+    showToast({
+      type: "success",
+      title: "Server suspension lifted",
+      message: `${event.guild_name} is active again.`,
+      duration: 8000,
+      id,
+    });
+  }
+}
+
+/**
+ * Tell a guild owner how their discovery listing was reviewed.
+ */
+async function handleGuildDiscoveryReviewed(event: {
+  guild_id: string;
+  guild_name: string;
+  status: "approved" | "rejected" | "delisted";
+  reason: string | null;
+}): Promise<void> {
+  const { showToast } = await import("@/components/ui/Toast");
+  const id = `guild-discovery-${event.guild_id}`;
+  if (event.status === "approved") {
+    showToast({
+      type: "success",
+      title: "Server listed in discovery",
+      message: `${event.guild_name} was approved and is now discoverable.`,
+      duration: 8000,
+      id,
+    });
+  } else {
+    showToast({
+      type: "error",
+      title:
+        event.status === "rejected"
+          ? "Discovery listing rejected"
+          : "Server removed from discovery",
+      message: event.reason
+        ? `${event.guild_name}: ${event.reason}`
+        : event.guild_name,
+      duration: 0,
+      id,
+    });
+  }
+}
+
+/**
+ * Apply the profile sent with `ready`, picking up profile changes made on
+ * other devices while this one was disconnected.
+ */
+function applyReadyProfile(profile: UserProfile | undefined): void {
+  const user = currentUser();
+  if (!profile || !user || user.id !== profile.id) return;
+
+  updateUser({
+    username: profile.username,
+    display_name: profile.display_name,
+    avatar_url: profile.avatar_url,
+    banner_url: profile.banner_url ?? null,
+    about_me: profile.about_me ?? null,
+    pronouns: profile.pronouns ?? null,
+    accent_color: profile.accent_color ?? null,
+  });
+}
+
+/**
+ * Refetch state after a reconnect whose missed events could not be replayed.
+ */
+async function resyncAfterFailedResume(reason: string): Promise<void> {
+  console.warn("[WebSocket] Session resume failed, refetching state:", reason);
+  Sentry.addBreadcrumb({
+    category: "ws",
+    message: "resume_failed",
+    data: { reason },
+    level: "warning",
+  });
+
+  const results = await Promise.allSettled([
+    loadGuilds(),
+    loadDMs(),
+    loadFriends(),
+    loadPendingRequests(),
+  ]);
+  for (const result of results) {
+    if (result.status === "rejected") {
+      console.error("[WebSocket] Resync failed:", result.reason);
+    }
+  }
+
+  const open = [channelsState.selectedChannelId, dmsState.selectedDMId];
+  for (const channelId of new Set(open)) {
+    if (channelId) {
+      await loadInitialMessages(channelId);
+    }
+  }
+}
+
+/**
+ * Sign out after an admin suspended the account or revoked its sessions.
+ */
+async function handleSessionRevoked(reason: string): Promise<void> {
+  const { showToast } = await import("@/components/ui/Toast");
+  showToast({
+    type: "error",
+    title: reason === "suspended" ? "Account suspended" : "Signed out",
+    message:
+      reason === "suspended"
+        ? "Your account has been suspended by an administrator."
+        : "An administrator ended your sessions. Please sign in again.",
+    duration: 0,
+  });
+  await logout();
+}
+
+/**
+ * Warn that the account was signed in to from a network it has not used before.
+ */
+async function handleNewLoginNetwork(event: {
+  ip: string;
+  user_agent: string | null;
+}): Promise<void> {
+  const { showToast } = await import("@/components/ui/Toast");
+  const device = event.user_agent ? ` (${event.user_agent})` : "";
+  showToast({
+    type: "warning",
+    title: "New sign-in to your account",
+    message: `Signed in from ${event.ip}${device}. If this wasn't you, change your password.`,
+    duration: 0,
+    id: `new-login-network-${event.ip}`,
+  });
+}
+
 /**
  * Initialize WebSocket event listeners.
  * Call this once when the app starts (after auth).
@@ -197,6 +457,12 @@ export async function initWebSocket(): Promise<void> {
       }),
     );
 
+    pending.push(
+      listen<{ profile?: UserProfile }>("ws:ready", (event) => {
+        applyReadyProfile(event.payload.profile);
+      }),
+    );
+
     pending.push(
       listen("ws:disconnected", () => {
         setWsState({ status: "disconnected" });
@@ -209,6 +475,39 @@ export async function initWebSocket(): Promise<void> {
       }),
     );
 
+    // Session resume after a reconnect
+    pending.push(
+      listen<{ session_id: string; replayed: number }>("ws:resumed", (event) => {
+        Sentry.addBreadcrumb({
+          category: "ws",
+          message: "resumed",
+          data: { replayed: event.payload.replayed },
+          level: "info",
+        });
+      }),
+    );
+
+    pending.push(
+      listen<{ reason: string }>("ws:resume_failed", (event) => {
+        void resyncAfterFailedResume(event.payload.reason);
+      }),
+    );
+
+    pending.push(
+      listen<{ reason: string }>("ws:session_revoked", (event) => {
+        void handleSessionRevoked(event.payload.reason);
+      }),
+    );
+
+    pending.push(
+      listen<{ ip: string; user_agent: string | null }>(
+        "ws:new_login_network",
+        (event) => {
+          void handleNewLoginNetwork(event.payload);
+        },
+      ),
+    );
+
     // Message events
     pending.push(
       listen<{ channel_id: string; message: Message }>("ws:message_new", async (event) => {
@@ -244,9 +543,11 @@ export async function initWebSocket(): Promise<void> {
         channel_id: string;
         message_id: string;
         content: string;
+        emojis?: MessageEmoji[];
         edited_at: string;
       }>("ws:message_edit", (event) => {
-        const { channel_id, message_id, content, edited_at } = event.payload;
+        const { channel_id, message_id, content, emojis, edited_at } =
+          event.payload;
         const messages = messagesState.byChannel[channel_id];
         if (messages) {
           const index = messages.findIndex((m) => m.id === message_id);
@@ -265,6 +566,13 @@ export async function initWebSocket(): Promise<void> {
               "edited_at",
               edited_at,
             );
+            setMessagesState(
+              "byChannel",
+              channel_id,
+              index,
+              "emojis",
+              emojis ?? [],
+            );
           }
         }
       }),
@@ -276,6 +584,26 @@ export async function initWebSocket(): Promise<void> {
       }),
     );
 
+    pending.push(
+      listen<{ channel_id: string; message_id: string; attachment: Attachment }>(
+        "ws:attachment_processed",
+        (event) => {
+          const { channel_id, message_id, attachment } = event.payload;
+          updateAttachment(channel_id, message_id, attachment);
+        },
+      ),
+    );
+
+    pending.push(
+      listen<{ channel_id: string; message_id: string; embeds: LinkEmbed[] }>(
+        "ws:message_embeds_update",
+        (event) => {
+          const { channel_id, message_id, embeds } = event.payload;
+          updateMessageEmbeds(channel_id, message_id, embeds);
+        },
+      ),
+    );
+
     // Typing events
     pending.push(
       listen<{ channel_id: string; user_id: string }>("ws:typing_start", (event) => {
@@ -390,16 +718,57 @@ export async function initWebSocket(): Promise<void> {
         user_id: string;
         username: string;
         display_name: string;
+        listener?: boolean;
       }>("ws:voice_user_joined", async (event) => {
         await handleVoiceUserJoined(
           event.payload.channel_id,
           event.payload.user_id,
           event.payload.username,
           event.payload.display_name,
+          event.payload.listener ?? false,
         );
       }),
     );
 
+    pending.push(
+      listen<{ channel_id: string; user_id: string }>(
+        "ws:voice_listener_promoted",
+        async (event) => {
+          await handleVoiceParticipantUpdate(
+            event.payload.channel_id,
+            event.payload.user_id,
+            { listener: false, hand_raised: false },
+          );
+        },
+      ),
+    );
+
+    pending.push(
+      listen<{ channel_id: string; user_id: string }>(
+        "ws:voice_hand_raised",
+        async (event) => {
+          await handleVoiceParticipantUpdate(
+            event.payload.channel_id,
+            event.payload.user_id,
+            { hand_raised: true },
+          );
+        },
+      ),
+    );
+
+    pending.push(
+      listen<{ channel_id: string; user_id: string }>(
+        "ws:voice_hand_lowered",
+        async (event) => {
+          await handleVoiceParticipantUpdate(
+            event.payload.channel_id,
+            event.payload.user_id,
+            { hand_raised: false },
+          );
+        },
+      ),
+    );
+
     pending.push(
       listen<{ channel_id: string; user_id: string }>("ws:voice_user_left", async (event) => {
         await handleVoiceUserLeft(
@@ -484,6 +853,27 @@ export async function initWebSocket(): Promise<void> {
       }),
     );
 
+    // Guild profile events
+    pending.push(
+      listen<{ guild_id: string; guild: Guild }>("ws:guild_update", (event) => {
+        handleGuildUpdate(event.payload.guild);
+      }),
+    );
+
+    pending.push(
+      listen<{
+        guild_id: string;
+        members: GuildMember[];
+        next_cursor?: string | null;
+      }>("ws:guild_member_chunk", (event) => {
+        handleGuildMemberChunk(
+          event.payload.guild_id,
+          event.payload.members,
+          event.payload.next_cursor ?? null,
+        );
+      }),
+    );
+
     // Guild emoji events
     pending.push(
       listen<{ guild_id: string; emojis: any[] }>("ws:guild_emoji_updated", (event) => {
@@ -491,6 +881,73 @@ export async function initWebSocket(): Promise<void> {
       }),
     );
 
+    // Guild supporter perk events
+    pending.push(
+      listen<{ guild_id: string; perks: GuildPerks }>("ws:guild_perks_update", (event) => {
+        setGuildPerks(event.payload.guild_id, event.payload.perks);
+      }),
+    );
+
+    // Guild role events
+    pending.push(
+      listen<{ guild_id: string; role: GuildRole }>("ws:role_create", (event) => {
+        handleRoleUpdateEvent(event.payload.guild_id, event.payload.role);
+      }),
+    );
+    pending.push(
+      listen<{ guild_id: string; role: GuildRole }>("ws:role_update", (event) => {
+        handleRoleUpdateEvent(event.payload.guild_id, event.payload.role);
+      }),
+    );
+    pending.push(
+      listen<{ guild_id: string; role_id: string }>("ws:role_delete", (event) => {
+        handleRoleDeleteEvent(event.payload.guild_id, event.payload.role_id);
+      }),
+    );
+    pending.push(
+      listen<{ guild_id: string; roles: GuildRole[] }>("ws:roles_reorder", (event) => {
+        handleRolesReorderEvent(event.payload.guild_id, event.payload.roles);
+      }),
+    );
+    pending.push(
+      listen<{
+        guild_id: string;
+        channels: ChannelPlacement[];
+        categories: CategoryPlacement[];
+      }>("ws:channel_positions_update", (event) => {
+        handleChannelPositionsUpdateEvent(event.payload.channels);
+        handleCategoryPositionsUpdateEvent(
+          event.payload.guild_id,
+          event.payload.categories,
+        );
+      }),
+    );
+    pending.push(
+      listen<{ guild_id: string; user_id: string; role_ids: string[] }>(
+        "ws:member_roles_update",
+        (event) => {
+          handleMemberRolesUpdateEvent(
+            event.payload.guild_id,
+            event.payload.user_id,
+            event.payload.role_ids,
+          );
+        },
+      ),
+    );
+    pending.push(
+      listen<{
+        guild_id: string;
+        user_id: string;
+        timeout_until: string | null;
+      }>("ws:member_timeout_update", (event) => {
+        setMemberTimeout(
+          event.payload.guild_id,
+          event.payload.user_id,
+          event.payload.timeout_until,
+        );
+      }),
+    );
+
     // Read sync events (Tauri → frontend parity with browser mode)
     pending.push(
       listen<{ channel_id: string }>("ws:channel_read", (event) => {
@@ -594,6 +1051,31 @@ export async function initWebSocket(): Promise<void> {
       }),
     );
 
+    pending.push(
+      listen<{
+        channel_id: string;
+        packet_loss: number;
+        audio_bitrate: number;
+      }>("ws:voice_quality_warning", async (event) => {
+        await handleVoiceQualityEvent({
+          type: "voice_quality_warning",
+          ...event.payload,
+        });
+      }),
+    );
+
+    pending.push(
+      listen<{ channel_id: string }>(
+        "ws:voice_quality_recovered",
+        async (event) => {
+          await handleVoiceQualityEvent({
+            type: "voice_quality_recovered",
+            ...event.payload,
+          });
+        },
+      ),
+    );
+
     // Admin events
     pending.push(
       listen<{ user_id: string; username: string }>("ws:admin_user_banned", async (event) => {
@@ -695,6 +1177,18 @@ export async function initWebSocket(): Promise<void> {
       }),
     );
 
+    pending.push(
+      listen<{ user_id: string; relationship: Relationship | null }>(
+        "ws:relationship_update",
+        (event) => {
+          handleRelationshipUpdate(
+            event.payload.user_id,
+            event.payload.relationship,
+          );
+        },
+      ),
+    );
+
     // Thread events
     pending.push(
       listen<{
@@ -740,6 +1234,30 @@ export async function initWebSocket(): Promise<void> {
       }),
     );
 
+    pending.push(
+      listen<{
+        channel_id: string;
+        message_id: string;
+        keyword: string;
+      }>("ws:message_highlight", (event) => {
+        handleMessageHighlight(event.payload.channel_id);
+      }),
+    );
+
+    // Subscription cap
+    pending.push(
+      listen<{ channel_id: string }>("ws:subscription_evicted", (event) => {
+        handleSubscriptionEvicted(event.payload.channel_id);
+      }),
+    );
+
+    // Clock sync
+    pending.push(
+      listen<{ server_time: number; client_time?: number }>("ws:time_sync", (event) => {
+        handleTimeSync(event.payload.server_time, event.payload.client_time);
+      }),
+    );
+
     // Preferences sync
     pending.push(
       listen<any>("ws:preferences_updated", (event) => {
@@ -747,6 +1265,55 @@ export async function initWebSocket(): Promise<void> {
       }),
     );
 
+    // Draft sync
+    pending.push(
+      listen<ServerDraft>("ws:draft_update", (event) => {
+        handleDraftUpdate(event.payload);
+      }),
+    );
+
+    // Data export
+    pending.push(
+      listen<{ job_id: string; download_url: string }>("ws:data_export_ready", (event) => {
+        handleDataExportEvent({ type: "data_export_ready", ...event.payload });
+      }),
+    );
+    pending.push(
+      listen<{ job_id: string; error_message: string }>("ws:data_export_failed", (event) => {
+        handleDataExportEvent({ type: "data_export_failed", ...event.payload });
+      }),
+    );
+
+    // Discovery review
+    pending.push(
+      listen<{
+        guild_id: string;
+        guild_name: string;
+        status: "approved" | "rejected" | "delisted";
+        reason: string | null;
+      }>("ws:guild_discovery_reviewed", (event) => {
+        handleGuildDiscoveryReviewed(event.payload);
+      }),
+    );
+
+    // Guild suspension
+    pending.push(
+      listen<{ guild_id: string; guild_name: string; reason: string }>(
+        "ws:guild_suspended",
+        (event) => {
+          handleGuildSuspension(event.payload);
+        },
+      ),
+    );
+    pending.push(
+      listen<{ guild_id: string; guild_name: string }>(
+        "ws:guild_unsuspended",
+        (event) => {
+          handleGuildSuspension(event.payload);
+        },
+      ),
+    );
+
     // State sync (patch)
     pending.push(
       listen<{
@@ -910,6 +1477,31 @@ async function handleServerEvent(event: ServerEvent): Promise<void> {
 
     case "subscribed":
     case "unsubscribed":
+    case "resumed":
+      break;
+
+    case "subscription_evicted":
+      handleSubscriptionEvicted(event.channel_id);
+      break;
+
+    case "ready":
+      applyReadyProfile(event.profile);
+      break;
+
+    case "resume_failed":
+      void resyncAfterFailedResume(event.reason);
+      break;
+
+    case "session_revoked":
+      void handleSessionRevoked(event.reason);
+      break;
+
+    case "new_login_network":
+      void handleNewLoginNetwork(event);
+      break;
+
+    case "time_sync":
+      handleTimeSync(event.server_time, event.client_time);
       break;
 
     case "message_edit": {
@@ -933,6 +1525,13 @@ async function handleServerEvent(event: ServerEvent): Promise<void> {
             "edited_at",
             event.edited_at,
           );
+          setMessagesState(
+            "byChannel",
+            event.channel_id,
+            editIndex,
+            "emojis",
+            event.emojis ?? [],
+          );
         }
       }
       break;
@@ -942,6 +1541,14 @@ async function handleServerEvent(event: ServerEvent): Promise<void> {
       removeMessage(event.channel_id, event.message_id);
       break;
 
+    case "attachment_processed":
+      updateAttachment(event.channel_id, event.message_id, event.attachment);
+      break;
+
+    case "message_embeds_update":
+      updateMessageEmbeds(event.channel_id, event.message_id, event.embeds);
+      break;
+
     case "typing_start":
       addTypingUser(event.channel_id, event.user_id);
       break;
@@ -978,6 +1585,7 @@ async function handleServerEvent(event: ServerEvent): Promise<void> {
         event.user_id,
         event.username,
         event.display_name,
+        event.listener ?? false,
       );
       break;
 
@@ -993,6 +1601,37 @@ async function handleServerEvent(event: ServerEvent): Promise<void> {
       await handleVoiceUserUnmuted(event.channel_id, event.user_id);
       break;
 
+    case "voice_speaking":
+      await handleVoiceParticipantUpdate(event.channel_id, event.user_id, {
+        speaking: event.speaking,
+      });
+      break;
+
+    case "voice_listener_promoted":
+      await handleVoiceParticipantUpdate(event.channel_id, event.user_id, {
+        listener: false,
+        hand_raised: false,
+      });
+      break;
+
+    case "voice_hand_raised":
+      await handleVoiceParticipantUpdate(event.channel_id, event.user_id, {
+        hand_raised: true,
+      });
+      break;
+
+    case "voice_hand_lowered":
+      await handleVoiceParticipantUpdate(event.channel_id, event.user_id, {
+        hand_raised: false,
+      });
+      break;
+
+    case "voice_activity_mode_changed":
+      await handleVoiceParticipantUpdate(event.channel_id, event.user_id, {
+        activity_mode: event.mode,
+      });
+      break;
+
     case "voice_room_state":
       await handleVoiceRoomState(
         event.channel_id,
@@ -1111,6 +1750,11 @@ async function handleServerEvent(event: ServerEvent): Promise<void> {
       await handleVoiceUserStatsEvent(event);
       break;
 
+    case "voice_quality_warning":
+    case "voice_quality_recovered":
+      await handleVoiceQualityEvent(event);
+      break;
+
     // Admin events
     case "admin_user_banned":
       await handleAdminUserBanned(event.user_id, event.username);
@@ -1156,6 +1800,28 @@ async function handleServerEvent(event: ServerEvent): Promise<void> {
       handlePreferencesUpdated(event);
       break;
 
+    // Draft sync events
+    case "draft_update":
+      handleDraftUpdate(event);
+      break;
+
+    // Data export events
+    case "data_export_ready":
+    case "data_export_failed":
+      await handleDataExportEvent(event);
+      break;
+
+    // Discovery review events
+    case "guild_discovery_reviewed":
+      await handleGuildDiscoveryReviewed(event);
+      break;
+
+    // Guild suspension events
+    case "guild_suspended":
+    case "guild_unsuspended":
+      await handleGuildSuspension(event);
+      break;
+
     // Reaction events
     case "reaction_add":
       handleReactionAdd(
@@ -1175,11 +1841,56 @@ async function handleServerEvent(event: ServerEvent): Promise<void> {
       );
       break;
 
+    // Guild profile events
+    case "guild_update":
+      handleGuildUpdate(event.guild);
+      break;
+
+    case "guild_member_chunk":
+      handleGuildMemberChunk(
+        event.guild_id,
+        event.members,
+        event.next_cursor ?? null,
+      );
+      break;
+
     // Guild emoji events
     case "guild_emoji_updated":
       handleGuildEmojiUpdated(event.guild_id, event.emojis);
       break;
 
+    // Guild supporter perk events
+    case "guild_perks_update":
+      setGuildPerks(event.guild_id, event.perks);
+      break;
+
+    // Guild role events
+    case "role_create":
+    case "role_update":
+      handleRoleUpdateEvent(event.guild_id, event.role);
+      break;
+
+    case "role_delete":
+      handleRoleDeleteEvent(event.guild_id, event.role_id);
+      break;
+
+    case "roles_reorder":
+      handleRolesReorderEvent(event.guild_id, event.roles);
+      break;
+
+    case "channel_positions_update":
+      handleChannelPositionsUpdateEvent(event.channels);
+      handleCategoryPositionsUpdateEvent(event.guild_id, event.categories);
+      break;
+
+    case "member_roles_update":
+      handleMemberRolesUpdateEvent(event.guild_id, event.user_id, event.role_ids);
+      break;
+
+    case "member_timeout_update":
+      setMemberTimeout(event.guild_id, event.user_id, event.timeout_until);
+      break;
+
     // Friend events
     case "friend_request_received":
       // New incoming friend request — refresh pending list
@@ -1200,6 +1911,10 @@ async function handleServerEvent(event: ServerEvent): Promise<void> {
       handleUserUnblocked(event.user_id);
       break;
 
+    case "relationship_update":
+      handleRelationshipUpdate(event.user_id, event.relationship);
+      break;
+
     // Admin report events
     case "admin_report_created":
       await handleAdminReportCreated(
@@ -1236,6 +1951,10 @@ async function handleServerEvent(event: ServerEvent): Promise<void> {
       handleThreadRead(event.thread_parent_id, event.last_read_message_id);
       break;
 
+    case "message_highlight":
+      handleMessageHighlight(event.channel_id);
+      break;
+
     // State sync events
     case "patch":
       await handlePatchEvent(event.entity_type, event.entity_id, event.diff);
@@ -1439,6 +2158,24 @@ export async function unsubscribeChannel(channelId: string): Promise<void> {
   }
 }
 
+/**
+ * Forget a channel the server dropped to stay within its per-connection
+ * subscription cap, so the next `subscribeChannel` subscribes again.
+ * The open conversation is subscribed again right away.
+ */
+function handleSubscriptionEvicted(channelId: string): void {
+  setWsState("subscribedChannels", (prev) => {
+    const next = new Set(prev);
+    next.delete(channelId);
+    return next;
+  });
+
+  const open = [channelsState.selectedChannelId, dmsState.selectedDMId];
+  if (open.includes(channelId)) {
+    void subscribeChannel(channelId);
+  }
+}
+
 /**
  * Send typing indicator (debounced).
  */
@@ -1588,6 +2325,7 @@ async function handleVoiceUserJoined(
   userId: string,
   username: string,
   displayName: string,
+  listener: boolean,
 ): Promise<void> {
   const { voiceState, setVoiceState } = await import("@/stores/voice");
   const { produce } = await import("solid-js/store");
@@ -1599,9 +2337,10 @@ async function handleVoiceUserJoined(
           user_id: userId,
           username: username,
           display_name: displayName,
-          muted: false,
+          muted: listener,
           speaking: false,
           screen_sharing: false,
+          listener,
         };
       }),
     );
@@ -1660,6 +2399,25 @@ async function handleVoiceUserUnmuted(
   }
 }
 
+async function handleVoiceParticipantUpdate(
+  channelId: string,
+  userId: string,
+  update: Partial<VoiceParticipant>,
+): Promise<void> {
+  const { voiceState, setVoiceState } = await import("@/stores/voice");
+  const { produce } = await import("solid-js/store");
+
+  if (voiceState.channelId === channelId) {
+    setVoiceState(
+      produce((state) => {
+        if (state.participants[userId]) {
+          Object.assign(state.participants[userId], update);
+        }
+      }),
+    );
+  }
+}
+
 async function handleVoiceRoomState(
   channelId: string,
   participants: any[],
@@ -1680,6 +2438,13 @@ async function handleVoiceRoomState(
         state.webcams = webcams ?? [];
       }),
     );
+
+    // Listeners join muted; the server rejects unmuting until promoted
+    const self = participants.find((p) => p.user_id === currentUser()?.id);
+    if (self?.listener && !voiceState.muted) {
+      const { setMute } = await import("@/stores/voice");
+      await setMute(true);
+    }
   }
 }
 
@@ -1828,6 +2593,9 @@ async function handleVoiceUserStatsEvent(event: {
   packet_loss: number;
   jitter: number;
   quality: number;
+  concealment_events?: number;
+  jitter_buffer_delay?: number;
+  audio_level?: number;
 }): Promise<void> {
   const { handleVoiceUserStats } = await import("@/stores/voice");
   handleVoiceUserStats(event);
@@ -1968,6 +2736,23 @@ function handleThreadNotification(message: Message): void {
   });
 }
 
+/**
+ * A message matched one of the user's highlight keywords; sound it like a
+ * mention unless the channel is already in view.
+ */
+function handleMessageHighlight(channelId: string): void {
+  if (channelsState.selectedChannelId === channelId && !document.hidden) {
+    return;
+  }
+
+  playNotification({
+    type: "message_mention",
+    channelId,
+    isDm: false,
+    mentionType: "direct",
+  });
+}
+
 // Reaction event handlers
 
 function handleReactionAdd(
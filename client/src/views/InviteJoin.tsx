@@ -1,28 +1,39 @@
 /**
  * InviteJoin - Handle invite link URLs
  *
- * Accepts invite code from URL, joins guild, redirects to guild.
+ * Previews the invite's guild, then joins it (logged-in users) or offers to
+ * log in first, and redirects to the guild.
  */
 
 import { Component, createSignal, onMount, Show } from "solid-js";
 import { useParams, useNavigate } from "@solidjs/router";
 import { joinViaInviteCode } from "@/stores/guilds";
 import { authState } from "@/stores/auth";
+import { getInvitePreview } from "@/lib/tauri";
+import type { InvitePreview } from "@/lib/types";
 
 const InviteJoin: Component = () => {
   const params = useParams<{ code: string }>();
   const navigate = useNavigate();
 
-  const [status, setStatus] = createSignal<"loading" | "success" | "error">(
-    "loading",
-  );
+  const [status, setStatus] = createSignal<
+    "loading" | "login" | "success" | "error"
+  >("loading");
   const [errorMessage, setErrorMessage] = createSignal("");
+  const [preview, setPreview] = createSignal<InvitePreview | null>(null);
 
   onMount(async () => {
-    // Check if user is logged in
+    try {
+      setPreview(await getInvitePreview(params.code));
+    } catch {
+      setStatus("error");
+      setErrorMessage("This invite is invalid, expired or has been used up.");
+      return;
+    }
+
+    // Not logged in: show the preview and let the user log in first
     if (!authState.user) {
-      // Redirect to login with return URL
-      navigate(`/login?redirect=/invite/${params.code}`);
+      setStatus("login");
       return;
     }
 
@@ -47,11 +58,36 @@ const InviteJoin: Component = () => {
         class="text-center p-8 rounded-2xl border border-white/10 max-w-md"
         style="background-color: var(--color-surface-layer1)"
       >
+        <Show when={preview()}>
+          {(p) => (
+            <div class="mb-4">
+              <div class="text-text-primary text-xl font-semibold">
+                {p().guild_name}
+              </div>
+              <div class="text-text-secondary text-sm">
+                {p().member_count} member{p().member_count !== 1 ? "s" : ""}
+              </div>
+            </div>
+          )}
+        </Show>
+
         <Show when={status() === "loading"}>
           <div class="text-text-primary text-lg mb-2">Joining guild...</div>
           <div class="text-text-secondary">Please wait</div>
         </Show>
 
+        <Show when={status() === "login"}>
+          <div class="text-text-secondary mb-4">
+            You've been invited to join this guild.
+          </div>
+          <button
+            onClick={() => navigate(`/login?redirect=/invite/${params.code}`)}
+            class="px-4 py-2 bg-accent-primary text-white rounded-lg hover:opacity-90"
+          >
+            Log in to join
+          </button>
+        </Show>
+
         <Show when={status() === "success"}>
           <div class="text-accent-primary text-lg mb-2">Success!</div>
           <div class="text-text-secondary">
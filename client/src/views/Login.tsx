@@ -3,13 +3,23 @@ import { A, useNavigate } from "@solidjs/router";
 import {
   login,
   loginWithOidc,
+  loginWithPasskey,
   authState,
   clearError,
   setAuthState,
 } from "@/stores/auth";
 import { fetchServerSettings, oidcAuthorize } from "@/lib/tauri";
+import { passkeyAssertion, passkeysSupported } from "@/lib/webauthn";
 import type { OidcProvider } from "@/lib/types";
-import { Github, Chrome, KeyRound, ShieldCheck } from "lucide-solid";
+import {
+  Github,
+  Chrome,
+  Fingerprint,
+  KeyRound,
+  ShieldCheck,
+  Smartphone,
+} from "lucide-solid";
+import DeviceLinkLogin from "@/components/auth/DeviceLinkLogin";
 import flokiWelcome from "@/assets/images/floki_auth_welcome.png";
 
 /** Map icon_hint to a Lucide icon component. */
@@ -35,6 +45,7 @@ const Login: Component = () => {
   const [mfaCode, setMfaCode] = createSignal("");
   const [localError, setLocalError] = createSignal("");
   const [oidcLoading, setOidcLoading] = createSignal<string | null>(null);
+  const [showDeviceLink, setShowDeviceLink] = createSignal(false);
 
   // Fetch server settings when server URL is entered (debounced)
   const [settingsUrl, setSettingsUrl] = createSignal(defaultServerUrl);
@@ -98,6 +109,43 @@ const Login: Component = () => {
     }
   };
 
+  /** Answer MFA_REQUIRED with a passkey instead of a code. */
+  const handlePasskeyMfa = async () => {
+    setLocalError("");
+    clearError();
+    try {
+      const assertion = await passkeyAssertion(serverUrl(), username());
+      await login(serverUrl(), username(), password(), undefined, assertion);
+      navigate("/", { replace: true });
+    } catch (err) {
+      // Store errors are already set; ceremony errors are not
+      if (!authState.error) {
+        setLocalError(err instanceof Error ? err.message : String(err));
+      }
+    }
+  };
+
+  const handlePasskeyLogin = async () => {
+    setLocalError("");
+    clearError();
+
+    if (isTauri && !serverUrl().trim()) {
+      setLocalError("Server URL is required");
+      return;
+    }
+    if (!username().trim()) {
+      setLocalError("Enter your username to sign in with a passkey");
+      return;
+    }
+
+    try {
+      await loginWithPasskey(serverUrl(), username());
+      navigate("/", { replace: true });
+    } catch {
+      // Error is already set in auth store
+    }
+  };
+
   const handleBackToLogin = () => {
     setAuthState({ mfaRequired: false, error: null });
     setMfaCode("");
@@ -185,6 +233,9 @@ const Login: Component = () => {
     return s?.oidc_enabled && s.oidc_providers.length > 0;
   };
 
+  const showPasskey = () =>
+    !!settings()?.passkeys_enabled && passkeysSupported();
+
   const error = () => localError() || authState.error;
 
   return (
@@ -215,7 +266,10 @@ const Login: Component = () => {
               value={serverUrl()}
               onInput={(e) => handleServerUrlChange(e.currentTarget.value)}
               disabled={
-                authState.isLoading || !!oidcLoading() || authState.mfaRequired
+                authState.isLoading ||
+                !!oidcLoading() ||
+                authState.mfaRequired ||
+                showDeviceLink()
               }
               required
             />
@@ -282,6 +336,18 @@ const Login: Component = () => {
               </Show>
             </button>
 
+            <Show when={showPasskey()}>
+              <button
+                type="button"
+                class="w-full flex items-center justify-center gap-2 px-4 py-2.5 rounded-lg border border-white/10 bg-white/5 hover:bg-white/10 text-text-primary text-sm font-medium transition-colors"
+                disabled={authState.isLoading}
+                onClick={handlePasskeyMfa}
+              >
+                <Fingerprint class="w-4 h-4" />
+                Use a passkey instead
+              </button>
+            </Show>
+
             <button
               type="button"
               onClick={handleBackToLogin}
@@ -292,8 +358,17 @@ const Login: Component = () => {
           </form>
         </Show>
 
-        {/* Normal Login Flow (hidden during MFA) */}
-        <Show when={!authState.mfaRequired}>
+        {/* Sign in through another device */}
+        <Show when={showDeviceLink()}>
+          <DeviceLinkLogin
+            serverUrl={serverUrl()}
+            onSignedIn={() => navigate("/", { replace: true })}
+            onCancel={() => setShowDeviceLink(false)}
+          />
+        </Show>
+
+        {/* Normal Login Flow (hidden during MFA and device linking) */}
+        <Show when={!authState.mfaRequired && !showDeviceLink()}>
           {/* SSO Buttons */}
           <Show when={showOidc()}>
             <div class="space-y-2 mb-4">
@@ -408,6 +483,19 @@ const Login: Component = () => {
                   Login
                 </Show>
               </button>
+
+              <Show when={showPasskey()}>
+                <button
+                  type="button"
+                  class="w-full flex items-center justify-center gap-2 px-4 py-2.5 rounded-lg border border-white/10 bg-white/5 hover:bg-white/10 text-text-primary text-sm font-medium transition-colors"
+                  data-testid="login-passkey"
+                  disabled={authState.isLoading}
+                  onClick={handlePasskeyLogin}
+                >
+                  <Fingerprint class="w-4 h-4" />
+                  Sign in with a passkey
+                </button>
+              </Show>
             </form>
           </Show>
 
@@ -421,6 +509,21 @@ const Login: Component = () => {
             </div>
           </Show>
 
+          <button
+            type="button"
+            class="w-full flex items-center justify-center gap-2 mt-4 text-sm text-text-secondary hover:text-text-primary transition-colors"
+            data-testid="login-device-link"
+            disabled={authState.isLoading || !serverUrl().trim()}
+            onClick={() => {
+              setLocalError("");
+              clearError();
+              setShowDeviceLink(true);
+            }}
+          >
+            <Smartphone class="w-4 h-4" />
+            Sign in with another device
+          </button>
+
           <p class="text-center text-sm text-text-secondary mt-4">
             Don't have an account?{" "}
             <A href="/register" class="text-primary hover:underline">
@@ -29,6 +29,7 @@ import HomeView from "@/components/home/HomeView";
 import HomeSidebar from "@/components/home/HomeSidebar";
 import SearchPanel from "@/components/search/SearchPanel";
 import KeyboardShortcutsDialog from "@/components/ui/KeyboardShortcutsDialog";
+import { isVoiceChannelType } from "@/lib/utils";
 import { selectedChannel } from "@/stores/channels";
 import { loadGuilds, guildsState, isDiscoveryActive } from "@/stores/guilds";
 import { threadsState } from "@/stores/threads";
@@ -173,7 +174,9 @@ const Main: Component = () => {
                     {/* Channel Header */}
                     <header class="h-12 px-4 flex items-center border-b border-white/5 bg-surface-layer1 shadow-sm">
                       <Show
-                        when={channel()?.channel_type === "voice"}
+                        when={
+                          channel() && isVoiceChannelType(channel()!.channel_type)
+                        }
                         fallback={
                           <Hash class="w-5 h-5 text-text-secondary mr-2" />
                         }
@@ -3,7 +3,7 @@ import { A, useNavigate } from "@solidjs/router";
 import { register, loginWithOidc, authState, clearError } from "@/stores/auth";
 import { fetchServerSettings, oidcAuthorize } from "@/lib/tauri";
 import type { OidcProvider } from "@/lib/types";
-import { Github, Chrome, KeyRound, ShieldAlert } from "lucide-solid";
+import { Github, Chrome, KeyRound, ShieldAlert, Clock } from "lucide-solid";
 import flokiRegister from "@/assets/images/floki_auth_register.png";
 
 /** Map icon_hint to a Lucide icon component. */
@@ -29,6 +29,8 @@ const Register: Component = () => {
   const [displayName, setDisplayName] = createSignal("");
   const [password, setPassword] = createSignal("");
   const [confirmPassword, setConfirmPassword] = createSignal("");
+  const [registrationCode, setRegistrationCode] = createSignal("");
+  const [pendingApproval, setPendingApproval] = createSignal(false);
   const [localError, setLocalError] = createSignal("");
   const [oidcLoading, setOidcLoading] = createSignal<string | null>(null);
 
@@ -79,6 +81,10 @@ const Register: Component = () => {
       setLocalError("Passwords do not match");
       return;
     }
+    if (isInviteOnly() && !registrationCode().trim()) {
+      setLocalError("Registration code is required");
+      return;
+    }
 
     try {
       await register(
@@ -87,10 +93,17 @@ const Register: Component = () => {
         password(),
         email() || undefined,
         displayName() || undefined,
+        registrationCode().trim() || undefined,
       );
       navigate("/", { replace: true });
     } catch (err) {
-      // Error is already set in auth store
+      if (
+        err instanceof Error &&
+        err.message === "REGISTRATION_PENDING_APPROVAL"
+      ) {
+        setPendingApproval(true);
+      }
+      // Other errors are already set in auth store
     }
   };
 
@@ -168,14 +181,21 @@ const Register: Component = () => {
     return s?.registration_policy === "closed";
   };
 
+  const isInviteOnly = () => settings()?.registration_policy === "invite_only";
+
+  const requiresApproval = () =>
+    settings()?.registration_policy === "approval";
+
   const showLocalRegister = () => {
     const s = settings();
+    if (pendingApproval()) return false;
     return !s || (s.auth_methods.local && s.registration_policy !== "closed");
   };
 
   const showOidc = () => {
     const s = settings();
     return (
+      !pendingApproval() &&
       s?.oidc_enabled &&
       s.oidc_providers.length > 0 &&
       s.registration_policy !== "closed"
@@ -232,6 +252,22 @@ const Register: Component = () => {
           </div>
         </Show>
 
+        {/* Account created, waiting for an admin */}
+        <Show when={pendingApproval()}>
+          <div class="flex items-center gap-3 p-4 rounded-lg border border-white/10 bg-white/5 text-text-secondary">
+            <Clock class="w-5 h-5 flex-shrink-0" />
+            <div>
+              <p class="font-medium text-text-primary">
+                Waiting for approval
+              </p>
+              <p class="text-sm mt-1">
+                Your account has been created. You can sign in once a server
+                admin approves it.
+              </p>
+            </div>
+          </div>
+        </Show>
+
         {/* SSO Buttons */}
         <Show when={showOidc()}>
           <div class="space-y-2 mb-4">
@@ -277,6 +313,32 @@ const Register: Component = () => {
         {/* Local Registration Form */}
         <Show when={showLocalRegister()}>
           <form onSubmit={handleRegister} class="space-y-4">
+            <Show when={requiresApproval()}>
+              <p class="text-sm text-text-secondary">
+                New accounts on this server must be approved by an admin before
+                they can sign in.
+              </p>
+            </Show>
+
+            <Show when={isInviteOnly()}>
+              <div>
+                <label class="block text-sm font-medium text-text-secondary mb-1">
+                  Registration Code <span class="text-danger">*</span>
+                </label>
+                <input
+                  type="text"
+                  class="input-field font-mono uppercase"
+                  data-testid="register-code"
+                  placeholder="Code from a server admin"
+                  value={registrationCode()}
+                  onInput={(e) => setRegistrationCode(e.currentTarget.value)}
+                  disabled={authState.isLoading}
+                  maxLength={32}
+                  required
+                />
+              </div>
+            </Show>
+
             <div>
               <label class="block text-sm font-medium text-text-secondary mb-1">
                 Username <span class="text-danger">*</span>
@@ -0,0 +1,31 @@
+//! Fuzz `EncryptedBackup::decrypt` with tampered backups.
+//!
+//! Input layout: 16-byte salt, 12-byte nonce, 4-byte version, ciphertext.
+//! Key derivation uses production Argon2id parameters (64 MiB), so expect
+//! only a handful of executions per second; run with `-max_total_time`.
+
+#![no_main]
+
+use std::sync::LazyLock;
+
+use libfuzzer_sys::fuzz_target;
+use vc_crypto::{EncryptedBackup, RecoveryKey};
+
+static KEY: LazyLock<RecoveryKey> = LazyLock::new(RecoveryKey::generate);
+
+fuzz_target!(|data: &[u8]| {
+    if data.len() < 32 {
+        return;
+    }
+    let (salt, rest) = data.split_at(16);
+    let (nonce, rest) = rest.split_at(12);
+    let (version, ciphertext) = rest.split_at(4);
+    let backup = EncryptedBackup {
+        salt: salt.try_into().expect("16 bytes"),
+        nonce: nonce.try_into().expect("12 bytes"),
+        ciphertext: ciphertext.to_vec(),
+        version: u32::from_le_bytes(version.try_into().expect("4 bytes")),
+    };
+    // Forged ciphertexts must be rejected, never panic
+    assert!(backup.decrypt(&KEY).is_err());
+});
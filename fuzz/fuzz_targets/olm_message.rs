@@ -0,0 +1,19 @@
+//! Fuzz `EncryptedMessage::to_olm_message` with arbitrary message types and
+//! ciphertexts, as received from the network.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use vc_crypto::olm::EncryptedMessage;
+
+fuzz_target!(|data: &[u8]| {
+    let Some((&message_type, ciphertext)) = data.split_first() else {
+        return;
+    };
+    let message = EncryptedMessage {
+        message_type,
+        ciphertext: String::from_utf8_lossy(ciphertext).into_owned(),
+    };
+    let _ = message.to_olm_message();
+    let _ = message.into_prekey_message();
+});
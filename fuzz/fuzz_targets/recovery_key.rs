@@ -0,0 +1,18 @@
+//! Fuzz `RecoveryKey::from_formatted_string` with user-entered text.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use vc_crypto::RecoveryKey;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(input) = std::str::from_utf8(data) else {
+        return;
+    };
+    if let Ok(key) = RecoveryKey::from_formatted_string(input) {
+        // Any accepted key must survive its own display format
+        let formatted = key.to_formatted_string();
+        let reparsed = RecoveryKey::from_formatted_string(&formatted).expect("round trip");
+        assert_eq!(reparsed.to_formatted_string(), formatted);
+    }
+});
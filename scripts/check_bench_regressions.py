@@ -0,0 +1,88 @@
+#!/usr/bin/env python3
+"""Check criterion results against the server's performance budgets.
+
+Reads `server/benches/thresholds.json` and the latest criterion estimates
+(`<target>/criterion/<bench id>/new/estimates.json`). Fails when a benchmark's
+mean exceeds its absolute budget, or (with `--baseline NAME`) when it is more
+than `max_regression_percent` slower than a baseline saved with
+`cargo bench -- --save-baseline NAME`.
+"""
+from __future__ import annotations
+
+import argparse
+import json
+import os
+import sys
+from pathlib import Path
+
+
+ROOT = Path(__file__).resolve().parents[1]
+THRESHOLDS = ROOT / "server/benches/thresholds.json"
+
+
+def criterion_dir() -> Path:
+    target = os.environ.get("CARGO_TARGET_DIR")
+    return (Path(target) if target else ROOT / "target") / "criterion"
+
+
+def read_mean_ns(path: Path) -> float | None:
+    if not path.exists():
+        return None
+    estimates = json.loads(path.read_text(encoding="utf-8"))
+    return float(estimates["mean"]["point_estimate"])
+
+
+def format_ns(value: float) -> str:
+    for unit, scale in (("s", 1e9), ("ms", 1e6), ("µs", 1e3)):
+        if value >= scale:
+            return f"{value / scale:.2f} {unit}"
+    return f"{value:.0f} ns"
+
+
+def main() -> int:
+    parser = argparse.ArgumentParser(description=__doc__.splitlines()[0])
+    parser.add_argument(
+        "--baseline",
+        help="Also compare against this saved criterion baseline",
+    )
+    args = parser.parse_args()
+
+    thresholds = json.loads(THRESHOLDS.read_text(encoding="utf-8"))
+    max_regression = float(thresholds["max_regression_percent"])
+    results = criterion_dir()
+    errors: list[str] = []
+
+    for bench_id, budget in thresholds["budgets_ns"].items():
+        mean = read_mean_ns(results / bench_id / "new" / "estimates.json")
+        if mean is None:
+            errors.append(f"{bench_id}: no results (run `cargo bench -p vc-server` first)")
+            continue
+
+        line = f"{bench_id}: {format_ns(mean)} (budget {format_ns(budget)})"
+        if mean > budget:
+            errors.append(f"{line} over budget")
+
+        if args.baseline:
+            base = read_mean_ns(results / bench_id / args.baseline / "estimates.json")
+            if base is None:
+                print(f"{line}, no '{args.baseline}' baseline")
+                continue
+            change = (mean - base) / base * 100
+            line = f"{line}, {change:+.1f}% vs {args.baseline}"
+            if change > max_regression:
+                errors.append(f"{line} exceeds {max_regression:.0f}% regression limit")
+
+        print(line)
+
+    if errors:
+        print("\nBenchmark regression check failed:")
+        for err in errors:
+            print(f"- {err}")
+        return 1
+
+    print("\nBenchmark regression check passed.")
+    return 0
+
+
+if __name__ == "__main__":
+    sys.exit(main())
@@ -0,0 +1,125 @@
+//! `FilterEngine` benchmarks.
+//!
+//! Sized for the largest guild configuration we expect: every built-in
+//! category enabled plus 10k custom keywords and 50 custom regexes. `check`
+//! runs on every message create, so its budgets are the ones that matter;
+//! `build` runs on each filter cache miss.
+//!
+//! Run with: `cargo bench -p vc-server --bench filter_engine`
+//! Budgets: `benches/thresholds.json` (checked by `scripts/check_bench_regressions.py`)
+
+use std::hint::black_box;
+
+use chrono::Utc;
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use uuid::Uuid;
+use vc_server::moderation::filter_engine::FilterEngine;
+use vc_server::moderation::filter_types::{
+    FilterAction, FilterCategory, GuildFilterConfig, GuildFilterPattern,
+};
+
+const KEYWORD_COUNT: usize = 10_000;
+const REGEX_COUNT: usize = 50;
+
+const SYLLABLES: [&str; 24] = [
+    "ka", "ri", "mo", "ne", "sha", "tor", "vel", "qu", "zan", "dri", "lo", "mek", "pra", "sun",
+    "thi", "gor", "wex", "ul", "bra", "cy", "fen", "hol", "jin", "yar",
+];
+
+fn configs(guild_id: Uuid) -> Vec<GuildFilterConfig> {
+    [
+        FilterCategory::Slurs,
+        FilterCategory::HateSpeech,
+        FilterCategory::Spam,
+        FilterCategory::AbusiveLanguage,
+    ]
+    .into_iter()
+    .map(|category| GuildFilterConfig {
+        id: Uuid::now_v7(),
+        guild_id,
+        category,
+        enabled: true,
+        action: FilterAction::Block,
+        link_mode: None,
+        created_at: Utc::now(),
+        updated_at: Utc::now(),
+    })
+    .collect()
+}
+
+fn pattern(guild_id: Uuid, pattern: String, is_regex: bool) -> GuildFilterPattern {
+    GuildFilterPattern {
+        id: Uuid::now_v7(),
+        guild_id,
+        pattern,
+        is_regex,
+        description: None,
+        enabled: true,
+        created_by: Uuid::nil(),
+        created_at: Utc::now(),
+        updated_at: Utc::now(),
+    }
+}
+
+/// Three-syllable pseudo words (`karimo`, `karine`, ...), deterministic.
+fn keyword(index: usize) -> String {
+    let n = SYLLABLES.len();
+    format!(
+        "{}{}{}",
+        SYLLABLES[index / (n * n) % n],
+        SYLLABLES[index / n % n],
+        SYLLABLES[index % n]
+    )
+}
+
+fn custom_patterns(guild_id: Uuid) -> Vec<GuildFilterPattern> {
+    let keywords = (0..KEYWORD_COUNT).map(|i| pattern(guild_id, keyword(i), false));
+    let regexes = (0..REGEX_COUNT).map(|i| {
+        let regex = match i % 3 {
+            0 => format!(r"(?i)\b{}\d{{2,4}}\b", keyword(i * 97)),
+            1 => format!(r"(?i)free\s+{}\s+(?:gift|nitro|skins)", keyword(i * 89)),
+            _ => format!(r"https?://[a-z0-9.-]*{}\.(?:ru|xyz|top)/", keyword(i * 83)),
+        };
+        pattern(guild_id, regex, true)
+    });
+    keywords.chain(regexes).collect()
+}
+
+fn messages() -> Vec<(&'static str, String)> {
+    let sentence = "Anyone up for a raid tonight? I can bring the healer build and a spare flask. ";
+    vec![
+        ("clean_short", "gg, see you all tomorrow".to_string()),
+        ("clean_long", sentence.repeat(2000 / sentence.len())),
+        (
+            "matching",
+            format!("free {} nitro here, also {}", keyword(89), keyword(4242)),
+        ),
+    ]
+}
+
+fn bench_filter_engine(c: &mut Criterion) {
+    let guild_id = Uuid::now_v7();
+    let configs = configs(guild_id);
+    let patterns = custom_patterns(guild_id);
+
+    let mut group = c.benchmark_group("filter_engine");
+
+    group.sample_size(20);
+    group.bench_function("build", |b| {
+        b.iter(|| FilterEngine::build(black_box(&configs), black_box(&patterns)).unwrap());
+    });
+
+    group.sample_size(100);
+    let engine = FilterEngine::build(&configs, &patterns).unwrap();
+    for (name, content) in messages() {
+        group.throughput(Throughput::Bytes(content.len() as u64));
+        group.bench_with_input(BenchmarkId::new("check", name), &content, |b, content| {
+            b.iter(|| engine.check(black_box(content)));
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_filter_engine);
+criterion_main!(benches);
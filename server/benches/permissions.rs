@@ -0,0 +1,113 @@
+//! Permission resolution benchmarks.
+//!
+//! `compute_guild_permissions` runs for every permission-checked request and
+//! every WebSocket subscription re-check. Measures role stacks from 1 up to
+//! 250 roles (5x the default `MAX_ROLES_PER_GUILD`), with and without a
+//! channel override per role.
+//!
+//! Run with: `cargo bench -p vc-server --bench permissions`
+//! Budgets: `benches/thresholds.json` (checked by `scripts/check_bench_regressions.py`)
+
+use std::hint::black_box;
+
+use chrono::Utc;
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use uuid::Uuid;
+use vc_server::permissions::{
+    compute_guild_permissions, ChannelOverride, GuildPermissions, GuildRole,
+};
+
+const ROLE_COUNTS: [usize; 4] = [1, 10, 50, 250];
+
+/// Roles with one distinct permission bit each, in reverse position order so
+/// resolution has to sort them.
+fn roles(guild_id: Uuid, count: usize) -> Vec<GuildRole> {
+    let bits: Vec<GuildPermissions> = GuildPermissions::all().iter().collect();
+    (0..count)
+        .rev()
+        .map(|i| GuildRole {
+            id: Uuid::now_v7(),
+            guild_id,
+            name: format!("Role {i}"),
+            color: None,
+            permissions: bits[i % bits.len()],
+            position: i as i32 + 1,
+            is_default: false,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        })
+        .collect()
+}
+
+/// One override per role, alternating between allowing and denying a bit.
+fn overrides(channel_id: Uuid, roles: &[GuildRole]) -> Vec<ChannelOverride> {
+    roles
+        .iter()
+        .enumerate()
+        .map(|(i, role)| {
+            let (allow, deny) = if i % 2 == 0 {
+                (GuildPermissions::SEND_MESSAGES, GuildPermissions::empty())
+            } else {
+                (
+                    GuildPermissions::empty(),
+                    GuildPermissions::MENTION_EVERYONE,
+                )
+            };
+            ChannelOverride {
+                id: Uuid::now_v7(),
+                channel_id,
+                role_id: role.id,
+                allow_permissions: allow,
+                deny_permissions: deny,
+            }
+        })
+        .collect()
+}
+
+fn bench_permissions(c: &mut Criterion) {
+    let guild_id = Uuid::now_v7();
+    let channel_id = Uuid::now_v7();
+    let owner_id = Uuid::now_v7();
+    let user_id = Uuid::now_v7();
+    let everyone = GuildPermissions::VIEW_CHANNEL | GuildPermissions::SEND_MESSAGES;
+
+    let mut group = c.benchmark_group("permissions");
+
+    for count in ROLE_COUNTS {
+        let roles = roles(guild_id, count);
+        let overrides = overrides(channel_id, &roles);
+
+        group.bench_with_input(BenchmarkId::new("guild", count), &roles, |b, roles| {
+            b.iter(|| {
+                compute_guild_permissions(
+                    black_box(user_id),
+                    owner_id,
+                    everyone,
+                    black_box(roles),
+                    None,
+                )
+            });
+        });
+
+        group.bench_with_input(
+            BenchmarkId::new("channel", count),
+            &(roles, overrides),
+            |b, (roles, overrides)| {
+                b.iter(|| {
+                    compute_guild_permissions(
+                        black_box(user_id),
+                        owner_id,
+                        everyone,
+                        black_box(roles),
+                        Some(black_box(overrides.as_slice())),
+                    )
+                });
+            },
+        );
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_permissions);
+criterion_main!(benches);
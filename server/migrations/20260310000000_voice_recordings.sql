@@ -0,0 +1,31 @@
+-- Server-side voice recordings with per-participant consent
+CREATE TABLE voice_recordings (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    channel_id UUID NOT NULL REFERENCES channels(id) ON DELETE CASCADE,
+    guild_id UUID REFERENCES guilds(id) ON DELETE CASCADE,
+    started_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    status TEXT NOT NULL DEFAULT 'recording'
+        CHECK (status IN ('recording', 'processing', 'completed', 'failed')),
+    s3_key TEXT,
+    size_bytes BIGINT,
+    duration_ms BIGINT,
+    track_count INTEGER NOT NULL DEFAULT 0,
+    started_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    ended_at TIMESTAMPTZ
+);
+
+CREATE INDEX idx_voice_recordings_channel ON voice_recordings(channel_id, started_at DESC);
+CREATE INDEX idx_voice_recordings_guild ON voice_recordings(guild_id, started_at DESC);
+
+-- Only one active recording per channel
+CREATE UNIQUE INDEX idx_voice_recordings_active
+    ON voice_recordings(channel_id) WHERE status = 'recording';
+
+-- Consent decisions per participant (only consenting participants are captured)
+CREATE TABLE voice_recording_consents (
+    recording_id UUID NOT NULL REFERENCES voice_recordings(id) ON DELETE CASCADE,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    consented BOOLEAN NOT NULL,
+    responded_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (recording_id, user_id)
+);
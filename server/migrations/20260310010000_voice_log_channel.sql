@@ -0,0 +1,2 @@
+-- Optional text channel receiving batched "joined/left voice" posts
+ALTER TABLE guilds ADD COLUMN voice_log_channel_id UUID REFERENCES channels(id) ON DELETE SET NULL;
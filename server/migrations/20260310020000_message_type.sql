@@ -0,0 +1,11 @@
+-- Distinguish server-generated system messages from user content
+CREATE TYPE message_type AS ENUM (
+    'default',
+    'system_join',
+    'system_pin',
+    'system_call',
+    'system_boost',
+    'system_voice'
+);
+
+ALTER TABLE messages ADD COLUMN message_type message_type NOT NULL DEFAULT 'default';
@@ -0,0 +1,8 @@
+-- Per-member supporter flag that unlocks guild perks (NULL = not a supporter)
+ALTER TABLE guild_members
+    ADD COLUMN supporter_since TIMESTAMPTZ,
+    ADD COLUMN supporter_source VARCHAR(16)
+        CHECK (supporter_source IN ('manual', 'billing'));
+
+CREATE INDEX idx_guild_members_supporters
+    ON guild_members(guild_id) WHERE supporter_since IS NOT NULL;
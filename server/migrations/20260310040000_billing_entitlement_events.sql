@@ -0,0 +1,12 @@
+-- Billing entitlement webhook events
+-- Records every applied event so retries from the billing system are idempotent
+-- and entitlement changes can be reconciled later.
+CREATE TABLE billing_entitlement_events (
+    event_id VARCHAR(128) PRIMARY KEY,
+    guild_id UUID NOT NULL REFERENCES guilds(id) ON DELETE CASCADE,
+    payload JSONB NOT NULL,
+    received_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_billing_entitlement_events_guild
+    ON billing_entitlement_events (guild_id, received_at DESC);
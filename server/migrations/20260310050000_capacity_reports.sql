@@ -0,0 +1,12 @@
+-- Daily capacity reports for hardware planning
+-- One row per UTC day, written by the nightly capacity report job.
+CREATE TABLE capacity_reports (
+    report_date DATE PRIMARY KEY,
+    daily_active_users BIGINT NOT NULL,
+    peak_ws_connections BIGINT NOT NULL,
+    peak_voice_sessions BIGINT NOT NULL,
+    storage_bytes BIGINT NOT NULL,
+    storage_growth_bytes BIGINT NOT NULL,
+    db_size_bytes BIGINT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
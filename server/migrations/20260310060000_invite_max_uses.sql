@@ -0,0 +1,4 @@
+-- Limited-use guild invites
+-- NULL max_uses means unlimited; use_count is claimed atomically on join.
+ALTER TABLE guild_invites
+    ADD COLUMN max_uses INTEGER CHECK (max_uses IS NULL OR max_uses > 0);
@@ -0,0 +1,8 @@
+-- Timed member mutes (NULL = not timed out)
+-- Enforcement compares against NOW(); the sweeper only clears expired rows
+-- and notifies clients.
+ALTER TABLE guild_members
+    ADD COLUMN timeout_until TIMESTAMPTZ;
+
+CREATE INDEX idx_guild_members_timeout_until
+    ON guild_members(timeout_until) WHERE timeout_until IS NOT NULL;
@@ -0,0 +1,26 @@
+-- Per-guild audit log for administrative actions (settings, roles, channels,
+-- bans, filters). Visible to members with VIEW_AUDIT_LOG.
+CREATE TABLE guild_audit_log (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    guild_id UUID NOT NULL REFERENCES guilds(id) ON DELETE CASCADE,
+    actor_id UUID REFERENCES users(id) ON DELETE SET NULL,
+    action VARCHAR(64) NOT NULL,
+    target_type VARCHAR(32),
+    target_id UUID,
+    changes JSONB,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_guild_audit_log_guild
+    ON guild_audit_log(guild_id, created_at DESC);
+CREATE INDEX idx_guild_audit_log_actor
+    ON guild_audit_log(guild_id, actor_id, created_at DESC);
+CREATE INDEX idx_guild_audit_log_action
+    ON guild_audit_log(guild_id, action, created_at DESC);
+
+-- Carry over guild actions previously written to the system audit log
+INSERT INTO guild_audit_log (guild_id, actor_id, action, changes, created_at)
+SELECT s.target_id, s.actor_id, s.action, s.details, s.created_at
+FROM system_audit_log s
+JOIN guilds g ON g.id = s.target_id
+WHERE s.target_type = 'guild' AND s.action LIKE 'guild.%';
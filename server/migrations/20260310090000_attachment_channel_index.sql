@@ -0,0 +1,32 @@
+-- Denormalize the channel onto attachments so a channel's media gallery can be
+-- paged from one index instead of walking the message history.
+ALTER TABLE file_attachments
+    ADD COLUMN channel_id UUID;
+
+UPDATE file_attachments fa
+SET channel_id = m.channel_id
+FROM messages m
+WHERE m.id = fa.message_id;
+
+ALTER TABLE file_attachments
+    ALTER COLUMN channel_id SET NOT NULL,
+    ADD CONSTRAINT fk_file_attachments_channel
+        FOREIGN KEY (channel_id) REFERENCES channels(id) ON DELETE CASCADE;
+
+-- Fill channel_id from the parent message so insert sites need no changes
+CREATE OR REPLACE FUNCTION set_file_attachment_channel()
+RETURNS trigger AS $$
+BEGIN
+    IF NEW.channel_id IS NULL THEN
+        SELECT channel_id INTO NEW.channel_id FROM messages WHERE id = NEW.message_id;
+    END IF;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER trg_file_attachments_channel
+    BEFORE INSERT ON file_attachments
+    FOR EACH ROW EXECUTE FUNCTION set_file_attachment_channel();
+
+CREATE INDEX idx_file_attachments_channel_created
+    ON file_attachments(channel_id, created_at DESC, id DESC);
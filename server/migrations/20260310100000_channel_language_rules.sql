@@ -0,0 +1,19 @@
+-- Per-channel language rules for the content filter pipeline.
+-- Messages are language-detected server-side; a message in a language outside
+-- `allowed_languages` (ISO 639-3 codes, e.g. 'eng') triggers `action`.
+ALTER TYPE filter_category ADD VALUE IF NOT EXISTS 'language';
+
+CREATE TABLE guild_channel_language_rules (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    guild_id UUID NOT NULL REFERENCES guilds(id) ON DELETE CASCADE,
+    channel_id UUID NOT NULL REFERENCES channels(id) ON DELETE CASCADE,
+    allowed_languages TEXT[] NOT NULL,
+    action filter_action NOT NULL DEFAULT 'warn',
+    enabled BOOLEAN NOT NULL DEFAULT true,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE(channel_id),
+    CHECK (cardinality(allowed_languages) > 0)
+);
+
+CREATE INDEX idx_guild_channel_language_rules_guild ON guild_channel_language_rules(guild_id);
@@ -0,0 +1,15 @@
+-- Read-only feed tokens for channel JSON Feed / Atom export.
+-- Only the SHA-256 hash of the token is stored; the raw token is shown once
+-- at creation. Feeds stop working when the token is deleted or its creator
+-- loses access to the channel.
+CREATE TABLE channel_feed_tokens (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    channel_id UUID NOT NULL REFERENCES channels(id) ON DELETE CASCADE,
+    created_by UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    name VARCHAR(64) NOT NULL,
+    token_hash TEXT NOT NULL UNIQUE,
+    last_used_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_channel_feed_tokens_channel ON channel_feed_tokens(channel_id);
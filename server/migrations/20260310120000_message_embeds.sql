@@ -0,0 +1,4 @@
+-- Link previews unfurled from URLs in message content.
+-- Filled asynchronously after the message is created or edited; an array of
+-- `{url, title, description, site_name, image_url}` objects.
+ALTER TABLE messages ADD COLUMN embeds JSONB NOT NULL DEFAULT '[]'::jsonb;
@@ -0,0 +1,21 @@
+-- CSV member role imports, processed in the background.
+-- The per-row result report is kept as CSV text and can be downloaded once
+-- the job completes.
+CREATE TABLE guild_member_import_jobs (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    guild_id UUID NOT NULL REFERENCES guilds(id) ON DELETE CASCADE,
+    created_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    status VARCHAR(20) NOT NULL DEFAULT 'pending'
+        CHECK (status IN ('pending', 'processing', 'completed', 'failed')),
+    total_rows INTEGER NOT NULL DEFAULT 0,
+    succeeded_rows INTEGER NOT NULL DEFAULT 0,
+    failed_rows INTEGER NOT NULL DEFAULT 0,
+    report TEXT,
+    error_message TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    completed_at TIMESTAMPTZ
+);
+
+CREATE INDEX idx_guild_member_import_jobs_guild ON guild_member_import_jobs(guild_id, created_at DESC);
+CREATE UNIQUE INDEX idx_guild_member_import_jobs_one_active
+    ON guild_member_import_jobs(guild_id) WHERE status IN ('pending', 'processing');
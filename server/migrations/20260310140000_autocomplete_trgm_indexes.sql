@@ -0,0 +1,19 @@
+-- Trigram indexes for mention autocomplete (GET /api/guilds/{id}/autocomplete).
+-- gin_trgm_ops serves the case-insensitive prefix matches (ILIKE 'abc%')
+-- used for member and channel suggestions. Roles are few per guild and are
+-- matched through the existing guild_id index.
+CREATE EXTENSION IF NOT EXISTS pg_trgm;
+
+CREATE INDEX IF NOT EXISTS idx_users_username_trgm
+    ON users USING GIN (username gin_trgm_ops);
+CREATE INDEX IF NOT EXISTS idx_users_display_name_trgm
+    ON users USING GIN (display_name gin_trgm_ops);
+CREATE INDEX IF NOT EXISTS idx_guild_members_nickname_trgm
+    ON guild_members USING GIN (nickname gin_trgm_ops)
+    WHERE nickname IS NOT NULL;
+CREATE INDEX IF NOT EXISTS idx_channels_name_trgm
+    ON channels USING GIN (name gin_trgm_ops);
+
+-- Latest message per author, for ranking members by recent activity.
+CREATE INDEX IF NOT EXISTS idx_messages_user_created
+    ON messages (user_id, created_at DESC);
@@ -0,0 +1,18 @@
+-- Command Center: per-consumer usage (top guilds/users by API volume).
+-- Hourly API request and WebSocket event counts per user and per guild,
+-- flushed from in-process counters. Purged with the other telemetry tables
+-- after 30 days.
+CREATE TABLE telemetry_consumer_usage (
+    bucket        TIMESTAMPTZ NOT NULL,
+    consumer_type TEXT        NOT NULL CHECK (consumer_type IN ('user', 'guild')),
+    consumer_id   UUID        NOT NULL,
+    http_requests BIGINT      NOT NULL DEFAULT 0,
+    ws_events     BIGINT      NOT NULL DEFAULT 0,
+    PRIMARY KEY (bucket, consumer_type, consumer_id)
+);
+
+CREATE INDEX idx_tcu_type_bucket ON telemetry_consumer_usage (consumer_type, bucket DESC);
+
+-- Voice minutes per guild are summed from connection_sessions by time range.
+CREATE INDEX IF NOT EXISTS idx_sessions_guild_time
+    ON connection_sessions (guild_id, started_at DESC) WHERE guild_id IS NOT NULL;
@@ -0,0 +1,19 @@
+-- Command Center: WebSocket disconnect audit (reconnect storm diagnosis).
+-- One row per closed gateway connection, or per handshake rejected because
+-- the access token had expired. Purged with the other telemetry tables after
+-- 30 days.
+CREATE TABLE telemetry_ws_disconnects (
+    id           BIGSERIAL        PRIMARY KEY,
+    ts           TIMESTAMPTZ      NOT NULL DEFAULT NOW(),
+    user_id      UUID,
+    cause        TEXT             NOT NULL CHECK (cause IN (
+                     'client_close', 'connection_lost', 'timeout',
+                     'rate_limited', 'auth_expired', 'server_shutdown'
+                 )),
+    close_code   INTEGER,
+    session_secs DOUBLE PRECISION
+);
+
+CREATE INDEX idx_twd_ts ON telemetry_ws_disconnects (ts DESC);
+CREATE INDEX idx_twd_user_ts ON telemetry_ws_disconnects (user_id, ts DESC)
+    WHERE user_id IS NOT NULL;
@@ -0,0 +1,24 @@
+-- Push notification devices.
+-- One row per browser subscription (Web Push) or mobile registration token
+-- (FCM). The push worker delivers mentions, DMs and incoming calls here while
+-- the user has no open WebSocket connection. Tokens the push service reports
+-- as gone are deleted by the worker.
+CREATE TYPE push_platform AS ENUM ('web_push', 'fcm');
+
+CREATE TABLE push_devices (
+    id           UUID          PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id      UUID          NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    platform     push_platform NOT NULL,
+    -- Web Push: subscription endpoint URL. FCM: registration token.
+    token        TEXT          NOT NULL,
+    -- Web Push only: client public key (P-256, base64url) and auth secret.
+    p256dh       TEXT,
+    auth_secret  TEXT,
+    device_name  VARCHAR(64),
+    created_at   TIMESTAMPTZ   NOT NULL DEFAULT NOW(),
+    last_used_at TIMESTAMPTZ,
+    UNIQUE (platform, token),
+    CHECK (platform <> 'web_push' OR (p256dh IS NOT NULL AND auth_secret IS NOT NULL))
+);
+
+CREATE INDEX idx_push_devices_user ON push_devices (user_id);
@@ -0,0 +1,24 @@
+-- Incoming webhooks: channel-scoped tokens that external services POST JSON
+-- to in order to create messages under a custom name and avatar.
+-- Only the SHA-256 hash of the token is stored; the raw token is shown once
+-- at creation. Messages keep the name and avatar they were posted with, so
+-- they still render after the webhook is deleted.
+CREATE TABLE channel_incoming_webhooks (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    channel_id UUID NOT NULL REFERENCES channels(id) ON DELETE CASCADE,
+    created_by UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    name VARCHAR(80) NOT NULL,
+    avatar_url TEXT,
+    token_hash TEXT NOT NULL UNIQUE,
+    last_used_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_channel_incoming_webhooks_channel ON channel_incoming_webhooks(channel_id);
+
+ALTER TABLE messages
+    ADD COLUMN webhook_id UUID REFERENCES channel_incoming_webhooks(id) ON DELETE SET NULL,
+    ADD COLUMN webhook_name VARCHAR(80),
+    ADD COLUMN webhook_avatar_url TEXT;
+
+CREATE INDEX idx_messages_webhook ON messages(webhook_id) WHERE webhook_id IS NOT NULL;
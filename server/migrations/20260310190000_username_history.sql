@@ -0,0 +1,14 @@
+-- Username history: one row per username change.
+-- Rows drive the change cooldown, keep a recently released username held
+-- for its previous owner (so @mentions of the old name still reach them),
+-- and give admins an audit trail of who was known under which name.
+CREATE TABLE username_history (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    old_username VARCHAR(32) NOT NULL,
+    new_username VARCHAR(32) NOT NULL,
+    changed_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_username_history_user ON username_history(user_id, changed_at DESC);
+CREATE INDEX idx_username_history_old_username ON username_history(old_username, changed_at DESC);
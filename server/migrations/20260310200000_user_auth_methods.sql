@@ -0,0 +1,34 @@
+-- Linked sign-in identities: an account can hold a password and any number
+-- of external identities (OIDC today, LDAP later) at the same time.
+--
+-- The password itself stays in users.password_hash; rows here are the
+-- external identities, keyed by the provider-qualified external ID that
+-- used to live in users.external_id ("{provider_slug}:{subject}").
+ALTER TYPE auth_method ADD VALUE IF NOT EXISTS 'ldap';
+
+CREATE TABLE user_auth_methods (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    method auth_method NOT NULL,
+    provider VARCHAR(64) NOT NULL,
+    external_id VARCHAR(255) NOT NULL UNIQUE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    last_used_at TIMESTAMPTZ,
+
+    CONSTRAINT external_method CHECK (method != 'local'),
+    -- One identity per provider per account
+    CONSTRAINT one_identity_per_provider UNIQUE (user_id, provider)
+);
+
+CREATE INDEX idx_user_auth_methods_user ON user_auth_methods(user_id);
+
+INSERT INTO user_auth_methods (user_id, method, provider, external_id, created_at)
+SELECT id, 'oidc', split_part(external_id, ':', 1), external_id, created_at
+FROM users
+WHERE auth_method = 'oidc' AND external_id IS NOT NULL;
+
+ALTER TABLE users DROP CONSTRAINT local_user_has_password;
+ALTER TABLE users DROP CONSTRAINT oidc_user_has_external_id;
+DROP INDEX idx_users_external_id;
+ALTER TABLE users DROP COLUMN external_id;
+ALTER TABLE users DROP COLUMN auth_method;
@@ -0,0 +1,9 @@
+-- Audio playout stats on connection metrics.
+-- Concealment and jitter-buffer figures describe received audio and the
+-- audio level describes the reporter's outgoing microphone, so a session's
+-- detail view can separate local network trouble from a remote sender's.
+-- Nullable: clients that predate these stats keep reporting without them.
+ALTER TABLE connection_metrics
+    ADD COLUMN IF NOT EXISTS concealment_events BIGINT,   -- Concealment events since the previous sample
+    ADD COLUMN IF NOT EXISTS jitter_buffer_ms   SMALLINT, -- Average jitter-buffer delay in milliseconds
+    ADD COLUMN IF NOT EXISTS audio_level        REAL;     -- Outgoing audio level (0.0 - 1.0)
@@ -0,0 +1,17 @@
+-- WebAuthn passkeys: each row is one registered authenticator credential.
+--
+-- The serialized webauthn-rs Passkey (public key, signature counter, backup
+-- state) lives in `credential` and is rewritten after every successful
+-- assertion. `credential_id` is kept separately so a credential can only
+-- ever belong to one account.
+CREATE TABLE webauthn_credentials (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    credential_id BYTEA NOT NULL UNIQUE,
+    name VARCHAR(64) NOT NULL,
+    credential JSONB NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    last_used_at TIMESTAMPTZ
+);
+
+CREATE INDEX idx_webauthn_credentials_user ON webauthn_credentials(user_id);
@@ -0,0 +1,15 @@
+-- Per-provider sign-in policy for OIDC identities that aren't linked yet.
+--
+-- provisioning decides whether an unknown identity gets a new account:
+--   registration_policy  follow the server-wide registration policy
+--   always               create accounts even when registration is closed
+--                        (e.g. a company IdP that already gates who can sign in)
+--   never                only identities linked to an existing account may sign in
+--
+-- link_by_email attaches an unknown identity to the existing account with the
+-- same email, but only when the provider marks that email as verified.
+ALTER TABLE oidc_providers
+    ADD COLUMN provisioning VARCHAR(32) NOT NULL DEFAULT 'registration_policy',
+    ADD COLUMN link_by_email BOOLEAN NOT NULL DEFAULT false,
+    ADD CONSTRAINT valid_provisioning
+        CHECK (provisioning IN ('registration_policy', 'always', 'never'));
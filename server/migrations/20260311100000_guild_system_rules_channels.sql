@@ -0,0 +1,7 @@
+-- Channels a guild designates for newcomers:
+--   system_channel_id  where join announcements go and where members land by default
+--   rules_channel_id   shown first to members who just joined
+-- Deleting the channel clears the designation.
+ALTER TABLE guilds
+    ADD COLUMN system_channel_id UUID REFERENCES channels(id) ON DELETE SET NULL,
+    ADD COLUMN rules_channel_id UUID REFERENCES channels(id) ON DELETE SET NULL;
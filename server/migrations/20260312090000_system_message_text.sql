@@ -0,0 +1,4 @@
+-- Catalog reference for server-generated system messages:
+--   system_text  {"id": "<fluent message id>", "args": {...}}, re-rendered per reader locale
+-- `content` keeps the English rendering for search, exports and older clients.
+ALTER TABLE messages ADD COLUMN system_text JSONB;
@@ -0,0 +1,29 @@
+-- Channel transcript exports (JSONL or HTML) requested by guild managers.
+-- channel_id is cleared when the channel is deleted so expiry cleanup can
+-- still remove the archive from S3.
+CREATE TABLE channel_export_jobs (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    channel_id UUID REFERENCES channels(id) ON DELETE SET NULL,
+    requested_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    format VARCHAR(10) NOT NULL CHECK (format IN ('jsonl', 'html')),
+    attachments VARCHAR(10) NOT NULL DEFAULT 'links'
+        CHECK (attachments IN ('links', 'bundled')),
+    range_from TIMESTAMPTZ,
+    range_to TIMESTAMPTZ,
+    status VARCHAR(20) NOT NULL DEFAULT 'pending'
+        CHECK (status IN ('pending', 'processing', 'completed', 'failed', 'expired')),
+    messages_total BIGINT,
+    messages_exported BIGINT NOT NULL DEFAULT 0,
+    s3_key TEXT,
+    file_size_bytes BIGINT,
+    error_message TEXT,
+    expires_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    -- Bumped with every progress update; stale-job recovery keys off it
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    completed_at TIMESTAMPTZ
+);
+
+CREATE INDEX idx_channel_export_jobs_channel ON channel_export_jobs(channel_id, created_at DESC);
+CREATE UNIQUE INDEX idx_channel_export_jobs_one_active
+    ON channel_export_jobs(channel_id) WHERE status IN ('pending', 'processing');
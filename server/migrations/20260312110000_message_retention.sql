@@ -0,0 +1,6 @@
+-- Message retention policies. A channel's setting overrides its guild's;
+-- NULL on both keeps messages forever.
+ALTER TABLE guilds ADD COLUMN retention_days INTEGER
+    CHECK (retention_days BETWEEN 1 AND 3650);
+ALTER TABLE channels ADD COLUMN retention_days INTEGER
+    CHECK (retention_days BETWEEN 1 AND 3650);
@@ -0,0 +1,13 @@
+-- Deleted-user tombstones. When an account is deleted its messages are
+-- attributed to a tombstone instead, so clients can still group a deleted
+-- author's messages ("Deleted User") without learning who it was.
+CREATE TABLE user_tombstones (
+    -- Fresh id, unrelated to the deleted user's id
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    -- DELETED_USER_CONTENT policy applied: anonymize or delete
+    content_policy VARCHAR(20) NOT NULL CHECK (content_policy IN ('anonymize', 'delete')),
+    deleted_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+ALTER TABLE messages ADD COLUMN author_tombstone_id UUID
+    REFERENCES user_tombstones(id) ON DELETE SET NULL;
@@ -0,0 +1,12 @@
+-- Per-channel message drafts synced across a user's devices.
+-- A cleared draft keeps its row (empty content) for a while so that an older
+-- save from another device cannot bring it back.
+CREATE TABLE message_drafts (
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    channel_id UUID NOT NULL REFERENCES channels(id) ON DELETE CASCADE,
+    content TEXT NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL,
+    PRIMARY KEY (user_id, channel_id)
+);
+
+CREATE INDEX idx_message_drafts_cleared ON message_drafts(updated_at) WHERE content = '';
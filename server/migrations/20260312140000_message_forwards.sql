@@ -0,0 +1,12 @@
+-- Forwarded messages keep a reference to the message they were copied from,
+-- so clients can render a "forwarded from" header. The author, channel and
+-- timestamp are copied as well: the original may be deleted or purged later.
+ALTER TABLE messages
+    ADD COLUMN forwarded_from_message_id UUID REFERENCES messages(id) ON DELETE SET NULL,
+    ADD COLUMN forwarded_from_channel_id UUID REFERENCES channels(id) ON DELETE SET NULL,
+    ADD COLUMN forwarded_from_author_id UUID REFERENCES users(id) ON DELETE SET NULL,
+    ADD COLUMN forwarded_from_created_at TIMESTAMPTZ;
+
+-- Keeps ON DELETE SET NULL cheap when an original message is deleted
+CREATE INDEX idx_messages_forwarded_from ON messages (forwarded_from_message_id)
+    WHERE forwarded_from_message_id IS NOT NULL;
@@ -0,0 +1,9 @@
+-- Settings versions for optimistic concurrency. Each PATCH of a guild,
+-- channel, role or page bumps its version; an update based on an older
+-- version is rejected instead of silently overwriting the newer change.
+-- (updated_at is not usable for this: guilds.updated_at also moves when the
+-- member count trigger runs.)
+ALTER TABLE guilds ADD COLUMN version INTEGER NOT NULL DEFAULT 1;
+ALTER TABLE channels ADD COLUMN version INTEGER NOT NULL DEFAULT 1;
+ALTER TABLE guild_roles ADD COLUMN version INTEGER NOT NULL DEFAULT 1;
+ALTER TABLE pages ADD COLUMN version INTEGER NOT NULL DEFAULT 1;
@@ -0,0 +1,15 @@
+-- Messages pinned to a channel by moderators (or DM participants). Pins are
+-- capped per channel by the server; soft-deleted messages are hidden from the
+-- pin list and no longer count towards the cap.
+CREATE TABLE channel_pins (
+    channel_id UUID NOT NULL REFERENCES channels(id) ON DELETE CASCADE,
+    message_id UUID NOT NULL REFERENCES messages(id) ON DELETE CASCADE,
+    pinned_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    pinned_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (channel_id, message_id)
+);
+
+CREATE INDEX idx_channel_pins_channel_pinned_at ON channel_pins (channel_id, pinned_at DESC);
+
+-- Keeps ON DELETE CASCADE cheap when a message is purged
+CREATE INDEX idx_channel_pins_message ON channel_pins (message_id);
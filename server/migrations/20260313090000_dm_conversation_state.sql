@@ -0,0 +1,12 @@
+-- Per-user organization of DM conversations. The state lives on the
+-- participant row, so leaving a conversation discards it.
+--
+-- hidden_at: conversation hidden from the DM list; it reappears once a
+--   message newer than this arrives.
+-- pinned_at: pinned to the top of the DM list (most recently pinned first).
+-- unread_kept: marked unread by the user; stays unread until read explicitly.
+ALTER TABLE dm_participants
+    ADD COLUMN hidden_at TIMESTAMPTZ,
+    ADD COLUMN archived BOOLEAN NOT NULL DEFAULT FALSE,
+    ADD COLUMN pinned_at TIMESTAMPTZ,
+    ADD COLUMN unread_kept BOOLEAN NOT NULL DEFAULT FALSE;
@@ -0,0 +1,3 @@
+-- Phonetic spelling of a user's display name, read out by screen readers
+-- (e.g. when announcing who joined a voice channel).
+ALTER TABLE users ADD COLUMN name_pronunciation VARCHAR(64);
@@ -0,0 +1,14 @@
+-- Text chat in voice channels
+--
+-- Voice channel messages need the VOICE_TEXT_CHAT permission (bit 26) instead
+-- of SEND_MESSAGES. Roles that can send messages today get it, so voice chat
+-- works out of the box; admins can remove it per role or per channel.
+UPDATE guild_roles
+SET permissions = permissions | (1::bigint << 26)
+WHERE permissions & 1 = 1;
+
+-- Voice chat is short-lived: voice channels keep messages for one day unless
+-- an admin sets another retention. New voice channels get this at creation.
+UPDATE channels
+SET retention_days = 1
+WHERE channel_type = 'voice' AND retention_days IS NULL;
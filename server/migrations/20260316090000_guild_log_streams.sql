@@ -0,0 +1,16 @@
+-- Guild moderation log streams
+--
+-- A guild can stream its audit log to an external endpoint. Streams are rows
+-- in `webhooks` owned by a guild instead of a bot application, so they share
+-- the delivery queue, retries, delivery log and dead letters.
+
+ALTER TYPE webhook_event_type ADD VALUE IF NOT EXISTS 'audit.logged';
+
+ALTER TABLE webhooks
+    ALTER COLUMN application_id DROP NOT NULL,
+    ADD COLUMN guild_id UUID REFERENCES guilds(id) ON DELETE CASCADE,
+    ADD CONSTRAINT webhooks_single_owner
+        CHECK ((application_id IS NULL) <> (guild_id IS NULL));
+
+-- At most one stream per guild
+CREATE UNIQUE INDEX idx_webhooks_guild_log_stream ON webhooks(guild_id) WHERE guild_id IS NOT NULL;
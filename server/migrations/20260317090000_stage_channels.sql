@@ -0,0 +1,6 @@
+-- Stage channels
+--
+-- A stage is a voice channel where only speakers publish audio. Members join
+-- as listeners and raise their hand to ask a moderator to let them speak.
+
+ALTER TYPE channel_type ADD VALUE IF NOT EXISTS 'stage';
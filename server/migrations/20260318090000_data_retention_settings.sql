@@ -0,0 +1,26 @@
+-- Instance data retention settings
+--
+-- Admins configure how long telemetry, messages, deleted messages and voice
+-- connection metrics are kept through GET/PATCH /api/admin/retention. The
+-- background jobs read these values on every run.
+
+INSERT INTO server_config (key, value)
+VALUES (
+    'data_retention',
+    '{"telemetry_days": 30, "message_default_days": null, "message_max_days": 3650,
+      "soft_delete_purge_days": 30, "connection_metrics_days": 7}'::jsonb
+)
+ON CONFLICT (key) DO NOTHING;
+
+-- Connection metrics were dropped by a fixed 7-day TimescaleDB policy; the
+-- telemetry retention job now purges them using connection_metrics_days.
+DO $$
+BEGIN
+    IF EXISTS(SELECT 1 FROM pg_extension WHERE extname = 'timescaledb') THEN
+        BEGIN
+            PERFORM remove_retention_policy('connection_metrics', if_exists => TRUE);
+        EXCEPTION WHEN OTHERS THEN
+            RAISE NOTICE 'Could not remove retention policy: %', SQLERRM;
+        END;
+    END IF;
+END $$;
@@ -0,0 +1,7 @@
+-- Noise suppression on connection sessions
+--
+-- Clients report at voice join whether they noise-suppress their microphone,
+-- so connection diagnostics can tell it apart from network problems. NULL for
+-- sessions recorded before clients reported it.
+
+ALTER TABLE connection_sessions ADD COLUMN IF NOT EXISTS noise_suppression BOOLEAN;
@@ -0,0 +1,7 @@
+-- Preferred voice region per guild
+--
+-- New voice rooms of the guild are hosted by a live cluster node whose
+-- NODE_REGION matches. NULL = no preference (rooms stay on the node the first
+-- participant joins through).
+
+ALTER TABLE guilds ADD COLUMN IF NOT EXISTS voice_region TEXT;
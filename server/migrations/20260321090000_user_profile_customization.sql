@@ -0,0 +1,8 @@
+-- Profile customization shown on user profile cards: a banner image,
+-- an about-me text (markdown, raw HTML stripped on save), pronouns and an
+-- accent colour (#rrggbb).
+ALTER TABLE users
+    ADD COLUMN banner_url TEXT,
+    ADD COLUMN about_me VARCHAR(1000),
+    ADD COLUMN pronouns VARCHAR(40),
+    ADD COLUMN accent_color VARCHAR(7);
@@ -0,0 +1,11 @@
+-- Per-guild member profiles
+--
+-- Members can set a guild avatar next to their guild nickname. Changing other
+-- members' nicknames and resetting their guild avatars needs the new
+-- MANAGE_NICKNAMES permission (bit 27); roles that can kick get it, matching
+-- the moderator preset.
+ALTER TABLE guild_members ADD COLUMN avatar_url TEXT;
+
+UPDATE guild_roles
+SET permissions = permissions | (1::bigint << 27)
+WHERE permissions & (1::bigint << 12) <> 0;
@@ -0,0 +1,23 @@
+-- Guild onboarding
+--
+-- A welcome screen (description and highlighted channels), an optional rules
+-- page new members must accept before posting, and roles handed out once
+-- onboarding is completed.
+CREATE TABLE guild_onboarding (
+    guild_id UUID PRIMARY KEY REFERENCES guilds(id) ON DELETE CASCADE,
+    enabled BOOLEAN NOT NULL DEFAULT FALSE,
+    welcome_description TEXT,
+    -- [{ "channel_id", "description" }]
+    welcome_channels JSONB NOT NULL DEFAULT '[]',
+    rules_page_id UUID REFERENCES pages(id) ON DELETE SET NULL,
+    default_role_ids UUID[] NOT NULL DEFAULT '{}',
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Members who joined while a rules page was required cannot post until they
+-- complete onboarding. Existing members count as onboarded.
+ALTER TABLE guild_members
+    ADD COLUMN pending BOOLEAN NOT NULL DEFAULT FALSE,
+    ADD COLUMN onboarded_at TIMESTAMPTZ;
+
+UPDATE guild_members SET onboarded_at = joined_at;
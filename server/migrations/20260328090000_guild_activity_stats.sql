@@ -0,0 +1,14 @@
+-- Guild activity stats for trending discovery
+--
+-- Recomputed nightly for discoverable guilds. `trending_score` blends member
+-- growth and message activity over the trailing seven days.
+CREATE TABLE guild_activity_stats (
+    guild_id UUID PRIMARY KEY REFERENCES guilds(id) ON DELETE CASCADE,
+    member_growth_7d INTEGER NOT NULL DEFAULT 0,
+    messages_7d BIGINT NOT NULL DEFAULT 0,
+    trending_score DOUBLE PRECISION NOT NULL DEFAULT 0,
+    computed_on DATE NOT NULL,
+    computed_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_guild_activity_stats_score ON guild_activity_stats (trending_score DESC);
@@ -0,0 +1,30 @@
+-- Discovery review queue
+--
+-- Marking a guild discoverable submits it for review; it is only listed once
+-- a system admin approves it. Listed guilds that collect enough reports are
+-- delisted and return to the queue.
+ALTER TABLE guilds
+    ADD COLUMN discovery_status TEXT NOT NULL DEFAULT 'none'
+        CHECK (discovery_status IN ('none', 'pending', 'approved', 'rejected', 'delisted')),
+    ADD COLUMN discovery_submitted_at TIMESTAMPTZ,
+    ADD COLUMN discovery_reviewed_at TIMESTAMPTZ,
+    ADD COLUMN discovery_reviewed_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    ADD COLUMN discovery_rejection_reason TEXT;
+
+-- Guilds listed before review existed stay listed.
+UPDATE guilds SET discovery_status = 'approved', discovery_reviewed_at = NOW()
+WHERE discoverable = true;
+
+CREATE INDEX idx_guilds_discovery_queue ON guilds (discovery_submitted_at)
+    WHERE discovery_status IN ('pending', 'delisted');
+
+-- Reports against a discovery listing, one per reporter (re-reporting
+-- refreshes it). Only reports newer than the last review count toward
+-- automatic delisting.
+CREATE TABLE guild_discovery_reports (
+    guild_id UUID NOT NULL REFERENCES guilds(id) ON DELETE CASCADE,
+    reporter_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    reason TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (guild_id, reporter_id)
+);
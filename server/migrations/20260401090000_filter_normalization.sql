@@ -0,0 +1,10 @@
+-- Per-guild obfuscation normalization for the content filter.
+-- Each stage folds message content (and keywords) before keyword matching so
+-- disguised words such as "b@dw0rd" still match. All stages are opt-in.
+CREATE TABLE guild_filter_settings (
+    guild_id UUID PRIMARY KEY REFERENCES guilds(id) ON DELETE CASCADE,
+    strip_invisible BOOLEAN NOT NULL DEFAULT false,
+    fold_confusables BOOLEAN NOT NULL DEFAULT false,
+    substitute_leetspeak BOOLEAN NOT NULL DEFAULT false,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
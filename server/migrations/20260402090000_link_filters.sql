@@ -0,0 +1,26 @@
+-- Link filtering for the content filter pipeline.
+-- The `links` category is toggled through guild_filter_configs like the
+-- built-in categories; `link_mode` picks which links match:
+--   all       - every link
+--   invites   - invite links to other guilds or chat platforms
+--   allowlist - links to domains outside the guild's allowlist
+-- Denylisted domains match in every mode.
+ALTER TYPE filter_category ADD VALUE IF NOT EXISTS 'links';
+
+CREATE TYPE link_filter_mode AS ENUM ('all', 'invites', 'allowlist');
+
+ALTER TABLE guild_filter_configs ADD COLUMN link_mode link_filter_mode;
+
+CREATE TYPE link_domain_list AS ENUM ('allow', 'deny');
+
+CREATE TABLE guild_filter_link_domains (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    guild_id UUID NOT NULL REFERENCES guilds(id) ON DELETE CASCADE,
+    domain TEXT NOT NULL,
+    list link_domain_list NOT NULL,
+    created_by UUID NOT NULL REFERENCES users(id),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE(guild_id, domain)
+);
+
+CREATE INDEX idx_guild_filter_link_domains_guild ON guild_filter_link_domains(guild_id);
@@ -0,0 +1,41 @@
+-- Automod escalation policies.
+-- A policy fires once a member collects `threshold` logged filter matches
+-- (moderation_actions rows) within `window_seconds`. Matches counted toward
+-- an earlier escalation by the same policy are not counted again.
+--   warn    - notify moderators only
+--   timeout - time the member out for `timeout_seconds`
+--   ban     - ban the member
+CREATE TYPE escalation_action AS ENUM ('warn', 'timeout', 'ban');
+
+CREATE TABLE guild_escalation_policies (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    guild_id UUID NOT NULL REFERENCES guilds(id) ON DELETE CASCADE,
+    name TEXT NOT NULL,
+    enabled BOOLEAN NOT NULL DEFAULT TRUE,
+    threshold INTEGER NOT NULL CHECK (threshold > 0),
+    window_seconds INTEGER NOT NULL CHECK (window_seconds > 0),
+    action escalation_action NOT NULL,
+    timeout_seconds INTEGER CHECK (timeout_seconds > 0),
+    notify_channel_id UUID REFERENCES channels(id) ON DELETE SET NULL,
+    created_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CHECK ((action = 'timeout') = (timeout_seconds IS NOT NULL))
+);
+
+CREATE INDEX idx_guild_escalation_policies_guild ON guild_escalation_policies(guild_id);
+
+CREATE TABLE moderation_escalations (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    guild_id UUID NOT NULL REFERENCES guilds(id) ON DELETE CASCADE,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    policy_id UUID REFERENCES guild_escalation_policies(id) ON DELETE SET NULL,
+    action escalation_action NOT NULL,
+    violation_count INTEGER NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_moderation_escalations_member
+    ON moderation_escalations(guild_id, user_id, created_at DESC);
+
+ALTER TYPE message_type ADD VALUE IF NOT EXISTS 'system_automod';
@@ -0,0 +1,18 @@
+-- Attachment malware scanning.
+-- When a scanner is configured, new attachments start out `pending` and stay
+-- hidden from other users until the scan passes.
+--   pending     - uploaded, scan not finished yet
+--   clean       - scanner found nothing
+--   quarantined - scanner reported a signature (kept for admin review)
+--   failed      - scanner unavailable or errored (fail-closed)
+--   released    - quarantined or failed, then released by a system admin
+--   skipped     - not scanned (no scanner configured, or fail-open error)
+ALTER TABLE file_attachments
+    ADD COLUMN scan_status VARCHAR(20) NOT NULL DEFAULT 'skipped'
+        CHECK (scan_status IN ('pending', 'clean', 'quarantined', 'failed', 'released', 'skipped')),
+    ADD COLUMN scan_signature TEXT,
+    ADD COLUMN scanned_at TIMESTAMPTZ;
+
+CREATE INDEX idx_file_attachments_scan_held
+    ON file_attachments(created_at DESC)
+    WHERE scan_status IN ('pending', 'quarantined', 'failed');
@@ -0,0 +1,50 @@
+-- Report queue triage: claim bookkeeping, internal admin notes and message
+-- evidence snapshots.
+--
+-- Reports move open (`pending`) -> in review (`reviewing`, claimed by one
+-- admin) -> `resolved` or `dismissed`. `claimed_at` and `resolved_at` back
+-- the time-to-claim and time-to-resolve SLA metrics.
+ALTER TABLE user_reports
+    ADD COLUMN claimed_at TIMESTAMPTZ,
+    ADD COLUMN resolved_by UUID REFERENCES users(id) ON DELETE SET NULL;
+
+-- Best effort for reports already in review.
+UPDATE user_reports SET claimed_at = updated_at WHERE status = 'reviewing';
+
+CREATE INDEX idx_reports_resolved_at
+    ON user_reports(resolved_at)
+    WHERE resolved_at IS NOT NULL;
+
+-- Internal notes, only visible to system admins.
+CREATE TABLE report_notes (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    report_id UUID NOT NULL REFERENCES user_reports(id) ON DELETE CASCADE,
+    author_id UUID REFERENCES users(id) ON DELETE SET NULL,
+    body TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_report_notes_report ON report_notes(report_id, created_at);
+
+-- Message snapshots linked to a report. The content is copied so it survives
+-- the message being edited or deleted; channel and author are plain IDs for
+-- the same reason.
+CREATE TABLE report_evidence (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    report_id UUID NOT NULL REFERENCES user_reports(id) ON DELETE CASCADE,
+    message_id UUID REFERENCES messages(id) ON DELETE SET NULL,
+    channel_id UUID NOT NULL,
+    author_id UUID,
+    content TEXT NOT NULL,
+    encrypted BOOLEAN NOT NULL DEFAULT FALSE,
+    -- [{id, filename, mime_type, size_bytes}]
+    attachments JSONB NOT NULL DEFAULT '[]',
+    message_created_at TIMESTAMPTZ NOT NULL,
+    edited_at TIMESTAMPTZ,
+    -- NULL when captured automatically with the report
+    added_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    captured_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (report_id, message_id)
+);
+
+CREATE INDEX idx_report_evidence_report ON report_evidence(report_id, captured_at);
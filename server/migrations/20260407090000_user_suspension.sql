@@ -0,0 +1,22 @@
+-- Account suspension and forced token revocation by system admins.
+--
+-- A suspended account cannot sign in or use existing tokens. Access tokens
+-- issued at or before `tokens_revoked_at` are rejected, which lets admins end
+-- every session of a user without waiting for the tokens to expire.
+ALTER TABLE users
+    ADD COLUMN suspended_at TIMESTAMPTZ,
+    ADD COLUMN suspension_reason TEXT,
+    ADD COLUMN suspended_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    ADD COLUMN tokens_revoked_at TIMESTAMPTZ;
+
+CREATE INDEX idx_users_suspended
+    ON users(suspended_at)
+    WHERE suspended_at IS NOT NULL;
+
+-- Connections closed because the user's sessions were revoked.
+ALTER TABLE telemetry_ws_disconnects
+    DROP CONSTRAINT telemetry_ws_disconnects_cause_check,
+    ADD CONSTRAINT telemetry_ws_disconnects_cause_check CHECK (cause IN (
+        'client_close', 'connection_lost', 'timeout',
+        'rate_limited', 'auth_expired', 'server_shutdown', 'session_revoked'
+    ));
@@ -0,0 +1,15 @@
+-- Security events in the system audit log.
+--
+-- Sign-ins, failed sign-ins and MFA changes are recorded next to admin
+-- actions. Failed sign-ins for unknown usernames have no actor, so the
+-- column stays nullable (see the data governance migration).
+ALTER TABLE system_audit_log
+    ADD COLUMN user_agent TEXT;
+
+-- Keyset pagination for GET /api/admin/audit-log
+CREATE INDEX idx_system_audit_created_id
+    ON system_audit_log(created_at DESC, id DESC);
+
+CREATE INDEX idx_system_audit_actor_created
+    ON system_audit_log(actor_id, created_at DESC);
+DROP INDEX IF EXISTS idx_system_audit_actor;
@@ -0,0 +1,35 @@
+-- Networks each account has signed in from, used to alert on new ones.
+--
+-- A network is the /24 of an IPv4 address or the /64 of an IPv6 address, so a
+-- new address from the same ISP pool or home router does not count as new.
+CREATE TABLE user_login_networks (
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    network CIDR NOT NULL,
+    last_ip INET NOT NULL,
+    last_user_agent TEXT,
+    first_seen_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    last_seen_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (user_id, network)
+);
+
+-- Seed from existing sessions so current users are not alerted on their next
+-- sign-in from a network they already use.
+INSERT INTO user_login_networks (user_id, network, last_ip, last_user_agent, first_seen_at, last_seen_at)
+SELECT DISTINCT ON (user_id, network)
+    user_id,
+    network,
+    ip_address,
+    user_agent,
+    MIN(created_at) OVER (PARTITION BY user_id, network),
+    created_at
+FROM (
+    SELECT
+        user_id,
+        network(set_masklen(ip_address, CASE WHEN family(ip_address) = 4 THEN 24 ELSE 64 END)) AS network,
+        ip_address,
+        user_agent,
+        created_at
+    FROM sessions
+    WHERE ip_address IS NOT NULL
+) s
+ORDER BY user_id, network, created_at DESC;
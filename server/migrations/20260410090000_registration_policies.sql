@@ -0,0 +1,22 @@
+-- Invite-only and approval registration policies.
+--
+-- Under `invite_only`, registering takes a code minted by a system admin.
+-- Under `approval`, new accounts are created with `pending_approval_at` set
+-- and cannot sign in until an admin approves them; rejecting deletes them.
+ALTER TABLE users
+    ADD COLUMN pending_approval_at TIMESTAMPTZ;
+
+CREATE INDEX idx_users_pending_approval
+    ON users(pending_approval_at)
+    WHERE pending_approval_at IS NOT NULL;
+
+CREATE TABLE registration_codes (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    code VARCHAR(16) NOT NULL UNIQUE,
+    note VARCHAR(200),
+    created_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    max_uses INTEGER CHECK (max_uses IS NULL OR max_uses > 0),
+    use_count INTEGER NOT NULL DEFAULT 0,
+    expires_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
@@ -0,0 +1,23 @@
+-- Email verification.
+--
+-- `email_verified_at` is set once the user confirms a code sent to the address
+-- on their account, and cleared whenever that address changes. Accounts that
+-- existed before this migration start out unverified.
+ALTER TABLE users
+    ADD COLUMN email_verified_at TIMESTAMPTZ;
+
+-- Verification codes are stored hashed, like password reset tokens. `email`
+-- is the address the code was sent to, so a code cannot verify an address
+-- the account changed to afterwards.
+CREATE TABLE email_verification_tokens (
+    id          UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id     UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    email       VARCHAR(255) NOT NULL,
+    token_hash  TEXT NOT NULL UNIQUE,
+    expires_at  TIMESTAMPTZ NOT NULL,
+    used_at     TIMESTAMPTZ,
+    created_at  TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_evt_user ON email_verification_tokens(user_id);
+CREATE INDEX idx_evt_expires ON email_verification_tokens(expires_at);
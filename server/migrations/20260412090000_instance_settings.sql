@@ -0,0 +1,9 @@
+-- Runtime overrides of environment settings
+--
+-- System admins change guild discovery and upload limits through
+-- GET/PATCH /api/admin/settings. Keys left out keep the environment value;
+-- upload limits can only be lowered below it.
+
+INSERT INTO server_config (key, value)
+VALUES ('instance_settings', '{}'::jsonb)
+ON CONFLICT (key) DO NOTHING;
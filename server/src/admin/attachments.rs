@@ -0,0 +1,249 @@
+//! Admin Attachment Quarantine handlers.
+//!
+//! Attachments the malware scanner flagged (`quarantined`) or could not scan
+//! (`failed`) stay hidden from everyone but their uploader. Listing them
+//! requires `SystemAdminUser`; releasing a false positive or deleting the
+//! file requires an elevated session.
+
+#![allow(clippy::used_underscore_binding)]
+
+use std::net::SocketAddr;
+
+use axum::extract::{ConnectInfo, Path, Query, State};
+use axum::http::StatusCode;
+use axum::{Extension, Json};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::handlers::PaginatedResponse;
+use super::types::{AdminError, ElevatedAdmin, SystemAdminUser};
+use crate::api::AppState;
+use crate::chat::uploads::publish_scanned_attachment;
+use crate::db;
+use crate::permissions::queries::write_audit_log;
+
+/// Scan statuses that keep an attachment hidden.
+const HELD_STATUSES: &[&str] = &["pending", "quarantined", "failed"];
+
+/// Held attachment list query parameters.
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+pub struct HeldAttachmentParams {
+    /// Only attachments with this scan status (`pending`, `quarantined` or
+    /// `failed`); all held attachments when omitted.
+    pub status: Option<String>,
+    /// Maximum number of items to return.
+    #[serde(default = "default_limit")]
+    pub limit: i64,
+    /// Number of items to skip.
+    #[serde(default)]
+    pub offset: i64,
+}
+
+#[allow(clippy::missing_const_for_fn)]
+fn default_limit() -> i64 {
+    50
+}
+
+/// An attachment held by the malware scanner.
+#[derive(Debug, Serialize, sqlx::FromRow, utoipa::ToSchema)]
+pub struct HeldAttachment {
+    pub id: Uuid,
+    pub message_id: Uuid,
+    pub channel_id: Uuid,
+    /// Guild of the channel (`None` for DMs).
+    pub guild_id: Option<Uuid>,
+    /// Author of the message (`None` if the account was deleted).
+    pub uploader_id: Option<Uuid>,
+    pub uploader_username: Option<String>,
+    pub filename: String,
+    pub mime_type: String,
+    pub size_bytes: i64,
+    /// `pending`, `quarantined` or `failed`.
+    pub scan_status: String,
+    /// Signature reported by the scanner.
+    pub scan_signature: Option<String>,
+    pub scanned_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// List attachments held by the malware scanner, newest first.
+///
+/// `GET /api/admin/attachments/held`
+#[utoipa::path(
+    get,
+    path = "/api/admin/attachments/held",
+    tag = "admin",
+    params(HeldAttachmentParams),
+    responses((status = 200, body = PaginatedResponse<HeldAttachment>)),
+    security(("bearer_auth" = []))
+)]
+#[tracing::instrument(skip(state))]
+pub async fn list_held(
+    State(state): State<AppState>,
+    Extension(_admin): Extension<SystemAdminUser>,
+    Query(params): Query<HeldAttachmentParams>,
+) -> Result<Json<PaginatedResponse<HeldAttachment>>, AdminError> {
+    let limit = params.limit.clamp(1, 100);
+    let offset = params.offset.max(0);
+    let statuses: Vec<&str> = match params.status.as_deref() {
+        None => HELD_STATUSES.to_vec(),
+        Some(status) if HELD_STATUSES.contains(&status) => vec![status],
+        Some(_) => {
+            return Err(AdminError::Validation(
+                "status must be one of: pending, quarantined, failed".to_string(),
+            ))
+        }
+    };
+
+    let total: i64 =
+        sqlx::query_scalar("SELECT COUNT(*) FROM file_attachments WHERE scan_status = ANY($1)")
+            .bind(&statuses)
+            .fetch_one(&state.db)
+            .await?;
+
+    let items = sqlx::query_as::<_, HeldAttachment>(
+        r"SELECT fa.id, fa.message_id, fa.channel_id, c.guild_id,
+                 m.user_id AS uploader_id, u.username AS uploader_username,
+                 fa.filename, fa.mime_type, fa.size_bytes,
+                 fa.scan_status, fa.scan_signature, fa.scanned_at, fa.created_at
+          FROM file_attachments fa
+          JOIN messages m ON m.id = fa.message_id
+          JOIN channels c ON c.id = fa.channel_id
+          LEFT JOIN users u ON u.id = m.user_id
+          WHERE fa.scan_status = ANY($1)
+          ORDER BY fa.created_at DESC, fa.id DESC
+          LIMIT $2 OFFSET $3",
+    )
+    .bind(&statuses)
+    .bind(limit)
+    .bind(offset)
+    .fetch_all(&state.db)
+    .await?;
+
+    Ok(Json(PaginatedResponse {
+        items,
+        total,
+        limit,
+        offset,
+    }))
+}
+
+/// Release a quarantined or failed attachment, e.g. a false positive.
+///
+/// The attachment and its message become visible to the channel.
+///
+/// `POST /api/admin/attachments/:id/release`
+#[utoipa::path(
+    post,
+    path = "/api/admin/attachments/{id}/release",
+    tag = "admin",
+    params(("id" = Uuid, Path, description = "Attachment ID")),
+    responses(
+        (status = 204, description = "Attachment released"),
+        (status = 404, description = "Attachment is not quarantined or failed"),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[tracing::instrument(skip(state))]
+pub async fn release(
+    State(state): State<AppState>,
+    Extension(admin): Extension<SystemAdminUser>,
+    Extension(_elevated): Extension<ElevatedAdmin>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    Path(id): Path<Uuid>,
+) -> Result<StatusCode, AdminError> {
+    let previous = db::find_file_attachment_by_id(&state.db, id).await?;
+    let attachment = db::release_file_attachment(&state.db, id)
+        .await?
+        .ok_or_else(|| AdminError::NotFound("Held attachment".to_string()))?;
+
+    let ip_address = addr.ip().to_string();
+    write_audit_log(
+        &state.db,
+        admin.user_id,
+        "admin.attachments.release",
+        Some("attachment"),
+        Some(id),
+        Some(serde_json::json!({
+            "message_id": attachment.message_id,
+            "filename": attachment.filename,
+            "scan_status": previous.map(|a| a.scan_status),
+            "scan_signature": attachment.scan_signature,
+        })),
+        Some(&ip_address),
+    )
+    .await?;
+
+    if let Some(s3) = state.s3.clone() {
+        tokio::spawn(async move {
+            publish_scanned_attachment(&state, s3, attachment, None, true).await;
+        });
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Delete a held attachment and its stored file. The message itself is kept.
+///
+/// `DELETE /api/admin/attachments/:id`
+#[utoipa::path(
+    delete,
+    path = "/api/admin/attachments/{id}",
+    tag = "admin",
+    params(("id" = Uuid, Path, description = "Attachment ID")),
+    responses(
+        (status = 204, description = "Attachment deleted"),
+        (status = 404, description = "Attachment is not held"),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[tracing::instrument(skip(state))]
+pub async fn delete(
+    State(state): State<AppState>,
+    Extension(admin): Extension<SystemAdminUser>,
+    Extension(_elevated): Extension<ElevatedAdmin>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    Path(id): Path<Uuid>,
+) -> Result<StatusCode, AdminError> {
+    let attachment = sqlx::query_as::<_, db::FileAttachment>(
+        "DELETE FROM file_attachments WHERE id = $1 AND scan_status = ANY($2) RETURNING *",
+    )
+    .bind(id)
+    .bind(HELD_STATUSES)
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or_else(|| AdminError::NotFound("Held attachment".to_string()))?;
+
+    if let Some(s3) = &state.s3 {
+        let keys = [
+            Some(&attachment.s3_key),
+            attachment.thumbnail_s3_key.as_ref(),
+            attachment.medium_s3_key.as_ref(),
+        ];
+        for key in keys.into_iter().flatten() {
+            if let Err(e) = s3.delete(key).await {
+                tracing::warn!(attachment_id = %id, s3_key = %key, error = %e, "Failed to delete held attachment object");
+            }
+        }
+    }
+
+    let ip_address = addr.ip().to_string();
+    write_audit_log(
+        &state.db,
+        admin.user_id,
+        "admin.attachments.delete",
+        Some("attachment"),
+        Some(id),
+        Some(serde_json::json!({
+            "message_id": attachment.message_id,
+            "filename": attachment.filename,
+            "scan_status": attachment.scan_status,
+            "scan_signature": attachment.scan_signature,
+        })),
+        Some(&ip_address),
+    )
+    .await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,45 @@
+//! Admin Cluster API
+//!
+//! Lists the server replicas in the cluster node registry with their load.
+//! Requires `SystemAdminUser` middleware (non-elevated).
+
+use axum::extract::State;
+use axum::{Extension, Json};
+use serde::Serialize;
+
+use super::types::{AdminError, SystemAdminUser};
+use crate::api::AppState;
+use crate::cluster::{registry, NodeInfo};
+
+/// Live cluster nodes.
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct ClusterNodesResponse {
+    /// Node that served this request.
+    pub current_node_id: String,
+    /// Live nodes, ordered by node ID.
+    pub nodes: Vec<NodeInfo>,
+}
+
+/// List the live server nodes and their load.
+#[utoipa::path(
+    get,
+    path = "/api/admin/cluster/nodes",
+    tag = "admin",
+    responses((status = 200, body = ClusterNodesResponse)),
+    security(("bearer_auth" = []))
+)]
+#[tracing::instrument(skip(state, _admin))]
+pub async fn list_nodes(
+    Extension(_admin): Extension<SystemAdminUser>,
+    State(state): State<AppState>,
+) -> Result<Json<ClusterNodesResponse>, AdminError> {
+    let nodes = registry::list_nodes(&state.redis).await.map_err(|e| {
+        tracing::error!(error = %e, "Failed to read cluster node registry");
+        AdminError::Internal("Cluster node registry unavailable".into())
+    })?;
+
+    Ok(Json(ClusterNodesResponse {
+        current_node_id: state.config().node_id.clone(),
+        nodes,
+    }))
+}
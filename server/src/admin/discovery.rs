@@ -0,0 +1,234 @@
+//! Admin Discovery Review handlers.
+//!
+//! Guilds marked discoverable wait in the review queue until a system admin
+//! approves or rejects them; guilds delisted by user reports return to it.
+//! Suspended guilds are left out of the queue until the suspension is lifted.
+//! Listing the queue requires `SystemAdminUser`; reviewing requires an
+//! elevated session. The guild owner is told the outcome.
+
+#![allow(clippy::used_underscore_binding)]
+
+use std::net::SocketAddr;
+
+use axum::extract::{ConnectInfo, Path, Query, State};
+use axum::{Extension, Json};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::handlers::PaginatedResponse;
+use super::types::{AdminError, ElevatedAdmin, SystemAdminUser};
+use crate::api::AppState;
+use crate::discovery::review::{self, MAX_REASON_LENGTH};
+use crate::permissions::queries::write_audit_log;
+
+/// Review queue query parameters.
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+pub struct DiscoveryQueueParams {
+    /// Maximum number of items to return.
+    #[serde(default = "default_limit")]
+    pub limit: i64,
+    /// Number of items to skip.
+    #[serde(default)]
+    pub offset: i64,
+}
+
+#[allow(clippy::missing_const_for_fn)]
+fn default_limit() -> i64 {
+    50
+}
+
+/// A guild waiting for discovery review.
+#[derive(Debug, Serialize, sqlx::FromRow, utoipa::ToSchema)]
+pub struct DiscoveryQueueEntry {
+    pub guild_id: Uuid,
+    pub name: String,
+    pub description: Option<String>,
+    pub icon_url: Option<String>,
+    pub tags: Vec<String>,
+    pub owner_id: Uuid,
+    pub owner_username: String,
+    pub member_count: i64,
+    /// `pending` (submitted by the owner) or `delisted` (by user reports).
+    pub status: String,
+    pub submitted_at: Option<DateTime<Utc>>,
+    /// Reports against the listing since its last review.
+    pub report_count: i64,
+}
+
+/// Rejection request.
+#[derive(Debug, Deserialize, utoipa::ToSchema)]
+pub struct RejectDiscoveryRequest {
+    /// Shown to the guild owner (1-500 characters).
+    pub reason: String,
+}
+
+/// Review result.
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct DiscoveryReviewResponse {
+    pub guild_id: Uuid,
+    /// `approved` or `rejected`.
+    pub status: String,
+}
+
+/// List guilds waiting for discovery review, oldest submission first.
+///
+/// `GET /api/admin/discovery/queue`
+#[utoipa::path(
+    get,
+    path = "/api/admin/discovery/queue",
+    tag = "admin",
+    params(DiscoveryQueueParams),
+    responses((status = 200, body = PaginatedResponse<DiscoveryQueueEntry>)),
+    security(("bearer_auth" = []))
+)]
+#[tracing::instrument(skip(state))]
+pub async fn list_queue(
+    State(state): State<AppState>,
+    Extension(_admin): Extension<SystemAdminUser>,
+    Query(params): Query<DiscoveryQueueParams>,
+) -> Result<Json<PaginatedResponse<DiscoveryQueueEntry>>, AdminError> {
+    let limit = params.limit.clamp(1, 100);
+    let offset = params.offset.max(0);
+
+    let total: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM guilds WHERE discoverable = true AND discovery_status IN ('pending', 'delisted') AND suspended_at IS NULL",
+    )
+    .fetch_one(&state.db)
+    .await?;
+
+    let items = sqlx::query_as::<_, DiscoveryQueueEntry>(
+        r"SELECT g.id AS guild_id, g.name, g.description, g.icon_url, g.tags,
+                 g.owner_id, u.username AS owner_username, g.member_count::bigint AS member_count,
+                 g.discovery_status AS status, g.discovery_submitted_at AS submitted_at,
+                 (SELECT COUNT(*) FROM guild_discovery_reports r
+                   WHERE r.guild_id = g.id
+                     AND r.created_at > COALESCE(g.discovery_reviewed_at, '-infinity')) AS report_count
+          FROM guilds g
+          INNER JOIN users u ON u.id = g.owner_id
+          WHERE g.discoverable = true AND g.discovery_status IN ('pending', 'delisted')
+            AND g.suspended_at IS NULL
+          ORDER BY g.discovery_submitted_at ASC NULLS FIRST, g.id
+          LIMIT $1 OFFSET $2",
+    )
+    .bind(limit)
+    .bind(offset)
+    .fetch_all(&state.db)
+    .await?;
+
+    Ok(Json(PaginatedResponse {
+        items,
+        total,
+        limit,
+        offset,
+    }))
+}
+
+/// Record a review decision for a queued guild and tell its owner.
+async fn record_review(
+    state: &AppState,
+    admin: &SystemAdminUser,
+    addr: SocketAddr,
+    guild_id: Uuid,
+    status: &'static str,
+    reason: Option<String>,
+) -> Result<Json<DiscoveryReviewResponse>, AdminError> {
+    let updated = sqlx::query(
+        r"UPDATE guilds SET
+              discovery_status = $2,
+              discovery_rejection_reason = $3,
+              discovery_reviewed_at = NOW(),
+              discovery_reviewed_by = $4
+          WHERE id = $1 AND discoverable = true AND discovery_status IN ('pending', 'delisted')
+            AND suspended_at IS NULL",
+    )
+    .bind(guild_id)
+    .bind(status)
+    .bind(&reason)
+    .bind(admin.user_id)
+    .execute(&state.db)
+    .await?
+    .rows_affected();
+    if updated == 0 {
+        return Err(AdminError::NotFound("Queued guild".to_string()));
+    }
+
+    let ip_address = addr.ip().to_string();
+    write_audit_log(
+        &state.db,
+        admin.user_id,
+        if reason.is_some() {
+            "admin.discovery.reject"
+        } else {
+            "admin.discovery.approve"
+        },
+        Some("guild"),
+        Some(guild_id),
+        reason.map(|reason| serde_json::json!({ "reason": reason })),
+        Some(&ip_address),
+    )
+    .await?;
+
+    review::notify_owner(&state.db, &state.redis, guild_id).await;
+
+    Ok(Json(DiscoveryReviewResponse {
+        guild_id,
+        status: status.to_string(),
+    }))
+}
+
+/// Approve a queued guild, listing it in discovery.
+///
+/// `POST /api/admin/discovery/queue/:guild_id/approve`
+#[utoipa::path(
+    post,
+    path = "/api/admin/discovery/queue/{guild_id}/approve",
+    tag = "admin",
+    params(("guild_id" = Uuid, Path, description = "Guild ID")),
+    responses(
+        (status = 200, description = "Guild listed", body = DiscoveryReviewResponse),
+        (status = 404, description = "Guild is not in the review queue"),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[tracing::instrument(skip(state))]
+pub async fn approve(
+    State(state): State<AppState>,
+    Extension(admin): Extension<SystemAdminUser>,
+    Extension(_elevated): Extension<ElevatedAdmin>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    Path(guild_id): Path<Uuid>,
+) -> Result<Json<DiscoveryReviewResponse>, AdminError> {
+    record_review(&state, &admin, addr, guild_id, "approved", None).await
+}
+
+/// Reject a queued guild; the reason is sent to its owner.
+///
+/// `POST /api/admin/discovery/queue/:guild_id/reject`
+#[utoipa::path(
+    post,
+    path = "/api/admin/discovery/queue/{guild_id}/reject",
+    tag = "admin",
+    params(("guild_id" = Uuid, Path, description = "Guild ID")),
+    request_body = RejectDiscoveryRequest,
+    responses(
+        (status = 200, description = "Guild rejected", body = DiscoveryReviewResponse),
+        (status = 400, description = "Invalid reason"),
+        (status = 404, description = "Guild is not in the review queue"),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[tracing::instrument(skip(state, body))]
+pub async fn reject(
+    State(state): State<AppState>,
+    Extension(admin): Extension<SystemAdminUser>,
+    Extension(_elevated): Extension<ElevatedAdmin>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    Path(guild_id): Path<Uuid>,
+    Json(body): Json<RejectDiscoveryRequest>,
+) -> Result<Json<DiscoveryReviewResponse>, AdminError> {
+    let reason = review::normalize_reason(&body.reason).ok_or_else(|| {
+        AdminError::Validation(format!("Reason must be 1-{MAX_REASON_LENGTH} characters"))
+    })?;
+    record_review(&state, &admin, addr, guild_id, "rejected", Some(reason)).await
+}
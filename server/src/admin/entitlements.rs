@@ -0,0 +1,261 @@
+//! Billing Entitlement Webhook
+//!
+//! Extension point for managed hosting: an external billing system POSTs
+//! signed entitlement updates that adjust a guild's plan, page quotas and
+//! billing-granted supporters. The server never talks to a payment provider
+//! itself.
+//!
+//! Requests are authenticated with an HMAC-SHA256 signature of the raw body
+//! (`X-Webhook-Signature: sha256=<hex>`, the same scheme as outgoing bot
+//! webhooks) keyed with `BILLING_WEBHOOK_SECRET`. Each `event_id` is applied at
+//! most once, so the billing system can safely retry deliveries.
+
+use axum::body::Bytes;
+use axum::extract::State;
+use axum::http::HeaderMap;
+use axum::Json;
+use serde::{Deserialize, Serialize};
+use tracing::info;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use super::handlers::{validate_page_limits, SetGuildPageLimitsRequest};
+use super::types::AdminError;
+use crate::api::AppState;
+use crate::guild::perks::{self, GuildPerks};
+use crate::webhooks::signing;
+
+/// Maximum supporter entries in a single event.
+const MAX_SUPPORTERS_PER_EVENT: usize = 1000;
+
+/// Supporter flag granted or revoked by the billing system.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct SupporterEntitlement {
+    /// Guild member.
+    pub user_id: Uuid,
+    /// Whether the member currently has an active subscription.
+    pub active: bool,
+}
+
+/// Entitlement update sent by the billing system.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct EntitlementUpdate {
+    /// Billing-system event ID (idempotency key, max 128 chars).
+    pub event_id: String,
+    /// Guild the entitlement applies to.
+    pub guild_id: Uuid,
+    /// New guild plan (lowercase letters, digits, `-` and `_`).
+    #[serde(default)]
+    pub plan: Option<String>,
+    /// Page quota overrides.
+    #[serde(default)]
+    pub quotas: Option<SetGuildPageLimitsRequest>,
+    /// Supporter flags to apply.
+    #[serde(default)]
+    pub supporters: Vec<SupporterEntitlement>,
+}
+
+/// Result of an entitlement update.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct EntitlementResponse {
+    pub event_id: String,
+    /// `false` if the event had already been applied.
+    pub applied: bool,
+    /// Supporter entries skipped because the user is not a guild member.
+    pub skipped_user_ids: Vec<Uuid>,
+    /// Effective guild perks after the update.
+    pub perks: GuildPerks,
+}
+
+/// Validate an entitlement update.
+fn validate_update(update: &EntitlementUpdate) -> Result<(), AdminError> {
+    if update.event_id.is_empty() || update.event_id.len() > 128 {
+        return Err(AdminError::Validation(
+            "event_id must be 1-128 characters".to_string(),
+        ));
+    }
+
+    if let Some(plan) = &update.plan {
+        let valid = (1..=32).contains(&plan.len())
+            && plan
+                .bytes()
+                .all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b == b'-' || b == b'_');
+        if !valid {
+            return Err(AdminError::Validation(
+                "plan must be 1-32 lowercase letters, digits, '-' or '_'".to_string(),
+            ));
+        }
+    }
+
+    if let Some(quotas) = &update.quotas {
+        validate_page_limits(quotas)?;
+    }
+
+    if update.supporters.len() > MAX_SUPPORTERS_PER_EVENT {
+        return Err(AdminError::Validation(format!(
+            "supporters must contain at most {MAX_SUPPORTERS_PER_EVENT} entries"
+        )));
+    }
+
+    Ok(())
+}
+
+/// Receive a signed entitlement update from the billing system.
+///
+/// POST /api/admin/entitlements/webhook
+#[utoipa::path(
+    post,
+    path = "/api/admin/entitlements/webhook",
+    tag = "admin",
+    request_body = EntitlementUpdate,
+    responses(
+        (status = 200, body = EntitlementResponse),
+        (status = 401, description = "Missing or invalid signature"),
+        (status = 404, description = "Webhook disabled or guild not found"),
+    )
+)]
+#[tracing::instrument(skip(state, headers, body))]
+pub async fn receive_entitlement_webhook(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<Json<EntitlementResponse>, AdminError> {
+    let Some(secret) = state.config().billing_webhook_secret.clone() else {
+        return Err(AdminError::NotFound("Billing webhook".into()));
+    };
+
+    let signature = headers
+        .get("x-webhook-signature")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("sha256="))
+        .ok_or(AdminError::InvalidSignature)?;
+    if !signing::verify_signature(&secret, &body, signature) {
+        return Err(AdminError::InvalidSignature);
+    }
+
+    let payload: serde_json::Value = serde_json::from_slice(&body)
+        .map_err(|e| AdminError::Validation(format!("Invalid payload: {e}")))?;
+    let update: EntitlementUpdate = serde_json::from_value(payload.clone())
+        .map_err(|e| AdminError::Validation(format!("Invalid payload: {e}")))?;
+    validate_update(&update)?;
+
+    let guild_id = update.guild_id;
+    let mut tx = state.db.begin().await?;
+
+    let guild_exists: bool =
+        sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM guilds WHERE id = $1)")
+            .bind(guild_id)
+            .fetch_one(&mut *tx)
+            .await?;
+    if !guild_exists {
+        return Err(AdminError::NotFound("Guild".into()));
+    }
+
+    // Record the event first; a conflict means it was already applied
+    let recorded = sqlx::query(
+        r"INSERT INTO billing_entitlement_events (event_id, guild_id, payload)
+           VALUES ($1, $2, $3)
+           ON CONFLICT (event_id) DO NOTHING",
+    )
+    .bind(&update.event_id)
+    .bind(guild_id)
+    .bind(&payload)
+    .execute(&mut *tx)
+    .await?;
+
+    if recorded.rows_affected() == 0 {
+        tx.rollback().await?;
+        info!(event_id = %update.event_id, guild_id = %guild_id, "Duplicate entitlement event ignored");
+        return Ok(Json(EntitlementResponse {
+            event_id: update.event_id,
+            applied: false,
+            skipped_user_ids: Vec::new(),
+            perks: perks::get_perks(&state.db, &state.config(), guild_id).await?,
+        }));
+    }
+
+    if update.plan.is_some() || update.quotas.is_some() {
+        let quotas = update.quotas.unwrap_or_default();
+        sqlx::query(
+            r"UPDATE guilds
+               SET plan = COALESCE($2, plan),
+                   max_pages = CASE WHEN $3 THEN $4 ELSE max_pages END,
+                   max_revisions = CASE WHEN $5 THEN $6 ELSE max_revisions END
+               WHERE id = $1",
+        )
+        .bind(guild_id)
+        .bind(&update.plan)
+        .bind(quotas.max_pages.is_some())
+        .bind(quotas.max_pages.flatten())
+        .bind(quotas.max_revisions.is_some())
+        .bind(quotas.max_revisions.flatten())
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    let mut skipped_user_ids = Vec::new();
+    for supporter in &update.supporters {
+        if !perks::set_billing_supporter(&mut *tx, guild_id, supporter.user_id, supporter.active)
+            .await?
+        {
+            skipped_user_ids.push(supporter.user_id);
+        }
+    }
+
+    tx.commit().await?;
+
+    info!(
+        event_id = %update.event_id,
+        guild_id = %guild_id,
+        plan = ?update.plan,
+        supporters = update.supporters.len(),
+        skipped = skipped_user_ids.len(),
+        "Applied billing entitlement update"
+    );
+
+    let perks = if update.supporters.is_empty() {
+        perks::get_perks(&state.db, &state.config(), guild_id).await?
+    } else {
+        perks::refresh(&state, guild_id).await?
+    };
+
+    Ok(Json(EntitlementResponse {
+        event_id: update.event_id,
+        applied: true,
+        skipped_user_ids,
+        perks,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn update(json: serde_json::Value) -> EntitlementUpdate {
+        serde_json::from_value(json).unwrap()
+    }
+
+    #[test]
+    fn test_validate_update() {
+        let guild_id = Uuid::new_v4();
+
+        assert!(validate_update(&update(serde_json::json!({
+            "event_id": "evt_1",
+            "guild_id": guild_id,
+            "plan": "pro-2",
+            "quotas": { "max_pages": 100, "max_revisions": null },
+        })))
+        .is_ok());
+
+        for bad in [
+            serde_json::json!({ "event_id": "", "guild_id": guild_id }),
+            serde_json::json!({ "event_id": "evt_2", "guild_id": guild_id, "plan": "Pro Plan" }),
+            serde_json::json!({ "event_id": "evt_3", "guild_id": guild_id, "quotas": { "max_pages": 0 } }),
+        ] {
+            assert!(matches!(
+                validate_update(&update(bad)),
+                Err(AdminError::Validation(_))
+            ));
+        }
+    }
+}
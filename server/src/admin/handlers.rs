@@ -18,7 +18,7 @@ use axum::response::IntoResponse;
 use axum::{Extension, Json};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Deserializer, Serialize};
-use sqlx::{PgPool, QueryBuilder};
+use sqlx::{Postgres, QueryBuilder};
 use tracing::warn;
 use utoipa::ToSchema;
 use uuid::Uuid;
@@ -30,9 +30,11 @@ use super::types::{
     SystemAdminUser,
 };
 use crate::api::AppState;
+use crate::db::{list_username_history, UsernameChange};
+use crate::guild::perks::{self, GuildPerks, SupporterSource};
 use crate::permissions::models::AuditLogEntry;
 use crate::permissions::queries::{create_elevated_session, write_audit_log};
-use crate::ws::{broadcast_admin_event, ServerEvent};
+use crate::ws::{broadcast_admin_event, broadcast_to_user, ServerEvent};
 
 // ============================================================================
 // Query Parameters
@@ -73,6 +75,13 @@ pub struct AuditLogParams {
     pub to_date: Option<DateTime<Utc>>,
     /// Filter by exact action type (e.g., "admin.users.ban").
     pub action_type: Option<String>,
+    /// Only entries by this user.
+    pub actor_id: Option<Uuid>,
+    /// Only entries about this target (user, guild, ...).
+    pub target_id: Option<Uuid>,
+    /// Keyset cursor: `next_cursor` of the previous page. Takes precedence
+    /// over `offset`.
+    pub cursor: Option<Uuid>,
 }
 
 // ============================================================================
@@ -89,7 +98,7 @@ pub struct PaginatedResponse<T> {
 }
 
 /// User summary for admin listing.
-#[derive(Debug, Serialize, utoipa::ToSchema)]
+#[derive(Debug, Serialize, sqlx::FromRow, utoipa::ToSchema)]
 pub struct UserSummary {
     pub id: Uuid,
     pub username: String,
@@ -98,6 +107,7 @@ pub struct UserSummary {
     pub avatar_url: Option<String>,
     pub created_at: DateTime<Utc>,
     pub is_banned: bool,
+    pub suspended_at: Option<DateTime<Utc>>,
 }
 
 /// Guild summary for admin listing.
@@ -116,7 +126,7 @@ pub struct GuildSummary {
 #[derive(Debug, Serialize, utoipa::ToSchema)]
 pub struct AuditLogEntryResponse {
     pub id: Uuid,
-    pub actor_id: Uuid,
+    pub actor_id: Option<Uuid>,
     pub actor_username: Option<String>,
     pub action: String,
     pub target_type: Option<String>,
@@ -124,9 +134,23 @@ pub struct AuditLogEntryResponse {
     #[schema(value_type = Option<Object>)]
     pub details: Option<serde_json::Value>,
     pub ip_address: Option<String>,
+    pub user_agent: Option<String>,
     pub created_at: DateTime<Utc>,
 }
 
+/// Page of the system audit log.
+///
+/// Pass `next_cursor` back as `cursor` to fetch the following page; it is
+/// `None` on the last page.
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct AuditLogPage {
+    pub items: Vec<AuditLogEntryResponse>,
+    pub total: i64,
+    pub limit: i64,
+    pub offset: i64,
+    pub next_cursor: Option<Uuid>,
+}
+
 /// De-elevate response.
 #[derive(Debug, Serialize, utoipa::ToSchema)]
 pub struct DeElevateResponse {
@@ -153,9 +177,14 @@ pub struct UserDetailsResponse {
     pub avatar_url: Option<String>,
     pub created_at: DateTime<Utc>,
     pub is_banned: bool,
+    pub suspended_at: Option<DateTime<Utc>>,
+    pub suspension_reason: Option<String>,
+    pub is_system_admin: bool,
     pub last_login: Option<DateTime<Utc>>,
     pub guild_count: i64,
     pub guilds: Vec<UserGuildMembership>,
+    /// Past usernames, newest change first.
+    pub username_history: Vec<UsernameChange>,
 }
 
 /// Guild member info for detail view.
@@ -329,8 +358,8 @@ pub async fn list_users(
     };
 
     // Get users with ban status (with or without search filter)
-    let users = if let Some(ref pattern) = search_pattern {
-        sqlx::query_as::<_, (Uuid, String, String, Option<String>, Option<String>, DateTime<Utc>, bool)>(
+    let items = if let Some(ref pattern) = search_pattern {
+        sqlx::query_as::<_, UserSummary>(
             r"
             SELECT
                 u.id,
@@ -339,7 +368,8 @@ pub async fn list_users(
                 u.email,
                 u.avatar_url,
                 u.created_at,
-                EXISTS(SELECT 1 FROM global_bans gb WHERE gb.user_id = u.id AND (gb.expires_at IS NULL OR gb.expires_at > NOW())) as is_banned
+                EXISTS(SELECT 1 FROM global_bans gb WHERE gb.user_id = u.id AND (gb.expires_at IS NULL OR gb.expires_at > NOW())) as is_banned,
+                u.suspended_at
             FROM users u
             WHERE LOWER(u.username) LIKE $3
                OR LOWER(u.display_name) LIKE $3
@@ -354,7 +384,7 @@ pub async fn list_users(
         .fetch_all(&state.db)
         .await?
     } else {
-        sqlx::query_as::<_, (Uuid, String, String, Option<String>, Option<String>, DateTime<Utc>, bool)>(
+        sqlx::query_as::<_, UserSummary>(
             r"
             SELECT
                 u.id,
@@ -363,7 +393,8 @@ pub async fn list_users(
                 u.email,
                 u.avatar_url,
                 u.created_at,
-                EXISTS(SELECT 1 FROM global_bans gb WHERE gb.user_id = u.id AND (gb.expires_at IS NULL OR gb.expires_at > NOW())) as is_banned
+                EXISTS(SELECT 1 FROM global_bans gb WHERE gb.user_id = u.id AND (gb.expires_at IS NULL OR gb.expires_at > NOW())) as is_banned,
+                u.suspended_at
             FROM users u
             ORDER BY u.created_at DESC
             LIMIT $1 OFFSET $2
@@ -375,21 +406,6 @@ pub async fn list_users(
         .await?
     };
 
-    let items: Vec<UserSummary> = users
-        .into_iter()
-        .map(
-            |(id, username, display_name, email, avatar_url, created_at, is_banned)| UserSummary {
-                id,
-                username,
-                display_name,
-                email,
-                avatar_url,
-                created_at,
-                is_banned,
-            },
-        )
-        .collect();
-
     Ok(Json(PaginatedResponse {
         items,
         total: total.0,
@@ -505,87 +521,44 @@ pub async fn list_guilds(
     }))
 }
 
-/// Helper function to query audit log with dynamic filters.
-async fn get_audit_log_filtered(
-    pool: &PgPool,
-    limit: i64,
-    offset: i64,
-    action_filter: Option<&str>,
-    exact_action_match: bool,
-    from_date: Option<DateTime<Utc>>,
-    to_date: Option<DateTime<Utc>>,
-) -> Result<(Vec<AuditLogEntry>, (i64,)), AdminError> {
-    let action_pattern = action_filter.map(|a| {
-        if exact_action_match {
-            a.to_string()
-        } else {
-            format!("{a}%")
-        }
-    });
-
-    // Shared filter logic for both count and main queries
-    macro_rules! push_audit_filters {
-        ($builder:expr) => {{
-            let mut has_condition = false;
-            if let Some(ref pattern) = action_pattern {
-                $builder.push(" WHERE ");
-                has_condition = true;
-                if exact_action_match {
-                    $builder.push("action = ").push_bind(pattern.clone());
-                } else {
-                    $builder.push("action LIKE ").push_bind(pattern.clone());
-                }
-            }
-            if let Some(from) = from_date {
-                $builder.push(if has_condition { " AND " } else { " WHERE " });
-                has_condition = true;
-                $builder.push("created_at >= ").push_bind(from);
-            }
-            if let Some(to) = to_date {
-                $builder.push(if has_condition { " AND " } else { " WHERE " });
-                let _ = has_condition;
-                $builder.push("created_at <= ").push_bind(to);
-            }
-        }};
+/// Append the `WHERE` clause shared by the audit log count and page queries.
+fn push_audit_filters(builder: &mut QueryBuilder<'_, Postgres>, params: &AuditLogParams) {
+    builder.push(" WHERE TRUE");
+    if let Some(action_type) = &params.action_type {
+        builder
+            .push(" AND action = ")
+            .push_bind(action_type.clone());
+    } else if let Some(action) = &params.action {
+        builder
+            .push(" AND starts_with(action, ")
+            .push_bind(action.clone())
+            .push(")");
+    }
+    if let Some(actor_id) = params.actor_id {
+        builder.push(" AND actor_id = ").push_bind(actor_id);
+    }
+    if let Some(target_id) = params.target_id {
+        builder.push(" AND target_id = ").push_bind(target_id);
+    }
+    if let Some(from) = params.from_date {
+        builder.push(" AND created_at >= ").push_bind(from);
+    }
+    if let Some(to) = params.to_date {
+        builder.push(" AND created_at <= ").push_bind(to);
     }
-
-    // Count query
-    let mut count_builder = QueryBuilder::new("SELECT COUNT(*) FROM system_audit_log");
-    push_audit_filters!(count_builder);
-    let total: (i64,) = count_builder
-        .build_query_as::<(i64,)>()
-        .fetch_one(pool)
-        .await?;
-
-    // Main query
-    let mut builder = QueryBuilder::new(
-        "SELECT id, actor_id, action, target_type, target_id, details, \
-         host(ip_address) as ip_address, created_at \
-         FROM system_audit_log",
-    );
-    push_audit_filters!(builder);
-    builder
-        .push(" ORDER BY created_at DESC LIMIT ")
-        .push_bind(limit)
-        .push(" OFFSET ")
-        .push_bind(offset);
-    let entries: Vec<AuditLogEntry> = builder
-        .build_query_as::<AuditLogEntry>()
-        .fetch_all(pool)
-        .await?;
-
-    Ok((entries, total))
 }
 
-/// Get system audit log with pagination and optional filters.
+/// Get system audit log with cursor pagination and optional filters.
 ///
 /// `GET /api/admin/audit-log`
 ///
 /// Query parameters:
 /// - `limit`: Max items to return (default 50, max 100)
-/// - `offset`: Number of items to skip
+/// - `cursor`: `next_cursor` from the previous page (keyset pagination)
+/// - `offset`: Number of items to skip, when no `cursor` is given
 /// - `action`: Filter by action prefix (e.g., "admin." for all admin actions)
 /// - `action_type`: Filter by exact action type (e.g., "admin.users.ban")
+/// - `actor_id` / `target_id`: Filter by acting user or target
 /// - `from_date`: Filter entries created on or after this date (ISO 8601)
 /// - `to_date`: Filter entries created on or before this date (ISO 8601)
 #[utoipa::path(
@@ -593,7 +566,10 @@ async fn get_audit_log_filtered(
     path = "/api/admin/audit-log",
     tag = "admin",
     params(AuditLogParams),
-    responses((status = 200, body = PaginatedResponse<AuditLogEntryResponse>)),
+    responses(
+        (status = 200, body = AuditLogPage),
+        (status = 400, description = "Invalid date range"),
+    ),
     security(("bearer_auth" = []))
 )]
 #[tracing::instrument(skip(state))]
@@ -601,30 +577,60 @@ pub async fn get_audit_log(
     State(state): State<AppState>,
     Extension(_admin): Extension<SystemAdminUser>,
     Query(params): Query<AuditLogParams>,
-) -> Result<Json<PaginatedResponse<AuditLogEntryResponse>>, AdminError> {
+) -> Result<Json<AuditLogPage>, AdminError> {
+    if let (Some(from), Some(to)) = (params.from_date, params.to_date) {
+        if from > to {
+            return Err(AdminError::Validation(
+                "from_date must not be after to_date".to_string(),
+            ));
+        }
+    }
+
     // Clamp limit to reasonable bounds
     let limit = params.limit.clamp(1, 100);
-    let offset = params.offset.max(0);
+    let offset = if params.cursor.is_some() {
+        0
+    } else {
+        params.offset.max(0)
+    };
 
-    // Determine action filter (exact action_type takes precedence over prefix)
-    let action_filter = params.action_type.as_deref().or(params.action.as_deref());
+    let mut count_builder = QueryBuilder::new("SELECT COUNT(*) FROM system_audit_log");
+    push_audit_filters(&mut count_builder, &params);
+    let total: i64 = count_builder
+        .build_query_scalar()
+        .fetch_one(&state.db)
+        .await?;
 
-    // Build dynamic query based on filters
-    let (entries, total) = get_audit_log_filtered(
-        &state.db,
-        limit,
-        offset,
-        action_filter,
-        params.action_type.is_some(), // exact match if action_type is provided
-        params.from_date,
-        params.to_date,
-    )
-    .await?;
+    let mut builder = QueryBuilder::new(
+        "SELECT id, actor_id, action, target_type, target_id, details, \
+         host(ip_address) as ip_address, user_agent, created_at \
+         FROM system_audit_log",
+    );
+    push_audit_filters(&mut builder, &params);
+    if let Some(cursor) = params.cursor {
+        // UUIDs are random, so order by (created_at, id) and resume after the
+        // cursor row
+        builder
+            .push(" AND (created_at, id) < ((SELECT created_at FROM system_audit_log WHERE id = ")
+            .push_bind(cursor)
+            .push("), ")
+            .push_bind(cursor)
+            .push(")");
+    }
+    builder
+        .push(" ORDER BY created_at DESC, id DESC LIMIT ")
+        .push_bind(limit)
+        .push(" OFFSET ")
+        .push_bind(offset);
+    let entries: Vec<AuditLogEntry> = builder
+        .build_query_as::<AuditLogEntry>()
+        .fetch_all(&state.db)
+        .await?;
 
     // Collect unique actor IDs for username lookup (deduplicated)
     let actor_ids: Vec<Uuid> = entries
         .iter()
-        .map(|e| e.actor_id)
+        .filter_map(|e| e.actor_id)
         .collect::<HashSet<_>>()
         .into_iter()
         .collect();
@@ -641,41 +647,94 @@ pub async fn get_audit_log(
             .collect()
     };
 
+    // Only provide next_cursor when the page is full (more results likely exist)
+    let next_cursor = if entries.len() as i64 == limit {
+        entries.last().map(|e| e.id)
+    } else {
+        None
+    };
+
     let items: Vec<AuditLogEntryResponse> = entries
         .into_iter()
         .map(|e| AuditLogEntryResponse {
             id: e.id,
             actor_id: e.actor_id,
-            actor_username: usernames.get(&e.actor_id).cloned(),
+            actor_username: e.actor_id.and_then(|id| usernames.get(&id).cloned()),
             action: e.action,
             target_type: e.target_type,
             target_id: e.target_id,
             details: e.details,
             ip_address: e.ip_address,
+            user_agent: e.user_agent,
             created_at: e.created_at,
         })
         .collect();
 
-    Ok(Json(PaginatedResponse {
+    Ok(Json(AuditLogPage {
         items,
-        total: total.0,
+        total,
         limit,
         offset,
+        next_cursor,
     }))
 }
 
+/// Confirm the admin's identity for elevation.
+///
+/// Returns the method used (`mfa` or `password`) for the audit log.
+fn verify_step_up(
+    state: &AppState,
+    user: &crate::db::User,
+    body: &ElevateRequest,
+) -> Result<&'static str, AdminError> {
+    if let Some(code) = body.mfa_code.as_deref() {
+        let secret = user.mfa_secret.as_deref().ok_or(AdminError::MfaRequired)?;
+        let valid =
+            crate::auth::handlers::check_totp_code(state, secret, &user.username, code.trim())
+                .map_err(|e| AdminError::Internal(e.to_string()))?;
+        return if valid {
+            Ok("mfa")
+        } else {
+            Err(AdminError::InvalidMfaCode)
+        };
+    }
+
+    if let Some(password) = body.password.as_deref() {
+        let hash = user.password_hash.as_deref().ok_or_else(|| {
+            AdminError::Validation("Account has no password; confirm with an MFA code".to_string())
+        })?;
+        let valid = crate::auth::verify_password(password, hash)
+            .map_err(|_| AdminError::Internal("Failed to verify password".to_string()))?;
+        return if valid {
+            Ok("password")
+        } else {
+            Err(AdminError::InvalidPassword)
+        };
+    }
+
+    Err(AdminError::Validation(
+        "Password or MFA code is required".to_string(),
+    ))
+}
+
 /// Elevate admin session.
 ///
 /// `POST /api/admin/elevate`
 ///
-/// Confirms elevation of the current admin session. MFA verification will be
-/// added in a future iteration.
+/// Step-up authentication: the admin re-enters their password or a TOTP code
+/// and gets an elevated session for `ELEVATION_TTL_MINUTES`. Failed attempts
+/// are audited and the endpoint is rate limited like login.
 #[utoipa::path(
     post,
     path = "/api/admin/elevate",
     tag = "admin",
     request_body = ElevateRequest,
-    responses((status = 200, body = ElevateResponse)),
+    responses(
+        (status = 200, body = ElevateResponse),
+        (status = 400, description = "No password or code given, or MFA not enabled"),
+        (status = 401, description = "Wrong password or MFA code"),
+        (status = 429, description = "Too many attempts"),
+    ),
     security(("bearer_auth" = []))
 )]
 #[tracing::instrument(skip(state, body))]
@@ -685,7 +744,30 @@ pub async fn elevate_session(
     ConnectInfo(addr): ConnectInfo<SocketAddr>,
     Json(body): Json<ElevateRequest>,
 ) -> Result<Json<ElevateResponse>, AdminError> {
-    // TODO: Re-add MFA verification here once the MFA enrollment flow is implemented.
+    let ip_address = addr.ip().to_string();
+
+    let user = crate::db::find_user_by_id(&state.db, admin.user_id)
+        .await?
+        .ok_or(AdminError::NotAdmin)?;
+    let method = match verify_step_up(&state, &user, &body) {
+        Ok(method) => method,
+        Err(e @ (AdminError::InvalidMfaCode | AdminError::InvalidPassword)) => {
+            write_audit_log(
+                &state.db,
+                admin.user_id,
+                "admin.session.elevation_failed",
+                Some("user"),
+                Some(admin.user_id),
+                Some(serde_json::json!({
+                    "method": if body.mfa_code.is_some() { "mfa" } else { "password" },
+                })),
+                Some(&ip_address),
+            )
+            .await?;
+            return Err(e);
+        }
+        Err(e) => return Err(e),
+    };
 
     // Find or create a session for this user
     // We need a valid session_id that references sessions table
@@ -712,20 +794,24 @@ pub async fn elevate_session(
         }
     };
 
-    // Create elevated session (15 minutes)
-    let ip_address = addr.ip().to_string();
     let elevated = create_elevated_session(
         &state.db,
         admin.user_id,
         session_id,
         &ip_address,
-        15, // 15 minutes
+        super::ELEVATION_TTL_MINUTES,
         body.reason.as_deref(),
     )
     .await?;
 
-    // Cache elevated status in Redis (TTL = 15 minutes = 900 seconds)
-    super::cache_elevated_status(&state.redis, admin.user_id, true, 900).await;
+    // Cache elevated status in Redis for the lifetime of the session
+    super::cache_elevated_status(
+        &state.redis,
+        admin.user_id,
+        true,
+        super::ELEVATION_TTL_MINUTES * 60,
+    )
+    .await;
 
     // Log the elevation
     write_audit_log(
@@ -737,6 +823,7 @@ pub async fn elevate_session(
         Some(serde_json::json!({
             "reason": body.reason,
             "session_id": session_id,
+            "method": method,
         })),
         Some(&ip_address),
     )
@@ -986,8 +1073,55 @@ pub async fn unban_user(
     }))
 }
 
+/// Longest guild suspension reason, in characters.
+const MAX_SUSPENSION_REASON_LEN: usize = 500;
+
+/// Trim a guild suspension reason and check its length.
+fn normalize_suspension_reason(raw: &str) -> Result<&str, AdminError> {
+    let reason = raw.trim();
+    if reason.is_empty() || reason.chars().count() > MAX_SUSPENSION_REASON_LEN {
+        return Err(AdminError::Validation(format!(
+            "Reason must be 1-{MAX_SUSPENSION_REASON_LEN} characters"
+        )));
+    }
+    Ok(reason)
+}
+
+/// Evict a newly suspended guild from discovery and tell its owner.
+///
+/// Listing and review queries skip suspended guilds; dropping the trending
+/// stats keeps the guild out of `sort=trending` until the nightly job scores
+/// it again after the suspension is lifted.
+async fn after_guild_suspended(
+    state: &AppState,
+    guild_id: Uuid,
+    owner_id: Uuid,
+    guild_name: String,
+    reason: &str,
+) {
+    if let Err(e) = sqlx::query("DELETE FROM guild_activity_stats WHERE guild_id = $1")
+        .bind(guild_id)
+        .execute(&state.db)
+        .await
+    {
+        warn!(guild_id = %guild_id, error = %e, "Failed to drop suspended guild's trending stats");
+    }
+
+    let event = ServerEvent::GuildSuspended {
+        guild_id,
+        guild_name,
+        reason: reason.to_string(),
+    };
+    if let Err(e) = broadcast_to_user(&state.redis, owner_id, &event).await {
+        warn!(guild_id = %guild_id, error = %e, "Failed to notify owner of guild suspension");
+    }
+}
+
 /// Suspend a guild.
 ///
+/// Nobody can post or join voice in a suspended guild, its invites stop
+/// working and it leaves discovery. The owner is notified.
+///
 /// `POST /api/admin/guilds/:id/suspend`
 #[utoipa::path(
     post,
@@ -995,7 +1129,11 @@ pub async fn unban_user(
     tag = "admin",
     params(("id" = Uuid, Path, description = "Guild ID")),
     request_body = SuspendGuildRequest,
-    responses((status = 200, description = "Guild suspended", body = SuspendResponse)),
+    responses(
+        (status = 200, description = "Guild suspended", body = SuspendResponse),
+        (status = 400, description = "Invalid reason or guild already suspended"),
+        (status = 404, description = "Guild not found"),
+    ),
     security(("bearer_auth" = []))
 )]
 #[tracing::instrument(skip(state))]
@@ -1007,16 +1145,15 @@ pub async fn suspend_guild(
     Path(guild_id): Path<Uuid>,
     Json(body): Json<SuspendGuildRequest>,
 ) -> Result<Json<SuspendResponse>, AdminError> {
-    // Get guild name for the event
-    let guild = sqlx::query_as::<_, (Uuid, String)>("SELECT id, name FROM guilds WHERE id = $1")
-        .bind(guild_id)
-        .fetch_optional(&state.db)
-        .await?;
+    let reason = normalize_suspension_reason(&body.reason)?;
 
-    let guild_name = match guild {
-        Some((_, name)) => name,
-        None => return Err(AdminError::NotFound("Guild".to_string())),
-    };
+    // Get owner and name for the notifications
+    let (owner_id, guild_name) =
+        sqlx::query_as::<_, (Uuid, String)>("SELECT owner_id, name FROM guilds WHERE id = $1")
+            .bind(guild_id)
+            .fetch_optional(&state.db)
+            .await?
+            .ok_or_else(|| AdminError::NotFound("Guild".to_string()))?;
 
     let result = sqlx::query(
         r"
@@ -1029,7 +1166,7 @@ pub async fn suspend_guild(
     )
     .bind(guild_id)
     .bind(admin.user_id)
-    .bind(&body.reason)
+    .bind(reason)
     .execute(&state.db)
     .await?;
 
@@ -1047,7 +1184,7 @@ pub async fn suspend_guild(
         "admin.guilds.suspend",
         Some("guild"),
         Some(guild_id),
-        Some(serde_json::json!({"reason": body.reason})),
+        Some(serde_json::json!({"reason": reason})),
         Some(&ip_address),
     )
     .await?;
@@ -1065,6 +1202,8 @@ pub async fn suspend_guild(
         warn!(guild_id = %guild_id, error = %e, "Failed to broadcast guild suspend event");
     }
 
+    after_guild_suspended(&state, guild_id, owner_id, guild_name, reason).await;
+
     Ok(Json(SuspendResponse {
         suspended: true,
         guild_id,
@@ -1073,6 +1212,8 @@ pub async fn suspend_guild(
 
 /// Unsuspend a guild.
 ///
+/// The owner is notified. Listed guilds return to discovery right away.
+///
 /// `DELETE /api/admin/guilds/:id/suspend`
 #[utoipa::path(
     delete,
@@ -1093,12 +1234,13 @@ pub async fn unsuspend_guild(
     ConnectInfo(addr): ConnectInfo<SocketAddr>,
     Path(guild_id): Path<Uuid>,
 ) -> Result<Json<SuspendResponse>, AdminError> {
-    // Get guild name for the event
-    let guild_name = sqlx::query_scalar::<_, String>("SELECT name FROM guilds WHERE id = $1")
-        .bind(guild_id)
-        .fetch_optional(&state.db)
-        .await?
-        .unwrap_or_else(|| "Unknown".to_string());
+    // Get owner and name for the notifications
+    let (owner_id, guild_name) =
+        sqlx::query_as::<_, (Uuid, String)>("SELECT owner_id, name FROM guilds WHERE id = $1")
+            .bind(guild_id)
+            .fetch_optional(&state.db)
+            .await?
+            .ok_or_else(|| AdminError::NotFound("Guild".to_string()))?;
 
     let result = sqlx::query(
         r"
@@ -1143,6 +1285,14 @@ pub async fn unsuspend_guild(
         warn!(guild_id = %guild_id, error = %e, "Failed to broadcast guild unsuspend event");
     }
 
+    let event = ServerEvent::GuildUnsuspended {
+        guild_id,
+        guild_name,
+    };
+    if let Err(e) = broadcast_to_user(&state.redis, owner_id, &event).await {
+        warn!(guild_id = %guild_id, error = %e, "Failed to notify owner of guild unsuspension");
+    }
+
     Ok(Json(SuspendResponse {
         suspended: false,
         guild_id,
@@ -1241,7 +1391,9 @@ pub async fn get_user_details(
     let user = sqlx::query!(
         r#"
         SELECT id, username, display_name, email, avatar_url, created_at,
-               EXISTS(SELECT 1 FROM global_bans gb WHERE gb.user_id = users.id AND (gb.expires_at IS NULL OR gb.expires_at > NOW())) as "is_banned!"
+               suspended_at, suspension_reason,
+               EXISTS(SELECT 1 FROM global_bans gb WHERE gb.user_id = users.id AND (gb.expires_at IS NULL OR gb.expires_at > NOW())) as "is_banned!",
+               EXISTS(SELECT 1 FROM system_admins sa WHERE sa.user_id = users.id) as "is_system_admin!"
         FROM users
         WHERE id = $1
         "#,
@@ -1289,6 +1441,8 @@ pub async fn get_user_details(
         })
         .collect();
 
+    let username_history = list_username_history(&state.db, user_id).await?;
+
     Ok(Json(UserDetailsResponse {
         id: user.id,
         username: user.username,
@@ -1297,9 +1451,13 @@ pub async fn get_user_details(
         avatar_url: user.avatar_url,
         created_at: user.created_at,
         is_banned: user.is_banned,
+        suspended_at: user.suspended_at,
+        suspension_reason: user.suspension_reason,
+        is_system_admin: user.is_system_admin,
         last_login,
         guild_count: guilds.len() as i64,
         guilds,
+        username_history,
     }))
 }
 
@@ -1693,9 +1851,7 @@ pub async fn bulk_suspend_guilds(
             "Cannot suspend more than 100 guilds at once".to_string(),
         ));
     }
-    if body.reason.trim().is_empty() {
-        return Err(AdminError::Validation("Reason is required".to_string()));
-    }
+    let reason = normalize_suspension_reason(&body.reason)?;
 
     let mut suspended_count = 0;
     let mut already_suspended = 0;
@@ -1723,17 +1879,25 @@ pub async fn bulk_suspend_guilds(
             }
             Some(_) => {
                 // Suspend the guild
-                let suspend_result = sqlx::query(
-                    "UPDATE guilds SET suspended_at = NOW(), suspension_reason = $1 WHERE id = $2",
+                let suspend_result = sqlx::query_as::<_, (Uuid, String)>(
+                    r"UPDATE guilds SET suspended_at = NOW(), suspended_by = $1, suspension_reason = $2
+                      WHERE id = $3 AND suspended_at IS NULL
+                      RETURNING owner_id, name",
                 )
-                .bind(&body.reason)
+                .bind(admin.user_id)
+                .bind(reason)
                 .bind(guild_id)
-                .execute(&state.db)
+                .fetch_optional(&state.db)
                 .await;
 
                 match suspend_result {
-                    Ok(_) => {
+                    Ok(Some((owner_id, guild_name))) => {
                         suspended_count += 1;
+                        after_guild_suspended(&state, *guild_id, owner_id, guild_name, reason)
+                            .await;
+                    }
+                    Ok(None) => {
+                        already_suspended += 1;
                     }
                     Err(e) => {
                         failed.push(BulkActionFailure {
@@ -1758,7 +1922,7 @@ pub async fn bulk_suspend_guilds(
             "suspended_count": suspended_count,
             "already_suspended": already_suspended,
             "failed_count": failed.len(),
-            "reason": body.reason
+            "reason": reason
         })),
         Some(&ip_address),
     )
@@ -1832,6 +1996,7 @@ pub async fn update_auth_settings(
     State(state): State<AppState>,
     Extension(admin): Extension<SystemAdminUser>,
     Extension(_elevated): Extension<ElevatedAdmin>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
     Json(body): Json<UpdateAuthSettingsRequest>,
 ) -> Result<Json<AuthSettingsResponse>, AdminError> {
     if let Some(ref methods) = body.auth_methods {
@@ -1839,10 +2004,13 @@ pub async fn update_auth_settings(
     }
 
     if let Some(ref policy) = body.registration_policy {
-        let valid = matches!(policy.as_str(), "open" | "invite_only" | "closed");
+        let valid = matches!(
+            policy.as_str(),
+            "open" | "invite_only" | "approval" | "closed"
+        );
         if !valid {
             return Err(AdminError::Validation(
-                "registration_policy must be 'open', 'invite_only', or 'closed'".into(),
+                "registration_policy must be 'open', 'invite_only', 'approval', or 'closed'".into(),
             ));
         }
         crate::db::set_config_value(
@@ -1862,6 +2030,20 @@ pub async fn update_auth_settings(
         .and_then(|v| v.as_str().map(String::from))
         .unwrap_or_else(|| "open".to_string());
 
+    write_audit_log(
+        &state.db,
+        admin.user_id,
+        "admin.auth_settings.update",
+        None,
+        None,
+        Some(serde_json::json!({
+            "auth_methods": auth_methods,
+            "registration_policy": registration_policy,
+        })),
+        Some(&addr.ip().to_string()),
+    )
+    .await?;
+
     Ok(Json(AuthSettingsResponse {
         auth_methods,
         registration_policy,
@@ -1885,6 +2067,8 @@ pub struct OidcProviderResponse {
     pub enabled: bool,
     pub position: i32,
     pub created_at: DateTime<Utc>,
+    pub provisioning: String,
+    pub link_by_email: bool,
 }
 
 impl From<crate::db::OidcProviderRow> for OidcProviderResponse {
@@ -1904,6 +2088,8 @@ impl From<crate::db::OidcProviderRow> for OidcProviderResponse {
             enabled: row.enabled,
             position: row.position,
             created_at: row.created_at,
+            provisioning: row.provisioning,
+            link_by_email: row.link_by_email,
         }
     }
 }
@@ -1941,6 +2127,22 @@ pub struct CreateOidcProviderRequest {
     pub client_id: String,
     pub client_secret: String,
     pub scopes: Option<String>,
+    /// Account creation for unlinked identities: `registration_policy`
+    /// (default), `always` or `never`.
+    pub provisioning: Option<String>,
+    /// Link unlinked identities to the account with the same verified email.
+    #[serde(default)]
+    pub link_by_email: bool,
+}
+
+/// Check an OIDC provisioning policy from an admin request.
+fn validate_provisioning(value: &str) -> Result<(), AdminError> {
+    if crate::auth::oidc::Provisioning::parse(value).is_none() {
+        return Err(AdminError::Validation(
+            "provisioning must be 'registration_policy', 'always', or 'never'".into(),
+        ));
+    }
+    Ok(())
 }
 
 /// Create a new OIDC provider.
@@ -1958,12 +2160,19 @@ pub async fn create_oidc_provider(
     State(state): State<AppState>,
     Extension(admin): Extension<SystemAdminUser>,
     Extension(_elevated): Extension<ElevatedAdmin>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
     Json(body): Json<CreateOidcProviderRequest>,
 ) -> Result<Json<OidcProviderResponse>, AdminError> {
     let oidc_manager = state.oidc_manager.as_ref().ok_or_else(|| {
         AdminError::Internal("OIDC manager not configured (requires MFA_ENCRYPTION_KEY)".into())
     })?;
 
+    let provisioning = body
+        .provisioning
+        .as_deref()
+        .unwrap_or(crate::auth::oidc::Provisioning::RegistrationPolicy.as_str());
+    validate_provisioning(provisioning)?;
+
     // Apply preset defaults
     let (provider_type, issuer_url, authorization_url, token_url, userinfo_url, scopes) =
         match body.slug.as_str() {
@@ -2015,6 +2224,8 @@ pub async fn create_oidc_provider(
             client_id: &body.client_id,
             client_secret_encrypted: &encrypted_secret,
             scopes: &scopes,
+            provisioning,
+            link_by_email: body.link_by_email,
             created_by: admin.user_id,
         },
     )
@@ -2025,6 +2236,17 @@ pub async fn create_oidc_provider(
         warn!(error = %e, "Failed to reload OIDC providers after creation");
     }
 
+    write_audit_log(
+        &state.db,
+        admin.user_id,
+        "admin.oidc_providers.create",
+        Some("oidc_provider"),
+        Some(row.id),
+        Some(serde_json::json!({ "slug": row.slug, "provisioning": row.provisioning })),
+        Some(&addr.ip().to_string()),
+    )
+    .await?;
+
     Ok(Json(row.into()))
 }
 
@@ -2042,6 +2264,10 @@ pub struct UpdateOidcProviderRequest {
     pub client_secret: Option<String>,
     pub scopes: String,
     pub enabled: bool,
+    /// If omitted, the existing policy is kept.
+    pub provisioning: Option<String>,
+    /// If omitted, the existing setting is kept.
+    pub link_by_email: Option<bool>,
 }
 
 /// Update an OIDC provider.
@@ -2058,8 +2284,9 @@ pub struct UpdateOidcProviderRequest {
 )]
 pub async fn update_oidc_provider(
     State(state): State<AppState>,
-    Extension(_admin): Extension<SystemAdminUser>,
+    Extension(admin): Extension<SystemAdminUser>,
     Extension(_elevated): Extension<ElevatedAdmin>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
     Path(id): Path<Uuid>,
     Json(body): Json<UpdateOidcProviderRequest>,
 ) -> Result<Json<OidcProviderResponse>, AdminError> {
@@ -2068,6 +2295,10 @@ pub async fn update_oidc_provider(
         .as_ref()
         .ok_or_else(|| AdminError::Internal("OIDC manager not configured".into()))?;
 
+    if let Some(ref provisioning) = body.provisioning {
+        validate_provisioning(provisioning)?;
+    }
+
     let encrypted_secret = if let Some(ref secret) = body.client_secret {
         Some(
             oidc_manager
@@ -2092,6 +2323,8 @@ pub async fn update_oidc_provider(
             client_secret_encrypted: encrypted_secret.as_deref(),
             scopes: &body.scopes,
             enabled: body.enabled,
+            provisioning: body.provisioning.as_deref(),
+            link_by_email: body.link_by_email,
         },
     )
     .await?;
@@ -2101,6 +2334,21 @@ pub async fn update_oidc_provider(
         warn!(error = %e, "Failed to reload OIDC providers after update");
     }
 
+    write_audit_log(
+        &state.db,
+        admin.user_id,
+        "admin.oidc_providers.update",
+        Some("oidc_provider"),
+        Some(id),
+        Some(serde_json::json!({
+            "slug": row.slug,
+            "enabled": row.enabled,
+            "secret_rotated": body.client_secret.is_some(),
+        })),
+        Some(&addr.ip().to_string()),
+    )
+    .await?;
+
     Ok(Json(row.into()))
 }
 
@@ -2117,8 +2365,9 @@ pub async fn update_oidc_provider(
 )]
 pub async fn delete_oidc_provider(
     State(state): State<AppState>,
-    Extension(_admin): Extension<SystemAdminUser>,
+    Extension(admin): Extension<SystemAdminUser>,
     Extension(_elevated): Extension<ElevatedAdmin>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
     Path(id): Path<Uuid>,
 ) -> Result<Json<serde_json::Value>, AdminError> {
     let oidc_manager = state
@@ -2133,6 +2382,17 @@ pub async fn delete_oidc_provider(
         warn!(error = %e, "Failed to reload OIDC providers after deletion");
     }
 
+    write_audit_log(
+        &state.db,
+        admin.user_id,
+        "admin.oidc_providers.delete",
+        Some("oidc_provider"),
+        Some(id),
+        None,
+        Some(&addr.ip().to_string()),
+    )
+    .await?;
+
     Ok(Json(serde_json::json!({ "success": true })))
 }
 
@@ -2294,7 +2554,7 @@ pub async fn delete_guild(
 // ============================================================================
 
 /// Request to set per-guild page limits.
-#[derive(Debug, Deserialize, ToSchema)]
+#[derive(Debug, Default, Deserialize, ToSchema)]
 pub struct SetGuildPageLimitsRequest {
     /// Maximum pages (null = reset to instance default, min 1).
     #[serde(default, deserialize_with = "deserialize_double_option")]
@@ -2305,7 +2565,9 @@ pub struct SetGuildPageLimitsRequest {
 }
 
 #[allow(clippy::option_option)]
-fn deserialize_double_option<'de, T, D>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+pub(super) fn deserialize_double_option<'de, T, D>(
+    deserializer: D,
+) -> Result<Option<Option<T>>, D::Error>
 where
     T: Deserialize<'de>,
     D: Deserializer<'de>,
@@ -2323,6 +2585,29 @@ pub struct GuildPageLimitsResponse {
     pub instance_default_revisions: i64,
 }
 
+/// Validate the bounds of per-guild page limit overrides.
+pub(crate) fn validate_page_limits(limits: &SetGuildPageLimitsRequest) -> Result<(), AdminError> {
+    const MAX_ALLOWED_PAGES: i32 = 1000;
+    const MAX_ALLOWED_REVISIONS: i32 = 500;
+
+    if let Some(Some(max_pages)) = limits.max_pages {
+        if !(1..=MAX_ALLOWED_PAGES).contains(&max_pages) {
+            return Err(AdminError::Validation(format!(
+                "max_pages must be between 1 and {MAX_ALLOWED_PAGES}"
+            )));
+        }
+    }
+    if let Some(Some(max_revisions)) = limits.max_revisions {
+        if !(5..=MAX_ALLOWED_REVISIONS).contains(&max_revisions) {
+            return Err(AdminError::Validation(format!(
+                "max_revisions must be between 5 and {MAX_ALLOWED_REVISIONS}"
+            )));
+        }
+    }
+
+    Ok(())
+}
+
 /// Get per-guild page limits.
 ///
 /// GET /api/admin/guilds/:id/page-limits
@@ -2353,8 +2638,8 @@ pub async fn get_guild_page_limits(
         guild_id,
         max_pages,
         max_revisions,
-        instance_default_pages: state.config.max_pages_per_guild,
-        instance_default_revisions: state.config.max_revisions_per_page,
+        instance_default_pages: state.config().max_pages_per_guild,
+        instance_default_revisions: state.config().max_revisions_per_page,
     }))
 }
 
@@ -2373,29 +2658,13 @@ pub async fn get_guild_page_limits(
 #[tracing::instrument(skip(state))]
 pub async fn set_guild_page_limits(
     State(state): State<AppState>,
-    Extension(_admin): Extension<SystemAdminUser>,
+    Extension(admin): Extension<SystemAdminUser>,
     Extension(_elevated): Extension<ElevatedAdmin>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
     Path(guild_id): Path<Uuid>,
     Json(body): Json<SetGuildPageLimitsRequest>,
 ) -> Result<Json<GuildPageLimitsResponse>, AdminError> {
-    // Validate bounds
-    const MAX_ALLOWED_PAGES: i32 = 1000;
-    const MAX_ALLOWED_REVISIONS: i32 = 500;
-
-    if let Some(Some(max_pages)) = body.max_pages {
-        if !(1..=MAX_ALLOWED_PAGES).contains(&max_pages) {
-            return Err(AdminError::Validation(format!(
-                "max_pages must be between 1 and {MAX_ALLOWED_PAGES}"
-            )));
-        }
-    }
-    if let Some(Some(max_revisions)) = body.max_revisions {
-        if !(5..=MAX_ALLOWED_REVISIONS).contains(&max_revisions) {
-            return Err(AdminError::Validation(format!(
-                "max_revisions must be between 5 and {MAX_ALLOWED_REVISIONS}"
-            )));
-        }
-    }
+    validate_page_limits(&body)?;
 
     let max_pages_present = body.max_pages.is_some();
     let max_pages_value = body.max_pages.flatten();
@@ -2419,11 +2688,84 @@ pub async fn set_guild_page_limits(
 
     let (max_pages, max_revisions) = row.ok_or(AdminError::NotFound("Guild not found".into()))?;
 
+    write_audit_log(
+        &state.db,
+        admin.user_id,
+        "admin.guilds.page_limits",
+        Some("guild"),
+        Some(guild_id),
+        Some(serde_json::json!({ "max_pages": max_pages, "max_revisions": max_revisions })),
+        Some(&addr.ip().to_string()),
+    )
+    .await?;
+
     Ok(Json(GuildPageLimitsResponse {
         guild_id,
         max_pages,
         max_revisions,
-        instance_default_pages: state.config.max_pages_per_guild,
-        instance_default_revisions: state.config.max_revisions_per_page,
+        instance_default_pages: state.config().max_pages_per_guild,
+        instance_default_revisions: state.config().max_revisions_per_page,
     }))
 }
+
+// ============================================================================
+// Guild Supporters
+// ============================================================================
+
+/// Request to grant or revoke supporter status of a guild member.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct SetSupporterRequest {
+    /// Whether the member is a supporter.
+    pub supporter: bool,
+}
+
+/// Grant or revoke supporter status of a guild member.
+///
+/// PUT `/api/admin/guilds/:id/members/:user_id/supporter`
+#[utoipa::path(
+    put,
+    path = "/api/admin/guilds/{id}/members/{user_id}/supporter",
+    tag = "admin",
+    params(
+        ("id" = Uuid, Path, description = "Guild ID"),
+        ("user_id" = Uuid, Path, description = "Member user ID"),
+    ),
+    request_body = SetSupporterRequest,
+    responses(
+        (status = 200, description = "Updated guild perks", body = GuildPerks),
+        (status = 404, description = "Guild member not found"),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[tracing::instrument(skip(state))]
+pub async fn set_guild_supporter(
+    State(state): State<AppState>,
+    Extension(admin): Extension<SystemAdminUser>,
+    Extension(_elevated): Extension<ElevatedAdmin>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    Path((guild_id, user_id)): Path<(Uuid, Uuid)>,
+    Json(body): Json<SetSupporterRequest>,
+) -> Result<Json<GuildPerks>, AdminError> {
+    let source = body.supporter.then_some(SupporterSource::Manual);
+    if !perks::set_supporter(&state.db, guild_id, user_id, source).await? {
+        return Err(AdminError::NotFound("Guild member".to_string()));
+    }
+
+    let ip_address = addr.ip().to_string();
+    write_audit_log(
+        &state.db,
+        admin.user_id,
+        if body.supporter {
+            "admin.guilds.supporter_grant"
+        } else {
+            "admin.guilds.supporter_revoke"
+        },
+        Some("guild"),
+        Some(guild_id),
+        Some(serde_json::json!({ "user_id": user_id })),
+        Some(&ip_address),
+    )
+    .await?;
+
+    Ok(Json(perks::refresh(&state, guild_id).await?))
+}
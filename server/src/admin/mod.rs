@@ -1,15 +1,32 @@
 //! System Admin Module
 //!
 //! Provides admin-only endpoints for platform management:
-//! - Non-elevated: list users, list guilds, audit log, elevate/de-elevate session
-//! - Elevated: ban users, suspend guilds, manage announcements
+//! - Non-elevated: list users, list guilds, audit log, cluster nodes,
+//!   live voice rooms, pending registrations and registration codes,
+//!   elevate/de-elevate session
+//! - Elevated: ban or suspend users, revoke their sessions, send password
+//!   resets, grant or revoke system admin, suspend guilds, manage
+//!   announcements, replay dead-lettered webhook deliveries, manage server
+//!   settings and data retention, review guilds for discovery, release or delete quarantined
+//!   attachments, approve or reject registrations, mint registration codes
+//! - Public: signed billing entitlement webhook
 
+pub mod attachments;
+pub mod cluster;
+pub mod discovery;
+pub mod entitlements;
 pub mod handlers;
 pub mod middleware;
 pub mod observability;
+pub mod registrations;
+pub mod retention;
+pub mod settings;
 pub mod types;
+pub mod users;
+pub mod voice;
+pub mod webhooks;
 
-use axum::middleware::from_fn_with_state;
+use axum::middleware::{from_fn, from_fn_with_state};
 use axum::routing::{delete, get, post, put};
 use axum::Router;
 use fred::prelude::*;
@@ -19,6 +36,11 @@ pub use types::{AdminError, ElevatedAdmin, SystemAdminUser};
 use uuid::Uuid;
 
 use crate::api::AppState;
+use crate::ratelimit::{rate_limit_by_ip, with_category, RateLimitCategory};
+
+/// Lifetime of an elevated admin session, in minutes. The Redis status key
+/// (`admin:elevated:{user_id}`) expires with it.
+pub const ELEVATION_TTL_MINUTES: i64 = 15;
 
 /// Check if a user is an elevated admin (for WebSocket subscription check).
 /// This checks both system admin status and elevated session validity.
@@ -79,6 +101,17 @@ pub async fn cache_elevated_status(
         .await;
 }
 
+/// Create the public admin router.
+///
+/// Machine-to-machine endpoints that authenticate the request themselves
+/// (no user session), such as the signed billing entitlement webhook.
+pub fn public_router() -> Router<AppState> {
+    Router::new().route(
+        "/entitlements/webhook",
+        post(entitlements::receive_entitlement_webhook),
+    )
+}
+
 /// Create the admin router.
 ///
 /// Most routes require system admin privileges (applied via middleware).
@@ -102,12 +135,23 @@ pub fn router(state: AppState) -> Router<AppState> {
         )
         .route(
             "/reports/{id}/claim",
-            post(crate::moderation::admin_handlers::claim_report),
+            post(crate::moderation::admin_handlers::claim_report)
+                .delete(crate::moderation::admin_handlers::unclaim_report),
         )
         .route(
             "/reports/{id}/resolve",
             post(crate::moderation::admin_handlers::resolve_report),
         )
+        .route(
+            "/reports/{id}/notes",
+            get(crate::moderation::admin_handlers::list_notes)
+                .post(crate::moderation::admin_handlers::create_note),
+        )
+        .route(
+            "/reports/{id}/evidence",
+            get(crate::moderation::admin_handlers::list_evidence)
+                .post(crate::moderation::admin_handlers::add_evidence),
+        )
         // User management
         .route(
             "/users/{id}/ban",
@@ -116,6 +160,23 @@ pub fn router(state: AppState) -> Router<AppState> {
         .route("/users/{id}/unban", post(handlers::unban_user))
         .route("/users/bulk-ban", post(handlers::bulk_ban_users))
         .route("/users/{id}", delete(handlers::delete_user))
+        .route(
+            "/users/{id}/suspend",
+            post(users::suspend_user).delete(users::unsuspend_user),
+        )
+        .route("/users/{id}/unsuspend", post(users::unsuspend_user))
+        .route(
+            "/users/{id}/revoke-sessions",
+            post(users::revoke_user_sessions),
+        )
+        .route(
+            "/users/{id}/password-reset",
+            post(users::send_password_reset),
+        )
+        .route(
+            "/users/{id}/system-admin",
+            post(users::grant_admin).delete(users::revoke_admin),
+        )
         .route(
             "/guilds/{id}/suspend",
             post(handlers::suspend_guild).delete(handlers::unsuspend_guild),
@@ -137,11 +198,63 @@ pub fn router(state: AppState) -> Router<AppState> {
             "/oidc-providers/{id}",
             put(handlers::update_oidc_provider).delete(handlers::delete_oidc_provider),
         )
+        // Instance data retention
+        .route(
+            "/retention",
+            get(retention::get_retention_settings).patch(retention::update_retention_settings),
+        )
+        // Server settings (name, registration, discovery, limits, retention)
+        .route(
+            "/settings",
+            get(settings::get_server_settings).patch(settings::update_server_settings),
+        )
+        // Reload the environment configuration (same as SIGHUP)
+        .route("/config/reload", post(settings::reload_config))
         // Per-guild page limits
         .route(
             "/guilds/{id}/page-limits",
             get(handlers::get_guild_page_limits).patch(handlers::set_guild_page_limits),
         )
+        // Guild supporters (perks)
+        .route(
+            "/guilds/{id}/members/{user_id}/supporter",
+            put(handlers::set_guild_supporter),
+        )
+        // Discovery review
+        .route(
+            "/discovery/queue/{guild_id}/approve",
+            post(discovery::approve),
+        )
+        .route(
+            "/discovery/queue/{guild_id}/reject",
+            post(discovery::reject),
+        )
+        // Registration approval and codes
+        .route(
+            "/registrations/{user_id}/approve",
+            post(registrations::approve),
+        )
+        .route(
+            "/registrations/{user_id}/reject",
+            post(registrations::reject),
+        )
+        .route("/registration-codes", post(registrations::create_code))
+        .route(
+            "/registration-codes/{id}",
+            delete(registrations::delete_code),
+        )
+        // Attachment quarantine
+        .route("/attachments/{id}", delete(attachments::delete))
+        .route("/attachments/{id}/release", post(attachments::release))
+        // Webhook dead letters
+        .route(
+            "/webhooks/dead-letters/replay",
+            post(webhooks::replay_dead_letters),
+        )
+        .route(
+            "/webhooks/dead-letters/{id}/replay",
+            post(webhooks::replay_dead_letter),
+        )
         .layer(from_fn_with_state(state.clone(), require_elevated));
 
     // Non-elevated admin routes (require system admin)
@@ -155,9 +268,23 @@ pub fn router(state: AppState) -> Router<AppState> {
         .route("/guilds/export", get(handlers::export_guilds_csv))
         .route("/guilds/{id}/details", get(handlers::get_guild_details))
         .route("/audit-log", get(handlers::get_audit_log))
+        .route("/reports/capacity", get(observability::capacity_report))
+        .route("/cluster/nodes", get(cluster::list_nodes))
+        .route("/voice/rooms", get(voice::list_voice_rooms))
+        .route("/voice/rooms/{channel_id}", get(voice::get_voice_room))
+        .route("/webhooks/dead-letters", get(webhooks::list_dead_letters))
+        .route("/discovery/queue", get(discovery::list_queue))
+        .route("/attachments/held", get(attachments::list_held))
+        .route("/registrations", get(registrations::list_pending))
+        .route("/registration-codes", get(registrations::list_codes))
+        // Elevation takes a password or TOTP code, so it is rate limited like
+        // login. The limit layers only wrap POST; DELETE is added after them.
         .route(
             "/elevate",
-            post(handlers::elevate_session).delete(handlers::de_elevate_session),
+            post(handlers::elevate_session)
+                .layer(from_fn_with_state(state.clone(), rate_limit_by_ip))
+                .layer(from_fn(with_category(RateLimitCategory::AuthLogin)))
+                .delete(handlers::de_elevate_session),
         )
         .nest("/observability", observability::router())
         .merge(elevated_routes)
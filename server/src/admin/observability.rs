@@ -8,6 +8,8 @@
 use std::time::Instant;
 
 use axum::extract::{Query, State};
+use axum::http::header;
+use axum::response::{IntoResponse, Response};
 use axum::{Extension, Json};
 use chrono::{DateTime, Duration, Utc};
 use futures::future::try_join_all;
@@ -16,7 +18,7 @@ use uuid::Uuid;
 
 use super::types::{AdminError, SystemAdminUser};
 use crate::api::AppState;
-use crate::observability::storage;
+use crate::observability::{capacity, consumers, reports, storage, ws_disconnects};
 
 /// Server start time. Call [`init_start_time`] early in `main()` for accuracy.
 static START_TIME: std::sync::OnceLock<Instant> = std::sync::OnceLock::new();
@@ -113,6 +115,56 @@ pub struct TopErrorsParams {
     pub limit: i64,
 }
 
+/// Consumer kind for top consumers queries.
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TopConsumersType {
+    #[default]
+    User,
+    Guild,
+}
+
+/// Sort order for top consumers queries.
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TopConsumersSort {
+    #[default]
+    Requests,
+    WsEvents,
+    VoiceMinutes,
+}
+
+/// Top consumers query parameters.
+#[derive(Debug, Deserialize)]
+pub struct TopConsumersParams {
+    pub range: TimeRange,
+    #[serde(default, rename = "type")]
+    pub consumer_type: TopConsumersType,
+    #[serde(default)]
+    pub sort: TopConsumersSort,
+    #[serde(default = "default_top_limit")]
+    pub limit: i64,
+}
+
+/// WebSocket disconnect breakdown query parameters.
+#[derive(Debug, Deserialize)]
+pub struct WsDisconnectsParams {
+    pub range: TimeRange,
+}
+
+/// Report SLA query parameters.
+#[derive(Debug, Deserialize)]
+pub struct ReportSlaParams {
+    pub range: TimeRange,
+    /// Target time from report creation to resolution, in hours.
+    #[serde(default = "default_sla_hours")]
+    pub sla_hours: i64,
+}
+
+const fn default_sla_hours() -> i64 {
+    24
+}
+
 /// Logs query parameters (cursor-based pagination).
 #[derive(Debug, Deserialize)]
 pub struct LogsParams {
@@ -215,6 +267,12 @@ pub struct ErrorCategoryEntry {
     pub avg_p95_ms: Option<f64>,
 }
 
+/// Top consumers response.
+#[derive(Debug, Serialize)]
+pub struct TopConsumersResponse {
+    pub consumers: Vec<consumers::TopConsumerEntry>,
+}
+
 /// Logs response with cursor-based pagination.
 #[derive(Debug, Serialize)]
 pub struct LogsResponse {
@@ -246,6 +304,13 @@ pub struct LinksResponse {
 ///
 /// Returns vital signs, server metadata, voice health, and recent error count.
 /// All telemetry queries run concurrently via `tokio::try_join!`.
+#[utoipa::path(
+    get,
+    path = "/api/admin/observability/summary",
+    tag = "admin",
+    responses((status = 200, description = "Vital signs and server metadata")),
+    security(("bearer_auth" = []))
+)]
 #[tracing::instrument(skip(state, _admin))]
 pub async fn summary(
     Extension(_admin): Extension<SystemAdminUser>,
@@ -256,128 +321,80 @@ pub async fn summary(
     let db = &state.db;
 
     // Run all queries concurrently
-    let (
-        latency_p95,
-        error_metrics,
-        ws_connections,
-        voice_sessions,
-        user_count,
-        guild_count,
-        active_alert_count,
-    ) = tokio::try_join!(
-        // Latency p95 (last 5 minutes)
-        async {
-            sqlx::query_scalar::<_, Option<f64>>(
-                "SELECT AVG(value_p95) FROM telemetry_metric_samples \
-                 WHERE metric_name = 'kaiku_http_request_duration_ms' \
-                 AND ts >= $1 AND ts <= $2",
-            )
-            .bind(five_min_ago)
-            .bind(now)
-            .fetch_optional(db)
-            .await
-            .map(|r| r.flatten())
-        },
-        // Error rate (last 5 minutes)
-        async {
-            sqlx::query_as::<_, (Option<i64>, Option<i64>)>(
-                "SELECT \
-                     SUM(CASE WHEN metric_name = 'kaiku_http_errors_total' THEN value_count ELSE 0 END), \
-                     SUM(CASE WHEN metric_name = 'kaiku_http_requests_total' THEN value_count ELSE 0 END) \
-                 FROM telemetry_metric_samples \
-                 WHERE metric_name IN ('kaiku_http_errors_total', 'kaiku_http_requests_total') \
-                 AND ts >= $1 AND ts <= $2",
-            )
-            .bind(five_min_ago)
-            .bind(now)
-            .fetch_optional(db)
-            .await
-        },
-        // Active WebSocket connections (most recent gauge)
+    let (vitals, user_count, guild_count) = tokio::try_join!(
+        // Latency, error rate, connection gauges and recent errors (last 5 minutes)
         async {
-            sqlx::query_scalar::<_, Option<i64>>(
-                "SELECT value_count FROM telemetry_metric_samples \
-                 WHERE metric_name = 'kaiku_ws_connections_active' \
-                 AND ts >= $1 \
-                 ORDER BY ts DESC LIMIT 1",
-            )
-            .bind(five_min_ago)
-            .fetch_optional(db)
-            .await
-            .map(|r| r.flatten())
-        },
-        // Active voice sessions (most recent gauge)
-        async {
-            sqlx::query_scalar::<_, Option<i64>>(
-                "SELECT value_count FROM telemetry_metric_samples \
-                 WHERE metric_name = 'kaiku_voice_sessions_active' \
-                 AND ts >= $1 \
-                 ORDER BY ts DESC LIMIT 1",
-            )
-            .bind(five_min_ago)
-            .fetch_optional(db)
-            .await
-            .map(|r| r.flatten())
+            state
+                .telemetry
+                .query_vital_signs(five_min_ago, now)
+                .await
+                .map_err(AdminError::from)
         },
         // User count
         async {
             sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM users")
                 .fetch_one(db)
                 .await
+                .map_err(AdminError::from)
         },
         // Guild count
         async {
             sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM guilds")
                 .fetch_one(db)
                 .await
-        },
-        // Recent error count (last 5 minutes)
-        async {
-            sqlx::query_scalar::<_, i64>(
-                "SELECT COUNT(*) FROM telemetry_log_events \
-                 WHERE level = 'ERROR' AND ts >= $1",
-            )
-            .bind(five_min_ago)
-            .fetch_one(db)
-            .await
+                .map_err(AdminError::from)
         },
     )?;
 
-    let error_rate_percent = error_metrics.and_then(|(errors, total)| {
-        let e = errors.unwrap_or(0) as f64;
-        let t = total.unwrap_or(0) as f64;
+    let error_rate_percent = {
+        let e = vitals.error_count.unwrap_or(0) as f64;
+        let t = vitals.request_count.unwrap_or(0) as f64;
         if t > 0.0 {
             Some(e / t * 100.0)
         } else {
             None
         }
-    });
+    };
 
     // Voice health score (cached, refreshed every 10s — no DB query)
     let voice_health_score = crate::observability::voice::get_voice_health_score().await;
 
     Ok(Json(SummaryResponse {
         vital_signs: VitalSigns {
-            latency_p95_ms: latency_p95,
+            latency_p95_ms: vitals.latency_p95_ms,
             error_rate_percent,
-            active_ws_connections: ws_connections,
-            active_voice_sessions: voice_sessions,
+            active_ws_connections: vitals.ws_connections,
+            active_voice_sessions: vitals.voice_sessions,
         },
         server_metadata: ServerMetadata {
             version: env!("CARGO_PKG_VERSION"),
             uptime_seconds: server_uptime_seconds(),
-            environment: state.config.environment.clone(),
+            environment: state.config().environment.clone(),
             active_user_count: user_count,
             guild_count,
         },
         voice_health_score,
-        active_alert_count,
+        active_alert_count: vitals.error_log_count,
     }))
 }
 
 /// `GET /api/admin/observability/trends`
 ///
 /// Returns time-series data for requested metrics over the given range.
+#[utoipa::path(
+    get,
+    path = "/api/admin/observability/trends",
+    tag = "admin",
+    params(
+        ("range" = String, Query, description = "1h, 6h, 24h, 7d or 30d"),
+        ("metric" = Vec<String>, Query, description = "Metric names (1-10, `kaiku_` prefix), repeatable"),
+    ),
+    responses(
+        (status = 200, description = "Metric time series"),
+        (status = 400, description = "Missing or invalid metric names"),
+    ),
+    security(("bearer_auth" = []))
+)]
 #[tracing::instrument(skip(state, _admin))]
 pub async fn trends(
     Extension(_admin): Extension<SystemAdminUser>,
@@ -412,11 +429,11 @@ pub async fn trends(
         .metric
         .iter()
         .map(|name| {
-            let db = state.db.clone();
+            let telemetry = state.telemetry.clone();
             let name = name.clone();
             async move {
-                let datapoints = storage::query_trends(&db, &name, from, to).await?;
-                Ok::<_, sqlx::Error>(MetricTrend {
+                let datapoints = telemetry.query_trends(&name, from, to).await?;
+                Ok::<_, storage::StorageError>(MetricTrend {
                     metric_name: name,
                     datapoints,
                 })
@@ -432,6 +449,18 @@ pub async fn trends(
 /// `GET /api/admin/observability/top-routes`
 ///
 /// Returns top routes ranked by latency or error count.
+#[utoipa::path(
+    get,
+    path = "/api/admin/observability/top-routes",
+    tag = "admin",
+    params(
+        ("range" = String, Query, description = "1h, 6h, 24h, 7d or 30d"),
+        ("sort" = Option<String>, Query, description = "latency (default) or errors"),
+        ("limit" = Option<i64>, Query, description = "Max routes (1-10, default 10)"),
+    ),
+    responses((status = 200, description = "Top routes")),
+    security(("bearer_auth" = []))
+)]
 #[tracing::instrument(skip(state, _admin))]
 pub async fn top_routes(
     Extension(_admin): Extension<SystemAdminUser>,
@@ -442,7 +471,10 @@ pub async fn top_routes(
     let sort_by_errors = matches!(params.sort, TopRoutesSort::Errors);
     let limit = params.limit.clamp(1, 10);
 
-    let raw = storage::query_top_routes(&state.db, from, to, sort_by_errors, limit).await?;
+    let raw = state
+        .telemetry
+        .query_top_routes(from, to, sort_by_errors, limit)
+        .await?;
 
     let routes = raw
         .into_iter()
@@ -470,6 +502,17 @@ pub async fn top_routes(
 /// `GET /api/admin/observability/top-errors`
 ///
 /// Returns top error categories ranked by count.
+#[utoipa::path(
+    get,
+    path = "/api/admin/observability/top-errors",
+    tag = "admin",
+    params(
+        ("range" = String, Query, description = "1h, 6h, 24h, 7d or 30d"),
+        ("limit" = Option<i64>, Query, description = "Max categories (1-10, default 10)"),
+    ),
+    responses((status = 200, description = "Top error categories")),
+    security(("bearer_auth" = []))
+)]
 #[tracing::instrument(skip(state, _admin))]
 pub async fn top_errors(
     Extension(_admin): Extension<SystemAdminUser>,
@@ -479,7 +522,7 @@ pub async fn top_errors(
     let (from, to) = params.range.to_time_bounds();
     let limit = params.limit.clamp(1, 10);
 
-    let raw = storage::query_top_errors(&state.db, from, to, limit).await?;
+    let raw = state.telemetry.query_top_errors(from, to, limit).await?;
 
     let error_categories = raw
         .into_iter()
@@ -493,9 +536,119 @@ pub async fn top_errors(
     Ok(Json(TopErrorsResponse { error_categories }))
 }
 
+/// `GET /api/admin/observability/top-consumers`
+///
+/// Returns the users or guilds generating the most API requests, WebSocket
+/// events, or voice minutes over the range.
+#[utoipa::path(
+    get,
+    path = "/api/admin/observability/top-consumers",
+    tag = "admin",
+    params(
+        ("range" = String, Query, description = "1h, 6h, 24h, 7d or 30d"),
+        ("type" = Option<String>, Query, description = "user (default) or guild"),
+        ("sort" = Option<String>, Query, description = "requests (default), ws_events or voice_minutes"),
+        ("limit" = Option<i64>, Query, description = "Max consumers (1-50, default 10)"),
+    ),
+    responses((status = 200, description = "Top consumers")),
+    security(("bearer_auth" = []))
+)]
+#[tracing::instrument(skip(state, _admin))]
+pub async fn top_consumers(
+    Extension(_admin): Extension<SystemAdminUser>,
+    State(state): State<AppState>,
+    Query(params): Query<TopConsumersParams>,
+) -> Result<Json<TopConsumersResponse>, AdminError> {
+    let (from, to) = params.range.to_time_bounds();
+    let limit = params.limit.clamp(1, 50);
+    let consumer_type = match params.consumer_type {
+        TopConsumersType::User => consumers::ConsumerType::User,
+        TopConsumersType::Guild => consumers::ConsumerType::Guild,
+    };
+    let sort = match params.sort {
+        TopConsumersSort::Requests => consumers::ConsumerSort::Requests,
+        TopConsumersSort::WsEvents => consumers::ConsumerSort::WsEvents,
+        TopConsumersSort::VoiceMinutes => consumers::ConsumerSort::VoiceMinutes,
+    };
+
+    let consumers =
+        consumers::query_top_consumers(&state.db, consumer_type, from, to, sort, limit).await?;
+
+    Ok(Json(TopConsumersResponse { consumers }))
+}
+
+/// `GET /api/admin/observability/ws-disconnects`
+///
+/// Returns WebSocket disconnects broken down by cause, close code, time, and
+/// the users who disconnected most often.
+#[utoipa::path(
+    get,
+    path = "/api/admin/observability/ws-disconnects",
+    tag = "admin",
+    params(
+        ("range" = String, Query, description = "1h, 6h, 24h, 7d or 30d"),
+    ),
+    responses((status = 200, description = "WebSocket disconnect breakdown")),
+    security(("bearer_auth" = []))
+)]
+#[tracing::instrument(skip(state, _admin))]
+pub async fn ws_disconnect_breakdown(
+    Extension(_admin): Extension<SystemAdminUser>,
+    State(state): State<AppState>,
+    Query(params): Query<WsDisconnectsParams>,
+) -> Result<Json<ws_disconnects::DisconnectBreakdown>, AdminError> {
+    let (from, to) = params.range.to_time_bounds();
+    let breakdown = ws_disconnects::query_disconnect_breakdown(&state.db, from, to, 10).await?;
+    Ok(Json(breakdown))
+}
+
+/// `GET /api/admin/observability/report-sla`
+///
+/// Returns the report queue backlog and time-to-claim / time-to-resolve
+/// percentiles for the range.
+#[utoipa::path(
+    get,
+    path = "/api/admin/observability/report-sla",
+    tag = "admin",
+    params(
+        ("range" = String, Query, description = "1h, 6h, 24h, 7d or 30d"),
+        ("sla_hours" = Option<i64>, Query, description = "SLA in hours (default 24, max 720)"),
+    ),
+    responses((status = 200, description = "Report queue SLA metrics")),
+    security(("bearer_auth" = []))
+)]
+#[tracing::instrument(skip(state, _admin))]
+pub async fn report_sla(
+    Extension(_admin): Extension<SystemAdminUser>,
+    State(state): State<AppState>,
+    Query(params): Query<ReportSlaParams>,
+) -> Result<Json<reports::ReportSla>, AdminError> {
+    let (from, to) = params.range.to_time_bounds();
+    let sla_secs = params.sla_hours.clamp(1, 720) * 3600;
+    let sla = reports::query_report_sla(&state.db, from, to, sla_secs).await?;
+    Ok(Json(sla))
+}
+
 /// `GET /api/admin/observability/logs`
 ///
 /// Returns paginated log events with optional filters.
+#[utoipa::path(
+    get,
+    path = "/api/admin/observability/logs",
+    tag = "admin",
+    params(
+        ("level" = Option<String>, Query, description = "Log level"),
+        ("domain" = Option<String>, Query, description = "Domain"),
+        ("service" = Option<String>, Query, description = "Service name"),
+        ("from" = Option<String>, Query, description = "Start time (RFC 3339, default 24h ago)"),
+        ("to" = Option<String>, Query, description = "End time (RFC 3339, default now)"),
+        ("search" = Option<String>, Query, description = "Message search text"),
+        ("cursor" = Option<Uuid>, Query, description = "Pagination cursor"),
+        ("limit" = Option<i64>, Query, description = "Page size (1-100, default 100)"),
+    ),
+    responses((status = 200, description = "Log events")),
+    security(("bearer_auth" = []))
+)]
 #[tracing::instrument(skip(state, _admin))]
 pub async fn logs(
     Extension(_admin): Extension<SystemAdminUser>,
@@ -516,7 +669,7 @@ pub async fn logs(
         limit,
     };
 
-    let items = storage::query_logs(&state.db, &filter).await?;
+    let items = state.telemetry.query_logs(&filter).await?;
     // Only provide next_cursor when the page is full (more results likely exist)
     let next_cursor = if items.len() as i64 == limit {
         items.last().map(|l| l.id)
@@ -533,6 +686,23 @@ pub async fn logs(
 /// `GET /api/admin/observability/traces`
 ///
 /// Returns paginated trace index entries with optional filters.
+#[utoipa::path(
+    get,
+    path = "/api/admin/observability/traces",
+    tag = "admin",
+    params(
+        ("status" = Option<String>, Query, description = "error or slow"),
+        ("domain" = Option<String>, Query, description = "Domain"),
+        ("route" = Option<String>, Query, description = "Route"),
+        ("duration_min" = Option<i32>, Query, description = "Minimum duration in ms"),
+        ("from" = Option<String>, Query, description = "Start time (RFC 3339, default 24h ago)"),
+        ("to" = Option<String>, Query, description = "End time (RFC 3339, default now)"),
+        ("cursor" = Option<Uuid>, Query, description = "Pagination cursor"),
+        ("limit" = Option<i64>, Query, description = "Page size (1-100, default 100)"),
+    ),
+    responses((status = 200, description = "Trace index entries")),
+    security(("bearer_auth" = []))
+)]
 #[tracing::instrument(skip(state, _admin))]
 pub async fn traces(
     Extension(_admin): Extension<SystemAdminUser>,
@@ -561,7 +731,7 @@ pub async fn traces(
         limit,
     };
 
-    let items = storage::query_traces(&state.db, &filter).await?;
+    let items = state.telemetry.query_traces(&filter).await?;
     let next_cursor = if items.len() as i64 == limit {
         items.last().map(|t| t.id)
     } else {
@@ -577,16 +747,101 @@ pub async fn traces(
 /// `GET /api/admin/observability/links`
 ///
 /// Returns configured external observability tool URLs (loaded once at startup).
+#[utoipa::path(
+    get,
+    path = "/api/admin/observability/links",
+    tag = "admin",
+    responses((status = 200, description = "External tool URLs")),
+    security(("bearer_auth" = []))
+)]
 #[tracing::instrument(skip(state, _admin))]
 pub async fn links(
     Extension(_admin): Extension<SystemAdminUser>,
     State(state): State<AppState>,
 ) -> Json<LinksResponse> {
     Json(LinksResponse {
-        grafana_url: state.config.grafana_url.clone(),
-        tempo_url: state.config.tempo_url.clone(),
-        loki_url: state.config.loki_url.clone(),
-        prometheus_url: state.config.prometheus_url.clone(),
+        grafana_url: state.config().grafana_url.clone(),
+        tempo_url: state.config().tempo_url.clone(),
+        loki_url: state.config().loki_url.clone(),
+        prometheus_url: state.config().prometheus_url.clone(),
+    })
+}
+
+/// Capacity report output format.
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CapacityFormat {
+    #[default]
+    Json,
+    Csv,
+    OpenMetrics,
+}
+
+/// Capacity report query parameters.
+#[derive(Debug, Deserialize)]
+pub struct CapacityParams {
+    /// Number of days to include (1–365).
+    #[serde(default = "default_capacity_days")]
+    pub days: i64,
+    #[serde(default)]
+    pub format: CapacityFormat,
+}
+
+const fn default_capacity_days() -> i64 {
+    30
+}
+
+/// `GET /api/admin/reports/capacity`
+///
+/// Returns the nightly capacity reports of the last `days` days, oldest first,
+/// as JSON, a CSV download (`format=csv`) or `OpenMetrics` text
+/// (`format=openmetrics`).
+#[utoipa::path(
+    get,
+    path = "/api/admin/reports/capacity",
+    tag = "admin",
+    params(
+        ("days" = Option<i64>, Query, description = "Days to include (1-365, default 30)"),
+        ("format" = Option<String>, Query, description = "json, csv or openmetrics"),
+    ),
+    responses((status = 200, body = Vec<capacity::CapacityReport>)),
+    security(("bearer_auth" = []))
+)]
+#[tracing::instrument(skip(state, _admin))]
+pub async fn capacity_report(
+    Extension(_admin): Extension<SystemAdminUser>,
+    State(state): State<AppState>,
+    Query(params): Query<CapacityParams>,
+) -> Result<Response, AdminError> {
+    if !(1..=365).contains(&params.days) {
+        return Err(AdminError::Validation(
+            "days must be between 1 and 365".to_string(),
+        ));
+    }
+
+    let reports = capacity::list_reports(&state.db, params.days).await?;
+
+    Ok(match params.format {
+        CapacityFormat::Json => Json(reports).into_response(),
+        CapacityFormat::Csv => (
+            [
+                (header::CONTENT_TYPE, "text/csv; charset=utf-8"),
+                (
+                    header::CONTENT_DISPOSITION,
+                    "attachment; filename=\"capacity_report.csv\"",
+                ),
+            ],
+            capacity::to_csv(&reports),
+        )
+            .into_response(),
+        CapacityFormat::OpenMetrics => (
+            [(
+                header::CONTENT_TYPE,
+                "application/openmetrics-text; version=1.0.0; charset=utf-8",
+            )],
+            capacity::to_openmetrics(&reports),
+        )
+            .into_response(),
     })
 }
 
@@ -606,6 +861,9 @@ pub fn router() -> axum::Router<AppState> {
         .route("/trends", get(trends))
         .route("/top-routes", get(top_routes))
         .route("/top-errors", get(top_errors))
+        .route("/top-consumers", get(top_consumers))
+        .route("/ws-disconnects", get(ws_disconnect_breakdown))
+        .route("/report-sla", get(report_sla))
         .route("/logs", get(logs))
         .route("/traces", get(traces))
         .route("/links", get(links))
@@ -638,6 +896,7 @@ mod tests {
     fn default_limits() {
         assert_eq!(default_top_limit(), 10);
         assert_eq!(default_log_limit(), 100);
+        assert_eq!(default_capacity_days(), 30);
     }
 
     #[test]
@@ -661,6 +920,26 @@ mod tests {
         assert!(serde_json::from_str::<TopRoutesSort>(r#""foobar""#).is_err());
     }
 
+    #[test]
+    fn top_consumers_params_deserialization() {
+        let sort: TopConsumersSort = serde_json::from_str(r#""ws_events""#).unwrap();
+        assert!(matches!(sort, TopConsumersSort::WsEvents));
+
+        let kind: TopConsumersType = serde_json::from_str(r#""guild""#).unwrap();
+        assert!(matches!(kind, TopConsumersType::Guild));
+
+        assert!(serde_json::from_str::<TopConsumersType>(r#""channel""#).is_err());
+    }
+
+    #[test]
+    fn capacity_format_deserialization() {
+        let csv: CapacityFormat = serde_json::from_str(r#""csv""#).unwrap();
+        assert!(matches!(csv, CapacityFormat::Csv));
+
+        let om: CapacityFormat = serde_json::from_str(r#""openmetrics""#).unwrap();
+        assert!(matches!(om, CapacityFormat::OpenMetrics));
+    }
+
     #[test]
     fn metric_name_prefix_validation() {
         assert!("kaiku_http_requests_total".starts_with("kaiku_"));
@@ -0,0 +1,447 @@
+//! Admin Registration handlers.
+//!
+//! Under the `approval` registration policy new accounts wait in a queue until
+//! a system admin approves them (they can then sign in) or rejects them (the
+//! account is deleted). Under `invite_only`, registering takes a code minted
+//! here. Listing requires `SystemAdminUser`; approving, rejecting and managing
+//! codes requires an elevated session.
+
+#![allow(clippy::used_underscore_binding)]
+
+use std::net::SocketAddr;
+
+use axum::extract::{ConnectInfo, Path, Query, State};
+use axum::http::StatusCode;
+use axum::{Extension, Json};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::handlers::PaginatedResponse;
+use super::types::{AdminError, ElevatedAdmin, SystemAdminUser};
+use crate::api::AppState;
+use crate::permissions::queries::write_audit_log;
+
+/// Length of a generated registration code.
+const CODE_LEN: usize = 12;
+
+/// Unambiguous code alphabet (no I, L, O or U).
+const CODE_ALPHABET: &[u8] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+
+/// Upper bound for `max_uses` on a single code.
+const MAX_CODE_USES: i32 = 1000;
+
+/// Longest allowed code lifetime, in hours (one year).
+const MAX_EXPIRY_HOURS: i64 = 24 * 365;
+
+/// Longest allowed note on a code.
+const MAX_NOTE_LENGTH: usize = 200;
+
+/// List query parameters.
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+pub struct RegistrationListParams {
+    /// Maximum number of items to return.
+    #[serde(default = "default_limit")]
+    pub limit: i64,
+    /// Number of items to skip.
+    #[serde(default)]
+    pub offset: i64,
+}
+
+#[allow(clippy::missing_const_for_fn)]
+fn default_limit() -> i64 {
+    50
+}
+
+/// An account waiting for approval.
+#[derive(Debug, Serialize, sqlx::FromRow, utoipa::ToSchema)]
+pub struct PendingRegistration {
+    pub user_id: Uuid,
+    pub username: String,
+    pub display_name: String,
+    pub email: Option<String>,
+    pub registered_at: DateTime<Utc>,
+}
+
+/// Review result.
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct RegistrationReviewResponse {
+    pub user_id: Uuid,
+    /// `approved` or `rejected`.
+    pub status: String,
+}
+
+/// A registration code for the `invite_only` policy.
+#[derive(Debug, Serialize, sqlx::FromRow, utoipa::ToSchema)]
+pub struct RegistrationCode {
+    pub id: Uuid,
+    pub code: String,
+    pub note: Option<String>,
+    pub created_by: Option<Uuid>,
+    /// Unlimited when absent.
+    pub max_uses: Option<i32>,
+    pub use_count: i32,
+    /// Never expires when absent.
+    pub expires_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Registration code creation request.
+#[derive(Debug, Deserialize, utoipa::ToSchema)]
+pub struct CreateRegistrationCodeRequest {
+    /// Shown to admins only, e.g. who the code was given to.
+    pub note: Option<String>,
+    /// Number of accounts the code can create (1-1000, unlimited if absent).
+    pub max_uses: Option<i32>,
+    /// Hours until the code expires (1-8760, never if absent).
+    pub expires_in_hours: Option<i64>,
+}
+
+/// Generate a random registration code.
+fn generate_code() -> String {
+    use rand::Rng;
+
+    let mut rng = rand::thread_rng();
+    (0..CODE_LEN)
+        .map(|_| char::from(CODE_ALPHABET[rng.gen_range(0..CODE_ALPHABET.len())]))
+        .collect()
+}
+
+/// List accounts waiting for approval, oldest first.
+///
+/// `GET /api/admin/registrations`
+#[utoipa::path(
+    get,
+    path = "/api/admin/registrations",
+    tag = "admin",
+    params(RegistrationListParams),
+    responses((status = 200, body = PaginatedResponse<PendingRegistration>)),
+    security(("bearer_auth" = []))
+)]
+#[tracing::instrument(skip(state))]
+pub async fn list_pending(
+    State(state): State<AppState>,
+    Extension(_admin): Extension<SystemAdminUser>,
+    Query(params): Query<RegistrationListParams>,
+) -> Result<Json<PaginatedResponse<PendingRegistration>>, AdminError> {
+    let limit = params.limit.clamp(1, 100);
+    let offset = params.offset.max(0);
+
+    let total: i64 =
+        sqlx::query_scalar("SELECT COUNT(*) FROM users WHERE pending_approval_at IS NOT NULL")
+            .fetch_one(&state.db)
+            .await?;
+
+    let items = sqlx::query_as::<_, PendingRegistration>(
+        r"SELECT id AS user_id, username, display_name, email,
+                 pending_approval_at AS registered_at
+          FROM users
+          WHERE pending_approval_at IS NOT NULL
+          ORDER BY pending_approval_at ASC, id
+          LIMIT $1 OFFSET $2",
+    )
+    .bind(limit)
+    .bind(offset)
+    .fetch_all(&state.db)
+    .await?;
+
+    Ok(Json(PaginatedResponse {
+        items,
+        total,
+        limit,
+        offset,
+    }))
+}
+
+/// Approve a pending account so it can sign in.
+///
+/// `POST /api/admin/registrations/:user_id/approve`
+#[utoipa::path(
+    post,
+    path = "/api/admin/registrations/{user_id}/approve",
+    tag = "admin",
+    params(("user_id" = Uuid, Path, description = "User ID")),
+    responses(
+        (status = 200, description = "Account approved", body = RegistrationReviewResponse),
+        (status = 404, description = "Account is not waiting for approval"),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[tracing::instrument(skip(state))]
+pub async fn approve(
+    State(state): State<AppState>,
+    Extension(admin): Extension<SystemAdminUser>,
+    Extension(_elevated): Extension<ElevatedAdmin>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    Path(user_id): Path<Uuid>,
+) -> Result<Json<RegistrationReviewResponse>, AdminError> {
+    let approved: Option<(String, Option<String>)> = sqlx::query_as(
+        r"UPDATE users SET pending_approval_at = NULL
+          WHERE id = $1 AND pending_approval_at IS NOT NULL
+          RETURNING username, email",
+    )
+    .bind(user_id)
+    .fetch_optional(&state.db)
+    .await?;
+    let Some((username, email)) = approved else {
+        return Err(AdminError::NotFound("Pending registration".to_string()));
+    };
+
+    let ip_address = addr.ip().to_string();
+    write_audit_log(
+        &state.db,
+        admin.user_id,
+        "admin.registrations.approve",
+        Some("user"),
+        Some(user_id),
+        Some(serde_json::json!({ "username": username })),
+        Some(&ip_address),
+    )
+    .await?;
+
+    // Let the user know they can sign in (best-effort)
+    if let (Some(service), Some(to)) = (state.email.clone(), email) {
+        tokio::spawn(async move {
+            if let Err(e) = service.send_registration_approved(&to, &username).await {
+                tracing::warn!(user_id = %user_id, error = %e, "Failed to send registration approval email");
+            }
+        });
+    }
+
+    Ok(Json(RegistrationReviewResponse {
+        user_id,
+        status: "approved".to_string(),
+    }))
+}
+
+/// Reject a pending account, deleting it.
+///
+/// `POST /api/admin/registrations/:user_id/reject`
+#[utoipa::path(
+    post,
+    path = "/api/admin/registrations/{user_id}/reject",
+    tag = "admin",
+    params(("user_id" = Uuid, Path, description = "User ID")),
+    responses(
+        (status = 200, description = "Account rejected and deleted", body = RegistrationReviewResponse),
+        (status = 404, description = "Account is not waiting for approval"),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[tracing::instrument(skip(state))]
+pub async fn reject(
+    State(state): State<AppState>,
+    Extension(admin): Extension<SystemAdminUser>,
+    Extension(_elevated): Extension<ElevatedAdmin>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    Path(user_id): Path<Uuid>,
+) -> Result<Json<RegistrationReviewResponse>, AdminError> {
+    let username: Option<String> = sqlx::query_scalar(
+        "DELETE FROM users WHERE id = $1 AND pending_approval_at IS NOT NULL RETURNING username",
+    )
+    .bind(user_id)
+    .fetch_optional(&state.db)
+    .await?;
+    let Some(username) = username else {
+        return Err(AdminError::NotFound("Pending registration".to_string()));
+    };
+
+    let ip_address = addr.ip().to_string();
+    write_audit_log(
+        &state.db,
+        admin.user_id,
+        "admin.registrations.reject",
+        Some("user"),
+        Some(user_id),
+        Some(serde_json::json!({ "username": username })),
+        Some(&ip_address),
+    )
+    .await?;
+
+    Ok(Json(RegistrationReviewResponse {
+        user_id,
+        status: "rejected".to_string(),
+    }))
+}
+
+/// List registration codes, newest first.
+///
+/// `GET /api/admin/registration-codes`
+#[utoipa::path(
+    get,
+    path = "/api/admin/registration-codes",
+    tag = "admin",
+    params(RegistrationListParams),
+    responses((status = 200, body = PaginatedResponse<RegistrationCode>)),
+    security(("bearer_auth" = []))
+)]
+#[tracing::instrument(skip(state))]
+pub async fn list_codes(
+    State(state): State<AppState>,
+    Extension(_admin): Extension<SystemAdminUser>,
+    Query(params): Query<RegistrationListParams>,
+) -> Result<Json<PaginatedResponse<RegistrationCode>>, AdminError> {
+    let limit = params.limit.clamp(1, 100);
+    let offset = params.offset.max(0);
+
+    let total: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM registration_codes")
+        .fetch_one(&state.db)
+        .await?;
+
+    let items = sqlx::query_as::<_, RegistrationCode>(
+        "SELECT * FROM registration_codes ORDER BY created_at DESC, id LIMIT $1 OFFSET $2",
+    )
+    .bind(limit)
+    .bind(offset)
+    .fetch_all(&state.db)
+    .await?;
+
+    Ok(Json(PaginatedResponse {
+        items,
+        total,
+        limit,
+        offset,
+    }))
+}
+
+/// Mint a registration code.
+///
+/// `POST /api/admin/registration-codes`
+#[utoipa::path(
+    post,
+    path = "/api/admin/registration-codes",
+    tag = "admin",
+    request_body = CreateRegistrationCodeRequest,
+    responses(
+        (status = 201, description = "Code created", body = RegistrationCode),
+        (status = 400, description = "Invalid note, use limit or expiry"),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[tracing::instrument(skip(state, body))]
+pub async fn create_code(
+    State(state): State<AppState>,
+    Extension(admin): Extension<SystemAdminUser>,
+    Extension(_elevated): Extension<ElevatedAdmin>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    Json(body): Json<CreateRegistrationCodeRequest>,
+) -> Result<(StatusCode, Json<RegistrationCode>), AdminError> {
+    let note = body
+        .note
+        .as_deref()
+        .map(str::trim)
+        .filter(|note| !note.is_empty());
+    if note.is_some_and(|note| note.chars().count() > MAX_NOTE_LENGTH) {
+        return Err(AdminError::Validation(format!(
+            "Note must be at most {MAX_NOTE_LENGTH} characters"
+        )));
+    }
+    if body
+        .max_uses
+        .is_some_and(|n| !(1..=MAX_CODE_USES).contains(&n))
+    {
+        return Err(AdminError::Validation(format!(
+            "max_uses must be between 1 and {MAX_CODE_USES}"
+        )));
+    }
+    let expires_at = match body.expires_in_hours {
+        Some(hours) if (1..=MAX_EXPIRY_HOURS).contains(&hours) => {
+            Some(Utc::now() + Duration::hours(hours))
+        }
+        Some(_) => {
+            return Err(AdminError::Validation(format!(
+                "expires_in_hours must be between 1 and {MAX_EXPIRY_HOURS}"
+            )))
+        }
+        None => None,
+    };
+
+    let code = sqlx::query_as::<_, RegistrationCode>(
+        r"INSERT INTO registration_codes (code, note, created_by, max_uses, expires_at)
+          VALUES ($1, $2, $3, $4, $5)
+          RETURNING *",
+    )
+    .bind(generate_code())
+    .bind(note)
+    .bind(admin.user_id)
+    .bind(body.max_uses)
+    .bind(expires_at)
+    .fetch_one(&state.db)
+    .await?;
+
+    let ip_address = addr.ip().to_string();
+    write_audit_log(
+        &state.db,
+        admin.user_id,
+        "admin.registration_codes.create",
+        Some("registration_code"),
+        Some(code.id),
+        Some(serde_json::json!({
+            "note": code.note,
+            "max_uses": code.max_uses,
+            "expires_at": code.expires_at,
+        })),
+        Some(&ip_address),
+    )
+    .await?;
+
+    Ok((StatusCode::CREATED, Json(code)))
+}
+
+/// Delete a registration code; it can no longer be used.
+///
+/// `DELETE /api/admin/registration-codes/:id`
+#[utoipa::path(
+    delete,
+    path = "/api/admin/registration-codes/{id}",
+    tag = "admin",
+    params(("id" = Uuid, Path, description = "Registration code ID")),
+    responses(
+        (status = 204, description = "Code deleted"),
+        (status = 404, description = "Code not found"),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[tracing::instrument(skip(state))]
+pub async fn delete_code(
+    State(state): State<AppState>,
+    Extension(admin): Extension<SystemAdminUser>,
+    Extension(_elevated): Extension<ElevatedAdmin>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    Path(id): Path<Uuid>,
+) -> Result<StatusCode, AdminError> {
+    let deleted = sqlx::query("DELETE FROM registration_codes WHERE id = $1")
+        .bind(id)
+        .execute(&state.db)
+        .await?
+        .rows_affected();
+    if deleted == 0 {
+        return Err(AdminError::NotFound("Registration code".to_string()));
+    }
+
+    let ip_address = addr.ip().to_string();
+    write_audit_log(
+        &state.db,
+        admin.user_id,
+        "admin.registration_codes.delete",
+        Some("registration_code"),
+        Some(id),
+        None,
+        Some(&ip_address),
+    )
+    .await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generated_codes_use_the_code_alphabet() {
+        let code = generate_code();
+        assert_eq!(code.len(), CODE_LEN);
+        assert!(code.bytes().all(|b| CODE_ALPHABET.contains(&b)));
+    }
+}
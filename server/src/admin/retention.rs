@@ -0,0 +1,261 @@
+//! Instance Data Retention
+//!
+//! How long the instance keeps native telemetry, messages, deleted messages
+//! and voice connection metrics. The settings live under the `data_retention`
+//! key of `server_config`; the background jobs read them on every run, so a
+//! change applies at the next run without a restart.
+//!
+//! Message retention here bounds the per-guild policies: guilds without one
+//! use `message_default_days`, and no guild or channel keeps messages longer
+//! than `message_max_days`.
+
+#![allow(clippy::used_underscore_binding)]
+
+use std::net::SocketAddr;
+
+use axum::extract::{ConnectInfo, State};
+use axum::{Extension, Json};
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use tracing::warn;
+use utoipa::ToSchema;
+
+use super::handlers::deserialize_double_option;
+use super::types::{AdminError, ElevatedAdmin, SystemAdminUser};
+use crate::api::AppState;
+use crate::guild::retention::{MAX_RETENTION_DAYS, MIN_RETENTION_DAYS};
+use crate::permissions::queries::write_audit_log;
+
+/// `server_config` key holding the settings.
+pub(crate) const CONFIG_KEY: &str = "data_retention";
+
+/// Longest telemetry and connection metric retention (one year).
+const MAX_METRICS_DAYS: i32 = 365;
+
+/// Instance-wide data retention settings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(default)]
+pub struct RetentionSettings {
+    /// Days native telemetry (metric samples, logs, traces) is kept.
+    pub telemetry_days: i32,
+    /// Message retention of guilds without their own policy (`null` = kept forever).
+    pub message_default_days: Option<i32>,
+    /// Longest message retention a guild or channel can use.
+    pub message_max_days: i32,
+    /// Days deleted messages are kept before they are purged for good.
+    pub soft_delete_purge_days: i32,
+    /// Days raw voice connection metrics are kept.
+    pub connection_metrics_days: i32,
+}
+
+impl Default for RetentionSettings {
+    fn default() -> Self {
+        Self {
+            telemetry_days: 30,
+            message_default_days: None,
+            message_max_days: MAX_RETENTION_DAYS,
+            soft_delete_purge_days: 30,
+            connection_metrics_days: 7,
+        }
+    }
+}
+
+impl RetentionSettings {
+    /// Load the current settings, falling back to the defaults when they
+    /// cannot be read.
+    pub async fn load(pool: &PgPool) -> Self {
+        match crate::db::get_config_value(pool, CONFIG_KEY).await {
+            Ok(value) => serde_json::from_value(value).unwrap_or_else(|e| {
+                warn!(error = %e, "Invalid data retention settings, using defaults");
+                Self::default()
+            }),
+            Err(e) => {
+                warn!(error = %e, "Failed to load data retention settings, using defaults");
+                Self::default()
+            }
+        }
+    }
+
+    /// Check every setting against its bounds.
+    pub fn validate(&self) -> Result<(), AdminError> {
+        let in_range = |name: &str, days: i32, max: i32| {
+            if (MIN_RETENTION_DAYS..=max).contains(&days) {
+                Ok(())
+            } else {
+                Err(AdminError::Validation(format!(
+                    "{name} must be between {MIN_RETENTION_DAYS} and {max}"
+                )))
+            }
+        };
+
+        in_range("telemetry_days", self.telemetry_days, MAX_METRICS_DAYS)?;
+        in_range(
+            "message_max_days",
+            self.message_max_days,
+            MAX_RETENTION_DAYS,
+        )?;
+        if let Some(days) = self.message_default_days {
+            in_range("message_default_days", days, self.message_max_days)?;
+        }
+        in_range(
+            "soft_delete_purge_days",
+            self.soft_delete_purge_days,
+            MAX_RETENTION_DAYS,
+        )?;
+        in_range(
+            "connection_metrics_days",
+            self.connection_metrics_days,
+            MAX_METRICS_DAYS,
+        )
+    }
+}
+
+/// Partial update of the retention settings; omitted fields are unchanged.
+#[derive(Debug, Default, Deserialize, ToSchema)]
+pub struct UpdateRetentionRequest {
+    pub telemetry_days: Option<i32>,
+    /// `null` keeps messages of guilds without a policy forever.
+    #[serde(default, deserialize_with = "deserialize_double_option")]
+    pub message_default_days: Option<Option<i32>>,
+    pub message_max_days: Option<i32>,
+    pub soft_delete_purge_days: Option<i32>,
+    pub connection_metrics_days: Option<i32>,
+}
+
+impl UpdateRetentionRequest {
+    /// `settings` with this update applied.
+    pub(crate) fn apply(&self, settings: RetentionSettings) -> RetentionSettings {
+        RetentionSettings {
+            telemetry_days: self.telemetry_days.unwrap_or(settings.telemetry_days),
+            message_default_days: self
+                .message_default_days
+                .unwrap_or(settings.message_default_days),
+            message_max_days: self.message_max_days.unwrap_or(settings.message_max_days),
+            soft_delete_purge_days: self
+                .soft_delete_purge_days
+                .unwrap_or(settings.soft_delete_purge_days),
+            connection_metrics_days: self
+                .connection_metrics_days
+                .unwrap_or(settings.connection_metrics_days),
+        }
+    }
+}
+
+/// Get the instance data retention settings.
+///
+/// GET /api/admin/retention
+#[utoipa::path(
+    get,
+    path = "/api/admin/retention",
+    tag = "admin",
+    responses((status = 200, body = RetentionSettings)),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_retention_settings(
+    State(state): State<AppState>,
+    Extension(_admin): Extension<SystemAdminUser>,
+    Extension(_elevated): Extension<ElevatedAdmin>,
+) -> Result<Json<RetentionSettings>, AdminError> {
+    Ok(Json(RetentionSettings::load(&state.db).await))
+}
+
+/// Update the instance data retention settings.
+///
+/// PATCH /api/admin/retention
+#[utoipa::path(
+    patch,
+    path = "/api/admin/retention",
+    tag = "admin",
+    request_body = UpdateRetentionRequest,
+    responses((status = 200, body = RetentionSettings)),
+    security(("bearer_auth" = []))
+)]
+#[tracing::instrument(skip(state))]
+pub async fn update_retention_settings(
+    State(state): State<AppState>,
+    Extension(admin): Extension<SystemAdminUser>,
+    Extension(_elevated): Extension<ElevatedAdmin>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    Json(body): Json<UpdateRetentionRequest>,
+) -> Result<Json<RetentionSettings>, AdminError> {
+    let settings = body.apply(RetentionSettings::load(&state.db).await);
+    settings.validate()?;
+
+    let value = serde_json::to_value(settings)
+        .map_err(|e| AdminError::Validation(format!("Invalid settings: {e}")))?;
+    crate::db::set_config_value(&state.db, CONFIG_KEY, value.clone(), admin.user_id).await?;
+
+    let ip_address = addr.ip().to_string();
+    write_audit_log(
+        &state.db,
+        admin.user_id,
+        "admin.retention.update",
+        None,
+        None,
+        Some(value),
+        Some(&ip_address),
+    )
+    .await?;
+
+    Ok(Json(settings))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_defaults_are_valid() {
+        let settings = RetentionSettings::default();
+        assert!(settings.validate().is_ok());
+        assert_eq!(settings.telemetry_days, 30);
+        assert_eq!(settings.connection_metrics_days, 7);
+    }
+
+    #[test]
+    fn test_validate_bounds() {
+        let with = |update: UpdateRetentionRequest| update.apply(RetentionSettings::default());
+
+        assert!(with(UpdateRetentionRequest {
+            telemetry_days: Some(0),
+            ..Default::default()
+        })
+        .validate()
+        .is_err());
+        assert!(with(UpdateRetentionRequest {
+            connection_metrics_days: Some(366),
+            ..Default::default()
+        })
+        .validate()
+        .is_err());
+        // The default cannot exceed the maximum
+        assert!(with(UpdateRetentionRequest {
+            message_default_days: Some(Some(90)),
+            message_max_days: Some(30),
+            ..Default::default()
+        })
+        .validate()
+        .is_err());
+        assert!(with(UpdateRetentionRequest {
+            message_default_days: Some(Some(30)),
+            message_max_days: Some(90),
+            ..Default::default()
+        })
+        .validate()
+        .is_ok());
+    }
+
+    #[test]
+    fn test_update_can_clear_message_default() {
+        let settings = RetentionSettings {
+            message_default_days: Some(30),
+            ..RetentionSettings::default()
+        };
+        let cleared: UpdateRetentionRequest =
+            serde_json::from_str(r#"{"message_default_days": null}"#).unwrap();
+        assert_eq!(cleared.apply(settings).message_default_days, None);
+
+        let unchanged: UpdateRetentionRequest = serde_json::from_str("{}").unwrap();
+        assert_eq!(unchanged.apply(settings).message_default_days, Some(30));
+    }
+}
@@ -0,0 +1,377 @@
+//! Instance Server Settings
+//!
+//! One place to manage the settings otherwise spread over setup, the
+//! environment and the retention page: server name, registration policy,
+//! guild discovery, upload limits and data retention.
+//!
+//! Discovery and upload limits override environment values; they are stored as
+//! [`InstanceOverrides`] and applied through [`AppState::config`].
+//! Setting one to `null` returns it to the environment value. Upload limits
+//! cannot exceed the environment value, which also bounds request bodies.
+//!
+//! The environment itself is reloaded with [`reload_config`].
+
+#![allow(clippy::used_underscore_binding)]
+
+use std::net::SocketAddr;
+
+use axum::extract::{ConnectInfo, State};
+use axum::{Extension, Json};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::handlers::deserialize_double_option;
+use super::retention::{self, RetentionSettings, UpdateRetentionRequest};
+use super::types::{AdminError, ElevatedAdmin, SystemAdminUser};
+use crate::api::instance_config::{self, InstanceOverrides};
+use crate::api::AppState;
+use crate::config::Config;
+use crate::permissions::queries::write_audit_log;
+
+/// Longest server name, as in setup.
+const MAX_SERVER_NAME_LENGTH: usize = 64;
+
+/// Smallest upload limit an admin can set.
+const MIN_UPLOAD_LIMIT: usize = 1024;
+
+/// Current server settings.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ServerSettings {
+    pub server_name: String,
+    /// `open`, `invite_only`, `approval` or `closed`.
+    pub registration_policy: String,
+    /// Whether guild discovery is enabled (environment value unless overridden).
+    pub guild_discovery_enabled: bool,
+    /// Maximum attachment size in bytes.
+    pub max_upload_size: usize,
+    /// Maximum avatar, banner and icon size in bytes.
+    pub max_avatar_size: usize,
+    /// Maximum custom emoji size in bytes.
+    pub max_emoji_size: usize,
+    /// The stored overrides; `null` fields use the environment value.
+    pub overrides: InstanceOverrides,
+    pub retention: RetentionSettings,
+}
+
+/// Partial update of the server settings; omitted fields are unchanged.
+#[derive(Debug, Default, Deserialize, ToSchema)]
+pub struct UpdateServerSettingsRequest {
+    pub server_name: Option<String>,
+    pub registration_policy: Option<String>,
+    /// `null` returns to `ENABLE_GUILD_DISCOVERY`.
+    #[serde(default, deserialize_with = "deserialize_double_option")]
+    pub guild_discovery_enabled: Option<Option<bool>>,
+    /// `null` returns to `MAX_UPLOAD_SIZE`.
+    #[serde(default, deserialize_with = "deserialize_double_option")]
+    pub max_upload_size: Option<Option<usize>>,
+    /// `null` returns to `MAX_AVATAR_SIZE`.
+    #[serde(default, deserialize_with = "deserialize_double_option")]
+    pub max_avatar_size: Option<Option<usize>>,
+    /// `null` returns to `MAX_EMOJI_SIZE`.
+    #[serde(default, deserialize_with = "deserialize_double_option")]
+    pub max_emoji_size: Option<Option<usize>>,
+    pub retention: Option<UpdateRetentionRequest>,
+}
+
+impl UpdateServerSettingsRequest {
+    /// `overrides` with this update applied.
+    fn apply_overrides(&self, overrides: InstanceOverrides) -> InstanceOverrides {
+        InstanceOverrides {
+            guild_discovery_enabled: self
+                .guild_discovery_enabled
+                .unwrap_or(overrides.guild_discovery_enabled),
+            max_upload_size: self.max_upload_size.unwrap_or(overrides.max_upload_size),
+            max_avatar_size: self.max_avatar_size.unwrap_or(overrides.max_avatar_size),
+            max_emoji_size: self.max_emoji_size.unwrap_or(overrides.max_emoji_size),
+        }
+    }
+
+    /// Check the name, policy and upload limits against their bounds.
+    fn validate(&self, config: &Config) -> Result<(), AdminError> {
+        if let Some(ref name) = self.server_name {
+            let length = name.trim().chars().count();
+            if length == 0 || length > MAX_SERVER_NAME_LENGTH {
+                return Err(AdminError::Validation(format!(
+                    "server_name must be between 1 and {MAX_SERVER_NAME_LENGTH} characters"
+                )));
+            }
+        }
+
+        if let Some(ref policy) = self.registration_policy {
+            if !matches!(
+                policy.as_str(),
+                "open" | "invite_only" | "approval" | "closed"
+            ) {
+                return Err(AdminError::Validation(
+                    "registration_policy must be 'open', 'invite_only', 'approval', or 'closed'"
+                        .into(),
+                ));
+            }
+        }
+
+        let in_range = |name: &str, size: Option<Option<usize>>, max: usize| match size {
+            Some(Some(size)) if !(MIN_UPLOAD_LIMIT..=max).contains(&size) => {
+                Err(AdminError::Validation(format!(
+                    "{name} must be between {MIN_UPLOAD_LIMIT} and {max} bytes"
+                )))
+            }
+            _ => Ok(()),
+        };
+        in_range(
+            "max_upload_size",
+            self.max_upload_size,
+            config.max_upload_size,
+        )?;
+        in_range(
+            "max_avatar_size",
+            self.max_avatar_size,
+            config.max_avatar_size,
+        )?;
+        in_range("max_emoji_size", self.max_emoji_size, config.max_emoji_size)
+    }
+}
+
+/// Read a string setting, falling back to `default`.
+async fn load_string(state: &AppState, key: &str, default: &str) -> String {
+    crate::db::get_config_value(&state.db, key)
+        .await
+        .ok()
+        .and_then(|v| v.as_str().map(String::from))
+        .unwrap_or_else(|| default.to_string())
+}
+
+async fn load_settings(state: &AppState) -> ServerSettings {
+    let overrides = InstanceOverrides::load(&state.db).await;
+    let effective = overrides.apply(&state.live_config.env());
+
+    ServerSettings {
+        server_name: load_string(state, "server_name", "Kaiku Server").await,
+        registration_policy: load_string(state, "registration_policy", "open").await,
+        guild_discovery_enabled: effective.enable_guild_discovery,
+        max_upload_size: effective.max_upload_size,
+        max_avatar_size: effective.max_avatar_size,
+        max_emoji_size: effective.max_emoji_size,
+        overrides,
+        retention: RetentionSettings::load(&state.db).await,
+    }
+}
+
+/// Get the server settings.
+///
+/// GET /api/admin/settings
+#[utoipa::path(
+    get,
+    path = "/api/admin/settings",
+    tag = "admin",
+    responses((status = 200, body = ServerSettings)),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_server_settings(
+    State(state): State<AppState>,
+    Extension(_admin): Extension<SystemAdminUser>,
+    Extension(_elevated): Extension<ElevatedAdmin>,
+) -> Result<Json<ServerSettings>, AdminError> {
+    Ok(Json(load_settings(&state).await))
+}
+
+/// Update the server settings.
+///
+/// Everything is validated before anything is saved.
+///
+/// PATCH /api/admin/settings
+#[utoipa::path(
+    patch,
+    path = "/api/admin/settings",
+    tag = "admin",
+    request_body = UpdateServerSettingsRequest,
+    responses((status = 200, body = ServerSettings)),
+    security(("bearer_auth" = []))
+)]
+#[tracing::instrument(skip(state))]
+pub async fn update_server_settings(
+    State(state): State<AppState>,
+    Extension(admin): Extension<SystemAdminUser>,
+    Extension(_elevated): Extension<ElevatedAdmin>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    Json(body): Json<UpdateServerSettingsRequest>,
+) -> Result<Json<ServerSettings>, AdminError> {
+    body.validate(&state.live_config.env())?;
+    let new_retention = match body.retention {
+        Some(ref update) => {
+            let settings = update.apply(RetentionSettings::load(&state.db).await);
+            settings.validate()?;
+            Some(settings)
+        }
+        None => None,
+    };
+
+    let mut changes = serde_json::Map::new();
+
+    if let Some(ref name) = body.server_name {
+        let name = name.trim();
+        crate::db::set_config_value(&state.db, "server_name", name.into(), admin.user_id).await?;
+        changes.insert("server_name".into(), name.into());
+    }
+
+    if let Some(ref policy) = body.registration_policy {
+        crate::db::set_config_value(
+            &state.db,
+            "registration_policy",
+            policy.as_str().into(),
+            admin.user_id,
+        )
+        .await?;
+        changes.insert("registration_policy".into(), policy.as_str().into());
+    }
+
+    let current = InstanceOverrides::load(&state.db).await;
+    let overrides = body.apply_overrides(current);
+    if overrides != current {
+        let value = serde_json::to_value(overrides)
+            .map_err(|e| AdminError::Validation(format!("Invalid settings: {e}")))?;
+        crate::db::set_config_value(
+            &state.db,
+            instance_config::CONFIG_KEY,
+            value.clone(),
+            admin.user_id,
+        )
+        .await?;
+        state.refresh_config().await;
+        changes.insert("overrides".into(), value);
+    }
+
+    if let Some(settings) = new_retention {
+        let value = serde_json::to_value(settings)
+            .map_err(|e| AdminError::Validation(format!("Invalid settings: {e}")))?;
+        crate::db::set_config_value(
+            &state.db,
+            retention::CONFIG_KEY,
+            value.clone(),
+            admin.user_id,
+        )
+        .await?;
+        changes.insert("retention".into(), value);
+    }
+
+    if !changes.is_empty() {
+        let ip_address = addr.ip().to_string();
+        write_audit_log(
+            &state.db,
+            admin.user_id,
+            "admin.settings.update",
+            None,
+            None,
+            Some(serde_json::Value::Object(changes)),
+            Some(&ip_address),
+        )
+        .await?;
+    }
+
+    Ok(Json(load_settings(&state).await))
+}
+
+/// Result of a configuration reload.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ConfigReloadResponse {
+    /// Changed settings that keep their previous value until a restart.
+    pub restart_required: Vec<String>,
+}
+
+/// Reload the configuration from the environment and `.env` file, like
+/// `SIGHUP`.
+///
+/// An invalid configuration is rejected and the current one is kept.
+///
+/// POST /api/admin/config/reload
+#[utoipa::path(
+    post,
+    path = "/api/admin/config/reload",
+    tag = "admin",
+    responses((status = 200, body = ConfigReloadResponse)),
+    security(("bearer_auth" = []))
+)]
+#[tracing::instrument(skip(state))]
+pub async fn reload_config(
+    State(state): State<AppState>,
+    Extension(admin): Extension<SystemAdminUser>,
+    Extension(_elevated): Extension<ElevatedAdmin>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+) -> Result<Json<ConfigReloadResponse>, AdminError> {
+    let restart_required: Vec<String> = state
+        .reload_config()
+        .await
+        .map_err(|e| AdminError::Validation(format!("Configuration reload failed: {e:#}")))?
+        .into_iter()
+        .map(String::from)
+        .collect();
+
+    let ip_address = addr.ip().to_string();
+    write_audit_log(
+        &state.db,
+        admin.user_id,
+        "admin.config.reload",
+        None,
+        None,
+        Some(serde_json::json!({ "restart_required": restart_required })),
+        Some(&ip_address),
+    )
+    .await?;
+
+    Ok(Json(ConfigReloadResponse { restart_required }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_server_name_and_policy() {
+        let config = Config::default_for_test();
+        let with_name = |name: &str| UpdateServerSettingsRequest {
+            server_name: Some(name.into()),
+            ..Default::default()
+        };
+        assert!(with_name("My Server").validate(&config).is_ok());
+        assert!(with_name("   ").validate(&config).is_err());
+        assert!(with_name(&"x".repeat(65)).validate(&config).is_err());
+
+        let with_policy = |policy: &str| UpdateServerSettingsRequest {
+            registration_policy: Some(policy.into()),
+            ..Default::default()
+        };
+        assert!(with_policy("approval").validate(&config).is_ok());
+        assert!(with_policy("everyone").validate(&config).is_err());
+    }
+
+    #[test]
+    fn test_upload_limits_bounded_by_environment() {
+        let config = Config::default_for_test();
+        let with_upload = |size: Option<usize>| UpdateServerSettingsRequest {
+            max_upload_size: Some(size),
+            ..Default::default()
+        };
+        assert!(with_upload(Some(config.max_upload_size))
+            .validate(&config)
+            .is_ok());
+        assert!(with_upload(Some(config.max_upload_size + 1))
+            .validate(&config)
+            .is_err());
+        assert!(with_upload(Some(100)).validate(&config).is_err());
+        // Clearing the override is always allowed
+        assert!(with_upload(None).validate(&config).is_ok());
+    }
+
+    #[test]
+    fn test_update_can_clear_overrides() {
+        let overrides = InstanceOverrides {
+            guild_discovery_enabled: Some(false),
+            max_upload_size: Some(4096),
+            ..Default::default()
+        };
+        let update: UpdateServerSettingsRequest =
+            serde_json::from_str(r#"{"max_upload_size": null}"#).unwrap();
+        let updated = update.apply_overrides(overrides);
+        assert_eq!(updated.max_upload_size, None);
+        assert_eq!(updated.guild_discovery_enabled, Some(false));
+    }
+}
@@ -8,6 +8,7 @@ use serde::{Deserialize, Serialize};
 use thiserror::Error;
 use uuid::Uuid;
 
+use crate::observability::storage::StorageError;
 use crate::permissions::PermissionError;
 
 /// Authenticated system admin user.
@@ -46,6 +47,14 @@ pub enum AdminError {
     #[error("Invalid MFA code")]
     InvalidMfaCode,
 
+    /// Wrong password provided when elevating.
+    #[error("Invalid password")]
+    InvalidPassword,
+
+    /// Missing or invalid webhook signature.
+    #[error("Invalid webhook signature")]
+    InvalidSignature,
+
     /// Resource not found.
     #[error("{0} not found")]
     NotFound(String),
@@ -67,6 +76,18 @@ pub enum AdminError {
     Internal(String),
 }
 
+impl From<StorageError> for AdminError {
+    fn from(err: StorageError) -> Self {
+        match err {
+            StorageError::Postgres(e) => Self::Database(e),
+            StorageError::ClickHouse(msg) => {
+                tracing::error!(error = %msg, "Telemetry storage query failed");
+                Self::Internal("Telemetry storage unavailable".into())
+            }
+        }
+    }
+}
+
 impl IntoResponse for AdminError {
     fn into_response(self) -> Response {
         let (status, body) = match self {
@@ -86,6 +107,14 @@ impl IntoResponse for AdminError {
                 StatusCode::UNAUTHORIZED,
                 serde_json::json!({"error": "invalid_mfa_code", "message": "Invalid MFA code"}),
             ),
+            Self::InvalidPassword => (
+                StatusCode::UNAUTHORIZED,
+                serde_json::json!({"error": "invalid_password", "message": "Invalid password"}),
+            ),
+            Self::InvalidSignature => (
+                StatusCode::UNAUTHORIZED,
+                serde_json::json!({"error": "invalid_signature", "message": "Invalid webhook signature"}),
+            ),
             Self::NotFound(what) => (
                 StatusCode::NOT_FOUND,
                 serde_json::json!({"error": "not_found", "message": format!("{} not found", what)}),
@@ -112,11 +141,25 @@ impl IntoResponse for AdminError {
 }
 
 // Request types
-#[derive(Debug, Deserialize, utoipa::ToSchema)]
+/// Step-up confirmation for elevation: the current password or, with MFA
+/// enabled, a TOTP code. The code is checked when both are given.
+#[derive(Deserialize, utoipa::ToSchema)]
 pub struct ElevateRequest {
+    pub password: Option<String>,
+    pub mfa_code: Option<String>,
     pub reason: Option<String>,
 }
 
+impl std::fmt::Debug for ElevateRequest {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ElevateRequest")
+            .field("password", &self.password.as_ref().map(|_| "[REDACTED]"))
+            .field("mfa_code", &self.mfa_code.as_ref().map(|_| "[REDACTED]"))
+            .field("reason", &self.reason)
+            .finish()
+    }
+}
+
 #[derive(Debug, Serialize, utoipa::ToSchema)]
 pub struct ElevateResponse {
     pub elevated: bool,
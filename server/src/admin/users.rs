@@ -0,0 +1,468 @@
+//! Admin User Account actions.
+//!
+//! Suspending an account, ending all of its sessions, sending it a password
+//! reset and granting or revoking system admin. Every action requires an
+//! elevated session and is written to the system audit log.
+//!
+//! Suspension and session revocation both set `users.tokens_revoked_at`, so
+//! outstanding access tokens stop working immediately, delete the refresh
+//! sessions, and publish `SessionRevoked` to the user's topic, which closes
+//! their WebSocket connections.
+
+#![allow(clippy::used_underscore_binding)]
+
+use std::net::SocketAddr;
+
+use axum::extract::{ConnectInfo, Path, State};
+use axum::http::StatusCode;
+use axum::{Extension, Json};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+use uuid::Uuid;
+
+use super::types::{AdminError, ElevatedAdmin, SystemAdminUser};
+use crate::api::AppState;
+use crate::auth::handlers::issue_password_reset;
+use crate::auth::AuthError;
+use crate::db;
+use crate::permissions::queries::{
+    grant_system_admin, is_system_admin, revoke_system_admin, write_audit_log,
+};
+use crate::ws::{broadcast_to_user, ServerEvent};
+
+/// Longest accepted suspension reason, in characters.
+const MAX_REASON_LEN: usize = 500;
+
+/// Suspend request.
+#[derive(Debug, Deserialize, utoipa::ToSchema)]
+pub struct SuspendUserRequest {
+    /// Why the account is suspended (shown in the audit log).
+    pub reason: String,
+}
+
+/// Suspension state of a user.
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct UserSuspensionResponse {
+    pub user_id: Uuid,
+    pub suspended: bool,
+    pub suspended_at: Option<DateTime<Utc>>,
+    pub reason: Option<String>,
+}
+
+/// Session revocation result.
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct RevokeSessionsResponse {
+    pub user_id: Uuid,
+    /// Refresh sessions that were deleted.
+    pub sessions_revoked: u64,
+}
+
+/// System admin state of a user.
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct SystemAdminResponse {
+    pub user_id: Uuid,
+    pub is_admin: bool,
+}
+
+/// Load a user or fail with `404`.
+async fn require_user(state: &AppState, user_id: Uuid) -> Result<db::User, AdminError> {
+    db::find_user_by_id(&state.db, user_id)
+        .await?
+        .ok_or_else(|| AdminError::NotFound("User".to_string()))
+}
+
+/// Reject tokens issued so far, delete the refresh sessions and close the
+/// user's WebSocket connections. Returns the number of deleted sessions.
+async fn revoke_sessions(state: &AppState, user_id: Uuid, reason: &str) -> Result<u64, AdminError> {
+    let mut tx = state.db.begin().await?;
+    sqlx::query("UPDATE users SET tokens_revoked_at = NOW() WHERE id = $1")
+        .bind(user_id)
+        .execute(&mut *tx)
+        .await?;
+    let deleted = sqlx::query("DELETE FROM sessions WHERE user_id = $1")
+        .bind(user_id)
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+    tx.commit().await?;
+
+    if let Err(e) = broadcast_to_user(
+        &state.redis,
+        user_id,
+        &ServerEvent::SessionRevoked {
+            reason: reason.to_string(),
+        },
+    )
+    .await
+    {
+        warn!(user_id = %user_id, error = %e, "Failed to publish session revocation");
+    }
+
+    Ok(deleted)
+}
+
+/// Suspend a user account.
+///
+/// The user is signed out everywhere and cannot sign in until unsuspended.
+/// System admins must have their admin role revoked first.
+///
+/// `POST /api/admin/users/:id/suspend`
+#[utoipa::path(
+    post,
+    path = "/api/admin/users/{id}/suspend",
+    tag = "admin",
+    params(("id" = Uuid, Path, description = "User ID")),
+    request_body = SuspendUserRequest,
+    responses(
+        (status = 200, description = "User suspended", body = UserSuspensionResponse),
+        (status = 400, description = "Invalid reason, own account or a system admin"),
+        (status = 404, description = "User not found"),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[tracing::instrument(skip(state))]
+pub async fn suspend_user(
+    State(state): State<AppState>,
+    Extension(admin): Extension<SystemAdminUser>,
+    Extension(_elevated): Extension<ElevatedAdmin>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    Path(user_id): Path<Uuid>,
+    Json(body): Json<SuspendUserRequest>,
+) -> Result<Json<UserSuspensionResponse>, AdminError> {
+    let reason = body.reason.trim();
+    if reason.is_empty() || reason.chars().count() > MAX_REASON_LEN {
+        return Err(AdminError::Validation(format!(
+            "reason must be 1-{MAX_REASON_LEN} characters"
+        )));
+    }
+    if user_id == admin.user_id {
+        return Err(AdminError::Validation(
+            "Cannot suspend yourself".to_string(),
+        ));
+    }
+    require_user(&state, user_id).await?;
+    if is_system_admin(&state.db, user_id).await? {
+        return Err(AdminError::Validation(
+            "Revoke system admin before suspending this user".to_string(),
+        ));
+    }
+
+    let suspended_at: DateTime<Utc> = sqlx::query_scalar(
+        r"UPDATE users
+          SET suspended_at = NOW(), suspension_reason = $2, suspended_by = $3
+          WHERE id = $1
+          RETURNING suspended_at",
+    )
+    .bind(user_id)
+    .bind(reason)
+    .bind(admin.user_id)
+    .fetch_one(&state.db)
+    .await?;
+
+    let sessions_revoked = revoke_sessions(&state, user_id, "suspended").await?;
+
+    let ip_address = addr.ip().to_string();
+    write_audit_log(
+        &state.db,
+        admin.user_id,
+        "admin.users.suspend",
+        Some("user"),
+        Some(user_id),
+        Some(serde_json::json!({
+            "reason": reason,
+            "sessions_revoked": sessions_revoked,
+        })),
+        Some(&ip_address),
+    )
+    .await?;
+
+    Ok(Json(UserSuspensionResponse {
+        user_id,
+        suspended: true,
+        suspended_at: Some(suspended_at),
+        reason: Some(reason.to_string()),
+    }))
+}
+
+/// Lift a user's suspension.
+///
+/// `DELETE /api/admin/users/:id/suspend`
+#[utoipa::path(
+    delete,
+    path = "/api/admin/users/{id}/suspend",
+    tag = "admin",
+    params(("id" = Uuid, Path, description = "User ID")),
+    responses(
+        (status = 200, description = "User unsuspended", body = UserSuspensionResponse),
+        (status = 404, description = "User is not suspended"),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[tracing::instrument(skip(state))]
+pub async fn unsuspend_user(
+    State(state): State<AppState>,
+    Extension(admin): Extension<SystemAdminUser>,
+    Extension(_elevated): Extension<ElevatedAdmin>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    Path(user_id): Path<Uuid>,
+) -> Result<Json<UserSuspensionResponse>, AdminError> {
+    let result = sqlx::query(
+        r"UPDATE users
+          SET suspended_at = NULL, suspension_reason = NULL, suspended_by = NULL
+          WHERE id = $1 AND suspended_at IS NOT NULL",
+    )
+    .bind(user_id)
+    .execute(&state.db)
+    .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(AdminError::NotFound("Suspension".to_string()));
+    }
+
+    let ip_address = addr.ip().to_string();
+    write_audit_log(
+        &state.db,
+        admin.user_id,
+        "admin.users.unsuspend",
+        Some("user"),
+        Some(user_id),
+        None,
+        Some(&ip_address),
+    )
+    .await?;
+
+    Ok(Json(UserSuspensionResponse {
+        user_id,
+        suspended: false,
+        suspended_at: None,
+        reason: None,
+    }))
+}
+
+/// Sign a user out everywhere.
+///
+/// Existing access and refresh tokens stop working and open WebSocket
+/// connections are closed; the user can sign in again.
+///
+/// `POST /api/admin/users/:id/revoke-sessions`
+#[utoipa::path(
+    post,
+    path = "/api/admin/users/{id}/revoke-sessions",
+    tag = "admin",
+    params(("id" = Uuid, Path, description = "User ID")),
+    responses(
+        (status = 200, description = "Sessions revoked", body = RevokeSessionsResponse),
+        (status = 404, description = "User not found"),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[tracing::instrument(skip(state))]
+pub async fn revoke_user_sessions(
+    State(state): State<AppState>,
+    Extension(admin): Extension<SystemAdminUser>,
+    Extension(_elevated): Extension<ElevatedAdmin>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    Path(user_id): Path<Uuid>,
+) -> Result<Json<RevokeSessionsResponse>, AdminError> {
+    require_user(&state, user_id).await?;
+
+    let sessions_revoked = revoke_sessions(&state, user_id, "revoked").await?;
+
+    let ip_address = addr.ip().to_string();
+    write_audit_log(
+        &state.db,
+        admin.user_id,
+        "admin.users.revoke_sessions",
+        Some("user"),
+        Some(user_id),
+        Some(serde_json::json!({ "sessions_revoked": sessions_revoked })),
+        Some(&ip_address),
+    )
+    .await?;
+
+    Ok(Json(RevokeSessionsResponse {
+        user_id,
+        sessions_revoked,
+    }))
+}
+
+/// Email a user a password reset code.
+///
+/// Requires SMTP and an account with an email address and a password
+/// sign-in method.
+///
+/// `POST /api/admin/users/:id/password-reset`
+#[utoipa::path(
+    post,
+    path = "/api/admin/users/{id}/password-reset",
+    tag = "admin",
+    params(("id" = Uuid, Path, description = "User ID")),
+    responses(
+        (status = 204, description = "Password reset email sent"),
+        (status = 400, description = "User has no email or no password sign-in"),
+        (status = 404, description = "User not found"),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[tracing::instrument(skip(state))]
+pub async fn send_password_reset(
+    State(state): State<AppState>,
+    Extension(admin): Extension<SystemAdminUser>,
+    Extension(_elevated): Extension<ElevatedAdmin>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    Path(user_id): Path<Uuid>,
+) -> Result<StatusCode, AdminError> {
+    let user = require_user(&state, user_id).await?;
+    let Some(email) = user.email.clone() else {
+        return Err(AdminError::Validation(
+            "User has no email address".to_string(),
+        ));
+    };
+    if user.password_hash.is_none() {
+        return Err(AdminError::Validation(
+            "User does not sign in with a password".to_string(),
+        ));
+    }
+
+    issue_password_reset(&state, &user, &email)
+        .await
+        .map_err(|e| match e {
+            AuthError::EmailNotConfigured => {
+                AdminError::Validation("Email is not configured on this server".to_string())
+            }
+            AuthError::Database(e) => AdminError::Database(e),
+            e => AdminError::Internal(e.to_string()),
+        })?;
+
+    let ip_address = addr.ip().to_string();
+    write_audit_log(
+        &state.db,
+        admin.user_id,
+        "admin.users.password_reset",
+        Some("user"),
+        Some(user_id),
+        None,
+        Some(&ip_address),
+    )
+    .await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Grant system admin to a user.
+///
+/// `POST /api/admin/users/:id/system-admin`
+#[utoipa::path(
+    post,
+    path = "/api/admin/users/{id}/system-admin",
+    tag = "admin",
+    params(("id" = Uuid, Path, description = "User ID")),
+    responses(
+        (status = 200, description = "System admin granted", body = SystemAdminResponse),
+        (status = 400, description = "User is suspended or already an admin"),
+        (status = 404, description = "User not found"),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[tracing::instrument(skip(state))]
+pub async fn grant_admin(
+    State(state): State<AppState>,
+    Extension(admin): Extension<SystemAdminUser>,
+    Extension(_elevated): Extension<ElevatedAdmin>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    Path(user_id): Path<Uuid>,
+) -> Result<Json<SystemAdminResponse>, AdminError> {
+    let user = require_user(&state, user_id).await?;
+    if user.is_bot {
+        return Err(AdminError::Validation(
+            "Bots cannot be system admins".to_string(),
+        ));
+    }
+    if user.suspended_at.is_some() {
+        return Err(AdminError::Validation("User is suspended".to_string()));
+    }
+    if is_system_admin(&state.db, user_id).await? {
+        return Err(AdminError::Validation(
+            "User is already a system admin".to_string(),
+        ));
+    }
+
+    grant_system_admin(&state.db, user_id, Some(admin.user_id)).await?;
+
+    let ip_address = addr.ip().to_string();
+    write_audit_log(
+        &state.db,
+        admin.user_id,
+        "admin.users.grant_admin",
+        Some("user"),
+        Some(user_id),
+        None,
+        Some(&ip_address),
+    )
+    .await?;
+
+    Ok(Json(SystemAdminResponse {
+        user_id,
+        is_admin: true,
+    }))
+}
+
+/// Revoke system admin from a user.
+///
+/// Their elevated sessions end with it. Admins cannot revoke themselves, so
+/// at least one admin always remains.
+///
+/// `DELETE /api/admin/users/:id/system-admin`
+#[utoipa::path(
+    delete,
+    path = "/api/admin/users/{id}/system-admin",
+    tag = "admin",
+    params(("id" = Uuid, Path, description = "User ID")),
+    responses(
+        (status = 200, description = "System admin revoked", body = SystemAdminResponse),
+        (status = 400, description = "Own account"),
+        (status = 404, description = "User is not a system admin"),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[tracing::instrument(skip(state))]
+pub async fn revoke_admin(
+    State(state): State<AppState>,
+    Extension(admin): Extension<SystemAdminUser>,
+    Extension(_elevated): Extension<ElevatedAdmin>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    Path(user_id): Path<Uuid>,
+) -> Result<Json<SystemAdminResponse>, AdminError> {
+    if user_id == admin.user_id {
+        return Err(AdminError::Validation(
+            "Cannot revoke your own system admin".to_string(),
+        ));
+    }
+    if !revoke_system_admin(&state.db, user_id).await? {
+        return Err(AdminError::NotFound("System admin".to_string()));
+    }
+
+    sqlx::query("DELETE FROM elevated_sessions WHERE user_id = $1")
+        .bind(user_id)
+        .execute(&state.db)
+        .await?;
+    super::cache_elevated_status(&state.redis, user_id, false, 60).await;
+
+    let ip_address = addr.ip().to_string();
+    write_audit_log(
+        &state.db,
+        admin.user_id,
+        "admin.users.revoke_admin",
+        Some("user"),
+        Some(user_id),
+        None,
+        Some(&ip_address),
+    )
+    .await?;
+
+    Ok(Json(SystemAdminResponse {
+        user_id,
+        is_admin: false,
+    }))
+}
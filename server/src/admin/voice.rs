@@ -0,0 +1,74 @@
+//! Admin Voice Diagnostics API
+//!
+//! Live SFU state of the voice rooms hosted by the node serving the request.
+//! Requires `SystemAdminUser` middleware (non-elevated).
+
+use axum::extract::{Path, State};
+use axum::{Extension, Json};
+use serde::Serialize;
+use uuid::Uuid;
+
+use super::types::{AdminError, SystemAdminUser};
+use crate::api::AppState;
+use crate::voice::diagnostics::{self, VoiceRoomDiagnostics, VoiceRoomSummary};
+
+/// Voice rooms hosted by this node.
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct VoiceRoomsResponse {
+    /// Node that served this request.
+    pub node_id: String,
+    /// Hosted rooms, ordered by channel ID.
+    pub rooms: Vec<VoiceRoomSummary>,
+}
+
+/// List the voice rooms hosted by this node.
+#[utoipa::path(
+    get,
+    path = "/api/admin/voice/rooms",
+    tag = "admin",
+    responses((status = 200, body = VoiceRoomsResponse)),
+    security(("bearer_auth" = []))
+)]
+#[tracing::instrument(skip(state, _admin))]
+pub async fn list_voice_rooms(
+    Extension(_admin): Extension<SystemAdminUser>,
+    State(state): State<AppState>,
+) -> Result<Json<VoiceRoomsResponse>, AdminError> {
+    let mut rooms = Vec::new();
+    for room in state.sfu.rooms().await {
+        rooms.push(diagnostics::room_summary(&room).await);
+    }
+
+    Ok(Json(VoiceRoomsResponse {
+        node_id: state.config().node_id.clone(),
+        rooms,
+    }))
+}
+
+/// Get the participants of a hosted voice room with their connection state,
+/// current bitrate and recent stats.
+#[utoipa::path(
+    get,
+    path = "/api/admin/voice/rooms/{channel_id}",
+    tag = "admin",
+    params(("channel_id" = Uuid, Path, description = "Voice channel ID")),
+    responses(
+        (status = 200, body = VoiceRoomDiagnostics),
+        (status = 404, description = "Room not hosted by this node"),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[tracing::instrument(skip(state, _admin))]
+pub async fn get_voice_room(
+    Extension(_admin): Extension<SystemAdminUser>,
+    State(state): State<AppState>,
+    Path(channel_id): Path<Uuid>,
+) -> Result<Json<VoiceRoomDiagnostics>, AdminError> {
+    let room = state
+        .sfu
+        .get_room(channel_id)
+        .await
+        .ok_or_else(|| AdminError::NotFound("Voice room".into()))?;
+
+    Ok(Json(diagnostics::room_diagnostics(&state.db, &room).await?))
+}
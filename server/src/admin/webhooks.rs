@@ -0,0 +1,229 @@
+//! Admin Webhook Dead-Letter handlers.
+//!
+//! Deliveries that exhausted their retries land in `webhook_dead_letters`.
+//! Listing them requires `SystemAdminUser`; replaying requires an elevated
+//! session and re-enqueues the event as a fresh first attempt with its
+//! original event ID, so receivers deduplicating on `X-Webhook-ID` ignore
+//! copies they already processed.
+
+#![allow(clippy::used_underscore_binding)]
+
+use std::net::SocketAddr;
+
+use axum::extract::{ConnectInfo, Path, Query, State};
+use axum::http::StatusCode;
+use axum::{Extension, Json};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::handlers::PaginatedResponse;
+use super::types::{AdminError, ElevatedAdmin, SystemAdminUser};
+use crate::api::AppState;
+use crate::permissions::queries::write_audit_log;
+use crate::webhooks::types::{DeadLetterEntry, WebhookDeliveryItem};
+use crate::webhooks::{delivery, queries};
+
+/// Most dead letters replayed by one bulk request.
+const MAX_BULK_REPLAY: i64 = 100;
+
+/// Dead-letter list query parameters.
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+pub struct DeadLetterParams {
+    /// Only dead letters of this webhook.
+    pub webhook_id: Option<Uuid>,
+    /// Maximum number of items to return.
+    #[serde(default = "default_limit")]
+    pub limit: i64,
+    /// Number of items to skip.
+    #[serde(default)]
+    pub offset: i64,
+}
+
+#[allow(clippy::missing_const_for_fn)]
+fn default_limit() -> i64 {
+    50
+}
+
+/// Bulk replay request.
+#[derive(Debug, Deserialize, utoipa::ToSchema)]
+pub struct ReplayDeadLettersRequest {
+    /// Webhook whose dead letters to replay, oldest first.
+    pub webhook_id: Uuid,
+}
+
+/// Replay result.
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct ReplayResponse {
+    /// Dead letters re-enqueued for delivery.
+    pub replayed: usize,
+    /// Dead letters still waiting (bulk replays are capped per request).
+    pub remaining: i64,
+}
+
+/// List dead-lettered webhook deliveries.
+///
+/// `GET /api/admin/webhooks/dead-letters`
+#[utoipa::path(
+    get,
+    path = "/api/admin/webhooks/dead-letters",
+    tag = "admin",
+    params(DeadLetterParams),
+    responses((status = 200, body = PaginatedResponse<DeadLetterEntry>)),
+    security(("bearer_auth" = []))
+)]
+#[tracing::instrument(skip(state))]
+pub async fn list_dead_letters(
+    State(state): State<AppState>,
+    Extension(_admin): Extension<SystemAdminUser>,
+    Query(params): Query<DeadLetterParams>,
+) -> Result<Json<PaginatedResponse<DeadLetterEntry>>, AdminError> {
+    let limit = params.limit.clamp(1, 100);
+    let offset = params.offset.max(0);
+
+    let total = queries::count_dead_letters(&state.db, params.webhook_id).await?;
+    let items = queries::list_dead_letters(&state.db, params.webhook_id, limit, offset).await?;
+
+    Ok(Json(PaginatedResponse {
+        items,
+        total,
+        limit,
+        offset,
+    }))
+}
+
+/// Replay one dead-lettered delivery.
+///
+/// `POST /api/admin/webhooks/dead-letters/:id/replay`
+#[utoipa::path(
+    post,
+    path = "/api/admin/webhooks/dead-letters/{id}/replay",
+    tag = "admin",
+    params(("id" = Uuid, Path, description = "Dead letter ID")),
+    responses(
+        (status = 202, description = "Delivery re-enqueued", body = ReplayResponse),
+        (status = 400, description = "Webhook is inactive"),
+        (status = 404, description = "Dead letter not found"),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[tracing::instrument(skip(state))]
+pub async fn replay_dead_letter(
+    State(state): State<AppState>,
+    Extension(admin): Extension<SystemAdminUser>,
+    Extension(_elevated): Extension<ElevatedAdmin>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    Path(id): Path<Uuid>,
+) -> Result<(StatusCode, Json<ReplayResponse>), AdminError> {
+    let entry = queries::get_dead_letter(&state.db, id)
+        .await?
+        .ok_or_else(|| AdminError::NotFound("Dead letter".to_string()))?;
+    ensure_active(&entry)?;
+
+    replay(&state, &entry).await?;
+
+    let ip_address = addr.ip().to_string();
+    write_audit_log(
+        &state.db,
+        admin.user_id,
+        "admin.webhooks.replay",
+        Some("webhook"),
+        Some(entry.webhook_id),
+        Some(serde_json::json!({
+            "dead_letter_id": entry.id,
+            "event_id": entry.event_id,
+        })),
+        Some(&ip_address),
+    )
+    .await?;
+
+    let remaining = queries::count_dead_letters(&state.db, Some(entry.webhook_id)).await?;
+    Ok((
+        StatusCode::ACCEPTED,
+        Json(ReplayResponse {
+            replayed: 1,
+            remaining,
+        }),
+    ))
+}
+
+/// Replay a webhook's dead-lettered deliveries, oldest first.
+///
+/// `POST /api/admin/webhooks/dead-letters/replay`
+#[utoipa::path(
+    post,
+    path = "/api/admin/webhooks/dead-letters/replay",
+    tag = "admin",
+    request_body = ReplayDeadLettersRequest,
+    responses(
+        (status = 202, description = "Deliveries re-enqueued", body = ReplayResponse),
+        (status = 400, description = "Webhook is inactive"),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[tracing::instrument(skip(state))]
+pub async fn replay_dead_letters(
+    State(state): State<AppState>,
+    Extension(admin): Extension<SystemAdminUser>,
+    Extension(_elevated): Extension<ElevatedAdmin>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    Json(req): Json<ReplayDeadLettersRequest>,
+) -> Result<(StatusCode, Json<ReplayResponse>), AdminError> {
+    let entries = queries::oldest_dead_letters(&state.db, req.webhook_id, MAX_BULK_REPLAY).await?;
+    if let Some(entry) = entries.first() {
+        ensure_active(entry)?;
+    }
+
+    for entry in &entries {
+        replay(&state, entry).await?;
+    }
+
+    let ip_address = addr.ip().to_string();
+    write_audit_log(
+        &state.db,
+        admin.user_id,
+        "admin.webhooks.replay",
+        Some("webhook"),
+        Some(req.webhook_id),
+        Some(serde_json::json!({ "replayed": entries.len() })),
+        Some(&ip_address),
+    )
+    .await?;
+
+    let remaining = queries::count_dead_letters(&state.db, Some(req.webhook_id)).await?;
+    Ok((
+        StatusCode::ACCEPTED,
+        Json(ReplayResponse {
+            replayed: entries.len(),
+            remaining,
+        }),
+    ))
+}
+
+/// Replaying into an inactive webhook would be dropped by the worker.
+fn ensure_active(entry: &DeadLetterEntry) -> Result<(), AdminError> {
+    if entry.webhook_active {
+        Ok(())
+    } else {
+        Err(AdminError::Validation(
+            "Webhook is inactive; reactivate it before replaying".to_string(),
+        ))
+    }
+}
+
+/// Re-enqueue a dead letter as a first attempt, then drop it from the table.
+async fn replay(state: &AppState, entry: &DeadLetterEntry) -> Result<(), AdminError> {
+    let item = WebhookDeliveryItem {
+        webhook_id: entry.webhook_id,
+        url: entry.url.clone(),
+        event_type: entry.event_type,
+        event_id: entry.event_id,
+        payload: entry.payload.clone(),
+        attempt: 0,
+        event_time: entry.event_time,
+    };
+    delivery::enqueue(&state.redis, &item)
+        .await
+        .map_err(|e| AdminError::Internal(format!("Failed to enqueue delivery: {e}")))?;
+    queries::delete_dead_letter(&state.db, entry.id).await?;
+    Ok(())
+}
@@ -0,0 +1,41 @@
+//! Optimistic Concurrency
+//!
+//! Guilds, channels, roles and pages carry a `version` that every PATCH
+//! bumps. Update requests may send the `version` they were based on; if the
+//! resource changed in the meantime the update is rejected with
+//! [`VersionConflict`] (409), which carries the current state so the client
+//! can reconcile and retry. Requests without a `version` are applied as before.
+
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use serde::Serialize;
+
+/// An update was based on an outdated version of the resource.
+#[derive(Debug, Clone)]
+pub struct VersionConflict {
+    current: serde_json::Value,
+}
+
+impl VersionConflict {
+    /// Conflict reporting `current` as the resource's latest state.
+    pub fn new(current: &impl Serialize) -> Self {
+        Self {
+            current: serde_json::to_value(current).unwrap_or_default(),
+        }
+    }
+}
+
+impl IntoResponse for VersionConflict {
+    fn into_response(self) -> Response {
+        (
+            StatusCode::CONFLICT,
+            Json(serde_json::json!({
+                "error": "VERSION_CONFLICT",
+                "message": "This was changed by someone else; review the current version and try again",
+                "current": self.current,
+            })),
+        )
+            .into_response()
+    }
+}
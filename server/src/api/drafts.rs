@@ -0,0 +1,294 @@
+//! Message Drafts API
+//!
+//! Per-channel draft text that syncs across a user's devices.
+//!
+//! Every save carries the time the draft was last edited on the device.
+//! The newest edit wins: a save older than the stored draft is ignored and
+//! the stored draft is returned instead, so the client can adopt it. Other
+//! devices are told about accepted saves through a `draft_update` event.
+//!
+//! Saving empty content clears a draft. Cleared drafts keep their timestamp
+//! for [`CLEARED_DRAFT_RETENTION_HOURS`] so a stale save from an offline
+//! device cannot bring them back.
+
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, put};
+use axum::{Json, Router};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::api::AppState;
+use crate::auth::AuthUser;
+use crate::ws::{broadcast_to_user, ServerEvent};
+
+/// Maximum draft length in characters (the message hard limit).
+const MAX_DRAFT_LENGTH: usize = 10_000;
+
+/// Maximum number of non-empty drafts per user.
+const MAX_DRAFTS_PER_USER: i64 = 100;
+
+/// How long cleared drafts are remembered.
+const CLEARED_DRAFT_RETENTION_HOURS: i32 = 72;
+
+// ============================================================================
+// Error Types
+// ============================================================================
+
+/// Error types for draft operations.
+#[derive(Debug, thiserror::Error)]
+pub enum DraftsError {
+    #[error("Database error: {0}")]
+    Database(#[from] sqlx::Error),
+    #[error("Validation error: {0}")]
+    Validation(String),
+    #[error("Channel not found")]
+    ChannelNotFound,
+    #[error("Draft limit reached")]
+    LimitExceeded,
+}
+
+impl IntoResponse for DraftsError {
+    fn into_response(self) -> Response {
+        use serde_json::json;
+
+        let (status, code, message) = match &self {
+            Self::Database(err) => {
+                tracing::error!("Database error: {}", err);
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "INTERNAL_ERROR",
+                    "Database error".to_string(),
+                )
+            }
+            Self::Validation(msg) => (StatusCode::BAD_REQUEST, "VALIDATION_ERROR", msg.clone()),
+            Self::ChannelNotFound => (
+                StatusCode::NOT_FOUND,
+                "CHANNEL_NOT_FOUND",
+                "Channel not found".to_string(),
+            ),
+            Self::LimitExceeded => (
+                StatusCode::BAD_REQUEST,
+                "LIMIT_EXCEEDED",
+                format!("Maximum number of drafts reached ({MAX_DRAFTS_PER_USER})"),
+            ),
+        };
+
+        (status, Json(json!({ "error": code, "message": message }))).into_response()
+    }
+}
+
+// ============================================================================
+// Request/Response Types
+// ============================================================================
+
+/// A channel draft.
+#[derive(Debug, Serialize, sqlx::FromRow, utoipa::ToSchema)]
+pub struct Draft {
+    pub channel_id: Uuid,
+    /// Draft text; empty when the draft was cleared.
+    pub content: String,
+    /// When the draft was last edited.
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Response for listing drafts
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct DraftsResponse {
+    pub drafts: Vec<Draft>,
+}
+
+/// Request body for saving a draft
+#[derive(Debug, Deserialize, utoipa::ToSchema)]
+pub struct SaveDraftRequest {
+    /// Draft text; empty clears the draft.
+    pub content: String,
+    /// When the draft was edited on the device (defaults to now). Times in
+    /// the future are capped to the server time.
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+// ============================================================================
+// Router
+// ============================================================================
+
+/// Create the drafts router.
+///
+/// Routes:
+/// - GET `/` - List the current user's drafts
+/// - PUT `/{channel_id}` - Save or clear a channel's draft
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route("/", get(list_drafts))
+        .route("/{channel_id}", put(save_draft))
+}
+
+// ============================================================================
+// Validation
+// ============================================================================
+
+fn validate_draft(content: &str) -> Result<(), DraftsError> {
+    let len = content.chars().count();
+    if len > MAX_DRAFT_LENGTH {
+        return Err(DraftsError::Validation(format!(
+            "Draft too long ({len} characters, max {MAX_DRAFT_LENGTH})"
+        )));
+    }
+    Ok(())
+}
+
+// ============================================================================
+// Handlers
+// ============================================================================
+
+/// GET /api/me/drafts
+/// Returns the current user's non-empty drafts, newest first
+#[utoipa::path(
+    get,
+    path = "/api/me/drafts",
+    tag = "drafts",
+    responses(
+        (status = 200, description = "User drafts", body = DraftsResponse),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[tracing::instrument(skip(state), fields(user_id = %auth_user.id))]
+pub async fn list_drafts(
+    State(state): State<AppState>,
+    auth_user: AuthUser,
+) -> Result<Json<DraftsResponse>, DraftsError> {
+    let drafts = sqlx::query_as::<_, Draft>(
+        r"
+        SELECT channel_id, content, updated_at
+        FROM message_drafts
+        WHERE user_id = $1 AND content <> ''
+        ORDER BY updated_at DESC
+        ",
+    )
+    .bind(auth_user.id)
+    .fetch_all(&state.db)
+    .await?;
+
+    Ok(Json(DraftsResponse { drafts }))
+}
+
+/// PUT `/api/me/drafts/{channel_id}`
+/// Saves a channel draft unless a newer one is stored, and returns the
+/// draft that is stored afterwards
+#[utoipa::path(
+    put,
+    path = "/api/me/drafts/{channel_id}",
+    tag = "drafts",
+    params(("channel_id" = Uuid, Path, description = "Channel ID")),
+    request_body = SaveDraftRequest,
+    responses(
+        (status = 200, description = "Stored draft (the newer one on conflict)", body = Draft),
+        (status = 400, description = "Validation error or draft limit reached"),
+        (status = 404, description = "Channel not found"),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[tracing::instrument(skip(state, request), fields(user_id = %auth_user.id))]
+pub async fn save_draft(
+    State(state): State<AppState>,
+    auth_user: AuthUser,
+    Path(channel_id): Path<Uuid>,
+    Json(request): Json<SaveDraftRequest>,
+) -> Result<Json<Draft>, DraftsError> {
+    validate_draft(&request.content)?;
+
+    crate::permissions::require_channel_access(&state.db, auth_user.id, channel_id)
+        .await
+        .map_err(|_| DraftsError::ChannelNotFound)?;
+
+    if !request.content.is_empty() {
+        let others: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM message_drafts
+             WHERE user_id = $1 AND channel_id <> $2 AND content <> ''",
+        )
+        .bind(auth_user.id)
+        .bind(channel_id)
+        .fetch_one(&state.db)
+        .await?;
+        if others >= MAX_DRAFTS_PER_USER {
+            return Err(DraftsError::LimitExceeded);
+        }
+    }
+
+    let saved = sqlx::query_as::<_, Draft>(
+        r"
+        INSERT INTO message_drafts (user_id, channel_id, content, updated_at)
+        VALUES ($1, $2, $3, LEAST(COALESCE($4, NOW()), NOW()))
+        ON CONFLICT (user_id, channel_id) DO UPDATE
+        SET content = EXCLUDED.content,
+            updated_at = EXCLUDED.updated_at
+        WHERE message_drafts.updated_at < EXCLUDED.updated_at
+        RETURNING channel_id, content, updated_at
+        ",
+    )
+    .bind(auth_user.id)
+    .bind(channel_id)
+    .bind(&request.content)
+    .bind(request.updated_at)
+    .fetch_optional(&state.db)
+    .await?;
+
+    let Some(draft) = saved else {
+        // A newer draft is stored; hand it back so the device can adopt it
+        let current = sqlx::query_as::<_, Draft>(
+            "SELECT channel_id, content, updated_at FROM message_drafts
+             WHERE user_id = $1 AND channel_id = $2",
+        )
+        .bind(auth_user.id)
+        .bind(channel_id)
+        .fetch_one(&state.db)
+        .await?;
+        return Ok(Json(current));
+    };
+
+    // Broadcast to all user's devices via WebSocket
+    let event = ServerEvent::DraftUpdate {
+        channel_id: draft.channel_id,
+        content: draft.content.clone(),
+        updated_at: draft.updated_at,
+    };
+    if let Err(e) = broadcast_to_user(&state.redis, auth_user.id, &event).await {
+        tracing::warn!("Failed to broadcast draft update: {}", e);
+        // Don't fail the request if broadcast fails - the draft was saved
+    }
+
+    Ok(Json(draft))
+}
+
+// ============================================================================
+// Maintenance
+// ============================================================================
+
+/// Forget cleared drafts older than [`CLEARED_DRAFT_RETENTION_HOURS`].
+pub async fn cleanup_cleared_drafts(pool: &PgPool) -> sqlx::Result<u64> {
+    let result = sqlx::query(
+        "DELETE FROM message_drafts
+         WHERE content = '' AND updated_at < NOW() - make_interval(hours => $1)",
+    )
+    .bind(CLEARED_DRAFT_RETENTION_HOURS)
+    .execute(pool)
+    .await?;
+    Ok(result.rows_affected())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_draft_length() {
+        assert!(validate_draft("").is_ok());
+        assert!(validate_draft(&"a".repeat(MAX_DRAFT_LENGTH)).is_ok());
+        assert!(validate_draft(&"a".repeat(MAX_DRAFT_LENGTH + 1)).is_err());
+        // Counted in characters, not bytes
+        assert!(validate_draft(&"ä".repeat(MAX_DRAFT_LENGTH)).is_ok());
+    }
+}
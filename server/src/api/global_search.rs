@@ -188,7 +188,7 @@ pub async fn search_all(
     if !guild_ids.is_empty() {
         let guild_channels: Vec<db::Channel> = sqlx::query_as(
             "SELECT id, name, channel_type, category_id, guild_id, topic, icon_url, \
-             user_limit, position, max_screen_shares, created_at, updated_at \
+             user_limit, position, max_screen_shares, created_at, updated_at, version \
              FROM channels WHERE guild_id = ANY($1) ORDER BY position ASC",
         )
         .bind(&guild_ids)
@@ -329,21 +329,29 @@ pub async fn search_all(
     let results: Vec<GlobalSearchResult> = messages
         .into_iter()
         .map(|msg| {
-            let author = msg
-                .user_id
-                .and_then(|uid| user_map.get(&uid))
-                .map(|u| GlobalSearchAuthor {
-                    id: u.id,
-                    username: u.username.clone(),
-                    display_name: u.display_name.clone(),
-                    avatar_url: u.avatar_url.clone(),
-                })
-                .unwrap_or_else(|| GlobalSearchAuthor {
-                    id: msg.user_id.unwrap_or(Uuid::nil()),
-                    username: "deleted".to_string(),
-                    display_name: "Deleted User".to_string(),
-                    avatar_url: None,
-                });
+            let author = if let Some(name) = &msg.webhook_name {
+                GlobalSearchAuthor {
+                    id: msg.webhook_id.unwrap_or(Uuid::nil()),
+                    username: name.clone(),
+                    display_name: name.clone(),
+                    avatar_url: msg.webhook_avatar_url.clone(),
+                }
+            } else {
+                msg.user_id
+                    .and_then(|uid| user_map.get(&uid))
+                    .map(|u| GlobalSearchAuthor {
+                        id: u.id,
+                        username: u.username.clone(),
+                        display_name: u.display_name.clone(),
+                        avatar_url: u.avatar_url.clone(),
+                    })
+                    .unwrap_or_else(|| GlobalSearchAuthor {
+                        id: msg.author_tombstone_id.unwrap_or(Uuid::nil()),
+                        username: "deleted".to_string(),
+                        display_name: "Deleted User".to_string(),
+                        avatar_url: None,
+                    })
+            };
 
             let channel_name = channel_name_map
                 .get(&msg.channel_id)
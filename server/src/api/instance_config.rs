@@ -0,0 +1,247 @@
+//! Runtime Instance Configuration
+//!
+//! The server configuration can change without a restart in two ways:
+//!
+//! - System admins override some environment settings through
+//!   `PATCH /api/admin/settings`. The overrides live under the
+//!   `instance_settings` key of `server_config`.
+//! - The environment is reloaded on `SIGHUP` or through
+//!   `POST /api/admin/config/reload`, which re-read the `.env` file.
+//!
+//! [`LiveConfig`] holds the result behind an `ArcSwap`; handlers take a
+//! snapshot with `state.config()` and never see a half-updated config. The
+//! node that saves an override refreshes right away; other nodes pick it up
+//! within [`REFRESH_INTERVAL`].
+//!
+//! Settings used to build the router, database and Redis clients, email or
+//! push backends at startup keep their startup value until a restart (see
+//! [`Config::keep_startup_settings`]). Upload limits can only be lowered: the
+//! startup values are also the request body limits of the router.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use arc_swap::ArcSwap;
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use tokio::sync::Mutex;
+use tracing::{info, warn};
+use utoipa::ToSchema;
+
+use super::AppState;
+use crate::config::Config;
+
+/// `server_config` key holding the overrides.
+pub const CONFIG_KEY: &str = "instance_settings";
+
+/// How often a node re-reads the overrides saved by other nodes.
+pub const REFRESH_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Environment settings overridden at runtime; `None` keeps the environment
+/// value.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(default)]
+pub struct InstanceOverrides {
+    /// Overrides `ENABLE_GUILD_DISCOVERY`.
+    pub guild_discovery_enabled: Option<bool>,
+    /// Overrides `MAX_UPLOAD_SIZE` (bytes, at most the environment value).
+    pub max_upload_size: Option<usize>,
+    /// Overrides `MAX_AVATAR_SIZE` (bytes, at most the environment value).
+    pub max_avatar_size: Option<usize>,
+    /// Overrides `MAX_EMOJI_SIZE` (bytes, at most the environment value).
+    pub max_emoji_size: Option<usize>,
+}
+
+impl InstanceOverrides {
+    /// Load the stored overrides, falling back to none when they cannot be
+    /// read.
+    pub async fn load(pool: &PgPool) -> Self {
+        match crate::db::get_config_value(pool, CONFIG_KEY).await {
+            Ok(value) => serde_json::from_value(value).unwrap_or_else(|e| {
+                warn!(error = %e, "Invalid instance settings, using environment values");
+                Self::default()
+            }),
+            Err(e) => {
+                warn!(error = %e, "Failed to load instance settings, using environment values");
+                Self::default()
+            }
+        }
+    }
+
+    /// `base` with these overrides applied.
+    ///
+    /// Upload limits above the environment value are capped to it.
+    #[must_use]
+    pub fn apply(&self, base: &Config) -> Config {
+        let mut config = base.clone();
+        if let Some(enabled) = self.guild_discovery_enabled {
+            config.enable_guild_discovery = enabled;
+        }
+        if let Some(size) = self.max_upload_size {
+            config.max_upload_size = size.min(base.max_upload_size);
+        }
+        if let Some(size) = self.max_avatar_size {
+            config.max_avatar_size = size.min(base.max_avatar_size);
+        }
+        if let Some(size) = self.max_emoji_size {
+            config.max_emoji_size = size.min(base.max_emoji_size);
+        }
+        config
+    }
+}
+
+/// This node's configuration: the environment values with the overrides
+/// applied, swapped as a whole on every change.
+pub struct LiveConfig {
+    /// The configuration the process started with.
+    startup: Arc<Config>,
+    /// Environment values as last (re)loaded.
+    env: ArcSwap<Config>,
+    /// `env` with the overrides applied.
+    current: ArcSwap<Config>,
+    /// Serializes refreshes so a slow one cannot overwrite a newer result.
+    refresh_lock: Mutex<()>,
+}
+
+impl LiveConfig {
+    /// Start from `config` without overrides; call [`refresh`](Self::refresh)
+    /// to apply them.
+    #[must_use]
+    pub fn new(config: Config) -> Self {
+        let config = Arc::new(config);
+        Self {
+            startup: config.clone(),
+            env: ArcSwap::new(config.clone()),
+            current: ArcSwap::new(config),
+            refresh_lock: Mutex::new(()),
+        }
+    }
+
+    /// The current configuration, overrides included.
+    #[must_use]
+    pub fn current(&self) -> Arc<Config> {
+        self.current.load_full()
+    }
+
+    /// The configuration the process started with. The router, its request
+    /// body limits included, is built from it.
+    #[must_use]
+    pub fn startup(&self) -> Arc<Config> {
+        self.startup.clone()
+    }
+
+    /// The environment values, without overrides. Upper bound of the upload
+    /// limit overrides.
+    #[must_use]
+    pub fn env(&self) -> Arc<Config> {
+        self.env.load_full()
+    }
+
+    /// Re-read the overrides and rebuild the current configuration.
+    pub async fn refresh(&self, pool: &PgPool) -> Arc<Config> {
+        let _guard = self.refresh_lock.lock().await;
+        let overrides = InstanceOverrides::load(pool).await;
+        let config = Arc::new(overrides.apply(&self.env.load()));
+        self.current.store(config.clone());
+        config
+    }
+
+    /// Replace the environment values with `config` and refresh.
+    ///
+    /// Returns the changed settings that only apply after a restart; they
+    /// keep their startup value.
+    pub async fn reload(&self, pool: &PgPool, mut config: Config) -> Vec<&'static str> {
+        let restart_required = config.keep_startup_settings(&self.startup);
+        self.env.store(Arc::new(config));
+        self.refresh(pool).await;
+        restart_required
+    }
+}
+
+/// Re-read the overrides every [`REFRESH_INTERVAL`], so changes saved on
+/// other nodes apply here too.
+pub fn spawn_refresh_task(state: AppState) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(REFRESH_INTERVAL);
+        interval.tick().await; // consume immediate first tick
+        loop {
+            interval.tick().await;
+            state.refresh_config().await;
+        }
+    })
+}
+
+/// Reload the configuration whenever the process receives `SIGHUP`.
+#[cfg(unix)]
+pub fn spawn_sighup_handler(state: AppState) -> tokio::task::JoinHandle<()> {
+    use tokio::signal::unix::{signal, SignalKind};
+
+    tokio::spawn(async move {
+        let mut hangup = match signal(SignalKind::hangup()) {
+            Ok(hangup) => hangup,
+            Err(e) => {
+                warn!(error = %e, "Failed to install SIGHUP handler, config reload by signal disabled");
+                return;
+            }
+        };
+        while hangup.recv().await.is_some() {
+            info!("Received SIGHUP, reloading configuration");
+            if let Err(e) = state.reload_config().await {
+                warn!(error = %e, "Configuration reload failed, keeping the current configuration");
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_no_overrides_keeps_environment_values() {
+        let base = Config::default_for_test();
+        let config = InstanceOverrides::default().apply(&base);
+        assert_eq!(config.enable_guild_discovery, base.enable_guild_discovery);
+        assert_eq!(config.max_upload_size, base.max_upload_size);
+        assert_eq!(config.max_avatar_size, base.max_avatar_size);
+    }
+
+    #[test]
+    fn test_upload_limits_are_capped_to_environment() {
+        let base = Config::default_for_test();
+        let overrides = InstanceOverrides {
+            guild_discovery_enabled: Some(!base.enable_guild_discovery),
+            max_upload_size: Some(base.max_upload_size * 2),
+            max_avatar_size: Some(1024),
+            max_emoji_size: None,
+        };
+        let config = overrides.apply(&base);
+        assert_eq!(config.enable_guild_discovery, !base.enable_guild_discovery);
+        assert_eq!(config.max_upload_size, base.max_upload_size);
+        assert_eq!(config.max_avatar_size, 1024);
+        assert_eq!(config.max_emoji_size, base.max_emoji_size);
+    }
+
+    #[test]
+    fn test_reload_keeps_startup_settings() {
+        let startup = Config::default_for_test();
+        let mut reloaded = startup.clone();
+        reloaded.redis_url = "redis://elsewhere:6379".into();
+        reloaded.node_id = "another-node".into();
+        reloaded.max_upload_size = startup.max_upload_size * 2;
+        reloaded.max_emoji_size = 2048;
+        reloaded.enable_guild_discovery = !startup.enable_guild_discovery;
+
+        let restart_required = reloaded.keep_startup_settings(&startup);
+        assert_eq!(restart_required, vec!["redis_url"]);
+        assert_eq!(reloaded.redis_url, startup.redis_url);
+        assert_eq!(reloaded.node_id, startup.node_id);
+        assert_eq!(reloaded.max_upload_size, startup.max_upload_size);
+        // Runtime settings take the reloaded value
+        assert_eq!(reloaded.max_emoji_size, 2048);
+        assert_eq!(
+            reloaded.enable_guild_discovery,
+            !startup.enable_guild_discovery
+        );
+    }
+}
@@ -0,0 +1,286 @@
+//! Interactions API
+//!
+//! Responses to slash command interactions. A bot receives an invocation as
+//! `command_invoked` on the bot gateway or as a `command.invoked` webhook and
+//! answers it once, within 5 minutes, either over the gateway or through
+//! `POST /api/interactions/{id}/response`. Ephemeral responses are delivered
+//! only to the invoking user; others are posted to the channel as the bot.
+
+use axum::extract::{Path, State};
+use axum::http::{HeaderMap, StatusCode};
+use axum::Json;
+use fred::interfaces::{KeysInterface, PubsubInterface};
+use serde::Deserialize;
+use thiserror::Error;
+use tracing::{error, info, instrument, warn};
+use uuid::Uuid;
+
+use crate::api::AppState;
+use crate::ws::bot_gateway::{authenticate_bot_token, extract_bot_token};
+
+/// Errors that can occur when responding to an interaction.
+#[derive(Error, Debug)]
+pub enum InteractionError {
+    /// Response content failed validation.
+    #[error("{0}")]
+    InvalidContent(String),
+    /// The interaction does not exist or expired.
+    #[error("Interaction not found or expired")]
+    NotFound,
+    /// The interaction was routed to another bot.
+    #[error("Interaction does not belong to this bot")]
+    Forbidden,
+    /// The interaction already has a response.
+    #[error("Response already provided for this interaction")]
+    AlreadyResponded,
+    /// Database error.
+    #[error("Database error: {0}")]
+    Database(#[from] sqlx::Error),
+    /// Redis error.
+    #[error("Redis error: {0}")]
+    Redis(#[from] fred::error::Error),
+}
+
+impl From<InteractionError> for (StatusCode, String) {
+    fn from(err: InteractionError) -> Self {
+        match err {
+            InteractionError::InvalidContent(_) => (StatusCode::BAD_REQUEST, err.to_string()),
+            InteractionError::NotFound => (StatusCode::NOT_FOUND, err.to_string()),
+            InteractionError::Forbidden => (StatusCode::FORBIDDEN, err.to_string()),
+            InteractionError::AlreadyResponded => (StatusCode::CONFLICT, err.to_string()),
+            InteractionError::Database(_) | InteractionError::Redis(_) => {
+                tracing::error!("Interaction response failed: {}", err);
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "Internal server error".to_string(),
+                )
+            }
+        }
+    }
+}
+
+/// Request body for responding to an interaction.
+#[derive(Debug, Deserialize, utoipa::ToSchema)]
+pub struct InteractionResponseRequest {
+    /// Response content.
+    pub content: String,
+    /// Whether the response is only visible to the invoking user.
+    #[serde(default)]
+    pub ephemeral: bool,
+}
+
+/// Respond to a slash command interaction (bot token auth).
+///
+/// For bots that receive invocations through webhooks; gateway bots can send
+/// `command_response` instead.
+#[utoipa::path(
+    post,
+    path = "/api/interactions/{interaction_id}/response",
+    tag = "commands",
+    params(
+        ("interaction_id" = Uuid, Path, description = "Interaction ID"),
+    ),
+    request_body = InteractionResponseRequest,
+    responses(
+        (status = 204, description = "Response delivered"),
+        (status = 400, description = "Invalid content"),
+        (status = 401, description = "Missing or invalid bot token"),
+        (status = 403, description = "Interaction belongs to another bot"),
+        (status = 404, description = "Interaction not found or expired"),
+        (status = 409, description = "Interaction already answered"),
+    ),
+    security(("bot_token" = [])),
+)]
+#[instrument(skip(state, headers, req))]
+pub async fn create_interaction_response(
+    State(state): State<AppState>,
+    Path(interaction_id): Path<Uuid>,
+    headers: HeaderMap,
+    Json(req): Json<InteractionResponseRequest>,
+) -> Result<StatusCode, (StatusCode, String)> {
+    let token = extract_bot_token(&headers).ok_or_else(|| {
+        (
+            StatusCode::UNAUTHORIZED,
+            "Missing or invalid Authorization header (expected: `Bot <token>`)".to_string(),
+        )
+    })?;
+    let (bot_user_id, _) = authenticate_bot_token(&state.db, &token).await?;
+
+    respond(
+        &state,
+        bot_user_id,
+        interaction_id,
+        req.content,
+        req.ephemeral,
+    )
+    .await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Deliver a bot's response to an interaction it owns.
+///
+/// The first response wins; later ones fail with
+/// [`InteractionError::AlreadyResponded`].
+pub async fn respond(
+    state: &AppState,
+    bot_user_id: Uuid,
+    interaction_id: Uuid,
+    content: String,
+    ephemeral: bool,
+) -> Result<(), InteractionError> {
+    if let Err(e) = crate::chat::messages::validate_message_content(&content) {
+        return Err(InteractionError::InvalidContent(
+            e.message.as_ref().map_or_else(
+                || "Invalid response content".to_string(),
+                ToString::to_string,
+            ),
+        ));
+    }
+
+    info!(
+        interaction_id = %interaction_id,
+        ephemeral = ephemeral,
+        "Bot responding to command"
+    );
+
+    let owner_key = format!("interaction:{interaction_id}:owner");
+    let expected_owner: Option<String> = state.redis.get(&owner_key).await?;
+    let expected_owner = expected_owner.ok_or(InteractionError::NotFound)?;
+    if expected_owner != bot_user_id.to_string() {
+        warn!(
+            interaction_id = %interaction_id,
+            bot_user_id = %bot_user_id,
+            expected_owner = %expected_owner,
+            "Bot attempted to respond to interaction it does not own"
+        );
+        return Err(InteractionError::Forbidden);
+    }
+
+    // Store command response in Redis with expiry (5 minutes); its presence
+    // also cancels the invoker's timeout notice
+    let response_key = format!("interaction:{interaction_id}:response");
+    let response_data = serde_json::json!({
+        "content": content,
+        "ephemeral": ephemeral,
+        "bot_user_id": bot_user_id,
+    });
+
+    let was_set: bool = state
+        .redis
+        .set(
+            &response_key,
+            response_data.to_string(),
+            Some(fred::types::Expiration::EX(300)),
+            Some(fred::types::SetOptions::NX),
+            false,
+        )
+        .await?;
+    if !was_set {
+        return Err(InteractionError::AlreadyResponded);
+    }
+
+    // Publish event to notify waiting clients
+    state
+        .redis
+        .publish::<(), _, _>(
+            format!("interaction:{interaction_id}"),
+            response_data.to_string(),
+        )
+        .await?;
+
+    // Deliver response to the invoking user/channel
+    let context_key = format!("interaction:{interaction_id}:context");
+    let context_raw: Option<String> = state.redis.get(&context_key).await?;
+    let Some(context_raw) = context_raw else {
+        warn!(interaction_id = %interaction_id, "Interaction context not found, skipping delivery");
+        return Ok(());
+    };
+    let Ok(context) = serde_json::from_str::<InteractionContext>(&context_raw) else {
+        error!(interaction_id = %interaction_id, "Failed to parse interaction context");
+        return Ok(());
+    };
+
+    let bot_user = crate::db::find_user_by_id(&state.db, bot_user_id).await?;
+    let bot_name = bot_user
+        .as_ref()
+        .map_or_else(|| "Bot".to_string(), |u| u.display_name.clone());
+
+    if ephemeral {
+        // Ephemeral: only deliver to the invoking user
+        crate::ws::broadcast_to_user(
+            &state.redis,
+            context.user_id,
+            &crate::ws::ServerEvent::CommandResponse {
+                interaction_id,
+                content,
+                command_name: context.command_name,
+                bot_name,
+                channel_id: context.channel_id,
+                ephemeral: true,
+            },
+        )
+        .await?;
+        return Ok(());
+    }
+
+    // Non-ephemeral: insert a real message and broadcast to channel
+    let message = crate::db::create_message(
+        &state.db,
+        context.channel_id,
+        bot_user_id,
+        &content,
+        false,
+        None,
+        None,
+    )
+    .await?;
+
+    let author_json = if let Some(ref u) = bot_user {
+        serde_json::json!({
+            "id": u.id,
+            "username": u.username,
+            "display_name": u.display_name,
+            "avatar_url": u.avatar_url,
+            "status": format!("{:?}", u.status).to_lowercase(),
+        })
+    } else {
+        serde_json::json!({
+            "id": bot_user_id,
+            "username": "bot",
+            "display_name": "Bot",
+            "avatar_url": null,
+            "status": "offline",
+        })
+    };
+
+    crate::ws::broadcast_to_channel(
+        &state.redis,
+        context.channel_id,
+        &crate::ws::ServerEvent::MessageNew {
+            channel_id: context.channel_id,
+            message: serde_json::json!({
+                "id": message.id,
+                "channel_id": context.channel_id,
+                "author": author_json,
+                "content": message.content,
+                "encrypted": message.encrypted,
+                "nonce": message.nonce,
+                "reply_to": message.reply_to,
+                "created_at": message.created_at.to_rfc3339(),
+            }),
+        },
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Invocation details stored at `interaction:{id}:context` when a command is
+/// routed to a bot.
+#[derive(Debug, Deserialize)]
+struct InteractionContext {
+    user_id: Uuid,
+    channel_id: Uuid,
+    command_name: String,
+}
@@ -4,8 +4,12 @@
 
 pub mod bots;
 pub mod commands;
+pub mod concurrency;
+pub mod drafts;
 pub mod favorites;
 pub mod global_search;
+pub mod instance_config;
+pub mod interactions;
 pub mod pins;
 pub mod preferences;
 pub mod reactions;
@@ -19,7 +23,7 @@ use axum::extract::{DefaultBodyLimit, FromRef, State};
 use axum::http::Request;
 use axum::middleware::{from_fn, from_fn_with_state, Next};
 use axum::response::Response;
-use axum::routing::{delete, get, post, put};
+use axum::routing::{delete, get, patch, post, put};
 use axum::{Json, Router};
 use axum_tracing_opentelemetry::middleware::{OtelAxumLayer, OtelInResponseLayer};
 use fred::interfaces::ClientLike;
@@ -32,18 +36,24 @@ use tower_http::trace::TraceLayer;
 use utoipa::OpenApi;
 use utoipa_swagger_ui::SwaggerUi;
 
+use self::instance_config::LiveConfig;
 use crate::auth::oidc::OidcProviderManager;
+use crate::chat::scanning::Scanner;
 use crate::chat::S3Client;
 use crate::config::Config;
 use crate::email::EmailService;
 use crate::moderation::filter_cache::FilterCache;
+use crate::observability::storage::postgres::PostgresStorage;
+use crate::observability::storage::TelemetryStorage;
 use crate::ratelimit::{
-    rate_limit_by_ip, rate_limit_by_user, with_category, RateLimitCategory, RateLimiter,
+    rate_limit_by_ip, rate_limit_by_route, rate_limit_by_user, with_category, RateLimitCategory,
+    RateLimiter,
 };
 use crate::voice::SfuServer;
+use crate::ws::fanout::EventFanout;
 use crate::{
-    admin, auth, chat, connectivity, crypto, discovery, governance, guild, moderation, pages,
-    social, voice, webhooks, workspaces, ws,
+    admin, auth, chat, connectivity, crypto, discovery, governance, guild, i18n, moderation, pages,
+    push, social, voice, webhooks, workspaces, ws,
 };
 
 /// Shared application state.
@@ -53,8 +63,8 @@ pub struct AppState {
     pub db: PgPool,
     /// Redis client
     pub redis: fred::clients::Client,
-    /// Server configuration
-    pub config: Arc<Config>,
+    /// Server configuration, reloadable at runtime (read it with [`Self::config`])
+    pub live_config: Arc<LiveConfig>,
     /// S3 client for file storage (optional)
     pub s3: Option<S3Client>,
     /// SFU server for voice channels
@@ -67,6 +77,12 @@ pub struct AppState {
     pub oidc_manager: Option<Arc<OidcProviderManager>>,
     /// Per-guild content filter engine cache
     pub filter_cache: Arc<FilterCache>,
+    /// Redis pub/sub fan-out to this node's WebSocket connections
+    pub event_fanout: Arc<EventFanout>,
+    /// Native telemetry backend (`PostgreSQL` unless `TELEMETRY_STORAGE` selects another)
+    pub telemetry: Arc<dyn TelemetryStorage>,
+    /// Attachment malware scanner (optional, requires `CLAMAV_ADDRESS`)
+    pub scanner: Option<Arc<dyn Scanner>>,
 }
 
 impl FromRef<AppState> for PgPool {
@@ -91,19 +107,83 @@ impl AppState {
     /// Create new application state.
     #[must_use]
     pub fn new(cfg: AppStateConfig) -> Self {
+        let event_fanout = Arc::new(EventFanout::new(cfg.redis.clone()));
+        let telemetry = Arc::new(PostgresStorage::new(cfg.db.clone()));
         Self {
             db: cfg.db,
             redis: cfg.redis,
-            config: Arc::new(cfg.config),
+            live_config: Arc::new(LiveConfig::new(cfg.config)),
             s3: cfg.s3,
             sfu: Arc::new(cfg.sfu),
             rate_limiter: cfg.rate_limiter,
             email: cfg.email.map(Arc::new),
             oidc_manager: cfg.oidc_manager.map(Arc::new),
             filter_cache: Arc::new(FilterCache::new()),
+            event_fanout,
+            telemetry,
+            scanner: None,
         }
     }
 
+    /// Replace the telemetry backend (defaults to the main database).
+    #[must_use]
+    pub fn with_telemetry_storage(mut self, telemetry: Arc<dyn TelemetryStorage>) -> Self {
+        self.telemetry = telemetry;
+        self
+    }
+
+    /// Scan new attachments with `scanner` before other users can see them.
+    #[must_use]
+    pub fn with_attachment_scanner(mut self, scanner: Option<Arc<dyn Scanner>>) -> Self {
+        self.scanner = scanner;
+        self
+    }
+
+    /// Snapshot of the current server configuration, runtime overrides
+    /// included.
+    ///
+    /// Take a new snapshot per request rather than keeping one, so reloads
+    /// apply.
+    #[must_use]
+    pub fn config(&self) -> Arc<Config> {
+        self.live_config.current()
+    }
+
+    /// Re-read the admin overrides and pass the result to the subsystems that
+    /// keep their own copy of the configuration.
+    pub async fn refresh_config(&self) {
+        let config = self.live_config.refresh(&self.db).await;
+        self.sfu.set_config(config);
+    }
+
+    /// Reload the environment (including the `.env` file) and the admin
+    /// overrides.
+    ///
+    /// Returns the changed settings that only apply after a restart.
+    ///
+    /// # Errors
+    /// Returns an error, and keeps the current configuration, if the new
+    /// environment is invalid.
+    pub async fn reload_config(&self) -> anyhow::Result<Vec<&'static str>> {
+        let vars = crate::config::EnvVars::reload()?;
+        let config = Config::from_vars(&vars)?;
+        let restart_required = self.live_config.reload(&self.db, config).await;
+        self.sfu.set_config(self.config());
+        if let Some(rate_limiter) = &self.rate_limiter {
+            rate_limiter.set_config(crate::ratelimit::RateLimitConfig::from_vars(&vars));
+        }
+
+        if restart_required.is_empty() {
+            tracing::info!("Configuration reloaded");
+        } else {
+            tracing::warn!(
+                settings = ?restart_required,
+                "Configuration reloaded; some changed settings apply only after a restart"
+            );
+        }
+        Ok(restart_required)
+    }
+
     /// Check if S3 storage is configured and available.
     #[must_use]
     pub const fn has_s3(&self) -> bool {
@@ -113,6 +193,9 @@ impl AppState {
 
 /// Create the main application router.
 pub fn create_router(state: AppState) -> Router {
+    // Layers are built once, so they use the startup configuration
+    let config = state.live_config.startup();
+
     // Configure CORS based on allowed origins
     let cors = {
         use axum::http::{header, HeaderName, Method};
@@ -131,7 +214,7 @@ pub fn create_router(state: AppState) -> Router {
             HeaderName::from_static("x-request-id"),
         ];
 
-        if state.config.cors_allowed_origins.iter().any(|o| o == "*") {
+        if config.cors_allowed_origins.iter().any(|o| o == "*") {
             // Wildcard `*` is incompatible with `allow_credentials(true)` per the
             // CORS spec, so mirror the request Origin header instead.
             tracing::warn!(
@@ -144,8 +227,7 @@ pub fn create_router(state: AppState) -> Router {
                 .allow_credentials(true)
         } else {
             // Production mode: restrict to configured origins
-            let origins: Vec<_> = state
-                .config
+            let origins: Vec<_> = config
                 .cors_allowed_origins
                 .iter()
                 .filter_map(|o| {
@@ -172,8 +254,9 @@ pub fn create_router(state: AppState) -> Router {
         }
     };
 
-    // Get max upload size from config (default 50MB)
-    let max_upload_size = state.config.max_upload_size;
+    // Get max upload size from config (default 50MB), raised to the highest
+    // supporter perk tier; handlers enforce the per-guild limit
+    let max_upload_size = crate::guild::perks::max_upload_size_ceiling(&config);
 
     // Social routes with Social rate limit category (20 req/60s)
     let social_routes = social::router()
@@ -213,8 +296,41 @@ pub fn create_router(state: AppState) -> Router {
             "/api/me/delete-account/cancel",
             post(governance::handlers::cancel_deletion),
         )
+        .route("/api/me/username", patch(auth::username::change_username))
+        .route(
+            "/api/me/profile",
+            patch(auth::profile::update_profile_customization),
+        )
+        .route(
+            "/api/me/profile/banner",
+            post(auth::profile::upload_banner)
+                .delete(auth::profile::delete_banner)
+                .layer(DefaultBodyLimit::max(config.max_avatar_size)),
+        )
+        .route(
+            "/api/me/auth-methods",
+            get(auth::auth_methods::list_auth_methods),
+        )
+        .route(
+            "/api/me/auth-methods/link",
+            post(auth::auth_methods::link_auth_method),
+        )
+        .route(
+            "/api/me/auth-methods/reauth",
+            post(auth::auth_methods::start_oidc_reauth),
+        )
+        .route(
+            "/api/me/auth-methods/local",
+            delete(auth::auth_methods::unlink_password),
+        )
+        .route(
+            "/api/me/auth-methods/{id}",
+            delete(auth::auth_methods::unlink_identity),
+        )
         .nest("/api/me/connection", connectivity::router())
         .nest("/api/me/preferences", preferences::router())
+        .nest("/api/me/drafts", drafts::router())
+        .nest("/api/me/push-devices", push::router())
         .route("/api/me/pins", get(pins::list_pins).post(pins::create_pin))
         .route("/api/me/pins/reorder", put(pins::reorder_pins))
         .route(
@@ -337,6 +453,7 @@ pub fn create_router(state: AppState) -> Router {
         .nest("/api", social_routes)
         .route("/api/reports", post(moderation::handlers::create_report))
         .nest("/api/admin", admin_routes)
+        .layer(from_fn(consumer_usage_counter))
         .layer(from_fn_with_state(state.clone(), auth::require_auth));
 
     let app_routes = Router::new()
@@ -347,6 +464,20 @@ pub fn create_router(state: AppState) -> Router {
                 .layer(from_fn_with_state(state.clone(), rate_limit_by_ip))
                 .layer(from_fn(with_category(RateLimitCategory::Search))),
         )
+        // Public invite preview (no auth required, IP rate limited)
+        .nest(
+            "/api/invites",
+            guild::invite_public_router()
+                .layer(from_fn_with_state(state.clone(), rate_limit_by_ip))
+                .layer(from_fn(with_category(RateLimitCategory::Read))),
+        )
+        // Billing entitlement webhook (HMAC-signed, IP rate limited)
+        .nest(
+            "/api/admin",
+            admin::public_router()
+                .layer(from_fn_with_state(state.clone(), rate_limit_by_ip))
+                .layer(from_fn(with_category(RateLimitCategory::Write))),
+        )
         // Public server settings
         .route("/api/settings", get(settings::get_server_settings))
         .route(
@@ -368,6 +499,21 @@ pub fn create_router(state: AppState) -> Router {
         .merge(protected_routes)
         // Public message routes (download handles its own auth via query param)
         .nest("/api/messages", chat::messages_public_router())
+        // Public channel feeds (feed token auth, IP rate limited)
+        .nest(
+            "/api/channels",
+            chat::channels_public_router()
+                .layer(from_fn_with_state(state.clone(), rate_limit_by_ip))
+                .layer(from_fn(with_category(RateLimitCategory::Read))),
+        )
+        // Incoming webhook execution (token in URL, IP rate limited; each
+        // webhook is also rate limited on its own)
+        .route(
+            "/api/channels/{id}/webhooks/{webhook_id}/{token}",
+            post(chat::incoming_webhooks::execute_webhook)
+                .route_layer(from_fn_with_state(state.clone(), rate_limit_by_ip))
+                .route_layer(from_fn(with_category(RateLimitCategory::Write))),
+        )
         // WebSocket
         .route("/ws", get(ws::handler))
         // Bot Gateway WebSocket (uses bot token auth)
@@ -375,17 +521,40 @@ pub fn create_router(state: AppState) -> Router {
             "/api/gateway/bot",
             get(ws::bot_gateway::bot_gateway_handler),
         )
+        // Interaction responses (bot token auth, IP rate limited)
+        .route(
+            "/api/interactions/{interaction_id}/response",
+            post(interactions::create_interaction_response)
+                .route_layer(from_fn_with_state(state.clone(), rate_limit_by_ip))
+                .route_layer(from_fn(with_category(RateLimitCategory::Write))),
+        )
         // API documentation
-        .merge(api_docs(state.config.enable_api_docs))
+        .merge(api_docs(config.enable_api_docs))
         .layer(OtelInResponseLayer)
         .layer(OtelAxumLayer::default());
 
+    // Prometheus scrape endpoint; only on this listener when protected by a token
+    let metrics_routes = match config
+        .observability
+        .prometheus
+        .as_ref()
+        .and_then(|p| p.bearer_token.clone())
+    {
+        Some(bearer_token) => crate::observability::metrics::prometheus_router(Some(bearer_token)),
+        None => Router::new(),
+    };
+
     Router::new()
         // Health check
         .route("/health", get(health_check))
+        .merge(metrics_routes)
         .merge(app_routes)
         // Middleware
+        // Global per-route token buckets (login, register, message create, uploads)
+        .layer(from_fn_with_state(state.clone(), rate_limit_by_route))
         .layer(from_fn(security_headers))
+        // Render error envelopes in the request's Accept-Language
+        .layer(from_fn(i18n::localize_errors))
         .layer(from_fn(http_error_counter))
         .layer(TraceLayer::new_for_http())
         .layer(CompressionLayer::new())
@@ -455,6 +624,16 @@ async fn http_error_counter(
     response
 }
 
+/// Middleware that counts authenticated requests per user and guild.
+///
+/// Runs inside `require_auth`, so the caller is always known.
+async fn consumer_usage_counter(request: Request<axum::body::Body>, next: Next) -> Response {
+    if let Some(auth_user) = request.extensions().get::<auth::AuthUser>() {
+        crate::observability::consumers::record_http_request(auth_user.id, request.uri().path());
+    }
+    next.run(request).await
+}
+
 /// Middleware that adds security headers to all responses.
 async fn security_headers(request: Request<axum::body::Body>, next: Next) -> Response {
     let mut response = next.run(request).await;
@@ -476,14 +655,14 @@ async fn security_headers(request: Request<axum::body::Body>, next: Next) -> Res
 
 /// API documentation routes.
 ///
-/// Serves Swagger UI at `/api/docs` when enabled via `ENABLE_API_DOCS` env var.
-/// Defaults to enabled in debug builds, disabled in release builds.
+/// Serves the `OpenAPI` spec at `/api/openapi.json` and Swagger UI at
+/// `/api/docs` when enabled via `ENABLE_API_DOCS` env var. Defaults to enabled
+/// in debug builds, disabled in release builds.
 fn api_docs(enable: bool) -> Router<AppState> {
     if !enable {
         return Router::new();
     }
     Router::new().merge(
-        SwaggerUi::new("/api/docs")
-            .url("/api/docs/openapi.json", crate::openapi::ApiDoc::openapi()),
+        SwaggerUi::new("/api/docs").url("/api/openapi.json", crate::openapi::ApiDoc::openapi()),
     )
 }
@@ -13,6 +13,7 @@ use uuid::Uuid;
 
 use crate::api::AppState;
 use crate::auth::AuthUser;
+use crate::chat::highlights::MAX_HIGHLIGHT_KEYWORDS;
 use crate::ws::{broadcast_to_user, ServerEvent};
 
 // ============================================================================
@@ -106,6 +107,10 @@ const MAX_KEYWORD_LEN: usize = 30;
 /// Minimum length for emergency keywords (prevents overly broad matches).
 const MIN_KEYWORD_LEN: usize = 3;
 const MAX_MODE_NAME_LEN: usize = 30;
+/// Highest per-user voice gain (200%).
+const MAX_VOICE_USER_GAIN: f64 = 2.0;
+/// Maximum number of users with a voice gain set.
+const MAX_VOICE_USER_GAINS: usize = 500;
 
 /// Counts Unicode scalar values (code points), matching `Array.from(str).length` in JavaScript.
 fn unicode_len(s: &str) -> usize {
@@ -130,9 +135,56 @@ fn validate_preferences(prefs: &serde_json::Value) -> Result<(), PreferencesErro
         validate_focus_preferences(focus)?;
     }
 
+    // Highlight keywords (min 3 chars, max 30 chars)
+    validate_keyword_array(
+        prefs,
+        "highlight_keywords",
+        MAX_HIGHLIGHT_KEYWORDS,
+        "highlight_keywords",
+    )?;
+
+    if let Some(gains) = prefs.get("voice_user_gains") {
+        validate_voice_user_gains(gains)?;
+    }
+
     Ok(())
 }
 
+/// Validate `voice_user_gains`: an object of user ID to gain.
+fn validate_voice_user_gains(gains: &serde_json::Value) -> Result<(), PreferencesError> {
+    let gains = gains
+        .as_object()
+        .ok_or_else(|| PreferencesError::Validation("voice_user_gains must be an object".into()))?;
+
+    if gains.len() > MAX_VOICE_USER_GAINS {
+        return Err(PreferencesError::Validation(format!(
+            "voice_user_gains too many entries ({}, max {MAX_VOICE_USER_GAINS})",
+            gains.len()
+        )));
+    }
+
+    for (user_id, gain) in gains {
+        if user_id.parse::<Uuid>().is_err() {
+            return Err(PreferencesError::Validation(format!(
+                "voice_user_gains key is not a valid UUID: {user_id}"
+            )));
+        }
+        validate_voice_user_gain(gain.as_f64().unwrap_or(f64::NAN))?;
+    }
+
+    Ok(())
+}
+
+fn validate_voice_user_gain(gain: f64) -> Result<(), PreferencesError> {
+    if (0.0..=MAX_VOICE_USER_GAIN).contains(&gain) {
+        Ok(())
+    } else {
+        Err(PreferencesError::Validation(format!(
+            "Voice gain must be between 0 and {MAX_VOICE_USER_GAIN}"
+        )))
+    }
+}
+
 fn validate_focus_preferences(focus: &serde_json::Value) -> Result<(), PreferencesError> {
     // modes array
     if let Some(modes) = focus.get("modes") {
@@ -312,6 +364,79 @@ fn validate_keyword_array(
     Ok(())
 }
 
+// ============================================================================
+// Voice Gain
+// ============================================================================
+
+/// Set how loud `target_user` plays for `user_id` in voice channels.
+///
+/// Stored in the `voice_user_gains` preference, which clients apply to the
+/// target's audio; a gain of 1.0 removes the entry. Every device of the user
+/// gets the updated preferences.
+pub async fn set_voice_user_gain(
+    pool: &sqlx::PgPool,
+    redis: &fred::clients::Client,
+    user_id: Uuid,
+    target_user: Uuid,
+    gain: f32,
+) -> Result<(), PreferencesError> {
+    // Whole percents, so the stored value does not carry f32 noise
+    let gain = (f64::from(gain) * 100.0).round() / 100.0;
+    validate_voice_user_gain(gain)?;
+    if target_user == user_id {
+        return Err(PreferencesError::Validation(
+            "Cannot set a voice gain for yourself".into(),
+        ));
+    }
+
+    let entry = if (gain - 1.0).abs() < f64::EPSILON {
+        serde_json::json!({})
+    } else {
+        serde_json::json!({ target_user.to_string(): gain })
+    };
+
+    let row = sqlx::query_as::<_, UserPreferencesRow>(
+        r"
+        INSERT INTO user_preferences (user_id, preferences, updated_at)
+        VALUES ($1, jsonb_build_object('voice_user_gains', $2::jsonb), NOW())
+        ON CONFLICT (user_id) DO UPDATE
+        SET preferences = jsonb_set(
+                user_preferences.preferences,
+                '{voice_user_gains}',
+                (COALESCE(user_preferences.preferences->'voice_user_gains', '{}') - $3) || $2
+            ),
+            updated_at = NOW()
+        WHERE $2 = '{}'::jsonb
+           OR user_preferences.preferences->'voice_user_gains' ? $3
+           OR (SELECT COUNT(*) FROM jsonb_object_keys(
+                   COALESCE(user_preferences.preferences->'voice_user_gains', '{}')
+               )) < $4
+        RETURNING user_id, preferences, updated_at
+        ",
+    )
+    .bind(user_id)
+    .bind(&entry)
+    .bind(target_user.to_string())
+    .bind(MAX_VOICE_USER_GAINS as i64)
+    .fetch_optional(pool)
+    .await?
+    .ok_or_else(|| {
+        PreferencesError::Validation(format!(
+            "Voice gains can be set for at most {MAX_VOICE_USER_GAINS} users"
+        ))
+    })?;
+
+    let event = ServerEvent::PreferencesUpdated {
+        preferences: row.preferences,
+        updated_at: row.updated_at,
+    };
+    if let Err(e) = broadcast_to_user(redis, user_id, &event).await {
+        tracing::warn!("Failed to broadcast preferences update: {}", e);
+    }
+
+    Ok(())
+}
+
 // ============================================================================
 // Handlers
 // ============================================================================
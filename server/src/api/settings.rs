@@ -20,7 +20,9 @@ pub struct ServerSettingsResponse {
     pub oidc_providers: Vec<PublicOidcProvider>,
     /// Which auth methods are enabled.
     pub auth_methods: AuthMethodsConfig,
-    /// Registration policy: "open", "`invite_only`", or "closed".
+    /// Whether passkeys can be registered and used to sign in.
+    pub passkeys_enabled: bool,
+    /// Registration policy: "open", "`invite_only`", "approval", or "closed".
     pub registration_policy: String,
 }
 
@@ -57,10 +59,11 @@ pub async fn get_server_settings(State(state): State<AppState>) -> Json<ServerSe
         .unwrap_or_else(|| "open".to_string());
 
     Json(ServerSettingsResponse {
-        require_e2ee_setup: state.config.require_e2ee_setup,
+        require_e2ee_setup: state.config().require_e2ee_setup,
         oidc_enabled: auth_methods.oidc && !oidc_providers.is_empty(),
         oidc_providers,
         auth_methods,
+        passkeys_enabled: state.config().has_webauthn(),
         registration_policy,
     })
 }
@@ -96,19 +99,20 @@ pub struct InstanceLimitsResponse {
     ),
 )]
 pub async fn get_instance_limits(State(state): State<AppState>) -> Json<InstanceLimitsResponse> {
+    let effective = state.config();
     Json(InstanceLimitsResponse {
-        max_guilds_per_user: state.config.max_guilds_per_user,
-        max_members_per_guild: state.config.max_members_per_guild,
-        max_channels_per_guild: state.config.max_channels_per_guild,
-        max_roles_per_guild: state.config.max_roles_per_guild,
-        max_emojis_per_guild: state.config.max_emojis_per_guild,
-        max_bots_per_guild: state.config.max_bots_per_guild,
-        max_webhooks_per_app: state.config.max_webhooks_per_app,
-        max_workspaces_per_user: state.config.max_workspaces_per_user,
-        max_entries_per_workspace: state.config.max_entries_per_workspace,
-        max_pages_per_guild: state.config.max_pages_per_guild,
-        max_revisions_per_page: state.config.max_revisions_per_page,
-        max_upload_size: state.config.max_upload_size,
+        max_guilds_per_user: state.config().max_guilds_per_user,
+        max_members_per_guild: state.config().max_members_per_guild,
+        max_channels_per_guild: state.config().max_channels_per_guild,
+        max_roles_per_guild: state.config().max_roles_per_guild,
+        max_emojis_per_guild: state.config().max_emojis_per_guild,
+        max_bots_per_guild: state.config().max_bots_per_guild,
+        max_webhooks_per_app: state.config().max_webhooks_per_app,
+        max_workspaces_per_user: state.config().max_workspaces_per_user,
+        max_entries_per_workspace: state.config().max_entries_per_workspace,
+        max_pages_per_guild: state.config().max_pages_per_guild,
+        max_revisions_per_page: state.config().max_revisions_per_page,
+        max_upload_size: effective.max_upload_size,
     })
 }
 
@@ -138,9 +142,10 @@ pub struct UploadLimitsResponse {
     ),
 )]
 pub async fn get_upload_limits(State(state): State<AppState>) -> Json<UploadLimitsResponse> {
+    let config = state.config();
     Json(UploadLimitsResponse {
-        max_avatar_size: state.config.max_avatar_size,
-        max_emoji_size: state.config.max_emoji_size,
-        max_upload_size: state.config.max_upload_size,
+        max_avatar_size: config.max_avatar_size,
+        max_emoji_size: config.max_emoji_size,
+        max_upload_size: config.max_upload_size,
     })
 }
@@ -2,10 +2,12 @@
 //!
 //! Endpoints for the first-time setup wizard that configures the server.
 
-use axum::extract::State;
-use axum::http::StatusCode;
+use std::net::SocketAddr;
+
+use axum::extract::{ConnectInfo, State};
+use axum::http::{HeaderMap, StatusCode};
 use axum::response::{IntoResponse, Response};
-use axum::Json;
+use axum::{Extension, Json};
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 use validator::Validate;
@@ -94,7 +96,7 @@ pub struct CompleteSetupRequest {
 }
 
 fn validate_registration_policy(policy: &str) -> Result<(), validator::ValidationError> {
-    if matches!(policy, "open" | "invite_only" | "closed") {
+    if matches!(policy, "open" | "invite_only" | "approval" | "closed") {
         Ok(())
     } else {
         Err(validator::ValidationError::new("invalid_policy"))
@@ -278,9 +280,11 @@ pub async fn get_config(
     ),
     security(("bearer_auth" = [])),
 )]
-#[tracing::instrument(skip(state))]
+#[tracing::instrument(skip(state, connect_info, headers))]
 pub async fn complete(
     State(state): State<AppState>,
+    connect_info: Option<Extension<ConnectInfo<SocketAddr>>>,
+    headers: HeaderMap,
     auth: AuthUser,
     Json(body): Json<CompleteSetupRequest>,
 ) -> Result<StatusCode, SetupError> {
@@ -431,6 +435,21 @@ pub async fn complete(
         registration_policy = %body.registration_policy,
         "Server setup completed successfully"
     );
+    crate::auth::audit::record(
+        &state.db,
+        "setup.completed",
+        Some(auth.id),
+        None,
+        Some(serde_json::json!({
+            "server_name": body.server_name,
+            "registration_policy": body.registration_policy,
+            "terms_url": body.terms_url,
+            "privacy_url": body.privacy_url,
+        })),
+        connect_info.map(|Extension(ConnectInfo(addr))| addr.ip()),
+        &headers,
+    )
+    .await;
 
     Ok(StatusCode::NO_CONTENT)
 }
@@ -96,7 +96,7 @@ pub async fn mark_all_read(
         (StatusCode::INTERNAL_SERVER_ERROR, "Failed to mark guild channels as read".to_string())
     })?;
 
-    // 2. Mark all DM channels as read
+    // 2. Mark all DM channels as read (except those kept unread)
     let dm_rows: Vec<(Uuid,)> = sqlx::query_as(
         r"INSERT INTO dm_read_state (user_id, channel_id, last_read_at, last_read_message_id)
           SELECT $1, dp.channel_id, $2, (
@@ -106,7 +106,7 @@ pub async fn mark_all_read(
           )
           FROM dm_participants dp
           INNER JOIN channels c ON c.id = dp.channel_id
-          WHERE dp.user_id = $1 AND c.channel_type = 'dm'
+          WHERE dp.user_id = $1 AND c.channel_type = 'dm' AND NOT dp.unread_kept
           ON CONFLICT (user_id, channel_id)
           DO UPDATE SET last_read_at = EXCLUDED.last_read_at, last_read_message_id = EXCLUDED.last_read_message_id
           RETURNING channel_id",
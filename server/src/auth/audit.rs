@@ -0,0 +1,43 @@
+//! Security Events
+//!
+//! Sign-ins, failed sign-ins, MFA and password changes are written to the
+//! system audit log as `auth.*` actions, next to the `admin.*` actions, so
+//! system admins can review them in `GET /api/admin/audit-log`.
+
+use std::net::IpAddr;
+
+use axum::http::HeaderMap;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use super::handlers::extract_user_agent;
+use crate::permissions::queries::{write_audit_event, AuditEvent};
+
+/// Record a security event in the system audit log.
+///
+/// `user_id` is the account the event is about. Never fails the calling
+/// request: a failed insert is logged and dropped.
+pub async fn record(
+    pool: &PgPool,
+    action: &str,
+    actor_id: Option<Uuid>,
+    user_id: Option<Uuid>,
+    details: Option<serde_json::Value>,
+    ip: Option<IpAddr>,
+    headers: &HeaderMap,
+) {
+    let ip_address = ip.map(|ip| ip.to_string());
+    let user_agent = extract_user_agent(headers);
+    let event = AuditEvent {
+        actor_id,
+        action,
+        target_type: user_id.map(|_| "user"),
+        target_id: user_id,
+        details,
+        ip_address: ip_address.as_deref(),
+        user_agent: user_agent.as_deref(),
+    };
+    if let Err(e) = write_audit_event(pool, event).await {
+        tracing::warn!(error = %e, action, "Failed to write security event to audit log");
+    }
+}
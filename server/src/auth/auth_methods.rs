@@ -0,0 +1,785 @@
+//! Linked Sign-in Methods
+//!
+//! An account can sign in with a password, with external identities (OIDC
+//! today, LDAP later), or with several of them at once:
+//!
+//! - the password method is linked while `users.password_hash` is set;
+//! - external identities live in `user_auth_methods`, one per provider;
+//! - passkeys live in `webauthn_credentials` (see [`super::webauthn`]).
+//!
+//! Linking and unlinking require re-authentication: the current password when
+//! one is linked, otherwise an MFA code when MFA is enabled. Accounts with neither
+//! sign in again, either through a linked provider (`POST
+//! /api/me/auth-methods/reauth` forces a fresh provider login and hands out a
+//! single-use re-auth token) or with a passkey assertion. The session alone is
+//! never enough. An OIDC link goes through the provider: the server returns an
+//! authorization URL and the callback links the identity instead of signing in.
+//!
+//! Unlinking is refused when it would leave the account without a method the
+//! server currently accepts for sign-in.
+
+use axum::extract::{Path, State};
+use axum::response::{Html, IntoResponse, Response};
+use axum::Json;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use chrono::{DateTime, Utc};
+use fred::prelude::*;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use uuid::Uuid;
+use validator::Validate;
+
+use super::error::{AuthError, AuthResult};
+use super::handlers::{
+    check_totp_code, oidc_server_callback_url, start_oidc_flow, OidcFlowPurpose,
+};
+use super::hash_token;
+use super::middleware::AuthUser;
+use super::oidc::FRESH_LOGIN_MAX_AGE;
+use super::password::{hash_password, verify_password};
+use super::webauthn::{has_passkey, verify_assertion, PasskeyAssertion};
+use crate::api::AppState;
+use crate::db::{
+    find_user_by_external_id, find_user_by_id, get_auth_methods_allowed,
+    invalidate_user_reset_tokens, list_user_auth_methods, AuthMethod, AuthMethodsConfig, User,
+    UserAuthMethod,
+};
+
+/// How long a re-auth token from a fresh provider sign-in stays valid.
+const REAUTH_TOKEN_TTL_SECS: i64 = 300;
+
+/// Sign-in methods linked to the current account.
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct AuthMethodsResponse {
+    /// Whether a password is linked.
+    pub has_password: bool,
+    /// Linked external identities, oldest first.
+    pub identities: Vec<UserAuthMethod>,
+}
+
+/// Proof that the caller still controls the account.
+///
+/// Accounts with a password give it; password-less accounts with MFA give a
+/// code. Accounts with neither sign in again: with a `reauth_token` from
+/// `POST /api/me/auth-methods/reauth`, or with a passkey assertion started
+/// through `/auth/webauthn/login/start`.
+#[derive(Default, Deserialize, utoipa::ToSchema)]
+pub struct Reauthentication {
+    /// Current password (required when the account has one).
+    pub current_password: Option<String>,
+    /// MFA code (required for password-less accounts with MFA enabled).
+    pub mfa_code: Option<String>,
+    /// Token from a fresh provider sign-in (accounts without password or MFA).
+    pub reauth_token: Option<String>,
+    /// Passkey assertion (accounts without password or MFA).
+    pub passkey: Option<PasskeyAssertion>,
+}
+
+impl std::fmt::Debug for Reauthentication {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Reauthentication")
+            .field("current_password", &"[REDACTED]")
+            .field("mfa_code", &"[REDACTED]")
+            .field("reauth_token", &"[REDACTED]")
+            .field("passkey", &self.passkey.as_ref().map(|p| p.challenge_id))
+            .finish()
+    }
+}
+
+/// Link a sign-in method request.
+#[derive(Deserialize, Validate, utoipa::ToSchema)]
+pub struct LinkAuthMethodRequest {
+    /// Method to link (`local` or `oidc`).
+    pub method: AuthMethod,
+    /// Re-authentication.
+    #[serde(flatten)]
+    pub reauth: Reauthentication,
+    /// Password to add (8-128 characters, for `local`).
+    #[validate(length(min = 8, max = 128))]
+    pub new_password: Option<String>,
+    /// Provider slug (for `oidc`).
+    pub provider: Option<String>,
+}
+
+impl std::fmt::Debug for LinkAuthMethodRequest {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LinkAuthMethodRequest")
+            .field("method", &self.method)
+            .field("reauth", &self.reauth)
+            .field("new_password", &"[REDACTED]")
+            .field("provider", &self.provider)
+            .finish()
+    }
+}
+
+/// Unlink a sign-in method request: re-authentication as for linking.
+#[derive(Debug, Default, Deserialize, utoipa::ToSchema)]
+pub struct UnlinkAuthMethodRequest {
+    /// Re-authentication.
+    #[serde(flatten)]
+    pub reauth: Reauthentication,
+}
+
+/// Start a re-authentication through a provider request.
+#[derive(Debug, Deserialize, utoipa::ToSchema)]
+pub struct StartReauthRequest {
+    /// Slug of a provider with an identity linked to the account.
+    pub provider: String,
+}
+
+/// Start a re-authentication through a provider response.
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct StartReauthResponse {
+    /// Provider authorization URL to open. Once the provider redirects back,
+    /// the popup posts an `oidc-reauth` message carrying the `reauth_token`.
+    pub authorize_url: String,
+}
+
+/// Link a sign-in method response.
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct LinkAuthMethodResponse {
+    /// Provider authorization URL to open (OIDC). The identity is linked
+    /// once the provider redirects back to the server.
+    pub authorize_url: Option<String>,
+    /// Sign-in methods after linking (password).
+    pub methods: Option<AuthMethodsResponse>,
+}
+
+/// Whether an account keeps a method the server currently accepts.
+///
+/// `has_passkey` should only be set while passkeys are configured.
+pub(super) fn has_usable_method(
+    allowed: &AuthMethodsConfig,
+    has_password: bool,
+    identities: &[UserAuthMethod],
+    has_passkey: bool,
+) -> bool {
+    (has_password && allowed.local)
+        || (allowed.oidc && identities.iter().any(|m| m.method == AuthMethod::Oidc))
+        || has_passkey
+}
+
+/// Confirm the caller still controls the account before changing its
+/// sign-in methods.
+///
+/// A session alone never passes: accounts without password or MFA need a
+/// fresh provider sign-in or a passkey assertion.
+pub(super) async fn reauthenticate(
+    state: &AppState,
+    user: &User,
+    proof: &Reauthentication,
+) -> AuthResult<()> {
+    if let Some(password_hash) = user.password_hash.as_deref() {
+        let password = proof
+            .current_password
+            .as_deref()
+            .ok_or_else(|| AuthError::Validation("Current password is required".to_string()))?;
+        let valid =
+            verify_password(password, password_hash).map_err(|_| AuthError::PasswordHash)?;
+        if !valid {
+            return Err(AuthError::InvalidCredentials);
+        }
+    } else if let Some(encrypted_secret) = user.mfa_secret.as_deref() {
+        let code = proof.mfa_code.as_deref().ok_or(AuthError::MfaRequired)?;
+        if !check_totp_code(state, encrypted_secret, &user.username, code)? {
+            return Err(AuthError::InvalidMfaCode);
+        }
+    } else if let Some(token) = proof.reauth_token.as_deref() {
+        // Single use: taken even when it belongs to someone else
+        let owner: Option<String> =
+            state
+                .redis
+                .getdel(reauth_token_key(token))
+                .await
+                .map_err(|e| {
+                    tracing::error!(error = %e, "Failed to read re-auth token from Redis");
+                    AuthError::Internal("Failed to read re-auth token".to_string())
+                })?;
+        if owner != Some(user.id.to_string()) {
+            return Err(AuthError::InvalidCredentials);
+        }
+    } else if let Some(assertion) = proof.passkey.as_ref() {
+        verify_assertion(state, assertion, Some(user.id)).await?;
+    } else {
+        return Err(AuthError::Validation(
+            "Sign in again with a linked provider or a passkey to confirm this change".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+fn reauth_token_key(token: &str) -> String {
+    format!("auth:reauth:{}", hash_token(token))
+}
+
+/// Finish a re-authentication flow: the provider must have just signed in
+/// an identity linked to the account. Returns a single-use re-auth token.
+pub(super) async fn finish_oidc_reauth(
+    state: &AppState,
+    user_id: Uuid,
+    external_id: &str,
+    auth_time: Option<DateTime<Utc>>,
+) -> AuthResult<String> {
+    let owner = find_user_by_external_id(&state.db, external_id).await?;
+    if owner.map(|user| user.id) != Some(user_id) {
+        return Err(AuthError::Validation(
+            "Sign in with an identity linked to this account".to_string(),
+        ));
+    }
+    // `max_age` asks the provider for a fresh login, but only `auth_time`
+    // proves it happened; a timestamp slightly ahead of ours is fresh
+    let fresh = auth_time.is_some_and(|signed_in_at| {
+        Utc::now()
+            .signed_duration_since(signed_in_at)
+            .to_std()
+            .map_or(true, |age| age <= FRESH_LOGIN_MAX_AGE)
+    });
+    if !fresh {
+        return Err(AuthError::Validation(
+            "The provider did not confirm a fresh sign-in".to_string(),
+        ));
+    }
+
+    let mut token_bytes = [0u8; 32];
+    rand::thread_rng().fill_bytes(&mut token_bytes);
+    let token = URL_SAFE_NO_PAD.encode(token_bytes);
+    state
+        .redis
+        .set::<(), _, _>(
+            reauth_token_key(&token),
+            user_id.to_string(),
+            Some(Expiration::EX(REAUTH_TOKEN_TTL_SECS)),
+            None,
+            false,
+        )
+        .await
+        .map_err(|e| {
+            tracing::error!(error = %e, "Failed to store re-auth token in Redis");
+            AuthError::Internal("Failed to store re-auth token".to_string())
+        })?;
+
+    tracing::info!(user_id = %user_id, "Re-authenticated through OIDC provider");
+    Ok(token)
+}
+
+async fn load_methods(pool: &PgPool, user: &User) -> AuthResult<AuthMethodsResponse> {
+    Ok(AuthMethodsResponse {
+        has_password: user.password_hash.is_some(),
+        identities: list_user_auth_methods(pool, user.id).await?,
+    })
+}
+
+/// List the current user's sign-in methods.
+///
+/// GET /api/me/auth-methods
+#[utoipa::path(
+    get,
+    path = "/api/me/auth-methods",
+    tag = "auth",
+    responses(
+        (status = 200, description = "Linked sign-in methods", body = AuthMethodsResponse),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[tracing::instrument(skip(state), fields(user_id = %auth_user.id))]
+pub async fn list_auth_methods(
+    State(state): State<AppState>,
+    auth_user: AuthUser,
+) -> AuthResult<Json<AuthMethodsResponse>> {
+    let user = find_user_by_id(&state.db, auth_user.id)
+        .await?
+        .ok_or(AuthError::UserNotFound)?;
+    Ok(Json(load_methods(&state.db, &user).await?))
+}
+
+/// Link a sign-in method to the current account.
+///
+/// POST /api/me/auth-methods/link
+#[utoipa::path(
+    post,
+    path = "/api/me/auth-methods/link",
+    tag = "auth",
+    request_body = LinkAuthMethodRequest,
+    responses(
+        (status = 200, description = "Password linked, or OIDC authorization URL returned", body = LinkAuthMethodResponse),
+        (status = 400, description = "Invalid request or method already linked"),
+        (status = 401, description = "Re-authentication failed"),
+        (status = 403, description = "Method disabled on this server, or MFA code required"),
+        (status = 404, description = "OIDC provider not found"),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[tracing::instrument(skip(state, body), fields(user_id = %auth_user.id))]
+pub async fn link_auth_method(
+    State(state): State<AppState>,
+    auth_user: AuthUser,
+    Json(body): Json<LinkAuthMethodRequest>,
+) -> AuthResult<Json<LinkAuthMethodResponse>> {
+    body.validate()
+        .map_err(|e| AuthError::Validation(e.to_string()))?;
+
+    let user = find_user_by_id(&state.db, auth_user.id)
+        .await?
+        .ok_or(AuthError::UserNotFound)?;
+    reauthenticate(&state, &user, &body.reauth).await?;
+
+    let allowed = get_auth_methods_allowed(&state.db).await?;
+    match body.method {
+        AuthMethod::Local => {
+            if !allowed.local {
+                return Err(AuthError::AuthMethodDisabled);
+            }
+            if user.password_hash.is_some() {
+                return Err(AuthError::Validation(
+                    "A password is already linked; change it instead".to_string(),
+                ));
+            }
+            let new_password = body
+                .new_password
+                .as_deref()
+                .ok_or_else(|| AuthError::Validation("new_password is required".to_string()))?;
+            let password_hash = hash_password(new_password).map_err(|_| AuthError::PasswordHash)?;
+
+            // Only set it if no password was linked concurrently
+            let updated = sqlx::query(
+                "UPDATE users SET password_hash = $1, updated_at = NOW()
+                 WHERE id = $2 AND password_hash IS NULL",
+            )
+            .bind(&password_hash)
+            .bind(user.id)
+            .execute(&state.db)
+            .await?;
+            if updated.rows_affected() == 0 {
+                return Err(AuthError::Validation(
+                    "A password is already linked; change it instead".to_string(),
+                ));
+            }
+
+            tracing::info!(user_id = %user.id, "Password linked to account");
+
+            Ok(Json(LinkAuthMethodResponse {
+                authorize_url: None,
+                methods: Some(AuthMethodsResponse {
+                    has_password: true,
+                    identities: list_user_auth_methods(&state.db, user.id).await?,
+                }),
+            }))
+        }
+        AuthMethod::Oidc => {
+            if !allowed.oidc {
+                return Err(AuthError::AuthMethodDisabled);
+            }
+            let provider = body
+                .provider
+                .as_deref()
+                .ok_or_else(|| AuthError::Validation("provider is required".to_string()))?;
+            let oidc_manager = state
+                .oidc_manager
+                .as_ref()
+                .ok_or(AuthError::OidcNotConfigured)?;
+            if oidc_manager.get_provider_row(provider).await.is_none() {
+                return Err(AuthError::OidcProviderNotFound);
+            }
+            let identities = list_user_auth_methods(&state.db, user.id).await?;
+            if identities.iter().any(|m| m.provider == provider) {
+                return Err(AuthError::Validation(
+                    "An identity from this provider is already linked".to_string(),
+                ));
+            }
+
+            let authorize_url = start_oidc_flow(
+                &state,
+                provider,
+                oidc_server_callback_url()?,
+                OidcFlowPurpose::Link(user.id),
+            )
+            .await?;
+
+            tracing::info!(user_id = %user.id, provider = %provider, "Started OIDC account link");
+            Ok(Json(LinkAuthMethodResponse {
+                authorize_url: Some(authorize_url),
+                methods: None,
+            }))
+        }
+        AuthMethod::Ldap => Err(AuthError::AuthMethodDisabled),
+    }
+}
+
+/// Start a fresh sign-in through a linked provider to re-authenticate.
+///
+/// POST /api/me/auth-methods/reauth
+///
+/// For accounts without password or MFA: the re-auth token the popup
+/// receives confirms the next change of sign-in methods.
+#[utoipa::path(
+    post,
+    path = "/api/me/auth-methods/reauth",
+    tag = "auth",
+    request_body = StartReauthRequest,
+    responses(
+        (status = 200, description = "Provider authorization URL", body = StartReauthResponse),
+        (status = 400, description = "The provider cannot confirm a fresh sign-in"),
+        (status = 403, description = "OIDC disabled on this server"),
+        (status = 404, description = "No identity from this provider is linked"),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[tracing::instrument(skip(state), fields(user_id = %auth_user.id))]
+pub async fn start_oidc_reauth(
+    State(state): State<AppState>,
+    auth_user: AuthUser,
+    Json(body): Json<StartReauthRequest>,
+) -> AuthResult<Json<StartReauthResponse>> {
+    if !get_auth_methods_allowed(&state.db).await?.oidc {
+        return Err(AuthError::AuthMethodDisabled);
+    }
+    let oidc_manager = state
+        .oidc_manager
+        .as_ref()
+        .ok_or(AuthError::OidcNotConfigured)?;
+    let identities = list_user_auth_methods(&state.db, auth_user.id).await?;
+    if !identities
+        .iter()
+        .any(|m| m.method == AuthMethod::Oidc && m.provider == body.provider)
+    {
+        return Err(AuthError::NotFound(
+            "No identity from this provider is linked".to_string(),
+        ));
+    }
+    if !oidc_manager.supports_fresh_login(&body.provider).await {
+        return Err(AuthError::Validation(
+            "This provider cannot confirm a fresh sign-in".to_string(),
+        ));
+    }
+
+    let authorize_url = start_oidc_flow(
+        &state,
+        &body.provider,
+        oidc_server_callback_url()?,
+        OidcFlowPurpose::Reauthenticate(auth_user.id),
+    )
+    .await?;
+
+    Ok(Json(StartReauthResponse { authorize_url }))
+}
+
+/// Link an OIDC identity to an account at the end of a link flow.
+pub(super) async fn link_oidc_identity(
+    pool: &PgPool,
+    user_id: Uuid,
+    provider: &str,
+    external_id: &str,
+) -> AuthResult<()> {
+    let owner: Option<Uuid> =
+        sqlx::query_scalar("SELECT user_id FROM user_auth_methods WHERE external_id = $1")
+            .bind(external_id)
+            .fetch_optional(pool)
+            .await?;
+    match owner {
+        Some(owner) if owner == user_id => return Ok(()),
+        Some(_) => return Err(AuthError::IdentityAlreadyLinked),
+        None => {}
+    }
+
+    let inserted = sqlx::query(
+        "INSERT INTO user_auth_methods (user_id, method, provider, external_id)
+         VALUES ($1, 'oidc', $2, $3)",
+    )
+    .bind(user_id)
+    .bind(provider)
+    .bind(external_id)
+    .execute(pool)
+    .await;
+    match inserted {
+        Ok(_) => {
+            tracing::info!(user_id = %user_id, provider = %provider, "OIDC identity linked to account");
+            Ok(())
+        }
+        // Lost a race against another link of the same identity or provider
+        Err(sqlx::Error::Database(db_err)) if db_err.is_unique_violation() => {
+            Err(AuthError::IdentityAlreadyLinked)
+        }
+        Err(e) => Err(AuthError::Database(e)),
+    }
+}
+
+/// Page shown in the provider popup at the end of a link flow.
+///
+/// Reports the outcome to the settings window that opened it.
+pub(super) fn oidc_link_result_page(provider: &str, result: AuthResult<()>) -> Response {
+    let (payload, text) = match result {
+        Ok(()) => (
+            serde_json::json!({ "type": "oidc-link", "provider": provider }),
+            "Sign-in method linked. You can close this window.".to_string(),
+        ),
+        Err(e) => {
+            tracing::warn!(error = %e, provider = %provider, "OIDC account link failed");
+            (
+                serde_json::json!({
+                    "type": "oidc-link",
+                    "provider": provider,
+                    "error": e.to_string(),
+                }),
+                format!("Linking failed: {e}"),
+            )
+        }
+    };
+    popup_result_page(&payload, text)
+}
+
+/// Page shown in the provider popup at the end of a re-authentication flow.
+pub(super) fn oidc_reauth_result_page(provider: &str, result: AuthResult<String>) -> Response {
+    let (payload, text) = match result {
+        Ok(reauth_token) => (
+            serde_json::json!({
+                "type": "oidc-reauth",
+                "provider": provider,
+                "reauth_token": reauth_token,
+            }),
+            "Identity confirmed. You can close this window.".to_string(),
+        ),
+        Err(e) => {
+            tracing::warn!(error = %e, provider = %provider, "OIDC re-authentication failed");
+            (
+                serde_json::json!({
+                    "type": "oidc-reauth",
+                    "provider": provider,
+                    "error": e.to_string(),
+                }),
+                format!("Confirmation failed: {e}"),
+            )
+        }
+    };
+    popup_result_page(&payload, text)
+}
+
+/// Post `payload` to the window that opened the popup, or show `text` when
+/// there is none.
+fn popup_result_page(payload: &serde_json::Value, text: String) -> Response {
+    let text = serde_json::Value::String(text);
+    Html(format!(
+        r"<!DOCTYPE html>
+<html><body><script>
+if (window.opener) {{
+    window.opener.postMessage({payload}, window.location.origin);
+    window.close();
+}} else {{
+    document.body.innerText = {text};
+}}
+</script></body></html>",
+    ))
+    .into_response()
+}
+
+/// Unlink the password from the current account.
+///
+/// DELETE /api/me/auth-methods/local
+#[utoipa::path(
+    delete,
+    path = "/api/me/auth-methods/local",
+    tag = "auth",
+    request_body = UnlinkAuthMethodRequest,
+    responses(
+        (status = 200, description = "Password unlinked", body = AuthMethodsResponse),
+        (status = 400, description = "Current password missing"),
+        (status = 401, description = "Re-authentication failed"),
+        (status = 404, description = "No password is linked"),
+        (status = 409, description = "The password is the last usable sign-in method"),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[tracing::instrument(skip(state, body), fields(user_id = %auth_user.id))]
+pub async fn unlink_password(
+    State(state): State<AppState>,
+    auth_user: AuthUser,
+    Json(body): Json<UnlinkAuthMethodRequest>,
+) -> AuthResult<Json<AuthMethodsResponse>> {
+    let user = find_user_by_id(&state.db, auth_user.id)
+        .await?
+        .ok_or(AuthError::UserNotFound)?;
+    reauthenticate(&state, &user, &body.reauth).await?;
+
+    let allowed = get_auth_methods_allowed(&state.db).await?;
+    let mut tx = state.db.begin().await?;
+
+    // Lock the user row so concurrent unlinks cannot both pass the check
+    let has_password: bool =
+        sqlx::query_scalar("SELECT password_hash IS NOT NULL FROM users WHERE id = $1 FOR UPDATE")
+            .bind(auth_user.id)
+            .fetch_one(&mut *tx)
+            .await?;
+    if !has_password {
+        return Err(AuthError::NotFound("No password is linked".to_string()));
+    }
+    let identities = sqlx::query_as::<_, UserAuthMethod>(
+        "SELECT id, method, provider, external_id, created_at, last_used_at
+         FROM user_auth_methods WHERE user_id = $1 ORDER BY created_at",
+    )
+    .bind(auth_user.id)
+    .fetch_all(&mut *tx)
+    .await?;
+    let has_passkey = state.config().has_webauthn() && has_passkey(&mut tx, auth_user.id).await?;
+    if !has_usable_method(&allowed, false, &identities, has_passkey) {
+        return Err(AuthError::LastAuthMethod);
+    }
+
+    sqlx::query("UPDATE users SET password_hash = NULL, updated_at = NOW() WHERE id = $1")
+        .bind(auth_user.id)
+        .execute(&mut *tx)
+        .await?;
+    tx.commit().await?;
+
+    if let Err(e) = invalidate_user_reset_tokens(&state.db, auth_user.id).await {
+        tracing::warn!(error = %e, user_id = %auth_user.id, "Failed to invalidate reset tokens");
+    }
+    tracing::info!(user_id = %auth_user.id, "Password unlinked from account");
+
+    Ok(Json(AuthMethodsResponse {
+        has_password: false,
+        identities,
+    }))
+}
+
+/// Unlink an external identity from the current account.
+///
+/// DELETE /api/me/auth-methods/{id}
+#[utoipa::path(
+    delete,
+    path = "/api/me/auth-methods/{id}",
+    tag = "auth",
+    params(("id" = Uuid, Path, description = "Identity ID")),
+    request_body = UnlinkAuthMethodRequest,
+    responses(
+        (status = 200, description = "Identity unlinked", body = AuthMethodsResponse),
+        (status = 400, description = "Current password missing"),
+        (status = 401, description = "Re-authentication failed"),
+        (status = 403, description = "MFA code required"),
+        (status = 404, description = "Identity not found"),
+        (status = 409, description = "The identity is the last usable sign-in method"),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[tracing::instrument(skip(state, body), fields(user_id = %auth_user.id))]
+pub async fn unlink_identity(
+    State(state): State<AppState>,
+    auth_user: AuthUser,
+    Path(id): Path<Uuid>,
+    Json(body): Json<UnlinkAuthMethodRequest>,
+) -> AuthResult<Json<AuthMethodsResponse>> {
+    let user = find_user_by_id(&state.db, auth_user.id)
+        .await?
+        .ok_or(AuthError::UserNotFound)?;
+    reauthenticate(&state, &user, &body.reauth).await?;
+
+    let allowed = get_auth_methods_allowed(&state.db).await?;
+    let mut tx = state.db.begin().await?;
+
+    // Lock the user row so concurrent unlinks cannot both pass the check
+    let has_password: bool =
+        sqlx::query_scalar("SELECT password_hash IS NOT NULL FROM users WHERE id = $1 FOR UPDATE")
+            .bind(auth_user.id)
+            .fetch_one(&mut *tx)
+            .await?;
+    let mut identities = sqlx::query_as::<_, UserAuthMethod>(
+        "SELECT id, method, provider, external_id, created_at, last_used_at
+         FROM user_auth_methods WHERE user_id = $1 ORDER BY created_at",
+    )
+    .bind(auth_user.id)
+    .fetch_all(&mut *tx)
+    .await?;
+    let index = identities
+        .iter()
+        .position(|m| m.id == id)
+        .ok_or_else(|| AuthError::NotFound("Identity not found".to_string()))?;
+    let removed = identities.remove(index);
+    let has_passkey = state.config().has_webauthn() && has_passkey(&mut tx, auth_user.id).await?;
+    if !has_usable_method(&allowed, has_password, &identities, has_passkey) {
+        return Err(AuthError::LastAuthMethod);
+    }
+
+    sqlx::query("DELETE FROM user_auth_methods WHERE id = $1")
+        .bind(id)
+        .execute(&mut *tx)
+        .await?;
+    tx.commit().await?;
+
+    tracing::info!(
+        user_id = %auth_user.id,
+        provider = %removed.provider,
+        "External identity unlinked from account"
+    );
+
+    Ok(Json(AuthMethodsResponse {
+        has_password,
+        identities,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+
+    use super::*;
+
+    fn identity(method: AuthMethod) -> UserAuthMethod {
+        UserAuthMethod {
+            id: Uuid::new_v4(),
+            method,
+            provider: "github".to_string(),
+            external_id: "github:1".to_string(),
+            created_at: Utc::now(),
+            last_used_at: None,
+        }
+    }
+
+    #[test]
+    fn password_is_usable_only_while_local_auth_is_allowed() {
+        let both = AuthMethodsConfig {
+            local: true,
+            oidc: true,
+        };
+        let oidc_only = AuthMethodsConfig {
+            local: false,
+            oidc: true,
+        };
+        assert!(has_usable_method(&both, true, &[], false));
+        assert!(!has_usable_method(&oidc_only, true, &[], false));
+    }
+
+    #[test]
+    fn identities_count_only_for_enabled_methods() {
+        let local_only = AuthMethodsConfig::default();
+        let both = AuthMethodsConfig {
+            local: true,
+            oidc: true,
+        };
+        let oidc = [identity(AuthMethod::Oidc)];
+        assert!(!has_usable_method(&local_only, false, &oidc, false));
+        assert!(has_usable_method(&both, false, &oidc, false));
+        // LDAP identities cannot sign in yet
+        assert!(!has_usable_method(
+            &both,
+            false,
+            &[identity(AuthMethod::Ldap)],
+            false
+        ));
+        assert!(!has_usable_method(&both, false, &[], false));
+    }
+
+    #[test]
+    fn passkey_keeps_account_usable() {
+        let oidc_only = AuthMethodsConfig {
+            local: false,
+            oidc: true,
+        };
+        assert!(has_usable_method(&oidc_only, true, &[], true));
+        assert!(has_usable_method(
+            &AuthMethodsConfig::default(),
+            false,
+            &[],
+            true
+        ));
+    }
+}
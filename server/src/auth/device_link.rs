@@ -0,0 +1,598 @@
+//! Device Linking
+//!
+//! Signs in a new client through a device that is already logged in, so new
+//! installs do not need the password. The new client starts a link request
+//! and shows its code as a QR code; the existing device approves that code;
+//! the new client then claims a session of its own with a secret only it
+//! holds, so a photographed QR code is useless on its own.
+//!
+//! Link requests live in Redis for [`LINK_TTL_SECS`]. The new client may
+//! include an X25519 public key, in which case the approving device can
+//! attach an opaque sealed payload (e.g. its E2EE key backup recovery key)
+//! that the server relays without being able to read it.
+
+use std::net::SocketAddr;
+
+use axum::extract::{ConnectInfo, Path, State};
+use axum::http::{HeaderMap, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use axum_extra::extract::CookieJar;
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use chrono::{DateTime, Duration, Utc};
+use fred::prelude::*;
+use fred::types::SetOptions;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::audit;
+use super::cookies;
+use super::error::{AuthError, AuthResult};
+use super::handlers::{extract_user_agent, should_return_refresh_token, AuthResponse};
+use super::hash_token;
+use super::jwt::generate_token_pair;
+use super::login_networks;
+use super::middleware::{ensure_account_active, AuthUser};
+use crate::api::AppState;
+use crate::db::{create_session, find_user_by_id, is_setup_complete};
+
+/// How long a link request waits for approval and claiming.
+pub const LINK_TTL_SECS: i64 = 120;
+
+/// Length of a link code (Crockford base32, ~50 bits).
+const CODE_LEN: usize = 10;
+
+/// Crockford base32 alphabet: no I, L, O or U, so codes survive being read
+/// aloud or typed by hand.
+const CODE_ALPHABET: &[u8] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+
+/// Size of the new client's X25519 public key.
+const PUBLIC_KEY_LEN: usize = 32;
+
+/// Maximum size of the sealed payload relayed to the new client (base64).
+const MAX_KEY_TRANSFER_LEN: usize = 8192;
+
+// ============================================================================
+// Request/Response Types
+// ============================================================================
+
+/// Start a device link request.
+#[derive(Debug, Default, Deserialize, utoipa::ToSchema)]
+pub struct StartDeviceLinkRequest {
+    /// X25519 public key of the new client (Base64). Required for the
+    /// approving device to hand over E2EE key backup access.
+    pub public_key: Option<String>,
+}
+
+/// A started link request. The code goes into the QR code; the secret stays
+/// on the new client.
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct DeviceLinkStarted {
+    /// Code the existing device approves.
+    pub code: String,
+    /// Secret the new client claims its session with.
+    pub secret: String,
+    /// Seconds until the request expires.
+    pub expires_in: i64,
+}
+
+/// What the approving device is shown before it approves.
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct DeviceLinkDetails {
+    /// Link code.
+    pub code: String,
+    /// User agent of the new client.
+    pub user_agent: Option<String>,
+    /// IP address the request came from.
+    pub ip_address: String,
+    /// When the request was started.
+    pub created_at: DateTime<Utc>,
+    /// Whether the new client can receive a sealed E2EE key transfer.
+    pub accepts_key_transfer: bool,
+}
+
+/// Approve a link request.
+#[derive(Debug, Default, Deserialize, utoipa::ToSchema)]
+pub struct ApproveDeviceLinkRequest {
+    /// Payload sealed to the new client's public key (Base64), relayed as-is.
+    pub key_transfer: Option<String>,
+}
+
+/// Claim the session of an approved link request.
+#[derive(Deserialize, utoipa::ToSchema)]
+pub struct ClaimDeviceLinkRequest {
+    /// Secret returned when the request was started.
+    pub secret: String,
+}
+
+impl std::fmt::Debug for ClaimDeviceLinkRequest {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ClaimDeviceLinkRequest")
+            .field("secret", &"[REDACTED]")
+            .finish()
+    }
+}
+
+/// Session issued to the new client once its request is approved.
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct DeviceLinkSession {
+    #[serde(flatten)]
+    pub auth: AuthResponse,
+    /// Sealed payload from the approving device, if it sent one.
+    pub key_transfer: Option<String>,
+}
+
+/// A link request waiting in Redis.
+#[derive(Debug, Serialize, Deserialize)]
+struct PendingLink {
+    secret_hash: String,
+    public_key: Option<String>,
+    user_agent: Option<String>,
+    ip_address: String,
+    created_at: i64,
+}
+
+/// Approval of a link request, stored next to it until claimed.
+#[derive(Debug, Serialize, Deserialize)]
+struct LinkApproval {
+    user_id: Uuid,
+    key_transfer: Option<String>,
+}
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+fn link_key(code: &str) -> String {
+    format!("device_link:{code}")
+}
+
+fn approval_key(code: &str) -> String {
+    format!("device_link:{code}:approval")
+}
+
+fn redis_error(e: &fred::error::Error) -> AuthError {
+    tracing::error!(error = %e, "Device link Redis operation failed");
+    AuthError::Internal("Failed to access link request".to_string())
+}
+
+fn generate_code() -> String {
+    use rand::Rng;
+
+    let mut rng = rand::thread_rng();
+    (0..CODE_LEN)
+        .map(|_| char::from(CODE_ALPHABET[rng.gen_range(0..CODE_ALPHABET.len())]))
+        .collect()
+}
+
+fn generate_secret() -> String {
+    use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+    use rand::RngCore;
+
+    let mut secret = [0u8; 32];
+    rand::thread_rng().fill_bytes(&mut secret);
+    URL_SAFE_NO_PAD.encode(secret)
+}
+
+/// Normalize a code typed by hand: uppercase, separators dropped.
+fn normalize_code(code: &str) -> Option<String> {
+    let code: String = code
+        .chars()
+        .filter(|c| !matches!(c, '-' | ' '))
+        .map(|c| c.to_ascii_uppercase())
+        .collect();
+    (code.len() == CODE_LEN && code.bytes().all(|b| CODE_ALPHABET.contains(&b))).then_some(code)
+}
+
+fn validate_public_key(public_key: Option<String>) -> AuthResult<Option<String>> {
+    let Some(key) = public_key.filter(|k| !k.is_empty()) else {
+        return Ok(None);
+    };
+    let decoded = STANDARD
+        .decode(&key)
+        .map_err(|_| AuthError::Validation("Invalid public key encoding".into()))?;
+    if decoded.len() != PUBLIC_KEY_LEN {
+        return Err(AuthError::Validation(format!(
+            "Public key must be {PUBLIC_KEY_LEN} bytes"
+        )));
+    }
+    Ok(Some(key))
+}
+
+fn validate_key_transfer(key_transfer: Option<String>) -> AuthResult<Option<String>> {
+    let Some(payload) = key_transfer.filter(|p| !p.is_empty()) else {
+        return Ok(None);
+    };
+    if payload.len() > MAX_KEY_TRANSFER_LEN {
+        return Err(AuthError::Validation("Key transfer too large".into()));
+    }
+    STANDARD
+        .decode(&payload)
+        .map_err(|_| AuthError::Validation("Invalid key transfer encoding".into()))?;
+    Ok(Some(payload))
+}
+
+/// Look up a pending link request by a (possibly hand-typed) code, returning
+/// the normalized code with it.
+async fn load_link(state: &AppState, code: &str) -> AuthResult<(String, PendingLink)> {
+    let not_found = || AuthError::NotFound("Link request not found or expired".into());
+    let code = normalize_code(code).ok_or_else(not_found)?;
+    let raw: Option<String> = state
+        .redis
+        .get(link_key(&code))
+        .await
+        .map_err(|e| redis_error(&e))?;
+    let raw = raw.ok_or_else(not_found)?;
+    let link = serde_json::from_str(&raw).map_err(|e| AuthError::Internal(e.to_string()))?;
+    Ok((code, link))
+}
+
+// ============================================================================
+// Handlers
+// ============================================================================
+
+/// Start a device link request from a new client.
+///
+/// POST /auth/device-link
+#[utoipa::path(
+    post,
+    path = "/auth/device-link",
+    tag = "auth",
+    request_body = StartDeviceLinkRequest,
+    responses(
+        (status = 201, description = "Link request started", body = DeviceLinkStarted),
+        (status = 400, description = "Invalid public key"),
+    ),
+    security(()),
+)]
+#[tracing::instrument(skip(state, headers, body))]
+pub async fn start_link(
+    State(state): State<AppState>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+    body: Option<Json<StartDeviceLinkRequest>>,
+) -> AuthResult<(StatusCode, Json<DeviceLinkStarted>)> {
+    let Json(body) = body.unwrap_or_default();
+    let public_key = validate_public_key(body.public_key)?;
+
+    let code = generate_code();
+    let secret = generate_secret();
+    let link = PendingLink {
+        secret_hash: hash_token(&secret),
+        public_key,
+        user_agent: extract_user_agent(&headers),
+        ip_address: addr.ip().to_string(),
+        created_at: Utc::now().timestamp(),
+    };
+    let link_json = serde_json::to_string(&link).map_err(|e| AuthError::Internal(e.to_string()))?;
+
+    let created: bool = state
+        .redis
+        .set(
+            link_key(&code),
+            link_json,
+            Some(Expiration::EX(LINK_TTL_SECS)),
+            Some(SetOptions::NX),
+            false,
+        )
+        .await
+        .map_err(|e| redis_error(&e))?;
+    if !created {
+        // A live code was drawn again; the client simply retries
+        return Err(AuthError::Internal("Link code collision".to_string()));
+    }
+
+    tracing::info!(ip = %link.ip_address, "Device link request started");
+
+    Ok((
+        StatusCode::CREATED,
+        Json(DeviceLinkStarted {
+            code,
+            secret,
+            expires_in: LINK_TTL_SECS,
+        }),
+    ))
+}
+
+/// Show a pending link request to the device about to approve it.
+///
+/// GET /auth/device-link/{code}
+#[utoipa::path(
+    get,
+    path = "/auth/device-link/{code}",
+    tag = "auth",
+    params(("code" = String, Path, description = "Link code")),
+    responses(
+        (status = 200, description = "Link request", body = DeviceLinkDetails),
+        (status = 404, description = "Link request not found or expired"),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[tracing::instrument(skip(state), fields(user_id = %auth_user.id))]
+pub async fn get_link(
+    State(state): State<AppState>,
+    auth_user: AuthUser,
+    Path(code): Path<String>,
+) -> AuthResult<Json<DeviceLinkDetails>> {
+    let (code, link) = load_link(&state, &code).await?;
+
+    Ok(Json(DeviceLinkDetails {
+        code,
+        user_agent: link.user_agent,
+        ip_address: link.ip_address,
+        created_at: DateTime::from_timestamp(link.created_at, 0).unwrap_or_else(Utc::now),
+        accepts_key_transfer: link.public_key.is_some(),
+    }))
+}
+
+/// Approve a link request, letting the new client sign in as the caller.
+///
+/// POST /auth/device-link/{code}/approve
+#[utoipa::path(
+    post,
+    path = "/auth/device-link/{code}/approve",
+    tag = "auth",
+    params(("code" = String, Path, description = "Link code")),
+    request_body = ApproveDeviceLinkRequest,
+    responses(
+        (status = 204, description = "Link request approved"),
+        (status = 400, description = "Invalid key transfer"),
+        (status = 404, description = "Link request not found or expired"),
+        (status = 409, description = "Link request already approved"),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[tracing::instrument(skip(state, body), fields(user_id = %auth_user.id))]
+pub async fn approve_link(
+    State(state): State<AppState>,
+    auth_user: AuthUser,
+    Path(code): Path<String>,
+    body: Option<Json<ApproveDeviceLinkRequest>>,
+) -> AuthResult<StatusCode> {
+    let Json(body) = body.unwrap_or_default();
+    let (code, link) = load_link(&state, &code).await?;
+
+    let key_transfer = validate_key_transfer(body.key_transfer)?;
+    if key_transfer.is_some() && link.public_key.is_none() {
+        return Err(AuthError::Validation(
+            "This device did not offer a key for key transfer".into(),
+        ));
+    }
+
+    let approval = LinkApproval {
+        user_id: auth_user.id,
+        key_transfer,
+    };
+    let approval_json =
+        serde_json::to_string(&approval).map_err(|e| AuthError::Internal(e.to_string()))?;
+
+    // NX: the first approval wins, so a second account cannot take over a
+    // request someone already approved
+    let approved: bool = state
+        .redis
+        .set(
+            approval_key(&code),
+            approval_json,
+            Some(Expiration::EX(LINK_TTL_SECS)),
+            Some(SetOptions::NX),
+            false,
+        )
+        .await
+        .map_err(|e| redis_error(&e))?;
+    if !approved {
+        return Err(AuthError::DeviceLinkAlreadyApproved);
+    }
+
+    tracing::info!(
+        user_id = %auth_user.id,
+        new_device_ip = %link.ip_address,
+        key_transfer = approval.key_transfer.is_some(),
+        "Device link request approved"
+    );
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Reject a link request.
+///
+/// DELETE /auth/device-link/{code}
+#[utoipa::path(
+    delete,
+    path = "/auth/device-link/{code}",
+    tag = "auth",
+    params(("code" = String, Path, description = "Link code")),
+    responses(
+        (status = 204, description = "Link request rejected"),
+        (status = 404, description = "Link request not found or expired"),
+        (status = 409, description = "Link request already approved"),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[tracing::instrument(skip(state), fields(user_id = %auth_user.id))]
+pub async fn reject_link(
+    State(state): State<AppState>,
+    auth_user: AuthUser,
+    Path(code): Path<String>,
+) -> AuthResult<StatusCode> {
+    let (code, _) = load_link(&state, &code).await?;
+
+    let approved: bool = state
+        .redis
+        .exists(approval_key(&code))
+        .await
+        .map_err(|e| redis_error(&e))?;
+    if approved {
+        return Err(AuthError::DeviceLinkAlreadyApproved);
+    }
+
+    state
+        .redis
+        .del::<(), _>(link_key(&code))
+        .await
+        .map_err(|e| redis_error(&e))?;
+
+    tracing::info!(user_id = %auth_user.id, "Device link request rejected");
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Claim the session of an approved link request. The new client polls this
+/// until the request is approved (`202`) and then receives its tokens.
+///
+/// POST /auth/device-link/{code}/claim
+#[utoipa::path(
+    post,
+    path = "/auth/device-link/{code}/claim",
+    tag = "auth",
+    params(("code" = String, Path, description = "Link code")),
+    request_body = ClaimDeviceLinkRequest,
+    responses(
+        (status = 200, description = "Approved; session issued", body = DeviceLinkSession),
+        (status = 202, description = "Waiting for approval"),
+        (status = 401, description = "Wrong secret"),
+        (status = 404, description = "Link request not found, expired, or rejected"),
+    ),
+    security(()),
+)]
+#[tracing::instrument(skip(state, headers, jar, body))]
+pub async fn claim_link(
+    State(state): State<AppState>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+    jar: CookieJar,
+    Path(code): Path<String>,
+    Json(body): Json<ClaimDeviceLinkRequest>,
+) -> AuthResult<Response> {
+    let (code, link) = load_link(&state, &code).await?;
+    if hash_token(&body.secret) != link.secret_hash {
+        return Err(AuthError::InvalidToken);
+    }
+
+    // Atomically take the approval so a session is issued exactly once
+    let approval: Option<String> = state
+        .redis
+        .getdel(approval_key(&code))
+        .await
+        .map_err(|e| redis_error(&e))?;
+    let Some(approval) = approval else {
+        return Ok(StatusCode::ACCEPTED.into_response());
+    };
+    let approval: LinkApproval =
+        serde_json::from_str(&approval).map_err(|e| AuthError::Internal(e.to_string()))?;
+    state
+        .redis
+        .del::<(), _>(link_key(&code))
+        .await
+        .map_err(|e| redis_error(&e))?;
+
+    let user = find_user_by_id(&state.db, approval.user_id)
+        .await?
+        .ok_or(AuthError::UserNotFound)?;
+    ensure_account_active(&user, None)?;
+    let new_network = login_networks::is_new_network(&state.db, user.id, addr.ip()).await?;
+
+    let tokens = generate_token_pair(
+        user.id,
+        &state.config().jwt_private_key,
+        state.config().jwt_access_expiry,
+        state.config().jwt_refresh_expiry,
+    )?;
+    let token_hash = hash_token(&tokens.refresh_token);
+    let expires_at = Utc::now() + Duration::seconds(state.config().jwt_refresh_expiry);
+    let user_agent = extract_user_agent(&headers);
+
+    create_session(
+        &state.db,
+        user.id,
+        &token_hash,
+        expires_at,
+        Some(&addr.ip().to_string()),
+        user_agent.as_deref(),
+    )
+    .await?;
+
+    let setup_complete = is_setup_complete(&state.db).await?;
+
+    tracing::info!(user_id = %user.id, "Device linked");
+    audit::record(
+        &state.db,
+        "auth.login",
+        Some(user.id),
+        Some(user.id),
+        Some(serde_json::json!({ "method": "device_link" })),
+        Some(addr.ip()),
+        &headers,
+    )
+    .await;
+    login_networks::record_login(
+        &state,
+        &user,
+        addr.ip(),
+        &headers,
+        "device_link",
+        new_network,
+    )
+    .await;
+
+    let include_refresh_token = should_return_refresh_token(&headers);
+    let jar = jar.add(cookies::build_refresh_cookie(
+        &tokens.refresh_token,
+        state.config().jwt_refresh_expiry,
+        &state.config(),
+    ));
+
+    Ok((
+        jar,
+        Json(DeviceLinkSession {
+            auth: AuthResponse {
+                access_token: tokens.access_token,
+                refresh_token: include_refresh_token.then_some(tokens.refresh_token),
+                expires_in: tokens.access_expires_in,
+                token_type: "Bearer".to_string(),
+                setup_required: !setup_complete,
+            },
+            key_transfer: approval.key_transfer,
+        }),
+    )
+        .into_response())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generated_codes_normalize_to_themselves() {
+        for _ in 0..100 {
+            let code = generate_code();
+            assert_eq!(normalize_code(&code).as_deref(), Some(code.as_str()));
+        }
+    }
+
+    #[test]
+    fn normalize_code_accepts_hand_typed_codes() {
+        assert_eq!(normalize_code("abcde-12345").as_deref(), Some("ABCDE12345"));
+        assert_eq!(normalize_code("ABCDE 12345").as_deref(), Some("ABCDE12345"));
+        assert_eq!(normalize_code("ABCDE1234"), None);
+        assert_eq!(normalize_code("ABCDE1234U"), None);
+        assert_eq!(normalize_code("device_link:ABCDE12345"), None);
+    }
+
+    #[test]
+    fn public_key_must_be_32_bytes() {
+        assert_eq!(validate_public_key(None).unwrap(), None);
+        assert_eq!(validate_public_key(Some(String::new())).unwrap(), None);
+        let key = STANDARD.encode([7u8; 32]);
+        assert_eq!(validate_public_key(Some(key.clone())).unwrap(), Some(key));
+        assert!(validate_public_key(Some(STANDARD.encode([7u8; 16]))).is_err());
+        assert!(validate_public_key(Some("not base64!".into())).is_err());
+    }
+
+    #[test]
+    fn key_transfer_is_bounded() {
+        assert!(validate_key_transfer(Some(STANDARD.encode([1u8; 64]))).is_ok());
+        assert!(validate_key_transfer(Some("A".repeat(MAX_KEY_TRANSFER_LEN + 4))).is_err());
+        assert!(validate_key_transfer(Some("%%%".into())).is_err());
+    }
+}
@@ -0,0 +1,204 @@
+//! Email Verification
+//!
+//! Confirms that users own the address on their account. A verification code
+//! is emailed on registration, when the address changes and on request;
+//! confirming it sets `users.email_verified_at`. Changing the address clears
+//! it again, and codes sent to an earlier address stop working.
+//!
+//! Codes are stored hashed and expire after [`VERIFICATION_TTL_HOURS`]. Only
+//! the newest code of an account is valid.
+
+use std::net::SocketAddr;
+
+use axum::extract::{ConnectInfo, State};
+use axum::http::HeaderMap;
+use axum::Json;
+use chrono::{Duration, Utc};
+use serde::Deserialize;
+use uuid::Uuid;
+
+use super::audit;
+use super::error::{AuthError, AuthResult};
+use super::hash_token;
+use super::middleware::AuthUser;
+use crate::api::AppState;
+use crate::db::{
+    consume_email_verification_token, create_email_verification_token,
+    invalidate_email_verification_tokens,
+};
+
+/// How long a verification code stays valid.
+pub const VERIFICATION_TTL_HOURS: i64 = 24;
+
+/// Minimum time between two verification emails to the same account.
+const RESEND_COOLDOWN_SECS: i64 = 60;
+
+/// Confirm email request.
+#[derive(Debug, Deserialize, utoipa::ToSchema)]
+pub struct VerifyEmailRequest {
+    /// The verification code (raw, as received via email).
+    pub token: String,
+}
+
+/// Create a verification code for `email` and send it.
+///
+/// Earlier codes of the account are invalidated. The new code is removed
+/// again if the email cannot be sent.
+pub async fn send_verification(
+    state: &AppState,
+    user_id: Uuid,
+    username: &str,
+    email: &str,
+) -> AuthResult<()> {
+    use base64::Engine;
+    use rand::RngCore;
+
+    let email_service = state.email.as_ref().ok_or(AuthError::EmailNotConfigured)?;
+
+    invalidate_email_verification_tokens(&state.db, user_id).await?;
+
+    let mut token_bytes = [0u8; 32];
+    rand::thread_rng().fill_bytes(&mut token_bytes);
+    let raw_token = base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(token_bytes);
+    let expires_at = Utc::now() + Duration::hours(VERIFICATION_TTL_HOURS);
+
+    create_email_verification_token(
+        &state.db,
+        user_id,
+        email,
+        &hash_token(&raw_token),
+        expires_at,
+    )
+    .await?;
+
+    if let Err(e) = email_service
+        .send_email_verification(email, username, &raw_token, VERIFICATION_TTL_HOURS)
+        .await
+    {
+        tracing::error!(error = %e, user_id = %user_id, "Failed to send verification email");
+        // The user never received the code
+        if let Err(e) = invalidate_email_verification_tokens(&state.db, user_id).await {
+            tracing::error!(error = %e, user_id = %user_id, "Failed to clean up verification token");
+        }
+        return Err(AuthError::Internal(
+            "Failed to send verification email".to_string(),
+        ));
+    }
+
+    tracing::info!(user_id = %user_id, "Verification email sent");
+    Ok(())
+}
+
+/// Send a verification code in the background, if email is configured.
+///
+/// For registration and address changes, which must not fail or wait on SMTP.
+pub fn spawn_verification(state: &AppState, user_id: Uuid, username: &str, email: &str) {
+    if state.email.is_none() {
+        return;
+    }
+    let state = state.clone();
+    let username = username.to_string();
+    let email = email.to_string();
+    tokio::spawn(async move {
+        // Errors are logged by the helper
+        let _ = send_verification(&state, user_id, &username, &email).await;
+    });
+}
+
+/// Send a new verification code to the address on the account.
+///
+/// POST /auth/email/verification
+#[utoipa::path(
+    post,
+    path = "/auth/email/verification",
+    tag = "auth",
+    responses(
+        (status = 200, description = "Verification email sent"),
+        (status = 400, description = "No email address on the account"),
+        (status = 409, description = "Email is already verified"),
+        (status = 429, description = "A verification email was sent less than a minute ago"),
+        (status = 503, description = "Email service not configured"),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[tracing::instrument(skip(state), fields(user_id = %auth_user.id))]
+pub async fn resend_verification(
+    State(state): State<AppState>,
+    auth_user: AuthUser,
+) -> AuthResult<Json<serde_json::Value>> {
+    let email = auth_user
+        .email
+        .as_deref()
+        .ok_or_else(|| AuthError::Validation("No email address on this account".to_string()))?;
+    if auth_user.email_verified {
+        return Err(AuthError::EmailAlreadyVerified);
+    }
+    if state.email.is_none() {
+        return Err(AuthError::EmailNotConfigured);
+    }
+
+    let recently_sent: bool = sqlx::query_scalar(
+        r"SELECT EXISTS(
+              SELECT 1 FROM email_verification_tokens
+              WHERE user_id = $1 AND created_at > $2
+          )",
+    )
+    .bind(auth_user.id)
+    .bind(Utc::now() - Duration::seconds(RESEND_COOLDOWN_SECS))
+    .fetch_one(&state.db)
+    .await?;
+    if recently_sent {
+        return Err(AuthError::VerificationEmailCooldown);
+    }
+
+    send_verification(&state, auth_user.id, &auth_user.username, email).await?;
+
+    Ok(Json(serde_json::json!({
+        "message": "Verification email sent"
+    })))
+}
+
+/// Verify the email address of an account with a code from a verification
+/// email.
+///
+/// Works without a session, so accounts waiting for approval can verify too.
+///
+/// POST /auth/email/verify
+#[utoipa::path(
+    post,
+    path = "/auth/email/verify",
+    tag = "auth",
+    request_body = VerifyEmailRequest,
+    responses(
+        (status = 200, description = "Email verified"),
+        (status = 401, description = "Invalid or expired code"),
+    ),
+    security(()),
+)]
+#[tracing::instrument(skip(state, headers, body))]
+pub async fn confirm_verification(
+    State(state): State<AppState>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+    Json(body): Json<VerifyEmailRequest>,
+) -> AuthResult<Json<serde_json::Value>> {
+    let user_id = consume_email_verification_token(&state.db, &hash_token(body.token.trim()))
+        .await?
+        .ok_or(AuthError::InvalidToken)?;
+
+    audit::record(
+        &state.db,
+        "auth.email.verified",
+        Some(user_id),
+        Some(user_id),
+        None,
+        Some(addr.ip()),
+        &headers,
+    )
+    .await;
+    tracing::info!(user_id = %user_id, "Email verified");
+
+    Ok(Json(serde_json::json!({
+        "message": "Email verified"
+    })))
+}
@@ -97,6 +97,71 @@ pub enum AuthError {
     #[error("This authentication method is disabled")]
     AuthMethodDisabled,
 
+    /// A device link request was already approved.
+    #[error("This link request was already approved")]
+    DeviceLinkAlreadyApproved,
+
+    /// Username is in use or held for its previous owner.
+    #[error("Username is already taken")]
+    UsernameTaken,
+
+    /// Username was changed too recently.
+    #[error("Username can be changed again after {}", .0.format("%Y-%m-%d %H:%M UTC"))]
+    UsernameChangeCooldown(chrono::DateTime<chrono::Utc>),
+
+    /// External identity is already linked to an account.
+    #[error("This identity is already linked to an account")]
+    IdentityAlreadyLinked,
+
+    /// No account is linked to the identity and the provider doesn't create
+    /// accounts.
+    #[error("No account is linked to this identity")]
+    OidcAccountNotLinked,
+
+    /// The identity's email belongs to an account it may not be linked to.
+    #[error(
+        "An account with this email already exists; sign in and link this provider from settings"
+    )]
+    OidcEmailInUse,
+
+    /// Removing the sign-in method would leave the account without one.
+    #[error("Cannot remove the last sign-in method")]
+    LastAuthMethod,
+
+    /// Passkeys are not configured on this server.
+    #[error("Passkeys are not configured")]
+    PasskeysNotConfigured,
+
+    /// Passkey challenge expired or the authenticator response was rejected.
+    #[error("Invalid or expired passkey response")]
+    InvalidPasskey,
+
+    /// The account was suspended by a system admin.
+    #[error("This account has been suspended")]
+    AccountSuspended,
+
+    /// The account was registered under the approval policy and has not been
+    /// approved by a system admin yet.
+    #[error("This account is waiting for admin approval")]
+    AccountPendingApproval,
+
+    /// Registration code missing, unknown, expired or used up.
+    #[error("Invalid or expired registration code")]
+    InvalidRegistrationCode,
+
+    /// A passkey cannot replace the TOTP code when signing in from a new
+    /// network.
+    #[error("Enter your authenticator code to sign in from a new network")]
+    NewNetworkTotpRequired,
+
+    /// The address on the account is already verified.
+    #[error("Email is already verified")]
+    EmailAlreadyVerified,
+
+    /// A verification email was sent too recently.
+    #[error("A verification email was sent recently; try again in a minute")]
+    VerificationEmailCooldown,
+
     /// Internal server error.
     #[error("Internal server error")]
     Internal(String),
@@ -136,6 +201,27 @@ impl IntoResponse for AuthError {
             Self::OidcCodeExchangeFailed(_) => (StatusCode::BAD_GATEWAY, "OIDC_EXCHANGE_FAILED"),
             Self::RegistrationDisabled => (StatusCode::FORBIDDEN, "REGISTRATION_DISABLED"),
             Self::AuthMethodDisabled => (StatusCode::FORBIDDEN, "AUTH_METHOD_DISABLED"),
+            Self::DeviceLinkAlreadyApproved => (StatusCode::CONFLICT, "LINK_ALREADY_APPROVED"),
+            Self::UsernameTaken => (StatusCode::CONFLICT, "USERNAME_TAKEN"),
+            Self::UsernameChangeCooldown(_) => {
+                (StatusCode::TOO_MANY_REQUESTS, "USERNAME_CHANGE_COOLDOWN")
+            }
+            Self::IdentityAlreadyLinked => (StatusCode::CONFLICT, "IDENTITY_ALREADY_LINKED"),
+            Self::OidcAccountNotLinked => (StatusCode::FORBIDDEN, "OIDC_ACCOUNT_NOT_LINKED"),
+            Self::OidcEmailInUse => (StatusCode::CONFLICT, "OIDC_EMAIL_IN_USE"),
+            Self::LastAuthMethod => (StatusCode::CONFLICT, "LAST_AUTH_METHOD"),
+            Self::PasskeysNotConfigured => {
+                (StatusCode::SERVICE_UNAVAILABLE, "PASSKEYS_NOT_CONFIGURED")
+            }
+            Self::InvalidPasskey => (StatusCode::UNAUTHORIZED, "INVALID_PASSKEY"),
+            Self::AccountSuspended => (StatusCode::FORBIDDEN, "ACCOUNT_SUSPENDED"),
+            Self::AccountPendingApproval => (StatusCode::FORBIDDEN, "ACCOUNT_PENDING_APPROVAL"),
+            Self::InvalidRegistrationCode => (StatusCode::FORBIDDEN, "INVALID_REGISTRATION_CODE"),
+            Self::NewNetworkTotpRequired => (StatusCode::FORBIDDEN, "NEW_NETWORK_TOTP_REQUIRED"),
+            Self::EmailAlreadyVerified => (StatusCode::CONFLICT, "EMAIL_ALREADY_VERIFIED"),
+            Self::VerificationEmailCooldown => {
+                (StatusCode::TOO_MANY_REQUESTS, "VERIFICATION_EMAIL_COOLDOWN")
+            }
             Self::Internal(_) => (StatusCode::INTERNAL_SERVER_ERROR, "INTERNAL_ERROR"),
         };
 
@@ -4,7 +4,7 @@ use std::net::SocketAddr;
 
 use axum::extract::{ConnectInfo, Multipart, Path, State};
 use axum::http::header::{ORIGIN, USER_AGENT};
-use axum::http::HeaderMap;
+use axum::http::{HeaderMap, StatusCode};
 use axum::response::{IntoResponse, Redirect, Response};
 use axum::{Extension, Json};
 use axum_extra::extract::CookieJar;
@@ -16,28 +16,45 @@ use totp_rs::{Algorithm, Secret, TOTP};
 use uuid::Uuid;
 use validator::Validate;
 
+use super::audit;
+use super::auth_methods::{
+    finish_oidc_reauth, link_oidc_identity, oidc_link_result_page, oidc_reauth_result_page,
+};
 use super::backup_codes::{find_matching_backup_code, generate_backup_codes, BACKUP_CODE_COUNT};
 use super::cookies;
+use super::email_verification;
 use super::error::{AuthError, AuthResult};
 use super::jwt::{generate_token_pair, validate_refresh_token};
+use super::login_networks;
 use super::mfa_crypto::{decrypt_mfa_secret, encrypt_mfa_secret};
-use super::middleware::AuthUser;
-use super::oidc::{append_collision_suffix, generate_username_from_claims, OidcFlowState};
+use super::middleware::{ensure_account_active, AuthUser};
+use super::oidc::{
+    append_collision_suffix, generate_username_from_claims, linkable_email, OidcFlowState,
+    OidcUserInfo, Provisioning,
+};
 use super::password::{hash_password, verify_password};
+use super::username::{RESERVED_USERNAMES, USERNAME_REDIRECT_DAYS};
+use super::webauthn::{self, PasskeyAssertion};
 use crate::api::AppState;
+use crate::config::Config;
 use crate::db::{
     self, count_all_mfa_backup_codes, count_unused_mfa_backup_codes, create_password_reset_token,
     create_session, delete_mfa_backup_codes, delete_session_by_token_hash, email_exists,
     find_session_by_token_hash, find_user_by_email, find_user_by_external_id, find_user_by_id,
     find_user_by_username, find_valid_reset_token, get_auth_methods_allowed,
     get_unused_mfa_backup_codes, invalidate_user_reset_tokens, is_setup_complete,
-    mark_mfa_backup_code_used, set_mfa_secret, store_mfa_backup_codes, update_user_avatar,
-    update_user_profile, username_exists, Session,
+    mark_mfa_backup_code_used, set_mfa_secret, store_mfa_backup_codes, touch_user_auth_method,
+    update_user_avatar, update_user_profile, username_exists, username_held, OidcProviderRow,
+    Session, User,
 };
+use crate::presence::registry::{self as presence_registry, MAX_CUSTOM_STATUS_LEN};
 use crate::ratelimit::NormalizedIp;
 use crate::util::format_file_size;
 use crate::ws::broadcast_user_patch;
 
+/// Maximum length of a name pronunciation, in characters.
+const MAX_NAME_PRONUNCIATION_LEN: usize = 64;
+
 /// Extract a refresh token from either the JSON body or `HttpOnly` cookie.
 fn extract_refresh_token(body_token: Option<String>, jar: &CookieJar) -> AuthResult<String> {
     if let Some(token) = body_token.filter(|t| !t.is_empty()) {
@@ -64,9 +81,12 @@ fn extract_refresh_token(body_token: Option<String>, jar: &CookieJar) -> AuthRes
 /// Browser (CORS) requests include the `Origin` header; for those clients the
 /// refresh token is delivered solely via `HttpOnly` cookie.  Non-browser clients
 /// (e.g. Tauri) omit `Origin` and receive the token in the response body.
-fn should_return_refresh_token(headers: &HeaderMap) -> bool {
+pub(super) fn should_return_refresh_token(headers: &HeaderMap) -> bool {
     let has_origin = headers.contains_key(ORIGIN);
-    tracing::debug!(has_origin_header = has_origin, "Refresh token delivery decision");
+    tracing::debug!(
+        has_origin_header = has_origin,
+        "Refresh token delivery decision"
+    );
     !has_origin
 }
 
@@ -89,6 +109,17 @@ pub struct RegisterRequest {
     /// Display name (optional, defaults to username).
     #[validate(length(max = 64))]
     pub display_name: Option<String>,
+    /// Registration code from a system admin (required when the registration
+    /// policy is `invite_only`).
+    #[validate(length(max = 32))]
+    pub registration_code: Option<String>,
+}
+
+/// Response to a registration that waits for admin approval.
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct RegistrationPendingResponse {
+    /// Always true; the account can sign in once a system admin approves it.
+    pub pending_approval: bool,
 }
 
 /// Login request.
@@ -100,6 +131,8 @@ pub struct LoginRequest {
     pub password: String,
     /// MFA code (required if MFA is enabled).
     pub mfa_code: Option<String>,
+    /// Passkey assertion, accepted instead of `mfa_code`.
+    pub passkey: Option<PasskeyAssertion>,
 }
 
 impl std::fmt::Debug for LoginRequest {
@@ -108,6 +141,7 @@ impl std::fmt::Debug for LoginRequest {
             .field("username", &self.username)
             .field("password", &"[REDACTED]")
             .field("mfa_code", &self.mfa_code.as_ref().map(|_| "[REDACTED]"))
+            .field("passkey", &self.passkey.as_ref().map(|_| "[REDACTED]"))
             .finish()
     }
 }
@@ -154,8 +188,18 @@ pub struct UserProfile {
     pub display_name: String,
     /// Email (if set).
     pub email: Option<String>,
+    /// Whether the email has been verified.
+    pub email_verified: bool,
     /// Avatar URL (if set).
     pub avatar_url: Option<String>,
+    /// Profile banner URL (if set).
+    pub banner_url: Option<String>,
+    /// About-me text (markdown).
+    pub about_me: Option<String>,
+    /// Pronouns.
+    pub pronouns: Option<String>,
+    /// Profile accent colour (`#rrggbb`).
+    pub accent_color: Option<String>,
     /// Online status.
     pub status: String,
     /// Whether MFA is enabled.
@@ -165,6 +209,26 @@ pub struct UserProfile {
     pub deletion_scheduled_at: Option<String>,
 }
 
+impl From<User> for UserProfile {
+    fn from(user: User) -> Self {
+        Self {
+            id: user.id.to_string(),
+            username: user.username,
+            display_name: user.display_name,
+            email: user.email,
+            email_verified: user.email_verified_at.is_some(),
+            avatar_url: user.avatar_url,
+            banner_url: user.banner_url,
+            about_me: user.about_me,
+            pronouns: user.pronouns,
+            accent_color: user.accent_color,
+            status: user.status.as_str().to_string(),
+            mfa_enabled: user.mfa_secret.is_some(),
+            deletion_scheduled_at: user.deletion_scheduled_at.map(|dt| dt.to_rfc3339()),
+        }
+    }
+}
+
 /// MFA setup response.
 #[derive(Debug, Serialize, utoipa::ToSchema)]
 pub struct MfaSetupResponse {
@@ -207,15 +271,22 @@ pub struct UpdateProfileRequest {
     #[validate(email)]
     pub email: Option<String>,
     /// Custom status message (Some(Some("text")) = set, Some(None) = clear, None = no change).
-    /// Not yet wired to database — prepared for future custom status feature.
-    #[allow(dead_code)]
+    /// At most 128 characters; blank text clears it.
     #[serde(default, deserialize_with = "deserialize_double_option")]
     #[allow(clippy::option_option)]
     pub status_message: Option<Option<String>>,
+    /// Phonetic spelling of the display name, read out by screen readers
+    /// (e.g. in voice join announcements). Same semantics as `status_message`;
+    /// at most 64 characters.
+    #[serde(default, deserialize_with = "deserialize_double_option")]
+    #[allow(clippy::option_option)]
+    pub name_pronunciation: Option<Option<String>>,
 }
 
 #[allow(clippy::option_option)]
-fn deserialize_double_option<'de, T, D>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+pub(super) fn deserialize_double_option<'de, T, D>(
+    deserializer: D,
+) -> Result<Option<Option<T>>, D::Error>
 where
     T: Deserialize<'de>,
     D: Deserializer<'de>,
@@ -254,9 +325,10 @@ impl std::fmt::Debug for UpdatePasswordRequest {
 // ============================================================================
 
 /// Username validation regex (matches DB constraint).
-static USERNAME_REGEX: std::sync::LazyLock<regex::Regex> = std::sync::LazyLock::new(|| {
-    regex::Regex::new(r"^[a-z0-9_]{3,32}$").expect("valid username regex")
-});
+pub(super) static USERNAME_REGEX: std::sync::LazyLock<regex::Regex> =
+    std::sync::LazyLock::new(|| {
+        regex::Regex::new(r"^[a-z0-9_]{3,32}$").expect("valid username regex")
+    });
 
 // ============================================================================
 // Helper Functions
@@ -266,7 +338,7 @@ static USERNAME_REGEX: std::sync::LazyLock<regex::Regex> = std::sync::LazyLock::
 use super::hash_token;
 
 /// Extract User-Agent from headers (sanitized and truncated to 512 chars for DB storage).
-fn extract_user_agent(headers: &HeaderMap) -> Option<String> {
+pub(super) fn extract_user_agent(headers: &HeaderMap) -> Option<String> {
     headers
         .get(USER_AGENT)
         .and_then(|h| h.to_str().ok())
@@ -283,7 +355,7 @@ fn extract_user_agent(headers: &HeaderMap) -> Option<String> {
 
 #[cfg(test)]
 mod tests {
-    use super::UpdateProfileRequest;
+    use super::{email_link_target, AuthError, UpdateProfileRequest, User};
 
     #[test]
     fn update_profile_request_distinguishes_missing_and_null_status_message() {
@@ -300,6 +372,45 @@ mod tests {
             .expect("string status_message should deserialize");
         assert_eq!(value.status_message, Some(Some("In queue".to_string())));
     }
+
+    #[test]
+    fn email_link_requires_verified_local_address() {
+        let user = |email_verified_at: Option<&str>| -> User {
+            serde_json::from_value(serde_json::json!({
+                "id": uuid::Uuid::new_v4(),
+                "username": "alice",
+                "display_name": "Alice",
+                "email": "alice@example.com",
+                "password_hash": "hash",
+                "avatar_url": null,
+                "banner_url": null,
+                "about_me": null,
+                "pronouns": null,
+                "accent_color": null,
+                "status": "offline",
+                "mfa_secret": null,
+                "is_bot": false,
+                "bot_owner_id": null,
+                "deletion_requested_at": null,
+                "deletion_scheduled_at": null,
+                "suspended_at": null,
+                "suspension_reason": null,
+                "tokens_revoked_at": null,
+                "pending_approval_at": null,
+                "email_verified_at": email_verified_at,
+                "created_at": "2026-01-01T00:00:00Z",
+                "updated_at": "2026-01-01T00:00:00Z",
+            }))
+            .expect("user should deserialize")
+        };
+
+        // Registered with someone else's address: never linked (pre-hijack)
+        assert!(matches!(
+            email_link_target(user(None)),
+            Err(AuthError::OidcEmailInUse)
+        ));
+        assert!(email_link_target(user(Some("2026-01-02T00:00:00Z"))).is_ok());
+    }
 }
 
 // ============================================================================
@@ -324,7 +435,9 @@ mod tests {
     request_body = RegisterRequest,
     responses(
         (status = 200, description = "User registered successfully", body = AuthResponse),
+        (status = 202, description = "Account created, waiting for admin approval", body = RegistrationPendingResponse),
         (status = 400, description = "Bad request"),
+        (status = 403, description = "Registration closed, or registration code invalid"),
         (status = 409, description = "Username or email already taken"),
     ),
     security(()),
@@ -336,7 +449,7 @@ pub async fn register(
     headers: HeaderMap,
     jar: CookieJar,
     Json(body): Json<RegisterRequest>,
-) -> AuthResult<(CookieJar, Json<AuthResponse>)> {
+) -> AuthResult<Response> {
     // Validate input first
     body.validate()
         .map_err(|e| AuthError::Validation(e.to_string()))?;
@@ -364,13 +477,26 @@ pub async fn register(
         );
         AuthError::Internal("Server configuration error".to_string())
     })?;
-    if reg_policy != "open" {
-        // Both "closed" and "invite_only" reject direct registration
-        return Err(AuthError::RegistrationDisabled);
-    }
+    // "open" registers directly, "invite_only" takes a registration code and
+    // "approval" creates the account pending admin approval
+    let registration_code = match reg_policy {
+        "open" | "approval" => None,
+        "invite_only" => Some(
+            body.registration_code
+                .as_deref()
+                .map(|code| code.trim().to_uppercase())
+                .filter(|code| !code.is_empty())
+                .ok_or(AuthError::InvalidRegistrationCode)?,
+        ),
+        _ => return Err(AuthError::RegistrationDisabled),
+    };
+    let requires_approval = reg_policy == "approval";
 
-    // Check username uniqueness (outside transaction - UNIQUE constraint will catch races)
-    if username_exists(&state.db, &body.username).await? {
+    // Check username uniqueness (outside transaction - UNIQUE constraint will catch races).
+    // Names recently released by a rename stay held for their previous owner.
+    if username_exists(&state.db, &body.username).await?
+        || username_held(&state.db, &body.username, None, USERNAME_REDIRECT_DAYS).await?
+    {
         return Err(AuthError::UserAlreadyExists);
     }
 
@@ -429,17 +555,38 @@ pub async fn register(
             e
         })?;
     let is_first_user = user_count == 0;
+    // The first user sets up the server, so it is never held for approval
+    let pending_approval = requires_approval && !is_first_user;
+
+    // Claim one use of the registration code; rolled back with the account
+    if let Some(ref code) = registration_code {
+        let claimed: Option<Uuid> = sqlx::query_scalar(
+            r"UPDATE registration_codes SET use_count = use_count + 1
+              WHERE code = $1
+                AND (expires_at IS NULL OR expires_at > NOW())
+                AND (max_uses IS NULL OR use_count < max_uses)
+              RETURNING id",
+        )
+        .bind(code)
+        .fetch_optional(&mut *tx)
+        .await?;
+        let Some(code_id) = claimed else {
+            return Err(AuthError::InvalidRegistrationCode);
+        };
+        tracing::info!(code_id = %code_id, username = %body.username, "Registration code used");
+    }
 
     // Create user (inline to use transaction)
     let user = sqlx::query_as::<_, crate::db::User>(
-        "INSERT INTO users (username, display_name, email, password_hash, auth_method)
-         VALUES ($1, $2, $3, $4, 'local')
+        "INSERT INTO users (username, display_name, email, password_hash, pending_approval_at)
+         VALUES ($1, $2, $3, $4, CASE WHEN $5 THEN NOW() END)
          RETURNING *",
     )
     .bind(&body.username)
     .bind(display_name)
     .bind(&body.email)
     .bind(password_hash)
+    .bind(pending_approval)
     .fetch_one(&mut *tx)
     .await
     .map_err(|e| {
@@ -476,12 +623,28 @@ pub async fn register(
         );
     }
 
+    // Accounts waiting for approval get no session until an admin approves
+    if pending_approval {
+        tx.commit().await?;
+        tracing::info!(user_id = %user.id, username = %user.username, "User registered, waiting for approval");
+        if let Some(ref email) = user.email {
+            email_verification::spawn_verification(&state, user.id, &user.username, email);
+        }
+        return Ok((
+            StatusCode::ACCEPTED,
+            Json(RegistrationPendingResponse {
+                pending_approval: true,
+            }),
+        )
+            .into_response());
+    }
+
     // Generate tokens
     let tokens = generate_token_pair(
         user.id,
-        &state.config.jwt_private_key,
-        state.config.jwt_access_expiry,
-        state.config.jwt_refresh_expiry,
+        &state.config().jwt_private_key,
+        state.config().jwt_access_expiry,
+        state.config().jwt_refresh_expiry,
     )
     .map_err(|e| {
         tracing::error!(
@@ -494,7 +657,7 @@ pub async fn register(
 
     // Store refresh token session (inline to use transaction)
     let token_hash = hash_token(&tokens.refresh_token);
-    let expires_at = Utc::now() + Duration::seconds(state.config.jwt_refresh_expiry);
+    let expires_at = Utc::now() + Duration::seconds(state.config().jwt_refresh_expiry);
     let user_agent = extract_user_agent(&headers);
 
     let ip_str = Some(addr.ip().to_string());
@@ -536,12 +699,16 @@ pub async fn register(
         tracing::info!(user_id = %user.id, username = %user.username, "User registered");
     }
 
+    if let Some(ref email) = user.email {
+        email_verification::spawn_verification(&state, user.id, &user.username, email);
+    }
+
     let include_refresh_token = should_return_refresh_token(&headers);
 
     let jar = jar.add(cookies::build_refresh_cookie(
         &tokens.refresh_token,
-        state.config.jwt_refresh_expiry,
-        &state.config,
+        state.config().jwt_refresh_expiry,
+        &state.config(),
     ));
 
     Ok((
@@ -553,7 +720,49 @@ pub async fn register(
             token_type: "Bearer".to_string(),
             setup_required: !setup_complete,
         }),
-    ))
+    )
+        .into_response())
+}
+
+/// Check a TOTP code against a user's encrypted MFA secret.
+pub fn check_totp_code(
+    state: &AppState,
+    encrypted_secret: &str,
+    username: &str,
+    code: &str,
+) -> AuthResult<bool> {
+    // Get encryption key from config
+    let encryption_key = state
+        .config()
+        .mfa_encryption_key
+        .clone()
+        .ok_or_else(|| AuthError::Internal("MFA encryption not configured".to_string()))?;
+
+    // Decode encryption key from hex
+    let key_bytes = hex::decode(encryption_key)
+        .map_err(|_| AuthError::Internal("Invalid MFA encryption key".to_string()))?;
+
+    // Decrypt the secret
+    let secret_str = decrypt_mfa_secret(encrypted_secret, &key_bytes)
+        .map_err(|e| AuthError::Internal(format!("Failed to decrypt MFA secret: {e}")))?;
+
+    // Parse the secret and create TOTP instance
+    let secret = Secret::Encoded(secret_str);
+    let totp = TOTP::new(
+        Algorithm::SHA1,
+        6,
+        1,
+        30,
+        secret
+            .to_bytes()
+            .map_err(|_| AuthError::Internal("Invalid TOTP secret encoding".into()))?,
+        Some("Kaiku".to_string()),
+        username.to_string(),
+    )
+    .map_err(|e| AuthError::Internal(format!("Failed to create TOTP: {e}")))?;
+
+    totp.check_current(code)
+        .map_err(|e| AuthError::Internal(format!("Failed to verify TOTP code: {e}")))
 }
 
 /// Login with username/password.
@@ -567,7 +776,7 @@ pub async fn register(
     responses(
         (status = 200, description = "Login successful", body = AuthResponse),
         (status = 401, description = "Invalid credentials"),
-        (status = 403, description = "MFA verification required or auth method disabled"),
+        (status = 403, description = "MFA verification required, authenticator code required from a new network, or auth method disabled"),
     ),
     security(()),
 )]
@@ -580,10 +789,21 @@ pub async fn login(
     jar: CookieJar,
     Json(body): Json<LoginRequest>,
 ) -> AuthResult<(CookieJar, Json<AuthResponse>)> {
-    // Helper macro to record failed auth (if rate limiter is configured)
+    // Helper macro to record failed auth in the audit log and the rate
+    // limiter (if configured)
     // SECURITY: Fails request if rate limiter is down (fail-closed pattern)
     macro_rules! record_failed_auth {
-        () => {
+        ($user_id:expr, $reason:expr) => {
+            audit::record(
+                &state.db,
+                "auth.login_failed",
+                None,
+                $user_id,
+                Some(serde_json::json!({ "username": body.username, "reason": $reason })),
+                Some(addr.ip()),
+                &headers,
+            )
+            .await;
             if let (Some(ref rl), Some(Extension(ref nip))) = (&state.rate_limiter, &normalized_ip)
             {
                 if let Err(e) = rl.record_failed_auth(&nip.0).await {
@@ -607,7 +827,7 @@ pub async fn login(
     let user = if let Some(u) = find_user_by_username(&state.db, &body.username).await? {
         u
     } else {
-        record_failed_auth!();
+        record_failed_auth!(None, "unknown_user");
         crate::observability::metrics::record_auth_login_attempt(false);
         return Err(AuthError::InvalidCredentials);
     };
@@ -616,7 +836,7 @@ pub async fn login(
     let password_hash = if let Some(h) = user.password_hash.as_ref() {
         h
     } else {
-        record_failed_auth!();
+        record_failed_auth!(Some(user.id), "no_password");
         crate::observability::metrics::record_auth_login_attempt(false);
         return Err(AuthError::InvalidCredentials);
     };
@@ -625,88 +845,108 @@ pub async fn login(
         verify_password(&body.password, password_hash).map_err(|_| AuthError::PasswordHash)?;
 
     if !valid {
-        record_failed_auth!();
+        record_failed_auth!(Some(user.id), "invalid_password");
         crate::observability::metrics::record_auth_login_attempt(false);
         return Err(AuthError::InvalidCredentials);
     }
 
+    let new_network = login_networks::is_new_network(&state.db, user.id, addr.ip()).await?;
+
     // Check MFA if enabled
     if let Some(ref encrypted_secret) = user.mfa_secret {
-        // MFA is enabled - code is required
-        let mfa_code = body.mfa_code.as_ref().ok_or(AuthError::MfaRequired)?;
-
-        // Get encryption key from config
-        let encryption_key = state
-            .config
-            .mfa_encryption_key
-            .as_ref()
-            .ok_or_else(|| AuthError::Internal("MFA encryption not configured".to_string()))?;
-
-        // Decode encryption key from hex
-        let key_bytes = hex::decode(encryption_key)
-            .map_err(|_| AuthError::Internal("Invalid MFA encryption key".to_string()))?;
-
-        // Decrypt the secret
-        let secret_str = decrypt_mfa_secret(encrypted_secret, &key_bytes)
-            .map_err(|e| AuthError::Internal(format!("Failed to decrypt MFA secret: {e}")))?;
-
-        // Parse the secret and create TOTP instance
-        let secret = Secret::Encoded(secret_str);
-        let totp = TOTP::new(
-            Algorithm::SHA1,
-            6,
-            1,
-            30,
-            secret
-                .to_bytes()
-                .map_err(|_| AuthError::Internal("Invalid TOTP secret encoding".into()))?,
-            Some("Kaiku".to_string()),
-            user.username.clone(),
-        )
-        .map_err(|e| AuthError::Internal(format!("Failed to create TOTP: {e}")))?;
-
-        // Try TOTP code first
-        let totp_valid = totp
-            .check_current(mfa_code)
-            .map_err(|e| AuthError::Internal(format!("Failed to verify TOTP code: {e}")))?;
+        if let Some(ref assertion) = body.passkey {
+            if new_network && state.config().new_network_require_totp {
+                audit::record(
+                    &state.db,
+                    "auth.login_failed",
+                    None,
+                    Some(user.id),
+                    Some(serde_json::json!({
+                        "username": body.username,
+                        "reason": "new_network_totp_required",
+                    })),
+                    Some(addr.ip()),
+                    &headers,
+                )
+                .await;
+                return Err(AuthError::NewNetworkTotpRequired);
+            }
+            // A passkey assertion stands in for the TOTP code
+            match webauthn::verify_assertion(&state, assertion, Some(user.id)).await {
+                Ok(_) => {}
+                Err(AuthError::InvalidPasskey) => {
+                    record_failed_auth!(Some(user.id), "invalid_passkey");
+                    crate::observability::metrics::record_auth_login_attempt(false);
+                    return Err(AuthError::InvalidPasskey);
+                }
+                Err(e) => return Err(e),
+            }
+        } else {
+            // MFA is enabled - code is required
+            let mfa_code = body.mfa_code.as_ref().ok_or(AuthError::MfaRequired)?;
 
-        if !totp_valid {
-            // TOTP failed — try backup code
-            let backup_codes = get_unused_mfa_backup_codes(&state.db, user.id)
-                .await
-                .map_err(AuthError::Database)?;
+            // Try TOTP code first
+            let totp_valid = check_totp_code(&state, encrypted_secret, &user.username, mfa_code)?;
 
-            let hashes: Vec<String> = backup_codes.iter().map(|c| c.code_hash.clone()).collect();
-            if let Some(matched_idx) = find_matching_backup_code(mfa_code, &hashes) {
-                // Mark backup code as used
-                let used_code_id = backup_codes[matched_idx].id;
-                mark_mfa_backup_code_used(&state.db, used_code_id)
+            if !totp_valid {
+                // TOTP failed — try backup code
+                let backup_codes = get_unused_mfa_backup_codes(&state.db, user.id)
                     .await
                     .map_err(AuthError::Database)?;
-                tracing::info!(
-                    user_id = %user.id,
-                    code_id = %used_code_id,
-                    "MFA backup code used for login"
-                );
-            } else {
-                record_failed_auth!();
-                crate::observability::metrics::record_auth_login_attempt(false);
-                return Err(AuthError::InvalidMfaCode);
+
+                let hashes: Vec<String> =
+                    backup_codes.iter().map(|c| c.code_hash.clone()).collect();
+                if let Some(matched_idx) = find_matching_backup_code(mfa_code, &hashes) {
+                    // Mark backup code as used
+                    let used_code_id = backup_codes[matched_idx].id;
+                    mark_mfa_backup_code_used(&state.db, used_code_id)
+                        .await
+                        .map_err(AuthError::Database)?;
+                    tracing::info!(
+                        user_id = %user.id,
+                        code_id = %used_code_id,
+                        "MFA backup code used for login"
+                    );
+                } else {
+                    record_failed_auth!(Some(user.id), "invalid_mfa_code");
+                    crate::observability::metrics::record_auth_login_attempt(false);
+                    return Err(AuthError::InvalidMfaCode);
+                }
             }
         }
     }
 
+    // Suspended accounts and accounts waiting for approval cannot sign in
+    if let Err(e) = ensure_account_active(&user, None) {
+        let reason = if matches!(e, AuthError::AccountPendingApproval) {
+            "pending_approval"
+        } else {
+            "suspended"
+        };
+        audit::record(
+            &state.db,
+            "auth.login_failed",
+            None,
+            Some(user.id),
+            Some(serde_json::json!({ "username": body.username, "reason": reason })),
+            Some(addr.ip()),
+            &headers,
+        )
+        .await;
+        return Err(e);
+    }
+
     // Generate tokens
     let tokens = generate_token_pair(
         user.id,
-        &state.config.jwt_private_key,
-        state.config.jwt_access_expiry,
-        state.config.jwt_refresh_expiry,
+        &state.config().jwt_private_key,
+        state.config().jwt_access_expiry,
+        state.config().jwt_refresh_expiry,
     )?;
 
     // Store refresh token session
     let token_hash = hash_token(&tokens.refresh_token);
-    let expires_at = Utc::now() + Duration::seconds(state.config.jwt_refresh_expiry);
+    let expires_at = Utc::now() + Duration::seconds(state.config().jwt_refresh_expiry);
     let user_agent = extract_user_agent(&headers);
 
     create_session(
@@ -728,14 +968,33 @@ pub async fn login(
     let setup_complete = is_setup_complete(&state.db).await?;
 
     tracing::info!(user_id = %user.id, setup_required = !setup_complete, "User logged in");
+    let method = if body.passkey.is_some() {
+        "passkey"
+    } else {
+        "password"
+    };
+    audit::record(
+        &state.db,
+        "auth.login",
+        Some(user.id),
+        Some(user.id),
+        Some(serde_json::json!({
+            "method": method,
+            "mfa": user.mfa_secret.is_some(),
+        })),
+        Some(addr.ip()),
+        &headers,
+    )
+    .await;
+    login_networks::record_login(&state, &user, addr.ip(), &headers, method, new_network).await;
     crate::observability::metrics::record_auth_login_attempt(true);
 
     let include_refresh_token = should_return_refresh_token(&headers);
 
     let jar = jar.add(cookies::build_refresh_cookie(
         &tokens.refresh_token,
-        state.config.jwt_refresh_expiry,
-        &state.config,
+        state.config().jwt_refresh_expiry,
+        &state.config(),
     ));
 
     Ok((
@@ -780,7 +1039,7 @@ pub async fn refresh_token(
     let raw_token = extract_refresh_token(body.map(|b| b.0.refresh_token), &jar)?;
 
     // Validate the refresh token (JWT validation)
-    let claims = validate_refresh_token(&raw_token, &state.config.jwt_public_key)?;
+    let claims = validate_refresh_token(&raw_token, &state.config().jwt_public_key)?;
 
     // Parse user ID
     let user_id: Uuid = claims.sub.parse().map_err(|_| AuthError::InvalidToken)?;
@@ -815,10 +1074,11 @@ pub async fn refresh_token(
         return Err(AuthError::InvalidToken);
     }
 
-    // Verify user still exists
-    let _user = find_user_by_id(&state.db, user_id)
+    // Verify user still exists and is not suspended
+    let user = find_user_by_id(&state.db, user_id)
         .await?
         .ok_or(AuthError::UserNotFound)?;
+    ensure_account_active(&user, None)?;
 
     // Delete old session within the transaction
     sqlx::query("DELETE FROM sessions WHERE token_hash = $1")
@@ -829,14 +1089,14 @@ pub async fn refresh_token(
     // Generate new token pair
     let new_tokens = generate_token_pair(
         user_id,
-        &state.config.jwt_private_key,
-        state.config.jwt_access_expiry,
-        state.config.jwt_refresh_expiry,
+        &state.config().jwt_private_key,
+        state.config().jwt_access_expiry,
+        state.config().jwt_refresh_expiry,
     )?;
 
     // Store new refresh token session within the transaction
     let new_token_hash = hash_token(&new_tokens.refresh_token);
-    let expires_at = Utc::now() + Duration::seconds(state.config.jwt_refresh_expiry);
+    let expires_at = Utc::now() + Duration::seconds(state.config().jwt_refresh_expiry);
     let user_agent = extract_user_agent(&headers);
 
     sqlx::query(
@@ -866,8 +1126,8 @@ pub async fn refresh_token(
 
     let jar = jar.add(cookies::build_refresh_cookie(
         &new_tokens.refresh_token,
-        state.config.jwt_refresh_expiry,
-        &state.config,
+        state.config().jwt_refresh_expiry,
+        &state.config(),
     ));
 
     Ok((
@@ -919,7 +1179,7 @@ pub async fn logout(
 
     tracing::info!(user_id = %auth_user.id, "User logged out");
 
-    Ok(jar.add(cookies::build_clear_cookie(&state.config)))
+    Ok(jar.add(cookies::build_clear_cookie(&state.config())))
 }
 
 /// Get current user profile.
@@ -941,53 +1201,53 @@ pub async fn get_profile(auth_user: AuthUser) -> Json<UserProfile> {
         username: auth_user.username,
         display_name: auth_user.display_name,
         email: auth_user.email,
+        email_verified: auth_user.email_verified,
         avatar_url: auth_user.avatar_url,
+        banner_url: auth_user.banner_url,
+        about_me: auth_user.about_me,
+        pronouns: auth_user.pronouns,
+        accent_color: auth_user.accent_color,
         status: "online".to_string(),
         mfa_enabled: auth_user.mfa_enabled,
         deletion_scheduled_at: auth_user.deletion_scheduled_at.map(|dt| dt.to_rfc3339()),
     })
 }
 
-/// Upload user avatar.
+/// A validated profile image (avatar or banner) read from a multipart upload.
+pub struct ProfileImage {
+    pub data: Vec<u8>,
+    pub mime: String,
+    /// Client filename with anything but alphanumerics and dots replaced.
+    pub filename: String,
+}
+
+/// Read the `field` file of a profile image upload and validate it.
 ///
-/// POST /auth/me/avatar
-#[utoipa::path(
-    post,
-    path = "/auth/me/avatar",
-    tag = "auth",
-    request_body(content = Vec<u8>, content_type = "multipart/form-data"),
-    responses(
-        (status = 200, description = "Avatar uploaded successfully", body = UserProfile),
-    ),
-    security(("bearer_auth" = [])),
-)]
-#[tracing::instrument(skip(state, multipart), fields(user_id = %auth_user.id))]
-pub async fn upload_avatar(
-    State(state): State<AppState>,
-    auth_user: AuthUser,
-    mut multipart: Multipart,
-) -> AuthResult<Json<UserProfile>> {
-    // Check if S3 is configured
-    let s3 = state
-        .s3
-        .as_ref()
-        .ok_or_else(|| AuthError::Internal("File storage not configured".to_string()))?;
+/// Rejects files over `max_size` and anything but PNG, JPEG, GIF and WebP,
+/// detected from the content rather than the client-provided MIME type.
+pub async fn read_profile_image(
+    multipart: &mut Multipart,
+    field: &str,
+    max_size: usize,
+) -> AuthResult<ProfileImage> {
+    let mut label = field.to_string();
+    label[..1].make_ascii_uppercase();
 
     // Get the file from multipart
     let mut file_data = None;
     let mut filename = None;
     let mut content_type = None;
 
-    while let Some(field) = multipart
+    while let Some(part) = multipart
         .next_field()
         .await
         .map_err(|e| AuthError::Internal(format!("Multipart error: {e}")))?
     {
-        if field.name() == Some("avatar") {
-            filename = field.file_name().map(ToString::to_string);
-            content_type = field.content_type().map(ToString::to_string);
+        if part.name() == Some(field) {
+            filename = part.file_name().map(ToString::to_string);
+            content_type = part.content_type().map(ToString::to_string);
 
-            let data = field
+            let data = part
                 .bytes()
                 .await
                 .map_err(|e| AuthError::Internal(format!("Upload error: {e}")))?;
@@ -997,14 +1257,15 @@ pub async fn upload_avatar(
         }
     }
 
-    let data = file_data.ok_or(AuthError::Validation("No avatar file provided".to_string()))?;
+    let data =
+        file_data.ok_or_else(|| AuthError::Validation(format!("No {field} file provided")))?;
 
     // SECURITY: Validate file size before processing to prevent resource exhaustion
-    if data.len() > state.config.max_avatar_size {
+    if data.len() > max_size {
         return Err(AuthError::Validation(format!(
-            "Avatar file too large ({}). Maximum size is {}",
+            "{label} file too large ({}). Maximum size is {}",
             format_file_size(data.len()),
-            format_file_size(state.config.max_avatar_size)
+            format_file_size(max_size)
         )));
     }
 
@@ -1017,9 +1278,9 @@ pub async fn upload_avatar(
 
     // Reject SVG files (potential XSS vector via embedded JavaScript)
     if mime.contains("svg") {
-        return Err(AuthError::Validation(
-            "SVG files are not allowed for avatars".to_string(),
-        ));
+        return Err(AuthError::Validation(format!(
+            "SVG files are not allowed for {field}s"
+        )));
     }
 
     // Validate actual file content using magic bytes (don't trust client-provided MIME type)
@@ -1043,25 +1304,25 @@ pub async fn upload_avatar(
         }
     }
 
-    // Generate S3 key: avatars/{user_id}/{timestamp}_{filename}
-    let timestamp = Utc::now().timestamp();
-    let safe_filename = filename
-        .unwrap_or_else(|| "avatar.png".to_string())
+    let filename = filename
+        .unwrap_or_else(|| format!("{field}.png"))
         .replace(|c: char| !c.is_alphanumeric() && c != '.', "_");
 
-    let key = format!("avatars/{}/{}_{}", auth_user.id, timestamp, safe_filename);
-
-    // Upload to S3
-    s3.upload(&key, data.to_vec(), &mime)
-        .await
-        .map_err(|e| AuthError::Internal(format!("S3 upload failed: {e}")))?;
+    Ok(ProfileImage {
+        data: data.to_vec(),
+        mime,
+        filename,
+    })
+}
 
+/// Public URL of an uploaded object.
+pub fn storage_url(config: &Config, key: &str) -> String {
     // Construct public URL (assuming bucket is public or proxied)
-    let bucket = &state.config.s3_bucket;
-    let endpoint = &state.config.s3_endpoint;
+    let bucket = &config.s3_bucket;
+    let endpoint = &config.s3_endpoint;
 
     // Handle localhost vs cloud endpoint formatting
-    let url = if let Some(ep) = endpoint
+    if let Some(ep) = endpoint
         .as_deref()
         .filter(|s| s.contains("localhost") || s.contains("127.0.0.1"))
     {
@@ -1076,31 +1337,54 @@ pub async fn upload_avatar(
         // We assume standard path style for simplicity if no endpoint logic matches
         // or just construct a relative path if proxied
         format!("/{bucket}/{key}")
-    };
+    }
+}
+
+/// Upload user avatar.
+///
+/// POST /auth/me/avatar
+#[utoipa::path(
+    post,
+    path = "/auth/me/avatar",
+    tag = "auth",
+    request_body(content = Vec<u8>, content_type = "multipart/form-data"),
+    responses(
+        (status = 200, description = "Avatar uploaded successfully", body = UserProfile),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[tracing::instrument(skip(state, multipart), fields(user_id = %auth_user.id))]
+pub async fn upload_avatar(
+    State(state): State<AppState>,
+    auth_user: AuthUser,
+    mut multipart: Multipart,
+) -> AuthResult<Json<UserProfile>> {
+    // Check if S3 is configured
+    let s3 = state
+        .s3
+        .as_ref()
+        .ok_or_else(|| AuthError::Internal("File storage not configured".to_string()))?;
+
+    let image =
+        read_profile_image(&mut multipart, "avatar", state.config().max_avatar_size).await?;
+
+    // Generate S3 key: avatars/{user_id}/{timestamp}_{filename}
+    let timestamp = Utc::now().timestamp();
+    let key = format!("avatars/{}/{}_{}", auth_user.id, timestamp, image.filename);
+
+    // Upload to S3
+    s3.upload(&key, image.data, &image.mime)
+        .await
+        .map_err(|e| AuthError::Internal(format!("S3 upload failed: {e}")))?;
+
+    let url = storage_url(&state.config(), &key);
 
     // Update user in DB
     let user = update_user_avatar(&state.db, auth_user.id, Some(&url))
         .await
         .map_err(|e| AuthError::Internal(format!("Database update failed: {e}")))?;
 
-    // Convert status to string
-    let status_str = match user.status {
-        crate::db::UserStatus::Online => "online",
-        crate::db::UserStatus::Away => "away",
-        crate::db::UserStatus::Busy => "busy",
-        crate::db::UserStatus::Offline => "offline",
-    };
-
-    Ok(Json(UserProfile {
-        id: user.id.to_string(),
-        username: user.username,
-        display_name: user.display_name,
-        email: user.email,
-        avatar_url: user.avatar_url,
-        status: status_str.to_string(),
-        mfa_enabled: user.mfa_secret.is_some(),
-        deletion_scheduled_at: user.deletion_scheduled_at.map(|dt| dt.to_rfc3339()),
-    }))
+    Ok(Json(user.into()))
 }
 
 // ============================================================================
@@ -1111,8 +1395,9 @@ pub async fn upload_avatar(
 ///
 /// POST /auth/me
 ///
-/// Updates `display_name` and/or email, then broadcasts a patch event
-/// to all subscribers so they see the changes in real-time.
+/// Updates `display_name`, email, custom status message and/or name pronunciation,
+/// then broadcasts a patch event to all subscribers so they see the changes in real-time.
+/// A new email address has to be verified again; a code is sent to it.
 #[utoipa::path(
     post,
     path = "/auth/me",
@@ -1134,10 +1419,38 @@ pub async fn update_profile(
         .map_err(|e| AuthError::Validation(e.to_string()))?;
 
     // Check if there's anything to update
-    if body.display_name.is_none() && body.email.is_none() && body.status_message.is_none() {
+    if body.display_name.is_none()
+        && body.email.is_none()
+        && body.status_message.is_none()
+        && body.name_pronunciation.is_none()
+    {
         return Err(AuthError::Validation("No fields to update".to_string()));
     }
 
+    let status_message = body
+        .status_message
+        .as_ref()
+        .map(|m| m.as_deref().map(str::trim).filter(|m| !m.is_empty()));
+    if let Some(Some(message)) = status_message {
+        if message.chars().count() > MAX_CUSTOM_STATUS_LEN {
+            return Err(AuthError::Validation(format!(
+                "Status message must be at most {MAX_CUSTOM_STATUS_LEN} characters"
+            )));
+        }
+    }
+
+    let name_pronunciation = body
+        .name_pronunciation
+        .as_ref()
+        .map(|p| p.as_deref().map(str::trim).filter(|p| !p.is_empty()));
+    if let Some(Some(pronunciation)) = name_pronunciation {
+        if pronunciation.chars().count() > MAX_NAME_PRONUNCIATION_LEN {
+            return Err(AuthError::Validation(format!(
+                "Name pronunciation must be at most {MAX_NAME_PRONUNCIATION_LEN} characters"
+            )));
+        }
+    }
+
     // Check email uniqueness if changing email
     if let Some(ref email) = body.email {
         if email_exists(&state.db, email)
@@ -1168,6 +1481,17 @@ pub async fn update_profile(
         diff.insert("email".to_string(), serde_json::json!(email));
         updated_fields.push("email".to_string());
     }
+    if let Some(message) = status_message {
+        diff.insert("status_message".to_string(), serde_json::json!(message));
+        updated_fields.push("status_message".to_string());
+    }
+    if let Some(pronunciation) = name_pronunciation {
+        diff.insert(
+            "name_pronunciation".to_string(),
+            serde_json::json!(pronunciation),
+        );
+        updated_fields.push("name_pronunciation".to_string());
+    }
 
     // Update database
     let _updated_user = update_user_profile(
@@ -1175,10 +1499,33 @@ pub async fn update_profile(
         auth_user.id,
         body.display_name.as_deref(),
         body.email.as_ref().map(|e| Some(e.as_str())),
+        status_message,
+        name_pronunciation,
     )
     .await
     .map_err(AuthError::Database)?;
 
+    // A new address starts out unverified
+    if let Some(ref email) = body.email {
+        if auth_user.email.as_ref() != Some(email) {
+            email_verification::spawn_verification(
+                &state,
+                auth_user.id,
+                &auth_user.username,
+                email,
+            );
+        }
+    }
+
+    // Share the custom status with every replica's presence view
+    if let Some(message) = status_message {
+        if let Err(e) =
+            presence_registry::set_custom_status(&state.redis, auth_user.id, message).await
+        {
+            tracing::warn!(error = %e, user_id = %auth_user.id, "Failed to store custom status");
+        }
+    }
+
     // Broadcast patch event to subscribers
     if !diff.is_empty() {
         if let Err(e) = broadcast_user_patch(
@@ -1218,9 +1565,11 @@ pub async fn update_profile(
     ),
     security(("bearer_auth" = [])),
 )]
-#[tracing::instrument(skip(state, body), fields(user_id = %auth_user.id))]
+#[tracing::instrument(skip(state, headers, body), fields(user_id = %auth_user.id))]
 pub async fn update_password(
     State(state): State<AppState>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
     auth_user: AuthUser,
     Json(body): Json<UpdatePasswordRequest>,
 ) -> AuthResult<Json<serde_json::Value>> {
@@ -1265,6 +1614,16 @@ pub async fn update_password(
 
     tx.commit().await.map_err(AuthError::Database)?;
     tracing::info!(user_id = %auth_user.id, "Password updated and all sessions invalidated");
+    audit::record(
+        &state.db,
+        "auth.password.changed",
+        Some(auth_user.id),
+        Some(auth_user.id),
+        None,
+        Some(addr.ip()),
+        &headers,
+    )
+    .await;
 
     Ok(Json(serde_json::json!({
         "success": true,
@@ -1296,9 +1655,9 @@ pub async fn mfa_setup(
 ) -> AuthResult<Json<MfaSetupResponse>> {
     // Check if encryption key is configured
     let encryption_key = state
-        .config
+        .config()
         .mfa_encryption_key
-        .as_ref()
+        .clone()
         .ok_or_else(|| AuthError::Internal("MFA encryption not configured".to_string()))?;
 
     // Decode encryption key from hex
@@ -1370,17 +1729,19 @@ pub async fn mfa_setup(
     ),
     security(("bearer_auth" = [])),
 )]
-#[tracing::instrument(skip(state, request), fields(user_id = %auth_user.id))]
+#[tracing::instrument(skip(state, headers, request), fields(user_id = %auth_user.id))]
 pub async fn mfa_verify(
     State(state): State<AppState>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
     auth_user: AuthUser,
     Json(request): Json<MfaVerifyRequest>,
 ) -> AuthResult<Json<serde_json::Value>> {
     // Check if encryption key is configured
     let encryption_key = state
-        .config
+        .config()
         .mfa_encryption_key
-        .as_ref()
+        .clone()
         .ok_or_else(|| AuthError::Internal("MFA encryption not configured".to_string()))?;
 
     // Decode encryption key from hex
@@ -1472,6 +1833,17 @@ pub async fn mfa_verify(
 
         let redis_key = format!("mfa:pending:{}", auth_user.id);
         let _ = state.redis.del::<(), _>(&redis_key).await;
+
+        audit::record(
+            &state.db,
+            "auth.mfa.enabled",
+            Some(auth_user.id),
+            Some(auth_user.id),
+            None,
+            Some(addr.ip()),
+            &headers,
+        )
+        .await;
     }
 
     // Auto-generate backup codes only on first-time setup completion.
@@ -1518,16 +1890,24 @@ pub async fn mfa_verify(
     ),
     security(("bearer_auth" = [])),
 )]
-#[tracing::instrument(skip(state, request), fields(user_id = %auth_user.id))]
+#[tracing::instrument(skip(state, headers, request), fields(user_id = %auth_user.id))]
 pub async fn mfa_disable(
     State(state): State<AppState>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
     auth_user: AuthUser,
     Json(request): Json<MfaVerifyRequest>,
 ) -> AuthResult<Json<serde_json::Value>> {
     // Require MFA verification before disabling (security measure)
     // First verify the provided code is valid
-    let verification_result =
-        mfa_verify(State(state.clone()), auth_user.clone(), Json(request)).await;
+    let verification_result = mfa_verify(
+        State(state.clone()),
+        ConnectInfo(addr),
+        headers.clone(),
+        auth_user.clone(),
+        Json(request),
+    )
+    .await;
 
     if verification_result.is_err() {
         return Err(AuthError::InvalidMfaCode);
@@ -1543,6 +1923,17 @@ pub async fn mfa_disable(
         .await
         .map_err(|e| AuthError::Internal(format!("Failed to delete backup codes: {e}")))?;
 
+    audit::record(
+        &state.db,
+        "auth.mfa.disabled",
+        Some(auth_user.id),
+        Some(auth_user.id),
+        None,
+        Some(addr.ip()),
+        &headers,
+    )
+    .await;
+
     Ok(Json(serde_json::json!({
         "success": true,
         "message": "MFA disabled successfully"
@@ -1570,9 +1961,11 @@ pub async fn mfa_disable(
     ),
     security(("bearer_auth" = [])),
 )]
-#[tracing::instrument(skip(state), fields(user_id = %auth_user.id))]
+#[tracing::instrument(skip(state, headers), fields(user_id = %auth_user.id))]
 pub async fn mfa_generate_backup_codes(
     State(state): State<AppState>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
     auth_user: AuthUser,
 ) -> AuthResult<Json<MfaBackupCodesResponse>> {
     // Verify MFA is enabled before generating backup codes
@@ -1601,6 +1994,16 @@ pub async fn mfa_generate_backup_codes(
         count = plaintext_codes.len(),
         "MFA backup codes generated"
     );
+    audit::record(
+        &state.db,
+        "auth.mfa.backup_codes_regenerated",
+        Some(auth_user.id),
+        Some(auth_user.id),
+        None,
+        Some(addr.ip()),
+        &headers,
+    )
+    .await;
 
     Ok(Json(MfaBackupCodesResponse {
         codes: plaintext_codes,
@@ -1744,15 +2147,52 @@ pub async fn oidc_authorize(
         }
     } else {
         // Browser flow: use the server's own callback endpoint
-        let public_url = std::env::var("PUBLIC_URL").map_err(|_| {
-            tracing::error!("PUBLIC_URL env var is not set; required for OIDC browser flow");
-            AuthError::Internal("Server misconfiguration: PUBLIC_URL is not set".to_string())
-        })?;
-        format!("{public_url}/auth/oidc/callback")
+        oidc_server_callback_url()?
     };
 
+    let auth_url =
+        start_oidc_flow(&state, &provider, callback_base, OidcFlowPurpose::SignIn).await?;
+
+    tracing::info!(provider = %provider, "Redirecting to OIDC provider");
+    Ok(Redirect::temporary(&auth_url).into_response())
+}
+
+/// The server's own OIDC callback URL, used by the browser flow.
+pub(super) fn oidc_server_callback_url() -> AuthResult<String> {
+    let public_url = std::env::var("PUBLIC_URL").map_err(|_| {
+        tracing::error!("PUBLIC_URL env var is not set; required for OIDC browser flow");
+        AuthError::Internal("Server misconfiguration: PUBLIC_URL is not set".to_string())
+    })?;
+    Ok(format!("{public_url}/auth/oidc/callback"))
+}
+
+/// What the callback of an OIDC flow does with the identity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum OidcFlowPurpose {
+    /// Sign in, or sign up as the provider allows.
+    SignIn,
+    /// Link the identity to this account.
+    Link(Uuid),
+    /// Confirm a fresh provider sign-in for this account.
+    Reauthenticate(Uuid),
+}
+
+/// Build the provider authorization URL and store the flow state for the
+/// callback.
+pub(super) async fn start_oidc_flow(
+    state: &AppState,
+    provider: &str,
+    callback_base: String,
+    purpose: OidcFlowPurpose,
+) -> AuthResult<String> {
+    let oidc_manager = state
+        .oidc_manager
+        .as_ref()
+        .ok_or(AuthError::OidcNotConfigured)?;
+
+    let fresh_login = matches!(purpose, OidcFlowPurpose::Reauthenticate(_));
     let (auth_url, csrf_state, nonce, pkce_verifier) = oidc_manager
-        .generate_auth_url(&provider, &callback_base)
+        .generate_auth_url(provider, &callback_base, fresh_login)
         .await
         .map_err(|e| {
             tracing::error!(error = %e, provider = %provider, "Failed to generate OIDC auth URL");
@@ -1763,11 +2203,19 @@ pub async fn oidc_authorize(
     let state_hash = hex::encode(Sha256::digest(csrf_state.as_bytes()));
     let redis_key = format!("oidc:state:{state_hash}");
     let flow_state = OidcFlowState {
-        slug: provider.clone(),
+        slug: provider.to_string(),
         pkce_verifier,
         nonce,
         redirect_uri: callback_base,
         created_at: Utc::now().timestamp(),
+        link_user_id: match purpose {
+            OidcFlowPurpose::Link(user_id) => Some(user_id),
+            _ => None,
+        },
+        reauth_user_id: match purpose {
+            OidcFlowPurpose::Reauthenticate(user_id) => Some(user_id),
+            _ => None,
+        },
     };
 
     let flow_json =
@@ -1775,9 +2223,9 @@ pub async fn oidc_authorize(
 
     // Encrypt the flow state before storing (protects PKCE verifier at rest)
     let enc_key = state
-        .config
+        .config()
         .mfa_encryption_key
-        .as_ref()
+        .clone()
         .ok_or_else(|| AuthError::Internal("MFA encryption not configured".to_string()))?;
     let enc_key_bytes = hex::decode(enc_key)
         .map_err(|_| AuthError::Internal("Invalid MFA encryption key".to_string()))?;
@@ -1799,8 +2247,7 @@ pub async fn oidc_authorize(
             AuthError::Internal("Failed to store OIDC state".to_string())
         })?;
 
-    tracing::info!(provider = %provider, "Redirecting to OIDC provider");
-    Ok(Redirect::temporary(&auth_url).into_response())
+    Ok(auth_url)
 }
 
 /// Handle OIDC callback.
@@ -1819,9 +2266,11 @@ pub async fn oidc_authorize(
         (status = 400, description = "Invalid callback parameters"),
     ),
 )]
-#[tracing::instrument(skip(state, jar, query))]
+#[tracing::instrument(skip(state, headers, jar, query))]
 pub async fn oidc_callback(
     State(state): State<AppState>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
     jar: CookieJar,
     axum::extract::Query(query): axum::extract::Query<OidcCallbackQuery>,
 ) -> Result<Response, AuthError> {
@@ -1844,9 +2293,9 @@ pub async fn oidc_callback(
 
     // Decrypt the flow state (PKCE verifier protected at rest)
     let enc_key = state
-        .config
+        .config()
         .mfa_encryption_key
-        .as_ref()
+        .clone()
         .ok_or_else(|| AuthError::Internal("MFA encryption not configured".to_string()))?;
     let enc_key_bytes = hex::decode(enc_key)
         .map_err(|_| AuthError::Internal("Invalid MFA encryption key".to_string()))?;
@@ -1859,7 +2308,7 @@ pub async fn oidc_callback(
         serde_json::from_str(&flow_json).map_err(|e| AuthError::Internal(e.to_string()))?;
 
     // Exchange code for tokens (also verifies ID token nonce for OIDC providers)
-    let (access_token, _id_token) = oidc_manager
+    let (access_token, _id_token, auth_time) = oidc_manager
         .exchange_code(
             &flow_state.slug,
             &query.code,
@@ -1888,38 +2337,193 @@ pub async fn oidc_callback(
     // Composite external_id: "{provider_slug}:{subject}"
     let external_id = format!("{}:{}", flow_state.slug, user_info.subject);
 
-    // User resolution
-    let user = if let Some(existing) = find_user_by_external_id(&state.db, &external_id).await? {
+    // Flow started from account settings: link instead of signing in
+    if let Some(user_id) = flow_state.link_user_id {
+        let linked = link_oidc_identity(&state.db, user_id, &flow_state.slug, &external_id).await;
+        return Ok(oidc_link_result_page(&flow_state.slug, linked));
+    }
+
+    // Flow started to re-authenticate before changing sign-in methods
+    if let Some(user_id) = flow_state.reauth_user_id {
+        let reauth = finish_oidc_reauth(&state, user_id, &external_id, auth_time).await;
+        return Ok(oidc_reauth_result_page(&flow_state.slug, reauth));
+    }
+
+    let provider = oidc_manager
+        .get_provider_row(&flow_state.slug)
+        .await
+        .ok_or(AuthError::OidcProviderNotFound)?;
+
+    // User resolution. The desktop app waits on its localhost redirect, so
+    // failures are reported there rather than as a JSON error page.
+    let parsed_redirect = openidconnect::url::Url::parse(&flow_state.redirect_uri)
+        .map_err(|e| AuthError::Internal(format!("Invalid redirect URI: {e}")))?;
+    let is_localhost = matches!(
+        (parsed_redirect.scheme(), parsed_redirect.host_str()),
+        ("http", Some("localhost" | "127.0.0.1"))
+    );
+    let user = match resolve_oidc_user(&state, &provider, &user_info, &external_id)
+        .await
+        .and_then(|user| ensure_account_active(&user, None).map(|()| user))
+    {
+        Ok(user) => user,
+        Err(e) if is_localhost => {
+            tracing::warn!(error = %e, provider = %provider.slug, "OIDC sign-in refused");
+            let mut redirect_url = parsed_redirect;
+            redirect_url
+                .query_pairs_mut()
+                .append_pair("error", "login_failed")
+                .append_pair("error_description", &e.to_string());
+            return Ok(Redirect::temporary(redirect_url.as_str()).into_response());
+        }
+        Err(e) => return Err(e),
+    };
+
+    let new_network = login_networks::is_new_network(&state.db, user.id, addr.ip()).await?;
+
+    // Generate JWT token pair
+    let tokens = generate_token_pair(
+        user.id,
+        &state.config().jwt_private_key,
+        state.config().jwt_access_expiry,
+        state.config().jwt_refresh_expiry,
+    )?;
+
+    // Store session
+    let token_hash = hash_token(&tokens.refresh_token);
+    let expires_at = Utc::now() + Duration::seconds(state.config().jwt_refresh_expiry);
+    let user_agent = extract_user_agent(&headers);
+    create_session(
+        &state.db,
+        user.id,
+        &token_hash,
+        expires_at,
+        Some(&addr.ip().to_string()),
+        user_agent.as_deref(),
+    )
+    .await?;
+
+    let setup_complete = is_setup_complete(&state.db).await?;
+
+    tracing::info!(user_id = %user.id, provider = %flow_state.slug, "User logged in via OIDC");
+    audit::record(
+        &state.db,
+        "auth.login",
+        Some(user.id),
+        Some(user.id),
+        Some(serde_json::json!({ "method": "oidc", "provider": flow_state.slug })),
+        Some(addr.ip()),
+        &headers,
+    )
+    .await;
+    login_networks::record_login(&state, &user, addr.ip(), &headers, "oidc", new_network).await;
+
+    if is_localhost {
+        // Tauri flow: redirect with tokens in query params
+        let mut redirect_url = parsed_redirect;
+        redirect_url
+            .query_pairs_mut()
+            .append_pair("access_token", &tokens.access_token)
+            .append_pair("refresh_token", &tokens.refresh_token)
+            .append_pair("expires_in", &tokens.access_expires_in.to_string())
+            .append_pair("setup_required", &(!setup_complete).to_string());
+        Ok(Redirect::temporary(redirect_url.as_str()).into_response())
+    } else {
+        // Browser flow: set HttpOnly refresh cookie + return HTML with postMessage
+        let jar = jar.add(cookies::build_refresh_cookie(
+            &tokens.refresh_token,
+            state.config().jwt_refresh_expiry,
+            &state.config(),
+        ));
+
+        // JSON-encode tokens to prevent any injection via token values
+        let payload = serde_json::json!({
+            "type": "oidc-callback",
+            "access_token": tokens.access_token,
+            "expires_in": tokens.access_expires_in,
+            "setup_required": !setup_complete,
+        });
+        let html = format!(
+            r#"<!DOCTYPE html>
+<html><body><script>
+if (window.opener) {{
+    window.opener.postMessage({payload}, window.location.origin);
+    window.close();
+}} else {{
+    document.body.innerText = "Login successful. You can close this window.";
+}}
+</script></body></html>"#,
+        );
+        Ok((jar, axum::response::Html(html)).into_response())
+    }
+}
+
+/// Find the account for a provider identity, linking or creating one as the
+/// provider's policy allows.
+async fn resolve_oidc_user(
+    state: &AppState,
+    provider: &OidcProviderRow,
+    user_info: &OidcUserInfo,
+    external_id: &str,
+) -> AuthResult<User> {
+    let user = if let Some(existing) = find_user_by_external_id(&state.db, external_id).await? {
         // Existing user — login
+        if let Err(e) = touch_user_auth_method(&state.db, external_id).await {
+            tracing::warn!(error = %e, user_id = %existing.id, "Failed to record OIDC identity use");
+        }
+        existing
+    } else if let Some(existing) =
+        link_by_verified_email(state, provider, user_info, external_id).await?
+    {
         existing
     } else {
-        // New user — check registration policy (fail-closed: deny if DB unreachable)
-        let reg_policy_value = db::get_config_value(&state.db, "registration_policy")
-            .await
-            .map_err(|e| {
-                tracing::error!(
-                    error = %e,
-                    provider = %flow_state.slug,
-                    "Failed to read registration_policy config - denying OIDC registration (fail-closed)"
-                );
-                AuthError::Database(e)
-            })?;
-        let reg_policy = reg_policy_value.as_str().ok_or_else(|| {
+        // New user — check the provider's provisioning policy
+        let provisioning = Provisioning::parse(&provider.provisioning).ok_or_else(|| {
             tracing::error!(
-                actual_value = ?reg_policy_value,
-                provider = %flow_state.slug,
-                "registration_policy config value is not a string"
+                provisioning = %provider.provisioning,
+                provider = %provider.slug,
+                "Unknown OIDC provisioning policy"
             );
             AuthError::Internal("Server configuration error".to_string())
         })?;
-        if reg_policy != "open" {
-            // Both "closed" and "invite_only" reject OIDC registration
-            // (no mechanism to carry invite tokens through OIDC flow)
-            return Err(AuthError::RegistrationDisabled);
+        if provisioning == Provisioning::Never {
+            return Err(AuthError::OidcAccountNotLinked);
+        }
+        if provisioning == Provisioning::RegistrationPolicy {
+            // Fail-closed: deny if DB unreachable
+            let reg_policy_value = db::get_config_value(&state.db, "registration_policy")
+                .await
+                .map_err(|e| {
+                    tracing::error!(
+                        error = %e,
+                        provider = %provider.slug,
+                        "Failed to read registration_policy config - denying OIDC registration (fail-closed)"
+                    );
+                    AuthError::Database(e)
+                })?;
+            let reg_policy = reg_policy_value.as_str().ok_or_else(|| {
+                tracing::error!(
+                    actual_value = ?reg_policy_value,
+                    provider = %provider.slug,
+                    "registration_policy config value is not a string"
+                );
+                AuthError::Internal("Server configuration error".to_string())
+            })?;
+            if !provisioning.allows_signup(reg_policy) {
+                return Err(AuthError::RegistrationDisabled);
+            }
+        }
+
+        // The email belongs to an account this identity may not be matched to;
+        // its owner has to link the provider from their settings instead
+        if let Some(ref email) = user_info.email {
+            if find_user_by_email(&state.db, email).await?.is_some() {
+                return Err(AuthError::OidcEmailInUse);
+            }
         }
 
         // Generate username from claims
-        let base_username = generate_username_from_claims(&user_info);
+        let base_username = generate_username_from_claims(user_info);
 
         let display_name = user_info
             .name
@@ -1927,11 +2531,15 @@ pub async fn oidc_callback(
             .unwrap_or_else(|| base_username.clone());
 
         // Use a transaction for atomic first-user detection + user creation.
-        // Retry on username collision (UNIQUE constraint violation).
+        // Retry on username collision (UNIQUE constraint violation). Reserved
+        // names and names recently released by a rename are never handed out.
         let mut username = base_username;
         let mut new_user = None;
         for attempt in 0..5u8 {
-            if attempt > 0 {
+            if attempt > 0
+                || RESERVED_USERNAMES.contains(&username.as_str())
+                || username_held(&state.db, &username, None, USERNAME_REDIRECT_DAYS).await?
+            {
                 username = append_collision_suffix(&username);
             }
 
@@ -1959,20 +2567,35 @@ pub async fn oidc_callback(
             let is_first_user = user_count == 0;
 
             let insert_result = sqlx::query_as::<_, crate::db::User>(
-                "INSERT INTO users (username, display_name, email, auth_method, external_id, avatar_url)
-                 VALUES ($1, $2, $3, 'oidc', $4, $5)
+                "INSERT INTO users (username, display_name, email, avatar_url, email_verified_at)
+                 VALUES ($1, $2, $3, $4, CASE WHEN $5 THEN NOW() END)
                  RETURNING *",
             )
             .bind(&username)
             .bind(&display_name)
             .bind(&user_info.email)
-            .bind(&external_id)
             .bind(&user_info.avatar_url)
+            // The provider vouches for the address
+            .bind(user_info.email.is_some() && user_info.email_verified)
             .fetch_one(&mut *tx)
             .await;
 
             match insert_result {
                 Ok(user) => {
+                    sqlx::query(
+                        "INSERT INTO user_auth_methods (user_id, method, provider, external_id, last_used_at)
+                         VALUES ($1, 'oidc', $2, $3, NOW())",
+                    )
+                    .bind(user.id)
+                    .bind(&provider.slug)
+                    .bind(external_id)
+                    .execute(&mut *tx)
+                    .await
+                    .map_err(|e| {
+                        tracing::error!(error = %e, user_id = %user.id, "Failed to store OIDC identity");
+                        AuthError::Database(e)
+                    })?;
+
                     // Grant admin to first user
                     if is_first_user {
                         sqlx::query!(
@@ -1993,7 +2616,7 @@ pub async fn oidc_callback(
                     tracing::info!(
                         user_id = %user.id,
                         username = %user.username,
-                        provider = %flow_state.slug,
+                        provider = %provider.slug,
                         "New user registered via OIDC"
                     );
                     new_user = Some(user);
@@ -2016,69 +2639,47 @@ pub async fn oidc_callback(
         })?
     };
 
-    // Generate JWT token pair
-    let tokens = generate_token_pair(
-        user.id,
-        &state.config.jwt_private_key,
-        state.config.jwt_access_expiry,
-        state.config.jwt_refresh_expiry,
-    )?;
-
-    // Store session
-    let token_hash = hash_token(&tokens.refresh_token);
-    let expires_at = Utc::now() + Duration::seconds(state.config.jwt_refresh_expiry);
-    create_session(&state.db, user.id, &token_hash, expires_at, None, None).await?;
-
-    let setup_complete = is_setup_complete(&state.db).await?;
+    Ok(user)
+}
 
-    tracing::info!(user_id = %user.id, provider = %flow_state.slug, "User logged in via OIDC");
+/// Attach an identity no account is linked to yet to the account with the
+/// same email, if the provider links by email and both sides verified the
+/// address.
+async fn link_by_verified_email(
+    state: &AppState,
+    provider: &OidcProviderRow,
+    user_info: &OidcUserInfo,
+    external_id: &str,
+) -> AuthResult<Option<User>> {
+    let Some(email) = linkable_email(provider, user_info) else {
+        return Ok(None);
+    };
+    let Some(user) = find_user_by_email(&state.db, email).await? else {
+        return Ok(None);
+    };
+    let user = email_link_target(user)?;
 
-    // Check if redirect_uri is a localhost callback (Tauri flow)
-    let parsed_redirect = openidconnect::url::Url::parse(&flow_state.redirect_uri)
-        .map_err(|e| AuthError::Internal(format!("Invalid redirect URI: {e}")))?;
-    let is_localhost = matches!(
-        (parsed_redirect.scheme(), parsed_redirect.host_str()),
-        ("http", Some("localhost" | "127.0.0.1"))
+    link_oidc_identity(&state.db, user.id, &provider.slug, external_id).await?;
+    if let Err(e) = touch_user_auth_method(&state.db, external_id).await {
+        tracing::warn!(error = %e, user_id = %user.id, "Failed to record OIDC identity use");
+    }
+    tracing::info!(
+        user_id = %user.id,
+        provider = %provider.slug,
+        "OIDC identity linked to existing account by verified email"
     );
+    Ok(Some(user))
+}
 
-    if is_localhost {
-        // Tauri flow: redirect with tokens in query params
-        let mut redirect_url = parsed_redirect;
-        redirect_url
-            .query_pairs_mut()
-            .append_pair("access_token", &tokens.access_token)
-            .append_pair("refresh_token", &tokens.refresh_token)
-            .append_pair("expires_in", &tokens.access_expires_in.to_string())
-            .append_pair("setup_required", &(!setup_complete).to_string());
-        Ok(Redirect::temporary(redirect_url.as_str()).into_response())
-    } else {
-        // Browser flow: set HttpOnly refresh cookie + return HTML with postMessage
-        let jar = jar.add(cookies::build_refresh_cookie(
-            &tokens.refresh_token,
-            state.config.jwt_refresh_expiry,
-            &state.config,
-        ));
-
-        // JSON-encode tokens to prevent any injection via token values
-        let payload = serde_json::json!({
-            "type": "oidc-callback",
-            "access_token": tokens.access_token,
-            "expires_in": tokens.access_expires_in,
-            "setup_required": !setup_complete,
-        });
-        let html = format!(
-            r#"<!DOCTYPE html>
-<html><body><script>
-if (window.opener) {{
-    window.opener.postMessage({payload}, window.location.origin);
-    window.close();
-}} else {{
-    document.body.innerText = "Login successful. You can close this window.";
-}}
-</script></body></html>"#,
-        );
-        Ok((jar, axum::response::Html(html)).into_response())
+/// The account an identity may be linked to by email: only one whose owner
+/// verified the address. Otherwise anyone could register with someone else's
+/// email and receive their identity on its first sign-in; the owner has to
+/// link the provider from their settings instead.
+fn email_link_target(user: User) -> AuthResult<User> {
+    if user.email_verified_at.is_none() {
+        return Err(AuthError::OidcEmailInUse);
     }
+    Ok(user)
 }
 
 // ============================================================================
@@ -2101,6 +2702,71 @@ pub struct ResetPasswordRequest {
     pub new_password: String,
 }
 
+/// Create a one-hour password reset token for `user` and email it to `email`.
+///
+/// Earlier reset tokens are invalidated. The new token is removed again if
+/// the email cannot be sent. Shared by `forgot_password` and the admin
+/// password reset action.
+pub async fn issue_password_reset(state: &AppState, user: &User, email: &str) -> AuthResult<()> {
+    use base64::Engine;
+    use rand::RngCore;
+
+    let email_service = state.email.as_ref().ok_or(AuthError::EmailNotConfigured)?;
+
+    // Invalidate existing tokens for this user — abort if this fails to prevent token accumulation
+    invalidate_user_reset_tokens(&state.db, user.id)
+        .await
+        .map_err(|e| {
+            tracing::error!(
+                error = %e,
+                user_id = %user.id,
+                "Failed to invalidate existing reset tokens, aborting reset flow"
+            );
+            AuthError::Database(e)
+        })?;
+
+    // Generate 32 random bytes → base64url token
+    let mut token_bytes = [0u8; 32];
+    rand::thread_rng().fill_bytes(&mut token_bytes);
+    let raw_token = base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(token_bytes);
+
+    // Hash for DB storage
+    let token_hash = hash_token(&raw_token);
+    let expires_at = Utc::now() + Duration::hours(1);
+
+    create_password_reset_token(&state.db, user.id, &token_hash, expires_at)
+        .await
+        .map_err(|e| {
+            tracing::error!(error = %e, user_id = %user.id, "Failed to create password reset token");
+            AuthError::Database(e)
+        })?;
+
+    if let Err(e) = email_service
+        .send_password_reset(email, &user.username, &raw_token)
+        .await
+    {
+        tracing::error!(
+            error = %e,
+            user_id = %user.id,
+            "Failed to send password reset email"
+        );
+        // Clean up the orphaned token since the user never received it
+        if let Err(cleanup_err) = invalidate_user_reset_tokens(&state.db, user.id).await {
+            tracing::error!(
+                error = %cleanup_err,
+                user_id = %user.id,
+                "Failed to clean up orphaned password reset token after email failure"
+            );
+        }
+        return Err(AuthError::Internal(
+            "Failed to send password reset email".to_string(),
+        ));
+    }
+
+    tracing::info!(user_id = %user.id, "Password reset email sent");
+    Ok(())
+}
+
 /// Request a password reset email.
 ///
 /// Always returns 200 with a generic message to prevent user enumeration.
@@ -2124,7 +2790,9 @@ pub async fn forgot_password(
     Json(body): Json<ForgotPasswordRequest>,
 ) -> AuthResult<Json<serde_json::Value>> {
     // Check if email service is configured
-    let email_service = state.email.as_ref().ok_or(AuthError::EmailNotConfigured)?;
+    if state.email.is_none() {
+        return Err(AuthError::EmailNotConfigured);
+    }
 
     // Basic email format validation
     if !body.email.contains('@') || body.email.len() < 5 {
@@ -2151,69 +2819,24 @@ pub async fn forgot_password(
         }
     };
 
-    // Only allow password reset for local auth users
-    if user.auth_method != crate::db::AuthMethod::Local {
+    // Only allow password reset for accounts with a linked password; others
+    // add one through account linking, which requires re-authentication
+    if user.password_hash.is_none() {
         return Ok(Json(serde_json::json!({
             "message": "If an account with that email exists, a reset code has been sent."
         })));
     }
 
-    // Invalidate existing tokens for this user — abort if this fails to prevent token accumulation
-    if let Err(e) = invalidate_user_reset_tokens(&state.db, user.id).await {
-        tracing::error!(
-            error = %e,
-            user_id = %user.id,
-            "Failed to invalidate existing reset tokens, aborting reset flow"
-        );
+    // Optionally only trust addresses the user proved they own
+    if state.config().password_reset_requires_verified_email && user.email_verified_at.is_none() {
+        tracing::info!(user_id = %user.id, "Password reset skipped for unverified email");
         return Ok(Json(serde_json::json!({
             "message": "If an account with that email exists, a reset code has been sent."
         })));
     }
 
-    // Generate 32 random bytes → base64url token
-    use base64::Engine;
-    use rand::RngCore;
-
-    let mut token_bytes = [0u8; 32];
-    rand::thread_rng().fill_bytes(&mut token_bytes);
-    let raw_token = base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(token_bytes);
-
-    // Hash for DB storage
-    let token_hash = hash_token(&raw_token);
-    let expires_at = Utc::now() + Duration::hours(1);
-
-    // Insert token into DB — catch DB errors to prevent enumeration via 500 responses
-    if let Err(e) = create_password_reset_token(&state.db, user.id, &token_hash, expires_at).await {
-        tracing::error!(error = %e, user_id = %user.id, "Failed to create password reset token");
-        return Ok(Json(serde_json::json!({
-            "message": "If an account with that email exists, a reset code has been sent."
-        })));
-    }
-
-    // Send email — log warning on failure, return same generic response to prevent enumeration
-    match email_service
-        .send_password_reset(&body.email, &user.username, &raw_token)
-        .await
-    {
-        Ok(()) => {
-            tracing::info!(user_id = %user.id, "Password reset email sent");
-        }
-        Err(e) => {
-            tracing::error!(
-                error = %e,
-                user_id = %user.id,
-                "Failed to send password reset email"
-            );
-            // Clean up the orphaned token since the user never received it
-            if let Err(cleanup_err) = invalidate_user_reset_tokens(&state.db, user.id).await {
-                tracing::error!(
-                    error = %cleanup_err,
-                    user_id = %user.id,
-                    "Failed to clean up orphaned password reset token after email failure"
-                );
-            }
-        }
-    }
+    // Errors are logged by the helper; the response stays generic
+    let _ = issue_password_reset(&state, &user, &body.email).await;
 
     // Always return generic message to prevent user enumeration
     Ok(Json(serde_json::json!({
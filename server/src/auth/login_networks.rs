@@ -0,0 +1,157 @@
+//! Login Networks
+//!
+//! Remembers the networks each account signs in from. A sign-in from a network
+//! the account has not used before is recorded as `auth.login.new_network`,
+//! pushed to the user's connected sessions as `new_login_network` and, if
+//! SMTP is configured and `NEW_NETWORK_LOGIN_EMAIL` is on, emailed.
+//!
+//! A network is the /24 of an IPv4 address or the /64 of an IPv6 address.
+
+use std::net::IpAddr;
+
+use axum::http::HeaderMap;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use super::audit;
+use super::error::AuthResult;
+use super::handlers::extract_user_agent;
+use crate::api::AppState;
+use crate::db::User;
+use crate::ws::{broadcast_to_user, ServerEvent};
+
+/// Network prefix of an address in CIDR notation.
+///
+/// IPv4-mapped IPv6 addresses are treated as IPv4.
+pub fn network_of(ip: IpAddr) -> String {
+    match ip.to_canonical() {
+        IpAddr::V4(v4) => {
+            let [a, b, c, _] = v4.octets();
+            format!("{a}.{b}.{c}.0/24")
+        }
+        IpAddr::V6(v6) => {
+            let seg = v6.segments();
+            format!("{:x}:{:x}:{:x}:{:x}::/64", seg[0], seg[1], seg[2], seg[3])
+        }
+    }
+}
+
+/// Whether `ip` is on a network the user has not signed in from before.
+///
+/// The first recorded sign-in of an account is never treated as new.
+pub async fn is_new_network(pool: &PgPool, user_id: Uuid, ip: IpAddr) -> AuthResult<bool> {
+    let (any, known): (bool, bool) = sqlx::query_as(
+        r"SELECT
+              EXISTS(SELECT 1 FROM user_login_networks WHERE user_id = $1),
+              EXISTS(SELECT 1 FROM user_login_networks WHERE user_id = $1 AND network = $2::cidr)",
+    )
+    .bind(user_id)
+    .bind(network_of(ip))
+    .fetch_one(pool)
+    .await?;
+
+    Ok(any && !known)
+}
+
+/// Remember the network of a successful sign-in and, if it is new, alert the
+/// user.
+///
+/// `method` is the sign-in method as recorded on `auth.login`. Never fails the
+/// sign-in: errors are logged and dropped.
+pub async fn record_login(
+    state: &AppState,
+    user: &User,
+    ip: IpAddr,
+    headers: &HeaderMap,
+    method: &str,
+    new_network: bool,
+) {
+    let network = network_of(ip);
+    let user_agent = extract_user_agent(headers);
+
+    if let Err(e) = sqlx::query(
+        r"INSERT INTO user_login_networks (user_id, network, last_ip, last_user_agent)
+          VALUES ($1, $2::cidr, $3::inet, $4)
+          ON CONFLICT (user_id, network) DO UPDATE
+          SET last_ip = EXCLUDED.last_ip,
+              last_user_agent = EXCLUDED.last_user_agent,
+              last_seen_at = NOW()",
+    )
+    .bind(user.id)
+    .bind(&network)
+    .bind(ip.to_canonical().to_string())
+    .bind(user_agent.as_deref())
+    .execute(&state.db)
+    .await
+    {
+        tracing::warn!(error = %e, user_id = %user.id, "Failed to record login network");
+    }
+
+    if !new_network {
+        return;
+    }
+
+    tracing::info!(user_id = %user.id, %network, "Sign-in from a new network");
+    audit::record(
+        &state.db,
+        "auth.login.new_network",
+        Some(user.id),
+        Some(user.id),
+        Some(serde_json::json!({ "method": method, "network": network })),
+        Some(ip),
+        headers,
+    )
+    .await;
+
+    let event = ServerEvent::NewLoginNetwork {
+        ip: ip.to_canonical().to_string(),
+        user_agent: user_agent.clone(),
+        method: method.to_string(),
+    };
+    if let Err(e) = broadcast_to_user(&state.redis, user.id, &event).await {
+        tracing::warn!(error = %e, user_id = %user.id, "Failed to publish new network alert");
+    }
+
+    if !state.config().new_network_login_email {
+        return;
+    }
+    if let (Some(email), Some(to)) = (state.email.clone(), user.email.clone()) {
+        let username = user.username.clone();
+        let user_id = user.id;
+        let ip = ip.to_canonical().to_string();
+        // Sent in the background so SMTP latency does not slow down sign-in
+        tokio::spawn(async move {
+            if let Err(e) = email
+                .send_new_network_login(&to, &username, &ip, user_agent.as_deref())
+                .await
+            {
+                tracing::warn!(error = %e, user_id = %user_id, "Failed to send new network alert email");
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::{Ipv4Addr, Ipv6Addr};
+
+    use super::*;
+
+    #[test]
+    fn ipv4_network_is_slash_24() {
+        let ip = IpAddr::V4(Ipv4Addr::new(203, 0, 113, 9));
+        assert_eq!(network_of(ip), "203.0.113.0/24");
+    }
+
+    #[test]
+    fn ipv6_network_is_slash_64() {
+        let ip = IpAddr::V6(Ipv6Addr::new(0x2001, 0xdb8, 0x85a3, 0x1234, 0, 0, 0, 1));
+        assert_eq!(network_of(ip), "2001:db8:85a3:1234::/64");
+    }
+
+    #[test]
+    fn ipv4_mapped_address_is_ipv4() {
+        let ip = IpAddr::V6(Ipv4Addr::new(198, 51, 100, 7).to_ipv6_mapped());
+        assert_eq!(network_of(ip), "198.51.100.0/24");
+    }
+}
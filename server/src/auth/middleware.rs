@@ -25,8 +25,18 @@ pub struct AuthUser {
     pub display_name: String,
     /// Email (if set).
     pub email: Option<String>,
+    /// Whether `email` has been verified.
+    pub email_verified: bool,
     /// Avatar URL (if set).
     pub avatar_url: Option<String>,
+    /// Profile banner URL (if set).
+    pub banner_url: Option<String>,
+    /// About-me text (markdown).
+    pub about_me: Option<String>,
+    /// Pronouns.
+    pub pronouns: Option<String>,
+    /// Profile accent colour (`#rrggbb`).
+    pub accent_color: Option<String>,
     /// Whether MFA is enabled.
     pub mfa_enabled: bool,
     /// When the account is scheduled for permanent deletion (if requested).
@@ -40,13 +50,39 @@ impl From<User> for AuthUser {
             username: user.username,
             display_name: user.display_name,
             email: user.email,
+            email_verified: user.email_verified_at.is_some(),
             avatar_url: user.avatar_url,
+            banner_url: user.banner_url,
+            about_me: user.about_me,
+            pronouns: user.pronouns,
+            accent_color: user.accent_color,
             mfa_enabled: user.mfa_secret.is_some(),
             deletion_scheduled_at: user.deletion_scheduled_at,
         }
     }
 }
 
+/// Reject suspended accounts, accounts waiting for admin approval, and tokens
+/// issued at or before the user's sessions were revoked by an admin.
+///
+/// `issued_at` is the token's `iat`; pass `None` when signing in.
+pub const fn ensure_account_active(user: &User, issued_at: Option<i64>) -> Result<(), AuthError> {
+    if user.suspended_at.is_some() {
+        return Err(AuthError::AccountSuspended);
+    }
+    if user.pending_approval_at.is_some() {
+        return Err(AuthError::AccountPendingApproval);
+    }
+    // `iat` has second precision, so a token from the second of the
+    // revocation counts as revoked
+    if let (Some(iat), Some(revoked_at)) = (issued_at, user.tokens_revoked_at) {
+        if iat <= revoked_at.timestamp() {
+            return Err(AuthError::InvalidToken);
+        }
+    }
+    Ok(())
+}
+
 /// Middleware to require authentication.
 ///
 /// Extracts Bearer token from Authorization header, validates JWT,
@@ -78,7 +114,7 @@ pub async fn require_auth(
         .ok_or(AuthError::InvalidAuthHeader)?;
 
     // Validate JWT
-    let claims = validate_access_token(token, &state.config.jwt_public_key)?;
+    let claims = validate_access_token(token, &state.config().jwt_public_key)?;
 
     // Parse user ID from claims
     let user_id: Uuid = claims.sub.parse().map_err(|_| AuthError::InvalidToken)?;
@@ -87,6 +123,7 @@ pub async fn require_auth(
     let user = find_user_by_id(&state.db, user_id)
         .await?
         .ok_or(AuthError::UserNotFound)?;
+    ensure_account_active(&user, Some(claims.iat))?;
 
     // Inject AuthUser into request extensions
     let auth_user = AuthUser::from(user);
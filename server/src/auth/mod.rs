@@ -2,22 +2,30 @@
 //!
 //! Handles local authentication, SSO/OIDC, MFA, and session management.
 
+pub(crate) mod audit;
+pub(crate) mod auth_methods;
 mod backup_codes;
 pub(crate) mod cookies;
+pub(crate) mod device_link;
+pub(crate) mod email_verification;
 pub(crate) mod error;
 pub(crate) mod handlers;
 pub mod jwt;
+pub(crate) mod login_networks;
 pub mod mfa_crypto;
 mod middleware;
 pub mod oidc;
 mod password;
+pub(crate) mod profile;
+pub(crate) mod username;
+pub(crate) mod webauthn;
 
 use axum::extract::DefaultBodyLimit;
-use axum::routing::{get, post};
+use axum::routing::{delete, get, post};
 use axum::{middleware as axum_middleware, Router};
 pub use error::{AuthError, AuthResult};
 pub use jwt::Claims;
-pub use middleware::{require_auth, AuthUser};
+pub use middleware::{ensure_account_active, require_auth, AuthUser};
 pub use password::{hash_password, verify_password};
 
 use crate::api::AppState;
@@ -42,9 +50,14 @@ pub fn hash_token(token: &str) -> String {
 /// - POST /refresh - Refresh access token
 /// - POST /forgot-password - Request password reset email
 /// - POST /reset-password - Reset password with token
+/// - POST /email/verify - Verify the account email with a code
 /// - GET /oidc/providers - List OIDC providers
 /// - GET /oidc/authorize/{provider} - Initiate OIDC flow
 /// - GET /oidc/callback - OIDC callback
+/// - POST /device-link - Start signing in through another device
+/// - POST /device-link/{code}/claim - Claim the session of an approved link
+/// - POST /webauthn/login/start - Start a passkey sign-in
+/// - POST /webauthn/login/finish - Finish a passkey sign-in
 ///
 /// Protected routes (auth required):
 /// - POST /logout - Invalidate session
@@ -52,10 +65,18 @@ pub fn hash_token(token: &str) -> String {
 /// - POST /me - Update profile
 /// - POST /me/password - Change password (invalidates all sessions)
 /// - POST /me/avatar - Upload avatar
+/// - POST /email/verification - Resend the email verification code
 /// - POST /mfa/setup - Setup MFA
 /// - POST /mfa/verify - Verify MFA (TOTP or backup code)
 /// - POST /mfa/disable - Disable MFA
 /// - POST /mfa/backup-codes - Generate MFA backup codes
+/// - GET /device-link/{code} - Show a link request before approving
+/// - POST /device-link/{code}/approve - Approve a link request
+/// - DELETE /device-link/{code} - Reject a link request
+/// - GET /webauthn/credentials - List passkeys
+/// - POST /webauthn/register/start - Start registering a passkey
+/// - POST /webauthn/register/finish - Finish registering a passkey
+/// - DELETE /webauthn/credentials/{id} - Remove a passkey
 pub fn router(state: AppState) -> Router<AppState> {
     // Login route with IP block check and rate limiting
     let login_route = Router::new()
@@ -145,13 +166,69 @@ pub fn router(state: AppState) -> Router<AppState> {
             RateLimitCategory::AuthPasswordReset,
         )));
 
+    // Verifying is a token guess like a password reset and gets its limit
+    let verify_email_route = Router::new()
+        .route(
+            "/email/verify",
+            post(email_verification::confirm_verification),
+        )
+        .layer(axum_middleware::from_fn_with_state(
+            state.clone(),
+            rate_limit_by_ip,
+        ))
+        .layer(axum_middleware::from_fn(with_category(
+            RateLimitCategory::AuthPasswordReset,
+        )));
+
+    // Device linking: starting is rate limited like other auth operations;
+    // claiming is polled by the waiting client, so it gets the read limit
+    let device_link_start_route = Router::new()
+        .route("/device-link", post(device_link::start_link))
+        .layer(axum_middleware::from_fn_with_state(
+            state.clone(),
+            rate_limit_by_ip,
+        ))
+        .layer(axum_middleware::from_fn(with_category(
+            RateLimitCategory::AuthOther,
+        )));
+
+    let device_link_claim_route = Router::new()
+        .route("/device-link/{code}/claim", post(device_link::claim_link))
+        .layer(axum_middleware::from_fn_with_state(
+            state.clone(),
+            rate_limit_by_ip,
+        ))
+        .layer(axum_middleware::from_fn(with_category(
+            RateLimitCategory::Read,
+        )));
+
+    // Passkey sign-in is a login attempt and gets the same protection
+    let webauthn_login_routes = Router::new()
+        .route("/webauthn/login/start", post(webauthn::start_login))
+        .route("/webauthn/login/finish", post(webauthn::finish_login))
+        .layer(axum_middleware::from_fn_with_state(
+            state.clone(),
+            rate_limit_by_ip,
+        ))
+        .layer(axum_middleware::from_fn(with_category(
+            RateLimitCategory::AuthLogin,
+        )))
+        .layer(axum_middleware::from_fn_with_state(
+            state.clone(),
+            check_ip_not_blocked,
+        ));
+
     // Merge all public routes
     let public_routes = login_route
         .merge(register_route)
         .merge(refresh_route)
         .merge(oidc_routes)
         .merge(forgot_password_route)
-        .merge(reset_password_route);
+        .merge(reset_password_route)
+        .merge(verify_email_route)
+        .merge(device_link_start_route)
+        .merge(device_link_claim_route)
+        .merge(webauthn_login_routes);
 
     // Protected routes (auth required)
     let protected_routes = Router::new()
@@ -161,8 +238,13 @@ pub fn router(state: AppState) -> Router<AppState> {
         .route("/me/password", post(handlers::update_password))
         .route(
             "/me/avatar",
-            post(handlers::upload_avatar)
-                .layer(DefaultBodyLimit::max(state.config.max_avatar_size)),
+            post(handlers::upload_avatar).layer(DefaultBodyLimit::max(
+                state.live_config.startup().max_avatar_size,
+            )),
+        )
+        .route(
+            "/email/verification",
+            post(email_verification::resend_verification),
         )
         .route("/mfa/setup", post(handlers::mfa_setup))
         .route("/mfa/verify", post(handlers::mfa_verify))
@@ -175,6 +257,27 @@ pub fn router(state: AppState) -> Router<AppState> {
             "/mfa/backup-codes/count",
             get(handlers::mfa_backup_code_count),
         )
+        .route(
+            "/device-link/{code}",
+            get(device_link::get_link).delete(device_link::reject_link),
+        )
+        .route(
+            "/device-link/{code}/approve",
+            post(device_link::approve_link),
+        )
+        .route("/webauthn/credentials", get(webauthn::list_passkeys))
+        .route(
+            "/webauthn/credentials/{id}",
+            delete(webauthn::delete_passkey),
+        )
+        .route(
+            "/webauthn/register/start",
+            post(webauthn::start_registration),
+        )
+        .route(
+            "/webauthn/register/finish",
+            post(webauthn::finish_registration),
+        )
         .layer(axum_middleware::from_fn_with_state(state, require_auth));
 
     public_routes.merge(protected_routes)
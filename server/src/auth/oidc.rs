@@ -5,8 +5,10 @@
 
 use std::collections::HashMap;
 use std::sync::LazyLock;
+use std::time::Duration;
 
-use openidconnect::core::{CoreClient, CoreProviderMetadata, CoreResponseType};
+use chrono::{DateTime, Utc};
+use openidconnect::core::{CoreAuthPrompt, CoreClient, CoreProviderMetadata, CoreResponseType};
 use openidconnect::reqwest::async_http_client;
 use openidconnect::{
     AuthenticationFlow, AuthorizationCode, ClientId, ClientSecret, CsrfToken, IssuerUrl, Nonce,
@@ -29,6 +31,8 @@ pub struct OidcUserInfo {
     pub subject: String,
     /// User's email address.
     pub email: Option<String>,
+    /// Whether the provider vouches that `email` belongs to the user.
+    pub email_verified: bool,
     /// User's display name.
     pub name: Option<String>,
     /// User's preferred username.
@@ -50,8 +54,20 @@ pub struct OidcFlowState {
     pub redirect_uri: String,
     /// When the state was created (for debugging).
     pub created_at: i64,
+    /// Account to link the identity to, when the flow was started from
+    /// `POST /api/me/auth-methods/link` rather than a sign-in.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub link_user_id: Option<Uuid>,
+    /// Account confirming a fresh sign-in, when the flow was started from
+    /// `POST /api/me/auth-methods/reauth`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reauth_user_id: Option<Uuid>,
 }
 
+/// Longest time since the user signed in at the provider that still counts
+/// as a fresh sign-in for re-authentication.
+pub const FRESH_LOGIN_MAX_AGE: Duration = Duration::from_secs(300);
+
 /// Cached provider configuration with pre-built client.
 struct CachedProvider {
     row: OidcProviderRow,
@@ -157,13 +173,27 @@ impl OidcProviderManager {
         list
     }
 
+    /// Whether the provider issues ID tokens, and so can prove when the user
+    /// last signed in (`auth_time`).
+    pub async fn supports_fresh_login(&self, slug: &str) -> bool {
+        self.providers
+            .read()
+            .await
+            .get(slug)
+            .is_some_and(|cached| cached.oidc_client.is_some())
+    }
+
     /// Generate authorization URL for a provider.
     ///
+    /// With `fresh_login`, the provider is asked to sign the user in again
+    /// (`prompt=login`, `max_age`); only OIDC discovery providers support it.
+    ///
     /// Returns (`auth_url`, state, nonce, `pkce_verifier`).
     pub async fn generate_auth_url(
         &self,
         slug: &str,
         redirect_uri: &str,
+        fresh_login: bool,
     ) -> anyhow::Result<(String, String, String, String)> {
         let providers = self.providers.read().await;
         let cached = providers
@@ -178,7 +208,7 @@ impl OidcProviderManager {
             // OIDC discovery flow
             let state_clone = state.clone();
             let nonce_clone = nonce.clone();
-            let (url, _, _) = client
+            let mut request = client
                 .authorize_url(
                     AuthenticationFlow::<CoreResponseType>::AuthorizationCode,
                     move || state_clone,
@@ -194,10 +224,18 @@ impl OidcProviderManager {
                         .scopes
                         .split_whitespace()
                         .map(|s| Scope::new(s.to_string())),
-                )
-                .url();
+                );
+            if fresh_login {
+                request = request
+                    .add_prompt(CoreAuthPrompt::Login)
+                    .set_max_age(FRESH_LOGIN_MAX_AGE);
+            }
+            let (url, _, _) = request.url();
             url.to_string()
         } else {
+            if fresh_login {
+                anyhow::bail!("Provider {slug} cannot confirm a fresh sign-in");
+            }
             // Manual OAuth2 endpoints (GitHub etc.)
             let auth_endpoint = cached
                 .row
@@ -227,7 +265,8 @@ impl OidcProviderManager {
 
     /// Exchange an authorization code for tokens.
     ///
-    /// Returns the access token and optionally an ID token (for OIDC).
+    /// Returns the access token, optionally an ID token (for OIDC) and when
+    /// the user last signed in at the provider, if the ID token says so.
     /// For OIDC discovery providers, the ID token's nonce is verified.
     pub async fn exchange_code(
         &self,
@@ -236,7 +275,7 @@ impl OidcProviderManager {
         pkce_verifier: &str,
         redirect_uri: &str,
         nonce: &str,
-    ) -> anyhow::Result<(String, Option<String>)> {
+    ) -> anyhow::Result<(String, Option<String>, Option<DateTime<Utc>>)> {
         let providers = self.providers.read().await;
         let cached = providers
             .get(slug)
@@ -257,13 +296,16 @@ impl OidcProviderManager {
                 .map_err(|e| anyhow::anyhow!("Token exchange failed: {e}"))?;
 
             // Verify ID token nonce if present
-            if let Some(id_token) = token_response.extra_fields().id_token() {
+            let auth_time = if let Some(id_token) = token_response.extra_fields().id_token() {
                 let verifier = client.id_token_verifier();
                 let expected_nonce = Nonce::new(nonce.to_string());
-                id_token
+                let claims = id_token
                     .claims(&verifier, &expected_nonce)
                     .map_err(|e| anyhow::anyhow!("ID token verification failed: {e}"))?;
-            }
+                claims.auth_time()
+            } else {
+                None
+            };
 
             let access_token = token_response.access_token().secret().clone();
             let id_token = token_response
@@ -271,7 +313,7 @@ impl OidcProviderManager {
                 .id_token()
                 .map(|t| t.to_string());
 
-            Ok((access_token, id_token))
+            Ok((access_token, id_token, auth_time))
         } else {
             // Manual OAuth2 (GitHub etc.)
             let token_url = cached
@@ -308,7 +350,7 @@ impl OidcProviderManager {
                 })?
                 .to_string();
 
-            Ok((access_token, None))
+            Ok((access_token, None, None))
         }
     }
 
@@ -353,6 +395,9 @@ impl OidcProviderManager {
         Ok(OidcUserInfo {
             subject,
             email: body["email"].as_str().map(String::from),
+            // Plain OAuth2 userinfo (e.g. GitHub) doesn't say whether the
+            // email is verified, so it is never trusted for linking
+            email_verified: body["email_verified"].as_bool().unwrap_or(false),
             name: body["name"].as_str().map(String::from),
             preferred_username: body["preferred_username"]
                 .as_str()
@@ -394,6 +439,7 @@ impl OidcProviderManager {
         Ok(OidcUserInfo {
             subject: claims.subject().to_string(),
             email: claims.email().map(|e| e.to_string()),
+            email_verified: claims.email_verified().unwrap_or(false),
             name: claims
                 .name()
                 .and_then(|n| n.get(None))
@@ -456,6 +502,8 @@ impl OidcProviderManager {
                 client_id,
                 client_secret_encrypted: &encrypted_secret,
                 scopes: "openid profile email",
+                provisioning: Provisioning::RegistrationPolicy.as_str(),
+                link_by_email: false,
                 created_by: Uuid::nil(),
             },
         )
@@ -471,6 +519,58 @@ impl OidcProviderManager {
     }
 }
 
+/// What happens when an identity that isn't linked to any account signs in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Provisioning {
+    /// Create an account if the server-wide `registration_policy` is open.
+    RegistrationPolicy,
+    /// Create an account even when registration is closed.
+    Always,
+    /// Refuse; only identities linked to an existing account may sign in.
+    Never,
+}
+
+impl Provisioning {
+    /// Parse the value stored in `oidc_providers.provisioning`.
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "registration_policy" => Some(Self::RegistrationPolicy),
+            "always" => Some(Self::Always),
+            "never" => Some(Self::Never),
+            _ => None,
+        }
+    }
+
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::RegistrationPolicy => "registration_policy",
+            Self::Always => "always",
+            Self::Never => "never",
+        }
+    }
+
+    /// Whether a new account may be created under `registration_policy`.
+    ///
+    /// Invite-only servers refuse too: an invite can't be carried through the
+    /// provider redirect.
+    pub fn allows_signup(self, registration_policy: &str) -> bool {
+        match self {
+            Self::RegistrationPolicy => registration_policy == "open",
+            Self::Always => true,
+            Self::Never => false,
+        }
+    }
+}
+
+/// The email an unlinked identity may be matched to an account by, if the
+/// provider allows it and has verified the address.
+pub fn linkable_email<'a>(provider: &OidcProviderRow, info: &'a OidcUserInfo) -> Option<&'a str> {
+    if !provider.link_by_email || !info.email_verified {
+        return None;
+    }
+    info.email.as_deref().filter(|email| !email.is_empty())
+}
+
 /// GitHub preset configuration.
 pub struct GitHubPreset;
 
@@ -574,6 +674,7 @@ mod tests {
         let info = OidcUserInfo {
             subject: "123".into(),
             email: Some("test@example.com".into()),
+            email_verified: false,
             name: Some("Test User".into()),
             preferred_username: Some("testuser".into()),
             avatar_url: None,
@@ -586,6 +687,7 @@ mod tests {
         let info = OidcUserInfo {
             subject: "123".into(),
             email: Some("test@example.com".into()),
+            email_verified: false,
             name: Some("John Doe".into()),
             preferred_username: None,
             avatar_url: None,
@@ -598,6 +700,7 @@ mod tests {
         let info = OidcUserInfo {
             subject: "123".into(),
             email: Some("jane.doe@example.com".into()),
+            email_verified: false,
             name: None,
             preferred_username: None,
             avatar_url: None,
@@ -610,6 +713,7 @@ mod tests {
         let info = OidcUserInfo {
             subject: "123".into(),
             email: None,
+            email_verified: false,
             name: None,
             preferred_username: None,
             avatar_url: None,
@@ -619,6 +723,69 @@ mod tests {
         assert!(username.len() >= 10);
     }
 
+    fn provider(link_by_email: bool) -> OidcProviderRow {
+        OidcProviderRow {
+            id: Uuid::new_v4(),
+            slug: "corp".into(),
+            display_name: "Corp SSO".into(),
+            icon_hint: None,
+            provider_type: "custom".into(),
+            issuer_url: Some("https://sso.example.com".into()),
+            authorization_url: None,
+            token_url: None,
+            userinfo_url: None,
+            client_id: "kaiku".into(),
+            client_secret_encrypted: String::new(),
+            scopes: "openid profile email".into(),
+            enabled: true,
+            position: 0,
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
+            created_by: None,
+            provisioning: "registration_policy".into(),
+            link_by_email,
+        }
+    }
+
+    #[test]
+    fn test_linkable_email_requires_opt_in_and_verification() {
+        let mut info = OidcUserInfo {
+            subject: "123".into(),
+            email: Some("alice@example.com".into()),
+            email_verified: true,
+            name: None,
+            preferred_username: None,
+            avatar_url: None,
+        };
+        assert_eq!(
+            linkable_email(&provider(true), &info),
+            Some("alice@example.com")
+        );
+        assert_eq!(linkable_email(&provider(false), &info), None);
+
+        info.email_verified = false;
+        assert_eq!(linkable_email(&provider(true), &info), None);
+    }
+
+    #[test]
+    fn test_provisioning_policy() {
+        assert_eq!(Provisioning::parse("always"), Some(Provisioning::Always));
+        assert_eq!(Provisioning::parse("sometimes"), None);
+        for p in [
+            Provisioning::RegistrationPolicy,
+            Provisioning::Always,
+            Provisioning::Never,
+        ] {
+            assert_eq!(Provisioning::parse(p.as_str()), Some(p));
+        }
+
+        assert!(Provisioning::RegistrationPolicy.allows_signup("open"));
+        assert!(!Provisioning::RegistrationPolicy.allows_signup("invite_only"));
+        assert!(!Provisioning::RegistrationPolicy.allows_signup("closed"));
+        assert!(Provisioning::Always.allows_signup("closed"));
+        assert!(!Provisioning::Never.allows_signup("open"));
+    }
+
     #[test]
     fn test_append_collision_suffix() {
         let result = append_collision_suffix("testuser");
@@ -0,0 +1,341 @@
+//! Profile Customization
+//!
+//! The parts of a user's profile card beyond name and avatar: a banner image,
+//! an about-me text, pronouns and an accent colour. The about-me text is
+//! markdown rendered by clients; raw HTML in it is stripped on save.
+//!
+//! Changes are broadcast as a user patch on the user's presence channel, so
+//! friends and guild members see them without refetching.
+
+use std::ops::Range;
+use std::sync::LazyLock;
+
+use axum::extract::{Multipart, State};
+use axum::Json;
+use chrono::Utc;
+use pulldown_cmark::{Event, Options, Parser};
+use serde::Deserialize;
+use sqlx::QueryBuilder;
+
+use super::error::{AuthError, AuthResult};
+use super::handlers::{deserialize_double_option, read_profile_image, storage_url, UserProfile};
+use super::middleware::AuthUser;
+use crate::api::AppState;
+use crate::db::User;
+use crate::ws::broadcast_user_patch;
+
+/// Maximum length of the about-me text, in characters.
+pub const MAX_ABOUT_ME_LEN: usize = 1000;
+
+/// Maximum length of the pronouns, in characters.
+pub const MAX_PRONOUNS_LEN: usize = 40;
+
+static ACCENT_COLOR_REGEX: LazyLock<regex::Regex> =
+    LazyLock::new(|| regex::Regex::new(r"^#[0-9a-fA-F]{6}$").unwrap());
+
+/// Update profile customization request.
+///
+/// Each field: `Some(Some(value))` = set, `Some(None)` = clear, absent = no change.
+#[derive(Debug, Deserialize, utoipa::ToSchema)]
+#[allow(clippy::option_option)]
+pub struct UpdateProfileCustomizationRequest {
+    /// About-me text (markdown, at most 1000 characters). Raw HTML is
+    /// stripped; blank text clears it.
+    #[serde(default, deserialize_with = "deserialize_double_option")]
+    pub about_me: Option<Option<String>>,
+    /// Pronouns (at most 40 characters); blank text clears them.
+    #[serde(default, deserialize_with = "deserialize_double_option")]
+    pub pronouns: Option<Option<String>>,
+    /// Accent colour as `#rrggbb`.
+    #[serde(default, deserialize_with = "deserialize_double_option")]
+    pub accent_color: Option<Option<String>>,
+}
+
+/// Byte ranges of raw HTML (blocks and inline tags) in a markdown text.
+fn html_ranges(text: &str) -> Vec<Range<usize>> {
+    Parser::new_ext(text, Options::all())
+        .into_offset_iter()
+        .filter(|(event, _)| matches!(event, Event::Html(_) | Event::InlineHtml(_)))
+        .map(|(_, range)| range)
+        .collect()
+}
+
+/// Strip control characters and raw HTML from an about-me text.
+///
+/// Returns `None` if nothing but whitespace is left.
+fn sanitize_about_me(raw: &str) -> AuthResult<Option<String>> {
+    let text: String = raw
+        .chars()
+        .filter(|c| !c.is_control() || *c == '\n' || *c == '\t')
+        .collect();
+
+    let mut ranges = html_ranges(&text);
+    ranges.sort_by_key(|range| range.start);
+    let mut sanitized = String::with_capacity(text.len());
+    let mut pos = 0;
+    for range in ranges {
+        if range.start > pos {
+            sanitized.push_str(&text[pos..range.start]);
+        }
+        pos = pos.max(range.end);
+    }
+    sanitized.push_str(&text[pos.min(text.len())..]);
+
+    let sanitized = sanitized.trim();
+    if sanitized.is_empty() {
+        return Ok(None);
+    }
+    if sanitized.chars().count() > MAX_ABOUT_ME_LEN {
+        return Err(AuthError::Validation(format!(
+            "About me must be at most {MAX_ABOUT_ME_LEN} characters"
+        )));
+    }
+    Ok(Some(sanitized.to_string()))
+}
+
+/// Trim and validate pronouns. Returns `None` for blank text.
+fn normalize_pronouns(raw: &str) -> AuthResult<Option<String>> {
+    let pronouns = raw.trim();
+    if pronouns.is_empty() {
+        return Ok(None);
+    }
+    if pronouns.chars().any(char::is_control) {
+        return Err(AuthError::Validation(
+            "Pronouns must not contain control characters".to_string(),
+        ));
+    }
+    if pronouns.chars().count() > MAX_PRONOUNS_LEN {
+        return Err(AuthError::Validation(format!(
+            "Pronouns must be at most {MAX_PRONOUNS_LEN} characters"
+        )));
+    }
+    Ok(Some(pronouns.to_string()))
+}
+
+/// Validate an accent colour and normalize it to lowercase `#rrggbb`.
+fn normalize_accent_color(raw: &str) -> AuthResult<String> {
+    let color = raw.trim();
+    if !ACCENT_COLOR_REGEX.is_match(color) {
+        return Err(AuthError::Validation(
+            "Accent color must be a hex color like #5865f2".to_string(),
+        ));
+    }
+    Ok(color.to_ascii_lowercase())
+}
+
+/// Broadcast changed profile fields to friends and guild members.
+async fn broadcast_profile_patch(state: &AppState, user_id: uuid::Uuid, diff: serde_json::Value) {
+    if let Err(e) = broadcast_user_patch(&state.redis, user_id, diff).await {
+        tracing::warn!(error = %e, user_id = %user_id, "Failed to broadcast profile update");
+    }
+}
+
+/// Update the current user's about-me text, pronouns and accent colour.
+///
+/// PATCH /api/me/profile
+#[utoipa::path(
+    patch,
+    path = "/api/me/profile",
+    tag = "auth",
+    request_body = UpdateProfileCustomizationRequest,
+    responses(
+        (status = 200, description = "Profile updated", body = UserProfile),
+        (status = 400, description = "Invalid field or nothing to update"),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[tracing::instrument(skip(state, body), fields(user_id = %auth_user.id))]
+pub async fn update_profile_customization(
+    State(state): State<AppState>,
+    auth_user: AuthUser,
+    Json(body): Json<UpdateProfileCustomizationRequest>,
+) -> AuthResult<Json<UserProfile>> {
+    if body.about_me.is_none() && body.pronouns.is_none() && body.accent_color.is_none() {
+        return Err(AuthError::Validation("No fields to update".to_string()));
+    }
+
+    let about_me = match body.about_me.as_ref() {
+        Some(Some(raw)) => Some(sanitize_about_me(raw)?),
+        Some(None) => Some(None),
+        None => None,
+    };
+    let pronouns = match body.pronouns.as_ref() {
+        Some(Some(raw)) => Some(normalize_pronouns(raw)?),
+        Some(None) => Some(None),
+        None => None,
+    };
+    let accent_color = match body.accent_color.as_ref() {
+        Some(Some(raw)) => Some(Some(normalize_accent_color(raw)?)),
+        Some(None) => Some(None),
+        None => None,
+    };
+
+    let mut diff = serde_json::Map::new();
+    let mut builder = QueryBuilder::new("UPDATE users SET updated_at = NOW()");
+    for (column, value) in [
+        ("about_me", about_me),
+        ("pronouns", pronouns),
+        ("accent_color", accent_color),
+    ] {
+        if let Some(value) = value {
+            builder
+                .push(", ")
+                .push(column)
+                .push(" = ")
+                .push_bind(value.clone());
+            diff.insert(column.to_string(), serde_json::json!(value));
+        }
+    }
+    builder
+        .push(" WHERE id = ")
+        .push_bind(auth_user.id)
+        .push(" RETURNING *");
+
+    let user = builder
+        .build_query_as::<User>()
+        .fetch_one(&state.db)
+        .await?;
+
+    broadcast_profile_patch(&state, auth_user.id, serde_json::Value::Object(diff)).await;
+
+    Ok(Json(user.into()))
+}
+
+/// Upload the current user's profile banner.
+///
+/// POST /api/me/profile/banner
+///
+/// Accepts the same formats and size as avatars (`max_avatar_size`).
+#[utoipa::path(
+    post,
+    path = "/api/me/profile/banner",
+    tag = "auth",
+    request_body(content = Vec<u8>, content_type = "multipart/form-data"),
+    responses(
+        (status = 200, description = "Banner uploaded", body = UserProfile),
+        (status = 400, description = "Missing, oversized or unsupported image"),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[tracing::instrument(skip(state, multipart), fields(user_id = %auth_user.id))]
+pub async fn upload_banner(
+    State(state): State<AppState>,
+    auth_user: AuthUser,
+    mut multipart: Multipart,
+) -> AuthResult<Json<UserProfile>> {
+    let s3 = state
+        .s3
+        .as_ref()
+        .ok_or_else(|| AuthError::Internal("File storage not configured".to_string()))?;
+
+    let image =
+        read_profile_image(&mut multipart, "banner", state.config().max_avatar_size).await?;
+
+    let key = format!(
+        "banners/{}/{}_{}",
+        auth_user.id,
+        Utc::now().timestamp(),
+        image.filename
+    );
+    s3.upload(&key, image.data, &image.mime)
+        .await
+        .map_err(|e| AuthError::Internal(format!("S3 upload failed: {e}")))?;
+    let url = storage_url(&state.config(), &key);
+
+    let user = set_banner_url(&state, auth_user.id, Some(&url)).await?;
+    Ok(Json(user.into()))
+}
+
+/// Remove the current user's profile banner.
+///
+/// DELETE /api/me/profile/banner
+#[utoipa::path(
+    delete,
+    path = "/api/me/profile/banner",
+    tag = "auth",
+    responses(
+        (status = 200, description = "Banner removed", body = UserProfile),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[tracing::instrument(skip(state), fields(user_id = %auth_user.id))]
+pub async fn delete_banner(
+    State(state): State<AppState>,
+    auth_user: AuthUser,
+) -> AuthResult<Json<UserProfile>> {
+    let user = set_banner_url(&state, auth_user.id, None).await?;
+    Ok(Json(user.into()))
+}
+
+async fn set_banner_url(
+    state: &AppState,
+    user_id: uuid::Uuid,
+    banner_url: Option<&str>,
+) -> AuthResult<User> {
+    let user = sqlx::query_as::<_, User>(
+        "UPDATE users SET banner_url = $1, updated_at = NOW() WHERE id = $2 RETURNING *",
+    )
+    .bind(banner_url)
+    .bind(user_id)
+    .fetch_one(&state.db)
+    .await?;
+
+    broadcast_profile_patch(
+        state,
+        user_id,
+        serde_json::json!({ "banner_url": banner_url }),
+    )
+    .await;
+
+    Ok(user)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sanitize_about_me_strips_html_and_keeps_markdown() {
+        let sanitized = sanitize_about_me(
+            "**Hi** <script>alert(1)</script> there `<b>`\n\n<div>\nblock\n</div>",
+        )
+        .unwrap()
+        .unwrap();
+        assert!(!sanitized.contains("<script>"));
+        assert!(!sanitized.contains("<div>"));
+        assert!(sanitized.starts_with("**Hi**"));
+        // Code spans are markdown, not HTML
+        assert!(sanitized.contains("`<b>`"));
+    }
+
+    #[test]
+    fn sanitize_about_me_clears_blank_and_rejects_long_text() {
+        assert_eq!(sanitize_about_me("  \n<br>  ").unwrap(), None);
+        assert!(matches!(
+            sanitize_about_me(&"x".repeat(MAX_ABOUT_ME_LEN + 1)),
+            Err(AuthError::Validation(_))
+        ));
+    }
+
+    #[test]
+    fn normalize_pronouns_trims_and_limits_length() {
+        assert_eq!(
+            normalize_pronouns(" they/them ").unwrap().as_deref(),
+            Some("they/them")
+        );
+        assert_eq!(normalize_pronouns("   ").unwrap(), None);
+        assert!(normalize_pronouns(&"x".repeat(MAX_PRONOUNS_LEN + 1)).is_err());
+        assert!(normalize_pronouns("she\u{0}her").is_err());
+    }
+
+    #[test]
+    fn normalize_accent_color_accepts_only_hex_colors() {
+        assert_eq!(normalize_accent_color("#5865F2").unwrap(), "#5865f2");
+        for raw in ["5865f2", "#fff", "#5865f2ff", "red", "#gggggg"] {
+            assert!(
+                normalize_accent_color(raw).is_err(),
+                "{raw} should be rejected"
+            );
+        }
+    }
+}
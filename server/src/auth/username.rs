@@ -0,0 +1,230 @@
+//! Username Changes
+//!
+//! Usernames are unique across the instance with no discriminator suffix, so
+//! a rename has to keep the namespace stable for everyone else:
+//!
+//! - a user may rename once per [`USERNAME_CHANGE_COOLDOWN_DAYS`];
+//! - a released name stays held for its previous owner for
+//!   [`USERNAME_REDIRECT_DAYS`], during which @mentions of it still reach them
+//!   and nobody else can claim it;
+//! - a few names that could pass for staff or system accounts, or collide
+//!   with mention keywords, are never available.
+//!
+//! Every change is recorded in `username_history`, which admins see on the
+//! user details view.
+
+use axum::extract::State;
+use axum::Json;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::error::{AuthError, AuthResult};
+use super::handlers::USERNAME_REGEX;
+use super::middleware::AuthUser;
+use crate::api::AppState;
+use crate::db::username_held;
+use crate::ws::broadcast_user_patch;
+
+/// Minimum days between two username changes.
+pub const USERNAME_CHANGE_COOLDOWN_DAYS: i32 = 30;
+
+/// Days a released username stays held for its previous owner.
+pub const USERNAME_REDIRECT_DAYS: i32 = 14;
+
+// Otherwise one user could hold several released names at once
+const _: () = assert!(USERNAME_REDIRECT_DAYS < USERNAME_CHANGE_COOLDOWN_DAYS);
+
+/// Usernames that can never be taken through a rename or an OIDC sign-up.
+pub const RESERVED_USERNAMES: &[&str] = &[
+    "admin",
+    "administrator",
+    "everyone",
+    "here",
+    "kaiku",
+    "mod",
+    "moderator",
+    "official",
+    "root",
+    "security",
+    "staff",
+    "support",
+    "system",
+];
+
+/// Change username request.
+#[derive(Debug, Deserialize, utoipa::ToSchema)]
+pub struct ChangeUsernameRequest {
+    /// New username (3-32 lowercase alphanumeric + underscore).
+    pub username: String,
+}
+
+/// Change username response.
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct ChangeUsernameResponse {
+    /// The new username.
+    pub username: String,
+    /// The username before this change.
+    pub previous_username: String,
+    /// Until when @mentions of the previous username still reach this user.
+    pub previous_username_held_until: DateTime<Utc>,
+    /// Earliest time the username can be changed again.
+    pub next_change_at: DateTime<Utc>,
+}
+
+/// Normalize and validate a requested username.
+fn validate_username(raw: &str) -> AuthResult<String> {
+    let username = raw.trim().to_lowercase();
+    if !USERNAME_REGEX.is_match(&username) {
+        return Err(AuthError::Validation(
+            "Username must be 3-32 characters of lowercase letters, digits or underscores"
+                .to_string(),
+        ));
+    }
+    if RESERVED_USERNAMES.contains(&username.as_str()) {
+        return Err(AuthError::Validation(
+            "This username is reserved".to_string(),
+        ));
+    }
+    Ok(username)
+}
+
+/// Change the current user's username.
+///
+/// PATCH /api/me/username
+#[utoipa::path(
+    patch,
+    path = "/api/me/username",
+    tag = "auth",
+    request_body = ChangeUsernameRequest,
+    responses(
+        (status = 200, description = "Username changed", body = ChangeUsernameResponse),
+        (status = 400, description = "Invalid, reserved or unchanged username"),
+        (status = 409, description = "Username is taken or held for its previous owner"),
+        (status = 429, description = "Username was changed too recently"),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[tracing::instrument(skip(state, body), fields(user_id = %auth_user.id))]
+pub async fn change_username(
+    State(state): State<AppState>,
+    auth_user: AuthUser,
+    Json(body): Json<ChangeUsernameRequest>,
+) -> AuthResult<Json<ChangeUsernameResponse>> {
+    let username = validate_username(&body.username)?;
+
+    if username_held(
+        &state.db,
+        &username,
+        Some(auth_user.id),
+        USERNAME_REDIRECT_DAYS,
+    )
+    .await?
+    {
+        return Err(AuthError::UsernameTaken);
+    }
+
+    let mut tx = state.db.begin().await?;
+
+    // Lock the user row so concurrent renames cannot both pass the cooldown
+    let previous_username: String =
+        sqlx::query_scalar("SELECT username FROM users WHERE id = $1 FOR UPDATE")
+            .bind(auth_user.id)
+            .fetch_one(&mut *tx)
+            .await?;
+    if previous_username == username {
+        return Err(AuthError::Validation(
+            "That is already your username".to_string(),
+        ));
+    }
+
+    let cooldown = Duration::days(i64::from(USERNAME_CHANGE_COOLDOWN_DAYS));
+    let last_change: Option<DateTime<Utc>> =
+        sqlx::query_scalar("SELECT MAX(changed_at) FROM username_history WHERE user_id = $1")
+            .bind(auth_user.id)
+            .fetch_one(&mut *tx)
+            .await?;
+    if let Some(last_change) = last_change {
+        let next_change_at = last_change + cooldown;
+        if next_change_at > Utc::now() {
+            return Err(AuthError::UsernameChangeCooldown(next_change_at));
+        }
+    }
+
+    let updated = sqlx::query("UPDATE users SET username = $1, updated_at = NOW() WHERE id = $2")
+        .bind(&username)
+        .bind(auth_user.id)
+        .execute(&mut *tx)
+        .await;
+    match updated {
+        Ok(_) => {}
+        Err(sqlx::Error::Database(db_err)) if db_err.is_unique_violation() => {
+            return Err(AuthError::UsernameTaken);
+        }
+        Err(e) => return Err(AuthError::Database(e)),
+    }
+
+    let changed_at: DateTime<Utc> = sqlx::query_scalar(
+        "INSERT INTO username_history (user_id, old_username, new_username)
+         VALUES ($1, $2, $3)
+         RETURNING changed_at",
+    )
+    .bind(auth_user.id)
+    .bind(&previous_username)
+    .bind(&username)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
+    tracing::info!(
+        user_id = %auth_user.id,
+        previous_username = %previous_username,
+        username = %username,
+        "Username changed"
+    );
+
+    if let Err(e) = broadcast_user_patch(
+        &state.redis,
+        auth_user.id,
+        serde_json::json!({ "username": username }),
+    )
+    .await
+    {
+        tracing::warn!(error = %e, user_id = %auth_user.id, "Failed to broadcast username change");
+    }
+
+    Ok(Json(ChangeUsernameResponse {
+        username,
+        previous_username,
+        previous_username_held_until: changed_at
+            + Duration::days(i64::from(USERNAME_REDIRECT_DAYS)),
+        next_change_at: changed_at + cooldown,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validate_username_normalizes_case_and_whitespace() {
+        assert_eq!(validate_username("  New_Name ").unwrap(), "new_name");
+    }
+
+    #[test]
+    fn validate_username_rejects_bad_format() {
+        for raw in ["ab", "has space", "dash-name", "émile", &"x".repeat(33)] {
+            assert!(
+                matches!(validate_username(raw), Err(AuthError::Validation(_))),
+                "{raw} should be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn validate_username_rejects_reserved_names() {
+        assert!(validate_username("Admin").is_err());
+        assert!(validate_username("everyone").is_err());
+        assert!(validate_username("admin_alice").is_ok());
+    }
+}
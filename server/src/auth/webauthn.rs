@@ -0,0 +1,713 @@
+//! `WebAuthn` Passkeys
+//!
+//! Passkeys sign in on their own or stand in for a TOTP code:
+//!
+//! - **Primary factor**: `POST /auth/webauthn/login/start` returns an assertion
+//!   challenge for the account's passkeys and `/login/finish` exchanges the
+//!   signed assertion for a session. Passkey ceremonies require user
+//!   verification, so this skips the TOTP step.
+//! - **Second factor**: when `/auth/login` answers `MFA_REQUIRED`, the client
+//!   runs the same ceremony and retries the login with the assertion in
+//!   `passkey` instead of an `mfa_code`.
+//!
+//! Challenges live in Redis for five minutes and are consumed on first use.
+//! Registering a passkey requires the same re-authentication as linking a
+//! sign-in method, so a password-less account without MFA must first sign in
+//! again with a provider or an existing passkey. Passkeys are only available while `WEBAUTHN_RP_ID` and
+//! `WEBAUTHN_RP_ORIGIN` are configured.
+
+use std::net::SocketAddr;
+
+use axum::extract::{ConnectInfo, Path, State};
+use axum::http::{HeaderMap, StatusCode};
+use axum::{Extension, Json};
+use axum_extra::extract::CookieJar;
+use chrono::{DateTime, Duration, Utc};
+use fred::prelude::*;
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, PgConnection, PgPool};
+use uuid::Uuid;
+use validator::Validate;
+use webauthn_rs::prelude::{
+    CreationChallengeResponse, Passkey, PasskeyAuthentication, PasskeyRegistration,
+    PublicKeyCredential, RegisterPublicKeyCredential, RequestChallengeResponse, Url, Webauthn,
+    WebauthnBuilder,
+};
+
+use super::audit;
+use super::auth_methods::{has_usable_method, reauthenticate, Reauthentication};
+use super::cookies;
+use super::error::{AuthError, AuthResult};
+use super::handlers::{extract_user_agent, should_return_refresh_token, AuthResponse};
+use super::hash_token;
+use super::jwt::generate_token_pair;
+use super::login_networks;
+use super::middleware::{ensure_account_active, AuthUser};
+use crate::api::AppState;
+use crate::config::Config;
+use crate::db::{
+    create_session, find_user_by_id, find_user_by_username, get_auth_methods_allowed,
+    is_setup_complete, UserAuthMethod,
+};
+use crate::ratelimit::NormalizedIp;
+
+/// How long a registration or assertion challenge stays valid.
+const CHALLENGE_TTL_SECS: i64 = 300;
+
+/// Maximum passkeys per account.
+const MAX_PASSKEYS_PER_USER: usize = 10;
+
+// ============================================================================
+// Types
+// ============================================================================
+
+/// A registered passkey.
+#[derive(Debug, Serialize, FromRow, utoipa::ToSchema)]
+pub struct PasskeyInfo {
+    /// Passkey ID.
+    pub id: Uuid,
+    /// Name chosen when registering (e.g., "Laptop").
+    pub name: String,
+    /// When the passkey was registered.
+    pub created_at: DateTime<Utc>,
+    /// When the passkey was last used to sign in.
+    pub last_used_at: Option<DateTime<Utc>>,
+}
+
+/// Start passkey registration request.
+#[derive(Debug, Default, Deserialize, utoipa::ToSchema)]
+pub struct StartPasskeyRegistrationRequest {
+    /// Proof of a fresh sign-in, as for linking a sign-in method.
+    #[serde(flatten)]
+    pub reauth: Reauthentication,
+}
+
+/// Registration challenge to pass to the authenticator.
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct PasskeyRegistrationChallenge {
+    /// Challenge ID to send back with the new credential.
+    pub challenge_id: Uuid,
+    /// `PublicKeyCredentialCreationOptions` for `navigator.credentials.create()`.
+    #[schema(value_type = Object)]
+    pub options: CreationChallengeResponse,
+}
+
+/// Finish passkey registration request.
+#[derive(Debug, Deserialize, Validate, utoipa::ToSchema)]
+pub struct FinishPasskeyRegistrationRequest {
+    /// Challenge ID from the start response.
+    pub challenge_id: Uuid,
+    /// Name for the passkey (1-64 characters).
+    #[validate(length(min = 1, max = 64))]
+    pub name: String,
+    /// Credential returned by the authenticator.
+    #[schema(value_type = Object)]
+    pub credential: RegisterPublicKeyCredential,
+}
+
+/// Start passkey sign-in request.
+#[derive(Debug, Deserialize, utoipa::ToSchema)]
+pub struct StartPasskeyLoginRequest {
+    /// Username of the account signing in.
+    pub username: String,
+}
+
+/// Assertion challenge to pass to the authenticator.
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct PasskeyLoginChallenge {
+    /// Challenge ID to send back with the assertion.
+    pub challenge_id: Uuid,
+    /// `PublicKeyCredentialRequestOptions` for `navigator.credentials.get()`.
+    #[schema(value_type = Object)]
+    pub options: RequestChallengeResponse,
+}
+
+/// A signed passkey assertion.
+///
+/// Finishes a passkey sign-in, or replaces `mfa_code` in `/auth/login`.
+#[derive(Debug, Deserialize, utoipa::ToSchema)]
+pub struct PasskeyAssertion {
+    /// Challenge ID from the start response.
+    pub challenge_id: Uuid,
+    /// Assertion returned by the authenticator.
+    #[schema(value_type = Object)]
+    pub credential: PublicKeyCredential,
+}
+
+/// Assertion challenge stored until the client answers it.
+#[derive(Serialize, Deserialize)]
+struct PendingAuthentication {
+    user_id: Uuid,
+    state: PasskeyAuthentication,
+}
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+fn registration_key(user_id: Uuid, challenge_id: Uuid) -> String {
+    format!("webauthn:register:{user_id}:{challenge_id}")
+}
+
+fn authentication_key(challenge_id: Uuid) -> String {
+    format!("webauthn:authenticate:{challenge_id}")
+}
+
+fn redis_error(e: &fred::error::Error) -> AuthError {
+    tracing::error!(error = %e, "WebAuthn Redis operation failed");
+    AuthError::Internal("Failed to access passkey challenge".to_string())
+}
+
+/// Build the relying party from configuration.
+fn relying_party(config: &Config) -> AuthResult<Webauthn> {
+    let (Some(rp_id), Some(rp_origin)) = (&config.webauthn_rp_id, &config.webauthn_rp_origin)
+    else {
+        return Err(AuthError::PasskeysNotConfigured);
+    };
+    let rp_origin = Url::parse(rp_origin)
+        .map_err(|e| AuthError::Internal(format!("Invalid WEBAUTHN_RP_ORIGIN: {e}")))?;
+    WebauthnBuilder::new(rp_id, &rp_origin)
+        .and_then(|builder| builder.rp_name("Kaiku").build())
+        .map_err(|e| AuthError::Internal(format!("Invalid WebAuthn configuration: {e}")))
+}
+
+/// Load an account's passkeys with their row IDs, oldest first.
+async fn load_passkeys(pool: &PgPool, user_id: Uuid) -> AuthResult<Vec<(Uuid, Passkey)>> {
+    let rows: Vec<(Uuid, sqlx::types::Json<Passkey>)> = sqlx::query_as(
+        "SELECT id, credential FROM webauthn_credentials
+         WHERE user_id = $1 ORDER BY created_at",
+    )
+    .bind(user_id)
+    .fetch_all(pool)
+    .await?;
+    Ok(rows
+        .into_iter()
+        .map(|(id, passkey)| (id, passkey.0))
+        .collect())
+}
+
+/// Whether an account has at least one passkey.
+pub(super) async fn has_passkey(conn: &mut PgConnection, user_id: Uuid) -> sqlx::Result<bool> {
+    sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM webauthn_credentials WHERE user_id = $1)")
+        .bind(user_id)
+        .fetch_one(conn)
+        .await
+}
+
+/// Verify a passkey assertion and return the account it signs in.
+///
+/// The challenge is consumed even when verification fails. With
+/// `expected_user`, assertions started for another account are rejected
+/// before checking the signature.
+pub(super) async fn verify_assertion(
+    state: &AppState,
+    assertion: &PasskeyAssertion,
+    expected_user: Option<Uuid>,
+) -> AuthResult<Uuid> {
+    let webauthn = relying_party(&state.config())?;
+    let pending: Option<String> = state
+        .redis
+        .getdel(authentication_key(assertion.challenge_id))
+        .await
+        .map_err(|e| redis_error(&e))?;
+    let pending: PendingAuthentication =
+        serde_json::from_str(&pending.ok_or(AuthError::InvalidPasskey)?)
+            .map_err(|e| AuthError::Internal(e.to_string()))?;
+    if expected_user.is_some_and(|user_id| user_id != pending.user_id) {
+        return Err(AuthError::InvalidPasskey);
+    }
+
+    let result = webauthn
+        .finish_passkey_authentication(&assertion.credential, &pending.state)
+        .map_err(|e| {
+            tracing::debug!(error = %e, user_id = %pending.user_id, "Passkey assertion rejected");
+            AuthError::InvalidPasskey
+        })?;
+
+    // Persist the new signature counter and backup state
+    let (id, mut passkey) = load_passkeys(&state.db, pending.user_id)
+        .await?
+        .into_iter()
+        .find(|(_, passkey)| passkey.cred_id() == result.cred_id())
+        .ok_or(AuthError::InvalidPasskey)?;
+    passkey.update_credential(&result);
+    sqlx::query(
+        "UPDATE webauthn_credentials SET credential = $1, last_used_at = NOW() WHERE id = $2",
+    )
+    .bind(sqlx::types::Json(&passkey))
+    .bind(id)
+    .execute(&state.db)
+    .await?;
+
+    Ok(pending.user_id)
+}
+
+// ============================================================================
+// Registration
+// ============================================================================
+
+/// List the current user's passkeys.
+///
+/// GET /auth/webauthn/credentials
+#[utoipa::path(
+    get,
+    path = "/auth/webauthn/credentials",
+    tag = "auth",
+    responses(
+        (status = 200, description = "Registered passkeys", body = Vec<PasskeyInfo>),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[tracing::instrument(skip(state), fields(user_id = %auth_user.id))]
+pub async fn list_passkeys(
+    State(state): State<AppState>,
+    auth_user: AuthUser,
+) -> AuthResult<Json<Vec<PasskeyInfo>>> {
+    let passkeys = sqlx::query_as::<_, PasskeyInfo>(
+        "SELECT id, name, created_at, last_used_at FROM webauthn_credentials
+         WHERE user_id = $1 ORDER BY created_at",
+    )
+    .bind(auth_user.id)
+    .fetch_all(&state.db)
+    .await?;
+    Ok(Json(passkeys))
+}
+
+/// Start registering a passkey for the current user.
+///
+/// POST /auth/webauthn/register/start
+#[utoipa::path(
+    post,
+    path = "/auth/webauthn/register/start",
+    tag = "auth",
+    request_body = StartPasskeyRegistrationRequest,
+    responses(
+        (status = 200, description = "Registration challenge", body = PasskeyRegistrationChallenge),
+        (status = 400, description = "Passkey limit reached or fresh sign-in required"),
+        (status = 401, description = "Re-authentication failed"),
+        (status = 403, description = "MFA code required"),
+        (status = 503, description = "Passkeys are not configured"),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[tracing::instrument(skip(state, body), fields(user_id = %auth_user.id))]
+pub async fn start_registration(
+    State(state): State<AppState>,
+    auth_user: AuthUser,
+    body: Option<Json<StartPasskeyRegistrationRequest>>,
+) -> AuthResult<Json<PasskeyRegistrationChallenge>> {
+    let Json(body) = body.unwrap_or_default();
+    let webauthn = relying_party(&state.config())?;
+
+    let user = find_user_by_id(&state.db, auth_user.id)
+        .await?
+        .ok_or(AuthError::UserNotFound)?;
+    reauthenticate(&state, &user, &body.reauth).await?;
+
+    let existing = load_passkeys(&state.db, user.id).await?;
+    if existing.len() >= MAX_PASSKEYS_PER_USER {
+        return Err(AuthError::Validation(format!(
+            "At most {MAX_PASSKEYS_PER_USER} passkeys can be registered"
+        )));
+    }
+    let exclude = existing
+        .iter()
+        .map(|(_, passkey)| passkey.cred_id().clone())
+        .collect();
+
+    let (options, registration) = webauthn
+        .start_passkey_registration(user.id, &user.username, &user.display_name, Some(exclude))
+        .map_err(|e| AuthError::Internal(format!("Failed to start passkey registration: {e}")))?;
+
+    let challenge_id = Uuid::new_v4();
+    let registration_json =
+        serde_json::to_string(&registration).map_err(|e| AuthError::Internal(e.to_string()))?;
+    state
+        .redis
+        .set::<(), _, _>(
+            registration_key(user.id, challenge_id),
+            registration_json,
+            Some(Expiration::EX(CHALLENGE_TTL_SECS)),
+            None,
+            false,
+        )
+        .await
+        .map_err(|e| redis_error(&e))?;
+
+    Ok(Json(PasskeyRegistrationChallenge {
+        challenge_id,
+        options,
+    }))
+}
+
+/// Finish registering a passkey with the authenticator's credential.
+///
+/// POST /auth/webauthn/register/finish
+#[utoipa::path(
+    post,
+    path = "/auth/webauthn/register/finish",
+    tag = "auth",
+    request_body = FinishPasskeyRegistrationRequest,
+    responses(
+        (status = 201, description = "Passkey registered", body = PasskeyInfo),
+        (status = 400, description = "Invalid name or passkey already registered"),
+        (status = 401, description = "Challenge expired or credential rejected"),
+        (status = 503, description = "Passkeys are not configured"),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[tracing::instrument(skip(state, body), fields(user_id = %auth_user.id))]
+pub async fn finish_registration(
+    State(state): State<AppState>,
+    auth_user: AuthUser,
+    Json(body): Json<FinishPasskeyRegistrationRequest>,
+) -> AuthResult<(StatusCode, Json<PasskeyInfo>)> {
+    let name = body.name.trim();
+    if name.is_empty() {
+        return Err(AuthError::Validation(
+            "Passkey name is required".to_string(),
+        ));
+    }
+    body.validate()
+        .map_err(|e| AuthError::Validation(e.to_string()))?;
+    let webauthn = relying_party(&state.config())?;
+
+    let pending: Option<String> = state
+        .redis
+        .getdel(registration_key(auth_user.id, body.challenge_id))
+        .await
+        .map_err(|e| redis_error(&e))?;
+    let registration: PasskeyRegistration =
+        serde_json::from_str(&pending.ok_or(AuthError::InvalidPasskey)?)
+            .map_err(|e| AuthError::Internal(e.to_string()))?;
+
+    let passkey = webauthn
+        .finish_passkey_registration(&body.credential, &registration)
+        .map_err(|e| {
+            tracing::debug!(error = %e, user_id = %auth_user.id, "Passkey registration rejected");
+            AuthError::InvalidPasskey
+        })?;
+
+    let inserted = sqlx::query_as::<_, PasskeyInfo>(
+        "INSERT INTO webauthn_credentials (user_id, credential_id, name, credential)
+         VALUES ($1, $2, $3, $4)
+         RETURNING id, name, created_at, last_used_at",
+    )
+    .bind(auth_user.id)
+    .bind(passkey.cred_id().as_ref())
+    .bind(name)
+    .bind(sqlx::types::Json(&passkey))
+    .fetch_one(&state.db)
+    .await;
+    let info = match inserted {
+        Ok(info) => info,
+        Err(sqlx::Error::Database(db_err)) if db_err.is_unique_violation() => {
+            return Err(AuthError::Validation(
+                "This passkey is already registered".to_string(),
+            ));
+        }
+        Err(e) => return Err(AuthError::Database(e)),
+    };
+
+    tracing::info!(user_id = %auth_user.id, passkey_id = %info.id, "Passkey registered");
+
+    Ok((StatusCode::CREATED, Json(info)))
+}
+
+/// Remove one of the current user's passkeys.
+///
+/// DELETE /auth/webauthn/credentials/{id}
+#[utoipa::path(
+    delete,
+    path = "/auth/webauthn/credentials/{id}",
+    tag = "auth",
+    params(("id" = Uuid, Path, description = "Passkey ID")),
+    responses(
+        (status = 204, description = "Passkey removed"),
+        (status = 404, description = "Passkey not found"),
+        (status = 409, description = "The passkey is the last usable sign-in method"),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[tracing::instrument(skip(state), fields(user_id = %auth_user.id))]
+pub async fn delete_passkey(
+    State(state): State<AppState>,
+    auth_user: AuthUser,
+    Path(id): Path<Uuid>,
+) -> AuthResult<StatusCode> {
+    let allowed = get_auth_methods_allowed(&state.db).await?;
+    let mut tx = state.db.begin().await?;
+
+    // Lock the user row so concurrent removals cannot both pass the check
+    let has_password: bool =
+        sqlx::query_scalar("SELECT password_hash IS NOT NULL FROM users WHERE id = $1 FOR UPDATE")
+            .bind(auth_user.id)
+            .fetch_one(&mut *tx)
+            .await?;
+    let deleted = sqlx::query("DELETE FROM webauthn_credentials WHERE id = $1 AND user_id = $2")
+        .bind(id)
+        .bind(auth_user.id)
+        .execute(&mut *tx)
+        .await?;
+    if deleted.rows_affected() == 0 {
+        return Err(AuthError::NotFound("Passkey not found".to_string()));
+    }
+    let identities = sqlx::query_as::<_, UserAuthMethod>(
+        "SELECT id, method, provider, external_id, created_at, last_used_at
+         FROM user_auth_methods WHERE user_id = $1 ORDER BY created_at",
+    )
+    .bind(auth_user.id)
+    .fetch_all(&mut *tx)
+    .await?;
+    let has_passkey = state.config().has_webauthn() && has_passkey(&mut tx, auth_user.id).await?;
+    if !has_usable_method(&allowed, has_password, &identities, has_passkey) {
+        // Dropping the transaction rolls the delete back
+        return Err(AuthError::LastAuthMethod);
+    }
+    tx.commit().await?;
+
+    tracing::info!(user_id = %auth_user.id, passkey_id = %id, "Passkey removed");
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+// ============================================================================
+// Sign-in
+// ============================================================================
+
+/// Start signing in with a passkey.
+///
+/// POST /auth/webauthn/login/start
+///
+/// Also used to answer `MFA_REQUIRED` from `/auth/login` with a passkey.
+#[utoipa::path(
+    post,
+    path = "/auth/webauthn/login/start",
+    tag = "auth",
+    request_body = StartPasskeyLoginRequest,
+    responses(
+        (status = 200, description = "Assertion challenge", body = PasskeyLoginChallenge),
+        (status = 401, description = "Unknown user or no passkey registered"),
+        (status = 503, description = "Passkeys are not configured"),
+    ),
+    security(()),
+)]
+#[tracing::instrument(skip(state, body), fields(username = %body.username))]
+pub async fn start_login(
+    State(state): State<AppState>,
+    Json(body): Json<StartPasskeyLoginRequest>,
+) -> AuthResult<Json<PasskeyLoginChallenge>> {
+    let webauthn = relying_party(&state.config())?;
+
+    let user = find_user_by_username(&state.db, &body.username)
+        .await?
+        .ok_or(AuthError::InvalidCredentials)?;
+    let passkeys: Vec<Passkey> = load_passkeys(&state.db, user.id)
+        .await?
+        .into_iter()
+        .map(|(_, passkey)| passkey)
+        .collect();
+    if passkeys.is_empty() {
+        return Err(AuthError::InvalidCredentials);
+    }
+
+    let (options, authentication) = webauthn
+        .start_passkey_authentication(&passkeys)
+        .map_err(|e| AuthError::Internal(format!("Failed to start passkey sign-in: {e}")))?;
+
+    let challenge_id = Uuid::new_v4();
+    let pending = serde_json::to_string(&PendingAuthentication {
+        user_id: user.id,
+        state: authentication,
+    })
+    .map_err(|e| AuthError::Internal(e.to_string()))?;
+    state
+        .redis
+        .set::<(), _, _>(
+            authentication_key(challenge_id),
+            pending,
+            Some(Expiration::EX(CHALLENGE_TTL_SECS)),
+            None,
+            false,
+        )
+        .await
+        .map_err(|e| redis_error(&e))?;
+
+    Ok(Json(PasskeyLoginChallenge {
+        challenge_id,
+        options,
+    }))
+}
+
+/// Finish signing in with a passkey assertion.
+///
+/// POST /auth/webauthn/login/finish
+#[utoipa::path(
+    post,
+    path = "/auth/webauthn/login/finish",
+    tag = "auth",
+    request_body = PasskeyAssertion,
+    responses(
+        (status = 200, description = "Login successful", body = AuthResponse),
+        (status = 401, description = "Challenge expired or assertion rejected"),
+        (status = 403, description = "Account suspended, or authenticator code required from a new network"),
+        (status = 503, description = "Passkeys are not configured"),
+    ),
+    security(()),
+)]
+#[tracing::instrument(skip(state, headers, jar, body, normalized_ip))]
+pub async fn finish_login(
+    State(state): State<AppState>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    normalized_ip: Option<Extension<NormalizedIp>>,
+    headers: HeaderMap,
+    jar: CookieJar,
+    Json(body): Json<PasskeyAssertion>,
+) -> AuthResult<(CookieJar, Json<AuthResponse>)> {
+    let user_id = match verify_assertion(&state, &body, None).await {
+        Ok(user_id) => user_id,
+        Err(AuthError::InvalidPasskey) => {
+            // SECURITY: Fail closed when the failed attempt cannot be recorded
+            if let (Some(rl), Some(Extension(nip))) = (&state.rate_limiter, &normalized_ip) {
+                rl.record_failed_auth(&nip.0).await.map_err(|e| {
+                    tracing::error!(error = %e, ip = ?nip.0, "SECURITY: Failed to record failed passkey sign-in");
+                    AuthError::Internal(
+                        "Authentication service temporarily unavailable. Please try again later."
+                            .to_string(),
+                    )
+                })?;
+            }
+            audit::record(
+                &state.db,
+                "auth.login_failed",
+                None,
+                None,
+                Some(serde_json::json!({ "reason": "invalid_passkey" })),
+                Some(addr.ip()),
+                &headers,
+            )
+            .await;
+            crate::observability::metrics::record_auth_login_attempt(false);
+            return Err(AuthError::InvalidPasskey);
+        }
+        Err(e) => return Err(e),
+    };
+
+    let user = find_user_by_id(&state.db, user_id)
+        .await?
+        .ok_or(AuthError::UserNotFound)?;
+    ensure_account_active(&user, None)?;
+
+    let new_network = login_networks::is_new_network(&state.db, user.id, addr.ip()).await?;
+    if new_network && state.config().new_network_require_totp && user.mfa_secret.is_some() {
+        audit::record(
+            &state.db,
+            "auth.login_failed",
+            None,
+            Some(user.id),
+            Some(serde_json::json!({ "reason": "new_network_totp_required" })),
+            Some(addr.ip()),
+            &headers,
+        )
+        .await;
+        return Err(AuthError::NewNetworkTotpRequired);
+    }
+
+    let tokens = generate_token_pair(
+        user.id,
+        &state.config().jwt_private_key,
+        state.config().jwt_access_expiry,
+        state.config().jwt_refresh_expiry,
+    )?;
+    let token_hash = hash_token(&tokens.refresh_token);
+    let expires_at = Utc::now() + Duration::seconds(state.config().jwt_refresh_expiry);
+    let user_agent = extract_user_agent(&headers);
+
+    create_session(
+        &state.db,
+        user.id,
+        &token_hash,
+        expires_at,
+        Some(&addr.ip().to_string()),
+        user_agent.as_deref(),
+    )
+    .await?;
+
+    if let (Some(rl), Some(Extension(nip))) = (&state.rate_limiter, &normalized_ip) {
+        let _ = rl.clear_failed_auth(&nip.0).await;
+    }
+
+    let setup_complete = is_setup_complete(&state.db).await?;
+
+    tracing::info!(user_id = %user.id, "User logged in with passkey");
+    audit::record(
+        &state.db,
+        "auth.login",
+        Some(user.id),
+        Some(user.id),
+        Some(serde_json::json!({ "method": "passkey" })),
+        Some(addr.ip()),
+        &headers,
+    )
+    .await;
+    login_networks::record_login(&state, &user, addr.ip(), &headers, "passkey", new_network).await;
+    crate::observability::metrics::record_auth_login_attempt(true);
+
+    let include_refresh_token = should_return_refresh_token(&headers);
+    let jar = jar.add(cookies::build_refresh_cookie(
+        &tokens.refresh_token,
+        state.config().jwt_refresh_expiry,
+        &state.config(),
+    ));
+
+    Ok((
+        jar,
+        Json(AuthResponse {
+            access_token: tokens.access_token,
+            refresh_token: include_refresh_token.then_some(tokens.refresh_token),
+            expires_in: tokens.access_expires_in,
+            token_type: "Bearer".to_string(),
+            setup_required: !setup_complete,
+        }),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn relying_party_requires_configuration() {
+        let mut config = Config::default_for_test();
+        assert!(matches!(
+            relying_party(&config),
+            Err(AuthError::PasskeysNotConfigured)
+        ));
+
+        config.webauthn_rp_id = Some("chat.example.com".to_string());
+        config.webauthn_rp_origin = Some("https://chat.example.com".to_string());
+        assert!(relying_party(&config).is_ok());
+    }
+
+    #[test]
+    fn relying_party_rejects_origin_outside_rp_id() {
+        let mut config = Config::default_for_test();
+        config.webauthn_rp_id = Some("chat.example.com".to_string());
+        config.webauthn_rp_origin = Some("https://other.example.org".to_string());
+        assert!(matches!(
+            relying_party(&config),
+            Err(AuthError::Internal(_))
+        ));
+    }
+
+    #[test]
+    fn challenge_keys_are_scoped() {
+        let user_id = Uuid::new_v4();
+        let challenge_id = Uuid::new_v4();
+        assert_ne!(
+            registration_key(user_id, challenge_id),
+            authentication_key(challenge_id)
+        );
+        assert!(registration_key(user_id, challenge_id).contains(&user_id.to_string()));
+    }
+}
@@ -0,0 +1,528 @@
+//! `Kaiku` Development Fixture Seeder
+//!
+//! Provisions realistic fixture data against a development database: users,
+//! guilds with default roles and channel overrides, message history with
+//! attachment metadata, and voice session history. Replaces the manual
+//! `create-test-users.sh` + click-through setup.
+//!
+//! ```bash
+//! cargo run --bin seed -- --scale 2
+//! cargo run --bin seed -- --reset   # remove previous fixtures first
+//! ```
+//!
+//! All fixture users are named `seed_user_NNNN` and share the password
+//! [`SEED_PASSWORD`]. Content is generated from a fixed RNG seed, so the same
+//! scale factor always produces the same shape of data. Attachments are
+//! metadata only; no objects are uploaded to S3.
+
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use sqlx::{PgPool, QueryBuilder};
+use uuid::Uuid;
+use vc_server::auth::hash_password;
+use vc_server::db::{self, ChannelType, CreateChannelParams};
+use vc_server::permissions::{
+    assign_member_role, create_default_roles, set_channel_override, GuildPermissions,
+};
+
+/// Password shared by all fixture users.
+const SEED_PASSWORD: &str = "kaiku-dev-password";
+
+/// Username prefix marking fixture users (used by `--reset`).
+const USERNAME_PREFIX: &str = "seed_user_";
+
+/// Maximum scale factor.
+const MAX_SCALE: u32 = 50;
+
+/// Rows per multi-row `INSERT`.
+const BATCH_SIZE: usize = 500;
+
+/// Days of history to spread messages and voice sessions over.
+const HISTORY_DAYS: i64 = 30;
+
+const TEXT_CHANNELS: [(&str, &str); 4] = [
+    ("general", "General discussion"),
+    ("random", "Anything goes"),
+    ("announcements", "Guild news"),
+    ("help", "Questions and answers"),
+];
+
+const VOICE_CHANNELS: [&str; 2] = ["Lobby", "Game Room"];
+
+const PHRASES: [&str; 12] = [
+    "Hey everyone!",
+    "Anyone up for a game tonight?",
+    "I just pushed the fix, can someone review?",
+    "lol",
+    "Has anyone tried the new patch yet?",
+    "Meeting moved to 18:00.",
+    "Check out this screenshot",
+    "Thanks, that worked!",
+    "Who's joining voice?",
+    "Reminder: keep it civil in here.",
+    "brb",
+    "Good morning :)",
+];
+
+const ATTACHMENTS: [(&str, &str, i64); 4] = [
+    ("screenshot.png", "image/png", 482_133),
+    ("notes.txt", "text/plain", 2_048),
+    ("clip.mp4", "video/mp4", 8_912_004),
+    ("report.pdf", "application/pdf", 153_600),
+];
+
+/// Command-line arguments.
+#[derive(Debug, PartialEq, Eq)]
+struct Args {
+    scale: u32,
+    reset: bool,
+}
+
+/// Parse `--scale N` and `--reset`.
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Args> {
+    let mut parsed = Args {
+        scale: 1,
+        reset: false,
+    };
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--reset" => parsed.reset = true,
+            "--scale" => {
+                let value = args.next().context("--scale requires a value")?;
+                parsed.scale = value
+                    .parse()
+                    .with_context(|| format!("invalid --scale value: {value}"))?;
+            }
+            "-h" | "--help" => {
+                bail!("usage: seed [--scale N] [--reset]  (N = 1-{MAX_SCALE}, default 1)")
+            }
+            other => bail!("unknown argument: {other}"),
+        }
+    }
+    if !(1..=MAX_SCALE).contains(&parsed.scale) {
+        bail!("--scale must be between 1 and {MAX_SCALE}");
+    }
+    Ok(parsed)
+}
+
+/// Fixture volumes for a scale factor.
+#[derive(Debug, PartialEq, Eq)]
+struct Plan {
+    users: usize,
+    guilds: usize,
+    members_per_guild: usize,
+    messages_per_channel: usize,
+    voice_sessions_per_guild: usize,
+}
+
+impl Plan {
+    fn for_scale(scale: u32) -> Self {
+        let scale = scale as usize;
+        let users = 20 * scale;
+        Self {
+            users,
+            guilds: 2 * scale,
+            members_per_guild: (users / 2).max(10),
+            messages_per_channel: 200 * scale,
+            voice_sessions_per_guild: 50 * scale,
+        }
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    dotenvy::dotenv().ok();
+    let args = parse_args(std::env::args().skip(1))?;
+    let database_url = std::env::var("DATABASE_URL").context("DATABASE_URL must be set")?;
+
+    let pool = db::create_pool(&database_url).await?;
+    db::run_migrations(&pool).await?;
+
+    if args.reset {
+        reset(&pool).await?;
+    } else if fixture_users_exist(&pool).await? {
+        bail!("fixture data already present; rerun with --reset to replace it");
+    }
+
+    let plan = Plan::for_scale(args.scale);
+    let mut rng = StdRng::seed_from_u64(u64::from(args.scale));
+    println!("Seeding at scale {}: {plan:?}", args.scale);
+
+    let users = seed_users(&pool, plan.users).await?;
+    for index in 0..plan.guilds {
+        seed_guild(&pool, &mut rng, &plan, &users, index).await?;
+    }
+
+    println!(
+        "Done. Log in as {USERNAME_PREFIX}0001 .. {USERNAME_PREFIX}{:04} with password '{SEED_PASSWORD}'",
+        plan.users
+    );
+    Ok(())
+}
+
+async fn fixture_users_exist(pool: &PgPool) -> Result<bool> {
+    Ok(
+        sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM users WHERE starts_with(username, $1))")
+            .bind(USERNAME_PREFIX)
+            .fetch_one(pool)
+            .await?,
+    )
+}
+
+/// Remove all fixture users, their guilds and voice history.
+async fn reset(pool: &PgPool) -> Result<()> {
+    let mut tx = pool.begin().await?;
+    let seed_users = "SELECT id FROM users WHERE starts_with(username, $1)";
+    sqlx::query(&format!(
+        "DELETE FROM connection_sessions WHERE user_id IN ({seed_users})"
+    ))
+    .bind(USERNAME_PREFIX)
+    .execute(&mut *tx)
+    .await?;
+    let guilds = sqlx::query(&format!(
+        "DELETE FROM guilds WHERE owner_id IN ({seed_users})"
+    ))
+    .bind(USERNAME_PREFIX)
+    .execute(&mut *tx)
+    .await?;
+    let users = sqlx::query("DELETE FROM users WHERE starts_with(username, $1)")
+        .bind(USERNAME_PREFIX)
+        .execute(&mut *tx)
+        .await?;
+    tx.commit().await?;
+    println!(
+        "Removed {} fixture users and {} guilds",
+        users.rows_affected(),
+        guilds.rows_affected()
+    );
+    Ok(())
+}
+
+async fn seed_users(pool: &PgPool, count: usize) -> Result<Vec<Uuid>> {
+    // Argon2 is deliberately slow; hash once and share the hash
+    let password_hash =
+        hash_password(SEED_PASSWORD).map_err(|e| anyhow::anyhow!("hash password: {e}"))?;
+
+    let mut ids = Vec::with_capacity(count);
+    for n in 1..=count {
+        let username = format!("{USERNAME_PREFIX}{n:04}");
+        let user = db::create_user(
+            pool,
+            &username,
+            &format!("Seed User {n}"),
+            Some(&format!("{username}@example.com")),
+            &password_hash,
+        )
+        .await?;
+        ids.push(user.id);
+    }
+    println!("Created {count} users");
+    Ok(ids)
+}
+
+async fn seed_guild(
+    pool: &PgPool,
+    rng: &mut StdRng,
+    plan: &Plan,
+    users: &[Uuid],
+    index: usize,
+) -> Result<()> {
+    let owner_id = users[index % users.len()];
+    let guild_id = Uuid::now_v7();
+    sqlx::query("INSERT INTO guilds (id, name, owner_id, description) VALUES ($1, $2, $3, $4)")
+        .bind(guild_id)
+        .bind(format!("Seed Guild {}", index + 1))
+        .bind(owner_id)
+        .bind("Fixture guild generated by the seed tool")
+        .execute(pool)
+        .await?;
+    create_default_roles(pool, guild_id).await?;
+
+    // Owner first, then a deterministic sample of other users
+    let mut members = vec![owner_id];
+    let mut others: Vec<Uuid> = users.iter().copied().filter(|&u| u != owner_id).collect();
+    others.shuffle(rng);
+    members.extend(others.into_iter().take(plan.members_per_guild - 1));
+
+    let mut qb = QueryBuilder::new("INSERT INTO guild_members (guild_id, user_id) ");
+    qb.push_values(&members, |mut b, user_id| {
+        b.push_bind(guild_id).push_bind(user_id);
+    });
+    qb.build().execute(pool).await?;
+
+    let roles: Vec<(Uuid, String)> =
+        sqlx::query_as("SELECT id, name FROM guild_roles WHERE guild_id = $1")
+            .bind(guild_id)
+            .fetch_all(pool)
+            .await?;
+    let role = |name: &str| roles.iter().find(|(_, n)| n == name).map(|(id, _)| *id);
+    let (everyone, moderator, officer) = (
+        role("@everyone").context("missing @everyone role")?,
+        role("Moderator").context("missing Moderator role")?,
+        role("Officer").context("missing Officer role")?,
+    );
+    if let Some(&user_id) = members.get(1) {
+        assign_member_role(pool, guild_id, user_id, officer, Some(owner_id)).await?;
+    }
+    for &user_id in members.iter().skip(2).take(2) {
+        assign_member_role(pool, guild_id, user_id, moderator, Some(owner_id)).await?;
+    }
+
+    let mut text_channels = Vec::new();
+    for (name, topic) in TEXT_CHANNELS {
+        let channel = db::create_channel(
+            pool,
+            CreateChannelParams {
+                name,
+                channel_type: &ChannelType::Text,
+                category_id: None,
+                guild_id: Some(guild_id),
+                topic: Some(topic),
+                icon_url: None,
+                user_limit: None,
+            },
+        )
+        .await?;
+        text_channels.push(channel.id);
+    }
+
+    // Staff-only channel: hidden from @everyone, visible to moderators
+    let staff = db::create_channel(
+        pool,
+        CreateChannelParams {
+            name: "staff",
+            channel_type: &ChannelType::Text,
+            category_id: None,
+            guild_id: Some(guild_id),
+            topic: Some("Moderator discussion"),
+            icon_url: None,
+            user_limit: None,
+        },
+    )
+    .await?;
+    set_channel_override(
+        pool,
+        staff.id,
+        everyone,
+        GuildPermissions::empty(),
+        GuildPermissions::VIEW_CHANNEL,
+    )
+    .await?;
+    set_channel_override(
+        pool,
+        staff.id,
+        moderator,
+        GuildPermissions::VIEW_CHANNEL,
+        GuildPermissions::empty(),
+    )
+    .await?;
+
+    let mut voice_channels = Vec::new();
+    for name in VOICE_CHANNELS {
+        let channel = db::create_channel(
+            pool,
+            CreateChannelParams {
+                name,
+                channel_type: &ChannelType::Voice,
+                category_id: None,
+                guild_id: Some(guild_id),
+                topic: None,
+                icon_url: None,
+                user_limit: None,
+            },
+        )
+        .await?;
+        voice_channels.push(channel.id);
+    }
+
+    let mut message_count = 0;
+    let mut attachment_count = 0;
+    for &channel_id in &text_channels {
+        let (messages, attachments) =
+            seed_messages(pool, rng, channel_id, &members, plan.messages_per_channel).await?;
+        message_count += messages;
+        attachment_count += attachments;
+    }
+    seed_voice_sessions(
+        pool,
+        rng,
+        guild_id,
+        &voice_channels,
+        &members,
+        plan.voice_sessions_per_guild,
+    )
+    .await?;
+
+    println!(
+        "Created guild {} ({} members, {message_count} messages, {attachment_count} attachments, {} voice sessions)",
+        index + 1,
+        members.len(),
+        plan.voice_sessions_per_guild
+    );
+    Ok(())
+}
+
+/// Random timestamp within the history window.
+fn random_past(rng: &mut StdRng, now: DateTime<Utc>) -> DateTime<Utc> {
+    now - Duration::seconds(rng.gen_range(0..HISTORY_DAYS * 24 * 60 * 60))
+}
+
+/// Insert `count` messages, about one in ten with an attachment.
+///
+/// Returns the number of messages and attachments created.
+async fn seed_messages(
+    pool: &PgPool,
+    rng: &mut StdRng,
+    channel_id: Uuid,
+    authors: &[Uuid],
+    count: usize,
+) -> Result<(usize, usize)> {
+    let now = Utc::now();
+    let mut messages: Vec<(Uuid, Uuid, &str, DateTime<Utc>)> = (0..count)
+        .map(|_| {
+            (
+                Uuid::now_v7(),
+                *authors.choose(rng).expect("guild has members"),
+                *PHRASES.choose(rng).expect("phrases are not empty"),
+                random_past(rng, now),
+            )
+        })
+        .collect();
+    messages.sort_by_key(|m| m.3);
+
+    for batch in messages.chunks(BATCH_SIZE) {
+        let mut qb = QueryBuilder::new(
+            "INSERT INTO messages (id, channel_id, user_id, content, created_at) ",
+        );
+        qb.push_values(batch, |mut b, (id, user_id, content, created_at)| {
+            b.push_bind(id)
+                .push_bind(channel_id)
+                .push_bind(user_id)
+                .push_bind(*content)
+                .push_bind(created_at);
+        });
+        qb.build().execute(pool).await?;
+    }
+
+    let mut attachments: Vec<(Uuid, &(&str, &str, i64), DateTime<Utc>)> = Vec::new();
+    for (id, _, _, created_at) in &messages {
+        if rng.gen_ratio(1, 10) {
+            let kind = ATTACHMENTS.choose(rng).expect("attachments are not empty");
+            attachments.push((*id, kind, *created_at));
+        }
+    }
+
+    for batch in attachments.chunks(BATCH_SIZE) {
+        let mut qb = QueryBuilder::new(
+            "INSERT INTO file_attachments (message_id, filename, mime_type, size_bytes, s3_key, created_at) ",
+        );
+        qb.push_values(
+            batch,
+            |mut b, (message_id, (filename, mime_type, size), created_at)| {
+                b.push_bind(message_id)
+                    .push_bind(*filename)
+                    .push_bind(*mime_type)
+                    .push_bind(size)
+                    .push_bind(format!("seed/{message_id}/{filename}"))
+                    .push_bind(created_at);
+            },
+        );
+        qb.build().execute(pool).await?;
+    }
+
+    Ok((messages.len(), attachments.len()))
+}
+
+/// Insert `count` finished voice sessions with plausible quality figures.
+async fn seed_voice_sessions(
+    pool: &PgPool,
+    rng: &mut StdRng,
+    guild_id: Uuid,
+    channels: &[Uuid],
+    members: &[Uuid],
+    count: usize,
+) -> Result<()> {
+    let now = Utc::now();
+    let sessions: Vec<_> = (0..count)
+        .map(|_| {
+            let started_at = random_past(rng, now);
+            let ended_at = started_at + Duration::minutes(rng.gen_range(5..180));
+            (
+                Uuid::now_v7(),
+                *members.choose(rng).expect("guild has members"),
+                *channels.choose(rng).expect("guild has voice channels"),
+                started_at,
+                ended_at.min(now),
+                rng.gen_range(20_i16..150),
+                rng.gen_range(0.0_f32..3.0),
+                rng.gen_range(2_i16..30),
+                rng.gen_range(1_i16..=4),
+            )
+        })
+        .collect();
+
+    for batch in sessions.chunks(BATCH_SIZE) {
+        let mut qb = QueryBuilder::new(
+            "INSERT INTO connection_sessions (id, user_id, channel_id, guild_id, started_at, ended_at, avg_latency, avg_loss, avg_jitter, worst_quality) ",
+        );
+        qb.push_values(batch, |mut b, s| {
+            b.push_bind(s.0)
+                .push_bind(s.1)
+                .push_bind(s.2)
+                .push_bind(guild_id)
+                .push_bind(s.3)
+                .push_bind(s.4)
+                .push_bind(s.5)
+                .push_bind(s.6)
+                .push_bind(s.7)
+                .push_bind(s.8);
+        });
+        qb.build().execute(pool).await?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(list: &[&str]) -> Result<Args> {
+        parse_args(list.iter().map(ToString::to_string))
+    }
+
+    #[test]
+    fn parses_arguments() {
+        assert_eq!(
+            args(&[]).unwrap(),
+            Args {
+                scale: 1,
+                reset: false
+            }
+        );
+        assert_eq!(
+            args(&["--scale", "3", "--reset"]).unwrap(),
+            Args {
+                scale: 3,
+                reset: true
+            }
+        );
+        assert!(args(&["--scale"]).is_err());
+        assert!(args(&["--scale", "0"]).is_err());
+        assert!(args(&["--scale", "abc"]).is_err());
+        assert!(args(&["--bogus"]).is_err());
+    }
+
+    #[test]
+    fn plan_grows_with_scale() {
+        let small = Plan::for_scale(1);
+        assert_eq!(small.users, 20);
+        assert_eq!(small.members_per_guild, 10);
+        let large = Plan::for_scale(4);
+        assert_eq!(large.guilds, 8);
+        assert_eq!(large.members_per_guild, 40);
+        assert!(large.members_per_guild <= large.users);
+    }
+}
@@ -0,0 +1,70 @@
+//! `Kaiku` SFU Node
+//!
+//! Hosts voice rooms for the server replicas on a separate media machine.
+//! Replicas list the node in `SFU_REMOTE_NODES` and drive it through the SFU
+//! control protocol (`vc_server::voice::control`), which this binary serves
+//! on `SFU_CONTROL_BIND_ADDRESS`.
+//!
+//! ```bash
+//! NODE_ID=sfu-eu-1 NODE_REGION=eu-central NODE_PUBLIC_ADDRESS=203.0.113.7 \
+//!     SFU_CONTROL_TOKEN=... cargo run --bin sfu-node
+//! ```
+//!
+//! The node reads the server configuration, but only its voice and node
+//! settings are used: it connects to neither the database nor Redis. The
+//! replicas register it in the cluster node registry.
+
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use anyhow::{Context, Result};
+use tracing::info;
+use vc_server::config;
+use vc_server::voice::control::ControlService;
+use vc_server::voice::SfuServer;
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    // Initialize rustls crypto provider (required for WebRTC)
+    let _ =
+        rustls::crypto::CryptoProvider::install_default(rustls::crypto::ring::default_provider());
+
+    config::load_dotenv();
+    let config = config::Config::from_env()?;
+    tracing_subscriber::fmt()
+        .with_env_filter(tracing_subscriber::EnvFilter::new(
+            &config.observability.log_level,
+        ))
+        .init();
+
+    let token = config
+        .sfu_control_token
+        .clone()
+        .context("SFU_CONTROL_TOKEN must be set")?;
+    let address: SocketAddr = config.sfu_control_bind_address.parse().with_context(|| {
+        format!(
+            "Invalid SFU_CONTROL_BIND_ADDRESS '{}'",
+            config.sfu_control_bind_address
+        )
+    })?;
+
+    let sfu = Arc::new(SfuServer::new(Arc::new(config.clone()), None)?);
+    let voice_cleanup_handle = sfu.start_cleanup_task();
+
+    info!(
+        version = env!("CARGO_PKG_VERSION"),
+        node_id = %config.node_id,
+        address = %address,
+        "SFU node listening"
+    );
+    tonic::transport::Server::builder()
+        .add_service(ControlService::new(sfu, &token))
+        .serve_with_shutdown(address, async {
+            let _ = tokio::signal::ctrl_c().await;
+        })
+        .await?;
+
+    voice_cleanup_handle.abort();
+    info!("SFU node stopped");
+    Ok(())
+}
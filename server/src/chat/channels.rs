@@ -8,6 +8,7 @@ use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 use validator::Validate;
 
+use crate::api::concurrency::VersionConflict;
 use crate::api::AppState;
 use crate::auth::AuthUser;
 use crate::db::{self, ChannelType};
@@ -21,8 +22,11 @@ use crate::ws::{broadcast_to_user, ServerEvent};
 pub enum ChannelError {
     NotFound,
     Forbidden,
+    /// A block between the users forbids a DM.
+    Blocked,
     Validation(String),
     LimitExceeded(String),
+    VersionConflict(VersionConflict),
     Database(sqlx::Error),
 }
 
@@ -39,8 +43,14 @@ impl IntoResponse for ChannelError {
                 "FORBIDDEN",
                 "Access denied".to_string(),
             ),
+            Self::Blocked => (
+                StatusCode::FORBIDDEN,
+                "BLOCKED",
+                "Cannot create DM with this user".to_string(),
+            ),
             Self::Validation(msg) => (StatusCode::BAD_REQUEST, "VALIDATION_ERROR", msg.clone()),
             Self::LimitExceeded(msg) => (StatusCode::FORBIDDEN, "LIMIT_EXCEEDED", msg.clone()),
+            Self::VersionConflict(conflict) => return conflict.clone().into_response(),
             Self::Database(err) => {
                 tracing::error!(%err, "Channel endpoint database error");
                 (
@@ -80,8 +90,13 @@ pub struct ChannelResponse {
     pub position: i32,
     /// Maximum concurrent screen shares (voice channels only).
     pub max_screen_shares: i32,
+    /// Days messages are kept before being purged (`None` = forever).
+    pub retention_days: Option<i32>,
     pub icon_url: Option<String>,
     pub created_at: chrono::DateTime<chrono::Utc>,
+    /// Bumped by every update; send it back with an update to detect
+    /// conflicting edits.
+    pub version: i32,
 }
 
 impl From<db::Channel> for ChannelResponse {
@@ -92,6 +107,7 @@ impl From<db::Channel> for ChannelResponse {
             channel_type: match ch.channel_type {
                 ChannelType::Text => "text".to_string(),
                 ChannelType::Voice => "voice".to_string(),
+                ChannelType::Stage => "stage".to_string(),
                 ChannelType::Dm => "dm".to_string(),
             },
             category_id: ch.category_id,
@@ -101,7 +117,9 @@ impl From<db::Channel> for ChannelResponse {
             user_limit: ch.user_limit,
             position: ch.position,
             max_screen_shares: ch.max_screen_shares,
+            retention_days: ch.retention_days,
             created_at: ch.created_at,
+            version: ch.version,
         }
     }
 }
@@ -117,13 +135,21 @@ pub struct CreateChannelRequest {
     pub user_limit: Option<i32>,
 }
 
-#[derive(Debug, Deserialize, Validate, utoipa::ToSchema)]
+/// Serializes only the fields being changed (recorded in the guild audit log).
+#[derive(Debug, Deserialize, Serialize, Validate, utoipa::ToSchema)]
 pub struct UpdateChannelRequest {
     #[validate(length(min = 1, max = 64, message = "Name must be 1-64 characters"))]
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub topic: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub user_limit: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub position: Option<i32>,
+    /// Channel version the update is based on; a newer version fails with 409.
+    #[serde(skip_serializing)]
+    pub version: Option<i32>,
 }
 
 #[derive(Debug, Deserialize, utoipa::ToSchema)]
@@ -169,12 +195,13 @@ pub async fn create(
     let channel_type = match body.channel_type.to_lowercase().as_str() {
         "text" => ChannelType::Text,
         "voice" => ChannelType::Voice,
+        "stage" => ChannelType::Stage,
         "dm" => ChannelType::Dm,
         _ => return Err(ChannelError::Validation("Invalid channel type".to_string())),
     };
 
     // Validate voice channel user limit
-    if channel_type == ChannelType::Voice {
+    if channel_type.is_voice() {
         if let Some(limit) = body.user_limit {
             if !(1..=99).contains(&limit) {
                 return Err(ChannelError::Validation(
@@ -209,10 +236,10 @@ pub async fn create(
                 .fetch_one(&mut *tx)
                 .await?;
 
-        if channel_count >= state.config.max_channels_per_guild {
+        if channel_count >= state.config().max_channels_per_guild {
             return Err(ChannelError::LimitExceeded(format!(
                 "Maximum number of channels per guild reached ({})",
-                state.config.max_channels_per_guild
+                state.config().max_channels_per_guild
             )));
         }
 
@@ -224,9 +251,11 @@ pub async fn create(
         .await?;
 
         let channel = sqlx::query_as::<_, db::Channel>(
-            r"INSERT INTO channels (name, channel_type, category_id, guild_id, topic, icon_url, user_limit, position)
-              VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
-              RETURNING id, name, channel_type, category_id, guild_id, topic, icon_url, user_limit, position, max_screen_shares, created_at, updated_at",
+            r"INSERT INTO channels (name, channel_type, category_id, guild_id, topic, icon_url, user_limit, position, retention_days)
+              VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+              RETURNING id, name, channel_type, category_id, guild_id, topic, icon_url, user_limit, position, max_screen_shares,
+                        COALESCE(retention_days, (SELECT g.retention_days FROM guilds g WHERE g.id = channels.guild_id)) AS retention_days,
+                        created_at, updated_at, version",
         )
         .bind(&body.name)
         .bind(&channel_type)
@@ -236,10 +265,26 @@ pub async fn create(
         .bind(None::<&str>) // icon_url
         .bind(body.user_limit)
         .bind(position)
+        .bind(crate::guild::retention::default_channel_retention(&channel_type))
         .fetch_one(&mut *tx)
         .await?;
 
         tx.commit().await?;
+
+        crate::guild::audit::record(
+            &state,
+            guild_id,
+            auth_user.id,
+            "guild.channels.created",
+            Some("channel"),
+            Some(channel.id),
+            Some(serde_json::json!({
+                "name": channel.name,
+                "channel_type": body.channel_type.to_lowercase(),
+            })),
+        )
+        .await;
+
         channel
     } else {
         db::create_channel(
@@ -299,6 +344,7 @@ pub async fn get(
     request_body = UpdateChannelRequest,
     responses(
         (status = 200, body = ChannelResponse),
+        (status = 409, description = "Channel changed since `version`"),
     ),
     security(("bearer_auth" = [])),
 )]
@@ -326,7 +372,7 @@ pub async fn update(
         return Err(ChannelError::Forbidden);
     }
 
-    let channel = db::update_channel(
+    let Some(channel) = db::update_channel(
         &state.db,
         id,
         body.name.as_deref(),
@@ -334,9 +380,31 @@ pub async fn update(
         None, // icon_url
         body.user_limit,
         body.position,
+        body.version,
     )
     .await?
-    .ok_or(ChannelError::NotFound)?;
+    else {
+        // Another update bumped the version since the client loaded the channel
+        let current = db::find_channel_by_id(&state.db, id)
+            .await?
+            .ok_or(ChannelError::NotFound)?;
+        return Err(ChannelError::VersionConflict(VersionConflict::new(
+            &ChannelResponse::from(current),
+        )));
+    };
+
+    if let Some(guild_id) = channel.guild_id {
+        crate::guild::audit::record(
+            &state,
+            guild_id,
+            auth_user.id,
+            "guild.channels.updated",
+            Some("channel"),
+            Some(id),
+            serde_json::to_value(&body).ok(),
+        )
+        .await;
+    }
 
     Ok(Json(channel.into()))
 }
@@ -367,13 +435,30 @@ pub async fn delete(
         return Err(ChannelError::Forbidden);
     }
 
+    let channel = db::find_channel_by_id(&state.db, id)
+        .await?
+        .ok_or(ChannelError::NotFound)?;
+
     let deleted = db::delete_channel(&state.db, id).await?;
 
-    if deleted {
-        Ok(StatusCode::NO_CONTENT)
-    } else {
-        Err(ChannelError::NotFound)
+    if !deleted {
+        return Err(ChannelError::NotFound);
     }
+
+    if let Some(guild_id) = channel.guild_id {
+        crate::guild::audit::record(
+            &state,
+            guild_id,
+            auth_user.id,
+            "guild.channels.deleted",
+            Some("channel"),
+            Some(id),
+            Some(serde_json::json!({ "name": channel.name })),
+        )
+        .await;
+    }
+
+    Ok(StatusCode::NO_CONTENT)
 }
 
 /// List members of a channel.
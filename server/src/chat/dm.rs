@@ -1,7 +1,7 @@
 //! Direct Message Channel Management
 //!
 //! Handles creation and management of DM channels (1:1 and group DMs).
-use axum::extract::{Multipart, Path, State};
+use axum::extract::{Multipart, Path, Query, State};
 use axum::http::StatusCode;
 use axum::response::IntoResponse;
 use axum::Json;
@@ -10,6 +10,7 @@ use uuid::Uuid;
 use validator::Validate;
 
 use super::channels::{ChannelError, ChannelResponse};
+use super::dm_state::{self, DmConversationState};
 use crate::api::AppState;
 use crate::auth::AuthUser;
 use crate::chat::uploads::UploadError;
@@ -62,6 +63,15 @@ pub struct DMListResponse {
     pub participants: Vec<DMParticipant>,
     pub last_message: Option<LastMessagePreview>,
     pub unread_count: i64,
+    /// How the user has organized the conversation.
+    pub state: DmConversationState,
+}
+
+#[derive(Debug, Default, Deserialize, utoipa::IntoParams)]
+#[serde(default)]
+pub struct ListDmsQuery {
+    /// Include conversations the user has hidden
+    pub include_hidden: bool,
 }
 
 #[derive(Debug, Serialize, utoipa::ToSchema)]
@@ -124,7 +134,7 @@ pub async fn get_or_create_dm(
     let channel = sqlx::query_as::<_, Channel>(
         r"INSERT INTO channels (id, name, channel_type, guild_id, position)
            VALUES ($1, $2, 'dm', NULL, 0)
-           RETURNING id, name, channel_type, category_id, guild_id, topic, icon_url, user_limit, position, max_screen_shares, created_at, updated_at",
+           RETURNING id, name, channel_type, category_id, guild_id, topic, icon_url, user_limit, position, max_screen_shares, created_at, updated_at, version",
     )
     .bind(channel_id)
     .bind(&dm_name)
@@ -187,7 +197,7 @@ pub async fn create_group_dm(
     let channel = sqlx::query_as::<_, Channel>(
         r"INSERT INTO channels (id, name, channel_type, guild_id, position)
            VALUES ($1, $2, 'dm', NULL, 0)
-           RETURNING id, name, channel_type, category_id, guild_id, topic, icon_url, user_limit, position, max_screen_shares, created_at, updated_at",
+           RETURNING id, name, channel_type, category_id, guild_id, topic, icon_url, user_limit, position, max_screen_shares, created_at, updated_at, version",
     )
     .bind(channel_id)
     .bind(&channel_name)
@@ -319,9 +329,7 @@ pub async fn create_dm(
         .await
         {
             Ok(true) => {
-                return Err(ChannelError::Validation(
-                    "Cannot create DM with this user".to_string(),
-                ));
+                return Err(ChannelError::Blocked);
             }
             Ok(false) => {}
             Err(e) => {
@@ -329,13 +337,11 @@ pub async fn create_dm(
                     error = %e,
                     user_id = %auth.id,
                     target_id = %body.participant_ids[0],
-                    fail_open = state.config.block_check_fail_open,
+                    fail_open = state.config().block_check_fail_open,
                     "Redis block check failed, using failsafe policy"
                 );
-                if !state.config.block_check_fail_open {
-                    return Err(ChannelError::Validation(
-                        "Cannot create DM with this user".to_string(),
-                    ));
+                if !state.config().block_check_fail_open {
+                    return Err(ChannelError::Blocked);
                 }
             }
         }
@@ -368,10 +374,14 @@ pub async fn create_dm(
 
 /// List all DM channels for the authenticated user
 /// GET /api/dm
+///
+/// Pinned conversations come first, then the rest by latest message. Hidden
+/// conversations are left out unless `include_hidden` is set.
 #[utoipa::path(
     get,
     path = "/api/dm",
     tag = "dm",
+    params(ListDmsQuery),
     responses(
         (status = 200, body = Vec<DMListResponse>),
     ),
@@ -380,8 +390,10 @@ pub async fn create_dm(
 pub async fn list_dms(
     State(state): State<AppState>,
     auth: AuthUser,
+    Query(query): Query<ListDmsQuery>,
 ) -> Result<Json<Vec<DMListResponse>>, ChannelError> {
     let channels = list_user_dms(&state.db, auth.id).await?;
+    let mut states = dm_state::load_states(&state.db, auth.id).await?;
 
     let mut responses = Vec::new();
     for channel in channels {
@@ -429,22 +441,41 @@ pub async fn list_dms(
             .await?
         };
 
-        responses.push(DMListResponse {
-            channel: channel.into(),
-            participants,
-            last_message,
-            unread_count,
-        });
+        let row = states.remove(&channel.id);
+        let conversation_state = row
+            .as_ref()
+            .map_or_else(DmConversationState::default, |row| {
+                row.state(last_message.as_ref().map(|m| m.created_at))
+            });
+        if conversation_state.hidden && !query.include_hidden {
+            continue;
+        }
+        let unread_count = if conversation_state.unread_kept {
+            unread_count.max(1)
+        } else {
+            unread_count
+        };
+
+        responses.push((
+            row.and_then(|row| row.pinned_at),
+            DMListResponse {
+                channel: channel.into(),
+                participants,
+                last_message,
+                unread_count,
+                state: conversation_state,
+            },
+        ));
     }
 
-    // Sort by last message time (most recent first)
-    responses.sort_by(|a, b| {
+    // Pinned first (most recently pinned on top), then by last message time
+    responses.sort_by(|(a_pinned, a), (b_pinned, b)| {
         let a_time = a.last_message.as_ref().map(|m| m.created_at);
         let b_time = b.last_message.as_ref().map(|m| m.created_at);
-        b_time.cmp(&a_time)
+        b_pinned.cmp(a_pinned).then_with(|| b_time.cmp(&a_time))
     });
 
-    Ok(Json(responses))
+    Ok(Json(responses.into_iter().map(|(_, dm)| dm).collect()))
 }
 
 /// Get a specific DM channel
@@ -623,7 +654,7 @@ pub async fn update_dm_name(
     let updated_channel = sqlx::query_as::<_, crate::db::Channel>(
         r"UPDATE channels SET name = $1, updated_at = NOW()
           WHERE id = $2
-          RETURNING id, name, channel_type, category_id, guild_id, topic, user_limit, position, max_screen_shares, created_at, updated_at",
+          RETURNING id, name, channel_type, category_id, guild_id, topic, user_limit, position, max_screen_shares, created_at, updated_at, version",
     )
     .bind(&body.name)
     .bind(channel_id)
@@ -711,6 +742,7 @@ pub async fn upload_dm_icon(
 
     // Process file upload (similar to uploads.rs)
     let s3 = state.s3.as_ref().ok_or(UploadError::NotConfigured)?;
+    let max_avatar_size = state.config().max_avatar_size;
 
     let mut file_data: Option<Vec<u8>> = None;
 
@@ -723,9 +755,9 @@ pub async fn upload_dm_icon(
                 .await
                 .map_err(|e| UploadError::Validation(e.to_string()))?;
 
-            if data.len() > state.config.max_avatar_size {
+            if data.len() > max_avatar_size {
                 return Err(UploadError::TooLarge {
-                    max_size: state.config.max_avatar_size,
+                    max_size: max_avatar_size,
                 });
             }
 
@@ -905,6 +937,16 @@ pub async fn mark_as_read(
     .execute(&state.db)
     .await?;
 
+    // Reading the conversation ends a "keep unread"
+    sqlx::query(
+        "UPDATE dm_participants SET unread_kept = FALSE
+         WHERE channel_id = $1 AND user_id = $2 AND unread_kept",
+    )
+    .bind(channel_id)
+    .bind(auth.id)
+    .execute(&state.db)
+    .await?;
+
     // Broadcast dm_read event to all user's other WebSocket sessions
     // Note: Broadcast failure shouldn't fail the request since the DB state is already updated
     if let Err(e) = broadcast_to_user(
@@ -935,6 +977,8 @@ pub async fn mark_as_read(
 
 /// Mark all DM channels as read.
 /// POST /api/dm/read-all
+///
+/// Conversations the user keeps unread are skipped.
 #[utoipa::path(
     post,
     path = "/api/dm/read-all",
@@ -961,7 +1005,7 @@ pub async fn mark_all_dms_read(
           )
           FROM dm_participants dp
           INNER JOIN channels c ON c.id = dp.channel_id
-          WHERE dp.user_id = $1 AND c.channel_type = 'dm'
+          WHERE dp.user_id = $1 AND c.channel_type = 'dm' AND NOT dp.unread_kept
           ON CONFLICT (user_id, channel_id)
           DO UPDATE SET last_read_at = EXCLUDED.last_read_at, last_read_message_id = EXCLUDED.last_read_message_id
           RETURNING channel_id",
@@ -275,7 +275,7 @@ pub async fn search_dm_messages(
                     avatar_url: u.avatar_url.clone(),
                 })
                 .unwrap_or_else(|| DmSearchAuthor {
-                    id: msg.user_id.unwrap_or(Uuid::nil()),
+                    id: msg.author_tombstone_id.unwrap_or(Uuid::nil()),
                     username: "deleted".to_string(),
                     display_name: "Deleted User".to_string(),
                     avatar_url: None,
@@ -0,0 +1,211 @@
+//! DM Conversation State
+//!
+//! Per-user organization of the DM list, kept on the user's
+//! `dm_participants` row so the conversation's history is never touched:
+//!
+//! - hidden: left out of `GET /api/dm` until a newer message arrives
+//! - archived: listed, but grouped away from active conversations
+//! - pinned: sorted to the top of the list, most recently pinned first
+//! - unread kept: reported unread and skipped by `POST /api/dm/read-all`
+//!   until the conversation is read explicitly
+//!
+//! Changes are broadcast as `dm_state_update` to the user's other sessions.
+
+use std::collections::HashMap;
+
+use axum::extract::{Path, State};
+use axum::Json;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::channels::ChannelError;
+use crate::api::AppState;
+use crate::auth::AuthUser;
+use crate::db::{self, ChannelType};
+use crate::ws::{broadcast_to_user, ServerEvent};
+
+/// How the user has organized a DM conversation.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+#[allow(clippy::struct_excessive_bools)]
+pub struct DmConversationState {
+    /// Hidden from the DM list until a new message arrives.
+    pub hidden: bool,
+    pub archived: bool,
+    /// Pinned to the top of the DM list.
+    pub pinned: bool,
+    /// Marked unread; stays unread until the conversation is read.
+    pub unread_kept: bool,
+}
+
+/// Stored state of one conversation for one user.
+#[derive(Debug, sqlx::FromRow)]
+pub(super) struct StateRow {
+    pub channel_id: Uuid,
+    pub hidden_at: Option<DateTime<Utc>>,
+    pub archived: bool,
+    pub pinned_at: Option<DateTime<Utc>>,
+    pub unread_kept: bool,
+}
+
+impl StateRow {
+    /// Effective state, given when the conversation's last message was sent.
+    pub(super) fn state(&self, last_message_at: Option<DateTime<Utc>>) -> DmConversationState {
+        DmConversationState {
+            hidden: self
+                .hidden_at
+                .is_some_and(|hidden_at| last_message_at.is_none_or(|at| at <= hidden_at)),
+            archived: self.archived,
+            pinned: self.pinned_at.is_some(),
+            unread_kept: self.unread_kept,
+        }
+    }
+}
+
+/// The user's state for each of their DM conversations.
+pub(super) async fn load_states(
+    pool: &sqlx::PgPool,
+    user_id: Uuid,
+) -> sqlx::Result<HashMap<Uuid, StateRow>> {
+    let rows = sqlx::query_as::<_, StateRow>(
+        r"SELECT channel_id, hidden_at, archived, pinned_at, unread_kept
+          FROM dm_participants
+          WHERE user_id = $1",
+    )
+    .bind(user_id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows.into_iter().map(|row| (row.channel_id, row)).collect())
+}
+
+/// Update DM conversation state request. Omitted fields are left unchanged.
+#[derive(Debug, Deserialize, utoipa::ToSchema)]
+pub struct UpdateDmStateRequest {
+    pub hidden: Option<bool>,
+    pub archived: Option<bool>,
+    pub pinned: Option<bool>,
+    pub unread_kept: Option<bool>,
+}
+
+/// Update how the user organizes a DM conversation
+/// PATCH /api/dm/:id/state
+#[utoipa::path(
+    patch,
+    path = "/api/dm/{id}/state",
+    tag = "dm",
+    params(("id" = Uuid, Path, description = "DM conversation ID")),
+    request_body = UpdateDmStateRequest,
+    responses(
+        (status = 200, body = DmConversationState),
+        (status = 403, description = "Not a participant"),
+        (status = 404, description = "DM not found"),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[tracing::instrument(skip(state, body))]
+pub async fn update_dm_state(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(channel_id): Path<Uuid>,
+    Json(body): Json<UpdateDmStateRequest>,
+) -> Result<Json<DmConversationState>, ChannelError> {
+    let channel = db::find_channel_by_id(&state.db, channel_id)
+        .await?
+        .ok_or(ChannelError::NotFound)?;
+
+    if channel.channel_type != ChannelType::Dm {
+        return Err(ChannelError::NotFound);
+    }
+
+    // Re-pinning keeps the original pin time, so the order stays stable
+    let row = sqlx::query_as::<_, StateRow>(
+        r"UPDATE dm_participants SET
+              hidden_at = CASE
+                  WHEN $3::BOOLEAN IS NULL THEN hidden_at
+                  WHEN $3 THEN NOW()
+                  ELSE NULL
+              END,
+              archived = COALESCE($4, archived),
+              pinned_at = CASE
+                  WHEN $5::BOOLEAN IS NULL THEN pinned_at
+                  WHEN $5 THEN COALESCE(pinned_at, NOW())
+                  ELSE NULL
+              END,
+              unread_kept = COALESCE($6, unread_kept)
+          WHERE channel_id = $1 AND user_id = $2
+          RETURNING channel_id, hidden_at, archived, pinned_at, unread_kept",
+    )
+    .bind(channel_id)
+    .bind(auth.id)
+    .bind(body.hidden)
+    .bind(body.archived)
+    .bind(body.pinned)
+    .bind(body.unread_kept)
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or(ChannelError::Forbidden)?;
+
+    let last_message_at: Option<DateTime<Utc>> = sqlx::query_scalar(
+        "SELECT MAX(created_at) FROM messages WHERE channel_id = $1 AND deleted_at IS NULL",
+    )
+    .bind(channel_id)
+    .fetch_one(&state.db)
+    .await?;
+    let conversation_state = row.state(last_message_at);
+
+    // Keep the user's other sessions' DM lists in sync
+    if let Err(e) = broadcast_to_user(
+        &state.redis,
+        auth.id,
+        &ServerEvent::DmStateUpdate {
+            channel_id,
+            state: conversation_state,
+        },
+    )
+    .await
+    {
+        tracing::warn!(
+            user_id = %auth.id,
+            channel_id = %channel_id,
+            error = %e,
+            "Failed to broadcast DmStateUpdate event"
+        );
+    }
+
+    Ok(Json(conversation_state))
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Duration;
+
+    use super::*;
+
+    fn row(hidden_at: Option<DateTime<Utc>>) -> StateRow {
+        StateRow {
+            channel_id: Uuid::new_v4(),
+            hidden_at,
+            archived: false,
+            pinned_at: None,
+            unread_kept: false,
+        }
+    }
+
+    #[test]
+    fn test_hidden_until_newer_message() {
+        let hidden_at = Utc::now();
+        assert!(!row(None).state(Some(hidden_at)).hidden);
+        assert!(row(Some(hidden_at)).state(None).hidden);
+        assert!(
+            row(Some(hidden_at))
+                .state(Some(hidden_at - Duration::minutes(1)))
+                .hidden
+        );
+        assert!(
+            !row(Some(hidden_at))
+                .state(Some(hidden_at + Duration::seconds(1)))
+                .hidden
+        );
+    }
+}
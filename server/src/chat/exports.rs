@@ -0,0 +1,1109 @@
+//! Channel Transcript Exports
+//!
+//! Guild managers can export a text channel's history for a time range as a
+//! JSONL or self-contained HTML transcript. Exports run as background jobs:
+//! the worker pages through the channel, records progress on the job row and
+//! uploads a ZIP archive to S3 that expires after
+//! [`CHANNEL_EXPORT_RETENTION_DAYS`]. Attachments are either linked or bundled
+//! into the archive.
+
+use std::fmt::Write as _;
+use std::io::Write as _;
+
+use anyhow::Context;
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use chrono::{DateTime, Duration, SecondsFormat, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use thiserror::Error;
+use tokio::sync::Semaphore;
+use uuid::Uuid;
+use zip::write::SimpleFileOptions;
+use zip::ZipWriter;
+
+use super::feeds::escape_xml;
+use crate::api::AppState;
+use crate::auth::AuthUser;
+use crate::chat::S3Client;
+use crate::db::{self, ChannelExportJob, ChannelType};
+use crate::governance::export::presign_download;
+use crate::permissions::{GuildPermissions, PermissionError};
+
+/// Maximum number of channel exports built at the same time on this node.
+const MAX_CONCURRENT_CHANNEL_EXPORTS: usize = 2;
+
+/// Worker slots shared by all channel export jobs on this node.
+static CHANNEL_EXPORT_SLOTS: Semaphore = Semaphore::const_new(MAX_CONCURRENT_CHANNEL_EXPORTS);
+
+/// Messages fetched (and progress reported) per batch.
+const EXPORT_BATCH_SIZE: i64 = 1000;
+
+/// Total attachment bytes bundled into one archive; further attachments are
+/// exported as links.
+const MAX_BUNDLED_ATTACHMENT_BYTES: i64 = 2 * 1024 * 1024 * 1024;
+
+/// Days a completed export archive stays downloadable.
+const CHANNEL_EXPORT_RETENTION_DAYS: i64 = 7;
+
+/// Jobs without a progress update for this long are considered abandoned.
+const STALE_CHANNEL_EXPORT_MINUTES: i64 = 30;
+
+/// Number of jobs returned by the job list.
+const JOB_LIST_LIMIT: i64 = 20;
+
+// ============================================================================
+// Error Type
+// ============================================================================
+
+#[derive(Debug, Error)]
+pub enum ChannelExportError {
+    #[error("Channel not found")]
+    ChannelNotFound,
+
+    #[error("Export job not found")]
+    JobNotFound,
+
+    #[error("An export of this channel is already in progress")]
+    AlreadyInProgress,
+
+    #[error("File storage is not configured")]
+    StorageNotConfigured,
+
+    #[error("{0}")]
+    Validation(String),
+
+    #[error("{0}")]
+    Permission(#[from] PermissionError),
+
+    #[error("Database error")]
+    Database(#[from] sqlx::Error),
+}
+
+impl IntoResponse for ChannelExportError {
+    fn into_response(self) -> Response {
+        let (status, body) = match &self {
+            Self::ChannelNotFound => (
+                StatusCode::NOT_FOUND,
+                serde_json::json!({"error": "not_found", "message": "Channel not found"}),
+            ),
+            Self::JobNotFound => (
+                StatusCode::NOT_FOUND,
+                serde_json::json!({"error": "not_found", "message": "Export job not found"}),
+            ),
+            Self::AlreadyInProgress => (
+                StatusCode::CONFLICT,
+                serde_json::json!({"error": "export_in_progress", "message": self.to_string()}),
+            ),
+            Self::StorageNotConfigured => (
+                StatusCode::SERVICE_UNAVAILABLE,
+                serde_json::json!({"error": "storage_not_configured", "message": self.to_string()}),
+            ),
+            Self::Validation(msg) => (
+                StatusCode::BAD_REQUEST,
+                serde_json::json!({"error": "validation", "message": msg}),
+            ),
+            Self::Permission(e) => (
+                StatusCode::FORBIDDEN,
+                serde_json::json!({"error": "permission", "message": e.to_string()}),
+            ),
+            Self::Database(_) => (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                serde_json::json!({"error": "database", "message": "Database error"}),
+            ),
+        };
+        (status, Json(body)).into_response()
+    }
+}
+
+// ============================================================================
+// Types
+// ============================================================================
+
+/// Transcript format.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    /// One JSON object per message (`transcript.jsonl`).
+    #[default]
+    Jsonl,
+    /// Self-contained HTML page (`transcript.html`).
+    Html,
+}
+
+impl ExportFormat {
+    const fn as_str(self) -> &'static str {
+        match self {
+            Self::Jsonl => "jsonl",
+            Self::Html => "html",
+        }
+    }
+}
+
+/// How attachments are included in the export.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum AttachmentMode {
+    /// Link to the attachment download endpoint (requires a session).
+    #[default]
+    Links,
+    /// Copy attachment files into the archive under `attachments/`.
+    Bundled,
+}
+
+impl AttachmentMode {
+    const fn as_str(self) -> &'static str {
+        match self {
+            Self::Links => "links",
+            Self::Bundled => "bundled",
+        }
+    }
+}
+
+#[derive(Debug, Default, Deserialize, utoipa::ToSchema)]
+pub struct CreateChannelExportRequest {
+    /// Transcript format (default `jsonl`).
+    #[serde(default)]
+    pub format: ExportFormat,
+    /// Attachment handling (default `links`).
+    #[serde(default)]
+    pub attachments: AttachmentMode,
+    /// Only export messages sent at or after this time.
+    pub from: Option<DateTime<Utc>>,
+    /// Only export messages sent before this time.
+    pub to: Option<DateTime<Utc>>,
+}
+
+/// Channel export job status.
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct ChannelExportJobResponse {
+    pub id: Uuid,
+    pub channel_id: Option<Uuid>,
+    pub requested_by: Option<Uuid>,
+    /// `jsonl` or `html`.
+    pub format: String,
+    /// `links` or `bundled`.
+    pub attachments: String,
+    pub from: Option<DateTime<Utc>>,
+    pub to: Option<DateTime<Utc>>,
+    /// `pending`, `processing`, `completed`, `failed` or `expired`.
+    pub status: String,
+    /// Messages in range, known once processing starts.
+    pub messages_total: Option<i64>,
+    /// Messages written so far.
+    pub messages_exported: i64,
+    pub file_size_bytes: Option<i64>,
+    /// When the archive is deleted.
+    pub expires_at: Option<DateTime<Utc>>,
+    pub error_message: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub completed_at: Option<DateTime<Utc>>,
+    /// Presigned archive URL (completed, unexpired jobs only).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub download_url: Option<String>,
+    /// When `download_url` stops working.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub download_url_expires_at: Option<DateTime<Utc>>,
+}
+
+/// Message row read by the export worker.
+#[derive(Debug, sqlx::FromRow)]
+struct TranscriptRow {
+    id: Uuid,
+    user_id: Option<Uuid>,
+    author_name: Option<String>,
+    content: String,
+    encrypted: bool,
+    message_type: String,
+    reply_to: Option<Uuid>,
+    parent_id: Option<Uuid>,
+    created_at: DateTime<Utc>,
+    edited_at: Option<DateTime<Utc>>,
+}
+
+/// Attachment entry of an exported message.
+#[derive(Debug, Serialize)]
+struct TranscriptAttachment {
+    id: Uuid,
+    filename: String,
+    mime_type: String,
+    size_bytes: i64,
+    /// Path inside the archive when bundled.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    path: Option<String>,
+    /// Download endpoint when linked.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    url: Option<String>,
+}
+
+/// One exported message (a JSONL line).
+#[derive(Debug, Serialize)]
+struct TranscriptMessage {
+    id: Uuid,
+    author_id: Option<Uuid>,
+    author: String,
+    /// `None` for end-to-end encrypted messages.
+    content: Option<String>,
+    encrypted: bool,
+    message_type: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    reply_to: Option<Uuid>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    parent_id: Option<Uuid>,
+    created_at: DateTime<Utc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    edited_at: Option<DateTime<Utc>>,
+    attachments: Vec<TranscriptAttachment>,
+}
+
+/// Archive manifest.
+#[derive(Debug, Serialize)]
+struct ExportManifest {
+    version: &'static str,
+    exported_at: DateTime<Utc>,
+    guild_id: Option<Uuid>,
+    channel_id: Uuid,
+    channel_name: String,
+    format: &'static str,
+    attachments: &'static str,
+    from: Option<DateTime<Utc>>,
+    to: Option<DateTime<Utc>>,
+    messages: i64,
+    bundled_attachments: usize,
+    /// Attachments exported as links because the bundle size cap was reached
+    /// or the file was missing from storage.
+    linked_attachments: usize,
+}
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+/// Require `MANAGE_GUILD` on a guild text channel.
+async fn require_export_manager(
+    state: &AppState,
+    user_id: Uuid,
+    channel_id: Uuid,
+) -> Result<db::Channel, ChannelExportError> {
+    let ctx = crate::permissions::require_channel_access(&state.db, user_id, channel_id)
+        .await
+        .map_err(|e| match e {
+            PermissionError::NotFound => ChannelExportError::ChannelNotFound,
+            other => ChannelExportError::Permission(other),
+        })?;
+
+    let channel = db::find_channel_by_id(&state.db, channel_id)
+        .await?
+        .ok_or(ChannelExportError::ChannelNotFound)?;
+    if channel.channel_type != ChannelType::Text || channel.guild_id.is_none() {
+        return Err(ChannelExportError::Validation(
+            "Exports are only available for guild text channels".to_string(),
+        ));
+    }
+
+    if !ctx.has_permission(GuildPermissions::MANAGE_GUILD) {
+        return Err(ChannelExportError::Permission(
+            PermissionError::MissingPermission(GuildPermissions::MANAGE_GUILD),
+        ));
+    }
+
+    Ok(channel)
+}
+
+/// Build the API response for a job, with a presigned download URL when the
+/// archive is completed and has not expired.
+async fn job_response(s3: Option<&S3Client>, job: ChannelExportJob) -> ChannelExportJobResponse {
+    let mut download = None;
+    if let (Some(s3), Some(s3_key), Some(expires_at)) = (s3, job.s3_key.as_deref(), job.expires_at)
+    {
+        if job.status == "completed" && expires_at > Utc::now() {
+            match presign_download(s3, s3_key, expires_at).await {
+                Ok(link) => download = Some(link),
+                Err(e) => {
+                    tracing::warn!(job_id = %job.id, error = %e, "Failed to presign channel export URL");
+                }
+            }
+        }
+    }
+    let (download_url, download_url_expires_at) = download.unzip();
+
+    ChannelExportJobResponse {
+        id: job.id,
+        channel_id: job.channel_id,
+        requested_by: job.requested_by,
+        format: job.format,
+        attachments: job.attachments,
+        from: job.range_from,
+        to: job.range_to,
+        status: job.status,
+        messages_total: job.messages_total,
+        messages_exported: job.messages_exported,
+        file_size_bytes: job.file_size_bytes,
+        expires_at: job.expires_at,
+        error_message: job.error_message,
+        created_at: job.created_at,
+        completed_at: job.completed_at,
+        download_url,
+        download_url_expires_at,
+    }
+}
+
+/// Archive path for a bundled attachment. The id prefix keeps names unique;
+/// the filename is reduced to characters that are safe in every unzip tool.
+fn bundled_attachment_path(id: Uuid, filename: &str) -> String {
+    let safe: String = filename
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    let safe = safe.trim_start_matches('.');
+    if safe.is_empty() {
+        format!("attachments/{id}")
+    } else {
+        format!("attachments/{id}-{safe}")
+    }
+}
+
+/// Link to an attachment's download endpoint.
+fn attachment_link(id: Uuid) -> String {
+    format!("/api/messages/attachments/{id}/download")
+}
+
+fn format_timestamp(ts: DateTime<Utc>) -> String {
+    ts.to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+/// Opening of the HTML transcript, up to the message list.
+fn html_header(
+    channel_name: &str,
+    from: Option<DateTime<Utc>>,
+    to: Option<DateTime<Utc>>,
+) -> String {
+    let title = escape_xml(&format!("#{channel_name}"));
+    let range = match (from, to) {
+        (None, None) => "All messages".to_string(),
+        (from, to) => format!(
+            "{} – {}",
+            from.map_or_else(|| "beginning".to_string(), format_timestamp),
+            to.map_or_else(|| "now".to_string(), format_timestamp),
+        ),
+    };
+
+    format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n\
+         <title>{title}</title>\n<style>\n\
+         body{{font-family:sans-serif;max-width:60rem;margin:2rem auto;color:#222}}\n\
+         .message{{padding:.4rem 0;border-bottom:1px solid #eee}}\n\
+         .author{{font-weight:bold}}\n\
+         .meta{{color:#777;font-size:.85em;margin-left:.5rem}}\n\
+         .content{{white-space:pre-wrap;margin:.2rem 0}}\n\
+         .system,.encrypted{{color:#777;font-style:italic}}\n\
+         </style>\n</head>\n<body>\n<h1>{title}</h1>\n<p class=\"meta\">{range}</p>\n"
+    )
+}
+
+const HTML_FOOTER: &str = "</body>\n</html>\n";
+
+/// Render one message as an HTML fragment.
+fn html_message(message: &TranscriptMessage) -> String {
+    let mut html = String::new();
+    let class = if message.message_type == "default" {
+        "message"
+    } else {
+        "message system"
+    };
+    let _ = write!(
+        html,
+        "<div class=\"{class}\" id=\"m-{id}\">\n<span class=\"author\">{author}</span>\
+         <span class=\"meta\">{created}{edited}</span>\n",
+        id = message.id,
+        author = escape_xml(&message.author),
+        created = format_timestamp(message.created_at),
+        edited = if message.edited_at.is_some() {
+            " (edited)"
+        } else {
+            ""
+        },
+    );
+    if let Some(reply_to) = message.reply_to {
+        let _ = writeln!(
+            html,
+            "<div class=\"meta\">Reply to <a href=\"#m-{reply_to}\">message</a></div>"
+        );
+    }
+    match &message.content {
+        Some(content) => {
+            let _ = writeln!(html, "<p class=\"content\">{}</p>", escape_xml(content));
+        }
+        None => html.push_str("<p class=\"content encrypted\">[encrypted]</p>\n"),
+    }
+    for attachment in &message.attachments {
+        let href = attachment
+            .path
+            .as_deref()
+            .or(attachment.url.as_deref())
+            .unwrap_or_default();
+        let _ = writeln!(
+            html,
+            "<div class=\"attachment\"><a href=\"{}\">{}</a> <span class=\"meta\">{} bytes</span></div>",
+            escape_xml(href),
+            escape_xml(&attachment.filename),
+            attachment.size_bytes,
+        );
+    }
+    html.push_str("</div>\n");
+    html
+}
+
+// ============================================================================
+// Handlers
+// ============================================================================
+
+/// Start a transcript export of a channel (requires `MANAGE_GUILD`).
+///
+/// The export runs in the background; poll the returned job for progress.
+/// Only one export per channel can be in progress.
+///
+/// `POST /api/channels/:id/export`
+#[utoipa::path(
+    post,
+    path = "/api/channels/{id}/export",
+    tag = "channels",
+    params(("id" = Uuid, Path, description = "Channel ID")),
+    request_body = CreateChannelExportRequest,
+    responses(
+        (status = 202, description = "Export job queued", body = ChannelExportJobResponse),
+        (status = 409, description = "Export already in progress"),
+        (status = 503, description = "File storage not configured"),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[tracing::instrument(skip(state, body))]
+pub async fn create_export(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(channel_id): Path<Uuid>,
+    Json(body): Json<CreateChannelExportRequest>,
+) -> Result<impl IntoResponse, ChannelExportError> {
+    let channel = require_export_manager(&state, auth.id, channel_id).await?;
+
+    if let (Some(from), Some(to)) = (body.from, body.to) {
+        if from >= to {
+            return Err(ChannelExportError::Validation(
+                "'from' must be before 'to'".to_string(),
+            ));
+        }
+    }
+
+    let s3 = state
+        .s3
+        .clone()
+        .ok_or(ChannelExportError::StorageNotConfigured)?;
+
+    // The unique partial index allows one active job per channel
+    let job = sqlx::query_as::<_, ChannelExportJob>(
+        "INSERT INTO channel_export_jobs
+            (channel_id, requested_by, format, attachments, range_from, range_to)
+         VALUES ($1, $2, $3, $4, $5, $6)
+         RETURNING *",
+    )
+    .bind(channel_id)
+    .bind(auth.id)
+    .bind(body.format.as_str())
+    .bind(body.attachments.as_str())
+    .bind(body.from)
+    .bind(body.to)
+    .fetch_one(&state.db)
+    .await
+    .map_err(|e| {
+        if let sqlx::Error::Database(ref db_err) = e {
+            if db_err.is_unique_violation() {
+                return ChannelExportError::AlreadyInProgress;
+            }
+        }
+        ChannelExportError::Database(e)
+    })?;
+
+    if let Some(guild_id) = channel.guild_id {
+        crate::guild::audit::record(
+            &state,
+            guild_id,
+            auth.id,
+            "guild.channels.export_requested",
+            Some("channel"),
+            Some(channel_id),
+            Some(serde_json::json!({
+                "job_id": job.id,
+                "format": job.format,
+                "attachments": job.attachments,
+                "from": job.range_from,
+                "to": job.range_to,
+            })),
+        )
+        .await;
+    }
+
+    let pool = state.db.clone();
+    let job_id = job.id;
+    tokio::spawn(async move {
+        if let Err(e) = process_channel_export(&pool, &s3, job_id).await {
+            tracing::error!(job_id = %job_id, channel_id = %channel_id, error = %e, "Channel export failed");
+        }
+    });
+
+    Ok((StatusCode::ACCEPTED, Json(job_response(None, job).await)))
+}
+
+/// List recent export jobs of a channel (requires `MANAGE_GUILD`).
+///
+/// `GET /api/channels/:id/exports`
+#[utoipa::path(
+    get,
+    path = "/api/channels/{id}/exports",
+    tag = "channels",
+    params(("id" = Uuid, Path, description = "Channel ID")),
+    responses(
+        (status = 200, body = Vec<ChannelExportJobResponse>),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[tracing::instrument(skip(state))]
+pub async fn list_exports(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(channel_id): Path<Uuid>,
+) -> Result<Json<Vec<ChannelExportJobResponse>>, ChannelExportError> {
+    require_export_manager(&state, auth.id, channel_id).await?;
+
+    let jobs = sqlx::query_as::<_, ChannelExportJob>(
+        "SELECT * FROM channel_export_jobs
+         WHERE channel_id = $1
+         ORDER BY created_at DESC
+         LIMIT $2",
+    )
+    .bind(channel_id)
+    .bind(JOB_LIST_LIMIT)
+    .fetch_all(&state.db)
+    .await?;
+
+    let mut responses = Vec::with_capacity(jobs.len());
+    for job in jobs {
+        responses.push(job_response(state.s3.as_ref(), job).await);
+    }
+    Ok(Json(responses))
+}
+
+/// Get an export job, with a download URL once completed (requires `MANAGE_GUILD`).
+///
+/// `GET /api/channels/:id/exports/:job_id`
+#[utoipa::path(
+    get,
+    path = "/api/channels/{id}/exports/{job_id}",
+    tag = "channels",
+    params(
+        ("id" = Uuid, Path, description = "Channel ID"),
+        ("job_id" = Uuid, Path, description = "Export job ID"),
+    ),
+    responses(
+        (status = 200, body = ChannelExportJobResponse),
+        (status = 404, description = "Export job not found"),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[tracing::instrument(skip(state))]
+pub async fn get_export(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path((channel_id, job_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<ChannelExportJobResponse>, ChannelExportError> {
+    require_export_manager(&state, auth.id, channel_id).await?;
+
+    let job = sqlx::query_as::<_, ChannelExportJob>(
+        "SELECT * FROM channel_export_jobs WHERE id = $1 AND channel_id = $2",
+    )
+    .bind(job_id)
+    .bind(channel_id)
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or(ChannelExportError::JobNotFound)?;
+
+    Ok(Json(job_response(state.s3.as_ref(), job).await))
+}
+
+// ============================================================================
+// Worker
+// ============================================================================
+
+/// Build and upload a channel export.
+///
+/// Waits for one of the [`MAX_CONCURRENT_CHANNEL_EXPORTS`] worker slots
+/// first; the job stays `pending` until then. Any failure marks the job
+/// `failed`.
+pub async fn process_channel_export(
+    pool: &PgPool,
+    s3: &S3Client,
+    job_id: Uuid,
+) -> anyhow::Result<()> {
+    let _slot = CHANNEL_EXPORT_SLOTS
+        .acquire()
+        .await
+        .context("Channel export worker slots closed")?;
+
+    // Claim the job; it may have been recovered as stale while queued
+    let job = sqlx::query_as::<_, ChannelExportJob>(
+        "UPDATE channel_export_jobs
+         SET status = 'processing', updated_at = NOW()
+         WHERE id = $1 AND status = 'pending'
+         RETURNING *",
+    )
+    .bind(job_id)
+    .fetch_optional(pool)
+    .await?;
+    let Some(job) = job else {
+        return Ok(());
+    };
+
+    let result = build_and_upload(pool, s3, &job).await;
+    if let Err(e) = &result {
+        if let Err(db_err) = sqlx::query(
+            "UPDATE channel_export_jobs
+             SET status = 'failed', error_message = $1, completed_at = NOW(), updated_at = NOW()
+             WHERE id = $2",
+        )
+        .bind(e.to_string())
+        .bind(job_id)
+        .execute(pool)
+        .await
+        {
+            tracing::error!(
+                job_id = %job_id,
+                original_error = %e,
+                db_error = %db_err,
+                "Failed to mark channel export as failed; stale-job recovery will handle it"
+            );
+        }
+    }
+    result
+}
+
+async fn build_and_upload(
+    pool: &PgPool,
+    s3: &S3Client,
+    job: &ChannelExportJob,
+) -> anyhow::Result<()> {
+    let channel_id = job.channel_id.context("Channel was deleted")?;
+    let tmp = build_archive(pool, s3, job, channel_id).await?;
+
+    let s3_key = format!("channel-exports/{channel_id}/{}.zip", job.id);
+    let file_size: i64 = s3
+        .upload_from_path(&s3_key, tmp.path(), "application/zip")
+        .await?
+        .try_into()
+        .context("Channel export archive too large")?;
+    let expires_at = Utc::now() + Duration::days(CHANNEL_EXPORT_RETENTION_DAYS);
+
+    sqlx::query(
+        "UPDATE channel_export_jobs
+         SET status = 'completed', s3_key = $1, file_size_bytes = $2, expires_at = $3,
+             completed_at = NOW(), updated_at = NOW()
+         WHERE id = $4",
+    )
+    .bind(&s3_key)
+    .bind(file_size)
+    .bind(expires_at)
+    .bind(job.id)
+    .execute(pool)
+    .await?;
+
+    tracing::info!(
+        job_id = %job.id,
+        channel_id = %channel_id,
+        file_size = file_size,
+        "Channel export completed"
+    );
+    Ok(())
+}
+
+/// Write the transcript, bundled attachments and manifest to a temporary ZIP.
+async fn build_archive(
+    pool: &PgPool,
+    s3: &S3Client,
+    job: &ChannelExportJob,
+    channel_id: Uuid,
+) -> anyhow::Result<tempfile::NamedTempFile> {
+    let channel = db::find_channel_by_id(pool, channel_id)
+        .await?
+        .context("Channel was deleted")?;
+    let format = if job.format == "html" {
+        ExportFormat::Html
+    } else {
+        ExportFormat::Jsonl
+    };
+    let bundle = job.attachments == "bundled";
+
+    let total: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM messages
+         WHERE channel_id = $1 AND deleted_at IS NULL
+           AND ($2::timestamptz IS NULL OR created_at >= $2)
+           AND ($3::timestamptz IS NULL OR created_at < $3)",
+    )
+    .bind(channel_id)
+    .bind(job.range_from)
+    .bind(job.range_to)
+    .fetch_one(pool)
+    .await?;
+    sqlx::query(
+        "UPDATE channel_export_jobs SET messages_total = $1, updated_at = NOW() WHERE id = $2",
+    )
+    .bind(total)
+    .bind(job.id)
+    .execute(pool)
+    .await?;
+
+    let tmp =
+        tempfile::NamedTempFile::new().context("Failed to create temp file for channel export")?;
+    let mut zip = ZipWriter::new(std::io::BufWriter::new(
+        tmp.as_file()
+            .try_clone()
+            .context("Failed to clone temp file handle for ZIP writer")?,
+    ));
+    let options = SimpleFileOptions::default()
+        .compression_method(zip::CompressionMethod::Deflated)
+        .large_file(true);
+
+    // Transcript first; bundled files are collected and written afterwards
+    // because a ZIP entry has to be finished before the next one starts.
+    let transcript_name = match format {
+        ExportFormat::Jsonl => "transcript.jsonl",
+        ExportFormat::Html => "transcript.html",
+    };
+    zip.start_file(transcript_name, options)?;
+    if format == ExportFormat::Html {
+        zip.write_all(html_header(&channel.name, job.range_from, job.range_to).as_bytes())?;
+    }
+
+    let mut to_bundle: Vec<(String, String)> = Vec::new();
+    let mut bundled_bytes: i64 = 0;
+    let mut linked_attachments = 0;
+    let mut exported: i64 = 0;
+    let mut cursor: Option<(DateTime<Utc>, Uuid)> = None;
+
+    loop {
+        let rows: Vec<TranscriptRow> = sqlx::query_as(
+            "SELECT m.id, m.user_id, COALESCE(u.display_name, u.username) AS author_name,
+                    m.content, m.encrypted, m.message_type::text AS message_type,
+                    m.reply_to, m.parent_id, m.created_at, m.edited_at
+             FROM messages m
+             LEFT JOIN users u ON u.id = m.user_id
+             WHERE m.channel_id = $1 AND m.deleted_at IS NULL
+               AND ($2::timestamptz IS NULL OR m.created_at >= $2)
+               AND ($3::timestamptz IS NULL OR m.created_at < $3)
+               AND ($4::timestamptz IS NULL OR (m.created_at, m.id) > ($4, $5))
+             ORDER BY m.created_at, m.id
+             LIMIT $6",
+        )
+        .bind(channel_id)
+        .bind(job.range_from)
+        .bind(job.range_to)
+        .bind(cursor.map(|(ts, _)| ts))
+        .bind(cursor.map(|(_, id)| id))
+        .bind(EXPORT_BATCH_SIZE)
+        .fetch_all(pool)
+        .await?;
+        let Some(last) = rows.last() else {
+            break;
+        };
+        cursor = Some((last.created_at, last.id));
+
+        let ids: Vec<Uuid> = rows.iter().map(|row| row.id).collect();
+        let mut files = db::list_file_attachments_by_messages(pool, &ids).await?;
+
+        for row in &rows {
+            let mut attachments = Vec::new();
+            for file in files.iter_mut().filter(|f| f.message_id == row.id) {
+                let mut entry = TranscriptAttachment {
+                    id: file.id,
+                    filename: file.filename.clone(),
+                    mime_type: file.mime_type.clone(),
+                    size_bytes: file.size_bytes,
+                    path: None,
+                    url: None,
+                };
+                if bundle && bundled_bytes + file.size_bytes <= MAX_BUNDLED_ATTACHMENT_BYTES {
+                    let path = bundled_attachment_path(file.id, &file.filename);
+                    bundled_bytes += file.size_bytes;
+                    to_bundle.push((std::mem::take(&mut file.s3_key), path.clone()));
+                    entry.path = Some(path);
+                } else {
+                    if bundle {
+                        linked_attachments += 1;
+                    }
+                    entry.url = Some(attachment_link(file.id));
+                }
+                attachments.push(entry);
+            }
+
+            let message = TranscriptMessage {
+                id: row.id,
+                author_id: row.user_id,
+                author: row
+                    .author_name
+                    .clone()
+                    .unwrap_or_else(|| "Deleted User".to_string()),
+                content: (!row.encrypted).then(|| row.content.clone()),
+                encrypted: row.encrypted,
+                message_type: row.message_type.clone(),
+                reply_to: row.reply_to,
+                parent_id: row.parent_id,
+                created_at: row.created_at,
+                edited_at: row.edited_at,
+                attachments,
+            };
+            match format {
+                ExportFormat::Jsonl => {
+                    serde_json::to_writer(&mut zip, &message)?;
+                    zip.write_all(b"\n")?;
+                }
+                ExportFormat::Html => zip.write_all(html_message(&message).as_bytes())?,
+            }
+        }
+
+        exported += rows.len() as i64;
+        sqlx::query(
+            "UPDATE channel_export_jobs
+             SET messages_exported = $1, updated_at = NOW()
+             WHERE id = $2",
+        )
+        .bind(exported)
+        .bind(job.id)
+        .execute(pool)
+        .await?;
+
+        if (rows.len() as i64) < EXPORT_BATCH_SIZE {
+            break;
+        }
+    }
+
+    if format == ExportFormat::Html {
+        zip.write_all(HTML_FOOTER.as_bytes())?;
+    }
+
+    let mut bundled_attachments = 0;
+    for (s3_key, path) in to_bundle {
+        let bytes = match s3.get_object_stream(&s3_key).await {
+            Ok(stream) => stream
+                .collect()
+                .await
+                .context("Failed to read attachment from storage")?
+                .into_bytes(),
+            Err(e) => {
+                // Keep going: the transcript still names the file
+                tracing::warn!(job_id = %job.id, s3_key = %s3_key, error = %e, "Attachment missing from storage; skipping");
+                linked_attachments += 1;
+                continue;
+            }
+        };
+        zip.start_file(path, options)?;
+        zip.write_all(&bytes)?;
+        bundled_attachments += 1;
+        sqlx::query("UPDATE channel_export_jobs SET updated_at = NOW() WHERE id = $1")
+            .bind(job.id)
+            .execute(pool)
+            .await?;
+    }
+
+    let manifest = ExportManifest {
+        version: "1.0",
+        exported_at: Utc::now(),
+        guild_id: channel.guild_id,
+        channel_id,
+        channel_name: channel.name,
+        format: format.as_str(),
+        attachments: if bundle {
+            AttachmentMode::Bundled.as_str()
+        } else {
+            AttachmentMode::Links.as_str()
+        },
+        from: job.range_from,
+        to: job.range_to,
+        messages: exported,
+        bundled_attachments,
+        linked_attachments,
+    };
+    zip.start_file("manifest.json", options)?;
+    serde_json::to_writer_pretty(&mut zip, &manifest)?;
+
+    let mut buf_writer = zip
+        .finish()
+        .map_err(|e| anyhow::anyhow!("Failed to finalize channel export archive: {e}"))?;
+    buf_writer
+        .flush()
+        .context("Failed to flush channel export BufWriter")?;
+    drop(buf_writer);
+
+    // sync_all is a blocking syscall — run off the async executor
+    let file = tmp
+        .as_file()
+        .try_clone()
+        .context("Failed to clone file handle for sync")?;
+    tokio::task::spawn_blocking(move || file.sync_all())
+        .await
+        .context("sync_all task panicked")?
+        .context("Failed to sync channel export to disk")?;
+
+    Ok(tmp)
+}
+
+// ============================================================================
+// Maintenance
+// ============================================================================
+
+/// Mark jobs without progress for [`STALE_CHANNEL_EXPORT_MINUTES`] as failed
+/// (e.g. after a restart), so the channel can be exported again.
+///
+/// Returns the number of recovered jobs.
+pub async fn recover_stale_channel_export_jobs(pool: &PgPool) -> anyhow::Result<u64> {
+    let result = sqlx::query(
+        "UPDATE channel_export_jobs
+         SET status = 'failed',
+             error_message = COALESCE(error_message, 'Job stale after restart; please retry'),
+             completed_at = NOW()
+         WHERE status IN ('pending', 'processing')
+           AND updated_at < NOW() - make_interval(mins => $1)",
+    )
+    .bind(STALE_CHANNEL_EXPORT_MINUTES as i32)
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected())
+}
+
+/// Delete expired channel export archives from S3 and mark their jobs expired.
+pub async fn cleanup_expired_channel_exports(
+    pool: &PgPool,
+    s3: &Option<S3Client>,
+) -> anyhow::Result<()> {
+    // Without S3 the archives cannot be deleted; keep the jobs for a later run
+    let Some(s3) = s3 else {
+        tracing::debug!("S3 unavailable — skipping channel export cleanup");
+        return Ok(());
+    };
+
+    let expired_jobs: Vec<(Uuid, Option<String>)> = sqlx::query_as(
+        "SELECT id, s3_key FROM channel_export_jobs
+         WHERE status = 'completed' AND expires_at < NOW()",
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let mut updatable_ids = Vec::new();
+    for (job_id, s3_key) in &expired_jobs {
+        match s3_key.as_deref() {
+            Some(key) => match s3.delete(key).await {
+                Ok(()) => updatable_ids.push(*job_id),
+                Err(e) => {
+                    tracing::warn!(
+                        job_id = %job_id,
+                        s3_key = %key,
+                        error = %e,
+                        "Failed to delete expired channel export from S3; keeping job retryable"
+                    );
+                }
+            },
+            None => updatable_ids.push(*job_id),
+        }
+    }
+
+    if !updatable_ids.is_empty() {
+        sqlx::query(
+            "UPDATE channel_export_jobs SET status = 'expired', s3_key = NULL
+             WHERE id = ANY($1)",
+        )
+        .bind(&updatable_ids)
+        .execute(pool)
+        .await?;
+        tracing::debug!(
+            count = updatable_ids.len(),
+            "Cleaned up expired channel exports"
+        );
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(content: Option<&str>) -> TranscriptMessage {
+        TranscriptMessage {
+            id: Uuid::nil(),
+            author_id: None,
+            author: "<Alice>".to_string(),
+            content: content.map(str::to_string),
+            encrypted: content.is_none(),
+            message_type: "default".to_string(),
+            reply_to: None,
+            parent_id: None,
+            created_at: DateTime::from_timestamp(0, 0).unwrap(),
+            edited_at: None,
+            attachments: vec![TranscriptAttachment {
+                id: Uuid::nil(),
+                filename: "a&b.png".to_string(),
+                mime_type: "image/png".to_string(),
+                size_bytes: 3,
+                path: None,
+                url: Some(attachment_link(Uuid::nil())),
+            }],
+        }
+    }
+
+    #[test]
+    fn html_message_escapes_content() {
+        let html = html_message(&message(Some("<script>alert(1)</script>")));
+        assert!(html.contains("&lt;script&gt;alert(1)&lt;/script&gt;"));
+        assert!(html.contains("&lt;Alice&gt;"));
+        assert!(html.contains(">a&amp;b.png</a>"));
+        assert!(!html.contains("<script>"));
+    }
+
+    #[test]
+    fn html_message_marks_encrypted_content() {
+        let html = html_message(&message(None));
+        assert!(html.contains("[encrypted]"));
+    }
+
+    #[test]
+    fn jsonl_line_omits_empty_fields() {
+        let line = serde_json::to_value(message(Some("hi"))).unwrap();
+        assert_eq!(line["content"], "hi");
+        assert!(line.get("edited_at").is_none());
+        assert!(line.get("reply_to").is_none());
+        assert_eq!(
+            line["attachments"][0]["url"],
+            format!("/api/messages/attachments/{}/download", Uuid::nil())
+        );
+        assert!(line["attachments"][0].get("path").is_none());
+    }
+
+    #[test]
+    fn bundled_paths_are_sanitized() {
+        let id = Uuid::nil();
+        assert_eq!(
+            bundled_attachment_path(id, "../../etc/passwd"),
+            format!("attachments/{id}-_.._etc_passwd")
+        );
+        assert_eq!(
+            bundled_attachment_path(id, "report final.pdf"),
+            format!("attachments/{id}-report_final.pdf")
+        );
+        assert_eq!(bundled_attachment_path(id, ""), format!("attachments/{id}"));
+    }
+}
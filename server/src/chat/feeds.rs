@@ -0,0 +1,535 @@
+//! Channel Feeds
+//!
+//! Read-only JSON Feed 1.1 / Atom export of a guild text channel. Feeds are
+//! authenticated with a per-channel feed token instead of a user session, so
+//! external dashboards can embed announcement channels without a bot account.
+//! A feed only ever shows what its token's creator can still see.
+
+use std::fmt::Write;
+
+use axum::extract::{Path, Query, State};
+use axum::http::{header, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::api::AppState;
+use crate::auth::{hash_token, AuthUser};
+use crate::db::{self, ChannelFeedToken, ChannelType, FeedMessageRow};
+use crate::permissions::{GuildPermissions, PermissionError};
+
+/// Maximum feed tokens per channel.
+const MAX_FEED_TOKENS_PER_CHANNEL: usize = 10;
+
+/// Maximum length of a feed token label.
+const MAX_FEED_TOKEN_NAME_LEN: usize = 64;
+
+/// Maximum characters of message content used as an entry title.
+const ENTRY_TITLE_CHARS: usize = 80;
+
+// ============================================================================
+// Error Type
+// ============================================================================
+
+#[derive(Debug, Error)]
+pub enum FeedError {
+    #[error("Channel not found")]
+    ChannelNotFound,
+
+    #[error("Feed token not found")]
+    TokenNotFound,
+
+    #[error("Invalid feed token")]
+    InvalidToken,
+
+    #[error("{0}")]
+    Validation(String),
+
+    #[error("{0}")]
+    Permission(#[from] PermissionError),
+
+    #[error("Database error")]
+    Database(#[from] sqlx::Error),
+}
+
+impl IntoResponse for FeedError {
+    fn into_response(self) -> Response {
+        let (status, body) = match &self {
+            Self::ChannelNotFound => (
+                StatusCode::NOT_FOUND,
+                serde_json::json!({"error": "not_found", "message": "Channel not found"}),
+            ),
+            Self::TokenNotFound => (
+                StatusCode::NOT_FOUND,
+                serde_json::json!({"error": "not_found", "message": "Feed token not found"}),
+            ),
+            Self::InvalidToken => (
+                StatusCode::UNAUTHORIZED,
+                serde_json::json!({"error": "invalid_token", "message": "Invalid feed token"}),
+            ),
+            Self::Validation(msg) => (
+                StatusCode::BAD_REQUEST,
+                serde_json::json!({"error": "validation", "message": msg}),
+            ),
+            Self::Permission(e) => (
+                StatusCode::FORBIDDEN,
+                serde_json::json!({"error": "permission", "message": e.to_string()}),
+            ),
+            Self::Database(_) => (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                serde_json::json!({"error": "database", "message": "Database error"}),
+            ),
+        };
+        (status, Json(body)).into_response()
+    }
+}
+
+// ============================================================================
+// Types
+// ============================================================================
+
+#[derive(Debug, Deserialize, utoipa::ToSchema)]
+pub struct CreateFeedTokenRequest {
+    /// Label shown in the token list (e.g. the dashboard using it).
+    pub name: String,
+}
+
+/// A newly created feed token. The raw token is only returned once.
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct CreatedFeedToken {
+    #[serde(flatten)]
+    pub feed_token: ChannelFeedToken,
+    /// Raw token to pass as `?token=` on the feed URL.
+    pub token: String,
+}
+
+/// Feed output format.
+#[derive(Debug, Clone, Copy, Default, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum FeedFormat {
+    /// JSON Feed 1.1 (`application/feed+json`).
+    #[default]
+    Json,
+    /// Atom 1.0 (`application/atom+xml`).
+    Atom,
+}
+
+/// Query parameters for the channel feed.
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+pub struct FeedQuery {
+    /// Feed token created via `POST /api/channels/{id}/feed-tokens`.
+    pub token: String,
+    /// Output format (default `json`).
+    #[serde(default)]
+    pub format: FeedFormat,
+    /// Number of messages to include (1-100, default 50).
+    #[serde(default = "default_limit")]
+    pub limit: i64,
+}
+
+const fn default_limit() -> i64 {
+    50
+}
+
+/// JSON Feed 1.1 document.
+#[derive(Debug, Serialize)]
+struct JsonFeed {
+    version: &'static str,
+    title: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<String>,
+    items: Vec<JsonFeedItem>,
+}
+
+#[derive(Debug, Serialize)]
+struct JsonFeedItem {
+    id: String,
+    title: String,
+    content_text: String,
+    date_published: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    date_modified: Option<String>,
+    authors: Vec<JsonFeedAuthor>,
+}
+
+#[derive(Debug, Serialize)]
+struct JsonFeedAuthor {
+    name: String,
+}
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+/// Require `MANAGE_CHANNELS` on a guild text channel.
+async fn require_feed_manager(
+    state: &AppState,
+    user_id: Uuid,
+    channel_id: Uuid,
+) -> Result<db::Channel, FeedError> {
+    let ctx = crate::permissions::require_channel_access(&state.db, user_id, channel_id)
+        .await
+        .map_err(|e| match e {
+            PermissionError::NotFound => FeedError::ChannelNotFound,
+            other => FeedError::Permission(other),
+        })?;
+
+    let channel = db::find_channel_by_id(&state.db, channel_id)
+        .await?
+        .ok_or(FeedError::ChannelNotFound)?;
+    if channel.channel_type != ChannelType::Text {
+        return Err(FeedError::Validation(
+            "Feeds are only available for guild text channels".to_string(),
+        ));
+    }
+
+    if !ctx.has_permission(GuildPermissions::MANAGE_CHANNELS) {
+        return Err(FeedError::Permission(PermissionError::MissingPermission(
+            GuildPermissions::MANAGE_CHANNELS,
+        )));
+    }
+
+    Ok(channel)
+}
+
+/// Generate a random feed token (32 bytes, base64url).
+fn generate_feed_token() -> String {
+    use base64::Engine;
+    use rand::RngCore;
+
+    let mut token_bytes = [0u8; 32];
+    rand::thread_rng().fill_bytes(&mut token_bytes);
+    base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(token_bytes)
+}
+
+fn rfc3339(at: DateTime<Utc>) -> String {
+    at.to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+/// Entry title: the first line of the message, truncated.
+fn entry_title(message: &FeedMessageRow) -> String {
+    let first_line = message.content.lines().next().unwrap_or_default().trim();
+    if first_line.is_empty() {
+        return format!(
+            "Message from {}",
+            message.author_name.as_deref().unwrap_or("Deleted User")
+        );
+    }
+    if first_line.chars().count() > ENTRY_TITLE_CHARS {
+        let truncated: String = first_line.chars().take(ENTRY_TITLE_CHARS - 1).collect();
+        format!("{truncated}…")
+    } else {
+        first_line.to_string()
+    }
+}
+
+/// Escape text for XML, dropping control characters XML 1.0 cannot carry.
+pub(super) fn escape_xml(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            '\t' | '\n' | '\r' => escaped.push(c),
+            c if c.is_control() => {}
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn json_feed(channel: &db::Channel, messages: &[FeedMessageRow]) -> JsonFeed {
+    JsonFeed {
+        version: "https://jsonfeed.org/version/1.1",
+        title: format!("#{}", channel.name),
+        description: channel.topic.clone(),
+        items: messages
+            .iter()
+            .map(|m| JsonFeedItem {
+                id: m.id.to_string(),
+                title: entry_title(m),
+                content_text: m.content.clone(),
+                date_published: rfc3339(m.created_at),
+                date_modified: m.edited_at.map(rfc3339),
+                authors: vec![JsonFeedAuthor {
+                    name: m
+                        .author_name
+                        .clone()
+                        .unwrap_or_else(|| "Deleted User".to_string()),
+                }],
+            })
+            .collect(),
+    }
+}
+
+fn atom_feed(channel: &db::Channel, messages: &[FeedMessageRow]) -> String {
+    let updated = messages
+        .iter()
+        .map(|m| m.edited_at.unwrap_or(m.created_at))
+        .max()
+        .unwrap_or(channel.updated_at);
+
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n");
+    xml.push_str("<feed xmlns=\"http://www.w3.org/2005/Atom\">\n");
+    let _ = writeln!(xml, "  <id>urn:uuid:{}</id>", channel.id);
+    let _ = writeln!(xml, "  <title>#{}</title>", escape_xml(&channel.name));
+    if let Some(topic) = &channel.topic {
+        let _ = writeln!(xml, "  <subtitle>{}</subtitle>", escape_xml(topic));
+    }
+    let _ = writeln!(xml, "  <updated>{}</updated>", rfc3339(updated));
+
+    for m in messages {
+        xml.push_str("  <entry>\n");
+        let _ = writeln!(xml, "    <id>urn:uuid:{}</id>", m.id);
+        let _ = writeln!(xml, "    <title>{}</title>", escape_xml(&entry_title(m)));
+        let _ = writeln!(xml, "    <published>{}</published>", rfc3339(m.created_at));
+        let _ = writeln!(
+            xml,
+            "    <updated>{}</updated>",
+            rfc3339(m.edited_at.unwrap_or(m.created_at))
+        );
+        let _ = writeln!(
+            xml,
+            "    <author><name>{}</name></author>",
+            escape_xml(m.author_name.as_deref().unwrap_or("Deleted User"))
+        );
+        let _ = writeln!(
+            xml,
+            "    <content type=\"text\">{}</content>",
+            escape_xml(&m.content)
+        );
+        xml.push_str("  </entry>\n");
+    }
+
+    xml.push_str("</feed>\n");
+    xml
+}
+
+// ============================================================================
+// Handlers
+// ============================================================================
+
+/// List feed tokens for a channel (requires `MANAGE_CHANNELS`).
+///
+/// `GET /api/channels/:id/feed-tokens`
+#[utoipa::path(
+    get,
+    path = "/api/channels/{id}/feed-tokens",
+    tag = "channels",
+    params(("id" = Uuid, Path, description = "Channel ID")),
+    responses(
+        (status = 200, body = Vec<ChannelFeedToken>),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[tracing::instrument(skip(state))]
+pub async fn list_feed_tokens(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(channel_id): Path<Uuid>,
+) -> Result<Json<Vec<ChannelFeedToken>>, FeedError> {
+    require_feed_manager(&state, auth.id, channel_id).await?;
+    let tokens = db::list_channel_feed_tokens(&state.db, channel_id).await?;
+    Ok(Json(tokens))
+}
+
+/// Create a feed token for a channel (requires `MANAGE_CHANNELS`).
+///
+/// `POST /api/channels/:id/feed-tokens`
+#[utoipa::path(
+    post,
+    path = "/api/channels/{id}/feed-tokens",
+    tag = "channels",
+    params(("id" = Uuid, Path, description = "Channel ID")),
+    request_body = CreateFeedTokenRequest,
+    responses(
+        (status = 201, body = CreatedFeedToken),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[tracing::instrument(skip(state, body))]
+pub async fn create_feed_token(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(channel_id): Path<Uuid>,
+    Json(body): Json<CreateFeedTokenRequest>,
+) -> Result<(StatusCode, Json<CreatedFeedToken>), FeedError> {
+    let channel = require_feed_manager(&state, auth.id, channel_id).await?;
+
+    let name = body.name.trim();
+    if name.is_empty() || name.chars().count() > MAX_FEED_TOKEN_NAME_LEN {
+        return Err(FeedError::Validation(format!(
+            "Name must be 1-{MAX_FEED_TOKEN_NAME_LEN} characters"
+        )));
+    }
+
+    let existing = db::list_channel_feed_tokens(&state.db, channel_id).await?;
+    if existing.len() >= MAX_FEED_TOKENS_PER_CHANNEL {
+        return Err(FeedError::Validation(format!(
+            "A channel can have at most {MAX_FEED_TOKENS_PER_CHANNEL} feed tokens"
+        )));
+    }
+
+    let token = generate_feed_token();
+    let feed_token =
+        db::create_channel_feed_token(&state.db, channel_id, auth.id, name, &hash_token(&token))
+            .await?;
+
+    if let Some(guild_id) = channel.guild_id {
+        crate::guild::audit::record(
+            &state,
+            guild_id,
+            auth.id,
+            "guild.channels.feed_token_created",
+            Some("channel"),
+            Some(channel_id),
+            Some(serde_json::json!({ "token_id": feed_token.id, "name": feed_token.name })),
+        )
+        .await;
+    }
+
+    Ok((
+        StatusCode::CREATED,
+        Json(CreatedFeedToken { feed_token, token }),
+    ))
+}
+
+/// Revoke a feed token (requires `MANAGE_CHANNELS`).
+///
+/// `DELETE /api/channels/:id/feed-tokens/:token_id`
+#[utoipa::path(
+    delete,
+    path = "/api/channels/{id}/feed-tokens/{token_id}",
+    tag = "channels",
+    params(
+        ("id" = Uuid, Path, description = "Channel ID"),
+        ("token_id" = Uuid, Path, description = "Feed token ID"),
+    ),
+    responses(
+        (status = 204, description = "Feed token revoked"),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[tracing::instrument(skip(state))]
+pub async fn delete_feed_token(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path((channel_id, token_id)): Path<(Uuid, Uuid)>,
+) -> Result<StatusCode, FeedError> {
+    let channel = require_feed_manager(&state, auth.id, channel_id).await?;
+
+    if !db::delete_channel_feed_token(&state.db, channel_id, token_id).await? {
+        return Err(FeedError::TokenNotFound);
+    }
+
+    if let Some(guild_id) = channel.guild_id {
+        crate::guild::audit::record(
+            &state,
+            guild_id,
+            auth.id,
+            "guild.channels.feed_token_deleted",
+            Some("channel"),
+            Some(channel_id),
+            Some(serde_json::json!({ "token_id": token_id })),
+        )
+        .await;
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Read a channel's feed as JSON Feed 1.1 or Atom.
+///
+/// `GET /api/channels/:id/feed?token=`
+///
+/// Authenticated by the feed token only. Lists the latest top-level messages,
+/// newest first; encrypted, thread reply and system messages are left out.
+#[utoipa::path(
+    get,
+    path = "/api/channels/{id}/feed",
+    tag = "channels",
+    params(("id" = Uuid, Path, description = "Channel ID"), FeedQuery),
+    responses(
+        (status = 200, description = "JSON Feed 1.1 or Atom document"),
+        (status = 401, description = "Invalid feed token"),
+    ),
+)]
+#[tracing::instrument(skip(state, query), fields(channel_id = %channel_id))]
+pub async fn get_feed(
+    State(state): State<AppState>,
+    Path(channel_id): Path<Uuid>,
+    Query(query): Query<FeedQuery>,
+) -> Result<Response, FeedError> {
+    let creator = db::use_channel_feed_token(&state.db, channel_id, &hash_token(&query.token))
+        .await?
+        .ok_or(FeedError::InvalidToken)?;
+
+    // The feed never exposes more than its creator can still see
+    crate::permissions::require_channel_access(&state.db, creator, channel_id)
+        .await
+        .map_err(|_| FeedError::InvalidToken)?;
+
+    let channel = db::find_channel_by_id(&state.db, channel_id)
+        .await?
+        .ok_or(FeedError::ChannelNotFound)?;
+
+    let limit = query.limit.clamp(1, 100);
+    let messages = db::list_feed_messages(&state.db, channel_id, limit).await?;
+
+    let response = match query.format {
+        FeedFormat::Json => (
+            [(header::CONTENT_TYPE, "application/feed+json")],
+            Json(json_feed(&channel, &messages)),
+        )
+            .into_response(),
+        FeedFormat::Atom => (
+            [(header::CONTENT_TYPE, "application/atom+xml; charset=utf-8")],
+            atom_feed(&channel, &messages),
+        )
+            .into_response(),
+    };
+    Ok(response)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(content: &str) -> FeedMessageRow {
+        FeedMessageRow {
+            id: Uuid::nil(),
+            content: content.to_string(),
+            author_name: Some("Alice".to_string()),
+            edited_at: None,
+            created_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn escapes_xml_and_drops_control_chars() {
+        assert_eq!(
+            escape_xml("<b>\"Tom\" & 'Jerry'</b>\u{0}\n"),
+            "&lt;b&gt;&quot;Tom&quot; &amp; &apos;Jerry&apos;&lt;/b&gt;\n"
+        );
+    }
+
+    #[test]
+    fn entry_title_uses_first_line() {
+        assert_eq!(
+            entry_title(&message("Release notes\nDetails")),
+            "Release notes"
+        );
+        assert_eq!(entry_title(&message("")), "Message from Alice");
+
+        let long = "a".repeat(200);
+        let title = entry_title(&message(&long));
+        assert_eq!(title.chars().count(), ENTRY_TITLE_CHARS);
+        assert!(title.ends_with('…'));
+    }
+}
@@ -0,0 +1,141 @@
+//! Channel Media Gallery
+//!
+//! Lists a channel's attachments directly from `file_attachments` (indexed by
+//! channel), so clients can render a gallery without paging through the full
+//! message history.
+
+use std::collections::{HashMap, HashSet};
+
+use axum::extract::{Path, Query, State};
+use axum::Json;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+use uuid::Uuid;
+
+use super::messages::{AttachmentInfo, AuthorProfile, CursorPaginatedResponse, MessageError};
+use crate::api::AppState;
+use crate::auth::AuthUser;
+use crate::db::{self, AttachmentKind};
+use crate::social::block_cache;
+
+/// Query parameters for the channel attachment listing.
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+pub struct ListChannelAttachmentsQuery {
+    /// Attachment ID cursor; returns attachments older than this one.
+    pub before: Option<Uuid>,
+    /// Only return attachments of this kind.
+    pub kind: Option<AttachmentKind>,
+    #[serde(default = "default_limit")]
+    pub limit: i64,
+}
+
+const fn default_limit() -> i64 {
+    50
+}
+
+/// A single gallery entry.
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct ChannelAttachment {
+    #[serde(flatten)]
+    pub attachment: AttachmentInfo,
+    pub kind: AttachmentKind,
+    pub message_id: Uuid,
+    /// Uploader profile (`None` if the account was deleted).
+    pub uploader: Option<AuthorProfile>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// List attachments in a channel, newest first.
+/// GET /`api/channels/:id/attachments`
+///
+/// Uses the same cursor pagination as the message list: pass `next_cursor`
+/// as `before` to fetch the next page.
+#[utoipa::path(
+    get,
+    path = "/api/channels/{id}/attachments",
+    tag = "channels",
+    params(("id" = Uuid, Path, description = "Channel ID"), ListChannelAttachmentsQuery),
+    responses(
+        (status = 200, body = CursorPaginatedResponse<ChannelAttachment>),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[tracing::instrument(skip(state), fields(user_id = %auth_user.id, channel_id = %channel_id))]
+pub async fn list_channel_attachments(
+    State(state): State<AppState>,
+    auth_user: AuthUser,
+    Path(channel_id): Path<Uuid>,
+    Query(query): Query<ListChannelAttachmentsQuery>,
+) -> Result<Json<CursorPaginatedResponse<ChannelAttachment>>, MessageError> {
+    let _ = db::find_channel_by_id(&state.db, channel_id)
+        .await?
+        .ok_or(MessageError::ChannelNotFound)?;
+
+    crate::permissions::require_channel_access(&state.db, auth_user.id, channel_id)
+        .await
+        .map_err(|_| MessageError::Forbidden)?;
+
+    // Hide uploads from blocked users in both directions, as in the message list
+    let blocked_ids = block_cache::load_blocked_users(&state.db, &state.redis, auth_user.id)
+        .await
+        .unwrap_or_else(|e| {
+            warn!(user_id = %auth_user.id, error = %e, "Failed to load blocked users, attachment filtering may be incomplete");
+            HashSet::default()
+        });
+    let blocked_by_ids = block_cache::load_blocked_by(&state.db, &state.redis, auth_user.id)
+        .await
+        .unwrap_or_else(|e| {
+            warn!(user_id = %auth_user.id, error = %e, "Failed to load blocked-by users, attachment filtering may be incomplete");
+            HashSet::default()
+        });
+    let excluded: Vec<Uuid> = blocked_ids.union(&blocked_by_ids).copied().collect();
+
+    let limit = query.limit.clamp(1, 100);
+
+    // Fetch one extra row to determine if there are more
+    let mut rows = db::list_channel_attachments(
+        &state.db,
+        channel_id,
+        query.before,
+        query.kind,
+        &excluded,
+        limit + 1,
+    )
+    .await?;
+
+    let has_more = rows.len() as i64 > limit;
+    if has_more {
+        rows.pop();
+    }
+
+    let uploader_ids: Vec<Uuid> = rows.iter().filter_map(|r| r.uploader_id).collect();
+    let users: HashMap<Uuid, AuthorProfile> = db::find_users_by_ids(&state.db, &uploader_ids)
+        .await?
+        .into_iter()
+        .map(|u| (u.id, AuthorProfile::from(u)))
+        .collect();
+
+    let items: Vec<ChannelAttachment> = rows
+        .into_iter()
+        .map(|row| ChannelAttachment {
+            attachment: AttachmentInfo::from_db(&row.attachment),
+            kind: AttachmentKind::from_mime(&row.attachment.mime_type),
+            message_id: row.attachment.message_id,
+            uploader: row.uploader_id.and_then(|id| users.get(&id).cloned()),
+            created_at: row.attachment.created_at,
+        })
+        .collect();
+
+    let next_cursor = if has_more {
+        items.last().map(|a| a.attachment.id)
+    } else {
+        None
+    };
+
+    Ok(Json(CursorPaginatedResponse {
+        items,
+        has_more,
+        next_cursor,
+    }))
+}
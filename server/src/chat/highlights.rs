@@ -0,0 +1,170 @@
+//! Highlight Keywords
+//!
+//! Users list words in the `highlight_keywords` field of their synced
+//! notification preferences. A guild message containing one of them notifies
+//! the user like an @mention: a `message_highlight` event on their sessions
+//! and a push notification when they are offline.
+//!
+//! Keywords and content are normalized like the content filter
+//! (`filter_engine::normalize`) and match whole words only. Members who muted
+//! the channel, cannot view it, or have a block with the author are skipped.
+
+use sqlx::PgPool;
+use tracing::warn;
+use uuid::Uuid;
+
+use crate::api::AppState;
+use crate::db;
+use crate::moderation::filter_engine::normalize;
+use crate::push::types::PushJob;
+use crate::social::block_cache;
+use crate::ws::{broadcast_to_user, ServerEvent};
+
+/// Most highlight keywords a user can configure.
+pub const MAX_HIGHLIGHT_KEYWORDS: usize = 25;
+
+/// First of `keywords` that appears in `content` as a whole word.
+pub fn match_keyword<'a>(keywords: &'a [String], content: &str) -> Option<&'a str> {
+    let content = normalize(content);
+    keywords
+        .iter()
+        .find(|keyword| contains_word(&content, &normalize(keyword)))
+        .map(String::as_str)
+}
+
+/// Whether `word` occurs in `text` without a letter or digit on either side.
+fn contains_word(text: &str, word: &str) -> bool {
+    if word.is_empty() {
+        return false;
+    }
+    text.match_indices(word).any(|(start, _)| {
+        let before = text[..start].chars().next_back();
+        let after = text[start + word.len()..].chars().next();
+        !before.is_some_and(char::is_alphanumeric) && !after.is_some_and(char::is_alphanumeric)
+    })
+}
+
+/// Guild members other than the author whose highlight keywords match
+/// `content`, with the keyword that matched.
+pub async fn highlighted_recipients(
+    pool: &PgPool,
+    guild_id: Uuid,
+    channel_id: Uuid,
+    author_id: Uuid,
+    content: &str,
+) -> Result<Vec<(Uuid, String)>, sqlx::Error> {
+    let candidates: Vec<(Uuid, serde_json::Value)> = sqlx::query_as(
+        r"SELECT up.user_id, up.preferences->'highlight_keywords'
+          FROM user_preferences up
+          JOIN guild_members gm ON gm.user_id = up.user_id AND gm.guild_id = $1
+          WHERE up.user_id <> $2
+            AND jsonb_typeof(up.preferences->'highlight_keywords') = 'array'
+            AND jsonb_array_length(up.preferences->'highlight_keywords') > 0
+            AND (up.preferences->'channel_notifications'->>$3) IS DISTINCT FROM 'muted'",
+    )
+    .bind(guild_id)
+    .bind(author_id)
+    .bind(channel_id.to_string())
+    .fetch_all(pool)
+    .await?;
+
+    let mut recipients = Vec::new();
+    for (user_id, keywords) in candidates {
+        let keywords: Vec<String> = serde_json::from_value(keywords).unwrap_or_default();
+        let Some(keyword) = match_keyword(&keywords, content) else {
+            continue;
+        };
+        if crate::permissions::require_channel_access(pool, user_id, channel_id)
+            .await
+            .is_ok()
+        {
+            recipients.push((user_id, keyword.to_string()));
+        }
+    }
+    Ok(recipients)
+}
+
+/// Notify highlighted members of a new guild message in the background.
+///
+/// `push_job` is queued when someone was highlighted, for messages that did
+/// not already queue one for their mentions.
+pub(crate) fn spawn_highlight_notifications(
+    state: &AppState,
+    guild_id: Uuid,
+    author_id: Uuid,
+    message: &db::Message,
+    push_job: Option<PushJob>,
+) {
+    let db = state.db.clone();
+    let redis = state.redis.clone();
+    let (channel_id, message_id) = (message.channel_id, message.id);
+    let content = message.content.clone();
+    tokio::spawn(async move {
+        let recipients = match highlighted_recipients(
+            &db, guild_id, channel_id, author_id, &content,
+        )
+        .await
+        {
+            Ok(recipients) => recipients,
+            Err(e) => {
+                warn!(channel_id = %channel_id, error = %e, "Failed to resolve highlight recipients");
+                return;
+            }
+        };
+        if recipients.is_empty() {
+            return;
+        }
+
+        for (user_id, keyword) in recipients {
+            if block_cache::is_blocked_either_direction(&redis, author_id, user_id)
+                .await
+                .unwrap_or(true)
+            {
+                continue;
+            }
+            if let Err(e) = broadcast_to_user(
+                &redis,
+                user_id,
+                &ServerEvent::MessageHighlight {
+                    channel_id,
+                    message_id,
+                    keyword,
+                },
+            )
+            .await
+            {
+                warn!(user_id = %user_id, error = %e, "Failed to send highlight event");
+            }
+        }
+
+        if let Some(job) = push_job {
+            crate::push::worker::enqueue_or_warn(&redis, &job).await;
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keywords(words: &[&str]) -> Vec<String> {
+        words.iter().map(|w| (*w).to_string()).collect()
+    }
+
+    #[test]
+    fn test_match_keyword() {
+        let words = keywords(&["release", "Kaiku Bot"]);
+        assert_eq!(
+            match_keyword(&words, "When is the RELEASE?"),
+            Some("release")
+        );
+        assert_eq!(
+            match_keyword(&words, "ask kaiku bot about it"),
+            Some("Kaiku Bot")
+        );
+        // Whole words only
+        assert_eq!(match_keyword(&words, "prereleases are out"), None);
+        assert_eq!(match_keyword(&words, "nothing here"), None);
+        assert_eq!(match_keyword(&keywords(&[""]), "anything"), None);
+    }
+}
@@ -0,0 +1,573 @@
+//! Incoming Webhooks
+//!
+//! Channel-scoped webhook URLs that external services (CI, monitoring,
+//! ticket trackers) POST JSON to in order to create messages, without a bot
+//! account. Each message is shown under the webhook's name and avatar, which
+//! a request may override. Posting follows the upload handlers' checks for
+//! the webhook's creator: channel access, `SEND_MESSAGES` and no active
+//! timeout. Each webhook is rate limited on its own, independent of the
+//! caller's IP.
+
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tracing::warn;
+use uuid::Uuid;
+
+use super::messages::{validate_message_content, AuthorProfile, MessageResponse};
+use crate::api::AppState;
+use crate::auth::{hash_token, AuthUser};
+use crate::db::{self, ChannelIncomingWebhook, ChannelType};
+use crate::moderation::filter_queries::{log_moderation_action, LogActionParams};
+use crate::moderation::filter_types::FilterAction;
+use crate::permissions::{GuildPermissions, PermissionError};
+use crate::ratelimit::{RateLimitCategory, RateLimitError};
+use crate::ws::{broadcast_to_channel, ServerEvent};
+
+/// Maximum incoming webhooks per channel.
+const MAX_WEBHOOKS_PER_CHANNEL: usize = 10;
+
+/// Maximum length of a webhook (or per-message) author name.
+const MAX_WEBHOOK_NAME_LEN: usize = 80;
+
+/// Maximum length of an avatar URL.
+const MAX_AVATAR_URL_LEN: usize = 2048;
+
+// ============================================================================
+// Error Type
+// ============================================================================
+
+#[derive(Debug, Error)]
+pub enum IncomingWebhookError {
+    #[error("Channel not found")]
+    ChannelNotFound,
+
+    #[error("Webhook not found")]
+    WebhookNotFound,
+
+    #[error("Invalid webhook token")]
+    InvalidToken,
+
+    #[error("{0}")]
+    Validation(String),
+
+    #[error("{0}")]
+    Permission(#[from] PermissionError),
+
+    #[error("Rate limited")]
+    RateLimited(RateLimitError),
+
+    #[error("Database error")]
+    Database(#[from] sqlx::Error),
+}
+
+impl IntoResponse for IncomingWebhookError {
+    fn into_response(self) -> Response {
+        let (status, body) = match self {
+            Self::RateLimited(e) => return e.into_response(),
+            Self::ChannelNotFound => (
+                StatusCode::NOT_FOUND,
+                serde_json::json!({"error": "not_found", "message": "Channel not found"}),
+            ),
+            Self::WebhookNotFound => (
+                StatusCode::NOT_FOUND,
+                serde_json::json!({"error": "not_found", "message": "Webhook not found"}),
+            ),
+            Self::InvalidToken => (
+                StatusCode::UNAUTHORIZED,
+                serde_json::json!({"error": "invalid_token", "message": "Invalid webhook token"}),
+            ),
+            Self::Validation(msg) => (
+                StatusCode::BAD_REQUEST,
+                serde_json::json!({"error": "validation", "message": msg}),
+            ),
+            Self::Permission(e) => (
+                StatusCode::FORBIDDEN,
+                serde_json::json!({"error": "permission", "message": e.to_string()}),
+            ),
+            Self::Database(_) => (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                serde_json::json!({"error": "database", "message": "Database error"}),
+            ),
+        };
+        (status, Json(body)).into_response()
+    }
+}
+
+// ============================================================================
+// Types
+// ============================================================================
+
+#[derive(Debug, Deserialize, utoipa::ToSchema)]
+pub struct CreateIncomingWebhookRequest {
+    /// Author name shown on posted messages.
+    pub name: String,
+    /// Author avatar shown on posted messages (HTTPS).
+    pub avatar_url: Option<String>,
+}
+
+/// A newly created incoming webhook. The raw token is only returned once.
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct CreatedIncomingWebhook {
+    #[serde(flatten)]
+    pub webhook: ChannelIncomingWebhook,
+    /// Raw token; the webhook URL is
+    /// `/api/channels/{channel_id}/webhooks/{id}/{token}`.
+    pub token: String,
+}
+
+/// Message posted through an incoming webhook.
+#[derive(Debug, Deserialize, utoipa::ToSchema)]
+pub struct ExecuteWebhookRequest {
+    /// Message content.
+    pub content: String,
+    /// Author name for this message, instead of the webhook's.
+    pub username: Option<String>,
+    /// Author avatar for this message, instead of the webhook's (HTTPS).
+    pub avatar_url: Option<String>,
+}
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+/// Look up a guild text channel; webhooks are not available anywhere else.
+async fn find_text_channel(
+    state: &AppState,
+    channel_id: Uuid,
+) -> Result<db::Channel, IncomingWebhookError> {
+    let channel = db::find_channel_by_id(&state.db, channel_id)
+        .await?
+        .ok_or(IncomingWebhookError::ChannelNotFound)?;
+    if channel.channel_type != ChannelType::Text || channel.guild_id.is_none() {
+        return Err(IncomingWebhookError::Validation(
+            "Webhooks are only available for guild text channels".to_string(),
+        ));
+    }
+    Ok(channel)
+}
+
+/// The upload handlers' posting checks: channel access, `SEND_MESSAGES` and
+/// no active timeout. Returns the permission context for further checks.
+async fn require_can_post(
+    state: &AppState,
+    user_id: Uuid,
+    channel: &db::Channel,
+) -> Result<crate::permissions::MemberPermissionContext, IncomingWebhookError> {
+    let ctx = crate::permissions::require_channel_access(&state.db, user_id, channel.id)
+        .await
+        .map_err(|e| match e {
+            PermissionError::NotFound => IncomingWebhookError::ChannelNotFound,
+            other => IncomingWebhookError::Permission(other),
+        })?;
+
+    if !ctx.has_permission(GuildPermissions::SEND_MESSAGES) {
+        return Err(IncomingWebhookError::Permission(
+            PermissionError::MissingPermission(GuildPermissions::SEND_MESSAGES),
+        ));
+    }
+
+    if let Some(guild_id) = channel.guild_id {
+        if db::is_guild_suspended(&state.db, guild_id).await?
+            || db::get_member_timeout(&state.db, guild_id, user_id)
+                .await?
+                .is_some()
+        {
+            return Err(IncomingWebhookError::Permission(PermissionError::Forbidden));
+        }
+    }
+
+    Ok(ctx)
+}
+
+/// Require `MANAGE_CHANNELS` on a guild text channel the user can post in.
+async fn require_webhook_manager(
+    state: &AppState,
+    user_id: Uuid,
+    channel_id: Uuid,
+) -> Result<db::Channel, IncomingWebhookError> {
+    let channel = find_text_channel(state, channel_id).await?;
+    let ctx = require_can_post(state, user_id, &channel).await?;
+
+    if !ctx.has_permission(GuildPermissions::MANAGE_CHANNELS) {
+        return Err(IncomingWebhookError::Permission(
+            PermissionError::MissingPermission(GuildPermissions::MANAGE_CHANNELS),
+        ));
+    }
+
+    Ok(channel)
+}
+
+/// Validate an author name, returning it trimmed.
+fn validate_name(name: &str) -> Result<&str, IncomingWebhookError> {
+    let name = name.trim();
+    if name.is_empty() || name.chars().count() > MAX_WEBHOOK_NAME_LEN {
+        return Err(IncomingWebhookError::Validation(format!(
+            "Name must be 1-{MAX_WEBHOOK_NAME_LEN} characters"
+        )));
+    }
+    Ok(name)
+}
+
+/// Validate an avatar URL; empty means none.
+fn validate_avatar_url(url: Option<&str>) -> Result<Option<&str>, IncomingWebhookError> {
+    let Some(url) = url.map(str::trim).filter(|u| !u.is_empty()) else {
+        return Ok(None);
+    };
+    if url.len() > MAX_AVATAR_URL_LEN {
+        return Err(IncomingWebhookError::Validation(format!(
+            "Avatar URL too long (max {MAX_AVATAR_URL_LEN} characters)"
+        )));
+    }
+    if !url.starts_with("https://") {
+        return Err(IncomingWebhookError::Validation(
+            "Avatar URL must use HTTPS".to_string(),
+        ));
+    }
+    Ok(Some(url))
+}
+
+/// Generate a random webhook token (32 bytes, base64url).
+fn generate_webhook_token() -> String {
+    use base64::Engine;
+    use rand::RngCore;
+
+    let mut token_bytes = [0u8; 32];
+    rand::thread_rng().fill_bytes(&mut token_bytes);
+    base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(token_bytes)
+}
+
+/// Per-webhook rate limit, so one leaked URL cannot flood a channel from
+/// many addresses.
+async fn check_rate_limit(state: &AppState, webhook_id: Uuid) -> Result<(), IncomingWebhookError> {
+    let Some(limiter) = &state.rate_limiter else {
+        return Ok(());
+    };
+    match limiter
+        .check(RateLimitCategory::IncomingWebhook, &webhook_id.to_string())
+        .await
+    {
+        Ok(result) if result.allowed => Ok(()),
+        Ok(result) => Err(IncomingWebhookError::RateLimited(
+            RateLimitError::LimitExceeded(result),
+        )),
+        Err(RateLimitError::RedisUnavailable) if limiter.config().fail_open => {
+            warn!(webhook_id = %webhook_id, "Redis unavailable, skipping webhook rate limit");
+            Ok(())
+        }
+        Err(e) => Err(IncomingWebhookError::RateLimited(e)),
+    }
+}
+
+// ============================================================================
+// Handlers
+// ============================================================================
+
+/// List incoming webhooks for a channel (requires `MANAGE_CHANNELS`).
+///
+/// `GET /api/channels/:id/webhooks`
+#[utoipa::path(
+    get,
+    path = "/api/channels/{id}/webhooks",
+    tag = "channels",
+    params(("id" = Uuid, Path, description = "Channel ID")),
+    responses(
+        (status = 200, body = Vec<ChannelIncomingWebhook>),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[tracing::instrument(skip(state))]
+pub async fn list_webhooks(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(channel_id): Path<Uuid>,
+) -> Result<Json<Vec<ChannelIncomingWebhook>>, IncomingWebhookError> {
+    require_webhook_manager(&state, auth.id, channel_id).await?;
+    let webhooks = db::list_channel_incoming_webhooks(&state.db, channel_id).await?;
+    Ok(Json(webhooks))
+}
+
+/// Create an incoming webhook for a channel (requires `MANAGE_CHANNELS`).
+///
+/// `POST /api/channels/:id/webhooks`
+#[utoipa::path(
+    post,
+    path = "/api/channels/{id}/webhooks",
+    tag = "channels",
+    params(("id" = Uuid, Path, description = "Channel ID")),
+    request_body = CreateIncomingWebhookRequest,
+    responses(
+        (status = 201, body = CreatedIncomingWebhook),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[tracing::instrument(skip(state, body))]
+pub async fn create_webhook(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(channel_id): Path<Uuid>,
+    Json(body): Json<CreateIncomingWebhookRequest>,
+) -> Result<(StatusCode, Json<CreatedIncomingWebhook>), IncomingWebhookError> {
+    let channel = require_webhook_manager(&state, auth.id, channel_id).await?;
+
+    let name = validate_name(&body.name)?;
+    let avatar_url = validate_avatar_url(body.avatar_url.as_deref())?;
+
+    let existing = db::list_channel_incoming_webhooks(&state.db, channel_id).await?;
+    if existing.len() >= MAX_WEBHOOKS_PER_CHANNEL {
+        return Err(IncomingWebhookError::Validation(format!(
+            "A channel can have at most {MAX_WEBHOOKS_PER_CHANNEL} webhooks"
+        )));
+    }
+
+    let token = generate_webhook_token();
+    let webhook = db::create_channel_incoming_webhook(
+        &state.db,
+        channel_id,
+        auth.id,
+        name,
+        avatar_url,
+        &hash_token(&token),
+    )
+    .await?;
+
+    if let Some(guild_id) = channel.guild_id {
+        crate::guild::audit::record(
+            &state,
+            guild_id,
+            auth.id,
+            "guild.channels.webhook_created",
+            Some("channel"),
+            Some(channel_id),
+            Some(serde_json::json!({ "webhook_id": webhook.id, "name": webhook.name })),
+        )
+        .await;
+    }
+
+    Ok((
+        StatusCode::CREATED,
+        Json(CreatedIncomingWebhook { webhook, token }),
+    ))
+}
+
+/// Revoke an incoming webhook (requires `MANAGE_CHANNELS`).
+///
+/// Messages it already posted keep its name and avatar.
+///
+/// `DELETE /api/channels/:id/webhooks/:webhook_id`
+#[utoipa::path(
+    delete,
+    path = "/api/channels/{id}/webhooks/{webhook_id}",
+    tag = "channels",
+    params(
+        ("id" = Uuid, Path, description = "Channel ID"),
+        ("webhook_id" = Uuid, Path, description = "Webhook ID"),
+    ),
+    responses(
+        (status = 204, description = "Webhook revoked"),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[tracing::instrument(skip(state))]
+pub async fn delete_webhook(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path((channel_id, webhook_id)): Path<(Uuid, Uuid)>,
+) -> Result<StatusCode, IncomingWebhookError> {
+    let channel = require_webhook_manager(&state, auth.id, channel_id).await?;
+
+    if !db::delete_channel_incoming_webhook(&state.db, channel_id, webhook_id).await? {
+        return Err(IncomingWebhookError::WebhookNotFound);
+    }
+
+    if let Some(guild_id) = channel.guild_id {
+        crate::guild::audit::record(
+            &state,
+            guild_id,
+            auth.id,
+            "guild.channels.webhook_deleted",
+            Some("channel"),
+            Some(channel_id),
+            Some(serde_json::json!({ "webhook_id": webhook_id })),
+        )
+        .await;
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Post a message through an incoming webhook.
+///
+/// `POST /api/channels/:id/webhooks/:webhook_id/:token`
+///
+/// Authenticated by the token in the URL only. Fails with 403 once the
+/// webhook's creator can no longer post in the channel.
+#[utoipa::path(
+    post,
+    path = "/api/channels/{id}/webhooks/{webhook_id}/{token}",
+    tag = "channels",
+    params(
+        ("id" = Uuid, Path, description = "Channel ID"),
+        ("webhook_id" = Uuid, Path, description = "Webhook ID"),
+        ("token" = String, Path, description = "Webhook token"),
+    ),
+    request_body = ExecuteWebhookRequest,
+    responses(
+        (status = 201, body = MessageResponse),
+        (status = 400, description = "Invalid content, name or avatar"),
+        (status = 401, description = "Invalid webhook token"),
+        (status = 403, description = "Webhook creator can no longer post here"),
+        (status = 429, description = "Webhook rate limit exceeded"),
+    ),
+)]
+#[tracing::instrument(skip(state, token, body), fields(channel_id = %channel_id, webhook_id = %webhook_id))]
+pub async fn execute_webhook(
+    State(state): State<AppState>,
+    Path((channel_id, webhook_id, token)): Path<(Uuid, Uuid, String)>,
+    Json(body): Json<ExecuteWebhookRequest>,
+) -> Result<(StatusCode, Json<MessageResponse>), IncomingWebhookError> {
+    let webhook =
+        db::use_channel_incoming_webhook(&state.db, channel_id, webhook_id, &hash_token(&token))
+            .await?
+            .ok_or(IncomingWebhookError::InvalidToken)?;
+
+    check_rate_limit(&state, webhook.id).await?;
+
+    let channel = find_text_channel(&state, channel_id).await?;
+    require_can_post(&state, webhook.created_by, &channel).await?;
+
+    if let Err(e) = validate_message_content(&body.content) {
+        return Err(IncomingWebhookError::Validation(
+            e.message
+                .as_ref()
+                .map_or_else(|| "Invalid content".to_string(), ToString::to_string),
+        ));
+    }
+    let name = match body.username.as_deref() {
+        Some(username) => validate_name(username)?,
+        None => webhook.name.as_str(),
+    };
+    let avatar_url = match body.avatar_url.as_deref() {
+        Some(url) => validate_avatar_url(Some(url))?,
+        None => webhook.avatar_url.as_deref(),
+    };
+
+    // Content filter; matches are attributed to the webhook's creator
+    if let Some(guild_id) = channel.guild_id {
+        if let Ok(engine) = state.filter_cache.get_or_build(&state.db, guild_id).await {
+            let result = engine.check_message(&body.content, channel_id);
+            for m in result.matches.iter().filter(|m| {
+                result.blocked || m.action == FilterAction::Log || m.action == FilterAction::Warn
+            }) {
+                log_moderation_action(
+                    &state.db,
+                    &LogActionParams {
+                        guild_id,
+                        user_id: webhook.created_by,
+                        channel_id,
+                        action: m.action,
+                        category: Some(m.category),
+                        matched_pattern: &m.matched_pattern,
+                        original_content: &body.content,
+                        custom_pattern_id: m.custom_pattern_id,
+                    },
+                )
+                .await
+                .ok();
+            }
+            if result.blocked {
+                return Err(IncomingWebhookError::Validation(
+                    "Your message was blocked by the server's content filter.".to_string(),
+                ));
+            }
+        }
+    }
+
+    let message = db::create_webhook_message(
+        &state.db,
+        webhook.id,
+        channel_id,
+        &body.content,
+        name,
+        avatar_url,
+    )
+    .await?;
+
+    let response = MessageResponse {
+        id: message.id,
+        channel_id: message.channel_id,
+        author: AuthorProfile::webhook(
+            Some(webhook.id),
+            name.to_string(),
+            avatar_url.map(str::to_string),
+        ),
+        message_type: message.message_type,
+        content: message.content.clone(),
+        encrypted: false,
+        attachments: vec![],
+        reply_to: None,
+        parent_id: None,
+        thread_reply_count: 0,
+        thread_last_reply_at: None,
+        edited_at: None,
+        created_at: message.created_at,
+        mention_type: None,
+        reactions: None,
+        thread_info: None,
+        embeds: vec![],
+        webhook: true,
+        system_text: None,
+        content_locale: None,
+        forwarded_from: None,
+        emojis: crate::guild::emojis::resolve_content_emojis(
+            &state.db,
+            channel_id,
+            &message.content,
+        )
+        .await?,
+    };
+
+    let message_json = serde_json::to_value(&response).unwrap_or_default();
+    if let Err(e) = broadcast_to_channel(
+        &state.redis,
+        channel_id,
+        &ServerEvent::MessageNew {
+            channel_id,
+            message: message_json,
+        },
+    )
+    .await
+    {
+        warn!(channel_id = %channel_id, error = %e, "Failed to broadcast webhook message");
+    }
+
+    super::unfurl::spawn_unfurl(&state, &message);
+
+    Ok((StatusCode::CREATED, Json(response)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validate_name_trims_and_bounds_length() {
+        assert_eq!(validate_name("  CI  ").unwrap(), "CI");
+        assert!(validate_name("   ").is_err());
+        assert!(validate_name(&"a".repeat(MAX_WEBHOOK_NAME_LEN + 1)).is_err());
+    }
+
+    #[test]
+    fn validate_avatar_url_requires_https() {
+        assert_eq!(validate_avatar_url(None).unwrap(), None);
+        assert_eq!(validate_avatar_url(Some(" ")).unwrap(), None);
+        assert_eq!(
+            validate_avatar_url(Some("https://example.com/a.png")).unwrap(),
+            Some("https://example.com/a.png")
+        );
+        assert!(validate_avatar_url(Some("http://example.com/a.png")).is_err());
+    }
+}
@@ -121,6 +121,17 @@ pub fn process_image(
     })
 }
 
+/// Read intrinsic dimensions from the image header without decoding pixels.
+///
+/// Cheap enough to run on the request path, so attachments carry their size
+/// before the background variant pipeline has finished.
+pub fn read_dimensions(data: &[u8], mime_type: &str) -> Result<(u32, u32), ProcessingError> {
+    let format = mime_to_format(mime_type)?;
+    ImageReader::with_format(Cursor::new(data), format)
+        .into_dimensions()
+        .map_err(|e| ProcessingError::DecodeFailed(e.to_string()))
+}
+
 /// Map MIME type to `image` crate format.
 fn mime_to_format(mime_type: &str) -> Result<ImageFormat, ProcessingError> {
     match mime_type {
@@ -267,6 +278,20 @@ mod tests {
         assert!(matches!(err, Err(ProcessingError::UnsupportedFormat(_))));
     }
 
+    #[test]
+    fn test_read_dimensions_from_header() {
+        let data = create_test_png(640, 480);
+        assert_eq!(read_dimensions(&data, "image/png").unwrap(), (640, 480));
+
+        let data = create_test_gif(32, 16);
+        assert_eq!(read_dimensions(&data, "image/gif").unwrap(), (32, 16));
+
+        assert!(matches!(
+            read_dimensions(b"not an image", "image/png"),
+            Err(ProcessingError::DecodeFailed(_))
+        ));
+    }
+
     #[test]
     fn test_medium_only_for_mid_size_image() {
         // Image bigger than thumbnail but smaller than medium
@@ -17,9 +17,14 @@ use validator::Validate;
 
 use crate::api::AppState;
 use crate::auth::AuthUser;
+use crate::chat::system_messages;
 use crate::db;
-use crate::moderation::filter_queries;
+use crate::guild::emojis;
+use crate::guild::member_profiles::{self, MemberProfile};
+use crate::guild::types::MessageEmoji;
+use crate::i18n::{LocalizedText, RequestLocale};
 use crate::moderation::filter_types::FilterAction;
+use crate::moderation::{escalation, filter_queries};
 use crate::permissions::{get_member_permission_context, GuildPermissions};
 use crate::social::block_cache;
 use crate::ws::{broadcast_admin_event, broadcast_to_channel, broadcast_to_user, ServerEvent};
@@ -35,6 +40,9 @@ pub enum MessageError {
     Forbidden,
     Blocked,
     ContentFiltered,
+    TimedOut(DateTime<Utc>),
+    OnboardingPending,
+    GuildSuspended,
     Validation(String),
     Database(#[allow(dead_code)] sqlx::Error),
 }
@@ -67,6 +75,24 @@ impl IntoResponse for MessageError {
                 "CONTENT_FILTERED",
                 "Your message was blocked by the server's content filter.".to_string(),
             ),
+            Self::TimedOut(until) => (
+                StatusCode::FORBIDDEN,
+                "TIMED_OUT",
+                format!(
+                    "You are timed out in this server until {}",
+                    until.to_rfc3339()
+                ),
+            ),
+            Self::OnboardingPending => (
+                StatusCode::FORBIDDEN,
+                "ONBOARDING_PENDING",
+                "Accept the server rules before posting".to_string(),
+            ),
+            Self::GuildSuspended => (
+                StatusCode::FORBIDDEN,
+                "GUILD_SUSPENDED",
+                "This server has been suspended".to_string(),
+            ),
             Self::Validation(msg) => (StatusCode::BAD_REQUEST, "VALIDATION_ERROR", msg.clone()),
             Self::Database(_) => (
                 StatusCode::INTERNAL_SERVER_ERROR,
@@ -102,6 +128,61 @@ pub struct AuthorProfile {
     pub status: String,
 }
 
+impl AuthorProfile {
+    /// Author shown for a message posted through an incoming webhook.
+    pub(crate) fn webhook(
+        webhook_id: Option<Uuid>,
+        name: String,
+        avatar_url: Option<String>,
+    ) -> Self {
+        Self {
+            id: webhook_id.unwrap_or(Uuid::nil()),
+            username: name.clone(),
+            display_name: name,
+            avatar_url,
+            status: "offline".to_string(),
+        }
+    }
+
+    /// Author shown for a message whose account was deleted. The id is the
+    /// author's tombstone, so messages by the same deleted user still group.
+    pub(crate) fn deleted(tombstone_id: Option<Uuid>) -> Self {
+        Self {
+            id: tombstone_id.unwrap_or(Uuid::nil()),
+            username: "deleted".to_string(),
+            display_name: "Deleted User".to_string(),
+            avatar_url: None,
+            status: "offline".to_string(),
+        }
+    }
+
+    /// Show the author's guild nickname and guild avatar, if they set them.
+    pub(crate) fn with_member_profile(mut self, profile: Option<&MemberProfile>) -> Self {
+        if let Some(profile) = profile {
+            if let Some(nickname) = &profile.nickname {
+                self.display_name.clone_from(nickname);
+            }
+            if profile.avatar_url.is_some() {
+                self.avatar_url.clone_from(&profile.avatar_url);
+            }
+        }
+        self
+    }
+
+    /// Load the author's guild profile for `channel_id` and apply it.
+    ///
+    /// Failures are logged and leave the global profile in place.
+    pub(crate) async fn with_guild_identity(self, pool: &sqlx::PgPool, channel_id: Uuid) -> Self {
+        match member_profiles::load_author_profile(pool, channel_id, self.id).await {
+            Ok(profile) => self.with_member_profile(profile.as_ref()),
+            Err(e) => {
+                warn!(channel_id = %channel_id, error = %e, "Failed to load author guild profile");
+                self
+            }
+        }
+    }
+}
+
 impl From<db::User> for AuthorProfile {
     fn from(user: db::User) -> Self {
         Self {
@@ -132,6 +213,10 @@ pub struct AttachmentInfo {
     pub thumbnail_url: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub medium_url: Option<String>,
+    /// Malware scan status while the attachment is held (`pending`,
+    /// `quarantined` or `failed`); absent once it is visible to everyone.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scan_status: Option<String>,
 }
 
 impl AttachmentInfo {
@@ -157,6 +242,9 @@ impl AttachmentInfo {
             blurhash: attachment.blurhash.clone(),
             thumbnail_url,
             medium_url,
+            scan_status: attachment
+                .is_scan_held()
+                .then(|| attachment.scan_status.clone()),
         }
     }
 }
@@ -186,12 +274,45 @@ pub struct ThreadInfoResponse {
     pub has_unread: Option<bool>,
 }
 
+/// Origin of a forwarded message, for the "forwarded from" header.
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+pub struct ForwardedFrom {
+    /// Original message (`None` once it was deleted).
+    pub message_id: Option<Uuid>,
+    /// Channel the original was posted in (`None` once it was deleted).
+    pub channel_id: Option<Uuid>,
+    /// Original author (`None` for webhook posts and deleted accounts).
+    pub author: Option<AuthorProfile>,
+    /// When the original was sent.
+    pub created_at: DateTime<Utc>,
+}
+
+impl ForwardedFrom {
+    /// Origin of `message`, if it was forwarded.
+    fn from_message(
+        message: &db::Message,
+        users: &std::collections::HashMap<Uuid, db::User>,
+    ) -> Option<Self> {
+        message.forwarded_from_created_at.map(|created_at| Self {
+            message_id: message.forwarded_from_message_id,
+            channel_id: message.forwarded_from_channel_id,
+            author: message
+                .forwarded_from_author_id
+                .and_then(|id| users.get(&id))
+                .map(|u| AuthorProfile::from(u.clone())),
+            created_at,
+        })
+    }
+}
+
 /// Full message response with author info (matches client Message type).
 #[derive(Debug, Serialize, utoipa::ToSchema)]
 pub struct MessageResponse {
     pub id: Uuid,
     pub channel_id: Uuid,
     pub author: AuthorProfile,
+    /// Message kind (system messages are rendered distinctly from user content).
+    pub message_type: db::MessageType,
     pub content: String,
     pub encrypted: bool,
     pub attachments: Vec<AttachmentInfo>,
@@ -211,6 +332,26 @@ pub struct MessageResponse {
     /// Thread info (only present for messages with thread replies).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub thread_info: Option<ThreadInfoResponse>,
+    /// Link previews, filled in asynchronously after the message is sent.
+    #[serde(default)]
+    pub embeds: Vec<db::LinkEmbed>,
+    /// Whether an incoming webhook posted the message; `author` then carries
+    /// the webhook's name and avatar.
+    #[serde(default)]
+    pub webhook: bool,
+    /// Catalog reference of a system message, for clients that render
+    /// `content` with their own translations.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub system_text: Option<LocalizedText>,
+    /// Locale `content` is rendered in (system messages only).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content_locale: Option<String>,
+    /// Where the message was forwarded from (forwarded messages only).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub forwarded_from: Option<ForwardedFrom>,
+    /// Guild custom emojis referenced as `:name:` in `content`.
+    #[serde(default)]
+    pub emojis: Vec<MessageEmoji>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
@@ -309,6 +450,12 @@ pub struct UpdateMessageRequest {
     pub content: String,
 }
 
+#[derive(Debug, Deserialize, utoipa::ToSchema)]
+pub struct ForwardMessageRequest {
+    /// Channel to forward the message to.
+    pub channel_id: Uuid,
+}
+
 static CODE_BLOCK_RE: LazyLock<regex::Regex> =
     LazyLock::new(|| regex::Regex::new(r"(?s)```.*?```").unwrap());
 
@@ -371,6 +518,7 @@ pub fn validate_message_content(content: &str) -> Result<(), validator::Validati
 pub async fn list(
     State(state): State<AppState>,
     auth_user: AuthUser,
+    RequestLocale(locale): RequestLocale,
     Path(channel_id): Path<Uuid>,
     Query(query): Query<ListMessagesQuery>,
 ) -> Result<Json<CursorPaginatedResponse<MessageResponse>>, MessageError> {
@@ -414,6 +562,9 @@ pub async fn list(
         });
     }
 
+    // Messages waiting on the attachment scanner are only shown to their author
+    hide_scan_held(&state.db, auth_user.id, &mut messages).await?;
+
     // Check if there are more messages beyond the requested limit
     let has_more = messages.len() as i64 > limit;
     if has_more {
@@ -421,7 +572,7 @@ pub async fn list(
     }
 
     // Build response with author info, attachments, and reactions
-    let response = build_message_responses(&state.db, auth_user.id, messages).await?;
+    let response = build_message_responses(&state.db, auth_user.id, locale, messages).await?;
 
     // Get the cursor for the next page (oldest message ID)
     let next_cursor = if has_more {
@@ -437,89 +588,96 @@ pub async fn list(
     }))
 }
 
-/// Create a new message.
-/// POST /`api/messages/channel/:channel_id`
-#[utoipa::path(
-    post,
-    path = "/api/messages/channel/{channel_id}",
-    tag = "messages",
-    params(("channel_id" = Uuid, Path, description = "Channel ID")),
-    request_body = CreateMessageRequest,
-    responses(
-        (status = 201, body = MessageResponse),
-    ),
-    security(("bearer_auth" = [])),
-)]
-#[tracing::instrument(skip(state, body), fields(user_id = %auth_user.id, channel_id = %channel_id))]
-pub async fn create(
-    State(state): State<AppState>,
-    auth_user: AuthUser,
-    Path(channel_id): Path<Uuid>,
-    Json(body): Json<CreateMessageRequest>,
-) -> Result<(StatusCode, Json<MessageResponse>), MessageError> {
-    // Validate input
-    body.validate()
-        .map_err(|e| MessageError::Validation(e.to_string()))?;
+/// Whether a block between `user_id` and another participant of the DM
+/// `channel_id` forbids posting. Redis errors follow the
+/// `block_check_fail_open` policy.
+pub async fn dm_blocked(state: &AppState, user_id: Uuid, channel_id: Uuid) -> sqlx::Result<bool> {
+    let participants: Vec<Uuid> = sqlx::query_scalar!(
+        "SELECT user_id FROM dm_participants WHERE channel_id = $1",
+        channel_id
+    )
+    .fetch_all(&state.db)
+    .await?;
 
-    // Check channel exists
-    let channel = db::find_channel_by_id(&state.db, channel_id)
-        .await?
-        .ok_or(MessageError::ChannelNotFound)?;
+    for &participant_id in &participants {
+        if participant_id != user_id {
+            match block_cache::is_blocked_either_direction(&state.redis, user_id, participant_id)
+                .await
+            {
+                Ok(true) => return Ok(true),
+                Ok(false) => {}
+                Err(e) => {
+                    warn!(
+                        error = %e,
+                        user_id = %user_id,
+                        target_id = %participant_id,
+                        fail_open = state.config().block_check_fail_open,
+                        "Redis block check failed, using failsafe policy"
+                    );
+                    if !state.config().block_check_fail_open {
+                        return Ok(true);
+                    }
+                }
+            }
+        }
+    }
+    Ok(false)
+}
+
+/// Checks that `user_id` may post `content` in `channel`: channel access and
+/// `SEND_MESSAGES` (`VOICE_TEXT_CHAT` in voice channels), guild suspension,
+/// timeouts, DM blocks, `@everyone` permission and the guild's content
+/// filter. Shared by sending and forwarding.
+async fn check_can_post(
+    state: &AppState,
+    user_id: Uuid,
+    channel: &db::Channel,
+    content: &str,
+    encrypted: bool,
+) -> Result<(), MessageError> {
+    let channel_id = channel.id;
 
     // Check if user has VIEW_CHANNEL permission
-    let ctx = crate::permissions::require_channel_access(&state.db, auth_user.id, channel_id)
+    let ctx = crate::permissions::require_channel_access(&state.db, user_id, channel_id)
         .await
         .map_err(|_| MessageError::Forbidden)?;
 
-    // For guild channels, also check SEND_MESSAGES permission
-    if channel.guild_id.is_some() && !ctx.has_permission(GuildPermissions::SEND_MESSAGES) {
+    // For guild channels, also check SEND_MESSAGES permission (VOICE_TEXT_CHAT
+    // in voice channels)
+    let send_permission = crate::permissions::send_permission_for(&channel.channel_type);
+    if channel.guild_id.is_some() && !ctx.has_permission(send_permission) {
         return Err(MessageError::Forbidden);
     }
 
-    // For DM channels, check if any participant has blocked the other
-    if channel.channel_type == db::ChannelType::Dm {
-        let participants: Vec<Uuid> = sqlx::query_scalar!(
-            "SELECT user_id FROM dm_participants WHERE channel_id = $1",
-            channel_id
-        )
-        .fetch_all(&state.db)
-        .await
-        .map_err(MessageError::Database)?;
-
-        for &participant_id in &participants {
-            if participant_id != auth_user.id {
-                match block_cache::is_blocked_either_direction(
-                    &state.redis,
-                    auth_user.id,
-                    participant_id,
-                )
-                .await
-                {
-                    Ok(true) => return Err(MessageError::Blocked),
-                    Ok(false) => {}
-                    Err(e) => {
-                        warn!(
-                            error = %e,
-                            user_id = %auth_user.id,
-                            target_id = %participant_id,
-                            fail_open = state.config.block_check_fail_open,
-                            "Redis block check failed, using failsafe policy"
-                        );
-                        if !state.config.block_check_fail_open {
-                            return Err(MessageError::Blocked);
-                        }
-                    }
-                }
-            }
+    // Nobody posts in a suspended guild, and timed-out members cannot post
+    // in the guild
+    if let Some(guild_id) = channel.guild_id {
+        if db::is_guild_suspended(&state.db, guild_id).await? {
+            return Err(MessageError::GuildSuspended);
+        }
+        if let Some(until) = db::get_member_timeout(&state.db, guild_id, user_id).await? {
+            return Err(MessageError::TimedOut(until));
         }
+        // So can members who have not accepted the rules yet
+        if db::is_member_pending(&state.db, guild_id, user_id).await? {
+            return Err(MessageError::OnboardingPending);
+        }
+    }
+
+    // For DM channels, check if any participant has blocked the other
+    if channel.channel_type == db::ChannelType::Dm
+        && dm_blocked(state, user_id, channel_id)
+            .await
+            .map_err(MessageError::Database)?
+    {
+        return Err(MessageError::Blocked);
     }
 
     // Check for @everyone/@here mentions in guild channels
     if let Some(guild_id) = channel.guild_id {
-        if body.content.contains("@everyone") || body.content.contains("@here") {
+        if content.contains("@everyone") || content.contains("@here") {
             // Load user's permissions in this guild
-            if let Ok(Some(ctx)) =
-                get_member_permission_context(&state.db, guild_id, auth_user.id).await
+            if let Ok(Some(ctx)) = get_member_permission_context(&state.db, guild_id, user_id).await
             {
                 if !ctx.has_permission(GuildPermissions::MENTION_EVERYONE) {
                     return Err(MessageError::Validation(
@@ -533,18 +691,11 @@ pub async fn create(
         }
     }
 
-    // Validate encrypted messages have nonce
-    if body.encrypted && body.nonce.is_none() {
-        return Err(MessageError::Validation(
-            "Encrypted messages require a nonce".to_string(),
-        ));
-    }
-
     // Content filtering: skip encrypted messages (can't inspect E2EE) and DMs (guild-scoped)
-    if !body.encrypted {
+    if !encrypted {
         if let Some(guild_id) = channel.guild_id {
             if let Ok(engine) = state.filter_cache.get_or_build(&state.db, guild_id).await {
-                let result = engine.check(&body.content);
+                let result = engine.check_message(content, channel_id);
                 if result.blocked {
                     // Log all matches to moderation_actions table
                     for m in &result.matches {
@@ -552,12 +703,12 @@ pub async fn create(
                             &state.db,
                             &filter_queries::LogActionParams {
                                 guild_id,
-                                user_id: auth_user.id,
+                                user_id,
                                 channel_id,
                                 action: m.action,
                                 category: Some(m.category),
                                 matched_pattern: &m.matched_pattern,
-                                original_content: &body.content,
+                                original_content: content,
                                 custom_pattern_id: m.custom_pattern_id,
                             },
                         )
@@ -570,7 +721,7 @@ pub async fn create(
                             &state.redis,
                             &ServerEvent::AdminModerationBlocked {
                                 guild_id,
-                                user_id: auth_user.id,
+                                user_id,
                                 channel_id,
                                 category: first.category.to_string(),
                             },
@@ -578,6 +729,7 @@ pub async fn create(
                         .await
                         .ok();
                     }
+                    escalation::spawn(state, guild_id, user_id);
                     return Err(MessageError::ContentFiltered);
                 }
                 // For "log" and "warn" actions, still log but allow the message
@@ -590,22 +742,73 @@ pub async fn create(
                         &state.db,
                         &filter_queries::LogActionParams {
                             guild_id,
-                            user_id: auth_user.id,
+                            user_id,
                             channel_id,
                             action: m.action,
                             category: Some(m.category),
                             matched_pattern: &m.matched_pattern,
-                            original_content: &body.content,
+                            original_content: content,
                             custom_pattern_id: m.custom_pattern_id,
                         },
                     )
                     .await
                     .ok();
                 }
+                if !result.matches.is_empty() {
+                    escalation::spawn(state, guild_id, user_id);
+                }
             }
         }
     }
 
+    Ok(())
+}
+
+/// Create a new message.
+/// POST /`api/messages/channel/:channel_id`
+#[utoipa::path(
+    post,
+    path = "/api/messages/channel/{channel_id}",
+    tag = "messages",
+    params(("channel_id" = Uuid, Path, description = "Channel ID")),
+    request_body = CreateMessageRequest,
+    responses(
+        (status = 201, body = MessageResponse),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[tracing::instrument(skip(state, body), fields(user_id = %auth_user.id, channel_id = %channel_id))]
+pub async fn create(
+    State(state): State<AppState>,
+    auth_user: AuthUser,
+    Path(channel_id): Path<Uuid>,
+    Json(body): Json<CreateMessageRequest>,
+) -> Result<(StatusCode, Json<MessageResponse>), MessageError> {
+    // Validate input
+    body.validate()
+        .map_err(|e| MessageError::Validation(e.to_string()))?;
+
+    // Check channel exists
+    let channel = db::find_channel_by_id(&state.db, channel_id)
+        .await?
+        .ok_or(MessageError::ChannelNotFound)?;
+
+    // Validate encrypted messages have nonce
+    if body.encrypted && body.nonce.is_none() {
+        return Err(MessageError::Validation(
+            "Encrypted messages require a nonce".to_string(),
+        ));
+    }
+
+    check_can_post(
+        &state,
+        auth_user.id,
+        &channel,
+        &body.content,
+        body.encrypted,
+    )
+    .await?;
+
     // Validate reply_to exists if provided
     if let Some(reply_id) = body.reply_to {
         let reply_msg = db::find_message_by_id(&state.db, reply_id).await?;
@@ -657,6 +860,7 @@ pub async fn create(
                         id: msg.0,
                         channel_id,
                         author,
+                        message_type: db::MessageType::Default,
                         content,
                         encrypted: false,
                         attachments: vec![],
@@ -669,6 +873,12 @@ pub async fn create(
                         mention_type: None,
                         reactions: None,
                         thread_info: None,
+                        embeds: vec![],
+                        webhook: false,
+                        system_text: None,
+                        content_locale: None,
+                        forwarded_from: None,
+                        emojis: vec![],
                     };
 
                     let message_json = serde_json::to_value(&response).unwrap_or_default();
@@ -766,13 +976,14 @@ pub async fn create(
                         }
 
                         let interaction_id = Uuid::new_v4();
+                        let options = serde_json::Value::Object(option_map);
                         let event = crate::ws::bot_gateway::BotServerEvent::CommandInvoked {
                             interaction_id,
                             command_name: command_name.clone(),
                             guild_id: Some(guild_id),
                             channel_id,
                             user_id: auth_user.id,
-                            options: serde_json::Value::Object(option_map),
+                            options: options.clone(),
                         };
 
                         let payload = serde_json::to_string(&event).map_err(|e| {
@@ -782,7 +993,7 @@ pub async fn create(
 
                         let owner_key = format!("interaction:{interaction_id}:owner");
                         let owner_value = bot_user_id.to_string();
-                        let routing_redis = db::create_redis_client(&state.config.redis_url)
+                        let routing_redis = db::create_redis_client(&state.config().redis_url)
                             .await
                             .map_err(|e| {
                                 warn!(
@@ -855,6 +1066,7 @@ pub async fn create(
                                 "guild_id": guild_id,
                                 "channel_id": channel_id,
                                 "user_id": auth_user.id,
+                                "options": options,
                             });
                             tokio::spawn(async move {
                                 crate::webhooks::dispatch::dispatch_command_event(
@@ -907,6 +1119,7 @@ pub async fn create(
                             id: Uuid::new_v4(),
                             channel_id,
                             author,
+                            message_type: db::MessageType::Default,
                             content: body.content.clone(),
                             encrypted: false,
                             attachments: vec![],
@@ -919,6 +1132,12 @@ pub async fn create(
                             mention_type: None,
                             reactions: None,
                             thread_info: None,
+                            embeds: vec![],
+                            webhook: false,
+                            system_text: None,
+                            content_locale: None,
+                            forwarded_from: None,
+                            emojis: vec![],
                         };
 
                         return Ok((StatusCode::ACCEPTED, Json(accepted)));
@@ -1003,7 +1222,9 @@ pub async fn create(
             display_name: "Unknown User".to_string(),
             avatar_url: None,
             status: "offline".to_string(),
-        });
+        })
+        .with_guild_identity(&state.db, message.channel_id)
+        .await;
 
     // Detect mentions (skip for encrypted messages)
     let mention_type = if message.encrypted {
@@ -1011,12 +1232,18 @@ pub async fn create(
     } else {
         detect_mention_type(&message.content, Some(&author.username))
     };
+    let emojis = if message.encrypted {
+        vec![]
+    } else {
+        emojis::resolve_content_emojis(&state.db, channel_id, &message.content).await?
+    };
 
     let response = MessageResponse {
         id: message.id,
         channel_id: message.channel_id,
         author: author.clone(),
-        content: message.content,
+        message_type: message.message_type,
+        content: message.content.clone(),
         encrypted: message.encrypted,
         attachments: vec![],
         reply_to: message.reply_to,
@@ -1028,6 +1255,12 @@ pub async fn create(
         mention_type,
         reactions: None,
         thread_info: None,
+        embeds: message.embeds.0.clone(),
+        webhook: false,
+        system_text: None,
+        content_locale: None,
+        forwarded_from: None,
+        emojis,
     };
 
     // Broadcast via Redis pub-sub
@@ -1068,41 +1301,212 @@ pub async fn create(
         }
     }
 
-    // Dispatch to bot ecosystem (non-blocking, fire-and-forget)
+    super::unfurl::spawn_unfurl(&state, &message);
+
+    // Sending a message ends the author's typing indicator
+    if let Err(e) = crate::ws::typing::stop_typing(&state.redis, channel_id, auth_user.id).await {
+        warn!(channel_id = %channel_id, error = %e, "Failed to clear typing indicator");
+    }
+
+    // Queue push notifications for offline recipients (DMs and mentions)
+    let push_job = crate::push::types::PushJob::Message {
+        channel_id,
+        message_id: message.id,
+        guild_id: channel.guild_id,
+        author_id: auth_user.id,
+        author_name: author.display_name.clone(),
+        channel_name: channel.name.clone(),
+        content: (!message.encrypted).then(|| message.content.clone()),
+    };
+    let push_queued = channel.guild_id.is_none() || response.mention_type.is_some();
+    if push_queued {
+        crate::push::worker::enqueue_or_warn(&state.redis, &push_job).await;
+    }
+
+    // Dispatch to bot ecosystem and highlight keywords (non-blocking, fire-and-forget)
     if let Some(guild_id) = channel.guild_id {
         if !body.encrypted {
-            let db = state.db.clone();
-            let redis = state.redis.clone();
-            let msg_id = message.id;
-            let ch_id = channel_id;
-            let uid = auth_user.id;
-            let content = body.content.clone();
-            tokio::spawn(async move {
-                crate::ws::bot_events::publish_message_created(
-                    &db, &redis, guild_id, ch_id, msg_id, uid, &content,
-                )
-                .await;
-                crate::webhooks::dispatch::dispatch_guild_event(
-                    &db,
-                    &redis,
-                    guild_id,
-                    crate::webhooks::events::BotEventType::MessageCreated,
-                    serde_json::json!({
-                        "guild_id": guild_id,
-                        "channel_id": ch_id,
-                        "message_id": msg_id,
-                        "user_id": uid,
-                        "content": content,
-                    }),
-                )
-                .await;
-            });
+            spawn_bot_dispatch(&state, guild_id, auth_user.id, &message);
+            super::highlights::spawn_highlight_notifications(
+                &state,
+                guild_id,
+                auth_user.id,
+                &message,
+                (!push_queued).then_some(push_job),
+            );
         }
     }
 
     Ok((StatusCode::CREATED, Json(response)))
 }
 
+/// Publish a new guild message to installed bots and webhook subscribers.
+fn spawn_bot_dispatch(state: &AppState, guild_id: Uuid, uid: Uuid, message: &db::Message) {
+    let db = state.db.clone();
+    let redis = state.redis.clone();
+    let msg_id = message.id;
+    let ch_id = message.channel_id;
+    let content = message.content.clone();
+    tokio::spawn(async move {
+        crate::ws::bot_events::publish_message_created(
+            &db, &redis, guild_id, ch_id, msg_id, uid, &content,
+        )
+        .await;
+        crate::webhooks::dispatch::dispatch_guild_event(
+            &db,
+            &redis,
+            guild_id,
+            crate::webhooks::events::BotEventType::MessageCreated,
+            serde_json::json!({
+                "guild_id": guild_id,
+                "channel_id": ch_id,
+                "message_id": msg_id,
+                "user_id": uid,
+                "content": content,
+            }),
+        )
+        .await;
+    });
+}
+
+/// Forward a message to another channel.
+/// POST /api/messages/:id/forward
+///
+/// Posts the message's text in the target channel as a new message by the
+/// forwarding user, with a reference to the original. The same checks as
+/// sending a message apply in the target channel. Encrypted and system
+/// messages cannot be forwarded, and attachments are not copied.
+#[utoipa::path(
+    post,
+    path = "/api/messages/{id}/forward",
+    tag = "messages",
+    params(("id" = Uuid, Path, description = "Message ID")),
+    request_body = ForwardMessageRequest,
+    responses(
+        (status = 201, body = MessageResponse),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[tracing::instrument(skip(state, body), fields(user_id = %auth_user.id, message_id = %id))]
+pub async fn forward(
+    State(state): State<AppState>,
+    auth_user: AuthUser,
+    Path(id): Path<Uuid>,
+    Json(body): Json<ForwardMessageRequest>,
+) -> Result<(StatusCode, Json<MessageResponse>), MessageError> {
+    let original = db::find_message_by_id(&state.db, id)
+        .await?
+        .ok_or(MessageError::NotFound)?;
+
+    // Messages the user cannot read look like they do not exist
+    crate::permissions::require_channel_access(&state.db, auth_user.id, original.channel_id)
+        .await
+        .map_err(|_| MessageError::NotFound)?;
+
+    if original.encrypted {
+        return Err(MessageError::Validation(
+            "Encrypted messages cannot be forwarded".to_string(),
+        ));
+    }
+    if original.message_type != db::MessageType::Default {
+        return Err(MessageError::Validation(
+            "System messages cannot be forwarded".to_string(),
+        ));
+    }
+    if original.content.trim().is_empty() {
+        return Err(MessageError::Validation(
+            "Messages without text cannot be forwarded".to_string(),
+        ));
+    }
+
+    let channel = db::find_channel_by_id(&state.db, body.channel_id)
+        .await?
+        .ok_or(MessageError::ChannelNotFound)?;
+    check_can_post(&state, auth_user.id, &channel, &original.content, false).await?;
+
+    let message =
+        db::create_forwarded_message(&state.db, channel.id, auth_user.id, &original).await?;
+
+    let user_ids: Vec<Uuid> = std::iter::once(auth_user.id)
+        .chain(message.forwarded_from_author_id)
+        .collect();
+    let user_map: std::collections::HashMap<Uuid, db::User> =
+        db::find_users_by_ids(&state.db, &user_ids)
+            .await?
+            .into_iter()
+            .map(|u| (u.id, u))
+            .collect();
+    let author = user_map
+        .get(&auth_user.id)
+        .cloned()
+        .map_or_else(|| AuthorProfile::deleted(None), AuthorProfile::from)
+        .with_guild_identity(&state.db, channel.id)
+        .await;
+
+    let response = MessageResponse {
+        id: message.id,
+        channel_id: message.channel_id,
+        author: author.clone(),
+        message_type: message.message_type,
+        content: message.content.clone(),
+        encrypted: false,
+        attachments: vec![],
+        reply_to: None,
+        parent_id: None,
+        thread_reply_count: 0,
+        thread_last_reply_at: None,
+        edited_at: None,
+        created_at: message.created_at,
+        mention_type: detect_mention_type(&message.content, Some(&author.username)),
+        reactions: None,
+        thread_info: None,
+        embeds: vec![],
+        webhook: false,
+        system_text: None,
+        content_locale: None,
+        forwarded_from: ForwardedFrom::from_message(&message, &user_map),
+        emojis: emojis::resolve_content_emojis(&state.db, channel.id, &message.content).await?,
+    };
+
+    let message_json = serde_json::to_value(&response).unwrap_or_default();
+    if let Err(e) = broadcast_to_channel(
+        &state.redis,
+        channel.id,
+        &ServerEvent::MessageNew {
+            channel_id: channel.id,
+            message: message_json,
+        },
+    )
+    .await
+    {
+        warn!(channel_id = %channel.id, error = %e, "Failed to broadcast forwarded message");
+    }
+
+    super::unfurl::spawn_unfurl(&state, &message);
+
+    if channel.guild_id.is_none() || response.mention_type.is_some() {
+        crate::push::worker::enqueue_or_warn(
+            &state.redis,
+            &crate::push::types::PushJob::Message {
+                channel_id: channel.id,
+                message_id: message.id,
+                guild_id: channel.guild_id,
+                author_id: auth_user.id,
+                author_name: author.display_name.clone(),
+                channel_name: channel.name.clone(),
+                content: Some(message.content.clone()),
+            },
+        )
+        .await;
+    }
+
+    if let Some(guild_id) = channel.guild_id {
+        spawn_bot_dispatch(&state, guild_id, auth_user.id, &message);
+    }
+
+    Ok((StatusCode::CREATED, Json(response)))
+}
+
 /// Update (edit) a message.
 /// PATCH /api/messages/:id
 #[utoipa::path(
@@ -1148,7 +1552,7 @@ pub async fn update(
             .ok_or(MessageError::ChannelNotFound)?;
         if let Some(guild_id) = channel.guild_id {
             if let Ok(engine) = state.filter_cache.get_or_build(&state.db, guild_id).await {
-                let result = engine.check(&body.content);
+                let result = engine.check_message(&body.content, existing_message.channel_id);
                 if result.blocked {
                     for m in &result.matches {
                         filter_queries::log_moderation_action(
@@ -1167,6 +1571,7 @@ pub async fn update(
                         .await
                         .ok();
                     }
+                    escalation::spawn(&state, guild_id, auth_user.id);
                     return Err(MessageError::ContentFiltered);
                 }
                 // For "log" and "warn" actions, still log but allow the edit
@@ -1191,6 +1596,9 @@ pub async fn update(
                     .await
                     .ok();
                 }
+                if !result.matches.is_empty() {
+                    escalation::spawn(&state, guild_id, auth_user.id);
+                }
             }
         }
     }
@@ -1210,7 +1618,9 @@ pub async fn update(
             display_name: "Unknown User".to_string(),
             avatar_url: None,
             status: "offline".to_string(),
-        });
+        })
+        .with_guild_identity(&state.db, message.channel_id)
+        .await;
 
     // Fetch existing attachments
     let attachments = db::list_file_attachments_by_message(&state.db, message.id)
@@ -1218,11 +1628,17 @@ pub async fn update(
         .iter()
         .map(AttachmentInfo::from_db)
         .collect();
+    let emojis = if message.encrypted {
+        vec![]
+    } else {
+        emojis::resolve_content_emojis(&state.db, message.channel_id, &message.content).await?
+    };
 
     let response = MessageResponse {
         id: message.id,
         channel_id: message.channel_id,
         author,
+        message_type: message.message_type,
         content: message.content.clone(),
         encrypted: message.encrypted,
         attachments,
@@ -1235,6 +1651,12 @@ pub async fn update(
         mention_type: None, // Edits don't trigger new notifications
         reactions: None,
         thread_info: None,
+        embeds: message.embeds.0.clone(),
+        webhook: false,
+        system_text: None,
+        content_locale: None,
+        forwarded_from: None,
+        emojis: emojis.clone(),
     };
 
     // Broadcast edit via Redis pub-sub
@@ -1244,7 +1666,8 @@ pub async fn update(
         &ServerEvent::MessageEdit {
             channel_id: message.channel_id,
             message_id: message.id,
-            content: message.content,
+            content: message.content.clone(),
+            emojis,
             edited_at: message
                 .edited_at
                 .map(|t| t.to_rfc3339())
@@ -1256,6 +1679,8 @@ pub async fn update(
         warn!(channel_id = %message.channel_id, message_id = %message.id, error = %e, "Failed to broadcast message edit event");
     }
 
+    super::unfurl::spawn_unfurl(&state, &message);
+
     Ok(Json(response))
 }
 
@@ -1348,24 +1773,63 @@ pub async fn delete(
 // Shared Helpers
 // ============================================================================
 
+/// Drop messages with an attachment held by the malware scanner, except the
+/// viewer's own.
+async fn hide_scan_held(
+    pool: &sqlx::PgPool,
+    viewer_id: Uuid,
+    messages: &mut Vec<db::Message>,
+) -> Result<(), MessageError> {
+    let message_ids: Vec<Uuid> = messages
+        .iter()
+        .filter(|m| m.user_id != Some(viewer_id))
+        .map(|m| m.id)
+        .collect();
+    let held: HashSet<Uuid> = db::list_scan_held_message_ids(pool, &message_ids)
+        .await?
+        .into_iter()
+        .collect();
+    if !held.is_empty() {
+        messages.retain(|m| !held.contains(&m.id));
+    }
+    Ok(())
+}
+
 /// Bulk-fetch users, attachments, and reactions for a set of messages, then
-/// map them into `MessageResponse` objects. Used by both `list` and
-/// `list_thread_replies` to avoid duplicating the N+1 avoidance logic.
-async fn build_message_responses(
+/// map them into `MessageResponse` objects. Used by `list`,
+/// `list_thread_replies` and the channel pin list to avoid duplicating the
+/// N+1 avoidance logic.
+///
+/// System message content is rendered in `locale`.
+pub async fn build_message_responses(
     pool: &sqlx::PgPool,
     requesting_user_id: Uuid,
+    locale: &'static str,
     messages: Vec<db::Message>,
 ) -> Result<Vec<MessageResponse>, MessageError> {
     if messages.is_empty() {
         return Ok(vec![]);
     }
 
-    // Bulk fetch users (skip messages with no author)
-    let user_ids: Vec<Uuid> = messages.iter().filter_map(|m| m.user_id).collect();
+    // Bulk fetch users (skip messages with no author), including the
+    // original authors of forwarded messages
+    let user_ids: Vec<Uuid> = messages
+        .iter()
+        .flat_map(|m| [m.user_id, m.forwarded_from_author_id])
+        .flatten()
+        .collect();
     let users = db::find_users_by_ids(pool, &user_ids).await?;
     let user_map: std::collections::HashMap<Uuid, db::User> =
         users.into_iter().map(|u| (u.id, u)).collect();
 
+    // Guild nicknames and avatars of the authors
+    let author_keys: Vec<(Uuid, Uuid)> = messages
+        .iter()
+        .filter(|m| m.webhook_name.is_none())
+        .filter_map(|m| m.user_id.map(|uid| (m.channel_id, uid)))
+        .collect();
+    let guild_profiles = member_profiles::load_author_profiles(pool, &author_keys).await?;
+
     // Bulk fetch attachments
     let message_ids: Vec<Uuid> = messages.iter().map(|m| m.id).collect();
     let all_attachments = db::list_file_attachments_by_messages(pool, &message_ids).await?;
@@ -1417,6 +1881,14 @@ async fn build_message_responses(
         .map(|m| m.id)
         .collect();
 
+    // Resolve custom emojis (encrypted and system content is opaque)
+    let emoji_sources: Vec<(Uuid, Uuid, &str)> = messages
+        .iter()
+        .filter(|m| !m.encrypted && m.system_text.is_none())
+        .map(|m| (m.id, m.channel_id, m.content.as_str()))
+        .collect();
+    let mut emojis_map = emojis::resolve_message_emojis(pool, &emoji_sources).await?;
+
     let mut thread_infos = build_batch_thread_infos(
         pool,
         requesting_user_id,
@@ -1429,17 +1901,19 @@ async fn build_message_responses(
     let response = messages
         .into_iter()
         .map(|msg| {
-            let author = msg
-                .user_id
-                .and_then(|uid| user_map.get(&uid))
-                .map(|u| AuthorProfile::from(u.clone()))
-                .unwrap_or_else(|| AuthorProfile {
-                    id: msg.user_id.unwrap_or(Uuid::nil()),
-                    username: "deleted".to_string(),
-                    display_name: "Deleted User".to_string(),
-                    avatar_url: None,
-                    status: "offline".to_string(),
-                });
+            let forwarded_from = ForwardedFrom::from_message(&msg, &user_map);
+            let webhook = msg.webhook_name.is_some();
+            let author = if let Some(name) = msg.webhook_name {
+                AuthorProfile::webhook(msg.webhook_id, name, msg.webhook_avatar_url)
+            } else {
+                msg.user_id
+                    .and_then(|uid| user_map.get(&uid))
+                    .map(|u| {
+                        AuthorProfile::from(u.clone())
+                            .with_member_profile(guild_profiles.get(&(msg.channel_id, u.id)))
+                    })
+                    .unwrap_or_else(|| AuthorProfile::deleted(msg.author_tombstone_id))
+            };
 
             let attachments = attachment_map.remove(&msg.id).unwrap_or_default();
             let reactions = reactions_map.remove(&msg.id);
@@ -1450,12 +1924,24 @@ async fn build_message_responses(
             };
 
             let thread_info = thread_infos.remove(&msg.id);
+            let emojis = emojis_map.remove(&msg.id).unwrap_or_default();
+
+            let system_text = msg.system_text.map(|text| text.0);
+            let (content, content_locale) = match &system_text {
+                Some(text) => {
+                    let (content, content_locale) =
+                        system_messages::render_content(text, msg.content, locale);
+                    (content, Some(content_locale.to_string()))
+                }
+                None => (msg.content, None),
+            };
 
             MessageResponse {
                 id: msg.id,
                 channel_id: msg.channel_id,
                 author,
-                content: msg.content,
+                message_type: msg.message_type,
+                content,
                 encrypted: msg.encrypted,
                 attachments,
                 reply_to: msg.reply_to,
@@ -1467,6 +1953,12 @@ async fn build_message_responses(
                 mention_type,
                 reactions,
                 thread_info,
+                embeds: msg.embeds.0,
+                webhook,
+                system_text,
+                content_locale,
+                forwarded_from,
+                emojis,
             }
         })
         .collect();
@@ -1657,6 +2149,7 @@ async fn build_batch_thread_infos(
 pub async fn list_thread_replies(
     State(state): State<AppState>,
     auth_user: AuthUser,
+    RequestLocale(locale): RequestLocale,
     Path(parent_id): Path<Uuid>,
     Query(query): Query<ListThreadRepliesQuery>,
 ) -> Result<Json<CursorPaginatedResponse<MessageResponse>>, MessageError> {
@@ -1697,12 +2190,14 @@ pub async fn list_thread_replies(
         });
     }
 
+    hide_scan_held(&state.db, auth_user.id, &mut messages).await?;
+
     let has_more = messages.len() as i64 > limit;
     if has_more {
         messages.pop();
     }
 
-    let response = build_message_responses(&state.db, auth_user.id, messages).await?;
+    let response = build_message_responses(&state.db, auth_user.id, locale, messages).await?;
 
     // For thread replies, cursor is the newest message (ascending order)
     let next_cursor = if has_more {
@@ -1909,7 +2404,12 @@ mod tests {
             username: user.username.clone(),
             display_name: user.display_name.clone(),
             email: user.email.clone(),
+            email_verified: false,
             avatar_url: user.avatar_url.clone(),
+            banner_url: None,
+            about_me: None,
+            pronouns: None,
+            accent_color: None,
             mfa_enabled: false,
             deletion_scheduled_at: None,
         }
@@ -2090,6 +2590,7 @@ mod tests {
         let result = list(
             State(state),
             test_auth_user(&user1),
+            RequestLocale(crate::i18n::DEFAULT_LOCALE),
             Path(channel.id),
             Query(query),
         )
@@ -2202,6 +2703,7 @@ mod tests {
         let result = list(
             State(state),
             test_auth_user(&user),
+            RequestLocale(crate::i18n::DEFAULT_LOCALE),
             Path(channel.id),
             Query(query),
         )
@@ -2281,6 +2783,7 @@ mod tests {
         let result1 = list(
             State(state.clone()),
             auth.clone(),
+            RequestLocale(crate::i18n::DEFAULT_LOCALE),
             Path(channel.id),
             Query(query1),
         )
@@ -2311,6 +2814,7 @@ mod tests {
         let result2 = list(
             State(state.clone()),
             auth.clone(),
+            RequestLocale(crate::i18n::DEFAULT_LOCALE),
             Path(channel.id),
             Query(query2),
         )
@@ -2336,9 +2840,15 @@ mod tests {
             limit: 100,
         };
 
-        let result_all = list(State(state), auth, Path(channel.id), Query(query_all))
-            .await
-            .expect("Fetch all failed");
+        let result_all = list(
+            State(state),
+            auth,
+            RequestLocale(crate::i18n::DEFAULT_LOCALE),
+            Path(channel.id),
+            Query(query_all),
+        )
+        .await
+        .expect("Fetch all failed");
 
         assert_eq!(
             result_all.0.items.len(),
@@ -2396,6 +2906,7 @@ mod tests {
         let result = list(
             State(state),
             test_auth_user(&user),
+            RequestLocale(crate::i18n::DEFAULT_LOCALE),
             Path(channel.id),
             Query(query),
         )
@@ -2495,6 +3006,7 @@ mod tests {
         let result_zero = list(
             State(state.clone()),
             auth.clone(),
+            RequestLocale(crate::i18n::DEFAULT_LOCALE),
             Path(channel.id),
             Query(query_zero),
         )
@@ -2509,9 +3021,15 @@ mod tests {
             limit: 200,
         };
 
-        let result_large = list(State(state), auth, Path(channel.id), Query(query_large))
-            .await
-            .expect("Handler failed");
+        let result_large = list(
+            State(state),
+            auth,
+            RequestLocale(crate::i18n::DEFAULT_LOCALE),
+            Path(channel.id),
+            Query(query_large),
+        )
+        .await
+        .expect("Handler failed");
 
         // Should return all 10 messages (max available), not more than 100
         assert_eq!(
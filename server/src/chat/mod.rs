@@ -5,11 +5,21 @@
 pub(crate) mod channels;
 pub mod dm;
 pub mod dm_search;
+pub mod dm_state;
+pub mod exports;
+pub(crate) mod feeds;
+pub(crate) mod gallery;
+pub mod highlights;
+pub(crate) mod incoming_webhooks;
 pub(crate) mod media_processing;
 pub(crate) mod messages;
 pub mod overrides;
+pub(crate) mod pins;
 pub mod s3;
+pub mod scanning;
 pub(crate) mod screenshare;
+pub(crate) mod system_messages;
+pub(crate) mod unfurl;
 pub(crate) mod uploads;
 
 use axum::routing::{delete, get, patch, post, put};
@@ -25,6 +35,28 @@ pub fn channels_router() -> Router<AppState> {
         .route("/{id}", get(channels::get))
         .route("/{id}", patch(channels::update))
         .route("/{id}", delete(channels::delete))
+        .route("/{id}/attachments", get(gallery::list_channel_attachments))
+        // Feed tokens
+        .route(
+            "/{id}/feed-tokens",
+            get(feeds::list_feed_tokens).post(feeds::create_feed_token),
+        )
+        .route(
+            "/{id}/feed-tokens/{token_id}",
+            delete(feeds::delete_feed_token),
+        )
+        // Incoming webhooks
+        .route(
+            "/{id}/webhooks",
+            get(incoming_webhooks::list_webhooks).post(incoming_webhooks::create_webhook),
+        )
+        .route(
+            "/{id}/webhooks/{webhook_id}",
+            delete(incoming_webhooks::delete_webhook),
+        )
+        .route("/{id}/export", post(exports::create_export))
+        .route("/{id}/exports", get(exports::list_exports))
+        .route("/{id}/exports/{job_id}", get(exports::get_export))
         .route("/{id}/members", get(channels::list_members))
         .route("/{id}/members", post(channels::add_member))
         .route("/{id}/members/{user_id}", delete(channels::remove_member))
@@ -34,6 +66,12 @@ pub fn channels_router() -> Router<AppState> {
             "/{id}/overrides/{role_id}",
             put(overrides::set_override).delete(overrides::delete_override),
         )
+        // Channel pins
+        .route("/{id}/pins", get(pins::list_pins))
+        .route(
+            "/{id}/pins/{message_id}",
+            put(pins::pin_message).delete(pins::unpin_message),
+        )
         // Read state
         .route("/{id}/read", post(channels::mark_as_read))
         // Screen Share
@@ -42,6 +80,12 @@ pub fn channels_router() -> Router<AppState> {
         .route("/{id}/screenshare/stop", post(screenshare::stop))
 }
 
+/// Create public channels router (routes that handle their own auth).
+/// The feed route is authenticated by a feed token in the query string.
+pub fn channels_public_router() -> Router<AppState> {
+    Router::new().route("/{id}/feed", get(feeds::get_feed))
+}
+
 /// Create messages router (protected routes).
 pub fn messages_router() -> Router<AppState> {
     Router::new()
@@ -54,6 +98,7 @@ pub fn messages_router() -> Router<AppState> {
             post(uploads::upload_message_with_file),
         )
         .route("/{id}", patch(messages::update).delete(messages::delete))
+        .route("/{id}/forward", post(messages::forward))
         .route("/{parent_id}/thread", get(messages::list_thread_replies))
         .route("/{parent_id}/thread/read", post(messages::mark_thread_read))
         .route("/upload", post(uploads::upload_file))
@@ -76,5 +121,6 @@ pub fn dm_router() -> Router<AppState> {
         .route("/{id}/leave", post(dm::leave_dm))
         .route("/{id}/name", patch(dm::update_dm_name))
         .route("/{id}/read", post(dm::mark_as_read))
+        .route("/{id}/state", patch(dm_state::update_dm_state))
         .route("/{id}/icon", get(dm::get_dm_icon).post(dm::upload_dm_icon))
 }
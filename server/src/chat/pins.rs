@@ -0,0 +1,295 @@
+//! Channel Pins
+//!
+//! Messages pinned to a channel, visible to everyone who can view it. In
+//! guild channels pinning requires `MANAGE_MESSAGES`; in DMs every
+//! participant can pin. A channel holds at most [`MAX_PINS_PER_CHANNEL`]
+//! pins.
+//!
+//! Pinning posts a [`MessageType::SystemPin`] message and broadcasts
+//! `message_pinned`; unpinning broadcasts `message_unpinned`. Deleted
+//! messages drop out of the pin list on their own.
+//!
+//! These are shared channel pins, unlike the personal pins in `api::pins`.
+
+use std::collections::HashMap;
+
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::Json;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use tracing::warn;
+use uuid::Uuid;
+
+use super::messages::{build_message_responses, MessageError, MessageResponse};
+use super::system_messages;
+use crate::api::AppState;
+use crate::auth::AuthUser;
+use crate::db::{self, MessageType};
+use crate::i18n::{LocalizedText, RequestLocale};
+use crate::permissions::GuildPermissions;
+use crate::ws::{broadcast_to_channel, ServerEvent};
+
+/// Maximum number of pinned messages per channel.
+pub const MAX_PINS_PER_CHANNEL: i64 = 50;
+
+/// A pinned message.
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct PinnedMessage {
+    pub message: MessageResponse,
+    /// User who pinned the message (`None` if the account was deleted).
+    pub pinned_by: Option<Uuid>,
+    pub pinned_at: DateTime<Utc>,
+}
+
+/// Check that the user may manage pins in the channel.
+async fn require_pin_manager(
+    state: &AppState,
+    user_id: Uuid,
+    channel: &db::Channel,
+) -> Result<(), MessageError> {
+    let ctx = crate::permissions::require_channel_access(&state.db, user_id, channel.id)
+        .await
+        .map_err(|_| MessageError::Forbidden)?;
+
+    // DM participants get full permissions, so only guild channels are gated
+    if channel.guild_id.is_some() && !ctx.has_permission(GuildPermissions::MANAGE_MESSAGES) {
+        return Err(MessageError::Forbidden);
+    }
+    Ok(())
+}
+
+/// Find a message that belongs to the channel.
+async fn find_channel_message(
+    state: &AppState,
+    channel_id: Uuid,
+    message_id: Uuid,
+) -> Result<db::Message, MessageError> {
+    db::find_message_by_id(&state.db, message_id)
+        .await?
+        .filter(|message| message.channel_id == channel_id)
+        .ok_or(MessageError::NotFound)
+}
+
+/// List a channel's pinned messages, most recently pinned first.
+/// GET /`api/channels/:id/pins`
+#[utoipa::path(
+    get,
+    path = "/api/channels/{id}/pins",
+    tag = "channels",
+    params(("id" = Uuid, Path, description = "Channel ID")),
+    responses(
+        (status = 200, body = Vec<PinnedMessage>),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[tracing::instrument(skip(state), fields(user_id = %auth_user.id, channel_id = %channel_id))]
+pub async fn list_pins(
+    State(state): State<AppState>,
+    auth_user: AuthUser,
+    RequestLocale(locale): RequestLocale,
+    Path(channel_id): Path<Uuid>,
+) -> Result<Json<Vec<PinnedMessage>>, MessageError> {
+    let _ = db::find_channel_by_id(&state.db, channel_id)
+        .await?
+        .ok_or(MessageError::ChannelNotFound)?;
+
+    crate::permissions::require_channel_access(&state.db, auth_user.id, channel_id)
+        .await
+        .map_err(|_| MessageError::Forbidden)?;
+
+    let pins: Vec<(Uuid, Option<Uuid>, DateTime<Utc>)> = sqlx::query_as(
+        r"
+        SELECT p.message_id, p.pinned_by, p.pinned_at
+        FROM channel_pins p
+        JOIN messages m ON m.id = p.message_id
+        WHERE p.channel_id = $1 AND m.deleted_at IS NULL
+        ORDER BY p.pinned_at DESC, p.message_id DESC
+        ",
+    )
+    .bind(channel_id)
+    .fetch_all(&state.db)
+    .await?;
+
+    let message_ids: Vec<Uuid> = pins.iter().map(|(id, _, _)| *id).collect();
+    let messages = sqlx::query_as::<_, db::Message>(
+        "SELECT * FROM messages WHERE id = ANY($1) AND deleted_at IS NULL",
+    )
+    .bind(&message_ids)
+    .fetch_all(&state.db)
+    .await?;
+
+    let mut messages: HashMap<Uuid, MessageResponse> =
+        build_message_responses(&state.db, auth_user.id, locale, messages)
+            .await?
+            .into_iter()
+            .map(|m| (m.id, m))
+            .collect();
+
+    Ok(Json(
+        pins.into_iter()
+            .filter_map(|(message_id, pinned_by, pinned_at)| {
+                messages.remove(&message_id).map(|message| PinnedMessage {
+                    message,
+                    pinned_by,
+                    pinned_at,
+                })
+            })
+            .collect(),
+    ))
+}
+
+/// Pin a message to its channel (requires `MANAGE_MESSAGES` in guilds).
+/// PUT /`api/channels/:id/pins/:message_id`
+///
+/// Pinning an already pinned message is a no-op.
+#[utoipa::path(
+    put,
+    path = "/api/channels/{id}/pins/{message_id}",
+    tag = "channels",
+    params(
+        ("id" = Uuid, Path, description = "Channel ID"),
+        ("message_id" = Uuid, Path, description = "Message ID"),
+    ),
+    responses(
+        (status = 204, description = "Message pinned"),
+        (status = 400, description = "Pin limit reached"),
+        (status = 403, description = "Missing MANAGE_MESSAGES"),
+        (status = 404, description = "Channel or message not found"),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[tracing::instrument(skip(state), fields(user_id = %auth_user.id, channel_id = %channel_id))]
+pub async fn pin_message(
+    State(state): State<AppState>,
+    auth_user: AuthUser,
+    Path((channel_id, message_id)): Path<(Uuid, Uuid)>,
+) -> Result<StatusCode, MessageError> {
+    let channel = db::find_channel_by_id(&state.db, channel_id)
+        .await?
+        .ok_or(MessageError::ChannelNotFound)?;
+    require_pin_manager(&state, auth_user.id, &channel).await?;
+    let message = find_channel_message(&state, channel_id, message_id).await?;
+    if message.message_type != MessageType::Default {
+        return Err(MessageError::Validation(
+            "System messages cannot be pinned".to_string(),
+        ));
+    }
+
+    let pinned: i64 = sqlx::query_scalar(
+        r"
+        SELECT COUNT(*) FROM channel_pins p
+        JOIN messages m ON m.id = p.message_id
+        WHERE p.channel_id = $1 AND p.message_id <> $2 AND m.deleted_at IS NULL
+        ",
+    )
+    .bind(channel_id)
+    .bind(message_id)
+    .fetch_one(&state.db)
+    .await?;
+    if pinned >= MAX_PINS_PER_CHANNEL {
+        return Err(MessageError::Validation(format!(
+            "This channel already has the maximum of {MAX_PINS_PER_CHANNEL} pinned messages"
+        )));
+    }
+
+    let pinned_at: Option<DateTime<Utc>> = sqlx::query_scalar(
+        r"
+        INSERT INTO channel_pins (channel_id, message_id, pinned_by)
+        VALUES ($1, $2, $3)
+        ON CONFLICT (channel_id, message_id) DO NOTHING
+        RETURNING pinned_at
+        ",
+    )
+    .bind(channel_id)
+    .bind(message_id)
+    .bind(auth_user.id)
+    .fetch_optional(&state.db)
+    .await?;
+
+    // Already pinned: nothing to announce
+    let Some(pinned_at) = pinned_at else {
+        return Ok(StatusCode::NO_CONTENT);
+    };
+
+    if let Err(e) = broadcast_to_channel(
+        &state.redis,
+        channel_id,
+        &ServerEvent::MessagePinned {
+            channel_id,
+            message_id,
+            pinned_by: auth_user.id,
+            pinned_at,
+        },
+    )
+    .await
+    {
+        warn!("Failed to broadcast message_pinned event: {}", e);
+    }
+
+    system_messages::post(
+        &state.db,
+        &state.redis,
+        channel_id,
+        auth_user.id,
+        MessageType::SystemPin,
+        &LocalizedText::new("system-message-pinned"),
+    )
+    .await;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Unpin a message (requires `MANAGE_MESSAGES` in guilds).
+/// DELETE /`api/channels/:id/pins/:message_id`
+#[utoipa::path(
+    delete,
+    path = "/api/channels/{id}/pins/{message_id}",
+    tag = "channels",
+    params(
+        ("id" = Uuid, Path, description = "Channel ID"),
+        ("message_id" = Uuid, Path, description = "Message ID"),
+    ),
+    responses(
+        (status = 204, description = "Message unpinned"),
+        (status = 403, description = "Missing MANAGE_MESSAGES"),
+        (status = 404, description = "Channel not found or message not pinned"),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[tracing::instrument(skip(state), fields(user_id = %auth_user.id, channel_id = %channel_id))]
+pub async fn unpin_message(
+    State(state): State<AppState>,
+    auth_user: AuthUser,
+    Path((channel_id, message_id)): Path<(Uuid, Uuid)>,
+) -> Result<StatusCode, MessageError> {
+    let channel = db::find_channel_by_id(&state.db, channel_id)
+        .await?
+        .ok_or(MessageError::ChannelNotFound)?;
+    require_pin_manager(&state, auth_user.id, &channel).await?;
+
+    let removed = sqlx::query("DELETE FROM channel_pins WHERE channel_id = $1 AND message_id = $2")
+        .bind(channel_id)
+        .bind(message_id)
+        .execute(&state.db)
+        .await?
+        .rows_affected();
+    if removed == 0 {
+        return Err(MessageError::NotFound);
+    }
+
+    if let Err(e) = broadcast_to_channel(
+        &state.redis,
+        channel_id,
+        &ServerEvent::MessageUnpinned {
+            channel_id,
+            message_id,
+        },
+    )
+    .await
+    {
+        warn!("Failed to broadcast message_unpinned event: {}", e);
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
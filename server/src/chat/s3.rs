@@ -310,4 +310,10 @@ impl S3Client {
     pub fn bucket(&self) -> &str {
         &self.bucket
     }
+
+    /// How long presigned URLs from [`Self::presign_get`] stay valid.
+    #[must_use]
+    pub const fn presign_expiry(&self) -> Duration {
+        self.presign_expiry
+    }
 }
@@ -0,0 +1,202 @@
+//! Attachment Malware Scanning
+//!
+//! Uploaded attachments are handed to a [`Scanner`] after they are stored in
+//! S3. The only built-in backend is [`ClamdScanner`], which streams the file
+//! to a clamd daemon over TCP (`CLAMAV_ADDRESS`). Without a configured
+//! scanner attachments are stored as `skipped` and shown immediately.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use futures::future::BoxFuture;
+use thiserror::Error;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+
+use crate::config::Config;
+
+/// Bytes sent per `INSTREAM` chunk.
+const CHUNK_SIZE: usize = 64 * 1024;
+
+/// Longest clamd reply we read before giving up.
+const MAX_REPLY_LEN: usize = 1024;
+
+/// Outcome of a successful scan.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScanVerdict {
+    /// Nothing found.
+    Clean,
+    /// The scanner reported a signature.
+    Infected(String),
+}
+
+/// Errors from a scanner backend.
+#[derive(Debug, Error)]
+pub enum ScanError {
+    /// The scanner could not be reached or the connection broke.
+    #[error("Scanner connection failed: {0}")]
+    Io(#[from] std::io::Error),
+
+    /// The scan did not finish in time.
+    #[error("Scan timed out")]
+    Timeout,
+
+    /// The scanner answered with an error or an unknown reply.
+    #[error("Scanner error: {0}")]
+    Scanner(String),
+}
+
+/// A malware scanning backend.
+pub trait Scanner: Send + Sync {
+    /// Backend name (for logging).
+    fn name(&self) -> &'static str;
+
+    /// Scan a file's contents.
+    fn scan<'a>(&'a self, data: &'a [u8]) -> BoxFuture<'a, Result<ScanVerdict, ScanError>>;
+}
+
+/// Build the configured scanner, if any.
+#[must_use]
+pub fn from_config(config: &Config) -> Option<Arc<dyn Scanner>> {
+    let address = config.clamav_address.clone()?;
+    Some(Arc::new(ClamdScanner::new(
+        address,
+        Duration::from_secs(config.attachment_scan_timeout_secs),
+    )))
+}
+
+/// Scans files with clamd's `INSTREAM` command over TCP.
+pub struct ClamdScanner {
+    address: String,
+    timeout: Duration,
+}
+
+impl ClamdScanner {
+    /// Create a scanner for the clamd daemon at `address` (`host:port`).
+    #[must_use]
+    pub const fn new(address: String, timeout: Duration) -> Self {
+        Self { address, timeout }
+    }
+
+    async fn instream(&self, data: &[u8]) -> Result<ScanVerdict, ScanError> {
+        let mut stream = TcpStream::connect(&self.address).await?;
+        stream.write_all(b"zINSTREAM\0").await?;
+        for chunk in data.chunks(CHUNK_SIZE) {
+            // Chunks are at most CHUNK_SIZE, so the length always fits
+            stream
+                .write_all(&(chunk.len() as u32).to_be_bytes())
+                .await?;
+            stream.write_all(chunk).await?;
+        }
+        stream.write_all(&0u32.to_be_bytes()).await?;
+        stream.flush().await?;
+
+        let mut reply = Vec::new();
+        (&mut stream)
+            .take(MAX_REPLY_LEN as u64)
+            .read_to_end(&mut reply)
+            .await?;
+        parse_reply(&String::from_utf8_lossy(&reply))
+    }
+}
+
+impl Scanner for ClamdScanner {
+    fn name(&self) -> &'static str {
+        "clamd"
+    }
+
+    fn scan<'a>(&'a self, data: &'a [u8]) -> BoxFuture<'a, Result<ScanVerdict, ScanError>> {
+        Box::pin(async move {
+            tokio::time::timeout(self.timeout, self.instream(data))
+                .await
+                .map_err(|_| ScanError::Timeout)?
+        })
+    }
+}
+
+/// Parse a clamd `INSTREAM` reply, e.g. `stream: OK` or
+/// `stream: Eicar-Signature FOUND`.
+fn parse_reply(reply: &str) -> Result<ScanVerdict, ScanError> {
+    let reply = reply.trim_end_matches(['\0', '\n', '\r']).trim();
+    let result = reply.strip_prefix("stream:").map(str::trim);
+    match result {
+        Some("OK") => Ok(ScanVerdict::Clean),
+        Some(result) => result
+            .strip_suffix(" FOUND")
+            .map(|signature| ScanVerdict::Infected(signature.trim().to_string()))
+            .ok_or_else(|| ScanError::Scanner(reply.to_string())),
+        None => Err(ScanError::Scanner(reply.to_string())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_clean_reply() {
+        assert_eq!(parse_reply("stream: OK\0").unwrap(), ScanVerdict::Clean);
+    }
+
+    #[test]
+    fn parses_infected_reply() {
+        assert_eq!(
+            parse_reply("stream: Win.Test.EICAR_HDB-1 FOUND\0").unwrap(),
+            ScanVerdict::Infected("Win.Test.EICAR_HDB-1".to_string())
+        );
+    }
+
+    /// Accept one `INSTREAM` session and reply `FOUND` if the streamed
+    /// bytes contain `marker`.
+    async fn fake_clamd(marker: &'static [u8]) -> String {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap().to_string();
+        tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut command = [0u8; 10];
+            socket.read_exact(&mut command).await.unwrap();
+            assert_eq!(&command, b"zINSTREAM\0");
+            let mut data = Vec::new();
+            loop {
+                let len = socket.read_u32().await.unwrap() as usize;
+                if len == 0 {
+                    break;
+                }
+                let mut chunk = vec![0u8; len];
+                socket.read_exact(&mut chunk).await.unwrap();
+                data.extend_from_slice(&chunk);
+            }
+            let reply: &[u8] = if data.windows(marker.len()).any(|w| w == marker) {
+                b"stream: Test-Signature FOUND\0"
+            } else {
+                b"stream: OK\0"
+            };
+            socket.write_all(reply).await.unwrap();
+        });
+        address
+    }
+
+    #[tokio::test]
+    async fn streams_file_to_clamd() {
+        let timeout = Duration::from_secs(5);
+        let data = vec![7u8; CHUNK_SIZE * 2 + 10];
+
+        let scanner = ClamdScanner::new(fake_clamd(b"EVIL").await, timeout);
+        assert_eq!(scanner.scan(&data).await.unwrap(), ScanVerdict::Clean);
+
+        let mut infected = data;
+        infected.extend_from_slice(b"EVIL");
+        let scanner = ClamdScanner::new(fake_clamd(b"EVIL").await, timeout);
+        assert_eq!(
+            scanner.scan(&infected).await.unwrap(),
+            ScanVerdict::Infected("Test-Signature".to_string())
+        );
+    }
+
+    #[test]
+    fn rejects_error_replies() {
+        assert!(parse_reply("INSTREAM size limit exceeded. ERROR\0").is_err());
+        assert!(parse_reply("stream: lstat() failed ERROR").is_err());
+        assert!(parse_reply("").is_err());
+    }
+}
@@ -0,0 +1,238 @@
+//! System Messages
+//!
+//! Server-generated messages for lifecycle events (member joins, DM calls,
+//! voice activity). They are stored like regular messages but carry a
+//! non-default [`db::MessageType`] so clients can render them distinctly.
+//!
+//! Content is a catalog message ([`LocalizedText`]). The English rendering is
+//! stored as `content`; readers get it re-rendered in their own locale (REST
+//! via `Accept-Language`, WebSocket per connection), tagged with
+//! `content_locale`.
+//!
+//! Posting is best-effort: failures are logged and never fail the request that
+//! triggered the event.
+
+use fred::clients::Client;
+use sqlx::PgPool;
+use tracing::{debug, warn};
+use uuid::Uuid;
+
+use super::messages::{AuthorProfile, MessageResponse};
+use crate::db::{self, MessageType};
+use crate::i18n::{LocalizedText, DEFAULT_LOCALE};
+use crate::ws::{broadcast_to_channel, ServerEvent};
+
+/// Create a system message about `user_id` and broadcast it to the channel.
+pub async fn post(
+    pool: &PgPool,
+    redis: &Client,
+    channel_id: Uuid,
+    user_id: Uuid,
+    message_type: MessageType,
+    text: &LocalizedText,
+) {
+    let (content, content_locale) = render_content(text, text.id.clone(), DEFAULT_LOCALE);
+    let message = match db::create_system_message(
+        pool,
+        channel_id,
+        user_id,
+        message_type,
+        &content,
+        text,
+    )
+    .await
+    {
+        Ok(message) => message,
+        Err(e) => {
+            warn!(channel_id = %channel_id, ?message_type, error = %e, "Failed to create system message");
+            return;
+        }
+    };
+
+    let author = match db::find_user_by_id(pool, user_id).await {
+        Ok(Some(user)) => AuthorProfile::from(user),
+        _ => return,
+    };
+
+    let response = MessageResponse {
+        id: message.id,
+        channel_id,
+        author,
+        message_type,
+        content: message.content,
+        encrypted: false,
+        attachments: vec![],
+        reply_to: None,
+        parent_id: None,
+        thread_reply_count: 0,
+        thread_last_reply_at: None,
+        edited_at: None,
+        created_at: message.created_at,
+        mention_type: None,
+        reactions: None,
+        thread_info: None,
+        embeds: vec![],
+        webhook: false,
+        system_text: Some(text.clone()),
+        content_locale: Some(content_locale.to_string()),
+        forwarded_from: None,
+        emojis: vec![],
+    };
+
+    if let Err(e) = broadcast_to_channel(
+        redis,
+        channel_id,
+        &ServerEvent::MessageNew {
+            channel_id,
+            message: serde_json::to_value(&response).unwrap_or_default(),
+        },
+    )
+    .await
+    {
+        warn!(channel_id = %channel_id, error = %e, "Failed to broadcast system message");
+    }
+
+    debug!(channel_id = %channel_id, user_id = %user_id, ?message_type, "Posted system message");
+}
+
+/// Announce a new member in the guild's system channel, or its first text
+/// channel if none is set.
+pub async fn post_member_join(pool: &PgPool, redis: &Client, guild_id: Uuid, user_id: Uuid) {
+    let channel_id: Option<Uuid> = match sqlx::query_scalar(
+        r"SELECT COALESCE(
+               (SELECT system_channel_id FROM guilds WHERE id = $1),
+               (SELECT id FROM channels
+                 WHERE guild_id = $1 AND channel_type = 'text'
+                 ORDER BY position, created_at
+                 LIMIT 1)
+           )",
+    )
+    .bind(guild_id)
+    .fetch_one(pool)
+    .await
+    {
+        Ok(channel_id) => channel_id,
+        Err(e) => {
+            warn!(guild_id = %guild_id, error = %e, "Failed to resolve join announcement channel");
+            return;
+        }
+    };
+
+    if let Some(channel_id) = channel_id {
+        post(
+            pool,
+            redis,
+            channel_id,
+            user_id,
+            MessageType::SystemJoin,
+            &LocalizedText::new("system-member-join"),
+        )
+        .await;
+    }
+}
+
+/// Render a system message in `locale`.
+///
+/// Falls back to the `stored` content (in [`DEFAULT_LOCALE`]) if the catalog
+/// cannot render the message.
+pub fn render_content(
+    text: &LocalizedText,
+    stored: String,
+    locale: &'static str,
+) -> (String, &'static str) {
+    text.render(locale).unwrap_or((stored, DEFAULT_LOCALE))
+}
+
+/// Re-render a serialized system message in `locale`.
+///
+/// Returns `true` if `content` changed; user messages are left alone.
+pub fn localize_json(message: &mut serde_json::Value, locale: &'static str) -> bool {
+    if message.get("content_locale").and_then(|l| l.as_str()) == Some(locale) {
+        return false;
+    }
+    let Some(text) = message
+        .get("system_text")
+        .and_then(|t| serde_json::from_value::<LocalizedText>(t.clone()).ok())
+    else {
+        return false;
+    };
+    let Some((content, content_locale)) = text.render(locale) else {
+        return false;
+    };
+    if content_locale != locale {
+        return false;
+    }
+
+    message["content"] = content.into();
+    message["content_locale"] = content_locale.into();
+    true
+}
+
+/// Build the content of a DM call summary.
+#[must_use]
+pub fn call_ended_text(duration_secs: Option<u32>) -> LocalizedText {
+    match duration_secs {
+        None => LocalizedText::new("system-call-ended"),
+        Some(secs) if secs < 60 => {
+            LocalizedText::new("system-call-ended-seconds").arg("seconds", secs)
+        }
+        Some(secs) if secs < 3600 => LocalizedText::new("system-call-ended-minutes")
+            .arg("minutes", secs / 60)
+            .arg("seconds", secs % 60),
+        Some(secs) => LocalizedText::new("system-call-ended-hours")
+            .arg("hours", secs / 3600)
+            .arg("minutes", (secs % 3600) / 60),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn render_en(text: &LocalizedText) -> String {
+        render_content(text, String::new(), DEFAULT_LOCALE).0
+    }
+
+    #[test]
+    fn test_call_ended_text() {
+        assert_eq!(render_en(&call_ended_text(None)), "call ended");
+        assert_eq!(
+            render_en(&call_ended_text(Some(42))),
+            "call ended after 42s"
+        );
+        assert_eq!(
+            render_en(&call_ended_text(Some(125))),
+            "call ended after 2m 5s"
+        );
+        assert_eq!(
+            render_en(&call_ended_text(Some(3_900))),
+            "call ended after 1h 5m"
+        );
+    }
+
+    #[test]
+    fn test_localize_json() {
+        let text = call_ended_text(Some(42));
+        let mut message = serde_json::json!({
+            "content": "call ended after 42s",
+            "content_locale": "en",
+            "system_text": text,
+        });
+
+        assert!(localize_json(&mut message, "de"));
+        assert_eq!(message["content"], "Anruf nach 42 s beendet");
+        assert_eq!(message["content_locale"], "de");
+
+        // Already in the requested locale
+        assert!(!localize_json(&mut message, "de"));
+        // No translation: stays as is
+        assert!(!localize_json(&mut message, "xx"));
+    }
+
+    #[test]
+    fn test_localize_json_ignores_user_messages() {
+        let mut message = serde_json::json!({"content": "hello"});
+        assert!(!localize_json(&mut message, "fi"));
+        assert_eq!(message["content"], "hello");
+    }
+}
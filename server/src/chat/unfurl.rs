@@ -0,0 +1,496 @@
+//! Link Previews (Unfurling)
+//!
+//! Detects URLs in new and edited messages and fetches their `OpenGraph`
+//! metadata in a background task. Fetches reuse the webhook SSRF guard: every
+//! hop's resolved address is checked and pinned, redirects are followed
+//! manually, only HTML is read and the body is capped. Results (including
+//! failures) are cached in Redis per URL. Finished embeds are stored on the
+//! message and broadcast as `MessageEmbedsUpdate`.
+
+use std::sync::LazyLock;
+use std::time::Duration;
+
+use fred::prelude::*;
+use regex::{Captures, Regex};
+use reqwest::header::{ACCEPT, CONTENT_TYPE, LOCATION};
+use reqwest::Url;
+use sha2::{Digest, Sha256};
+use tokio::sync::Semaphore;
+use tracing::warn;
+
+use crate::api::AppState;
+use crate::db::{self, LinkEmbed};
+use crate::webhooks::ssrf;
+use crate::ws::{broadcast_to_channel, ServerEvent};
+
+/// Maximum number of links unfurled per message.
+const MAX_EMBEDS_PER_MESSAGE: usize = 3;
+
+/// Maximum URL length considered for unfurling.
+const MAX_URL_LEN: usize = 2048;
+
+/// Only the first 512 KiB of a page are read (`OpenGraph` tags live in `<head>`).
+const MAX_BODY_BYTES: usize = 512 * 1024;
+
+/// Maximum redirects followed per URL (each hop is SSRF-checked).
+const MAX_REDIRECTS: usize = 3;
+
+/// Total time budget for one fetch, including redirects' individual requests.
+const FETCH_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Ports a preview may be fetched from.
+const ALLOWED_PORTS: &[u16] = &[80, 443, 8080, 8443];
+
+/// Cache lifetime for successful unfurls.
+const CACHE_TTL_SECS: i64 = 24 * 3600;
+
+/// Cache lifetime for URLs that produced no preview.
+const FAILURE_CACHE_TTL_SECS: i64 = 3600;
+
+const MAX_TITLE_CHARS: usize = 256;
+const MAX_DESCRIPTION_CHARS: usize = 1024;
+const MAX_SITE_NAME_CHARS: usize = 128;
+
+const USER_AGENT: &str = "Mozilla/5.0 (compatible; KaikuBot/1.0; link preview)";
+
+/// Limits concurrent unfurl jobs across all messages.
+static UNFURL_PERMITS: Semaphore = Semaphore::const_new(8);
+
+static URL_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"https?://[^\s<>"'`]+"#).expect("valid regex"));
+static CODE_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?s)```.*?```|`[^`\n]*`").expect("valid regex"));
+static META_TAG_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?is)<meta\b[^>]*>").expect("valid regex"));
+static ATTR_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"([a-zA-Z_:-]+)\s*=\s*(?:"([^"]*)"|'([^']*)'|([^\s"'>]+))"#).expect("valid regex")
+});
+static TITLE_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?is)<title[^>]*>(.*?)</title>").expect("valid regex"));
+static ENTITY_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"&(#[xX][0-9a-fA-F]{1,6}|#[0-9]{1,7}|[a-zA-Z]{2,6});").expect("valid regex")
+});
+
+// ============================================================================
+// URL Detection
+// ============================================================================
+
+/// Strip trailing punctuation that is almost never part of a pasted link.
+///
+/// A closing parenthesis is kept when it balances one inside the URL
+/// (e.g. Wikipedia article links).
+fn trim_url(mut url: &str) -> &str {
+    loop {
+        let Some(last) = url.chars().last() else {
+            return url;
+        };
+        let strip = match last {
+            '.' | ',' | ';' | ':' | '!' | '?' | ']' | '}' => true,
+            ')' => url.matches('(').count() < url.matches(')').count(),
+            _ => false,
+        };
+        if !strip {
+            return url;
+        }
+        url = &url[..url.len() - last.len_utf8()];
+    }
+}
+
+/// Extract unique, unfurlable URLs from message content.
+///
+/// Links inside code spans/blocks and links wrapped in `<...>` (explicitly
+/// suppressed) are ignored.
+pub fn extract_urls(content: &str) -> Vec<String> {
+    let text = CODE_RE.replace_all(content, " ");
+    let mut urls: Vec<String> = Vec::new();
+
+    for m in URL_RE.find_iter(&text) {
+        let suppressed = text[..m.start()].ends_with('<');
+        let url = trim_url(m.as_str());
+        if suppressed || url.len() > MAX_URL_LEN {
+            continue;
+        }
+        if !urls.iter().any(|u| u == url) {
+            urls.push(url.to_string());
+        }
+        if urls.len() == MAX_EMBEDS_PER_MESSAGE {
+            break;
+        }
+    }
+
+    urls
+}
+
+// ============================================================================
+// HTML Parsing
+// ============================================================================
+
+/// Decode the HTML entities that commonly appear in meta tags and titles.
+fn decode_entities(text: &str) -> String {
+    ENTITY_RE
+        .replace_all(text, |caps: &Captures| {
+            let entity = &caps[1];
+            let decoded = if let Some(hex) = entity
+                .strip_prefix("#x")
+                .or_else(|| entity.strip_prefix("#X"))
+            {
+                u32::from_str_radix(hex, 16).ok().and_then(char::from_u32)
+            } else if let Some(dec) = entity.strip_prefix('#') {
+                dec.parse().ok().and_then(char::from_u32)
+            } else {
+                match entity {
+                    "amp" => Some('&'),
+                    "lt" => Some('<'),
+                    "gt" => Some('>'),
+                    "quot" => Some('"'),
+                    "apos" => Some('\''),
+                    "nbsp" => Some(' '),
+                    _ => None,
+                }
+            };
+            decoded.map_or_else(|| caps[0].to_string(), String::from)
+        })
+        .into_owned()
+}
+
+/// Decode, collapse whitespace and truncate a metadata value.
+fn clean_text(raw: &str, max_chars: usize) -> Option<String> {
+    let text = decode_entities(raw)
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+    if text.is_empty() {
+        return None;
+    }
+    if text.chars().count() > max_chars {
+        let truncated: String = text.chars().take(max_chars - 1).collect();
+        return Some(format!("{truncated}…"));
+    }
+    Some(text)
+}
+
+/// Build an embed from a page's `<head>`.
+///
+/// `page_url` is the final URL after redirects (used to resolve relative
+/// image URLs); the embed keeps the URL as it appeared in the message.
+/// Returns `None` if the page has neither a title nor a description.
+fn parse_embed(url: &str, page_url: &Url, html: &str) -> Option<LinkEmbed> {
+    let head_end = html
+        .to_ascii_lowercase()
+        .find("</head")
+        .unwrap_or(html.len());
+    let head = &html[..head_end];
+
+    let mut meta: Vec<(String, String)> = Vec::new();
+    for tag in META_TAG_RE.find_iter(head) {
+        let mut key = None;
+        let mut content = None;
+        for attr in ATTR_RE.captures_iter(tag.as_str()) {
+            let value = attr
+                .get(2)
+                .or_else(|| attr.get(3))
+                .or_else(|| attr.get(4))
+                .map_or("", |v| v.as_str());
+            match attr[1].to_ascii_lowercase().as_str() {
+                "property" | "name" => key = Some(value.to_ascii_lowercase()),
+                "content" => content = Some(value.to_string()),
+                _ => {}
+            }
+        }
+        if let (Some(key), Some(content)) = (key, content) {
+            meta.push((key, content));
+        }
+    }
+
+    let first = |keys: &[&str]| {
+        keys.iter().find_map(|key| {
+            meta.iter()
+                .find(|(k, v)| k == key && !v.trim().is_empty())
+                .map(|(_, v)| v.as_str())
+        })
+    };
+
+    let title = first(&["og:title", "twitter:title"])
+        .or_else(|| {
+            TITLE_RE
+                .captures(head)
+                .and_then(|c| c.get(1))
+                .map(|t| t.as_str())
+        })
+        .and_then(|t| clean_text(t, MAX_TITLE_CHARS));
+    let description = first(&["og:description", "twitter:description", "description"])
+        .and_then(|d| clean_text(d, MAX_DESCRIPTION_CHARS));
+    if title.is_none() && description.is_none() {
+        return None;
+    }
+
+    let site_name = first(&["og:site_name"]).and_then(|s| clean_text(s, MAX_SITE_NAME_CHARS));
+    let image_url = first(&[
+        "og:image:secure_url",
+        "og:image",
+        "og:image:url",
+        "twitter:image",
+    ])
+    .and_then(|i| page_url.join(decode_entities(i.trim()).as_str()).ok())
+    .filter(|i| matches!(i.scheme(), "http" | "https") && i.as_str().len() <= MAX_URL_LEN)
+    .map(String::from);
+
+    Some(LinkEmbed {
+        url: url.to_string(),
+        title,
+        description,
+        site_name,
+        image_url,
+    })
+}
+
+// ============================================================================
+// Fetching
+// ============================================================================
+
+/// Fetch a page and build its embed, following redirects manually so every
+/// hop passes the SSRF check.
+async fn fetch_embed(url: &str) -> Option<LinkEmbed> {
+    let mut current = Url::parse(url).ok()?;
+
+    for _ in 0..=MAX_REDIRECTS {
+        if !matches!(current.scheme(), "http" | "https")
+            || !current.username().is_empty()
+            || current.password().is_some()
+            || !current
+                .port_or_known_default()
+                .is_some_and(|port| ALLOWED_PORTS.contains(&port))
+            || ssrf::is_blocked_host(current.host_str()?)
+        {
+            return None;
+        }
+
+        // Pin the verified address so DNS cannot be rebound between check and fetch
+        let verified = ssrf::verify_resolved_ip(current.as_str()).await.ok()?;
+        let client = reqwest::Client::builder()
+            .resolve(&verified.host, verified.addr)
+            .redirect(reqwest::redirect::Policy::none())
+            .timeout(FETCH_TIMEOUT)
+            .user_agent(USER_AGENT)
+            .build()
+            .ok()?;
+
+        let mut resp = client
+            .get(current.clone())
+            .header(ACCEPT, "text/html,application/xhtml+xml")
+            .send()
+            .await
+            .ok()?;
+
+        if resp.status().is_redirection() {
+            let location = resp.headers().get(LOCATION)?.to_str().ok()?;
+            current = current.join(location).ok()?;
+            continue;
+        }
+        if !resp.status().is_success() {
+            return None;
+        }
+
+        let is_html = resp
+            .headers()
+            .get(CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .is_some_and(|ct| {
+                let ct = ct.to_ascii_lowercase();
+                ct.starts_with("text/html") || ct.starts_with("application/xhtml+xml")
+            });
+        if !is_html {
+            return None;
+        }
+
+        let mut body = Vec::new();
+        while let Ok(Some(chunk)) = resp.chunk().await {
+            let take = chunk.len().min(MAX_BODY_BYTES - body.len());
+            body.extend_from_slice(&chunk[..take]);
+            if body.len() >= MAX_BODY_BYTES {
+                break;
+            }
+        }
+
+        return parse_embed(url, &current, &String::from_utf8_lossy(&body));
+    }
+
+    None
+}
+
+/// Redis key for a cached unfurl result.
+fn cache_key(url: &str) -> String {
+    format!("unfurl:{}", hex::encode(Sha256::digest(url.as_bytes())))
+}
+
+/// Return the cached embed for a URL, fetching and caching it on a miss.
+async fn cached_or_fetch(redis: &Client, url: &str) -> Option<LinkEmbed> {
+    let key = cache_key(url);
+    match redis.get::<Option<String>, _>(&key).await {
+        Ok(Some(cached)) => {
+            if let Ok(embed) = serde_json::from_str::<Option<LinkEmbed>>(&cached) {
+                return embed;
+            }
+        }
+        Ok(None) => {}
+        Err(e) => warn!(error = %e, "Failed to read unfurl cache"),
+    }
+
+    let embed = fetch_embed(url).await;
+
+    let ttl = if embed.is_some() {
+        CACHE_TTL_SECS
+    } else {
+        FAILURE_CACHE_TTL_SECS
+    };
+    if let Ok(value) = serde_json::to_string(&embed) {
+        if let Err(e) = redis
+            .set::<(), _, _>(&key, value, Some(Expiration::EX(ttl)), None, false)
+            .await
+        {
+            warn!(error = %e, "Failed to write unfurl cache");
+        }
+    }
+
+    embed
+}
+
+/// Unfurl the links in a new or edited message in the background.
+///
+/// Nothing is written or broadcast when the result matches the embeds already
+/// stored on `message`, so edits that keep the same links are free.
+pub fn spawn_unfurl(state: &AppState, message: &db::Message) {
+    if !state.config().enable_link_previews || message.encrypted {
+        return;
+    }
+    let urls = extract_urls(&message.content);
+    if urls.is_empty() && message.embeds.is_empty() {
+        return;
+    }
+
+    let pool = state.db.clone();
+    let redis = state.redis.clone();
+    let message_id = message.id;
+    let channel_id = message.channel_id;
+    let content = message.content.clone();
+    let previous = message.embeds.0.clone();
+
+    tokio::spawn(async move {
+        let mut embeds = Vec::new();
+        if !urls.is_empty() {
+            let Ok(_permit) = UNFURL_PERMITS.acquire().await else {
+                return;
+            };
+            for url in &urls {
+                if let Some(embed) = cached_or_fetch(&redis, url).await {
+                    embeds.push(embed);
+                }
+            }
+        }
+        if embeds == previous {
+            return;
+        }
+
+        match db::set_message_embeds(&pool, message_id, &content, &embeds).await {
+            Ok(true) => {}
+            // Edited again or deleted while unfurling
+            Ok(false) => return,
+            Err(e) => {
+                warn!(message_id = %message_id, error = %e, "Failed to store message embeds");
+                return;
+            }
+        }
+
+        if let Err(e) = broadcast_to_channel(
+            &redis,
+            channel_id,
+            &ServerEvent::MessageEmbedsUpdate {
+                channel_id,
+                message_id,
+                embeds: serde_json::to_value(&embeds).unwrap_or_default(),
+            },
+        )
+        .await
+        {
+            warn!(message_id = %message_id, error = %e, "Failed to broadcast message embeds");
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extracts_urls_and_trims_punctuation() {
+        assert_eq!(
+            extract_urls("see https://example.com/a, and (https://example.org/b)."),
+            vec!["https://example.com/a", "https://example.org/b"]
+        );
+        assert_eq!(
+            extract_urls("https://en.wikipedia.org/wiki/Rust_(programming_language)"),
+            vec!["https://en.wikipedia.org/wiki/Rust_(programming_language)"]
+        );
+    }
+
+    #[test]
+    fn skips_code_suppressed_and_duplicate_urls() {
+        let content = "`https://a.example` ```\nhttps://b.example\n``` <https://c.example> \
+                       https://d.example https://d.example";
+        assert_eq!(extract_urls(content), vec!["https://d.example"]);
+    }
+
+    #[test]
+    fn limits_urls_per_message() {
+        let content = (0..5)
+            .map(|i| format!("https://example.com/{i}"))
+            .collect::<Vec<_>>()
+            .join(" ");
+        assert_eq!(extract_urls(&content).len(), MAX_EMBEDS_PER_MESSAGE);
+    }
+
+    #[test]
+    fn parses_opengraph_tags() {
+        let page = Url::parse("https://example.com/posts/1").unwrap();
+        let html = r#"<html><head>
+            <title>Fallback</title>
+            <meta content="Tom &amp; Jerry" property="og:title">
+            <meta property='og:description' content='A   classic&#x21;'>
+            <meta property="og:site_name" content="Example">
+            <meta property="og:image" content="/img/cover.png">
+            </head><body><meta property="og:title" content="ignored"></body></html>"#;
+
+        let embed = parse_embed("https://example.com/p/1", &page, html).unwrap();
+        assert_eq!(embed.url, "https://example.com/p/1");
+        assert_eq!(embed.title.as_deref(), Some("Tom & Jerry"));
+        assert_eq!(embed.description.as_deref(), Some("A classic!"));
+        assert_eq!(embed.site_name.as_deref(), Some("Example"));
+        assert_eq!(
+            embed.image_url.as_deref(),
+            Some("https://example.com/img/cover.png")
+        );
+    }
+
+    #[test]
+    fn falls_back_to_title_and_rejects_empty_pages() {
+        let page = Url::parse("https://example.com").unwrap();
+        let embed = parse_embed(
+            "https://example.com",
+            &page,
+            "<head><title>\n Plain page \n</title></head>",
+        )
+        .unwrap();
+        assert_eq!(embed.title.as_deref(), Some("Plain page"));
+        assert!(embed.image_url.is_none());
+
+        assert!(parse_embed("https://example.com", &page, "<head></head>").is_none());
+    }
+
+    #[test]
+    fn ignores_non_http_images() {
+        let page = Url::parse("https://example.com").unwrap();
+        let html = r#"<meta property="og:title" content="x"><meta property="og:image" content="javascript:alert(1)">"#;
+        let embed = parse_embed("https://example.com", &page, html).unwrap();
+        assert!(embed.image_url.is_none());
+    }
+}
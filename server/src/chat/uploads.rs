@@ -2,20 +2,29 @@
 //!
 //! Handles file uploads to S3-compatible storage and metadata management.
 
+use std::sync::Arc;
+
 use axum::extract::{Multipart, Path, Query, State};
 use axum::http::{HeaderName, StatusCode};
 use axum::response::{IntoResponse, Response};
 use axum::Json;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
+use tokio::sync::Semaphore;
 use uuid::Uuid;
 
-use super::messages::{detect_mention_type, AttachmentInfo, AuthorProfile, MessageResponse};
+use super::messages::{
+    build_message_responses, detect_mention_type, dm_blocked, AttachmentInfo, AuthorProfile,
+    MessageResponse,
+};
 use super::s3::S3Client;
+use super::scanning::{ScanVerdict, Scanner};
 use crate::api::AppState;
 use crate::auth::jwt::validate_access_token;
 use crate::auth::AuthUser;
 use crate::db;
+use crate::guild::perks;
+use crate::i18n::DEFAULT_LOCALE;
 use crate::ws::{broadcast_to_channel, ServerEvent};
 
 // ============================================================================
@@ -63,6 +72,14 @@ pub enum UploadError {
     #[error("Access denied")]
     Forbidden,
 
+    /// A participant of the DM blocked the other.
+    #[error("Cannot send messages to this user")]
+    Blocked,
+
+    /// Attachment is held by the malware scanner.
+    #[error("Attachment is held by malware scanning")]
+    ScanHeld,
+
     /// Storage error.
     #[error("Storage error: {0}")]
     Storage(String),
@@ -103,6 +120,8 @@ impl IntoResponse for UploadError {
             ),
             Self::MessageNotFound => (StatusCode::NOT_FOUND, "MESSAGE_NOT_FOUND", self.to_string()),
             Self::Forbidden => (StatusCode::FORBIDDEN, "FORBIDDEN", self.to_string()),
+            Self::Blocked => (StatusCode::FORBIDDEN, "BLOCKED", self.to_string()),
+            Self::ScanHeld => (StatusCode::FORBIDDEN, "ATTACHMENT_HELD", self.to_string()),
             Self::Storage(_) => (
                 StatusCode::INTERNAL_SERVER_ERROR,
                 "STORAGE_ERROR",
@@ -146,6 +165,16 @@ pub struct UploadedFile {
     pub size: i64,
     /// URL to access the file.
     pub url: String,
+    /// Intrinsic width in pixels (images only).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub width: Option<i32>,
+    /// Intrinsic height in pixels (images only).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub height: Option<i32>,
+    /// Variant generation status: `pending`, `processed`, `partial`, `failed` or `skipped`.
+    pub processing_status: String,
+    /// Malware scan status: `pending` while other users cannot see the file yet.
+    pub scan_status: String,
 }
 
 /// Response for attachment metadata.
@@ -161,18 +190,40 @@ pub struct AttachmentResponse {
     pub mime_type: String,
     /// File size in bytes.
     pub size_bytes: i64,
+    /// Intrinsic width in pixels (images only).
+    pub width: Option<i32>,
+    /// Intrinsic height in pixels (images only).
+    pub height: Option<i32>,
+    /// Blurhash placeholder, once processing has finished.
+    pub blurhash: Option<String>,
+    /// Thumbnail variant URL, once processing has finished.
+    pub thumbnail_url: Option<String>,
+    /// Medium variant URL, once processing has finished.
+    pub medium_url: Option<String>,
+    /// Variant generation status: `pending`, `processed`, `partial`, `failed` or `skipped`.
+    pub processing_status: String,
+    /// Malware scan status: `pending`, `clean`, `quarantined`, `failed`, `released` or `skipped`.
+    pub scan_status: String,
     /// When the attachment was created.
     pub created_at: chrono::DateTime<chrono::Utc>,
 }
 
 impl From<db::FileAttachment> for AttachmentResponse {
     fn from(a: db::FileAttachment) -> Self {
+        let info = AttachmentInfo::from_db(&a);
         Self {
             id: a.id,
             message_id: a.message_id,
             filename: a.filename,
             mime_type: a.mime_type,
             size_bytes: a.size_bytes,
+            width: a.width,
+            height: a.height,
+            blurhash: a.blurhash,
+            thumbnail_url: info.thumbnail_url,
+            medium_url: info.medium_url,
+            processing_status: a.processing_status,
+            scan_status: a.scan_status,
             created_at: a.created_at,
         }
     }
@@ -257,6 +308,13 @@ fn validate_file_content(data: &[u8], claimed_mime: &str) -> Result<String, Uplo
     })
 }
 
+/// Upload size limit for a channel, including guild supporter perks.
+async fn effective_upload_size(state: &AppState, channel_id: Uuid) -> Result<usize, UploadError> {
+    let config = state.config();
+    let guild_perks = perks::get_channel_perks(&state.db, &config, channel_id).await?;
+    Ok(guild_perks.map_or(config.max_upload_size, |p| p.max_upload_size))
+}
+
 // ============================================================================
 // Handlers
 // ============================================================================
@@ -306,11 +364,11 @@ pub async fn upload_file(
                     .await
                     .map_err(|e| UploadError::Validation(e.to_string()))?;
 
-                // Check file size
-                if data.len() > state.config.max_upload_size {
-                    return Err(UploadError::TooLarge {
-                        max_size: state.config.max_upload_size,
-                    });
+                // Check file size against the largest perk tier; the guild's
+                // own limit is checked once the message is resolved
+                let ceiling = perks::max_upload_size_ceiling(&state.config());
+                if data.len() > ceiling {
+                    return Err(UploadError::TooLarge { max_size: ceiling });
                 }
 
                 file_data = Some(data.to_vec());
@@ -354,7 +412,8 @@ pub async fn upload_file(
         .unwrap_or_else(|| "application/octet-stream".to_string());
 
     // Validate MIME type
-    let allowed_types: Vec<&str> = state.config.allowed_mime_types.as_ref().map_or_else(
+    let config = state.config();
+    let allowed_types: Vec<&str> = config.allowed_mime_types.as_ref().map_or_else(
         || DEFAULT_ALLOWED_TYPES.to_vec(),
         |v| v.iter().map(std::string::String::as_str).collect(),
     );
@@ -378,6 +437,13 @@ pub async fn upload_file(
         return Err(UploadError::Forbidden);
     }
 
+    let max_upload_size = effective_upload_size(&state, message.channel_id).await?;
+    if file_data.len() > max_upload_size {
+        return Err(UploadError::TooLarge {
+            max_size: max_upload_size,
+        });
+    }
+
     // Generate S3 key
     let file_id = Uuid::now_v7();
     let extension = std::path::Path::new(&safe_filename)
@@ -389,23 +455,14 @@ pub async fn upload_file(
         message.channel_id, message_id, file_id, extension
     );
 
-    // Process image before S3 upload (clones data internally for spawn_blocking)
+    // Read dimensions from the header now; the scan and variants run in the background
     let file_size = file_data.len() as i64;
-    let media = process_and_upload_variants(s3, &file_data, &content_type, &s3_key).await;
+    let media = initial_media_state(&file_data, &content_type);
+    let background_data = (state.scanner.is_some() || media.processing_status == "pending")
+        .then(|| file_data.clone());
 
     // Upload original to S3
     if let Err(e) = s3.upload(&s3_key, file_data, &content_type).await {
-        // Clean up orphaned variant objects
-        let mut keys = Vec::new();
-        if let Some(k) = media.thumb_key {
-            keys.push(k);
-        }
-        if let Some(k) = media.medium_key {
-            keys.push(k);
-        }
-        if !keys.is_empty() {
-            cleanup_s3_objects(s3.clone(), keys);
-        }
         return Err(UploadError::Storage(e.to_string()));
     }
 
@@ -419,22 +476,16 @@ pub async fn upload_file(
         &s3_key,
         media.width,
         media.height,
-        media.blurhash.as_deref(),
-        media.thumb_key.as_deref(),
-        media.medium_key.as_deref(),
+        None,
+        None,
+        None,
         media.processing_status,
+        initial_scan_status(&state),
     )
     .await
     .map_err(|e| {
-        // Clean up orphaned S3 objects (original + variants)
-        let mut keys = vec![s3_key.clone()];
-        if let Some(k) = media.thumb_key.clone() {
-            keys.push(k);
-        }
-        if let Some(k) = media.medium_key.clone() {
-            keys.push(k);
-        }
-        cleanup_s3_objects(s3.clone(), keys);
+        // Clean up orphaned S3 object
+        cleanup_s3_objects(s3.clone(), vec![s3_key.clone()]);
         tracing::error!(
             message_id = %message_id,
             "Failed to create attachment record, cleaning up S3 objects: {e}"
@@ -442,6 +493,10 @@ pub async fn upload_file(
         e
     })?;
 
+    if let Some(data) = background_data {
+        spawn_background_work(&state, s3.clone(), attachment.clone(), data, false);
+    }
+
     // Generate download URL
     let url = format!("/api/messages/attachments/{}", attachment.id);
 
@@ -461,6 +516,10 @@ pub async fn upload_file(
             mime_type: content_type,
             size: file_size,
             url,
+            width: attachment.width,
+            height: attachment.height,
+            processing_status: attachment.processing_status,
+            scan_status: attachment.scan_status,
         }),
     ))
 }
@@ -503,13 +562,35 @@ pub async fn upload_message_with_file(
         .await
         .map_err(|_| UploadError::Forbidden)?;
 
-    // For guild channels, also check SEND_MESSAGES permission
-    if channel.guild_id.is_some()
-        && !ctx.has_permission(crate::permissions::GuildPermissions::SEND_MESSAGES)
-    {
+    // For guild channels, also check SEND_MESSAGES permission (VOICE_TEXT_CHAT
+    // in voice channels)
+    let send_permission = crate::permissions::send_permission_for(&channel.channel_type);
+    if channel.guild_id.is_some() && !ctx.has_permission(send_permission) {
         return Err(UploadError::Forbidden);
     }
 
+    // Timed-out members, and members who have not accepted the rules, cannot
+    // post in the guild; nobody can post in a suspended guild
+    if let Some(guild_id) = channel.guild_id {
+        if db::is_guild_suspended(&state.db, guild_id).await?
+            || db::get_member_timeout(&state.db, guild_id, auth_user.id)
+                .await?
+                .is_some()
+            || db::is_member_pending(&state.db, guild_id, auth_user.id).await?
+        {
+            return Err(UploadError::Forbidden);
+        }
+    }
+
+    // Blocks apply to DMs with attachments like to any other DM message
+    if channel.channel_type == db::ChannelType::Dm
+        && dm_blocked(&state, auth_user.id, channel_id).await?
+    {
+        return Err(UploadError::Blocked);
+    }
+
+    let max_upload_size = effective_upload_size(&state, channel_id).await?;
+
     let mut file_data: Option<Vec<u8>> = None;
     let mut filename: Option<String> = None;
     let mut content_type: Option<String> = None;
@@ -530,9 +611,9 @@ pub async fn upload_message_with_file(
                     .map_err(|e| UploadError::Validation(e.to_string()))?;
 
                 // Check file size
-                if data.len() > state.config.max_upload_size {
+                if data.len() > max_upload_size {
                     return Err(UploadError::TooLarge {
-                        max_size: state.config.max_upload_size,
+                        max_size: max_upload_size,
                     });
                 }
 
@@ -570,7 +651,8 @@ pub async fn upload_message_with_file(
         .unwrap_or_else(|| "application/octet-stream".to_string());
 
     // Validate MIME type
-    let allowed_types: Vec<&str> = state.config.allowed_mime_types.as_ref().map_or_else(
+    let config = state.config();
+    let allowed_types: Vec<&str> = config.allowed_mime_types.as_ref().map_or_else(
         || DEFAULT_ALLOWED_TYPES.to_vec(),
         |v| v.iter().map(std::string::String::as_str).collect(),
     );
@@ -593,7 +675,7 @@ pub async fn upload_message_with_file(
     if !content.is_empty() {
         if let Some(guild_id) = channel.guild_id {
             if let Ok(engine) = state.filter_cache.get_or_build(&state.db, guild_id).await {
-                let result = engine.check(&content);
+                let result = engine.check_message(&content, channel_id);
                 if result.blocked {
                     for m in &result.matches {
                         crate::moderation::filter_queries::log_moderation_action(
@@ -612,6 +694,7 @@ pub async fn upload_message_with_file(
                         .await
                         .ok();
                     }
+                    crate::moderation::escalation::spawn(&state, guild_id, auth_user.id);
                     return Err(UploadError::Validation(
                         "Your message was blocked by the server's content filter.".to_string(),
                     ));
@@ -637,6 +720,9 @@ pub async fn upload_message_with_file(
                     .await
                     .ok();
                 }
+                if !result.matches.is_empty() {
+                    crate::moderation::escalation::spawn(&state, guild_id, auth_user.id);
+                }
             }
         }
     }
@@ -668,23 +754,14 @@ pub async fn upload_message_with_file(
         channel_id, message.id, file_id, extension
     );
 
-    // Process image before S3 upload (clones data internally for spawn_blocking)
+    // Read dimensions from the header now; the scan and variants run in the background
     let file_size = file_data.len() as i64;
-    let media = process_and_upload_variants(s3, &file_data, &file_content_type, &s3_key).await;
+    let media = initial_media_state(&file_data, &file_content_type);
+    let background_data = (state.scanner.is_some() || media.processing_status == "pending")
+        .then(|| file_data.clone());
 
     // Upload original to S3 - if this fails, message is already created (acceptable trade-off)
     if let Err(e) = s3.upload(&s3_key, file_data, &file_content_type).await {
-        // Clean up orphaned variant objects
-        let mut keys = Vec::new();
-        if let Some(k) = media.thumb_key {
-            keys.push(k);
-        }
-        if let Some(k) = media.medium_key {
-            keys.push(k);
-        }
-        if !keys.is_empty() {
-            cleanup_s3_objects(s3.clone(), keys);
-        }
         tracing::error!(
             "S3 upload failed for message {}: {}. Message exists without attachment.",
             message.id,
@@ -703,22 +780,16 @@ pub async fn upload_message_with_file(
         &s3_key,
         media.width,
         media.height,
-        media.blurhash.as_deref(),
-        media.thumb_key.as_deref(),
-        media.medium_key.as_deref(),
+        None,
+        None,
+        None,
         media.processing_status,
+        initial_scan_status(&state),
     )
     .await
     .map_err(|e| {
-        // If attachment record creation fails after S3 upload, we have orphaned S3 objects
-        let mut keys = vec![s3_key.clone()];
-        if let Some(k) = media.thumb_key.clone() {
-            keys.push(k);
-        }
-        if let Some(k) = media.medium_key.clone() {
-            keys.push(k);
-        }
-        cleanup_s3_objects(s3.clone(), keys);
+        // If attachment record creation fails after S3 upload, we have an orphaned S3 object
+        cleanup_s3_objects(s3.clone(), vec![s3_key.clone()]);
         tracing::error!(
             "Failed to create attachment record for message {}: {}",
             message.id,
@@ -737,7 +808,9 @@ pub async fn upload_message_with_file(
             display_name: "Unknown User".to_string(),
             avatar_url: None,
             status: "offline".to_string(),
-        });
+        })
+        .with_guild_identity(&state.db, message.channel_id)
+        .await;
 
     let mention_type = detect_mention_type(&message.content, Some(&author.username));
 
@@ -745,7 +818,8 @@ pub async fn upload_message_with_file(
         id: message.id,
         channel_id: message.channel_id,
         author: author.clone(),
-        content: message.content,
+        message_type: message.message_type,
+        content: message.content.clone(),
         encrypted: message.encrypted,
         attachments: vec![AttachmentInfo::from_db(&attachment)],
         reply_to: message.reply_to,
@@ -757,11 +831,26 @@ pub async fn upload_message_with_file(
         created_at: message.created_at,
         mention_type,
         reactions: None,
+        embeds: message.embeds.0.clone(),
+        webhook: false,
+        system_text: None,
+        content_locale: None,
+        forwarded_from: None,
+        emojis: if message.encrypted {
+            vec![]
+        } else {
+            crate::guild::emojis::resolve_content_emojis(&state.db, channel_id, &message.content)
+                .await?
+        },
     };
 
-    // Broadcast new message via Redis pub-sub
+    // Broadcast new message via Redis pub-sub. While the attachment is being
+    // scanned only the author (through this response) sees the message; the
+    // scan task announces it once it passes.
     let message_json = serde_json::to_value(&response).unwrap_or_default();
-    if let Err(e) = broadcast_to_channel(
+    if attachment.is_scan_held() {
+        tracing::debug!(message_id = %message.id, "Message held until attachment scan passes");
+    } else if let Err(e) = broadcast_to_channel(
         &state.redis,
         channel_id,
         &ServerEvent::MessageNew {
@@ -779,6 +868,17 @@ pub async fn upload_message_with_file(
         );
     }
 
+    // Spawn after MessageNew so clients always see the pending attachment first
+    if let Some(data) = background_data {
+        spawn_background_work(&state, s3.clone(), attachment.clone(), data, true);
+    }
+    super::unfurl::spawn_unfurl(&state, &message);
+
+    // Sending a message ends the author's typing indicator
+    if let Err(e) = crate::ws::typing::stop_typing(&state.redis, channel_id, auth_user.id).await {
+        tracing::warn!(channel_id = %channel_id, error = %e, "Failed to clear typing indicator");
+    }
+
     tracing::info!(
         message_id = %message.id,
         attachment_id = %attachment.id,
@@ -885,7 +985,7 @@ pub async fn download(
              Use GET /api/messages/attachments/<id>/url with Authorization header instead."
         );
         // Validate token from query parameter
-        let claims = validate_access_token(&token, &state.config.jwt_public_key)
+        let claims = validate_access_token(&token, &state.config().jwt_public_key)
             .map_err(|_| UploadError::Forbidden)?;
         claims
             .sub
@@ -908,6 +1008,9 @@ pub async fn download(
     let attachment = db::find_file_attachment_by_id(&state.db, id)
         .await?
         .ok_or(UploadError::NotFound)?;
+    if attachment.is_scan_held() {
+        return Err(UploadError::ScanHeld);
+    }
 
     // Determine S3 key and content type based on requested variant
     let (s3_key, content_type) = match query.variant.as_deref() {
@@ -1036,6 +1139,9 @@ pub async fn get_signed_url(
     let attachment = db::find_file_attachment_by_id(&state.db, id)
         .await?
         .ok_or(UploadError::NotFound)?;
+    if attachment.is_scan_held() {
+        return Err(UploadError::ScanHeld);
+    }
 
     // Resolve S3 key based on requested variant
     let s3_key = match query.variant.as_deref() {
@@ -1070,7 +1176,7 @@ pub async fn get_signed_url(
 
     Ok(Json(SignedUrlResponse {
         url: presigned_url,
-        expires_in: state.config.s3_presign_expiry,
+        expires_in: state.config().s3_presign_expiry,
     }))
 }
 
@@ -1088,14 +1194,327 @@ struct MediaProcessingOutput {
     processing_status: &'static str,
 }
 
+/// Limits how many images are decoded and resized concurrently.
+static MEDIA_PROCESSING_PERMITS: Semaphore = Semaphore::const_new(4);
+
+/// Intrinsic dimensions and initial processing status for a new attachment.
+struct InitialMediaState {
+    width: Option<i32>,
+    height: Option<i32>,
+    processing_status: &'static str,
+}
+
+/// Read image dimensions from the header on the request path.
+///
+/// Images whose header can be read start out `pending` and are handed to
+/// [`spawn_media_processing`]; unreadable images are `failed` and everything
+/// else is `skipped`.
+fn initial_media_state(file_data: &[u8], content_type: &str) -> InitialMediaState {
+    if !content_type.starts_with("image/") {
+        return InitialMediaState {
+            width: None,
+            height: None,
+            processing_status: "skipped",
+        };
+    }
+
+    match super::media_processing::read_dimensions(file_data, content_type) {
+        Ok((width, height)) => InitialMediaState {
+            width: Some(width.min(i32::MAX as u32) as i32),
+            height: Some(height.min(i32::MAX as u32) as i32),
+            processing_status: "pending",
+        },
+        Err(e) => {
+            tracing::warn!(error = %e, "Failed to read image dimensions, storing without variants");
+            InitialMediaState {
+                width: None,
+                height: None,
+                processing_status: "failed",
+            }
+        }
+    }
+}
+
+/// Scan status for a new attachment: `pending` when a scanner is configured.
+fn initial_scan_status(state: &AppState) -> &'static str {
+    if state.scanner.is_some() {
+        "pending"
+    } else {
+        "skipped"
+    }
+}
+
+/// Start the background work for a new attachment: the malware scan when a
+/// scanner is configured, otherwise image processing right away.
+fn spawn_background_work(
+    state: &AppState,
+    s3: S3Client,
+    attachment: db::FileAttachment,
+    file_data: Vec<u8>,
+    announce: bool,
+) {
+    match state.scanner.clone() {
+        Some(scanner) => spawn_attachment_scan(state, scanner, s3, attachment, file_data, announce),
+        None => spawn_media_processing(state, s3, attachment, file_data),
+    }
+}
+
+/// Scan an attachment in the background and publish it if it passes.
+///
+/// Infected files are `quarantined`; scanner errors leave the attachment
+/// `failed` (or `skipped` with `ATTACHMENT_SCAN_FAIL_OPEN`). Held attachments
+/// stay hidden until a system admin releases them.
+fn spawn_attachment_scan(
+    state: &AppState,
+    scanner: Arc<dyn Scanner>,
+    s3: S3Client,
+    attachment: db::FileAttachment,
+    file_data: Vec<u8>,
+    announce: bool,
+) {
+    let state = state.clone();
+    tokio::spawn(async move {
+        let (scan_status, signature) = match scanner.scan(&file_data).await {
+            Ok(ScanVerdict::Clean) => ("clean", None),
+            Ok(ScanVerdict::Infected(signature)) => {
+                tracing::warn!(
+                    attachment_id = %attachment.id,
+                    message_id = %attachment.message_id,
+                    signature = %signature,
+                    "Attachment quarantined by malware scanner"
+                );
+                ("quarantined", Some(signature))
+            }
+            Err(e) => {
+                tracing::error!(
+                    attachment_id = %attachment.id,
+                    scanner = scanner.name(),
+                    error = %e,
+                    "Attachment scan failed"
+                );
+                if state.config().attachment_scan_fail_open {
+                    ("skipped", None)
+                } else {
+                    ("failed", None)
+                }
+            }
+        };
+
+        let updated = match db::update_file_attachment_scan(
+            &state.db,
+            attachment.id,
+            scan_status,
+            signature.as_deref(),
+        )
+        .await
+        {
+            Ok(Some(updated)) => updated,
+            Ok(None) => return,
+            Err(e) => {
+                tracing::error!(
+                    attachment_id = %attachment.id,
+                    "Failed to store attachment scan result: {e}"
+                );
+                return;
+            }
+        };
+
+        if !updated.is_scan_held() {
+            publish_scanned_attachment(&state, s3, updated, Some(file_data), announce).await;
+        }
+    });
+}
+
+/// Show an attachment that passed scanning or was released by an admin.
+///
+/// With `announce`, the message is broadcast as `MessageNew` once none of its
+/// attachments are held, since the channel has not seen it yet.
+/// `AttachmentProcessed` then clears the scan state on clients that already
+/// have the message, and image processing deferred by the scan starts; the
+/// original is read back from S3 when `file_data` is not at hand.
+pub async fn publish_scanned_attachment(
+    state: &AppState,
+    s3: S3Client,
+    attachment: db::FileAttachment,
+    file_data: Option<Vec<u8>>,
+    announce: bool,
+) {
+    if announce {
+        announce_message(state, attachment.message_id).await;
+    }
+    broadcast_attachment_update(&state.redis, &attachment).await;
+
+    if attachment.processing_status != "pending" {
+        return;
+    }
+    let file_data = match file_data {
+        Some(data) => data,
+        None => match s3.get_object_stream(&attachment.s3_key).await {
+            Ok(stream) => match stream.collect().await {
+                Ok(bytes) => bytes.into_bytes().to_vec(),
+                Err(e) => {
+                    tracing::warn!(attachment_id = %attachment.id, error = %e, "Failed to read attachment for processing");
+                    return;
+                }
+            },
+            Err(e) => {
+                tracing::warn!(attachment_id = %attachment.id, error = %e, "Failed to read attachment for processing");
+                return;
+            }
+        },
+    };
+    spawn_media_processing(state, s3, attachment, file_data);
+}
+
+/// Broadcast a message held back by the scanner as `MessageNew`.
+async fn announce_message(state: &AppState, message_id: Uuid) {
+    match db::list_scan_held_message_ids(&state.db, &[message_id]).await {
+        Ok(held) if held.is_empty() => {}
+        Ok(_) => return,
+        Err(e) => {
+            tracing::warn!(message_id = %message_id, "Failed to check held attachments: {e}");
+            return;
+        }
+    }
+    let message = match db::find_message_by_id(&state.db, message_id).await {
+        Ok(Some(message)) => message,
+        Ok(None) => return,
+        Err(e) => {
+            tracing::warn!(message_id = %message_id, "Failed to load held message: {e}");
+            return;
+        }
+    };
+
+    let channel_id = message.channel_id;
+    let author_id = message.user_id.unwrap_or_default();
+    let response = match build_message_responses(
+        &state.db,
+        author_id,
+        DEFAULT_LOCALE,
+        vec![message],
+    )
+    .await
+    {
+        Ok(mut responses) => match responses.pop() {
+            Some(response) => response,
+            None => return,
+        },
+        Err(e) => {
+            tracing::warn!(message_id = %message_id, error = ?e, "Failed to build held message");
+            return;
+        }
+    };
+
+    let message_json = serde_json::to_value(&response).unwrap_or_default();
+    if let Err(e) = broadcast_to_channel(
+        &state.redis,
+        channel_id,
+        &ServerEvent::MessageNew {
+            channel_id,
+            message: message_json,
+        },
+    )
+    .await
+    {
+        tracing::warn!(message_id = %message_id, "Failed to broadcast scanned message: {e}");
+    }
+}
+
+/// Push an attachment's current state to the channel as `AttachmentProcessed`.
+async fn broadcast_attachment_update(
+    redis: &fred::clients::Client,
+    attachment: &db::FileAttachment,
+) {
+    let attachment_json =
+        serde_json::to_value(AttachmentInfo::from_db(attachment)).unwrap_or_default();
+    if let Err(e) = broadcast_to_channel(
+        redis,
+        attachment.channel_id,
+        &ServerEvent::AttachmentProcessed {
+            channel_id: attachment.channel_id,
+            message_id: attachment.message_id,
+            attachment: attachment_json,
+        },
+    )
+    .await
+    {
+        tracing::warn!(
+            attachment_id = %attachment.id,
+            "Failed to broadcast attachment update: {e}"
+        );
+    }
+}
+
+/// Generate image variants in the background and notify the channel.
+///
+/// Stores the blurhash and variant keys on the attachment, then broadcasts
+/// `AttachmentProcessed` so clients can swap in the thumbnail. If the
+/// attachment was deleted in the meantime, the uploaded variants are removed.
+fn spawn_media_processing(
+    state: &AppState,
+    s3: S3Client,
+    attachment: db::FileAttachment,
+    file_data: Vec<u8>,
+) {
+    let pool = state.db.clone();
+    let redis = state.redis.clone();
+    tokio::spawn(async move {
+        let Ok(_permit) = MEDIA_PROCESSING_PERMITS.acquire().await else {
+            return;
+        };
+        let media =
+            process_and_upload_variants(&s3, file_data, &attachment.mime_type, &attachment.s3_key)
+                .await;
+        let variant_keys: Vec<String> = media
+            .thumb_key
+            .iter()
+            .chain(media.medium_key.iter())
+            .cloned()
+            .collect();
+
+        let updated = match db::update_file_attachment_media(
+            &pool,
+            attachment.id,
+            media.width,
+            media.height,
+            media.blurhash.as_deref(),
+            media.thumb_key.as_deref(),
+            media.medium_key.as_deref(),
+            media.processing_status,
+        )
+        .await
+        {
+            Ok(Some(updated)) => updated,
+            Ok(None) => {
+                if !variant_keys.is_empty() {
+                    cleanup_s3_objects(s3, variant_keys);
+                }
+                return;
+            }
+            Err(e) => {
+                tracing::error!(
+                    attachment_id = %attachment.id,
+                    "Failed to store attachment processing result: {e}"
+                );
+                if !variant_keys.is_empty() {
+                    cleanup_s3_objects(s3, variant_keys);
+                }
+                return;
+            }
+        };
+
+        broadcast_attachment_update(&redis, &updated).await;
+    });
+}
+
 /// Process an image and upload thumbnail/medium variants to S3.
 ///
 /// Returns metadata for storing in the database. Processing failures are
 /// logged and result in `processing_status = "failed"` — they never propagate
-/// as errors to avoid blocking the upload.
+/// as errors since the original is already stored.
 async fn process_and_upload_variants(
     s3: &S3Client,
-    file_data: &[u8],
+    file_data: Vec<u8>,
     content_type: &str,
     base_s3_key: &str,
 ) -> MediaProcessingOutput {
@@ -1110,11 +1529,9 @@ async fn process_and_upload_variants(
         };
     }
 
-    // process_image takes &[u8] but spawn_blocking needs 'static
-    let data = file_data.to_vec();
     let mime = content_type.to_string();
     let meta = match tokio::task::spawn_blocking(move || {
-        super::media_processing::process_image(&data, &mime)
+        super::media_processing::process_image(&file_data, &mime)
     })
     .await
     {
@@ -1206,7 +1623,7 @@ async fn process_and_upload_variants(
     }
 }
 
-/// Clean up S3 objects in the background (used when DB insert fails).
+/// Clean up S3 objects in the background (used when DB writes fail).
 fn cleanup_s3_objects(s3: S3Client, keys: Vec<String>) {
     tokio::spawn(async move {
         for key in keys {
@@ -0,0 +1,27 @@
+//! Cluster Coordination
+//!
+//! Redis-based membership for running several server replicas side by side.
+//! Every replica registers itself in the node registry under its
+//! [`Config::node_id`](crate::config::Config::node_id) and refreshes the entry
+//! with a heartbeat; a node that misses heartbeats for [`registry::NODE_TTL`]
+//! is considered dead and the first live node to notice cleans up after it.
+//!
+//! Consumers:
+//! - WebSocket gateway: each node tracks its open connections, which gives
+//!   the per-node load and lets presence drop a dead node's connections at
+//!   once instead of waiting for them to time out.
+//! - SFU: a voice channel is hosted by exactly one node at a time
+//!   ([`rooms`]), so two replicas never host separate halves of one call.
+//!   Nodes advertise their region and public address, which places new rooms
+//!   in a guild's preferred voice region. Remote SFU nodes have no Redis
+//!   connection; the replicas driving them write their entries
+//!   ([`crate::voice::remote`]).
+//! - Admin: `GET /api/admin/cluster/nodes` lists live nodes and their load.
+//!
+//! Event delivery itself does not need the registry: events already reach
+//! every replica through Redis pub/sub (see `ws::fanout`).
+
+pub mod registry;
+pub mod rooms;
+
+pub use registry::{spawn_node_heartbeat, NodeInfo};
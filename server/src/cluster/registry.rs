@@ -0,0 +1,297 @@
+//! Node Registry
+//!
+//! Redis keys:
+//! - `cluster:nodes` — sorted set of node ID -> heartbeat expiry (Unix
+//!   milliseconds).
+//! - `cluster:node:{node_id}` — JSON [`NodeInfo`] written with every
+//!   heartbeat.
+//! - `cluster:node:{node_id}:connections` — set of `{user_id}:{connection_id}`
+//!   for the gateway connections open on the node.
+//!
+//! Dead nodes are claimed with `ZREM`, so with several replicas each dead
+//! node is cleaned up exactly once.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use fred::prelude::*;
+use fred::types::Expiration;
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use super::rooms;
+use crate::presence::registry as presence_registry;
+use crate::voice::SfuServer;
+
+/// Sorted set of node ID -> heartbeat expiry (ms).
+pub(super) const NODES_KEY: &str = "cluster:nodes";
+
+/// How often each node refreshes its registry entry.
+pub const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(10);
+
+/// A node that has not sent a heartbeat for this long is considered dead.
+pub const NODE_TTL: Duration = Duration::from_secs(HEARTBEAT_INTERVAL.as_secs() * 3);
+
+/// A node's registry entry.
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct NodeInfo {
+    /// Node identifier (`NODE_ID`).
+    pub node_id: String,
+    /// Server version the node runs.
+    pub version: String,
+    /// When the node registered.
+    pub started_at: DateTime<Utc>,
+    /// When the node last refreshed its entry.
+    pub last_heartbeat: DateTime<Utc>,
+    /// Open gateway connections.
+    pub ws_connections: u64,
+    /// Voice channels hosted by the node's SFU.
+    pub voice_rooms: u64,
+    /// Participants across those voice channels.
+    pub voice_participants: u64,
+    /// Region label (`NODE_REGION`), if configured.
+    #[serde(default)]
+    pub region: Option<String>,
+    /// Public IP of the node's SFU (`NODE_PUBLIC_ADDRESS`), if configured.
+    #[serde(default)]
+    pub public_address: Option<String>,
+}
+
+fn node_key(node_id: &str) -> String {
+    format!("cluster:node:{node_id}")
+}
+
+fn connections_key(node_id: &str) -> String {
+    format!("cluster:node:{node_id}:connections")
+}
+
+fn connection_member(user_id: Uuid, connection_id: Uuid) -> String {
+    format!("{user_id}:{connection_id}")
+}
+
+fn parse_connection_member(member: &str) -> Option<(Uuid, Uuid)> {
+    let (user_id, connection_id) = member.split_once(':')?;
+    Some((
+        Uuid::parse_str(user_id).ok()?,
+        Uuid::parse_str(connection_id).ok()?,
+    ))
+}
+
+/// Whether `node_id` has a live registry entry.
+pub async fn is_alive(redis: &Client, node_id: &str) -> Result<bool, Error> {
+    let expiry: Option<f64> = redis.zscore(NODES_KEY, node_id).await?;
+    Ok(expiry.is_some_and(|expiry| expiry > Utc::now().timestamp_millis() as f64))
+}
+
+/// Record a gateway connection opened on this node.
+pub async fn track_connection(
+    redis: &Client,
+    node_id: &str,
+    user_id: Uuid,
+    connection_id: Uuid,
+) -> Result<(), Error> {
+    redis
+        .sadd(
+            connections_key(node_id),
+            connection_member(user_id, connection_id),
+        )
+        .await
+}
+
+/// Forget a gateway connection that closed on this node.
+pub async fn untrack_connection(
+    redis: &Client,
+    node_id: &str,
+    user_id: Uuid,
+    connection_id: Uuid,
+) -> Result<(), Error> {
+    redis
+        .srem(
+            connections_key(node_id),
+            connection_member(user_id, connection_id),
+        )
+        .await
+}
+
+/// Live nodes, ordered by node ID.
+pub async fn list_nodes(redis: &Client) -> Result<Vec<NodeInfo>, Error> {
+    let now = Utc::now().timestamp_millis();
+    let node_ids: Vec<String> = redis
+        .zrangebyscore(NODES_KEY, (now + 1) as f64, f64::INFINITY, false, None)
+        .await?;
+
+    let mut nodes = Vec::with_capacity(node_ids.len());
+    for node_id in node_ids {
+        let info: Option<String> = redis.get(node_key(&node_id)).await?;
+        match info.map(|info| serde_json::from_str::<NodeInfo>(&info)) {
+            Some(Ok(info)) => nodes.push(info),
+            Some(Err(e)) => warn!(node_id = %node_id, error = %e, "Invalid cluster node entry"),
+            None => {}
+        }
+    }
+    nodes.sort_by(|a, b| a.node_id.cmp(&b.node_id));
+    Ok(nodes)
+}
+
+/// Write this node's entry and push its expiry forward.
+async fn heartbeat(
+    redis: &Client,
+    sfu: &SfuServer,
+    node_id: &str,
+    started_at: DateTime<Utc>,
+) -> Result<(), Error> {
+    let now = Utc::now();
+    let ws_connections: u64 = redis.scard(connections_key(node_id)).await?;
+    let (voice_rooms, voice_participants) = sfu.load().await;
+    let info = NodeInfo {
+        node_id: node_id.to_string(),
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        started_at,
+        last_heartbeat: now,
+        ws_connections,
+        voice_rooms: voice_rooms as u64,
+        voice_participants: voice_participants as u64,
+        region: sfu.config().node_region.clone(),
+        public_address: sfu.config().node_public_address.clone(),
+    };
+    write_node_entry(redis, &info).await
+}
+
+/// Write a node's entry and push its expiry forward.
+///
+/// Replicas call this for themselves and for the remote SFU nodes they
+/// drive, which have no Redis connection of their own.
+pub async fn write_node_entry(redis: &Client, info: &NodeInfo) -> Result<(), Error> {
+    let node_id = info.node_id.as_str();
+    let now = info.last_heartbeat;
+    let info = serde_json::to_string(info)
+        .map_err(|e| Error::new(ErrorKind::Parse, format!("JSON serialize error: {e}")))?;
+
+    let ttl_ms = NODE_TTL.as_millis() as i64;
+    redis
+        .set::<(), _, _>(
+            node_key(node_id),
+            info,
+            Some(Expiration::PX(ttl_ms)),
+            None,
+            false,
+        )
+        .await?;
+    redis
+        .zadd::<(), _, _>(
+            NODES_KEY,
+            None,
+            None,
+            false,
+            false,
+            ((now.timestamp_millis() + ttl_ms) as f64, node_id),
+        )
+        .await
+}
+
+/// Clean up after a node: close its gateway connections in presence and
+/// release the voice channels it hosted.
+async fn reap_node(db: &PgPool, redis: &Client, node_id: &str) -> Result<(), Error> {
+    let connections: Vec<String> = redis.smembers(connections_key(node_id)).await?;
+    for member in &connections {
+        let Some((user_id, connection_id)) = parse_connection_member(member) else {
+            continue;
+        };
+        if let Some(status) = presence_registry::disconnect(redis, user_id, connection_id).await? {
+            presence_registry::publish_change(db, redis, user_id, &status).await;
+        }
+    }
+
+    let released = rooms::release_node_rooms(redis, node_id).await?;
+
+    redis
+        .del::<(), _>(vec![connections_key(node_id), node_key(node_id)])
+        .await?;
+
+    info!(
+        node_id = %node_id,
+        connections = connections.len(),
+        voice_rooms = released,
+        "Cleaned up cluster node"
+    );
+    Ok(())
+}
+
+/// Claim and clean up nodes whose heartbeat expired.
+pub async fn reap_dead_nodes(db: &PgPool, redis: &Client) -> Result<(), Error> {
+    let now = Utc::now().timestamp_millis();
+    let dead: Vec<String> = redis
+        .zrangebyscore(NODES_KEY, f64::NEG_INFINITY, now as f64, false, None)
+        .await?;
+
+    for node_id in dead {
+        // Whoever removes the entry does the cleanup
+        let claimed: i64 = redis.zrem(NODES_KEY, node_id.as_str()).await?;
+        if claimed == 1 {
+            warn!(node_id = %node_id, "Cluster node missed its heartbeats");
+            reap_node(db, redis, &node_id).await?;
+        }
+    }
+    Ok(())
+}
+
+/// Remove this node from the registry on shutdown.
+pub async fn deregister(db: &PgPool, redis: &Client, node_id: &str) -> Result<(), Error> {
+    redis.zrem::<(), _, _>(NODES_KEY, node_id).await?;
+    reap_node(db, redis, node_id).await
+}
+
+/// Spawn the background task that registers this node, keeps its entry
+/// fresh, and cleans up after dead nodes.
+///
+/// Leftovers from an earlier run under the same node ID (e.g. a restarted
+/// pod with a stable hostname) are cleaned up before registering.
+pub fn spawn_node_heartbeat(
+    db: PgPool,
+    redis: Client,
+    node_id: String,
+    sfu: Arc<SfuServer>,
+) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        if let Err(e) = deregister(&db, &redis, &node_id).await {
+            warn!(node_id = %node_id, error = %e, "Failed to clean up previous cluster entry");
+        }
+
+        let started_at = Utc::now();
+        info!(node_id = %node_id, "Registered cluster node");
+
+        let mut interval = tokio::time::interval(HEARTBEAT_INTERVAL);
+        loop {
+            interval.tick().await;
+            if let Err(e) = heartbeat(&redis, &sfu, &node_id, started_at).await {
+                warn!(node_id = %node_id, error = %e, "Failed to send cluster heartbeat");
+            }
+            if let Err(e) = reap_dead_nodes(&db, &redis).await {
+                warn!(error = %e, "Failed to clean up dead cluster nodes");
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_connection_member_round_trip() {
+        let (user_id, connection_id) = (Uuid::new_v4(), Uuid::new_v4());
+        let member = connection_member(user_id, connection_id);
+        assert_eq!(
+            parse_connection_member(&member),
+            Some((user_id, connection_id))
+        );
+        assert_eq!(parse_connection_member("garbage"), None);
+        assert_eq!(
+            parse_connection_member(&format!("{user_id}:not-a-uuid")),
+            None
+        );
+    }
+}
@@ -0,0 +1,168 @@
+//! Voice Room Ownership
+//!
+//! `cluster:voice_rooms` maps each active voice channel to the node whose SFU
+//! hosts it. A node claims the channel when the first participant joins
+//! through it and releases the claim when the room empties. Joining a
+//! channel hosted on another live node fails with
+//! [`VoiceError::RoomOnOtherNode`](crate::voice::VoiceError::RoomOnOtherNode)
+//! so clients can reconnect through that node; claims held by dead nodes are
+//! taken over.
+//!
+//! Guilds can prefer a voice region. A room nobody hosts yet goes to the
+//! least loaded live node of that region ([`preferred_node`]); when the
+//! region has no live node (or its node stops heartbeating and the claim is
+//! released), the room fails over to whichever node the next participant
+//! joins through.
+
+use fred::interfaces::LuaInterface;
+use fred::prelude::*;
+use uuid::Uuid;
+
+use super::registry::{self, NodeInfo, NODES_KEY};
+
+/// Hash of voice channel ID -> hosting node ID.
+const ROOMS_KEY: &str = "cluster:voice_rooms";
+
+/// Claims a room unless a live node other than the caller holds it.
+///
+/// KEYS: rooms hash, nodes sorted set.
+/// ARGV: channel ID, node ID, now (ms).
+/// Returns the live owner, or `false` (nil) if the caller holds the claim.
+const CLAIM_LUA: &str = r"
+local owner = redis.call('HGET', KEYS[1], ARGV[1])
+if owner and owner ~= ARGV[2] then
+    local expiry = redis.call('ZSCORE', KEYS[2], owner)
+    if expiry and tonumber(expiry) > tonumber(ARGV[3]) then
+        return owner
+    end
+end
+redis.call('HSET', KEYS[1], ARGV[1], ARGV[2])
+return false
+";
+
+/// Removes the rooms claimed by a node.
+///
+/// KEYS: rooms hash. ARGV: node ID, channel ID (optional; all of the node's
+/// rooms if omitted). Returns the number of released claims.
+const RELEASE_LUA: &str = r"
+local released = 0
+if ARGV[2] then
+    if redis.call('HGET', KEYS[1], ARGV[2]) == ARGV[1] then
+        released = redis.call('HDEL', KEYS[1], ARGV[2])
+    end
+    return released
+end
+local entries = redis.call('HGETALL', KEYS[1])
+for i = 1, #entries, 2 do
+    if entries[i + 1] == ARGV[1] then
+        released = released + redis.call('HDEL', KEYS[1], entries[i])
+    end
+end
+return released
+";
+
+/// Claim `channel_id` for `node_id`.
+///
+/// Returns the ID of the live node that already hosts the channel, or `None`
+/// once the caller holds the claim.
+pub async fn claim_room(
+    redis: &Client,
+    channel_id: Uuid,
+    node_id: &str,
+) -> Result<Option<String>, Error> {
+    redis
+        .eval(
+            CLAIM_LUA,
+            vec![ROOMS_KEY, NODES_KEY],
+            vec![
+                channel_id.to_string(),
+                node_id.to_string(),
+                chrono::Utc::now().timestamp_millis().to_string(),
+            ],
+        )
+        .await
+}
+
+/// Release `node_id`'s claim on `channel_id`, if it holds one.
+pub async fn release_room(redis: &Client, channel_id: Uuid, node_id: &str) -> Result<(), Error> {
+    redis
+        .eval::<i64, _, _, _>(
+            RELEASE_LUA,
+            vec![ROOMS_KEY],
+            vec![node_id.to_string(), channel_id.to_string()],
+        )
+        .await?;
+    Ok(())
+}
+
+/// Release every claim held by `node_id`. Returns the number released.
+pub async fn release_node_rooms(redis: &Client, node_id: &str) -> Result<i64, Error> {
+    redis
+        .eval(RELEASE_LUA, vec![ROOMS_KEY], vec![node_id.to_string()])
+        .await
+}
+
+/// Node hosting `channel_id`, if any (the owner may have died since).
+pub async fn room_owner(redis: &Client, channel_id: Uuid) -> Result<Option<String>, Error> {
+    redis.hget(ROOMS_KEY, channel_id.to_string()).await
+}
+
+/// Least loaded node in `region`, by voice participants then node ID.
+fn least_loaded_in_region<'a>(nodes: &'a [NodeInfo], region: &str) -> Option<&'a NodeInfo> {
+    nodes
+        .iter()
+        .filter(|node| node.region.as_deref() == Some(region))
+        .min_by(|a, b| {
+            a.voice_participants
+                .cmp(&b.voice_participants)
+                .then_with(|| a.node_id.cmp(&b.node_id))
+        })
+}
+
+/// Live node that should host a new room of a guild preferring `region`.
+///
+/// Returns `None` when no live node is in the region, in which case the
+/// joining node hosts the room.
+pub async fn preferred_node(redis: &Client, region: &str) -> Result<Option<NodeInfo>, Error> {
+    let nodes = registry::list_nodes(redis).await?;
+    Ok(least_loaded_in_region(&nodes, region).cloned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn node(node_id: &str, region: Option<&str>, voice_participants: u64) -> NodeInfo {
+        NodeInfo {
+            node_id: node_id.to_string(),
+            version: "test".to_string(),
+            started_at: chrono::Utc::now(),
+            last_heartbeat: chrono::Utc::now(),
+            ws_connections: 0,
+            voice_rooms: 0,
+            voice_participants,
+            region: region.map(str::to_string),
+            public_address: None,
+        }
+    }
+
+    #[test]
+    fn test_least_loaded_in_region() {
+        let nodes = vec![
+            node("eu-2", Some("eu"), 8),
+            node("us-1", Some("us"), 0),
+            node("eu-1", Some("eu"), 8),
+            node("eu-3", Some("eu"), 12),
+            node("none", None, 0),
+        ];
+        assert_eq!(
+            least_loaded_in_region(&nodes, "eu").map(|n| n.node_id.as_str()),
+            Some("eu-1")
+        );
+        assert_eq!(
+            least_loaded_in_region(&nodes, "us").map(|n| n.node_id.as_str()),
+            Some("us-1")
+        );
+        assert!(least_loaded_in_region(&nodes, "ap").is_none());
+    }
+}
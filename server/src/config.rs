@@ -1,11 +1,99 @@
 //! Server Configuration
 //!
-//! Loads configuration from environment variables.
+//! Loads configuration from environment variables and the optional `.env`
+//! file. The configuration can be reloaded at runtime (see
+//! [`crate::api::instance_config`]); variables set in the process environment
+//! always take precedence over the `.env` file.
 
+use std::collections::{HashMap, HashSet};
 use std::env;
+use std::path::PathBuf;
+use std::sync::OnceLock;
 
 use anyhow::{Context, Result};
 
+use crate::ratelimit::{parse_token_bucket_config, TokenBucketConfig};
+use crate::voice::regions::{parse_media_nodes, MediaNode};
+
+/// Variables set in the process environment before the `.env` file was read.
+static PROCESS_ENV_KEYS: OnceLock<HashSet<String>> = OnceLock::new();
+
+/// The `.env` file loaded at startup, if any.
+static DOTENV_PATH: OnceLock<Option<PathBuf>> = OnceLock::new();
+
+/// Load the `.env` file, if any, into the process environment.
+///
+/// Call once at startup, before [`Config::from_env`]. Existing variables are
+/// not overridden, now or on a later reload (see [`EnvVars::reload`]).
+pub fn load_dotenv() {
+    PROCESS_ENV_KEYS.get_or_init(|| {
+        env::vars_os()
+            .filter_map(|(k, _)| k.into_string().ok())
+            .collect()
+    });
+    DOTENV_PATH.get_or_init(|| dotenvy::dotenv().ok());
+}
+
+/// Source of the configuration variables.
+///
+/// At startup that is the process environment, which [`load_dotenv`] filled
+/// from the `.env` file. A reload reads the `.env` file again into a map
+/// instead: the environment is never modified once the server runs, as other
+/// threads may read it concurrently.
+#[derive(Debug, Default)]
+pub struct EnvVars {
+    /// Variables of the re-read `.env` file; `None` reads the process
+    /// environment only.
+    dotenv: Option<HashMap<String, String>>,
+}
+
+impl EnvVars {
+    /// The process environment.
+    #[must_use]
+    pub fn process() -> Self {
+        Self::default()
+    }
+
+    /// The `.env` file loaded at startup as it is now, below the variables
+    /// that were already set in the process environment at startup.
+    ///
+    /// # Errors
+    /// Returns an error if the `.env` file cannot be read or parsed.
+    pub fn reload() -> Result<Self> {
+        let mut dotenv = HashMap::new();
+        if let Some(path) = DOTENV_PATH.get().and_then(Option::as_ref) {
+            let iter = dotenvy::from_path_iter(path)
+                .with_context(|| format!("Failed to read {}", path.display()))?;
+            for item in iter {
+                let (key, value) = item.context("Failed to parse .env file")?;
+                dotenv.insert(key, value);
+            }
+        }
+        Ok(Self {
+            dotenv: Some(dotenv),
+        })
+    }
+
+    /// Look up a variable, like [`std::env::var`].
+    ///
+    /// # Errors
+    /// Returns [`env::VarError::NotPresent`] if the variable is not set.
+    pub fn var(&self, key: &str) -> Result<String, env::VarError> {
+        let Some(dotenv) = &self.dotenv else {
+            return env::var(key);
+        };
+        // Startup `.env` values are also in the environment; only variables
+        // set before it was read take precedence over the file
+        let from_process = PROCESS_ENV_KEYS.get().is_none_or(|keys| keys.contains(key));
+        if from_process {
+            if let Ok(value) = env::var(key) {
+                return Ok(value);
+            }
+        }
+        dotenv.get(key).cloned().ok_or(env::VarError::NotPresent)
+    }
+}
+
 /// Observability and telemetry configuration.
 #[derive(Debug, Clone)]
 pub struct ObservabilityConfig {
@@ -23,24 +111,244 @@ pub struct ObservabilityConfig {
 
     /// Log level filter (env: `RUST_LOG`, default: `"vc_server=info"`)
     pub log_level: String,
+
+    /// Backend for native telemetry (env: `TELEMETRY_STORAGE`, `postgres` or `clickhouse`,
+    /// default: `postgres`)
+    pub telemetry_storage: TelemetryStorageBackend,
+
+    /// `ClickHouse` connection, required when `telemetry_storage` is `clickhouse`
+    /// (env: `CLICKHOUSE_URL`)
+    pub clickhouse: Option<ClickHouseConfig>,
+
+    /// Prometheus `/metrics` endpoint, served in addition to the OTLP push
+    /// (env: `PROMETHEUS_ENABLED`, default: disabled)
+    pub prometheus: Option<PrometheusConfig>,
+}
+
+/// Where native telemetry (metric samples, log events, trace index) is stored.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TelemetryStorageBackend {
+    /// The `telemetry_*` tables in the main database.
+    #[default]
+    Postgres,
+    /// A separate `ClickHouse` database, for high-volume instances.
+    ClickHouse,
+}
+
+/// What happens to a deleted account's messages.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DeletedUserContent {
+    /// Keep message text, attributed to a "Deleted User" tombstone.
+    #[default]
+    Anonymize,
+    /// Delete the messages (thread parents keep an empty placeholder).
+    Delete,
+}
+
+impl DeletedUserContent {
+    /// Value stored in `user_tombstones.content_policy`.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Anonymize => "anonymize",
+            Self::Delete => "delete",
+        }
+    }
+}
+
+/// Prometheus scrape endpoint settings. At least one of them must be set, so
+/// the metrics are never served publicly without a token.
+#[derive(Debug, Clone)]
+pub struct PrometheusConfig {
+    /// Token scrapers send as `Authorization: Bearer <token>`; with it
+    /// `/metrics` is also served on the main listener
+    /// (env: `PROMETHEUS_BEARER_TOKEN`)
+    pub bearer_token: Option<String>,
+
+    /// Separate listener for `/metrics`, meant for an internal address
+    /// (env: `PROMETHEUS_BIND_ADDRESS`, e.g. `"127.0.0.1:9464"`)
+    pub bind_address: Option<String>,
+}
+
+/// `ClickHouse` HTTP interface settings for the telemetry backend.
+#[derive(Debug, Clone)]
+pub struct ClickHouseConfig {
+    /// HTTP interface URL (env: `CLICKHOUSE_URL`, e.g. `"http://clickhouse:8123"`)
+    pub url: String,
+
+    /// Database for the telemetry tables, created if missing
+    /// (env: `CLICKHOUSE_DATABASE`, default: `"kaiku"`)
+    pub database: String,
+
+    /// User name (env: `CLICKHOUSE_USER`, default: server default user)
+    pub user: Option<String>,
+
+    /// Password (env: `CLICKHOUSE_PASSWORD`)
+    pub password: Option<String>,
 }
 
 impl ObservabilityConfig {
     /// Load observability configuration from environment variables.
-    pub fn from_env() -> Self {
-        Self {
-            enabled: env::var("OBSERVABILITY_ENABLED")
+    pub fn from_env() -> Result<Self> {
+        Self::from_vars(&EnvVars::process())
+    }
+
+    /// Load observability configuration from `vars`.
+    pub fn from_vars(vars: &EnvVars) -> Result<Self> {
+        let telemetry_storage = match vars
+            .var("TELEMETRY_STORAGE")
+            .unwrap_or_else(|_| "postgres".to_string())
+            .to_lowercase()
+            .as_str()
+        {
+            "postgres" => TelemetryStorageBackend::Postgres,
+            "clickhouse" => TelemetryStorageBackend::ClickHouse,
+            other => anyhow::bail!(
+                "Invalid TELEMETRY_STORAGE value '{other}'. Must be one of: postgres, clickhouse"
+            ),
+        };
+
+        let clickhouse = match vars.var("CLICKHOUSE_URL").ok().filter(|u| !u.is_empty()) {
+            Some(url) => {
+                let database = vars
+                    .var("CLICKHOUSE_DATABASE")
+                    .unwrap_or_else(|_| "kaiku".to_string());
+                anyhow::ensure!(
+                    !database.is_empty()
+                        && database
+                            .chars()
+                            .all(|c| c.is_ascii_alphanumeric() || c == '_'),
+                    "Invalid CLICKHOUSE_DATABASE '{database}'. Use letters, digits and underscores"
+                );
+                Some(ClickHouseConfig {
+                    url,
+                    database,
+                    user: vars.var("CLICKHOUSE_USER").ok().filter(|u| !u.is_empty()),
+                    password: vars
+                        .var("CLICKHOUSE_PASSWORD")
+                        .ok()
+                        .filter(|p| !p.is_empty()),
+                })
+            }
+            None => None,
+        };
+        anyhow::ensure!(
+            telemetry_storage != TelemetryStorageBackend::ClickHouse || clickhouse.is_some(),
+            "TELEMETRY_STORAGE=clickhouse requires CLICKHOUSE_URL"
+        );
+
+        let prometheus = if vars
+            .var("PROMETHEUS_ENABLED")
+            .ok()
+            .is_some_and(|v| v.to_lowercase() == "true" || v == "1")
+        {
+            let prometheus = PrometheusConfig {
+                bearer_token: vars
+                    .var("PROMETHEUS_BEARER_TOKEN")
+                    .ok()
+                    .filter(|t| !t.is_empty()),
+                bind_address: vars
+                    .var("PROMETHEUS_BIND_ADDRESS")
+                    .ok()
+                    .filter(|a| !a.is_empty()),
+            };
+            anyhow::ensure!(
+                prometheus.bearer_token.is_some() || prometheus.bind_address.is_some(),
+                "PROMETHEUS_ENABLED requires PROMETHEUS_BEARER_TOKEN or PROMETHEUS_BIND_ADDRESS"
+            );
+            Some(prometheus)
+        } else {
+            None
+        };
+
+        Ok(Self {
+            enabled: vars
+                .var("OBSERVABILITY_ENABLED")
                 .ok()
                 .map(|v| v.to_lowercase() == "true" || v == "1")
                 .unwrap_or(false),
-            otlp_endpoint: env::var("OTEL_EXPORTER_OTLP_ENDPOINT")
+            otlp_endpoint: vars
+                .var("OTEL_EXPORTER_OTLP_ENDPOINT")
                 .unwrap_or_else(|_| "http://localhost:4317".into()),
-            service_name: env::var("OTEL_SERVICE_NAME").unwrap_or_else(|_| "vc-server".into()),
-            trace_sample_ratio: env::var("OTEL_TRACES_SAMPLER_ARG")
+            service_name: vars
+                .var("OTEL_SERVICE_NAME")
+                .unwrap_or_else(|_| "vc-server".into()),
+            trace_sample_ratio: vars
+                .var("OTEL_TRACES_SAMPLER_ARG")
                 .ok()
                 .and_then(|v| v.parse().ok())
                 .unwrap_or(0.1),
-            log_level: env::var("RUST_LOG").unwrap_or_else(|_| "vc_server=info".into()),
+            log_level: vars
+                .var("RUST_LOG")
+                .unwrap_or_else(|_| "vc_server=info".into()),
+            telemetry_storage,
+            clickhouse,
+            prometheus,
+        })
+    }
+}
+
+/// Per-route token buckets enforced by the global rate limit layer.
+///
+/// Buckets are keyed by the authenticated user, or by client IP when the
+/// request carries no valid access token. They apply on top of the
+/// per-category limits set on individual routers.
+#[derive(Debug, Clone)]
+pub struct RoutePolicyConfig {
+    /// `POST /auth/login` (default: 10 per 60s)
+    pub login: TokenBucketConfig,
+    /// `POST /auth/register` (default: 5 per 600s)
+    pub register: TokenBucketConfig,
+    /// `POST /api/messages/channel/{channel_id}` (default: 20 per 20s)
+    pub message_create: TokenBucketConfig,
+    /// Attachment, avatar, DM icon and emoji uploads (default: 10 per 60s)
+    pub uploads: TokenBucketConfig,
+}
+
+impl Default for RoutePolicyConfig {
+    fn default() -> Self {
+        Self {
+            login: TokenBucketConfig {
+                capacity: 10,
+                refill_secs: 60,
+            },
+            register: TokenBucketConfig {
+                capacity: 5,
+                refill_secs: 600,
+            },
+            message_create: TokenBucketConfig {
+                capacity: 20,
+                refill_secs: 20,
+            },
+            uploads: TokenBucketConfig {
+                capacity: 10,
+                refill_secs: 60,
+            },
+        }
+    }
+}
+
+impl RoutePolicyConfig {
+    /// Load route policies from `RATE_LIMIT_ROUTE_*` environment variables,
+    /// each formatted as "`capacity,refill_secs`".
+    pub fn from_env() -> Self {
+        Self::from_vars(&EnvVars::process())
+    }
+
+    /// Load route policies from `vars`.
+    pub fn from_vars(vars: &EnvVars) -> Self {
+        let defaults = Self::default();
+        let bucket = |name: &str, default: TokenBucketConfig| {
+            vars.var(name)
+                .ok()
+                .and_then(|v| parse_token_bucket_config(&v))
+                .unwrap_or(default)
+        };
+        Self {
+            login: bucket("RATE_LIMIT_ROUTE_LOGIN", defaults.login),
+            register: bucket("RATE_LIMIT_ROUTE_REGISTER", defaults.register),
+            message_create: bucket("RATE_LIMIT_ROUTE_MESSAGE_CREATE", defaults.message_create),
+            uploads: bucket("RATE_LIMIT_ROUTE_UPLOADS", defaults.uploads),
         }
     }
 }
@@ -88,6 +396,18 @@ pub struct Config {
     /// Allowed MIME types for file uploads (comma-separated)
     pub allowed_mime_types: Option<Vec<String>>,
 
+    /// clamd TCP address for attachment malware scanning (env: `CLAMAV_ADDRESS`,
+    /// e.g. `"clamav:3310"`). Attachments are not scanned when unset.
+    pub clamav_address: Option<String>,
+
+    /// Attachment scan timeout in seconds (env: `ATTACHMENT_SCAN_TIMEOUT`, default: 30)
+    pub attachment_scan_timeout_secs: u64,
+
+    /// Whether attachments become visible when the scanner is unavailable
+    /// (env: `ATTACHMENT_SCAN_FAIL_OPEN`, default: false). When false they stay
+    /// hidden as `failed` until a system admin releases them.
+    pub attachment_scan_fail_open: bool,
+
     /// OIDC issuer URL (optional)
     pub oidc_issuer_url: Option<String>,
 
@@ -127,9 +447,43 @@ pub struct Config {
     /// WebRTC TURN credential (optional)
     pub turn_credential: Option<String>,
 
+    /// SFU media nodes offered for latency-based placement (default: none = single node)
+    pub voice_media_nodes: Vec<MediaNode>,
+
+    /// Packet loss (percent) a participant must average over
+    /// `voice_loss_window_secs` to be warned and have their bitrate lowered;
+    /// the cap is lifted below half of it (default: 5)
+    pub voice_loss_warning_percent: f32,
+
+    /// Window of stored connection metrics averaged for packet loss
+    /// detection, in seconds (default: 30)
+    pub voice_loss_window_secs: u64,
+
+    /// Opus bitrate cap (bps) applied while packet loss is sustained (default: 24000)
+    pub voice_loss_audio_bitrate: u32,
+
     /// MFA secret encryption key (32-byte hex string)
     pub mfa_encryption_key: Option<String>,
 
+    /// `WebAuthn` relying party ID, the domain passkeys are bound to (optional,
+    /// enables passkeys together with `webauthn_rp_origin`)
+    pub webauthn_rp_id: Option<String>,
+
+    /// `WebAuthn` relying party origin (e.g., "<https://chat.example.com>")
+    pub webauthn_rp_origin: Option<String>,
+
+    /// Whether sign-ins from a network the account has not used before also
+    /// send an email alert, when SMTP is configured
+    /// (env: `NEW_NETWORK_LOGIN_EMAIL`, default: true)
+    pub new_network_login_email: bool,
+
+    /// Whether accounts with TOTP enabled must enter their authenticator code
+    /// when signing in from a new network, instead of a passkey standing in
+    /// for it. Accounts without TOTP, OIDC and device-link sign-ins are not
+    /// challenged
+    /// (env: `NEW_NETWORK_REQUIRE_TOTP`, default: false)
+    pub new_network_require_totp: bool,
+
     /// Whether E2EE setup is required before using the app (default: false)
     pub require_e2ee_setup: bool,
 
@@ -175,7 +529,18 @@ pub struct Config {
     /// SMTP TLS mode: "starttls" (default), "tls", or "none"
     pub smtp_tls: String,
 
-    /// Whether to enable API documentation (Swagger UI) at /api/docs
+    /// Email backend: "smtp" (default) or "console", which logs emails
+    /// instead of sending them and needs no SMTP settings
+    /// (env: `EMAIL_BACKEND`)
+    pub email_backend: String,
+
+    /// Whether self-service password reset emails are only sent to verified
+    /// addresses; admin-initiated resets are not affected
+    /// (env: `PASSWORD_RESET_REQUIRES_VERIFIED_EMAIL`, default: false)
+    pub password_reset_requires_verified_email: bool,
+
+    /// Whether to serve API documentation: Swagger UI at /api/docs and the
+    /// `OpenAPI` spec at /api/openapi.json
     ///
     /// Defaults to `true` in debug builds, `false` in release builds.
     /// Override via `ENABLE_API_DOCS` env var ("true"/"1" to enable, "false"/"0" to disable).
@@ -186,6 +551,30 @@ pub struct Config {
     /// Defaults to `true`. Override via `ENABLE_GUILD_DISCOVERY` env var.
     pub enable_guild_discovery: bool,
 
+    /// Reports (since the last review) that automatically delist a guild from
+    /// discovery and return it to the review queue.
+    ///
+    /// Defaults to `5`. Override via `DISCOVERY_REPORT_THRESHOLD` env var.
+    pub discovery_report_threshold: i64,
+
+    /// Whether to fetch link previews (`OpenGraph` metadata) for URLs in messages.
+    ///
+    /// Defaults to `true`. Override via `ENABLE_LINK_PREVIEWS` env var.
+    pub enable_link_previews: bool,
+
+    // ========================================================================
+    // Push Notifications
+    // ========================================================================
+    /// VAPID private key for Web Push: the raw P-256 scalar, base64url-encoded
+    /// (optional, enables the Web Push backend together with `push_vapid_subject`)
+    pub push_vapid_private_key: Option<String>,
+
+    /// VAPID contact URI sent to push services (e.g., "mailto:admin@example.com")
+    pub push_vapid_subject: Option<String>,
+
+    /// Path to a Firebase service account JSON file (optional, enables the FCM backend)
+    pub push_fcm_service_account: Option<String>,
+
     // ========================================================================
     // Resource Limits
     // ========================================================================
@@ -222,6 +611,63 @@ pub struct Config {
     /// Maximum number of revisions per page (default: 25)
     pub max_revisions_per_page: i64,
 
+    // ========================================================================
+    // Supporter Perks
+    // ========================================================================
+    /// Supporter counts at which a guild reaches perk tiers 1, 2, 3, ... (default: 2,7,14)
+    pub perk_tier_thresholds: Vec<i64>,
+
+    /// Extra custom emoji slots per perk tier (default: 50)
+    pub perk_emoji_slots_per_tier: i64,
+
+    /// Extra upload size in bytes per perk tier (default: 25MB)
+    pub perk_upload_size_per_tier: usize,
+
+    /// Opus voice bitrate cap in bits per second, indexed by perk tier (default: 64000,128000,256000,384000)
+    pub perk_voice_bitrates: Vec<u32>,
+
+    /// Shared secret for signed billing entitlement webhooks (default: unset = disabled)
+    pub billing_webhook_secret: Option<String>,
+
+    // ========================================================================
+    // Data Governance
+    // ========================================================================
+    /// What happens to the messages of deleted accounts (env: `DELETED_USER_CONTENT`,
+    /// `anonymize` or `delete`, default: `anonymize`)
+    pub deleted_user_content: DeletedUserContent,
+
+    // ========================================================================
+    // Clustering
+    // ========================================================================
+    /// Identifier of this server replica in the cluster node registry
+    /// (env: `NODE_ID`, default: `HOSTNAME`, else a random `node-…` id)
+    pub node_id: String,
+
+    /// Region label of this node, matched against a guild's preferred voice
+    /// region (env: `NODE_REGION`, optional)
+    pub node_region: Option<String>,
+
+    /// Public IP address advertised in this node's ICE candidates and
+    /// returned to clients joining voice through it (env:
+    /// `NODE_PUBLIC_ADDRESS`, optional)
+    pub node_public_address: Option<String>,
+
+    /// Control endpoints of remote SFU nodes that host voice rooms for this
+    /// server, e.g. `http://sfu-1.internal:50051` (env: `SFU_REMOTE_NODES`,
+    /// comma-separated, optional)
+    pub sfu_remote_nodes: Vec<String>,
+
+    /// Shared secret of the SFU control protocol (env: `SFU_CONTROL_TOKEN`,
+    /// required with `SFU_REMOTE_NODES` and on SFU nodes)
+    pub sfu_control_token: Option<String>,
+
+    /// Address the `sfu-node` binary serves the control protocol on
+    /// (env: `SFU_CONTROL_BIND_ADDRESS`, default: `0.0.0.0:50051`)
+    pub sfu_control_bind_address: String,
+
+    /// Global per-route rate limit policies
+    pub route_rate_limits: RoutePolicyConfig,
+
     /// Observability and telemetry configuration
     pub observability: ObservabilityConfig,
 
@@ -247,67 +693,112 @@ pub struct Config {
 impl Config {
     /// Load configuration from environment variables.
     pub fn from_env() -> Result<Self> {
+        Self::from_vars(&EnvVars::process())
+    }
+
+    /// Load configuration from `vars`.
+    pub fn from_vars(vars: &EnvVars) -> Result<Self> {
         let config = Self {
-            bind_address: env::var("BIND_ADDRESS").unwrap_or_else(|_| "0.0.0.0:8080".into()),
-            database_url: env::var("DATABASE_URL").context("DATABASE_URL must be set")?,
-            redis_url: env::var("REDIS_URL").unwrap_or_else(|_| "redis://localhost:6379".into()),
-            jwt_private_key: env::var("JWT_PRIVATE_KEY")
+            bind_address: vars.var("BIND_ADDRESS").unwrap_or_else(|_| "0.0.0.0:8080".into()),
+            database_url: vars.var("DATABASE_URL").context("DATABASE_URL must be set")?,
+            redis_url: vars.var("REDIS_URL").unwrap_or_else(|_| "redis://localhost:6379".into()),
+            jwt_private_key: vars.var("JWT_PRIVATE_KEY")
                 .context("JWT_PRIVATE_KEY must be set (base64-encoded PEM)")?,
-            jwt_public_key: env::var("JWT_PUBLIC_KEY")
+            jwt_public_key: vars.var("JWT_PUBLIC_KEY")
                 .context("JWT_PUBLIC_KEY must be set (base64-encoded PEM)")?,
-            jwt_access_expiry: env::var("JWT_ACCESS_EXPIRY")
+            jwt_access_expiry: vars.var("JWT_ACCESS_EXPIRY")
                 .ok()
                 .and_then(|v| v.parse().ok())
                 .unwrap_or(900),
-            jwt_refresh_expiry: env::var("JWT_REFRESH_EXPIRY")
+            jwt_refresh_expiry: vars.var("JWT_REFRESH_EXPIRY")
                 .ok()
                 .and_then(|v| v.parse().ok())
                 .unwrap_or(604800),
-            s3_endpoint: env::var("S3_ENDPOINT").ok(),
-            s3_bucket: env::var("S3_BUCKET").unwrap_or_else(|_| "voicechat".into()),
-            s3_presign_expiry: env::var("S3_PRESIGN_EXPIRY")
+            s3_endpoint: vars.var("S3_ENDPOINT").ok(),
+            s3_bucket: vars.var("S3_BUCKET").unwrap_or_else(|_| "voicechat".into()),
+            s3_presign_expiry: vars.var("S3_PRESIGN_EXPIRY")
                 .ok()
                 .and_then(|v| v.parse().ok())
                 .unwrap_or(3600) // 1 hour
                 .max(1),
-            s3_access_key: env::var("AWS_ACCESS_KEY_ID").ok(),
-            s3_secret_key: env::var("AWS_SECRET_ACCESS_KEY").ok(),
-            allowed_mime_types: env::var("ALLOWED_MIME_TYPES").ok().map(|s| {
+            s3_access_key: vars.var("AWS_ACCESS_KEY_ID").ok(),
+            s3_secret_key: vars.var("AWS_SECRET_ACCESS_KEY").ok(),
+            allowed_mime_types: vars.var("ALLOWED_MIME_TYPES").ok().map(|s| {
                 s.split(',')
                     .map(|t| t.trim().to_string())
                     .filter(|t| !t.is_empty())
                     .collect()
             }),
-            oidc_issuer_url: env::var("OIDC_ISSUER_URL").ok(),
-            oidc_client_id: env::var("OIDC_CLIENT_ID").ok(),
-            oidc_client_secret: env::var("OIDC_CLIENT_SECRET").ok(),
-            max_upload_size: env::var("MAX_UPLOAD_SIZE")
+            clamav_address: vars.var("CLAMAV_ADDRESS").ok().filter(|a| !a.is_empty()),
+            attachment_scan_timeout_secs: vars.var("ATTACHMENT_SCAN_TIMEOUT")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(30)
+                .max(1),
+            attachment_scan_fail_open: vars.var("ATTACHMENT_SCAN_FAIL_OPEN")
+                .ok()
+                .map(|v| v.to_lowercase() == "true" || v == "1")
+                .unwrap_or(false),
+            oidc_issuer_url: vars.var("OIDC_ISSUER_URL").ok(),
+            oidc_client_id: vars.var("OIDC_CLIENT_ID").ok(),
+            oidc_client_secret: vars.var("OIDC_CLIENT_SECRET").ok(),
+            max_upload_size: vars.var("MAX_UPLOAD_SIZE")
                 .ok()
                 .and_then(|v| v.parse().ok())
                 .unwrap_or(50 * 1024 * 1024), // 50MB
-            max_avatar_size: env::var("MAX_AVATAR_SIZE")
+            max_avatar_size: vars.var("MAX_AVATAR_SIZE")
                 .ok()
                 .and_then(|v| v.parse().ok())
                 .unwrap_or(5 * 1024 * 1024), // 5MB
-            max_emoji_size: env::var("MAX_EMOJI_SIZE")
+            max_emoji_size: vars.var("MAX_EMOJI_SIZE")
                 .ok()
                 .and_then(|v| v.parse().ok())
                 .unwrap_or(256 * 1024), // 256KB
-            stun_server: env::var("STUN_SERVER")
+            stun_server: vars.var("STUN_SERVER")
                 .unwrap_or_else(|_| "stun:stun.l.google.com:19302".into()),
-            turn_server: env::var("TURN_SERVER").ok(),
-            turn_username: env::var("TURN_USERNAME").ok(),
-            turn_credential: env::var("TURN_CREDENTIAL").ok(),
-            mfa_encryption_key: env::var("MFA_ENCRYPTION_KEY").ok(),
-            require_e2ee_setup: env::var("REQUIRE_E2EE_SETUP")
+            turn_server: vars.var("TURN_SERVER").ok(),
+            turn_username: vars.var("TURN_USERNAME").ok(),
+            turn_credential: vars.var("TURN_CREDENTIAL").ok(),
+            voice_media_nodes: vars.var("VOICE_MEDIA_NODES")
+                .ok()
+                .map(|s| parse_media_nodes(&s))
+                .unwrap_or_default(),
+            voice_loss_warning_percent: vars.var("VOICE_LOSS_WARNING_PERCENT")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .filter(|v: &f32| *v > 0.0 && *v <= 100.0)
+                .unwrap_or(5.0),
+            voice_loss_window_secs: vars.var("VOICE_LOSS_WINDOW_SECS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .filter(|v: &u64| *v > 0)
+                .unwrap_or(30),
+            voice_loss_audio_bitrate: vars.var("VOICE_LOSS_AUDIO_BITRATE")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(24_000),
+            mfa_encryption_key: vars.var("MFA_ENCRYPTION_KEY").ok(),
+            webauthn_rp_id: vars.var("WEBAUTHN_RP_ID").ok().filter(|s| !s.is_empty()),
+            webauthn_rp_origin: vars.var("WEBAUTHN_RP_ORIGIN")
+                .ok()
+                .filter(|s| !s.is_empty()),
+            new_network_login_email: vars.var("NEW_NETWORK_LOGIN_EMAIL")
+                .ok()
+                .map(|v| v.to_lowercase() == "true" || v == "1")
+                .unwrap_or(true),
+            new_network_require_totp: vars.var("NEW_NETWORK_REQUIRE_TOTP")
                 .ok()
                 .map(|v| v.to_lowercase() == "true" || v == "1")
                 .unwrap_or(false),
-            block_check_fail_open: env::var("BLOCK_CHECK_FAIL_OPEN")
+            require_e2ee_setup: vars.var("REQUIRE_E2EE_SETUP")
                 .ok()
                 .map(|v| v.to_lowercase() == "true" || v == "1")
                 .unwrap_or(false),
-            cors_allowed_origins: env::var("CORS_ALLOWED_ORIGINS")
+            block_check_fail_open: vars.var("BLOCK_CHECK_FAIL_OPEN")
+                .ok()
+                .map(|v| v.to_lowercase() == "true" || v == "1")
+                .unwrap_or(false),
+            cors_allowed_origins: vars.var("CORS_ALLOWED_ORIGINS")
                 .ok()
                 .map(|s| {
                     s.split(',')
@@ -316,13 +807,13 @@ impl Config {
                         .collect()
                 })
                 .unwrap_or_else(|| vec!["*".to_string()]),
-            cookie_secure: env::var("COOKIE_SECURE")
+            cookie_secure: vars.var("COOKIE_SECURE")
                 .ok()
                 .map(|v| v.to_lowercase() == "true" || v == "1")
                 .unwrap_or(!cfg!(debug_assertions)),
-            cookie_domain: env::var("COOKIE_DOMAIN").ok().filter(|d| !d.is_empty()),
+            cookie_domain: vars.var("COOKIE_DOMAIN").ok().filter(|d| !d.is_empty()),
             cookie_same_site: {
-                let value = env::var("COOKIE_SAMESITE")
+                let value = vars.var("COOKIE_SAMESITE")
                     .unwrap_or_else(|_| "lax".to_string())
                     .to_lowercase();
                 anyhow::ensure!(
@@ -331,84 +822,190 @@ impl Config {
                 );
                 value
             },
-            smtp_host: env::var("SMTP_HOST").ok(),
-            smtp_port: env::var("SMTP_PORT")
+            smtp_host: vars.var("SMTP_HOST").ok(),
+            smtp_port: vars.var("SMTP_PORT")
                 .ok()
                 .and_then(|v| v.parse().ok())
                 .unwrap_or(587),
-            smtp_username: env::var("SMTP_USERNAME").ok(),
-            smtp_password: env::var("SMTP_PASSWORD").ok(),
-            smtp_from: env::var("SMTP_FROM").ok(),
-            smtp_tls: env::var("SMTP_TLS").unwrap_or_else(|_| "starttls".into()),
-            enable_api_docs: env::var("ENABLE_API_DOCS")
+            smtp_username: vars.var("SMTP_USERNAME").ok(),
+            smtp_password: vars.var("SMTP_PASSWORD").ok(),
+            smtp_from: vars.var("SMTP_FROM").ok(),
+            smtp_tls: vars.var("SMTP_TLS").unwrap_or_else(|_| "starttls".into()),
+            email_backend: vars.var("EMAIL_BACKEND")
+                .map(|v| v.to_lowercase())
+                .unwrap_or_else(|_| "smtp".into()),
+            password_reset_requires_verified_email: vars.var(
+                "PASSWORD_RESET_REQUIRES_VERIFIED_EMAIL",
+            )
+            .ok()
+            .map(|v| v.to_lowercase() == "true" || v == "1")
+            .unwrap_or(false),
+            enable_api_docs: vars.var("ENABLE_API_DOCS")
                 .ok()
                 .map(|v| v.to_lowercase() == "true" || v == "1")
                 .unwrap_or(cfg!(debug_assertions)),
-            enable_guild_discovery: env::var("ENABLE_GUILD_DISCOVERY")
+            enable_guild_discovery: vars.var("ENABLE_GUILD_DISCOVERY")
                 .ok()
                 .map(|v| v.to_lowercase() == "true" || v == "1")
                 .unwrap_or(true),
-            max_guilds_per_user: env::var("MAX_GUILDS_PER_USER")
+            discovery_report_threshold: vars.var("DISCOVERY_REPORT_THRESHOLD")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(5)
+                .max(1),
+            enable_link_previews: vars.var("ENABLE_LINK_PREVIEWS")
+                .ok()
+                .map(|v| v.to_lowercase() == "true" || v == "1")
+                .unwrap_or(true),
+            push_vapid_private_key: vars.var("PUSH_VAPID_PRIVATE_KEY")
+                .ok()
+                .filter(|s| !s.is_empty()),
+            push_vapid_subject: vars.var("PUSH_VAPID_SUBJECT")
+                .ok()
+                .filter(|s| !s.is_empty()),
+            push_fcm_service_account: vars.var("PUSH_FCM_SERVICE_ACCOUNT")
+                .ok()
+                .filter(|s| !s.is_empty()),
+            max_guilds_per_user: vars.var("MAX_GUILDS_PER_USER")
                 .ok()
                 .and_then(|v| v.parse().ok())
                 .unwrap_or(100)
                 .max(1),
-            max_members_per_guild: env::var("MAX_MEMBERS_PER_GUILD")
+            max_members_per_guild: vars.var("MAX_MEMBERS_PER_GUILD")
                 .ok()
                 .and_then(|v| v.parse().ok())
                 .unwrap_or(1000)
                 .max(1),
-            max_channels_per_guild: env::var("MAX_CHANNELS_PER_GUILD")
+            max_channels_per_guild: vars.var("MAX_CHANNELS_PER_GUILD")
                 .ok()
                 .and_then(|v| v.parse().ok())
                 .unwrap_or(200)
                 .max(1),
-            max_roles_per_guild: env::var("MAX_ROLES_PER_GUILD")
+            max_roles_per_guild: vars.var("MAX_ROLES_PER_GUILD")
                 .ok()
                 .and_then(|v| v.parse().ok())
                 .unwrap_or(50)
                 .max(1),
-            max_emojis_per_guild: env::var("MAX_EMOJIS_PER_GUILD")
+            max_emojis_per_guild: vars.var("MAX_EMOJIS_PER_GUILD")
                 .ok()
                 .and_then(|v| v.parse().ok())
                 .unwrap_or(50)
                 .max(1),
-            max_bots_per_guild: env::var("MAX_BOTS_PER_GUILD")
+            max_bots_per_guild: vars.var("MAX_BOTS_PER_GUILD")
                 .ok()
                 .and_then(|v| v.parse().ok())
                 .unwrap_or(10)
                 .max(1),
-            max_webhooks_per_app: env::var("MAX_WEBHOOKS_PER_APP")
+            max_webhooks_per_app: vars.var("MAX_WEBHOOKS_PER_APP")
                 .ok()
                 .and_then(|v| v.parse().ok())
                 .unwrap_or(5)
                 .max(1),
-            max_workspaces_per_user: env::var("MAX_WORKSPACES_PER_USER")
+            max_workspaces_per_user: vars.var("MAX_WORKSPACES_PER_USER")
                 .ok()
                 .and_then(|v| v.parse().ok())
                 .unwrap_or(20)
                 .max(1),
-            max_entries_per_workspace: env::var("MAX_ENTRIES_PER_WORKSPACE")
+            max_entries_per_workspace: vars.var("MAX_ENTRIES_PER_WORKSPACE")
                 .ok()
                 .and_then(|v| v.parse().ok())
                 .unwrap_or(50)
                 .max(1),
-            max_pages_per_guild: env::var("MAX_PAGES_PER_GUILD")
+            max_pages_per_guild: vars.var("MAX_PAGES_PER_GUILD")
                 .ok()
                 .and_then(|v| v.parse().ok())
                 .unwrap_or(10)
                 .max(1),
-            max_revisions_per_page: env::var("MAX_REVISIONS_PER_PAGE")
+            max_revisions_per_page: vars.var("MAX_REVISIONS_PER_PAGE")
                 .ok()
                 .and_then(|v| v.parse().ok())
                 .unwrap_or(25)
                 .max(1),
-            observability: ObservabilityConfig::from_env(),
-            environment: env::var("KAIKU_ENV").unwrap_or_else(|_| "production".into()),
-            grafana_url: env::var("GRAFANA_URL").ok(),
-            tempo_url: env::var("TEMPO_URL").ok(),
-            loki_url: env::var("LOKI_URL").ok(),
-            prometheus_url: env::var("PROMETHEUS_URL").ok(),
+            perk_tier_thresholds: vars.var("PERK_TIER_THRESHOLDS")
+                .ok()
+                .map(|s| {
+                    let mut thresholds: Vec<i64> = s
+                        .split(',')
+                        .filter_map(|t| t.trim().parse().ok())
+                        .filter(|&t| t > 0)
+                        .collect();
+                    thresholds.sort_unstable();
+                    thresholds
+                })
+                .unwrap_or_else(|| vec![2, 7, 14]),
+            perk_emoji_slots_per_tier: vars.var("PERK_EMOJI_SLOTS_PER_TIER")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(50)
+                .max(0),
+            perk_upload_size_per_tier: vars.var("PERK_UPLOAD_SIZE_PER_TIER")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(25 * 1024 * 1024),
+            perk_voice_bitrates: vars.var("PERK_VOICE_BITRATES")
+                .ok()
+                .map(|s| {
+                    s.split(',')
+                        .filter_map(|b| b.trim().parse().ok())
+                        .filter(|&b| b >= 6_000)
+                        .collect::<Vec<u32>>()
+                })
+                .filter(|v| !v.is_empty())
+                .unwrap_or_else(|| vec![64_000, 128_000, 256_000, 384_000]),
+            billing_webhook_secret: vars.var("BILLING_WEBHOOK_SECRET")
+                .ok()
+                .filter(|s| !s.is_empty()),
+            deleted_user_content: match vars.var("DELETED_USER_CONTENT")
+                .unwrap_or_else(|_| "anonymize".to_string())
+                .to_lowercase()
+                .as_str()
+            {
+                "anonymize" => DeletedUserContent::Anonymize,
+                "delete" => DeletedUserContent::Delete,
+                other => anyhow::bail!(
+                    "Invalid DELETED_USER_CONTENT value '{other}'. Must be one of: anonymize, delete"
+                ),
+            },
+            node_id: vars.var("NODE_ID")
+                .or_else(|_| vars.var("HOSTNAME"))
+                .ok()
+                .filter(|s| !s.trim().is_empty())
+                .map_or_else(
+                    || format!("node-{}", &uuid::Uuid::new_v4().simple().to_string()[..12]),
+                    |s| s.trim().to_string(),
+                ),
+            node_region: vars.var("NODE_REGION")
+                .ok()
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty()),
+            node_public_address: match vars.var("NODE_PUBLIC_ADDRESS") {
+                Ok(s) if !s.trim().is_empty() => {
+                    let address = s.trim();
+                    address.parse::<std::net::IpAddr>().with_context(|| {
+                        format!("NODE_PUBLIC_ADDRESS must be an IP address, got '{address}'")
+                    })?;
+                    Some(address.to_string())
+                }
+                _ => None,
+            },
+            sfu_remote_nodes: vars.var("SFU_REMOTE_NODES")
+                .map(|s| {
+                    s.split(',')
+                        .map(|url| url.trim().to_string())
+                        .filter(|url| !url.is_empty())
+                        .collect()
+                })
+                .unwrap_or_default(),
+            sfu_control_token: vars.var("SFU_CONTROL_TOKEN").ok().filter(|s| !s.is_empty()),
+            sfu_control_bind_address: vars.var("SFU_CONTROL_BIND_ADDRESS")
+                .unwrap_or_else(|_| "0.0.0.0:50051".into()),
+            route_rate_limits: RoutePolicyConfig::from_vars(vars),
+            observability: ObservabilityConfig::from_vars(vars)?,
+            environment: vars.var("KAIKU_ENV").unwrap_or_else(|_| "production".into()),
+            grafana_url: vars.var("GRAFANA_URL").ok(),
+            tempo_url: vars.var("TEMPO_URL").ok(),
+            loki_url: vars.var("LOKI_URL").ok(),
+            prometheus_url: vars.var("PROMETHEUS_URL").ok(),
         };
 
         // SameSite=None requires the Secure flag — browsers reject the cookie otherwise
@@ -418,6 +1015,11 @@ impl Config {
              browsers will reject the refresh cookie without the Secure flag"
         );
 
+        anyhow::ensure!(
+            config.sfu_remote_nodes.is_empty() || config.sfu_control_token.is_some(),
+            "SFU_REMOTE_NODES requires SFU_CONTROL_TOKEN"
+        );
+
         Ok(config)
     }
 
@@ -438,12 +1040,101 @@ impl Config {
             && self.smtp_from.is_some()
     }
 
+    /// Check if an email backend is available: SMTP is configured or
+    /// `EMAIL_BACKEND=console`.
+    #[must_use]
+    pub fn has_email(&self) -> bool {
+        self.email_backend == "console" || self.has_smtp()
+    }
+
+    /// Check if the Web Push (VAPID) backend is configured.
+    #[must_use]
+    pub const fn has_web_push(&self) -> bool {
+        self.push_vapid_private_key.is_some() && self.push_vapid_subject.is_some()
+    }
+
+    /// Check if the FCM push backend is configured.
+    #[must_use]
+    pub const fn has_fcm(&self) -> bool {
+        self.push_fcm_service_account.is_some()
+    }
+
     /// Check if TURN is configured.
     #[must_use]
     pub const fn has_turn(&self) -> bool {
         self.turn_server.is_some()
     }
 
+    /// Check if `WebAuthn` passkeys are configured.
+    #[must_use]
+    pub const fn has_webauthn(&self) -> bool {
+        self.webauthn_rp_id.is_some() && self.webauthn_rp_origin.is_some()
+    }
+
+    /// Restore the settings only read at startup to their `startup` value.
+    ///
+    /// These build the listener, database and Redis pools, storage, email, push
+    /// and OIDC clients, or identify the node, so a new value would be ignored
+    /// or inconsistent until a restart. Upload limits are capped to `startup`,
+    /// whose values are also the request body limits of the router.
+    ///
+    /// Returns the restart-only settings whose value changed.
+    pub fn keep_startup_settings(&mut self, startup: &Self) -> Vec<&'static str> {
+        let mut changed = Vec::new();
+        macro_rules! keep {
+            ($($field:ident),+ $(,)?) => {$(
+                if self.$field != startup.$field {
+                    changed.push(stringify!($field));
+                    self.$field.clone_from(&startup.$field);
+                }
+            )+};
+        }
+        keep!(
+            bind_address,
+            database_url,
+            redis_url,
+            jwt_private_key,
+            jwt_public_key,
+            mfa_encryption_key,
+            s3_endpoint,
+            s3_bucket,
+            s3_access_key,
+            s3_secret_key,
+            clamav_address,
+            oidc_issuer_url,
+            oidc_client_id,
+            oidc_client_secret,
+            cors_allowed_origins,
+            enable_api_docs,
+            smtp_host,
+            smtp_port,
+            smtp_username,
+            smtp_password,
+            smtp_from,
+            smtp_tls,
+            email_backend,
+            push_vapid_private_key,
+            push_vapid_subject,
+            push_fcm_service_account,
+            deleted_user_content,
+            node_region,
+            node_public_address,
+            sfu_remote_nodes,
+            sfu_control_token,
+            sfu_control_bind_address,
+        );
+
+        // Generated when `NODE_ID` is unset, so it differs on every load
+        self.node_id.clone_from(&startup.node_id);
+        self.observability = startup.observability.clone();
+
+        self.max_upload_size = self.max_upload_size.min(startup.max_upload_size);
+        self.max_avatar_size = self.max_avatar_size.min(startup.max_avatar_size);
+        self.max_emoji_size = self.max_emoji_size.min(startup.max_emoji_size);
+
+        changed
+    }
+
     /// Create a default configuration for testing.
     ///
     /// Respects `DATABASE_URL` and `REDIS_URL` environment variables (for CI),
@@ -471,6 +1162,9 @@ impl Config {
             s3_access_key: None,
             s3_secret_key: None,
             allowed_mime_types: None,
+            clamav_address: None,
+            attachment_scan_timeout_secs: 30,
+            attachment_scan_fail_open: false,
             max_upload_size: 50 * 1024 * 1024,
             max_avatar_size: 5 * 1024 * 1024,
             max_emoji_size: 256 * 1024,
@@ -481,7 +1175,15 @@ impl Config {
             turn_server: None,
             turn_username: None,
             turn_credential: None,
+            voice_media_nodes: Vec::new(),
+            voice_loss_warning_percent: 5.0,
+            voice_loss_window_secs: 30,
+            voice_loss_audio_bitrate: 24_000,
             mfa_encryption_key: Some(TEST_MFA_ENCRYPTION_KEY.into()),
+            webauthn_rp_id: None,
+            webauthn_rp_origin: None,
+            new_network_login_email: true,
+            new_network_require_totp: false,
             require_e2ee_setup: false,
             block_check_fail_open: false,
             cors_allowed_origins: vec!["*".to_string()],
@@ -494,8 +1196,16 @@ impl Config {
             smtp_password: None,
             smtp_from: None,
             smtp_tls: "starttls".into(),
+            email_backend: "smtp".into(),
+            password_reset_requires_verified_email: false,
             enable_api_docs: true,
             enable_guild_discovery: true,
+            discovery_report_threshold: 5,
+            // Tests must not make outbound requests
+            enable_link_previews: false,
+            push_vapid_private_key: None,
+            push_vapid_subject: None,
+            push_fcm_service_account: None,
             max_guilds_per_user: 100,
             max_members_per_guild: 1000,
             max_channels_per_guild: 200,
@@ -507,12 +1217,28 @@ impl Config {
             max_entries_per_workspace: 50,
             max_pages_per_guild: 10,
             max_revisions_per_page: 25,
+            perk_tier_thresholds: vec![2, 7, 14],
+            perk_emoji_slots_per_tier: 50,
+            perk_upload_size_per_tier: 25 * 1024 * 1024,
+            perk_voice_bitrates: vec![64_000, 128_000, 256_000, 384_000],
+            billing_webhook_secret: None,
+            deleted_user_content: DeletedUserContent::default(),
+            node_id: format!("test-node-{}", uuid::Uuid::new_v4().simple()),
+            node_region: None,
+            node_public_address: None,
+            sfu_remote_nodes: Vec::new(),
+            sfu_control_token: None,
+            sfu_control_bind_address: "0.0.0.0:50051".into(),
+            route_rate_limits: RoutePolicyConfig::default(),
             observability: ObservabilityConfig {
                 enabled: false,
                 otlp_endpoint: "http://localhost:4317".into(),
                 service_name: "vc-server".into(),
                 trace_sample_ratio: 0.1,
                 log_level: "vc_server=info".into(),
+                telemetry_storage: TelemetryStorageBackend::Postgres,
+                clickhouse: None,
+                prometheus: None,
             },
             environment: "test".into(),
             grafana_url: None,
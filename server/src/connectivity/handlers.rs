@@ -127,6 +127,8 @@ pub struct SessionSummary {
     pub avg_jitter: Option<i16>,
     /// Worst quality score observed (0=poor, 1=fair, 2=good, 3=excellent).
     pub worst_quality: Option<i16>,
+    /// Whether the client noise-suppressed its microphone (null if not reported).
+    pub noise_suppression: Option<bool>,
 }
 
 /// Paginated session list response.
@@ -168,6 +170,12 @@ pub struct MetricPoint {
     pub jitter_ms: i16,
     /// Quality score (0=poor, 1=fair, 2=good, 3=excellent).
     pub quality: i16,
+    /// Concealment events on received audio (summed per bucket when downsampled).
+    pub concealment_events: Option<i64>,
+    /// Jitter-buffer delay of received audio in milliseconds.
+    pub jitter_buffer_ms: Option<i16>,
+    /// Outgoing audio level (0.0 - 1.0).
+    pub audio_level: Option<f32>,
 }
 
 // ============================================================================
@@ -311,7 +319,8 @@ pub async fn get_sessions(
             s.avg_latency,
             s.avg_loss,
             s.avg_jitter,
-            s.worst_quality
+            s.worst_quality,
+            s.noise_suppression
         FROM connection_sessions s
         LEFT JOIN channels c ON c.id = s.channel_id
         LEFT JOIN guilds g ON g.id = s.guild_id
@@ -368,7 +377,8 @@ pub async fn get_session_detail(
             s.avg_latency,
             s.avg_loss,
             s.avg_jitter,
-            s.worst_quality
+            s.worst_quality,
+            s.noise_suppression
         FROM connection_sessions s
         LEFT JOIN channels c ON c.id = s.channel_id
         LEFT JOIN guilds g ON g.id = s.guild_id
@@ -404,7 +414,10 @@ pub async fn get_session_detail(
                 AVG(latency_ms)::SMALLINT AS latency_ms,
                 AVG(packet_loss)::REAL AS packet_loss,
                 AVG(jitter_ms)::SMALLINT AS jitter_ms,
-                MIN(quality)::SMALLINT AS quality
+                MIN(quality)::SMALLINT AS quality,
+                SUM(concealment_events)::BIGINT AS concealment_events,
+                AVG(jitter_buffer_ms)::SMALLINT AS jitter_buffer_ms,
+                AVG(audio_level)::REAL AS audio_level
             FROM connection_metrics
             WHERE session_id = $2
             GROUP BY time_bucket($1::INTERVAL, time)
@@ -424,7 +437,10 @@ pub async fn get_session_detail(
                 latency_ms,
                 packet_loss,
                 jitter_ms,
-                quality
+                quality,
+                concealment_events,
+                jitter_buffer_ms,
+                audio_level
             FROM connection_metrics
             WHERE session_id = $1
             ORDER BY time ASC
@@ -18,7 +18,7 @@
 //!   - Called from: `server/src/discovery/handlers.rs` (discovery join path)
 //! - 55 = `channel_create` (per-guild channel creation limit)
 //!   - Called from: `server/src/chat/channels.rs`
-//! - 57 = `role_create` (per-guild role creation limit)
+//! - 57 = `role_create` (per-guild role creation limit and role reordering)
 //!   - Called from: `server/src/guild/roles.rs`
 //! - 59 = `emoji_create` (per-guild emoji creation limit, COUNT + INSERT only)
 //!   - Called from: `server/src/guild/emojis.rs`
@@ -16,14 +16,18 @@ pub struct User {
     pub display_name: String,
     /// Email address (optional).
     pub email: Option<String>,
-    /// Argon2id password hash (for local auth).
+    /// Argon2id password hash (set when the password sign-in method is linked).
     pub password_hash: Option<String>,
-    /// Authentication method (local or OIDC).
-    pub auth_method: AuthMethod,
-    /// External ID from OIDC provider.
-    pub external_id: Option<String>,
     /// Avatar image URL.
     pub avatar_url: Option<String>,
+    /// Profile banner image URL.
+    pub banner_url: Option<String>,
+    /// About-me text (markdown, raw HTML stripped).
+    pub about_me: Option<String>,
+    /// Pronouns shown on the profile.
+    pub pronouns: Option<String>,
+    /// Profile accent colour (`#rrggbb`).
+    pub accent_color: Option<String>,
     /// Current online status.
     pub status: UserStatus,
     /// Encrypted MFA secret for TOTP.
@@ -36,6 +40,16 @@ pub struct User {
     pub deletion_requested_at: Option<DateTime<Utc>>,
     /// When the account is scheduled to be hard-deleted.
     pub deletion_scheduled_at: Option<DateTime<Utc>>,
+    /// When a system admin suspended the account.
+    pub suspended_at: Option<DateTime<Utc>>,
+    /// Reason given for the suspension.
+    pub suspension_reason: Option<String>,
+    /// Access tokens issued at or before this time are rejected.
+    pub tokens_revoked_at: Option<DateTime<Utc>>,
+    /// When the account registered, if it still waits for admin approval.
+    pub pending_approval_at: Option<DateTime<Utc>>,
+    /// When the user confirmed the address in `email` (None if unverified).
+    pub email_verified_at: Option<DateTime<Utc>>,
     /// When the user was created.
     pub created_at: DateTime<Utc>,
     /// When the user was last updated.
@@ -45,11 +59,35 @@ pub struct User {
 /// Authentication method.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, sqlx::Type, utoipa::ToSchema)]
 #[sqlx(type_name = "auth_method", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
 pub enum AuthMethod {
     /// Local password authentication.
     Local,
     /// `OpenID` Connect authentication.
     Oidc,
+    /// LDAP directory authentication (reserved, no provider support yet).
+    Ldap,
+}
+
+/// An external sign-in identity linked to a user account.
+///
+/// The password method is not stored here; it is linked while
+/// `users.password_hash` is set.
+#[derive(Debug, Clone, FromRow, Serialize, utoipa::ToSchema)]
+pub struct UserAuthMethod {
+    /// Identity ID.
+    pub id: Uuid,
+    /// Method the identity signs in with.
+    pub method: AuthMethod,
+    /// Provider slug (e.g. the OIDC provider).
+    pub provider: String,
+    /// Provider-qualified subject: `"{provider}:{subject}"`.
+    #[serde(skip_serializing)]
+    pub external_id: String,
+    /// When the identity was linked.
+    pub created_at: DateTime<Utc>,
+    /// When the identity was last used to sign in.
+    pub last_used_at: Option<DateTime<Utc>>,
 }
 
 /// User online status.
@@ -57,15 +95,44 @@ pub enum AuthMethod {
 #[sqlx(type_name = "user_status", rename_all = "lowercase")]
 pub enum UserStatus {
     /// User is actively using the app.
+    #[serde(alias = "online")]
     Online,
     /// User is idle.
+    #[serde(alias = "away")]
     Away,
     /// User is busy (do not disturb).
+    #[serde(alias = "busy")]
     Busy,
     /// User is offline.
+    #[serde(alias = "offline")]
     Offline,
 }
 
+impl UserStatus {
+    /// Database and wire name (`"online"`, `"away"`, `"busy"`, `"offline"`).
+    #[must_use]
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Online => "online",
+            Self::Away => "away",
+            Self::Busy => "busy",
+            Self::Offline => "offline",
+        }
+    }
+}
+
+/// A past username change, kept for the change cooldown, the old-name
+/// mention redirect window and the admin audit trail.
+#[derive(Debug, Clone, FromRow, Serialize, utoipa::ToSchema)]
+pub struct UsernameChange {
+    /// Username before the change.
+    pub old_username: String,
+    /// Username after the change.
+    pub new_username: String,
+    /// When the change was made.
+    pub changed_at: DateTime<Utc>,
+}
+
 /// Default value for `max_screen_shares` field.
 const fn default_max_screen_shares() -> i32 {
     1
@@ -95,10 +162,19 @@ pub struct Channel {
     /// Maximum concurrent screen shares (voice channels only).
     #[serde(default = "default_max_screen_shares")]
     pub max_screen_shares: i32,
+    /// Days messages are kept (the channel's own setting, else the guild
+    /// default). `None` keeps messages forever.
+    #[sqlx(default)]
+    #[serde(default)]
+    pub retention_days: Option<i32>,
     /// When the channel was created.
     pub created_at: DateTime<Utc>,
     /// When the channel was last updated.
     pub updated_at: DateTime<Utc>,
+    /// Bumped by every channel update, for detecting conflicting edits.
+    #[sqlx(default)]
+    #[serde(default)]
+    pub version: i32,
 }
 
 /// Channel type.
@@ -110,10 +186,43 @@ pub enum ChannelType {
     Text,
     /// Voice channel.
     Voice,
+    /// Voice channel where only speakers publish audio.
+    Stage,
     /// Direct message channel.
     Dm,
 }
 
+impl ChannelType {
+    /// Whether members connect to the channel through the SFU.
+    pub const fn is_voice(&self) -> bool {
+        matches!(self, Self::Voice | Self::Stage)
+    }
+}
+
+/// Message kind (user content or a server-generated system message).
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, sqlx::Type, utoipa::ToSchema,
+)]
+#[sqlx(type_name = "message_type", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum MessageType {
+    /// Regular user message.
+    #[default]
+    Default,
+    /// A member joined the guild.
+    SystemJoin,
+    /// A message was pinned.
+    SystemPin,
+    /// A DM call started or ended.
+    SystemCall,
+    /// The guild gained or lost supporter perks.
+    SystemBoost,
+    /// Voice channel join/leave activity.
+    SystemVoice,
+    /// An automod escalation policy acted on a member.
+    SystemAutomod,
+}
+
 /// Message model.
 #[derive(Debug, Clone, FromRow, Serialize, Deserialize, utoipa::ToSchema)]
 pub struct Message {
@@ -144,6 +253,69 @@ pub struct Message {
     pub deleted_at: Option<DateTime<Utc>>,
     /// When the message was created.
     pub created_at: DateTime<Utc>,
+    /// Message kind.
+    #[sqlx(default)]
+    #[serde(default)]
+    pub message_type: MessageType,
+    /// Link previews unfurled from URLs in the content.
+    #[sqlx(default)]
+    #[serde(default)]
+    #[schema(value_type = Vec<LinkEmbed>)]
+    pub embeds: sqlx::types::Json<Vec<LinkEmbed>>,
+    /// Incoming webhook that posted the message (`None` once it is deleted).
+    #[sqlx(default)]
+    #[serde(default)]
+    pub webhook_id: Option<Uuid>,
+    /// Author name for webhook messages; set whenever a webhook posted it.
+    #[sqlx(default)]
+    #[serde(default)]
+    pub webhook_name: Option<String>,
+    /// Author avatar for webhook messages.
+    #[sqlx(default)]
+    #[serde(default)]
+    pub webhook_avatar_url: Option<String>,
+    /// Catalog reference for system messages, used to render `content` in the
+    /// reader's locale.
+    #[sqlx(default)]
+    #[serde(default)]
+    #[schema(value_type = Option<crate::i18n::LocalizedText>)]
+    pub system_text: Option<sqlx::types::Json<crate::i18n::LocalizedText>>,
+    /// Tombstone standing in for the author after their account was deleted.
+    #[sqlx(default)]
+    #[serde(default)]
+    pub author_tombstone_id: Option<Uuid>,
+    /// Message this one was forwarded from (`None` once it is deleted).
+    #[sqlx(default)]
+    #[serde(default)]
+    pub forwarded_from_message_id: Option<Uuid>,
+    /// Channel of the forwarded message.
+    #[sqlx(default)]
+    #[serde(default)]
+    pub forwarded_from_channel_id: Option<Uuid>,
+    /// Author of the forwarded message.
+    #[sqlx(default)]
+    #[serde(default)]
+    pub forwarded_from_author_id: Option<Uuid>,
+    /// When the forwarded message was originally sent; set on every forward.
+    #[sqlx(default)]
+    #[serde(default)]
+    pub forwarded_from_created_at: Option<DateTime<Utc>>,
+}
+
+/// Link preview built from a URL's `OpenGraph` metadata.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct LinkEmbed {
+    /// URL as it appears in the message.
+    pub url: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub site_name: Option<String>,
+    /// Absolute preview image URL.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub image_url: Option<String>,
 }
 
 /// Role model.
@@ -184,6 +356,8 @@ pub struct FileAttachment {
     pub id: Uuid,
     /// Message this attachment belongs to.
     pub message_id: Uuid,
+    /// Channel of the parent message (filled by a trigger on insert).
+    pub channel_id: Uuid,
     /// Original filename.
     pub filename: String,
     /// MIME type (e.g., image/png).
@@ -206,6 +380,96 @@ pub struct FileAttachment {
     pub medium_s3_key: Option<String>,
     /// Processing status: pending, processed, failed, skipped.
     pub processing_status: String,
+    /// Malware scan status: pending, clean, quarantined, failed, released, skipped.
+    pub scan_status: String,
+    /// Signature reported by the scanner (if quarantined).
+    pub scan_signature: Option<String>,
+    /// When the scan finished.
+    pub scanned_at: Option<DateTime<Utc>>,
+}
+
+impl FileAttachment {
+    /// Whether the attachment is held back from other users by the scanner.
+    #[must_use]
+    pub fn is_scan_held(&self) -> bool {
+        matches!(
+            self.scan_status.as_str(),
+            "pending" | "quarantined" | "failed"
+        )
+    }
+}
+
+/// Coarse attachment category, derived from the MIME type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum AttachmentKind {
+    Image,
+    Video,
+    Audio,
+    /// Anything that is not image, video or audio.
+    File,
+}
+
+impl AttachmentKind {
+    /// Classify a MIME type.
+    pub fn from_mime(mime_type: &str) -> Self {
+        match mime_type.split('/').next() {
+            Some("image") => Self::Image,
+            Some("video") => Self::Video,
+            Some("audio") => Self::Audio,
+            _ => Self::File,
+        }
+    }
+
+    /// MIME type prefix for this kind (`None` for [`Self::File`]).
+    pub const fn mime_prefix(self) -> Option<&'static str> {
+        match self {
+            Self::Image => Some("image/"),
+            Self::Video => Some("video/"),
+            Self::Audio => Some("audio/"),
+            Self::File => None,
+        }
+    }
+}
+
+/// Read-only feed token for a channel's JSON Feed / Atom export.
+///
+/// The token itself is never stored; only its SHA256 hash.
+#[derive(Debug, Clone, FromRow, Serialize, utoipa::ToSchema)]
+pub struct ChannelFeedToken {
+    /// Token ID.
+    pub id: Uuid,
+    /// Channel the token grants read access to.
+    pub channel_id: Uuid,
+    /// User who created the token; the feed follows their channel access.
+    pub created_by: Uuid,
+    /// Label shown in the token list.
+    pub name: String,
+    /// When the feed was last fetched with this token.
+    pub last_used_at: Option<DateTime<Utc>>,
+    /// When the token was created.
+    pub created_at: DateTime<Utc>,
+}
+
+/// Incoming webhook that external services post messages to a channel with.
+///
+/// The token itself is never stored; only its SHA256 hash.
+#[derive(Debug, Clone, FromRow, Serialize, utoipa::ToSchema)]
+pub struct ChannelIncomingWebhook {
+    /// Webhook ID.
+    pub id: Uuid,
+    /// Channel the webhook posts to.
+    pub channel_id: Uuid,
+    /// User who created the webhook; posting follows their channel access.
+    pub created_by: Uuid,
+    /// Default author name for posted messages.
+    pub name: String,
+    /// Default author avatar for posted messages.
+    pub avatar_url: Option<String>,
+    /// When a message was last posted through the webhook.
+    pub last_used_at: Option<DateTime<Utc>>,
+    /// When the webhook was created.
+    pub created_at: DateTime<Utc>,
 }
 
 /// Session model for refresh token tracking.
@@ -259,6 +523,25 @@ pub struct PasswordResetToken {
     pub created_at: DateTime<Utc>,
 }
 
+/// Email verification token model.
+#[derive(Debug, Clone, FromRow)]
+pub struct EmailVerificationToken {
+    /// Token ID.
+    pub id: Uuid,
+    /// User this token belongs to.
+    pub user_id: Uuid,
+    /// Address the token was sent to.
+    pub email: String,
+    /// SHA256 hash of the verification token.
+    pub token_hash: String,
+    /// When the token expires.
+    pub expires_at: DateTime<Utc>,
+    /// When the token was used (None if unused).
+    pub used_at: Option<DateTime<Utc>>,
+    /// When the token was created.
+    pub created_at: DateTime<Utc>,
+}
+
 /// OIDC/OAuth2 provider configuration stored in the database.
 #[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
 pub struct OidcProviderRow {
@@ -296,6 +579,12 @@ pub struct OidcProviderRow {
     pub updated_at: DateTime<Utc>,
     /// User who created the provider.
     pub created_by: Option<Uuid>,
+    /// Account creation policy for unlinked identities: `registration_policy`,
+    /// `always` or `never`.
+    pub provisioning: String,
+    /// Whether unlinked identities attach to the account with the same
+    /// verified email.
+    pub link_by_email: bool,
 }
 
 /// Public-facing OIDC provider info (no secrets).
@@ -385,6 +674,45 @@ pub struct DataExportJob {
     pub completed_at: Option<DateTime<Utc>>,
 }
 
+/// Channel transcript export job model.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct ChannelExportJob {
+    /// Unique job ID.
+    pub id: Uuid,
+    /// Exported channel (`None` once the channel is deleted).
+    pub channel_id: Option<Uuid>,
+    /// User who requested the export.
+    pub requested_by: Option<Uuid>,
+    /// Transcript format: jsonl, html.
+    pub format: String,
+    /// Attachment handling: links, bundled.
+    pub attachments: String,
+    /// Only messages sent at or after this time.
+    pub range_from: Option<DateTime<Utc>>,
+    /// Only messages sent before this time.
+    pub range_to: Option<DateTime<Utc>>,
+    /// Job status: pending, processing, completed, failed, expired.
+    pub status: String,
+    /// Messages in range, counted when processing starts.
+    pub messages_total: Option<i64>,
+    /// Messages written to the transcript so far.
+    pub messages_exported: i64,
+    /// S3 object key for the export archive.
+    pub s3_key: Option<String>,
+    /// Export archive size in bytes.
+    pub file_size_bytes: Option<i64>,
+    /// Error message if the job failed.
+    pub error_message: Option<String>,
+    /// When the export download expires.
+    pub expires_at: Option<DateTime<Utc>>,
+    /// When the job was created.
+    pub created_at: DateTime<Utc>,
+    /// Last progress update.
+    pub updated_at: DateTime<Utc>,
+    /// When the job completed.
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
 /// Bot application model.
 #[derive(Debug, Clone, FromRow, Serialize, Deserialize, utoipa::ToSchema)]
 pub struct BotApplication {
@@ -12,10 +12,12 @@ use tracing::error;
 use uuid::Uuid;
 
 use super::models::{
-    AuthMethodsConfig, Channel, ChannelMember, ChannelType, ChannelUnread, FileAttachment,
-    GuildUnreadSummary, Message, MfaBackupCode, OidcProviderRow, PasswordResetToken, Session,
-    UnreadAggregate, User,
+    AttachmentKind, AuthMethodsConfig, Channel, ChannelFeedToken, ChannelIncomingWebhook,
+    ChannelMember, ChannelType, ChannelUnread, EmailVerificationToken, FileAttachment,
+    GuildUnreadSummary, LinkEmbed, Message, MessageType, MfaBackupCode, OidcProviderRow,
+    PasswordResetToken, Session, UnreadAggregate, User, UserAuthMethod, UsernameChange,
 };
+use crate::i18n::LocalizedText;
 
 /// Log and return a database error with context.
 ///
@@ -52,16 +54,47 @@ pub async fn find_user_by_username(pool: &PgPool, username: &str) -> sqlx::Resul
         .map_err(db_error!("find_user_by_username", username = %username))
 }
 
-/// Find user by external ID (for OIDC).
+/// Find the user a linked external identity belongs to (for OIDC).
 pub async fn find_user_by_external_id(
     pool: &PgPool,
     external_id: &str,
 ) -> sqlx::Result<Option<User>> {
-    sqlx::query_as::<_, User>("SELECT * FROM users WHERE external_id = $1")
+    sqlx::query_as::<_, User>(
+        "SELECT u.* FROM users u
+         JOIN user_auth_methods m ON m.user_id = u.id
+         WHERE m.external_id = $1",
+    )
+    .bind(external_id)
+    .fetch_optional(pool)
+    .await
+    .map_err(db_error!("find_user_by_external_id", external_id = %external_id))
+}
+
+/// List the external identities linked to a user, oldest first.
+pub async fn list_user_auth_methods(
+    pool: &PgPool,
+    user_id: Uuid,
+) -> sqlx::Result<Vec<UserAuthMethod>> {
+    sqlx::query_as::<_, UserAuthMethod>(
+        "SELECT id, method, provider, external_id, created_at, last_used_at
+         FROM user_auth_methods
+         WHERE user_id = $1
+         ORDER BY created_at",
+    )
+    .bind(user_id)
+    .fetch_all(pool)
+    .await
+    .map_err(db_error!("list_user_auth_methods", user_id = %user_id))
+}
+
+/// Record a sign-in through a linked external identity.
+pub async fn touch_user_auth_method(pool: &PgPool, external_id: &str) -> sqlx::Result<()> {
+    sqlx::query("UPDATE user_auth_methods SET last_used_at = NOW() WHERE external_id = $1")
         .bind(external_id)
-        .fetch_optional(pool)
+        .execute(pool)
         .await
-        .map_err(db_error!("find_user_by_external_id", external_id = %external_id))
+        .map_err(db_error!("touch_user_auth_method", external_id = %external_id))?;
+    Ok(())
 }
 
 /// Find user by email.
@@ -95,6 +128,48 @@ pub async fn username_exists(pool: &PgPool, username: &str) -> sqlx::Result<bool
     Ok(result.0)
 }
 
+/// Check if `username` is held for another user.
+///
+/// A username released by a rename stays held for its previous owner for
+/// `hold_days` days, so @mentions of the old name keep reaching them.
+pub async fn username_held(
+    pool: &PgPool,
+    username: &str,
+    exclude_user_id: Option<Uuid>,
+    hold_days: i32,
+) -> sqlx::Result<bool> {
+    sqlx::query_scalar(
+        "SELECT EXISTS(
+            SELECT 1 FROM username_history
+            WHERE old_username = $1
+              AND ($2::uuid IS NULL OR user_id <> $2)
+              AND changed_at > NOW() - make_interval(days => $3)
+        )",
+    )
+    .bind(username)
+    .bind(exclude_user_id)
+    .bind(hold_days)
+    .fetch_one(pool)
+    .await
+    .map_err(db_error!("username_held", username = %username))
+}
+
+/// List a user's username changes, newest first.
+pub async fn list_username_history(
+    pool: &PgPool,
+    user_id: Uuid,
+) -> sqlx::Result<Vec<UsernameChange>> {
+    sqlx::query_as::<_, UsernameChange>(
+        "SELECT old_username, new_username, changed_at FROM username_history
+         WHERE user_id = $1
+         ORDER BY changed_at DESC",
+    )
+    .bind(user_id)
+    .fetch_all(pool)
+    .await
+    .map_err(db_error!("list_username_history", user_id = %user_id))
+}
+
 /// Check if email exists.
 pub async fn email_exists(pool: &PgPool, email: &str) -> sqlx::Result<bool> {
     let result: (bool,) = sqlx::query_as("SELECT EXISTS(SELECT 1 FROM users WHERE email = $1)")
@@ -115,8 +190,8 @@ pub async fn create_user(
 ) -> sqlx::Result<User> {
     sqlx::query_as::<_, User>(
         r"
-        INSERT INTO users (username, display_name, email, password_hash, auth_method)
-        VALUES ($1, $2, $3, $4, 'local')
+        INSERT INTO users (username, display_name, email, password_hash)
+        VALUES ($1, $2, $3, $4)
         RETURNING *
         ",
     )
@@ -151,6 +226,8 @@ pub async fn update_user_profile(
     user_id: Uuid,
     display_name: Option<&str>,
     email: Option<Option<&str>>, // Some(Some(email)) = set, Some(None) = clear, None = no change
+    status_message: Option<Option<&str>>, // Same semantics as email
+    name_pronunciation: Option<Option<&str>>, // Same semantics as email
 ) -> sqlx::Result<User> {
     let mut builder = QueryBuilder::new("UPDATE users SET updated_at = NOW()");
 
@@ -158,8 +235,21 @@ pub async fn update_user_profile(
         builder.push(", display_name = ").push_bind(name);
     }
     if let Some(mail) = email {
+        // A new address has to be verified again
+        builder
+            .push(", email_verified_at = CASE WHEN email IS NOT DISTINCT FROM ")
+            .push_bind(mail)
+            .push(" THEN email_verified_at END");
         builder.push(", email = ").push_bind(mail);
     }
+    if let Some(message) = status_message {
+        builder.push(", status_message = ").push_bind(message);
+    }
+    if let Some(pronunciation) = name_pronunciation {
+        builder
+            .push(", name_pronunciation = ")
+            .push_bind(pronunciation);
+    }
 
     builder
         .push(" WHERE id = ")
@@ -406,6 +496,102 @@ pub async fn cleanup_expired_reset_tokens(pool: &PgPool) -> sqlx::Result<u64> {
     Ok(result.rows_affected())
 }
 
+// ============================================================================
+// Email Verification Token Queries
+// ============================================================================
+
+/// Create an email verification token for the address it is sent to.
+pub async fn create_email_verification_token(
+    pool: &PgPool,
+    user_id: Uuid,
+    email: &str,
+    token_hash: &str,
+    expires_at: DateTime<Utc>,
+) -> sqlx::Result<EmailVerificationToken> {
+    sqlx::query_as::<_, EmailVerificationToken>(
+        r"
+        INSERT INTO email_verification_tokens (user_id, email, token_hash, expires_at)
+        VALUES ($1, $2, $3, $4)
+        RETURNING *
+        ",
+    )
+    .bind(user_id)
+    .bind(email)
+    .bind(token_hash)
+    .bind(expires_at)
+    .fetch_one(pool)
+    .await
+    .map_err(db_error!("create_email_verification_token", user_id = %user_id))
+}
+
+/// Verify the email of the account a valid token belongs to.
+///
+/// Marks the token used and sets `email_verified_at`, but only while the
+/// account still has the address the token was sent to. Returns the user ID,
+/// or `None` if the token is unknown, used, expired or for an old address.
+pub async fn consume_email_verification_token(
+    pool: &PgPool,
+    token_hash: &str,
+) -> sqlx::Result<Option<Uuid>> {
+    sqlx::query_scalar(
+        r"
+        WITH token AS (
+            UPDATE email_verification_tokens t
+            SET used_at = NOW()
+            FROM users u
+            WHERE t.token_hash = $1
+              AND t.used_at IS NULL
+              AND t.expires_at > NOW()
+              AND u.id = t.user_id
+              AND u.email = t.email
+            RETURNING t.user_id
+        )
+        UPDATE users
+        SET email_verified_at = NOW(), updated_at = NOW()
+        WHERE id = (SELECT user_id FROM token)
+        RETURNING id
+        ",
+    )
+    .bind(token_hash)
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| {
+        error!(query = "consume_email_verification_token", error = %e, "Database query failed");
+        e
+    })
+}
+
+/// Invalidate all unused email verification tokens for a user.
+pub async fn invalidate_email_verification_tokens(
+    pool: &PgPool,
+    user_id: Uuid,
+) -> sqlx::Result<u64> {
+    let result = sqlx::query(
+        "UPDATE email_verification_tokens SET used_at = NOW() WHERE user_id = $1 AND used_at IS NULL",
+    )
+    .bind(user_id)
+    .execute(pool)
+    .await
+    .map_err(db_error!("invalidate_email_verification_tokens", user_id = %user_id))?;
+    Ok(result.rows_affected())
+}
+
+/// Clean up expired email verification tokens (for background job).
+///
+/// Removes tokens that expired more than 24 hours ago.
+pub async fn cleanup_expired_email_verification_tokens(pool: &PgPool) -> sqlx::Result<u64> {
+    let result = sqlx::query(
+        "DELETE FROM email_verification_tokens WHERE expires_at < NOW() - INTERVAL '24 hours'",
+    )
+    .execute(pool)
+    .await
+    .map_err(|e| {
+        error!(query = "cleanup_expired_email_verification_tokens", error = %e, "Database query failed");
+        e
+    })?;
+    Ok(result.rows_affected())
+}
+
 // ============================================================================
 // MFA Backup Code Queries
 // ============================================================================
@@ -514,7 +700,9 @@ pub async fn delete_mfa_backup_codes(pool: &PgPool, user_id: Uuid) -> sqlx::Resu
 pub async fn find_channel_by_id(pool: &PgPool, id: Uuid) -> sqlx::Result<Option<Channel>> {
     sqlx::query_as::<_, Channel>(
         r"
-        SELECT id, name, channel_type, category_id, guild_id, topic, icon_url, user_limit, position, max_screen_shares, created_at, updated_at
+        SELECT id, name, channel_type, category_id, guild_id, topic, icon_url, user_limit, position, max_screen_shares,
+               COALESCE(retention_days, (SELECT g.retention_days FROM guilds g WHERE g.id = channels.guild_id)) AS retention_days,
+               created_at, updated_at, version
         FROM channels
         WHERE id = $1
         ",
@@ -553,9 +741,11 @@ pub async fn create_channel(
 
     sqlx::query_as::<_, Channel>(
         r"
-        INSERT INTO channels (name, channel_type, category_id, guild_id, topic, icon_url, user_limit, position)
-        VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
-        RETURNING id, name, channel_type, category_id, guild_id, topic, icon_url, user_limit, position, max_screen_shares, created_at, updated_at
+        INSERT INTO channels (name, channel_type, category_id, guild_id, topic, icon_url, user_limit, position, retention_days)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+        RETURNING id, name, channel_type, category_id, guild_id, topic, icon_url, user_limit, position, max_screen_shares,
+               COALESCE(retention_days, (SELECT g.retention_days FROM guilds g WHERE g.id = channels.guild_id)) AS retention_days,
+               created_at, updated_at, version
         ",
     )
     .bind(params.name)
@@ -566,11 +756,16 @@ pub async fn create_channel(
     .bind(params.icon_url)
     .bind(params.user_limit)
     .bind(position)
+    .bind(crate::guild::retention::default_channel_retention(params.channel_type))
     .fetch_one(pool)
     .await
 }
 
 /// Update a channel.
+///
+/// With `expected_version`, the update only applies if the channel is still
+/// at that version; `None` is returned otherwise.
+#[allow(clippy::too_many_arguments)]
 pub async fn update_channel(
     pool: &PgPool,
     id: Uuid,
@@ -579,6 +774,7 @@ pub async fn update_channel(
     icon_url: Option<&str>,
     user_limit: Option<i32>,
     position: Option<i32>,
+    expected_version: Option<i32>,
 ) -> sqlx::Result<Option<Channel>> {
     sqlx::query_as::<_, Channel>(
         r"
@@ -588,9 +784,12 @@ pub async fn update_channel(
             icon_url = COALESCE($4, icon_url),
             user_limit = COALESCE($5, user_limit),
             position = COALESCE($6, position),
-            updated_at = NOW()
-        WHERE id = $1
-        RETURNING id, name, channel_type, category_id, guild_id, topic, icon_url, user_limit, position, max_screen_shares, created_at, updated_at
+            updated_at = NOW(),
+            version = version + 1
+        WHERE id = $1 AND ($7::int IS NULL OR version = $7)
+        RETURNING id, name, channel_type, category_id, guild_id, topic, icon_url, user_limit, position, max_screen_shares,
+               COALESCE(retention_days, (SELECT g.retention_days FROM guilds g WHERE g.id = channels.guild_id)) AS retention_days,
+               created_at, updated_at, version
         ",
     )
     .bind(id)
@@ -599,6 +798,7 @@ pub async fn update_channel(
     .bind(icon_url)
     .bind(user_limit)
     .bind(position)
+    .bind(expected_version)
     .fetch_optional(pool)
     .await
 }
@@ -855,6 +1055,78 @@ pub async fn create_message(
     .await
 }
 
+/// Forward `original` into another channel as a new message by `user_id`.
+///
+/// The copy references the original's id, channel, author and send time.
+/// Forwarding a forwarded message references the first original.
+pub async fn create_forwarded_message(
+    pool: &PgPool,
+    channel_id: Uuid,
+    user_id: Uuid,
+    original: &Message,
+) -> sqlx::Result<Message> {
+    let (message_id, source_channel_id, author_id, created_at) =
+        match original.forwarded_from_created_at {
+            Some(created_at) => (
+                original.forwarded_from_message_id,
+                original.forwarded_from_channel_id,
+                original.forwarded_from_author_id,
+                created_at,
+            ),
+            None => (
+                Some(original.id),
+                Some(original.channel_id),
+                original.user_id,
+                original.created_at,
+            ),
+        };
+
+    sqlx::query_as::<_, Message>(
+        r"
+        INSERT INTO messages (
+            channel_id, user_id, content, forwarded_from_message_id,
+            forwarded_from_channel_id, forwarded_from_author_id, forwarded_from_created_at
+        )
+        VALUES ($1, $2, $3, $4, $5, $6, $7)
+        RETURNING *
+        ",
+    )
+    .bind(channel_id)
+    .bind(user_id)
+    .bind(&original.content)
+    .bind(message_id)
+    .bind(source_channel_id)
+    .bind(author_id)
+    .bind(created_at)
+    .fetch_one(pool)
+    .await
+}
+
+/// Create a server-generated system message.
+pub async fn create_system_message(
+    pool: &PgPool,
+    channel_id: Uuid,
+    user_id: Uuid,
+    message_type: MessageType,
+    content: &str,
+    system_text: &LocalizedText,
+) -> sqlx::Result<Message> {
+    sqlx::query_as::<_, Message>(
+        r"
+        INSERT INTO messages (channel_id, user_id, content, message_type, system_text)
+        VALUES ($1, $2, $3, $4, $5)
+        RETURNING *
+        ",
+    )
+    .bind(channel_id)
+    .bind(user_id)
+    .bind(content)
+    .bind(message_type)
+    .bind(sqlx::types::Json(system_text))
+    .fetch_one(pool)
+    .await
+}
+
 /// Update a message (edit).
 pub async fn update_message(
     pool: &PgPool,
@@ -866,7 +1138,7 @@ pub async fn update_message(
         r"
         UPDATE messages
         SET content = $3, edited_at = NOW()
-        WHERE id = $1 AND user_id = $2 AND deleted_at IS NULL
+        WHERE id = $1 AND user_id = $2 AND deleted_at IS NULL AND message_type = 'default'
         RETURNING *
         ",
     )
@@ -927,6 +1199,27 @@ pub async fn admin_delete_message(pool: &PgPool, id: Uuid) -> sqlx::Result<bool>
     Ok(deleted)
 }
 
+/// Store unfurled link previews on a message.
+///
+/// Only applies if the message still has `content` (an edit made while
+/// unfurling was running wins) and has not been deleted.
+pub async fn set_message_embeds(
+    pool: &PgPool,
+    message_id: Uuid,
+    content: &str,
+    embeds: &[LinkEmbed],
+) -> sqlx::Result<bool> {
+    let result = sqlx::query(
+        "UPDATE messages SET embeds = $3 WHERE id = $1 AND content = $2 AND deleted_at IS NULL",
+    )
+    .bind(message_id)
+    .bind(content)
+    .bind(sqlx::types::Json(embeds))
+    .execute(pool)
+    .await?;
+    Ok(result.rows_affected() > 0)
+}
+
 // ============================================================================
 // Thread Queries
 // ============================================================================
@@ -1306,6 +1599,10 @@ pub struct SearchMessageRow {
     pub created_at: DateTime<Utc>,
     pub rank: f32,
     pub headline: String,
+    pub webhook_id: Option<Uuid>,
+    pub webhook_name: Option<String>,
+    pub webhook_avatar_url: Option<String>,
+    pub author_tombstone_id: Option<Uuid>,
 }
 
 /// Search messages with advanced filters using dynamic SQL.
@@ -1327,6 +1624,7 @@ pub async fn search_messages_filtered(
 
     let mut builder = QueryBuilder::new(
         "SELECT m.id, m.channel_id, m.user_id, m.content, m.created_at, \
+         m.webhook_id, m.webhook_name, m.webhook_avatar_url, m.author_tombstone_id, \
          ts_rank(m.content_search, websearch_to_tsquery('english', ",
     );
     builder.push_bind(query);
@@ -1448,13 +1746,14 @@ pub async fn create_file_attachment(
     thumbnail_s3_key: Option<&str>,
     medium_s3_key: Option<&str>,
     processing_status: &str,
+    scan_status: &str,
 ) -> sqlx::Result<FileAttachment> {
     sqlx::query_as::<_, FileAttachment>(
         r"
         INSERT INTO file_attachments (message_id, filename, mime_type, size_bytes, s3_key,
                                       width, height, blurhash, thumbnail_s3_key, medium_s3_key,
-                                      processing_status)
-        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+                                      processing_status, scan_status)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
         RETURNING *
         ",
     )
@@ -1469,10 +1768,149 @@ pub async fn create_file_attachment(
     .bind(thumbnail_s3_key)
     .bind(medium_s3_key)
     .bind(processing_status)
+    .bind(scan_status)
     .fetch_one(pool)
     .await
 }
 
+/// Store the result of background image processing on an attachment.
+///
+/// Returns `None` if the attachment was deleted while processing ran.
+#[allow(clippy::too_many_arguments)]
+pub async fn update_file_attachment_media(
+    pool: &PgPool,
+    id: Uuid,
+    width: Option<i32>,
+    height: Option<i32>,
+    blurhash: Option<&str>,
+    thumbnail_s3_key: Option<&str>,
+    medium_s3_key: Option<&str>,
+    processing_status: &str,
+) -> sqlx::Result<Option<FileAttachment>> {
+    sqlx::query_as::<_, FileAttachment>(
+        r"
+        UPDATE file_attachments
+        SET width = COALESCE($2, width),
+            height = COALESCE($3, height),
+            blurhash = $4,
+            thumbnail_s3_key = $5,
+            medium_s3_key = $6,
+            processing_status = $7
+        WHERE id = $1
+        RETURNING *
+        ",
+    )
+    .bind(id)
+    .bind(width)
+    .bind(height)
+    .bind(blurhash)
+    .bind(thumbnail_s3_key)
+    .bind(medium_s3_key)
+    .bind(processing_status)
+    .fetch_optional(pool)
+    .await
+}
+
+/// Mark attachments stuck in `pending` as failed (for background job).
+///
+/// Processing runs in-process, so a restart mid-job leaves the row pending;
+/// clients fall back to the original file once it is marked failed.
+/// Attachments held by the scanner are skipped: their processing only starts
+/// once they are published.
+pub async fn fail_stale_attachment_processing(pool: &PgPool) -> sqlx::Result<u64> {
+    let result = sqlx::query(
+        "UPDATE file_attachments SET processing_status = 'failed' \
+         WHERE processing_status = 'pending' AND created_at < NOW() - INTERVAL '1 hour' \
+           AND scan_status NOT IN ('pending', 'quarantined', 'failed')",
+    )
+    .execute(pool)
+    .await
+    .map_err(|e| {
+        error!(query = "fail_stale_attachment_processing", error = %e, "Database query failed");
+        e
+    })?;
+    Ok(result.rows_affected())
+}
+
+/// Store the scanner's verdict on a `pending` attachment.
+///
+/// Returns `None` if the attachment was deleted while the scan ran.
+pub async fn update_file_attachment_scan(
+    pool: &PgPool,
+    id: Uuid,
+    scan_status: &str,
+    scan_signature: Option<&str>,
+) -> sqlx::Result<Option<FileAttachment>> {
+    sqlx::query_as::<_, FileAttachment>(
+        r"
+        UPDATE file_attachments
+        SET scan_status = $2, scan_signature = $3, scanned_at = NOW()
+        WHERE id = $1 AND scan_status = 'pending'
+        RETURNING *
+        ",
+    )
+    .bind(id)
+    .bind(scan_status)
+    .bind(scan_signature)
+    .fetch_optional(pool)
+    .await
+}
+
+/// Release a quarantined or failed attachment.
+///
+/// Returns `None` if the attachment does not exist or is not held.
+pub async fn release_file_attachment(
+    pool: &PgPool,
+    id: Uuid,
+) -> sqlx::Result<Option<FileAttachment>> {
+    sqlx::query_as::<_, FileAttachment>(
+        r"
+        UPDATE file_attachments
+        SET scan_status = 'released'
+        WHERE id = $1 AND scan_status IN ('quarantined', 'failed')
+        RETURNING *
+        ",
+    )
+    .bind(id)
+    .fetch_optional(pool)
+    .await
+}
+
+/// Mark attachment scans stuck in `pending` as failed (for background job).
+///
+/// Scans run in-process, so a restart mid-scan leaves the row pending; failed
+/// attachments stay hidden until a system admin releases them.
+pub async fn fail_stale_attachment_scans(pool: &PgPool) -> sqlx::Result<u64> {
+    let result = sqlx::query(
+        "UPDATE file_attachments SET scan_status = 'failed', scanned_at = NOW() \
+         WHERE scan_status = 'pending' AND created_at < NOW() - INTERVAL '1 hour'",
+    )
+    .execute(pool)
+    .await
+    .map_err(|e| {
+        error!(query = "fail_stale_attachment_scans", error = %e, "Database query failed");
+        e
+    })?;
+    Ok(result.rows_affected())
+}
+
+/// Messages among `message_ids` that carry an attachment held by the scanner.
+pub async fn list_scan_held_message_ids(
+    pool: &PgPool,
+    message_ids: &[Uuid],
+) -> sqlx::Result<Vec<Uuid>> {
+    if message_ids.is_empty() {
+        return Ok(vec![]);
+    }
+    sqlx::query_scalar(
+        "SELECT DISTINCT message_id FROM file_attachments \
+         WHERE message_id = ANY($1) AND scan_status IN ('pending', 'quarantined', 'failed')",
+    )
+    .bind(message_ids)
+    .fetch_all(pool)
+    .await
+}
+
 /// Find file attachment by ID.
 pub async fn find_file_attachment_by_id(
     pool: &PgPool,
@@ -1513,6 +1951,77 @@ pub async fn list_file_attachments_by_messages(
     .await
 }
 
+/// Attachment row for a channel's media gallery.
+#[derive(Debug, sqlx::FromRow)]
+pub struct ChannelAttachmentRow {
+    #[sqlx(flatten)]
+    pub attachment: FileAttachment,
+    /// Author of the parent message (`None` if the account was deleted).
+    pub uploader_id: Option<Uuid>,
+}
+
+/// List attachments in a channel, newest first.
+///
+/// Attachments on deleted messages, attachments held by the scanner and
+/// attachments from `excluded_users` are skipped.
+/// `before` is an attachment ID cursor, compared by `(created_at, id)`.
+pub async fn list_channel_attachments(
+    pool: &PgPool,
+    channel_id: Uuid,
+    before: Option<Uuid>,
+    kind: Option<AttachmentKind>,
+    excluded_users: &[Uuid],
+    limit: i64,
+) -> sqlx::Result<Vec<ChannelAttachmentRow>> {
+    let mut builder = QueryBuilder::new(
+        "SELECT fa.*, m.user_id AS uploader_id \
+         FROM file_attachments fa \
+         JOIN messages m ON m.id = fa.message_id \
+         WHERE fa.channel_id = ",
+    );
+    builder.push_bind(channel_id);
+    builder.push(
+        " AND m.deleted_at IS NULL \
+         AND fa.scan_status NOT IN ('pending', 'quarantined', 'failed')",
+    );
+
+    if let Some(before_id) = before {
+        builder
+            .push(" AND (fa.created_at, fa.id) < (SELECT created_at, id FROM file_attachments WHERE id = ")
+            .push_bind(before_id)
+            .push(")");
+    }
+    if let Some(kind) = kind {
+        if let Some(prefix) = kind.mime_prefix() {
+            builder
+                .push(" AND starts_with(fa.mime_type, ")
+                .push_bind(prefix)
+                .push(")");
+        } else {
+            builder.push(
+                " AND NOT (starts_with(fa.mime_type, 'image/') \
+                 OR starts_with(fa.mime_type, 'video/') \
+                 OR starts_with(fa.mime_type, 'audio/'))",
+            );
+        }
+    }
+    if !excluded_users.is_empty() {
+        builder
+            .push(" AND (m.user_id IS NULL OR m.user_id <> ALL(")
+            .push_bind(excluded_users)
+            .push("))");
+    }
+
+    builder
+        .push(" ORDER BY fa.created_at DESC, fa.id DESC LIMIT ")
+        .push_bind(limit);
+
+    builder
+        .build_query_as::<ChannelAttachmentRow>()
+        .fetch_all(pool)
+        .await
+}
+
 /// Delete file attachment by ID, returning the deleted record.
 pub async fn delete_file_attachment(
     pool: &PgPool,
@@ -1579,6 +2088,243 @@ pub async fn check_attachment_access(
     Ok(result.0)
 }
 
+// ============================================================================
+// Channel Feed Queries
+// ============================================================================
+
+/// List feed tokens for a channel, oldest first.
+pub async fn list_channel_feed_tokens(
+    pool: &PgPool,
+    channel_id: Uuid,
+) -> sqlx::Result<Vec<ChannelFeedToken>> {
+    sqlx::query_as::<_, ChannelFeedToken>(
+        r"
+        SELECT id, channel_id, created_by, name, last_used_at, created_at
+        FROM channel_feed_tokens
+        WHERE channel_id = $1
+        ORDER BY created_at ASC
+        ",
+    )
+    .bind(channel_id)
+    .fetch_all(pool)
+    .await
+}
+
+/// Create a feed token. `token_hash` is the SHA256 hash of the raw token.
+pub async fn create_channel_feed_token(
+    pool: &PgPool,
+    channel_id: Uuid,
+    created_by: Uuid,
+    name: &str,
+    token_hash: &str,
+) -> sqlx::Result<ChannelFeedToken> {
+    sqlx::query_as::<_, ChannelFeedToken>(
+        r"
+        INSERT INTO channel_feed_tokens (channel_id, created_by, name, token_hash)
+        VALUES ($1, $2, $3, $4)
+        RETURNING id, channel_id, created_by, name, last_used_at, created_at
+        ",
+    )
+    .bind(channel_id)
+    .bind(created_by)
+    .bind(name)
+    .bind(token_hash)
+    .fetch_one(pool)
+    .await
+}
+
+/// Delete a feed token. Returns `false` if it did not exist in this channel.
+pub async fn delete_channel_feed_token(
+    pool: &PgPool,
+    channel_id: Uuid,
+    token_id: Uuid,
+) -> sqlx::Result<bool> {
+    let result = sqlx::query("DELETE FROM channel_feed_tokens WHERE id = $1 AND channel_id = $2")
+        .bind(token_id)
+        .bind(channel_id)
+        .execute(pool)
+        .await?;
+    Ok(result.rows_affected() > 0)
+}
+
+/// Look up a feed token by hash and record its use.
+///
+/// Returns the token creator, or `None` if the hash does not match a token
+/// for this channel.
+pub async fn use_channel_feed_token(
+    pool: &PgPool,
+    channel_id: Uuid,
+    token_hash: &str,
+) -> sqlx::Result<Option<Uuid>> {
+    sqlx::query_scalar(
+        r"
+        UPDATE channel_feed_tokens
+        SET last_used_at = NOW()
+        WHERE token_hash = $1 AND channel_id = $2
+        RETURNING created_by
+        ",
+    )
+    .bind(token_hash)
+    .bind(channel_id)
+    .fetch_optional(pool)
+    .await
+}
+
+/// Message row for a channel feed.
+#[derive(Debug, sqlx::FromRow)]
+pub struct FeedMessageRow {
+    pub id: Uuid,
+    pub content: String,
+    /// Author display name, or the webhook name for webhook messages (`None`
+    /// if the account was deleted).
+    pub author_name: Option<String>,
+    pub edited_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// List the latest top-level user messages in a channel for its feed.
+///
+/// Skips deleted, encrypted, thread reply and system messages.
+pub async fn list_feed_messages(
+    pool: &PgPool,
+    channel_id: Uuid,
+    limit: i64,
+) -> sqlx::Result<Vec<FeedMessageRow>> {
+    sqlx::query_as::<_, FeedMessageRow>(
+        r"
+        SELECT m.id, m.content, COALESCE(m.webhook_name, u.display_name) AS author_name,
+               m.edited_at, m.created_at
+        FROM messages m
+        LEFT JOIN users u ON u.id = m.user_id
+        WHERE m.channel_id = $1
+          AND m.deleted_at IS NULL
+          AND m.encrypted = false
+          AND m.parent_id IS NULL
+          AND m.message_type = 'default'
+        ORDER BY m.created_at DESC, m.id DESC
+        LIMIT $2
+        ",
+    )
+    .bind(channel_id)
+    .bind(limit)
+    .fetch_all(pool)
+    .await
+}
+
+// ============================================================================
+// Incoming Webhook Queries
+// ============================================================================
+
+/// List incoming webhooks for a channel, oldest first.
+pub async fn list_channel_incoming_webhooks(
+    pool: &PgPool,
+    channel_id: Uuid,
+) -> sqlx::Result<Vec<ChannelIncomingWebhook>> {
+    sqlx::query_as::<_, ChannelIncomingWebhook>(
+        r"
+        SELECT id, channel_id, created_by, name, avatar_url, last_used_at, created_at
+        FROM channel_incoming_webhooks
+        WHERE channel_id = $1
+        ORDER BY created_at ASC
+        ",
+    )
+    .bind(channel_id)
+    .fetch_all(pool)
+    .await
+}
+
+/// Create an incoming webhook. `token_hash` is the SHA256 hash of the raw token.
+pub async fn create_channel_incoming_webhook(
+    pool: &PgPool,
+    channel_id: Uuid,
+    created_by: Uuid,
+    name: &str,
+    avatar_url: Option<&str>,
+    token_hash: &str,
+) -> sqlx::Result<ChannelIncomingWebhook> {
+    sqlx::query_as::<_, ChannelIncomingWebhook>(
+        r"
+        INSERT INTO channel_incoming_webhooks (channel_id, created_by, name, avatar_url, token_hash)
+        VALUES ($1, $2, $3, $4, $5)
+        RETURNING id, channel_id, created_by, name, avatar_url, last_used_at, created_at
+        ",
+    )
+    .bind(channel_id)
+    .bind(created_by)
+    .bind(name)
+    .bind(avatar_url)
+    .bind(token_hash)
+    .fetch_one(pool)
+    .await
+}
+
+/// Delete an incoming webhook. Returns `false` if it did not exist in this
+/// channel.
+pub async fn delete_channel_incoming_webhook(
+    pool: &PgPool,
+    channel_id: Uuid,
+    webhook_id: Uuid,
+) -> sqlx::Result<bool> {
+    let result =
+        sqlx::query("DELETE FROM channel_incoming_webhooks WHERE id = $1 AND channel_id = $2")
+            .bind(webhook_id)
+            .bind(channel_id)
+            .execute(pool)
+            .await?;
+    Ok(result.rows_affected() > 0)
+}
+
+/// Look up an incoming webhook by ID and token hash and record its use.
+///
+/// Returns `None` if the webhook does not exist in this channel or the hash
+/// does not match.
+pub async fn use_channel_incoming_webhook(
+    pool: &PgPool,
+    channel_id: Uuid,
+    webhook_id: Uuid,
+    token_hash: &str,
+) -> sqlx::Result<Option<ChannelIncomingWebhook>> {
+    sqlx::query_as::<_, ChannelIncomingWebhook>(
+        r"
+        UPDATE channel_incoming_webhooks
+        SET last_used_at = NOW()
+        WHERE id = $1 AND channel_id = $2 AND token_hash = $3
+        RETURNING id, channel_id, created_by, name, avatar_url, last_used_at, created_at
+        ",
+    )
+    .bind(webhook_id)
+    .bind(channel_id)
+    .bind(token_hash)
+    .fetch_optional(pool)
+    .await
+}
+
+/// Create a message posted through an incoming webhook. It has no user
+/// author; the webhook's name and avatar are stored on the message.
+pub async fn create_webhook_message(
+    pool: &PgPool,
+    webhook_id: Uuid,
+    channel_id: Uuid,
+    content: &str,
+    name: &str,
+    avatar_url: Option<&str>,
+) -> sqlx::Result<Message> {
+    sqlx::query_as::<_, Message>(
+        r"
+        INSERT INTO messages (channel_id, content, webhook_id, webhook_name, webhook_avatar_url)
+        VALUES ($1, $2, $3, $4, $5)
+        RETURNING *
+        ",
+    )
+    .bind(channel_id)
+    .bind(content)
+    .bind(webhook_id)
+    .bind(name)
+    .bind(avatar_url)
+    .fetch_one(pool)
+    .await
+}
+
 // ============================================================================
 // Guild Queries
 // ============================================================================
@@ -1596,11 +2342,55 @@ pub async fn is_guild_member(pool: &PgPool, guild_id: Uuid, user_id: Uuid) -> sq
     Ok(result.0)
 }
 
+/// Whether a system admin has suspended the guild. Nobody can post or join
+/// voice in a suspended guild.
+pub async fn is_guild_suspended(pool: &PgPool, guild_id: Uuid) -> sqlx::Result<bool> {
+    sqlx::query_scalar(
+        "SELECT EXISTS(SELECT 1 FROM guilds WHERE id = $1 AND suspended_at IS NOT NULL)",
+    )
+    .bind(guild_id)
+    .fetch_one(pool)
+    .await
+}
+
+/// Get the end of a member's active timeout, if any.
+///
+/// Expired timeouts that the sweeper has not cleared yet are ignored.
+pub async fn get_member_timeout(
+    pool: &PgPool,
+    guild_id: Uuid,
+    user_id: Uuid,
+) -> sqlx::Result<Option<DateTime<Utc>>> {
+    sqlx::query_scalar(
+        "SELECT timeout_until FROM guild_members
+         WHERE guild_id = $1 AND user_id = $2 AND timeout_until > NOW()",
+    )
+    .bind(guild_id)
+    .bind(user_id)
+    .fetch_optional(pool)
+    .await
+}
+
+/// Whether a member still has to complete the guild's onboarding (accept
+/// its rules) before posting.
+pub async fn is_member_pending(pool: &PgPool, guild_id: Uuid, user_id: Uuid) -> sqlx::Result<bool> {
+    let pending: Option<bool> = sqlx::query_scalar(
+        "SELECT pending FROM guild_members WHERE guild_id = $1 AND user_id = $2",
+    )
+    .bind(guild_id)
+    .bind(user_id)
+    .fetch_optional(pool)
+    .await?;
+    Ok(pending.unwrap_or(false))
+}
+
 /// Get channels for a guild.
 pub async fn get_guild_channels(pool: &PgPool, guild_id: Uuid) -> sqlx::Result<Vec<Channel>> {
     sqlx::query_as::<_, Channel>(
         r"
-        SELECT id, name, channel_type, category_id, guild_id, topic, icon_url, user_limit, position, max_screen_shares, created_at, updated_at
+        SELECT id, name, channel_type, category_id, guild_id, topic, icon_url, user_limit, position, max_screen_shares,
+               COALESCE(retention_days, (SELECT g.retention_days FROM guilds g WHERE g.id = channels.guild_id)) AS retention_days,
+               created_at, updated_at, version
         FROM channels
         WHERE guild_id = $1
         ORDER BY position ASC
@@ -1899,6 +2689,8 @@ pub struct CreateOidcProviderParams<'a> {
     pub client_id: &'a str,
     pub client_secret_encrypted: &'a str,
     pub scopes: &'a str,
+    pub provisioning: &'a str,
+    pub link_by_email: bool,
     pub created_by: Uuid,
 }
 
@@ -1912,11 +2704,12 @@ pub async fn create_oidc_provider(
             slug, display_name, icon_hint, provider_type,
             issuer_url, authorization_url, token_url, userinfo_url,
             client_id, client_secret_encrypted, scopes,
+            provisioning, link_by_email,
             position, created_by
         ) VALUES (
-            $1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11,
+            $1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13,
             COALESCE((SELECT MAX(position) + 1 FROM oidc_providers), 0),
-            $12
+            $14
         ) RETURNING *",
     )
     .bind(params.slug)
@@ -1930,6 +2723,8 @@ pub async fn create_oidc_provider(
     .bind(params.client_id)
     .bind(params.client_secret_encrypted)
     .bind(params.scopes)
+    .bind(params.provisioning)
+    .bind(params.link_by_email)
     .bind(params.created_by)
     .fetch_one(pool)
     .await
@@ -1952,6 +2747,10 @@ pub struct UpdateOidcProviderParams<'a> {
     pub client_secret_encrypted: Option<&'a str>,
     pub scopes: &'a str,
     pub enabled: bool,
+    /// If `None`, the existing policy is kept.
+    pub provisioning: Option<&'a str>,
+    /// If `None`, the existing setting is kept.
+    pub link_by_email: Option<bool>,
 }
 
 /// Update an OIDC provider.
@@ -1966,7 +2765,8 @@ pub async fn update_oidc_provider(
                 display_name = $2, icon_hint = $3,
                 issuer_url = $4, authorization_url = $5, token_url = $6, userinfo_url = $7,
                 client_id = $8, client_secret_encrypted = $9, scopes = $10,
-                enabled = $11, updated_at = NOW()
+                enabled = $11, provisioning = COALESCE($12, provisioning),
+                link_by_email = COALESCE($13, link_by_email), updated_at = NOW()
             WHERE id = $1 RETURNING *",
         )
         .bind(params.id)
@@ -1980,6 +2780,8 @@ pub async fn update_oidc_provider(
         .bind(secret)
         .bind(params.scopes)
         .bind(params.enabled)
+        .bind(params.provisioning)
+        .bind(params.link_by_email)
         .fetch_one(pool)
         .await
     } else {
@@ -1988,7 +2790,8 @@ pub async fn update_oidc_provider(
                 display_name = $2, icon_hint = $3,
                 issuer_url = $4, authorization_url = $5, token_url = $6, userinfo_url = $7,
                 client_id = $8, scopes = $9,
-                enabled = $10, updated_at = NOW()
+                enabled = $10, provisioning = COALESCE($11, provisioning),
+                link_by_email = COALESCE($12, link_by_email), updated_at = NOW()
             WHERE id = $1 RETURNING *",
         )
         .bind(params.id)
@@ -2001,6 +2804,8 @@ pub async fn update_oidc_provider(
         .bind(params.client_id)
         .bind(params.scopes)
         .bind(params.enabled)
+        .bind(params.provisioning)
+        .bind(params.link_by_email)
         .fetch_one(pool)
         .await
     }
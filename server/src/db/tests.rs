@@ -27,7 +27,7 @@ mod postgres_tests {
         assert_eq!(user.username, username);
         assert_eq!(user.display_name, display_name);
         assert_eq!(user.email.as_deref(), Some("test@example.com"));
-        assert_eq!(user.auth_method, AuthMethod::Local);
+        assert_eq!(user.password_hash.as_deref(), Some(password_hash));
         assert_eq!(user.status, UserStatus::Offline);
 
         // Find by ID
@@ -403,6 +403,7 @@ mod postgres_tests {
             None,
             None,
             None, // position
+            None, // expected_version
         )
         .await
         .expect("Failed to update channel")
@@ -410,6 +411,22 @@ mod postgres_tests {
 
         assert_eq!(updated.name, "new-name");
         assert_eq!(updated.topic.as_deref(), Some("New topic"));
+        assert_eq!(updated.version, channel.version + 1);
+
+        // An update based on the old version no longer applies
+        let stale = update_channel(
+            &pool,
+            channel.id,
+            Some("stale-name"),
+            None,
+            None,
+            None,
+            None,
+            Some(channel.version),
+        )
+        .await
+        .expect("Failed to update channel");
+        assert!(stale.is_none());
     }
 
     #[sqlx::test]
@@ -816,6 +833,7 @@ mod postgres_tests {
             None,
             None,
             "skipped",
+            "skipped",
         )
         .await
         .expect("Failed to create attachment");
@@ -893,6 +911,7 @@ mod postgres_tests {
             None,
             None,
             "skipped",
+            "skipped",
         )
         .await
         .expect("Failed to create attachment 1");
@@ -909,6 +928,7 @@ mod postgres_tests {
             None,
             None,
             "skipped",
+            "skipped",
         )
         .await
         .expect("Failed to create attachment 2");
@@ -9,7 +9,7 @@ use uuid::Uuid;
 
 use super::types::{
     DiscoverQuery, DiscoverResponse, DiscoverSort, DiscoverableGuild, JoinDiscoverableResponse,
-    TAG_REGEX,
+    RecommendedGuildsResponse, RecommendedQuery, TAG_REGEX,
 };
 use crate::api::AppState;
 use crate::auth::AuthUser;
@@ -88,7 +88,7 @@ pub async fn browse_guilds(
     State(state): State<AppState>,
     Query(query): Query<DiscoverQuery>,
 ) -> Result<Json<DiscoverResponse>, DiscoveryError> {
-    if !state.config.enable_guild_discovery {
+    if !state.config().enable_guild_discovery {
         return Err(DiscoveryError::Disabled);
     }
 
@@ -136,9 +136,12 @@ pub async fn browse_guilds(
         r"SELECT g.id, g.name, g.icon_url, g.banner_url, g.description, g.tags, g.created_at,
                  g.member_count::bigint,
                  COUNT(*) OVER() as total_count
-          FROM guilds g
-          WHERE g.discoverable = true AND g.suspended_at IS NULL",
+          FROM guilds g",
     );
+    if matches!(query.sort, DiscoverSort::Trending) {
+        builder.push(" LEFT JOIN guild_activity_stats s ON s.guild_id = g.id");
+    }
+    builder.push(" WHERE g.discoverable = true AND g.discovery_status = 'approved' AND g.suspended_at IS NULL");
 
     if has_search {
         builder.push(" AND g.search_vector @@ websearch_to_tsquery('english', ");
@@ -166,6 +169,12 @@ pub async fn browse_guilds(
         DiscoverSort::Newest => {
             builder.push(" ORDER BY g.created_at DESC");
         }
+        DiscoverSort::Trending => {
+            // Guilds without stats yet (discoverable since the last run) rank last.
+            builder.push(
+                " ORDER BY COALESCE(s.trending_score, 0) DESC, g.member_count DESC, g.created_at DESC",
+            );
+        }
     }
 
     builder.push(" LIMIT ");
@@ -193,7 +202,7 @@ pub async fn browse_guilds(
     } else if offset > 0 {
         // Past-the-end page: re-run the WHERE clause with COUNT(*) only
         let mut count_builder: QueryBuilder<sqlx::Postgres> = QueryBuilder::new(
-            "SELECT COUNT(*) FROM guilds g WHERE g.discoverable = true AND g.suspended_at IS NULL",
+            "SELECT COUNT(*) FROM guilds g WHERE g.discoverable = true AND g.discovery_status = 'approved' AND g.suspended_at IS NULL",
         );
         if has_search {
             count_builder.push(" AND g.search_vector @@ websearch_to_tsquery('english', ");
@@ -243,6 +252,97 @@ pub async fn browse_guilds(
     }))
 }
 
+/// Recommend discoverable guilds sharing tags with the user's current guilds.
+///
+/// Guilds with more shared tags rank first, then by trending score. Guilds
+/// the user is in or banned from are excluded.
+#[utoipa::path(
+    get,
+    path = "/api/discover/guilds/recommended",
+    tag = "discovery",
+    params(RecommendedQuery),
+    responses(
+        (status = 200, description = "Recommended guilds", body = RecommendedGuildsResponse),
+        (status = 401, description = "Authentication required"),
+        (status = 404, description = "Discovery disabled"),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[tracing::instrument(skip(state))]
+pub async fn recommended_guilds(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Query(query): Query<RecommendedQuery>,
+) -> Result<Json<RecommendedGuildsResponse>, DiscoveryError> {
+    if !state.config().enable_guild_discovery {
+        return Err(DiscoveryError::Disabled);
+    }
+
+    let limit = query.limit.unwrap_or(10).clamp(1, 25);
+
+    let rows: Vec<(
+        Uuid,
+        String,
+        Option<String>,
+        Option<String>,
+        Option<String>,
+        Vec<String>,
+        chrono::DateTime<chrono::Utc>,
+        i64,
+    )> = sqlx::query_as(
+        r"WITH my_tags AS (
+              SELECT DISTINCT UNNEST(g.tags) AS tag
+              FROM guild_members gm
+              INNER JOIN guilds g ON g.id = gm.guild_id
+              WHERE gm.user_id = $1
+          )
+          SELECT g.id, g.name, g.icon_url, g.banner_url, g.description, g.tags, g.created_at,
+                 g.member_count::bigint
+          FROM guilds g
+          CROSS JOIN LATERAL (
+              SELECT COUNT(*) AS shared FROM my_tags WHERE my_tags.tag = ANY(g.tags)
+          ) overlap
+          LEFT JOIN guild_activity_stats s ON s.guild_id = g.id
+          WHERE g.discoverable = true AND g.discovery_status = 'approved' AND g.suspended_at IS NULL
+            AND overlap.shared > 0
+            AND NOT EXISTS (
+                SELECT 1 FROM guild_members gm WHERE gm.guild_id = g.id AND gm.user_id = $1
+            )
+            AND NOT EXISTS (
+                SELECT 1 FROM guild_bans b
+                WHERE b.guild_id = g.id AND b.user_id = $1
+                  AND (b.expires_at IS NULL OR b.expires_at > NOW())
+            )
+          ORDER BY overlap.shared DESC, COALESCE(s.trending_score, 0) DESC,
+                   g.member_count DESC, g.created_at DESC
+          LIMIT $2",
+    )
+    .bind(auth.id)
+    .bind(limit)
+    .fetch_all(&state.db)
+    .await?;
+
+    let guilds = rows
+        .into_iter()
+        .map(
+            |(id, name, icon_url, banner_url, description, tags, created_at, member_count)| {
+                DiscoverableGuild {
+                    id,
+                    name,
+                    icon_url,
+                    banner_url,
+                    description,
+                    tags,
+                    member_count,
+                    created_at,
+                }
+            },
+        )
+        .collect();
+
+    Ok(Json(RecommendedGuildsResponse { guilds }))
+}
+
 /// Join a discoverable guild (requires authentication).
 #[utoipa::path(
     post,
@@ -262,13 +362,13 @@ pub async fn join_discoverable(
     auth: AuthUser,
     Path(guild_id): Path<Uuid>,
 ) -> Result<Json<JoinDiscoverableResponse>, DiscoveryError> {
-    if !state.config.enable_guild_discovery {
+    if !state.config().enable_guild_discovery {
         return Err(DiscoveryError::Disabled);
     }
 
     // Verify guild is discoverable and not suspended
     let guild: Option<(String,)> = sqlx::query_as(
-        "SELECT name FROM guilds WHERE id = $1 AND discoverable = true AND suspended_at IS NULL",
+        "SELECT name FROM guilds WHERE id = $1 AND discoverable = true AND discovery_status = 'approved' AND suspended_at IS NULL",
     )
     .bind(guild_id)
     .fetch_optional(&state.db)
@@ -318,10 +418,10 @@ pub async fn join_discoverable(
             .bind(guild_id)
             .fetch_one(&mut *tx)
             .await?;
-    if member_count >= state.config.max_members_per_guild {
+    if member_count >= state.config().max_members_per_guild {
         return Err(DiscoveryError::LimitExceeded(format!(
             "Guild has reached the maximum number of members ({})",
-            state.config.max_members_per_guild
+            state.config().max_members_per_guild
         )));
     }
 
@@ -333,6 +433,9 @@ pub async fn join_discoverable(
     .bind(auth.id)
     .execute(&mut *tx)
     .await?;
+    if result.rows_affected() > 0 {
+        crate::guild::onboarding::mark_pending(&mut tx, guild_id, auth.id).await?;
+    }
 
     tx.commit().await?;
 
@@ -342,6 +445,7 @@ pub async fn join_discoverable(
             guild_id,
             guild_name,
             already_member: true,
+            onboarding: crate::guild::onboarding::join_payload(&state.db, guild_id).await,
         }));
     }
 
@@ -358,6 +462,9 @@ pub async fn join_discoverable(
         // Non-fatal: member was already inserted, read state can be retried on channel access
     }
 
+    crate::chat::system_messages::post_member_join(&state.db, &state.redis, guild_id, auth.id)
+        .await;
+
     // Broadcast MemberJoined to bot ecosystem (non-blocking)
     {
         let db = state.db.clone();
@@ -435,5 +542,6 @@ pub async fn join_discoverable(
         guild_id,
         guild_name,
         already_member: false,
+        onboarding: crate::guild::onboarding::join_payload(&state.db, guild_id).await,
     }))
 }
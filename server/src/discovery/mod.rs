@@ -1,8 +1,12 @@
 //! Guild Discovery Module
 //!
-//! Provides public browsing of discoverable guilds and join-via-discovery.
+//! Provides public browsing of discoverable guilds, join-via-discovery and
+//! per-user recommendations. Trending scores come from a nightly job
+//! (`trending`); listings need admin approval and can be reported (`review`).
 
 pub mod handlers;
+pub mod review;
+pub mod trending;
 pub mod types;
 
 use axum::routing::{get, post};
@@ -15,7 +19,11 @@ pub fn public_router() -> Router<AppState> {
     Router::new().route("/guilds", get(handlers::browse_guilds))
 }
 
-/// Protected routes (auth required) — joining guilds.
+/// Protected routes (auth required) — recommendations, joining and reporting
+/// guilds.
 pub fn protected_router() -> Router<AppState> {
-    Router::new().route("/guilds/{id}/join", post(handlers::join_discoverable))
+    Router::new()
+        .route("/guilds/recommended", get(handlers::recommended_guilds))
+        .route("/guilds/{id}/join", post(handlers::join_discoverable))
+        .route("/guilds/{id}/report", post(review::report_listing))
 }
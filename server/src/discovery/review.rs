@@ -0,0 +1,167 @@
+//! Discovery Review
+//!
+//! Marking a guild discoverable submits it for review (`discovery_status`
+//! `pending`); it is only listed once a system admin approves it through
+//! `/api/admin/discovery/queue`. Rejections carry a reason for the owner.
+//! Users can report a listing; once the reports since the last review reach
+//! `DISCOVERY_REPORT_THRESHOLD` the guild is delisted and queued again.
+//!
+//! Owners learn about every outcome through `guild_discovery_reviewed`.
+
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::Json;
+use serde::Deserialize;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use super::handlers::DiscoveryError;
+use crate::api::AppState;
+use crate::auth::AuthUser;
+use crate::ws::{broadcast_to_user, ServerEvent};
+
+/// Longest report or rejection reason, in characters.
+pub const MAX_REASON_LENGTH: usize = 500;
+
+/// `UPDATE guilds` assignments that (re)submit a guild for review, unless it
+/// is already approved or queued.
+pub const SUBMIT_FOR_REVIEW: &str = r"
+    discovery_status = CASE WHEN discovery_status IN ('approved', 'pending')
+        THEN discovery_status ELSE 'pending' END,
+    discovery_submitted_at = CASE WHEN discovery_status IN ('approved', 'pending')
+        THEN discovery_submitted_at ELSE NOW() END,
+    discovery_rejection_reason = CASE WHEN discovery_status IN ('approved', 'pending')
+        THEN discovery_rejection_reason END";
+
+/// Request body for reporting a discovery listing.
+#[derive(Debug, Deserialize, utoipa::ToSchema)]
+pub struct ReportListingRequest {
+    /// What is wrong with the listing (1-500 characters).
+    pub reason: String,
+}
+
+/// Trim a report or rejection reason and check its length.
+#[must_use]
+pub fn normalize_reason(raw: &str) -> Option<String> {
+    let reason = raw.trim();
+    (!reason.is_empty() && reason.chars().count() <= MAX_REASON_LENGTH).then(|| reason.to_string())
+}
+
+/// Tell a guild's owner about a review outcome.
+pub async fn notify_owner(pool: &PgPool, redis: &fred::prelude::Client, guild_id: Uuid) {
+    let guild: Option<(Uuid, String, String, Option<String>)> = match sqlx::query_as(
+        "SELECT owner_id, name, discovery_status, discovery_rejection_reason FROM guilds WHERE id = $1",
+    )
+    .bind(guild_id)
+    .fetch_optional(pool)
+    .await
+    {
+        Ok(guild) => guild,
+        Err(e) => {
+            tracing::warn!(guild_id = %guild_id, error = %e, "Failed to load guild for discovery review notice");
+            return;
+        }
+    };
+    let Some((owner_id, guild_name, status, reason)) = guild else {
+        return;
+    };
+
+    let event = ServerEvent::GuildDiscoveryReviewed {
+        guild_id,
+        guild_name,
+        status,
+        reason,
+    };
+    if let Err(e) = broadcast_to_user(redis, owner_id, &event).await {
+        tracing::warn!(guild_id = %guild_id, error = %e, "Failed to send discovery review notice");
+    }
+}
+
+/// Report a discovery listing (requires authentication).
+///
+/// Reporting again replaces the earlier report. Enough reports since the last
+/// review delist the guild until an admin reviews it again.
+#[utoipa::path(
+    post,
+    path = "/api/discover/guilds/{id}/report",
+    tag = "discovery",
+    params(("id" = Uuid, Path, description = "Guild ID")),
+    request_body = ReportListingRequest,
+    responses(
+        (status = 204, description = "Report recorded"),
+        (status = 400, description = "Invalid reason"),
+        (status = 404, description = "Guild not found or not discoverable"),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[tracing::instrument(skip(state, body))]
+pub async fn report_listing(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(guild_id): Path<Uuid>,
+    Json(body): Json<ReportListingRequest>,
+) -> Result<StatusCode, DiscoveryError> {
+    if !state.config().enable_guild_discovery {
+        return Err(DiscoveryError::Disabled);
+    }
+    let reason = normalize_reason(&body.reason).ok_or_else(|| {
+        DiscoveryError::Validation(format!("Reason must be 1-{MAX_REASON_LENGTH} characters"))
+    })?;
+
+    let listed: bool = sqlx::query_scalar(
+        "SELECT EXISTS(SELECT 1 FROM guilds WHERE id = $1 AND discoverable = true AND discovery_status = 'approved' AND suspended_at IS NULL)",
+    )
+    .bind(guild_id)
+    .fetch_one(&state.db)
+    .await?;
+    if !listed {
+        return Err(DiscoveryError::NotFound);
+    }
+
+    sqlx::query(
+        r"INSERT INTO guild_discovery_reports (guild_id, reporter_id, reason)
+          VALUES ($1, $2, $3)
+          ON CONFLICT (guild_id, reporter_id)
+          DO UPDATE SET reason = EXCLUDED.reason, created_at = NOW()",
+    )
+    .bind(guild_id)
+    .bind(auth.id)
+    .bind(&reason)
+    .execute(&state.db)
+    .await?;
+
+    let delisted = sqlx::query(
+        r"UPDATE guilds g SET discovery_status = 'delisted', discovery_submitted_at = NOW(),
+              discovery_rejection_reason = 'Delisted after reports from users; awaiting review'
+          WHERE g.id = $1 AND g.discovery_status = 'approved'
+            AND (SELECT COUNT(*) FROM guild_discovery_reports r
+                  WHERE r.guild_id = g.id
+                    AND r.created_at > COALESCE(g.discovery_reviewed_at, '-infinity')) >= $2",
+    )
+    .bind(guild_id)
+    .bind(state.config().discovery_report_threshold)
+    .execute(&state.db)
+    .await?
+    .rows_affected()
+        > 0;
+
+    if delisted {
+        tracing::info!(guild_id = %guild_id, "Guild delisted from discovery after reports");
+        notify_owner(&state.db, &state.redis, guild_id).await;
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_reason() {
+        assert_eq!(normalize_reason("  spam  ").as_deref(), Some("spam"));
+        assert_eq!(normalize_reason("   "), None);
+        assert!(normalize_reason(&"x".repeat(MAX_REASON_LENGTH)).is_some());
+        assert!(normalize_reason(&"x".repeat(MAX_REASON_LENGTH + 1)).is_none());
+    }
+}
@@ -0,0 +1,139 @@
+//! Trending Guild Stats
+//!
+//! A nightly job computes `guild_activity_stats` for every listed guild:
+//! members gained and messages posted over the trailing seven days, blended
+//! into a `trending_score` used by `sort=trending`. Both inputs are
+//! log-scaled so a handful of huge guilds cannot crowd out smaller ones, and
+//! growth is weighted above chat volume.
+
+use std::time::Duration;
+
+use chrono::{NaiveDate, Utc};
+use sqlx::PgPool;
+
+/// Weight of member growth relative to message activity.
+const GROWTH_WEIGHT: f64 = 2.0;
+
+/// Trending score for a guild's seven-day activity.
+#[must_use]
+pub fn trending_score(member_growth: i64, messages: i64) -> f64 {
+    #[allow(clippy::cast_precision_loss)]
+    let log = |n: i64| (n.max(0) as f64).ln_1p();
+    GROWTH_WEIGHT.mul_add(log(member_growth), log(messages))
+}
+
+/// Start the nightly trending stats job.
+///
+/// Checks hourly and recomputes the stats once the latest run is from an
+/// earlier UTC day, so a restart never skips a night.
+pub fn spawn_trending_stats_task(pool: PgPool) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(3600));
+        loop {
+            interval.tick().await;
+            let today = Utc::now().date_naive();
+            match refresh_if_stale(&pool, today).await {
+                Ok(Some(guilds)) => {
+                    tracing::info!(date = %today, guilds, "Computed guild trending stats");
+                }
+                Ok(None) => {}
+                Err(e) => {
+                    tracing::warn!(error = %e, date = %today, "Failed to compute guild trending stats");
+                }
+            }
+        }
+    })
+}
+
+/// Recompute the stats unless they were already computed on `today`.
+///
+/// Returns the number of guilds scored, or `None` if the stats were fresh.
+pub async fn refresh_if_stale(pool: &PgPool, today: NaiveDate) -> Result<Option<u64>, sqlx::Error> {
+    let latest: Option<NaiveDate> =
+        sqlx::query_scalar("SELECT MAX(computed_on) FROM guild_activity_stats")
+            .fetch_one(pool)
+            .await?;
+    if latest.is_some_and(|latest| latest >= today) {
+        return Ok(None);
+    }
+    refresh_activity_stats(pool, today).await.map(Some)
+}
+
+/// Recompute the activity stats of all listed guilds.
+///
+/// Rows of guilds that are no longer listed are dropped. Returns the
+/// number of guilds scored.
+pub async fn refresh_activity_stats(pool: &PgPool, today: NaiveDate) -> Result<u64, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+
+    let rows: Vec<(uuid::Uuid, i64, i64)> = sqlx::query_as(
+        r"SELECT g.id,
+                 (SELECT COUNT(*) FROM guild_members gm
+                   WHERE gm.guild_id = g.id AND gm.joined_at >= NOW() - INTERVAL '7 days'),
+                 (SELECT COUNT(*) FROM messages m
+                   INNER JOIN channels c ON c.id = m.channel_id
+                   WHERE c.guild_id = g.id AND m.deleted_at IS NULL
+                     AND m.created_at >= NOW() - INTERVAL '7 days')
+          FROM guilds g
+          WHERE g.discoverable = true AND g.discovery_status = 'approved' AND g.suspended_at IS NULL",
+    )
+    .fetch_all(&mut *tx)
+    .await?;
+
+    let mut guild_ids = Vec::with_capacity(rows.len());
+    let mut growth = Vec::with_capacity(rows.len());
+    let mut messages = Vec::with_capacity(rows.len());
+    let mut scores = Vec::with_capacity(rows.len());
+    for (guild_id, joined, posted) in rows {
+        guild_ids.push(guild_id);
+        growth.push(i32::try_from(joined).unwrap_or(i32::MAX));
+        messages.push(posted);
+        scores.push(trending_score(joined, posted));
+    }
+
+    sqlx::query("DELETE FROM guild_activity_stats WHERE guild_id <> ALL($1)")
+        .bind(&guild_ids)
+        .execute(&mut *tx)
+        .await?;
+
+    let scored = sqlx::query(
+        r"INSERT INTO guild_activity_stats
+              (guild_id, member_growth_7d, messages_7d, trending_score, computed_on, computed_at)
+          SELECT s.guild_id, s.growth, s.messages, s.score, $5, NOW()
+          FROM UNNEST($1::uuid[], $2::int[], $3::bigint[], $4::float8[])
+               AS s(guild_id, growth, messages, score)
+          ON CONFLICT (guild_id) DO UPDATE SET
+              member_growth_7d = EXCLUDED.member_growth_7d,
+              messages_7d = EXCLUDED.messages_7d,
+              trending_score = EXCLUDED.trending_score,
+              computed_on = EXCLUDED.computed_on,
+              computed_at = EXCLUDED.computed_at",
+    )
+    .bind(&guild_ids)
+    .bind(&growth)
+    .bind(&messages)
+    .bind(&scores)
+    .bind(today)
+    .execute(&mut *tx)
+    .await?
+    .rows_affected();
+
+    tx.commit().await?;
+    Ok(scored)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_trending_score() {
+        assert!(trending_score(0, 0).abs() < f64::EPSILON);
+        assert!(trending_score(-5, -5).abs() < f64::EPSILON);
+        // Growth outweighs the same amount of chat.
+        assert!(trending_score(10, 0) > trending_score(0, 10));
+        // Log scaling keeps sheer volume from dominating.
+        assert!(trending_score(20, 50) > trending_score(0, 5_000));
+        assert!(trending_score(5, 100) > trending_score(5, 99));
+    }
+}
@@ -17,6 +17,8 @@ pub enum DiscoverSort {
     /// Sort by creation date (newest first).
     #[default]
     Newest,
+    /// Sort by recent member growth and message activity.
+    Trending,
 }
 
 /// Query parameters for browsing discoverable guilds.
@@ -27,7 +29,7 @@ pub struct DiscoverQuery {
     /// Filter by tags (comma-separated, array overlap).
     #[serde(default, deserialize_with = "deserialize_tags")]
     pub tags: Option<Vec<String>>,
-    /// Sort order: "members" (popular) or "newest" (default).
+    /// Sort order: "members" (popular), "trending" or "newest" (default).
     #[serde(default)]
     pub sort: DiscoverSort,
     /// Number of results per page (1-50, default 20).
@@ -62,6 +64,13 @@ pub struct DiscoverableGuild {
     pub created_at: chrono::DateTime<chrono::Utc>,
 }
 
+/// Query parameters for guild recommendations.
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+pub struct RecommendedQuery {
+    /// Number of recommendations (1-25, default 10).
+    pub limit: Option<i64>,
+}
+
 /// Paginated response for guild discovery.
 #[derive(Debug, Serialize, utoipa::ToSchema)]
 pub struct DiscoverResponse {
@@ -71,10 +80,19 @@ pub struct DiscoverResponse {
     pub offset: i64,
 }
 
+/// Guilds recommended from the tags of the user's current guilds.
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct RecommendedGuildsResponse {
+    pub guilds: Vec<DiscoverableGuild>,
+}
+
 /// Response after joining a discoverable guild.
 #[derive(Debug, Serialize, utoipa::ToSchema)]
 pub struct JoinDiscoverableResponse {
     pub guild_id: Uuid,
     pub guild_name: String,
     pub already_member: bool,
+    /// The guild's onboarding, when enabled.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub onboarding: Option<crate::guild::types::GuildOnboarding>,
 }
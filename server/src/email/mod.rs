@@ -1,26 +1,56 @@
 //! Email Service
 //!
-//! SMTP-based email delivery for transactional emails (password resets, etc.).
+//! Transactional emails (password resets, email verification, alerts). Messages
+//! are rendered from [`templates`] and handed to a backend: SMTP, or the
+//! console backend, which only logs them and is meant for development.
+
+pub mod templates;
 
 use anyhow::{Context, Result};
 use lettre::message::Mailbox;
 use lettre::transport::smtp::authentication::Credentials;
 use lettre::{AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor};
 
+pub use self::templates::EmailTemplate;
 use crate::config::Config;
 
-/// Email service for sending transactional emails via SMTP.
+/// Sender used by the console backend when `SMTP_FROM` is not set.
+const CONSOLE_FROM: &str = "noreply@localhost";
+
+/// Where emails are delivered.
+#[derive(Clone)]
+enum Transport {
+    Smtp(AsyncSmtpTransport<Tokio1Executor>),
+    /// Logs every email instead of sending it.
+    Console,
+}
+
+/// Email service for sending transactional emails.
 #[derive(Clone)]
 pub struct EmailService {
-    mailer: AsyncSmtpTransport<Tokio1Executor>,
+    transport: Transport,
     from_address: Mailbox,
 }
 
 impl EmailService {
     /// Create a new email service from server configuration.
     ///
-    /// Requires SMTP to be fully configured (`config.has_smtp()` must be true).
+    /// With `EMAIL_BACKEND=console` emails are only logged. Otherwise SMTP must
+    /// be fully configured (`config.has_smtp()` must be true).
     pub fn new(config: &Config) -> Result<Self> {
+        if config.email_backend == "console" {
+            let from_address: Mailbox = config
+                .smtp_from
+                .as_deref()
+                .unwrap_or(CONSOLE_FROM)
+                .parse()
+                .context("SMTP_FROM is not a valid email address")?;
+            return Ok(Self {
+                transport: Transport::Console,
+                from_address,
+            });
+        }
+
         let host = config.smtp_host.as_ref().context("SMTP_HOST is required")?;
         let username = config
             .smtp_username
@@ -57,15 +87,25 @@ impl EmailService {
         };
 
         Ok(Self {
-            mailer,
+            transport: Transport::Smtp(mailer),
             from_address,
         })
     }
 
+    /// Whether emails are only logged instead of delivered.
+    #[must_use]
+    pub const fn is_console(&self) -> bool {
+        matches!(self.transport, Transport::Console)
+    }
+
     /// Test the SMTP connection by sending a NOOP command.
+    ///
+    /// Always succeeds for the console backend.
     pub async fn test_connection(&self) -> Result<()> {
-        let ok = self
-            .mailer
+        let Transport::Smtp(mailer) = &self.transport else {
+            return Ok(());
+        };
+        let ok = mailer
             .test_connection()
             .await
             .context("SMTP connection test failed")?;
@@ -75,74 +115,105 @@ impl EmailService {
         Ok(())
     }
 
-    /// Send a password reset email with the given reset code.
-    pub async fn send_password_reset(
-        &self,
-        to_email: &str,
-        username: &str,
-        reset_token: &str,
-    ) -> Result<()> {
+    /// Render `template` and send it to `to_email`.
+    pub async fn send(&self, to_email: &str, template: EmailTemplate<'_>) -> Result<()> {
         let to_mailbox: Mailbox = to_email
             .parse()
             .context("Invalid recipient email address")?;
 
-        let body = format!(
-            "Hello {username},\n\
-             \n\
-             A password reset was requested for your account.\n\
-             \n\
-             Your reset code: {reset_token}\n\
-             \n\
-             Enter this code on the password reset page to set a new password.\n\
-             This code expires in 1 hour.\n\
-             \n\
-             If you did not request this, you can safely ignore this email.\n"
-        );
-
-        let email = Message::builder()
-            .from(self.from_address.clone())
-            .to(to_mailbox)
-            .subject("Password Reset Request")
-            .body(body)
-            .context("Failed to build email message")?;
+        match &self.transport {
+            Transport::Smtp(mailer) => {
+                let email = Message::builder()
+                    .from(self.from_address.clone())
+                    .to(to_mailbox)
+                    .subject(template.subject())
+                    .body(template.body())
+                    .context("Failed to build email message")?;
 
-        self.mailer
-            .send(email)
-            .await
-            .context("Failed to send email via SMTP")?;
+                mailer
+                    .send(email)
+                    .await
+                    .with_context(|| format!("Failed to send {} email", template.kind()))?;
+            }
+            Transport::Console => {
+                tracing::info!(
+                    to = %to_mailbox,
+                    kind = template.kind(),
+                    subject = template.subject(),
+                    "Email (console backend):\n{}",
+                    template.body()
+                );
+            }
+        }
 
         Ok(())
     }
 
+    /// Send a password reset email with the given reset code.
+    pub async fn send_password_reset(
+        &self,
+        to_email: &str,
+        username: &str,
+        reset_token: &str,
+    ) -> Result<()> {
+        self.send(
+            to_email,
+            EmailTemplate::PasswordReset {
+                username,
+                token: reset_token,
+            },
+        )
+        .await
+    }
+
     /// Send a notification that the user's data export is ready for download.
     pub async fn send_data_export_ready(&self, to_email: &str, username: &str) -> Result<()> {
-        let to_mailbox: Mailbox = to_email
-            .parse()
-            .context("Invalid recipient email address")?;
-
-        let body = format!(
-            "Hello {username},\n\
-             \n\
-             Your data export is ready for download.\n\
-             \n\
-             You can download it from your account settings.\n\
-             \n\
-             The download link will expire in 7 days.\n"
-        );
+        self.send(to_email, EmailTemplate::DataExportReady { username })
+            .await
+    }
 
-        let email = Message::builder()
-            .from(self.from_address.clone())
-            .to(to_mailbox)
-            .subject("Your Data Export is Ready")
-            .body(body)
-            .context("Failed to build email message")?;
+    /// Alert the user that their account was signed in from a new network.
+    pub async fn send_new_network_login(
+        &self,
+        to_email: &str,
+        username: &str,
+        ip: &str,
+        user_agent: Option<&str>,
+    ) -> Result<()> {
+        self.send(
+            to_email,
+            EmailTemplate::NewNetworkLogin {
+                username,
+                ip,
+                user_agent,
+            },
+        )
+        .await
+    }
 
-        self.mailer
-            .send(email)
+    /// Tell the user a system admin approved their registration.
+    pub async fn send_registration_approved(&self, to_email: &str, username: &str) -> Result<()> {
+        self.send(to_email, EmailTemplate::RegistrationApproved { username })
             .await
-            .context("Failed to send export notification email")?;
+    }
 
-        Ok(())
+    /// Send an email verification code.
+    pub async fn send_email_verification(
+        &self,
+        to_email: &str,
+        username: &str,
+        token: &str,
+        expires_in_hours: i64,
+    ) -> Result<()> {
+        self.send(
+            to_email,
+            EmailTemplate::VerifyEmail {
+                username,
+                token,
+                expires_in_hours,
+            },
+        )
+        .await
     }
 }
 
@@ -224,6 +295,15 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_new_console_backend_needs_no_smtp() {
+        let mut config = Config::default_for_test();
+        config.email_backend = "console".into();
+        let service = EmailService::new(&config).expect("console backend needs no SMTP");
+        assert!(service.is_console());
+        assert!(!EmailService::new(&smtp_test_config()).unwrap().is_console());
+    }
+
     #[test]
     fn test_new_invalid_from_address() {
         let mut config = smtp_test_config();
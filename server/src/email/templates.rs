@@ -0,0 +1,158 @@
+//! Email Templates
+//!
+//! Subject and plain-text body of every transactional email. Kept apart from
+//! delivery so the console backend and tests see exactly what SMTP would send.
+
+/// A transactional email and the values it is rendered with.
+#[derive(Debug, Clone, Copy)]
+pub enum EmailTemplate<'a> {
+    /// Password reset code from `POST /auth/forgot-password`.
+    PasswordReset { username: &'a str, token: &'a str },
+    /// A requested data export finished.
+    DataExportReady { username: &'a str },
+    /// The account was signed in to from a network it has not used before.
+    NewNetworkLogin {
+        username: &'a str,
+        ip: &'a str,
+        user_agent: Option<&'a str>,
+    },
+    /// A system admin approved a pending registration.
+    RegistrationApproved { username: &'a str },
+    /// Confirm that the user owns the address on their account.
+    VerifyEmail {
+        username: &'a str,
+        token: &'a str,
+        expires_in_hours: i64,
+    },
+}
+
+impl EmailTemplate<'_> {
+    /// Short name for logs.
+    #[must_use]
+    pub const fn kind(&self) -> &'static str {
+        match self {
+            Self::PasswordReset { .. } => "password_reset",
+            Self::DataExportReady { .. } => "data_export_ready",
+            Self::NewNetworkLogin { .. } => "new_network_login",
+            Self::RegistrationApproved { .. } => "registration_approved",
+            Self::VerifyEmail { .. } => "verify_email",
+        }
+    }
+
+    /// Subject line.
+    #[must_use]
+    pub const fn subject(&self) -> &'static str {
+        match self {
+            Self::PasswordReset { .. } => "Password Reset Request",
+            Self::DataExportReady { .. } => "Your Data Export is Ready",
+            Self::NewNetworkLogin { .. } => "New Sign-In to Your Account",
+            Self::RegistrationApproved { .. } => "Your Account Has Been Approved",
+            Self::VerifyEmail { .. } => "Verify Your Email Address",
+        }
+    }
+
+    /// Plain-text body.
+    #[must_use]
+    pub fn body(&self) -> String {
+        match *self {
+            Self::PasswordReset { username, token } => format!(
+                "Hello {username},\n\
+                 \n\
+                 A password reset was requested for your account.\n\
+                 \n\
+                 Your reset code: {token}\n\
+                 \n\
+                 Enter this code on the password reset page to set a new password.\n\
+                 This code expires in 1 hour.\n\
+                 \n\
+                 If you did not request this, you can safely ignore this email.\n"
+            ),
+            Self::DataExportReady { username } => format!(
+                "Hello {username},\n\
+                 \n\
+                 Your data export is ready for download.\n\
+                 \n\
+                 You can download it from your account settings.\n\
+                 \n\
+                 The download link will expire in 7 days.\n"
+            ),
+            Self::NewNetworkLogin {
+                username,
+                ip,
+                user_agent,
+            } => {
+                let device = user_agent.unwrap_or("unknown device");
+                format!(
+                    "Hello {username},\n\
+                     \n\
+                     Your account was just signed in to from a network you have not used before.\n\
+                     \n\
+                     IP address: {ip}\n\
+                     Device: {device}\n\
+                     \n\
+                     If this was you, no action is needed.\n\
+                     If not, change your password and review your active sessions right away.\n"
+                )
+            }
+            Self::RegistrationApproved { username } => format!(
+                "Hello {username},\n\
+                 \n\
+                 Your account has been approved by an administrator.\n\
+                 \n\
+                 You can now sign in.\n"
+            ),
+            Self::VerifyEmail {
+                username,
+                token,
+                expires_in_hours,
+            } => format!(
+                "Hello {username},\n\
+                 \n\
+                 Please confirm that this is the email address for your account.\n\
+                 \n\
+                 Your verification code: {token}\n\
+                 \n\
+                 Enter this code in the app to verify your email address.\n\
+                 This code expires in {expires_in_hours} hours.\n\
+                 \n\
+                 If you did not create an account, you can safely ignore this email.\n"
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn codes_are_in_the_body() {
+        let reset = EmailTemplate::PasswordReset {
+            username: "alice",
+            token: "reset-token",
+        };
+        assert!(reset.body().contains("Your reset code: reset-token"));
+
+        let verify = EmailTemplate::VerifyEmail {
+            username: "alice",
+            token: "verify-token",
+            expires_in_hours: 24,
+        };
+        assert!(verify.body().starts_with("Hello alice,"));
+        assert!(verify
+            .body()
+            .contains("Your verification code: verify-token"));
+        assert!(verify.body().contains("expires in 24 hours"));
+    }
+
+    #[test]
+    fn new_network_login_without_user_agent() {
+        let template = EmailTemplate::NewNetworkLogin {
+            username: "alice",
+            ip: "203.0.113.9",
+            user_agent: None,
+        };
+        assert!(template.body().contains("Device: unknown device"));
+        assert_eq!(template.subject(), "New Sign-In to Your Account");
+    }
+}
@@ -1,26 +1,36 @@
 //! Account Deletion Worker
 //!
 //! Processes accounts whose 30-day grace period has expired.
-//! Collects S3 objects, then deletes the user row — DB cascades and
-//! SET NULL handle the rest.
+//!
+//! The user's messages are attributed to a fresh tombstone (shown as
+//! "Deleted User") and, depending on [`DeletedUserContent`], either keep their
+//! text or are deleted. Friendships and DM conversations nobody else is left
+//! in are removed, then the user row is deleted — DB cascades and SET NULL
+//! handle the rest. S3 objects are collected up front and deleted last.
 
 use sqlx::PgPool;
 use uuid::Uuid;
 
 use crate::chat::S3Client;
+use crate::config::DeletedUserContent;
 
 /// S3 keys that belong to a user and must be cleaned up before deletion.
 struct UserS3Objects {
     /// Avatar image key (e.g. `avatars/{user_id}/...`).
     avatar_key: Option<String>,
-    /// File attachment keys from the user's messages.
+    /// File attachment keys (with image renditions) from the user's messages
+    /// and from DM conversations that are removed with the account.
     attachment_keys: Vec<String>,
     /// Data export archive keys.
     export_keys: Vec<String>,
 }
 
 /// Collect all S3 keys associated with a user.
-async fn collect_user_s3_keys(pool: &PgPool, user_id: Uuid) -> anyhow::Result<UserS3Objects> {
+async fn collect_user_s3_keys(
+    pool: &PgPool,
+    user_id: Uuid,
+    orphaned_dms: &[Uuid],
+) -> anyhow::Result<UserS3Objects> {
     // Avatar — avatar_url stores a full URL, extract the S3 key portion
     let avatar_key: Option<String> =
         sqlx::query_scalar("SELECT avatar_url FROM users WHERE id = $1")
@@ -30,15 +40,24 @@ async fn collect_user_s3_keys(pool: &PgPool, user_id: Uuid) -> anyhow::Result<Us
             .flatten()
             .and_then(|url: String| url.find("avatars/").map(|pos| url[pos..].to_string()));
 
-    // File attachments on the user's messages
-    let attachment_keys: Vec<String> = sqlx::query_scalar(
-        "SELECT fa.s3_key FROM file_attachments fa
+    // File attachments on the user's messages and in orphaned DMs. Both
+    // content policies drop the user's attachments: files are more likely to
+    // be personal data than message text.
+    let attachment_rows: Vec<(String, Option<String>, Option<String>)> = sqlx::query_as(
+        "SELECT fa.s3_key, fa.thumbnail_s3_key, fa.medium_s3_key
+         FROM file_attachments fa
          JOIN messages m ON m.id = fa.message_id
-         WHERE m.user_id = $1",
+         WHERE m.user_id = $1 OR m.channel_id = ANY($2)",
     )
     .bind(user_id)
+    .bind(orphaned_dms)
     .fetch_all(pool)
     .await?;
+    let attachment_keys = attachment_rows
+        .into_iter()
+        .flat_map(|(key, thumbnail, medium)| [Some(key), thumbnail, medium])
+        .flatten()
+        .collect();
 
     // Data export archives
     let export_keys: Vec<String> = sqlx::query_scalar(
@@ -76,13 +95,160 @@ async fn delete_s3_objects(s3: &S3Client, objects: &UserS3Objects, user_id: Uuid
     }
 }
 
+/// DM channels in which the user is the only participant left.
+async fn find_orphaned_dms(pool: &PgPool, user_id: Uuid) -> sqlx::Result<Vec<Uuid>> {
+    sqlx::query_scalar(
+        "SELECT dp.channel_id FROM dm_participants dp
+         WHERE dp.user_id = $1
+           AND NOT EXISTS (
+               SELECT 1 FROM dm_participants other
+               WHERE other.channel_id = dp.channel_id AND other.user_id != $1
+           )",
+    )
+    .bind(user_id)
+    .fetch_all(pool)
+    .await
+}
+
+/// Outcome of deleting one account, for logging.
+struct DeletionSummary {
+    tombstone_id: Uuid,
+    messages_kept: u64,
+    messages_deleted: u64,
+    friendships_removed: u64,
+    dms_removed: u64,
+}
+
+/// Tombstone, clean up and delete one user inside a transaction.
+///
+/// Returns `None` if the user row was already gone.
+async fn delete_account(
+    pool: &PgPool,
+    user_id: Uuid,
+    content_policy: DeletedUserContent,
+    orphaned_dms: &[Uuid],
+) -> anyhow::Result<Option<DeletionSummary>> {
+    let mut tx = pool.begin().await?;
+
+    let tombstone_id: Uuid =
+        sqlx::query_scalar("INSERT INTO user_tombstones (content_policy) VALUES ($1) RETURNING id")
+            .bind(content_policy.as_str())
+            .fetch_one(&mut *tx)
+            .await?;
+
+    // Conversations nobody else is in go first, so their messages are not
+    // counted below
+    let dms_removed = sqlx::query("DELETE FROM channels WHERE id = ANY($1)")
+        .bind(orphaned_dms)
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+
+    // Attachment files are deleted from S3 under both policies
+    sqlx::query(
+        "DELETE FROM file_attachments
+         WHERE message_id IN (SELECT id FROM messages WHERE user_id = $1)",
+    )
+    .bind(user_id)
+    .execute(&mut *tx)
+    .await?;
+
+    let (messages_kept, messages_deleted) = match content_policy {
+        DeletedUserContent::Anonymize => {
+            let kept =
+                sqlx::query("UPDATE messages SET author_tombstone_id = $2 WHERE user_id = $1")
+                    .bind(user_id)
+                    .bind(tombstone_id)
+                    .execute(&mut *tx)
+                    .await?
+                    .rows_affected();
+            (kept, 0)
+        }
+        DeletedUserContent::Delete => {
+            // Replies in other users' threads no longer count towards them
+            sqlx::query(
+                "UPDATE messages p
+                 SET thread_reply_count = GREATEST(p.thread_reply_count - r.replies, 0)
+                 FROM (
+                     SELECT parent_id, COUNT(*)::int AS replies FROM messages
+                     WHERE user_id = $1 AND parent_id IS NOT NULL AND deleted_at IS NULL
+                     GROUP BY parent_id
+                 ) r
+                 WHERE p.id = r.parent_id",
+            )
+            .bind(user_id)
+            .execute(&mut *tx)
+            .await?;
+
+            // Thread parents would take other users' replies with them, so
+            // they keep an empty, deleted placeholder instead
+            let wiped = sqlx::query(
+                "UPDATE messages
+                 SET content = '', encrypted = false, nonce = NULL,
+                     deleted_at = COALESCE(deleted_at, NOW()), author_tombstone_id = $2
+                 WHERE user_id = $1 AND thread_reply_count > 0",
+            )
+            .bind(user_id)
+            .bind(tombstone_id)
+            .execute(&mut *tx)
+            .await?
+            .rows_affected();
+
+            let deleted =
+                sqlx::query("DELETE FROM messages WHERE user_id = $1 AND thread_reply_count = 0")
+                    .bind(user_id)
+                    .execute(&mut *tx)
+                    .await?
+                    .rows_affected();
+            (wiped, deleted)
+        }
+    };
+
+    let friendships_removed =
+        sqlx::query("DELETE FROM friendships WHERE requester_id = $1 OR addressee_id = $1")
+            .bind(user_id)
+            .execute(&mut *tx)
+            .await?
+            .rows_affected();
+
+    // Delete the user row — cascades handle everything else:
+    //   CASCADE: sessions, guild_members, channel_members, dm_participants,
+    //            user_keys, user_roles, favorites, pins, read_state, preferences,
+    //            mfa_backup_codes, password_reset_tokens, device_transfers,
+    //            prekeys, user_blocks, user_reports, bot_installations, etc.
+    //   SET NULL: messages.user_id, content filters, audit logs, etc.
+    let result = sqlx::query("DELETE FROM users WHERE id = $1")
+        .bind(user_id)
+        .execute(&mut *tx)
+        .await?;
+    if result.rows_affected() == 0 {
+        tx.rollback().await?;
+        return Ok(None);
+    }
+
+    tx.commit().await?;
+
+    Ok(Some(DeletionSummary {
+        tombstone_id,
+        messages_kept,
+        messages_deleted,
+        friendships_removed,
+        dms_removed,
+    }))
+}
+
 /// Process accounts whose deletion grace period has expired.
 ///
 /// For each due account:
 /// 1. Collect S3 keys (avatar, attachments, exports)
-/// 2. Delete the user row (cascades handle DB cleanup, SET NULL anonymizes messages)
+/// 2. Tombstone the user's messages, remove friendships and orphaned DMs,
+///    and delete the user row, all in one transaction
 /// 3. Clean up S3 objects
-pub async fn process_pending_deletions(pool: &PgPool, s3: &Option<S3Client>) -> anyhow::Result<()> {
+pub async fn process_pending_deletions(
+    pool: &PgPool,
+    s3: &Option<S3Client>,
+    content_policy: DeletedUserContent,
+) -> anyhow::Result<()> {
     let due_users: Vec<(Uuid, String)> = sqlx::query_as(
         "SELECT id, username FROM users
          WHERE deletion_scheduled_at IS NOT NULL AND deletion_scheduled_at <= NOW()",
@@ -98,27 +264,19 @@ pub async fn process_pending_deletions(pool: &PgPool, s3: &Option<S3Client>) ->
         tracing::info!(
             user_id = %user_id,
             username = %username,
+            content_policy = content_policy.as_str(),
             "Processing account deletion"
         );
 
         // Collect S3 keys before deleting the user (FK relationships still intact)
-        let s3_objects = collect_user_s3_keys(pool, *user_id).await?;
-
-        // Delete the user row — cascades handle everything:
-        //   CASCADE: sessions, guild_members, channel_members, user_keys, user_roles,
-        //            favorites, pins, read_state, preferences, friend_requests,
-        //            mfa_backup_codes, password_reset_tokens, device_transfers,
-        //            prekeys, user_blocks, user_reports, bot_installations, etc.
-        //   SET NULL: messages.user_id, content filters, audit logs, etc.
-        let result = sqlx::query("DELETE FROM users WHERE id = $1")
-            .bind(user_id)
-            .execute(pool)
-            .await?;
+        let orphaned_dms = find_orphaned_dms(pool, *user_id).await?;
+        let s3_objects = collect_user_s3_keys(pool, *user_id, &orphaned_dms).await?;
 
-        if result.rows_affected() == 0 {
+        let Some(summary) = delete_account(pool, *user_id, content_policy, &orphaned_dms).await?
+        else {
             tracing::warn!(user_id = %user_id, "User already deleted, skipping");
             continue;
-        }
+        };
 
         // Clean up S3 objects (best-effort, logged on failure)
         if let Some(s3) = s3 {
@@ -128,6 +286,11 @@ pub async fn process_pending_deletions(pool: &PgPool, s3: &Option<S3Client>) ->
         tracing::info!(
             user_id = %user_id,
             username = %username,
+            tombstone_id = %summary.tombstone_id,
+            messages_kept = summary.messages_kept,
+            messages_deleted = summary.messages_deleted,
+            friendships_removed = summary.friendships_removed,
+            dms_removed = summary.dms_removed,
             attachments_cleaned = s3_objects.attachment_keys.len(),
             exports_cleaned = s3_objects.export_keys.len(),
             "Account deletion completed"
@@ -1,20 +1,33 @@
 //! Data Export Worker
 //!
 //! Gathers user data from all tables into a versioned JSON archive and uploads to S3.
+//! When the archive is ready (or the job fails) the user's sessions receive a
+//! `data_export_ready` / `data_export_failed` WebSocket event; the ready event
+//! carries a presigned download URL.
 
 use std::io::Write;
 use std::sync::Arc;
 
 use anyhow::Context;
-use chrono::{Duration, Utc};
+use chrono::{DateTime, Duration, Utc};
 use serde::Serialize;
 use sqlx::PgPool;
+use tokio::sync::Semaphore;
 use uuid::Uuid;
 use zip::write::SimpleFileOptions;
 use zip::ZipWriter;
 
+use crate::chat::s3::S3Error;
 use crate::chat::S3Client;
 use crate::email::EmailService;
+use crate::ws::{broadcast_to_user, ServerEvent};
+
+/// Maximum number of export archives built at the same time across the server.
+/// Further jobs stay `pending` until a slot frees up.
+const MAX_CONCURRENT_EXPORTS: usize = 2;
+
+/// Worker slots shared by all export jobs on this node.
+static EXPORT_SLOTS: Semaphore = Semaphore::const_new(MAX_CONCURRENT_EXPORTS);
 
 /// Maximum number of messages included in a data export.
 const EXPORT_CAP_MESSAGES: i64 = 500_000;
@@ -42,10 +55,12 @@ struct ExportProfile {
     username: String,
     display_name: String,
     email: Option<String>,
-    auth_method: String,
+    has_password: bool,
+    linked_identities: Vec<crate::db::UserAuthMethod>,
     avatar_url: Option<String>,
     is_bot: bool,
     created_at: String,
+    username_history: Vec<crate::db::UsernameChange>,
 }
 
 /// Exported message record.
@@ -155,14 +170,39 @@ struct ExportAuditLogEntry {
     created_at: chrono::DateTime<chrono::Utc>,
 }
 
+/// Presign a download URL for a completed export archive.
+///
+/// Returns the URL and when it stops working: after `S3_PRESIGN_EXPIRY`, or
+/// when the archive itself expires if that is sooner.
+pub async fn presign_download(
+    s3: &S3Client,
+    s3_key: &str,
+    archive_expires_at: DateTime<Utc>,
+) -> Result<(String, DateTime<Utc>), S3Error> {
+    let url = s3.presign_get(s3_key).await?;
+    let url_expires_at = Duration::from_std(s3.presign_expiry())
+        .map_or(archive_expires_at, |expiry| Utc::now() + expiry)
+        .min(archive_expires_at);
+    Ok((url, url_expires_at))
+}
+
 /// Process a data export job.
+///
+/// Waits for one of the [`MAX_CONCURRENT_EXPORTS`] worker slots first; the job
+/// stays `pending` until then.
 pub async fn process_export_job(
     pool: &PgPool,
+    redis: &fred::clients::Client,
     s3: &S3Client,
     email_service: &Option<Arc<EmailService>>,
     job_id: Uuid,
     user_id: Uuid,
 ) -> anyhow::Result<()> {
+    let _slot = EXPORT_SLOTS
+        .acquire()
+        .await
+        .context("Export worker slots closed")?;
+
     // Mark job as processing
     sqlx::query("UPDATE data_export_jobs SET status = 'processing' WHERE id = $1")
         .bind(job_id)
@@ -203,6 +243,34 @@ pub async fn process_export_job(
                 "Export job completed"
             );
 
+            // Notify open sessions with a download link (best-effort, non-fatal)
+            match presign_download(s3, &s3_key, expires_at).await {
+                Ok((download_url, download_url_expires_at)) => {
+                    let event = ServerEvent::DataExportReady {
+                        job_id,
+                        download_url,
+                        download_url_expires_at,
+                        file_size_bytes: file_size,
+                    };
+                    if let Err(e) = broadcast_to_user(redis, user_id, &event).await {
+                        tracing::warn!(
+                            job_id = %job_id,
+                            user_id = %user_id,
+                            error = %e,
+                            "Failed to send data export ready event"
+                        );
+                    }
+                }
+                Err(e) => {
+                    tracing::warn!(
+                        job_id = %job_id,
+                        user_id = %user_id,
+                        error = %e,
+                        "Failed to presign export download URL"
+                    );
+                }
+            }
+
             // Send email notification if configured (best-effort, non-fatal)
             if let Some(email) = email_service {
                 match crate::db::find_user_by_id(pool, user_id).await {
@@ -259,6 +327,19 @@ pub async fn process_export_job(
                 );
             }
 
+            let event = ServerEvent::DataExportFailed {
+                job_id,
+                error_message: e.to_string(),
+            };
+            if let Err(ws_err) = broadcast_to_user(redis, user_id, &event).await {
+                tracing::warn!(
+                    job_id = %job_id,
+                    user_id = %user_id,
+                    error = %ws_err,
+                    "Failed to send data export failed event"
+                );
+            }
+
             return Err(e);
         }
     }
@@ -290,16 +371,20 @@ async fn build_export_archive(
     let user = crate::db::find_user_by_id(pool, user_id)
         .await?
         .ok_or_else(|| anyhow::anyhow!("User not found"))?;
+    let username_history = crate::db::list_username_history(pool, user_id).await?;
+    let linked_identities = crate::db::list_user_auth_methods(pool, user_id).await?;
 
     let profile = ExportProfile {
         id: user.id.to_string(),
         username: user.username.clone(),
         display_name: user.display_name,
         email: user.email,
-        auth_method: format!("{:?}", user.auth_method),
+        has_password: user.password_hash.is_some(),
+        linked_identities,
         avatar_url: user.avatar_url,
         is_bot: user.is_bot,
         created_at: user.created_at.to_rfc3339(),
+        username_history,
     };
 
     zip.start_file("profile.json", options)?;
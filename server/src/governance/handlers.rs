@@ -13,6 +13,7 @@ use super::types::{
 };
 use crate::api::AppState;
 use crate::auth::{verify_password, AuthUser};
+use crate::chat::S3Client;
 use crate::db;
 
 // ============================================================================
@@ -26,10 +27,42 @@ const STALE_EXPORT_JOB_THRESHOLD: Duration = Duration::hours(1);
 // Data Export Handlers
 // ============================================================================
 
+/// Build the API response for an export job, with a presigned download URL
+/// when the archive is completed and has not expired.
+async fn export_job_response(s3: Option<&S3Client>, job: db::DataExportJob) -> ExportJobResponse {
+    let mut download = None;
+    if let (Some(s3), Some(s3_key), Some(expires_at)) = (s3, job.s3_key.as_deref(), job.expires_at)
+    {
+        if job.status == "completed" && expires_at > Utc::now() {
+            match super::export::presign_download(s3, s3_key, expires_at).await {
+                Ok(link) => download = Some(link),
+                Err(e) => {
+                    tracing::warn!(job_id = %job.id, error = %e, "Failed to presign export download URL");
+                }
+            }
+        }
+    }
+    let (download_url, download_url_expires_at) = download.unzip();
+
+    ExportJobResponse {
+        id: job.id,
+        status: job.status,
+        file_size_bytes: job.file_size_bytes,
+        expires_at: job.expires_at,
+        error_message: job.error_message,
+        created_at: job.created_at,
+        completed_at: job.completed_at,
+        download_url,
+        download_url_expires_at,
+    }
+}
+
 /// Request a data export.
 ///
-/// Creates a background job to gather all user data into a downloadable archive.
-/// Only one pending/processing export per user is allowed.
+/// Creates a background job to gather all user data into a downloadable archive
+/// and notifies the user's sessions with a `data_export_ready` event when done.
+/// Only one pending/processing export per user is allowed; jobs queue while the
+/// server-wide export workers are busy. Poll `GET /api/me/data-export` for status.
 #[utoipa::path(
     post,
     path = "/api/me/data-export",
@@ -98,6 +131,7 @@ pub async fn request_export(
 
     // Spawn background export worker
     let pool = state.db.clone();
+    let redis = state.redis.clone();
     let s3 = s3.clone();
     let email_service = state.email.clone();
     let job_id = job.id;
@@ -105,7 +139,8 @@ pub async fn request_export(
 
     tokio::spawn(async move {
         if let Err(e) =
-            super::export::process_export_job(&pool, &s3, &email_service, job_id, user_id).await
+            super::export::process_export_job(&pool, &redis, &s3, &email_service, job_id, user_id)
+                .await
         {
             tracing::error!(
                 job_id = %job_id,
@@ -116,20 +151,14 @@ pub async fn request_export(
         }
     });
 
-    let response = ExportJobResponse {
-        id: job.id,
-        status: job.status,
-        file_size_bytes: job.file_size_bytes,
-        expires_at: job.expires_at,
-        error_message: job.error_message,
-        created_at: job.created_at,
-        completed_at: job.completed_at,
-    };
+    let response = export_job_response(None, job).await;
 
     Ok((StatusCode::CREATED, Json(response)))
 }
 
 /// Get the status of the most recent export job.
+///
+/// Completed, unexpired jobs include a presigned `download_url`.
 #[utoipa::path(
     get,
     path = "/api/me/data-export",
@@ -154,15 +183,7 @@ pub async fn get_export_status(
     .await?
     .ok_or(GovError::ExportNotFound)?;
 
-    Ok(Json(ExportJobResponse {
-        id: job.id,
-        status: job.status,
-        file_size_bytes: job.file_size_bytes,
-        expires_at: job.expires_at,
-        error_message: job.error_message,
-        created_at: job.created_at,
-        completed_at: job.completed_at,
-    }))
+    Ok(Json(export_job_response(state.s3.as_ref(), job).await))
 }
 
 /// Download the completed export archive.
@@ -267,25 +288,17 @@ pub async fn request_deletion(
         return Err(GovError::DeletionAlreadyScheduled);
     }
 
-    // Verify password (local auth) or confirmation (OIDC)
-    match user.auth_method {
-        db::AuthMethod::Local => {
-            let password = body
-                .password
-                .as_deref()
-                .ok_or(GovError::Validation("Password is required".to_string()))?;
-            let password_hash = user
-                .password_hash
-                .as_deref()
-                .ok_or(GovError::PasswordInvalid)?;
-            let valid =
-                verify_password(password, password_hash).map_err(|_| GovError::PasswordInvalid)?;
-            if !valid {
-                return Err(GovError::PasswordInvalid);
-            }
-        }
-        db::AuthMethod::Oidc => {
-            // OIDC users don't have passwords — confirmation string is sufficient
+    // Verify password if one is linked; for accounts that only sign in
+    // through external identities the confirmation string is sufficient
+    if let Some(password_hash) = user.password_hash.as_deref() {
+        let password = body
+            .password
+            .as_deref()
+            .ok_or(GovError::Validation("Password is required".to_string()))?;
+        let valid =
+            verify_password(password, password_hash).map_err(|_| GovError::PasswordInvalid)?;
+        if !valid {
+            return Err(GovError::PasswordInvalid);
         }
     }
 
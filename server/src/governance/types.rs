@@ -21,6 +21,10 @@ pub struct ExportJobResponse {
     pub created_at: DateTime<Utc>,
     /// When the job completed (when completed).
     pub completed_at: Option<DateTime<Utc>>,
+    /// Presigned archive download URL (when completed and not expired).
+    pub download_url: Option<String>,
+    /// When `download_url` stops working.
+    pub download_url_expires_at: Option<DateTime<Utc>>,
 }
 
 /// Request to delete an account.
@@ -0,0 +1,158 @@
+//! Guild Audit Log
+//!
+//! Administrative actions inside a guild (settings, roles, channels, bans,
+//! timeouts, invites, filter config) are recorded in `guild_audit_log` via
+//! [`record`]. Members with `VIEW_AUDIT_LOG` can page through the log and
+//! filter it by actor, action and time range, and guilds can stream new
+//! entries to an external endpoint (see [`super::log_stream`]).
+
+use axum::extract::{Path, Query, State};
+use axum::Json;
+use chrono::{DateTime, Utc};
+use sqlx::{Postgres, QueryBuilder};
+use uuid::Uuid;
+
+use super::bans::require_moderator;
+use super::handlers::GuildError;
+use super::types::{AuditLogQuery, GuildAuditLogEntry, GuildAuditLogPage};
+use crate::api::AppState;
+use crate::auth::AuthUser;
+use crate::permissions::GuildPermissions;
+
+/// Record an administrative action in the guild audit log.
+///
+/// The entry is also streamed to the guild's log stream, if it has one.
+/// Never fails the calling request: a failed insert is logged and dropped.
+pub async fn record(
+    state: &AppState,
+    guild_id: Uuid,
+    actor_id: Uuid,
+    action: &str,
+    target_type: Option<&str>,
+    target_id: Option<Uuid>,
+    changes: Option<serde_json::Value>,
+) {
+    let result: Result<(Uuid, Option<String>, DateTime<Utc>), _> = sqlx::query_as(
+        r"INSERT INTO guild_audit_log (guild_id, actor_id, action, target_type, target_id, changes)
+          VALUES ($1, $2, $3, $4, $5, $6)
+          RETURNING id, (SELECT username FROM users WHERE id = $2), created_at",
+    )
+    .bind(guild_id)
+    .bind(actor_id)
+    .bind(action)
+    .bind(target_type)
+    .bind(target_id)
+    .bind(&changes)
+    .fetch_one(&state.db)
+    .await;
+
+    let (id, actor_username, created_at) = match result {
+        Ok(row) => row,
+        Err(e) => {
+            tracing::warn!(error = %e, guild_id = %guild_id, action, "Failed to write guild audit log");
+            return;
+        }
+    };
+
+    let entry = GuildAuditLogEntry {
+        id,
+        actor_id: Some(actor_id),
+        actor_username,
+        action: action.to_string(),
+        target_type: target_type.map(str::to_string),
+        target_id,
+        changes,
+        created_at,
+    };
+    let payload = serde_json::json!({ "guild_id": guild_id, "entry": entry });
+    let (db, redis) = (state.db.clone(), state.redis.clone());
+    tokio::spawn(async move {
+        crate::webhooks::dispatch::dispatch_guild_log_event(&db, &redis, guild_id, id, payload)
+            .await;
+    });
+}
+
+/// Append the `WHERE` clause shared by the count and page queries.
+fn push_filters(builder: &mut QueryBuilder<'_, Postgres>, guild_id: Uuid, query: &AuditLogQuery) {
+    builder.push(" WHERE a.guild_id = ").push_bind(guild_id);
+    if let Some(actor_id) = query.actor_id {
+        builder.push(" AND a.actor_id = ").push_bind(actor_id);
+    }
+    if let Some(action_type) = &query.action_type {
+        builder
+            .push(" AND a.action = ")
+            .push_bind(action_type.clone());
+    } else if let Some(action) = &query.action {
+        builder
+            .push(" AND starts_with(a.action, ")
+            .push_bind(action.clone())
+            .push(")");
+    }
+    if let Some(from) = query.from_date {
+        builder.push(" AND a.created_at >= ").push_bind(from);
+    }
+    if let Some(to) = query.to_date {
+        builder.push(" AND a.created_at <= ").push_bind(to);
+    }
+}
+
+/// List the guild audit log (requires `VIEW_AUDIT_LOG`)
+#[utoipa::path(
+    get,
+    path = "/api/guilds/{id}/audit-log",
+    tag = "guilds",
+    params(("id" = Uuid, Path, description = "Guild ID"), AuditLogQuery),
+    responses((status = 200, body = GuildAuditLogPage)),
+    security(("bearer_auth" = []))
+)]
+#[tracing::instrument(skip(state))]
+pub async fn list_audit_log(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(guild_id): Path<Uuid>,
+    Query(query): Query<AuditLogQuery>,
+) -> Result<Json<GuildAuditLogPage>, GuildError> {
+    require_moderator(&state, guild_id, auth.id, GuildPermissions::VIEW_AUDIT_LOG).await?;
+
+    if let (Some(from), Some(to)) = (query.from_date, query.to_date) {
+        if from > to {
+            return Err(GuildError::Validation(
+                "from_date must not be after to_date".to_string(),
+            ));
+        }
+    }
+
+    let limit = query.limit.clamp(1, 100);
+    let offset = query.offset.max(0);
+
+    let mut count_builder = QueryBuilder::new("SELECT COUNT(*) FROM guild_audit_log a");
+    push_filters(&mut count_builder, guild_id, &query);
+    let total: i64 = count_builder
+        .build_query_scalar()
+        .fetch_one(&state.db)
+        .await?;
+
+    let mut builder = QueryBuilder::new(
+        "SELECT a.id, a.actor_id, u.username AS actor_username, a.action, \
+         a.target_type, a.target_id, a.changes, a.created_at \
+         FROM guild_audit_log a \
+         LEFT JOIN users u ON u.id = a.actor_id",
+    );
+    push_filters(&mut builder, guild_id, &query);
+    builder
+        .push(" ORDER BY a.created_at DESC, a.id DESC LIMIT ")
+        .push_bind(limit)
+        .push(" OFFSET ")
+        .push_bind(offset);
+    let items = builder
+        .build_query_as::<GuildAuditLogEntry>()
+        .fetch_all(&state.db)
+        .await?;
+
+    Ok(Json(GuildAuditLogPage {
+        items,
+        total,
+        limit,
+        offset,
+    }))
+}
@@ -0,0 +1,317 @@
+//! Mention Autocomplete
+//!
+//! Prefix search over guild members, channels, and roles for the message
+//! composer. Results are permission-filtered and ranked so recently active
+//! members and recently read channels come first.
+
+use axum::extract::{Path, Query, State};
+use axum::Json;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::handlers::GuildError;
+use crate::api::AppState;
+use crate::auth::AuthUser;
+use crate::db::{self, ChannelType};
+use crate::permissions::{require_guild_permission, GuildPermissions, PermissionError};
+
+/// Maximum accepted query length.
+const MAX_QUERY_LENGTH: usize = 64;
+
+/// Maximum number of suggestions per request.
+const MAX_LIMIT: i64 = 25;
+
+/// How far back message activity counts towards member ranking.
+const RECENT_ACTIVITY_DAYS: i32 = 30;
+
+// ============================================================================
+// Request/Response Types
+// ============================================================================
+
+/// Kind of entity to suggest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum AutocompleteType {
+    User,
+    Channel,
+    Role,
+}
+
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+pub struct AutocompleteQuery {
+    /// Prefix to match (empty returns the top-ranked suggestions)
+    #[serde(default)]
+    pub q: String,
+    /// What to suggest: `user`, `channel`, or `role`
+    #[serde(rename = "type")]
+    pub kind: AutocompleteType,
+    /// Maximum results to return (default 10, max 25)
+    #[serde(default = "default_limit")]
+    pub limit: i64,
+}
+
+const fn default_limit() -> i64 {
+    10
+}
+
+/// A single mention suggestion.
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum AutocompleteItem {
+    User {
+        id: Uuid,
+        username: String,
+        display_name: String,
+        nickname: Option<String>,
+        avatar_url: Option<String>,
+    },
+    Channel {
+        id: Uuid,
+        name: String,
+        channel_type: ChannelType,
+    },
+    Role {
+        id: Uuid,
+        name: String,
+        color: Option<String>,
+    },
+}
+
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct AutocompleteResponse {
+    pub results: Vec<AutocompleteItem>,
+}
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+/// Build an ILIKE prefix pattern, escaping metacharacters in user input.
+fn prefix_pattern(input: &str) -> String {
+    let escaped = input
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_");
+    format!("{escaped}%")
+}
+
+// ============================================================================
+// Handler
+// ============================================================================
+
+/// Suggest members, channels, or roles for mentions.
+///
+/// `GET /api/guilds/:guild_id/autocomplete?q=...&type=user|channel|role`
+#[utoipa::path(
+    get,
+    path = "/api/guilds/{id}/autocomplete",
+    tag = "guilds",
+    params(("id" = Uuid, Path, description = "Guild ID"), AutocompleteQuery),
+    responses((status = 200, body = AutocompleteResponse)),
+    security(("bearer_auth" = []))
+)]
+#[tracing::instrument(skip(state))]
+pub async fn autocomplete(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(guild_id): Path<Uuid>,
+    Query(query): Query<AutocompleteQuery>,
+) -> Result<Json<AutocompleteResponse>, GuildError> {
+    let term = query.q.trim().trim_start_matches(['@', '#']);
+    if term.chars().count() > MAX_QUERY_LENGTH {
+        return Err(GuildError::Validation(format!(
+            "Query must not exceed {MAX_QUERY_LENGTH} characters"
+        )));
+    }
+    let limit = query.limit.clamp(1, MAX_LIMIT);
+    let pattern = prefix_pattern(term);
+
+    let ctx = require_guild_permission(&state.db, guild_id, auth.id, GuildPermissions::empty())
+        .await
+        .map_err(|e| match e {
+            PermissionError::NotGuildMember => GuildError::Forbidden,
+            other => GuildError::Permission(other),
+        })?;
+
+    let results = match query.kind {
+        AutocompleteType::User => {
+            let accessible = accessible_channel_ids(&state, guild_id, auth.id).await?;
+            suggest_users(&state, guild_id, &accessible, &pattern, term, limit).await?
+        }
+        AutocompleteType::Channel => {
+            let accessible = accessible_channel_ids(&state, guild_id, auth.id).await?;
+            suggest_channels(&state, auth.id, &accessible, &pattern, term, limit).await?
+        }
+        AutocompleteType::Role => {
+            // Suggesting @everyone follows the same rule as sending it
+            let include_default = ctx.has_permission(GuildPermissions::MENTION_EVERYONE);
+            suggest_roles(&state, guild_id, include_default, &pattern, term, limit).await?
+        }
+    };
+
+    Ok(Json(AutocompleteResponse { results }))
+}
+
+/// Channels in the guild the user can view.
+async fn accessible_channel_ids(
+    state: &AppState,
+    guild_id: Uuid,
+    user_id: Uuid,
+) -> Result<Vec<Uuid>, GuildError> {
+    let channel_ids: Vec<Uuid> = db::get_guild_channels(&state.db, guild_id)
+        .await?
+        .iter()
+        .map(|c| c.id)
+        .collect();
+    crate::permissions::filter_accessible_channels(&state.db, guild_id, user_id, &channel_ids)
+        .await
+        .map_err(|e| match e {
+            PermissionError::NotGuildMember => GuildError::Forbidden,
+            other => GuildError::Permission(other),
+        })
+}
+
+/// Members matching by username, display name, or nickname.
+///
+/// Exact matches rank first, then members by their latest message in a
+/// channel the requester can see.
+async fn suggest_users(
+    state: &AppState,
+    guild_id: Uuid,
+    accessible_channel_ids: &[Uuid],
+    pattern: &str,
+    term: &str,
+    limit: i64,
+) -> Result<Vec<AutocompleteItem>, GuildError> {
+    let rows: Vec<(Uuid, String, String, Option<String>, Option<String>)> = sqlx::query_as(
+        r"SELECT u.id, u.username, u.display_name, gm.nickname, u.avatar_url
+          FROM guild_members gm
+          INNER JOIN users u ON u.id = gm.user_id
+          LEFT JOIN LATERAL (
+              SELECT m.created_at
+              FROM messages m
+              WHERE m.user_id = u.id
+                AND m.channel_id = ANY($3)
+                AND m.deleted_at IS NULL
+                AND m.created_at > NOW() - make_interval(days => $5)
+              ORDER BY m.created_at DESC
+              LIMIT 1
+          ) recent ON TRUE
+          WHERE gm.guild_id = $1
+            AND (u.username ILIKE $2 OR u.display_name ILIKE $2 OR gm.nickname ILIKE $2)
+          ORDER BY
+              (LOWER(u.username) = LOWER($4)
+                  OR LOWER(u.display_name) = LOWER($4)
+                  OR LOWER(gm.nickname) IS NOT DISTINCT FROM LOWER($4)) DESC,
+              recent.created_at DESC NULLS LAST,
+              u.username
+          LIMIT $6",
+    )
+    .bind(guild_id)
+    .bind(pattern)
+    .bind(accessible_channel_ids)
+    .bind(term)
+    .bind(RECENT_ACTIVITY_DAYS)
+    .bind(limit)
+    .fetch_all(&state.db)
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(
+            |(id, username, display_name, nickname, avatar_url)| AutocompleteItem::User {
+                id,
+                username,
+                display_name,
+                nickname,
+                avatar_url,
+            },
+        )
+        .collect())
+}
+
+/// Viewable channels matching by name, most recently read first.
+async fn suggest_channels(
+    state: &AppState,
+    user_id: Uuid,
+    accessible_channel_ids: &[Uuid],
+    pattern: &str,
+    term: &str,
+    limit: i64,
+) -> Result<Vec<AutocompleteItem>, GuildError> {
+    if accessible_channel_ids.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let rows: Vec<(Uuid, String, ChannelType)> = sqlx::query_as(
+        r"SELECT c.id, c.name, c.channel_type
+          FROM channels c
+          LEFT JOIN channel_read_state crs ON crs.channel_id = c.id AND crs.user_id = $3
+          WHERE c.id = ANY($1) AND c.name ILIKE $2
+          ORDER BY
+              (LOWER(c.name) = LOWER($4)) DESC,
+              crs.last_read_at DESC NULLS LAST,
+              c.position,
+              c.name
+          LIMIT $5",
+    )
+    .bind(accessible_channel_ids)
+    .bind(pattern)
+    .bind(user_id)
+    .bind(term)
+    .bind(limit)
+    .fetch_all(&state.db)
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|(id, name, channel_type)| AutocompleteItem::Channel {
+            id,
+            name,
+            channel_type,
+        })
+        .collect())
+}
+
+/// Roles matching by name (ignoring the `@` of `@everyone`), highest position first.
+async fn suggest_roles(
+    state: &AppState,
+    guild_id: Uuid,
+    include_default: bool,
+    pattern: &str,
+    term: &str,
+    limit: i64,
+) -> Result<Vec<AutocompleteItem>, GuildError> {
+    let rows: Vec<(Uuid, String, Option<String>)> = sqlx::query_as(
+        r"SELECT id, name, color
+          FROM guild_roles
+          WHERE guild_id = $1 AND LTRIM(name, '@') ILIKE $2 AND ($3 OR NOT is_default)
+          ORDER BY (LOWER(LTRIM(name, '@')) = LOWER($4)) DESC, position DESC, name
+          LIMIT $5",
+    )
+    .bind(guild_id)
+    .bind(pattern)
+    .bind(include_default)
+    .bind(term)
+    .bind(limit)
+    .fetch_all(&state.db)
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|(id, name, color)| AutocompleteItem::Role { id, name, color })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_prefix_pattern_escapes_metacharacters() {
+        assert_eq!(prefix_pattern("ali"), "ali%");
+        assert_eq!(prefix_pattern(""), "%");
+        assert_eq!(prefix_pattern("50%_off"), "50\\%\\_off%");
+        assert_eq!(prefix_pattern("a\\b"), "a\\\\b%");
+    }
+}
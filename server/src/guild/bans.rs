@@ -0,0 +1,421 @@
+//! Guild Ban and Kick Handlers
+//!
+//! Members with `BAN_MEMBERS` can ban users (members or not) from a guild,
+//! optionally deleting their recent messages; members with `KICK_MEMBERS` can
+//! kick. Both respect the role hierarchy: the owner can never be moderated and
+//! targets must rank strictly below the moderator. Bans are enforced by every
+//! join path (invites and discovery).
+
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use axum::Json;
+use uuid::Uuid;
+
+use super::audit;
+use super::handlers::GuildError;
+use super::types::{BanMemberResponse, CreateBanRequest, GuildBan, GuildBanList, ListBansQuery};
+use crate::api::AppState;
+use crate::auth::AuthUser;
+use crate::db;
+use crate::permissions::{
+    can_moderate_member, get_member_highest_role, require_guild_permission, GuildPermissions,
+    MemberPermissionContext, PermissionError,
+};
+use crate::ws::{broadcast_to_channel, ServerEvent};
+
+/// Longest message prune window (7 days).
+const MAX_DELETE_MESSAGE_SECONDS: i64 = 7 * 24 * 60 * 60;
+
+/// Maximum ban reason length.
+const MAX_REASON_LENGTH: usize = 512;
+
+/// Load the caller's permission context, requiring `permission`.
+pub(crate) async fn require_moderator(
+    state: &AppState,
+    guild_id: Uuid,
+    user_id: Uuid,
+    permission: GuildPermissions,
+) -> Result<MemberPermissionContext, GuildError> {
+    require_guild_permission(&state.db, guild_id, user_id, permission)
+        .await
+        .map_err(|e| match e {
+            PermissionError::NotGuildMember => GuildError::Forbidden,
+            other => GuildError::Permission(other),
+        })
+}
+
+/// Check that the moderator outranks `target_id`.
+///
+/// Non-members have no roles and can always be moderated.
+pub(crate) async fn require_outranks(
+    state: &AppState,
+    ctx: &MemberPermissionContext,
+    guild_id: Uuid,
+    target_id: Uuid,
+) -> Result<(), GuildError> {
+    if !db::is_guild_member(&state.db, guild_id, target_id).await? {
+        return Ok(());
+    }
+
+    let actor_position = if ctx.is_owner {
+        -1
+    } else {
+        ctx.highest_role_position.unwrap_or(i32::MAX)
+    };
+    let target_position = get_member_highest_role(&state.db, guild_id, target_id)
+        .await?
+        .unwrap_or(i32::MAX);
+
+    can_moderate_member(
+        actor_position,
+        target_position,
+        target_id == ctx.guild_owner_id,
+    )
+    .map_err(GuildError::Permission)
+}
+
+/// Require `KICK_MEMBERS` and that the caller outranks the target.
+pub(crate) async fn require_can_kick(
+    state: &AppState,
+    guild_id: Uuid,
+    actor_id: Uuid,
+    target_id: Uuid,
+) -> Result<(), GuildError> {
+    let ctx = require_moderator(state, guild_id, actor_id, GuildPermissions::KICK_MEMBERS).await?;
+    require_outranks(state, &ctx, guild_id, target_id).await
+}
+
+/// Dispatch `MemberLeft` to the bot ecosystem after a member was removed.
+pub(crate) fn dispatch_member_left(state: &AppState, guild_id: Uuid, user_id: Uuid) {
+    let db = state.db.clone();
+    let redis = state.redis.clone();
+    tokio::spawn(async move {
+        crate::ws::bot_events::publish_member_left(&db, &redis, guild_id, user_id).await;
+        crate::webhooks::dispatch::dispatch_guild_event(
+            &db,
+            &redis,
+            guild_id,
+            crate::webhooks::events::BotEventType::MemberLeft,
+            serde_json::json!({ "guild_id": guild_id, "user_id": user_id }),
+        )
+        .await;
+    });
+}
+
+/// Ban a user and remove them from the guild's members.
+///
+/// Returns true if the user was a member. Re-banning replaces the existing
+/// ban. Callers check permissions and write the audit entry.
+pub(crate) async fn insert_ban(
+    state: &AppState,
+    guild_id: Uuid,
+    user_id: Uuid,
+    banned_by: Option<Uuid>,
+    reason: &str,
+    expires_at: Option<chrono::DateTime<chrono::Utc>>,
+) -> Result<bool, sqlx::Error> {
+    let mut tx = state.db.begin().await?;
+
+    sqlx::query(
+        r"INSERT INTO guild_bans (guild_id, user_id, banned_by, reason, expires_at)
+           VALUES ($1, $2, $3, $4, $5)
+           ON CONFLICT (guild_id, user_id) DO UPDATE SET
+               banned_by = $3,
+               reason = $4,
+               expires_at = $5,
+               created_at = NOW()",
+    )
+    .bind(guild_id)
+    .bind(user_id)
+    .bind(banned_by)
+    .bind(reason)
+    .bind(expires_at)
+    .execute(&mut *tx)
+    .await?;
+
+    let removed = sqlx::query("DELETE FROM guild_members WHERE guild_id = $1 AND user_id = $2")
+        .bind(guild_id)
+        .bind(user_id)
+        .execute(&mut *tx)
+        .await?
+        .rows_affected()
+        > 0;
+
+    tx.commit().await?;
+
+    if removed {
+        dispatch_member_left(state, guild_id, user_id);
+    }
+
+    Ok(removed)
+}
+
+/// Validate a ban request.
+fn validate_ban(body: &CreateBanRequest) -> Result<(), GuildError> {
+    if body.reason.chars().count() > MAX_REASON_LENGTH {
+        return Err(GuildError::Validation(format!(
+            "Reason must be at most {MAX_REASON_LENGTH} characters"
+        )));
+    }
+    if !(0..=MAX_DELETE_MESSAGE_SECONDS).contains(&body.delete_message_seconds) {
+        return Err(GuildError::Validation(format!(
+            "delete_message_seconds must be between 0 and {MAX_DELETE_MESSAGE_SECONDS}"
+        )));
+    }
+    if body.expires_at.is_some_and(|at| at <= chrono::Utc::now()) {
+        return Err(GuildError::Validation(
+            "expires_at must be in the future".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Soft-delete the user's guild messages from the last `seconds` seconds.
+///
+/// Returns the number of deleted messages.
+async fn prune_messages(
+    state: &AppState,
+    guild_id: Uuid,
+    user_id: Uuid,
+    seconds: i64,
+) -> Result<i64, GuildError> {
+    let since = chrono::Utc::now() - chrono::Duration::seconds(seconds);
+    let deleted: Vec<(Uuid, Uuid, Option<Uuid>)> = sqlx::query_as(
+        r"UPDATE messages
+           SET deleted_at = NOW(), content = '[deleted]'
+           WHERE user_id = $1
+             AND channel_id IN (SELECT id FROM channels WHERE guild_id = $2)
+             AND created_at > $3
+             AND deleted_at IS NULL
+           RETURNING id, channel_id, parent_id",
+    )
+    .bind(user_id)
+    .bind(guild_id)
+    .bind(since)
+    .fetch_all(&state.db)
+    .await?;
+
+    for (message_id, channel_id, parent_id) in &deleted {
+        if let Some(parent_id) = parent_id {
+            if let Err(err) = db::decrement_thread_counters(&state.db, *parent_id).await {
+                tracing::warn!(%err, %parent_id, "Failed to update thread counters after prune");
+            }
+        }
+        let event = ServerEvent::MessageDelete {
+            channel_id: *channel_id,
+            message_id: *message_id,
+        };
+        if let Err(err) = broadcast_to_channel(&state.redis, *channel_id, &event).await {
+            tracing::warn!(%err, %channel_id, "Failed to broadcast pruned message deletion");
+        }
+    }
+
+    Ok(deleted.len() as i64)
+}
+
+/// List active bans (requires `BAN_MEMBERS`)
+#[utoipa::path(
+    get,
+    path = "/api/guilds/{id}/bans",
+    tag = "guilds",
+    params(("id" = Uuid, Path, description = "Guild ID"), ListBansQuery),
+    responses((status = 200, body = GuildBanList)),
+    security(("bearer_auth" = []))
+)]
+#[tracing::instrument(skip(state))]
+pub async fn list_bans(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(guild_id): Path<Uuid>,
+    Query(query): Query<ListBansQuery>,
+) -> Result<Json<GuildBanList>, GuildError> {
+    require_moderator(&state, guild_id, auth.id, GuildPermissions::BAN_MEMBERS).await?;
+
+    let limit = query.limit.clamp(1, 100);
+    let offset = query.offset.max(0);
+
+    let total: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM guild_bans WHERE guild_id = $1 AND (expires_at IS NULL OR expires_at > NOW())",
+    )
+    .bind(guild_id)
+    .fetch_one(&state.db)
+    .await?;
+
+    let items = sqlx::query_as::<_, GuildBan>(
+        r"SELECT b.user_id, u.username, u.display_name, u.avatar_url,
+                 b.banned_by, b.reason, b.expires_at, b.created_at
+           FROM guild_bans b
+           INNER JOIN users u ON u.id = b.user_id
+           WHERE b.guild_id = $1 AND (b.expires_at IS NULL OR b.expires_at > NOW())
+           ORDER BY b.created_at DESC, b.user_id
+           LIMIT $2 OFFSET $3",
+    )
+    .bind(guild_id)
+    .bind(limit)
+    .bind(offset)
+    .fetch_all(&state.db)
+    .await?;
+
+    Ok(Json(GuildBanList {
+        items,
+        total,
+        limit,
+        offset,
+    }))
+}
+
+/// Ban a user from the guild (requires `BAN_MEMBERS`)
+#[utoipa::path(
+    post,
+    path = "/api/guilds/{id}/bans",
+    tag = "guilds",
+    params(("id" = Uuid, Path, description = "Guild ID")),
+    request_body = CreateBanRequest,
+    responses((status = 200, body = BanMemberResponse)),
+    security(("bearer_auth" = []))
+)]
+#[tracing::instrument(skip(state, body))]
+pub async fn ban_member(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(guild_id): Path<Uuid>,
+    Json(body): Json<CreateBanRequest>,
+) -> Result<Json<BanMemberResponse>, GuildError> {
+    let ctx = require_moderator(&state, guild_id, auth.id, GuildPermissions::BAN_MEMBERS).await?;
+    validate_ban(&body)?;
+
+    let user_id = body.user_id;
+    if user_id == auth.id {
+        return Err(GuildError::Validation(
+            "Cannot ban yourself from the guild".to_string(),
+        ));
+    }
+
+    let user_exists: bool = sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM users WHERE id = $1)")
+        .bind(user_id)
+        .fetch_one(&state.db)
+        .await?;
+    if !user_exists {
+        return Err(GuildError::Validation("User not found".to_string()));
+    }
+
+    require_outranks(&state, &ctx, guild_id, user_id).await?;
+
+    let removed = insert_ban(
+        &state,
+        guild_id,
+        user_id,
+        Some(auth.id),
+        &body.reason,
+        body.expires_at,
+    )
+    .await?;
+
+    let deleted_messages = if body.delete_message_seconds > 0 {
+        prune_messages(&state, guild_id, user_id, body.delete_message_seconds).await?
+    } else {
+        0
+    };
+
+    audit::record(
+        &state,
+        guild_id,
+        auth.id,
+        "guild.members.banned",
+        Some("user"),
+        Some(user_id),
+        Some(serde_json::json!({
+            "reason": body.reason,
+            "expires_at": body.expires_at,
+            "deleted_messages": deleted_messages,
+        })),
+    )
+    .await;
+
+    Ok(Json(BanMemberResponse {
+        user_id,
+        removed,
+        deleted_messages,
+    }))
+}
+
+/// Lift a ban (requires `BAN_MEMBERS`)
+#[utoipa::path(
+    delete,
+    path = "/api/guilds/{id}/bans/{user_id}",
+    tag = "guilds",
+    params(
+        ("id" = Uuid, Path, description = "Guild ID"),
+        ("user_id" = Uuid, Path, description = "User ID")
+    ),
+    responses(
+        (status = 204, description = "Ban lifted"),
+        (status = 404, description = "Ban not found"),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[tracing::instrument(skip(state))]
+pub async fn unban_member(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path((guild_id, user_id)): Path<(Uuid, Uuid)>,
+) -> Result<StatusCode, GuildError> {
+    require_moderator(&state, guild_id, auth.id, GuildPermissions::BAN_MEMBERS).await?;
+
+    let result = sqlx::query("DELETE FROM guild_bans WHERE guild_id = $1 AND user_id = $2")
+        .bind(guild_id)
+        .bind(user_id)
+        .execute(&state.db)
+        .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(GuildError::NotFound);
+    }
+
+    audit::record(
+        &state,
+        guild_id,
+        auth.id,
+        "guild.members.unbanned",
+        Some("user"),
+        Some(user_id),
+        None,
+    )
+    .await;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(json: serde_json::Value) -> CreateBanRequest {
+        serde_json::from_value(json).unwrap()
+    }
+
+    #[test]
+    fn test_validate_ban() {
+        let user_id = Uuid::new_v4();
+
+        assert!(validate_ban(&request(serde_json::json!({ "user_id": user_id }))).is_ok());
+        assert!(validate_ban(&request(serde_json::json!({
+            "user_id": user_id,
+            "reason": "spam",
+            "delete_message_seconds": MAX_DELETE_MESSAGE_SECONDS,
+        })))
+        .is_ok());
+
+        for bad in [
+            serde_json::json!({ "user_id": user_id, "delete_message_seconds": -1 }),
+            serde_json::json!({ "user_id": user_id, "delete_message_seconds": MAX_DELETE_MESSAGE_SECONDS + 1 }),
+            serde_json::json!({ "user_id": user_id, "reason": "x".repeat(MAX_REASON_LENGTH + 1) }),
+            serde_json::json!({ "user_id": user_id, "expires_at": "2000-01-01T00:00:00Z" }),
+        ] {
+            assert!(matches!(
+                validate_ban(&request(bad)),
+                Err(GuildError::Validation(_))
+            ));
+        }
+    }
+}
@@ -0,0 +1,297 @@
+//! Channel Positions
+//!
+//! Bulk placement of a guild's channels and categories for drag-and-drop
+//! reordering. All moves in a request are validated and applied in one
+//! transaction, then announced with a single `ChannelPositionsUpdate` event.
+//!
+//! Channels only move into categories of their own guild, and categories
+//! keep the two-level nesting limit (see `categories.rs`).
+
+use std::collections::{HashMap, HashSet};
+
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::Json;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::audit;
+use super::handlers::GuildError;
+use crate::api::AppState;
+use crate::auth::AuthUser;
+use crate::permissions::{require_guild_permission, GuildPermissions, PermissionError};
+use crate::ws::{broadcast_to_guild, ServerEvent};
+
+/// Maximum number of channels plus categories moved in one request.
+const MAX_POSITION_UPDATES: usize = 500;
+
+/// Placement of a channel.
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct ChannelPlacement {
+    pub id: Uuid,
+    pub position: i32,
+    /// Category the channel sits in (`null` or omitted for top level).
+    #[serde(default)]
+    pub category_id: Option<Uuid>,
+}
+
+/// Placement of a category.
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct CategoryPlacement {
+    pub id: Uuid,
+    pub position: i32,
+    /// Parent category (`null` or omitted for top level).
+    #[serde(default)]
+    pub parent_id: Option<Uuid>,
+}
+
+/// Request to move channels and categories.
+#[derive(Debug, Deserialize, utoipa::ToSchema)]
+pub struct UpdateChannelPositionsRequest {
+    #[serde(default)]
+    pub channels: Vec<ChannelPlacement>,
+    #[serde(default)]
+    pub categories: Vec<CategoryPlacement>,
+}
+
+/// Check a batch of placements against the guild's categories.
+///
+/// `categories` maps each of the guild's categories to its current parent.
+fn validate_placements(
+    categories: &HashMap<Uuid, Option<Uuid>>,
+    channels: &[ChannelPlacement],
+    category_moves: &[CategoryPlacement],
+) -> Result<(), String> {
+    if channels.len() + category_moves.len() > MAX_POSITION_UPDATES {
+        return Err(format!(
+            "Cannot move more than {MAX_POSITION_UPDATES} channels and categories at once"
+        ));
+    }
+
+    let mut seen = HashSet::new();
+    if !channels.iter().all(|ch| seen.insert(ch.id)) {
+        return Err("Each channel may only be listed once".to_string());
+    }
+    for ch in channels {
+        if let Some(category_id) = ch.category_id {
+            if !categories.contains_key(&category_id) {
+                return Err(format!("Category {category_id} not found in this guild"));
+            }
+        }
+    }
+
+    // Nesting is checked against the tree as it will be after the update
+    let mut parents = categories.clone();
+    let mut seen = HashSet::new();
+    for cat in category_moves {
+        if !seen.insert(cat.id) {
+            return Err("Each category may only be listed once".to_string());
+        }
+        if !categories.contains_key(&cat.id) {
+            return Err(format!("Category {} not found in this guild", cat.id));
+        }
+        parents.insert(cat.id, cat.parent_id);
+    }
+    for (id, parent_id) in &parents {
+        let Some(parent_id) = parent_id else {
+            continue;
+        };
+        match parents.get(parent_id) {
+            None => {
+                return Err(format!("Parent category {parent_id} not found"));
+            }
+            _ if parent_id == id => {
+                return Err("A category cannot be its own parent".to_string());
+            }
+            Some(Some(_)) => {
+                return Err("Cannot nest more than 2 levels".to_string());
+            }
+            Some(None) => {}
+        }
+    }
+
+    Ok(())
+}
+
+/// Move channels and categories, then broadcast the new placement.
+pub(super) async fn apply_positions(
+    state: &AppState,
+    guild_id: Uuid,
+    actor_id: Uuid,
+    channels: Vec<ChannelPlacement>,
+    categories: Vec<CategoryPlacement>,
+) -> Result<(), GuildError> {
+    if channels.is_empty() && categories.is_empty() {
+        return Ok(());
+    }
+
+    let mut tx = state.db.begin().await?;
+
+    // Lock the guild's categories so the tree cannot change underneath us
+    let current: Vec<(Uuid, Option<Uuid>)> = sqlx::query_as(
+        "SELECT id, parent_id FROM channel_categories WHERE guild_id = $1 FOR UPDATE",
+    )
+    .bind(guild_id)
+    .fetch_all(&mut *tx)
+    .await?;
+    let current: HashMap<Uuid, Option<Uuid>> = current.into_iter().collect();
+
+    validate_placements(&current, &channels, &categories).map_err(GuildError::Validation)?;
+
+    for ch in &channels {
+        let updated = sqlx::query(
+            r"
+            UPDATE channels
+            SET position = $3, category_id = $4
+            WHERE id = $1 AND guild_id = $2
+            ",
+        )
+        .bind(ch.id)
+        .bind(guild_id)
+        .bind(ch.position)
+        .bind(ch.category_id)
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+        if updated == 0 {
+            return Err(GuildError::Validation(format!(
+                "Channel {} not found in this guild",
+                ch.id
+            )));
+        }
+    }
+
+    for cat in &categories {
+        sqlx::query(
+            r"
+            UPDATE channel_categories
+            SET position = $3, parent_id = $4
+            WHERE id = $1 AND guild_id = $2
+            ",
+        )
+        .bind(cat.id)
+        .bind(guild_id)
+        .bind(cat.position)
+        .bind(cat.parent_id)
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    tx.commit().await?;
+
+    if let Err(e) = broadcast_to_guild(
+        &state.redis,
+        guild_id,
+        &ServerEvent::ChannelPositionsUpdate {
+            guild_id,
+            channels: channels.clone(),
+            categories: categories.clone(),
+        },
+    )
+    .await
+    {
+        tracing::warn!(guild_id = %guild_id, error = %e, "Failed to broadcast channel positions");
+    }
+
+    audit::record(
+        state,
+        guild_id,
+        actor_id,
+        "guild.channels.reordered",
+        None,
+        None,
+        Some(serde_json::json!({
+            "channel_ids": channels.iter().map(|ch| ch.id).collect::<Vec<_>>(),
+            "category_ids": categories.iter().map(|cat| cat.id).collect::<Vec<_>>(),
+        })),
+    )
+    .await;
+
+    Ok(())
+}
+
+/// Move channels and categories in one transaction.
+///
+/// `PATCH /api/guilds/:guild_id/channels/positions`
+#[utoipa::path(
+    patch,
+    path = "/api/guilds/{id}/channels/positions",
+    tag = "guilds",
+    params(("id" = Uuid, Path, description = "Guild ID")),
+    request_body = UpdateChannelPositionsRequest,
+    responses(
+        (status = 204, description = "Positions updated"),
+        (status = 400, description = "Unknown channel or category, or invalid nesting"),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[tracing::instrument(skip(state, body))]
+pub async fn update_channel_positions(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(guild_id): Path<Uuid>,
+    Json(body): Json<UpdateChannelPositionsRequest>,
+) -> Result<StatusCode, GuildError> {
+    require_guild_permission(
+        &state.db,
+        guild_id,
+        auth.id,
+        GuildPermissions::MANAGE_CHANNELS,
+    )
+    .await
+    .map_err(|e| match e {
+        PermissionError::NotGuildMember => GuildError::Forbidden,
+        other => GuildError::Permission(other),
+    })?;
+
+    apply_positions(&state, guild_id, auth.id, body.channels, body.categories).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn channel(category_id: Option<Uuid>) -> ChannelPlacement {
+        ChannelPlacement {
+            id: Uuid::new_v4(),
+            position: 0,
+            category_id,
+        }
+    }
+
+    fn category(id: Uuid, parent_id: Option<Uuid>) -> CategoryPlacement {
+        CategoryPlacement {
+            id,
+            position: 0,
+            parent_id,
+        }
+    }
+
+    #[test]
+    fn test_validate_placements() {
+        let (top, nested, other) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let categories = HashMap::from([(top, None), (nested, Some(top)), (other, None)]);
+
+        assert!(validate_placements(&categories, &[channel(Some(nested))], &[]).is_ok());
+        // Category from another guild
+        assert!(validate_placements(&categories, &[channel(Some(Uuid::new_v4()))], &[]).is_err());
+
+        let ch = channel(None);
+        assert!(validate_placements(&categories, &[ch.clone(), ch], &[]).is_err());
+
+        // Nesting under a nested category makes three levels
+        assert!(validate_placements(&categories, &[], &[category(other, Some(nested))]).is_err());
+        // ...but is fine once that category moves to the top level
+        assert!(validate_placements(
+            &categories,
+            &[],
+            &[category(nested, None), category(other, Some(nested))]
+        )
+        .is_ok());
+        // A category with children cannot be nested
+        assert!(validate_placements(&categories, &[], &[category(top, Some(other))]).is_err());
+        assert!(validate_placements(&categories, &[], &[category(top, Some(top))]).is_err());
+    }
+}
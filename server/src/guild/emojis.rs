@@ -1,22 +1,40 @@
 //! Guild Emojis API
 //!
-//! Handlers for managing custom guild emojis.
+//! Handlers for managing custom guild emojis, plus resolution of `:name:`
+//! tokens in message content against the guild's emoji table.
 
-use axum::extract::{Multipart, Path, State};
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+use axum::extract::{Multipart, Path, Query, State};
 use axum::http::StatusCode;
 use axum::response::IntoResponse;
 use axum::routing::get;
 use axum::{Json, Router};
 use fred::interfaces::PubsubInterface;
+use serde::Deserialize;
 use serde_json::json;
 use uuid::Uuid;
 use validator::Validate;
 
 use crate::api::AppState;
 use crate::auth::AuthUser;
-use crate::guild::types::{CreateEmojiRequest, GuildEmoji, UpdateEmojiRequest};
+use crate::guild::types::{CreateEmojiRequest, GuildEmoji, MessageEmoji, UpdateEmojiRequest};
 use crate::ws::ServerEvent;
 
+/// `:name:` token referencing a custom emoji.
+static EMOJI_TOKEN_RE: LazyLock<regex::Regex> =
+    LazyLock::new(|| regex::Regex::new(r":([\w-]{2,32}):").unwrap());
+
+/// Maximum number of distinct custom emojis resolved per message.
+const MAX_EMOJIS_PER_MESSAGE: usize = 50;
+
+/// Maximum accepted search query length (emoji names are at most 32).
+const MAX_SEARCH_QUERY_LENGTH: usize = 32;
+
+/// Maximum number of search results per request.
+const MAX_SEARCH_LIMIT: i64 = 25;
+
 // ============================================================================
 // Error Types
 // ============================================================================
@@ -131,6 +149,112 @@ async fn check_guild_membership(
     Ok(result.0)
 }
 
+/// Distinct emoji names referenced as `:name:` in `content`, in order of
+/// first appearance.
+fn parse_emoji_tokens(content: &str) -> Vec<&str> {
+    let mut names: Vec<&str> = Vec::new();
+    for cap in EMOJI_TOKEN_RE.captures_iter(content) {
+        let name = cap.get(1).map_or("", |m| m.as_str());
+        if !names.contains(&name) {
+            names.push(name);
+            if names.len() == MAX_EMOJIS_PER_MESSAGE {
+                break;
+            }
+        }
+    }
+    names
+}
+
+/// Resolve the custom emojis referenced in a batch of messages.
+///
+/// Takes `(message_id, channel_id, content)` triples. Tokens are matched
+/// against the emojis of the guild each channel belongs to, so a message can
+/// only use its own guild's emojis; unknown names (and DM channels) resolve
+/// to nothing and stay plain text. Messages without resolved emojis are
+/// absent from the returned map.
+pub async fn resolve_message_emojis(
+    db: &sqlx::PgPool,
+    messages: &[(Uuid, Uuid, &str)],
+) -> Result<HashMap<Uuid, Vec<MessageEmoji>>, sqlx::Error> {
+    let tokens: Vec<(Uuid, Uuid, Vec<&str>)> = messages
+        .iter()
+        .map(|(message_id, channel_id, content)| {
+            (*message_id, *channel_id, parse_emoji_tokens(content))
+        })
+        .filter(|(_, _, names)| !names.is_empty())
+        .collect();
+    if tokens.is_empty() {
+        return Ok(HashMap::new());
+    }
+
+    let mut channel_ids: Vec<Uuid> = tokens
+        .iter()
+        .map(|(_, channel_id, _)| *channel_id)
+        .collect();
+    channel_ids.sort_unstable();
+    channel_ids.dedup();
+    let mut names: Vec<&str> = tokens
+        .iter()
+        .flat_map(|(_, _, names)| names.clone())
+        .collect();
+    names.sort_unstable();
+    names.dedup();
+
+    let rows: Vec<(Uuid, Uuid, String, String, bool)> = sqlx::query_as(
+        r"
+        SELECT c.id, e.id, e.name, e.image_url, e.animated
+        FROM guild_emojis e
+        JOIN channels c ON c.guild_id = e.guild_id
+        WHERE c.id = ANY($1) AND e.name = ANY($2)
+        ",
+    )
+    .bind(&channel_ids)
+    .bind(&names)
+    .fetch_all(db)
+    .await?;
+
+    let known: HashMap<(Uuid, String), MessageEmoji> = rows
+        .into_iter()
+        .map(|(channel_id, id, name, url, animated)| {
+            (
+                (channel_id, name.clone()),
+                MessageEmoji {
+                    id,
+                    name,
+                    url,
+                    animated,
+                },
+            )
+        })
+        .collect();
+
+    Ok(tokens
+        .into_iter()
+        .filter_map(|(message_id, channel_id, names)| {
+            let emojis: Vec<MessageEmoji> = names
+                .into_iter()
+                .filter_map(|name| known.get(&(channel_id, name.to_string())).cloned())
+                .collect();
+            (!emojis.is_empty()).then_some((message_id, emojis))
+        })
+        .collect())
+}
+
+/// Resolve the custom emojis referenced in a single message's content.
+pub async fn resolve_content_emojis(
+    db: &sqlx::PgPool,
+    channel_id: Uuid,
+    content: &str,
+) -> Result<Vec<MessageEmoji>, sqlx::Error> {
+    let message_id = Uuid::nil();
+    Ok(
+        resolve_message_emojis(db, &[(message_id, channel_id, content)])
+            .await?
+            .remove(&message_id)
+            .unwrap_or_default(),
+    )
+}
+
 // ============================================================================
 // Router
 // ============================================================================
@@ -138,6 +262,7 @@ async fn check_guild_membership(
 pub fn router() -> Router<AppState> {
     Router::new()
         .route("/", get(list_emojis).post(create_emoji))
+        .route("/search", get(search_emojis))
         .route(
             "/{emoji_id}",
             get(get_emoji).patch(update_emoji).delete(delete_emoji),
@@ -183,6 +308,74 @@ pub async fn list_emojis(
     Ok(Json(emojis))
 }
 
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+pub struct SearchEmojisQuery {
+    /// Name prefix to match, case-insensitive (a leading `:` is ignored)
+    #[serde(default)]
+    pub q: String,
+    /// Maximum results to return (default 10, max 25)
+    #[serde(default = "default_search_limit")]
+    pub limit: i64,
+}
+
+const fn default_search_limit() -> i64 {
+    10
+}
+
+/// Search guild emojis by name prefix, for `:` autocomplete.
+///
+/// `GET /api/guilds/{id}/emojis/search?q=...`
+#[utoipa::path(
+    get,
+    path = "/api/guilds/{id}/emojis/search",
+    tag = "emojis",
+    params(("id" = Uuid, Path, description = "Guild ID"), SearchEmojisQuery),
+    responses((status = 200, body = Vec<GuildEmoji>)),
+    security(("bearer_auth" = []))
+)]
+pub async fn search_emojis(
+    State(state): State<AppState>,
+    Path(guild_id): Path<Uuid>,
+    auth_user: AuthUser,
+    Query(query): Query<SearchEmojisQuery>,
+) -> Result<Json<Vec<GuildEmoji>>, EmojiError> {
+    if !check_guild_membership(&state.db, guild_id, auth_user.id).await? {
+        return Err(EmojiError::GuildNotFound);
+    }
+
+    let term = query.q.trim().trim_start_matches(':');
+    if term.chars().count() > MAX_SEARCH_QUERY_LENGTH {
+        return Err(EmojiError::Validation(format!(
+            "Query must not exceed {MAX_SEARCH_QUERY_LENGTH} characters"
+        )));
+    }
+    let limit = query.limit.clamp(1, MAX_SEARCH_LIMIT);
+    let pattern = format!(
+        "{}%",
+        term.replace('\\', "\\\\")
+            .replace('%', "\\%")
+            .replace('_', "\\_")
+    );
+
+    // Exact matches first, then shorter names
+    let emojis = sqlx::query_as::<_, GuildEmoji>(
+        r"
+        SELECT * FROM guild_emojis
+        WHERE guild_id = $1 AND name ILIKE $2
+        ORDER BY LOWER(name) = LOWER($3) DESC, LENGTH(name), name
+        LIMIT $4
+        ",
+    )
+    .bind(guild_id)
+    .bind(&pattern)
+    .bind(term)
+    .bind(limit)
+    .fetch_all(&state.db)
+    .await?;
+
+    Ok(Json(emojis))
+}
+
 /// Get specific emoji.
 ///
 /// `GET /api/guilds/{id}/emojis/{emoji_id}`
@@ -250,6 +443,7 @@ pub async fn create_emoji(
         .s3
         .as_ref()
         .ok_or(EmojiError::Storage("S3 not configured".into()))?;
+    let max_emoji_size = state.config().max_emoji_size;
 
     let mut name: Option<String> = None;
     let mut file_data: Option<Vec<u8>> = None;
@@ -270,9 +464,9 @@ pub async fn create_emoji(
                     .bytes()
                     .await
                     .map_err(|e| EmojiError::Validation(e.to_string()))?;
-                if data.len() > state.config.max_emoji_size {
+                if data.len() > max_emoji_size {
                     return Err(EmojiError::FileTooLarge {
-                        max_size: state.config.max_emoji_size,
+                        max_size: max_emoji_size,
                     });
                 }
                 file_data = Some(data.to_vec());
@@ -314,6 +508,11 @@ pub async fn create_emoji(
     let s3_key = format!("emojis/{guild_id}/{emoji_id}.{extension}");
     let image_url = format!("/api/guilds/{guild_id}/emojis/{emoji_id}/image");
 
+    // Supporter perks raise the emoji limit
+    let emoji_slots = crate::guild::perks::get_perks(&state.db, &state.config(), guild_id)
+        .await?
+        .emoji_slots;
+
     // Phase 1 — Reserve DB slot under advisory lock (short-lived).
     // Advisory lock seed 59 = emoji_create (see db/mod.rs registry).
     // Lock is held only for COUNT + INSERT, not during S3 upload.
@@ -330,10 +529,9 @@ pub async fn create_emoji(
             .fetch_one(&mut *tx)
             .await?;
 
-    if emoji_count >= state.config.max_emojis_per_guild {
+    if emoji_count >= emoji_slots {
         return Err(EmojiError::LimitExceeded(format!(
-            "Maximum number of emojis per guild reached ({})",
-            state.config.max_emojis_per_guild
+            "Maximum number of emojis per guild reached ({emoji_slots})"
         )));
     }
 
@@ -614,3 +812,18 @@ pub async fn delete_emoji(
 
     Ok(StatusCode::NO_CONTENT)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_emoji_tokens() {
+        assert_eq!(
+            parse_emoji_tokens("hi :party_parrot: and :blob-cat::party_parrot:"),
+            vec!["party_parrot", "blob-cat"]
+        );
+        assert!(parse_emoji_tokens("no emoji here: just colons :a: ::").is_empty());
+        assert_eq!(parse_emoji_tokens("at 12:30:45"), vec!["30"]);
+    }
+}
@@ -1,6 +1,6 @@
 //! Guild Management Handlers
 
-use axum::extract::{Path, State};
+use axum::extract::{Path, Query, State};
 use axum::http::StatusCode;
 use axum::response::{IntoResponse, Response};
 use axum::Json;
@@ -9,17 +9,19 @@ use sqlx::QueryBuilder;
 use uuid::Uuid;
 use validator::Validate;
 
-use super::limits;
+use super::channel_positions::ChannelPlacement;
 use super::types::{
     CreateGuildRequest, Guild, GuildCommandInfo, GuildMember, GuildSettings, GuildWithMemberCount,
-    UpdateGuildRequest, UpdateGuildSettingsRequest,
+    ListMembersQuery, UpdateGuildRequest, UpdateGuildSettingsRequest,
 };
+use super::{audit, bans, limits};
+use crate::api::concurrency::VersionConflict;
 use crate::api::AppState;
 use crate::auth::AuthUser;
 use crate::db::{self, ChannelType};
 use crate::discovery::types::TAG_REGEX;
 use crate::permissions::{require_guild_permission, GuildPermissions, PermissionError};
-use crate::ws::{broadcast_to_user, ServerEvent};
+use crate::ws::{broadcast_to_guild, broadcast_to_user, ServerEvent};
 
 // ============================================================================
 // Response Types
@@ -77,6 +79,7 @@ pub enum GuildError {
     Permission(PermissionError),
     Validation(String),
     LimitExceeded(String),
+    VersionConflict(VersionConflict),
     Database(sqlx::Error),
 }
 
@@ -97,6 +100,7 @@ impl IntoResponse for GuildError {
             Self::Permission(e) => (StatusCode::FORBIDDEN, "PERMISSION_DENIED", e.to_string()),
             Self::Validation(msg) => (StatusCode::BAD_REQUEST, "VALIDATION_ERROR", msg.clone()),
             Self::LimitExceeded(msg) => (StatusCode::FORBIDDEN, "LIMIT_EXCEEDED", msg.clone()),
+            Self::VersionConflict(conflict) => return conflict.clone().into_response(),
             Self::Database(err) => {
                 tracing::error!(%err, "Guild endpoint database error");
                 (
@@ -156,10 +160,10 @@ pub async fn create_guild(
         .bind(auth.id)
         .fetch_one(&mut *tx)
         .await?;
-    if owned_count >= state.config.max_guilds_per_user {
+    if owned_count >= state.config().max_guilds_per_user {
         return Err(GuildError::LimitExceeded(format!(
             "Maximum number of guilds reached ({})",
-            state.config.max_guilds_per_user
+            state.config().max_guilds_per_user
         )));
     }
 
@@ -168,7 +172,7 @@ pub async fn create_guild(
     let guild = sqlx::query_as::<_, Guild>(
         r"INSERT INTO guilds (id, name, owner_id, description)
            VALUES ($1, $2, $3, $4)
-           RETURNING id, name, owner_id, icon_url, description, threads_enabled, discoverable, tags, banner_url, plan, created_at",
+           RETURNING id, name, owner_id, icon_url, description, threads_enabled, discoverable, tags, banner_url, plan, created_at, system_channel_id, rules_channel_id, version",
     )
     .bind(guild_id)
     .bind(&body.name)
@@ -225,12 +229,15 @@ pub async fn list_guilds(
         Option<String>,
         String,
         chrono::DateTime<chrono::Utc>,
+        Option<Uuid>,
+        Option<Uuid>,
+        i32,
         i64,
     )> = sqlx::query_as(
         r"SELECT
             g.id, g.name, g.owner_id, g.icon_url, g.description, g.threads_enabled,
             g.discoverable, g.tags, g.banner_url, g.plan, g.created_at,
-            g.member_count::bigint
+            g.system_channel_id, g.rules_channel_id, g.version, g.member_count::bigint
            FROM guilds g
            INNER JOIN guild_members gm ON g.id = gm.guild_id
            WHERE gm.user_id = $1
@@ -255,6 +262,9 @@ pub async fn list_guilds(
                 banner_url,
                 plan,
                 created_at,
+                system_channel_id,
+                rules_channel_id,
+                version,
                 member_count,
             )| {
                 GuildWithMemberCount {
@@ -270,6 +280,9 @@ pub async fn list_guilds(
                         banner_url,
                         plan,
                         created_at,
+                        system_channel_id,
+                        rules_channel_id,
+                        version,
                     },
                     member_count,
                 }
@@ -302,7 +315,7 @@ pub async fn get_guild(
     }
 
     let guild = sqlx::query_as::<_, Guild>(
-        "SELECT id, name, owner_id, icon_url, description, threads_enabled, discoverable, tags, banner_url, plan, created_at FROM guilds WHERE id = $1",
+        "SELECT id, name, owner_id, icon_url, description, threads_enabled, discoverable, tags, banner_url, plan, created_at, system_channel_id, rules_channel_id, version FROM guilds WHERE id = $1",
     )
     .bind(guild_id)
     .fetch_optional(&state.db)
@@ -319,7 +332,10 @@ pub async fn get_guild(
     tag = "guilds",
     params(("id" = Uuid, Path, description = "Guild ID")),
     request_body = UpdateGuildRequest,
-    responses((status = 200, body = Guild)),
+    responses(
+        (status = 200, body = Guild),
+        (status = 409, description = "Guild changed since `version`"),
+    ),
     security(("bearer_auth" = []))
 )]
 #[tracing::instrument(skip(state))]
@@ -345,42 +361,115 @@ pub async fn update_guild(
         return Err(GuildError::Forbidden);
     }
 
+    if let Some(Some(channel_id)) = body.system_channel_id {
+        require_guild_text_channel(&state.db, guild_id, channel_id, "System channel").await?;
+    }
+    if let Some(Some(channel_id)) = body.rules_channel_id {
+        require_guild_text_channel(&state.db, guild_id, channel_id, "Rules channel").await?;
+    }
+
     // Build dynamic update query
-    let mut has_changes = false;
+    let mut changes = serde_json::Map::new();
     let mut builder = QueryBuilder::new("UPDATE guilds SET ");
     {
         let mut sep = builder.separated(", ");
         if let Some(name) = body.name {
+            changes.insert("name".to_string(), name.clone().into());
             sep.push("name = ").push_bind_unseparated(name);
-            has_changes = true;
         }
         if let Some(desc) = body.description {
+            changes.insert("description".to_string(), desc.clone().into());
             sep.push("description = ").push_bind_unseparated(desc);
-            has_changes = true;
         }
-        if let Some(icon) = body.icon_url {
-            sep.push("icon_url = ").push_bind_unseparated(icon);
-            has_changes = true;
+        if let Some(channel_id) = body.system_channel_id {
+            changes.insert(
+                "system_channel_id".to_string(),
+                serde_json::json!(channel_id),
+            );
+            sep.push("system_channel_id = ")
+                .push_bind_unseparated(channel_id);
+        }
+        if let Some(channel_id) = body.rules_channel_id {
+            changes.insert(
+                "rules_channel_id".to_string(),
+                serde_json::json!(channel_id),
+            );
+            sep.push("rules_channel_id = ")
+                .push_bind_unseparated(channel_id);
         }
     }
 
-    if !has_changes {
+    if changes.is_empty() {
         return get_guild(State(state), auth, Path(guild_id)).await;
     }
 
-    builder.push(" WHERE id = ");
+    builder.push(", version = version + 1 WHERE id = ");
     builder.push_bind(guild_id);
+    if let Some(version) = body.version {
+        builder.push(" AND version = ").push_bind(version);
+    }
     builder
-        .push(" RETURNING id, name, owner_id, icon_url, description, threads_enabled, discoverable, tags, banner_url, plan, created_at");
+        .push(" RETURNING id, name, owner_id, icon_url, description, threads_enabled, discoverable, tags, banner_url, plan, created_at, system_channel_id, rules_channel_id, version");
 
-    let updated_guild = builder
+    let Some(updated_guild) = builder
         .build_query_as::<Guild>()
-        .fetch_one(&state.db)
-        .await?;
+        .fetch_optional(&state.db)
+        .await?
+    else {
+        // Another update bumped the version since the client loaded the guild
+        let Json(current) = get_guild(State(state), auth, Path(guild_id)).await?;
+        return Err(GuildError::VersionConflict(VersionConflict::new(&current)));
+    };
+
+    audit::record(
+        &state,
+        guild_id,
+        auth.id,
+        "guild.updated",
+        None,
+        None,
+        Some(changes.into()),
+    )
+    .await;
+
+    if let Err(e) = broadcast_to_guild(
+        &state.redis,
+        guild_id,
+        &ServerEvent::GuildUpdate {
+            guild_id,
+            guild: updated_guild.clone(),
+        },
+    )
+    .await
+    {
+        tracing::warn!(guild_id = %guild_id, error = %e, "Failed to broadcast GuildUpdate event");
+    }
 
     Ok(Json(updated_guild))
 }
 
+/// Reject designating a channel that isn't a text channel of this guild.
+async fn require_guild_text_channel(
+    db: &sqlx::PgPool,
+    guild_id: Uuid,
+    channel_id: Uuid,
+    label: &str,
+) -> Result<(), GuildError> {
+    let is_text_channel: bool = sqlx::query_scalar(
+        "SELECT EXISTS(SELECT 1 FROM channels WHERE id = $1 AND guild_id = $2 AND channel_type = 'text')",
+    )
+    .bind(channel_id)
+    .bind(guild_id)
+    .fetch_one(db)
+    .await?;
+    if !is_text_channel {
+        return Err(GuildError::Validation(format!(
+            "{label} must be a text channel in this guild"
+        )));
+    }
+    Ok(())
+}
+
 /// Delete guild (owner only)
 #[utoipa::path(
     delete,
@@ -477,34 +566,72 @@ pub async fn leave_guild(
         .execute(&state.db)
         .await?;
 
-    // Dispatch MemberLeft to bot ecosystem (non-blocking)
-    {
-        let db = state.db.clone();
-        let redis = state.redis.clone();
-        let gid = guild_id;
-        let uid = auth.id;
-        tokio::spawn(async move {
-            crate::ws::bot_events::publish_member_left(&db, &redis, gid, uid).await;
-            crate::webhooks::dispatch::dispatch_guild_event(
-                &db,
-                &redis,
-                gid,
-                crate::webhooks::events::BotEventType::MemberLeft,
-                serde_json::json!({ "guild_id": gid, "user_id": uid }),
+    bans::dispatch_member_left(&state, guild_id, auth.id);
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// `GuildMember` columns, selected from `guild_members gm` joined with `users u`.
+pub(super) const MEMBER_COLUMNS: &str =
+    "u.id AS user_id, u.username, u.display_name, u.avatar_url, \
+    gm.nickname, gm.avatar_url AS guild_avatar_url, gm.joined_at, u.status::text AS status, \
+    u.last_seen_at, CASE WHEN gm.timeout_until > NOW() THEN gm.timeout_until END AS timeout_until, \
+    ARRAY(SELECT mr.role_id FROM guild_member_roles mr \
+          WHERE mr.guild_id = gm.guild_id AND mr.user_id = gm.user_id) AS role_ids, gm.pending";
+
+/// Largest member list page.
+pub const MAX_MEMBER_PAGE: i64 = 1000;
+
+/// Query guild members matching the list filters.
+///
+/// Shared by the member list endpoint and WebSocket member chunk requests.
+pub async fn query_members(
+    pool: &sqlx::PgPool,
+    guild_id: Uuid,
+    query: &ListMembersQuery,
+) -> sqlx::Result<Vec<GuildMember>> {
+    let mut builder = QueryBuilder::new(format!(
+        "SELECT {MEMBER_COLUMNS} FROM guild_members gm INNER JOIN users u ON gm.user_id = u.id"
+    ));
+    builder.push(" WHERE gm.guild_id = ").push_bind(guild_id);
+    if let Some(role_id) = query.role_id {
+        builder
+            .push(
+                " AND EXISTS (SELECT 1 FROM guild_member_roles mr \
+                 WHERE mr.guild_id = gm.guild_id AND mr.user_id = gm.user_id AND mr.role_id = ",
             )
-            .await;
-        });
+            .push_bind(role_id)
+            .push(")");
+    }
+    if query.online {
+        builder.push(" AND u.status <> 'offline'");
+    }
+    if let Some(after) = query.after {
+        builder.push(" AND gm.user_id > ").push_bind(after);
+    }
+    if query.limit.is_some() || query.after.is_some() {
+        builder.push(" ORDER BY gm.user_id");
+    } else {
+        builder.push(" ORDER BY gm.joined_at");
+    }
+    if let Some(limit) = query.limit {
+        builder
+            .push(" LIMIT ")
+            .push_bind(limit.clamp(1, MAX_MEMBER_PAGE));
     }
 
-    Ok(StatusCode::NO_CONTENT)
+    builder.build_query_as().fetch_all(pool).await
 }
 
 /// List guild members
+///
+/// Supports cursor pagination and role and online filters, so clients can
+/// load a large member list one group at a time.
 #[utoipa::path(
     get,
     path = "/api/guilds/{id}/members",
     tag = "guilds",
-    params(("id" = Uuid, Path, description = "Guild ID")),
+    params(("id" = Uuid, Path, description = "Guild ID"), ListMembersQuery),
     responses((status = 200, body = Vec<GuildMember>)),
     security(("bearer_auth" = []))
 )]
@@ -513,6 +640,7 @@ pub async fn list_members(
     State(state): State<AppState>,
     auth: AuthUser,
     Path(guild_id): Path<Uuid>,
+    Query(query): Query<ListMembersQuery>,
 ) -> Result<Json<Vec<GuildMember>>, GuildError> {
     // Verify membership
     let is_member = db::is_guild_member(&state.db, guild_id, auth.id).await?;
@@ -520,29 +648,12 @@ pub async fn list_members(
         return Err(GuildError::Forbidden);
     }
 
-    let members = sqlx::query_as::<_, GuildMember>(
-        r"SELECT
-            u.id as user_id,
-            u.username,
-            u.display_name,
-            u.avatar_url,
-            gm.nickname,
-            gm.joined_at,
-            u.status::text as status,
-            u.last_seen_at
-           FROM guild_members gm
-           INNER JOIN users u ON gm.user_id = u.id
-           WHERE gm.guild_id = $1
-           ORDER BY gm.joined_at",
-    )
-    .bind(guild_id)
-    .fetch_all(&state.db)
-    .await?;
+    let members = query_members(&state.db, guild_id, &query).await?;
 
     Ok(Json(members))
 }
 
-/// Kick a member from guild (owner only)
+/// Kick a member from guild (requires `KICK_MEMBERS`)
 #[utoipa::path(
     delete,
     path = "/api/guilds/{id}/members/{user_id}",
@@ -560,25 +671,15 @@ pub async fn kick_member(
     auth: AuthUser,
     Path((guild_id, user_id)): Path<(Uuid, Uuid)>,
 ) -> Result<StatusCode, GuildError> {
-    // Verify ownership
-    let owner_check: Option<(Uuid,)> = sqlx::query_as("SELECT owner_id FROM guilds WHERE id = $1")
-        .bind(guild_id)
-        .fetch_optional(&state.db)
-        .await?;
-
-    let owner_id = owner_check.ok_or(GuildError::NotFound)?.0;
-
-    if owner_id != auth.id {
-        return Err(GuildError::Forbidden);
-    }
-
-    // Cannot kick yourself (owner)
+    // Cannot kick yourself
     if user_id == auth.id {
         return Err(GuildError::Validation(
             "Cannot kick yourself from the guild".to_string(),
         ));
     }
 
+    bans::require_can_kick(&state, guild_id, auth.id, user_id).await?;
+
     // Remove membership
     let result = sqlx::query("DELETE FROM guild_members WHERE guild_id = $1 AND user_id = $2")
         .bind(guild_id)
@@ -590,24 +691,18 @@ pub async fn kick_member(
         return Err(GuildError::NotFound);
     }
 
-    // Dispatch MemberLeft to bot ecosystem (non-blocking)
-    {
-        let db = state.db.clone();
-        let redis = state.redis.clone();
-        let gid = guild_id;
-        let uid = user_id;
-        tokio::spawn(async move {
-            crate::ws::bot_events::publish_member_left(&db, &redis, gid, uid).await;
-            crate::webhooks::dispatch::dispatch_guild_event(
-                &db,
-                &redis,
-                gid,
-                crate::webhooks::events::BotEventType::MemberLeft,
-                serde_json::json!({ "guild_id": gid, "user_id": uid }),
-            )
-            .await;
-        });
-    }
+    bans::dispatch_member_left(&state, guild_id, user_id);
+
+    audit::record(
+        &state,
+        guild_id,
+        auth.id,
+        "guild.members.kicked",
+        Some("user"),
+        Some(user_id),
+        None,
+    )
+    .await;
 
     Ok(StatusCode::NO_CONTENT)
 }
@@ -707,6 +802,8 @@ pub async fn list_channels(
 /// Reorder channels in a guild.
 ///
 /// `POST /api/guilds/:guild_id/channels/reorder`
+///
+/// Channels-only form of `PATCH /api/guilds/:guild_id/channels/positions`.
 #[utoipa::path(
     post,
     path = "/api/guilds/{id}/channels/reorder",
@@ -736,30 +833,16 @@ pub async fn reorder_channels(
         other => GuildError::Permission(other),
     })?;
 
-    if body.channels.is_empty() {
-        return Ok(StatusCode::NO_CONTENT);
-    }
-
-    // Update positions in transaction
-    let mut tx = state.db.begin().await?;
-
-    for ch in &body.channels {
-        sqlx::query(
-            r"
-            UPDATE channels
-            SET position = $3, category_id = $4
-            WHERE id = $1 AND guild_id = $2
-            ",
-        )
-        .bind(ch.id)
-        .bind(guild_id)
-        .bind(ch.position)
-        .bind(ch.category_id)
-        .execute(&mut *tx)
-        .await?;
-    }
-
-    tx.commit().await?;
+    let channels = body
+        .channels
+        .into_iter()
+        .map(|ch| ChannelPlacement {
+            id: ch.id,
+            position: ch.position,
+            category_id: ch.category_id,
+        })
+        .collect();
+    super::channel_positions::apply_positions(&state, guild_id, auth.id, channels, vec![]).await?;
 
     Ok(StatusCode::NO_CONTENT)
 }
@@ -836,10 +919,10 @@ pub async fn add_bot_to_guild(
             .fetch_one(&mut *tx)
             .await?;
 
-    if bot_count >= state.config.max_bots_per_guild {
+    if bot_count >= state.config().max_bots_per_guild {
         return Err(GuildError::LimitExceeded(format!(
             "Maximum number of bots per guild reached ({})",
-            state.config.max_bots_per_guild
+            state.config().max_bots_per_guild
         )));
     }
 
@@ -1088,6 +1171,46 @@ pub async fn mark_all_channels_read(
     Ok(StatusCode::NO_CONTENT)
 }
 
+/// Longest preferred voice region label.
+const MAX_VOICE_REGION_LENGTH: usize = 32;
+
+/// `guilds` columns backing [`GuildSettings`].
+type GuildSettingsRow = (
+    bool,
+    bool,
+    Vec<String>,
+    Option<String>,
+    Option<Uuid>,
+    Option<String>,
+    String,
+    Option<String>,
+);
+
+impl From<GuildSettingsRow> for GuildSettings {
+    fn from(row: GuildSettingsRow) -> Self {
+        let (
+            threads_enabled,
+            discoverable,
+            tags,
+            banner_url,
+            voice_log_channel_id,
+            voice_region,
+            discovery_status,
+            discovery_rejection_reason,
+        ) = row;
+        Self {
+            threads_enabled,
+            discoverable,
+            tags,
+            banner_url,
+            voice_log_channel_id,
+            voice_region,
+            discovery_status,
+            discovery_rejection_reason,
+        }
+    }
+}
+
 /// Get guild settings.
 /// GET /api/guilds/{id}/settings
 #[utoipa::path(
@@ -1110,20 +1233,15 @@ pub async fn get_guild_settings(
         return Err(GuildError::Forbidden);
     }
 
-    let settings: (bool, bool, Vec<String>, Option<String>) = sqlx::query_as(
-        "SELECT threads_enabled, discoverable, tags, banner_url FROM guilds WHERE id = $1",
+    let settings: GuildSettingsRow = sqlx::query_as(
+        "SELECT threads_enabled, discoverable, tags, banner_url, voice_log_channel_id, voice_region, discovery_status, discovery_rejection_reason FROM guilds WHERE id = $1",
     )
     .bind(guild_id)
     .fetch_optional(&state.db)
     .await?
     .ok_or(GuildError::NotFound)?;
 
-    Ok(Json(GuildSettings {
-        threads_enabled: settings.0,
-        discoverable: settings.1,
-        tags: settings.2,
-        banner_url: settings.3,
-    }))
+    Ok(Json(settings.into()))
 }
 
 /// Update guild settings (requires `MANAGE_GUILD`).
@@ -1168,73 +1286,101 @@ pub async fn update_guild_settings(
         }
     }
 
-    // Validate banner_url if provided (empty string clears the banner)
-    if let Some(ref url) = body.banner_url {
-        if !url.is_empty() {
-            if url.len() > 2048 {
-                return Err(GuildError::Validation(
-                    "Banner URL too long (max 2048 characters)".to_string(),
-                ));
-            }
-            if !url.starts_with("https://") {
-                return Err(GuildError::Validation(
-                    "Banner URL must use HTTPS".to_string(),
-                ));
-            }
+    // Validate voice log channel: must be a text channel of this guild
+    if let Some(Some(log_channel_id)) = body.voice_log_channel_id {
+        let is_text_channel: bool = sqlx::query_scalar(
+            "SELECT EXISTS(SELECT 1 FROM channels WHERE id = $1 AND guild_id = $2 AND channel_type = 'text')",
+        )
+        .bind(log_channel_id)
+        .bind(guild_id)
+        .fetch_one(&state.db)
+        .await?;
+        if !is_text_channel {
+            return Err(GuildError::Validation(
+                "Voice log channel must be a text channel in this guild".to_string(),
+            ));
         }
     }
 
-    let mut has_changes = false;
+    // Validate voice region: a label like "eu-central", matched against NODE_REGION
+    if let Some(Some(region)) = &body.voice_region {
+        if region.is_empty()
+            || region.len() > MAX_VOICE_REGION_LENGTH
+            || !TAG_REGEX.is_match(region)
+        {
+            return Err(GuildError::Validation(format!(
+                "Voice region must be 1-{MAX_VOICE_REGION_LENGTH} letters, numbers, and hyphens"
+            )));
+        }
+    }
+
+    let mut changed: Vec<&str> = Vec::new();
     let mut builder = QueryBuilder::new("UPDATE guilds SET ");
     {
         let mut sep = builder.separated(", ");
         if let Some(threads_enabled) = body.threads_enabled {
             sep.push("threads_enabled = ")
                 .push_bind_unseparated(threads_enabled);
-            has_changes = true;
+            changed.push("threads_enabled");
         }
         if let Some(discoverable) = body.discoverable {
             sep.push("discoverable = ")
                 .push_bind_unseparated(discoverable);
-            has_changes = true;
+            if discoverable {
+                // Listing needs admin approval
+                sep.push(crate::discovery::review::SUBMIT_FOR_REVIEW);
+            }
+            changed.push("discoverable");
         }
         if let Some(tags) = body.tags {
             let tags: Vec<String> = tags.into_iter().map(|t| t.to_lowercase()).collect();
             sep.push("tags = ").push_bind_unseparated(tags);
-            has_changes = true;
+            changed.push("tags");
         }
-        if let Some(banner_url) = body.banner_url {
-            // Normalize empty string to NULL (clears the banner)
-            let normalized: Option<String> = if banner_url.is_empty() {
-                None
-            } else {
-                Some(banner_url)
-            };
-            sep.push("banner_url = ").push_bind_unseparated(normalized);
-            has_changes = true;
+        if let Some(voice_log_channel_id) = body.voice_log_channel_id {
+            sep.push("voice_log_channel_id = ")
+                .push_bind_unseparated(voice_log_channel_id);
+            changed.push("voice_log_channel_id");
+        }
+        if let Some(voice_region) = body.voice_region {
+            sep.push("voice_region = ")
+                .push_bind_unseparated(voice_region);
+            changed.push("voice_region");
         }
     }
 
-    if !has_changes {
+    if changed.is_empty() {
         return get_guild_settings(State(state), auth, Path(guild_id)).await;
     }
 
     builder
         .push(" WHERE id = ")
         .push_bind(guild_id)
-        .push(" RETURNING threads_enabled, discoverable, tags, banner_url");
+        .push(" RETURNING threads_enabled, discoverable, tags, banner_url, voice_log_channel_id, voice_region, discovery_status, discovery_rejection_reason");
 
-    let (threads_enabled, discoverable, tags, banner_url) = builder
-        .build_query_as::<(bool, bool, Vec<String>, Option<String>)>()
+    let settings: GuildSettings = builder
+        .build_query_as::<GuildSettingsRow>()
         .fetch_one(&state.db)
-        .await?;
+        .await?
+        .into();
 
-    Ok(Json(GuildSettings {
-        threads_enabled,
-        discoverable,
-        tags,
-        banner_url,
-    }))
+    // Record the new value of each changed setting
+    let mut changes = serde_json::to_value(&settings).unwrap_or_default();
+    if let Some(fields) = changes.as_object_mut() {
+        fields.retain(|key, _| changed.contains(&key.as_str()));
+    }
+    audit::record(
+        &state,
+        guild_id,
+        auth.id,
+        "guild.settings.updated",
+        None,
+        None,
+        Some(changes),
+    )
+    .await;
+
+    Ok(Json(settings))
 }
 
 // ============================================================================
@@ -1291,18 +1437,16 @@ pub async fn get_guild_usage(
         .ok_or(GuildError::NotFound)?;
 
     // Run count queries in parallel
-    let (members, channels, roles, emojis, bots, pages, page_limit) = tokio::join!(
+    let config = state.config();
+    let (members, channels, roles, emojis, bots, pages, page_limit, perks) = tokio::join!(
         limits::get_member_count(&state.db, guild_id),
         limits::count_guild_channels(&state.db, guild_id),
         limits::count_guild_roles(&state.db, guild_id),
         limits::count_guild_emojis(&state.db, guild_id),
         limits::count_guild_bots(&state.db, guild_id),
         crate::pages::count_pages(&state.db, Some(guild_id)),
-        crate::pages::get_effective_page_limit(
-            &state.db,
-            guild_id,
-            state.config.max_pages_per_guild,
-        ),
+        crate::pages::get_effective_page_limit(&state.db, guild_id, config.max_pages_per_guild),
+        super::perks::get_perks(&state.db, &config, guild_id),
     );
 
     Ok(Json(GuildUsageStats {
@@ -1310,27 +1454,27 @@ pub async fn get_guild_usage(
         plan,
         members: UsageStat {
             current: members?,
-            limit: state.config.max_members_per_guild,
+            limit: state.config().max_members_per_guild,
         },
         channels: UsageStat {
             current: channels?,
-            limit: state.config.max_channels_per_guild,
+            limit: state.config().max_channels_per_guild,
         },
         roles: UsageStat {
             current: roles?,
-            limit: state.config.max_roles_per_guild,
+            limit: state.config().max_roles_per_guild,
         },
         emojis: UsageStat {
             current: emojis?,
-            limit: state.config.max_emojis_per_guild,
+            limit: perks?.emoji_slots,
         },
         bots: UsageStat {
             current: bots?,
-            limit: state.config.max_bots_per_guild,
+            limit: state.config().max_bots_per_guild,
         },
         pages: UsageStat {
             current: pages?,
-            limit: page_limit.unwrap_or(state.config.max_pages_per_guild),
+            limit: page_limit.unwrap_or(state.config().max_pages_per_guild),
         },
     }))
 }
@@ -0,0 +1,659 @@
+//! Guild Icon, Banner and Member Avatar Uploads
+//!
+//! Uploaded images are validated, cropped to the target aspect ratio and
+//! re-encoded as WebP renditions at fixed widths. The guild's `icon_url` /
+//! `banner_url` (or the member's guild `avatar_url`) points at the primary
+//! rendition; the other sizes live next to it as `{width}.webp`.
+
+use std::io::Cursor;
+
+use axum::extract::{Multipart, Path, State};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use image::imageops::FilterType;
+use image::{GenericImageView, ImageFormat, ImageReader, Limits};
+use serde_json::json;
+use uuid::Uuid;
+
+use super::audit;
+use super::member_profiles;
+use super::types::{Guild, GuildMember};
+use crate::api::AppState;
+use crate::auth::AuthUser;
+use crate::config::Config;
+use crate::db;
+use crate::permissions::{require_guild_permission, GuildPermissions, PermissionError};
+use crate::ws::{broadcast_to_guild, ServerEvent};
+
+/// Largest accepted source dimension (prevents decompression bombs).
+const MAX_SOURCE_DIMENSION: u32 = 4096;
+
+/// Decoded image memory budget, matching attachment processing.
+const MAX_DECODE_ALLOC: u64 = 64 * 1024 * 1024;
+
+/// Columns returned after changing a guild image.
+const GUILD_COLUMNS: &str = "id, name, owner_id, icon_url, description, threads_enabled, discoverable, tags, banner_url, plan, created_at, system_channel_id, rules_channel_id, version";
+
+// ============================================================================
+// Image Kinds
+// ============================================================================
+
+/// Which guild image is being changed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GuildImageKind {
+    Icon,
+    Banner,
+    /// A member's guild-specific avatar (stored on `guild_members`).
+    MemberAvatar,
+}
+
+impl GuildImageKind {
+    /// Rendition widths, smallest first.
+    pub const fn widths(self) -> &'static [u32] {
+        match self {
+            Self::Icon => &[64, 128, 256, 512],
+            Self::Banner => &[480, 960, 1920],
+            Self::MemberAvatar => &[64, 128, 256],
+        }
+    }
+
+    /// Width of the rendition whose URL is stored.
+    pub const fn primary_width(self) -> u32 {
+        match self {
+            Self::Icon | Self::MemberAvatar => 256,
+            Self::Banner => 960,
+        }
+    }
+
+    /// Minimum accepted source size (width, height).
+    pub const fn min_dimensions(self) -> (u32, u32) {
+        match self {
+            Self::Icon => (128, 128),
+            Self::Banner => (600, 200),
+            Self::MemberAvatar => (64, 64),
+        }
+    }
+
+    /// Height of the rendition at `width` (icons and avatars are square,
+    /// banners 3:1).
+    pub const fn height_for(self, width: u32) -> u32 {
+        match self {
+            Self::Icon | Self::MemberAvatar => width,
+            Self::Banner => width / 3,
+        }
+    }
+
+    /// Column holding the image URL (on `guilds`, or `guild_members` for
+    /// member avatars).
+    const fn column(self) -> &'static str {
+        match self {
+            Self::Icon => "icon_url",
+            Self::Banner => "banner_url",
+            Self::MemberAvatar => "avatar_url",
+        }
+    }
+
+    const fn key_segment(self) -> &'static str {
+        match self {
+            Self::Icon => "icons",
+            Self::Banner => "banners",
+            Self::MemberAvatar => "members",
+        }
+    }
+
+    const fn label(self) -> &'static str {
+        match self {
+            Self::Icon => "icon",
+            Self::Banner => "banner",
+            Self::MemberAvatar => "member avatar",
+        }
+    }
+}
+
+// ============================================================================
+// Error Types
+// ============================================================================
+
+#[derive(Debug, thiserror::Error)]
+pub enum GuildImageError {
+    #[error("Guild not found")]
+    NotFound,
+    #[error("Permission denied: {0}")]
+    Permission(PermissionError),
+    #[error("File too large (maximum {max_size} bytes)")]
+    FileTooLarge { max_size: usize },
+    #[error("No file provided")]
+    NoFile,
+    #[error("Invalid image: {0}")]
+    InvalidImage(String),
+    #[error("File storage is not configured")]
+    NotConfigured,
+    #[error("Storage error: {0}")]
+    Storage(String),
+    #[error("Database error: {0}")]
+    Database(#[from] sqlx::Error),
+}
+
+impl IntoResponse for GuildImageError {
+    fn into_response(self) -> Response {
+        if let Self::FileTooLarge { max_size } = self {
+            let message = format!(
+                "File too large (max {} for guild images)",
+                crate::util::format_file_size(max_size)
+            );
+            return (
+                StatusCode::PAYLOAD_TOO_LARGE,
+                Json(json!({
+                    "error": "FILE_TOO_LARGE",
+                    "message": message,
+                    "max_size_bytes": max_size
+                })),
+            )
+                .into_response();
+        }
+
+        let (status, code, message) = match &self {
+            Self::NotFound => (
+                StatusCode::NOT_FOUND,
+                "GUILD_NOT_FOUND",
+                "Guild not found".to_string(),
+            ),
+            Self::Permission(e) => (StatusCode::FORBIDDEN, "PERMISSION_DENIED", e.to_string()),
+            Self::NoFile => (
+                StatusCode::BAD_REQUEST,
+                "NO_FILE",
+                "No file provided".to_string(),
+            ),
+            Self::InvalidImage(msg) => (StatusCode::BAD_REQUEST, "INVALID_IMAGE", msg.clone()),
+            Self::NotConfigured => (
+                StatusCode::SERVICE_UNAVAILABLE,
+                "STORAGE_NOT_CONFIGURED",
+                "File storage is not configured".to_string(),
+            ),
+            Self::Storage(err) => {
+                tracing::error!(%err, "Guild image storage error");
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "STORAGE_ERROR",
+                    "Failed to store image".to_string(),
+                )
+            }
+            Self::Database(err) => {
+                tracing::error!(%err, "Guild image database error");
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "INTERNAL_ERROR",
+                    "Database error".to_string(),
+                )
+            }
+            Self::FileTooLarge { .. } => unreachable!("Handled above"),
+        };
+        (status, Json(json!({ "error": code, "message": message }))).into_response()
+    }
+}
+
+// ============================================================================
+// Processing
+// ============================================================================
+
+/// A WebP rendition ready for upload.
+pub struct Rendition {
+    pub width: u32,
+    pub height: u32,
+    pub data: Vec<u8>,
+}
+
+/// Validate an uploaded image and render its WebP renditions.
+///
+/// The format is detected from magic bytes. Animated GIFs are flattened to
+/// their first frame. This function is CPU-bound and should be called inside
+/// `spawn_blocking`.
+pub fn render_renditions(
+    data: &[u8],
+    kind: GuildImageKind,
+) -> Result<Vec<Rendition>, GuildImageError> {
+    let format = image::guess_format(data)
+        .map_err(|_| GuildImageError::InvalidImage("Unable to detect image format".into()))?;
+    if !matches!(
+        format,
+        ImageFormat::Png | ImageFormat::Jpeg | ImageFormat::Gif | ImageFormat::WebP
+    ) {
+        return Err(GuildImageError::InvalidImage(
+            "Unsupported image format. Only PNG, JPEG, GIF, and WebP are allowed.".into(),
+        ));
+    }
+
+    let mut reader = ImageReader::with_format(Cursor::new(data), format);
+    let mut limits = Limits::default();
+    limits.max_image_width = Some(MAX_SOURCE_DIMENSION);
+    limits.max_image_height = Some(MAX_SOURCE_DIMENSION);
+    limits.max_alloc = Some(MAX_DECODE_ALLOC);
+    reader.limits(limits);
+    let img = reader.decode().map_err(|e| {
+        GuildImageError::InvalidImage(format!(
+            "Image could not be decoded (max {MAX_SOURCE_DIMENSION}x{MAX_SOURCE_DIMENSION}): {e}"
+        ))
+    })?;
+
+    let (width, height) = img.dimensions();
+    let (min_width, min_height) = kind.min_dimensions();
+    if width < min_width || height < min_height {
+        return Err(GuildImageError::InvalidImage(format!(
+            "Guild {} must be at least {min_width}x{min_height} pixels",
+            kind.label()
+        )));
+    }
+
+    kind.widths()
+        .iter()
+        .map(|&width| {
+            let height = kind.height_for(width);
+            let resized = img.resize_to_fill(width, height, FilterType::Lanczos3);
+            let mut buf = Cursor::new(Vec::new());
+            resized
+                .write_to(&mut buf, ImageFormat::WebP)
+                .map_err(|e| GuildImageError::InvalidImage(format!("Encoding failed: {e}")))?;
+            Ok(Rendition {
+                width,
+                height,
+                data: buf.into_inner(),
+            })
+        })
+        .collect()
+}
+
+/// Public URL of a stored object (same layout as user avatars).
+fn public_url(config: &Config, key: &str) -> String {
+    match config.s3_endpoint.as_deref() {
+        Some(endpoint) => format!("{endpoint}/{}/{key}", config.s3_bucket),
+        None => format!("/{}/{key}", config.s3_bucket),
+    }
+}
+
+/// S3 keys of the renditions behind a stored guild image URL.
+///
+/// Returns nothing for URLs that weren't produced by this pipeline.
+fn rendition_keys(url: &str, guild_id: Uuid, kind: GuildImageKind) -> Vec<String> {
+    let prefix = format!("guilds/{guild_id}/{}/", kind.key_segment());
+    let Some(start) = url.find(&prefix) else {
+        return Vec::new();
+    };
+    let Some((dir, _)) = url[start..].rsplit_once('/') else {
+        return Vec::new();
+    };
+    kind.widths()
+        .iter()
+        .map(|width| format!("{dir}/{width}.webp"))
+        .collect()
+}
+
+// ============================================================================
+// Handlers
+// ============================================================================
+
+/// Upload a guild icon.
+///
+/// `POST /api/guilds/{id}/icon`
+/// Expects a multipart form with `file`. Requires `MANAGE_GUILD`.
+#[utoipa::path(
+    post,
+    path = "/api/guilds/{id}/icon",
+    tag = "guilds",
+    params(("id" = Uuid, Path, description = "Guild ID")),
+    request_body(content = Vec<u8>, content_type = "multipart/form-data"),
+    responses((status = 200, body = Guild)),
+    security(("bearer_auth" = []))
+)]
+#[tracing::instrument(skip(state, auth, multipart))]
+pub async fn upload_icon(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(guild_id): Path<Uuid>,
+    multipart: Multipart,
+) -> Result<Json<Guild>, GuildImageError> {
+    upload(state, auth, guild_id, multipart, GuildImageKind::Icon).await
+}
+
+/// Remove the guild icon.
+///
+/// `DELETE /api/guilds/{id}/icon`
+#[utoipa::path(
+    delete,
+    path = "/api/guilds/{id}/icon",
+    tag = "guilds",
+    params(("id" = Uuid, Path, description = "Guild ID")),
+    responses((status = 200, body = Guild)),
+    security(("bearer_auth" = []))
+)]
+#[tracing::instrument(skip(state))]
+pub async fn delete_icon(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(guild_id): Path<Uuid>,
+) -> Result<Json<Guild>, GuildImageError> {
+    replace(&state, &auth, guild_id, GuildImageKind::Icon, None).await
+}
+
+/// Upload a guild banner.
+///
+/// `POST /api/guilds/{id}/banner`
+/// Expects a multipart form with `file`. Requires `MANAGE_GUILD`.
+#[utoipa::path(
+    post,
+    path = "/api/guilds/{id}/banner",
+    tag = "guilds",
+    params(("id" = Uuid, Path, description = "Guild ID")),
+    request_body(content = Vec<u8>, content_type = "multipart/form-data"),
+    responses((status = 200, body = Guild)),
+    security(("bearer_auth" = []))
+)]
+#[tracing::instrument(skip(state, auth, multipart))]
+pub async fn upload_banner(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(guild_id): Path<Uuid>,
+    multipart: Multipart,
+) -> Result<Json<Guild>, GuildImageError> {
+    upload(state, auth, guild_id, multipart, GuildImageKind::Banner).await
+}
+
+/// Remove the guild banner.
+///
+/// `DELETE /api/guilds/{id}/banner`
+#[utoipa::path(
+    delete,
+    path = "/api/guilds/{id}/banner",
+    tag = "guilds",
+    params(("id" = Uuid, Path, description = "Guild ID")),
+    responses((status = 200, body = Guild)),
+    security(("bearer_auth" = []))
+)]
+#[tracing::instrument(skip(state))]
+pub async fn delete_banner(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(guild_id): Path<Uuid>,
+) -> Result<Json<Guild>, GuildImageError> {
+    replace(&state, &auth, guild_id, GuildImageKind::Banner, None).await
+}
+
+/// Upload the caller's guild avatar.
+///
+/// `POST /api/guilds/{id}/members/@me/avatar`
+/// Expects a multipart form with `file`. Any member can set their own.
+#[utoipa::path(
+    post,
+    path = "/api/guilds/{id}/members/@me/avatar",
+    tag = "guilds",
+    params(("id" = Uuid, Path, description = "Guild ID")),
+    request_body(content = Vec<u8>, content_type = "multipart/form-data"),
+    responses((status = 200, body = GuildMember)),
+    security(("bearer_auth" = []))
+)]
+#[tracing::instrument(skip(state, auth, multipart))]
+pub async fn upload_member_avatar(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(guild_id): Path<Uuid>,
+    mut multipart: Multipart,
+) -> Result<Json<GuildMember>, GuildImageError> {
+    if !db::is_guild_member(&state.db, guild_id, auth.id).await? {
+        return Err(GuildImageError::Permission(PermissionError::NotGuildMember));
+    }
+
+    let url = store(
+        &state,
+        guild_id,
+        GuildImageKind::MemberAvatar,
+        &mut multipart,
+    )
+    .await?;
+    let member = member_profiles::set_guild_avatar(&state, guild_id, auth.id, Some(url))
+        .await?
+        .ok_or(GuildImageError::Permission(PermissionError::NotGuildMember))?;
+    Ok(Json(member))
+}
+
+async fn require_manage_guild(
+    state: &AppState,
+    guild_id: Uuid,
+    user_id: Uuid,
+) -> Result<(), GuildImageError> {
+    let exists: bool = sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM guilds WHERE id = $1)")
+        .bind(guild_id)
+        .fetch_one(&state.db)
+        .await?;
+    if !exists {
+        return Err(GuildImageError::NotFound);
+    }
+    require_guild_permission(&state.db, guild_id, user_id, GuildPermissions::MANAGE_GUILD)
+        .await
+        .map_err(GuildImageError::Permission)?;
+    Ok(())
+}
+
+async fn upload(
+    state: AppState,
+    auth: AuthUser,
+    guild_id: Uuid,
+    mut multipart: Multipart,
+    kind: GuildImageKind,
+) -> Result<Json<Guild>, GuildImageError> {
+    require_manage_guild(&state, guild_id, auth.id).await?;
+    let url = store(&state, guild_id, kind, &mut multipart).await?;
+    replace(&state, &auth, guild_id, kind, Some(url)).await
+}
+
+/// Read the multipart `file` field, then render and upload its renditions.
+///
+/// Returns the URL of the primary rendition.
+async fn store(
+    state: &AppState,
+    guild_id: Uuid,
+    kind: GuildImageKind,
+    multipart: &mut Multipart,
+) -> Result<String, GuildImageError> {
+    let s3 = state.s3.as_ref().ok_or(GuildImageError::NotConfigured)?;
+    let max_avatar_size = state.config().max_avatar_size;
+
+    let mut file_data: Option<Vec<u8>> = None;
+    while let Ok(Some(field)) = multipart.next_field().await {
+        if field.name() == Some("file") {
+            let data = field
+                .bytes()
+                .await
+                .map_err(|e| GuildImageError::InvalidImage(e.to_string()))?;
+            if data.len() > max_avatar_size {
+                return Err(GuildImageError::FileTooLarge {
+                    max_size: max_avatar_size,
+                });
+            }
+            file_data = Some(data.to_vec());
+            break;
+        }
+    }
+    let file_data = file_data.ok_or(GuildImageError::NoFile)?;
+
+    let renditions = tokio::task::spawn_blocking(move || render_renditions(&file_data, kind))
+        .await
+        .map_err(|e| GuildImageError::Storage(format!("Image processing task failed: {e}")))??;
+
+    let image_id = Uuid::now_v7();
+    let dir = format!("guilds/{guild_id}/{}/{image_id}", kind.key_segment());
+    let mut uploaded: Vec<String> = Vec::with_capacity(renditions.len());
+    for rendition in renditions {
+        let key = format!("{dir}/{}.webp", rendition.width);
+        if let Err(e) = s3.upload(&key, rendition.data, "image/webp").await {
+            // Don't leave a partial set behind
+            for key in &uploaded {
+                if let Err(e) = s3.delete(key).await {
+                    tracing::warn!(key = %key, error = %e, "Failed to remove partial guild image");
+                }
+            }
+            return Err(GuildImageError::Storage(e.to_string()));
+        }
+        uploaded.push(key);
+    }
+
+    Ok(public_url(
+        &state.config(),
+        &format!("{dir}/{}.webp", kind.primary_width()),
+    ))
+}
+
+/// Delete the renditions behind a replaced image URL (best effort).
+pub(super) async fn delete_renditions(
+    state: &AppState,
+    url: &str,
+    guild_id: Uuid,
+    kind: GuildImageKind,
+) {
+    let Some(s3) = state.s3.as_ref() else {
+        return;
+    };
+    for key in rendition_keys(url, guild_id, kind) {
+        if let Err(e) = s3.delete(&key).await {
+            tracing::warn!(key = %key, error = %e, "Failed to delete replaced guild image");
+        }
+    }
+}
+
+/// Point the guild at a new image (or none), then clean up and broadcast.
+async fn replace(
+    state: &AppState,
+    auth: &AuthUser,
+    guild_id: Uuid,
+    kind: GuildImageKind,
+    url: Option<String>,
+) -> Result<Json<Guild>, GuildImageError> {
+    if url.is_none() {
+        require_manage_guild(state, guild_id, auth.id).await?;
+    }
+
+    let column = kind.column();
+    let previous: Option<String> =
+        sqlx::query_scalar(&format!("SELECT {column} FROM guilds WHERE id = $1"))
+            .bind(guild_id)
+            .fetch_optional(&state.db)
+            .await?
+            .ok_or(GuildImageError::NotFound)?;
+
+    let guild: Guild = sqlx::query_as(&format!(
+        "UPDATE guilds SET {column} = $1 WHERE id = $2 RETURNING {GUILD_COLUMNS}"
+    ))
+    .bind(&url)
+    .bind(guild_id)
+    .fetch_one(&state.db)
+    .await?;
+
+    if let Some(previous) = previous {
+        delete_renditions(state, &previous, guild_id, kind).await;
+    }
+
+    audit::record(
+        state,
+        guild_id,
+        auth.id,
+        "guild.updated",
+        None,
+        None,
+        Some(json!({ column: url })),
+    )
+    .await;
+
+    if let Err(e) = broadcast_to_guild(
+        &state.redis,
+        guild_id,
+        &ServerEvent::GuildUpdate {
+            guild_id,
+            guild: guild.clone(),
+        },
+    )
+    .await
+    {
+        tracing::warn!(guild_id = %guild_id, error = %e, "Failed to broadcast GuildUpdate event");
+    }
+
+    Ok(Json(guild))
+}
+
+#[cfg(test)]
+mod tests {
+    use image::DynamicImage;
+
+    use super::*;
+
+    fn png(width: u32, height: u32) -> Vec<u8> {
+        let mut buf = Cursor::new(Vec::new());
+        DynamicImage::new_rgba8(width, height)
+            .write_to(&mut buf, ImageFormat::Png)
+            .unwrap();
+        buf.into_inner()
+    }
+
+    #[test]
+    fn test_icon_renditions_are_square_webp() {
+        let renditions = render_renditions(&png(300, 200), GuildImageKind::Icon).unwrap();
+        let sizes: Vec<(u32, u32)> = renditions.iter().map(|r| (r.width, r.height)).collect();
+        assert_eq!(sizes, vec![(64, 64), (128, 128), (256, 256), (512, 512)]);
+        for rendition in &renditions {
+            assert_eq!(
+                image::guess_format(&rendition.data).unwrap(),
+                ImageFormat::WebP
+            );
+        }
+    }
+
+    #[test]
+    fn test_banner_renditions_are_cropped_to_three_by_one() {
+        let renditions = render_renditions(&png(1200, 800), GuildImageKind::Banner).unwrap();
+        let sizes: Vec<(u32, u32)> = renditions.iter().map(|r| (r.width, r.height)).collect();
+        assert_eq!(sizes, vec![(480, 160), (960, 320), (1920, 640)]);
+    }
+
+    #[test]
+    fn test_too_small_images_are_rejected() {
+        assert!(matches!(
+            render_renditions(&png(100, 300), GuildImageKind::Icon),
+            Err(GuildImageError::InvalidImage(_))
+        ));
+        assert!(matches!(
+            render_renditions(&png(800, 150), GuildImageKind::Banner),
+            Err(GuildImageError::InvalidImage(_))
+        ));
+    }
+
+    #[test]
+    fn test_non_images_are_rejected() {
+        assert!(matches!(
+            render_renditions(
+                b"<svg xmlns=\"http://www.w3.org/2000/svg\"/>",
+                GuildImageKind::Icon
+            ),
+            Err(GuildImageError::InvalidImage(_))
+        ));
+    }
+
+    #[test]
+    fn test_rendition_keys_only_cover_managed_urls() {
+        let guild_id = Uuid::nil();
+        let url = format!("http://localhost:9000/kaiku/guilds/{guild_id}/icons/abc/256.webp");
+        assert_eq!(
+            rendition_keys(&url, guild_id, GuildImageKind::Icon),
+            vec![
+                format!("guilds/{guild_id}/icons/abc/64.webp"),
+                format!("guilds/{guild_id}/icons/abc/128.webp"),
+                format!("guilds/{guild_id}/icons/abc/256.webp"),
+                format!("guilds/{guild_id}/icons/abc/512.webp"),
+            ]
+        );
+        assert!(rendition_keys(&url, guild_id, GuildImageKind::Banner).is_empty());
+        assert!(rendition_keys(
+            "https://cdn.example.com/icon.png",
+            guild_id,
+            GuildImageKind::Icon
+        )
+        .is_empty());
+    }
+}
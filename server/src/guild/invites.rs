@@ -1,4 +1,9 @@
 //! Guild Invite Handlers
+//!
+//! Invites are short codes with an optional expiry and an optional use limit.
+//! Members with `CREATE_INVITE` can create them, members with `MANAGE_INVITES`
+//! can list and revoke them (creators can always revoke their own). Anyone can
+//! preview an invite without logging in; joining claims one use atomically.
 
 use axum::extract::{Path, State};
 use axum::http::StatusCode;
@@ -7,10 +12,17 @@ use chrono::{Duration, Utc};
 use rand::Rng;
 use uuid::Uuid;
 
+use super::audit;
 use super::handlers::GuildError;
-use super::types::{CreateInviteRequest, GuildInvite, InviteResponse};
+use super::types::{CreateInviteRequest, GuildInvite, InvitePreview, InviteResponse};
 use crate::api::AppState;
 use crate::auth::AuthUser;
+use crate::permissions::{
+    require_guild_permission, GuildPermissions, MemberPermissionContext, PermissionError,
+};
+
+/// Upper bound for `max_uses` on a single invite.
+const MAX_INVITE_USES: i32 = 1000;
 
 /// Generate a cryptographically random 8-character invite code
 fn generate_invite_code() -> String {
@@ -36,7 +48,32 @@ fn parse_expiry(expires_in: &str) -> Option<Duration> {
     }
 }
 
-/// List invites for a guild (owner only)
+/// Validate the requested use limit.
+fn validate_max_uses(max_uses: Option<i32>) -> Result<(), GuildError> {
+    match max_uses {
+        Some(n) if !(1..=MAX_INVITE_USES).contains(&n) => Err(GuildError::Validation(format!(
+            "max_uses must be between 1 and {MAX_INVITE_USES}"
+        ))),
+        _ => Ok(()),
+    }
+}
+
+/// Load the caller's permission context, requiring `permission`.
+async fn require_invite_permission(
+    state: &AppState,
+    guild_id: Uuid,
+    user_id: Uuid,
+    permission: GuildPermissions,
+) -> Result<MemberPermissionContext, GuildError> {
+    require_guild_permission(&state.db, guild_id, user_id, permission)
+        .await
+        .map_err(|e| match e {
+            PermissionError::NotGuildMember => GuildError::Forbidden,
+            other => GuildError::Permission(other),
+        })
+}
+
+/// List invites for a guild (requires `MANAGE_INVITES`)
 #[utoipa::path(
     get,
     path = "/api/guilds/{id}/invites",
@@ -51,22 +88,14 @@ pub async fn list_invites(
     auth: AuthUser,
     Path(guild_id): Path<Uuid>,
 ) -> Result<Json<Vec<GuildInvite>>, GuildError> {
-    // Verify ownership
-    let guild = sqlx::query_as::<_, (Uuid,)>("SELECT owner_id FROM guilds WHERE id = $1")
-        .bind(guild_id)
-        .fetch_optional(&state.db)
-        .await?
-        .ok_or(GuildError::NotFound)?;
-
-    if guild.0 != auth.id {
-        return Err(GuildError::Forbidden);
-    }
+    require_invite_permission(&state, guild_id, auth.id, GuildPermissions::MANAGE_INVITES).await?;
 
-    // Get active invites (not expired)
+    // Get active invites (not expired, uses left)
     let invites = sqlx::query_as::<_, GuildInvite>(
-        r"SELECT id, guild_id, code, created_by, expires_at, use_count, created_at
+        r"SELECT id, guild_id, code, created_by, expires_at, use_count, max_uses, created_at
            FROM guild_invites
            WHERE guild_id = $1 AND (expires_at IS NULL OR expires_at > NOW())
+             AND (max_uses IS NULL OR use_count < max_uses)
            ORDER BY created_at DESC",
     )
     .bind(guild_id)
@@ -76,7 +105,7 @@ pub async fn list_invites(
     Ok(Json(invites))
 }
 
-/// Create a new invite (owner only)
+/// Create a new invite (requires `CREATE_INVITE`)
 #[utoipa::path(
     post,
     path = "/api/guilds/{id}/invites",
@@ -93,21 +122,14 @@ pub async fn create_invite(
     Path(guild_id): Path<Uuid>,
     Json(body): Json<CreateInviteRequest>,
 ) -> Result<Json<GuildInvite>, GuildError> {
-    // Verify ownership
-    let guild = sqlx::query_as::<_, (Uuid,)>("SELECT owner_id FROM guilds WHERE id = $1")
-        .bind(guild_id)
-        .fetch_optional(&state.db)
-        .await?
-        .ok_or(GuildError::NotFound)?;
-
-    if guild.0 != auth.id {
-        return Err(GuildError::Forbidden);
-    }
+    require_invite_permission(&state, guild_id, auth.id, GuildPermissions::CREATE_INVITE).await?;
+    validate_max_uses(body.max_uses)?;
 
     // Check rate limit (max 10 active invites per guild)
     let active_count: (i64,) = sqlx::query_as(
         r"SELECT COUNT(*) FROM guild_invites
-           WHERE guild_id = $1 AND (expires_at IS NULL OR expires_at > NOW())",
+           WHERE guild_id = $1 AND (expires_at IS NULL OR expires_at > NOW())
+             AND (max_uses IS NULL OR use_count < max_uses)",
     )
     .bind(guild_id)
     .fetch_one(&state.db)
@@ -140,21 +162,25 @@ pub async fn create_invite(
 
     // Insert invite
     let invite = sqlx::query_as::<_, GuildInvite>(
-        r"INSERT INTO guild_invites (guild_id, code, created_by, expires_at)
-           VALUES ($1, $2, $3, $4)
-           RETURNING id, guild_id, code, created_by, expires_at, use_count, created_at",
+        r"INSERT INTO guild_invites (guild_id, code, created_by, expires_at, max_uses)
+           VALUES ($1, $2, $3, $4, $5)
+           RETURNING id, guild_id, code, created_by, expires_at, use_count, max_uses, created_at",
     )
     .bind(guild_id)
     .bind(&code)
     .bind(auth.id)
     .bind(expires_at)
+    .bind(body.max_uses)
     .fetch_one(&state.db)
     .await?;
 
+    // The link filter exempts the guild's own invites
+    state.filter_cache.invalidate(guild_id);
+
     Ok(Json(invite))
 }
 
-/// Delete/revoke an invite (owner only)
+/// Delete/revoke an invite (requires `MANAGE_INVITES`, or being its creator)
 #[utoipa::path(
     delete,
     path = "/api/guilds/{id}/invites/{code}",
@@ -172,15 +198,21 @@ pub async fn delete_invite(
     auth: AuthUser,
     Path((guild_id, code)): Path<(Uuid, String)>,
 ) -> Result<StatusCode, GuildError> {
-    // Verify ownership
-    let guild = sqlx::query_as::<_, (Uuid,)>("SELECT owner_id FROM guilds WHERE id = $1")
-        .bind(guild_id)
-        .fetch_optional(&state.db)
-        .await?
-        .ok_or(GuildError::NotFound)?;
+    let ctx =
+        require_invite_permission(&state, guild_id, auth.id, GuildPermissions::empty()).await?;
 
-    if guild.0 != auth.id {
-        return Err(GuildError::Forbidden);
+    let created_by: Uuid = sqlx::query_scalar(
+        "SELECT created_by FROM guild_invites WHERE guild_id = $1 AND code = $2",
+    )
+    .bind(guild_id)
+    .bind(&code)
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or(GuildError::NotFound)?;
+
+    if created_by != auth.id {
+        ctx.require_permission(GuildPermissions::MANAGE_INVITES)
+            .map_err(GuildError::Permission)?;
     }
 
     // Delete the invite
@@ -194,9 +226,55 @@ pub async fn delete_invite(
         return Err(GuildError::NotFound);
     }
 
+    audit::record(
+        &state,
+        guild_id,
+        auth.id,
+        "guild.invites.revoked",
+        Some("invite"),
+        None,
+        Some(serde_json::json!({ "code": code, "created_by": created_by })),
+    )
+    .await;
+
     Ok(StatusCode::NO_CONTENT)
 }
 
+/// Preview an invite (public, no authentication required)
+#[utoipa::path(
+    get,
+    path = "/api/invites/{code}",
+    tag = "invites",
+    params(("code" = String, Path, description = "Invite code")),
+    responses(
+        (status = 200, body = InvitePreview),
+        (status = 404, description = "Invalid, expired or used-up invite"),
+    )
+)]
+#[tracing::instrument(skip(state))]
+pub async fn preview_invite(
+    State(state): State<AppState>,
+    Path(code): Path<String>,
+) -> Result<Json<InvitePreview>, GuildError> {
+    let preview = sqlx::query_as::<_, InvitePreview>(
+        r"SELECT i.code, g.id AS guild_id, g.name AS guild_name, g.icon_url AS guild_icon_url,
+                 g.description AS guild_description,
+                 (SELECT COUNT(*) FROM guild_members m WHERE m.guild_id = g.id) AS member_count,
+                 i.expires_at, i.max_uses - i.use_count AS uses_remaining
+           FROM guild_invites i
+           JOIN guilds g ON g.id = i.guild_id
+           WHERE i.code = $1 AND (i.expires_at IS NULL OR i.expires_at > NOW())
+             AND (i.max_uses IS NULL OR i.use_count < i.max_uses)
+             AND g.suspended_at IS NULL",
+    )
+    .bind(&code)
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or(GuildError::NotFound)?;
+
+    Ok(Json(preview))
+}
+
 /// Join a guild via invite code (any authenticated user)
 #[utoipa::path(
     post,
@@ -212,11 +290,15 @@ pub async fn join_via_invite(
     auth: AuthUser,
     Path(code): Path<String>,
 ) -> Result<Json<InviteResponse>, GuildError> {
-    // Find the invite
+    // Find the invite (not expired, uses left, guild not suspended)
     let invite = sqlx::query_as::<_, GuildInvite>(
-        r"SELECT id, guild_id, code, created_by, expires_at, use_count, created_at
-           FROM guild_invites
-           WHERE code = $1 AND (expires_at IS NULL OR expires_at > NOW())",
+        r"SELECT i.id, i.guild_id, i.code, i.created_by, i.expires_at, i.use_count, i.max_uses,
+                 i.created_at
+           FROM guild_invites i
+           JOIN guilds g ON g.id = i.guild_id
+           WHERE i.code = $1 AND (i.expires_at IS NULL OR i.expires_at > NOW())
+             AND (i.max_uses IS NULL OR i.use_count < i.max_uses)
+             AND g.suspended_at IS NULL",
     )
     .bind(&code)
     .fetch_optional(&state.db)
@@ -283,7 +365,9 @@ pub async fn join_via_invite(
             guild_name: guild_name.0,
             expires_at: invite.expires_at,
             use_count: invite.use_count,
+            max_uses: invite.max_uses,
             created_at: invite.created_at,
+            onboarding: super::onboarding::join_payload(&state.db, invite.guild_id).await,
         }));
     }
 
@@ -293,10 +377,10 @@ pub async fn join_via_invite(
             .bind(invite.guild_id)
             .fetch_one(&mut *tx)
             .await?;
-    if member_count >= state.config.max_members_per_guild {
+    if member_count >= state.config().max_members_per_guild {
         return Err(GuildError::LimitExceeded(format!(
             "Guild has reached the maximum number of members ({})",
-            state.config.max_members_per_guild
+            state.config().max_members_per_guild
         )));
     }
 
@@ -325,18 +409,47 @@ pub async fn join_via_invite(
             guild_name: guild_name.0,
             expires_at: invite.expires_at,
             use_count: invite.use_count,
+            max_uses: invite.max_uses,
             created_at: invite.created_at,
+            onboarding: super::onboarding::join_payload(&state.db, invite.guild_id).await,
         }));
     }
 
-    // Increment use count
-    sqlx::query("UPDATE guild_invites SET use_count = use_count + 1 WHERE id = $1")
-        .bind(invite.id)
-        .execute(&mut *tx)
-        .await?;
+    super::onboarding::mark_pending(&mut tx, invite.guild_id, auth.id).await?;
+
+    // Claim one use. The conditional UPDATE row-locks the invite, so concurrent
+    // joins cannot overshoot max_uses; if it's used up the membership insert is
+    // rolled back with the transaction.
+    let use_count: i32 = sqlx::query_scalar(
+        r"UPDATE guild_invites SET use_count = use_count + 1
+           WHERE id = $1 AND (expires_at IS NULL OR expires_at > NOW())
+             AND (max_uses IS NULL OR use_count < max_uses)
+           RETURNING use_count",
+    )
+    .bind(invite.id)
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or(GuildError::Validation(
+        "Invalid or expired invite code".to_string(),
+    ))?;
 
     tx.commit().await?;
 
+    audit::record(
+        &state,
+        invite.guild_id,
+        auth.id,
+        "guild.invites.used",
+        Some("invite"),
+        None,
+        Some(serde_json::json!({
+            "code": invite.code,
+            "created_by": invite.created_by,
+            "use_count": use_count,
+        })),
+    )
+    .await;
+
     // Initialize read state for all text channels (best-effort, non-critical)
     if let Err(err) =
         super::handlers::initialize_channel_read_state(&state.db, invite.guild_id, auth.id).await
@@ -350,6 +463,14 @@ pub async fn join_via_invite(
         // Non-fatal: member was already inserted, read state can be retried on channel access
     }
 
+    crate::chat::system_messages::post_member_join(
+        &state.db,
+        &state.redis,
+        invite.guild_id,
+        auth.id,
+    )
+    .await;
+
     // Get guild name for response
     let guild_name: (String,) = sqlx::query_as("SELECT name FROM guilds WHERE id = $1")
         .bind(invite.guild_id)
@@ -362,7 +483,9 @@ pub async fn join_via_invite(
         guild_id: invite.guild_id,
         guild_name: guild_name.0,
         expires_at: invite.expires_at,
-        use_count: invite.use_count + 1,
+        use_count,
+        max_uses: invite.max_uses,
         created_at: invite.created_at,
+        onboarding: super::onboarding::join_payload(&state.db, invite.guild_id).await,
     }))
 }
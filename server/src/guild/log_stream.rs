@@ -0,0 +1,184 @@
+//! Moderation Log Streaming
+//!
+//! A guild can stream its audit log, moderation actions included, to an
+//! external endpoint so the history can be archived outside the instance.
+//! Every entry written by [`audit::record`] is delivered as a signed
+//! `audit.logged` event.
+//!
+//! A stream is a row in `webhooks` owned by the guild instead of a bot
+//! application, so it shares the webhook delivery queue: HMAC signatures,
+//! retries with backoff, the delivery log and dead letters.
+
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::Json;
+use uuid::Uuid;
+
+use super::audit;
+use super::handlers::GuildError;
+use super::types::{GuildLogStream, SetLogStreamRequest};
+use crate::api::AppState;
+use crate::auth::mfa_crypto::encrypt_mfa_secret;
+use crate::auth::AuthUser;
+use crate::permissions::{require_guild_permission, GuildPermissions};
+use crate::webhooks::handlers::{signing_secret_key, validate_url};
+use crate::webhooks::signing::generate_signing_secret;
+
+/// Stream row returned by the upsert, and whether it was inserted.
+#[derive(sqlx::FromRow)]
+struct Upserted {
+    #[sqlx(flatten)]
+    stream: GuildLogStream,
+    created: bool,
+}
+
+async fn require_stream_manager(
+    state: &AppState,
+    guild_id: Uuid,
+    user_id: Uuid,
+) -> Result<(), GuildError> {
+    require_guild_permission(&state.db, guild_id, user_id, GuildPermissions::MANAGE_GUILD)
+        .await
+        .map_err(GuildError::Permission)?;
+    Ok(())
+}
+
+/// Get the guild's moderation log stream (requires `MANAGE_GUILD`)
+///
+/// Returns `null` when the guild has none.
+#[utoipa::path(
+    get,
+    path = "/api/guilds/{id}/log-stream",
+    tag = "guilds",
+    params(("id" = Uuid, Path, description = "Guild ID")),
+    responses((status = 200, body = Option<GuildLogStream>)),
+    security(("bearer_auth" = []))
+)]
+#[tracing::instrument(skip(state))]
+pub async fn get_log_stream(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(guild_id): Path<Uuid>,
+) -> Result<Json<Option<GuildLogStream>>, GuildError> {
+    require_stream_manager(&state, guild_id, auth.id).await?;
+
+    let stream = sqlx::query_as::<_, GuildLogStream>(
+        "SELECT url, active, created_at, updated_at FROM webhooks WHERE guild_id = $1",
+    )
+    .bind(guild_id)
+    .fetch_optional(&state.db)
+    .await?;
+
+    Ok(Json(stream))
+}
+
+/// Create or update the guild's moderation log stream (requires `MANAGE_GUILD`)
+///
+/// The signing secret is returned when the stream is created or
+/// `rotate_secret` is set, and never again.
+#[utoipa::path(
+    put,
+    path = "/api/guilds/{id}/log-stream",
+    tag = "guilds",
+    params(("id" = Uuid, Path, description = "Guild ID")),
+    request_body = SetLogStreamRequest,
+    responses((status = 200, body = GuildLogStream)),
+    security(("bearer_auth" = []))
+)]
+#[tracing::instrument(skip(state, body))]
+pub async fn set_log_stream(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(guild_id): Path<Uuid>,
+    Json(body): Json<SetLogStreamRequest>,
+) -> Result<Json<GuildLogStream>, GuildError> {
+    require_stream_manager(&state, guild_id, auth.id).await?;
+    validate_url(&body.url).map_err(GuildError::Validation)?;
+
+    // Only stored when the stream is new or the secret is rotated
+    let secret = generate_signing_secret();
+    let key = signing_secret_key(&state.config()).map_err(GuildError::Validation)?;
+    let encrypted_secret = encrypt_mfa_secret(&secret, &key)
+        .map_err(|e| GuildError::Validation(format!("Failed to encrypt signing secret: {e}")))?;
+
+    let Upserted {
+        mut stream,
+        created,
+    } = sqlx::query_as(
+        r"INSERT INTO webhooks (guild_id, url, signing_secret, subscribed_events, active)
+          VALUES ($1, $2, $3, '{audit.logged}', $4)
+          ON CONFLICT (guild_id) WHERE guild_id IS NOT NULL DO UPDATE
+          SET url = EXCLUDED.url,
+              active = EXCLUDED.active,
+              signing_secret = CASE WHEN $5 THEN EXCLUDED.signing_secret
+                                    ELSE webhooks.signing_secret END,
+              updated_at = NOW()
+          RETURNING url, active, created_at, updated_at, (xmax = 0) AS created",
+    )
+    .bind(guild_id)
+    .bind(&body.url)
+    .bind(&encrypted_secret)
+    .bind(body.active)
+    .bind(body.rotate_secret)
+    .fetch_one(&state.db)
+    .await?;
+    if created || body.rotate_secret {
+        stream.signing_secret = Some(secret);
+    }
+
+    audit::record(
+        &state,
+        guild_id,
+        auth.id,
+        "guild.log_stream.updated",
+        Some("guild"),
+        Some(guild_id),
+        Some(serde_json::json!({
+            "url": stream.url,
+            "active": stream.active,
+            "secret_rotated": body.rotate_secret && !created,
+        })),
+    )
+    .await;
+
+    Ok(Json(stream))
+}
+
+/// Remove the guild's moderation log stream (requires `MANAGE_GUILD`)
+#[utoipa::path(
+    delete,
+    path = "/api/guilds/{id}/log-stream",
+    tag = "guilds",
+    params(("id" = Uuid, Path, description = "Guild ID")),
+    responses((status = 204, description = "Log stream removed")),
+    security(("bearer_auth" = []))
+)]
+#[tracing::instrument(skip(state))]
+pub async fn delete_log_stream(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(guild_id): Path<Uuid>,
+) -> Result<StatusCode, GuildError> {
+    require_stream_manager(&state, guild_id, auth.id).await?;
+
+    let deleted = sqlx::query("DELETE FROM webhooks WHERE guild_id = $1")
+        .bind(guild_id)
+        .execute(&state.db)
+        .await?
+        .rows_affected();
+
+    if deleted > 0 {
+        audit::record(
+            &state,
+            guild_id,
+            auth.id,
+            "guild.log_stream.deleted",
+            Some("guild"),
+            Some(guild_id),
+            None,
+        )
+        .await;
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,670 @@
+//! CSV Member Role Import
+//!
+//! Maps usernames or emails to roles from an uploaded CSV and applies the
+//! changes in a background job. Each row's outcome is written to a CSV report
+//! that can be downloaded once the job completes. Only existing guild members
+//! are matched, so the report never reveals whether an email is registered.
+//!
+//! Expected columns: `user,role[,action]` where `user` is a username or email,
+//! `role` a role name or ID and `action` either `add` (default) or `remove`.
+//! A header row is detected and skipped.
+
+use std::collections::HashMap;
+use std::fmt::Write;
+
+use axum::extract::{Path, State};
+use axum::http::{header, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use thiserror::Error;
+use uuid::Uuid;
+
+use super::roles::{apply_bulk_role_change, check_bulk_assignable, RoleError};
+use super::types::{BulkRoleAction, MemberImportJob};
+use crate::api::AppState;
+use crate::auth::AuthUser;
+use crate::permissions::{
+    require_guild_permission, GuildPermissions, MemberPermissionContext, PermissionError,
+};
+
+/// Maximum size of an uploaded CSV.
+const MAX_IMPORT_BYTES: usize = 1024 * 1024;
+
+/// Maximum data rows per import.
+const MAX_IMPORT_ROWS: usize = 5000;
+
+/// Jobs still pending/processing after this long are considered abandoned.
+const STALE_IMPORT_JOB_HOURS: i32 = 1;
+
+const JOB_COLUMNS: &str = "id, guild_id, created_by, status, total_rows, succeeded_rows, \
+                           failed_rows, error_message, created_at, completed_at";
+
+// ============================================================================
+// Error Type
+// ============================================================================
+
+#[derive(Debug, Error)]
+pub enum MemberImportError {
+    #[error("Import job not found")]
+    NotFound,
+
+    #[error("Not a member of this guild")]
+    NotMember,
+
+    #[error("{0}")]
+    Permission(#[from] PermissionError),
+
+    #[error("Validation failed: {0}")]
+    Validation(String),
+
+    #[error("An import is already running for this guild")]
+    AlreadyRunning,
+
+    #[error("Import report is not available yet")]
+    ReportNotReady,
+
+    #[error("Database error")]
+    Database(#[from] sqlx::Error),
+}
+
+impl IntoResponse for MemberImportError {
+    fn into_response(self) -> Response {
+        if let Self::Database(db_err) = &self {
+            tracing::error!(error = %db_err, "Member import database operation failed");
+        }
+        let (status, code) = match &self {
+            Self::NotFound => (StatusCode::NOT_FOUND, "IMPORT_NOT_FOUND"),
+            Self::NotMember => (StatusCode::FORBIDDEN, "NOT_MEMBER"),
+            Self::Permission(_) => (StatusCode::FORBIDDEN, "PERMISSION_DENIED"),
+            Self::Validation(_) => (StatusCode::BAD_REQUEST, "VALIDATION_ERROR"),
+            Self::AlreadyRunning => (StatusCode::CONFLICT, "IMPORT_IN_PROGRESS"),
+            Self::ReportNotReady => (StatusCode::CONFLICT, "REPORT_NOT_READY"),
+            Self::Database(_) => (StatusCode::INTERNAL_SERVER_ERROR, "INTERNAL_ERROR"),
+        };
+        let body = serde_json::json!({"error": code, "message": self.to_string()});
+        (status, Json(body)).into_response()
+    }
+}
+
+fn map_permission_error(e: PermissionError) -> MemberImportError {
+    match e {
+        PermissionError::NotGuildMember => MemberImportError::NotMember,
+        other => MemberImportError::Permission(other),
+    }
+}
+
+// ============================================================================
+// CSV Parsing
+// ============================================================================
+
+/// One data row of an import file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ImportRow {
+    /// 1-based line number in the uploaded file.
+    line: usize,
+    user: String,
+    role: String,
+    action: String,
+}
+
+/// Split CSV text into records (RFC 4180 quoting, `\n` or `\r\n` line ends).
+///
+/// Returns each record with the line number it starts on.
+fn parse_csv(input: &str) -> Result<Vec<(usize, Vec<String>)>, String> {
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut line = 1;
+    let mut record_line = 1;
+    let mut chars = input.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if in_quotes {
+            match c {
+                '"' if chars.peek() == Some(&'"') => {
+                    chars.next();
+                    field.push('"');
+                }
+                '"' => in_quotes = false,
+                '\n' => {
+                    line += 1;
+                    field.push(c);
+                }
+                _ => field.push(c),
+            }
+            continue;
+        }
+        match c {
+            '"' if field.is_empty() => in_quotes = true,
+            ',' => record.push(std::mem::take(&mut field)),
+            '\r' if chars.peek() == Some(&'\n') => {}
+            '\n' => {
+                record.push(std::mem::take(&mut field));
+                records.push((record_line, std::mem::take(&mut record)));
+                line += 1;
+                record_line = line;
+            }
+            _ => field.push(c),
+        }
+    }
+    if in_quotes {
+        return Err(format!(
+            "Unterminated quoted field starting on line {record_line}"
+        ));
+    }
+    if !field.is_empty() || !record.is_empty() {
+        record.push(field);
+        records.push((record_line, record));
+    }
+
+    Ok(records)
+}
+
+/// Parse an import file into rows, skipping blank lines and a header row.
+fn parse_import(input: &str) -> Result<Vec<ImportRow>, String> {
+    // Spreadsheet exports often start with a byte order mark
+    let input = input.strip_prefix('\u{feff}').unwrap_or(input);
+    let mut rows = Vec::new();
+
+    for (index, (line, fields)) in parse_csv(input)?.into_iter().enumerate() {
+        let fields: Vec<&str> = fields.iter().map(|f| f.trim()).collect();
+        if fields.iter().all(|f| f.is_empty()) {
+            continue;
+        }
+        if index == 0
+            && matches!(
+                fields[0].to_ascii_lowercase().as_str(),
+                "user" | "username" | "email" | "member"
+            )
+        {
+            continue;
+        }
+        if fields.len() < 2 || fields.len() > 3 {
+            return Err(format!(
+                "Line {line}: expected 2 or 3 columns (user, role, action)"
+            ));
+        }
+        rows.push(ImportRow {
+            line,
+            user: fields[0].to_string(),
+            role: fields[1].to_string(),
+            action: fields.get(2).copied().unwrap_or_default().to_string(),
+        });
+        if rows.len() > MAX_IMPORT_ROWS {
+            return Err(format!("Imports are limited to {MAX_IMPORT_ROWS} rows"));
+        }
+    }
+
+    if rows.is_empty() {
+        return Err("The CSV contains no rows".to_string());
+    }
+    Ok(rows)
+}
+
+/// Escape a report field, neutralizing spreadsheet formulas.
+fn report_field(value: &str) -> String {
+    let value = if value.starts_with(['=', '+', '-', '@']) {
+        format!("'{value}")
+    } else {
+        value.to_string()
+    };
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value
+    }
+}
+
+// ============================================================================
+// Job Processing
+// ============================================================================
+
+/// Outcome of one import row.
+enum RowOutcome {
+    /// Resolved; final status is known once the role change is applied.
+    Pending {
+        user_id: Uuid,
+        role_id: Uuid,
+        action: BulkRoleAction,
+    },
+    Failed(String),
+}
+
+/// Resolve rows, apply the role changes and build the report.
+///
+/// Returns `(succeeded, failed, report)`.
+async fn run_import(
+    state: &AppState,
+    guild_id: Uuid,
+    actor_id: Uuid,
+    ctx: &MemberPermissionContext,
+    rows: &[ImportRow],
+) -> Result<(i32, i32, String), sqlx::Error> {
+    let members: Vec<(Uuid, String, Option<String>)> = sqlx::query_as(
+        r"
+        SELECT u.id, u.username, u.email
+        FROM guild_members gm
+        JOIN users u ON u.id = gm.user_id
+        WHERE gm.guild_id = $1
+        ",
+    )
+    .bind(guild_id)
+    .fetch_all(&state.db)
+    .await?;
+    let mut by_username = HashMap::new();
+    let mut by_email = HashMap::new();
+    for (id, username, email) in members {
+        by_username.insert(username.to_lowercase(), id);
+        if let Some(email) = email {
+            by_email.insert(email.to_lowercase(), id);
+        }
+    }
+
+    let roles: Vec<(Uuid, String, i32, bool)> = sqlx::query_as(
+        "SELECT id, name, position, is_default FROM guild_roles WHERE guild_id = $1",
+    )
+    .bind(guild_id)
+    .fetch_all(&state.db)
+    .await?;
+
+    let resolve_role = |cell: &str| -> Result<Uuid, String> {
+        let matches: Vec<&(Uuid, String, i32, bool)> = match Uuid::parse_str(cell) {
+            Ok(id) => roles.iter().filter(|r| r.0 == id).collect(),
+            Err(_) => roles
+                .iter()
+                .filter(|r| r.1.eq_ignore_ascii_case(cell))
+                .collect(),
+        };
+        let role = match matches.as_slice() {
+            [] => return Err("Role not found".to_string()),
+            [role] => role,
+            _ => return Err("Role name is ambiguous; use the role ID".to_string()),
+        };
+        check_bulk_assignable(ctx, role.2, role.3).map_err(|e| match e {
+            RoleError::Permission(PermissionError::RoleHierarchy { .. }) => {
+                "Role is above your highest role".to_string()
+            }
+            other => other.to_string(),
+        })?;
+        Ok(role.0)
+    };
+
+    let mut outcomes = Vec::with_capacity(rows.len());
+    // Grouped in order of first appearance
+    let mut groups: Vec<((Uuid, BulkRoleAction), Vec<Uuid>)> = Vec::new();
+    for row in rows {
+        let user_id = if let Some(name) = row.user.strip_prefix('@') {
+            by_username.get(&name.to_lowercase())
+        } else if row.user.contains('@') {
+            by_email.get(&row.user.to_lowercase())
+        } else {
+            by_username.get(&row.user.to_lowercase())
+        };
+        let action = match row.action.to_ascii_lowercase().as_str() {
+            "" | "add" => Some(BulkRoleAction::Add),
+            "remove" => Some(BulkRoleAction::Remove),
+            _ => None,
+        };
+
+        let outcome = match (user_id, action) {
+            (None, _) => RowOutcome::Failed("No guild member with this username or email".into()),
+            (_, None) => RowOutcome::Failed("Action must be 'add' or 'remove'".into()),
+            (Some(&user_id), Some(action)) => match resolve_role(&row.role) {
+                Ok(role_id) => {
+                    let key = (role_id, action);
+                    match groups.iter_mut().find(|(k, _)| *k == key) {
+                        Some((_, users)) => users.push(user_id),
+                        None => groups.push((key, vec![user_id])),
+                    }
+                    RowOutcome::Pending {
+                        user_id,
+                        role_id,
+                        action,
+                    }
+                }
+                Err(reason) => RowOutcome::Failed(reason),
+            },
+        };
+        outcomes.push(outcome);
+    }
+
+    let mut changed = Vec::new();
+    for ((role_id, action), user_ids) in &groups {
+        for user_id in
+            apply_bulk_role_change(state, guild_id, actor_id, *role_id, *action, user_ids).await?
+        {
+            changed.push((user_id, *role_id, *action));
+        }
+    }
+
+    let mut report = String::from("line,user,role,action,status,detail\n");
+    let (mut succeeded, mut failed) = (0, 0);
+    for (row, outcome) in rows.iter().zip(outcomes) {
+        let (status, detail) = match outcome {
+            RowOutcome::Pending {
+                user_id,
+                role_id,
+                action,
+            } => {
+                succeeded += 1;
+                // Each change is reported once, on the first row that caused it
+                let position = changed
+                    .iter()
+                    .position(|c| *c == (user_id, role_id, action));
+                let status = match (position, action) {
+                    (Some(index), BulkRoleAction::Add) => {
+                        changed.swap_remove(index);
+                        "added"
+                    }
+                    (Some(index), BulkRoleAction::Remove) => {
+                        changed.swap_remove(index);
+                        "removed"
+                    }
+                    (None, _) => "unchanged",
+                };
+                (status, String::new())
+            }
+            RowOutcome::Failed(reason) => {
+                failed += 1;
+                ("error", reason)
+            }
+        };
+        writeln!(
+            report,
+            "{},{},{},{},{},{}",
+            row.line,
+            report_field(&row.user),
+            report_field(&row.role),
+            report_field(&row.action),
+            status,
+            report_field(&detail)
+        )
+        .expect("write to String is infallible");
+    }
+
+    Ok((succeeded, failed, report))
+}
+
+/// Background task for one import job.
+async fn process_import_job(
+    state: AppState,
+    job_id: Uuid,
+    guild_id: Uuid,
+    actor_id: Uuid,
+    ctx: MemberPermissionContext,
+    rows: Vec<ImportRow>,
+) {
+    if let Err(e) =
+        sqlx::query("UPDATE guild_member_import_jobs SET status = 'processing' WHERE id = $1")
+            .bind(job_id)
+            .execute(&state.db)
+            .await
+    {
+        tracing::warn!(job_id = %job_id, error = %e, "Failed to mark member import as processing");
+    }
+
+    let result = match run_import(&state, guild_id, actor_id, &ctx, &rows).await {
+        Ok((succeeded, failed, report)) => {
+            sqlx::query(
+                r"
+                UPDATE guild_member_import_jobs
+                SET status = 'completed', succeeded_rows = $2, failed_rows = $3,
+                    report = $4, completed_at = NOW()
+                WHERE id = $1
+                ",
+            )
+            .bind(job_id)
+            .bind(succeeded)
+            .bind(failed)
+            .bind(report)
+            .execute(&state.db)
+            .await
+        }
+        Err(e) => {
+            tracing::error!(job_id = %job_id, guild_id = %guild_id, error = %e, "Member import failed");
+            sqlx::query(
+                r"
+                UPDATE guild_member_import_jobs
+                SET status = 'failed', error_message = 'Import failed; please retry',
+                    completed_at = NOW()
+                WHERE id = $1
+                ",
+            )
+            .bind(job_id)
+            .execute(&state.db)
+            .await
+        }
+    };
+
+    if let Err(e) = result {
+        tracing::error!(job_id = %job_id, error = %e, "Failed to store member import result");
+    }
+}
+
+// ============================================================================
+// Handlers
+// ============================================================================
+
+/// Start a CSV member role import.
+///
+/// `POST /api/guilds/:guild_id/members/import`
+#[utoipa::path(
+    post,
+    path = "/api/guilds/{id}/members/import",
+    tag = "roles",
+    params(("id" = Uuid, Path, description = "Guild ID")),
+    request_body(content = String, content_type = "text/csv", description = "Rows of `user,role[,action]`"),
+    responses(
+        (status = 202, description = "Import job started", body = MemberImportJob),
+        (status = 409, description = "An import is already running"),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[tracing::instrument(skip(state, body))]
+pub async fn start_member_import(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(guild_id): Path<Uuid>,
+    body: String,
+) -> Result<impl IntoResponse, MemberImportError> {
+    let ctx =
+        require_guild_permission(&state.db, guild_id, auth.id, GuildPermissions::MANAGE_ROLES)
+            .await
+            .map_err(map_permission_error)?;
+
+    if body.len() > MAX_IMPORT_BYTES {
+        return Err(MemberImportError::Validation(
+            "CSV must be at most 1 MiB".to_string(),
+        ));
+    }
+    let rows = parse_import(&body).map_err(MemberImportError::Validation)?;
+
+    // Recover jobs abandoned by a restart so the guild is not blocked forever
+    sqlx::query(
+        r"
+        UPDATE guild_member_import_jobs
+        SET status = 'failed',
+            error_message = COALESCE(error_message, 'Job stale after restart; please retry'),
+            completed_at = NOW()
+        WHERE guild_id = $1
+          AND status IN ('pending', 'processing')
+          AND created_at < NOW() - make_interval(hours => $2)
+        ",
+    )
+    .bind(guild_id)
+    .bind(STALE_IMPORT_JOB_HOURS)
+    .execute(&state.db)
+    .await?;
+
+    let job = sqlx::query_as::<_, MemberImportJob>(&format!(
+        "INSERT INTO guild_member_import_jobs (guild_id, created_by, total_rows)
+         VALUES ($1, $2, $3)
+         RETURNING {JOB_COLUMNS}"
+    ))
+    .bind(guild_id)
+    .bind(auth.id)
+    .bind(rows.len() as i32)
+    .fetch_one(&state.db)
+    .await
+    .map_err(|e| match e {
+        sqlx::Error::Database(ref db_err) if db_err.is_unique_violation() => {
+            MemberImportError::AlreadyRunning
+        }
+        other => MemberImportError::Database(other),
+    })?;
+
+    tokio::spawn(process_import_job(
+        state.clone(),
+        job.id,
+        guild_id,
+        auth.id,
+        ctx,
+        rows,
+    ));
+
+    Ok((StatusCode::ACCEPTED, Json(job)))
+}
+
+/// Get the status of a member import.
+///
+/// `GET /api/guilds/:guild_id/members/import/:job_id`
+#[utoipa::path(
+    get,
+    path = "/api/guilds/{id}/members/import/{job_id}",
+    tag = "roles",
+    params(
+        ("id" = Uuid, Path, description = "Guild ID"),
+        ("job_id" = Uuid, Path, description = "Import job ID")
+    ),
+    responses((status = 200, body = MemberImportJob)),
+    security(("bearer_auth" = []))
+)]
+#[tracing::instrument(skip(state))]
+pub async fn get_member_import(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path((guild_id, job_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<MemberImportJob>, MemberImportError> {
+    require_guild_permission(&state.db, guild_id, auth.id, GuildPermissions::MANAGE_ROLES)
+        .await
+        .map_err(map_permission_error)?;
+
+    let job = sqlx::query_as::<_, MemberImportJob>(&format!(
+        "SELECT {JOB_COLUMNS} FROM guild_member_import_jobs WHERE id = $1 AND guild_id = $2"
+    ))
+    .bind(job_id)
+    .bind(guild_id)
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or(MemberImportError::NotFound)?;
+
+    Ok(Json(job))
+}
+
+/// Download the per-row result report of a completed member import.
+///
+/// `GET /api/guilds/:guild_id/members/import/:job_id/report`
+#[utoipa::path(
+    get,
+    path = "/api/guilds/{id}/members/import/{job_id}/report",
+    tag = "roles",
+    params(
+        ("id" = Uuid, Path, description = "Guild ID"),
+        ("job_id" = Uuid, Path, description = "Import job ID")
+    ),
+    responses(
+        (status = 200, description = "CSV report (line,user,role,action,status,detail)"),
+        (status = 409, description = "Import has not completed"),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[tracing::instrument(skip(state))]
+pub async fn download_member_import_report(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path((guild_id, job_id)): Path<(Uuid, Uuid)>,
+) -> Result<impl IntoResponse, MemberImportError> {
+    require_guild_permission(&state.db, guild_id, auth.id, GuildPermissions::MANAGE_ROLES)
+        .await
+        .map_err(map_permission_error)?;
+
+    let report: Option<String> = sqlx::query_scalar(
+        "SELECT report FROM guild_member_import_jobs WHERE id = $1 AND guild_id = $2",
+    )
+    .bind(job_id)
+    .bind(guild_id)
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or(MemberImportError::NotFound)?;
+    let report = report.ok_or(MemberImportError::ReportNotReady)?;
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, "text/csv; charset=utf-8".to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"member-import-{job_id}.csv\""),
+            ),
+            (header::X_CONTENT_TYPE_OPTIONS, "nosniff".to_string()),
+        ],
+        report,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_quoted_csv() {
+        let records = parse_csv("a,\"b,c\"\r\n\"say \"\"hi\"\"\",\"multi\nline\"\nlast").unwrap();
+        assert_eq!(
+            records,
+            vec![
+                (1, vec!["a".to_string(), "b,c".to_string()]),
+                (2, vec!["say \"hi\"".to_string(), "multi\nline".to_string()]),
+                (4, vec!["last".to_string()]),
+            ]
+        );
+        assert!(parse_csv("a,\"open").is_err());
+    }
+
+    #[test]
+    fn parses_import_rows() {
+        let rows =
+            parse_import("user,role,action\nalice, Moderator \n\nbob@example.com,Member,remove\n")
+                .unwrap();
+        assert_eq!(
+            rows,
+            vec![
+                ImportRow {
+                    line: 2,
+                    user: "alice".to_string(),
+                    role: "Moderator".to_string(),
+                    action: String::new(),
+                },
+                ImportRow {
+                    line: 4,
+                    user: "bob@example.com".to_string(),
+                    role: "Member".to_string(),
+                    action: "remove".to_string(),
+                },
+            ]
+        );
+
+        assert!(parse_import("user,role\n").is_err());
+        assert!(parse_import("alice\n").is_err());
+        assert!(parse_import("a,b,c,d\n").is_err());
+    }
+
+    #[test]
+    fn report_fields_are_escaped() {
+        assert_eq!(report_field("alice"), "alice");
+        assert_eq!(report_field("a,b"), "\"a,b\"");
+        assert_eq!(
+            report_field("=HYPERLINK(\"x\")"),
+            "\"'=HYPERLINK(\"\"x\"\")\""
+        );
+    }
+}
@@ -0,0 +1,302 @@
+//! Guild Member Profiles
+//!
+//! A guild nickname and guild avatar replace a member's display name and
+//! avatar inside one guild. Members change their own; changing someone else's
+//! nickname or resetting their guild avatar needs `MANAGE_NICKNAMES` and a
+//! higher role. Guild avatars are uploaded through the guild image pipeline
+//! (`images::upload_member_avatar`).
+//!
+//! Message authors in guild channels carry the effective identity; the
+//! overrides are resolved here in bulk.
+
+use std::collections::{HashMap, HashSet};
+
+use axum::extract::{Path, State};
+use axum::Json;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use super::audit;
+use super::bans::{require_moderator, require_outranks};
+use super::handlers::{GuildError, MEMBER_COLUMNS};
+use super::images::{self, GuildImageKind};
+use super::types::{GuildMember, UpdateMemberRequest};
+use crate::api::AppState;
+use crate::auth::AuthUser;
+use crate::db;
+use crate::permissions::GuildPermissions;
+use crate::ws::broadcast_member_patch;
+
+/// Longest guild nickname, in characters.
+const MAX_NICKNAME_LENGTH: usize = 32;
+
+/// A member's guild overrides of their display name and avatar.
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct MemberProfile {
+    pub nickname: Option<String>,
+    pub avatar_url: Option<String>,
+}
+
+/// Trim and validate a nickname. Returns `None` for blank text.
+fn normalize_nickname(raw: &str) -> Result<Option<String>, GuildError> {
+    let nickname = raw.trim();
+    if nickname.is_empty() {
+        return Ok(None);
+    }
+    if nickname.chars().any(char::is_control) {
+        return Err(GuildError::Validation(
+            "Nickname must not contain control characters".to_string(),
+        ));
+    }
+    if nickname.chars().count() > MAX_NICKNAME_LENGTH {
+        return Err(GuildError::Validation(format!(
+            "Nickname must be at most {MAX_NICKNAME_LENGTH} characters"
+        )));
+    }
+    Ok(Some(nickname.to_string()))
+}
+
+/// Tell guild members that a member's guild profile changed.
+async fn broadcast_profile(
+    state: &AppState,
+    guild_id: Uuid,
+    user_id: Uuid,
+    diff: serde_json::Value,
+) {
+    if let Err(e) = broadcast_member_patch(&state.redis, guild_id, user_id, diff).await {
+        tracing::warn!(guild_id = %guild_id, error = %e, "Failed to broadcast member profile patch");
+    }
+}
+
+/// Load one member as returned by the member list.
+async fn fetch_member(
+    pool: &PgPool,
+    guild_id: Uuid,
+    user_id: Uuid,
+) -> sqlx::Result<Option<GuildMember>> {
+    sqlx::query_as::<_, GuildMember>(&format!(
+        "SELECT {MEMBER_COLUMNS}
+           FROM guild_members gm
+           INNER JOIN users u ON gm.user_id = u.id
+           WHERE gm.guild_id = $1 AND gm.user_id = $2"
+    ))
+    .bind(guild_id)
+    .bind(user_id)
+    .fetch_optional(pool)
+    .await
+}
+
+/// Set or reset a member's guild avatar, deleting the replaced image.
+///
+/// Returns `None` if the user is not a member.
+pub(super) async fn set_guild_avatar(
+    state: &AppState,
+    guild_id: Uuid,
+    user_id: Uuid,
+    avatar_url: Option<String>,
+) -> sqlx::Result<Option<GuildMember>> {
+    let previous: Option<Option<String>> = sqlx::query_scalar(
+        "SELECT avatar_url FROM guild_members WHERE guild_id = $1 AND user_id = $2",
+    )
+    .bind(guild_id)
+    .bind(user_id)
+    .fetch_optional(&state.db)
+    .await?;
+    let Some(previous) = previous else {
+        return Ok(None);
+    };
+
+    sqlx::query("UPDATE guild_members SET avatar_url = $3 WHERE guild_id = $1 AND user_id = $2")
+        .bind(guild_id)
+        .bind(user_id)
+        .bind(&avatar_url)
+        .execute(&state.db)
+        .await?;
+
+    if let Some(previous) = previous.filter(|previous| Some(previous) != avatar_url.as_ref()) {
+        images::delete_renditions(state, &previous, guild_id, GuildImageKind::MemberAvatar).await;
+    }
+
+    broadcast_profile(
+        state,
+        guild_id,
+        user_id,
+        serde_json::json!({ "guild_avatar_url": avatar_url }),
+    )
+    .await;
+
+    fetch_member(&state.db, guild_id, user_id).await
+}
+
+/// Change a member's guild nickname or reset their guild avatar
+///
+/// Members can change their own; others need `MANAGE_NICKNAMES` and a
+/// higher role than the target.
+#[utoipa::path(
+    patch,
+    path = "/api/guilds/{id}/members/{user_id}",
+    tag = "guilds",
+    params(
+        ("id" = Uuid, Path, description = "Guild ID"),
+        ("user_id" = Uuid, Path, description = "User ID")
+    ),
+    request_body = UpdateMemberRequest,
+    responses((status = 200, body = GuildMember)),
+    security(("bearer_auth" = []))
+)]
+#[tracing::instrument(skip(state, body))]
+pub async fn update_member(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path((guild_id, user_id)): Path<(Uuid, Uuid)>,
+    Json(body): Json<UpdateMemberRequest>,
+) -> Result<Json<GuildMember>, GuildError> {
+    if body.nickname.is_none() && body.guild_avatar_url.is_none() {
+        return Err(GuildError::Validation("No fields to update".to_string()));
+    }
+    if matches!(body.guild_avatar_url, Some(Some(_))) {
+        return Err(GuildError::Validation(
+            "Guild avatars can only be reset here; upload new ones to /members/@me/avatar"
+                .to_string(),
+        ));
+    }
+    let nickname = match body.nickname.as_ref() {
+        Some(Some(raw)) => Some(normalize_nickname(raw)?),
+        Some(None) => Some(None),
+        None => None,
+    };
+
+    if user_id == auth.id {
+        if !db::is_guild_member(&state.db, guild_id, auth.id).await? {
+            return Err(GuildError::Forbidden);
+        }
+    } else {
+        let ctx = require_moderator(
+            &state,
+            guild_id,
+            auth.id,
+            GuildPermissions::MANAGE_NICKNAMES,
+        )
+        .await?;
+        require_outranks(&state, &ctx, guild_id, user_id).await?;
+    }
+
+    let not_member = || GuildError::Validation("User is not a member of this guild".to_string());
+
+    if let Some(nickname) = &nickname {
+        let updated = sqlx::query(
+            "UPDATE guild_members SET nickname = $3 WHERE guild_id = $1 AND user_id = $2",
+        )
+        .bind(guild_id)
+        .bind(user_id)
+        .bind(nickname)
+        .execute(&state.db)
+        .await?
+        .rows_affected();
+        if updated == 0 {
+            return Err(not_member());
+        }
+        broadcast_profile(
+            &state,
+            guild_id,
+            user_id,
+            serde_json::json!({ "nickname": nickname }),
+        )
+        .await;
+    }
+
+    let member = if body.guild_avatar_url.is_some() {
+        set_guild_avatar(&state, guild_id, user_id, None).await?
+    } else {
+        fetch_member(&state.db, guild_id, user_id).await?
+    }
+    .ok_or_else(not_member)?;
+
+    let mut changes = serde_json::Map::new();
+    if let Some(nickname) = nickname {
+        changes.insert("nickname".to_string(), serde_json::json!(nickname));
+    }
+    if body.guild_avatar_url.is_some() {
+        changes.insert("guild_avatar_url".to_string(), serde_json::Value::Null);
+    }
+    audit::record(
+        &state,
+        guild_id,
+        auth.id,
+        "guild.members.profile_updated",
+        Some("user"),
+        Some(user_id),
+        Some(serde_json::Value::Object(changes)),
+    )
+    .await;
+
+    Ok(Json(member))
+}
+
+/// Guild profiles of message authors, keyed by `(channel_id, user_id)`.
+///
+/// Only authors in guild channels who set a nickname or guild avatar are
+/// included.
+pub async fn load_author_profiles(
+    pool: &PgPool,
+    authors: &[(Uuid, Uuid)],
+) -> sqlx::Result<HashMap<(Uuid, Uuid), MemberProfile>> {
+    let authors: HashSet<(Uuid, Uuid)> = authors.iter().copied().collect();
+    if authors.is_empty() {
+        return Ok(HashMap::new());
+    }
+    let (channel_ids, user_ids): (Vec<Uuid>, Vec<Uuid>) = authors.into_iter().unzip();
+
+    let rows: Vec<(Uuid, Uuid, Option<String>, Option<String>)> = sqlx::query_as(
+        r"SELECT a.channel_id, a.user_id, gm.nickname, gm.avatar_url
+           FROM UNNEST($1::uuid[], $2::uuid[]) AS a(channel_id, user_id)
+           INNER JOIN channels c ON c.id = a.channel_id
+           INNER JOIN guild_members gm ON gm.guild_id = c.guild_id AND gm.user_id = a.user_id
+           WHERE gm.nickname IS NOT NULL OR gm.avatar_url IS NOT NULL",
+    )
+    .bind(&channel_ids)
+    .bind(&user_ids)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|(channel_id, user_id, nickname, avatar_url)| {
+            (
+                (channel_id, user_id),
+                MemberProfile {
+                    nickname,
+                    avatar_url,
+                },
+            )
+        })
+        .collect())
+}
+
+/// Guild profile of one message author, if they set one.
+pub async fn load_author_profile(
+    pool: &PgPool,
+    channel_id: Uuid,
+    user_id: Uuid,
+) -> sqlx::Result<Option<MemberProfile>> {
+    Ok(load_author_profiles(pool, &[(channel_id, user_id)])
+        .await?
+        .remove(&(channel_id, user_id)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_nickname() {
+        assert_eq!(
+            normalize_nickname("  Captain  ").unwrap().as_deref(),
+            Some("Captain")
+        );
+        assert_eq!(normalize_nickname("   ").unwrap(), None);
+        assert!(normalize_nickname(&"x".repeat(MAX_NICKNAME_LENGTH)).is_ok());
+        assert!(normalize_nickname(&"x".repeat(MAX_NICKNAME_LENGTH + 1)).is_err());
+        assert!(normalize_nickname("nick\u{7}").is_err());
+    }
+}
@@ -1,17 +1,29 @@
 //! Guild (Server) Management Module
 //!
-//! Handles guild creation, membership, invites, roles, categories, search, and management.
+//! Handles guild creation, icon/banner uploads, membership, member profiles, bans, timeouts, invites, roles, member imports, onboarding, message retention, categories, search, mention autocomplete, audit log and its streaming, and management.
 
+pub mod audit;
+pub mod autocomplete;
+pub mod bans;
 pub mod categories;
+pub mod channel_positions;
 pub mod emojis;
 pub mod handlers;
+pub mod images;
 pub mod invites;
 pub mod limits;
+pub mod log_stream;
+pub mod member_import;
+pub mod member_profiles;
+pub mod onboarding;
+pub mod perks;
+pub mod retention;
 pub mod roles;
 pub mod search;
+pub mod timeouts;
 pub mod types;
 
-use axum::routing::{delete, get, patch, post};
+use axum::routing::{delete, get, patch, post, put};
 use axum::Router;
 
 use crate::api::AppState;
@@ -27,9 +39,47 @@ pub fn router() -> Router<AppState> {
                 .patch(handlers::update_guild)
                 .delete(handlers::delete_guild),
         )
+        .route(
+            "/{id}/icon",
+            post(images::upload_icon).delete(images::delete_icon),
+        )
+        .route(
+            "/{id}/banner",
+            post(images::upload_banner).delete(images::delete_banner),
+        )
         .route("/{id}/leave", post(handlers::leave_guild))
         .route("/{id}/members", get(handlers::list_members))
-        .route("/{id}/members/{user_id}", delete(handlers::kick_member))
+        .route(
+            "/{id}/members/bulk-roles",
+            post(roles::bulk_update_member_roles),
+        )
+        .route(
+            "/{id}/members/import",
+            post(member_import::start_member_import),
+        )
+        .route(
+            "/{id}/members/import/{job_id}",
+            get(member_import::get_member_import),
+        )
+        .route(
+            "/{id}/members/import/{job_id}/report",
+            get(member_import::download_member_import_report),
+        )
+        .route(
+            "/{id}/members/@me/avatar",
+            post(images::upload_member_avatar),
+        )
+        .route(
+            "/{id}/members/{user_id}",
+            patch(member_profiles::update_member).delete(handlers::kick_member),
+        )
+        .route(
+            "/{id}/members/{user_id}/timeout",
+            patch(timeouts::set_member_timeout),
+        )
+        .route("/{id}/bans", get(bans::list_bans).post(bans::ban_member))
+        .route("/{id}/bans/{user_id}", delete(bans::unban_member))
+        .route("/{id}/audit-log", get(audit::list_audit_log))
         .route("/{id}/bots", get(handlers::list_guild_bots))
         .route("/{id}/bots/{bot_id}/add", post(handlers::add_bot_to_guild))
         .route(
@@ -37,24 +87,57 @@ pub fn router() -> Router<AppState> {
             delete(handlers::remove_bot_from_guild),
         )
         .route("/{id}/usage", get(handlers::get_guild_usage))
+        .route("/{id}/perks", get(perks::get_guild_perks))
         .route("/{id}/channels", get(handlers::list_channels))
         .route("/{id}/channels/reorder", post(handlers::reorder_channels))
+        .route(
+            "/{id}/channels/positions",
+            patch(channel_positions::update_channel_positions),
+        )
         .route("/{id}/read-all", post(handlers::mark_all_channels_read))
         .route("/{id}/commands", get(handlers::list_guild_commands))
+        .route("/{id}/autocomplete", get(autocomplete::autocomplete))
         // Guild settings
         .route(
             "/{id}/settings",
             get(handlers::get_guild_settings).patch(handlers::update_guild_settings),
         )
+        // Message retention
+        .route(
+            "/{id}/retention",
+            get(retention::get_retention).put(retention::set_guild_retention),
+        )
+        .route(
+            "/{id}/retention/channels/{channel_id}",
+            put(retention::set_channel_retention),
+        )
+        // Moderation log streaming
+        .route(
+            "/{id}/log-stream",
+            get(log_stream::get_log_stream)
+                .put(log_stream::set_log_stream)
+                .delete(log_stream::delete_log_stream),
+        )
+        // Onboarding
+        .route(
+            "/{id}/onboarding",
+            get(onboarding::get_onboarding).put(onboarding::set_onboarding),
+        )
+        .route(
+            "/{id}/onboarding/complete",
+            post(onboarding::complete_onboarding),
+        )
         // Role routes
         .route(
             "/{id}/roles",
             get(roles::list_roles).post(roles::create_role),
         )
+        .route("/{id}/roles/reorder", post(roles::reorder_roles))
         .route(
             "/{id}/roles/{role_id}",
             patch(roles::update_role).delete(roles::delete_role),
         )
+        .route("/{id}/member-roles", get(roles::list_member_roles))
         .route(
             "/{id}/members/{user_id}/roles/{role_id}",
             post(roles::assign_role).delete(roles::remove_role),
@@ -93,3 +176,8 @@ pub fn router() -> Router<AppState> {
 pub fn invite_router() -> Router<AppState> {
     Router::new().route("/{code}/join", post(invites::join_via_invite))
 }
+
+/// Create the public invite preview router (no authentication required)
+pub fn invite_public_router() -> Router<AppState> {
+    Router::new().route("/{code}", get(invites::preview_invite))
+}
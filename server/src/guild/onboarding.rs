@@ -0,0 +1,440 @@
+//! Guild Onboarding
+//!
+//! A guild can greet new members with a welcome screen (a description and
+//! highlighted channels), require them to accept a rules page before they can
+//! post, and hand out roles once they complete onboarding. The rules gate uses
+//! the pages acceptance tracking: completing onboarding records acceptance of
+//! the page's current version.
+//!
+//! Members who join while the rules gate is set are `pending` until they
+//! complete onboarding. Join responses carry the onboarding so clients can
+//! show the welcome screen right away.
+
+use axum::extract::{Path, State};
+use axum::Json;
+use sqlx::{PgConnection, PgPool};
+use uuid::Uuid;
+
+use super::audit;
+use super::handlers::GuildError;
+use super::roles::{check_bulk_assignable, RoleError};
+use super::types::{
+    GuildOnboarding, OnboardingCompletion, UpdateOnboardingRequest, WelcomeChannel,
+};
+use crate::api::AppState;
+use crate::auth::AuthUser;
+use crate::db;
+use crate::pages::queries as page_queries;
+use crate::permissions::{require_guild_permission, GuildPermissions, MemberPermissionContext};
+use crate::ws::{broadcast_member_patch, broadcast_to_guild, ServerEvent};
+
+/// Longest welcome screen description, in characters.
+const MAX_DESCRIPTION_LENGTH: usize = 1000;
+
+/// Most channels highlighted on the welcome screen.
+const MAX_WELCOME_CHANNELS: usize = 5;
+
+/// Longest description of a highlighted channel, in characters.
+const MAX_CHANNEL_DESCRIPTION_LENGTH: usize = 100;
+
+/// Most roles assigned on completion.
+const MAX_DEFAULT_ROLES: usize = 10;
+
+type OnboardingRow = (
+    bool,
+    Option<String>,
+    sqlx::types::Json<Vec<WelcomeChannel>>,
+    Option<Uuid>,
+    Vec<Uuid>,
+);
+
+fn onboarding_from_row(guild_id: Uuid, row: Option<OnboardingRow>) -> GuildOnboarding {
+    match row {
+        Some((enabled, welcome_description, channels, rules_page_id, default_role_ids)) => {
+            GuildOnboarding {
+                guild_id,
+                enabled,
+                welcome_description,
+                welcome_channels: channels.0,
+                rules_page_id,
+                default_role_ids,
+            }
+        }
+        None => GuildOnboarding {
+            guild_id,
+            enabled: false,
+            welcome_description: None,
+            welcome_channels: Vec::new(),
+            rules_page_id: None,
+            default_role_ids: Vec::new(),
+        },
+    }
+}
+
+/// Load a guild's onboarding; guilds that never configured it get a disabled one.
+pub async fn load(pool: &PgPool, guild_id: Uuid) -> sqlx::Result<GuildOnboarding> {
+    let row: Option<OnboardingRow> = sqlx::query_as(
+        r"SELECT enabled, welcome_description, welcome_channels, rules_page_id, default_role_ids
+          FROM guild_onboarding WHERE guild_id = $1",
+    )
+    .bind(guild_id)
+    .fetch_optional(pool)
+    .await?;
+    Ok(onboarding_from_row(guild_id, row))
+}
+
+/// Onboarding to include in a join response, if the guild enabled it.
+///
+/// Never fails the join: a failed load is logged and left out.
+pub async fn join_payload(pool: &PgPool, guild_id: Uuid) -> Option<GuildOnboarding> {
+    match load(pool, guild_id).await {
+        Ok(onboarding) => onboarding.enabled.then_some(onboarding),
+        Err(e) => {
+            tracing::warn!(guild_id = %guild_id, error = %e, "Failed to load guild onboarding");
+            None
+        }
+    }
+}
+
+/// Mark a member who just joined as pending if the guild gates on rules.
+///
+/// Run in the join transaction, right after the membership insert.
+pub async fn mark_pending(
+    conn: &mut PgConnection,
+    guild_id: Uuid,
+    user_id: Uuid,
+) -> sqlx::Result<()> {
+    sqlx::query(
+        r"UPDATE guild_members SET pending = true
+          WHERE guild_id = $1 AND user_id = $2
+            AND EXISTS (
+                SELECT 1 FROM guild_onboarding o
+                WHERE o.guild_id = $1 AND o.enabled AND o.rules_page_id IS NOT NULL
+            )",
+    )
+    .bind(guild_id)
+    .bind(user_id)
+    .execute(conn)
+    .await?;
+    Ok(())
+}
+
+fn validate_text(text: &str, max: usize, field: &str) -> Result<(), GuildError> {
+    if text.chars().count() > max {
+        return Err(GuildError::Validation(format!(
+            "{field} must be at most {max} characters"
+        )));
+    }
+    Ok(())
+}
+
+/// Check the request against the guild: channels, rules page and roles must
+/// belong to it, and the actor must be able to assign every default role.
+async fn validate_request(
+    state: &AppState,
+    guild_id: Uuid,
+    ctx: &MemberPermissionContext,
+    body: &UpdateOnboardingRequest,
+) -> Result<(), GuildError> {
+    if let Some(description) = &body.welcome_description {
+        validate_text(description, MAX_DESCRIPTION_LENGTH, "Welcome description")?;
+    }
+    if body.welcome_channels.len() > MAX_WELCOME_CHANNELS {
+        return Err(GuildError::Validation(format!(
+            "At most {MAX_WELCOME_CHANNELS} welcome channels are allowed"
+        )));
+    }
+    for channel in &body.welcome_channels {
+        validate_text(
+            &channel.description,
+            MAX_CHANNEL_DESCRIPTION_LENGTH,
+            "Channel description",
+        )?;
+    }
+    let channel_ids: Vec<Uuid> = body.welcome_channels.iter().map(|c| c.channel_id).collect();
+    let found: i64 = sqlx::query_scalar(
+        "SELECT COUNT(DISTINCT id) FROM channels WHERE guild_id = $1 AND id = ANY($2)",
+    )
+    .bind(guild_id)
+    .bind(&channel_ids)
+    .fetch_one(&state.db)
+    .await?;
+    if found != channel_ids.len() as i64 {
+        return Err(GuildError::Validation(
+            "Welcome channels must be distinct channels of this guild".to_string(),
+        ));
+    }
+
+    if let Some(page_id) = body.rules_page_id {
+        let page = page_queries::get_page_by_id(&state.db, page_id).await?;
+        match page {
+            Some(page) if page.guild_id == Some(guild_id) && page.requires_acceptance => {}
+            _ => {
+                return Err(GuildError::Validation(
+                    "Rules page must be a page of this guild that requires acceptance".to_string(),
+                ))
+            }
+        }
+    }
+
+    if body.default_role_ids.len() > MAX_DEFAULT_ROLES {
+        return Err(GuildError::Validation(format!(
+            "At most {MAX_DEFAULT_ROLES} default roles are allowed"
+        )));
+    }
+    if !body.default_role_ids.is_empty() {
+        let roles: Vec<(i32, bool)> = sqlx::query_as(
+            "SELECT position, is_default FROM guild_roles WHERE guild_id = $1 AND id = ANY($2)",
+        )
+        .bind(guild_id)
+        .bind(&body.default_role_ids)
+        .fetch_all(&state.db)
+        .await?;
+        if roles.len() != body.default_role_ids.len() {
+            return Err(GuildError::Validation(
+                "Default roles must be distinct roles of this guild".to_string(),
+            ));
+        }
+        for (position, is_default) in roles {
+            check_bulk_assignable(ctx, position, is_default).map_err(|e| match e {
+                RoleError::Permission(e) => GuildError::Permission(e),
+                RoleError::Validation(msg) => GuildError::Validation(msg),
+                other => GuildError::Validation(other.to_string()),
+            })?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Get the guild's onboarding (members only)
+#[utoipa::path(
+    get,
+    path = "/api/guilds/{id}/onboarding",
+    tag = "guilds",
+    params(("id" = Uuid, Path, description = "Guild ID")),
+    responses((status = 200, body = GuildOnboarding)),
+    security(("bearer_auth" = []))
+)]
+#[tracing::instrument(skip(state))]
+pub async fn get_onboarding(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(guild_id): Path<Uuid>,
+) -> Result<Json<GuildOnboarding>, GuildError> {
+    if !db::is_guild_member(&state.db, guild_id, auth.id).await? {
+        return Err(GuildError::Forbidden);
+    }
+    Ok(Json(load(&state.db, guild_id).await?))
+}
+
+/// Replace the guild's onboarding (requires `MANAGE_GUILD`)
+///
+/// Default roles also need `MANAGE_ROLES` and must be below the caller's
+/// highest role.
+#[utoipa::path(
+    put,
+    path = "/api/guilds/{id}/onboarding",
+    tag = "guilds",
+    params(("id" = Uuid, Path, description = "Guild ID")),
+    request_body = UpdateOnboardingRequest,
+    responses((status = 200, body = GuildOnboarding)),
+    security(("bearer_auth" = []))
+)]
+#[tracing::instrument(skip(state, body))]
+pub async fn set_onboarding(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(guild_id): Path<Uuid>,
+    Json(mut body): Json<UpdateOnboardingRequest>,
+) -> Result<Json<GuildOnboarding>, GuildError> {
+    let ctx =
+        require_guild_permission(&state.db, guild_id, auth.id, GuildPermissions::MANAGE_GUILD)
+            .await
+            .map_err(GuildError::Permission)?;
+
+    body.welcome_description = body
+        .welcome_description
+        .map(|d| d.trim().to_string())
+        .filter(|d| !d.is_empty());
+    for channel in &mut body.welcome_channels {
+        channel.description = channel.description.trim().to_string();
+    }
+    validate_request(&state, guild_id, &ctx, &body).await?;
+
+    sqlx::query(
+        r"INSERT INTO guild_onboarding
+              (guild_id, enabled, welcome_description, welcome_channels, rules_page_id, default_role_ids)
+          VALUES ($1, $2, $3, $4, $5, $6)
+          ON CONFLICT (guild_id) DO UPDATE SET
+              enabled = EXCLUDED.enabled,
+              welcome_description = EXCLUDED.welcome_description,
+              welcome_channels = EXCLUDED.welcome_channels,
+              rules_page_id = EXCLUDED.rules_page_id,
+              default_role_ids = EXCLUDED.default_role_ids,
+              updated_at = NOW()",
+    )
+    .bind(guild_id)
+    .bind(body.enabled)
+    .bind(&body.welcome_description)
+    .bind(sqlx::types::Json(&body.welcome_channels))
+    .bind(body.rules_page_id)
+    .bind(&body.default_role_ids)
+    .execute(&state.db)
+    .await?;
+
+    audit::record(
+        &state,
+        guild_id,
+        auth.id,
+        "guild.onboarding.updated",
+        Some("guild"),
+        Some(guild_id),
+        Some(serde_json::json!({
+            "enabled": body.enabled,
+            "rules_page_id": body.rules_page_id,
+            "default_role_ids": body.default_role_ids,
+        })),
+    )
+    .await;
+
+    Ok(Json(load(&state.db, guild_id).await?))
+}
+
+/// Complete onboarding
+///
+/// Accepts the rules page (if the guild has one), lifts the pending state and
+/// assigns the default roles. Roles are only assigned the first time.
+#[utoipa::path(
+    post,
+    path = "/api/guilds/{id}/onboarding/complete",
+    tag = "guilds",
+    params(("id" = Uuid, Path, description = "Guild ID")),
+    responses((status = 200, body = OnboardingCompletion)),
+    security(("bearer_auth" = []))
+)]
+#[tracing::instrument(skip(state))]
+pub async fn complete_onboarding(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(guild_id): Path<Uuid>,
+) -> Result<Json<OnboardingCompletion>, GuildError> {
+    if !db::is_guild_member(&state.db, guild_id, auth.id).await? {
+        return Err(GuildError::Forbidden);
+    }
+    let onboarding = load(&state.db, guild_id).await?;
+    if !onboarding.enabled {
+        return Err(GuildError::Validation(
+            "This guild has no onboarding".to_string(),
+        ));
+    }
+
+    if let Some(page_id) = onboarding.rules_page_id {
+        if let Some(page) = page_queries::get_page_by_id(&state.db, page_id).await? {
+            page_queries::accept_page(&state.db, auth.id, page_id, &page.content_hash).await?;
+        }
+    }
+
+    let mut tx = state.db.begin().await?;
+    // `None` once the member has completed onboarding before
+    let was_pending: Option<bool> = sqlx::query_scalar(
+        r"SELECT pending FROM guild_members
+          WHERE guild_id = $1 AND user_id = $2 AND onboarded_at IS NULL
+          FOR UPDATE",
+    )
+    .bind(guild_id)
+    .bind(auth.id)
+    .fetch_optional(&mut *tx)
+    .await?;
+    if was_pending.is_some() {
+        sqlx::query(
+            "UPDATE guild_members SET pending = false, onboarded_at = NOW()
+             WHERE guild_id = $1 AND user_id = $2",
+        )
+        .bind(guild_id)
+        .bind(auth.id)
+        .execute(&mut *tx)
+        .await?;
+    }
+    let assigned: Vec<Uuid> = if was_pending.is_some() {
+        sqlx::query_scalar(
+            r"INSERT INTO guild_member_roles (guild_id, user_id, role_id)
+              SELECT $1, $2, r.id FROM guild_roles r
+              WHERE r.guild_id = $1 AND r.id = ANY($3) AND NOT r.is_default
+              ON CONFLICT (guild_id, user_id, role_id) DO NOTHING
+              RETURNING role_id",
+        )
+        .bind(guild_id)
+        .bind(auth.id)
+        .bind(&onboarding.default_role_ids)
+        .fetch_all(&mut *tx)
+        .await?
+    } else {
+        Vec::new()
+    };
+    tx.commit().await?;
+
+    let role_ids: Vec<Uuid> = sqlx::query_scalar(
+        "SELECT role_id FROM guild_member_roles WHERE guild_id = $1 AND user_id = $2",
+    )
+    .bind(guild_id)
+    .bind(auth.id)
+    .fetch_all(&state.db)
+    .await?;
+
+    if was_pending == Some(true) {
+        if let Err(e) = broadcast_member_patch(
+            &state.redis,
+            guild_id,
+            auth.id,
+            serde_json::json!({ "pending": false }),
+        )
+        .await
+        {
+            tracing::warn!(guild_id = %guild_id, error = %e, "Failed to broadcast onboarding patch");
+        }
+    }
+    if !assigned.is_empty() {
+        let event = ServerEvent::MemberRolesUpdate {
+            guild_id,
+            user_id: auth.id,
+            role_ids: role_ids.clone(),
+        };
+        if let Err(e) = broadcast_to_guild(&state.redis, guild_id, &event).await {
+            tracing::warn!(guild_id = %guild_id, error = %e, "Failed to broadcast onboarding roles");
+        }
+    }
+
+    Ok(Json(OnboardingCompletion { role_ids }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_missing_row_is_disabled() {
+        let guild_id = Uuid::new_v4();
+        let onboarding = onboarding_from_row(guild_id, None);
+        assert_eq!(onboarding.guild_id, guild_id);
+        assert!(!onboarding.enabled);
+        assert!(onboarding.welcome_channels.is_empty());
+        assert!(onboarding.rules_page_id.is_none());
+    }
+
+    #[test]
+    fn test_validate_text_counts_characters() {
+        assert!(validate_text(
+            &"é".repeat(MAX_CHANNEL_DESCRIPTION_LENGTH),
+            MAX_CHANNEL_DESCRIPTION_LENGTH,
+            "x"
+        )
+        .is_ok());
+        assert!(validate_text(
+            &"é".repeat(MAX_CHANNEL_DESCRIPTION_LENGTH + 1),
+            MAX_CHANNEL_DESCRIPTION_LENGTH,
+            "x"
+        )
+        .is_err());
+    }
+}
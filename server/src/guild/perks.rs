@@ -0,0 +1,312 @@
+//! Guild Supporter Perks
+//!
+//! Members can be flagged as supporters of a guild (granted manually by a
+//! system admin, or by the billing entitlement webhook in `admin::entitlements`). The number of supporters
+//! puts the guild into a perk tier, and each tier raises a few limits:
+//!
+//! - custom emoji slots (enforced in `guild::emojis`)
+//! - upload size (enforced in `chat::uploads`)
+//! - Opus voice bitrate cap (enforced by the SFU via the SDP offer)
+//!
+//! Tier thresholds and per-tier bonuses come from [`Config`].
+
+use axum::extract::{Path, State};
+use axum::Json;
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use tracing::warn;
+use uuid::Uuid;
+
+use super::handlers::GuildError;
+use crate::api::AppState;
+use crate::auth::AuthUser;
+use crate::config::Config;
+use crate::db;
+use crate::ws::{broadcast_to_guild, ServerEvent};
+
+/// How a member became a supporter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SupporterSource {
+    /// Granted by a system admin.
+    Manual,
+    /// Granted by a billing integration.
+    Billing,
+}
+
+impl SupporterSource {
+    const fn as_str(self) -> &'static str {
+        match self {
+            Self::Manual => "manual",
+            Self::Billing => "billing",
+        }
+    }
+}
+
+/// Effective perks of a guild.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct GuildPerks {
+    pub guild_id: Uuid,
+    /// Perk tier (0 = no perks).
+    pub tier: u32,
+    /// Number of supporting members.
+    pub supporter_count: i64,
+    /// Supporters needed for the next tier (`None` at the highest tier).
+    pub next_tier_at: Option<i64>,
+    /// Maximum number of custom emojis.
+    pub emoji_slots: i64,
+    /// Maximum upload size in bytes.
+    pub max_upload_size: usize,
+    /// Opus voice bitrate cap in bits per second.
+    pub voice_bitrate: u32,
+}
+
+impl GuildPerks {
+    /// Compute the perks for a given supporter count.
+    #[must_use]
+    pub fn compute(config: &Config, guild_id: Uuid, supporter_count: i64) -> Self {
+        let tier = config
+            .perk_tier_thresholds
+            .iter()
+            .take_while(|&&threshold| supporter_count >= threshold)
+            .count();
+
+        Self {
+            guild_id,
+            tier: tier as u32,
+            supporter_count,
+            next_tier_at: config.perk_tier_thresholds.get(tier).copied(),
+            emoji_slots: config.max_emojis_per_guild
+                + config.perk_emoji_slots_per_tier * tier as i64,
+            max_upload_size: config.max_upload_size + config.perk_upload_size_per_tier * tier,
+            voice_bitrate: voice_bitrate_for_tier(config, tier),
+        }
+    }
+
+    /// Perks for DM channels and guilds without supporters.
+    #[must_use]
+    pub fn base(config: &Config, guild_id: Uuid) -> Self {
+        Self::compute(config, guild_id, 0)
+    }
+}
+
+/// Voice bitrate for a tier, falling back to the last configured value.
+fn voice_bitrate_for_tier(config: &Config, tier: usize) -> u32 {
+    config
+        .perk_voice_bitrates
+        .get(tier)
+        .or_else(|| config.perk_voice_bitrates.last())
+        .copied()
+        .unwrap_or(64_000)
+}
+
+/// Largest upload size any guild can reach (used for the request body limit).
+#[must_use]
+pub fn max_upload_size_ceiling(config: &Config) -> usize {
+    config.max_upload_size + config.perk_upload_size_per_tier * config.perk_tier_thresholds.len()
+}
+
+/// Count supporting members of a guild.
+pub async fn count_supporters(pool: &PgPool, guild_id: Uuid) -> Result<i64, sqlx::Error> {
+    let (count,): (i64,) = sqlx::query_as(
+        "SELECT COUNT(*) FROM guild_members WHERE guild_id = $1 AND supporter_since IS NOT NULL",
+    )
+    .bind(guild_id)
+    .fetch_one(pool)
+    .await?;
+    Ok(count)
+}
+
+/// Load the effective perks of a guild.
+pub async fn get_perks(
+    pool: &PgPool,
+    config: &Config,
+    guild_id: Uuid,
+) -> Result<GuildPerks, sqlx::Error> {
+    let supporters = count_supporters(pool, guild_id).await?;
+    Ok(GuildPerks::compute(config, guild_id, supporters))
+}
+
+/// Load the perks that apply to a channel (`None` for DM channels).
+pub async fn get_channel_perks(
+    pool: &PgPool,
+    config: &Config,
+    channel_id: Uuid,
+) -> Result<Option<GuildPerks>, sqlx::Error> {
+    let guild_id: Option<Uuid> = sqlx::query_scalar("SELECT guild_id FROM channels WHERE id = $1")
+        .bind(channel_id)
+        .fetch_optional(pool)
+        .await?
+        .flatten();
+
+    match guild_id {
+        Some(guild_id) => get_perks(pool, config, guild_id).await.map(Some),
+        None => Ok(None),
+    }
+}
+
+/// Grant (`Some`) or revoke (`None`) supporter status of a member.
+///
+/// Returns `false` if the user is not a member of the guild. Re-granting keeps
+/// the original `supporter_since`.
+pub async fn set_supporter(
+    pool: &PgPool,
+    guild_id: Uuid,
+    user_id: Uuid,
+    source: Option<SupporterSource>,
+) -> Result<bool, sqlx::Error> {
+    let result = sqlx::query(
+        r"UPDATE guild_members
+           SET supporter_since = CASE WHEN $3::text IS NULL THEN NULL
+                                      ELSE COALESCE(supporter_since, NOW()) END,
+               supporter_source = $3
+           WHERE guild_id = $1 AND user_id = $2",
+    )
+    .bind(guild_id)
+    .bind(user_id)
+    .bind(source.map(SupporterSource::as_str))
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// Apply a billing entitlement to a member's supporter status.
+///
+/// Billing never overrides a manual grant: activation keeps an existing
+/// source, and deactivation only clears billing-granted status. Returns
+/// `false` if the user is not a member of the guild.
+pub async fn set_billing_supporter(
+    executor: impl sqlx::PgExecutor<'_>,
+    guild_id: Uuid,
+    user_id: Uuid,
+    active: bool,
+) -> Result<bool, sqlx::Error> {
+    let query = if active {
+        sqlx::query(
+            r"UPDATE guild_members
+               SET supporter_since = COALESCE(supporter_since, NOW()),
+                   supporter_source = COALESCE(supporter_source, 'billing')
+               WHERE guild_id = $1 AND user_id = $2",
+        )
+    } else {
+        sqlx::query(
+            r"UPDATE guild_members
+               SET supporter_since = CASE WHEN supporter_source = 'billing'
+                                          THEN NULL ELSE supporter_since END,
+                   supporter_source = CASE WHEN supporter_source = 'billing'
+                                           THEN NULL ELSE supporter_source END
+               WHERE guild_id = $1 AND user_id = $2",
+        )
+    };
+
+    let result = query.bind(guild_id).bind(user_id).execute(executor).await?;
+    Ok(result.rows_affected() > 0)
+}
+
+/// Recompute a guild's perks after a supporter change and propagate them.
+///
+/// Broadcasts `GuildPerksUpdate` to the guild and applies the new voice
+/// bitrate cap to active voice rooms.
+pub async fn refresh(state: &AppState, guild_id: Uuid) -> Result<GuildPerks, sqlx::Error> {
+    let perks = get_perks(&state.db, &state.config(), guild_id).await?;
+
+    if let Err(e) = broadcast_to_guild(
+        &state.redis,
+        guild_id,
+        &ServerEvent::GuildPerksUpdate {
+            guild_id,
+            perks: perks.clone(),
+        },
+    )
+    .await
+    {
+        warn!(guild_id = %guild_id, error = %e, "Failed to broadcast GuildPerksUpdate event");
+    }
+
+    let voice_channels: Vec<Uuid> = sqlx::query_scalar(
+        "SELECT id FROM channels WHERE guild_id = $1 AND channel_type IN ('voice', 'stage')",
+    )
+    .bind(guild_id)
+    .fetch_all(&state.db)
+    .await?;
+    state
+        .sfu
+        .apply_audio_bitrate(&voice_channels, perks.voice_bitrate)
+        .await;
+
+    Ok(perks)
+}
+
+/// Get guild perks.
+/// GET /api/guilds/{id}/perks
+#[utoipa::path(
+    get,
+    path = "/api/guilds/{id}/perks",
+    tag = "guilds",
+    params(("id" = Uuid, Path, description = "Guild ID")),
+    responses((status = 200, body = GuildPerks)),
+    security(("bearer_auth" = []))
+)]
+#[tracing::instrument(skip(state))]
+pub async fn get_guild_perks(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(guild_id): Path<Uuid>,
+) -> Result<Json<GuildPerks>, GuildError> {
+    if !db::is_guild_member(&state.db, guild_id, auth.id).await? {
+        return Err(GuildError::Forbidden);
+    }
+
+    Ok(Json(get_perks(&state.db, &state.config(), guild_id).await?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> Config {
+        let mut config = Config::default_for_test();
+        config.max_emojis_per_guild = 50;
+        config.max_upload_size = 50;
+        config.perk_tier_thresholds = vec![2, 7, 14];
+        config.perk_emoji_slots_per_tier = 25;
+        config.perk_upload_size_per_tier = 10;
+        config.perk_voice_bitrates = vec![64_000, 128_000];
+        config
+    }
+
+    #[test]
+    fn test_tiers_follow_thresholds() {
+        let config = config();
+        let guild_id = Uuid::new_v4();
+
+        let tiers: Vec<u32> = [0, 1, 2, 6, 7, 14, 100]
+            .into_iter()
+            .map(|count| GuildPerks::compute(&config, guild_id, count).tier)
+            .collect();
+        assert_eq!(tiers, vec![0, 0, 1, 1, 2, 3, 3]);
+    }
+
+    #[test]
+    fn test_perks_scale_with_tier() {
+        let config = config();
+        let guild_id = Uuid::new_v4();
+
+        let base = GuildPerks::base(&config, guild_id);
+        assert_eq!(base.emoji_slots, 50);
+        assert_eq!(base.max_upload_size, 50);
+        assert_eq!(base.voice_bitrate, 64_000);
+        assert_eq!(base.next_tier_at, Some(2));
+
+        let tier2 = GuildPerks::compute(&config, guild_id, 7);
+        assert_eq!(tier2.emoji_slots, 100);
+        assert_eq!(tier2.max_upload_size, 70);
+        // Bitrate list shorter than the tier list: last value applies
+        assert_eq!(tier2.voice_bitrate, 128_000);
+        assert_eq!(tier2.next_tier_at, Some(14));
+
+        let top = GuildPerks::compute(&config, guild_id, 20);
+        assert_eq!(top.next_tier_at, None);
+        assert_eq!(max_upload_size_ceiling(&config), top.max_upload_size);
+    }
+}
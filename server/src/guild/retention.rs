@@ -0,0 +1,393 @@
+//! Message Retention
+//!
+//! Guilds can set how many days messages are kept, and each text or voice
+//! channel can override the guild default. Voice channel chat is short-lived:
+//! new voice channels start with a one-day override. A scheduled purge hard-deletes expired
+//! messages together with their S3 attachments. Threads are purged as a
+//! whole once their last reply has expired, so a live thread never loses
+//! its parent.
+//!
+//! The policy in effect is part of the channel metadata (`retention_days`)
+//! so clients can tell users how long messages are kept.
+//!
+//! Instance admins bound these policies (`admin::retention`): guilds without
+//! a policy use the instance default, and no policy can exceed the instance
+//! maximum. Deleted messages are purged for good after the instance's
+//! soft-delete window.
+
+use axum::extract::{Path, State};
+use axum::Json;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use super::audit;
+use super::handlers::GuildError;
+use super::types::{ChannelRetention, GuildRetentionPolicy, SetRetentionRequest};
+use crate::admin::retention::RetentionSettings;
+use crate::api::AppState;
+use crate::auth::AuthUser;
+use crate::chat::S3Client;
+use crate::db::ChannelType;
+use crate::permissions::{require_guild_permission, GuildPermissions};
+
+/// Shortest allowed retention.
+pub const MIN_RETENTION_DAYS: i32 = 1;
+
+/// Longest allowed retention (about ten years); admins can lower it for the
+/// instance.
+pub const MAX_RETENTION_DAYS: i32 = 3650;
+
+/// Retention new voice channels start with (24 hours).
+pub const VOICE_CHAT_RETENTION_DAYS: i32 = 1;
+
+/// Messages deleted per purge batch.
+const PURGE_BATCH_SIZE: i64 = 500;
+
+/// Batches per purge run; the rest is picked up by the next run.
+const MAX_PURGE_BATCHES: usize = 100;
+
+/// Check `retention_days` against the instance maximum, `max_days`.
+fn validate_retention(retention_days: Option<i32>, max_days: i32) -> Result<(), GuildError> {
+    match retention_days {
+        Some(days) if !(MIN_RETENTION_DAYS..=max_days).contains(&days) => {
+            Err(GuildError::Validation(format!(
+                "retention_days must be between {MIN_RETENTION_DAYS} and {max_days}"
+            )))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Retention override a new channel of `channel_type` starts with.
+pub fn default_channel_retention(channel_type: &ChannelType) -> Option<i32> {
+    channel_type.is_voice().then_some(VOICE_CHAT_RETENTION_DAYS)
+}
+
+async fn require_retention_manager(
+    state: &AppState,
+    guild_id: Uuid,
+    user_id: Uuid,
+) -> Result<(), GuildError> {
+    require_guild_permission(&state.db, guild_id, user_id, GuildPermissions::MANAGE_GUILD)
+        .await
+        .map_err(GuildError::Permission)?;
+    Ok(())
+}
+
+/// Load the guild default and the text and voice channel overrides.
+///
+/// The effective retention falls back to the instance default and is capped
+/// at the instance maximum, like the purge.
+async fn load_policy(pool: &PgPool, guild_id: Uuid) -> Result<GuildRetentionPolicy, GuildError> {
+    let settings = RetentionSettings::load(pool).await;
+    let retention_days: Option<i32> =
+        sqlx::query_scalar("SELECT retention_days FROM guilds WHERE id = $1")
+            .bind(guild_id)
+            .fetch_optional(pool)
+            .await?
+            .ok_or(GuildError::NotFound)?;
+
+    let mut channels: Vec<ChannelRetention> = sqlx::query_as(
+        r"SELECT id AS channel_id, name, retention_days,
+                 COALESCE(retention_days, $2) AS effective_retention_days
+          FROM channels
+          WHERE guild_id = $1 AND channel_type IN ('text', 'voice', 'stage')
+          ORDER BY position ASC",
+    )
+    .bind(guild_id)
+    .bind(retention_days)
+    .fetch_all(pool)
+    .await?;
+    for channel in &mut channels {
+        channel.effective_retention_days = channel
+            .effective_retention_days
+            .or(settings.message_default_days)
+            .map(|days| days.min(settings.message_max_days));
+    }
+
+    Ok(GuildRetentionPolicy {
+        retention_days,
+        channels,
+        max_retention_days: settings.message_max_days,
+    })
+}
+
+/// Get the guild's message retention policy (requires `MANAGE_GUILD`)
+#[utoipa::path(
+    get,
+    path = "/api/guilds/{id}/retention",
+    tag = "guilds",
+    params(("id" = Uuid, Path, description = "Guild ID")),
+    responses((status = 200, body = GuildRetentionPolicy)),
+    security(("bearer_auth" = []))
+)]
+#[tracing::instrument(skip(state))]
+pub async fn get_retention(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(guild_id): Path<Uuid>,
+) -> Result<Json<GuildRetentionPolicy>, GuildError> {
+    require_retention_manager(&state, guild_id, auth.id).await?;
+    Ok(Json(load_policy(&state.db, guild_id).await?))
+}
+
+/// Set the guild's default message retention (requires `MANAGE_GUILD`)
+#[utoipa::path(
+    put,
+    path = "/api/guilds/{id}/retention",
+    tag = "guilds",
+    params(("id" = Uuid, Path, description = "Guild ID")),
+    request_body = SetRetentionRequest,
+    responses((status = 200, body = GuildRetentionPolicy)),
+    security(("bearer_auth" = []))
+)]
+#[tracing::instrument(skip(state))]
+pub async fn set_guild_retention(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(guild_id): Path<Uuid>,
+    Json(body): Json<SetRetentionRequest>,
+) -> Result<Json<GuildRetentionPolicy>, GuildError> {
+    require_retention_manager(&state, guild_id, auth.id).await?;
+    let settings = RetentionSettings::load(&state.db).await;
+    validate_retention(body.retention_days, settings.message_max_days)?;
+
+    let updated = sqlx::query("UPDATE guilds SET retention_days = $2 WHERE id = $1")
+        .bind(guild_id)
+        .bind(body.retention_days)
+        .execute(&state.db)
+        .await?
+        .rows_affected();
+    if updated == 0 {
+        return Err(GuildError::NotFound);
+    }
+
+    audit::record(
+        &state,
+        guild_id,
+        auth.id,
+        "guild.retention.updated",
+        Some("guild"),
+        Some(guild_id),
+        Some(serde_json::json!({ "retention_days": body.retention_days })),
+    )
+    .await;
+
+    Ok(Json(load_policy(&state.db, guild_id).await?))
+}
+
+/// Set a text or voice channel's message retention override (requires `MANAGE_GUILD`)
+#[utoipa::path(
+    put,
+    path = "/api/guilds/{id}/retention/channels/{channel_id}",
+    tag = "guilds",
+    params(
+        ("id" = Uuid, Path, description = "Guild ID"),
+        ("channel_id" = Uuid, Path, description = "Channel ID")
+    ),
+    request_body = SetRetentionRequest,
+    responses((status = 200, body = GuildRetentionPolicy)),
+    security(("bearer_auth" = []))
+)]
+#[tracing::instrument(skip(state))]
+pub async fn set_channel_retention(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path((guild_id, channel_id)): Path<(Uuid, Uuid)>,
+    Json(body): Json<SetRetentionRequest>,
+) -> Result<Json<GuildRetentionPolicy>, GuildError> {
+    require_retention_manager(&state, guild_id, auth.id).await?;
+    let settings = RetentionSettings::load(&state.db).await;
+    validate_retention(body.retention_days, settings.message_max_days)?;
+
+    let updated = sqlx::query(
+        "UPDATE channels SET retention_days = $3, updated_at = NOW()
+         WHERE id = $2 AND guild_id = $1 AND channel_type IN ('text', 'voice', 'stage')",
+    )
+    .bind(guild_id)
+    .bind(channel_id)
+    .bind(body.retention_days)
+    .execute(&state.db)
+    .await?
+    .rows_affected();
+    if updated == 0 {
+        return Err(GuildError::Validation(
+            "Channel is not a text or voice channel in this guild".to_string(),
+        ));
+    }
+
+    audit::record(
+        &state,
+        guild_id,
+        auth.id,
+        "guild.channels.retention_updated",
+        Some("channel"),
+        Some(channel_id),
+        Some(serde_json::json!({ "retention_days": body.retention_days })),
+    )
+    .await;
+
+    Ok(Json(load_policy(&state.db, guild_id).await?))
+}
+
+/// Hard-delete messages older than their channel's retention policy,
+/// together with their attachments.
+///
+/// Guilds without a policy use the instance default, and every policy is
+/// capped at the instance maximum. Works in batches of [`PURGE_BATCH_SIZE`]
+/// top-level messages (thread replies go with their parent). Returns the
+/// number of deleted messages, replies included.
+pub async fn purge_expired_messages(pool: &PgPool, s3: &Option<S3Client>) -> anyhow::Result<u64> {
+    let settings = RetentionSettings::load(pool).await;
+    let mut purged = 0;
+
+    for _ in 0..MAX_PURGE_BATCHES {
+        let ids: Vec<Uuid> = sqlx::query_scalar(
+            r"SELECT m.id
+              FROM messages m
+              JOIN channels c ON c.id = m.channel_id
+              JOIN guilds g ON g.id = c.guild_id
+              WHERE COALESCE(c.retention_days, g.retention_days, $2) IS NOT NULL
+                AND m.parent_id IS NULL
+                AND COALESCE(m.thread_last_reply_at, m.created_at)
+                    < NOW() - make_interval(days => LEAST(
+                        COALESCE(c.retention_days, g.retention_days, $2), $3))
+              LIMIT $1",
+        )
+        .bind(PURGE_BATCH_SIZE)
+        .bind(settings.message_default_days)
+        .bind(settings.message_max_days)
+        .fetch_all(pool)
+        .await?;
+        if ids.is_empty() {
+            break;
+        }
+
+        purged += delete_messages(pool, s3.as_ref(), &ids).await?;
+
+        if (ids.len() as i64) < PURGE_BATCH_SIZE {
+            break;
+        }
+    }
+
+    Ok(purged)
+}
+
+/// Hard-delete messages that were deleted longer ago than the instance's
+/// soft-delete window, together with their attachments.
+///
+/// Guild and DM messages alike. A deleted thread parent waits until none of
+/// its replies is left undeleted. Returns the number of purged messages.
+pub async fn purge_deleted_messages(pool: &PgPool, s3: &Option<S3Client>) -> anyhow::Result<u64> {
+    let settings = RetentionSettings::load(pool).await;
+    let mut purged = 0;
+
+    for _ in 0..MAX_PURGE_BATCHES {
+        let ids: Vec<Uuid> = sqlx::query_scalar(
+            r"SELECT m.id
+              FROM messages m
+              WHERE m.deleted_at < NOW() - make_interval(days => $2)
+                AND NOT EXISTS (
+                    SELECT 1 FROM messages r
+                    WHERE r.parent_id = m.id AND r.deleted_at IS NULL
+                )
+              LIMIT $1",
+        )
+        .bind(PURGE_BATCH_SIZE)
+        .bind(settings.soft_delete_purge_days)
+        .fetch_all(pool)
+        .await?;
+        if ids.is_empty() {
+            break;
+        }
+
+        purged += delete_messages(pool, s3.as_ref(), &ids).await?;
+
+        if (ids.len() as i64) < PURGE_BATCH_SIZE {
+            break;
+        }
+    }
+
+    Ok(purged)
+}
+
+/// Delete messages `ids` and their thread replies, attachments included.
+///
+/// Attachment objects are deleted from S3 before the rows; a failed S3
+/// delete is logged and the object orphaned.
+async fn delete_messages(
+    pool: &PgPool,
+    s3: Option<&S3Client>,
+    ids: &[Uuid],
+) -> anyhow::Result<u64> {
+    let keys: Vec<(String, Option<String>, Option<String>)> = sqlx::query_as(
+        r"SELECT fa.s3_key, fa.thumbnail_s3_key, fa.medium_s3_key
+          FROM file_attachments fa
+          JOIN messages m ON m.id = fa.message_id
+          WHERE m.id = ANY($1) OR m.parent_id = ANY($1)",
+    )
+    .bind(ids)
+    .fetch_all(pool)
+    .await?;
+
+    if let Some(s3) = s3 {
+        let all_keys: Vec<&String> = keys
+            .iter()
+            .flat_map(|(key, thumbnail, medium)| [Some(key), thumbnail.as_ref(), medium.as_ref()])
+            .flatten()
+            .collect();
+        for key in all_keys {
+            if let Err(e) = s3.delete(key).await {
+                tracing::warn!(
+                    s3_key = %key,
+                    error = %e,
+                    "Failed to delete attachment of purged message from S3"
+                );
+            }
+        }
+    } else if !keys.is_empty() {
+        tracing::warn!(
+            count = keys.len(),
+            "S3 unavailable — attachments of purged messages left in storage"
+        );
+    }
+
+    let deleted = sqlx::query("DELETE FROM messages WHERE id = ANY($1) OR parent_id = ANY($1)")
+        .bind(ids)
+        .execute(pool)
+        .await?
+        .rows_affected();
+    Ok(deleted)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_retention() {
+        let max = MAX_RETENTION_DAYS;
+        assert!(validate_retention(None, max).is_ok());
+        assert!(validate_retention(Some(1), max).is_ok());
+        assert!(validate_retention(Some(3650), max).is_ok());
+        assert!(validate_retention(Some(0), max).is_err());
+        assert!(validate_retention(Some(-7), max).is_err());
+        assert!(validate_retention(Some(3651), max).is_err());
+        // An instance maximum below the absolute limit
+        assert!(validate_retention(Some(90), 30).is_err());
+        assert!(validate_retention(Some(30), 30).is_ok());
+    }
+
+    #[test]
+    fn test_default_channel_retention() {
+        assert_eq!(
+            default_channel_retention(&ChannelType::Voice),
+            Some(VOICE_CHAT_RETENTION_DAYS)
+        );
+        assert_eq!(
+            default_channel_retention(&ChannelType::Stage),
+            Some(VOICE_CHAT_RETENTION_DAYS)
+        );
+        assert_eq!(default_channel_retention(&ChannelType::Text), None);
+    }
+}
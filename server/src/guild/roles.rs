@@ -1,4 +1,12 @@
 //! Guild role management handlers.
+//!
+//! Every change is broadcast to the guild (`RoleCreate`, `RoleUpdate`,
+//! `RoleDelete`, `RolesReorder`, `MemberRolesUpdate`). Permissions are computed
+//! per request from the database, so the only cached permission state is the
+//! WebSocket channel subscriptions, which re-check `VIEW_CHANNEL` on these
+//! events (see `ws::revalidate_subscriptions`).
+
+use std::collections::{HashMap, HashSet};
 
 use axum::extract::{Path, State};
 use axum::http::StatusCode;
@@ -8,12 +16,19 @@ use thiserror::Error;
 use uuid::Uuid;
 use validator::Validate;
 
-use super::types::{CreateRoleRequest, RoleResponse, UpdateRoleRequest};
+use super::audit;
+use super::types::{
+    BulkMemberRolesRequest, BulkMemberRolesResponse, BulkRoleAction, BulkRoleFailure,
+    CreateRoleRequest, ReorderRolesRequest, RoleResponse, UpdateRoleRequest,
+};
+use crate::api::concurrency::VersionConflict;
 use crate::api::AppState;
 use crate::auth::AuthUser;
 use crate::permissions::{
-    can_manage_role, require_guild_permission, GuildPermissions, PermissionError,
+    can_manage_role, require_guild_permission, GuildPermissions, MemberPermissionContext,
+    PermissionError,
 };
+use crate::ws::{broadcast_to_guild, ServerEvent};
 
 // ============================================================================
 // Error Type
@@ -36,6 +51,9 @@ pub enum RoleError {
     #[error("Limit exceeded: {0}")]
     LimitExceeded(String),
 
+    #[error("Role was changed by another update")]
+    VersionConflict(VersionConflict),
+
     #[error("Database error")]
     Database(#[from] sqlx::Error),
 }
@@ -94,6 +112,7 @@ impl IntoResponse for RoleError {
                 StatusCode::FORBIDDEN,
                 serde_json::json!({"error": "LIMIT_EXCEEDED", "message": msg}),
             ),
+            Self::VersionConflict(conflict) => return conflict.clone().into_response(),
             Self::Database(_) => (
                 StatusCode::INTERNAL_SERVER_ERROR,
                 serde_json::json!({"error": "INTERNAL_ERROR", "message": "Database error"}),
@@ -103,6 +122,226 @@ impl IntoResponse for RoleError {
     }
 }
 
+/// Maximum members per bulk role request.
+const MAX_BULK_ROLE_MEMBERS: usize = 500;
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+type RoleRow = (
+    Uuid,
+    Uuid,
+    String,
+    Option<String>,
+    i64,
+    i32,
+    bool,
+    chrono::DateTime<chrono::Utc>,
+    i32,
+);
+
+fn role_response(row: RoleRow) -> RoleResponse {
+    let (id, guild_id, name, color, permissions, position, is_default, created_at, version) = row;
+    RoleResponse {
+        id,
+        guild_id,
+        name,
+        color,
+        permissions: permissions as u64,
+        position,
+        is_default,
+        created_at,
+        version,
+    }
+}
+
+/// Load all roles of a guild, highest rank first.
+async fn fetch_roles(
+    pool: &sqlx::PgPool,
+    guild_id: Uuid,
+) -> Result<Vec<RoleResponse>, sqlx::Error> {
+    let rows = sqlx::query_as::<_, RoleRow>(
+        r"
+        SELECT id, guild_id, name, color, permissions, position, is_default, created_at, version
+        FROM guild_roles
+        WHERE guild_id = $1
+        ORDER BY position ASC
+        ",
+    )
+    .bind(guild_id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows.into_iter().map(role_response).collect())
+}
+
+/// Load the role IDs currently assigned to a member.
+async fn fetch_member_role_ids(
+    pool: &sqlx::PgPool,
+    guild_id: Uuid,
+    user_id: Uuid,
+) -> Result<Vec<Uuid>, sqlx::Error> {
+    sqlx::query_scalar(
+        "SELECT role_id FROM guild_member_roles WHERE guild_id = $1 AND user_id = $2",
+    )
+    .bind(guild_id)
+    .bind(user_id)
+    .fetch_all(pool)
+    .await
+}
+
+/// Broadcast a role event to all guild members.
+async fn broadcast_role_event(state: &AppState, guild_id: Uuid, event: ServerEvent) {
+    if let Err(e) = broadcast_to_guild(&state.redis, guild_id, &event).await {
+        tracing::warn!(guild_id = %guild_id, error = %e, "Failed to broadcast role event");
+    }
+}
+
+/// Check that the actor may hand out or take away a role in bulk.
+pub(super) fn check_bulk_assignable(
+    ctx: &MemberPermissionContext,
+    role_position: i32,
+    is_default: bool,
+) -> Result<(), RoleError> {
+    if is_default {
+        return Err(RoleError::Validation(
+            "Cannot assign @everyone role".to_string(),
+        ));
+    }
+    let actor_position = if ctx.is_owner {
+        -1
+    } else {
+        ctx.highest_role_position.unwrap_or(i32::MAX)
+    };
+    can_manage_role(
+        ctx.computed_permissions,
+        actor_position,
+        role_position,
+        None,
+    )?;
+    Ok(())
+}
+
+/// Add or remove a role for many members at once.
+///
+/// Non-members are ignored. Records a single audit entry and broadcasts
+/// `MemberRolesUpdate` for every member whose roles changed, which are
+/// returned.
+pub(super) async fn apply_bulk_role_change(
+    state: &AppState,
+    guild_id: Uuid,
+    actor_id: Uuid,
+    role_id: Uuid,
+    action: BulkRoleAction,
+    user_ids: &[Uuid],
+) -> Result<Vec<Uuid>, sqlx::Error> {
+    if user_ids.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let changed: Vec<Uuid> = match action {
+        BulkRoleAction::Add => {
+            sqlx::query_scalar(
+                r"
+                INSERT INTO guild_member_roles (guild_id, user_id, role_id, assigned_by)
+                SELECT $1, gm.user_id, $3, $4
+                FROM guild_members gm
+                WHERE gm.guild_id = $1 AND gm.user_id = ANY($2)
+                ON CONFLICT (guild_id, user_id, role_id) DO NOTHING
+                RETURNING user_id
+                ",
+            )
+            .bind(guild_id)
+            .bind(user_ids)
+            .bind(role_id)
+            .bind(actor_id)
+            .fetch_all(&state.db)
+            .await?
+        }
+        BulkRoleAction::Remove => {
+            sqlx::query_scalar(
+                r"
+                DELETE FROM guild_member_roles
+                WHERE guild_id = $1 AND user_id = ANY($2) AND role_id = $3
+                RETURNING user_id
+                ",
+            )
+            .bind(guild_id)
+            .bind(user_ids)
+            .bind(role_id)
+            .fetch_all(&state.db)
+            .await?
+        }
+    };
+
+    if changed.is_empty() {
+        return Ok(changed);
+    }
+
+    let action_name = match action {
+        BulkRoleAction::Add => "guild.members.roles_bulk_added",
+        BulkRoleAction::Remove => "guild.members.roles_bulk_removed",
+    };
+    audit::record(
+        state,
+        guild_id,
+        actor_id,
+        action_name,
+        Some("role"),
+        Some(role_id),
+        Some(serde_json::json!({ "user_ids": changed })),
+    )
+    .await;
+
+    let rows: Vec<(Uuid, Uuid)> = sqlx::query_as(
+        "SELECT user_id, role_id FROM guild_member_roles WHERE guild_id = $1 AND user_id = ANY($2)",
+    )
+    .bind(guild_id)
+    .bind(&changed)
+    .fetch_all(&state.db)
+    .await?;
+    let mut assignments: HashMap<Uuid, Vec<Uuid>> =
+        changed.iter().map(|id| (*id, Vec::new())).collect();
+    for (user_id, role_id) in rows {
+        assignments.entry(user_id).or_default().push(role_id);
+    }
+
+    for (user_id, role_ids) in assignments {
+        broadcast_role_event(
+            state,
+            guild_id,
+            ServerEvent::MemberRolesUpdate {
+                guild_id,
+                user_id,
+                role_ids,
+            },
+        )
+        .await;
+    }
+
+    Ok(changed)
+}
+
+/// Compute position changes for a reorder.
+///
+/// `current` holds the non-default roles sorted by position. The existing
+/// positions are kept as slots and handed out in the requested order, so gaps
+/// and the `@everyone` position are preserved. Returns `(role_id, old, new)`
+/// for every role that moves.
+fn plan_reorder(current: &[(Uuid, i32)], role_ids: &[Uuid]) -> Vec<(Uuid, i32, i32)> {
+    let old_positions: HashMap<Uuid, i32> = current.iter().copied().collect();
+
+    role_ids
+        .iter()
+        .zip(current.iter().map(|(_, position)| *position))
+        .filter_map(|(role_id, new)| {
+            let old = *old_positions.get(role_id)?;
+            (old != new).then_some((*role_id, old, new))
+        })
+        .collect()
+}
+
 // ============================================================================
 // Handlers
 // ============================================================================
@@ -132,49 +371,7 @@ pub async fn list_roles(
             other => RoleError::Permission(other),
         })?;
 
-    let roles = sqlx::query_as::<
-        _,
-        (
-            Uuid,
-            Uuid,
-            String,
-            Option<String>,
-            i64,
-            i32,
-            bool,
-            chrono::DateTime<chrono::Utc>,
-        ),
-    >(
-        r"
-        SELECT id, guild_id, name, color, permissions, position, is_default, created_at
-        FROM guild_roles
-        WHERE guild_id = $1
-        ORDER BY position ASC
-        ",
-    )
-    .bind(guild_id)
-    .fetch_all(&state.db)
-    .await?;
-
-    let response: Vec<RoleResponse> = roles
-        .into_iter()
-        .map(
-            |(id, guild_id, name, color, permissions, position, is_default, created_at)| {
-                RoleResponse {
-                    id,
-                    guild_id,
-                    name,
-                    color,
-                    permissions: permissions as u64,
-                    position,
-                    is_default,
-                    created_at,
-                }
-            },
-        )
-        .collect();
-
-    Ok(Json(response))
+    Ok(Json(fetch_roles(&state.db, guild_id).await?))
 }
 
 /// Create a new role.
@@ -236,10 +433,10 @@ pub async fn create_role(
             .fetch_one(&mut *tx)
             .await?;
 
-    if role_count >= state.config.max_roles_per_guild {
+    if role_count >= state.config().max_roles_per_guild {
         return Err(RoleError::LimitExceeded(format!(
             "Maximum number of roles per guild reached ({})",
-            state.config.max_roles_per_guild
+            state.config().max_roles_per_guild
         )));
     }
 
@@ -254,23 +451,11 @@ pub async fn create_role(
     let role_id = Uuid::now_v7();
     let position = max_position + 1;
 
-    let role = sqlx::query_as::<
-        _,
-        (
-            Uuid,
-            Uuid,
-            String,
-            Option<String>,
-            i64,
-            i32,
-            bool,
-            chrono::DateTime<chrono::Utc>,
-        ),
-    >(
+    let role = sqlx::query_as::<_, RoleRow>(
         r"
         INSERT INTO guild_roles (id, guild_id, name, color, permissions, position)
         VALUES ($1, $2, $3, $4, $5, $6)
-        RETURNING id, guild_id, name, color, permissions, position, is_default, created_at
+        RETURNING id, guild_id, name, color, permissions, position, is_default, created_at, version
         ",
     )
     .bind(role_id)
@@ -284,16 +469,33 @@ pub async fn create_role(
 
     tx.commit().await?;
 
-    Ok(Json(RoleResponse {
-        id: role.0,
-        guild_id: role.1,
-        name: role.2,
-        color: role.3,
-        permissions: role.4 as u64,
-        position: role.5,
-        is_default: role.6,
-        created_at: role.7,
-    }))
+    let role = role_response(role);
+    broadcast_role_event(
+        &state,
+        guild_id,
+        ServerEvent::RoleCreate {
+            guild_id,
+            role: role.clone(),
+        },
+    )
+    .await;
+
+    audit::record(
+        &state,
+        guild_id,
+        auth.id,
+        "guild.roles.created",
+        Some("role"),
+        Some(role.id),
+        Some(serde_json::json!({
+            "name": role.name,
+            "color": role.color,
+            "permissions": role.permissions,
+        })),
+    )
+    .await;
+
+    Ok(Json(role))
 }
 
 /// Update a role.
@@ -308,7 +510,10 @@ pub async fn create_role(
         ("role_id" = Uuid, Path, description = "Role ID")
     ),
     request_body = UpdateRoleRequest,
-    responses((status = 200, body = RoleResponse)),
+    responses(
+        (status = 200, body = RoleResponse),
+        (status = 409, description = "Role changed since `version`"),
+    ),
     security(("bearer_auth" = []))
 )]
 #[tracing::instrument(skip(state, body))]
@@ -386,27 +591,17 @@ pub async fn update_role(
         }
     }
 
-    let role = sqlx::query_as::<
-        _,
-        (
-            Uuid,
-            Uuid,
-            String,
-            Option<String>,
-            i64,
-            i32,
-            bool,
-            chrono::DateTime<chrono::Utc>,
-        ),
-    >(
+    let role = sqlx::query_as::<_, RoleRow>(
         r"
         UPDATE guild_roles SET
             name = COALESCE($3, name),
             color = COALESCE($4, color),
             permissions = COALESCE($5, permissions),
-            position = COALESCE($6, position)
-        WHERE id = $1 AND guild_id = $2
-        RETURNING id, guild_id, name, color, permissions, position, is_default, created_at
+            position = COALESCE($6, position),
+            updated_at = NOW(),
+            version = version + 1
+        WHERE id = $1 AND guild_id = $2 AND ($7::int IS NULL OR version = $7)
+        RETURNING id, guild_id, name, color, permissions, position, is_default, created_at, version
         ",
     )
     .bind(role_id)
@@ -415,19 +610,42 @@ pub async fn update_role(
     .bind(&body.color)
     .bind(body.permissions.map(|p| p as i64))
     .bind(body.position)
-    .fetch_one(&state.db)
+    .bind(body.version)
+    .fetch_optional(&state.db)
     .await?;
 
-    Ok(Json(RoleResponse {
-        id: role.0,
-        guild_id: role.1,
-        name: role.2,
-        color: role.3,
-        permissions: role.4 as u64,
-        position: role.5,
-        is_default: role.6,
-        created_at: role.7,
-    }))
+    let Some(role) = role else {
+        // Another update bumped the version since the client loaded the role
+        let current = fetch_roles(&state.db, guild_id)
+            .await?
+            .into_iter()
+            .find(|r| r.id == role_id)
+            .ok_or(RoleError::NotFound)?;
+        return Err(RoleError::VersionConflict(VersionConflict::new(&current)));
+    };
+    let role = role_response(role);
+    broadcast_role_event(
+        &state,
+        guild_id,
+        ServerEvent::RoleUpdate {
+            guild_id,
+            role: role.clone(),
+        },
+    )
+    .await;
+
+    audit::record(
+        &state,
+        guild_id,
+        auth.id,
+        "guild.roles.updated",
+        Some("role"),
+        Some(role_id),
+        serde_json::to_value(&body).ok(),
+    )
+    .await;
+
+    Ok(Json(role))
 }
 
 /// Delete a role.
@@ -459,8 +677,8 @@ pub async fn delete_role(
             })?;
 
     // Get role to check position and if it's default
-    let role: Option<(i32, bool)> = sqlx::query_as(
-        "SELECT position, is_default FROM guild_roles WHERE id = $1 AND guild_id = $2",
+    let role: Option<(i32, bool, String)> = sqlx::query_as(
+        "SELECT position, is_default, name FROM guild_roles WHERE id = $1 AND guild_id = $2",
     )
     .bind(role_id)
     .bind(guild_id)
@@ -488,11 +706,180 @@ pub async fn delete_role(
         .execute(&state.db)
         .await?;
 
+    broadcast_role_event(
+        &state,
+        guild_id,
+        ServerEvent::RoleDelete { guild_id, role_id },
+    )
+    .await;
+
+    audit::record(
+        &state,
+        guild_id,
+        auth.id,
+        "guild.roles.deleted",
+        Some("role"),
+        Some(role_id),
+        Some(serde_json::json!({ "name": role.2 })),
+    )
+    .await;
+
     Ok(Json(
         serde_json::json!({"deleted": true, "role_id": role_id}),
     ))
 }
 
+/// Reorder roles.
+///
+/// `POST /api/guilds/:guild_id/roles/reorder`
+#[utoipa::path(
+    post,
+    path = "/api/guilds/{id}/roles/reorder",
+    tag = "roles",
+    params(("id" = Uuid, Path, description = "Guild ID")),
+    request_body = ReorderRolesRequest,
+    responses((status = 200, body = Vec<RoleResponse>)),
+    security(("bearer_auth" = []))
+)]
+#[tracing::instrument(skip(state, body))]
+pub async fn reorder_roles(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(guild_id): Path<Uuid>,
+    Json(body): Json<ReorderRolesRequest>,
+) -> Result<Json<Vec<RoleResponse>>, RoleError> {
+    let ctx =
+        require_guild_permission(&state.db, guild_id, auth.id, GuildPermissions::MANAGE_ROLES)
+            .await
+            .map_err(|e| match e {
+                PermissionError::NotGuildMember => RoleError::NotMember,
+                other => RoleError::Permission(other),
+            })?;
+
+    let actor_position = if ctx.is_owner {
+        -1
+    } else {
+        ctx.highest_role_position.unwrap_or(i32::MAX)
+    };
+
+    let mut tx = state.db.begin().await?;
+
+    // Advisory lock seed 57 = role_create (also serializes position changes)
+    sqlx::query("SELECT pg_advisory_xact_lock(hashtextextended($1::text, 57))")
+        .bind(guild_id)
+        .execute(&mut *tx)
+        .await?;
+
+    let current: Vec<(Uuid, i32)> = sqlx::query_as(
+        r"
+        SELECT id, position FROM guild_roles
+        WHERE guild_id = $1 AND is_default = false
+        ORDER BY position ASC, created_at ASC
+        ",
+    )
+    .bind(guild_id)
+    .fetch_all(&mut *tx)
+    .await?;
+
+    let requested: HashSet<Uuid> = body.role_ids.iter().copied().collect();
+    if requested.len() != body.role_ids.len()
+        || body.role_ids.len() != current.len()
+        || !current.iter().all(|(id, _)| requested.contains(id))
+    {
+        return Err(RoleError::Validation(
+            "role_ids must list every non-default role exactly once".to_string(),
+        ));
+    }
+
+    let changes = plan_reorder(&current, &body.role_ids);
+
+    // Security: only roles strictly below the actor may move, and only to
+    // positions below the actor
+    for &(_, old, new) in &changes {
+        if old <= actor_position || new <= actor_position {
+            return Err(RoleError::Permission(PermissionError::RoleHierarchy {
+                actor_position,
+                target_position: old.min(new),
+            }));
+        }
+    }
+
+    for (role_id, _, new) in &changes {
+        sqlx::query("UPDATE guild_roles SET position = $2 WHERE id = $1")
+            .bind(role_id)
+            .bind(new)
+            .execute(&mut *tx)
+            .await?;
+    }
+
+    tx.commit().await?;
+
+    let roles = fetch_roles(&state.db, guild_id).await?;
+    if !changes.is_empty() {
+        broadcast_role_event(
+            &state,
+            guild_id,
+            ServerEvent::RolesReorder {
+                guild_id,
+                roles: roles.clone(),
+            },
+        )
+        .await;
+
+        audit::record(
+            &state,
+            guild_id,
+            auth.id,
+            "guild.roles.reordered",
+            None,
+            None,
+            Some(serde_json::json!({ "role_ids": body.role_ids })),
+        )
+        .await;
+    }
+
+    Ok(Json(roles))
+}
+
+/// List role assignments of all members.
+///
+/// `GET /api/guilds/:guild_id/member-roles`
+#[utoipa::path(
+    get,
+    path = "/api/guilds/{id}/member-roles",
+    tag = "roles",
+    params(("id" = Uuid, Path, description = "Guild ID")),
+    responses((status = 200, description = "Map of user ID to assigned role IDs")),
+    security(("bearer_auth" = []))
+)]
+#[tracing::instrument(skip(state))]
+pub async fn list_member_roles(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(guild_id): Path<Uuid>,
+) -> Result<Json<HashMap<Uuid, Vec<Uuid>>>, RoleError> {
+    // Just need to be a member to view role assignments
+    let _ctx = require_guild_permission(&state.db, guild_id, auth.id, GuildPermissions::empty())
+        .await
+        .map_err(|e| match e {
+            PermissionError::NotGuildMember => RoleError::NotMember,
+            other => RoleError::Permission(other),
+        })?;
+
+    let rows: Vec<(Uuid, Uuid)> =
+        sqlx::query_as("SELECT user_id, role_id FROM guild_member_roles WHERE guild_id = $1")
+            .bind(guild_id)
+            .fetch_all(&state.db)
+            .await?;
+
+    let mut assignments: HashMap<Uuid, Vec<Uuid>> = HashMap::new();
+    for (user_id, role_id) in rows {
+        assignments.entry(user_id).or_default().push(role_id);
+    }
+
+    Ok(Json(assignments))
+}
+
 /// Assign a role to a member.
 ///
 /// `POST /api/guilds/:guild_id/members/:user_id/roles/:role_id`
@@ -562,7 +949,7 @@ pub async fn assign_role(
     }
 
     // Assign role (ignore if already assigned)
-    sqlx::query(
+    let assigned = sqlx::query(
         r"
         INSERT INTO guild_member_roles (guild_id, user_id, role_id, assigned_by)
         VALUES ($1, $2, $3, $4)
@@ -574,7 +961,34 @@ pub async fn assign_role(
     .bind(role_id)
     .bind(auth.id)
     .execute(&state.db)
-    .await?;
+    .await?
+    .rows_affected()
+        > 0;
+
+    if assigned {
+        audit::record(
+            &state,
+            guild_id,
+            auth.id,
+            "guild.members.role_added",
+            Some("user"),
+            Some(user_id),
+            Some(serde_json::json!({ "role_id": role_id })),
+        )
+        .await;
+    }
+
+    let role_ids = fetch_member_role_ids(&state.db, guild_id, user_id).await?;
+    broadcast_role_event(
+        &state,
+        guild_id,
+        ServerEvent::MemberRolesUpdate {
+            guild_id,
+            user_id,
+            role_ids,
+        },
+    )
+    .await;
 
     Ok(Json(
         serde_json::json!({"assigned": true, "user_id": user_id, "role_id": role_id}),
@@ -641,7 +1055,142 @@ pub async fn remove_role(
         return Err(RoleError::NotFound);
     }
 
+    audit::record(
+        &state,
+        guild_id,
+        auth.id,
+        "guild.members.role_removed",
+        Some("user"),
+        Some(user_id),
+        Some(serde_json::json!({ "role_id": role_id })),
+    )
+    .await;
+
+    let role_ids = fetch_member_role_ids(&state.db, guild_id, user_id).await?;
+    broadcast_role_event(
+        &state,
+        guild_id,
+        ServerEvent::MemberRolesUpdate {
+            guild_id,
+            user_id,
+            role_ids,
+        },
+    )
+    .await;
+
     Ok(Json(
         serde_json::json!({"removed": true, "user_id": user_id, "role_id": role_id}),
     ))
 }
+
+/// Add or remove a role for many members at once.
+///
+/// `POST /api/guilds/:guild_id/members/bulk-roles`
+#[utoipa::path(
+    post,
+    path = "/api/guilds/{id}/members/bulk-roles",
+    tag = "roles",
+    params(("id" = Uuid, Path, description = "Guild ID")),
+    request_body = BulkMemberRolesRequest,
+    responses((status = 200, body = BulkMemberRolesResponse)),
+    security(("bearer_auth" = []))
+)]
+#[tracing::instrument(skip(state, body))]
+pub async fn bulk_update_member_roles(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(guild_id): Path<Uuid>,
+    Json(body): Json<BulkMemberRolesRequest>,
+) -> Result<Json<BulkMemberRolesResponse>, RoleError> {
+    let ctx =
+        require_guild_permission(&state.db, guild_id, auth.id, GuildPermissions::MANAGE_ROLES)
+            .await
+            .map_err(|e| match e {
+                PermissionError::NotGuildMember => RoleError::NotMember,
+                other => RoleError::Permission(other),
+            })?;
+
+    let mut user_ids = body.user_ids;
+    let mut seen = HashSet::new();
+    user_ids.retain(|id| seen.insert(*id));
+    if user_ids.is_empty() {
+        return Err(RoleError::Validation("No user IDs provided".to_string()));
+    }
+    if user_ids.len() > MAX_BULK_ROLE_MEMBERS {
+        return Err(RoleError::Validation(format!(
+            "Cannot update more than {MAX_BULK_ROLE_MEMBERS} members at once"
+        )));
+    }
+
+    let role: (i32, bool) = sqlx::query_as(
+        "SELECT position, is_default FROM guild_roles WHERE id = $1 AND guild_id = $2",
+    )
+    .bind(body.role_id)
+    .bind(guild_id)
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or(RoleError::NotFound)?;
+    check_bulk_assignable(&ctx, role.0, role.1)?;
+
+    let members: HashSet<Uuid> = sqlx::query_scalar(
+        "SELECT user_id FROM guild_members WHERE guild_id = $1 AND user_id = ANY($2)",
+    )
+    .bind(guild_id)
+    .bind(&user_ids)
+    .fetch_all(&state.db)
+    .await?
+    .into_iter()
+    .collect();
+
+    let (member_ids, non_members): (Vec<Uuid>, Vec<Uuid>) =
+        user_ids.into_iter().partition(|id| members.contains(id));
+
+    let updated = apply_bulk_role_change(
+        &state,
+        guild_id,
+        auth.id,
+        body.role_id,
+        body.action,
+        &member_ids,
+    )
+    .await?;
+    let updated_set: HashSet<Uuid> = updated.iter().copied().collect();
+    let unchanged = member_ids
+        .into_iter()
+        .filter(|id| !updated_set.contains(id))
+        .collect();
+
+    Ok(Json(BulkMemberRolesResponse {
+        updated,
+        unchanged,
+        failed: non_members
+            .into_iter()
+            .map(|user_id| BulkRoleFailure {
+                user_id,
+                reason: "Not a member of this guild".to_string(),
+            })
+            .collect(),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plan_reorder_reuses_position_slots() {
+        let (a, b, c) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let current = [(a, 50), (b, 100), (c, 1001)];
+
+        // Move c to the top: positions 50/100/1001 are handed out in order
+        let changes = plan_reorder(&current, &[c, a, b]);
+        assert_eq!(changes, vec![(c, 1001, 50), (a, 50, 100), (b, 100, 1001)]);
+
+        // Unchanged order moves nothing
+        assert!(plan_reorder(&current, &[a, b, c]).is_empty());
+
+        // Swapping the lower two leaves the top role alone
+        let changes = plan_reorder(&current, &[a, c, b]);
+        assert_eq!(changes, vec![(c, 1001, 100), (b, 100, 1001)]);
+    }
+}
@@ -297,21 +297,29 @@ pub async fn search_messages(
     let results: Vec<SearchResult> = messages
         .into_iter()
         .map(|msg| {
-            let author = msg
-                .user_id
-                .and_then(|uid| user_map.get(&uid))
-                .map(|u| SearchAuthor {
-                    id: u.id,
-                    username: u.username.clone(),
-                    display_name: u.display_name.clone(),
-                    avatar_url: u.avatar_url.clone(),
-                })
-                .unwrap_or_else(|| SearchAuthor {
-                    id: msg.user_id.unwrap_or(Uuid::nil()),
-                    username: "deleted".to_string(),
-                    display_name: "Deleted User".to_string(),
-                    avatar_url: None,
-                });
+            let author = if let Some(name) = &msg.webhook_name {
+                SearchAuthor {
+                    id: msg.webhook_id.unwrap_or(Uuid::nil()),
+                    username: name.clone(),
+                    display_name: name.clone(),
+                    avatar_url: msg.webhook_avatar_url.clone(),
+                }
+            } else {
+                msg.user_id
+                    .and_then(|uid| user_map.get(&uid))
+                    .map(|u| SearchAuthor {
+                        id: u.id,
+                        username: u.username.clone(),
+                        display_name: u.display_name.clone(),
+                        avatar_url: u.avatar_url.clone(),
+                    })
+                    .unwrap_or_else(|| SearchAuthor {
+                        id: msg.author_tombstone_id.unwrap_or(Uuid::nil()),
+                        username: "deleted".to_string(),
+                        display_name: "Deleted User".to_string(),
+                        avatar_url: None,
+                    })
+            };
 
             let channel_name = channel_map
                 .get(&msg.channel_id)
@@ -0,0 +1,222 @@
+//! Guild Member Timeouts
+//!
+//! Members with `TIMEOUT_MEMBERS` can mute a lower-ranked member until a
+//! given time. A timed-out member cannot send guild messages or join voice
+//! channels. Enforcement compares against `NOW()`, so a timeout ends on time
+//! even before the sweeper clears it; the sweeper only resets the column and
+//! tells clients via `MemberTimeoutUpdate`.
+
+use std::time::Duration;
+
+use axum::extract::{Path, State};
+use axum::Json;
+use chrono::{DateTime, Utc};
+use fred::clients::Client;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use super::audit;
+use super::bans::{require_moderator, require_outranks};
+use super::handlers::GuildError;
+use super::types::{MemberTimeoutResponse, SetMemberTimeoutRequest};
+use crate::api::AppState;
+use crate::auth::AuthUser;
+use crate::permissions::GuildPermissions;
+use crate::ws::{broadcast_to_guild, ServerEvent};
+
+/// Longest allowed timeout (28 days).
+const MAX_TIMEOUT_DAYS: i64 = 28;
+
+/// Maximum timeout reason length.
+const MAX_REASON_LENGTH: usize = 512;
+
+/// How often expired timeouts are cleared.
+const SWEEP_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Validate a timeout request against `now`.
+fn validate_timeout(body: &SetMemberTimeoutRequest, now: DateTime<Utc>) -> Result<(), GuildError> {
+    if body.reason.chars().count() > MAX_REASON_LENGTH {
+        return Err(GuildError::Validation(format!(
+            "Reason must be at most {MAX_REASON_LENGTH} characters"
+        )));
+    }
+    if let Some(until) = body.timeout_until {
+        if until <= now {
+            return Err(GuildError::Validation(
+                "timeout_until must be in the future".to_string(),
+            ));
+        }
+        if until > now + chrono::Duration::days(MAX_TIMEOUT_DAYS) {
+            return Err(GuildError::Validation(format!(
+                "Timeouts can last at most {MAX_TIMEOUT_DAYS} days"
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Tell guild members that a member's timeout changed.
+pub(crate) async fn broadcast_timeout(
+    redis: &Client,
+    guild_id: Uuid,
+    user_id: Uuid,
+    timeout_until: Option<DateTime<Utc>>,
+) {
+    let event = ServerEvent::MemberTimeoutUpdate {
+        guild_id,
+        user_id,
+        timeout_until,
+    };
+    if let Err(e) = broadcast_to_guild(redis, guild_id, &event).await {
+        tracing::warn!(guild_id = %guild_id, error = %e, "Failed to broadcast MemberTimeoutUpdate event");
+    }
+}
+
+/// Time out a member or lift their timeout (requires `TIMEOUT_MEMBERS`)
+#[utoipa::path(
+    patch,
+    path = "/api/guilds/{id}/members/{user_id}/timeout",
+    tag = "guilds",
+    params(
+        ("id" = Uuid, Path, description = "Guild ID"),
+        ("user_id" = Uuid, Path, description = "User ID")
+    ),
+    request_body = SetMemberTimeoutRequest,
+    responses((status = 200, body = MemberTimeoutResponse)),
+    security(("bearer_auth" = []))
+)]
+#[tracing::instrument(skip(state, body))]
+pub async fn set_member_timeout(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path((guild_id, user_id)): Path<(Uuid, Uuid)>,
+    Json(body): Json<SetMemberTimeoutRequest>,
+) -> Result<Json<MemberTimeoutResponse>, GuildError> {
+    let ctx =
+        require_moderator(&state, guild_id, auth.id, GuildPermissions::TIMEOUT_MEMBERS).await?;
+    validate_timeout(&body, Utc::now())?;
+
+    if user_id == auth.id {
+        return Err(GuildError::Validation(
+            "Cannot time out yourself".to_string(),
+        ));
+    }
+
+    require_outranks(&state, &ctx, guild_id, user_id).await?;
+
+    let updated = sqlx::query(
+        "UPDATE guild_members SET timeout_until = $3 WHERE guild_id = $1 AND user_id = $2",
+    )
+    .bind(guild_id)
+    .bind(user_id)
+    .bind(body.timeout_until)
+    .execute(&state.db)
+    .await?
+    .rows_affected();
+    if updated == 0 {
+        return Err(GuildError::Validation(
+            "User is not a member of this guild".to_string(),
+        ));
+    }
+
+    broadcast_timeout(&state.redis, guild_id, user_id, body.timeout_until).await;
+
+    let (action, details) = match body.timeout_until {
+        Some(until) => (
+            "guild.members.timed_out",
+            serde_json::json!({ "timeout_until": until, "reason": body.reason }),
+        ),
+        None => (
+            "guild.members.timeout_removed",
+            serde_json::json!({ "reason": body.reason }),
+        ),
+    };
+    audit::record(
+        &state,
+        guild_id,
+        auth.id,
+        action,
+        Some("user"),
+        Some(user_id),
+        Some(details),
+    )
+    .await;
+
+    Ok(Json(MemberTimeoutResponse {
+        user_id,
+        timeout_until: body.timeout_until,
+    }))
+}
+
+/// Clear expired timeouts and notify the affected guilds.
+///
+/// Returns the number of timeouts cleared.
+pub async fn sweep_expired_timeouts(pool: &PgPool, redis: &Client) -> Result<usize, sqlx::Error> {
+    let expired: Vec<(Uuid, Uuid)> = sqlx::query_as(
+        r"UPDATE guild_members SET timeout_until = NULL
+           WHERE timeout_until <= NOW()
+           RETURNING guild_id, user_id",
+    )
+    .fetch_all(pool)
+    .await?;
+
+    for (guild_id, user_id) in &expired {
+        broadcast_timeout(redis, *guild_id, *user_id, None).await;
+    }
+
+    Ok(expired.len())
+}
+
+/// Start the background task that clears expired timeouts.
+pub fn spawn_timeout_sweeper(pool: PgPool, redis: Client) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(SWEEP_INTERVAL);
+        loop {
+            interval.tick().await;
+            match sweep_expired_timeouts(&pool, &redis).await {
+                Ok(count) if count > 0 => {
+                    tracing::debug!(count, "Cleared expired member timeouts");
+                }
+                Err(e) => {
+                    tracing::warn!(error = %e, "Failed to clear expired member timeouts");
+                }
+                _ => {}
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(timeout_until: Option<DateTime<Utc>>) -> SetMemberTimeoutRequest {
+        SetMemberTimeoutRequest {
+            timeout_until,
+            reason: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_validate_timeout() {
+        let now = Utc::now();
+
+        assert!(validate_timeout(&request(None), now).is_ok());
+        assert!(validate_timeout(&request(Some(now + chrono::Duration::minutes(10))), now).is_ok());
+        assert!(validate_timeout(&request(Some(now + chrono::Duration::days(28))), now).is_ok());
+
+        assert!(validate_timeout(&request(Some(now)), now).is_err());
+        assert!(validate_timeout(&request(Some(now - chrono::Duration::hours(1))), now).is_err());
+        assert!(validate_timeout(
+            &request(Some(
+                now + chrono::Duration::days(28) + chrono::Duration::seconds(1)
+            )),
+            now
+        )
+        .is_err());
+
+        let mut long_reason = request(None);
+        long_reason.reason = "x".repeat(MAX_REASON_LENGTH + 1);
+        assert!(validate_timeout(&long_reason, now).is_err());
+    }
+}
@@ -9,7 +9,7 @@ use validator::Validate;
 // Guild Entity
 // ============================================================================
 
-#[derive(Debug, Clone, FromRow, Serialize, utoipa::ToSchema)]
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, utoipa::ToSchema)]
 pub struct Guild {
     pub id: Uuid,
     pub name: String,
@@ -22,6 +22,15 @@ pub struct Guild {
     pub banner_url: Option<String>,
     pub plan: String,
     pub created_at: chrono::DateTime<chrono::Utc>,
+    /// Text channel for join announcements; members land here by default.
+    pub system_channel_id: Option<Uuid>,
+    /// Text channel shown first to members who just joined.
+    pub rules_channel_id: Option<Uuid>,
+    /// Bumped by every guild update; send it back with an update to detect
+    /// conflicting edits.
+    #[sqlx(default)]
+    #[serde(default)]
+    pub version: i32,
 }
 
 /// Guild with member count for list responses.
@@ -52,7 +61,16 @@ pub struct UpdateGuildRequest {
     pub name: Option<String>,
     #[validate(length(max = 1000, message = "Description must be at most 1000 characters"))]
     pub description: Option<String>,
-    pub icon_url: Option<String>,
+    /// System channel (null = unset, absent = unchanged).
+    #[serde(default, deserialize_with = "deserialize_double_option")]
+    #[schema(value_type = Option<Uuid>)]
+    pub system_channel_id: Option<Option<Uuid>>,
+    /// Rules channel (null = unset, absent = unchanged).
+    #[serde(default, deserialize_with = "deserialize_double_option")]
+    #[schema(value_type = Option<Uuid>)]
+    pub rules_channel_id: Option<Option<Uuid>>,
+    /// Guild version the update is based on; a newer version fails with 409.
+    pub version: Option<i32>,
 }
 
 #[derive(Debug, Deserialize, utoipa::ToSchema)]
@@ -64,16 +82,44 @@ pub struct JoinGuildRequest {
 // Response Types
 // ============================================================================
 
-#[derive(Debug, Clone, FromRow, Serialize, utoipa::ToSchema)]
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, utoipa::ToSchema)]
 pub struct GuildMember {
     pub user_id: Uuid,
     pub username: String,
     pub display_name: String,
     pub avatar_url: Option<String>,
     pub nickname: Option<String>,
+    /// Guild-specific avatar, shown instead of `avatar_url` in this guild.
+    pub guild_avatar_url: Option<String>,
     pub joined_at: chrono::DateTime<chrono::Utc>,
     pub status: String,
     pub last_seen_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// Active timeout end (`None` = not timed out)
+    pub timeout_until: Option<chrono::DateTime<chrono::Utc>>,
+    /// Assigned roles (excluding @everyone), for grouping the member list
+    #[serde(default)]
+    pub role_ids: Vec<Uuid>,
+    /// Joined behind the rules gate and has not completed onboarding yet
+    #[serde(default)]
+    pub pending: bool,
+}
+
+/// Member list filters and pagination.
+///
+/// Without `limit` or `after` every matching member is returned in join
+/// order. Paginated requests are ordered by user ID; pass the last user ID
+/// of a page as `after` to get the next one.
+#[derive(Debug, Default, Deserialize, utoipa::IntoParams)]
+pub struct ListMembersQuery {
+    /// Maximum number of members to return (1-1000)
+    pub limit: Option<i64>,
+    /// Only members whose user ID sorts after this one
+    pub after: Option<Uuid>,
+    /// Only members with this role
+    pub role_id: Option<Uuid>,
+    /// Only members who are not offline
+    #[serde(default)]
+    pub online: bool,
 }
 
 // ============================================================================
@@ -88,6 +134,8 @@ pub struct GuildInvite {
     pub created_by: Uuid,
     pub expires_at: Option<chrono::DateTime<chrono::Utc>>,
     pub use_count: i32,
+    /// Maximum number of uses (`None` = unlimited)
+    pub max_uses: Option<i32>,
     pub created_at: chrono::DateTime<chrono::Utc>,
 }
 
@@ -95,6 +143,9 @@ pub struct GuildInvite {
 pub struct CreateInviteRequest {
     /// Expiry duration: "30m", "1h", "1d", "7d", or "never"
     pub expires_in: String,
+    /// Maximum number of uses (1-1000, omitted = unlimited)
+    #[serde(default)]
+    pub max_uses: Option<i32>,
 }
 
 #[derive(Debug, Serialize, utoipa::ToSchema)]
@@ -105,9 +156,297 @@ pub struct InviteResponse {
     pub guild_name: String,
     pub expires_at: Option<chrono::DateTime<chrono::Utc>>,
     pub use_count: i32,
+    pub max_uses: Option<i32>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    /// The guild's onboarding, when enabled.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub onboarding: Option<GuildOnboarding>,
+}
+
+/// Public invite preview (no authentication required)
+#[derive(Debug, FromRow, Serialize, utoipa::ToSchema)]
+pub struct InvitePreview {
+    pub code: String,
+    pub guild_id: Uuid,
+    pub guild_name: String,
+    pub guild_icon_url: Option<String>,
+    pub guild_description: Option<String>,
+    pub member_count: i64,
+    pub expires_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// Remaining uses (`None` = unlimited)
+    pub uses_remaining: Option<i32>,
+}
+
+// ============================================================================
+// Ban Types
+// ============================================================================
+
+/// Request to ban a user from a guild.
+#[derive(Debug, Deserialize, utoipa::ToSchema)]
+pub struct CreateBanRequest {
+    pub user_id: Uuid,
+    /// Reason shown in the ban list (max 512 characters)
+    #[serde(default)]
+    pub reason: String,
+    /// When the ban lifts (`None` = permanent)
+    #[serde(default)]
+    pub expires_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// Delete the user's messages from the last N seconds (0-604800)
+    #[serde(default)]
+    pub delete_message_seconds: i64,
+}
+
+/// Active guild ban with the banned user's profile.
+#[derive(Debug, Clone, FromRow, Serialize, utoipa::ToSchema)]
+pub struct GuildBan {
+    pub user_id: Uuid,
+    pub username: String,
+    pub display_name: String,
+    pub avatar_url: Option<String>,
+    pub banned_by: Option<Uuid>,
+    pub reason: String,
+    pub expires_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Result of banning a user.
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct BanMemberResponse {
+    pub user_id: Uuid,
+    /// Whether the user was a member and has been removed
+    pub removed: bool,
+    /// Number of messages deleted by the prune window
+    pub deleted_messages: i64,
+}
+
+/// Page of active guild bans.
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct GuildBanList {
+    pub items: Vec<GuildBan>,
+    pub total: i64,
+    pub limit: i64,
+    pub offset: i64,
+}
+
+/// Ban list pagination.
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+pub struct ListBansQuery {
+    /// Maximum number of bans to return (1-100, default 50)
+    #[serde(default = "default_ban_limit")]
+    pub limit: i64,
+    /// Number of bans to skip
+    #[serde(default)]
+    pub offset: i64,
+}
+
+const fn default_ban_limit() -> i64 {
+    50
+}
+
+// ============================================================================
+// Timeout Types
+// ============================================================================
+
+/// Request to time out a member (or lift a timeout).
+#[derive(Debug, Deserialize, utoipa::ToSchema)]
+pub struct SetMemberTimeoutRequest {
+    /// When the timeout ends (at most 28 days ahead; `None` lifts it)
+    pub timeout_until: Option<chrono::DateTime<chrono::Utc>>,
+    /// Reason recorded in the audit log (max 512 characters)
+    #[serde(default)]
+    pub reason: String,
+}
+
+/// A member's timeout state after an update.
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct MemberTimeoutResponse {
+    pub user_id: Uuid,
+    pub timeout_until: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// Request to change a member's guild profile. Absent fields are unchanged.
+#[derive(Debug, Deserialize, utoipa::ToSchema)]
+pub struct UpdateMemberRequest {
+    /// Guild nickname (max 32 characters; null or blank clears it)
+    #[serde(default, deserialize_with = "deserialize_double_option")]
+    #[schema(value_type = Option<String>)]
+    pub nickname: Option<Option<String>>,
+    /// Only `null` is accepted, resetting the guild avatar. New avatars are
+    /// uploaded with `POST /api/guilds/{id}/members/@me/avatar`.
+    #[serde(default, deserialize_with = "deserialize_double_option")]
+    #[schema(value_type = Option<String>)]
+    pub guild_avatar_url: Option<Option<String>>,
+}
+
+// ============================================================================
+// Retention Types
+// ============================================================================
+
+/// Request to set a guild's or channel's message retention.
+#[derive(Debug, Deserialize, utoipa::ToSchema)]
+pub struct SetRetentionRequest {
+    /// Days messages are kept (1-3650). `None` keeps messages forever (guild)
+    /// or falls back to the guild default (channel).
+    pub retention_days: Option<i32>,
+}
+
+/// Retention settings of one channel.
+#[derive(Debug, Serialize, FromRow, utoipa::ToSchema)]
+pub struct ChannelRetention {
+    pub channel_id: Uuid,
+    pub name: String,
+    /// The channel's own setting (`None` = guild default).
+    pub retention_days: Option<i32>,
+    /// The policy in effect (`None` = kept forever).
+    pub effective_retention_days: Option<i32>,
+}
+
+/// Message retention policy of a guild.
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct GuildRetentionPolicy {
+    /// Guild default (`None` = kept forever).
+    pub retention_days: Option<i32>,
+    /// Text channels, with their overrides.
+    pub channels: Vec<ChannelRetention>,
+    /// Longest retention the instance allows.
+    pub max_retention_days: i32,
+}
+
+// ============================================================================
+// Log Stream Types
+// ============================================================================
+
+/// A guild's moderation log stream.
+#[derive(Debug, Serialize, FromRow, utoipa::ToSchema)]
+pub struct GuildLogStream {
+    /// Endpoint receiving signed `audit.logged` events.
+    pub url: String,
+    pub active: bool,
+    /// HMAC signing secret; only returned when the stream is created or the
+    /// secret is rotated.
+    #[sqlx(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signing_secret: Option<String>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Request to configure a guild's moderation log stream.
+#[derive(Debug, Deserialize, utoipa::ToSchema)]
+pub struct SetLogStreamRequest {
+    /// HTTP(S) endpoint to deliver to.
+    pub url: String,
+    /// Pause delivery without removing the stream (default `true`).
+    #[serde(default = "default_true")]
+    pub active: bool,
+    /// Replace the signing secret of an existing stream.
+    #[serde(default)]
+    pub rotate_secret: bool,
+}
+
+const fn default_true() -> bool {
+    true
+}
+
+// ============================================================================
+// Onboarding Types
+// ============================================================================
+
+/// A channel highlighted on the welcome screen.
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct WelcomeChannel {
+    pub channel_id: Uuid,
+    /// Why new members should visit the channel.
+    pub description: String,
+}
+
+/// A guild's onboarding: welcome screen, rules gate and roles for new members.
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+pub struct GuildOnboarding {
+    pub guild_id: Uuid,
+    pub enabled: bool,
+    pub welcome_description: Option<String>,
+    pub welcome_channels: Vec<WelcomeChannel>,
+    /// Guild page new members must accept before they can post.
+    pub rules_page_id: Option<Uuid>,
+    /// Roles assigned when a member completes onboarding.
+    pub default_role_ids: Vec<Uuid>,
+}
+
+/// Request to replace a guild's onboarding.
+#[derive(Debug, Deserialize, utoipa::ToSchema)]
+pub struct UpdateOnboardingRequest {
+    pub enabled: bool,
+    pub welcome_description: Option<String>,
+    #[serde(default)]
+    pub welcome_channels: Vec<WelcomeChannel>,
+    /// Guild page that requires acceptance (`None` = no rules gate).
+    pub rules_page_id: Option<Uuid>,
+    #[serde(default)]
+    pub default_role_ids: Vec<Uuid>,
+}
+
+/// Result of completing onboarding.
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct OnboardingCompletion {
+    /// The member's roles afterwards.
+    pub role_ids: Vec<Uuid>,
+}
+
+// ============================================================================
+// Audit Log Types
+// ============================================================================
+
+/// Guild audit log entry.
+#[derive(Debug, Clone, FromRow, Serialize, utoipa::ToSchema)]
+pub struct GuildAuditLogEntry {
+    pub id: Uuid,
+    /// Acting user (`None` if the account was deleted)
+    pub actor_id: Option<Uuid>,
+    pub actor_username: Option<String>,
+    /// Dotted action name, e.g. `guild.roles.updated`
+    pub action: String,
+    pub target_type: Option<String>,
+    pub target_id: Option<Uuid>,
+    /// Action-specific details (changed fields, reason, ...)
+    pub changes: Option<serde_json::Value>,
     pub created_at: chrono::DateTime<chrono::Utc>,
 }
 
+/// Page of guild audit log entries, newest first.
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct GuildAuditLogPage {
+    pub items: Vec<GuildAuditLogEntry>,
+    pub total: i64,
+    pub limit: i64,
+    pub offset: i64,
+}
+
+/// Guild audit log filters and pagination.
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+pub struct AuditLogQuery {
+    /// Maximum number of entries to return (1-100, default 50)
+    #[serde(default = "default_audit_limit")]
+    pub limit: i64,
+    /// Number of entries to skip
+    #[serde(default)]
+    pub offset: i64,
+    /// Only entries by this user
+    pub actor_id: Option<Uuid>,
+    /// Action prefix (e.g. `guild.roles.` for all role changes)
+    pub action: Option<String>,
+    /// Exact action type (e.g. `guild.members.banned`); overrides `action`
+    pub action_type: Option<String>,
+    /// Entries created on or after this time
+    pub from_date: Option<chrono::DateTime<chrono::Utc>>,
+    /// Entries created on or before this time
+    pub to_date: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+const fn default_audit_limit() -> i64 {
+    50
+}
+
 // ============================================================================
 // Role Types
 // ============================================================================
@@ -122,16 +461,32 @@ pub struct CreateRoleRequest {
 }
 
 /// Request to update a guild role.
-#[derive(Debug, Deserialize, utoipa::ToSchema)]
+///
+/// Serializes only the fields being changed (recorded in the audit log).
+#[derive(Debug, Deserialize, Serialize, utoipa::ToSchema)]
 pub struct UpdateRoleRequest {
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub color: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub permissions: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub position: Option<i32>,
+    /// Role version the update is based on; a newer version fails with 409.
+    #[serde(skip_serializing)]
+    pub version: Option<i32>,
+}
+
+/// Request to reorder guild roles.
+#[derive(Debug, Deserialize, utoipa::ToSchema)]
+pub struct ReorderRolesRequest {
+    /// All non-default role IDs, highest rank first.
+    pub role_ids: Vec<Uuid>,
 }
 
 /// Guild role response.
-#[derive(Debug, Serialize, utoipa::ToSchema)]
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
 pub struct RoleResponse {
     pub id: Uuid,
     pub guild_id: Uuid,
@@ -141,6 +496,66 @@ pub struct RoleResponse {
     pub position: i32,
     pub is_default: bool,
     pub created_at: chrono::DateTime<chrono::Utc>,
+    /// Bumped by every role update; send it back with an update to detect
+    /// conflicting edits.
+    #[serde(default)]
+    pub version: i32,
+}
+
+/// Whether a bulk role change adds or removes the role.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum BulkRoleAction {
+    Add,
+    Remove,
+}
+
+/// Request to add or remove one role for many members.
+#[derive(Debug, Deserialize, utoipa::ToSchema)]
+pub struct BulkMemberRolesRequest {
+    pub role_id: Uuid,
+    pub action: BulkRoleAction,
+    /// Members to update (at most 500)
+    pub user_ids: Vec<Uuid>,
+}
+
+/// Result of a bulk role change.
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct BulkMemberRolesResponse {
+    /// Members whose roles changed
+    pub updated: Vec<Uuid>,
+    /// Members that already had (or already lacked) the role
+    pub unchanged: Vec<Uuid>,
+    /// Users that could not be updated (e.g. not a member)
+    pub failed: Vec<BulkRoleFailure>,
+}
+
+/// A user skipped by a bulk role change.
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct BulkRoleFailure {
+    pub user_id: Uuid,
+    pub reason: String,
+}
+
+// ============================================================================
+// Member Import Types
+// ============================================================================
+
+/// Background CSV member role import.
+#[derive(Debug, Clone, FromRow, Serialize, utoipa::ToSchema)]
+pub struct MemberImportJob {
+    pub id: Uuid,
+    pub guild_id: Uuid,
+    /// User who started the import (`None` if the account was deleted)
+    pub created_by: Option<Uuid>,
+    /// `pending`, `processing`, `completed` or `failed`
+    pub status: String,
+    pub total_rows: i32,
+    pub succeeded_rows: i32,
+    pub failed_rows: i32,
+    pub error_message: Option<String>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub completed_at: Option<chrono::DateTime<chrono::Utc>>,
 }
 
 // ============================================================================
@@ -158,6 +573,15 @@ pub struct GuildEmoji {
     pub created_at: chrono::DateTime<chrono::Utc>,
 }
 
+/// Custom emoji referenced as `:name:` in a message.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct MessageEmoji {
+    pub id: Uuid,
+    pub name: String,
+    pub url: String,
+    pub animated: bool,
+}
+
 #[derive(Debug, Deserialize, Validate, utoipa::ToSchema)]
 pub struct CreateEmojiRequest {
     #[validate(length(min = 2, max = 32, message = "Name must be 2-32 characters"))]
@@ -181,6 +605,16 @@ pub struct GuildSettings {
     pub discoverable: bool,
     pub tags: Vec<String>,
     pub banner_url: Option<String>,
+    /// Text channel receiving "joined/left voice" posts (null = disabled).
+    pub voice_log_channel_id: Option<Uuid>,
+    /// Preferred voice region: new voice rooms go to a cluster node of this
+    /// region when one is live (null = no preference).
+    pub voice_region: Option<String>,
+    /// Discovery review state: `none`, `pending`, `approved`, `rejected` or
+    /// `delisted`. Only `approved` guilds are listed.
+    pub discovery_status: String,
+    /// Why the listing was rejected or delisted.
+    pub discovery_rejection_reason: Option<String>,
 }
 
 /// Request to update guild settings.
@@ -189,7 +623,23 @@ pub struct UpdateGuildSettingsRequest {
     pub threads_enabled: Option<bool>,
     pub discoverable: Option<bool>,
     pub tags: Option<Vec<String>>,
-    pub banner_url: Option<String>,
+    /// Voice log channel (null = disable, absent = unchanged).
+    #[serde(default, deserialize_with = "deserialize_double_option")]
+    #[schema(value_type = Option<Uuid>)]
+    pub voice_log_channel_id: Option<Option<Uuid>>,
+    /// Preferred voice region (null = no preference, absent = unchanged).
+    #[serde(default, deserialize_with = "deserialize_double_option")]
+    #[schema(value_type = Option<String>)]
+    pub voice_region: Option<Option<String>>,
+}
+
+#[allow(clippy::option_option)]
+fn deserialize_double_option<'de, T, D>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+    T: Deserialize<'de>,
+    D: serde::Deserializer<'de>,
+{
+    Option::<T>::deserialize(deserializer).map(Some)
 }
 
 // ============================================================================
@@ -0,0 +1,171 @@
+//! Error Envelope Localization
+//!
+//! Error responses are built by each module's error type without access to
+//! the request, so translation happens afterwards: [`localize_errors`] looks
+//! up JSON error envelopes (`{"error": "<code>", "message": "..."}`) in the
+//! catalog by `error-<code>` and tags them with the `locale` of `message`.
+
+use axum::body::{Body, HttpBody};
+use axum::extract::Request;
+use axum::http::header::{CONTENT_LANGUAGE, CONTENT_LENGTH, CONTENT_TYPE};
+use axum::http::HeaderValue;
+use axum::middleware::Next;
+use axum::response::Response;
+use serde_json::Value;
+
+use super::{fluent_args, render, translate, RequestLocale, DEFAULT_LOCALE};
+
+/// Larger error bodies are passed through untouched.
+const MAX_ENVELOPE_BYTES: u64 = 16 * 1024;
+
+/// Middleware that renders JSON error envelopes in the request's locale.
+pub async fn localize_errors(request: Request, next: Next) -> Response {
+    let RequestLocale(locale) = RequestLocale::from_headers(request.headers());
+    let response = next.run(request).await;
+
+    let status = response.status();
+    let is_json = response
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.starts_with("application/json"));
+    let small =
+        matches!(response.body().size_hint().exact(), Some(len) if len <= MAX_ENVELOPE_BYTES);
+    if !(status.is_client_error() || status.is_server_error()) || !is_json || !small {
+        return response;
+    }
+
+    let (mut parts, body) = response.into_parts();
+    let Ok(bytes) = axum::body::to_bytes(body, MAX_ENVELOPE_BYTES as usize).await else {
+        return Response::from_parts(parts, Body::empty());
+    };
+    let Ok(mut envelope) = serde_json::from_slice::<Value>(&bytes) else {
+        return Response::from_parts(parts, Body::from(bytes));
+    };
+    let Some(content_locale) = localize_envelope(&mut envelope, locale) else {
+        return Response::from_parts(parts, Body::from(bytes));
+    };
+    let Ok(localized) = serde_json::to_vec(&envelope) else {
+        return Response::from_parts(parts, Body::from(bytes));
+    };
+
+    parts.headers.remove(CONTENT_LENGTH);
+    parts
+        .headers
+        .insert(CONTENT_LANGUAGE, HeaderValue::from_static(content_locale));
+    Response::from_parts(parts, Body::from(localized))
+}
+
+/// Translate an envelope's `message` in place and add its `locale`.
+///
+/// Returns the locale of the message, or `None` if the body is not an error
+/// envelope. A code is only translated when the English message matches the
+/// catalog verbatim, so a generic code (`not_found`, `validation`) never
+/// replaces a more specific message.
+fn localize_envelope(envelope: &mut Value, locale: &'static str) -> Option<&'static str> {
+    let object = envelope.as_object_mut()?;
+    let code = object.get("error")?.as_str()?;
+    let id = format!("error-{code}");
+
+    let args = fluent_args(object);
+    let known = match object.get("message") {
+        Some(Value::String(message)) => {
+            translate(DEFAULT_LOCALE, &id, Some(&args)).as_deref() == Some(message.as_str())
+        }
+        Some(_) => false,
+        None => true,
+    };
+    let rendered = if known {
+        render(locale, &id, Some(&args))
+    } else {
+        None
+    };
+    drop(args);
+
+    let content_locale = match rendered {
+        Some((message, content_locale)) => {
+            object.insert("message".to_string(), Value::String(message));
+            content_locale
+        }
+        None => DEFAULT_LOCALE,
+    };
+    object.insert(
+        "locale".to_string(),
+        Value::String(content_locale.to_string()),
+    );
+    Some(content_locale)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_translates_known_message() {
+        let mut envelope = json!({
+            "error": "rate_limited",
+            "message": "Too many requests. Wait 30 seconds.",
+            "retry_after": 30,
+        });
+        assert_eq!(localize_envelope(&mut envelope, "fi"), Some("fi"));
+        assert_eq!(
+            envelope["message"],
+            "Liian monta pyyntöä. Odota 30 sekuntia."
+        );
+        assert_eq!(envelope["locale"], "fi");
+        assert_eq!(envelope["error"], "rate_limited");
+    }
+
+    #[test]
+    fn test_keeps_specific_message_for_generic_code() {
+        let mut envelope = json!({
+            "error": "INTERNAL_ERROR",
+            "message": "Password processing failed",
+        });
+        assert_eq!(localize_envelope(&mut envelope, "de"), Some("en"));
+        assert_eq!(envelope["message"], "Password processing failed");
+        assert_eq!(envelope["locale"], "en");
+    }
+
+    #[test]
+    fn test_unknown_code_is_tagged_english() {
+        let mut envelope = json!({"error": "not_found", "message": "Channel not found"});
+        assert_eq!(localize_envelope(&mut envelope, "fi"), Some("en"));
+        assert_eq!(envelope["locale"], "en");
+    }
+
+    #[test]
+    fn test_fills_missing_message() {
+        let mut envelope = json!({"error": "service_unavailable"});
+        assert_eq!(localize_envelope(&mut envelope, "de"), Some("de"));
+        assert_eq!(envelope["message"], "Dienst vorübergehend nicht verfügbar");
+    }
+
+    #[test]
+    fn test_ignores_non_envelopes() {
+        let mut body = json!([1, 2, 3]);
+        assert_eq!(localize_envelope(&mut body, "fi"), None);
+        let mut body = json!({"message": "no code"});
+        assert_eq!(localize_envelope(&mut body, "fi"), None);
+    }
+
+    #[test]
+    fn test_english_catalog_matches_auth_errors() {
+        use crate::auth::AuthError;
+
+        for (code, error) in [
+            ("INVALID_CREDENTIALS", AuthError::InvalidCredentials),
+            ("INVALID_TOKEN", AuthError::InvalidToken),
+            ("MFA_REQUIRED", AuthError::MfaRequired),
+            ("USERNAME_TAKEN", AuthError::UsernameTaken),
+            ("LAST_AUTH_METHOD", AuthError::LastAuthMethod),
+        ] {
+            assert_eq!(
+                translate("en", &format!("error-{code}"), None),
+                Some(error.to_string())
+            );
+        }
+    }
+}
@@ -0,0 +1,42 @@
+## Authentication
+
+error-INVALID_CREDENTIALS = Ungültige Anmeldedaten
+error-USER_NOT_FOUND = Benutzer nicht gefunden
+error-USER_EXISTS = Benutzername oder E-Mail-Adresse bereits vergeben
+error-EMAIL_TAKEN = E-Mail-Adresse wird bereits von einem anderen Konto verwendet
+error-INVALID_TOKEN = Ungültiges oder abgelaufenes Token
+error-TOKEN_EXPIRED = Token abgelaufen
+error-MISSING_AUTH = Authorization-Header fehlt
+error-INVALID_AUTH_HEADER = Ungültiges Format des Authorization-Headers
+error-MFA_REQUIRED = Bestätigung per MFA erforderlich
+error-INVALID_MFA = Ungültiger MFA-Code
+error-EMAIL_NOT_CONFIGURED = E-Mail-Dienst ist nicht verfügbar
+error-INTERNAL_ERROR = Interner Serverfehler
+error-REGISTRATION_DISABLED = Registrierung ist deaktiviert
+error-USERNAME_TAKEN = Benutzername ist bereits vergeben
+error-LAST_AUTH_METHOD = Die letzte Anmeldemethode kann nicht entfernt werden
+
+## Messages
+
+error-MESSAGE_NOT_FOUND = Nachricht nicht gefunden
+error-CHANNEL_NOT_FOUND = Kanal nicht gefunden
+error-BLOCKED = Diesem Benutzer können keine Nachrichten gesendet werden
+error-CONTENT_FILTERED = Deine Nachricht wurde vom Inhaltsfilter des Servers blockiert.
+
+## Administration
+
+error-not_admin = Systemadministrator-Rechte erforderlich
+error-elevation_required = Diese Aktion erfordert eine erhöhte Sitzung
+error-mfa_required = MFA muss aktiviert sein, um die Sitzung zu erhöhen
+error-invalid_mfa_code = Ungültiger MFA-Code
+
+## Rate limiting
+
+error-rate_limited = Zu viele Anfragen. Bitte { $retry_after } Sekunden warten.
+error-ip_blocked = IP gesperrt. Bitte { $retry_after } Sekunden warten.
+error-service_unavailable = Dienst vorübergehend nicht verfügbar
+
+## Common
+
+error-not_member = Kein Mitglied dieses Servers
+error-database = Datenbankfehler
@@ -0,0 +1,18 @@
+system-member-join = ist dem Server beigetreten
+
+system-message-pinned = hat eine Nachricht in diesem Kanal angeheftet
+
+system-call-started = hat einen Anruf gestartet
+system-call-declined = hat den Anruf abgelehnt
+system-call-ended = Anruf beendet
+system-call-ended-seconds = Anruf nach { $seconds } s beendet
+system-call-ended-minutes = Anruf nach { $minutes } min { $seconds } s beendet
+system-call-ended-hours = Anruf nach { $hours } h { $minutes } min beendet
+
+system-voice-joined = ist dem Sprachkanal { $channel } beigetreten
+system-voice-left = hat den Sprachkanal { $channel } verlassen
+system-voice-moved = ist von Sprachkanal { $from } zu { $to } gewechselt
+
+system-automod-warned = hat { $violations } Filterverstöße erreicht (Automod-Regel „{ $policy }“)
+system-automod-timed-out = wurde nach { $violations } Filterverstößen für { $minutes } Min. stummgeschaltet (Automod-Regel „{ $policy }“)
+system-automod-banned = wurde nach { $violations } Filterverstößen gebannt (Automod-Regel „{ $policy }“)
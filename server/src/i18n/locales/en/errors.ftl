@@ -0,0 +1,46 @@
+# Error envelope messages, keyed by `error-<code>` where <code> is the exact
+# value of the envelope's `error` field. English entries must match the
+# message the server builds, otherwise the envelope is left untranslated.
+
+## Authentication
+
+error-INVALID_CREDENTIALS = Invalid credentials
+error-USER_NOT_FOUND = User not found
+error-USER_EXISTS = Username or email already taken
+error-EMAIL_TAKEN = Email already in use by another account
+error-INVALID_TOKEN = Invalid or expired token
+error-TOKEN_EXPIRED = Token expired
+error-MISSING_AUTH = Missing authorization header
+error-INVALID_AUTH_HEADER = Invalid authorization header format
+error-MFA_REQUIRED = MFA verification required
+error-INVALID_MFA = Invalid MFA code
+error-EMAIL_NOT_CONFIGURED = Email service is not available
+error-INTERNAL_ERROR = Internal server error
+error-REGISTRATION_DISABLED = Registration is disabled
+error-USERNAME_TAKEN = Username is already taken
+error-LAST_AUTH_METHOD = Cannot remove the last sign-in method
+
+## Messages
+
+error-MESSAGE_NOT_FOUND = Message not found
+error-CHANNEL_NOT_FOUND = Channel not found
+error-BLOCKED = Cannot send messages to this user
+error-CONTENT_FILTERED = Your message was blocked by the server's content filter.
+
+## Administration
+
+error-not_admin = System admin privileges required
+error-elevation_required = This action requires an elevated session
+error-mfa_required = MFA must be enabled to elevate session
+error-invalid_mfa_code = Invalid MFA code
+
+## Rate limiting
+
+error-rate_limited = Too many requests. Wait { $retry_after } seconds.
+error-ip_blocked = IP blocked. Wait { $retry_after } seconds.
+error-service_unavailable = Service temporarily unavailable
+
+## Common
+
+error-not_member = Not a member of this guild
+error-database = Database error
@@ -0,0 +1,21 @@
+# System message content. Messages follow the author's name, so they start
+# lowercase and carry no subject.
+
+system-member-join = joined the server
+
+system-message-pinned = pinned a message to this channel
+
+system-call-started = started a call
+system-call-declined = declined the call
+system-call-ended = call ended
+system-call-ended-seconds = call ended after { $seconds }s
+system-call-ended-minutes = call ended after { $minutes }m { $seconds }s
+system-call-ended-hours = call ended after { $hours }h { $minutes }m
+
+system-voice-joined = joined voice channel { $channel }
+system-voice-left = left voice channel { $channel }
+system-voice-moved = moved from voice channel { $from } to { $to }
+
+system-automod-warned = reached { $violations } filter violations (automod policy "{ $policy }")
+system-automod-timed-out = was timed out for { $minutes } min after { $violations } filter violations (automod policy "{ $policy }")
+system-automod-banned = was banned after { $violations } filter violations (automod policy "{ $policy }")
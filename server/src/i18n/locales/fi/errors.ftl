@@ -0,0 +1,42 @@
+## Authentication
+
+error-INVALID_CREDENTIALS = Virheelliset kirjautumistiedot
+error-USER_NOT_FOUND = Käyttäjää ei löytynyt
+error-USER_EXISTS = Käyttäjänimi tai sähköpostiosoite on jo käytössä
+error-EMAIL_TAKEN = Sähköpostiosoite on jo toisen tilin käytössä
+error-INVALID_TOKEN = Virheellinen tai vanhentunut tunniste
+error-TOKEN_EXPIRED = Tunniste on vanhentunut
+error-MISSING_AUTH = Authorization-otsake puuttuu
+error-INVALID_AUTH_HEADER = Authorization-otsakkeen muoto on virheellinen
+error-MFA_REQUIRED = Monivaiheinen tunnistautuminen vaaditaan
+error-INVALID_MFA = Virheellinen MFA-koodi
+error-EMAIL_NOT_CONFIGURED = Sähköpostipalvelu ei ole käytettävissä
+error-INTERNAL_ERROR = Palvelinvirhe
+error-REGISTRATION_DISABLED = Rekisteröityminen on poistettu käytöstä
+error-USERNAME_TAKEN = Käyttäjänimi on jo varattu
+error-LAST_AUTH_METHOD = Viimeistä kirjautumistapaa ei voi poistaa
+
+## Messages
+
+error-MESSAGE_NOT_FOUND = Viestiä ei löytynyt
+error-CHANNEL_NOT_FOUND = Kanavaa ei löytynyt
+error-BLOCKED = Tälle käyttäjälle ei voi lähettää viestejä
+error-CONTENT_FILTERED = Palvelimen sisältösuodatin esti viestisi.
+
+## Administration
+
+error-not_admin = Vaatii järjestelmänvalvojan oikeudet
+error-elevation_required = Toiminto vaatii korotetun istunnon
+error-mfa_required = Monivaiheinen tunnistautuminen on otettava käyttöön istunnon korottamiseksi
+error-invalid_mfa_code = Virheellinen MFA-koodi
+
+## Rate limiting
+
+error-rate_limited = Liian monta pyyntöä. Odota { $retry_after } sekuntia.
+error-ip_blocked = IP-osoite on estetty. Odota { $retry_after } sekuntia.
+error-service_unavailable = Palvelu ei ole tilapäisesti käytettävissä
+
+## Common
+
+error-not_member = Et ole tämän palvelimen jäsen
+error-database = Tietokantavirhe
@@ -0,0 +1,18 @@
+system-member-join = liittyi palvelimelle
+
+system-message-pinned = kiinnitti viestin kanavalle
+
+system-call-started = aloitti puhelun
+system-call-declined = hylkäsi puhelun
+system-call-ended = puhelu päättyi
+system-call-ended-seconds = puhelu päättyi { $seconds } s jälkeen
+system-call-ended-minutes = puhelu päättyi { $minutes } min { $seconds } s jälkeen
+system-call-ended-hours = puhelu päättyi { $hours } h { $minutes } min jälkeen
+
+system-voice-joined = liittyi puhekanavalle { $channel }
+system-voice-left = poistui puhekanavalta { $channel }
+system-voice-moved = siirtyi puhekanavalta { $from } kanavalle { $to }
+
+system-automod-warned = saavutti { $violations } suodatinrikkomusta (automod-sääntö ”{ $policy }”)
+system-automod-timed-out = asetettiin jäähylle { $minutes } min ajaksi { $violations } suodatinrikkomuksen jälkeen (automod-sääntö ”{ $policy }”)
+system-automod-banned = sai porttikiellon { $violations } suodatinrikkomuksen jälkeen (automod-sääntö ”{ $policy }”)
@@ -0,0 +1,306 @@
+//! Localization
+//!
+//! Server-generated strings (error envelopes, system messages) are rendered
+//! from the Fluent catalogs in `locales/<locale>/*.ftl`, which are compiled
+//! into the binary. The locale is negotiated from the request's
+//! `Accept-Language` header and anything that is not translated falls back
+//! to [`DEFAULT_LOCALE`].
+//!
+//! Rendered strings are always tagged with the locale they are in (the
+//! `locale` field of error envelopes, `content_locale` on system messages),
+//! so clients can use their own translations when the server had none.
+
+mod envelope;
+
+use std::sync::LazyLock;
+
+use axum::extract::FromRequestParts;
+use axum::http::header::ACCEPT_LANGUAGE;
+use axum::http::request::Parts;
+use axum::http::HeaderMap;
+use fluent_bundle::concurrent::FluentBundle;
+use fluent_bundle::{FluentArgs, FluentResource, FluentValue};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tracing::debug;
+use unic_langid::LanguageIdentifier;
+
+pub use envelope::localize_errors;
+
+/// Locale used when negotiation fails and for untranslated strings.
+pub const DEFAULT_LOCALE: &str = "en";
+
+/// Bundled catalogs, keyed by language tag.
+const CATALOGS: &[(&str, &[&str])] = &[
+    (
+        "en",
+        &[
+            include_str!("locales/en/errors.ftl"),
+            include_str!("locales/en/system.ftl"),
+        ],
+    ),
+    (
+        "fi",
+        &[
+            include_str!("locales/fi/errors.ftl"),
+            include_str!("locales/fi/system.ftl"),
+        ],
+    ),
+    (
+        "de",
+        &[
+            include_str!("locales/de/errors.ftl"),
+            include_str!("locales/de/system.ftl"),
+        ],
+    ),
+];
+
+static BUNDLES: LazyLock<Vec<(&'static str, FluentBundle<FluentResource>)>> = LazyLock::new(|| {
+    CATALOGS
+        .iter()
+        .map(|(tag, sources)| (*tag, build_bundle(tag, sources)))
+        .collect()
+});
+
+fn build_bundle(tag: &str, sources: &[&str]) -> FluentBundle<FluentResource> {
+    let langid: LanguageIdentifier = tag.parse().expect("valid locale tag");
+    let mut bundle = FluentBundle::new_concurrent(vec![langid]);
+    // Bidi isolation marks would end up in JSON strings
+    bundle.set_use_isolating(false);
+    for source in sources {
+        let resource = FluentResource::try_new((*source).to_string())
+            .unwrap_or_else(|(_, errors)| panic!("invalid {tag} catalog: {errors:?}"));
+        bundle
+            .add_resource(resource)
+            .unwrap_or_else(|errors| panic!("duplicate ids in {tag} catalog: {errors:?}"));
+    }
+    bundle
+}
+
+/// Language tags with a bundled catalog.
+pub fn supported_locales() -> impl Iterator<Item = &'static str> {
+    CATALOGS.iter().map(|(tag, _)| *tag)
+}
+
+/// Pick the best supported locale for an `Accept-Language` header value.
+///
+/// Ranges are tried by descending quality; a range matches a catalog by its
+/// primary language subtag (`fi-FI` uses `fi`).
+pub fn negotiate(accept_language: Option<&str>) -> &'static str {
+    let Some(header) = accept_language else {
+        return DEFAULT_LOCALE;
+    };
+
+    let mut ranges: Vec<(&str, f32)> = header
+        .split(',')
+        .filter_map(|item| {
+            let mut parts = item.split(';');
+            let range = parts.next()?.trim();
+            let quality = match parts.find_map(|p| p.trim().strip_prefix("q=")) {
+                Some(q) => q.trim().parse::<f32>().ok()?,
+                None => 1.0,
+            };
+            (!range.is_empty() && quality > 0.0).then_some((range, quality))
+        })
+        .collect();
+    // Stable sort keeps header order for equal qualities
+    ranges.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+    ranges
+        .iter()
+        .find_map(|(range, _)| {
+            if *range == "*" {
+                return Some(DEFAULT_LOCALE);
+            }
+            let primary = range.split(['-', '_']).next()?;
+            supported_locales().find(|tag| tag.eq_ignore_ascii_case(primary))
+        })
+        .unwrap_or(DEFAULT_LOCALE)
+}
+
+/// Locale negotiated from the request's `Accept-Language` header.
+///
+/// ```ignore
+/// async fn handler(RequestLocale(locale): RequestLocale) -> String {
+///     i18n::translate(locale, "system-member-join", None).unwrap_or_default()
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RequestLocale(pub &'static str);
+
+impl RequestLocale {
+    /// Negotiate the locale from request headers.
+    pub fn from_headers(headers: &HeaderMap) -> Self {
+        Self(negotiate(
+            headers
+                .get(ACCEPT_LANGUAGE)
+                .and_then(|value| value.to_str().ok()),
+        ))
+    }
+}
+
+impl<S> FromRequestParts<S> for RequestLocale
+where
+    S: Send + Sync,
+{
+    type Rejection = std::convert::Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        Ok(Self::from_headers(&parts.headers))
+    }
+}
+
+/// Format catalog message `id` in `locale`.
+///
+/// Returns `None` if the locale has no such message or an argument is missing.
+pub fn translate(locale: &str, id: &str, args: Option<&FluentArgs<'_>>) -> Option<String> {
+    let (_, bundle) = BUNDLES.iter().find(|(tag, _)| *tag == locale)?;
+    let pattern = bundle.get_message(id)?.value()?;
+
+    let mut errors = Vec::new();
+    let text = bundle.format_pattern(pattern, args, &mut errors);
+    if !errors.is_empty() {
+        debug!(locale, id, ?errors, "Failed to format localized message");
+        return None;
+    }
+    Some(text.into_owned())
+}
+
+/// Format `id` in `locale`, falling back to [`DEFAULT_LOCALE`].
+///
+/// Returns the text together with the locale it was rendered in.
+pub fn render(
+    locale: &'static str,
+    id: &str,
+    args: Option<&FluentArgs<'_>>,
+) -> Option<(String, &'static str)> {
+    translate(locale, id, args)
+        .map(|text| (text, locale))
+        .or_else(|| translate(DEFAULT_LOCALE, id, args).map(|text| (text, DEFAULT_LOCALE)))
+}
+
+/// Build Fluent arguments from the string and number fields of a JSON object.
+fn fluent_args(values: &serde_json::Map<String, Value>) -> FluentArgs<'_> {
+    let mut args = FluentArgs::new();
+    for (name, value) in values {
+        match value {
+            Value::String(s) => args.set(name.as_str(), FluentValue::from(s.as_str())),
+            Value::Number(n) => {
+                if let Some(n) = n.as_f64() {
+                    args.set(name.as_str(), FluentValue::from(n));
+                }
+            }
+            _ => {}
+        }
+    }
+    args
+}
+
+/// Reference to a catalog message, stored with server-generated content so it
+/// can be rendered again in another locale.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct LocalizedText {
+    /// Catalog message id (e.g. `system-member-join`).
+    pub id: String,
+    /// Message arguments (strings and numbers).
+    #[serde(default, skip_serializing_if = "serde_json::Map::is_empty")]
+    #[schema(value_type = Object)]
+    pub args: serde_json::Map<String, Value>,
+}
+
+impl LocalizedText {
+    /// Reference message `id` without arguments.
+    #[must_use]
+    pub fn new(id: &str) -> Self {
+        Self {
+            id: id.to_string(),
+            args: serde_json::Map::new(),
+        }
+    }
+
+    /// Add an argument.
+    #[must_use]
+    pub fn arg(mut self, name: &str, value: impl Into<Value>) -> Self {
+        self.args.insert(name.to_string(), value.into());
+        self
+    }
+
+    /// Render in `locale`, falling back to [`DEFAULT_LOCALE`].
+    pub fn render(&self, locale: &'static str) -> Option<(String, &'static str)> {
+        render(locale, &self.id, Some(&fluent_args(&self.args)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_catalogs_parse() {
+        assert_eq!(BUNDLES.len(), CATALOGS.len());
+    }
+
+    #[test]
+    fn test_translations_have_english_source() {
+        let (_, english) = &BUNDLES[0];
+        for (tag, sources) in CATALOGS {
+            let ids = sources
+                .iter()
+                .flat_map(|source| source.lines())
+                .filter(|line| !line.starts_with(['#', ' ']))
+                .filter_map(|line| line.split_once(" = ").map(|(id, _)| id));
+            for id in ids {
+                assert!(
+                    english.has_message(id),
+                    "{tag} message {id} has no English source"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_negotiate() {
+        assert_eq!(negotiate(None), "en");
+        assert_eq!(negotiate(Some("fi")), "fi");
+        assert_eq!(negotiate(Some("fi-FI,fi;q=0.9,en;q=0.8")), "fi");
+        assert_eq!(negotiate(Some("sv-SE,de;q=0.7,en;q=0.5")), "de");
+        assert_eq!(negotiate(Some("en;q=0.5, de")), "de");
+        assert_eq!(negotiate(Some("de;q=0, fi;q=0.1")), "fi");
+        assert_eq!(negotiate(Some("ja, *;q=0.1")), "en");
+        assert_eq!(negotiate(Some("ja")), "en");
+        assert_eq!(negotiate(Some("")), "en");
+        assert_eq!(negotiate(Some("de;q=abc")), "en");
+    }
+
+    #[test]
+    fn test_translate_with_args() {
+        let text = LocalizedText::new("system-voice-moved")
+            .arg("from", "Lobby")
+            .arg("to", "Raid");
+        assert_eq!(
+            text.render("en"),
+            Some(("moved from voice channel Lobby to Raid".to_string(), "en"))
+        );
+        assert_eq!(
+            text.render("fi"),
+            Some((
+                "siirtyi puhekanavalta Lobby kanavalle Raid".to_string(),
+                "fi"
+            ))
+        );
+    }
+
+    #[test]
+    fn test_render_falls_back_to_default_locale() {
+        assert_eq!(
+            render("xx", "system-member-join", None),
+            Some(("joined the server".to_string(), "en"))
+        );
+        assert_eq!(render("fi", "no-such-message", None), None);
+    }
+
+    #[test]
+    fn test_missing_argument_is_not_rendered() {
+        assert_eq!(translate("en", "system-voice-joined", None), None);
+    }
+}
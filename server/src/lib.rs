@@ -7,6 +7,7 @@ pub mod admin;
 pub mod api;
 pub mod auth;
 pub mod chat;
+pub mod cluster;
 pub mod config;
 pub mod connectivity;
 pub mod crypto;
@@ -15,12 +16,14 @@ pub mod discovery;
 pub mod email;
 pub mod governance;
 pub mod guild;
+pub mod i18n;
 pub mod moderation;
 pub mod observability;
 pub mod openapi;
 pub mod pages;
 pub mod permissions;
 pub mod presence;
+pub mod push;
 pub mod ratelimit;
 pub mod social;
 pub mod util;
@@ -17,7 +17,7 @@ async fn main() -> Result<()> {
 
     // Load configuration (must happen before observability init so we have the
     // OTLP endpoint, service name, and log-level filter available).
-    dotenvy::dotenv().ok();
+    config::load_dotenv();
     let config = config::Config::from_env()?;
 
     // Record server start time for uptime reporting in admin summary
@@ -37,9 +37,14 @@ async fn main() -> Result<()> {
     // Register database pool observable gauges (meter provider is always active)
     vc_server::observability::metrics::register_db_pool_metrics(db_pool.clone());
 
+    // Select the native telemetry backend (PostgreSQL unless TELEMETRY_STORAGE=clickhouse)
+    let telemetry_storage =
+        vc_server::observability::storage::connect(&config.observability, db_pool.clone()).await?;
+    info!(backend = ?telemetry_storage.backend(), "Telemetry storage ready");
+
     // Spawn native telemetry ingestion workers (log events + trace index + metrics)
     let ingestion_handles = vc_server::observability::ingestion::spawn_ingestion_workers(
-        db_pool.clone(),
+        telemetry_storage.clone(),
         ingestion_channels.log_rx,
         ingestion_channels.span_rx,
         ingestion_channels.metric_rx,
@@ -53,9 +58,32 @@ async fn main() -> Result<()> {
     let voice_health_handle =
         vc_server::observability::voice::spawn_voice_health_task(db_pool.clone());
 
+    // Spawn nightly capacity report job (checks hourly for a missing report)
+    let capacity_report_handle =
+        vc_server::observability::capacity::spawn_capacity_report_task(db_pool.clone());
+
+    // Spawn nightly discovery trending stats job (checks hourly for a stale run)
+    let trending_stats_handle =
+        vc_server::discovery::trending::spawn_trending_stats_task(db_pool.clone());
+
+    // Spawn consumer usage flusher (per-user/guild request counts, every minute)
+    let consumer_usage_handle =
+        vc_server::observability::consumers::spawn_consumer_usage_flusher(db_pool.clone());
+
     // Initialize Redis
     let redis = db::create_redis_client(&config.redis_url).await?;
 
+    // Spawn member timeout sweeper (clears expired timeouts every 30s)
+    let timeout_sweeper_handle =
+        vc_server::guild::timeouts::spawn_timeout_sweeper(db_pool.clone(), redis.clone());
+
+    // Spawn typing indicator sweeper (expires stale indicators every second)
+    let typing_sweeper_handle = vc_server::ws::typing::spawn_typing_sweeper(redis.clone());
+
+    // Spawn presence sweeper (expires dead connections and marks idle users away)
+    let presence_sweeper_handle =
+        vc_server::presence::registry::spawn_presence_sweeper(db_pool.clone(), redis.clone());
+
     // Initialize S3 client (optional - file uploads will be disabled if not configured)
     // Skip initialization if S3 credentials aren't available (Config fields or env vars)
     let has_s3_credentials = (config.s3_access_key.is_some() && config.s3_secret_key.is_some())
@@ -124,6 +152,9 @@ async fn main() -> Result<()> {
     // Start background cleanup task for voice stats rate limiter to prevent memory leaks
     let voice_cleanup_handle = sfu.start_cleanup_task();
 
+    // Start batched voice join/leave posting to guild voice log channels
+    let voice_activity_log_handle = sfu.start_activity_log_task(db_pool.clone(), redis.clone());
+
     // Start RTP packet counter flush task (every 5 seconds)
     let rtp_flush_handle = tokio::spawn(async {
         let mut interval = tokio::time::interval(std::time::Duration::from_secs(5));
@@ -136,6 +167,7 @@ async fn main() -> Result<()> {
     // Start background cleanup task for database (sessions, prekeys, device transfers, governance)
     let db_pool_clone = db_pool.clone();
     let s3_clone = s3.clone();
+    let deleted_user_content = config.deleted_user_content;
     let db_cleanup_handle = tokio::spawn(async move {
         let mut interval = tokio::time::interval(std::time::Duration::from_secs(3600)); // Every hour
         loop {
@@ -185,6 +217,39 @@ async fn main() -> Result<()> {
                 _ => {}
             }
 
+            // Cleanup expired email verification tokens
+            match db::cleanup_expired_email_verification_tokens(&db_pool_clone).await {
+                Ok(count) if count > 0 => {
+                    tracing::debug!(count, "Cleaned up expired email verification tokens");
+                }
+                Err(e) => {
+                    tracing::error!(error = %e, "Failed to cleanup expired email verification tokens");
+                }
+                _ => {}
+            }
+
+            // Fail attachments whose background image processing never finished
+            match db::fail_stale_attachment_processing(&db_pool_clone).await {
+                Ok(count) if count > 0 => {
+                    tracing::debug!(count, "Marked stale attachment processing as failed");
+                }
+                Err(e) => {
+                    tracing::warn!(error = %e, "Failed to sweep stale attachment processing");
+                }
+                _ => {}
+            }
+
+            // Fail attachment scans that never finished (held until released)
+            match db::fail_stale_attachment_scans(&db_pool_clone).await {
+                Ok(count) if count > 0 => {
+                    tracing::warn!(count, "Marked stale attachment scans as failed");
+                }
+                Err(e) => {
+                    tracing::warn!(error = %e, "Failed to sweep stale attachment scans");
+                }
+                _ => {}
+            }
+
             // Cleanup webhook delivery logs older than 7 days
             match vc_server::webhooks::queries::cleanup_old_delivery_logs(&db_pool_clone, 7).await {
                 Ok(count) if count > 0 => {
@@ -211,6 +276,7 @@ async fn main() -> Result<()> {
             if let Err(e) = vc_server::governance::deletion::process_pending_deletions(
                 &db_pool_clone,
                 &s3_clone,
+                deleted_user_content,
             )
             .await
             {
@@ -235,14 +301,70 @@ async fn main() -> Result<()> {
                 }
                 _ => {}
             }
+
+            // Purge messages past their guild/channel retention policy
+            match vc_server::guild::retention::purge_expired_messages(&db_pool_clone, &s3_clone)
+                .await
+            {
+                Ok(count) if count > 0 => {
+                    tracing::info!(count, "Purged messages past retention");
+                }
+                Err(e) => {
+                    tracing::error!(error = %e, "Failed to purge messages past retention");
+                }
+                _ => {}
+            }
+
+            // Purge deleted messages past the instance's soft-delete window
+            match vc_server::guild::retention::purge_deleted_messages(&db_pool_clone, &s3_clone)
+                .await
+            {
+                Ok(count) if count > 0 => {
+                    tracing::info!(count, "Purged deleted messages");
+                }
+                Err(e) => {
+                    tracing::error!(error = %e, "Failed to purge deleted messages");
+                }
+                _ => {}
+            }
+
+            // Cleanup expired channel transcript exports
+            if let Err(e) =
+                vc_server::chat::exports::cleanup_expired_channel_exports(&db_pool_clone, &s3_clone)
+                    .await
+            {
+                tracing::error!(error = %e, "Failed to cleanup expired channel exports");
+            }
+
+            // Recover channel exports that stopped reporting progress
+            match vc_server::chat::exports::recover_stale_channel_export_jobs(&db_pool_clone).await
+            {
+                Ok(count) if count > 0 => {
+                    tracing::info!(count, "Recovered stale channel export jobs");
+                }
+                Err(e) => {
+                    tracing::error!(error = %e, "Failed to recover stale channel export jobs");
+                }
+                _ => {}
+            }
+
+            // Forget cleared message drafts
+            if let Err(e) = vc_server::api::drafts::cleanup_cleared_drafts(&db_pool_clone).await {
+                tracing::error!(error = %e, "Failed to cleanup cleared drafts");
+            }
         }
     });
 
     info!("Voice SFU server initialized");
 
-    // Initialize email service (optional - password reset will be disabled if not configured)
-    let email_service = if config.has_smtp() {
+    // Initialize email service (optional - password reset and email
+    // verification will be disabled if not configured)
+    let email_service = if config.has_email() {
         match email::EmailService::new(&config) {
+            Ok(service) if service.is_console() => {
+                tracing::warn!("EMAIL_BACKEND=console: emails are logged, not sent");
+                Some(service)
+            }
             Ok(service) => match service.test_connection().await {
                 Ok(()) => {
                     info!("Email service initialized and SMTP connection verified");
@@ -250,7 +372,7 @@ async fn main() -> Result<()> {
                 }
                 Err(e) => {
                     tracing::error!(
-                        "SMTP connection test failed: {}. Password reset disabled.",
+                        "SMTP connection test failed: {}. Password reset and email verification disabled.",
                         e
                     );
                     None
@@ -258,14 +380,14 @@ async fn main() -> Result<()> {
             },
             Err(e) => {
                 tracing::error!(
-                    "Email service initialization failed: {}. Password reset disabled.",
+                    "Email service initialization failed: {}. Password reset and email verification disabled.",
                     e
                 );
                 None
             }
         }
     } else {
-        info!("SMTP not configured. Password reset disabled.");
+        info!("SMTP not configured. Password reset and email verification disabled.");
         None
     };
 
@@ -325,6 +447,27 @@ async fn main() -> Result<()> {
     ));
     info!("Webhook delivery worker started");
 
+    // Spawn push notification worker
+    let push_http_client = reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(10))
+        .build()
+        .expect("Failed to build push HTTP client");
+    let push_gateway = std::sync::Arc::new(vc_server::push::gateway::PushGateway::from_config(
+        &config,
+        push_http_client,
+    ));
+    let push_worker_handle = tokio::spawn(vc_server::push::worker::spawn_push_worker(
+        db_pool.clone(),
+        redis.clone(),
+        push_gateway,
+    ));
+
+    // Attachment malware scanner (optional)
+    let attachment_scanner = chat::scanning::from_config(&config);
+    if let Some(scanner) = &attachment_scanner {
+        info!(backend = scanner.name(), "Attachment scanning enabled");
+    }
+
     // Build application state
     let state = api::AppState::new(api::AppStateConfig {
         db: db_pool.clone(),
@@ -335,7 +478,34 @@ async fn main() -> Result<()> {
         rate_limiter,
         email: email_service,
         oidc_manager,
-    });
+    })
+    .with_telemetry_storage(telemetry_storage)
+    .with_attachment_scanner(attachment_scanner);
+
+    // Register this replica in the cluster node registry and keep it fresh
+    let cluster_heartbeat_handle = vc_server::cluster::spawn_node_heartbeat(
+        db_pool.clone(),
+        redis.clone(),
+        config.node_id.clone(),
+        state.sfu.clone(),
+    );
+
+    // Drive the remote SFU nodes in `SFU_REMOTE_NODES`, if any
+    let remote_sfu_handle = state.sfu.remote_nodes().spawn(redis.clone(), &config);
+
+    // Apply the admin overrides and keep the configuration current: overrides
+    // saved on other nodes are picked up periodically, SIGHUP reloads `.env`
+    state.refresh_config().await;
+    let config_refresh_handle = api::instance_config::spawn_refresh_task(state.clone());
+    #[cfg(unix)]
+    let sighup_handle = api::instance_config::spawn_sighup_handler(state.clone());
+
+    // Prometheus `/metrics` on its own (internal) listener, if configured
+    let prometheus_handle = config
+        .observability
+        .prometheus
+        .as_ref()
+        .and_then(vc_server::observability::metrics::spawn_prometheus_listener);
 
     // Build router
     let app = api::create_router(state);
@@ -350,6 +520,7 @@ async fn main() -> Result<()> {
             .await
             .expect("Failed to install CTRL+C signal handler");
         info!("Received shutdown signal, initiating graceful shutdown...");
+        vc_server::ws::lifecycle::begin_shutdown();
     };
 
     axum::serve(
@@ -365,19 +536,63 @@ async fn main() -> Result<()> {
 
     info!("HTTP server stopped, cleaning up background tasks...");
 
+    // Let gateway connections finish their disconnect bookkeeping first
+    let open_connections =
+        vc_server::ws::lifecycle::drain_connections(std::time::Duration::from_secs(5)).await;
+    if open_connections > 0 {
+        tracing::warn!(
+            open_connections,
+            "WebSocket connections still open at shutdown"
+        );
+    }
+
     // 1. Abort non-draining background tasks
     voice_cleanup_handle.abort();
+    voice_activity_log_handle.abort();
     db_cleanup_handle.abort();
     webhook_worker_handle.abort();
+    push_worker_handle.abort();
     rtp_flush_handle.abort();
     retention_handle.abort();
     voice_health_handle.abort();
+    capacity_report_handle.abort();
+    trending_stats_handle.abort();
+    consumer_usage_handle.abort();
+    timeout_sweeper_handle.abort();
+    typing_sweeper_handle.abort();
+    presence_sweeper_handle.abort();
+    cluster_heartbeat_handle.abort();
+    if let Some(handle) = &remote_sfu_handle {
+        handle.abort();
+    }
+    config_refresh_handle.abort();
+    if let Some(handle) = &prometheus_handle {
+        handle.abort();
+    }
+    #[cfg(unix)]
+    sighup_handle.abort();
     let _ = voice_cleanup_handle.await;
     let _ = db_cleanup_handle.await;
     let _ = webhook_worker_handle.await;
+    let _ = push_worker_handle.await;
     let _ = rtp_flush_handle.await;
     let _ = retention_handle.await;
     let _ = voice_health_handle.await;
+    let _ = capacity_report_handle.await;
+    let _ = trending_stats_handle.await;
+    let _ = timeout_sweeper_handle.await;
+    let _ = typing_sweeper_handle.await;
+    let _ = presence_sweeper_handle.await;
+    let _ = cluster_heartbeat_handle.await;
+    if let Err(e) =
+        vc_server::cluster::registry::deregister(&db_pool, &redis, &config.node_id).await
+    {
+        tracing::warn!(error = %e, "Failed to leave the cluster node registry");
+    }
+    let _ = consumer_usage_handle.await;
+    if let Err(e) = vc_server::observability::consumers::flush_consumer_usage(&db_pool).await {
+        tracing::warn!(error = %e, "Failed to flush consumer usage on shutdown");
+    }
     info!("Background cleanup tasks stopped");
 
     // 2. Flush and shut down OTel providers. Dropping these closes the channel senders
@@ -1,12 +1,21 @@
 //! Admin-facing report handlers.
+//!
+//! Reports move open (`pending`) -> in review (`reviewing`, claimed by one
+//! admin) -> `resolved` or `dismissed`. Only the claimer can resolve a report
+//! in review; an open report is claimed implicitly when resolved directly.
 
 use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
 use axum::{Extension, Json};
+use sqlx::PgPool;
 use uuid::Uuid;
+use validator::Validate;
 
+use super::evidence;
 use super::types::{
-    ListReportsQuery, PaginatedReports, Report, ReportError, ReportResponse, ReportStatsResponse,
-    ResolveReportRequest,
+    AddReportEvidenceRequest, CreateReportNoteRequest, ListReportsQuery, PaginatedReports, Report,
+    ReportError, ReportEvidence, ReportNote, ReportResponse, ReportStatsResponse, ReportStatus,
+    ResolveReportRequest, UnclaimReportQuery,
 };
 use crate::admin::ElevatedAdmin;
 use crate::api::AppState;
@@ -85,14 +94,50 @@ pub async fn get_report(
     Ok(Json(report.into()))
 }
 
+/// Explain why a state transition matched no row: the report is missing,
+/// claimed by someone else, or already in a state the transition does not
+/// apply to (reported as not found, like a missing report).
+async fn transition_error(pool: &PgPool, report_id: Uuid, admin_id: Uuid) -> ReportError {
+    let report = sqlx::query_as::<_, Report>("SELECT * FROM user_reports WHERE id = $1")
+        .bind(report_id)
+        .fetch_optional(pool)
+        .await;
+    match report {
+        Ok(Some(report))
+            if report.status == ReportStatus::Reviewing
+                && report
+                    .assigned_admin_id
+                    .is_some_and(|assigned| assigned != admin_id) =>
+        {
+            ReportError::ClaimedByOther
+        }
+        Ok(_) => ReportError::NotFound,
+        Err(e) => ReportError::Database(e),
+    }
+}
+
+/// Ensure a report exists.
+async fn require_report(pool: &PgPool, report_id: Uuid) -> Result<(), ReportError> {
+    sqlx::query_scalar::<_, Uuid>("SELECT id FROM user_reports WHERE id = $1")
+        .bind(report_id)
+        .fetch_optional(pool)
+        .await?
+        .ok_or(ReportError::NotFound)?;
+    Ok(())
+}
+
 /// POST /api/admin/reports/:id/claim
-/// Claim a report for review.
+/// Claim an open report for review.
 #[utoipa::path(
     post,
     path = "/api/admin/reports/{id}/claim",
     tag = "moderation",
     params(("id" = Uuid, Path, description = "Report ID")),
-    responses((status = 200, body = ReportResponse)),
+    responses(
+        (status = 200, body = ReportResponse),
+        (status = 404, description = "Report not found or not open"),
+        (status = 409, description = "Report is claimed by another admin"),
+    ),
     security(("bearer_auth" = []))
 )]
 pub async fn claim_report(
@@ -100,9 +145,9 @@ pub async fn claim_report(
     Extension(elevated): Extension<ElevatedAdmin>,
     Path(report_id): Path<Uuid>,
 ) -> Result<Json<ReportResponse>, ReportError> {
-    let report = sqlx::query_as::<_, Report>(
+    let Some(report) = sqlx::query_as::<_, Report>(
         r"UPDATE user_reports
-           SET status = 'reviewing', assigned_admin_id = $2, updated_at = NOW()
+           SET status = 'reviewing', assigned_admin_id = $2, claimed_at = NOW(), updated_at = NOW()
            WHERE id = $1 AND status = 'pending'
            RETURNING *",
     )
@@ -110,24 +155,73 @@ pub async fn claim_report(
     .bind(elevated.user_id)
     .fetch_optional(&state.db)
     .await?
-    .ok_or(ReportError::NotFound)?;
+    else {
+        return Err(transition_error(&state.db, report_id, elevated.user_id).await);
+    };
+
+    Ok(Json(report.into()))
+}
+
+/// DELETE /api/admin/reports/:id/claim
+/// Release a claim, returning the report to the open queue.
+#[utoipa::path(
+    delete,
+    path = "/api/admin/reports/{id}/claim",
+    tag = "moderation",
+    params(("id" = Uuid, Path, description = "Report ID"), UnclaimReportQuery),
+    responses(
+        (status = 200, body = ReportResponse),
+        (status = 404, description = "Report not found or not in review"),
+        (status = 409, description = "Report is claimed by another admin (use force)"),
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn unclaim_report(
+    State(state): State<AppState>,
+    Extension(elevated): Extension<ElevatedAdmin>,
+    Path(report_id): Path<Uuid>,
+    Query(query): Query<UnclaimReportQuery>,
+) -> Result<Json<ReportResponse>, ReportError> {
+    let Some(report) = sqlx::query_as::<_, Report>(
+        r"UPDATE user_reports
+           SET status = 'pending', assigned_admin_id = NULL, claimed_at = NULL, updated_at = NOW()
+           WHERE id = $1 AND status = 'reviewing'
+             AND ($3 OR assigned_admin_id IS NULL OR assigned_admin_id = $2)
+           RETURNING *",
+    )
+    .bind(report_id)
+    .bind(elevated.user_id)
+    .bind(query.force)
+    .fetch_optional(&state.db)
+    .await?
+    else {
+        return Err(transition_error(&state.db, report_id, elevated.user_id).await);
+    };
 
     Ok(Json(report.into()))
 }
 
 /// POST /api/admin/reports/:id/resolve
 /// Resolve a report with an action.
+///
+/// Open reports are claimed by the resolving admin on the way; reports in
+/// review can only be resolved by their claimer.
 #[utoipa::path(
     post,
     path = "/api/admin/reports/{id}/resolve",
     tag = "moderation",
     params(("id" = Uuid, Path, description = "Report ID")),
     request_body = ResolveReportRequest,
-    responses((status = 200, body = ReportResponse)),
+    responses(
+        (status = 200, body = ReportResponse),
+        (status = 404, description = "Report not found or already closed"),
+        (status = 409, description = "Report is claimed by another admin"),
+    ),
     security(("bearer_auth" = []))
 )]
 pub async fn resolve_report(
     State(state): State<AppState>,
+    Extension(elevated): Extension<ElevatedAdmin>,
     Path(report_id): Path<Uuid>,
     Json(body): Json<ResolveReportRequest>,
 ) -> Result<Json<ReportResponse>, ReportError> {
@@ -140,22 +234,30 @@ pub async fn resolve_report(
         )));
     }
 
-    let report = sqlx::query_as::<_, Report>(
+    let Some(report) = sqlx::query_as::<_, Report>(
         r"UPDATE user_reports
            SET status = CASE WHEN $2 = 'dismissed' THEN 'dismissed'::report_status ELSE 'resolved'::report_status END,
                resolution_action = $2,
                resolution_note = $3,
                resolved_at = NOW(),
+               resolved_by = $4,
+               assigned_admin_id = COALESCE(assigned_admin_id, $4),
+               claimed_at = COALESCE(claimed_at, NOW()),
                updated_at = NOW()
-           WHERE id = $1 AND status IN ('pending', 'reviewing')
+           WHERE id = $1
+             AND (status = 'pending'
+                  OR (status = 'reviewing' AND (assigned_admin_id IS NULL OR assigned_admin_id = $4)))
            RETURNING *",
     )
     .bind(report_id)
     .bind(&body.resolution_action)
     .bind(&body.resolution_note)
+    .bind(elevated.user_id)
     .fetch_optional(&state.db)
     .await?
-    .ok_or(ReportError::NotFound)?;
+    else {
+        return Err(transition_error(&state.db, report_id, elevated.user_id).await);
+    };
 
     // Broadcast resolution to admin events
     let event = ServerEvent::AdminReportResolved {
@@ -168,6 +270,146 @@ pub async fn resolve_report(
     Ok(Json(report.into()))
 }
 
+/// GET /api/admin/reports/:id/notes
+/// List a report's internal notes, oldest first.
+#[utoipa::path(
+    get,
+    path = "/api/admin/reports/{id}/notes",
+    tag = "moderation",
+    params(("id" = Uuid, Path, description = "Report ID")),
+    responses((status = 200, body = Vec<ReportNote>)),
+    security(("bearer_auth" = []))
+)]
+pub async fn list_notes(
+    State(state): State<AppState>,
+    Path(report_id): Path<Uuid>,
+) -> Result<Json<Vec<ReportNote>>, ReportError> {
+    require_report(&state.db, report_id).await?;
+
+    let notes = sqlx::query_as::<_, ReportNote>(
+        r"SELECT n.id, n.report_id, n.author_id, u.username AS author_username,
+                 n.body, n.created_at
+           FROM report_notes n
+           LEFT JOIN users u ON u.id = n.author_id
+           WHERE n.report_id = $1
+           ORDER BY n.created_at ASC, n.id ASC",
+    )
+    .bind(report_id)
+    .fetch_all(&state.db)
+    .await?;
+
+    Ok(Json(notes))
+}
+
+/// POST /api/admin/reports/:id/notes
+/// Add an internal note. Notes are never shown to the reporter.
+#[utoipa::path(
+    post,
+    path = "/api/admin/reports/{id}/notes",
+    tag = "moderation",
+    params(("id" = Uuid, Path, description = "Report ID")),
+    request_body = CreateReportNoteRequest,
+    responses((status = 201, body = ReportNote)),
+    security(("bearer_auth" = []))
+)]
+pub async fn create_note(
+    State(state): State<AppState>,
+    Extension(elevated): Extension<ElevatedAdmin>,
+    Path(report_id): Path<Uuid>,
+    Json(body): Json<CreateReportNoteRequest>,
+) -> Result<(StatusCode, Json<ReportNote>), ReportError> {
+    body.validate()
+        .map_err(|e| ReportError::Validation(e.to_string()))?;
+    require_report(&state.db, report_id).await?;
+
+    let note = sqlx::query_as::<_, ReportNote>(
+        r"WITH inserted AS (
+              INSERT INTO report_notes (report_id, author_id, body)
+              VALUES ($1, $2, $3)
+              RETURNING *
+          )
+          SELECT n.id, n.report_id, n.author_id, u.username AS author_username,
+                 n.body, n.created_at
+          FROM inserted n
+          LEFT JOIN users u ON u.id = n.author_id",
+    )
+    .bind(report_id)
+    .bind(elevated.user_id)
+    .bind(body.body.trim())
+    .fetch_one(&state.db)
+    .await?;
+
+    Ok((StatusCode::CREATED, Json(note)))
+}
+
+/// GET /api/admin/reports/:id/evidence
+/// List the message snapshots linked to a report.
+#[utoipa::path(
+    get,
+    path = "/api/admin/reports/{id}/evidence",
+    tag = "moderation",
+    params(("id" = Uuid, Path, description = "Report ID")),
+    responses((status = 200, body = Vec<ReportEvidence>)),
+    security(("bearer_auth" = []))
+)]
+pub async fn list_evidence(
+    State(state): State<AppState>,
+    Path(report_id): Path<Uuid>,
+) -> Result<Json<Vec<ReportEvidence>>, ReportError> {
+    require_report(&state.db, report_id).await?;
+    Ok(Json(evidence::list(&state.db, report_id).await?))
+}
+
+/// POST /api/admin/reports/:id/evidence
+/// Snapshot another message (e.g. surrounding context) into a report.
+#[utoipa::path(
+    post,
+    path = "/api/admin/reports/{id}/evidence",
+    tag = "moderation",
+    params(("id" = Uuid, Path, description = "Report ID")),
+    request_body = AddReportEvidenceRequest,
+    responses(
+        (status = 201, body = ReportEvidence),
+        (status = 400, description = "Message not found or deleted"),
+        (status = 409, description = "Message is already linked"),
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn add_evidence(
+    State(state): State<AppState>,
+    Extension(elevated): Extension<ElevatedAdmin>,
+    Path(report_id): Path<Uuid>,
+    Json(body): Json<AddReportEvidenceRequest>,
+) -> Result<(StatusCode, Json<ReportEvidence>), ReportError> {
+    require_report(&state.db, report_id).await?;
+
+    let deleted: Option<bool> =
+        sqlx::query_scalar("SELECT deleted_at IS NOT NULL FROM messages WHERE id = $1")
+            .bind(body.message_id)
+            .fetch_optional(&state.db)
+            .await?;
+    match deleted {
+        None => return Err(ReportError::Validation("Message not found".to_string())),
+        Some(true) => {
+            return Err(ReportError::Validation(
+                "Message has already been deleted".to_string(),
+            ))
+        }
+        Some(false) => {}
+    }
+
+    let evidence = evidence::capture_message(
+        &state.db,
+        report_id,
+        body.message_id,
+        Some(elevated.user_id),
+    )
+    .await?
+    .ok_or_else(|| ReportError::Conflict("Message is already linked to this report".to_string()))?;
+
+    Ok((StatusCode::CREATED, Json(evidence)))
+}
+
 /// GET /api/admin/reports/stats
 /// Get report counts by status.
 #[utoipa::path(
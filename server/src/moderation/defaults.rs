@@ -44,7 +44,7 @@ fn category_text(category: FilterCategory) -> &'static str {
         FilterCategory::HateSpeech => HATE_SPEECH_TXT,
         FilterCategory::Spam => SPAM_PATTERNS_TXT,
         FilterCategory::AbusiveLanguage => ABUSIVE_TXT,
-        FilterCategory::Custom => "",
+        FilterCategory::Custom | FilterCategory::Language | FilterCategory::Links => "",
     }
 }
 
@@ -0,0 +1,305 @@
+//! Automod Escalation
+//!
+//! Applies a guild's escalation policies after filter violations are logged.
+//! Each policy counts the member's logged filter matches within its window;
+//! matches a policy already acted on are not counted again, so a policy fires
+//! once per `threshold` violations. When several policies are reached at once
+//! only the most severe one is applied.
+//!
+//! Guild owners and moderators (`MANAGE_GUILD`, `TIMEOUT_MEMBERS` or
+//! `BAN_MEMBERS`) are never escalated. Escalations run in the background and
+//! never fail the request that logged the violation.
+
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use super::filter_queries;
+use super::filter_types::{
+    EscalationAction, EscalationEvaluation, EscalationPolicy, PolicyEvaluation,
+};
+use crate::api::AppState;
+use crate::chat::system_messages;
+use crate::db::MessageType;
+use crate::guild::{audit, bans, timeouts};
+use crate::i18n::LocalizedText;
+use crate::permissions::{
+    get_member_permission_context, GuildPermissions, MemberPermissionContext,
+};
+
+/// Whether a member is exempt from escalation.
+const fn is_exempt(ctx: &MemberPermissionContext) -> bool {
+    ctx.is_owner
+        || ctx.has_permission(GuildPermissions::MANAGE_GUILD)
+        || ctx.has_permission(GuildPermissions::TIMEOUT_MEMBERS)
+        || ctx.has_permission(GuildPermissions::BAN_MEMBERS)
+}
+
+/// Pick the policy to apply: the most severe reached policy, then the one
+/// with the highest threshold.
+pub fn select(evaluations: &[PolicyEvaluation]) -> Option<&PolicyEvaluation> {
+    evaluations
+        .iter()
+        .filter(|evaluation| evaluation.reached())
+        .max_by_key(|evaluation| (evaluation.action, evaluation.threshold))
+}
+
+/// Count a member's violations against every policy of the guild.
+///
+/// Non-members are reported as exempt.
+pub async fn evaluate(
+    pool: &PgPool,
+    guild_id: Uuid,
+    user_id: Uuid,
+) -> sqlx::Result<(EscalationEvaluation, Vec<EscalationPolicy>)> {
+    let ctx = get_member_permission_context(pool, guild_id, user_id).await?;
+    let policies = filter_queries::list_escalation_policies(pool, guild_id).await?;
+
+    let mut evaluations = Vec::with_capacity(policies.len());
+    for policy in &policies {
+        evaluations.push(PolicyEvaluation {
+            policy_id: policy.id,
+            name: policy.name.clone(),
+            enabled: policy.enabled,
+            action: policy.action,
+            threshold: policy.threshold,
+            violations: filter_queries::count_policy_violations(pool, policy, user_id).await?,
+        });
+    }
+
+    let exempt = ctx.as_ref().is_none_or(is_exempt);
+    let triggered_policy_id = if exempt {
+        None
+    } else {
+        select(&evaluations).map(|evaluation| evaluation.policy_id)
+    };
+
+    Ok((
+        EscalationEvaluation {
+            user_id,
+            exempt,
+            policies: evaluations,
+            triggered_policy_id,
+        },
+        policies,
+    ))
+}
+
+/// Evaluate and apply the escalation policies in the background after a
+/// violation was logged for `user_id`.
+pub fn spawn(state: &AppState, guild_id: Uuid, user_id: Uuid) {
+    let state = state.clone();
+    tokio::spawn(async move {
+        if let Err(e) = run(&state, guild_id, user_id).await {
+            tracing::warn!(guild_id = %guild_id, user_id = %user_id, error = %e, "Failed to apply automod escalation");
+        }
+    });
+}
+
+/// Apply the triggered policy for a member, if any.
+async fn run(state: &AppState, guild_id: Uuid, user_id: Uuid) -> sqlx::Result<()> {
+    let (evaluation, policies) = evaluate(&state.db, guild_id, user_id).await?;
+    let Some(policy) = evaluation
+        .triggered_policy_id
+        .and_then(|id| policies.into_iter().find(|policy| policy.id == id))
+    else {
+        return Ok(());
+    };
+    let violations = evaluation
+        .policies
+        .iter()
+        .find(|p| p.policy_id == policy.id)
+        .map_or(0, |p| p.violations);
+
+    // Record first so violations counted here are not counted again.
+    filter_queries::record_escalation(
+        &state.db,
+        guild_id,
+        user_id,
+        policy.id,
+        policy.action,
+        violations,
+    )
+    .await?;
+
+    let details = match policy.action {
+        EscalationAction::Warn => serde_json::json!({}),
+        EscalationAction::Timeout => {
+            let until = apply_timeout(state, &policy, user_id).await?;
+            serde_json::json!({ "timeout_until": until })
+        }
+        EscalationAction::Ban => {
+            let reason = format!("Automod: {}", policy.name);
+            bans::insert_ban(state, guild_id, user_id, None, &reason, None).await?;
+            serde_json::json!({ "reason": reason })
+        }
+    };
+
+    tracing::info!(
+        guild_id = %guild_id,
+        user_id = %user_id,
+        policy_id = %policy.id,
+        action = ?policy.action,
+        violations,
+        "Applied automod escalation"
+    );
+
+    record_audit(state, &policy, user_id, violations, details).await?;
+    notify(state, &policy, user_id, violations).await;
+    Ok(())
+}
+
+/// Time the member out for the policy's duration, never shortening a longer
+/// timeout that is already running.
+async fn apply_timeout(
+    state: &AppState,
+    policy: &EscalationPolicy,
+    user_id: Uuid,
+) -> sqlx::Result<Option<DateTime<Utc>>> {
+    let seconds = policy.timeout_seconds.unwrap_or_default();
+    let until: Option<DateTime<Utc>> = sqlx::query_scalar(
+        r"UPDATE guild_members
+           SET timeout_until = GREATEST(COALESCE(timeout_until, NOW()), NOW() + make_interval(secs => $3))
+           WHERE guild_id = $1 AND user_id = $2
+           RETURNING timeout_until",
+    )
+    .bind(policy.guild_id)
+    .bind(user_id)
+    .bind(f64::from(seconds))
+    .fetch_optional(&state.db)
+    .await?
+    .flatten();
+
+    if until.is_some() {
+        timeouts::broadcast_timeout(&state.redis, policy.guild_id, user_id, until).await;
+    }
+    Ok(until)
+}
+
+/// Record the escalation in the guild audit log.
+///
+/// The policy's creator is the actor, or the guild owner if the creator's
+/// account is gone.
+async fn record_audit(
+    state: &AppState,
+    policy: &EscalationPolicy,
+    user_id: Uuid,
+    violations: i64,
+    mut details: serde_json::Value,
+) -> sqlx::Result<()> {
+    let actor_id = match policy.created_by {
+        Some(id) => id,
+        None => {
+            sqlx::query_scalar("SELECT owner_id FROM guilds WHERE id = $1")
+                .bind(policy.guild_id)
+                .fetch_one(&state.db)
+                .await?
+        }
+    };
+    let action = match policy.action {
+        EscalationAction::Warn => "guild.automod.warned",
+        EscalationAction::Timeout => "guild.automod.timed_out",
+        EscalationAction::Ban => "guild.automod.banned",
+    };
+    details["automod"] = true.into();
+    details["policy_id"] = policy.id.to_string().into();
+    details["policy_name"] = policy.name.clone().into();
+    details["violations"] = violations.into();
+
+    audit::record(
+        state,
+        policy.guild_id,
+        actor_id,
+        action,
+        Some("user"),
+        Some(user_id),
+        Some(details),
+    )
+    .await;
+    Ok(())
+}
+
+/// Post a notice to the policy's moderator channel, if it has one.
+async fn notify(state: &AppState, policy: &EscalationPolicy, user_id: Uuid, violations: i64) {
+    let Some(channel_id) = policy.notify_channel_id else {
+        return;
+    };
+    let text = match policy.action {
+        EscalationAction::Warn => LocalizedText::new("system-automod-warned"),
+        EscalationAction::Timeout => LocalizedText::new("system-automod-timed-out").arg(
+            "minutes",
+            (policy.timeout_seconds.unwrap_or_default() + 59) / 60,
+        ),
+        EscalationAction::Ban => LocalizedText::new("system-automod-banned"),
+    }
+    .arg("violations", violations)
+    .arg("policy", policy.name.as_str());
+
+    system_messages::post(
+        &state.db,
+        &state.redis,
+        channel_id,
+        user_id,
+        MessageType::SystemAutomod,
+        &text,
+    )
+    .await;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn evaluation(action: EscalationAction, threshold: i32, violations: i64) -> PolicyEvaluation {
+        PolicyEvaluation {
+            policy_id: Uuid::new_v4(),
+            name: String::new(),
+            enabled: true,
+            action,
+            threshold,
+            violations,
+        }
+    }
+
+    #[test]
+    fn selects_nothing_below_threshold() {
+        let evaluations = [
+            evaluation(EscalationAction::Warn, 3, 2),
+            evaluation(EscalationAction::Ban, 10, 2),
+        ];
+        assert!(select(&evaluations).is_none());
+    }
+
+    #[test]
+    fn selects_most_severe_reached_policy() {
+        let evaluations = [
+            evaluation(EscalationAction::Warn, 3, 6),
+            evaluation(EscalationAction::Timeout, 5, 6),
+            evaluation(EscalationAction::Ban, 10, 6),
+        ];
+        assert_eq!(
+            select(&evaluations).map(|e| e.action),
+            Some(EscalationAction::Timeout)
+        );
+    }
+
+    #[test]
+    fn prefers_higher_threshold_for_same_action() {
+        let evaluations = [
+            evaluation(EscalationAction::Timeout, 3, 8),
+            evaluation(EscalationAction::Timeout, 8, 8),
+        ];
+        assert_eq!(select(&evaluations).map(|e| e.threshold), Some(8));
+    }
+
+    #[test]
+    fn skips_disabled_policies() {
+        let mut ban = evaluation(EscalationAction::Ban, 1, 5);
+        ban.enabled = false;
+        let evaluations = [ban, evaluation(EscalationAction::Warn, 1, 5)];
+        assert_eq!(
+            select(&evaluations).map(|e| e.action),
+            Some(EscalationAction::Warn)
+        );
+    }
+}
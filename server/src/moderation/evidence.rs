@@ -0,0 +1,72 @@
+//! Report Evidence
+//!
+//! Copies reported messages into `report_evidence` so admins still see what
+//! was reported after the author edits or deletes the message. The reported
+//! message is captured when the report is created; admins can link further
+//! messages (e.g. surrounding context) while triaging.
+
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use super::types::ReportEvidence;
+
+/// Snapshot a message into a report's evidence.
+///
+/// Returns `None` if the message does not exist, is already deleted, or is
+/// already linked to the report.
+pub async fn capture_message(
+    pool: &PgPool,
+    report_id: Uuid,
+    message_id: Uuid,
+    added_by: Option<Uuid>,
+) -> sqlx::Result<Option<ReportEvidence>> {
+    sqlx::query_as::<_, ReportEvidence>(
+        r"WITH inserted AS (
+              INSERT INTO report_evidence
+                  (report_id, message_id, channel_id, author_id, content, encrypted,
+                   attachments, message_created_at, edited_at, added_by)
+              SELECT $1, m.id, m.channel_id, m.user_id, m.content, m.encrypted,
+                     COALESCE(
+                         (SELECT jsonb_agg(jsonb_build_object(
+                                     'id', fa.id,
+                                     'filename', fa.filename,
+                                     'mime_type', fa.mime_type,
+                                     'size_bytes', fa.size_bytes
+                                 ) ORDER BY fa.created_at)
+                          FROM file_attachments fa WHERE fa.message_id = m.id),
+                         '[]'::jsonb
+                     ),
+                     m.created_at, m.edited_at, $3
+              FROM messages m
+              WHERE m.id = $2 AND m.deleted_at IS NULL
+              ON CONFLICT (report_id, message_id) DO NOTHING
+              RETURNING *
+          )
+          SELECT e.id, e.report_id, e.message_id, e.channel_id, e.author_id,
+                 u.username AS author_username, e.content, e.encrypted, e.attachments,
+                 e.message_created_at, e.edited_at, e.added_by, e.captured_at
+          FROM inserted e
+          LEFT JOIN users u ON u.id = e.author_id",
+    )
+    .bind(report_id)
+    .bind(message_id)
+    .bind(added_by)
+    .fetch_optional(pool)
+    .await
+}
+
+/// All evidence linked to a report, oldest first.
+pub async fn list(pool: &PgPool, report_id: Uuid) -> sqlx::Result<Vec<ReportEvidence>> {
+    sqlx::query_as::<_, ReportEvidence>(
+        r"SELECT e.id, e.report_id, e.message_id, e.channel_id, e.author_id,
+                 u.username AS author_username, e.content, e.encrypted, e.attachments,
+                 e.message_created_at, e.edited_at, e.added_by, e.captured_at
+          FROM report_evidence e
+          LEFT JOIN users u ON u.id = e.author_id
+          WHERE e.report_id = $1
+          ORDER BY e.captured_at ASC, e.message_created_at ASC",
+    )
+    .bind(report_id)
+    .fetch_all(pool)
+    .await
+}
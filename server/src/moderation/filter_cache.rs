@@ -72,15 +72,7 @@ impl FilterCache {
         let gen_before = gen.load(Ordering::Acquire);
 
         // Slow path: build from database
-        let configs = filter_queries::list_filter_configs(pool, guild_id)
-            .await
-            .map_err(|e| format!("Failed to load filter configs: {e}"))?;
-
-        let patterns = filter_queries::list_custom_patterns(pool, guild_id)
-            .await
-            .map_err(|e| format!("Failed to load custom patterns: {e}"))?;
-
-        let engine = Arc::new(FilterEngine::build(&configs, &patterns)?);
+        let engine = Arc::new(load_engine(pool, guild_id).await?);
 
         // Only insert if no invalidation happened for THIS guild since we started.
         let gen_after = gen.load(Ordering::Acquire);
@@ -106,15 +98,7 @@ impl FilterCache {
         pool: &PgPool,
         guild_id: Uuid,
     ) -> Result<Arc<FilterEngine>, String> {
-        let configs = filter_queries::list_filter_configs(pool, guild_id)
-            .await
-            .map_err(|e| format!("Failed to load filter configs: {e}"))?;
-
-        let patterns = filter_queries::list_custom_patterns(pool, guild_id)
-            .await
-            .map_err(|e| format!("Failed to load custom patterns: {e}"))?;
-
-        Ok(Arc::new(FilterEngine::build(&configs, &patterns)?))
+        load_engine(pool, guild_id).await.map(Arc::new)
     }
 
     /// Invalidate the cached engine for a guild.
@@ -127,3 +111,37 @@ impl FilterCache {
         self.engines.remove(&guild_id);
     }
 }
+
+/// Build a guild's filter engine from the database.
+async fn load_engine(pool: &PgPool, guild_id: Uuid) -> Result<FilterEngine, String> {
+    let configs = filter_queries::list_filter_configs(pool, guild_id)
+        .await
+        .map_err(|e| format!("Failed to load filter configs: {e}"))?;
+
+    let patterns = filter_queries::list_custom_patterns(pool, guild_id)
+        .await
+        .map_err(|e| format!("Failed to load custom patterns: {e}"))?;
+
+    let language_rules = filter_queries::list_language_rules(pool, guild_id)
+        .await
+        .map_err(|e| format!("Failed to load language rules: {e}"))?;
+
+    let normalization = filter_queries::get_normalization(pool, guild_id)
+        .await
+        .map_err(|e| format!("Failed to load normalization settings: {e}"))?;
+
+    let mut engine = FilterEngine::build_with_normalization(&configs, &patterns, normalization)?
+        .with_language_rules(&language_rules);
+
+    if engine.filters_links() {
+        let domains = filter_queries::list_link_domains(pool, guild_id)
+            .await
+            .map_err(|e| format!("Failed to load link domains: {e}"))?;
+        let own_invites = filter_queries::list_invite_codes(pool, guild_id)
+            .await
+            .map_err(|e| format!("Failed to load invite codes: {e}"))?;
+        engine = engine.with_link_domains(&domains, &own_invites);
+    }
+
+    Ok(engine)
+}
@@ -2,16 +2,23 @@
 //!
 //! Hybrid Aho-Corasick + regex engine for content filtering.
 //! Aho-Corasick handles keyword matching (fast path), regex handles
-//! pattern-based rules.
+//! pattern-based rules. The `links` category and channel language rules
+//! run on top of both.
+
+use std::collections::{HashMap, HashSet};
 
 use aho_corasick::AhoCorasick;
 use regex::Regex;
 use uuid::Uuid;
+use whatlang::Lang;
 
-use super::defaults;
 use super::filter_types::{
-    FilterAction, FilterCategory, FilterMatch, FilterResult, GuildFilterConfig, GuildFilterPattern,
+    ChannelLanguageRule, FilterAction, FilterCategory, FilterMatch, FilterNormalization,
+    FilterResult, GuildFilterConfig, GuildFilterPattern, GuildLinkDomain, LinkDomainList,
+    LinkFilterMode,
 };
+use super::links::{self, Invite};
+use super::{defaults, language, obfuscation};
 
 /// Metadata for a keyword in the Aho-Corasick automaton.
 #[derive(Debug)]
@@ -30,12 +37,91 @@ struct CompiledPattern {
     source: String,
 }
 
+/// A channel's allowed languages.
+#[derive(Debug)]
+struct LanguageRule {
+    allowed: Vec<Lang>,
+    action: FilterAction,
+}
+
+/// The `links` category with the guild's domain lists.
+#[derive(Debug)]
+struct LinkRules {
+    mode: LinkFilterMode,
+    action: FilterAction,
+    allow: Vec<String>,
+    deny: Vec<String>,
+    /// Invite codes of the guild itself, never treated as external.
+    own_invites: HashSet<String>,
+}
+
+impl LinkRules {
+    /// Match the links in `content`, one match per distinct host.
+    fn check(&self, content: &str, matches: &mut Vec<FilterMatch>) {
+        let mut seen = HashSet::new();
+
+        for link in links::extract(content) {
+            let denied = self
+                .deny
+                .iter()
+                .find(|domain| links::host_matches(&link.host, domain));
+            let matched = if let Some(domain) = denied {
+                Some(format!("domain:{domain}"))
+            } else {
+                let hit = match self.mode {
+                    LinkFilterMode::All => link.explicit,
+                    LinkFilterMode::Invites => match link.invite() {
+                        Some(Invite::Platform) => true,
+                        Some(Invite::Guild(code)) => !self.own_invites.contains(code),
+                        None => false,
+                    },
+                    LinkFilterMode::Allowlist => {
+                        link.explicit
+                            && !self
+                                .allow
+                                .iter()
+                                .any(|domain| links::host_matches(&link.host, domain))
+                    }
+                };
+                let kind = if self.mode == LinkFilterMode::Invites {
+                    "invite"
+                } else {
+                    "link"
+                };
+                hit.then(|| format!("{kind}:{}", link.host))
+            };
+
+            if let Some(pattern) = matched {
+                if seen.insert(pattern.clone()) {
+                    matches.push(FilterMatch {
+                        category: FilterCategory::Links,
+                        action: self.action,
+                        matched_pattern: pattern,
+                        custom_pattern_id: None,
+                    });
+                }
+            }
+        }
+    }
+}
+
+/// Normalize text before keyword matching.
+///
+/// Applied to both keywords and message content, so other keyword matchers
+/// (e.g. notification highlights) agree with the filter on what matches.
+pub fn normalize(text: &str) -> String {
+    text.to_lowercase()
+}
+
 /// Content filter engine combining Aho-Corasick keyword matching with regex patterns.
 pub struct FilterEngine {
     keyword_matcher: Option<AhoCorasick>,
     keyword_meta: Vec<KeywordMeta>,
     keyword_strings: Vec<String>,
     regex_patterns: Vec<CompiledPattern>,
+    language_rules: HashMap<Uuid, LanguageRule>,
+    link_rules: Option<LinkRules>,
+    normalization: FilterNormalization,
 }
 
 impl FilterEngine {
@@ -46,10 +132,23 @@ impl FilterEngine {
     pub fn build(
         configs: &[GuildFilterConfig],
         custom_patterns: &[GuildFilterPattern],
+    ) -> Result<Self, String> {
+        Self::build_with_normalization(configs, custom_patterns, FilterNormalization::default())
+    }
+
+    /// Build a filter engine that folds obfuscated text before keyword matching.
+    ///
+    /// Keywords are folded with the same stages, so the automaton matches
+    /// folded content. Matches still report the keyword as configured.
+    pub fn build_with_normalization(
+        configs: &[GuildFilterConfig],
+        custom_patterns: &[GuildFilterPattern],
+        normalization: FilterNormalization,
     ) -> Result<Self, String> {
         let mut keywords: Vec<String> = Vec::new();
         let mut keyword_meta: Vec<KeywordMeta> = Vec::new();
         let mut regex_patterns: Vec<CompiledPattern> = Vec::new();
+        let mut link_rules = None;
 
         // Load enabled built-in categories
         for config in configs {
@@ -57,9 +156,20 @@ impl FilterEngine {
                 continue;
             }
 
+            if config.category == FilterCategory::Links {
+                link_rules = Some(LinkRules {
+                    mode: config.link_mode.unwrap_or_default(),
+                    action: config.action,
+                    allow: Vec::new(),
+                    deny: Vec::new(),
+                    own_invites: HashSet::new(),
+                });
+                continue;
+            }
+
             // Add keywords from built-in lists
             for kw in defaults::default_keywords(config.category) {
-                keywords.push(kw.to_lowercase());
+                keywords.push(normalize(kw));
                 keyword_meta.push(KeywordMeta {
                     category: config.category,
                     action: config.action,
@@ -116,7 +226,7 @@ impl FilterEngine {
                     }
                 }
             } else {
-                keywords.push(pattern.pattern.to_lowercase());
+                keywords.push(normalize(&pattern.pattern));
                 keyword_meta.push(KeywordMeta {
                     category: FilterCategory::Custom,
                     action: FilterAction::Block,
@@ -128,10 +238,18 @@ impl FilterEngine {
         let keyword_matcher = if keywords.is_empty() {
             None
         } else {
+            let folded: Vec<String> = if normalization.is_enabled() {
+                keywords
+                    .iter()
+                    .map(|kw| obfuscation::fold(kw, normalization))
+                    .collect()
+            } else {
+                keywords.clone()
+            };
             Some(
                 AhoCorasick::builder()
                     .ascii_case_insensitive(true)
-                    .build(&keywords)
+                    .build(&folded)
                     .map_err(|e| format!("Failed to build Aho-Corasick automaton: {e}"))?,
             )
         };
@@ -141,16 +259,78 @@ impl FilterEngine {
             keyword_meta,
             keyword_strings: keywords,
             regex_patterns,
+            language_rules: HashMap::new(),
+            link_rules,
+            normalization,
         })
     }
 
+    /// Attach per-channel language rules.
+    ///
+    /// Disabled rules are skipped, as are codes `whatlang` does not know.
+    #[must_use]
+    pub fn with_language_rules(mut self, rules: &[ChannelLanguageRule]) -> Self {
+        for rule in rules {
+            if !rule.enabled {
+                continue;
+            }
+            let allowed: Vec<Lang> = rule
+                .allowed_languages
+                .iter()
+                .filter_map(|code| language::parse_code(code))
+                .collect();
+            if allowed.is_empty() {
+                continue;
+            }
+            self.language_rules.insert(
+                rule.channel_id,
+                LanguageRule {
+                    allowed,
+                    action: rule.action,
+                },
+            );
+        }
+        self
+    }
+
+    /// Attach the link allowlist/denylist and the guild's own invite codes.
+    ///
+    /// Ignored unless the `links` category is enabled.
+    #[must_use]
+    pub fn with_link_domains(
+        mut self,
+        domains: &[GuildLinkDomain],
+        own_invites: &[String],
+    ) -> Self {
+        if let Some(rules) = self.link_rules.as_mut() {
+            for entry in domains {
+                match entry.list {
+                    LinkDomainList::Allow => rules.allow.push(entry.domain.clone()),
+                    LinkDomainList::Deny => rules.deny.push(entry.domain.clone()),
+                }
+            }
+            rules.own_invites.extend(own_invites.iter().cloned());
+        }
+        self
+    }
+
+    /// Returns true if the `links` category is enabled.
+    pub const fn filters_links(&self) -> bool {
+        self.link_rules.is_some()
+    }
+
     /// Check content against all active filters.
     ///
-    /// Runs Aho-Corasick first (fast path), then regex patterns.
+    /// Runs Aho-Corasick first (fast path), then regex patterns, then link rules.
+    /// Keyword matching sees the folded content when normalization is
+    /// enabled; regex patterns always run on the raw content.
     /// Returns all matches with the highest-priority action determining `blocked`.
     pub fn check(&self, content: &str) -> FilterResult {
         let mut matches = Vec::new();
-        let content_lower = content.to_lowercase();
+        let mut content_lower = normalize(content);
+        if self.normalization.is_enabled() {
+            content_lower = obfuscation::fold(&content_lower, self.normalization);
+        }
 
         // Aho-Corasick keyword matching
         if let Some(ref matcher) = self.keyword_matcher {
@@ -183,14 +363,45 @@ impl FilterEngine {
             }
         }
 
+        if let Some(ref rules) = self.link_rules {
+            rules.check(content, &mut matches);
+        }
+
         let blocked = matches.iter().any(|m| m.action == FilterAction::Block);
 
         FilterResult { blocked, matches }
     }
 
+    /// Check a message posted to `channel_id`: all content filters plus the
+    /// channel's language rule, if any.
+    ///
+    /// Language detection only runs for channels with a rule.
+    pub fn check_message(&self, content: &str, channel_id: Uuid) -> FilterResult {
+        let mut result = self.check(content);
+
+        if let Some(rule) = self.language_rules.get(&channel_id) {
+            if let Some(lang) = language::detect(content) {
+                if !rule.allowed.contains(&lang) {
+                    result.blocked |= rule.action == FilterAction::Block;
+                    result.matches.push(FilterMatch {
+                        category: FilterCategory::Language,
+                        action: rule.action,
+                        matched_pattern: format!("language:{}", lang.code()),
+                        custom_pattern_id: None,
+                    });
+                }
+            }
+        }
+
+        result
+    }
+
     /// Returns true if this engine has no active filters.
     pub fn is_empty(&self) -> bool {
-        self.keyword_matcher.is_none() && self.regex_patterns.is_empty()
+        self.keyword_matcher.is_none()
+            && self.regex_patterns.is_empty()
+            && self.language_rules.is_empty()
+            && self.link_rules.is_none()
     }
 }
 
@@ -209,6 +420,7 @@ mod tests {
             category,
             enabled,
             action,
+            link_mode: (category == FilterCategory::Links).then(LinkFilterMode::default),
             created_at: chrono::Utc::now(),
             updated_at: chrono::Utc::now(),
         }
@@ -303,6 +515,240 @@ mod tests {
         assert!(result.blocked);
     }
 
+    fn make_language_rule(
+        channel_id: Uuid,
+        codes: &[&str],
+        action: FilterAction,
+    ) -> ChannelLanguageRule {
+        ChannelLanguageRule {
+            id: Uuid::new_v4(),
+            guild_id: Uuid::new_v4(),
+            channel_id,
+            allowed_languages: codes.iter().map(ToString::to_string).collect(),
+            action,
+            enabled: true,
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
+        }
+    }
+
+    #[test]
+    fn language_rule_applies_to_its_channel_only() {
+        let channel_id = Uuid::new_v4();
+        let rule = make_language_rule(channel_id, &["eng"], FilterAction::Block);
+        let engine = FilterEngine::build(&[], &[])
+            .unwrap()
+            .with_language_rules(&[rule]);
+        let german = "Der schnelle braune Fuchs springt über den faulen Hund und alle schauen zu";
+
+        let result = engine.check_message(german, channel_id);
+        assert!(result.blocked);
+        assert_eq!(result.matches[0].category, FilterCategory::Language);
+        assert_eq!(result.matches[0].matched_pattern, "language:deu");
+
+        assert!(!engine.check_message(german, Uuid::new_v4()).blocked);
+        assert!(
+            !engine
+                .check_message(
+                    "The quick brown fox jumps over the lazy dog while everyone watches",
+                    channel_id
+                )
+                .blocked
+        );
+        // Too short to classify
+        assert!(!engine.check_message("danke", channel_id).blocked);
+    }
+
+    #[test]
+    fn language_rule_warn_does_not_block() {
+        let channel_id = Uuid::new_v4();
+        let rule = make_language_rule(channel_id, &["deu"], FilterAction::Warn);
+        let engine = FilterEngine::build(&[], &[])
+            .unwrap()
+            .with_language_rules(&[rule]);
+
+        let result = engine.check_message(
+            "The quick brown fox jumps over the lazy dog while everyone watches",
+            channel_id,
+        );
+        assert!(!result.blocked);
+        assert_eq!(result.matches.len(), 1);
+        assert_eq!(result.matches[0].action, FilterAction::Warn);
+    }
+
+    const ALL_NORMALIZATION: FilterNormalization = FilterNormalization {
+        strip_invisible: true,
+        fold_confusables: true,
+        substitute_leetspeak: true,
+    };
+
+    #[test]
+    fn obfuscated_keyword_passes_without_normalization() {
+        let pattern = make_custom_pattern("badword", false);
+        let engine = FilterEngine::build(&[], &[pattern]).unwrap();
+
+        assert!(!engine.check("this has a b@dw0rd in it").blocked);
+    }
+
+    #[test]
+    fn normalization_catches_common_evasions() {
+        let pattern = make_custom_pattern("badword", false);
+        let engine =
+            FilterEngine::build_with_normalization(&[], &[pattern], ALL_NORMALIZATION).unwrap();
+
+        for evasion in [
+            "b@dw0rd",
+            "B4DW0RD",
+            "bad\u{200B}word",
+            "b\u{200D}a\u{2060}d\u{FEFF}word",
+            "bádwörd",
+            "ba\u{0301}dword",
+            "bаdwоrd",
+            "ｂａｄｗｏｒｄ",
+            "𝐛𝐚𝐝𝐰𝐨𝐫𝐝",
+            "ⓑⓐⓓⓦⓞⓡⓓ",
+            "ʙᴀᴅᴡᴏʀᴅ",
+            "ｂ@d\u{200B}wö𝐫d",
+        ] {
+            let result = engine.check(&format!("this has a {evasion} in it"));
+            assert!(result.blocked, "{evasion:?} slipped through");
+            // Matches report the keyword as configured.
+            assert_eq!(result.matches[0].matched_pattern, "badword");
+        }
+        assert!(!engine.check("this is perfectly fine").blocked);
+    }
+
+    #[test]
+    fn normalization_stages_are_independent() {
+        let pattern = make_custom_pattern("badword", false);
+        let leet_only = FilterNormalization {
+            substitute_leetspeak: true,
+            ..FilterNormalization::default()
+        };
+        let engine = FilterEngine::build_with_normalization(&[], &[pattern], leet_only).unwrap();
+
+        assert!(engine.check("b@dw0rd").blocked);
+        assert!(!engine.check("bad\u{200B}word").blocked);
+        assert!(!engine.check("bаdword").blocked);
+    }
+
+    #[test]
+    fn normalization_folds_keywords_too() {
+        // A keyword spelled with digits still matches itself and its letter form.
+        let pattern = make_custom_pattern("h4x0r", false);
+        let engine =
+            FilterEngine::build_with_normalization(&[], &[pattern], ALL_NORMALIZATION).unwrap();
+
+        assert!(engine.check("h4x0r").blocked);
+        assert!(engine.check("haxor").blocked);
+        assert_eq!(engine.check("haxor").matches[0].matched_pattern, "h4x0r");
+    }
+
+    #[test]
+    fn normalization_leaves_regex_on_raw_content() {
+        let pattern = make_custom_pattern(r"(?i)free\s+money", true);
+        let engine =
+            FilterEngine::build_with_normalization(&[], &[pattern], ALL_NORMALIZATION).unwrap();
+
+        assert!(engine.check("get FREE MONEY now!").blocked);
+        assert!(!engine.check("get fr33 m0ney now!").blocked);
+    }
+
+    fn make_link_domain(domain: &str, list: LinkDomainList) -> GuildLinkDomain {
+        GuildLinkDomain {
+            id: Uuid::new_v4(),
+            guild_id: Uuid::new_v4(),
+            domain: domain.to_string(),
+            list,
+            created_by: Uuid::new_v4(),
+            created_at: chrono::Utc::now(),
+        }
+    }
+
+    fn link_engine(mode: LinkFilterMode, domains: &[GuildLinkDomain]) -> FilterEngine {
+        let mut config = make_config(FilterCategory::Links, FilterAction::Block, true);
+        config.link_mode = Some(mode);
+        FilterEngine::build(&[config], &[])
+            .unwrap()
+            .with_link_domains(domains, &["OwnCode1".to_string()])
+    }
+
+    #[test]
+    fn links_all_mode_blocks_every_link() {
+        let engine = link_engine(LinkFilterMode::All, &[]);
+
+        let result = engine.check("look at https://example.com/a and www.example.com/b");
+        assert!(result.blocked);
+        assert_eq!(result.matches.len(), 1, "one match per host");
+        assert_eq!(result.matches[0].category, FilterCategory::Links);
+        assert_eq!(result.matches[0].matched_pattern, "link:example.com");
+
+        // Bare mentions are not explicit links
+        assert!(!engine.check("open notes.txt or example.com").blocked);
+    }
+
+    #[test]
+    fn links_invites_mode_blocks_external_invites() {
+        let engine = link_engine(LinkFilterMode::Invites, &[]);
+
+        for content in [
+            "join discord.gg/abc123",
+            "https://discord.com/invite/abc123",
+            "https://t.me/+abcdef",
+            "https://chat.example.org/invite/OtherCode",
+        ] {
+            let result = engine.check(content);
+            assert!(result.blocked, "{content:?} should be blocked");
+            assert!(result.matches[0].matched_pattern.starts_with("invite:"));
+        }
+
+        // Our own invites and ordinary links pass
+        assert!(
+            !engine
+                .check("https://chat.example.org/invite/OwnCode1")
+                .blocked
+        );
+        assert!(!engine.check("https://example.com/docs").blocked);
+    }
+
+    #[test]
+    fn links_allowlist_mode() {
+        let engine = link_engine(
+            LinkFilterMode::Allowlist,
+            &[make_link_domain("example.com", LinkDomainList::Allow)],
+        );
+
+        assert!(!engine.check("https://example.com/a").blocked);
+        assert!(!engine.check("https://cdn.example.com/a.png").blocked);
+        let result = engine.check("https://badexample.com/a");
+        assert!(result.blocked);
+        assert_eq!(result.matches[0].matched_pattern, "link:badexample.com");
+    }
+
+    #[test]
+    fn links_denylist_applies_in_every_mode() {
+        let deny = [make_link_domain("evil.net", LinkDomainList::Deny)];
+        for mode in [
+            LinkFilterMode::All,
+            LinkFilterMode::Invites,
+            LinkFilterMode::Allowlist,
+        ] {
+            let engine = link_engine(mode, &deny);
+            let result = engine.check("free stuff at login.evil.net/claim");
+            assert!(result.blocked, "{mode:?}");
+            assert_eq!(result.matches[0].matched_pattern, "domain:evil.net");
+        }
+    }
+
+    #[test]
+    fn link_domains_ignored_when_links_disabled() {
+        let engine = FilterEngine::build(&[], &[])
+            .unwrap()
+            .with_link_domains(&[make_link_domain("evil.net", LinkDomainList::Deny)], &[]);
+        assert!(engine.is_empty());
+        assert!(!engine.check("https://evil.net").blocked);
+    }
+
     #[test]
     fn disabled_config_skipped() {
         let config = make_config(FilterCategory::Spam, FilterAction::Block, false);
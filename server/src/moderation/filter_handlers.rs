@@ -1,23 +1,29 @@
 //! Content Filter API Handlers
 //!
 //! CRUD endpoints for guild content filter configuration,
-//! custom patterns, moderation log, and filter testing.
+//! custom patterns, link domains, channel language rules, obfuscation
+//! normalization, automod escalation policies, moderation log, and filter
+//! testing.
 //! All endpoints require `MANAGE_GUILD` permission.
 
 use axum::extract::{Path, Query, State};
 use axum::http::StatusCode;
-use axum::routing::{get, post, put};
+use axum::routing::{delete, get, post, put};
 use axum::{Json, Router};
 use uuid::Uuid;
 
-use super::filter_queries;
 use super::filter_types::{
-    CreatePatternRequest, FilterError, FilterMatchResponse, GuildFilterConfig, GuildFilterPattern,
-    PaginatedModerationLog, PaginationQuery, TestFilterRequest, TestFilterResponse,
-    UpdateFilterConfigsRequest, UpdatePatternRequest,
+    AddLinkDomainRequest, ChannelLanguageRule, CreatePatternRequest, EscalationAction,
+    EscalationEvaluation, EscalationPolicy, EscalationPolicyRequest, EvaluateEscalationRequest,
+    FilterCategory, FilterError, FilterMatchResponse, FilterNormalization, GuildFilterConfig,
+    GuildFilterPattern, GuildLinkDomain, PaginatedModerationLog, PaginationQuery,
+    SetLanguageRuleRequest, TestFilterRequest, TestFilterResponse, UpdateFilterConfigsRequest,
+    UpdatePatternRequest,
 };
+use super::{escalation, filter_queries, language, links};
 use crate::api::AppState;
 use crate::auth::AuthUser;
+use crate::db::ChannelType;
 use crate::permissions::{require_guild_permission, GuildPermissions};
 
 /// Maximum custom patterns per guild.
@@ -29,6 +35,27 @@ const MAX_PATTERN_LENGTH: usize = 500;
 /// Maximum test input length.
 const MAX_TEST_INPUT_LENGTH: usize = 4000;
 
+/// Maximum link allowlist + denylist entries per guild.
+const MAX_LINK_DOMAINS: i64 = 200;
+
+/// Maximum allowed languages per channel rule.
+const MAX_ALLOWED_LANGUAGES: usize = 20;
+
+/// Maximum escalation policies per guild.
+const MAX_ESCALATION_POLICIES: i64 = 20;
+
+/// Maximum escalation policy name length.
+const MAX_POLICY_NAME_LENGTH: usize = 64;
+
+/// Highest violation threshold for an escalation policy.
+const MAX_ESCALATION_THRESHOLD: i32 = 100;
+
+/// Shortest and longest escalation window (1 minute to 30 days).
+const ESCALATION_WINDOW_SECONDS: std::ops::RangeInclusive<i32> = 60..=30 * 24 * 60 * 60;
+
+/// Shortest and longest escalation timeout (1 minute to 28 days).
+const ESCALATION_TIMEOUT_SECONDS: std::ops::RangeInclusive<i32> = 60..=28 * 24 * 60 * 60;
+
 // ============================================================================
 // Router
 // ============================================================================
@@ -45,6 +72,26 @@ pub fn router() -> Router<AppState> {
             "/patterns/{pid}",
             put(update_custom_pattern).delete(delete_custom_pattern),
         )
+        .route("/domains", get(list_link_domains).post(add_link_domain))
+        .route("/domains/{domain_id}", delete(delete_link_domain))
+        .route("/languages", get(list_language_rules))
+        .route(
+            "/languages/{channel_id}",
+            put(set_language_rule).delete(delete_language_rule),
+        )
+        .route(
+            "/normalization",
+            get(get_normalization).put(update_normalization),
+        )
+        .route(
+            "/escalations",
+            get(list_escalation_policies).post(create_escalation_policy),
+        )
+        .route("/escalations/evaluate", post(evaluate_escalation))
+        .route(
+            "/escalations/{policy_id}",
+            put(update_escalation_policy).delete(delete_escalation_policy),
+        )
         .route("/log", get(list_moderation_log))
         .route("/test", post(test_filter))
 }
@@ -123,6 +170,24 @@ async fn update_filter_configs(
             "At least one config entry is required".to_string(),
         ));
     }
+    if body
+        .configs
+        .iter()
+        .any(|c| c.category == FilterCategory::Language)
+    {
+        return Err(FilterError::Validation(
+            "Language rules are configured per channel under /filters/languages".to_string(),
+        ));
+    }
+    if body
+        .configs
+        .iter()
+        .any(|c| c.link_mode.is_some() && c.category != FilterCategory::Links)
+    {
+        return Err(FilterError::Validation(
+            "link_mode only applies to the links category".to_string(),
+        ));
+    }
 
     let configs = filter_queries::upsert_filter_configs(&state.db, guild_id, &body.configs).await?;
 
@@ -130,19 +195,18 @@ async fn update_filter_configs(
     state.filter_cache.invalidate(guild_id);
 
     // Audit log
-    crate::permissions::queries::write_audit_log(
-        &state.db,
+    crate::guild::audit::record(
+        &state,
+        guild_id,
         auth_user.id,
         "guild.filters.updated",
-        Some("guild"),
-        Some(guild_id),
+        Some("filter_config"),
+        None,
         Some(serde_json::json!({
             "categories": body.configs.len(),
         })),
-        None,
     )
-    .await
-    .ok();
+    .await;
 
     Ok(Json(configs))
 }
@@ -246,20 +310,16 @@ pub(crate) async fn create_custom_pattern(
     state.filter_cache.invalidate(guild_id);
 
     // Audit log
-    crate::permissions::queries::write_audit_log(
-        &state.db,
+    crate::guild::audit::record(
+        &state,
+        guild_id,
         auth_user.id,
         "guild.filters.pattern_created",
-        Some("guild"),
-        Some(guild_id),
-        Some(serde_json::json!({
-            "pattern_id": pattern.id,
-            "is_regex": body.is_regex,
-        })),
-        None,
+        Some("filter_pattern"),
+        Some(pattern.id),
+        Some(serde_json::json!({ "is_regex": body.is_regex })),
     )
-    .await
-    .ok();
+    .await;
 
     Ok((StatusCode::CREATED, Json(pattern)))
 }
@@ -407,21 +467,664 @@ pub(crate) async fn delete_custom_pattern(
     state.filter_cache.invalidate(guild_id);
 
     // Audit log
-    crate::permissions::queries::write_audit_log(
-        &state.db,
+    crate::guild::audit::record(
+        &state,
+        guild_id,
         auth_user.id,
         "guild.filters.pattern_deleted",
-        Some("guild"),
-        Some(guild_id),
-        Some(serde_json::json!({ "pattern_id": pattern_id })),
+        Some("filter_pattern"),
+        Some(pattern_id),
+        None,
+    )
+    .await;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// List the link allowlist and denylist.
+///
+/// GET `/api/guilds/{id}/filters/domains`
+#[utoipa::path(
+    get,
+    path = "/api/guilds/{id}/filters/domains",
+    tag = "moderation",
+    params(("id" = Uuid, Path, description = "Guild ID")),
+    responses(
+        (status = 200, description = "Link domains", body = Vec<GuildLinkDomain>),
+        (status = 403, description = "Missing MANAGE_GUILD permission"),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[tracing::instrument(skip(state, auth_user))]
+async fn list_link_domains(
+    State(state): State<AppState>,
+    auth_user: AuthUser,
+    Path(guild_id): Path<Uuid>,
+) -> Result<Json<Vec<GuildLinkDomain>>, FilterError> {
+    require_guild_permission(
+        &state.db,
+        guild_id,
+        auth_user.id,
+        GuildPermissions::MANAGE_GUILD,
+    )
+    .await
+    .map_err(|_| FilterError::Forbidden)?;
+
+    let domains = filter_queries::list_link_domains(&state.db, guild_id).await?;
+    Ok(Json(domains))
+}
+
+/// Add a domain to the link allowlist or denylist.
+///
+/// POST `/api/guilds/{id}/filters/domains`
+#[utoipa::path(
+    post,
+    path = "/api/guilds/{id}/filters/domains",
+    tag = "moderation",
+    params(("id" = Uuid, Path, description = "Guild ID")),
+    request_body = AddLinkDomainRequest,
+    responses(
+        (status = 201, description = "Domain listed", body = GuildLinkDomain),
+        (status = 400, description = "Invalid domain or limit exceeded"),
+        (status = 403, description = "Missing MANAGE_GUILD permission"),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[tracing::instrument(skip(state, auth_user, body))]
+async fn add_link_domain(
+    State(state): State<AppState>,
+    auth_user: AuthUser,
+    Path(guild_id): Path<Uuid>,
+    Json(body): Json<AddLinkDomainRequest>,
+) -> Result<(StatusCode, Json<GuildLinkDomain>), FilterError> {
+    require_guild_permission(
+        &state.db,
+        guild_id,
+        auth_user.id,
+        GuildPermissions::MANAGE_GUILD,
+    )
+    .await
+    .map_err(|_| FilterError::Forbidden)?;
+
+    let domain = links::normalize_domain(&body.domain)
+        .ok_or_else(|| FilterError::Validation("Invalid domain".to_string()))?;
+
+    let count = filter_queries::count_link_domains(&state.db, guild_id).await?;
+    if count >= MAX_LINK_DOMAINS {
+        return Err(FilterError::Validation(format!(
+            "Maximum of {MAX_LINK_DOMAINS} link domains per guild"
+        )));
+    }
+
+    let entry =
+        filter_queries::upsert_link_domain(&state.db, guild_id, &domain, body.list, auth_user.id)
+            .await?;
+
+    // Invalidate cache
+    state.filter_cache.invalidate(guild_id);
+
+    // Audit log
+    crate::guild::audit::record(
+        &state,
+        guild_id,
+        auth_user.id,
+        "guild.filters.link_domain_added",
+        Some("link_domain"),
+        Some(entry.id),
+        Some(serde_json::json!({ "domain": entry.domain, "list": entry.list })),
+    )
+    .await;
+
+    Ok((StatusCode::CREATED, Json(entry)))
+}
+
+/// Remove a domain from the link allowlist or denylist.
+///
+/// DELETE `/api/guilds/{id}/filters/domains/{domain_id}`
+#[utoipa::path(
+    delete,
+    path = "/api/guilds/{id}/filters/domains/{domain_id}",
+    tag = "moderation",
+    params(
+        ("id" = Uuid, Path, description = "Guild ID"),
+        ("domain_id" = Uuid, Path, description = "Link domain ID"),
+    ),
+    responses(
+        (status = 204, description = "Domain removed"),
+        (status = 403, description = "Missing MANAGE_GUILD permission"),
+        (status = 404, description = "Domain not found"),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[tracing::instrument(skip(state, auth_user))]
+async fn delete_link_domain(
+    State(state): State<AppState>,
+    auth_user: AuthUser,
+    Path((guild_id, domain_id)): Path<(Uuid, Uuid)>,
+) -> Result<StatusCode, FilterError> {
+    require_guild_permission(
+        &state.db,
+        guild_id,
+        auth_user.id,
+        GuildPermissions::MANAGE_GUILD,
+    )
+    .await
+    .map_err(|_| FilterError::Forbidden)?;
+
+    let deleted = filter_queries::delete_link_domain(&state.db, guild_id, domain_id).await?;
+    if !deleted {
+        return Err(FilterError::NotFound);
+    }
+
+    // Invalidate cache
+    state.filter_cache.invalidate(guild_id);
+
+    // Audit log
+    crate::guild::audit::record(
+        &state,
+        guild_id,
+        auth_user.id,
+        "guild.filters.link_domain_removed",
+        Some("link_domain"),
+        Some(domain_id),
+        None,
+    )
+    .await;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// List channel language rules for a guild.
+///
+/// GET `/api/guilds/{id}/filters/languages`
+#[utoipa::path(
+    get,
+    path = "/api/guilds/{id}/filters/languages",
+    tag = "moderation",
+    params(("id" = Uuid, Path, description = "Guild ID")),
+    responses(
+        (status = 200, description = "List of language rules", body = Vec<ChannelLanguageRule>),
+        (status = 403, description = "Missing MANAGE_GUILD permission"),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[tracing::instrument(skip(state, auth_user))]
+async fn list_language_rules(
+    State(state): State<AppState>,
+    auth_user: AuthUser,
+    Path(guild_id): Path<Uuid>,
+) -> Result<Json<Vec<ChannelLanguageRule>>, FilterError> {
+    require_guild_permission(
+        &state.db,
+        guild_id,
+        auth_user.id,
+        GuildPermissions::MANAGE_GUILD,
+    )
+    .await
+    .map_err(|_| FilterError::Forbidden)?;
+
+    let rules = filter_queries::list_language_rules(&state.db, guild_id).await?;
+    Ok(Json(rules))
+}
+
+/// Create or replace a channel's language rule.
+///
+/// PUT `/api/guilds/{id}/filters/languages/{channel_id}`
+#[utoipa::path(
+    put,
+    path = "/api/guilds/{id}/filters/languages/{channel_id}",
+    tag = "moderation",
+    params(
+        ("id" = Uuid, Path, description = "Guild ID"),
+        ("channel_id" = Uuid, Path, description = "Channel ID"),
+    ),
+    request_body = SetLanguageRuleRequest,
+    responses(
+        (status = 200, description = "Language rule saved", body = ChannelLanguageRule),
+        (status = 400, description = "Unknown language code or channel not in guild"),
+        (status = 403, description = "Missing MANAGE_GUILD permission"),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[tracing::instrument(skip(state, auth_user, body))]
+async fn set_language_rule(
+    State(state): State<AppState>,
+    auth_user: AuthUser,
+    Path((guild_id, channel_id)): Path<(Uuid, Uuid)>,
+    Json(body): Json<SetLanguageRuleRequest>,
+) -> Result<Json<ChannelLanguageRule>, FilterError> {
+    require_guild_permission(
+        &state.db,
+        guild_id,
+        auth_user.id,
+        GuildPermissions::MANAGE_GUILD,
+    )
+    .await
+    .map_err(|_| FilterError::Forbidden)?;
+
+    let allowed_languages = normalize_language_codes(&body.allowed_languages)?;
+
+    let channel = crate::db::find_channel_by_id(&state.db, channel_id).await?;
+    if channel.and_then(|c| c.guild_id) != Some(guild_id) {
+        return Err(FilterError::Validation(
+            "Channel does not belong to this guild".to_string(),
+        ));
+    }
+
+    let rule = filter_queries::upsert_language_rule(
+        &state.db,
+        guild_id,
+        channel_id,
+        &allowed_languages,
+        body.action,
+        body.enabled,
+    )
+    .await?;
+
+    // Invalidate cache
+    state.filter_cache.invalidate(guild_id);
+
+    // Audit log
+    crate::guild::audit::record(
+        &state,
+        guild_id,
+        auth_user.id,
+        "guild.filters.language_rule_updated",
+        Some("channel"),
+        Some(channel_id),
+        Some(serde_json::json!({
+            "allowed_languages": rule.allowed_languages,
+            "action": rule.action,
+            "enabled": rule.enabled,
+        })),
+    )
+    .await;
+
+    Ok(Json(rule))
+}
+
+/// Delete a channel's language rule.
+///
+/// DELETE `/api/guilds/{id}/filters/languages/{channel_id}`
+#[utoipa::path(
+    delete,
+    path = "/api/guilds/{id}/filters/languages/{channel_id}",
+    tag = "moderation",
+    params(
+        ("id" = Uuid, Path, description = "Guild ID"),
+        ("channel_id" = Uuid, Path, description = "Channel ID"),
+    ),
+    responses(
+        (status = 204, description = "Language rule deleted"),
+        (status = 403, description = "Missing MANAGE_GUILD permission"),
+        (status = 404, description = "No rule for this channel"),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[tracing::instrument(skip(state, auth_user))]
+async fn delete_language_rule(
+    State(state): State<AppState>,
+    auth_user: AuthUser,
+    Path((guild_id, channel_id)): Path<(Uuid, Uuid)>,
+) -> Result<StatusCode, FilterError> {
+    require_guild_permission(
+        &state.db,
+        guild_id,
+        auth_user.id,
+        GuildPermissions::MANAGE_GUILD,
+    )
+    .await
+    .map_err(|_| FilterError::Forbidden)?;
+
+    let deleted = filter_queries::delete_language_rule(&state.db, guild_id, channel_id).await?;
+    if !deleted {
+        return Err(FilterError::NotFound);
+    }
+
+    // Invalidate cache
+    state.filter_cache.invalidate(guild_id);
+
+    // Audit log
+    crate::guild::audit::record(
+        &state,
+        guild_id,
+        auth_user.id,
+        "guild.filters.language_rule_deleted",
+        Some("channel"),
+        Some(channel_id),
+        None,
+    )
+    .await;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Get the guild's obfuscation normalization settings.
+///
+/// GET `/api/guilds/{id}/filters/normalization`
+#[utoipa::path(
+    get,
+    path = "/api/guilds/{id}/filters/normalization",
+    tag = "moderation",
+    params(("id" = Uuid, Path, description = "Guild ID")),
+    responses(
+        (status = 200, description = "Normalization settings", body = FilterNormalization),
+        (status = 403, description = "Missing MANAGE_GUILD permission"),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[tracing::instrument(skip(state, auth_user))]
+async fn get_normalization(
+    State(state): State<AppState>,
+    auth_user: AuthUser,
+    Path(guild_id): Path<Uuid>,
+) -> Result<Json<FilterNormalization>, FilterError> {
+    require_guild_permission(
+        &state.db,
+        guild_id,
+        auth_user.id,
+        GuildPermissions::MANAGE_GUILD,
+    )
+    .await
+    .map_err(|_| FilterError::Forbidden)?;
+
+    let settings = filter_queries::get_normalization(&state.db, guild_id).await?;
+    Ok(Json(settings))
+}
+
+/// Replace the guild's obfuscation normalization settings.
+///
+/// PUT `/api/guilds/{id}/filters/normalization`
+#[utoipa::path(
+    put,
+    path = "/api/guilds/{id}/filters/normalization",
+    tag = "moderation",
+    params(("id" = Uuid, Path, description = "Guild ID")),
+    request_body = FilterNormalization,
+    responses(
+        (status = 200, description = "Normalization settings saved", body = FilterNormalization),
+        (status = 403, description = "Missing MANAGE_GUILD permission"),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[tracing::instrument(skip(state, auth_user, body))]
+async fn update_normalization(
+    State(state): State<AppState>,
+    auth_user: AuthUser,
+    Path(guild_id): Path<Uuid>,
+    Json(body): Json<FilterNormalization>,
+) -> Result<Json<FilterNormalization>, FilterError> {
+    require_guild_permission(
+        &state.db,
+        guild_id,
+        auth_user.id,
+        GuildPermissions::MANAGE_GUILD,
+    )
+    .await
+    .map_err(|_| FilterError::Forbidden)?;
+
+    let settings = filter_queries::set_normalization(&state.db, guild_id, body).await?;
+
+    // Invalidate cache
+    state.filter_cache.invalidate(guild_id);
+
+    // Audit log
+    crate::guild::audit::record(
+        &state,
+        guild_id,
+        auth_user.id,
+        "guild.filters.normalization_updated",
+        Some("filter_config"),
         None,
+        Some(serde_json::json!(settings)),
+    )
+    .await;
+
+    Ok(Json(settings))
+}
+
+/// List guild automod escalation policies.
+///
+/// GET `/api/guilds/{id}/filters/escalations`
+#[utoipa::path(
+    get,
+    path = "/api/guilds/{id}/filters/escalations",
+    tag = "moderation",
+    params(("id" = Uuid, Path, description = "Guild ID")),
+    responses(
+        (status = 200, description = "List of escalation policies", body = Vec<EscalationPolicy>),
+        (status = 403, description = "Missing MANAGE_GUILD permission"),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[tracing::instrument(skip(state, auth_user))]
+async fn list_escalation_policies(
+    State(state): State<AppState>,
+    auth_user: AuthUser,
+    Path(guild_id): Path<Uuid>,
+) -> Result<Json<Vec<EscalationPolicy>>, FilterError> {
+    require_guild_permission(
+        &state.db,
+        guild_id,
+        auth_user.id,
+        GuildPermissions::MANAGE_GUILD,
+    )
+    .await
+    .map_err(|_| FilterError::Forbidden)?;
+
+    let policies = filter_queries::list_escalation_policies(&state.db, guild_id).await?;
+    Ok(Json(policies))
+}
+
+/// Create an automod escalation policy.
+///
+/// POST `/api/guilds/{id}/filters/escalations`
+#[utoipa::path(
+    post,
+    path = "/api/guilds/{id}/filters/escalations",
+    tag = "moderation",
+    params(("id" = Uuid, Path, description = "Guild ID")),
+    request_body = EscalationPolicyRequest,
+    responses(
+        (status = 201, description = "Policy created", body = EscalationPolicy),
+        (status = 400, description = "Validation error or limit exceeded"),
+        (status = 403, description = "Missing MANAGE_GUILD permission"),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[tracing::instrument(skip(state, auth_user, body))]
+async fn create_escalation_policy(
+    State(state): State<AppState>,
+    auth_user: AuthUser,
+    Path(guild_id): Path<Uuid>,
+    Json(body): Json<EscalationPolicyRequest>,
+) -> Result<(StatusCode, Json<EscalationPolicy>), FilterError> {
+    require_guild_permission(
+        &state.db,
+        guild_id,
+        auth_user.id,
+        GuildPermissions::MANAGE_GUILD,
+    )
+    .await
+    .map_err(|_| FilterError::Forbidden)?;
+
+    validate_escalation_policy(&body)?;
+    require_notify_channel(&state, guild_id, body.notify_channel_id).await?;
+
+    let count = filter_queries::count_escalation_policies(&state.db, guild_id).await?;
+    if count >= MAX_ESCALATION_POLICIES {
+        return Err(FilterError::Validation(format!(
+            "Maximum of {MAX_ESCALATION_POLICIES} escalation policies per guild"
+        )));
+    }
+
+    let policy =
+        filter_queries::create_escalation_policy(&state.db, guild_id, &body, auth_user.id).await?;
+
+    // Audit log
+    crate::guild::audit::record(
+        &state,
+        guild_id,
+        auth_user.id,
+        "guild.filters.escalation_created",
+        Some("escalation_policy"),
+        Some(policy.id),
+        Some(escalation_policy_details(&policy)),
+    )
+    .await;
+
+    Ok((StatusCode::CREATED, Json(policy)))
+}
+
+/// Replace an automod escalation policy.
+///
+/// PUT `/api/guilds/{id}/filters/escalations/{policy_id}`
+#[utoipa::path(
+    put,
+    path = "/api/guilds/{id}/filters/escalations/{policy_id}",
+    tag = "moderation",
+    params(
+        ("id" = Uuid, Path, description = "Guild ID"),
+        ("policy_id" = Uuid, Path, description = "Escalation policy ID"),
+    ),
+    request_body = EscalationPolicyRequest,
+    responses(
+        (status = 200, description = "Policy updated", body = EscalationPolicy),
+        (status = 400, description = "Validation error"),
+        (status = 403, description = "Missing MANAGE_GUILD permission"),
+        (status = 404, description = "Policy not found"),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[tracing::instrument(skip(state, auth_user, body))]
+async fn update_escalation_policy(
+    State(state): State<AppState>,
+    auth_user: AuthUser,
+    Path((guild_id, policy_id)): Path<(Uuid, Uuid)>,
+    Json(body): Json<EscalationPolicyRequest>,
+) -> Result<Json<EscalationPolicy>, FilterError> {
+    require_guild_permission(
+        &state.db,
+        guild_id,
+        auth_user.id,
+        GuildPermissions::MANAGE_GUILD,
     )
     .await
-    .ok();
+    .map_err(|_| FilterError::Forbidden)?;
+
+    validate_escalation_policy(&body)?;
+    require_notify_channel(&state, guild_id, body.notify_channel_id).await?;
+
+    let policy = filter_queries::update_escalation_policy(&state.db, guild_id, policy_id, &body)
+        .await?
+        .ok_or(FilterError::NotFound)?;
+
+    // Audit log
+    crate::guild::audit::record(
+        &state,
+        guild_id,
+        auth_user.id,
+        "guild.filters.escalation_updated",
+        Some("escalation_policy"),
+        Some(policy.id),
+        Some(escalation_policy_details(&policy)),
+    )
+    .await;
+
+    Ok(Json(policy))
+}
+
+/// Delete an automod escalation policy.
+///
+/// DELETE `/api/guilds/{id}/filters/escalations/{policy_id}`
+#[utoipa::path(
+    delete,
+    path = "/api/guilds/{id}/filters/escalations/{policy_id}",
+    tag = "moderation",
+    params(
+        ("id" = Uuid, Path, description = "Guild ID"),
+        ("policy_id" = Uuid, Path, description = "Escalation policy ID"),
+    ),
+    responses(
+        (status = 204, description = "Policy deleted"),
+        (status = 403, description = "Missing MANAGE_GUILD permission"),
+        (status = 404, description = "Policy not found"),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[tracing::instrument(skip(state, auth_user))]
+async fn delete_escalation_policy(
+    State(state): State<AppState>,
+    auth_user: AuthUser,
+    Path((guild_id, policy_id)): Path<(Uuid, Uuid)>,
+) -> Result<StatusCode, FilterError> {
+    require_guild_permission(
+        &state.db,
+        guild_id,
+        auth_user.id,
+        GuildPermissions::MANAGE_GUILD,
+    )
+    .await
+    .map_err(|_| FilterError::Forbidden)?;
+
+    let deleted = filter_queries::delete_escalation_policy(&state.db, guild_id, policy_id).await?;
+    if !deleted {
+        return Err(FilterError::NotFound);
+    }
+
+    // Audit log
+    crate::guild::audit::record(
+        &state,
+        guild_id,
+        auth_user.id,
+        "guild.filters.escalation_deleted",
+        Some("escalation_policy"),
+        Some(policy_id),
+        None,
+    )
+    .await;
 
     Ok(StatusCode::NO_CONTENT)
 }
 
+/// Dry-run the escalation policies for a member.
+///
+/// Reports the member's violation count against each policy and which one
+/// would be applied now, without applying it.
+///
+/// POST `/api/guilds/{id}/filters/escalations/evaluate`
+#[utoipa::path(
+    post,
+    path = "/api/guilds/{id}/filters/escalations/evaluate",
+    tag = "moderation",
+    params(("id" = Uuid, Path, description = "Guild ID")),
+    request_body = EvaluateEscalationRequest,
+    responses(
+        (status = 200, description = "Evaluation result", body = EscalationEvaluation),
+        (status = 403, description = "Missing MANAGE_GUILD permission"),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[tracing::instrument(skip(state, auth_user, body))]
+async fn evaluate_escalation(
+    State(state): State<AppState>,
+    auth_user: AuthUser,
+    Path(guild_id): Path<Uuid>,
+    Json(body): Json<EvaluateEscalationRequest>,
+) -> Result<Json<EscalationEvaluation>, FilterError> {
+    require_guild_permission(
+        &state.db,
+        guild_id,
+        auth_user.id,
+        GuildPermissions::MANAGE_GUILD,
+    )
+    .await
+    .map_err(|_| FilterError::Forbidden)?;
+
+    let (evaluation, _) = escalation::evaluate(&state.db, guild_id, body.user_id).await?;
+    Ok(Json(evaluation))
+}
+
 /// List moderation action log for a guild (paginated).
 ///
 /// GET `/api/guilds/{id}/filters/log`
@@ -515,7 +1218,10 @@ async fn test_filter(
         .await
         .map_err(|e| FilterError::Validation(format!("Failed to build filter engine: {e}")))?;
 
-    let result = engine.check(&body.content);
+    let result = match body.channel_id {
+        Some(channel_id) => engine.check_message(&body.content, channel_id),
+        None => engine.check(&body.content),
+    };
 
     Ok(Json(TestFilterResponse {
         blocked: result.blocked,
@@ -535,6 +1241,97 @@ async fn test_filter(
 // Helpers
 // ============================================================================
 
+/// Validate and canonicalize language codes (lowercase ISO 639-3, deduplicated).
+fn normalize_language_codes(codes: &[String]) -> Result<Vec<String>, FilterError> {
+    if codes.is_empty() || codes.len() > MAX_ALLOWED_LANGUAGES {
+        return Err(FilterError::Validation(format!(
+            "Between 1 and {MAX_ALLOWED_LANGUAGES} languages are required"
+        )));
+    }
+
+    let mut normalized: Vec<String> = Vec::with_capacity(codes.len());
+    for code in codes {
+        let lang = language::parse_code(code)
+            .ok_or_else(|| FilterError::Validation(format!("Unknown language code: {code}")))?;
+        let code = lang.code().to_string();
+        if !normalized.contains(&code) {
+            normalized.push(code);
+        }
+    }
+    Ok(normalized)
+}
+
+/// Validate an escalation policy request.
+fn validate_escalation_policy(body: &EscalationPolicyRequest) -> Result<(), FilterError> {
+    let name_length = body.name.trim().chars().count();
+    if name_length == 0 || name_length > MAX_POLICY_NAME_LENGTH {
+        return Err(FilterError::Validation(format!(
+            "Name must be between 1 and {MAX_POLICY_NAME_LENGTH} characters"
+        )));
+    }
+    if !(1..=MAX_ESCALATION_THRESHOLD).contains(&body.threshold) {
+        return Err(FilterError::Validation(format!(
+            "Threshold must be between 1 and {MAX_ESCALATION_THRESHOLD}"
+        )));
+    }
+    if !ESCALATION_WINDOW_SECONDS.contains(&body.window_seconds) {
+        return Err(FilterError::Validation(format!(
+            "window_seconds must be between {} and {}",
+            ESCALATION_WINDOW_SECONDS.start(),
+            ESCALATION_WINDOW_SECONDS.end()
+        )));
+    }
+    match (body.action, body.timeout_seconds) {
+        (EscalationAction::Timeout, Some(seconds))
+            if ESCALATION_TIMEOUT_SECONDS.contains(&seconds) => {}
+        (EscalationAction::Timeout, _) => {
+            return Err(FilterError::Validation(format!(
+                "timeout_seconds must be between {} and {}",
+                ESCALATION_TIMEOUT_SECONDS.start(),
+                ESCALATION_TIMEOUT_SECONDS.end()
+            )));
+        }
+        (_, Some(_)) => {
+            return Err(FilterError::Validation(
+                "timeout_seconds is only valid for the timeout action".to_string(),
+            ));
+        }
+        (_, None) => {}
+    }
+    Ok(())
+}
+
+/// Require the notification channel, if any, to be a text channel of the guild.
+async fn require_notify_channel(
+    state: &AppState,
+    guild_id: Uuid,
+    channel_id: Option<Uuid>,
+) -> Result<(), FilterError> {
+    let Some(channel_id) = channel_id else {
+        return Ok(());
+    };
+    let channel = crate::db::find_channel_by_id(&state.db, channel_id).await?;
+    match channel {
+        Some(c) if c.guild_id == Some(guild_id) && c.channel_type == ChannelType::Text => Ok(()),
+        _ => Err(FilterError::Validation(
+            "Notification channel must be a text channel in this guild".to_string(),
+        )),
+    }
+}
+
+/// Audit log details for an escalation policy.
+fn escalation_policy_details(policy: &EscalationPolicy) -> serde_json::Value {
+    serde_json::json!({
+        "name": policy.name,
+        "enabled": policy.enabled,
+        "threshold": policy.threshold,
+        "window_seconds": policy.window_seconds,
+        "action": policy.action,
+        "timeout_seconds": policy.timeout_seconds,
+        "notify_channel_id": policy.notify_channel_id,
+    })
+}
+
 /// Validate a regex pattern for compilation and `ReDoS` protection.
 fn validate_regex(pattern: &str) -> Result<(), FilterError> {
     // Try to compile
@@ -1,14 +1,16 @@
 //! Filter Database Queries
 //!
 //! All database operations for content filter configuration,
-//! custom patterns, and moderation action logging.
+//! custom patterns, link domains, escalation policies, and moderation
+//! action logging.
 
 use sqlx::PgPool;
 use uuid::Uuid;
 
 use super::filter_types::{
-    FilterAction, FilterCategory, FilterConfigEntry, GuildFilterConfig, GuildFilterPattern,
-    ModerationAction,
+    ChannelLanguageRule, EscalationAction, EscalationPolicy, EscalationPolicyRequest, FilterAction,
+    FilterCategory, FilterConfigEntry, FilterNormalization, GuildFilterConfig, GuildFilterPattern,
+    GuildLinkDomain, LinkDomainList, ModerationAction,
 };
 
 /// Maximum characters of original content stored in moderation log.
@@ -25,7 +27,7 @@ pub async fn list_filter_configs(
     guild_id: Uuid,
 ) -> sqlx::Result<Vec<GuildFilterConfig>> {
     sqlx::query_as::<_, GuildFilterConfig>(
-        "SELECT id, guild_id, category, enabled, action, created_at, updated_at
+        "SELECT id, guild_id, category, enabled, action, link_mode, created_at, updated_at
          FROM guild_filter_configs
          WHERE guild_id = $1
          ORDER BY category",
@@ -36,6 +38,9 @@ pub async fn list_filter_configs(
 }
 
 /// Upsert filter configs for a guild (batch, transactional).
+///
+/// The `links` category defaults to `invites` mode; other categories never
+/// store a link mode.
 #[tracing::instrument(skip(pool, configs))]
 pub async fn upsert_filter_configs(
     pool: &PgPool,
@@ -46,17 +51,20 @@ pub async fn upsert_filter_configs(
     let mut results = Vec::new();
 
     for entry in configs {
+        let link_mode =
+            (entry.category == FilterCategory::Links).then(|| entry.link_mode.unwrap_or_default());
         let row = sqlx::query_as::<_, GuildFilterConfig>(
-            "INSERT INTO guild_filter_configs (guild_id, category, enabled, action, updated_at)
-             VALUES ($1, $2, $3, $4, NOW())
+            "INSERT INTO guild_filter_configs (guild_id, category, enabled, action, link_mode, updated_at)
+             VALUES ($1, $2, $3, $4, $5, NOW())
              ON CONFLICT (guild_id, category)
-             DO UPDATE SET enabled = $3, action = $4, updated_at = NOW()
-             RETURNING id, guild_id, category, enabled, action, created_at, updated_at",
+             DO UPDATE SET enabled = $3, action = $4, link_mode = $5, updated_at = NOW()
+             RETURNING id, guild_id, category, enabled, action, link_mode, created_at, updated_at",
         )
         .bind(guild_id)
         .bind(entry.category)
         .bind(entry.enabled)
         .bind(entry.action)
+        .bind(link_mode)
         .fetch_one(&mut *tx)
         .await?;
 
@@ -188,6 +196,352 @@ pub async fn delete_custom_pattern(
     Ok(result.rows_affected() > 0)
 }
 
+// ============================================================================
+// Channel Language Rule Queries
+// ============================================================================
+
+/// List all channel language rules for a guild.
+#[tracing::instrument(skip(pool))]
+pub async fn list_language_rules(
+    pool: &PgPool,
+    guild_id: Uuid,
+) -> sqlx::Result<Vec<ChannelLanguageRule>> {
+    sqlx::query_as::<_, ChannelLanguageRule>(
+        "SELECT id, guild_id, channel_id, allowed_languages, action, enabled, created_at, updated_at
+         FROM guild_channel_language_rules
+         WHERE guild_id = $1
+         ORDER BY created_at",
+    )
+    .bind(guild_id)
+    .fetch_all(pool)
+    .await
+}
+
+/// Create or replace the language rule for a channel.
+#[tracing::instrument(skip(pool))]
+pub async fn upsert_language_rule(
+    pool: &PgPool,
+    guild_id: Uuid,
+    channel_id: Uuid,
+    allowed_languages: &[String],
+    action: FilterAction,
+    enabled: bool,
+) -> sqlx::Result<ChannelLanguageRule> {
+    sqlx::query_as::<_, ChannelLanguageRule>(
+        "INSERT INTO guild_channel_language_rules (guild_id, channel_id, allowed_languages, action, enabled)
+         VALUES ($1, $2, $3, $4, $5)
+         ON CONFLICT (channel_id)
+         DO UPDATE SET allowed_languages = $3, action = $4, enabled = $5, updated_at = NOW()
+         RETURNING id, guild_id, channel_id, allowed_languages, action, enabled, created_at, updated_at",
+    )
+    .bind(guild_id)
+    .bind(channel_id)
+    .bind(allowed_languages)
+    .bind(action)
+    .bind(enabled)
+    .fetch_one(pool)
+    .await
+}
+
+/// Delete a channel's language rule. Returns true if deleted.
+#[tracing::instrument(skip(pool))]
+pub async fn delete_language_rule(
+    pool: &PgPool,
+    guild_id: Uuid,
+    channel_id: Uuid,
+) -> sqlx::Result<bool> {
+    let result = sqlx::query(
+        "DELETE FROM guild_channel_language_rules WHERE guild_id = $1 AND channel_id = $2",
+    )
+    .bind(guild_id)
+    .bind(channel_id)
+    .execute(pool)
+    .await?;
+    Ok(result.rows_affected() > 0)
+}
+
+// ============================================================================
+// Link Domain Queries
+// ============================================================================
+
+/// List the link allowlist and denylist of a guild.
+#[tracing::instrument(skip(pool))]
+pub async fn list_link_domains(
+    pool: &PgPool,
+    guild_id: Uuid,
+) -> sqlx::Result<Vec<GuildLinkDomain>> {
+    sqlx::query_as::<_, GuildLinkDomain>(
+        "SELECT id, guild_id, domain, list, created_by, created_at
+         FROM guild_filter_link_domains
+         WHERE guild_id = $1
+         ORDER BY list, domain",
+    )
+    .bind(guild_id)
+    .fetch_all(pool)
+    .await
+}
+
+/// Count link domains for a guild.
+#[tracing::instrument(skip(pool))]
+pub async fn count_link_domains(pool: &PgPool, guild_id: Uuid) -> sqlx::Result<i64> {
+    let row: (i64,) =
+        sqlx::query_as("SELECT COUNT(*) FROM guild_filter_link_domains WHERE guild_id = $1")
+            .bind(guild_id)
+            .fetch_one(pool)
+            .await?;
+    Ok(row.0)
+}
+
+/// Add a domain to a list, moving it if it is already on the other one.
+#[tracing::instrument(skip(pool))]
+pub async fn upsert_link_domain(
+    pool: &PgPool,
+    guild_id: Uuid,
+    domain: &str,
+    list: LinkDomainList,
+    created_by: Uuid,
+) -> sqlx::Result<GuildLinkDomain> {
+    sqlx::query_as::<_, GuildLinkDomain>(
+        "INSERT INTO guild_filter_link_domains (guild_id, domain, list, created_by)
+         VALUES ($1, $2, $3, $4)
+         ON CONFLICT (guild_id, domain)
+         DO UPDATE SET list = $3
+         RETURNING id, guild_id, domain, list, created_by, created_at",
+    )
+    .bind(guild_id)
+    .bind(domain)
+    .bind(list)
+    .bind(created_by)
+    .fetch_one(pool)
+    .await
+}
+
+/// Delete a link domain. Returns true if deleted.
+#[tracing::instrument(skip(pool))]
+pub async fn delete_link_domain(
+    pool: &PgPool,
+    guild_id: Uuid,
+    domain_id: Uuid,
+) -> sqlx::Result<bool> {
+    let result =
+        sqlx::query("DELETE FROM guild_filter_link_domains WHERE id = $1 AND guild_id = $2")
+            .bind(domain_id)
+            .bind(guild_id)
+            .execute(pool)
+            .await?;
+    Ok(result.rows_affected() > 0)
+}
+
+/// List the guild's own invite codes, which the link filter never treats
+/// as external invites.
+#[tracing::instrument(skip(pool))]
+pub async fn list_invite_codes(pool: &PgPool, guild_id: Uuid) -> sqlx::Result<Vec<String>> {
+    sqlx::query_scalar("SELECT code FROM guild_invites WHERE guild_id = $1")
+        .bind(guild_id)
+        .fetch_all(pool)
+        .await
+}
+
+// ============================================================================
+// Normalization Settings Queries
+// ============================================================================
+
+/// Get a guild's obfuscation normalization settings (all off if never set).
+#[tracing::instrument(skip(pool))]
+pub async fn get_normalization(pool: &PgPool, guild_id: Uuid) -> sqlx::Result<FilterNormalization> {
+    let settings = sqlx::query_as::<_, FilterNormalization>(
+        "SELECT strip_invisible, fold_confusables, substitute_leetspeak
+         FROM guild_filter_settings
+         WHERE guild_id = $1",
+    )
+    .bind(guild_id)
+    .fetch_optional(pool)
+    .await?;
+    Ok(settings.unwrap_or_default())
+}
+
+/// Replace a guild's obfuscation normalization settings.
+#[tracing::instrument(skip(pool))]
+pub async fn set_normalization(
+    pool: &PgPool,
+    guild_id: Uuid,
+    settings: FilterNormalization,
+) -> sqlx::Result<FilterNormalization> {
+    sqlx::query_as::<_, FilterNormalization>(
+        "INSERT INTO guild_filter_settings (guild_id, strip_invisible, fold_confusables, substitute_leetspeak)
+         VALUES ($1, $2, $3, $4)
+         ON CONFLICT (guild_id)
+         DO UPDATE SET strip_invisible = $2, fold_confusables = $3, substitute_leetspeak = $4, updated_at = NOW()
+         RETURNING strip_invisible, fold_confusables, substitute_leetspeak",
+    )
+    .bind(guild_id)
+    .bind(settings.strip_invisible)
+    .bind(settings.fold_confusables)
+    .bind(settings.substitute_leetspeak)
+    .fetch_one(pool)
+    .await
+}
+
+// ============================================================================
+// Escalation Policy Queries
+// ============================================================================
+
+const ESCALATION_POLICY_COLUMNS: &str = "id, guild_id, name, enabled, threshold, window_seconds, action, timeout_seconds, notify_channel_id, created_by, created_at, updated_at";
+
+/// List a guild's escalation policies, lowest threshold first.
+#[tracing::instrument(skip(pool))]
+pub async fn list_escalation_policies(
+    pool: &PgPool,
+    guild_id: Uuid,
+) -> sqlx::Result<Vec<EscalationPolicy>> {
+    sqlx::query_as::<_, EscalationPolicy>(&format!(
+        "SELECT {ESCALATION_POLICY_COLUMNS}
+         FROM guild_escalation_policies
+         WHERE guild_id = $1
+         ORDER BY threshold, created_at"
+    ))
+    .bind(guild_id)
+    .fetch_all(pool)
+    .await
+}
+
+/// Count escalation policies for a guild.
+#[tracing::instrument(skip(pool))]
+pub async fn count_escalation_policies(pool: &PgPool, guild_id: Uuid) -> sqlx::Result<i64> {
+    let row: (i64,) =
+        sqlx::query_as("SELECT COUNT(*) FROM guild_escalation_policies WHERE guild_id = $1")
+            .bind(guild_id)
+            .fetch_one(pool)
+            .await?;
+    Ok(row.0)
+}
+
+/// Create an escalation policy.
+#[tracing::instrument(skip(pool, body))]
+pub async fn create_escalation_policy(
+    pool: &PgPool,
+    guild_id: Uuid,
+    body: &EscalationPolicyRequest,
+    created_by: Uuid,
+) -> sqlx::Result<EscalationPolicy> {
+    sqlx::query_as::<_, EscalationPolicy>(&format!(
+        "INSERT INTO guild_escalation_policies
+             (guild_id, name, enabled, threshold, window_seconds, action, timeout_seconds, notify_channel_id, created_by)
+         VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+         RETURNING {ESCALATION_POLICY_COLUMNS}"
+    ))
+    .bind(guild_id)
+    .bind(body.name.trim())
+    .bind(body.enabled)
+    .bind(body.threshold)
+    .bind(body.window_seconds)
+    .bind(body.action)
+    .bind(body.timeout_seconds)
+    .bind(body.notify_channel_id)
+    .bind(created_by)
+    .fetch_one(pool)
+    .await
+}
+
+/// Replace an escalation policy. Returns `None` if not found.
+#[tracing::instrument(skip(pool, body))]
+pub async fn update_escalation_policy(
+    pool: &PgPool,
+    guild_id: Uuid,
+    policy_id: Uuid,
+    body: &EscalationPolicyRequest,
+) -> sqlx::Result<Option<EscalationPolicy>> {
+    sqlx::query_as::<_, EscalationPolicy>(&format!(
+        "UPDATE guild_escalation_policies
+         SET name = $3, enabled = $4, threshold = $5, window_seconds = $6, action = $7,
+             timeout_seconds = $8, notify_channel_id = $9, updated_at = NOW()
+         WHERE id = $1 AND guild_id = $2
+         RETURNING {ESCALATION_POLICY_COLUMNS}"
+    ))
+    .bind(policy_id)
+    .bind(guild_id)
+    .bind(body.name.trim())
+    .bind(body.enabled)
+    .bind(body.threshold)
+    .bind(body.window_seconds)
+    .bind(body.action)
+    .bind(body.timeout_seconds)
+    .bind(body.notify_channel_id)
+    .fetch_optional(pool)
+    .await
+}
+
+/// Delete an escalation policy. Returns true if deleted.
+#[tracing::instrument(skip(pool))]
+pub async fn delete_escalation_policy(
+    pool: &PgPool,
+    guild_id: Uuid,
+    policy_id: Uuid,
+) -> sqlx::Result<bool> {
+    let result =
+        sqlx::query("DELETE FROM guild_escalation_policies WHERE id = $1 AND guild_id = $2")
+            .bind(policy_id)
+            .bind(guild_id)
+            .execute(pool)
+            .await?;
+    Ok(result.rows_affected() > 0)
+}
+
+/// Count a member's logged filter matches that a policy has not acted on yet.
+///
+/// Only matches within the policy window and after the policy last fired for
+/// the member are counted.
+#[tracing::instrument(skip(pool, policy), fields(policy_id = %policy.id))]
+pub async fn count_policy_violations(
+    pool: &PgPool,
+    policy: &EscalationPolicy,
+    user_id: Uuid,
+) -> sqlx::Result<i64> {
+    let row: (i64,) = sqlx::query_as(
+        "SELECT COUNT(*) FROM moderation_actions
+         WHERE guild_id = $1 AND user_id = $2
+           AND created_at > GREATEST(
+               NOW() - make_interval(secs => $3),
+               COALESCE(
+                   (SELECT MAX(created_at) FROM moderation_escalations
+                    WHERE policy_id = $4 AND user_id = $2),
+                   '-infinity'
+               )
+           )",
+    )
+    .bind(policy.guild_id)
+    .bind(user_id)
+    .bind(f64::from(policy.window_seconds))
+    .bind(policy.id)
+    .fetch_one(pool)
+    .await?;
+    Ok(row.0)
+}
+
+/// Record that a policy acted on a member.
+#[tracing::instrument(skip(pool))]
+pub async fn record_escalation(
+    pool: &PgPool,
+    guild_id: Uuid,
+    user_id: Uuid,
+    policy_id: Uuid,
+    action: EscalationAction,
+    violation_count: i64,
+) -> sqlx::Result<()> {
+    sqlx::query(
+        "INSERT INTO moderation_escalations (guild_id, user_id, policy_id, action, violation_count)
+         VALUES ($1, $2, $3, $4, $5)",
+    )
+    .bind(guild_id)
+    .bind(user_id)
+    .bind(policy_id)
+    .bind(action)
+    .bind(i32::try_from(violation_count).unwrap_or(i32::MAX))
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
 // ============================================================================
 // Moderation Action Log Queries
 // ============================================================================
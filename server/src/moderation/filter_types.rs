@@ -26,6 +26,10 @@ pub enum FilterCategory {
     Spam,
     AbusiveLanguage,
     Custom,
+    /// Per-channel language rules (not toggled through category configs).
+    Language,
+    /// Links and invites, matched according to the config's `link_mode`.
+    Links,
 }
 
 impl std::fmt::Display for FilterCategory {
@@ -36,6 +40,8 @@ impl std::fmt::Display for FilterCategory {
             Self::Spam => write!(f, "spam"),
             Self::AbusiveLanguage => write!(f, "abusive_language"),
             Self::Custom => write!(f, "custom"),
+            Self::Language => write!(f, "language"),
+            Self::Links => write!(f, "links"),
         }
     }
 }
@@ -62,6 +68,60 @@ impl std::fmt::Display for FilterAction {
     }
 }
 
+/// Which links the `links` category matches.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, sqlx::Type, utoipa::ToSchema,
+)]
+#[sqlx(type_name = "link_filter_mode", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum LinkFilterMode {
+    /// Every link.
+    All,
+    /// Invite links to other guilds or chat platforms.
+    #[default]
+    Invites,
+    /// Links to domains outside the guild's allowlist.
+    Allowlist,
+}
+
+/// List a link filter domain belongs to.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type, utoipa::ToSchema,
+)]
+#[sqlx(type_name = "link_domain_list", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum LinkDomainList {
+    Allow,
+    Deny,
+}
+
+/// What an escalation policy does once its threshold is reached.
+///
+/// Variants are ordered by severity.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Serialize,
+    Deserialize,
+    sqlx::Type,
+    utoipa::ToSchema,
+)]
+#[sqlx(type_name = "escalation_action", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum EscalationAction {
+    /// Notify moderators only.
+    Warn,
+    /// Time the member out for the policy's `timeout_seconds`.
+    Timeout,
+    /// Ban the member from the guild.
+    Ban,
+}
+
 // ============================================================================
 // Database Models
 // ============================================================================
@@ -74,6 +134,8 @@ pub struct GuildFilterConfig {
     pub category: FilterCategory,
     pub enabled: bool,
     pub action: FilterAction,
+    /// Only set for the `links` category.
+    pub link_mode: Option<LinkFilterMode>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -92,6 +154,84 @@ pub struct GuildFilterPattern {
     pub updated_at: DateTime<Utc>,
 }
 
+/// Channel language rule row.
+#[derive(Debug, Clone, sqlx::FromRow, Serialize, utoipa::ToSchema)]
+pub struct ChannelLanguageRule {
+    pub id: Uuid,
+    pub guild_id: Uuid,
+    pub channel_id: Uuid,
+    /// ISO 639-3 codes (e.g. `eng`, `deu`).
+    pub allowed_languages: Vec<String>,
+    pub action: FilterAction,
+    pub enabled: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Obfuscation normalization stages applied before keyword matching.
+///
+/// Guilds without a settings row have every stage off.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    PartialEq,
+    Eq,
+    sqlx::FromRow,
+    Serialize,
+    Deserialize,
+    utoipa::ToSchema,
+)]
+pub struct FilterNormalization {
+    /// Drop zero-width characters and diacritics (`bádword` → `badword`).
+    pub strip_invisible: bool,
+    /// Fold look-alike letters from other scripts and styled Unicode
+    /// (Cyrillic `а`, fullwidth `ｂ`, `𝐛`) to ASCII.
+    pub fold_confusables: bool,
+    /// Map leetspeak digits and symbols to letters (`b@dw0rd` → `badword`).
+    pub substitute_leetspeak: bool,
+}
+
+impl FilterNormalization {
+    /// Returns true if any stage is enabled.
+    pub const fn is_enabled(self) -> bool {
+        self.strip_invisible || self.fold_confusables || self.substitute_leetspeak
+    }
+}
+
+/// Link filter allowlist/denylist entry.
+#[derive(Debug, Clone, sqlx::FromRow, Serialize, utoipa::ToSchema)]
+pub struct GuildLinkDomain {
+    pub id: Uuid,
+    pub guild_id: Uuid,
+    /// Lowercase host; also covers its subdomains.
+    pub domain: String,
+    pub list: LinkDomainList,
+    pub created_by: Uuid,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Automod escalation policy row.
+#[derive(Debug, Clone, sqlx::FromRow, Serialize, utoipa::ToSchema)]
+pub struct EscalationPolicy {
+    pub id: Uuid,
+    pub guild_id: Uuid,
+    pub name: String,
+    pub enabled: bool,
+    /// Filter violations needed within the window.
+    pub threshold: i32,
+    pub window_seconds: i32,
+    pub action: EscalationAction,
+    /// Only set for the `timeout` action.
+    pub timeout_seconds: Option<i32>,
+    /// Channel that receives a notice when the policy fires.
+    pub notify_channel_id: Option<Uuid>,
+    pub created_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
 /// Moderation action log entry.
 #[derive(Debug, Clone, sqlx::FromRow, Serialize, utoipa::ToSchema)]
 pub struct ModerationAction {
@@ -117,6 +257,9 @@ pub struct FilterConfigEntry {
     pub category: FilterCategory,
     pub enabled: bool,
     pub action: FilterAction,
+    /// Only valid for the `links` category (default `invites`).
+    #[serde(default)]
+    pub link_mode: Option<LinkFilterMode>,
 }
 
 /// Request to update guild filter configs (bulk upsert).
@@ -162,10 +305,64 @@ where
     Option::<String>::deserialize(deserializer).map(Some)
 }
 
+/// Request to add a domain to the link allowlist or denylist.
+///
+/// Adding a domain that is already listed moves it to `list`.
+#[derive(Debug, Deserialize, utoipa::ToSchema)]
+pub struct AddLinkDomainRequest {
+    /// Host name; a scheme, `www.` prefix or path is stripped.
+    pub domain: String,
+    pub list: LinkDomainList,
+}
+
+/// Request to create or replace a channel's language rule.
+#[derive(Debug, Deserialize, utoipa::ToSchema)]
+pub struct SetLanguageRuleRequest {
+    /// ISO 639-3 codes (e.g. `eng`, `deu`).
+    pub allowed_languages: Vec<String>,
+    #[serde(default = "default_language_action")]
+    pub action: FilterAction,
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+}
+
+const fn default_language_action() -> FilterAction {
+    FilterAction::Warn
+}
+
+const fn default_true() -> bool {
+    true
+}
+
+/// Request to create or replace an escalation policy.
+#[derive(Debug, Deserialize, utoipa::ToSchema)]
+pub struct EscalationPolicyRequest {
+    pub name: String,
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    pub threshold: i32,
+    pub window_seconds: i32,
+    pub action: EscalationAction,
+    /// Required for the `timeout` action, rejected otherwise.
+    #[serde(default)]
+    pub timeout_seconds: Option<i32>,
+    #[serde(default)]
+    pub notify_channel_id: Option<Uuid>,
+}
+
+/// Request to dry-run the escalation policies for a member.
+#[derive(Debug, Deserialize, utoipa::ToSchema)]
+pub struct EvaluateEscalationRequest {
+    pub user_id: Uuid,
+}
+
 /// Request to test content against active filters.
 #[derive(Debug, Deserialize, utoipa::ToSchema)]
 pub struct TestFilterRequest {
     pub content: String,
+    /// Also apply this channel's language rule.
+    #[serde(default)]
+    pub channel_id: Option<Uuid>,
 }
 
 /// Pagination query parameters.
@@ -209,6 +406,36 @@ pub struct FilterMatchResponse {
     pub matched_pattern: String,
 }
 
+/// Dry-run result of the escalation policies for a member.
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct EscalationEvaluation {
+    pub user_id: Uuid,
+    /// Owners and moderators are never escalated.
+    pub exempt: bool,
+    pub policies: Vec<PolicyEvaluation>,
+    /// Policy that would be applied now, if any.
+    pub triggered_policy_id: Option<Uuid>,
+}
+
+/// Violation count of a member against one policy.
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct PolicyEvaluation {
+    pub policy_id: Uuid,
+    pub name: String,
+    pub enabled: bool,
+    pub action: EscalationAction,
+    pub threshold: i32,
+    /// Violations within the window since the policy last fired for the member.
+    pub violations: i64,
+}
+
+impl PolicyEvaluation {
+    /// Returns true if the policy is enabled and its threshold is reached.
+    pub fn reached(&self) -> bool {
+        self.enabled && self.violations >= i64::from(self.threshold)
+    }
+}
+
 // ============================================================================
 // Internal Types
 // ============================================================================
@@ -5,6 +5,7 @@ use axum::Json;
 use fred::prelude::*;
 use validator::Validate;
 
+use super::evidence;
 use super::types::{CreateReportRequest, Report, ReportError, ReportResponse};
 use crate::api::AppState;
 use crate::auth::AuthUser;
@@ -107,6 +108,13 @@ pub async fn create_report(
         ReportError::Database(e)
     })?;
 
+    // Snapshot the reported message so it survives edits and deletion
+    if let Some(message_id) = report.target_message_id {
+        if let Err(e) = evidence::capture_message(&state.db, report.id, message_id, None).await {
+            tracing::warn!(report_id = %report.id, error = %e, "Failed to capture report evidence");
+        }
+    }
+
     // Broadcast to admin events channel
     let event = ServerEvent::AdminReportCreated {
         report_id: report.id,
@@ -0,0 +1,79 @@
+//! Message Language Detection
+//!
+//! Thin wrapper around `whatlang` for channel language rules. Links,
+//! mentions and custom emoji are stripped first, and short or ambiguous
+//! messages yield no result so they never trip a rule.
+
+use whatlang::Lang;
+
+/// Minimum alphabetic characters before detection is attempted.
+const MIN_DETECTION_CHARS: usize = 20;
+
+/// Parse an ISO 639-3 language code (case-insensitive).
+pub fn parse_code(code: &str) -> Option<Lang> {
+    Lang::from_code(code.trim().to_ascii_lowercase())
+}
+
+/// Whether a token carries no language signal (links, mentions, emoji).
+fn is_noise(token: &str) -> bool {
+    token.starts_with("http://")
+        || token.starts_with("https://")
+        || token.starts_with("www.")
+        || token.starts_with('@')
+        || token.starts_with('#')
+        || token.starts_with('<')
+        || (token.len() > 2 && token.starts_with(':') && token.ends_with(':'))
+}
+
+/// Detect the language of a message.
+///
+/// Returns `None` when the text is too short or `whatlang` is not confident.
+pub fn detect(content: &str) -> Option<Lang> {
+    let text: Vec<&str> = content
+        .split_whitespace()
+        .filter(|token| !is_noise(token))
+        .collect();
+    let text = text.join(" ");
+
+    if text.chars().filter(|c| c.is_alphabetic()).count() < MIN_DETECTION_CHARS {
+        return None;
+    }
+
+    whatlang::detect(&text)
+        .filter(whatlang::Info::is_reliable)
+        .map(|info| info.lang())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_codes() {
+        assert_eq!(parse_code("eng"), Some(Lang::Eng));
+        assert_eq!(parse_code(" DEU "), Some(Lang::Deu));
+        assert_eq!(parse_code("en"), None);
+        assert_eq!(parse_code("xyz"), None);
+    }
+
+    #[test]
+    fn detects_long_messages() {
+        assert_eq!(
+            detect("The quick brown fox jumps over the lazy dog while everyone watches"),
+            Some(Lang::Eng)
+        );
+        assert_eq!(
+            detect("Der schnelle braune Fuchs springt über den faulen Hund und alle schauen zu"),
+            Some(Lang::Deu)
+        );
+    }
+
+    #[test]
+    fn ignores_short_and_noisy_messages() {
+        assert_eq!(detect("ok"), None);
+        assert_eq!(
+            detect("lol :smile: <@123> https://example.com/a/very/long/path"),
+            None
+        );
+    }
+}
@@ -0,0 +1,202 @@
+//! Link Extraction
+//!
+//! Finds links in message content for the `links` filter category. Links
+//! with a scheme or a `www.` prefix are explicit; bare `host.tld/path`
+//! mentions are only considered for invite and denylist checks, so file
+//! names and abbreviations never trip the `all` or `allowlist` modes.
+
+use std::sync::LazyLock;
+
+use regex::Regex;
+
+/// Longest host name accepted for the allowlist and denylist.
+const MAX_DOMAIN_LENGTH: usize = 253;
+
+static LINK_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r#"(?i)\b(https?://)?((?:[a-z0-9](?:[a-z0-9-]{0,61}[a-z0-9])?\.)+[a-z]{2,63})(?::\d{1,5})?(/[^\s<>"]*)?"#,
+    )
+    .unwrap()
+});
+
+static DOMAIN_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^(?:[a-z0-9](?:[a-z0-9-]{0,61}[a-z0-9])?\.)+[a-z]{2,63}$").unwrap()
+});
+
+/// Chat platforms whose invite links count as external invites, with the
+/// path prefix their invites use.
+const INVITE_HOSTS: &[(&str, &str)] = &[
+    ("discord.gg", "/"),
+    ("discord.com", "/invite/"),
+    ("discordapp.com", "/invite/"),
+    ("discord.me", "/"),
+    ("t.me", "/joinchat/"),
+    ("t.me", "/+"),
+    ("telegram.me", "/joinchat/"),
+    ("chat.whatsapp.com", "/"),
+    ("guilded.gg", "/i/"),
+    ("rvlt.gg", "/"),
+    ("revolt.chat", "/invite/"),
+    ("matrix.to", "/#/"),
+];
+
+/// A link found in message content.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Link {
+    /// Lowercase host without a `www.` prefix.
+    pub host: String,
+    /// Path including the leading `/`, trailing punctuation trimmed.
+    pub path: String,
+    /// Written with a scheme or `www.` prefix.
+    pub explicit: bool,
+}
+
+/// Where an invite link points.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Invite<'a> {
+    /// A guild invite (`/invite/{code}`) on a Kaiku instance.
+    Guild(&'a str),
+    /// An invite to another chat platform.
+    Platform,
+}
+
+/// Extract all links from message content.
+pub fn extract(content: &str) -> Vec<Link> {
+    LINK_REGEX
+        .captures_iter(content)
+        .filter_map(|caps| {
+            let host = caps.get(2)?.as_str().to_ascii_lowercase();
+            let explicit = caps.get(1).is_some() || host.starts_with("www.");
+            let host = host.strip_prefix("www.").unwrap_or(&host).to_string();
+            let path = caps.get(3).map_or("", |m| {
+                m.as_str()
+                    .trim_end_matches(['.', ',', ';', ':', '!', '?', ')', ']', '}', '\''])
+            });
+            Some(Link {
+                host,
+                path: path.to_string(),
+                explicit,
+            })
+        })
+        .collect()
+}
+
+/// Canonicalize a domain for the allowlist or denylist.
+///
+/// Accepts a bare host or a URL; returns `None` if no valid host remains.
+pub fn normalize_domain(raw: &str) -> Option<String> {
+    let raw = raw.trim().to_ascii_lowercase();
+    let raw = raw
+        .strip_prefix("https://")
+        .or_else(|| raw.strip_prefix("http://"))
+        .unwrap_or(&raw);
+    let host = raw.split(['/', '?', '#', ':']).next()?;
+    let host = host.strip_prefix("www.").unwrap_or(host);
+    let host = host.strip_suffix('.').unwrap_or(host);
+    (host.len() <= MAX_DOMAIN_LENGTH && DOMAIN_REGEX.is_match(host)).then(|| host.to_string())
+}
+
+/// Whether `host` is `domain` or one of its subdomains.
+pub fn host_matches(host: &str, domain: &str) -> bool {
+    host.strip_suffix(domain)
+        .is_some_and(|rest| rest.is_empty() || rest.ends_with('.'))
+}
+
+impl Link {
+    /// Classify the link as an invite, if it is one.
+    pub fn invite(&self) -> Option<Invite<'_>> {
+        let platform = INVITE_HOSTS.iter().any(|(host, prefix)| {
+            self.host == *host
+                && self
+                    .path
+                    .strip_prefix(prefix)
+                    .is_some_and(|code| !code.is_empty())
+        });
+        if platform {
+            return Some(Invite::Platform);
+        }
+
+        let code = self.path.strip_prefix("/invite/")?;
+        let code = code.split(['/', '?', '#']).next().unwrap_or_default();
+        (!code.is_empty()).then_some(Invite::Guild(code))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hosts(content: &str) -> Vec<(String, bool)> {
+        extract(content)
+            .into_iter()
+            .map(|link| (link.host, link.explicit))
+            .collect()
+    }
+
+    #[test]
+    fn extracts_links() {
+        assert_eq!(
+            hosts("see https://Example.com/a and www.foo.org, or bar.net/x."),
+            vec![
+                ("example.com".to_string(), true),
+                ("foo.org".to_string(), true),
+                ("bar.net".to_string(), false),
+            ]
+        );
+        assert_eq!(
+            extract("(https://example.com/path).")[0].path,
+            "/path".to_string()
+        );
+        assert!(extract("no links here, e.g. v1.2 or 3.14").is_empty());
+    }
+
+    #[test]
+    fn normalizes_domains() {
+        assert_eq!(
+            normalize_domain("Example.COM").as_deref(),
+            Some("example.com")
+        );
+        assert_eq!(
+            normalize_domain("https://www.example.com/path?q=1").as_deref(),
+            Some("example.com")
+        );
+        assert_eq!(
+            normalize_domain("example.com:8080").as_deref(),
+            Some("example.com")
+        );
+        assert_eq!(normalize_domain("localhost"), None);
+        assert_eq!(normalize_domain("exa mple.com"), None);
+        assert_eq!(normalize_domain(""), None);
+    }
+
+    #[test]
+    fn matches_subdomains() {
+        assert!(host_matches("example.com", "example.com"));
+        assert!(host_matches("cdn.example.com", "example.com"));
+        assert!(!host_matches("badexample.com", "example.com"));
+        assert!(!host_matches("example.com.evil.net", "example.com"));
+    }
+
+    #[test]
+    fn classifies_invites() {
+        let invite = |content: &str| {
+            extract(content)
+                .into_iter()
+                .next()
+                .and_then(|link| link.invite().map(|i| format!("{i:?}")))
+        };
+        assert_eq!(invite("discord.gg/abc").as_deref(), Some("Platform"));
+        assert_eq!(
+            invite("https://discord.com/invite/abc").as_deref(),
+            Some("Platform")
+        );
+        assert_eq!(invite("https://t.me/+abc").as_deref(), Some("Platform"));
+        assert_eq!(
+            invite("https://chat.example.org/invite/aBcD1234?x=1").as_deref(),
+            Some("Guild(\"aBcD1234\")")
+        );
+        assert_eq!(invite("https://discord.com/channels/1/2"), None);
+        assert_eq!(invite("https://discord.gg/"), None);
+        assert_eq!(invite("https://example.com/about"), None);
+    }
+}
@@ -1,13 +1,19 @@
 //! Moderation Module
 //!
-//! User reporting, admin report queue management, and content filtering.
+//! User reporting, admin report queue management, content filtering, and
+//! automod escalation.
 
 pub mod admin_handlers;
 pub mod defaults;
+pub mod escalation;
+pub mod evidence;
 pub mod filter_cache;
 pub mod filter_engine;
 pub mod filter_handlers;
 pub mod filter_queries;
 pub mod filter_types;
 pub mod handlers;
+pub mod language;
+pub mod links;
+pub mod obfuscation;
 pub mod types;
@@ -0,0 +1,260 @@
+//! Obfuscation Normalization
+//!
+//! Folds disguised text before keyword matching so "b@dw0rd", "bаdword"
+//! (Cyrillic `а`) or "bad\u{200B}word" still hit a `badword` filter. The
+//! same folding is applied to keywords and message content, so keywords
+//! containing digits or accents keep matching their own spelling.
+//!
+//! Stages run per character in a fixed order: invisible and combining marks
+//! are dropped, confusables are folded to ASCII, diacritics are stripped,
+//! then leetspeak is substituted. Each stage is toggled per guild through
+//! [`FilterNormalization`]. Regex patterns always see the raw content.
+
+use super::filter_types::FilterNormalization;
+
+/// Fold already lowercased text with the enabled stages.
+pub fn fold(text: &str, normalization: FilterNormalization) -> String {
+    text.chars()
+        .filter(|&c| !(normalization.strip_invisible && is_invisible(c)))
+        .map(|mut c| {
+            if normalization.fold_confusables {
+                c = fold_confusable(c);
+            }
+            if normalization.strip_invisible {
+                c = strip_diacritic(c);
+            }
+            if normalization.substitute_leetspeak {
+                c = substitute_leetspeak(c);
+            }
+            c
+        })
+        .collect()
+}
+
+/// Zero-width, formatting and combining characters that render as nothing
+/// (or only decorate the previous character).
+const fn is_invisible(c: char) -> bool {
+    matches!(
+        c,
+        '\u{00AD}'
+            | '\u{034F}'
+            | '\u{061C}'
+            | '\u{115F}'
+            | '\u{1160}'
+            | '\u{17B4}'
+            | '\u{17B5}'
+            | '\u{180E}'
+            | '\u{200B}'..='\u{200F}'
+            | '\u{202A}'..='\u{202E}'
+            | '\u{2060}'..='\u{206F}'
+            | '\u{3164}'
+            | '\u{FE00}'..='\u{FE0F}'
+            | '\u{FEFF}'
+            | '\u{FFA0}'
+            | '\u{E0000}'..='\u{E007F}'
+            // Combining diacritical marks
+            | '\u{0300}'..='\u{036F}'
+            | '\u{1AB0}'..='\u{1AFF}'
+            | '\u{1DC0}'..='\u{1DFF}'
+            | '\u{20D0}'..='\u{20FF}'
+            | '\u{FE20}'..='\u{FE2F}'
+    )
+}
+
+/// Fold a look-alike character from another script or a styled Unicode
+/// block to its lowercase ASCII counterpart.
+fn fold_confusable(c: char) -> char {
+    let code = u32::from(c);
+    let offset =
+        |base: u32, first: char| char::from_u32(u32::from(first) + (code - base)).unwrap_or(c);
+    match c {
+        // Fullwidth ASCII
+        '\u{FF01}'..='\u{FF5E}' => offset(0xFF01, '!').to_ascii_lowercase(),
+        // Circled letters
+        '\u{24B6}'..='\u{24CF}' => offset(0x24B6, 'a'),
+        '\u{24D0}'..='\u{24E9}' => offset(0x24D0, 'a'),
+        // Mathematical alphanumeric letters: repeating A-Z, a-z styles
+        '\u{1D400}'..='\u{1D6A3}' => {
+            let index = (code - 0x1D400) % 52 % 26;
+            char::from_u32(u32::from('a') + index).unwrap_or(c)
+        }
+        // Mathematical digits: repeating 0-9 styles
+        '\u{1D7CE}'..='\u{1D7FF}' => {
+            char::from_u32(u32::from('0') + (code - 0x1D7CE) % 10).unwrap_or(c)
+        }
+        // Cyrillic
+        'а' => 'a',
+        'в' | 'ь' => 'b',
+        'с' => 'c',
+        'ԁ' => 'd',
+        'е' => 'e',
+        'һ' | 'н' => 'h',
+        'і' => 'i',
+        'ј' => 'j',
+        'к' => 'k',
+        'ӏ' => 'l',
+        'м' => 'm',
+        'о' => 'o',
+        'р' => 'p',
+        'ԛ' => 'q',
+        'ѕ' => 's',
+        'т' => 't',
+        'у' => 'y',
+        'ԝ' => 'w',
+        'х' => 'x',
+        // Greek
+        'α' => 'a',
+        'β' => 'b',
+        'ϲ' => 'c',
+        'ε' => 'e',
+        'ι' => 'i',
+        'κ' => 'k',
+        'η' => 'n',
+        'ο' => 'o',
+        'ρ' => 'p',
+        'τ' => 't',
+        'υ' => 'u',
+        'ν' => 'v',
+        'ω' => 'w',
+        'χ' => 'x',
+        'ζ' => 'z',
+        // Latin variants and small capitals
+        'ɑ' | 'ᴀ' => 'a',
+        'ʙ' => 'b',
+        'ᴄ' => 'c',
+        'ᴅ' => 'd',
+        'ᴇ' => 'e',
+        'ɡ' | 'ɢ' => 'g',
+        'ʜ' => 'h',
+        'ı' | 'ɪ' => 'i',
+        'ᴊ' => 'j',
+        'ᴋ' => 'k',
+        'ʟ' => 'l',
+        'ᴍ' => 'm',
+        'ɴ' => 'n',
+        'ᴏ' => 'o',
+        'ᴘ' => 'p',
+        'ʀ' => 'r',
+        'ſ' | 'ꜱ' => 's',
+        'ᴛ' => 't',
+        'ᴜ' => 'u',
+        'ᴠ' => 'v',
+        'ᴡ' => 'w',
+        'ʏ' => 'y',
+        'ᴢ' => 'z',
+        _ => c,
+    }
+}
+
+/// Strip the accent from a precomposed lowercase Latin letter.
+const fn strip_diacritic(c: char) -> char {
+    match c {
+        'à'..='å' | 'ā' | 'ă' | 'ą' => 'a',
+        'ç' | 'ć' | 'ĉ' | 'ċ' | 'č' => 'c',
+        'ď' | 'đ' => 'd',
+        'è'..='ë' | 'ē' | 'ĕ' | 'ė' | 'ę' | 'ě' => 'e',
+        'ĝ' | 'ğ' | 'ġ' | 'ģ' => 'g',
+        'ĥ' | 'ħ' => 'h',
+        'ì'..='ï' | 'ĩ' | 'ī' | 'ĭ' | 'į' => 'i',
+        'ĵ' => 'j',
+        'ķ' => 'k',
+        'ĺ' | 'ļ' | 'ľ' | 'ŀ' | 'ł' => 'l',
+        'ñ' | 'ń' | 'ņ' | 'ň' => 'n',
+        'ò'..='ö' | 'ø' | 'ō' | 'ŏ' | 'ő' => 'o',
+        'ŕ' | 'ŗ' | 'ř' => 'r',
+        'ś' | 'ŝ' | 'ş' | 'š' => 's',
+        'ţ' | 'ť' | 'ŧ' => 't',
+        'ù'..='ü' | 'ũ' | 'ū' | 'ŭ' | 'ů' | 'ű' | 'ų' => 'u',
+        'ŵ' => 'w',
+        'ý' | 'ÿ' | 'ŷ' => 'y',
+        'ź' | 'ż' | 'ž' => 'z',
+        _ => c,
+    }
+}
+
+/// Map leetspeak digits and symbols to letters.
+///
+/// `1`, `!`, `|` and `l` all collapse to `i`, so "k1ll", "ki||" and "kill"
+/// fold to the same string whichever letter was meant.
+const fn substitute_leetspeak(c: char) -> char {
+    match c {
+        '0' => 'o',
+        '1' | '!' | '|' | 'l' => 'i',
+        '2' => 'z',
+        '3' | '€' => 'e',
+        '4' | '@' => 'a',
+        '5' | '$' => 's',
+        '7' | '+' => 't',
+        '8' => 'b',
+        '9' => 'g',
+        _ => c,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALL: FilterNormalization = FilterNormalization {
+        strip_invisible: true,
+        fold_confusables: true,
+        substitute_leetspeak: true,
+    };
+
+    #[test]
+    fn disabled_stages_leave_text_alone() {
+        let text = "b@dw0rd bаd\u{200B}wörd";
+        assert_eq!(fold(text, FilterNormalization::default()), text);
+    }
+
+    #[test]
+    fn strips_zero_width_and_diacritics() {
+        let strip = FilterNormalization {
+            strip_invisible: true,
+            ..FilterNormalization::default()
+        };
+        assert_eq!(fold("bad\u{200B}wo\u{200D}rd", strip), "badword");
+        assert_eq!(fold("bad\u{FEFF}word\u{00AD}", strip), "badword");
+        assert_eq!(fold("bádwörd", strip), "badword");
+        assert_eq!(fold("bade\u{0301}word", strip), "badeword");
+        // Other stages stay off.
+        assert_eq!(fold("b@dw0rd", strip), "b@dw0rd");
+    }
+
+    #[test]
+    fn folds_confusables() {
+        let confusables = FilterNormalization {
+            fold_confusables: true,
+            ..FilterNormalization::default()
+        };
+        // Cyrillic а and о
+        assert_eq!(fold("bаdwоrd", confusables), "badword");
+        // Fullwidth
+        assert_eq!(fold("ｂａｄｗｏｒｄ", confusables), "badword");
+        // Mathematical bold and double-struck
+        assert_eq!(fold("𝐛𝐚𝐝𝕨𝕠𝕣𝕕", confusables), "badword");
+        // Circled and small capitals
+        assert_eq!(fold("ⓑⓐⓓᴡᴏʀᴅ", confusables), "badword");
+        // Greek omicron
+        assert_eq!(fold("badwοrd", confusables), "badword");
+    }
+
+    #[test]
+    fn substitutes_leetspeak() {
+        let leet = FilterNormalization {
+            substitute_leetspeak: true,
+            ..FilterNormalization::default()
+        };
+        assert_eq!(fold("b@dw0rd", leet), "badword");
+        assert_eq!(fold("8ad", leet), "bad");
+        assert_eq!(fold("$p4m", leet), "spam");
+        assert_eq!(fold("k1ll", leet), fold("kill", leet));
+        assert_eq!(fold("ki||", leet), fold("kill", leet));
+    }
+
+    #[test]
+    fn combined_stages() {
+        assert_eq!(fold("ｂ@d\u{200B}wö𝐫d", ALL), "badword");
+        assert_eq!(fold("bаd\u{2060}w0rd", ALL), "badword");
+    }
+}
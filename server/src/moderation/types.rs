@@ -68,6 +68,26 @@ pub struct ResolveReportRequest {
     pub resolution_note: Option<String>,
 }
 
+/// Query for releasing a claim.
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+pub struct UnclaimReportQuery {
+    /// Release another admin's claim.
+    #[serde(default)]
+    pub force: bool,
+}
+
+#[derive(Debug, Deserialize, Validate, utoipa::ToSchema)]
+pub struct CreateReportNoteRequest {
+    #[validate(length(min = 1, max = 2000, message = "Note must be 1-2000 characters"))]
+    pub body: String,
+}
+
+#[derive(Debug, Deserialize, utoipa::ToSchema)]
+pub struct AddReportEvidenceRequest {
+    /// Message to snapshot. Must not be deleted yet.
+    pub message_id: Uuid,
+}
+
 #[derive(Debug, Deserialize, utoipa::ToSchema, utoipa::IntoParams)]
 pub struct ListReportsQuery {
     pub status: Option<ReportStatus>,
@@ -97,6 +117,10 @@ pub struct Report {
     pub description: Option<String>,
     pub status: ReportStatus,
     pub assigned_admin_id: Option<Uuid>,
+    /// When the current claim was taken.
+    pub claimed_at: Option<DateTime<Utc>>,
+    /// Admin who resolved or dismissed the report.
+    pub resolved_by: Option<Uuid>,
     pub resolution_action: Option<String>,
     pub resolution_note: Option<String>,
     pub resolved_at: Option<DateTime<Utc>>,
@@ -115,6 +139,10 @@ pub struct ReportResponse {
     pub description: Option<String>,
     pub status: ReportStatus,
     pub assigned_admin_id: Option<Uuid>,
+    /// When the current claim was taken.
+    pub claimed_at: Option<DateTime<Utc>>,
+    /// Admin who resolved or dismissed the report.
+    pub resolved_by: Option<Uuid>,
     pub resolution_action: Option<String>,
     pub resolution_note: Option<String>,
     pub resolved_at: Option<DateTime<Utc>>,
@@ -134,6 +162,8 @@ impl From<Report> for ReportResponse {
             description: r.description,
             status: r.status,
             assigned_admin_id: r.assigned_admin_id,
+            claimed_at: r.claimed_at,
+            resolved_by: r.resolved_by,
             resolution_action: r.resolution_action,
             resolution_note: r.resolution_note,
             resolved_at: r.resolved_at,
@@ -151,6 +181,41 @@ pub struct ReportStatsResponse {
     pub dismissed: i64,
 }
 
+/// An internal admin note on a report.
+#[derive(Debug, Serialize, sqlx::FromRow, utoipa::ToSchema)]
+pub struct ReportNote {
+    pub id: Uuid,
+    pub report_id: Uuid,
+    /// `None` if the author's account was deleted.
+    pub author_id: Option<Uuid>,
+    pub author_username: Option<String>,
+    pub body: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// A message snapshot linked to a report.
+#[derive(Debug, Serialize, sqlx::FromRow, utoipa::ToSchema)]
+pub struct ReportEvidence {
+    pub id: Uuid,
+    pub report_id: Uuid,
+    /// `None` once the message was removed from the database.
+    pub message_id: Option<Uuid>,
+    pub channel_id: Uuid,
+    pub author_id: Option<Uuid>,
+    pub author_username: Option<String>,
+    /// Content at capture time (ciphertext if `encrypted`).
+    pub content: String,
+    pub encrypted: bool,
+    /// Attachment metadata: `[{id, filename, mime_type, size_bytes}]`.
+    #[schema(value_type = Vec<Object>)]
+    pub attachments: serde_json::Value,
+    pub message_created_at: DateTime<Utc>,
+    pub edited_at: Option<DateTime<Utc>>,
+    /// Admin who linked the message (`None` = captured with the report).
+    pub added_by: Option<Uuid>,
+    pub captured_at: DateTime<Utc>,
+}
+
 #[derive(Debug, Serialize, utoipa::ToSchema)]
 pub struct PaginatedReports {
     pub items: Vec<ReportResponse>,
@@ -179,6 +244,12 @@ pub enum ReportError {
 
     #[error("Duplicate report: you already have an active report for this target")]
     Duplicate,
+
+    #[error("Report is claimed by another admin")]
+    ClaimedByOther,
+
+    #[error("{0}")]
+    Conflict(String),
 }
 
 impl IntoResponse for ReportError {
@@ -200,6 +271,8 @@ impl IntoResponse for ReportError {
                 self.to_string(),
             ),
             Self::Duplicate => (StatusCode::CONFLICT, "DUPLICATE_REPORT", self.to_string()),
+            Self::ClaimedByOther => (StatusCode::CONFLICT, "REPORT_CLAIMED", self.to_string()),
+            Self::Conflict(msg) => (StatusCode::CONFLICT, "CONFLICT", msg.clone()),
         };
 
         (
@@ -0,0 +1,272 @@
+//! Daily capacity reports.
+//!
+//! A nightly job summarises each UTC day into one `capacity_reports` row so
+//! operators can see growth trends and plan hardware before hitting limits:
+//!
+//! - daily active users (users that logged in or refreshed a session)
+//! - peak concurrent WebSocket connections and voice sessions (from the
+//!   native `telemetry_metric_samples` gauges)
+//! - stored attachment and recording bytes, and their growth over the day
+//! - database size
+//!
+//! Reports are served by `GET /api/admin/reports/capacity` as JSON, CSV or
+//! `OpenMetrics` text (for backfilling into Prometheus).
+
+use std::fmt::Write;
+use std::time::Duration;
+
+use chrono::{NaiveDate, NaiveTime, Utc};
+use serde::Serialize;
+use sqlx::PgPool;
+
+/// One day of capacity figures.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, sqlx::FromRow, utoipa::ToSchema)]
+pub struct CapacityReport {
+    /// UTC day the figures cover.
+    pub report_date: NaiveDate,
+    /// Distinct users that logged in or refreshed a session.
+    pub daily_active_users: i64,
+    /// Highest sampled number of open WebSocket connections.
+    pub peak_ws_connections: i64,
+    /// Highest sampled number of active voice sessions.
+    pub peak_voice_sessions: i64,
+    /// Total stored attachment and recording bytes at the end of the day.
+    pub storage_bytes: i64,
+    /// Attachment and recording bytes added during the day.
+    pub storage_growth_bytes: i64,
+    /// Database size in bytes when the report was generated.
+    pub db_size_bytes: i64,
+}
+
+/// Start the nightly capacity report job.
+///
+/// Checks hourly and generates the report for the previous UTC day once it
+/// is missing, so a restart around midnight never skips a day.
+pub fn spawn_capacity_report_task(pool: PgPool) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(3600));
+        loop {
+            interval.tick().await;
+            let yesterday = Utc::now().date_naive() - chrono::Duration::days(1);
+            match generate_report(&pool, yesterday).await {
+                Ok(true) => tracing::info!(date = %yesterday, "Generated capacity report"),
+                Ok(false) => {}
+                Err(e) => {
+                    tracing::warn!(error = %e, date = %yesterday, "Failed to generate capacity report");
+                }
+            }
+        }
+    })
+}
+
+/// Generate the report for `date` unless it already exists.
+///
+/// Returns `true` if a new report was written.
+pub async fn generate_report(pool: &PgPool, date: NaiveDate) -> Result<bool, sqlx::Error> {
+    let exists: bool =
+        sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM capacity_reports WHERE report_date = $1)")
+            .bind(date)
+            .fetch_one(pool)
+            .await?;
+    if exists {
+        return Ok(false);
+    }
+
+    let from = date.and_time(NaiveTime::MIN).and_utc();
+    let to = from + chrono::Duration::days(1);
+
+    let daily_active_users: i64 = sqlx::query_scalar(
+        "SELECT COUNT(DISTINCT user_id) FROM sessions WHERE created_at >= $1 AND created_at < $2",
+    )
+    .bind(from)
+    .bind(to)
+    .fetch_one(pool)
+    .await?;
+
+    let peak_gauge = |metric: &'static str| {
+        sqlx::query_scalar::<_, Option<i64>>(
+            "SELECT MAX(value_count) FROM telemetry_metric_samples \
+             WHERE metric_name = $1 AND ts >= $2 AND ts < $3",
+        )
+        .bind(metric)
+        .bind(from)
+        .bind(to)
+        .fetch_one(pool)
+    };
+    let peak_ws_connections = peak_gauge("kaiku_ws_connections_active")
+        .await?
+        .unwrap_or(0);
+    let peak_voice_sessions = peak_gauge("kaiku_voice_sessions_active")
+        .await?
+        .unwrap_or(0);
+
+    // (total up to end of day, added during the day)
+    let (storage_bytes, storage_growth_bytes): (i64, i64) = sqlx::query_as(
+        r"SELECT COALESCE(SUM(size_bytes), 0)::BIGINT,
+                 COALESCE(SUM(size_bytes) FILTER (WHERE created_at >= $1), 0)::BIGINT
+          FROM (
+              SELECT size_bytes, created_at FROM file_attachments WHERE created_at < $2
+              UNION ALL
+              SELECT size_bytes, started_at FROM voice_recordings
+              WHERE started_at < $2 AND size_bytes IS NOT NULL
+          ) stored",
+    )
+    .bind(from)
+    .bind(to)
+    .fetch_one(pool)
+    .await?;
+
+    let db_size_bytes: i64 = sqlx::query_scalar("SELECT pg_database_size(current_database())")
+        .fetch_one(pool)
+        .await?;
+
+    let inserted = sqlx::query(
+        r"INSERT INTO capacity_reports
+              (report_date, daily_active_users, peak_ws_connections, peak_voice_sessions,
+               storage_bytes, storage_growth_bytes, db_size_bytes)
+          VALUES ($1, $2, $3, $4, $5, $6, $7)
+          ON CONFLICT (report_date) DO NOTHING",
+    )
+    .bind(date)
+    .bind(daily_active_users)
+    .bind(peak_ws_connections)
+    .bind(peak_voice_sessions)
+    .bind(storage_bytes)
+    .bind(storage_growth_bytes)
+    .bind(db_size_bytes)
+    .execute(pool)
+    .await?;
+
+    Ok(inserted.rows_affected() > 0)
+}
+
+/// Load the reports of the last `days` days, oldest first.
+pub async fn list_reports(pool: &PgPool, days: i64) -> Result<Vec<CapacityReport>, sqlx::Error> {
+    let since = Utc::now().date_naive() - chrono::Duration::days(days);
+    sqlx::query_as::<_, CapacityReport>(
+        r"SELECT report_date, daily_active_users, peak_ws_connections, peak_voice_sessions,
+                 storage_bytes, storage_growth_bytes, db_size_bytes
+          FROM capacity_reports
+          WHERE report_date >= $1
+          ORDER BY report_date ASC",
+    )
+    .bind(since)
+    .fetch_all(pool)
+    .await
+}
+
+/// Render reports as CSV.
+#[must_use]
+pub fn to_csv(reports: &[CapacityReport]) -> String {
+    let mut csv = String::from(
+        "date,daily_active_users,peak_ws_connections,peak_voice_sessions,storage_bytes,storage_growth_bytes,db_size_bytes\n",
+    );
+    for r in reports {
+        writeln!(
+            csv,
+            "{},{},{},{},{},{},{}",
+            r.report_date,
+            r.daily_active_users,
+            r.peak_ws_connections,
+            r.peak_voice_sessions,
+            r.storage_bytes,
+            r.storage_growth_bytes,
+            r.db_size_bytes
+        )
+        .expect("write to String is infallible");
+    }
+    csv
+}
+
+/// Render reports in the `OpenMetrics` text format.
+///
+/// Each figure becomes a gauge family with one timestamped point per day
+/// (end of the reported day), which `promtool tsdb create-blocks-from
+/// openmetrics` can import.
+#[must_use]
+pub fn to_openmetrics(reports: &[CapacityReport]) -> String {
+    type Field = fn(&CapacityReport) -> i64;
+    let families: [(&str, &str, Field); 6] = [
+        (
+            "kaiku_capacity_daily_active_users",
+            "Distinct users that logged in or refreshed a session during the day.",
+            |r| r.daily_active_users,
+        ),
+        (
+            "kaiku_capacity_peak_ws_connections",
+            "Peak concurrent WebSocket connections during the day.",
+            |r| r.peak_ws_connections,
+        ),
+        (
+            "kaiku_capacity_peak_voice_sessions",
+            "Peak concurrent voice sessions during the day.",
+            |r| r.peak_voice_sessions,
+        ),
+        (
+            "kaiku_capacity_storage_bytes",
+            "Stored attachment and recording bytes.",
+            |r| r.storage_bytes,
+        ),
+        (
+            "kaiku_capacity_storage_growth_bytes",
+            "Attachment and recording bytes added during the day.",
+            |r| r.storage_growth_bytes,
+        ),
+        (
+            "kaiku_capacity_db_size_bytes",
+            "Database size in bytes.",
+            |r| r.db_size_bytes,
+        ),
+    ];
+
+    let mut out = String::new();
+    for (name, help, field) in families {
+        writeln!(out, "# TYPE {name} gauge").expect("write to String is infallible");
+        writeln!(out, "# HELP {name} {help}").expect("write to String is infallible");
+        for r in reports {
+            let ts = (r.report_date + chrono::Duration::days(1))
+                .and_time(NaiveTime::MIN)
+                .and_utc()
+                .timestamp();
+            writeln!(out, "{name} {} {ts}", field(r)).expect("write to String is infallible");
+        }
+    }
+    out.push_str("# EOF\n");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report(day: u32) -> CapacityReport {
+        CapacityReport {
+            report_date: NaiveDate::from_ymd_opt(2026, 3, day).unwrap(),
+            daily_active_users: 42,
+            peak_ws_connections: 30,
+            peak_voice_sessions: 5,
+            storage_bytes: 1_000,
+            storage_growth_bytes: 100,
+            db_size_bytes: 50_000,
+        }
+    }
+
+    #[test]
+    fn csv_has_header_and_row_per_day() {
+        let csv = to_csv(&[report(1), report(2)]);
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].starts_with("date,daily_active_users,"));
+        assert_eq!(lines[1], "2026-03-01,42,30,5,1000,100,50000");
+    }
+
+    #[test]
+    fn openmetrics_is_terminated_and_timestamped() {
+        let text = to_openmetrics(&[report(1)]);
+        assert!(text.ends_with("# EOF\n"));
+        assert!(text.contains("# TYPE kaiku_capacity_daily_active_users gauge\n"));
+        // 2026-03-02T00:00:00Z, the end of the reported day
+        assert!(text.contains("kaiku_capacity_daily_active_users 42 1772409600\n"));
+        assert_eq!(text.matches("# TYPE ").count(), 6);
+    }
+}
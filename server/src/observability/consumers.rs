@@ -0,0 +1,330 @@
+//! Per-consumer usage accounting.
+//!
+//! Counts authenticated API requests and WebSocket events per user and per
+//! guild so the Command Center can point at the heaviest consumers (runaway
+//! bots, abusive integrations). User and guild IDs are far too high-cardinality
+//! for metric labels, so counts accumulate in memory and are flushed every
+//! minute into hourly buckets in `telemetry_consumer_usage`.
+//!
+//! Requests and events are attributed to a guild when they address one
+//! directly (`/api/guilds/{id}/...`) or through one of its channels
+//! (`/api/channels/{id}/...`, channel-scoped WebSocket events); channels are
+//! resolved to guilds in bulk at flush time. Voice minutes come from
+//! `connection_sessions` at query time.
+
+use std::collections::HashMap;
+use std::sync::LazyLock;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use dashmap::DashMap;
+use serde::Serialize;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// How often pending counts are written to the database.
+const FLUSH_INTERVAL: Duration = Duration::from_secs(60);
+
+/// What a pending count is attributed to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum UsageKey {
+    User(Uuid),
+    Guild(Uuid),
+    /// Resolved to its guild (if any) at flush time.
+    Channel(Uuid),
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct Counts {
+    http_requests: i64,
+    ws_events: i64,
+}
+
+impl Counts {
+    fn add(&mut self, other: Self) {
+        self.http_requests += other.http_requests;
+        self.ws_events += other.ws_events;
+    }
+}
+
+static PENDING: LazyLock<DashMap<UsageKey, Counts>> = LazyLock::new(DashMap::new);
+
+fn bump(key: UsageKey, counts: Counts) {
+    PENDING.entry(key).or_default().add(counts);
+}
+
+/// Extract the guild or channel an API path addresses.
+fn scope_from_path(path: &str) -> Option<UsageKey> {
+    let mut segments = path.trim_start_matches('/').split('/');
+    if segments.next() != Some("api") {
+        return None;
+    }
+    let kind = segments.next()?;
+    let id = Uuid::parse_str(segments.next()?).ok()?;
+    match kind {
+        "guilds" => Some(UsageKey::Guild(id)),
+        "channels" => Some(UsageKey::Channel(id)),
+        _ => None,
+    }
+}
+
+/// Count an authenticated API request.
+pub fn record_http_request(user_id: Uuid, path: &str) {
+    let counts = Counts {
+        http_requests: 1,
+        ws_events: 0,
+    };
+    bump(UsageKey::User(user_id), counts);
+    if let Some(scope) = scope_from_path(path) {
+        bump(scope, counts);
+    }
+}
+
+/// Count a WebSocket event received from a user or bot.
+pub fn record_ws_event(user_id: Uuid, channel_id: Option<Uuid>) {
+    let counts = Counts {
+        http_requests: 0,
+        ws_events: 1,
+    };
+    bump(UsageKey::User(user_id), counts);
+    if let Some(channel_id) = channel_id {
+        bump(UsageKey::Channel(channel_id), counts);
+    }
+}
+
+/// Write pending counts into the current hourly bucket.
+///
+/// Counts recorded while the flush runs stay pending for the next one, and
+/// drained counts are put back if the write fails.
+pub async fn flush_consumer_usage(pool: &PgPool) -> Result<(), sqlx::Error> {
+    let keys: Vec<UsageKey> = PENDING.iter().map(|e| *e.key()).collect();
+    if keys.is_empty() {
+        return Ok(());
+    }
+    let drained: Vec<(UsageKey, Counts)> = keys
+        .into_iter()
+        .filter_map(|k| PENDING.remove(&k))
+        .collect();
+
+    if let Err(e) = write_usage(pool, &drained).await {
+        for (key, counts) in drained {
+            bump(key, counts);
+        }
+        return Err(e);
+    }
+    Ok(())
+}
+
+async fn write_usage(pool: &PgPool, drained: &[(UsageKey, Counts)]) -> Result<(), sqlx::Error> {
+    let mut users: HashMap<Uuid, Counts> = HashMap::new();
+    let mut guilds: HashMap<Uuid, Counts> = HashMap::new();
+    let mut channels: HashMap<Uuid, Counts> = HashMap::new();
+    for &(key, counts) in drained {
+        match key {
+            UsageKey::User(id) => users.entry(id).or_default().add(counts),
+            UsageKey::Guild(id) => guilds.entry(id).or_default().add(counts),
+            UsageKey::Channel(id) => channels.entry(id).or_default().add(counts),
+        }
+    }
+
+    if !channels.is_empty() {
+        let channel_ids: Vec<Uuid> = channels.keys().copied().collect();
+        let channel_guilds: Vec<(Uuid, Uuid)> = sqlx::query_as(
+            "SELECT id, guild_id FROM channels WHERE id = ANY($1) AND guild_id IS NOT NULL",
+        )
+        .bind(&channel_ids)
+        .fetch_all(pool)
+        .await?;
+        for (channel_id, guild_id) in channel_guilds {
+            if let Some(counts) = channels.get(&channel_id) {
+                guilds.entry(guild_id).or_default().add(*counts);
+            }
+        }
+    }
+
+    // One transaction, so a retry after a failure never double-counts
+    let mut tx = pool.begin().await?;
+    upsert_usage(&mut tx, "user", &users).await?;
+    upsert_usage(&mut tx, "guild", &guilds).await?;
+    tx.commit().await
+}
+
+async fn upsert_usage(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    consumer_type: &str,
+    usage: &HashMap<Uuid, Counts>,
+) -> Result<(), sqlx::Error> {
+    if usage.is_empty() {
+        return Ok(());
+    }
+    let ids: Vec<Uuid> = usage.keys().copied().collect();
+    let http: Vec<i64> = ids.iter().map(|id| usage[id].http_requests).collect();
+    let ws: Vec<i64> = ids.iter().map(|id| usage[id].ws_events).collect();
+
+    sqlx::query(
+        r"INSERT INTO telemetry_consumer_usage
+              (bucket, consumer_type, consumer_id, http_requests, ws_events)
+          SELECT date_trunc('hour', NOW()), $1, t.id, t.http, t.ws
+          FROM UNNEST($2::uuid[], $3::bigint[], $4::bigint[]) AS t(id, http, ws)
+          ON CONFLICT (bucket, consumer_type, consumer_id) DO UPDATE
+          SET http_requests = telemetry_consumer_usage.http_requests + EXCLUDED.http_requests,
+              ws_events = telemetry_consumer_usage.ws_events + EXCLUDED.ws_events",
+    )
+    .bind(consumer_type)
+    .bind(&ids)
+    .bind(&http)
+    .bind(&ws)
+    .execute(&mut **tx)
+    .await?;
+    Ok(())
+}
+
+/// Start the background task that flushes usage counts every minute.
+pub fn spawn_consumer_usage_flusher(pool: PgPool) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(FLUSH_INTERVAL);
+        interval.tick().await; // nothing to flush yet
+        loop {
+            interval.tick().await;
+            if let Err(e) = flush_consumer_usage(&pool).await {
+                tracing::warn!(error = %e, "Failed to flush consumer usage");
+            }
+        }
+    })
+}
+
+// ============================================================================
+// Queries
+// ============================================================================
+
+/// Kind of consumer to rank.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConsumerType {
+    User,
+    Guild,
+}
+
+/// Ranking column for top consumer queries.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConsumerSort {
+    Requests,
+    WsEvents,
+    VoiceMinutes,
+}
+
+/// One consumer's usage over a time range.
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+pub struct TopConsumerEntry {
+    pub id: Uuid,
+    /// Username or guild name (`None` if it has since been deleted).
+    pub name: Option<String>,
+    pub http_requests: i64,
+    pub ws_events: i64,
+    pub voice_minutes: f64,
+}
+
+/// Rank users or guilds by API requests, WebSocket events, or voice minutes.
+#[tracing::instrument(skip(pool))]
+pub async fn query_top_consumers(
+    pool: &PgPool,
+    consumer_type: ConsumerType,
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+    sort: ConsumerSort,
+    limit: i64,
+) -> Result<Vec<TopConsumerEntry>, sqlx::Error> {
+    // Static query fragments only; nothing user-supplied is interpolated.
+    const USER_SQL: &str = r"
+        WITH usage AS (
+            SELECT consumer_id AS id, SUM(http_requests)::BIGINT AS http_requests,
+                   SUM(ws_events)::BIGINT AS ws_events
+            FROM telemetry_consumer_usage
+            WHERE consumer_type = 'user' AND bucket >= date_trunc('hour', $1) AND bucket < $2
+            GROUP BY consumer_id
+        ), voice AS (
+            SELECT user_id AS id,
+                   SUM(EXTRACT(EPOCH FROM LEAST(ended_at, $2) - GREATEST(started_at, $1))) / 60
+                       AS voice_minutes
+            FROM connection_sessions
+            WHERE started_at < $2 AND ended_at > $1
+            GROUP BY user_id
+        )
+        SELECT COALESCE(usage.id, voice.id) AS id, u.username AS name,
+               COALESCE(usage.http_requests, 0) AS http_requests,
+               COALESCE(usage.ws_events, 0) AS ws_events,
+               COALESCE(voice.voice_minutes, 0)::DOUBLE PRECISION AS voice_minutes
+        FROM usage
+        FULL OUTER JOIN voice ON voice.id = usage.id
+        LEFT JOIN users u ON u.id = COALESCE(usage.id, voice.id)";
+    const GUILD_SQL: &str = r"
+        WITH usage AS (
+            SELECT consumer_id AS id, SUM(http_requests)::BIGINT AS http_requests,
+                   SUM(ws_events)::BIGINT AS ws_events
+            FROM telemetry_consumer_usage
+            WHERE consumer_type = 'guild' AND bucket >= date_trunc('hour', $1) AND bucket < $2
+            GROUP BY consumer_id
+        ), voice AS (
+            SELECT guild_id AS id,
+                   SUM(EXTRACT(EPOCH FROM LEAST(ended_at, $2) - GREATEST(started_at, $1))) / 60
+                       AS voice_minutes
+            FROM connection_sessions
+            WHERE guild_id IS NOT NULL AND started_at < $2 AND ended_at > $1
+            GROUP BY guild_id
+        )
+        SELECT COALESCE(usage.id, voice.id) AS id, g.name AS name,
+               COALESCE(usage.http_requests, 0) AS http_requests,
+               COALESCE(usage.ws_events, 0) AS ws_events,
+               COALESCE(voice.voice_minutes, 0)::DOUBLE PRECISION AS voice_minutes
+        FROM usage
+        FULL OUTER JOIN voice ON voice.id = usage.id
+        LEFT JOIN guilds g ON g.id = COALESCE(usage.id, voice.id)";
+    const ORDER_BY_REQUESTS: &str =
+        " ORDER BY http_requests DESC, ws_events DESC, voice_minutes DESC LIMIT $3";
+    const ORDER_BY_WS_EVENTS: &str =
+        " ORDER BY ws_events DESC, http_requests DESC, voice_minutes DESC LIMIT $3";
+    const ORDER_BY_VOICE: &str =
+        " ORDER BY voice_minutes DESC, http_requests DESC, ws_events DESC LIMIT $3";
+
+    let base = match consumer_type {
+        ConsumerType::User => USER_SQL,
+        ConsumerType::Guild => GUILD_SQL,
+    };
+    let order = match sort {
+        ConsumerSort::Requests => ORDER_BY_REQUESTS,
+        ConsumerSort::WsEvents => ORDER_BY_WS_EVENTS,
+        ConsumerSort::VoiceMinutes => ORDER_BY_VOICE,
+    };
+
+    sqlx::query_as::<_, TopConsumerEntry>(&format!("{base}{order}"))
+        .bind(from)
+        .bind(to)
+        .bind(limit)
+        .fetch_all(pool)
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scope_from_path_extracts_guild_and_channel() {
+        let id = Uuid::new_v4();
+        assert_eq!(
+            scope_from_path(&format!("/api/guilds/{id}/members")),
+            Some(UsageKey::Guild(id))
+        );
+        assert_eq!(
+            scope_from_path(&format!("/api/channels/{id}/messages")),
+            Some(UsageKey::Channel(id))
+        );
+        assert_eq!(
+            scope_from_path(&format!("/api/guilds/{id}")),
+            Some(UsageKey::Guild(id))
+        );
+        assert_eq!(scope_from_path("/api/guilds"), None);
+        assert_eq!(scope_from_path("/api/guilds/not-a-uuid/members"), None);
+        assert_eq!(scope_from_path(&format!("/api/users/{id}")), None);
+        assert_eq!(scope_from_path(&format!("/guilds/{id}")), None);
+    }
+}
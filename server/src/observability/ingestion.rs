@@ -6,21 +6,23 @@
 //! Architecture: the tracing subscriber and span processor are initialised
 //! before the database pool, so we use `tokio::sync::mpsc` channels to
 //! decouple capture from storage. Background workers (spawned after pool
-//! creation) drain the channels and batch-write to `PostgreSQL`.
+//! creation) drain the channels and batch-write to the configured
+//! [`super::storage::TelemetryStorage`] backend.
 //!
 //! Design reference: §11 (Data Model), Phase 2 (Ingestion and Safety)
 
 use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
 
 use chrono::{DateTime, Utc};
 use opentelemetry::trace::TraceContextExt as _;
 use opentelemetry::KeyValue;
-use sqlx::PgPool;
 use tokio::sync::mpsc;
 use tracing_subscriber::layer::Context;
 use tracing_subscriber::registry::LookupSpan;
 use tracing_subscriber::Layer;
 
+use super::storage::TelemetryStorage;
 use super::tracing::is_forbidden_attribute_key;
 
 // ============================================================================
@@ -632,22 +634,22 @@ pub struct IngestionHandles {
 /// Max items to accumulate before flushing a batch INSERT.
 const BATCH_CAPACITY: usize = 64;
 
-/// Spawn the background workers that drain ingestion channels and write to DB.
+/// Spawn the background workers that drain ingestion channels and write to
+/// the telemetry backend.
 ///
 /// Each worker accumulates up to [`BATCH_CAPACITY`] items before flushing a
 /// single multi-row INSERT, reducing per-row overhead from network round-trips
 /// and transaction commits.
 ///
-/// Call this in `main()` after the database pool is created.
+/// Call this in `main()` after the storage backend is connected.
 pub fn spawn_ingestion_workers(
-    pool: PgPool,
+    storage: Arc<dyn TelemetryStorage>,
     mut log_rx: mpsc::Receiver<CapturedLogEvent>,
     mut span_rx: mpsc::Receiver<CapturedSpan>,
     mut metric_rx: mpsc::Receiver<CapturedMetricSample>,
 ) -> IngestionHandles {
-    let log_pool = pool.clone();
+    let log_storage = storage.clone();
     let log_handle = tokio::spawn(async move {
-        let empty_attrs = serde_json::Value::Object(serde_json::Map::new());
         let mut batch = Vec::with_capacity(BATCH_CAPACITY);
         loop {
             batch.clear();
@@ -664,28 +666,13 @@ pub fn spawn_ingestion_workers(
                 }
             }
             // Flush batch
-            let mut qb: sqlx::QueryBuilder<'_, sqlx::Postgres> = sqlx::QueryBuilder::new(
-                "INSERT INTO telemetry_log_events \
-                 (ts, level, service, domain, event, message, trace_id, span_id, attrs) ",
-            );
-            qb.push_values(&batch, |mut b, event| {
-                b.push_bind(event.ts)
-                    .push_bind(&event.level)
-                    .push_bind(&event.service)
-                    .push_bind(&event.domain)
-                    .push_bind(&event.event)
-                    .push_bind(&event.message)
-                    .push_bind(&event.trace_id)
-                    .push_bind(&event.span_id)
-                    .push_bind(&empty_attrs);
-            });
-            if let Err(e) = qb.build().execute(&log_pool).await {
+            if let Err(e) = log_storage.insert_log_events(&batch).await {
                 tracing::debug!(error = %e, batch_size = batch.len(), "Failed to persist native log events");
             }
         }
     });
 
-    let span_pool = pool.clone();
+    let span_storage = storage.clone();
     let span_handle = tokio::spawn(async move {
         let mut batch = Vec::with_capacity(BATCH_CAPACITY);
         loop {
@@ -700,21 +687,7 @@ pub fn spawn_ingestion_workers(
                     Err(_) => break,
                 }
             }
-            let mut qb: sqlx::QueryBuilder<'_, sqlx::Postgres> = sqlx::QueryBuilder::new(
-                "INSERT INTO telemetry_trace_index \
-                 (trace_id, span_name, domain, route, status_code, duration_ms, ts, service) ",
-            );
-            qb.push_values(&batch, |mut b, span| {
-                b.push_bind(&span.trace_id)
-                    .push_bind(&span.span_name)
-                    .push_bind(&span.domain)
-                    .push_bind(&span.route)
-                    .push_bind(&span.status_code)
-                    .push_bind(span.duration_ms)
-                    .push_bind(span.ts)
-                    .push_bind(&span.service);
-            });
-            if let Err(e) = qb.build().execute(&span_pool).await {
+            if let Err(e) = span_storage.insert_trace_entries(&batch).await {
                 tracing::debug!(error = %e, batch_size = batch.len(), "Failed to persist native trace index entries");
             }
         }
@@ -734,22 +707,7 @@ pub fn spawn_ingestion_workers(
                     Err(_) => break,
                 }
             }
-            let mut qb: sqlx::QueryBuilder<'_, sqlx::Postgres> = sqlx::QueryBuilder::new(
-                "INSERT INTO telemetry_metric_samples \
-                 (ts, metric_name, scope, labels, value_count, value_sum, value_p50, value_p95, value_p99) ",
-            );
-            qb.push_values(&batch, |mut b, sample| {
-                b.push_bind(sample.ts)
-                    .push_bind(&sample.metric_name)
-                    .push_bind(&sample.scope)
-                    .push_bind(&sample.labels)
-                    .push_bind(sample.value_count)
-                    .push_bind(sample.value_sum)
-                    .push_bind(sample.value_p50)
-                    .push_bind(sample.value_p95)
-                    .push_bind(sample.value_p99);
-            });
-            if let Err(e) = qb.build().execute(&pool).await {
+            if let Err(e) = storage.insert_metric_samples(&batch).await {
                 tracing::debug!(error = %e, batch_size = batch.len(), "Failed to persist native metric samples");
             }
         }
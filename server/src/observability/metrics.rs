@@ -3,18 +3,24 @@
 //! Configures a periodic OTLP metric exporter and installs it as the global
 //! meter provider so that any crate can call `opentelemetry::global::meter()`
 //! to obtain an instrument without explicit provider wiring.
+//!
+//! When `PROMETHEUS_ENABLED` is set, the same instruments are also rendered
+//! in the Prometheus text format and served at `/metrics`, for deployments
+//! without an OTLP collector.
 
-use std::sync::atomic::{AtomicU64, Ordering};
-use std::sync::OnceLock;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{OnceLock, RwLock};
+use std::time::Duration;
 
 use opentelemetry::metrics::{Counter, Histogram, UpDownCounter};
 use opentelemetry::{global, KeyValue};
 use opentelemetry_otlp::WithExportConfig as _;
-use opentelemetry_sdk::metrics::SdkMeterProvider;
+use opentelemetry_sdk::metrics::{MeterProviderBuilder, PeriodicReader, SdkMeterProvider};
 use opentelemetry_sdk::Resource;
 use sqlx::PgPool;
 
-use crate::config::ObservabilityConfig;
+use crate::config::{ObservabilityConfig, PrometheusConfig};
+use crate::util::has_bearer_token;
 
 // ============================================================================
 // Existing metrics
@@ -41,8 +47,13 @@ static WS_CONNECTIONS_ACTIVE: OnceLock<UpDownCounter<i64>> = OnceLock::new();
 /// new connections from re-connections.
 static WS_RECONNECTS_TOTAL: OnceLock<Counter<u64>> = OnceLock::new();
 static WS_MESSAGES_TOTAL: OnceLock<Counter<u64>> = OnceLock::new();
+static WS_DISCONNECTS_TOTAL: OnceLock<Counter<u64>> = OnceLock::new();
+static WS_CHANNEL_SUBSCRIPTIONS_ACTIVE: OnceLock<UpDownCounter<i64>> = OnceLock::new();
+static WS_SUBSCRIPTION_EVICTIONS_TOTAL: OnceLock<Counter<u64>> = OnceLock::new();
 static VOICE_SESSIONS_ACTIVE: OnceLock<UpDownCounter<i64>> = OnceLock::new();
 static VOICE_SESSION_DURATION_SECONDS: OnceLock<Histogram<f64>> = OnceLock::new();
+static VOICE_QUALITY_WARNINGS_TOTAL: OnceLock<Counter<u64>> = OnceLock::new();
+static VOICE_BITRATE_ADJUSTMENTS_TOTAL: OnceLock<Counter<u64>> = OnceLock::new();
 
 /// Hot-path atomic for RTP packet counting — avoids `OTel` overhead per packet.
 static VOICE_RTP_PACKETS_FORWARDED: AtomicU64 = AtomicU64::new(0);
@@ -52,6 +63,7 @@ static VOICE_RTP_COUNTER: OnceLock<Counter<u64>> = OnceLock::new();
 static DB_QUERY_DURATION_SECONDS: OnceLock<Histogram<f64>> = OnceLock::new();
 
 static AUTH_TOKEN_REFRESH_TOTAL: OnceLock<Counter<u64>> = OnceLock::new();
+static RATE_LIMIT_REJECTIONS_TOTAL: OnceLock<Counter<u64>> = OnceLock::new();
 static OTEL_EXPORT_FAILURES_TOTAL: OnceLock<Counter<u64>> = OnceLock::new();
 
 /// Registered but not wired — `OTel` SDK 0.29 has no dropped-span callback.
@@ -97,8 +109,8 @@ pub fn init(
     let native_exporter = super::ingestion::NativeMetricExporter::new(metric_tx);
 
     if !config.enabled {
-        // No OTLP export — only the native exporter
-        let provider = SdkMeterProvider::builder()
+        // No OTLP export — only the native exporter (and Prometheus)
+        let provider = with_prometheus(SdkMeterProvider::builder(), config)
             .with_periodic_exporter(native_exporter)
             .build();
         global::set_meter_provider(provider.clone());
@@ -118,7 +130,7 @@ pub fn init(
 
     // `with_periodic_exporter` defaults to a 60-second interval.
     // Override by setting `OTEL_METRIC_EXPORT_INTERVAL` (milliseconds).
-    let provider = SdkMeterProvider::builder()
+    let provider = with_prometheus(SdkMeterProvider::builder(), config)
         .with_resource(resource)
         .with_periodic_exporter(exporter)
         .with_periodic_exporter(native_exporter)
@@ -129,6 +141,22 @@ pub fn init(
     Some(provider)
 }
 
+/// Add the Prometheus snapshot reader when `/metrics` is enabled.
+fn with_prometheus(
+    builder: MeterProviderBuilder,
+    config: &ObservabilityConfig,
+) -> MeterProviderBuilder {
+    if config.prometheus.is_none() {
+        return builder;
+    }
+    PROMETHEUS_ENABLED.store(true, Ordering::Relaxed);
+    builder.with_reader(
+        PeriodicReader::builder(PrometheusSnapshotExporter)
+            .with_interval(PROMETHEUS_SNAPSHOT_INTERVAL)
+            .build(),
+    )
+}
+
 /// Return the global [`opentelemetry::metrics::Meter`] scoped to `name`.
 ///
 /// Convenience wrapper so callers don't need to import `opentelemetry::global`.
@@ -211,6 +239,27 @@ pub fn register_metrics() {
             .build()
     });
 
+    WS_DISCONNECTS_TOTAL.get_or_init(|| {
+        meter
+            .u64_counter("kaiku_ws_disconnects_total")
+            .with_description("WebSocket disconnects by cause")
+            .build()
+    });
+
+    WS_CHANNEL_SUBSCRIPTIONS_ACTIVE.get_or_init(|| {
+        meter
+            .i64_up_down_counter("kaiku_ws_channel_subscriptions_active")
+            .with_description("Current channel subscriptions across open WebSocket connections")
+            .build()
+    });
+
+    WS_SUBSCRIPTION_EVICTIONS_TOTAL.get_or_init(|| {
+        meter
+            .u64_counter("kaiku_ws_subscription_evictions_total")
+            .with_description("Channel subscriptions evicted to stay within the per-connection cap")
+            .build()
+    });
+
     VOICE_SESSIONS_ACTIVE.get_or_init(|| {
         meter
             .i64_up_down_counter("kaiku_voice_sessions_active")
@@ -226,6 +275,20 @@ pub fn register_metrics() {
             .build()
     });
 
+    VOICE_QUALITY_WARNINGS_TOTAL.get_or_init(|| {
+        meter
+            .u64_counter("kaiku_voice_quality_warnings_total")
+            .with_description("Voice participants warned about sustained packet loss")
+            .build()
+    });
+
+    VOICE_BITRATE_ADJUSTMENTS_TOTAL.get_or_init(|| {
+        meter
+            .u64_counter("kaiku_voice_bitrate_adjustments_total")
+            .with_description("Packet-loss bitrate caps applied and lifted, by direction")
+            .build()
+    });
+
     VOICE_RTP_COUNTER.get_or_init(|| {
         meter
             .u64_counter("kaiku_voice_rtp_packets_forwarded_total")
@@ -248,6 +311,13 @@ pub fn register_metrics() {
             .build()
     });
 
+    RATE_LIMIT_REJECTIONS_TOTAL.get_or_init(|| {
+        meter
+            .u64_counter("kaiku_rate_limit_rejections_total")
+            .with_description("Requests rejected by rate limiting, by route")
+            .build()
+    });
+
     OTEL_EXPORT_FAILURES_TOTAL.get_or_init(|| {
         meter
             .u64_counter("kaiku_otel_export_failures_total")
@@ -338,6 +408,15 @@ pub fn record_http_error(status: u16) {
     }
 }
 
+/// Record a request rejected with 429 by rate limiting.
+///
+/// `route` is the matched route template, never the resolved path.
+pub fn record_rate_limit_rejection(route: &str) {
+    if let Some(counter) = RATE_LIMIT_REJECTIONS_TOTAL.get() {
+        counter.add(1, &[KeyValue::new("http.route", route.to_owned())]);
+    }
+}
+
 /// Record a new WebSocket connection.
 pub fn record_ws_connect() {
     if let Some(counter) = WS_CONNECTIONS_ACTIVE.get() {
@@ -352,6 +431,13 @@ pub fn record_ws_disconnect() {
     }
 }
 
+/// Record why a WebSocket connection ended.
+pub fn record_ws_disconnect_cause(cause: &'static str) {
+    if let Some(counter) = WS_DISCONNECTS_TOTAL.get() {
+        counter.add(1, &[KeyValue::new("ws.disconnect_cause", cause)]);
+    }
+}
+
 /// Record a dispatched WebSocket message by event type.
 pub fn record_ws_message(event_type: &'static str) {
     if let Some(counter) = WS_MESSAGES_TOTAL.get() {
@@ -359,6 +445,20 @@ pub fn record_ws_message(event_type: &'static str) {
     }
 }
 
+/// Record channel subscriptions added (positive) or dropped (negative).
+pub fn record_ws_channel_subscriptions(delta: i64) {
+    if let Some(counter) = WS_CHANNEL_SUBSCRIPTIONS_ACTIVE.get() {
+        counter.add(delta, &[]);
+    }
+}
+
+/// Record a channel subscription evicted by the per-connection cap.
+pub fn record_ws_subscription_eviction() {
+    if let Some(counter) = WS_SUBSCRIPTION_EVICTIONS_TOTAL.get() {
+        counter.add(1, &[]);
+    }
+}
+
 /// Record a voice session start.
 pub fn record_voice_session_start() {
     if let Some(counter) = VOICE_SESSIONS_ACTIVE.get() {
@@ -376,6 +476,21 @@ pub fn record_voice_session_end(duration_s: f64) {
     }
 }
 
+/// Record a sustained packet loss warning sent to a voice participant.
+pub fn record_voice_quality_warning() {
+    if let Some(counter) = VOICE_QUALITY_WARNINGS_TOTAL.get() {
+        counter.add(1, &[]);
+    }
+}
+
+/// Record a packet-loss bitrate cap being applied (`down`) or lifted (`up`).
+pub fn record_voice_bitrate_adjustment(lowered: bool) {
+    let direction = if lowered { "down" } else { "up" };
+    if let Some(counter) = VOICE_BITRATE_ADJUSTMENTS_TOTAL.get() {
+        counter.add(1, &[KeyValue::new("direction", direction)]);
+    }
+}
+
 /// Increment the RTP packet counter atomically (hot path, no `OTel` overhead).
 pub fn record_rtp_packet_forwarded() {
     VOICE_RTP_PACKETS_FORWARDED.fetch_add(1, Ordering::Relaxed);
@@ -483,10 +598,319 @@ impl<E: PushMetricExporter> PushMetricExporter for FailureCountingMetricExporter
     }
 }
 
+// ============================================================================
+// Prometheus scrape endpoint
+// ============================================================================
+
+/// How often the `/metrics` snapshot is rebuilt; scrapes in between see the
+/// previous one.
+const PROMETHEUS_SNAPSHOT_INTERVAL: Duration = Duration::from_secs(15);
+
+/// Content type of the Prometheus text exposition format.
+const PROMETHEUS_CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+/// Whether the Prometheus reader was installed by [`init`].
+static PROMETHEUS_ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// The latest rendered snapshot served at `/metrics`.
+static PROMETHEUS_SNAPSHOT: RwLock<String> = RwLock::new(String::new());
+
+/// A `PushMetricExporter` that renders each collection in the Prometheus text
+/// format and keeps it for `/metrics`, instead of sending it anywhere.
+#[derive(Debug)]
+struct PrometheusSnapshotExporter;
+
+impl PushMetricExporter for PrometheusSnapshotExporter {
+    async fn export(
+        &self,
+        metrics: &mut ResourceMetrics,
+    ) -> opentelemetry_sdk::error::OTelSdkResult {
+        let text = render_prometheus(metrics);
+        if let Ok(mut snapshot) = PROMETHEUS_SNAPSHOT.write() {
+            *snapshot = text;
+        }
+        Ok(())
+    }
+
+    fn force_flush(&self) -> opentelemetry_sdk::error::OTelSdkResult {
+        Ok(())
+    }
+
+    fn shutdown(&self) -> opentelemetry_sdk::error::OTelSdkResult {
+        Ok(())
+    }
+
+    fn temporality(&self) -> opentelemetry_sdk::metrics::Temporality {
+        // Prometheus counters and histograms are cumulative
+        opentelemetry_sdk::metrics::Temporality::Cumulative
+    }
+}
+
+/// Render all metrics in the Prometheus text exposition format.
+fn render_prometheus(metrics: &ResourceMetrics) -> String {
+    let mut out = String::new();
+    let mut seen = std::collections::HashSet::new();
+    for scope_metrics in &metrics.scope_metrics {
+        for metric in &scope_metrics.metrics {
+            let name = sanitize_metric_name(&metric.name);
+            // The same name from another scope would be a duplicate family
+            if seen.insert(name.clone()) {
+                let _ = render_metric(&mut out, &name, &metric.description, metric.data.as_ref());
+            }
+        }
+    }
+    out
+}
+
+/// Render one metric family; aggregations without a Prometheus equivalent
+/// are skipped.
+fn render_metric(
+    out: &mut String,
+    name: &str,
+    description: &str,
+    data: &dyn opentelemetry_sdk::metrics::data::Aggregation,
+) -> std::fmt::Result {
+    use std::fmt::Write as _;
+
+    use opentelemetry_sdk::metrics::data::{Gauge, Histogram, Sum};
+
+    let any = data.as_any();
+    let help = description.replace('\\', r"\\").replace('\n', r"\n");
+
+    macro_rules! sum {
+        ($sum:expr) => {{
+            let sum = $sum;
+            let (kind, name) = if sum.is_monotonic {
+                let name = if name.ends_with("_total") {
+                    name.to_owned()
+                } else {
+                    format!("{name}_total")
+                };
+                ("counter", name)
+            } else {
+                ("gauge", name.to_owned())
+            };
+            writeln!(out, "# HELP {name} {help}")?;
+            writeln!(out, "# TYPE {name} {kind}")?;
+            for dp in &sum.data_points {
+                writeln!(
+                    out,
+                    "{name}{} {}",
+                    format_labels(&dp.attributes, None),
+                    dp.value
+                )?;
+            }
+            return Ok(());
+        }};
+    }
+    macro_rules! gauge {
+        ($gauge:expr) => {{
+            writeln!(out, "# HELP {name} {help}")?;
+            writeln!(out, "# TYPE {name} gauge")?;
+            for dp in &$gauge.data_points {
+                writeln!(
+                    out,
+                    "{name}{} {}",
+                    format_labels(&dp.attributes, None),
+                    dp.value
+                )?;
+            }
+            return Ok(());
+        }};
+    }
+
+    if let Some(sum) = any.downcast_ref::<Sum<u64>>() {
+        sum!(sum);
+    }
+    if let Some(sum) = any.downcast_ref::<Sum<i64>>() {
+        sum!(sum);
+    }
+    if let Some(sum) = any.downcast_ref::<Sum<f64>>() {
+        sum!(sum);
+    }
+    if let Some(gauge) = any.downcast_ref::<Gauge<u64>>() {
+        gauge!(gauge);
+    }
+    if let Some(gauge) = any.downcast_ref::<Gauge<i64>>() {
+        gauge!(gauge);
+    }
+    if let Some(gauge) = any.downcast_ref::<Gauge<f64>>() {
+        gauge!(gauge);
+    }
+    if let Some(hist) = any.downcast_ref::<Histogram<f64>>() {
+        writeln!(out, "# HELP {name} {help}")?;
+        writeln!(out, "# TYPE {name} histogram")?;
+        for dp in &hist.data_points {
+            let mut cumulative = 0;
+            for (bound, count) in dp.bounds.iter().zip(&dp.bucket_counts) {
+                cumulative += count;
+                let le = bound.to_string();
+                let labels = format_labels(&dp.attributes, Some(&le));
+                writeln!(out, "{name}_bucket{labels} {cumulative}")?;
+            }
+            let labels = format_labels(&dp.attributes, Some("+Inf"));
+            writeln!(out, "{name}_bucket{labels} {}", dp.count)?;
+            let labels = format_labels(&dp.attributes, None);
+            writeln!(out, "{name}_sum{labels} {}", dp.sum)?;
+            writeln!(out, "{name}_count{labels} {}", dp.count)?;
+        }
+    }
+    Ok(())
+}
+
+/// Replace characters Prometheus does not allow in metric and label names.
+fn sanitize_metric_name(name: &str) -> String {
+    let mut sanitized: String = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '_' || c == ':' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    if sanitized.starts_with(|c: char| c.is_ascii_digit()) {
+        sanitized.insert(0, '_');
+    }
+    sanitized
+}
+
+/// Format attributes as a `{key="value",...}` label set, with the histogram
+/// bucket bound `le` last; empty when there are no labels.
+fn format_labels(attributes: &[KeyValue], le: Option<&str>) -> String {
+    let mut labels: Vec<String> = attributes
+        .iter()
+        .map(|kv| {
+            let value = kv.value.as_str();
+            let value = value
+                .replace('\\', r"\\")
+                .replace('"', "\\\"")
+                .replace('\n', r"\n");
+            format!(
+                "{}=\"{value}\"",
+                sanitize_metric_name(kv.key.as_str()).replace(':', "_")
+            )
+        })
+        .collect();
+    if let Some(le) = le {
+        labels.push(format!("le=\"{le}\""));
+    }
+    if labels.is_empty() {
+        String::new()
+    } else {
+        format!("{{{}}}", labels.join(","))
+    }
+}
+
+/// Serve the latest snapshot, checking the bearer token when one is set.
+fn serve_snapshot(
+    headers: &axum::http::HeaderMap,
+    bearer_token: Option<&str>,
+) -> axum::response::Response {
+    use axum::http::{header, StatusCode};
+    use axum::response::IntoResponse;
+
+    if !PROMETHEUS_ENABLED.load(Ordering::Relaxed) {
+        return StatusCode::NOT_FOUND.into_response();
+    }
+    if bearer_token.is_some_and(|token| !has_bearer_token(headers, token)) {
+        return (
+            StatusCode::UNAUTHORIZED,
+            [(header::WWW_AUTHENTICATE, "Bearer")],
+        )
+            .into_response();
+    }
+    let body = PROMETHEUS_SNAPSHOT
+        .read()
+        .map(|snapshot| snapshot.clone())
+        .unwrap_or_default();
+    ([(header::CONTENT_TYPE, PROMETHEUS_CONTENT_TYPE)], body).into_response()
+}
+
+/// Router serving `GET /metrics`, requiring `bearer_token` when set.
+pub fn prometheus_router<S>(bearer_token: Option<String>) -> axum::Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    axum::Router::new().route(
+        "/metrics",
+        axum::routing::get(move |headers: axum::http::HeaderMap| {
+            let bearer_token = bearer_token.clone();
+            async move { serve_snapshot(&headers, bearer_token.as_deref()) }
+        }),
+    )
+}
+
+/// Serve `/metrics` on `PROMETHEUS_BIND_ADDRESS`, if set.
+///
+/// Returns `None` when no separate listener is configured. The bearer token
+/// is required here too when one is set.
+pub fn spawn_prometheus_listener(config: &PrometheusConfig) -> Option<tokio::task::JoinHandle<()>> {
+    let bind_address = config.bind_address.clone()?;
+    let router: axum::Router = prometheus_router(config.bearer_token.clone());
+    Some(tokio::spawn(async move {
+        let listener = match tokio::net::TcpListener::bind(&bind_address).await {
+            Ok(listener) => listener,
+            Err(e) => {
+                tracing::error!(
+                    address = %bind_address,
+                    error = %e,
+                    "Failed to bind Prometheus metrics listener"
+                );
+                return;
+            }
+        };
+        tracing::info!(address = %bind_address, "Prometheus metrics listening");
+        if let Err(e) = axum::serve(listener, router).await {
+            tracing::warn!(error = %e, "Prometheus metrics listener stopped");
+        }
+    }))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn prometheus_names_and_labels_are_sanitized() {
+        assert_eq!(
+            sanitize_metric_name("kaiku_http_requests_total"),
+            "kaiku_http_requests_total"
+        );
+        assert_eq!(
+            sanitize_metric_name("db.query.duration"),
+            "db_query_duration"
+        );
+        assert_eq!(sanitize_metric_name("9lives"), "_9lives");
+
+        assert_eq!(format_labels(&[], None), "");
+        let labels = format_labels(
+            &[
+                KeyValue::new("http.route", "/api/\"x\""),
+                KeyValue::new("status", 200_i64),
+            ],
+            Some("+Inf"),
+        );
+        assert_eq!(
+            labels,
+            r#"{http_route="/api/\"x\"",status="200",le="+Inf"}"#
+        );
+    }
+
+    #[test]
+    fn prometheus_bearer_token_is_checked() {
+        let mut headers = axum::http::HeaderMap::new();
+        assert!(!has_bearer_token(&headers, "secret"));
+        headers.insert(
+            axum::http::header::AUTHORIZATION,
+            "Bearer secret".parse().unwrap(),
+        );
+        assert!(has_bearer_token(&headers, "secret"));
+        assert!(!has_bearer_token(&headers, "secreT"));
+        assert!(!has_bearer_token(&headers, "secret2"));
+    }
+
     #[test]
     fn register_metrics_does_not_panic() {
         register_metrics();
@@ -502,8 +926,10 @@ mod tests {
         record_auth_login_attempt(true);
         record_auth_login_attempt(false);
         record_http_error(500);
+        record_rate_limit_rejection("/auth/login");
         record_ws_connect();
         record_ws_disconnect();
+        record_ws_disconnect_cause("timeout");
         record_ws_message("ping");
         record_voice_session_start();
         record_voice_session_end(10.5);
@@ -3,26 +3,33 @@
 //! # Quick start
 //!
 //! ```rust,no_run
-//! # use vc_server::{config::ObservabilityConfig, observability};
+//! # use vc_server::{config::{ObservabilityConfig, TelemetryStorageBackend}, observability};
 //! # let config = ObservabilityConfig {
 //! #     enabled: false,
 //! #     otlp_endpoint: String::new(),
 //! #     service_name: String::new(),
 //! #     trace_sample_ratio: 0.1,
 //! #     log_level: String::new(),
+//! #     telemetry_storage: TelemetryStorageBackend::Postgres,
+//! #     clickhouse: None,
+//! #     prometheus: None,
 //! # };
 //! // In main(), before any logging:
 //! let (_otel_guard, _meter_provider, _ingestion) = observability::init(&config);
 //! // `_otel_guard` must stay alive until the end of `main`.
 //! ```
 
+pub mod capacity;
+pub mod consumers;
 pub mod ingestion;
 pub mod metrics;
+pub mod reports;
 pub mod retention;
 pub mod sqlx_metrics;
 pub mod storage;
 pub mod tracing;
 pub mod voice;
+pub mod ws_disconnects;
 
 use opentelemetry_sdk::metrics::SdkMeterProvider;
 use tokio::sync::mpsc;
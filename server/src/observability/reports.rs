@@ -0,0 +1,123 @@
+//! Report queue SLA metrics.
+//!
+//! Summarizes how quickly user reports are triaged: the current backlog (open
+//! and in-review reports, how long the oldest one has waited, how many are
+//! past the SLA) and, for a time range, time-to-claim and time-to-resolve
+//! percentiles.
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sqlx::PgPool;
+
+/// Current report backlog.
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+pub struct ReportBacklog {
+    /// Reports nobody has claimed yet.
+    pub open: i64,
+    /// Reports claimed by an admin.
+    pub in_review: i64,
+    /// Age of the oldest open report.
+    pub oldest_open_secs: Option<f64>,
+    /// Open or in-review reports older than the SLA.
+    pub breaching: i64,
+}
+
+/// Median and 90th percentile of a duration.
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+pub struct DurationPercentiles {
+    pub count: i64,
+    pub p50_secs: Option<f64>,
+    pub p90_secs: Option<f64>,
+}
+
+/// Report SLA metrics over a time range.
+#[derive(Debug, Clone, Serialize)]
+pub struct ReportSla {
+    /// SLA used for `breaching` and `closed_within_sla`.
+    pub sla_secs: i64,
+    pub backlog: ReportBacklog,
+    /// Reports created in the range.
+    pub created: i64,
+    /// Creation to claim, for reports claimed in the range.
+    pub time_to_claim: DurationPercentiles,
+    /// Creation to resolution, for reports resolved or dismissed in the range.
+    pub time_to_resolve: DurationPercentiles,
+    /// Reports closed in the range within the SLA.
+    pub closed_within_sla: i64,
+}
+
+/// Compute report SLA metrics for `[from, to)`.
+#[tracing::instrument(skip(pool))]
+pub async fn query_report_sla(
+    pool: &PgPool,
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+    sla_secs: i64,
+) -> Result<ReportSla, sqlx::Error> {
+    let sla = sla_secs as f64;
+
+    let backlog = sqlx::query_as::<_, ReportBacklog>(
+        "SELECT COUNT(*) FILTER (WHERE status = 'pending') AS open, \
+                COUNT(*) FILTER (WHERE status = 'reviewing') AS in_review, \
+                EXTRACT(EPOCH FROM NOW() - MIN(created_at) FILTER (WHERE status = 'pending'))::float8 \
+                    AS oldest_open_secs, \
+                COUNT(*) FILTER (WHERE created_at < NOW() - make_interval(secs => $1)) AS breaching \
+         FROM user_reports \
+         WHERE status IN ('pending', 'reviewing')",
+    )
+    .bind(sla)
+    .fetch_one(pool)
+    .await?;
+
+    let created: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM user_reports WHERE created_at >= $1 AND created_at < $2",
+    )
+    .bind(from)
+    .bind(to)
+    .fetch_one(pool)
+    .await?;
+
+    let time_to_claim = sqlx::query_as::<_, DurationPercentiles>(
+        "SELECT COUNT(*) AS count, \
+                percentile_cont(0.5) WITHIN GROUP (ORDER BY EXTRACT(EPOCH FROM claimed_at - created_at)) AS p50_secs, \
+                percentile_cont(0.9) WITHIN GROUP (ORDER BY EXTRACT(EPOCH FROM claimed_at - created_at)) AS p90_secs \
+         FROM user_reports \
+         WHERE claimed_at >= $1 AND claimed_at < $2",
+    )
+    .bind(from)
+    .bind(to)
+    .fetch_one(pool)
+    .await?;
+
+    let time_to_resolve = sqlx::query_as::<_, DurationPercentiles>(
+        "SELECT COUNT(*) AS count, \
+                percentile_cont(0.5) WITHIN GROUP (ORDER BY EXTRACT(EPOCH FROM resolved_at - created_at)) AS p50_secs, \
+                percentile_cont(0.9) WITHIN GROUP (ORDER BY EXTRACT(EPOCH FROM resolved_at - created_at)) AS p90_secs \
+         FROM user_reports \
+         WHERE resolved_at >= $1 AND resolved_at < $2",
+    )
+    .bind(from)
+    .bind(to)
+    .fetch_one(pool)
+    .await?;
+
+    let closed_within_sla: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM user_reports \
+         WHERE resolved_at >= $1 AND resolved_at < $2 \
+           AND resolved_at - created_at <= make_interval(secs => $3)",
+    )
+    .bind(from)
+    .bind(to)
+    .bind(sla)
+    .fetch_one(pool)
+    .await?;
+
+    Ok(ReportSla {
+        sla_secs,
+        backlog,
+        created,
+        time_to_claim,
+        time_to_resolve,
+        closed_within_sla,
+    })
+}
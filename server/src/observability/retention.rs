@@ -2,7 +2,11 @@
 //!
 //! Runs hourly to:
 //! 1. Refresh the `telemetry_trend_rollups` materialized view concurrently.
-//! 2. Hard-delete rows older than 30 days from all native telemetry tables.
+//! 2. Hard-delete rows older than the instance's `telemetry_days` from all
+//!    native telemetry tables.
+//! 3. Hard-delete voice connection metrics older than `connection_metrics_days`.
+//!
+//! Both windows are admin settings ([`RetentionSettings`]), read every cycle.
 //!
 //! Design reference: §11.5 (Retention Policies)
 
@@ -10,7 +14,8 @@ use std::time::{Duration, Instant};
 
 use sqlx::PgPool;
 
-const RETENTION_DAYS: i32 = 30;
+use crate::admin::retention::RetentionSettings;
+
 const DELETE_BATCH_SIZE: i64 = 10_000;
 
 /// Start the hourly retention and rollup refresh background task.
@@ -41,13 +46,19 @@ pub fn spawn_retention_task(pool: PgPool) -> tokio::task::JoinHandle<()> {
 #[tracing::instrument(skip(pool))]
 async fn run_retention_cycle(pool: &PgPool) {
     let start = Instant::now();
+    let settings = RetentionSettings::load(pool).await;
+    let days = settings.telemetry_days;
 
     // Refresh rollups FIRST so boundary-day data is captured before deletion
     refresh_trend_rollups(pool).await;
 
-    let metrics_deleted = purge_old_metric_samples(pool).await;
-    let logs_deleted = purge_old_log_events(pool).await;
-    let traces_deleted = purge_old_trace_index(pool).await;
+    let metrics_deleted = purge_old_metric_samples(pool, days).await;
+    let logs_deleted = purge_old_log_events(pool, days).await;
+    let traces_deleted = purge_old_trace_index(pool, days).await;
+    let consumer_usage_deleted = purge_old_consumer_usage(pool, days).await;
+    let ws_disconnects_deleted = purge_old_ws_disconnects(pool, days).await;
+    let connection_metrics_deleted =
+        purge_old_connection_metrics(pool, settings.connection_metrics_days).await;
 
     let elapsed = start.elapsed();
     tracing::info!(
@@ -55,19 +66,23 @@ async fn run_retention_cycle(pool: &PgPool) {
         metrics_deleted,
         logs_deleted,
         traces_deleted,
+        consumer_usage_deleted,
+        ws_disconnects_deleted,
+        connection_metrics_deleted,
         "Telemetry retention cycle completed"
     );
 }
 
-/// Delete metric samples older than 30 days.
+/// Delete metric samples older than `days`.
 ///
 /// Attempts `TimescaleDB` `drop_chunks` first for efficient chunk-level deletion.
 /// Falls back to batched `DELETE` if `TimescaleDB` is not available.
-async fn purge_old_metric_samples(pool: &PgPool) -> i64 {
+async fn purge_old_metric_samples(pool: &PgPool, days: i32) -> i64 {
     // Try TimescaleDB drop_chunks first (much faster for hypertables)
     let ts_result = sqlx::query(
-        "SELECT drop_chunks('telemetry_metric_samples', older_than => INTERVAL '30 days')",
+        "SELECT drop_chunks('telemetry_metric_samples', older_than => make_interval(days => $1))",
     )
+    .bind(days)
     .execute(pool)
     .await;
 
@@ -85,6 +100,7 @@ async fn purge_old_metric_samples(pool: &PgPool) -> i64 {
                      SELECT ctid FROM telemetry_metric_samples \
                      WHERE ts < NOW() - make_interval(days => $1) LIMIT $2\
                  )",
+                days,
                 "metric samples",
             )
             .await
@@ -92,42 +108,101 @@ async fn purge_old_metric_samples(pool: &PgPool) -> i64 {
     }
 }
 
-/// Delete log events older than 30 days in batches.
-async fn purge_old_log_events(pool: &PgPool) -> i64 {
+/// Delete log events older than `days` in batches.
+async fn purge_old_log_events(pool: &PgPool, days: i32) -> i64 {
     purge_in_batches(
         pool,
         "DELETE FROM telemetry_log_events WHERE id IN (\
              SELECT id FROM telemetry_log_events \
              WHERE ts < NOW() - make_interval(days => $1) LIMIT $2\
          )",
+        days,
         "log events",
     )
     .await
 }
 
-/// Delete trace index entries older than 30 days in batches.
-async fn purge_old_trace_index(pool: &PgPool) -> i64 {
+/// Delete trace index entries older than `days` in batches.
+async fn purge_old_trace_index(pool: &PgPool, days: i32) -> i64 {
     purge_in_batches(
         pool,
         "DELETE FROM telemetry_trace_index WHERE id IN (\
              SELECT id FROM telemetry_trace_index \
              WHERE ts < NOW() - make_interval(days => $1) LIMIT $2\
          )",
+        days,
         "trace index entries",
     )
     .await
 }
 
+/// Delete consumer usage buckets older than `days` in batches.
+async fn purge_old_consumer_usage(pool: &PgPool, days: i32) -> i64 {
+    purge_in_batches(
+        pool,
+        "DELETE FROM telemetry_consumer_usage WHERE ctid IN (\
+             SELECT ctid FROM telemetry_consumer_usage \
+             WHERE bucket < NOW() - make_interval(days => $1) LIMIT $2\
+         )",
+        days,
+        "consumer usage buckets",
+    )
+    .await
+}
+
+/// Delete WebSocket disconnect records older than `days` in batches.
+async fn purge_old_ws_disconnects(pool: &PgPool, days: i32) -> i64 {
+    purge_in_batches(
+        pool,
+        "DELETE FROM telemetry_ws_disconnects WHERE id IN (\
+             SELECT id FROM telemetry_ws_disconnects \
+             WHERE ts < NOW() - make_interval(days => $1) LIMIT $2\
+         )",
+        days,
+        "WS disconnects",
+    )
+    .await
+}
+
+/// Delete voice connection metrics older than `days`.
+///
+/// `connection_metrics` is a hypertable when `TimescaleDB` is available, so
+/// this tries `drop_chunks` first like the metric samples.
+async fn purge_old_connection_metrics(pool: &PgPool, days: i32) -> i64 {
+    let ts_result = sqlx::query(
+        "SELECT drop_chunks('connection_metrics', older_than => make_interval(days => $1))",
+    )
+    .bind(days)
+    .execute(pool)
+    .await;
+
+    match ts_result {
+        Ok(_) => 0,
+        Err(_) => {
+            purge_in_batches(
+                pool,
+                "DELETE FROM connection_metrics WHERE ctid IN (\
+                     SELECT ctid FROM connection_metrics \
+                     WHERE time < NOW() - make_interval(days => $1) LIMIT $2\
+                 )",
+                days,
+                "connection metrics",
+            )
+            .await
+        }
+    }
+}
+
 /// Execute batched DELETEs to avoid holding table-level locks for too long.
 ///
 /// Deletes up to [`DELETE_BATCH_SIZE`] rows per iteration until no more rows
 /// match the retention cutoff. The SQL must accept `$1` (retention days) and
 /// `$2` (batch size limit).
-async fn purge_in_batches(pool: &PgPool, sql: &str, table_label: &str) -> i64 {
+async fn purge_in_batches(pool: &PgPool, sql: &str, days: i32, table_label: &str) -> i64 {
     let mut total_deleted: i64 = 0;
     loop {
         match sqlx::query(sql)
-            .bind(RETENTION_DAYS)
+            .bind(days)
             .bind(DELETE_BATCH_SIZE)
             .execute(pool)
             .await
@@ -174,7 +249,7 @@ mod tests {
     use super::*;
 
     #[test]
-    fn retention_days_is_30() {
-        assert_eq!(RETENTION_DAYS, 30);
+    fn default_retention_days_is_30() {
+        assert_eq!(RetentionSettings::default().telemetry_days, 30);
     }
 }
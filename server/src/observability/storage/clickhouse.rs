@@ -0,0 +1,785 @@
+//! `ClickHouse` telemetry backend (`TELEMETRY_STORAGE=clickhouse`).
+//!
+//! Talks to `ClickHouse` over its HTTP interface: queries are sent with
+//! `{name:Type}` placeholders bound through `param_*` URL parameters and read
+//! back as `JSONEachRow`. The tables mirror the `PostgreSQL` telemetry tables,
+//! with a `MergeTree` TTL taking the place of the retention job, and daily trend
+//! aggregates computed at query time instead of from a materialized view.
+
+// `{name:Type}` query placeholders are not format arguments
+#![allow(clippy::literal_string_with_formatting_args)]
+
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use futures::future::BoxFuture;
+use futures::FutureExt;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use uuid::Uuid;
+
+use super::{
+    clamp_from_time, escape_ilike_pattern, LogEvent, LogFilter, StorageError, TelemetryStorage,
+    TopRouteEntry, TraceFilter, TraceIndexEntry, TrendDataPoint, VitalSigns, MAX_PAGE_SIZE,
+    MAX_TREND_ROWS,
+};
+use crate::config::{ClickHouseConfig, TelemetryStorageBackend};
+use crate::observability::ingestion::{CapturedLogEvent, CapturedMetricSample, CapturedSpan};
+
+/// HTTP request timeout for `ClickHouse` queries and inserts.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Table definitions, created on startup if missing. The 30-day TTL matches
+/// the `PostgreSQL` retention job and the maximum query range.
+const SCHEMA: [&str; 3] = [
+    "CREATE TABLE IF NOT EXISTS telemetry_metric_samples (\
+         ts DateTime64(3, 'UTC'), \
+         metric_name LowCardinality(String), \
+         scope LowCardinality(String), \
+         labels Map(LowCardinality(String), String), \
+         value_count Nullable(Int64), \
+         value_sum Nullable(Float64), \
+         value_p50 Nullable(Float64), \
+         value_p95 Nullable(Float64), \
+         value_p99 Nullable(Float64)\
+     ) ENGINE = MergeTree \
+     PARTITION BY toDate(ts) \
+     ORDER BY (metric_name, ts) \
+     TTL toDateTime(ts) + INTERVAL 30 DAY",
+    "CREATE TABLE IF NOT EXISTS telemetry_log_events (\
+         id UUID DEFAULT generateUUIDv4(), \
+         ts DateTime64(3, 'UTC'), \
+         level LowCardinality(String), \
+         service LowCardinality(String), \
+         domain LowCardinality(String), \
+         event String, \
+         message String, \
+         trace_id Nullable(String), \
+         span_id Nullable(String), \
+         attrs String DEFAULT '{}'\
+     ) ENGINE = MergeTree \
+     PARTITION BY toDate(ts) \
+     ORDER BY (ts, id) \
+     TTL toDateTime(ts) + INTERVAL 30 DAY",
+    "CREATE TABLE IF NOT EXISTS telemetry_trace_index (\
+         id UUID DEFAULT generateUUIDv4(), \
+         trace_id String, \
+         span_name String, \
+         domain LowCardinality(String), \
+         route Nullable(String), \
+         status_code Nullable(String), \
+         duration_ms Int32, \
+         ts DateTime64(3, 'UTC'), \
+         service LowCardinality(String)\
+     ) ENGINE = MergeTree \
+     PARTITION BY toDate(ts) \
+     ORDER BY (ts, id) \
+     TTL toDateTime(ts) + INTERVAL 30 DAY",
+];
+
+/// Telemetry storage in a `ClickHouse` database.
+#[derive(Clone)]
+pub struct ClickHouseStorage {
+    http: reqwest::Client,
+    url: String,
+    database: String,
+    user: Option<String>,
+    password: Option<String>,
+}
+
+impl ClickHouseStorage {
+    /// Create a backend for the configured server. Does not connect.
+    pub fn new(config: &ClickHouseConfig) -> Result<Self, StorageError> {
+        let http = reqwest::Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .build()
+            .map_err(http_error)?;
+
+        Ok(Self {
+            http,
+            url: config.url.clone(),
+            database: config.database.clone(),
+            user: config.user.clone(),
+            password: config.password.clone(),
+        })
+    }
+
+    /// Create the database and telemetry tables if they do not exist.
+    pub async fn ensure_schema(&self) -> Result<(), StorageError> {
+        // The database name is validated to `[A-Za-z0-9_]` when the config is loaded.
+        let create_database = Query::new(&format!(
+            "CREATE DATABASE IF NOT EXISTS `{}`",
+            self.database
+        ));
+        self.send("default", &create_database, None).await?;
+
+        for statement in SCHEMA {
+            self.send(&self.database, &Query::new(statement), None)
+                .await?;
+        }
+
+        tracing::info!(database = %self.database, "ClickHouse telemetry schema ready");
+        Ok(())
+    }
+
+    /// Run a `SELECT` and decode its `JSONEachRow` output.
+    async fn fetch<T: DeserializeOwned>(&self, query: &Query) -> Result<Vec<T>, StorageError> {
+        let mut query = query.clone();
+        query.push(" FORMAT JSONEachRow");
+        let body = self.send(&self.database, &query, None).await?;
+        parse_rows(&body)
+    }
+
+    /// Insert rows into `table` as `JSONEachRow`.
+    async fn insert(&self, table: &str, rows: &[serde_json::Value]) -> Result<(), StorageError> {
+        if rows.is_empty() {
+            return Ok(());
+        }
+        let mut body = String::new();
+        for row in rows {
+            body.push_str(&row.to_string());
+            body.push('\n');
+        }
+        let query = Query::new(&format!("INSERT INTO {table} FORMAT JSONEachRow"));
+        self.send(&self.database, &query, Some(body)).await?;
+        Ok(())
+    }
+
+    /// Send one statement over HTTP. Without `rows` the statement is the
+    /// request body; with `rows` it moves to the `query` URL parameter and the
+    /// rows are sent as the body.
+    async fn send(
+        &self,
+        database: &str,
+        query: &Query,
+        rows: Option<String>,
+    ) -> Result<String, StorageError> {
+        let mut request = self
+            .http
+            .post(&self.url)
+            .query(&[
+                ("database", database),
+                ("date_time_output_format", "iso"),
+                ("output_format_json_quote_64bit_integers", "0"),
+            ])
+            .query(&query.params);
+
+        request = match rows {
+            Some(rows) => request.query(&[("query", query.sql.as_str())]).body(rows),
+            None => request.body(query.sql.clone()),
+        };
+        if let Some(user) = &self.user {
+            request = request.header("X-ClickHouse-User", user);
+        }
+        if let Some(password) = &self.password {
+            request = request.header("X-ClickHouse-Key", password);
+        }
+
+        let response = request.send().await.map_err(http_error)?;
+        let status = response.status();
+        let body = response.text().await.map_err(http_error)?;
+        if !status.is_success() {
+            return Err(StorageError::ClickHouse(format!(
+                "HTTP {status}: {}",
+                body.trim()
+            )));
+        }
+        Ok(body)
+    }
+}
+
+impl TelemetryStorage for ClickHouseStorage {
+    fn backend(&self) -> TelemetryStorageBackend {
+        TelemetryStorageBackend::ClickHouse
+    }
+
+    fn insert_log_events<'a>(
+        &'a self,
+        events: &'a [CapturedLogEvent],
+    ) -> BoxFuture<'a, Result<(), StorageError>> {
+        async move {
+            let rows: Vec<_> = events.iter().map(log_event_row).collect();
+            self.insert("telemetry_log_events", &rows).await
+        }
+        .boxed()
+    }
+
+    fn insert_trace_entries<'a>(
+        &'a self,
+        spans: &'a [CapturedSpan],
+    ) -> BoxFuture<'a, Result<(), StorageError>> {
+        async move {
+            let rows: Vec<_> = spans.iter().map(trace_entry_row).collect();
+            self.insert("telemetry_trace_index", &rows).await
+        }
+        .boxed()
+    }
+
+    fn insert_metric_samples<'a>(
+        &'a self,
+        samples: &'a [CapturedMetricSample],
+    ) -> BoxFuture<'a, Result<(), StorageError>> {
+        async move {
+            let rows: Vec<_> = samples.iter().map(metric_sample_row).collect();
+            self.insert("telemetry_metric_samples", &rows).await
+        }
+        .boxed()
+    }
+
+    fn query_trends<'a>(
+        &'a self,
+        metric_name: &'a str,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> BoxFuture<'a, Result<Vec<TrendDataPoint>, StorageError>> {
+        async move { self.fetch(&trends_query(metric_name, from, to)).await }.boxed()
+    }
+
+    fn query_logs<'a>(
+        &'a self,
+        filter: &'a LogFilter,
+    ) -> BoxFuture<'a, Result<Vec<LogEvent>, StorageError>> {
+        async move {
+            let rows: Vec<LogRow> = self.fetch(&logs_query(filter)).await?;
+            Ok(rows.into_iter().map(LogEvent::from).collect())
+        }
+        .boxed()
+    }
+
+    fn query_traces<'a>(
+        &'a self,
+        filter: &'a TraceFilter,
+    ) -> BoxFuture<'a, Result<Vec<TraceIndexEntry>, StorageError>> {
+        async move { self.fetch(&traces_query(filter)).await }.boxed()
+    }
+
+    fn query_top_routes(
+        &self,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+        sort_by_errors: bool,
+        limit: i64,
+    ) -> BoxFuture<'_, Result<Vec<TopRouteEntry>, StorageError>> {
+        async move {
+            let limit = limit.clamp(1, MAX_PAGE_SIZE);
+            let mut query = Query::new(
+                "SELECT \
+                     labels['http.route'] AS route, \
+                     sum(value_count) AS request_count, \
+                     sumIf(value_count, toInt32OrZero(labels['http.response.status_code']) >= 500) AS error_count, \
+                     avg(value_p95) AS avg_p95, \
+                     max(value_p95) AS max_p95 \
+                 FROM telemetry_metric_samples \
+                 WHERE metric_name = 'kaiku_http_request_duration_ms' \
+                   AND ts >= {from:DateTime64(3, 'UTC')} AND ts <= {to:DateTime64(3, 'UTC')} \
+                   AND mapContains(labels, 'http.route') \
+                 GROUP BY route",
+            );
+            query
+                .bind("from", timestamp(clamp_from_time(from, to)))
+                .bind("to", timestamp(to));
+            if sort_by_errors {
+                query.push(" ORDER BY error_count DESC");
+            } else {
+                query.push(" ORDER BY avg_p95 DESC");
+            }
+            query.push(&format!(" LIMIT {limit}"));
+            self.fetch(&query).await
+        }
+        .boxed()
+    }
+
+    fn query_top_errors(
+        &self,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+        limit: i64,
+    ) -> BoxFuture<'_, Result<Vec<TopRouteEntry>, StorageError>> {
+        async move {
+            let limit = limit.clamp(1, MAX_PAGE_SIZE);
+            let mut query = Query::new(
+                "SELECT \
+                     labels['error.type'] AS route, \
+                     sum(value_count) AS request_count, \
+                     sum(value_count) AS error_count, \
+                     avg(value_p95) AS avg_p95, \
+                     max(value_p95) AS max_p95 \
+                 FROM telemetry_metric_samples \
+                 WHERE metric_name = 'kaiku_http_errors_total' \
+                   AND ts >= {from:DateTime64(3, 'UTC')} AND ts <= {to:DateTime64(3, 'UTC')} \
+                   AND mapContains(labels, 'error.type') \
+                 GROUP BY route \
+                 ORDER BY error_count DESC",
+            );
+            query
+                .bind("from", timestamp(clamp_from_time(from, to)))
+                .bind("to", timestamp(to))
+                .push(&format!(" LIMIT {limit}"));
+            self.fetch(&query).await
+        }
+        .boxed()
+    }
+
+    fn query_vital_signs(
+        &self,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> BoxFuture<'_, Result<VitalSigns, StorageError>> {
+        async move {
+            let mut metrics = Query::new(
+                "SELECT \
+                     avgIf(value_p95, metric_name = 'kaiku_http_request_duration_ms') AS latency_p95_ms, \
+                     sumIf(value_count, metric_name = 'kaiku_http_errors_total') AS error_count, \
+                     sumIf(value_count, metric_name = 'kaiku_http_requests_total') AS request_count, \
+                     if(countIf(metric_name = 'kaiku_ws_connections_active') = 0, NULL, \
+                        argMaxIf(value_count, ts, metric_name = 'kaiku_ws_connections_active')) AS ws_connections, \
+                     if(countIf(metric_name = 'kaiku_voice_sessions_active') = 0, NULL, \
+                        argMaxIf(value_count, ts, metric_name = 'kaiku_voice_sessions_active')) AS voice_sessions, \
+                     0 AS error_log_count \
+                 FROM telemetry_metric_samples \
+                 WHERE ts >= {from:DateTime64(3, 'UTC')} AND ts <= {to:DateTime64(3, 'UTC')}",
+            );
+            metrics
+                .bind("from", timestamp(from))
+                .bind("to", timestamp(to));
+
+            let mut errors = Query::new(
+                "SELECT count() AS count FROM telemetry_log_events \
+                 WHERE level = 'ERROR' AND ts >= {from:DateTime64(3, 'UTC')}",
+            );
+            errors.bind("from", timestamp(from));
+
+            let (vitals, error_logs) = tokio::try_join!(
+                self.fetch::<VitalSigns>(&metrics),
+                self.fetch::<CountRow>(&errors),
+            )?;
+
+            let mut vitals = vitals.into_iter().next().unwrap_or_default();
+            vitals.error_log_count = error_logs.first().map_or(0, |row| row.count);
+            Ok(vitals)
+        }
+        .boxed()
+    }
+}
+
+// ============================================================================
+// Queries
+// ============================================================================
+
+/// SQL text plus the `param_*` URL parameters for its placeholders.
+#[derive(Debug, Clone)]
+struct Query {
+    sql: String,
+    params: Vec<(String, String)>,
+}
+
+impl Query {
+    fn new(sql: &str) -> Self {
+        Self {
+            sql: sql.to_owned(),
+            params: Vec::new(),
+        }
+    }
+
+    fn push(&mut self, sql: &str) -> &mut Self {
+        self.sql.push_str(sql);
+        self
+    }
+
+    /// Bind the value of the `{name:Type}` placeholder.
+    fn bind(&mut self, name: &str, value: impl ToString) -> &mut Self {
+        self.params
+            .push((format!("param_{name}"), value.to_string()));
+        self
+    }
+}
+
+/// Trend points from raw samples for ranges up to a day, daily aggregates
+/// beyond that (p50/p99 are not available at daily granularity).
+fn trends_query(metric_name: &str, from: DateTime<Utc>, to: DateTime<Utc>) -> Query {
+    let mut query = if (to - from).num_days() <= 1 {
+        let mut query = Query::new(
+            "SELECT ts, metric_name, value_count, value_sum, value_p50, value_p95, value_p99 \
+             FROM telemetry_metric_samples \
+             WHERE metric_name = {metric:String} \
+               AND ts >= {from:DateTime64(3, 'UTC')} AND ts <= {to:DateTime64(3, 'UTC')} \
+             ORDER BY ts ASC",
+        );
+        query.push(&format!(" LIMIT {MAX_TREND_ROWS}"));
+        query
+    } else {
+        Query::new(
+            "SELECT \
+                 day AS ts, \
+                 metric_name, \
+                 total_count AS value_count, \
+                 NULL AS value_sum, \
+                 NULL AS value_p50, \
+                 avg_p95 AS value_p95, \
+                 NULL AS value_p99 \
+             FROM ( \
+                 SELECT toStartOfDay(ts) AS day, metric_name, \
+                        sum(value_count) AS total_count, avg(value_p95) AS avg_p95 \
+                 FROM telemetry_metric_samples \
+                 WHERE metric_name = {metric:String} \
+                   AND ts >= {from:DateTime64(3, 'UTC')} AND ts <= {to:DateTime64(3, 'UTC')} \
+                 GROUP BY day, metric_name \
+             ) \
+             ORDER BY ts ASC",
+        )
+    };
+    query
+        .bind("metric", metric_name)
+        .bind("from", timestamp(clamp_from_time(from, to)))
+        .bind("to", timestamp(to));
+    query
+}
+
+/// Paginated log events. Optional filters are only added to the `WHERE`
+/// clause when set, so no `NULL` parameters are needed.
+fn logs_query(filter: &LogFilter) -> Query {
+    let mut query = Query::new(
+        "SELECT id, ts, level, service, domain, event, message, trace_id, span_id, attrs \
+         FROM telemetry_log_events \
+         WHERE ts >= {from:DateTime64(3, 'UTC')} AND ts <= {to:DateTime64(3, 'UTC')}",
+    );
+    query
+        .bind("from", timestamp(clamp_from_time(filter.from, filter.to)))
+        .bind("to", timestamp(filter.to));
+
+    if let Some(level) = &filter.level {
+        query
+            .push(" AND level = {level:String}")
+            .bind("level", level);
+    }
+    if let Some(domain) = &filter.domain {
+        query
+            .push(" AND domain = {domain:String}")
+            .bind("domain", domain);
+    }
+    if let Some(service) = &filter.service {
+        query
+            .push(" AND service = {service:String}")
+            .bind("service", service);
+    }
+    if let Some(search) = &filter.search {
+        query
+            .push(" AND (event ILIKE {search:String} OR message ILIKE {search:String})")
+            .bind("search", format!("%{}%", escape_ilike_pattern(search)));
+    }
+    if let Some(cursor) = filter.cursor {
+        query
+            .push(
+                " AND (ts, id) < ((SELECT ts FROM telemetry_log_events \
+                 WHERE id = {cursor:UUID} LIMIT 1), {cursor:UUID})",
+            )
+            .bind("cursor", cursor);
+    }
+
+    let limit = filter.limit.clamp(1, MAX_PAGE_SIZE);
+    query.push(&format!(" ORDER BY ts DESC, id DESC LIMIT {limit}"));
+    query
+}
+
+/// Paginated trace index entries, filtered like [`logs_query`].
+fn traces_query(filter: &TraceFilter) -> Query {
+    let mut query = Query::new(
+        "SELECT id, trace_id, span_name, domain, route, status_code, duration_ms, ts, service \
+         FROM telemetry_trace_index \
+         WHERE ts >= {from:DateTime64(3, 'UTC')} AND ts <= {to:DateTime64(3, 'UTC')}",
+    );
+    query
+        .bind("from", timestamp(clamp_from_time(filter.from, filter.to)))
+        .bind("to", timestamp(filter.to));
+
+    if let Some(status_code) = &filter.status_code {
+        query
+            .push(" AND status_code = {status_code:String}")
+            .bind("status_code", status_code);
+    }
+    if filter.is_error {
+        query.push(" AND startsWith(status_code, '5')");
+    }
+    if let Some(domain) = &filter.domain {
+        query
+            .push(" AND domain = {domain:String}")
+            .bind("domain", domain);
+    }
+    if let Some(route) = &filter.route {
+        query
+            .push(" AND route = {route:String}")
+            .bind("route", route);
+    }
+    if let Some(duration_min) = filter.duration_min {
+        query
+            .push(" AND duration_ms >= {duration_min:Int32}")
+            .bind("duration_min", duration_min);
+    }
+    if let Some(cursor) = filter.cursor {
+        query
+            .push(
+                " AND (ts, id) < ((SELECT ts FROM telemetry_trace_index \
+                 WHERE id = {cursor:UUID} LIMIT 1), {cursor:UUID})",
+            )
+            .bind("cursor", cursor);
+    }
+
+    let limit = filter.limit.clamp(1, MAX_PAGE_SIZE);
+    query.push(&format!(" ORDER BY ts DESC, id DESC LIMIT {limit}"));
+    query
+}
+
+// ============================================================================
+// Rows
+// ============================================================================
+
+/// Log event as returned by `ClickHouse`, where `attrs` is JSON text.
+#[derive(Debug, Deserialize)]
+struct LogRow {
+    id: Uuid,
+    ts: DateTime<Utc>,
+    level: String,
+    service: String,
+    domain: String,
+    event: String,
+    message: String,
+    trace_id: Option<String>,
+    span_id: Option<String>,
+    attrs: String,
+}
+
+impl From<LogRow> for LogEvent {
+    fn from(row: LogRow) -> Self {
+        Self {
+            id: row.id,
+            ts: row.ts,
+            level: row.level,
+            service: row.service,
+            domain: row.domain,
+            event: row.event,
+            message: row.message,
+            trace_id: row.trace_id,
+            span_id: row.span_id,
+            attrs: serde_json::from_str(&row.attrs)
+                .unwrap_or_else(|_| serde_json::Value::Object(serde_json::Map::new())),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct CountRow {
+    count: i64,
+}
+
+fn log_event_row(event: &CapturedLogEvent) -> serde_json::Value {
+    serde_json::json!({
+        "ts": timestamp(event.ts),
+        "level": event.level,
+        "service": event.service,
+        "domain": event.domain,
+        "event": event.event,
+        "message": event.message,
+        "trace_id": event.trace_id,
+        "span_id": event.span_id,
+    })
+}
+
+fn trace_entry_row(span: &CapturedSpan) -> serde_json::Value {
+    serde_json::json!({
+        "trace_id": span.trace_id,
+        "span_name": span.span_name,
+        "domain": span.domain,
+        "route": span.route,
+        "status_code": span.status_code,
+        "duration_ms": span.duration_ms,
+        "ts": timestamp(span.ts),
+        "service": span.service,
+    })
+}
+
+fn metric_sample_row(sample: &CapturedMetricSample) -> serde_json::Value {
+    serde_json::json!({
+        "ts": timestamp(sample.ts),
+        "metric_name": sample.metric_name,
+        "scope": sample.scope,
+        "labels": string_labels(&sample.labels),
+        "value_count": sample.value_count,
+        "value_sum": sample.value_sum,
+        "value_p50": sample.value_p50,
+        "value_p95": sample.value_p95,
+        "value_p99": sample.value_p99,
+    })
+}
+
+/// Convert JSON labels to the string values of a `Map(String, String)` column.
+fn string_labels(labels: &serde_json::Value) -> serde_json::Map<String, serde_json::Value> {
+    labels
+        .as_object()
+        .into_iter()
+        .flatten()
+        .map(|(key, value)| {
+            let value = match value {
+                serde_json::Value::String(s) => s.clone(),
+                other => other.to_string(),
+            };
+            (key.clone(), serde_json::Value::String(value))
+        })
+        .collect()
+}
+
+/// Format a timestamp in the text format `ClickHouse` parses for `DateTime64(3)`.
+fn timestamp(ts: DateTime<Utc>) -> String {
+    ts.format("%Y-%m-%d %H:%M:%S%.3f").to_string()
+}
+
+fn parse_rows<T: DeserializeOwned>(body: &str) -> Result<Vec<T>, StorageError> {
+    body.lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            serde_json::from_str(line)
+                .map_err(|e| StorageError::ClickHouse(format!("Unexpected row format: {e}")))
+        })
+        .collect()
+}
+
+fn http_error(e: reqwest::Error) -> StorageError {
+    StorageError::ClickHouse(e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::super::MAX_TIME_RANGE_DAYS;
+    use super::*;
+
+    fn param<'a>(query: &'a Query, name: &str) -> Option<&'a str> {
+        query
+            .params
+            .iter()
+            .find(|(key, _)| key == &format!("param_{name}"))
+            .map(|(_, value)| value.as_str())
+    }
+
+    #[test]
+    fn timestamp_uses_clickhouse_text_format() {
+        let ts = Utc.with_ymd_and_hms(2026, 3, 11, 9, 5, 7).unwrap()
+            + chrono::Duration::milliseconds(42);
+        assert_eq!(timestamp(ts), "2026-03-11 09:05:07.042");
+    }
+
+    #[test]
+    fn logs_query_only_binds_set_filters() {
+        let to = Utc::now();
+        let filter = LogFilter {
+            level: Some("ERROR".into()),
+            search: Some("50%_off".into()),
+            from: to - chrono::Duration::hours(1),
+            to,
+            limit: 500,
+            ..Default::default()
+        };
+        let query = logs_query(&filter);
+
+        assert!(query.sql.contains("level = {level:String}"));
+        assert!(!query.sql.contains("{domain:String}"));
+        assert!(!query.sql.contains("{cursor:UUID}"));
+        assert!(query.sql.ends_with("LIMIT 100"));
+        assert_eq!(param(&query, "level"), Some("ERROR"));
+        assert_eq!(param(&query, "search"), Some("%50\\%\\_off%"));
+        assert_eq!(param(&query, "domain"), None);
+    }
+
+    #[test]
+    fn logs_query_clamps_time_range() {
+        let to = Utc::now();
+        let filter = LogFilter {
+            from: to - chrono::Duration::days(90),
+            to,
+            limit: 10,
+            ..Default::default()
+        };
+        let query = logs_query(&filter);
+        let expected = timestamp(to - chrono::Duration::days(MAX_TIME_RANGE_DAYS));
+        assert_eq!(param(&query, "from"), Some(expected.as_str()));
+    }
+
+    #[test]
+    fn traces_query_filters_errors_and_cursor() {
+        let to = Utc::now();
+        let cursor = Uuid::new_v4();
+        let filter = TraceFilter {
+            is_error: true,
+            duration_min: Some(1000),
+            cursor: Some(cursor),
+            from: to - chrono::Duration::hours(1),
+            to,
+            limit: 20,
+            ..Default::default()
+        };
+        let query = traces_query(&filter);
+
+        assert!(query.sql.contains("startsWith(status_code, '5')"));
+        assert!(query.sql.contains("duration_ms >= {duration_min:Int32}"));
+        assert_eq!(param(&query, "duration_min"), Some("1000"));
+        assert_eq!(param(&query, "cursor"), Some(cursor.to_string().as_str()));
+        assert!(query.sql.ends_with("LIMIT 20"));
+    }
+
+    #[test]
+    fn trends_query_switches_to_daily_aggregates() {
+        let to = Utc::now();
+        let live = trends_query(
+            "kaiku_http_request_duration_ms",
+            to - chrono::Duration::hours(6),
+            to,
+        );
+        assert!(!live.sql.contains("toStartOfDay"));
+        assert!(live.sql.ends_with("LIMIT 1440"));
+
+        let daily = trends_query(
+            "kaiku_http_request_duration_ms",
+            to - chrono::Duration::days(7),
+            to,
+        );
+        assert!(daily.sql.contains("toStartOfDay"));
+        assert_eq!(
+            param(&daily, "metric"),
+            Some("kaiku_http_request_duration_ms")
+        );
+    }
+
+    #[test]
+    fn parse_rows_decodes_log_events() {
+        let body = concat!(
+            r#"{"id":"6f1c1a8e-3a5d-4a55-9d43-0c2a3f0b9a11","ts":"2026-03-11T09:05:07.042Z","level":"ERROR","service":"vc-server","domain":"auth","event":"login_failed","message":"bad password","trace_id":null,"span_id":null,"attrs":"{\"attempt\":3}"}"#,
+            "\n\n"
+        );
+        let rows: Vec<LogRow> = parse_rows(body).unwrap();
+        let events: Vec<LogEvent> = rows.into_iter().map(LogEvent::from).collect();
+
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].domain, "auth");
+        assert_eq!(events[0].attrs, serde_json::json!({ "attempt": 3 }));
+        assert!(events[0].trace_id.is_none());
+    }
+
+    #[test]
+    fn metric_rows_stringify_labels() {
+        let sample = CapturedMetricSample {
+            ts: Utc::now(),
+            metric_name: "kaiku_http_request_duration_ms".into(),
+            scope: "http".into(),
+            labels: serde_json::json!({ "http.route": "/api/guilds", "http.response.status_code": 200 }),
+            value_count: Some(3),
+            value_sum: None,
+            value_p50: None,
+            value_p95: Some(12.5),
+            value_p99: None,
+        };
+        let row = metric_sample_row(&sample);
+        assert_eq!(row["labels"]["http.route"], "/api/guilds");
+        assert_eq!(row["labels"]["http.response.status_code"], "200");
+        assert_eq!(row["value_count"], 3);
+    }
+}
@@ -0,0 +1,335 @@
+//! Native telemetry storage — backend trait and shared query types.
+//!
+//! Ingestion workers and the admin observability API talk to a
+//! [`TelemetryStorage`] selected at startup by `TELEMETRY_STORAGE`:
+//! - [`postgres::PostgresStorage`] (default) — the `telemetry_*` tables in the
+//!   main database.
+//! - [`clickhouse::ClickHouseStorage`] — a `ClickHouse` database over its HTTP
+//!   interface, for instances whose telemetry volume outgrows `PostgreSQL`.
+//!
+//! All queries enforce:
+//! - Max page size: 100
+//! - Max time range: 30 days
+//! - Required time filters
+//!
+//! Design reference: §11 (Data Model), §12 (API Design), §16 (Performance)
+
+pub mod clickhouse;
+pub mod postgres;
+
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+use futures::future::BoxFuture;
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, PgPool};
+use uuid::Uuid;
+
+use super::ingestion::{CapturedLogEvent, CapturedMetricSample, CapturedSpan};
+use crate::config::{ObservabilityConfig, TelemetryStorageBackend};
+
+// ============================================================================
+// Constants
+// ============================================================================
+
+const MAX_PAGE_SIZE: i64 = 100;
+const MAX_TIME_RANGE_DAYS: i64 = 30;
+/// Max rows for live trend queries (one per minute over 24h).
+const MAX_TREND_ROWS: i64 = 1440;
+
+// ============================================================================
+// Types
+// ============================================================================
+
+/// A single metric sample row.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MetricSample {
+    pub ts: DateTime<Utc>,
+    pub metric_name: String,
+    pub scope: String,
+    pub labels: serde_json::Value,
+    pub value_count: Option<i64>,
+    pub value_sum: Option<f64>,
+    pub value_p50: Option<f64>,
+    pub value_p95: Option<f64>,
+    pub value_p99: Option<f64>,
+}
+
+/// A single log event row.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct LogEvent {
+    pub id: Uuid,
+    pub ts: DateTime<Utc>,
+    pub level: String,
+    pub service: String,
+    pub domain: String,
+    pub event: String,
+    pub message: String,
+    pub trace_id: Option<String>,
+    pub span_id: Option<String>,
+    pub attrs: serde_json::Value,
+}
+
+/// A single trace index row.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct TraceIndexEntry {
+    pub id: Uuid,
+    pub trace_id: String,
+    pub span_name: String,
+    pub domain: String,
+    pub route: Option<String>,
+    pub status_code: Option<String>,
+    pub duration_ms: i32,
+    pub ts: DateTime<Utc>,
+    pub service: String,
+}
+
+/// Time-series data point for trend queries.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct TrendDataPoint {
+    pub ts: DateTime<Utc>,
+    pub metric_name: String,
+    pub value_count: Option<i64>,
+    pub value_sum: Option<f64>,
+    pub value_p50: Option<f64>,
+    pub value_p95: Option<f64>,
+    pub value_p99: Option<f64>,
+}
+
+/// Daily rollup data point for 7d/30d trend queries.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct TrendRollup {
+    pub day: DateTime<Utc>,
+    pub metric_name: String,
+    pub scope: String,
+    pub route: Option<String>,
+    pub sample_count: Option<i64>,
+    pub avg_p95: Option<f64>,
+    pub max_p95: Option<f64>,
+    pub total_count: Option<i64>,
+    pub error_count: Option<i64>,
+}
+
+/// Route ranking entry for top-routes / top-errors queries.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct TopRouteEntry {
+    pub route: Option<String>,
+    pub request_count: Option<i64>,
+    pub error_count: Option<i64>,
+    pub avg_p95: Option<f64>,
+    pub max_p95: Option<f64>,
+}
+
+/// Log query filters.
+#[derive(Debug, Clone, Default)]
+pub struct LogFilter {
+    pub level: Option<String>,
+    pub domain: Option<String>,
+    pub service: Option<String>,
+    pub search: Option<String>,
+    pub from: DateTime<Utc>,
+    pub to: DateTime<Utc>,
+    pub cursor: Option<Uuid>,
+    pub limit: i64,
+}
+
+/// Trace query filters.
+#[derive(Debug, Clone, Default)]
+pub struct TraceFilter {
+    pub status_code: Option<String>,
+    /// When true, filter to any 5xx status code (overrides `status_code`).
+    pub is_error: bool,
+    pub domain: Option<String>,
+    pub route: Option<String>,
+    pub duration_min: Option<i32>,
+    pub from: DateTime<Utc>,
+    pub to: DateTime<Utc>,
+    pub cursor: Option<Uuid>,
+    pub limit: i64,
+}
+
+/// Aggregated vital signs for the admin summary (one time window).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct VitalSigns {
+    /// Average HTTP request p95 latency.
+    pub latency_p95_ms: Option<f64>,
+    /// Sum of `kaiku_http_errors_total` samples.
+    pub error_count: Option<i64>,
+    /// Sum of `kaiku_http_requests_total` samples.
+    pub request_count: Option<i64>,
+    /// Most recent `kaiku_ws_connections_active` gauge value.
+    pub ws_connections: Option<i64>,
+    /// Most recent `kaiku_voice_sessions_active` gauge value.
+    pub voice_sessions: Option<i64>,
+    /// Number of ERROR log events.
+    pub error_log_count: i64,
+}
+
+// ============================================================================
+// Backend trait
+// ============================================================================
+
+/// Telemetry storage error.
+#[derive(Debug, thiserror::Error)]
+pub enum StorageError {
+    #[error("Database error: {0}")]
+    Postgres(#[from] sqlx::Error),
+
+    #[error("ClickHouse error: {0}")]
+    ClickHouse(String),
+}
+
+/// Storage backend for native telemetry.
+///
+/// Methods return boxed futures so the backend can be shared as
+/// `Arc<dyn TelemetryStorage>` in [`crate::api::AppState`]. Implementations
+/// apply the page size and time range limits documented on this module.
+pub trait TelemetryStorage: Send + Sync {
+    /// Which backend this is (for logging).
+    fn backend(&self) -> TelemetryStorageBackend;
+
+    /// Persist a batch of curated log events.
+    fn insert_log_events<'a>(
+        &'a self,
+        events: &'a [CapturedLogEvent],
+    ) -> BoxFuture<'a, Result<(), StorageError>>;
+
+    /// Persist a batch of trace index entries.
+    fn insert_trace_entries<'a>(
+        &'a self,
+        spans: &'a [CapturedSpan],
+    ) -> BoxFuture<'a, Result<(), StorageError>>;
+
+    /// Persist a batch of pre-aggregated metric samples.
+    fn insert_metric_samples<'a>(
+        &'a self,
+        samples: &'a [CapturedMetricSample],
+    ) -> BoxFuture<'a, Result<(), StorageError>>;
+
+    /// Metric trend data points for one metric. Ranges over one day return
+    /// daily aggregates without p50/p99.
+    fn query_trends<'a>(
+        &'a self,
+        metric_name: &'a str,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> BoxFuture<'a, Result<Vec<TrendDataPoint>, StorageError>>;
+
+    /// Paginated log events, newest first.
+    fn query_logs<'a>(
+        &'a self,
+        filter: &'a LogFilter,
+    ) -> BoxFuture<'a, Result<Vec<LogEvent>, StorageError>>;
+
+    /// Paginated trace index entries, newest first.
+    fn query_traces<'a>(
+        &'a self,
+        filter: &'a TraceFilter,
+    ) -> BoxFuture<'a, Result<Vec<TraceIndexEntry>, StorageError>>;
+
+    /// Top routes ranked by p95 latency or error count.
+    fn query_top_routes(
+        &self,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+        sort_by_errors: bool,
+        limit: i64,
+    ) -> BoxFuture<'_, Result<Vec<TopRouteEntry>, StorageError>>;
+
+    /// Top error categories grouped by the `error.type` label. The category
+    /// is returned in [`TopRouteEntry::route`].
+    fn query_top_errors(
+        &self,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+        limit: i64,
+    ) -> BoxFuture<'_, Result<Vec<TopRouteEntry>, StorageError>>;
+
+    /// Vital signs over a short window (the admin summary uses 5 minutes).
+    fn query_vital_signs(
+        &self,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> BoxFuture<'_, Result<VitalSigns, StorageError>>;
+}
+
+/// Build the telemetry backend selected in `config`.
+///
+/// The `ClickHouse` backend creates its database and tables if they are
+/// missing, so a misconfigured URL fails startup instead of silently dropping
+/// telemetry.
+pub async fn connect(
+    config: &ObservabilityConfig,
+    pool: PgPool,
+) -> Result<Arc<dyn TelemetryStorage>, StorageError> {
+    match (config.telemetry_storage, &config.clickhouse) {
+        (TelemetryStorageBackend::ClickHouse, Some(clickhouse_config)) => {
+            let storage = clickhouse::ClickHouseStorage::new(clickhouse_config)?;
+            storage.ensure_schema().await?;
+            Ok(Arc::new(storage))
+        }
+        (TelemetryStorageBackend::ClickHouse, None) => Err(StorageError::ClickHouse(
+            "CLICKHOUSE_URL is not configured".into(),
+        )),
+        (TelemetryStorageBackend::Postgres, _) => {
+            Ok(Arc::new(postgres::PostgresStorage::new(pool)))
+        }
+    }
+}
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+/// Clamp the `from` timestamp to ensure the time range does not exceed 30 days.
+fn clamp_from_time(from: DateTime<Utc>, to: DateTime<Utc>) -> DateTime<Utc> {
+    let max_from = to - chrono::Duration::days(MAX_TIME_RANGE_DAYS);
+    if from < max_from {
+        max_from
+    } else {
+        from
+    }
+}
+
+/// Escape ILIKE pattern metacharacters (`%` and `_`) in user-supplied search text.
+fn escape_ilike_pattern(input: &str) -> String {
+    input
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clamp_from_time_within_range() {
+        let to = Utc::now();
+        let from = to - chrono::Duration::days(7);
+        let clamped = clamp_from_time(from, to);
+        assert_eq!(clamped, from);
+    }
+
+    #[test]
+    fn clamp_from_time_exceeds_range() {
+        let to = Utc::now();
+        let from = to - chrono::Duration::days(60);
+        let clamped = clamp_from_time(from, to);
+        let expected = to - chrono::Duration::days(MAX_TIME_RANGE_DAYS);
+        assert_eq!(clamped, expected);
+    }
+
+    #[test]
+    fn max_page_size_is_100() {
+        assert_eq!(MAX_PAGE_SIZE, 100);
+    }
+
+    #[test]
+    fn escape_ilike_pattern_handles_metacharacters() {
+        assert_eq!(escape_ilike_pattern("hello%world"), "hello\\%world");
+        assert_eq!(escape_ilike_pattern("test_value"), "test\\_value");
+        assert_eq!(escape_ilike_pattern("normal"), "normal");
+        assert_eq!(escape_ilike_pattern("a\\b%c_d"), "a\\\\b\\%c\\_d");
+    }
+}
@@ -0,0 +1,512 @@
+//! `PostgreSQL` telemetry backend (default).
+//!
+//! Writes to and reads from the `telemetry_*` tables in the main database.
+//! Retention and the `telemetry_trend_rollups` refresh are handled by
+//! [`crate::observability::retention`].
+//!
+//! Uses runtime-checked queries (`sqlx::query` / `sqlx::query_as`) instead of
+//! compile-time macros because the telemetry tables do not exist in the offline
+//! sqlx cache yet.
+
+use chrono::{DateTime, Utc};
+use futures::future::BoxFuture;
+use futures::{FutureExt, TryFutureExt};
+use sqlx::PgPool;
+
+use super::{
+    clamp_from_time, escape_ilike_pattern, LogEvent, LogFilter, StorageError, TelemetryStorage,
+    TopRouteEntry, TraceFilter, TraceIndexEntry, TrendDataPoint, VitalSigns, MAX_PAGE_SIZE,
+    MAX_TREND_ROWS,
+};
+use crate::config::TelemetryStorageBackend;
+use crate::observability::ingestion::{CapturedLogEvent, CapturedMetricSample, CapturedSpan};
+
+/// Telemetry storage in the main `PostgreSQL` database.
+#[derive(Clone)]
+pub struct PostgresStorage {
+    pool: PgPool,
+}
+
+impl PostgresStorage {
+    /// Create a backend over the given pool.
+    #[must_use]
+    pub const fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+impl TelemetryStorage for PostgresStorage {
+    fn backend(&self) -> TelemetryStorageBackend {
+        TelemetryStorageBackend::Postgres
+    }
+
+    fn insert_log_events<'a>(
+        &'a self,
+        events: &'a [CapturedLogEvent],
+    ) -> BoxFuture<'a, Result<(), StorageError>> {
+        insert_log_events(&self.pool, events)
+            .map_err(StorageError::from)
+            .boxed()
+    }
+
+    fn insert_trace_entries<'a>(
+        &'a self,
+        spans: &'a [CapturedSpan],
+    ) -> BoxFuture<'a, Result<(), StorageError>> {
+        insert_trace_entries(&self.pool, spans)
+            .map_err(StorageError::from)
+            .boxed()
+    }
+
+    fn insert_metric_samples<'a>(
+        &'a self,
+        samples: &'a [CapturedMetricSample],
+    ) -> BoxFuture<'a, Result<(), StorageError>> {
+        insert_metric_samples(&self.pool, samples)
+            .map_err(StorageError::from)
+            .boxed()
+    }
+
+    fn query_trends<'a>(
+        &'a self,
+        metric_name: &'a str,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> BoxFuture<'a, Result<Vec<TrendDataPoint>, StorageError>> {
+        query_trends(&self.pool, metric_name, from, to)
+            .map_err(StorageError::from)
+            .boxed()
+    }
+
+    fn query_logs<'a>(
+        &'a self,
+        filter: &'a LogFilter,
+    ) -> BoxFuture<'a, Result<Vec<LogEvent>, StorageError>> {
+        query_logs(&self.pool, filter)
+            .map_err(StorageError::from)
+            .boxed()
+    }
+
+    fn query_traces<'a>(
+        &'a self,
+        filter: &'a TraceFilter,
+    ) -> BoxFuture<'a, Result<Vec<TraceIndexEntry>, StorageError>> {
+        query_traces(&self.pool, filter)
+            .map_err(StorageError::from)
+            .boxed()
+    }
+
+    fn query_top_routes(
+        &self,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+        sort_by_errors: bool,
+        limit: i64,
+    ) -> BoxFuture<'_, Result<Vec<TopRouteEntry>, StorageError>> {
+        query_top_routes(&self.pool, from, to, sort_by_errors, limit)
+            .map_err(StorageError::from)
+            .boxed()
+    }
+
+    fn query_top_errors(
+        &self,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+        limit: i64,
+    ) -> BoxFuture<'_, Result<Vec<TopRouteEntry>, StorageError>> {
+        query_top_errors(&self.pool, from, to, limit)
+            .map_err(StorageError::from)
+            .boxed()
+    }
+
+    fn query_vital_signs(
+        &self,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> BoxFuture<'_, Result<VitalSigns, StorageError>> {
+        query_vital_signs(&self.pool, from, to)
+            .map_err(StorageError::from)
+            .boxed()
+    }
+}
+
+// ============================================================================
+// Insert helpers
+// ============================================================================
+
+/// Batch-insert curated log events (WARN/ERROR only).
+async fn insert_log_events(pool: &PgPool, events: &[CapturedLogEvent]) -> Result<(), sqlx::Error> {
+    if events.is_empty() {
+        return Ok(());
+    }
+    let empty_attrs = serde_json::Value::Object(serde_json::Map::new());
+    let mut qb: sqlx::QueryBuilder<'_, sqlx::Postgres> = sqlx::QueryBuilder::new(
+        "INSERT INTO telemetry_log_events \
+         (ts, level, service, domain, event, message, trace_id, span_id, attrs) ",
+    );
+    qb.push_values(events, |mut b, event| {
+        b.push_bind(event.ts)
+            .push_bind(&event.level)
+            .push_bind(&event.service)
+            .push_bind(&event.domain)
+            .push_bind(&event.event)
+            .push_bind(&event.message)
+            .push_bind(&event.trace_id)
+            .push_bind(&event.span_id)
+            .push_bind(&empty_attrs);
+    });
+    qb.build().execute(pool).await?;
+    Ok(())
+}
+
+/// Batch-insert trace index entries (metadata only, no span payload).
+async fn insert_trace_entries(pool: &PgPool, spans: &[CapturedSpan]) -> Result<(), sqlx::Error> {
+    if spans.is_empty() {
+        return Ok(());
+    }
+    let mut qb: sqlx::QueryBuilder<'_, sqlx::Postgres> = sqlx::QueryBuilder::new(
+        "INSERT INTO telemetry_trace_index \
+         (trace_id, span_name, domain, route, status_code, duration_ms, ts, service) ",
+    );
+    qb.push_values(spans, |mut b, span| {
+        b.push_bind(&span.trace_id)
+            .push_bind(&span.span_name)
+            .push_bind(&span.domain)
+            .push_bind(&span.route)
+            .push_bind(&span.status_code)
+            .push_bind(span.duration_ms)
+            .push_bind(span.ts)
+            .push_bind(&span.service);
+    });
+    qb.build().execute(pool).await?;
+    Ok(())
+}
+
+/// Batch-insert pre-aggregated metric samples.
+async fn insert_metric_samples(
+    pool: &PgPool,
+    samples: &[CapturedMetricSample],
+) -> Result<(), sqlx::Error> {
+    if samples.is_empty() {
+        return Ok(());
+    }
+    let mut qb: sqlx::QueryBuilder<'_, sqlx::Postgres> = sqlx::QueryBuilder::new(
+        "INSERT INTO telemetry_metric_samples \
+         (ts, metric_name, scope, labels, value_count, value_sum, value_p50, value_p95, value_p99) ",
+    );
+    qb.push_values(samples, |mut b, sample| {
+        b.push_bind(sample.ts)
+            .push_bind(&sample.metric_name)
+            .push_bind(&sample.scope)
+            .push_bind(&sample.labels)
+            .push_bind(sample.value_count)
+            .push_bind(sample.value_sum)
+            .push_bind(sample.value_p50)
+            .push_bind(sample.value_p95)
+            .push_bind(sample.value_p99);
+    });
+    qb.build().execute(pool).await?;
+    Ok(())
+}
+
+// ============================================================================
+// Query helpers
+// ============================================================================
+
+/// Query metric trend data points for a given metric and time range.
+///
+/// For ranges <= 24h, queries `telemetry_metric_samples` directly (capped at
+/// 1440 rows). For 7d/30d, queries the `telemetry_trend_rollups` materialized
+/// view. p99 is not available at daily rollup granularity and is set to NULL.
+#[tracing::instrument(skip(pool))]
+async fn query_trends(
+    pool: &PgPool,
+    metric_name: &str,
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+) -> Result<Vec<TrendDataPoint>, sqlx::Error> {
+    let range = to - from;
+
+    if range.num_days() <= 1 {
+        // Live query — fine-grained data, capped to prevent unbounded results
+        sqlx::query_as::<_, TrendDataPoint>(
+            "SELECT ts, metric_name, value_count, value_sum, value_p50, value_p95, value_p99 \
+             FROM telemetry_metric_samples \
+             WHERE metric_name = $1 AND ts >= $2 AND ts <= $3 \
+             ORDER BY ts ASC \
+             LIMIT $4",
+        )
+        .bind(metric_name)
+        .bind(from)
+        .bind(to)
+        .bind(MAX_TREND_ROWS)
+        .fetch_all(pool)
+        .await
+    } else {
+        // Rollup query — daily aggregates (p99 not available at this granularity)
+        sqlx::query_as::<_, TrendDataPoint>(
+            "SELECT \
+                 day AS ts, \
+                 metric_name, \
+                 total_count AS value_count, \
+                 NULL::double precision AS value_sum, \
+                 NULL::double precision AS value_p50, \
+                 avg_p95 AS value_p95, \
+                 NULL::double precision AS value_p99 \
+             FROM telemetry_trend_rollups \
+             WHERE metric_name = $1 AND day >= $2 AND day <= $3 \
+             ORDER BY day ASC",
+        )
+        .bind(metric_name)
+        .bind(from)
+        .bind(to)
+        .fetch_all(pool)
+        .await
+    }
+}
+
+/// Query paginated log events with filters.
+///
+/// Uses composite `(ts DESC, id DESC)` ordering with a subquery-based cursor
+/// for stable chronological pagination despite UUID v4 primary keys.
+///
+/// **Search performance note:** The `ILIKE '%term%'` search uses a leading
+/// wildcard which prevents B-tree index usage, causing a sequential scan
+/// within the time range. For large tables, consider adding a GIN trigram
+/// index (`gin_trgm_ops`) or full-text search index in a future iteration.
+#[tracing::instrument(skip(pool))]
+async fn query_logs(pool: &PgPool, filter: &LogFilter) -> Result<Vec<LogEvent>, sqlx::Error> {
+    let limit = filter.limit.min(MAX_PAGE_SIZE);
+    let from = clamp_from_time(filter.from, filter.to);
+    let search = filter.search.as_deref().map(escape_ilike_pattern);
+
+    sqlx::query_as::<_, LogEvent>(
+        "SELECT id, ts, level, service, domain, event, message, trace_id, span_id, attrs \
+         FROM telemetry_log_events \
+         WHERE ts >= $1 \
+           AND ts <= $2 \
+           AND ($3::text IS NULL OR level = $3) \
+           AND ($4::text IS NULL OR domain = $4) \
+           AND ($5::text IS NULL OR service = $5) \
+           AND ($6::text IS NULL OR event ILIKE '%' || $6 || '%' OR message ILIKE '%' || $6 || '%') \
+           AND ($7::uuid IS NULL OR (ts, id) < ((SELECT ts FROM telemetry_log_events WHERE id = $7), $7)) \
+         ORDER BY ts DESC, id DESC \
+         LIMIT $8",
+    )
+    .bind(from)
+    .bind(filter.to)
+    .bind(filter.level.as_deref())
+    .bind(filter.domain.as_deref())
+    .bind(filter.service.as_deref())
+    .bind(search.as_deref())
+    .bind(filter.cursor)
+    .bind(limit)
+    .fetch_all(pool)
+    .await
+}
+
+/// Query paginated trace index entries with filters.
+///
+/// Uses composite `(ts DESC, id DESC)` ordering with a subquery-based cursor
+/// for stable chronological pagination despite UUID v4 primary keys.
+#[tracing::instrument(skip(pool))]
+async fn query_traces(
+    pool: &PgPool,
+    filter: &TraceFilter,
+) -> Result<Vec<TraceIndexEntry>, sqlx::Error> {
+    let limit = filter.limit.min(MAX_PAGE_SIZE);
+    let from = clamp_from_time(filter.from, filter.to);
+
+    sqlx::query_as::<_, TraceIndexEntry>(
+        "SELECT id, trace_id, span_name, domain, route, status_code, duration_ms, ts, service \
+         FROM telemetry_trace_index \
+         WHERE ts >= $1 \
+           AND ts <= $2 \
+           AND ($3::text IS NULL OR status_code = $3) \
+           AND ($4::bool IS NOT TRUE OR status_code LIKE '5%') \
+           AND ($5::text IS NULL OR domain = $5) \
+           AND ($6::text IS NULL OR route = $6) \
+           AND ($7::int IS NULL OR duration_ms >= $7) \
+           AND ($8::uuid IS NULL OR (ts, id) < ((SELECT ts FROM telemetry_trace_index WHERE id = $8), $8)) \
+         ORDER BY ts DESC, id DESC \
+         LIMIT $9",
+    )
+    .bind(from)
+    .bind(filter.to)
+    .bind(filter.status_code.as_deref())
+    .bind(filter.is_error)
+    .bind(filter.domain.as_deref())
+    .bind(filter.route.as_deref())
+    .bind(filter.duration_min)
+    .bind(filter.cursor)
+    .bind(limit)
+    .fetch_all(pool)
+    .await
+}
+
+/// Query top routes ranked by p95 latency or error count.
+#[tracing::instrument(skip(pool))]
+async fn query_top_routes(
+    pool: &PgPool,
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+    sort_by_errors: bool,
+    limit: i64,
+) -> Result<Vec<TopRouteEntry>, sqlx::Error> {
+    let limit = limit.min(MAX_PAGE_SIZE);
+    let from = clamp_from_time(from, to);
+
+    // Two separate query strings to avoid format! SQL injection pattern.
+    // Safe status_code cast: only cast values matching digit-only pattern.
+    const BASE: &str = "\
+        SELECT \
+             labels->>'http.route' AS route, \
+             SUM(value_count) AS request_count, \
+             SUM(CASE \
+                 WHEN labels->>'http.response.status_code' ~ '^\\d+$' \
+                      AND (labels->>'http.response.status_code')::int >= 500 \
+                 THEN value_count ELSE 0 END) AS error_count, \
+             AVG(value_p95) AS avg_p95, \
+             MAX(value_p95) AS max_p95 \
+         FROM telemetry_metric_samples \
+         WHERE metric_name = 'kaiku_http_request_duration_ms' \
+           AND ts >= $1 AND ts <= $2 \
+           AND labels->>'http.route' IS NOT NULL \
+         GROUP BY labels->>'http.route'";
+
+    const ORDER_BY_ERRORS: &str = " ORDER BY error_count DESC NULLS LAST LIMIT $3";
+    const ORDER_BY_P95: &str = " ORDER BY avg_p95 DESC NULLS LAST LIMIT $3";
+
+    let sql = if sort_by_errors {
+        format!("{BASE}{ORDER_BY_ERRORS}")
+    } else {
+        format!("{BASE}{ORDER_BY_P95}")
+    };
+
+    sqlx::query_as::<_, TopRouteEntry>(&sql)
+        .bind(from)
+        .bind(to)
+        .bind(limit)
+        .fetch_all(pool)
+        .await
+}
+
+/// Query top error categories grouped by `error.type` label.
+#[tracing::instrument(skip(pool))]
+async fn query_top_errors(
+    pool: &PgPool,
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+    limit: i64,
+) -> Result<Vec<TopRouteEntry>, sqlx::Error> {
+    let limit = limit.min(MAX_PAGE_SIZE);
+    let from = clamp_from_time(from, to);
+
+    sqlx::query_as::<_, TopRouteEntry>(
+        "SELECT \
+             labels->>'error.type' AS route, \
+             SUM(value_count) AS request_count, \
+             SUM(value_count) AS error_count, \
+             AVG(value_p95) AS avg_p95, \
+             MAX(value_p95) AS max_p95 \
+         FROM telemetry_metric_samples \
+         WHERE metric_name = 'kaiku_http_errors_total' \
+           AND ts >= $1 AND ts <= $2 \
+           AND labels->>'error.type' IS NOT NULL \
+         GROUP BY labels->>'error.type' \
+         ORDER BY error_count DESC NULLS LAST \
+         LIMIT $3",
+    )
+    .bind(from)
+    .bind(to)
+    .bind(limit)
+    .fetch_all(pool)
+    .await
+}
+
+/// Query latency, error totals, the latest connection gauges and the ERROR
+/// log count for a short window.
+#[tracing::instrument(skip(pool))]
+async fn query_vital_signs(
+    pool: &PgPool,
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+) -> Result<VitalSigns, sqlx::Error> {
+    let (latency_p95_ms, error_metrics, ws_connections, voice_sessions, error_log_count) = tokio::try_join!(
+        // Latency p95
+        async {
+            sqlx::query_scalar::<_, Option<f64>>(
+                "SELECT AVG(value_p95) FROM telemetry_metric_samples \
+                 WHERE metric_name = 'kaiku_http_request_duration_ms' \
+                 AND ts >= $1 AND ts <= $2",
+            )
+            .bind(from)
+            .bind(to)
+            .fetch_optional(pool)
+            .await
+            .map(|r| r.flatten())
+        },
+        // Error and request totals
+        async {
+            sqlx::query_as::<_, (Option<i64>, Option<i64>)>(
+                "SELECT \
+                     SUM(CASE WHEN metric_name = 'kaiku_http_errors_total' THEN value_count ELSE 0 END), \
+                     SUM(CASE WHEN metric_name = 'kaiku_http_requests_total' THEN value_count ELSE 0 END) \
+                 FROM telemetry_metric_samples \
+                 WHERE metric_name IN ('kaiku_http_errors_total', 'kaiku_http_requests_total') \
+                 AND ts >= $1 AND ts <= $2",
+            )
+            .bind(from)
+            .bind(to)
+            .fetch_optional(pool)
+            .await
+        },
+        // Active WebSocket connections (most recent gauge)
+        async {
+            sqlx::query_scalar::<_, Option<i64>>(
+                "SELECT value_count FROM telemetry_metric_samples \
+                 WHERE metric_name = 'kaiku_ws_connections_active' \
+                 AND ts >= $1 \
+                 ORDER BY ts DESC LIMIT 1",
+            )
+            .bind(from)
+            .fetch_optional(pool)
+            .await
+            .map(|r| r.flatten())
+        },
+        // Active voice sessions (most recent gauge)
+        async {
+            sqlx::query_scalar::<_, Option<i64>>(
+                "SELECT value_count FROM telemetry_metric_samples \
+                 WHERE metric_name = 'kaiku_voice_sessions_active' \
+                 AND ts >= $1 \
+                 ORDER BY ts DESC LIMIT 1",
+            )
+            .bind(from)
+            .fetch_optional(pool)
+            .await
+            .map(|r| r.flatten())
+        },
+        // ERROR log events
+        async {
+            sqlx::query_scalar::<_, i64>(
+                "SELECT COUNT(*) FROM telemetry_log_events \
+                 WHERE level = 'ERROR' AND ts >= $1",
+            )
+            .bind(from)
+            .fetch_one(pool)
+            .await
+        },
+    )?;
+
+    let (error_count, request_count) = error_metrics.unwrap_or_default();
+
+    Ok(VitalSigns {
+        latency_p95_ms,
+        error_count,
+        request_count,
+        ws_connections,
+        voice_sessions,
+        error_log_count,
+    })
+}
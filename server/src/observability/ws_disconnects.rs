@@ -0,0 +1,233 @@
+//! WebSocket disconnect audit.
+//!
+//! Every closed client gateway connection is recorded in
+//! `telemetry_ws_disconnects` with its cause, the close code of the closing
+//! handshake (if there was one), and how long the session lasted. The Command
+//! Center breaks these down by cause, close code, time, and user so reconnect
+//! storms can be traced to a cause (clients dropping, server timeouts, rate
+//! limiting, stale tokens, deploys).
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// Why a gateway connection ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DisconnectCause {
+    /// The client sent a close frame.
+    ClientClose,
+    /// The transport failed or the stream ended without a close frame.
+    ConnectionLost,
+    /// Nothing was received (not even a pong) within the idle timeout.
+    Timeout,
+    /// The client exceeded the per-connection message rate.
+    RateLimited,
+    /// The handshake was rejected because the access token had expired.
+    AuthExpired,
+    /// The server is shutting down.
+    ServerShutdown,
+    /// An admin suspended the account or revoked its sessions.
+    SessionRevoked,
+}
+
+impl DisconnectCause {
+    /// Label stored in the database and used as the metric attribute.
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::ClientClose => "client_close",
+            Self::ConnectionLost => "connection_lost",
+            Self::Timeout => "timeout",
+            Self::RateLimited => "rate_limited",
+            Self::AuthExpired => "auth_expired",
+            Self::ServerShutdown => "server_shutdown",
+            Self::SessionRevoked => "session_revoked",
+        }
+    }
+}
+
+/// A single disconnect to record.
+#[derive(Debug, Clone, Copy)]
+pub struct Disconnect {
+    /// `None` when the handshake was rejected before the user was known.
+    pub user_id: Option<Uuid>,
+    pub cause: DisconnectCause,
+    /// Close code sent or received in the closing handshake.
+    pub close_code: Option<u16>,
+    /// Session length; `None` if the connection was never established.
+    pub session_secs: Option<f64>,
+}
+
+/// Record a disconnect in the metrics counter and the audit table.
+///
+/// Failures are logged and otherwise ignored; losing an audit row must never
+/// affect connection cleanup.
+pub async fn record_disconnect(pool: &PgPool, disconnect: Disconnect) {
+    super::metrics::record_ws_disconnect_cause(disconnect.cause.as_str());
+
+    let result = sqlx::query(
+        "INSERT INTO telemetry_ws_disconnects (user_id, cause, close_code, session_secs) \
+         VALUES ($1, $2, $3, $4)",
+    )
+    .bind(disconnect.user_id)
+    .bind(disconnect.cause.as_str())
+    .bind(disconnect.close_code.map(i32::from))
+    .bind(disconnect.session_secs)
+    .execute(pool)
+    .await;
+
+    if let Err(e) = result {
+        tracing::warn!(error = %e, cause = disconnect.cause.as_str(), "Failed to record WS disconnect");
+    }
+}
+
+// ============================================================================
+// Queries
+// ============================================================================
+
+/// Disconnect count for one cause.
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+pub struct CauseCount {
+    pub cause: String,
+    pub count: i64,
+    /// Average session length (`None` for rejected handshakes only).
+    pub avg_session_secs: Option<f64>,
+}
+
+/// Disconnect count for one close code (`None` = no closing handshake).
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+pub struct CloseCodeCount {
+    pub close_code: Option<i32>,
+    pub count: i64,
+}
+
+/// Disconnects per cause in one time bucket.
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+pub struct DisconnectBucket {
+    pub bucket: DateTime<Utc>,
+    pub cause: String,
+    pub count: i64,
+}
+
+/// A user who disconnected most often.
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+pub struct UserDisconnectCount {
+    pub user_id: Uuid,
+    pub username: Option<String>,
+    pub count: i64,
+}
+
+/// Disconnect breakdown over a time range.
+#[derive(Debug, Clone, Serialize)]
+pub struct DisconnectBreakdown {
+    pub total: i64,
+    pub by_cause: Vec<CauseCount>,
+    pub by_close_code: Vec<CloseCodeCount>,
+    /// Width of each timeline bucket.
+    pub bucket_secs: i64,
+    pub timeline: Vec<DisconnectBucket>,
+    pub top_users: Vec<UserDisconnectCount>,
+}
+
+/// Timeline bucket width: about 60 buckets per range, at least one minute.
+fn bucket_secs(from: DateTime<Utc>, to: DateTime<Utc>) -> i64 {
+    ((to - from).num_seconds() / 60).max(60)
+}
+
+/// Break down disconnects in `[from, to)` by cause, close code, time, and user.
+#[tracing::instrument(skip(pool))]
+pub async fn query_disconnect_breakdown(
+    pool: &PgPool,
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+    top_users_limit: i64,
+) -> Result<DisconnectBreakdown, sqlx::Error> {
+    let by_cause = sqlx::query_as::<_, CauseCount>(
+        "SELECT cause, COUNT(*) AS count, AVG(session_secs) AS avg_session_secs \
+         FROM telemetry_ws_disconnects \
+         WHERE ts >= $1 AND ts < $2 \
+         GROUP BY cause \
+         ORDER BY count DESC",
+    )
+    .bind(from)
+    .bind(to)
+    .fetch_all(pool)
+    .await?;
+
+    let by_close_code = sqlx::query_as::<_, CloseCodeCount>(
+        "SELECT close_code, COUNT(*) AS count \
+         FROM telemetry_ws_disconnects \
+         WHERE ts >= $1 AND ts < $2 \
+         GROUP BY close_code \
+         ORDER BY count DESC",
+    )
+    .bind(from)
+    .bind(to)
+    .fetch_all(pool)
+    .await?;
+
+    let bucket_secs = bucket_secs(from, to);
+    let timeline = sqlx::query_as::<_, DisconnectBucket>(
+        "SELECT date_bin(make_interval(secs => $3), ts, $1) AS bucket, cause, COUNT(*) AS count \
+         FROM telemetry_ws_disconnects \
+         WHERE ts >= $1 AND ts < $2 \
+         GROUP BY bucket, cause \
+         ORDER BY bucket ASC, cause",
+    )
+    .bind(from)
+    .bind(to)
+    .bind(bucket_secs as f64)
+    .fetch_all(pool)
+    .await?;
+
+    let top_users = sqlx::query_as::<_, UserDisconnectCount>(
+        "SELECT d.user_id AS user_id, u.username, COUNT(*) AS count \
+         FROM telemetry_ws_disconnects d \
+         LEFT JOIN users u ON u.id = d.user_id \
+         WHERE d.ts >= $1 AND d.ts < $2 AND d.user_id IS NOT NULL \
+         GROUP BY d.user_id, u.username \
+         ORDER BY count DESC \
+         LIMIT $3",
+    )
+    .bind(from)
+    .bind(to)
+    .bind(top_users_limit)
+    .fetch_all(pool)
+    .await?;
+
+    let total = by_cause.iter().map(|c| c.count).sum();
+
+    Ok(DisconnectBreakdown {
+        total,
+        by_cause,
+        by_close_code,
+        bucket_secs,
+        timeline,
+        top_users,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bucket_secs_scales_with_range() {
+        let to = Utc::now();
+        assert_eq!(bucket_secs(to - chrono::Duration::minutes(30), to), 60);
+        assert_eq!(bucket_secs(to - chrono::Duration::hours(1), to), 60);
+        assert_eq!(bucket_secs(to - chrono::Duration::hours(24), to), 1440);
+        assert_eq!(bucket_secs(to - chrono::Duration::days(7), to), 10_080);
+    }
+
+    #[test]
+    fn cause_labels_are_stable() {
+        assert_eq!(DisconnectCause::ClientClose.as_str(), "client_close");
+        assert_eq!(DisconnectCause::ConnectionLost.as_str(), "connection_lost");
+        assert_eq!(DisconnectCause::Timeout.as_str(), "timeout");
+        assert_eq!(DisconnectCause::RateLimited.as_str(), "rate_limited");
+        assert_eq!(DisconnectCause::AuthExpired.as_str(), "auth_expired");
+        assert_eq!(DisconnectCause::ServerShutdown.as_str(), "server_shutdown");
+        assert_eq!(DisconnectCause::SessionRevoked.as_str(), "session_revoked");
+    }
+}
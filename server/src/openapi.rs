@@ -7,7 +7,7 @@
     reason = "triggered by utoipa OpenApi derive macro"
 )]
 
-use utoipa::openapi::security::{HttpAuthScheme, HttpBuilder, SecurityScheme};
+use utoipa::openapi::security::{ApiKey, ApiKeyValue, HttpAuthScheme, HttpBuilder, SecurityScheme};
 use utoipa::{Modify, OpenApi};
 
 /// `OpenAPI` documentation for the `Kaiku` API.
@@ -44,11 +44,13 @@ use utoipa::{Modify, OpenApi};
         (name = "reactions", description = "Message reactions"),
         (name = "unread", description = "Unread message tracking"),
         (name = "preferences", description = "User preferences"),
+        (name = "drafts", description = "Message drafts"),
         (name = "pages", description = "Platform and guild pages"),
         (name = "connectivity", description = "Connection and session info"),
         (name = "discovery", description = "Public guild discovery and browsing"),
         (name = "governance", description = "Data export and account deletion"),
         (name = "workspaces", description = "Personal workspace management"),
+        (name = "push", description = "Push notification devices"),
         (name = "settings", description = "Server settings and configuration"),
         (name = "setup", description = "Initial server setup"),
         (name = "uploads", description = "File upload operations"),
@@ -65,19 +67,43 @@ use utoipa::{Modify, OpenApi};
         crate::auth::handlers::refresh_token,
         crate::auth::handlers::forgot_password,
         crate::auth::handlers::reset_password,
+        crate::auth::email_verification::confirm_verification,
         crate::auth::handlers::oidc_providers,
         crate::auth::handlers::oidc_authorize,
         crate::auth::handlers::oidc_callback,
         // Auth - protected
         crate::auth::handlers::logout,
+        crate::auth::email_verification::resend_verification,
         crate::auth::handlers::get_profile,
         crate::auth::handlers::update_profile,
+        crate::auth::handlers::update_password,
+        crate::auth::username::change_username,
+        crate::auth::profile::update_profile_customization,
+        crate::auth::profile::upload_banner,
+        crate::auth::profile::delete_banner,
+        crate::auth::auth_methods::list_auth_methods,
+        crate::auth::auth_methods::link_auth_method,
+        crate::auth::auth_methods::start_oidc_reauth,
+        crate::auth::auth_methods::unlink_password,
+        crate::auth::auth_methods::unlink_identity,
         crate::auth::handlers::upload_avatar,
         crate::auth::handlers::mfa_setup,
         crate::auth::handlers::mfa_verify,
         crate::auth::handlers::mfa_disable,
         crate::auth::handlers::mfa_generate_backup_codes,
         crate::auth::handlers::mfa_backup_code_count,
+        // Auth - device linking
+        crate::auth::device_link::start_link,
+        crate::auth::device_link::get_link,
+        crate::auth::device_link::approve_link,
+        crate::auth::device_link::reject_link,
+        crate::auth::device_link::claim_link,
+        crate::auth::webauthn::list_passkeys,
+        crate::auth::webauthn::start_registration,
+        crate::auth::webauthn::finish_registration,
+        crate::auth::webauthn::delete_passkey,
+        crate::auth::webauthn::start_login,
+        crate::auth::webauthn::finish_login,
         // Channels
         crate::chat::channels::create,
         crate::chat::channels::get,
@@ -87,11 +113,27 @@ use utoipa::{Modify, OpenApi};
         crate::chat::channels::add_member,
         crate::chat::channels::remove_member,
         crate::chat::channels::mark_as_read,
+        crate::chat::gallery::list_channel_attachments,
+        crate::chat::pins::list_pins,
+        crate::chat::pins::pin_message,
+        crate::chat::pins::unpin_message,
+        crate::chat::exports::create_export,
+        crate::chat::exports::list_exports,
+        crate::chat::exports::get_export,
+        crate::chat::feeds::list_feed_tokens,
+        crate::chat::feeds::create_feed_token,
+        crate::chat::feeds::delete_feed_token,
+        crate::chat::feeds::get_feed,
+        crate::chat::incoming_webhooks::list_webhooks,
+        crate::chat::incoming_webhooks::create_webhook,
+        crate::chat::incoming_webhooks::delete_webhook,
+        crate::chat::incoming_webhooks::execute_webhook,
         // Messages
         crate::chat::messages::list,
         crate::chat::messages::create,
         crate::chat::messages::update,
         crate::chat::messages::delete,
+        crate::chat::messages::forward,
         crate::chat::messages::list_thread_replies,
         crate::chat::messages::mark_thread_read,
         // Uploads
@@ -107,6 +149,7 @@ use utoipa::{Modify, OpenApi};
         crate::chat::dm::leave_dm,
         crate::chat::dm::update_dm_name,
         crate::chat::dm::mark_as_read,
+        crate::chat::dm_state::update_dm_state,
         crate::chat::dm::mark_all_dms_read,
         crate::chat::dm::upload_dm_icon,
         crate::chat::dm::get_dm_icon,
@@ -125,8 +168,24 @@ use utoipa::{Modify, OpenApi};
         crate::guild::handlers::leave_guild,
         crate::guild::handlers::list_members,
         crate::guild::handlers::kick_member,
+        crate::guild::bans::list_bans,
+        crate::guild::bans::ban_member,
+        crate::guild::bans::unban_member,
+        crate::guild::timeouts::set_member_timeout,
+        crate::guild::member_profiles::update_member,
+        crate::guild::retention::get_retention,
+        crate::guild::retention::set_guild_retention,
+        crate::guild::retention::set_channel_retention,
+        crate::guild::log_stream::get_log_stream,
+        crate::guild::log_stream::set_log_stream,
+        crate::guild::log_stream::delete_log_stream,
+        crate::guild::onboarding::get_onboarding,
+        crate::guild::onboarding::set_onboarding,
+        crate::guild::onboarding::complete_onboarding,
+        crate::guild::audit::list_audit_log,
         crate::guild::handlers::list_channels,
         crate::guild::handlers::reorder_channels,
+        crate::guild::channel_positions::update_channel_positions,
         crate::guild::handlers::mark_all_channels_read,
         crate::guild::handlers::list_guild_bots,
         crate::guild::handlers::add_bot_to_guild,
@@ -134,19 +193,32 @@ use utoipa::{Modify, OpenApi};
         crate::guild::handlers::list_guild_commands,
         crate::guild::handlers::get_guild_settings,
         crate::guild::handlers::update_guild_settings,
+        crate::guild::images::upload_icon,
+        crate::guild::images::delete_icon,
+        crate::guild::images::upload_banner,
+        crate::guild::images::delete_banner,
+        crate::guild::images::upload_member_avatar,
         crate::guild::handlers::get_guild_usage,
+        crate::guild::perks::get_guild_perks,
         // Roles
         crate::guild::roles::list_roles,
         crate::guild::roles::create_role,
         crate::guild::roles::update_role,
         crate::guild::roles::delete_role,
+        crate::guild::roles::reorder_roles,
+        crate::guild::roles::list_member_roles,
         crate::guild::roles::assign_role,
         crate::guild::roles::remove_role,
+        crate::guild::roles::bulk_update_member_roles,
+        crate::guild::member_import::start_member_import,
+        crate::guild::member_import::get_member_import,
+        crate::guild::member_import::download_member_import_report,
         // Invites
         crate::guild::invites::list_invites,
         crate::guild::invites::create_invite,
         crate::guild::invites::delete_invite,
         crate::guild::invites::join_via_invite,
+        crate::guild::invites::preview_invite,
         // Categories
         crate::guild::categories::list_categories,
         crate::guild::categories::create_category,
@@ -155,15 +227,19 @@ use utoipa::{Modify, OpenApi};
         crate::guild::categories::reorder_categories,
         // Emojis
         crate::guild::emojis::list_emojis,
+        crate::guild::emojis::search_emojis,
         crate::guild::emojis::get_emoji,
         crate::guild::emojis::create_emoji,
         crate::guild::emojis::update_emoji,
         crate::guild::emojis::delete_emoji,
         // Guild Search
         crate::guild::search::search_messages,
+        crate::guild::autocomplete::autocomplete,
         // Discovery
         crate::discovery::handlers::browse_guilds,
+        crate::discovery::handlers::recommended_guilds,
         crate::discovery::handlers::join_discoverable,
+        crate::discovery::review::report_listing,
         // Guild Pages
         crate::pages::handlers::list_guild_pages,
         crate::pages::handlers::create_guild_page,
@@ -172,6 +248,15 @@ use utoipa::{Modify, OpenApi};
         crate::pages::handlers::reorder_guild_pages,
         crate::pages::handlers::get_guild_page,
         crate::pages::handlers::accept_guild_page,
+        crate::pages::handlers::list_guild_page_revisions,
+        crate::pages::handlers::get_guild_page_revision,
+        crate::pages::handlers::restore_guild_page_revision,
+        // Guild Page Categories
+        crate::pages::handlers::list_guild_categories,
+        crate::pages::handlers::create_guild_category,
+        crate::pages::handlers::update_guild_category,
+        crate::pages::handlers::delete_guild_category,
+        crate::pages::handlers::reorder_guild_categories,
         // Admin
         crate::admin::handlers::get_admin_status,
         crate::admin::handlers::get_admin_stats,
@@ -183,20 +268,54 @@ use utoipa::{Modify, OpenApi};
         crate::admin::handlers::list_guilds,
         crate::admin::handlers::export_guilds_csv,
         crate::admin::handlers::get_guild_details,
+        crate::admin::handlers::get_guild_page_limits,
+        crate::admin::handlers::set_guild_page_limits,
+        crate::admin::retention::get_retention_settings,
+        crate::admin::retention::update_retention_settings,
+        crate::admin::settings::get_server_settings,
+        crate::admin::settings::update_server_settings,
+        crate::admin::settings::reload_config,
         crate::admin::handlers::get_audit_log,
+        crate::admin::observability::capacity_report,
+        crate::admin::cluster::list_nodes,
+        crate::admin::voice::list_voice_rooms,
+        crate::admin::voice::get_voice_room,
+        crate::admin::observability::summary,
+        crate::admin::observability::trends,
+        crate::admin::observability::top_routes,
+        crate::admin::observability::top_errors,
+        crate::admin::observability::top_consumers,
+        crate::admin::observability::ws_disconnect_breakdown,
+        crate::admin::observability::report_sla,
+        crate::admin::observability::logs,
+        crate::admin::observability::traces,
+        crate::admin::observability::links,
         crate::moderation::admin_handlers::list_reports,
         crate::moderation::admin_handlers::report_stats,
         crate::moderation::admin_handlers::get_report,
         crate::moderation::admin_handlers::claim_report,
+        crate::moderation::admin_handlers::unclaim_report,
         crate::moderation::admin_handlers::resolve_report,
+        crate::moderation::admin_handlers::list_notes,
+        crate::moderation::admin_handlers::create_note,
+        crate::moderation::admin_handlers::list_evidence,
+        crate::moderation::admin_handlers::add_evidence,
         crate::admin::handlers::ban_user,
         crate::admin::handlers::unban_user,
         crate::admin::handlers::bulk_ban_users,
         crate::admin::handlers::delete_user,
+        crate::admin::users::suspend_user,
+        crate::admin::users::unsuspend_user,
+        crate::admin::users::revoke_user_sessions,
+        crate::admin::users::send_password_reset,
+        crate::admin::users::grant_admin,
+        crate::admin::users::revoke_admin,
         crate::admin::handlers::suspend_guild,
         crate::admin::handlers::unsuspend_guild,
         crate::admin::handlers::bulk_suspend_guilds,
         crate::admin::handlers::delete_guild,
+        crate::admin::handlers::set_guild_supporter,
+        crate::admin::entitlements::receive_entitlement_webhook,
         crate::admin::handlers::create_announcement,
         crate::admin::handlers::get_auth_settings,
         crate::admin::handlers::update_auth_settings,
@@ -204,6 +323,21 @@ use utoipa::{Modify, OpenApi};
         crate::admin::handlers::create_oidc_provider,
         crate::admin::handlers::update_oidc_provider,
         crate::admin::handlers::delete_oidc_provider,
+        crate::admin::attachments::list_held,
+        crate::admin::attachments::release,
+        crate::admin::attachments::delete,
+        crate::admin::webhooks::list_dead_letters,
+        crate::admin::webhooks::replay_dead_letter,
+        crate::admin::webhooks::replay_dead_letters,
+        crate::admin::discovery::list_queue,
+        crate::admin::discovery::approve,
+        crate::admin::discovery::reject,
+        crate::admin::registrations::list_pending,
+        crate::admin::registrations::approve,
+        crate::admin::registrations::reject,
+        crate::admin::registrations::list_codes,
+        crate::admin::registrations::create_code,
+        crate::admin::registrations::delete_code,
         // Moderation
         crate::moderation::handlers::create_report,
         crate::moderation::filter_handlers::list_filter_configs,
@@ -212,6 +346,19 @@ use utoipa::{Modify, OpenApi};
         crate::moderation::filter_handlers::create_custom_pattern,
         crate::moderation::filter_handlers::update_custom_pattern,
         crate::moderation::filter_handlers::delete_custom_pattern,
+        crate::moderation::filter_handlers::list_link_domains,
+        crate::moderation::filter_handlers::add_link_domain,
+        crate::moderation::filter_handlers::delete_link_domain,
+        crate::moderation::filter_handlers::list_language_rules,
+        crate::moderation::filter_handlers::set_language_rule,
+        crate::moderation::filter_handlers::delete_language_rule,
+        crate::moderation::filter_handlers::get_normalization,
+        crate::moderation::filter_handlers::update_normalization,
+        crate::moderation::filter_handlers::list_escalation_policies,
+        crate::moderation::filter_handlers::create_escalation_policy,
+        crate::moderation::filter_handlers::update_escalation_policy,
+        crate::moderation::filter_handlers::delete_escalation_policy,
+        crate::moderation::filter_handlers::evaluate_escalation,
         crate::moderation::filter_handlers::list_moderation_log,
         crate::moderation::filter_handlers::test_filter,
         // Social
@@ -226,6 +373,10 @@ use utoipa::{Modify, OpenApi};
         crate::social::friends::remove_friend,
         // Voice
         crate::voice::handlers::get_ice_servers,
+        crate::voice::regions::get_regions,
+        crate::voice::recording::start_recording,
+        crate::voice::recording::stop_recording_handler,
+        crate::voice::recording::list_recordings,
         crate::voice::call_handlers::get_call,
         crate::voice::call_handlers::start_call,
         crate::voice::call_handlers::join_call,
@@ -256,6 +407,7 @@ use utoipa::{Modify, OpenApi};
         crate::api::commands::register_commands,
         crate::api::commands::delete_all_commands,
         crate::api::commands::delete_command,
+        crate::api::interactions::create_interaction_response,
         // Webhooks
         crate::webhooks::handlers::list_webhooks,
         crate::webhooks::handlers::create_webhook,
@@ -290,12 +442,20 @@ use utoipa::{Modify, OpenApi};
         crate::workspaces::handlers::remove_entry,
         crate::workspaces::handlers::reorder_entries,
         crate::workspaces::handlers::reorder_workspaces,
+        // Push
+        crate::push::handlers::get_config,
+        crate::push::handlers::register_device,
+        crate::push::handlers::list_devices,
+        crate::push::handlers::delete_device,
         // Unread
         crate::api::unread::get_unread_aggregate,
         crate::api::unread::mark_all_read,
         // Preferences
         crate::api::preferences::get_preferences,
         crate::api::preferences::update_preferences,
+        // Drafts
+        crate::api::drafts::list_drafts,
+        crate::api::drafts::save_draft,
         // Connectivity
         crate::connectivity::handlers::get_summary,
         crate::connectivity::handlers::get_sessions,
@@ -309,6 +469,9 @@ use utoipa::{Modify, OpenApi};
         crate::pages::handlers::update_platform_page,
         crate::pages::handlers::delete_platform_page,
         crate::pages::handlers::accept_page,
+        crate::pages::handlers::list_platform_page_revisions,
+        crate::pages::handlers::get_platform_page_revision,
+        crate::pages::handlers::restore_platform_page_revision,
         // Settings
         crate::api::settings::get_server_settings,
         crate::api::settings::get_upload_limits,
@@ -335,25 +498,44 @@ use utoipa::{Modify, OpenApi};
         crate::auth::handlers::RefreshRequest,
         crate::auth::handlers::LogoutRequest,
         crate::auth::handlers::AuthResponse,
+        crate::auth::handlers::RegistrationPendingResponse,
         crate::auth::handlers::UserProfile,
         crate::auth::handlers::MfaSetupResponse,
         crate::auth::handlers::MfaBackupCodesResponse,
         crate::auth::handlers::MfaBackupCodeCountResponse,
         crate::auth::handlers::MfaVerifyRequest,
         crate::auth::handlers::UpdateProfileRequest,
+        crate::auth::profile::UpdateProfileCustomizationRequest,
         crate::auth::handlers::UpdateProfileResponse,
         crate::auth::handlers::ForgotPasswordRequest,
         crate::auth::handlers::ResetPasswordRequest,
+        crate::auth::email_verification::VerifyEmailRequest,
+        crate::auth::device_link::StartDeviceLinkRequest,
+        crate::auth::device_link::DeviceLinkStarted,
+        crate::auth::device_link::DeviceLinkDetails,
+        crate::auth::device_link::ApproveDeviceLinkRequest,
+        crate::auth::device_link::ClaimDeviceLinkRequest,
+        crate::auth::device_link::DeviceLinkSession,
+        crate::auth::webauthn::PasskeyInfo,
+        crate::auth::webauthn::StartPasskeyRegistrationRequest,
+        crate::auth::webauthn::PasskeyRegistrationChallenge,
+        crate::auth::webauthn::FinishPasskeyRegistrationRequest,
+        crate::auth::webauthn::StartPasskeyLoginRequest,
+        crate::auth::webauthn::PasskeyLoginChallenge,
+        crate::auth::webauthn::PasskeyAssertion,
         crate::auth::error::ErrorResponse,
         // DB Models
         crate::db::AuthMethod,
         crate::db::UserStatus,
         crate::db::ChannelType,
+        crate::db::MessageType,
         crate::db::Channel,
         // Note: db::User intentionally excluded — contains password_hash, mfa_secret
         crate::db::Message,
+        crate::db::LinkEmbed,
         crate::db::Role,
         crate::db::FileAttachment,
+        crate::db::AttachmentKind,
         crate::db::PublicOidcProvider,
         crate::db::AuthMethodsConfig,
         crate::db::ChannelUnread,
@@ -374,13 +556,33 @@ use utoipa::{Modify, OpenApi};
         crate::chat::messages::AttachmentInfo,
         crate::chat::messages::MentionType,
         crate::chat::messages::ThreadInfoResponse,
+        crate::chat::messages::ForwardedFrom,
         crate::chat::messages::MessageResponse,
         crate::chat::messages::ReactionInfo,
         crate::chat::messages::ListMessagesQuery,
         crate::chat::messages::CreateMessageRequest,
         crate::chat::messages::ListThreadRepliesQuery,
         crate::chat::messages::UpdateMessageRequest,
+        crate::chat::messages::ForwardMessageRequest,
         crate::chat::messages::CursorPaginatedResponse<crate::chat::messages::MessageResponse>,
+        // Chat - Gallery
+        crate::chat::gallery::ChannelAttachment,
+        crate::chat::pins::PinnedMessage,
+        crate::chat::messages::CursorPaginatedResponse<crate::chat::gallery::ChannelAttachment>,
+        // Chat - Feeds
+        crate::db::ChannelFeedToken,
+        crate::chat::exports::CreateChannelExportRequest,
+        crate::chat::exports::ChannelExportJobResponse,
+        crate::chat::exports::ExportFormat,
+        crate::chat::exports::AttachmentMode,
+        crate::chat::feeds::CreateFeedTokenRequest,
+        crate::chat::feeds::CreatedFeedToken,
+        crate::chat::feeds::FeedFormat,
+        // Chat - Incoming Webhooks
+        crate::db::ChannelIncomingWebhook,
+        crate::chat::incoming_webhooks::CreateIncomingWebhookRequest,
+        crate::chat::incoming_webhooks::CreatedIncomingWebhook,
+        crate::chat::incoming_webhooks::ExecuteWebhookRequest,
         // Chat - DM
         crate::chat::dm::CreateDMRequest,
         crate::chat::dm::DMResponse,
@@ -391,6 +593,8 @@ use utoipa::{Modify, OpenApi};
         crate::chat::dm::DMIconResponse,
         crate::chat::dm::MarkAsReadRequest,
         crate::chat::dm::MarkAsReadResponse,
+        crate::chat::dm_state::DmConversationState,
+        crate::chat::dm_state::UpdateDmStateRequest,
         // Chat - DM Search
         crate::chat::dm_search::DmSearchQuery,
         crate::chat::dm_search::DmSearchAuthor,
@@ -409,10 +613,36 @@ use utoipa::{Modify, OpenApi};
         crate::guild::types::GuildInvite,
         crate::guild::types::CreateInviteRequest,
         crate::guild::types::InviteResponse,
+        crate::guild::types::InvitePreview,
+        crate::guild::types::CreateBanRequest,
+        crate::guild::types::GuildBan,
+        crate::guild::types::BanMemberResponse,
+        crate::guild::types::GuildBanList,
+        crate::guild::types::SetMemberTimeoutRequest,
+        crate::guild::types::MemberTimeoutResponse,
+        crate::guild::types::UpdateMemberRequest,
+        crate::guild::types::SetRetentionRequest,
+        crate::guild::types::ChannelRetention,
+        crate::guild::types::GuildRetentionPolicy,
+        crate::guild::types::GuildLogStream,
+        crate::guild::types::SetLogStreamRequest,
+        crate::guild::types::WelcomeChannel,
+        crate::guild::types::GuildOnboarding,
+        crate::guild::types::UpdateOnboardingRequest,
+        crate::guild::types::OnboardingCompletion,
+        crate::guild::types::GuildAuditLogEntry,
+        crate::guild::types::GuildAuditLogPage,
         crate::guild::types::CreateRoleRequest,
         crate::guild::types::UpdateRoleRequest,
+        crate::guild::types::ReorderRolesRequest,
         crate::guild::types::RoleResponse,
+        crate::guild::types::BulkRoleAction,
+        crate::guild::types::BulkMemberRolesRequest,
+        crate::guild::types::BulkMemberRolesResponse,
+        crate::guild::types::BulkRoleFailure,
+        crate::guild::types::MemberImportJob,
         crate::guild::types::GuildEmoji,
+        crate::guild::types::MessageEmoji,
         crate::guild::types::CreateEmojiRequest,
         crate::guild::types::UpdateEmojiRequest,
         crate::guild::types::GuildSettings,
@@ -420,10 +650,14 @@ use utoipa::{Modify, OpenApi};
         crate::guild::types::GuildCommandInfo,
         crate::guild::handlers::UsageStat,
         crate::guild::handlers::GuildUsageStats,
+        crate::guild::perks::GuildPerks,
         crate::guild::handlers::ChannelWithUnread,
         crate::guild::handlers::InstalledBot,
         crate::guild::handlers::ChannelPosition,
         crate::guild::handlers::ReorderChannelsRequest,
+        crate::guild::channel_positions::UpdateChannelPositionsRequest,
+        crate::guild::channel_positions::ChannelPlacement,
+        crate::guild::channel_positions::CategoryPlacement,
         // Guild - Categories
         crate::guild::categories::Category,
         crate::guild::categories::CreateCategoryRequest,
@@ -434,12 +668,17 @@ use utoipa::{Modify, OpenApi};
         crate::discovery::types::DiscoverSort,
         crate::discovery::types::DiscoverableGuild,
         crate::discovery::types::DiscoverResponse,
+        crate::discovery::types::RecommendedGuildsResponse,
         crate::discovery::types::JoinDiscoverableResponse,
+        crate::discovery::review::ReportListingRequest,
         // Guild - Search
         crate::guild::search::SearchQuery,
         crate::guild::search::SearchAuthor,
         crate::guild::search::SearchResult,
         crate::guild::search::SearchResponse,
+        crate::guild::autocomplete::AutocompleteType,
+        crate::guild::autocomplete::AutocompleteItem,
+        crate::guild::autocomplete::AutocompleteResponse,
         // Admin
         crate::admin::types::ElevateRequest,
         crate::admin::types::ElevateResponse,
@@ -466,8 +705,46 @@ use utoipa::{Modify, OpenApi};
         crate::admin::handlers::GuildDetailsResponse,
         crate::admin::handlers::PaginatedResponse<crate::admin::handlers::UserSummary>,
         crate::admin::handlers::PaginatedResponse<crate::admin::handlers::GuildSummary>,
-        crate::admin::handlers::PaginatedResponse<crate::admin::handlers::AuditLogEntryResponse>,
+        crate::admin::handlers::AuditLogPage,
+        crate::admin::handlers::PaginatedResponse<crate::webhooks::types::DeadLetterEntry>,
+        crate::admin::handlers::PaginatedResponse<crate::admin::attachments::HeldAttachment>,
+        crate::admin::attachments::HeldAttachment,
+        crate::admin::users::SuspendUserRequest,
+        crate::admin::users::UserSuspensionResponse,
+        crate::admin::users::RevokeSessionsResponse,
+        crate::admin::users::SystemAdminResponse,
+        crate::admin::webhooks::ReplayDeadLettersRequest,
+        crate::admin::webhooks::ReplayResponse,
+        crate::admin::handlers::PaginatedResponse<crate::admin::discovery::DiscoveryQueueEntry>,
+        crate::admin::discovery::DiscoveryQueueEntry,
+        crate::admin::discovery::RejectDiscoveryRequest,
+        crate::admin::discovery::DiscoveryReviewResponse,
+        crate::admin::handlers::PaginatedResponse<crate::admin::registrations::PendingRegistration>,
+        crate::admin::handlers::PaginatedResponse<crate::admin::registrations::RegistrationCode>,
+        crate::admin::registrations::PendingRegistration,
+        crate::admin::registrations::RegistrationReviewResponse,
+        crate::admin::registrations::RegistrationCode,
+        crate::admin::registrations::CreateRegistrationCodeRequest,
         crate::admin::handlers::DeleteResponse,
+        crate::admin::handlers::SetSupporterRequest,
+        crate::admin::handlers::SetGuildPageLimitsRequest,
+        crate::admin::retention::RetentionSettings,
+        crate::admin::retention::UpdateRetentionRequest,
+        crate::admin::settings::ServerSettings,
+        crate::admin::settings::UpdateServerSettingsRequest,
+        crate::admin::settings::ConfigReloadResponse,
+        crate::api::instance_config::InstanceOverrides,
+        crate::admin::entitlements::EntitlementUpdate,
+        crate::admin::entitlements::SupporterEntitlement,
+        crate::admin::entitlements::EntitlementResponse,
+        crate::observability::capacity::CapacityReport,
+        crate::admin::cluster::ClusterNodesResponse,
+        crate::cluster::NodeInfo,
+        crate::admin::voice::VoiceRoomsResponse,
+        crate::voice::diagnostics::VoiceRoomSummary,
+        crate::voice::diagnostics::VoiceRoomDiagnostics,
+        crate::voice::diagnostics::PeerDiagnostics,
+        crate::voice::diagnostics::RecentVoiceStats,
         crate::admin::handlers::AnnouncementResponse,
         crate::admin::handlers::AuthSettingsResponse,
         crate::admin::handlers::OidcProviderResponse,
@@ -482,10 +759,14 @@ use utoipa::{Modify, OpenApi};
         crate::moderation::types::ReportTargetType,
         crate::moderation::types::CreateReportRequest,
         crate::moderation::types::ResolveReportRequest,
+        crate::moderation::types::CreateReportNoteRequest,
+        crate::moderation::types::AddReportEvidenceRequest,
         crate::moderation::types::ListReportsQuery,
         crate::moderation::types::Report,
         crate::moderation::types::ReportResponse,
         crate::moderation::types::ReportStatsResponse,
+        crate::moderation::types::ReportNote,
+        crate::moderation::types::ReportEvidence,
         crate::moderation::types::PaginatedReports,
         // Moderation - Filters
         crate::moderation::filter_types::FilterCategory,
@@ -494,6 +775,19 @@ use utoipa::{Modify, OpenApi};
         crate::moderation::filter_types::GuildFilterPattern,
         crate::moderation::filter_types::CreatePatternRequest,
         crate::moderation::filter_types::UpdatePatternRequest,
+        crate::moderation::filter_types::LinkFilterMode,
+        crate::moderation::filter_types::LinkDomainList,
+        crate::moderation::filter_types::GuildLinkDomain,
+        crate::moderation::filter_types::AddLinkDomainRequest,
+        crate::moderation::filter_types::ChannelLanguageRule,
+        crate::moderation::filter_types::SetLanguageRuleRequest,
+        crate::moderation::filter_types::FilterNormalization,
+        crate::moderation::filter_types::EscalationAction,
+        crate::moderation::filter_types::EscalationPolicy,
+        crate::moderation::filter_types::EscalationPolicyRequest,
+        crate::moderation::filter_types::EvaluateEscalationRequest,
+        crate::moderation::filter_types::EscalationEvaluation,
+        crate::moderation::filter_types::PolicyEvaluation,
         crate::moderation::filter_types::UpdateFilterConfigsRequest,
         crate::moderation::filter_types::TestFilterRequest,
         crate::moderation::filter_types::TestFilterResponse,
@@ -501,12 +795,17 @@ use utoipa::{Modify, OpenApi};
         crate::moderation::filter_types::PaginatedModerationLog,
         // Voice - Calls
         crate::voice::call_handlers::CallStateResponse,
+        crate::voice::recording::VoiceRecording,
+        crate::voice::recording::VoiceRecordingWithUrl,
+        crate::voice::regions::MediaNode,
+        crate::voice::regions::VoiceRegionsResponse,
         crate::voice::call_handlers::CallApiError,
         crate::voice::call::CallState,
         // Bots
         crate::api::bots::CreateApplicationRequest,
         crate::api::bots::ApplicationResponse,
         crate::api::bots::BotTokenResponse,
+        crate::api::interactions::InteractionResponseRequest,
         // Workspaces
         crate::workspaces::types::WorkspaceResponse,
         crate::workspaces::types::WorkspaceListItem,
@@ -517,6 +816,11 @@ use utoipa::{Modify, OpenApi};
         crate::workspaces::types::AddEntryRequest,
         crate::workspaces::types::ReorderEntriesRequest,
         crate::workspaces::types::ReorderWorkspacesRequest,
+        // Push
+        crate::push::types::PushPlatform,
+        crate::push::types::RegisterDeviceRequest,
+        crate::push::types::PushDeviceResponse,
+        crate::push::types::PushConfigResponse,
         // Settings
         crate::api::settings::InstanceLimitsResponse,
         // Data Governance
@@ -542,5 +846,12 @@ impl Modify for SecurityAddon {
                     .build(),
             ),
         );
+        components.add_security_scheme(
+            "bot_token",
+            SecurityScheme::ApiKey(ApiKey::Header(ApiKeyValue::with_description(
+                "Authorization",
+                "Bot token, sent as `Bot <token>`",
+            ))),
+        );
     }
 }
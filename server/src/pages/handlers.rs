@@ -6,6 +6,7 @@ use axum::Json;
 use tracing::error;
 use uuid::Uuid;
 
+use crate::api::concurrency::VersionConflict;
 use crate::api::AppState;
 use crate::auth::AuthUser;
 use crate::pages::{
@@ -21,6 +22,24 @@ use crate::permissions::{
 /// Error response type for page handlers.
 type PageResult<T> = Result<T, (StatusCode, String)>;
 
+/// Page updates can also fail with a version conflict carrying the current page.
+type PageUpdateResult = PageResult<Result<Json<Page>, VersionConflict>>;
+
+/// Report that a page update was based on an outdated version.
+async fn version_conflict(state: &AppState, id: Uuid) -> PageUpdateResult {
+    let current = queries::get_page_by_id(&state.db, id)
+        .await
+        .map_err(|e| {
+            error!("Failed to get page {}: {}", id, e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Internal server error".to_string(),
+            )
+        })?
+        .ok_or_else(|| (StatusCode::NOT_FOUND, "Page not found".to_string()))?;
+    Ok(Err(VersionConflict::new(&current)))
+}
+
 // ============================================================================
 // Platform Pages (system admin only)
 // ============================================================================
@@ -177,7 +196,7 @@ pub async fn create_platform_page(
         })?;
 
     // Check page limit inside lock for atomicity
-    let max_limit = state.config.max_pages_per_guild;
+    let max_limit = state.config().max_pages_per_guild;
     let at_limit: i64 = sqlx::query_scalar(
         r"SELECT COUNT(*) FROM pages WHERE guild_id IS NULL AND deleted_at IS NULL",
     )
@@ -247,6 +266,7 @@ pub async fn create_platform_page(
     request_body = UpdatePageRequest,
     responses(
         (status = 200, description = "Platform page updated", body = Page),
+        (status = 409, description = "Page changed since `version`, or slug taken"),
     ),
     security(("bearer_auth" = [])),
 )]
@@ -255,7 +275,7 @@ pub async fn update_platform_page(
     user: AuthUser,
     Path(id): Path<Uuid>,
     Json(req): Json<UpdatePageRequest>,
-) -> PageResult<Json<Page>> {
+) -> PageUpdateResult {
     // Verify system admin (fail-fast on DB error for security)
     let is_admin = is_system_admin(&state.db, user.id).await.map_err(|e| {
         error!("Permission check failed: {}", e);
@@ -321,6 +341,7 @@ pub async fn update_platform_page(
             requires_acceptance: req.requires_acceptance,
             category_id: None,
             updated_by: user.id,
+            expected_version: req.version,
         },
     )
     .await
@@ -331,6 +352,9 @@ pub async fn update_platform_page(
             "Internal server error".to_string(),
         )
     })?;
+    let Some(page) = page else {
+        return version_conflict(&state, id).await;
+    };
 
     // Log audit
     if let Err(e) = queries::log_audit(
@@ -367,13 +391,14 @@ pub async fn update_platform_page(
         }
         // Prune old revisions (best-effort — pruning failure doesn't affect correctness)
         if let Err(e) =
-            queries::prune_revisions(&state.db, page.id, state.config.max_revisions_per_page).await
+            queries::prune_revisions(&state.db, page.id, state.config().max_revisions_per_page)
+                .await
         {
             error!("Failed to prune revisions for page {}: {}", page.id, e);
         }
     }
 
-    Ok(Json(page))
+    Ok(Ok(Json(page)))
 }
 
 /// Delete a platform page (system admin only).
@@ -673,14 +698,14 @@ pub async fn create_guild_page(
 
     // Check page limit using per-guild override or instance default, inside lock for atomicity
     let max_limit =
-        queries::get_effective_page_limit(&state.db, guild_id, state.config.max_pages_per_guild)
+        queries::get_effective_page_limit(&state.db, guild_id, state.config().max_pages_per_guild)
             .await
             .unwrap_or_else(|e| {
                 error!(
                     "Failed to get effective page limit for guild {}, using instance default: {}",
                     guild_id, e
                 );
-                state.config.max_pages_per_guild
+                state.config().max_pages_per_guild
             });
 
     let at_limit: i64 = sqlx::query_scalar(
@@ -774,7 +799,10 @@ pub async fn create_guild_page(
         ("page_id" = Uuid, Path, description = "Page ID")
     ),
     request_body = UpdatePageRequest,
-    responses((status = 200, description = "Guild page updated", body = Page)),
+    responses(
+        (status = 200, description = "Guild page updated", body = Page),
+        (status = 409, description = "Page changed since `version`, or slug taken"),
+    ),
     security(("bearer_auth" = []))
 )]
 pub async fn update_guild_page(
@@ -782,7 +810,7 @@ pub async fn update_guild_page(
     user: AuthUser,
     Path((guild_id, id)): Path<(Uuid, Uuid)>,
     Json(req): Json<UpdatePageRequest>,
-) -> PageResult<Json<Page>> {
+) -> PageUpdateResult {
     // Check permission
     check_manage_pages_permission(&state, guild_id, user.id).await?;
 
@@ -851,6 +879,7 @@ pub async fn update_guild_page(
             requires_acceptance: req.requires_acceptance,
             category_id: req.category_id,
             updated_by: user.id,
+            expected_version: req.version,
         },
     )
     .await
@@ -861,6 +890,9 @@ pub async fn update_guild_page(
             "Internal server error".to_string(),
         )
     })?;
+    let Some(page) = page else {
+        return version_conflict(&state, id).await;
+    };
 
     // Log audit
     if let Err(e) = queries::log_audit(
@@ -899,7 +931,7 @@ pub async fn update_guild_page(
         let max_revisions = queries::get_effective_revision_limit(
             &state.db,
             guild_id,
-            state.config.max_revisions_per_page,
+            state.config().max_revisions_per_page,
         )
         .await
         .unwrap_or_else(|e| {
@@ -907,7 +939,7 @@ pub async fn update_guild_page(
                 "Failed to get effective revision limit, using instance default: {}",
                 e
             );
-            state.config.max_revisions_per_page
+            state.config().max_revisions_per_page
         });
 
         if let Err(e) = queries::prune_revisions(&state.db, page.id, max_revisions).await {
@@ -915,7 +947,7 @@ pub async fn update_guild_page(
         }
     }
 
-    Ok(Json(page))
+    Ok(Ok(Json(page)))
 }
 
 /// Delete a guild page.
@@ -1331,6 +1363,7 @@ pub async fn restore_guild_page_revision(
             requires_acceptance: None,
             category_id: None,
             updated_by: user.id,
+            expected_version: None,
         },
     )
     .await
@@ -1343,7 +1376,8 @@ pub async fn restore_guild_page_revision(
             StatusCode::INTERNAL_SERVER_ERROR,
             "Internal server error".to_string(),
         )
-    })?;
+    })?
+    .ok_or_else(|| (StatusCode::NOT_FOUND, "Page not found".to_string()))?;
 
     // Create a new revision for the restore (best-effort — concurrent operations
     // may collide on the unique constraint; the restore itself already succeeded)
@@ -1367,7 +1401,7 @@ pub async fn restore_guild_page_revision(
     let max_revisions = queries::get_effective_revision_limit(
         &state.db,
         guild_id,
-        state.config.max_revisions_per_page,
+        state.config().max_revisions_per_page,
     )
     .await
     .unwrap_or_else(|e| {
@@ -1375,7 +1409,7 @@ pub async fn restore_guild_page_revision(
             "Failed to get effective revision limit, using instance default: {}",
             e
         );
-        state.config.max_revisions_per_page
+        state.config().max_revisions_per_page
     });
 
     if let Err(e) = queries::prune_revisions(&state.db, page.id, max_revisions).await {
@@ -1587,6 +1621,7 @@ pub async fn restore_platform_page_revision(
             requires_acceptance: None,
             category_id: None,
             updated_by: user.id,
+            expected_version: None,
         },
     )
     .await
@@ -1599,7 +1634,8 @@ pub async fn restore_platform_page_revision(
             StatusCode::INTERNAL_SERVER_ERROR,
             "Internal server error".to_string(),
         )
-    })?;
+    })?
+    .ok_or_else(|| (StatusCode::NOT_FOUND, "Page not found".to_string()))?;
 
     // Best-effort — concurrent operations may collide on the unique constraint;
     // the restore itself already succeeded
@@ -1620,7 +1656,7 @@ pub async fn restore_platform_page_revision(
     }
 
     if let Err(e) =
-        queries::prune_revisions(&state.db, page.id, state.config.max_revisions_per_page).await
+        queries::prune_revisions(&state.db, page.id, state.config().max_revisions_per_page).await
     {
         error!("Failed to prune revisions for page {}: {}", page.id, e);
     }
@@ -303,13 +303,26 @@ pub struct UpdatePageParams<'a> {
     /// `None` = no change, `Some(None)` = remove category, `Some(Some(id))` = set category.
     pub category_id: Option<Option<Uuid>>,
     pub updated_by: Uuid,
+    /// Only apply the update if the page is still at this version.
+    pub expected_version: Option<i32>,
 }
 
 /// Update an existing page.
-pub async fn update_page(pool: &PgPool, params: UpdatePageParams<'_>) -> Result<Page, sqlx::Error> {
+///
+/// Returns `None` if the page is no longer at `expected_version`.
+pub async fn update_page(
+    pool: &PgPool,
+    params: UpdatePageParams<'_>,
+) -> Result<Option<Page>, sqlx::Error> {
     let page = get_page_by_id(pool, params.id)
         .await?
         .ok_or(sqlx::Error::RowNotFound)?;
+    if params
+        .expected_version
+        .is_some_and(|version| version != page.version)
+    {
+        return Ok(None);
+    }
 
     let new_title = params.title.unwrap_or(&page.title);
     let new_slug = params.slug.unwrap_or(&page.slug);
@@ -327,11 +340,14 @@ pub async fn update_page(pool: &PgPool, params: UpdatePageParams<'_>) -> Result<
         None => page.category_id,
     };
 
-    let updated_page: Page = sqlx::query_as(
+    // Fields left out of the update were read above; they must not have
+    // changed in between when the caller asked for a version check
+    let updated_page: Option<Page> = sqlx::query_as(
         r"UPDATE pages SET
             title = $2, slug = $3, content = $4, content_hash = $5,
-            requires_acceptance = $6, category_id = $7, updated_by = $8, updated_at = NOW()
-        WHERE id = $1 RETURNING *",
+            requires_acceptance = $6, category_id = $7, updated_by = $8, updated_at = NOW(),
+            version = version + 1
+        WHERE id = $1 AND ($9::int IS NULL OR version = $9) RETURNING *",
     )
     .bind(params.id)
     .bind(new_title)
@@ -341,7 +357,8 @@ pub async fn update_page(pool: &PgPool, params: UpdatePageParams<'_>) -> Result<
     .bind(new_requires_acceptance)
     .bind(new_category_id)
     .bind(params.updated_by)
-    .fetch_one(pool)
+    .bind(params.expected_version)
+    .fetch_optional(pool)
     .await?;
 
     Ok(updated_page)
@@ -38,6 +38,9 @@ pub struct Page {
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
     pub deleted_at: Option<DateTime<Utc>>,
+    /// Bumped by every page update; send it back with an update to detect
+    /// conflicting edits.
+    pub version: i32,
 }
 
 /// Page metadata for listing (without content for efficiency).
@@ -87,6 +90,8 @@ pub struct UpdatePageRequest {
     /// `"category_id": "uuid"` → `Some(Some(uuid))`.
     #[serde(default, deserialize_with = "deserialize_double_option")]
     pub category_id: Option<Option<Uuid>>,
+    /// Page version the update is based on; a newer version fails with 409.
+    pub version: Option<i32>,
 }
 
 /// Request body for reordering pages.
@@ -156,6 +161,7 @@ pub struct PageCategory {
 
 /// Request body for creating a page category.
 #[derive(Debug, Deserialize, utoipa::ToSchema)]
+#[schema(as = PageCreateCategoryRequest)]
 pub struct CreateCategoryRequest {
     /// Category name (max 50 characters).
     pub name: String,
@@ -163,6 +169,7 @@ pub struct CreateCategoryRequest {
 
 /// Request body for updating a page category.
 #[derive(Debug, Deserialize, utoipa::ToSchema)]
+#[schema(as = PageUpdateCategoryRequest)]
 pub struct UpdateCategoryRequest {
     /// New category name (max 50 characters).
     pub name: String,
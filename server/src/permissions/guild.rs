@@ -8,6 +8,8 @@
 //! - Invites (bits 19-20): Invite-related permissions
 //! - Pages (bit 21): Information page management
 //! - Screen Sharing (bit 22): Screen sharing in voice channels
+//! - Recording (bit 25): Server-side voice recording
+//! - Voice Chat (bit 26): Text chat in voice channels
 
 use bitflags::bitflags;
 
@@ -85,6 +87,18 @@ bitflags! {
         // === Channel Visibility (bit 24) ===
         /// Permission to view a channel and read its message history
         const VIEW_CHANNEL       = 1 << 24;
+
+        // === Recording (bit 25) ===
+        /// Permission to start and stop server-side voice recordings
+        const RECORD_VOICE       = 1 << 25;
+
+        // === Voice Chat (bit 26) ===
+        /// Permission to send text messages in a voice channel's chat
+        const VOICE_TEXT_CHAT    = 1 << 26;
+
+        // === Member Profiles (bit 27) ===
+        /// Permission to change other members' guild nicknames and reset their guild avatars
+        const MANAGE_NICKNAMES   = 1 << 27;
     }
 }
 
@@ -102,6 +116,7 @@ impl GuildPermissions {
         .union(Self::ADD_REACTIONS)
         .union(Self::VOICE_CONNECT)
         .union(Self::VOICE_SPEAK)
+        .union(Self::VOICE_TEXT_CHAT)
         .union(Self::CREATE_INVITE);
 
     /// Default permissions for moderators.
@@ -114,6 +129,7 @@ impl GuildPermissions {
         .union(Self::MANAGE_MESSAGES)
         .union(Self::TIMEOUT_MEMBERS)
         .union(Self::KICK_MEMBERS)
+        .union(Self::MANAGE_NICKNAMES)
         .union(Self::VIEW_AUDIT_LOG)
         .union(Self::MANAGE_INVITES)
         .union(Self::SCREEN_SHARE)
@@ -125,7 +141,8 @@ impl GuildPermissions {
     pub const OFFICER_DEFAULT: Self = Self::MODERATOR_DEFAULT
         .union(Self::BAN_MEMBERS)
         .union(Self::MANAGE_CHANNELS)
-        .union(Self::MANAGE_PAGES);
+        .union(Self::MANAGE_PAGES)
+        .union(Self::RECORD_VOICE);
 
     /// Permissions that @everyone can NEVER have.
     ///
@@ -146,7 +163,9 @@ impl GuildPermissions {
         .union(Self::MANAGE_INVITES)
         .union(Self::MANAGE_PAGES)
         .union(Self::SCREEN_SHARE)
-        .union(Self::MENTION_EVERYONE);
+        .union(Self::MENTION_EVERYONE)
+        .union(Self::RECORD_VOICE)
+        .union(Self::MANAGE_NICKNAMES);
 
     // === Database Conversion ===
 
@@ -282,6 +301,27 @@ mod tests {
         assert_eq!(GuildPermissions::VIEW_CHANNEL.bits(), 1 << 24);
     }
 
+    #[test]
+    fn test_record_voice_permission_bits() {
+        assert_eq!(GuildPermissions::RECORD_VOICE.bits(), 1 << 25);
+        assert!(!GuildPermissions::EVERYONE_DEFAULT.has(GuildPermissions::RECORD_VOICE));
+        assert!(GuildPermissions::OFFICER_DEFAULT.has(GuildPermissions::RECORD_VOICE));
+    }
+
+    #[test]
+    fn test_voice_text_chat_permission_bits() {
+        assert_eq!(GuildPermissions::VOICE_TEXT_CHAT.bits(), 1 << 26);
+        assert!(GuildPermissions::EVERYONE_DEFAULT.has(GuildPermissions::VOICE_TEXT_CHAT));
+    }
+
+    #[test]
+    fn test_manage_nicknames_permission_bits() {
+        assert_eq!(GuildPermissions::MANAGE_NICKNAMES.bits(), 1 << 27);
+        assert!(!GuildPermissions::EVERYONE_DEFAULT.has(GuildPermissions::MANAGE_NICKNAMES));
+        assert!(GuildPermissions::EVERYONE_FORBIDDEN.has(GuildPermissions::MANAGE_NICKNAMES));
+        assert!(GuildPermissions::MODERATOR_DEFAULT.has(GuildPermissions::MANAGE_NICKNAMES));
+    }
+
     // === Preset Tests ===
 
     #[test]
@@ -477,6 +517,7 @@ mod tests {
             GuildPermissions::MANAGE_PAGES,
             GuildPermissions::SCREEN_SHARE,
             GuildPermissions::MENTION_EVERYONE,
+            GuildPermissions::MANAGE_NICKNAMES,
         ];
 
         for forbidden in forbidden_perms {
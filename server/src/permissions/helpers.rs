@@ -287,6 +287,19 @@ pub async fn require_channel_access(
     })
 }
 
+/// The permission needed to post in a guild channel of `channel_type`:
+/// `VOICE_TEXT_CHAT` for a voice or stage channel's chat, `SEND_MESSAGES`
+/// otherwise.
+#[must_use]
+pub const fn send_permission_for(channel_type: &crate::db::ChannelType) -> GuildPermissions {
+    match channel_type {
+        crate::db::ChannelType::Voice | crate::db::ChannelType::Stage => {
+            GuildPermissions::VOICE_TEXT_CHAT
+        }
+        _ => GuildPermissions::SEND_MESSAGES,
+    }
+}
+
 /// Filter a list of guild channel IDs down to those the user can view.
 ///
 /// Fetches membership + roles once, batch-fetches all channel overrides in a single query,
@@ -14,7 +14,7 @@ pub mod system;
 pub use guild::GuildPermissions;
 pub use helpers::{
     filter_accessible_channels, get_member_permission_context, require_channel_access,
-    require_guild_permission, MemberPermissionContext,
+    require_guild_permission, send_permission_for, MemberPermissionContext,
 };
 pub use models::*;
 pub use queries::*;
@@ -68,12 +68,15 @@ pub struct ChannelOverride {
 #[derive(Debug, Clone, FromRow, Serialize)]
 pub struct AuditLogEntry {
     pub id: Uuid,
-    pub actor_id: Uuid,
+    /// `None` for failed sign-ins with an unknown username, or once the actor
+    /// has been deleted.
+    pub actor_id: Option<Uuid>,
     pub action: String,
     pub target_type: Option<String>,
     pub target_id: Option<Uuid>,
     pub details: Option<serde_json::Value>,
     pub ip_address: Option<String>,
+    pub user_agent: Option<String>,
     pub created_at: DateTime<Utc>,
 }
 
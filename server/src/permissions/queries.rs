@@ -541,6 +541,21 @@ pub async fn delete_channel_override(
 // Audit Log Queries
 // ============================================================================
 
+/// An entry to write to the system audit log.
+///
+/// Admin actions go through [`write_audit_log`]; security events that also
+/// carry a user agent or have no known actor use [`write_audit_event`].
+#[derive(Debug, Default)]
+pub struct AuditEvent<'a> {
+    pub actor_id: Option<Uuid>,
+    pub action: &'a str,
+    pub target_type: Option<&'a str>,
+    pub target_id: Option<Uuid>,
+    pub details: Option<JsonValue>,
+    pub ip_address: Option<&'a str>,
+    pub user_agent: Option<&'a str>,
+}
+
 /// Write an entry to the system audit log.
 pub async fn write_audit_log(
     pool: &PgPool,
@@ -550,11 +565,32 @@ pub async fn write_audit_log(
     target_id: Option<Uuid>,
     details: Option<JsonValue>,
     ip_address: Option<&str>,
+) -> sqlx::Result<AuditLogEntry> {
+    write_audit_event(
+        pool,
+        AuditEvent {
+            actor_id: Some(actor_id),
+            action,
+            target_type,
+            target_id,
+            details,
+            ip_address,
+            user_agent: None,
+        },
+    )
+    .await
+}
+
+/// Write an [`AuditEvent`] to the system audit log.
+pub async fn write_audit_event(
+    pool: &PgPool,
+    event: AuditEvent<'_>,
 ) -> sqlx::Result<AuditLogEntry> {
     sqlx::query_as::<_, AuditLogEntry>(
         r"
-        INSERT INTO system_audit_log (actor_id, action, target_type, target_id, details, ip_address)
-        VALUES ($1, $2, $3, $4, $5, $6::inet)
+        INSERT INTO system_audit_log
+            (actor_id, action, target_type, target_id, details, ip_address, user_agent)
+        VALUES ($1, $2, $3, $4, $5, $6::inet, $7)
         RETURNING
             id,
             actor_id,
@@ -563,15 +599,17 @@ pub async fn write_audit_log(
             target_id,
             details,
             host(ip_address) as ip_address,
+            user_agent,
             created_at
         ",
     )
-    .bind(actor_id)
-    .bind(action)
-    .bind(target_type)
-    .bind(target_id)
-    .bind(details)
-    .bind(ip_address)
+    .bind(event.actor_id)
+    .bind(event.action)
+    .bind(event.target_type)
+    .bind(event.target_id)
+    .bind(event.details)
+    .bind(event.ip_address)
+    .bind(event.user_agent)
     .fetch_one(pool)
     .await
 }
@@ -598,6 +636,7 @@ pub async fn get_audit_log(
                 target_id,
                 details,
                 host(ip_address) as ip_address,
+                user_agent,
                 created_at
             FROM system_audit_log
             WHERE action LIKE $1
@@ -621,6 +660,7 @@ pub async fn get_audit_log(
                 target_id,
                 details,
                 host(ip_address) as ip_address,
+                user_agent,
                 created_at
             FROM system_audit_log
             ORDER BY created_at DESC
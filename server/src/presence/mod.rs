@@ -1,5 +1,7 @@
-//! Rich presence module for game/activity detection.
+//! Presence module: online status shared across replicas and rich presence
+//! for game/activity detection.
 
+pub mod registry;
 mod types;
 
 pub use types::*;
@@ -0,0 +1,287 @@
+//! Presence Registry
+//!
+//! Redis-backed presence shared by every server replica, so a user's status
+//! does not depend on which instance holds their connections.
+//!
+//! Per user:
+//! - `ws:sessions:{user_id}` — sorted set of open connections (score = expiry
+//!   in Unix milliseconds), refreshed on every gateway heartbeat. Connections
+//!   on an instance that dies stop counting after `SESSION_TTL`.
+//! - `presence:state:{user_id}` — hash with the status the user picked
+//!   (`status`), their custom status text (`custom`), whether they are idle
+//!   (`idle`), and the last status broadcast (`effective`).
+//!
+//! Globally, `presence:connected` tracks when each connected user's sessions
+//! expire and `presence:active` when each user last sent an activity ping;
+//! the sweeper uses them to mark users offline or idle.
+//!
+//! Every change runs as one Lua script that recomputes the effective status
+//! and compares it with the last broadcast, so with several replicas each
+//! transition is announced exactly once.
+
+use std::time::Duration;
+
+use fred::interfaces::{HashesInterface, LuaInterface, SortedSetsInterface};
+use fred::prelude::*;
+use sqlx::PgPool;
+use tracing::warn;
+use uuid::Uuid;
+
+use crate::db::UserStatus;
+use crate::ws::lifecycle::HEARTBEAT_INTERVAL;
+use crate::ws::{broadcast_presence_update, ServerEvent};
+
+/// Sorted set of user -> latest session expiry (ms).
+const CONNECTED_ZSET_KEY: &str = "presence:connected";
+
+/// Sorted set of user -> last activity ping (ms), for connected users who
+/// are not idle.
+const ACTIVE_ZSET_KEY: &str = "presence:active";
+
+/// How long a connection counts without a heartbeat refresh.
+const SESSION_TTL: Duration = Duration::from_secs(HEARTBEAT_INTERVAL.as_secs() * 2 + 10);
+
+/// How long a user's chosen status and custom text outlive their last change.
+const STATE_TTL: Duration = Duration::from_secs(30 * 24 * 60 * 60);
+
+/// A connected user without an activity ping for this long is shown as away.
+///
+/// Clients ping every minute while their own idle detector says the user is
+/// active, so this is the grace period after they stop.
+pub const IDLE_AFTER: Duration = Duration::from_secs(3 * 60);
+
+/// How often expired connections and idle users are swept.
+const SWEEP_INTERVAL: Duration = Duration::from_secs(15);
+
+/// Maximum length of custom status text (matches `users.status_message`).
+pub const MAX_CUSTOM_STATUS_LEN: usize = 128;
+
+/// Applies one presence operation and returns the new effective status if it
+/// changed, or `false` (nil) otherwise.
+///
+/// KEYS: sessions, state, connected set, active set.
+/// ARGV: op, now (ms), user ID, connection ID, session TTL (ms), state TTL (ms),
+/// op argument (status for `set_status`, idle cutoff for `idle`).
+const PRESENCE_LUA: &str = r"
+local op = ARGV[1]
+local now = tonumber(ARGV[2])
+local session_ttl = tonumber(ARGV[5])
+
+redis.call('ZREMRANGEBYSCORE', KEYS[1], '-inf', now)
+
+if op == 'connect' or op == 'heartbeat' then
+    redis.call('ZADD', KEYS[1], now + session_ttl, ARGV[4])
+    redis.call('PEXPIRE', KEYS[1], session_ttl)
+    redis.call('ZADD', KEYS[3], now + session_ttl, ARGV[3])
+    if op == 'connect' then
+        redis.call('ZADD', KEYS[4], now, ARGV[3])
+        redis.call('HSET', KEYS[2], 'idle', '0')
+    end
+elseif op == 'disconnect' then
+    redis.call('ZREM', KEYS[1], ARGV[4])
+elseif op == 'ping' then
+    if redis.call('ZCARD', KEYS[1]) > 0 then
+        redis.call('ZADD', KEYS[4], now, ARGV[3])
+        redis.call('HSET', KEYS[2], 'idle', '0')
+    end
+elseif op == 'set_status' then
+    redis.call('HSET', KEYS[2], 'status', ARGV[7])
+elseif op == 'idle' then
+    local last = redis.call('ZSCORE', KEYS[4], ARGV[3])
+    if last and tonumber(last) <= tonumber(ARGV[7]) then
+        redis.call('ZREM', KEYS[4], ARGV[3])
+        redis.call('HSET', KEYS[2], 'idle', '1')
+    end
+end
+
+local effective
+if redis.call('ZCARD', KEYS[1]) == 0 then
+    redis.call('ZREM', KEYS[3], ARGV[3])
+    redis.call('ZREM', KEYS[4], ARGV[3])
+    effective = 'offline'
+else
+    effective = redis.call('HGET', KEYS[2], 'status') or 'online'
+    if effective == 'online' and redis.call('HGET', KEYS[2], 'idle') == '1' then
+        effective = 'away'
+    end
+end
+
+redis.call('PEXPIRE', KEYS[2], tonumber(ARGV[6]))
+local previous = redis.call('HGET', KEYS[2], 'effective') or 'offline'
+if previous == effective then
+    return false
+end
+redis.call('HSET', KEYS[2], 'effective', effective)
+return effective
+";
+
+fn sessions_key(user_id: Uuid) -> String {
+    format!("ws:sessions:{user_id}")
+}
+
+fn state_key(user_id: Uuid) -> String {
+    format!("presence:state:{user_id}")
+}
+
+fn now_millis() -> i64 {
+    chrono::Utc::now().timestamp_millis()
+}
+
+async fn apply(
+    redis: &Client,
+    user_id: Uuid,
+    op: &str,
+    connection_id: Option<Uuid>,
+    arg: &str,
+) -> Result<Option<String>, Error> {
+    redis
+        .eval(
+            PRESENCE_LUA,
+            vec![
+                sessions_key(user_id),
+                state_key(user_id),
+                CONNECTED_ZSET_KEY.to_string(),
+                ACTIVE_ZSET_KEY.to_string(),
+            ],
+            vec![
+                op.to_string(),
+                now_millis().to_string(),
+                user_id.to_string(),
+                connection_id.unwrap_or_default().to_string(),
+                SESSION_TTL.as_millis().to_string(),
+                STATE_TTL.as_millis().to_string(),
+                arg.to_string(),
+            ],
+        )
+        .await
+}
+
+/// Register a new gateway connection. Counts as activity.
+pub async fn connect(
+    redis: &Client,
+    user_id: Uuid,
+    connection_id: Uuid,
+) -> Result<Option<String>, Error> {
+    apply(redis, user_id, "connect", Some(connection_id), "").await
+}
+
+/// Refresh a connection's expiry. Does not count as activity.
+pub async fn heartbeat(
+    redis: &Client,
+    user_id: Uuid,
+    connection_id: Uuid,
+) -> Result<Option<String>, Error> {
+    apply(redis, user_id, "heartbeat", Some(connection_id), "").await
+}
+
+/// Remove a closed connection.
+pub async fn disconnect(
+    redis: &Client,
+    user_id: Uuid,
+    connection_id: Uuid,
+) -> Result<Option<String>, Error> {
+    apply(redis, user_id, "disconnect", Some(connection_id), "").await
+}
+
+/// Record user activity reported by a client, ending idle.
+pub async fn activity_ping(redis: &Client, user_id: Uuid) -> Result<Option<String>, Error> {
+    apply(redis, user_id, "ping", None, "").await
+}
+
+/// Set the status the user picked.
+pub async fn set_status(
+    redis: &Client,
+    user_id: Uuid,
+    status: &UserStatus,
+) -> Result<Option<String>, Error> {
+    apply(redis, user_id, "set_status", None, status.as_str()).await
+}
+
+/// Set or clear the user's custom status text.
+pub async fn set_custom_status(
+    redis: &Client,
+    user_id: Uuid,
+    text: Option<&str>,
+) -> Result<(), Error> {
+    let key = state_key(user_id);
+    match text {
+        Some(text) => redis.hset::<(), _, _>(&key, ("custom", text)).await?,
+        None => redis.hdel::<(), _, _>(&key, "custom").await?,
+    }
+    redis
+        .pexpire::<(), _>(&key, STATE_TTL.as_millis() as i64, None)
+        .await
+}
+
+/// Whether the user has at least one live gateway connection on any instance.
+pub async fn has_active_connection(redis: &Client, user_id: Uuid) -> Result<bool, Error> {
+    let count: i64 = redis
+        .zcount(sessions_key(user_id), now_millis() as f64, f64::INFINITY)
+        .await?;
+    Ok(count > 0)
+}
+
+/// Persist a status change and broadcast it to the user's presence subscribers.
+pub async fn publish_change(db: &PgPool, redis: &Client, user_id: Uuid, status: &str) {
+    if let Err(e) = sqlx::query("UPDATE users SET status = $1::user_status WHERE id = $2")
+        .bind(status)
+        .bind(user_id)
+        .execute(db)
+        .await
+    {
+        warn!(user_id = %user_id, error = %e, "Failed to persist presence");
+    }
+
+    broadcast_presence_update(
+        redis,
+        user_id,
+        &ServerEvent::PresenceUpdate {
+            user_id,
+            status: status.to_string(),
+        },
+    )
+    .await;
+}
+
+/// Users whose score in `key` is at or below `max`.
+async fn due_users(redis: &Client, key: &str, max: i64) -> Result<Vec<Uuid>, Error> {
+    let members: Vec<String> = redis
+        .zrangebyscore(key, f64::NEG_INFINITY, max as f64, false, Some((0, 500)))
+        .await?;
+    Ok(members
+        .iter()
+        .filter_map(|m| Uuid::parse_str(m).ok())
+        .collect())
+}
+
+/// Mark users offline whose connections all expired, and idle users away.
+async fn sweep(db: &PgPool, redis: &Client) -> Result<(), Error> {
+    let now = now_millis();
+
+    for user_id in due_users(redis, CONNECTED_ZSET_KEY, now).await? {
+        if let Some(status) = apply(redis, user_id, "expire", None, "").await? {
+            publish_change(db, redis, user_id, &status).await;
+        }
+    }
+
+    let cutoff = now - IDLE_AFTER.as_millis() as i64;
+    for user_id in due_users(redis, ACTIVE_ZSET_KEY, cutoff).await? {
+        if let Some(status) = apply(redis, user_id, "idle", None, &cutoff.to_string()).await? {
+            publish_change(db, redis, user_id, &status).await;
+        }
+    }
+    Ok(())
+}
+
+/// Spawn the background task that expires stale connections and detects idle users.
+pub fn spawn_presence_sweeper(db: PgPool, redis: Client) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(SWEEP_INTERVAL);
+        loop {
+            interval.tick().await;
+            if let Err(e) = sweep(&db, &redis).await {
+                warn!(error = %e, "Failed to sweep presence");
+            }
+        }
+    })
+}
@@ -0,0 +1,60 @@
+//! Push Notification Error Types
+
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::Json;
+
+#[derive(Debug, thiserror::Error)]
+pub enum PushError {
+    #[error("Push device not found")]
+    NotFound,
+
+    #[error("Push platform not enabled on this server")]
+    PlatformDisabled,
+
+    #[error("Maximum push devices limit reached")]
+    DeviceLimitExceeded,
+
+    #[error("Validation error: {0}")]
+    Validation(String),
+
+    #[error("Database error: {0}")]
+    Database(#[from] sqlx::Error),
+}
+
+impl IntoResponse for PushError {
+    fn into_response(self) -> axum::response::Response {
+        let (status, code, message) = match &self {
+            Self::NotFound => (
+                StatusCode::NOT_FOUND,
+                "DEVICE_NOT_FOUND",
+                "Push device not found".to_string(),
+            ),
+            Self::PlatformDisabled => (
+                StatusCode::BAD_REQUEST,
+                "PLATFORM_DISABLED",
+                "This push platform is not enabled on this server".to_string(),
+            ),
+            Self::DeviceLimitExceeded => (
+                StatusCode::FORBIDDEN,
+                "LIMIT_EXCEEDED",
+                "Maximum push devices limit reached".to_string(),
+            ),
+            Self::Validation(msg) => (StatusCode::BAD_REQUEST, "VALIDATION_ERROR", msg.clone()),
+            Self::Database(err) => {
+                tracing::error!(%err, "Push endpoint database error");
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "INTERNAL_ERROR",
+                    "Database error".to_string(),
+                )
+            }
+        };
+
+        (
+            status,
+            Json(serde_json::json!({ "error": code, "message": message })),
+        )
+            .into_response()
+    }
+}
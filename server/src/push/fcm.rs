@@ -0,0 +1,185 @@
+//! FCM Backend
+//!
+//! Delivers to mobile registration tokens through the Firebase Cloud
+//! Messaging HTTP v1 API, authenticating with a Google service account.
+
+use std::time::{Duration, Instant};
+
+use jsonwebtoken::{Algorithm, EncodingKey, Header};
+use serde::{Deserialize, Serialize};
+use tokio::sync::Mutex;
+
+use super::gateway::PushSendError;
+use super::types::{PushDeviceRow, PushNotification};
+
+/// OAuth scope required to send messages.
+const FCM_SCOPE: &str = "https://www.googleapis.com/auth/firebase.messaging";
+
+/// Lifetime requested for the service account assertion (Google's maximum).
+const ASSERTION_LIFETIME_SECS: i64 = 60 * 60;
+
+/// Refresh the access token this long before it expires.
+const TOKEN_REFRESH_MARGIN: Duration = Duration::from_secs(5 * 60);
+
+/// The fields of a service account key file used here.
+#[derive(Debug, Deserialize)]
+struct ServiceAccount {
+    project_id: String,
+    client_email: String,
+    private_key: String,
+    token_uri: String,
+}
+
+#[derive(Debug, Serialize)]
+struct AssertionClaims<'a> {
+    iss: &'a str,
+    scope: &'a str,
+    aud: &'a str,
+    iat: i64,
+    exp: i64,
+}
+
+#[derive(Debug, Deserialize)]
+struct TokenResponse {
+    access_token: String,
+    expires_in: u64,
+}
+
+/// FCM sender for one Firebase project.
+pub struct FcmBackend {
+    http: reqwest::Client,
+    project_id: String,
+    client_email: String,
+    token_uri: String,
+    encoding_key: EncodingKey,
+    access_token: Mutex<Option<(String, Instant)>>,
+}
+
+impl FcmBackend {
+    /// Create a backend from the contents of a service account key file.
+    pub fn from_service_account_json(json: &str, http: reqwest::Client) -> Result<Self, String> {
+        let account: ServiceAccount = serde_json::from_str(json)
+            .map_err(|e| format!("Invalid FCM service account file: {e}"))?;
+        let encoding_key = EncodingKey::from_rsa_pem(account.private_key.as_bytes())
+            .map_err(|e| format!("Invalid FCM service account private key: {e}"))?;
+        Ok(Self {
+            http,
+            project_id: account.project_id,
+            client_email: account.client_email,
+            token_uri: account.token_uri,
+            encoding_key,
+            access_token: Mutex::new(None),
+        })
+    }
+
+    /// Return a cached OAuth access token, exchanging a new assertion if needed.
+    async fn access_token(&self) -> Result<String, PushSendError> {
+        let mut cached = self.access_token.lock().await;
+        if let Some((token, expires_at)) = cached.as_ref() {
+            if Instant::now() + TOKEN_REFRESH_MARGIN < *expires_at {
+                return Ok(token.clone());
+            }
+        }
+
+        let now = chrono::Utc::now().timestamp();
+        let assertion = jsonwebtoken::encode(
+            &Header::new(Algorithm::RS256),
+            &AssertionClaims {
+                iss: &self.client_email,
+                scope: FCM_SCOPE,
+                aud: &self.token_uri,
+                iat: now,
+                exp: now + ASSERTION_LIFETIME_SECS,
+            },
+            &self.encoding_key,
+        )
+        .map_err(|e| PushSendError::Failed(format!("Failed to sign FCM assertion: {e}")))?;
+
+        let response = self
+            .http
+            .post(&self.token_uri)
+            .form(&[
+                ("grant_type", "urn:ietf:params:oauth:grant-type:jwt-bearer"),
+                ("assertion", assertion.as_str()),
+            ])
+            .send()
+            .await
+            .map_err(|e| PushSendError::Failed(format!("FCM token exchange failed: {e}")))?;
+        if !response.status().is_success() {
+            return Err(PushSendError::Failed(format!(
+                "FCM token exchange returned {}",
+                response.status()
+            )));
+        }
+        let token: TokenResponse = response
+            .json()
+            .await
+            .map_err(|e| PushSendError::Failed(format!("Invalid FCM token response: {e}")))?;
+
+        let expires_at = Instant::now() + Duration::from_secs(token.expires_in);
+        *cached = Some((token.access_token.clone(), expires_at));
+        Ok(token.access_token)
+    }
+
+    /// Send a notification to one registration token.
+    pub async fn send(
+        &self,
+        device: &PushDeviceRow,
+        notification: &PushNotification,
+    ) -> Result<(), PushSendError> {
+        let access_token = self.access_token().await?;
+
+        // FCM data values must be strings
+        let mut data = serde_json::Map::new();
+        data.insert("kind".into(), notification.kind.into());
+        data.insert(
+            "channel_id".into(),
+            notification.channel_id.to_string().into(),
+        );
+        if let Some(guild_id) = notification.guild_id {
+            data.insert("guild_id".into(), guild_id.to_string().into());
+        }
+        if let Some(message_id) = notification.message_id {
+            data.insert("message_id".into(), message_id.to_string().into());
+        }
+
+        let body = serde_json::json!({
+            "message": {
+                "token": device.token,
+                "notification": {
+                    "title": notification.title,
+                    "body": notification.body,
+                },
+                "data": data,
+                "android": { "priority": "high" },
+                "apns": { "headers": { "apns-priority": "10" } },
+            }
+        });
+
+        let response = self
+            .http
+            .post(format!(
+                "https://fcm.googleapis.com/v1/projects/{}/messages:send",
+                self.project_id
+            ))
+            .bearer_auth(access_token)
+            .json(&body)
+            .send()
+            .await
+            .map_err(|e| PushSendError::Failed(e.to_string()))?;
+
+        let status = response.status();
+        if status.is_success() {
+            return Ok(());
+        }
+        let error_body = response.text().await.unwrap_or_default();
+        if status.as_u16() == 404 || error_body.contains("UNREGISTERED") {
+            return Err(PushSendError::InvalidToken);
+        }
+        if status.as_u16() == 401 {
+            // Force a fresh access token on the next send
+            *self.access_token.lock().await = None;
+        }
+        Err(PushSendError::Failed(format!("FCM returned {status}")))
+    }
+}
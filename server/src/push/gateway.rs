@@ -0,0 +1,112 @@
+//! Push Gateway
+//!
+//! Routes a notification to the backend for the device's platform. Each
+//! backend is enabled independently through `Config`; devices on a platform
+//! without a backend are skipped.
+
+use tracing::{info, warn};
+
+use super::fcm::FcmBackend;
+use super::types::{PushDeviceRow, PushNotification, PushPlatform};
+use super::webpush::WebPushBackend;
+use crate::config::Config;
+
+/// Why a push could not be delivered.
+#[derive(Debug, thiserror::Error)]
+pub enum PushSendError {
+    /// The push service no longer knows this device; it should be removed.
+    #[error("Push token is no longer valid")]
+    InvalidToken,
+
+    /// No backend is configured for the device's platform.
+    #[error("Push platform not enabled")]
+    Disabled,
+
+    /// Transient or unexpected failure.
+    #[error("Push delivery failed: {0}")]
+    Failed(String),
+}
+
+/// The configured push backends.
+#[derive(Default)]
+pub struct PushGateway {
+    web_push: Option<WebPushBackend>,
+    fcm: Option<FcmBackend>,
+}
+
+impl PushGateway {
+    /// Build the gateway from configuration, disabling backends whose
+    /// credentials are invalid.
+    pub fn from_config(config: &Config, http: reqwest::Client) -> Self {
+        let web_push = match (&config.push_vapid_private_key, &config.push_vapid_subject) {
+            (Some(key), Some(subject)) => match WebPushBackend::new(key, subject) {
+                Ok(backend) => {
+                    info!("Web Push backend enabled");
+                    Some(backend)
+                }
+                Err(e) => {
+                    warn!(error = %e, "Web Push disabled");
+                    None
+                }
+            },
+            (Some(_), None) => {
+                warn!("PUSH_VAPID_PRIVATE_KEY set without PUSH_VAPID_SUBJECT. Web Push disabled.");
+                None
+            }
+            _ => None,
+        };
+
+        let fcm = config.push_fcm_service_account.as_ref().and_then(|path| {
+            let backend = std::fs::read_to_string(path)
+                .map_err(|e| format!("Failed to read {path}: {e}"))
+                .and_then(|json| FcmBackend::from_service_account_json(&json, http));
+            match backend {
+                Ok(backend) => {
+                    info!("FCM push backend enabled");
+                    Some(backend)
+                }
+                Err(e) => {
+                    warn!(error = %e, "FCM push disabled");
+                    None
+                }
+            }
+        });
+
+        Self { web_push, fcm }
+    }
+
+    /// Whether any backend is enabled.
+    #[must_use]
+    pub const fn is_enabled(&self) -> bool {
+        self.web_push.is_some() || self.fcm.is_some()
+    }
+
+    /// Send a notification to one device.
+    pub async fn send(
+        &self,
+        device: &PushDeviceRow,
+        notification: &PushNotification,
+    ) -> Result<(), PushSendError> {
+        match device.platform {
+            PushPlatform::WebPush => match &self.web_push {
+                Some(backend) => backend.send(device, notification).await,
+                None => Err(PushSendError::Disabled),
+            },
+            PushPlatform::Fcm => match &self.fcm {
+                Some(backend) => backend.send(device, notification).await,
+                None => Err(PushSendError::Disabled),
+            },
+        }
+    }
+}
+
+/// The VAPID application server key for clients, if Web Push is configured.
+pub fn vapid_public_key(config: &Config) -> Option<String> {
+    let (key, subject) = (
+        config.push_vapid_private_key.as_ref()?,
+        config.push_vapid_subject.as_ref()?,
+    );
+    WebPushBackend::new(key, subject)
+        .ok()
+        .map(|backend| backend.public_key().to_string())
+}
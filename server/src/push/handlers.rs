@@ -0,0 +1,169 @@
+//! Push Device HTTP Handlers
+
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::Json;
+use uuid::Uuid;
+use validator::Validate;
+
+use super::error::PushError;
+use super::gateway::vapid_public_key;
+use super::queries;
+use super::types::{
+    PushConfigResponse, PushDeviceResponse, PushPlatform, RegisterDeviceRequest,
+    MAX_DEVICES_PER_USER,
+};
+use crate::api::AppState;
+use crate::auth::AuthUser;
+
+/// Get the server's push configuration.
+///
+/// GET /api/me/push-devices/config
+#[utoipa::path(
+    get,
+    path = "/api/me/push-devices/config",
+    tag = "push",
+    responses(
+        (status = 200, body = PushConfigResponse),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[tracing::instrument(skip(state, _auth_user))]
+pub async fn get_config(
+    State(state): State<AppState>,
+    _auth_user: AuthUser,
+) -> Json<PushConfigResponse> {
+    Json(PushConfigResponse {
+        vapid_public_key: vapid_public_key(&state.config()),
+        fcm: state.config().has_fcm(),
+    })
+}
+
+/// Register a device for push notifications.
+///
+/// Re-registering an existing token updates it (and moves it to the caller).
+///
+/// POST /api/me/push-devices
+#[utoipa::path(
+    post,
+    path = "/api/me/push-devices",
+    tag = "push",
+    request_body = RegisterDeviceRequest,
+    responses(
+        (status = 201, body = PushDeviceResponse),
+        (status = 400, description = "Invalid device or platform not enabled"),
+        (status = 403, description = "Device limit exceeded"),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[tracing::instrument(skip(state, request))]
+pub async fn register_device(
+    State(state): State<AppState>,
+    auth_user: AuthUser,
+    Json(request): Json<RegisterDeviceRequest>,
+) -> Result<(StatusCode, Json<PushDeviceResponse>), PushError> {
+    request
+        .validate()
+        .map_err(|e| PushError::Validation(e.to_string()))?;
+
+    match request.platform {
+        PushPlatform::WebPush => {
+            if !state.config().has_web_push() {
+                return Err(PushError::PlatformDisabled);
+            }
+            if !request.token.starts_with("https://") {
+                return Err(PushError::Validation(
+                    "Web Push endpoint must be an https URL".to_string(),
+                ));
+            }
+            if request.p256dh.is_none() || request.auth.is_none() {
+                return Err(PushError::Validation(
+                    "Web Push subscriptions require p256dh and auth keys".to_string(),
+                ));
+            }
+        }
+        PushPlatform::Fcm => {
+            if !state.config().has_fcm() {
+                return Err(PushError::PlatformDisabled);
+            }
+        }
+    }
+
+    let existing =
+        queries::count_other_devices(&state.db, auth_user.id, request.platform, &request.token)
+            .await?;
+    if existing >= MAX_DEVICES_PER_USER {
+        return Err(PushError::DeviceLimitExceeded);
+    }
+
+    let (p256dh, auth) = match request.platform {
+        PushPlatform::WebPush => (request.p256dh.as_deref(), request.auth.as_deref()),
+        PushPlatform::Fcm => (None, None),
+    };
+    let device_name = request
+        .device_name
+        .as_deref()
+        .map(str::trim)
+        .filter(|s| !s.is_empty());
+
+    let row = queries::upsert_device(
+        &state.db,
+        auth_user.id,
+        request.platform,
+        &request.token,
+        p256dh,
+        auth,
+        device_name,
+    )
+    .await?;
+
+    Ok((StatusCode::CREATED, Json(row.into())))
+}
+
+/// List the caller's push devices.
+///
+/// GET /api/me/push-devices
+#[utoipa::path(
+    get,
+    path = "/api/me/push-devices",
+    tag = "push",
+    responses(
+        (status = 200, body = Vec<PushDeviceResponse>),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[tracing::instrument(skip(state))]
+pub async fn list_devices(
+    State(state): State<AppState>,
+    auth_user: AuthUser,
+) -> Result<Json<Vec<PushDeviceResponse>>, PushError> {
+    let rows = queries::list_devices(&state.db, auth_user.id).await?;
+    Ok(Json(rows.into_iter().map(Into::into).collect()))
+}
+
+/// Unregister a push device.
+///
+/// DELETE /api/me/push-devices/{id}
+#[utoipa::path(
+    delete,
+    path = "/api/me/push-devices/{id}",
+    tag = "push",
+    params(("id" = Uuid, Path, description = "Push device ID")),
+    responses(
+        (status = 204, description = "Device removed"),
+        (status = 404, description = "Device not found"),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[tracing::instrument(skip(state))]
+pub async fn delete_device(
+    State(state): State<AppState>,
+    auth_user: AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<StatusCode, PushError> {
+    if queries::delete_device(&state.db, auth_user.id, id).await? {
+        Ok(StatusCode::NO_CONTENT)
+    } else {
+        Err(PushError::NotFound)
+    }
+}
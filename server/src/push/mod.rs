@@ -0,0 +1,33 @@
+//! Push Notifications
+//!
+//! Delivers mentions, DMs and incoming calls to users who are not connected
+//! over WebSocket, via Web Push (VAPID) for browsers and FCM for mobile.
+//! Producers enqueue a `PushJob`; the worker resolves recipients and sends
+//! through whichever backends `Config` enables.
+
+pub mod error;
+pub mod fcm;
+pub mod gateway;
+pub mod handlers;
+pub mod queries;
+pub mod types;
+pub mod webpush;
+pub mod worker;
+
+use axum::routing::{delete, get};
+use axum::Router;
+
+use crate::api::AppState;
+
+/// Create push device routes.
+///
+/// Mounted at `/api/me/push-devices` in the main router.
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/",
+            get(handlers::list_devices).post(handlers::register_device),
+        )
+        .route("/config", get(handlers::get_config))
+        .route("/{id}", delete(handlers::delete_device))
+}
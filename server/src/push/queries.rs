@@ -0,0 +1,111 @@
+//! Push Device Queries
+
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use super::types::{PushDeviceRow, PushPlatform};
+
+const DEVICE_COLUMNS: &str =
+    "id, user_id, platform, token, p256dh, auth_secret, device_name, created_at, last_used_at";
+
+/// Number of devices a user has registered, not counting `(platform, token)`.
+pub async fn count_other_devices(
+    pool: &PgPool,
+    user_id: Uuid,
+    platform: PushPlatform,
+    token: &str,
+) -> Result<i64, sqlx::Error> {
+    sqlx::query_scalar(
+        "SELECT COUNT(*) FROM push_devices
+         WHERE user_id = $1 AND NOT (platform = $2 AND token = $3)",
+    )
+    .bind(user_id)
+    .bind(platform)
+    .bind(token)
+    .fetch_one(pool)
+    .await
+}
+
+/// Register a device, taking over the token if another account had it.
+///
+/// A browser subscription or FCM token belongs to one install, so whoever
+/// registered it last is the one who gets its notifications.
+pub async fn upsert_device(
+    pool: &PgPool,
+    user_id: Uuid,
+    platform: PushPlatform,
+    token: &str,
+    p256dh: Option<&str>,
+    auth_secret: Option<&str>,
+    device_name: Option<&str>,
+) -> Result<PushDeviceRow, sqlx::Error> {
+    sqlx::query_as(&format!(
+        "INSERT INTO push_devices (user_id, platform, token, p256dh, auth_secret, device_name)
+         VALUES ($1, $2, $3, $4, $5, $6)
+         ON CONFLICT (platform, token) DO UPDATE SET
+             user_id = EXCLUDED.user_id,
+             p256dh = EXCLUDED.p256dh,
+             auth_secret = EXCLUDED.auth_secret,
+             device_name = EXCLUDED.device_name
+         RETURNING {DEVICE_COLUMNS}"
+    ))
+    .bind(user_id)
+    .bind(platform)
+    .bind(token)
+    .bind(p256dh)
+    .bind(auth_secret)
+    .bind(device_name)
+    .fetch_one(pool)
+    .await
+}
+
+/// List a user's devices, newest first.
+pub async fn list_devices(pool: &PgPool, user_id: Uuid) -> Result<Vec<PushDeviceRow>, sqlx::Error> {
+    sqlx::query_as(&format!(
+        "SELECT {DEVICE_COLUMNS} FROM push_devices WHERE user_id = $1 ORDER BY created_at DESC"
+    ))
+    .bind(user_id)
+    .fetch_all(pool)
+    .await
+}
+
+/// Delete one of a user's devices. Returns whether it existed.
+pub async fn delete_device(pool: &PgPool, user_id: Uuid, id: Uuid) -> Result<bool, sqlx::Error> {
+    let result = sqlx::query("DELETE FROM push_devices WHERE id = $1 AND user_id = $2")
+        .bind(id)
+        .bind(user_id)
+        .execute(pool)
+        .await?;
+    Ok(result.rows_affected() > 0)
+}
+
+/// All devices belonging to the given users.
+pub async fn devices_for_users(
+    pool: &PgPool,
+    user_ids: &[Uuid],
+) -> Result<Vec<PushDeviceRow>, sqlx::Error> {
+    sqlx::query_as(&format!(
+        "SELECT {DEVICE_COLUMNS} FROM push_devices WHERE user_id = ANY($1)"
+    ))
+    .bind(user_ids)
+    .fetch_all(pool)
+    .await
+}
+
+/// Remove a device the push service reported as no longer valid.
+pub async fn delete_device_by_id(pool: &PgPool, id: Uuid) -> Result<(), sqlx::Error> {
+    sqlx::query("DELETE FROM push_devices WHERE id = $1")
+        .bind(id)
+        .execute(pool)
+        .await?;
+    Ok(())
+}
+
+/// Record a successful delivery.
+pub async fn mark_used(pool: &PgPool, id: Uuid) -> Result<(), sqlx::Error> {
+    sqlx::query("UPDATE push_devices SET last_used_at = NOW() WHERE id = $1")
+        .bind(id)
+        .execute(pool)
+        .await?;
+    Ok(())
+}
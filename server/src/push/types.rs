@@ -0,0 +1,133 @@
+//! Push Notification Types
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+use validator::Validate;
+
+/// Maximum push devices a single user may register.
+pub const MAX_DEVICES_PER_USER: i64 = 20;
+
+/// Push service a device is registered with.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type, utoipa::ToSchema,
+)]
+#[sqlx(type_name = "push_platform", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum PushPlatform {
+    /// Browser Push API subscription (RFC 8030, VAPID).
+    WebPush,
+    /// Firebase Cloud Messaging registration token.
+    Fcm,
+}
+
+// ============================================================================
+// Database Row Types
+// ============================================================================
+
+#[derive(Debug, Clone, FromRow)]
+pub struct PushDeviceRow {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub platform: PushPlatform,
+    pub token: String,
+    pub p256dh: Option<String>,
+    pub auth_secret: Option<String>,
+    pub device_name: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub last_used_at: Option<DateTime<Utc>>,
+}
+
+// ============================================================================
+// API Request/Response Types
+// ============================================================================
+
+/// Register a device for push notifications.
+///
+/// For Web Push, `token` is the subscription endpoint and `p256dh`/`auth`
+/// come from the subscription's keys. For FCM, `token` is the registration
+/// token and the keys are omitted.
+#[derive(Debug, Deserialize, Validate, utoipa::ToSchema)]
+pub struct RegisterDeviceRequest {
+    pub platform: PushPlatform,
+    #[validate(length(min = 1, max = 2048))]
+    pub token: String,
+    #[validate(length(min = 1, max = 256))]
+    pub p256dh: Option<String>,
+    #[validate(length(min = 1, max = 256))]
+    pub auth: Option<String>,
+    #[validate(length(max = 64))]
+    pub device_name: Option<String>,
+}
+
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct PushDeviceResponse {
+    pub id: Uuid,
+    pub platform: PushPlatform,
+    pub device_name: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub last_used_at: Option<DateTime<Utc>>,
+}
+
+impl From<PushDeviceRow> for PushDeviceResponse {
+    fn from(row: PushDeviceRow) -> Self {
+        Self {
+            id: row.id,
+            platform: row.platform,
+            device_name: row.device_name,
+            created_at: row.created_at,
+            last_used_at: row.last_used_at,
+        }
+    }
+}
+
+/// Which push backends the server has configured.
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct PushConfigResponse {
+    /// VAPID application server key (base64url, uncompressed P-256 point) to
+    /// pass to `PushManager.subscribe()`. `None` if Web Push is disabled.
+    pub vapid_public_key: Option<String>,
+    /// Whether FCM delivery is enabled.
+    pub fcm: bool,
+}
+
+// ============================================================================
+// Queue Types
+// ============================================================================
+
+/// An event that may produce push notifications, queued for the push worker.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum PushJob {
+    /// A new message in a DM or guild channel.
+    Message {
+        channel_id: Uuid,
+        message_id: Uuid,
+        guild_id: Option<Uuid>,
+        author_id: Uuid,
+        author_name: String,
+        channel_name: String,
+        /// Message text; `None` for encrypted messages.
+        content: Option<String>,
+    },
+    /// A DM call was started.
+    Call {
+        channel_id: Uuid,
+        initiator_id: Uuid,
+        initiator_name: String,
+        recipients: Vec<Uuid>,
+    },
+}
+
+/// Notification content handed to a push backend.
+#[derive(Debug, Clone, Serialize)]
+pub struct PushNotification {
+    pub title: String,
+    pub body: String,
+    /// Event kind: `"mention"`, `"dm"` or `"call"`.
+    pub kind: &'static str,
+    pub channel_id: Uuid,
+    pub guild_id: Option<Uuid>,
+    pub message_id: Option<Uuid>,
+}
@@ -0,0 +1,285 @@
+//! Web Push Backend
+//!
+//! Delivers to browser Push API subscriptions: VAPID authentication
+//! (RFC 8292) and `aes128gcm` payload encryption (RFC 8291, RFC 8188).
+//! Subscription endpoints are client-supplied URLs, so each request goes
+//! through the webhook SSRF check and is pinned to the verified address.
+
+use std::time::Duration;
+
+use aes_gcm::aead::{Aead, KeyInit, OsRng};
+use aes_gcm::{Aes128Gcm, Nonce};
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use hkdf::Hkdf;
+use p256::ecdh::EphemeralSecret;
+use p256::ecdsa::signature::Signer;
+use p256::ecdsa::{Signature, SigningKey};
+use p256::elliptic_curve::sec1::ToEncodedPoint;
+use p256::PublicKey;
+use rand::RngCore;
+use sha2::Sha256;
+
+use super::gateway::PushSendError;
+use super::types::{PushDeviceRow, PushNotification};
+use crate::webhooks::ssrf;
+
+/// Record size advertised in the `aes128gcm` header. Payloads are far smaller,
+/// so every message is a single record.
+const RECORD_SIZE: u32 = 4096;
+
+/// How long the push service should keep an undelivered message.
+const MESSAGE_TTL_SECS: u32 = 60 * 60;
+
+/// Lifetime of a VAPID token (the spec caps it at 24 hours).
+const VAPID_TOKEN_LIFETIME_SECS: i64 = 12 * 60 * 60;
+
+/// Web Push sender holding the server's VAPID key pair.
+pub struct WebPushBackend {
+    signing_key: SigningKey,
+    /// Uncompressed public key, base64url; the `applicationServerKey`.
+    public_key: String,
+    /// Contact URI (`mailto:` or `https:`) sent in the VAPID `sub` claim.
+    subject: String,
+}
+
+impl WebPushBackend {
+    /// Create a backend from a base64url-encoded raw P-256 private key.
+    pub fn new(private_key: &str, subject: &str) -> Result<Self, String> {
+        let bytes = URL_SAFE_NO_PAD
+            .decode(private_key.trim().trim_end_matches('='))
+            .map_err(|e| format!("Invalid VAPID private key encoding: {e}"))?;
+        let signing_key = SigningKey::from_slice(&bytes)
+            .map_err(|e| format!("Invalid VAPID private key: {e}"))?;
+        let public_key = URL_SAFE_NO_PAD.encode(
+            signing_key
+                .verifying_key()
+                .to_encoded_point(false)
+                .as_bytes(),
+        );
+        Ok(Self {
+            signing_key,
+            public_key,
+            subject: subject.to_string(),
+        })
+    }
+
+    /// The application server key clients subscribe with.
+    pub fn public_key(&self) -> &str {
+        &self.public_key
+    }
+
+    /// Build the `Authorization` header value for an endpoint.
+    fn authorization(&self, endpoint: &reqwest::Url) -> Result<String, PushSendError> {
+        let header = URL_SAFE_NO_PAD.encode(br#"{"typ":"JWT","alg":"ES256"}"#);
+        let claims = serde_json::json!({
+            "aud": endpoint.origin().ascii_serialization(),
+            "exp": chrono::Utc::now().timestamp() + VAPID_TOKEN_LIFETIME_SECS,
+            "sub": self.subject,
+        });
+        let claims = URL_SAFE_NO_PAD
+            .encode(serde_json::to_vec(&claims).map_err(|e| PushSendError::Failed(e.to_string()))?);
+        let signing_input = format!("{header}.{claims}");
+        let signature: Signature = self.signing_key.sign(signing_input.as_bytes());
+        let token = format!(
+            "{signing_input}.{}",
+            URL_SAFE_NO_PAD.encode(signature.to_bytes())
+        );
+        Ok(format!("vapid t={token}, k={}", self.public_key))
+    }
+
+    /// Send a notification to one browser subscription.
+    pub async fn send(
+        &self,
+        device: &PushDeviceRow,
+        notification: &PushNotification,
+    ) -> Result<(), PushSendError> {
+        let (Some(p256dh), Some(auth)) = (&device.p256dh, &device.auth_secret) else {
+            return Err(PushSendError::InvalidToken);
+        };
+        let endpoint =
+            reqwest::Url::parse(&device.token).map_err(|_| PushSendError::InvalidToken)?;
+        if endpoint.scheme() != "https" {
+            return Err(PushSendError::InvalidToken);
+        }
+
+        let payload =
+            serde_json::to_vec(notification).map_err(|e| PushSendError::Failed(e.to_string()))?;
+        let ua_public = decode_key(p256dh)?;
+        let auth_secret = decode_key(auth)?;
+        let body = encrypt(&payload, &ua_public, &auth_secret)?;
+
+        let verified = ssrf::verify_resolved_ip(endpoint.as_str())
+            .await
+            .map_err(PushSendError::Failed)?;
+        let client = reqwest::Client::builder()
+            .resolve(&verified.host, verified.addr)
+            .timeout(Duration::from_secs(10))
+            .build()
+            .map_err(|e| PushSendError::Failed(e.to_string()))?;
+
+        let response = client
+            .post(endpoint.clone())
+            .header("Authorization", self.authorization(&endpoint)?)
+            .header("Content-Encoding", "aes128gcm")
+            .header("Content-Type", "application/octet-stream")
+            .header("TTL", MESSAGE_TTL_SECS.to_string())
+            .header("Urgency", "high")
+            .body(body)
+            .send()
+            .await
+            .map_err(|e| PushSendError::Failed(e.to_string()))?;
+
+        match response.status().as_u16() {
+            200..=299 => Ok(()),
+            404 | 410 => Err(PushSendError::InvalidToken),
+            status => Err(PushSendError::Failed(format!(
+                "push service returned {status}"
+            ))),
+        }
+    }
+}
+
+fn decode_key(value: &str) -> Result<Vec<u8>, PushSendError> {
+    URL_SAFE_NO_PAD
+        .decode(value.trim_end_matches('='))
+        .map_err(|_| PushSendError::InvalidToken)
+}
+
+fn hkdf_expand<const N: usize>(salt: &[u8], ikm: &[u8], info: &[u8]) -> [u8; N] {
+    let mut okm = [0u8; N];
+    Hkdf::<Sha256>::new(Some(salt), ikm)
+        .expand(info, &mut okm)
+        .expect("output length is valid for HKDF-SHA256");
+    okm
+}
+
+/// Derive the content encryption key and nonce (RFC 8291 section 3.4).
+fn derive_key_and_nonce(
+    ecdh_secret: &[u8],
+    auth_secret: &[u8],
+    ua_public: &[u8],
+    as_public: &[u8],
+    salt: &[u8],
+) -> ([u8; 16], [u8; 12]) {
+    let mut key_info = b"WebPush: info\0".to_vec();
+    key_info.extend_from_slice(ua_public);
+    key_info.extend_from_slice(as_public);
+    let ikm: [u8; 32] = hkdf_expand(auth_secret, ecdh_secret, &key_info);
+
+    let cek = hkdf_expand(salt, &ikm, b"Content-Encoding: aes128gcm\0");
+    let nonce = hkdf_expand(salt, &ikm, b"Content-Encoding: nonce\0");
+    (cek, nonce)
+}
+
+/// Encrypt a payload for a subscription as a single `aes128gcm` record.
+fn encrypt(payload: &[u8], ua_public: &[u8], auth_secret: &[u8]) -> Result<Vec<u8>, PushSendError> {
+    let ua_key = PublicKey::from_sec1_bytes(ua_public).map_err(|_| PushSendError::InvalidToken)?;
+    let ephemeral = EphemeralSecret::random(&mut OsRng);
+    let as_public = ephemeral.public_key().to_encoded_point(false);
+    let shared = ephemeral.diffie_hellman(&ua_key);
+
+    let mut salt = [0u8; 16];
+    OsRng.fill_bytes(&mut salt);
+
+    let (cek, nonce) = derive_key_and_nonce(
+        shared.raw_secret_bytes(),
+        auth_secret,
+        ua_public,
+        as_public.as_bytes(),
+        &salt,
+    );
+
+    // Padding delimiter 0x02 marks the last (and only) record
+    let mut plaintext = payload.to_vec();
+    plaintext.push(0x02);
+    let ciphertext = Aes128Gcm::new_from_slice(&cek)
+        .map_err(|e| PushSendError::Failed(e.to_string()))?
+        .encrypt(Nonce::from_slice(&nonce), plaintext.as_slice())
+        .map_err(|e| PushSendError::Failed(e.to_string()))?;
+
+    let key_id = as_public.as_bytes();
+    let mut body = Vec::with_capacity(16 + 4 + 1 + key_id.len() + ciphertext.len());
+    body.extend_from_slice(&salt);
+    body.extend_from_slice(&RECORD_SIZE.to_be_bytes());
+    body.push(key_id.len() as u8);
+    body.extend_from_slice(key_id);
+    body.extend_from_slice(&ciphertext);
+    Ok(body)
+}
+
+#[cfg(test)]
+mod tests {
+    use p256::SecretKey;
+
+    use super::*;
+
+    /// Decrypt as a browser would, using the subscription's private key.
+    fn decrypt(body: &[u8], ua_secret: &SecretKey, auth_secret: &[u8]) -> Vec<u8> {
+        let salt = &body[..16];
+        let key_len = body[20] as usize;
+        let as_public = &body[21..21 + key_len];
+        let ciphertext = &body[21 + key_len..];
+
+        let as_key = PublicKey::from_sec1_bytes(as_public).unwrap();
+        let shared = p256::ecdh::diffie_hellman(ua_secret.to_nonzero_scalar(), as_key.as_affine());
+        let ua_public = ua_secret.public_key().to_encoded_point(false);
+
+        let (cek, nonce) = derive_key_and_nonce(
+            shared.raw_secret_bytes(),
+            auth_secret,
+            ua_public.as_bytes(),
+            as_public,
+            salt,
+        );
+        let mut plaintext = Aes128Gcm::new_from_slice(&cek)
+            .unwrap()
+            .decrypt(Nonce::from_slice(&nonce), ciphertext)
+            .unwrap();
+        assert_eq!(plaintext.pop(), Some(0x02));
+        plaintext
+    }
+
+    #[test]
+    fn test_encrypt_round_trip() {
+        let ua_secret = SecretKey::random(&mut OsRng);
+        let ua_public = ua_secret.public_key().to_encoded_point(false);
+        let auth_secret = [7u8; 16];
+
+        let body = encrypt(b"hello push", ua_public.as_bytes(), &auth_secret).unwrap();
+
+        assert_eq!(&body[16..20], &RECORD_SIZE.to_be_bytes());
+        assert_eq!(body[20], 65);
+        assert_eq!(decrypt(&body, &ua_secret, &auth_secret), b"hello push");
+    }
+
+    #[test]
+    fn test_encrypt_rejects_invalid_client_key() {
+        assert!(matches!(
+            encrypt(b"x", &[4u8; 65], &[0u8; 16]),
+            Err(PushSendError::InvalidToken)
+        ));
+    }
+
+    #[test]
+    fn test_vapid_authorization_header() {
+        let backend = WebPushBackend::new(
+            &URL_SAFE_NO_PAD.encode([1u8; 32]),
+            "mailto:admin@example.com",
+        )
+        .unwrap();
+        let endpoint = reqwest::Url::parse("https://push.example.com/send/abc").unwrap();
+        let header = backend.authorization(&endpoint).unwrap();
+
+        let (token, key) = header
+            .strip_prefix("vapid t=")
+            .and_then(|rest| rest.split_once(", k="))
+            .unwrap();
+        assert_eq!(key, backend.public_key());
+        let claims = token.split('.').nth(1).unwrap();
+        let claims: serde_json::Value =
+            serde_json::from_slice(&URL_SAFE_NO_PAD.decode(claims).unwrap()).unwrap();
+        assert_eq!(claims["aud"], "https://push.example.com");
+        assert_eq!(claims["sub"], "mailto:admin@example.com");
+    }
+}
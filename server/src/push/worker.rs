@@ -0,0 +1,439 @@
+//! Push Delivery Worker
+//!
+//! Consumes `PushJob`s from a Redis list, works out who should be notified,
+//! and hands the notification to the gateway for each of their devices.
+//!
+//! A recipient is skipped when they:
+//! - have an open WebSocket connection on any instance (they see it live),
+//! - muted the channel in their synced notification preferences,
+//! - blocked the sender or are blocked by them.
+//!
+//! Guild messages notify directly @mentioned members who can view the
+//! channel, plus members whose highlight keywords match the message (see
+//! `chat::highlights`); `@everyone`/`@here` are left to connected clients.
+
+use std::collections::HashSet;
+use std::sync::{Arc, LazyLock};
+use std::time::Duration;
+
+use fred::interfaces::ListInterface;
+use fred::prelude::*;
+use sqlx::PgPool;
+use tracing::{debug, error, info, warn};
+use uuid::Uuid;
+
+use super::gateway::{PushGateway, PushSendError};
+use super::queries;
+use super::types::{PushJob, PushNotification};
+use crate::auth::username::USERNAME_REDIRECT_DAYS;
+use crate::chat::highlights;
+use crate::presence::registry as presence_registry;
+use crate::social::block_cache;
+
+/// Redis key for the push job queue.
+const PUSH_QUEUE_KEY: &str = "push:queue";
+
+/// Most mentioned users resolved per message.
+const MAX_MENTIONS_PER_MESSAGE: usize = 20;
+
+/// Longest message preview sent in a notification body.
+const MAX_BODY_CHARS: usize = 140;
+
+static MENTION_REGEX: LazyLock<regex::Regex> =
+    LazyLock::new(|| regex::Regex::new(r"@(\w+)").expect("valid regex"));
+
+/// Enqueue a job for the push worker.
+pub async fn enqueue(redis: &Client, job: &PushJob) -> Result<(), Error> {
+    let payload = serde_json::to_string(job)
+        .map_err(|e| Error::new(ErrorKind::Parse, format!("JSON serialize error: {e}")))?;
+
+    redis.lpush::<(), _, _>(PUSH_QUEUE_KEY, payload).await?;
+    Ok(())
+}
+
+/// Enqueue a job, logging instead of failing the caller.
+pub async fn enqueue_or_warn(redis: &Client, job: &PushJob) {
+    if let Err(e) = enqueue(redis, job).await {
+        warn!(error = %e, "Failed to enqueue push job");
+    }
+}
+
+/// Spawn the background push worker.
+pub async fn spawn_push_worker(db: PgPool, redis: Client, gateway: Arc<PushGateway>) {
+    info!("Push delivery worker started");
+
+    let mut consecutive_errors: u32 = 0;
+
+    loop {
+        let result: Result<Option<(String, String)>, _> = redis.brpop(PUSH_QUEUE_KEY, 2.0).await;
+
+        let payload_str = match result {
+            Ok(Some((_key, value))) => {
+                consecutive_errors = 0;
+                value
+            }
+            // fred 10.x reports an empty BRPOP as ErrorKind::Timeout
+            Ok(None) => {
+                consecutive_errors = 0;
+                continue;
+            }
+            Err(ref e) if matches!(e.kind(), fred::error::ErrorKind::Timeout) => {
+                consecutive_errors = 0;
+                continue;
+            }
+            Err(e) => {
+                consecutive_errors += 1;
+                let backoff_secs = 1u64 << consecutive_errors.min(6); // 2, 4, 8, ... 64
+                error!(
+                    consecutive_errors,
+                    backoff_secs, "Failed to BRPOP from push queue: {}", e
+                );
+                tokio::time::sleep(Duration::from_secs(backoff_secs)).await;
+                continue;
+            }
+        };
+
+        let job: PushJob = match serde_json::from_str(&payload_str) {
+            Ok(job) => job,
+            Err(e) => {
+                let truncated: String = payload_str.chars().take(500).collect();
+                error!(
+                    error = %e,
+                    payload_preview = %truncated,
+                    "Failed to deserialize push job"
+                );
+                continue;
+            }
+        };
+
+        let db = db.clone();
+        let redis = redis.clone();
+        let gateway = gateway.clone();
+        tokio::spawn(async move {
+            let handle = tokio::spawn(async move {
+                process_job(&db, &redis, &gateway, job).await;
+            });
+            if let Err(e) = handle.await {
+                error!("Push job task panicked: {}", e);
+            }
+        });
+    }
+}
+
+/// Resolve recipients for a job and deliver to their devices.
+async fn process_job(db: &PgPool, redis: &Client, gateway: &PushGateway, job: PushJob) {
+    let (sender_id, channel_id, candidates) = match &job {
+        PushJob::Message {
+            channel_id,
+            guild_id,
+            author_id,
+            content,
+            ..
+        } => {
+            let candidates = match guild_id {
+                None => dm_recipients(db, *channel_id, *author_id).await,
+                Some(guild_id) => match content {
+                    Some(content) => {
+                        guild_recipients(db, *guild_id, *channel_id, *author_id, content).await
+                    }
+                    None => Ok(Vec::new()),
+                },
+            };
+            match candidates {
+                Ok(candidates) => (*author_id, *channel_id, candidates),
+                Err(e) => {
+                    error!(channel_id = %channel_id, error = %e, "Failed to resolve push recipients");
+                    return;
+                }
+            }
+        }
+        PushJob::Call {
+            channel_id,
+            initiator_id,
+            recipients,
+            ..
+        } => (*initiator_id, *channel_id, recipients.clone()),
+    };
+
+    let recipients = filter_recipients(db, redis, sender_id, channel_id, candidates).await;
+    if recipients.is_empty() {
+        return;
+    }
+
+    let devices = match queries::devices_for_users(db, &recipients).await {
+        Ok(devices) => devices,
+        Err(e) => {
+            error!(error = %e, "Failed to load push devices");
+            return;
+        }
+    };
+    if devices.is_empty() {
+        return;
+    }
+
+    let notification = build_notification(&job);
+    for device in devices {
+        match gateway.send(&device, &notification).await {
+            Ok(()) => {
+                if let Err(e) = queries::mark_used(db, device.id).await {
+                    warn!(device_id = %device.id, error = %e, "Failed to update push device");
+                }
+            }
+            Err(PushSendError::InvalidToken) => {
+                debug!(device_id = %device.id, "Removing invalid push device");
+                if let Err(e) = queries::delete_device_by_id(db, device.id).await {
+                    warn!(device_id = %device.id, error = %e, "Failed to remove push device");
+                }
+            }
+            Err(PushSendError::Disabled) => {}
+            Err(e) => {
+                warn!(device_id = %device.id, error = %e, "Push delivery failed");
+            }
+        }
+    }
+}
+
+/// Other participants of a DM or group DM.
+async fn dm_recipients(
+    db: &PgPool,
+    channel_id: Uuid,
+    author_id: Uuid,
+) -> Result<Vec<Uuid>, sqlx::Error> {
+    sqlx::query_scalar(
+        "SELECT user_id FROM dm_participants WHERE channel_id = $1 AND user_id <> $2",
+    )
+    .bind(channel_id)
+    .bind(author_id)
+    .fetch_all(db)
+    .await
+}
+
+/// Guild members to notify: @mentioned members plus highlighted members.
+async fn guild_recipients(
+    db: &PgPool,
+    guild_id: Uuid,
+    channel_id: Uuid,
+    author_id: Uuid,
+    content: &str,
+) -> Result<Vec<Uuid>, sqlx::Error> {
+    let mut recipients = mentioned_recipients(db, guild_id, channel_id, author_id, content).await?;
+    for (user_id, _) in
+        highlights::highlighted_recipients(db, guild_id, channel_id, author_id, content).await?
+    {
+        if !recipients.contains(&user_id) {
+            recipients.push(user_id);
+        }
+    }
+    Ok(recipients)
+}
+
+/// Guild members directly @mentioned in `content` who can view the channel.
+///
+/// A mention of a username its owner recently renamed away from still reaches
+/// them while the old name is held for them (see [`USERNAME_REDIRECT_DAYS`]).
+async fn mentioned_recipients(
+    db: &PgPool,
+    guild_id: Uuid,
+    channel_id: Uuid,
+    author_id: Uuid,
+    content: &str,
+) -> Result<Vec<Uuid>, sqlx::Error> {
+    let usernames = mentioned_usernames(content);
+    if usernames.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let members: Vec<Uuid> = sqlx::query_scalar(
+        "SELECT gm.user_id FROM guild_members gm
+         JOIN users u ON u.id = gm.user_id
+         WHERE gm.guild_id = $1 AND gm.user_id <> $3
+           AND (LOWER(u.username) = ANY($2) OR EXISTS(
+               SELECT 1 FROM username_history h
+               WHERE h.user_id = u.id AND h.old_username = ANY($2)
+                 AND h.changed_at > NOW() - make_interval(days => $4)
+                 AND NOT EXISTS(SELECT 1 FROM users o WHERE o.username = h.old_username)
+           ))",
+    )
+    .bind(guild_id)
+    .bind(&usernames)
+    .bind(author_id)
+    .bind(USERNAME_REDIRECT_DAYS)
+    .fetch_all(db)
+    .await?;
+
+    let mut recipients = Vec::with_capacity(members.len());
+    for user_id in members {
+        if crate::permissions::require_channel_access(db, user_id, channel_id)
+            .await
+            .is_ok()
+        {
+            recipients.push(user_id);
+        }
+    }
+    Ok(recipients)
+}
+
+/// Lowercased usernames of direct @mentions, excluding `@everyone`/`@here`.
+fn mentioned_usernames(content: &str) -> Vec<String> {
+    let mut seen = HashSet::new();
+    MENTION_REGEX
+        .captures_iter(content)
+        .map(|cap| cap[1].to_lowercase())
+        .filter(|name| name != "everyone" && name != "here")
+        .filter(|name| seen.insert(name.clone()))
+        .take(MAX_MENTIONS_PER_MESSAGE)
+        .collect()
+}
+
+/// Drop recipients who are connected, muted the channel, or have a block
+/// with the sender.
+async fn filter_recipients(
+    db: &PgPool,
+    redis: &Client,
+    sender_id: Uuid,
+    channel_id: Uuid,
+    candidates: Vec<Uuid>,
+) -> Vec<Uuid> {
+    if candidates.is_empty() {
+        return candidates;
+    }
+
+    let muted: HashSet<Uuid> = match sqlx::query_scalar::<_, Uuid>(
+        "SELECT user_id FROM user_preferences
+         WHERE user_id = ANY($1)
+           AND preferences->'channel_notifications'->>$2 = 'muted'",
+    )
+    .bind(&candidates)
+    .bind(channel_id.to_string())
+    .fetch_all(db)
+    .await
+    {
+        Ok(rows) => rows.into_iter().collect(),
+        Err(e) => {
+            warn!(error = %e, "Failed to load channel mutes for push");
+            HashSet::new()
+        }
+    };
+
+    let mut recipients = Vec::with_capacity(candidates.len());
+    for user_id in candidates {
+        if muted.contains(&user_id) {
+            continue;
+        }
+        // Fail closed: a failed lookup never results in a push
+        match presence_registry::has_active_connection(redis, user_id).await {
+            Ok(false) => {}
+            Ok(true) => continue,
+            Err(e) => {
+                warn!(user_id = %user_id, error = %e, "Failed to check sessions for push");
+                continue;
+            }
+        }
+        match block_cache::is_blocked_either_direction(redis, sender_id, user_id).await {
+            Ok(false) => recipients.push(user_id),
+            Ok(true) => {}
+            Err(e) => {
+                warn!(user_id = %user_id, error = %e, "Failed to check blocks for push");
+            }
+        }
+    }
+    recipients
+}
+
+fn build_notification(job: &PushJob) -> PushNotification {
+    match job {
+        PushJob::Message {
+            channel_id,
+            message_id,
+            guild_id,
+            author_name,
+            channel_name,
+            content,
+            ..
+        } => {
+            let body = content.as_deref().map_or_else(
+                || "New encrypted message".to_string(),
+                |c| truncate(c, MAX_BODY_CHARS),
+            );
+            let (title, kind) = if guild_id.is_some() {
+                (
+                    format!("{author_name} mentioned you in #{channel_name}"),
+                    "mention",
+                )
+            } else {
+                (author_name.clone(), "dm")
+            };
+            PushNotification {
+                title,
+                body,
+                kind,
+                channel_id: *channel_id,
+                guild_id: *guild_id,
+                message_id: Some(*message_id),
+            }
+        }
+        PushJob::Call {
+            channel_id,
+            initiator_name,
+            ..
+        } => PushNotification {
+            title: "Incoming call".to_string(),
+            body: format!("{initiator_name} is calling you"),
+            kind: "call",
+            channel_id: *channel_id,
+            guild_id: None,
+            message_id: None,
+        },
+    }
+}
+
+fn truncate(text: &str, max_chars: usize) -> String {
+    match text.char_indices().nth(max_chars) {
+        Some((idx, _)) => format!("{}…", &text[..idx]),
+        None => text.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mentioned_usernames() {
+        assert_eq!(
+            mentioned_usernames("hi @Alice and @bob, @alice again @everyone @here"),
+            vec!["alice".to_string(), "bob".to_string()]
+        );
+        assert!(mentioned_usernames("no mentions").is_empty());
+    }
+
+    #[test]
+    fn test_build_notification_for_dm_and_mention() {
+        let channel_id = Uuid::new_v4();
+        let dm = PushJob::Message {
+            channel_id,
+            message_id: Uuid::new_v4(),
+            guild_id: None,
+            author_id: Uuid::new_v4(),
+            author_name: "Alice".to_string(),
+            channel_name: "dm".to_string(),
+            content: None,
+        };
+        let n = build_notification(&dm);
+        assert_eq!((n.title.as_str(), n.kind), ("Alice", "dm"));
+        assert_eq!(n.body, "New encrypted message");
+
+        let mention = PushJob::Message {
+            channel_id,
+            message_id: Uuid::new_v4(),
+            guild_id: Some(Uuid::new_v4()),
+            author_id: Uuid::new_v4(),
+            author_name: "Alice".to_string(),
+            channel_name: "general".to_string(),
+            content: Some("x".repeat(200)),
+        };
+        let n = build_notification(&mention);
+        assert_eq!(n.title, "Alice mentioned you in #general");
+        assert_eq!(n.body.chars().count(), MAX_BODY_CHARS + 1);
+    }
+}
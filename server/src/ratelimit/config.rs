@@ -2,6 +2,8 @@
 
 use std::collections::HashSet;
 
+use crate::config::EnvVars;
+
 /// Configuration for the rate limiting system.
 #[derive(Debug, Clone)]
 pub struct RateLimitConfig {
@@ -46,6 +48,8 @@ pub struct RateLimits {
     pub search: LimitConfig,
     /// Data governance operations (export, deletion)
     pub data_governance: LimitConfig,
+    /// Messages posted through one incoming webhook
+    pub incoming_webhook: LimitConfig,
     /// Failed authentication tracking
     pub failed_auth: FailedAuthConfig,
     /// Failed auth as `LimitConfig` (for consistency in `get_limit_config`)
@@ -62,6 +66,18 @@ pub struct LimitConfig {
     pub window_secs: u64,
 }
 
+/// Configuration for a token bucket.
+///
+/// The bucket holds up to `capacity` requests and refills at a steady rate,
+/// going from empty to full over `refill_secs`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TokenBucketConfig {
+    /// Maximum burst size
+    pub capacity: u32,
+    /// Seconds for an empty bucket to refill completely
+    pub refill_secs: u64,
+}
+
 /// Configuration for failed authentication tracking.
 #[derive(Debug, Clone)]
 pub struct FailedAuthConfig {
@@ -144,6 +160,10 @@ impl Default for RateLimits {
                 requests: 2,
                 window_secs: 60,
             },
+            incoming_webhook: LimitConfig {
+                requests: 30,
+                window_secs: 60,
+            },
             failed_auth_as_limit: LimitConfig {
                 requests: failed_auth.max_failures,
                 window_secs: failed_auth.window_secs,
@@ -172,83 +192,94 @@ impl RateLimitConfig {
     /// - `RATE_LIMIT_WS_CONNECT`: WebSocket connect limit as "`requests,window_secs`"
     /// - `RATE_LIMIT_WS_MESSAGE`: WebSocket message limit as "`requests,window_secs`"
     /// - `RATE_LIMIT_SEARCH`: Search limit as "`requests,window_secs`"
+    /// - `RATE_LIMIT_INCOMING_WEBHOOK`: Per-webhook message limit as "`requests,window_secs`"
     /// - `RATE_LIMIT_FAILED_AUTH`: Failed auth as "`max_failures,block_duration_secs,window_secs`"
     pub fn from_env() -> Self {
+        Self::from_vars(&EnvVars::process())
+    }
+
+    /// Load configuration from `vars`, with the variables of [`Self::from_env`].
+    pub fn from_vars(vars: &EnvVars) -> Self {
         let mut config = Self::default();
 
-        if let Ok(val) = std::env::var("RATE_LIMIT_ENABLED") {
+        if let Ok(val) = vars.var("RATE_LIMIT_ENABLED") {
             config.enabled = val.parse().unwrap_or(true);
         }
-        if let Ok(val) = std::env::var("RATE_LIMIT_PREFIX") {
+        if let Ok(val) = vars.var("RATE_LIMIT_PREFIX") {
             config.redis_key_prefix = val;
         }
-        if let Ok(val) = std::env::var("RATE_LIMIT_FAIL_OPEN") {
+        if let Ok(val) = vars.var("RATE_LIMIT_FAIL_OPEN") {
             config.fail_open = val.parse().unwrap_or(false);
         }
-        if let Ok(val) = std::env::var("RATE_LIMIT_TRUST_PROXY") {
+        if let Ok(val) = vars.var("RATE_LIMIT_TRUST_PROXY") {
             config.trust_proxy = val.parse().unwrap_or(false);
         }
-        if let Ok(val) = std::env::var("RATE_LIMIT_ALLOWLIST") {
+        if let Ok(val) = vars.var("RATE_LIMIT_ALLOWLIST") {
             config.allowlist = val.split(',').map(|s| s.trim().to_string()).collect();
         }
 
         // Parse per-category limits (format: "requests,window_secs")
-        if let Ok(val) = std::env::var("RATE_LIMIT_AUTH_LOGIN") {
+        if let Ok(val) = vars.var("RATE_LIMIT_AUTH_LOGIN") {
             if let Some(limit) = parse_limit_config(&val) {
                 config.limits.auth_login = limit;
             }
         }
-        if let Ok(val) = std::env::var("RATE_LIMIT_AUTH_REGISTER") {
+        if let Ok(val) = vars.var("RATE_LIMIT_AUTH_REGISTER") {
             if let Some(limit) = parse_limit_config(&val) {
                 config.limits.auth_register = limit;
             }
         }
-        if let Ok(val) = std::env::var("RATE_LIMIT_AUTH_PASSWORD_RESET") {
+        if let Ok(val) = vars.var("RATE_LIMIT_AUTH_PASSWORD_RESET") {
             if let Some(limit) = parse_limit_config(&val) {
                 config.limits.auth_password_reset = limit;
             }
         }
-        if let Ok(val) = std::env::var("RATE_LIMIT_AUTH_OTHER") {
+        if let Ok(val) = vars.var("RATE_LIMIT_AUTH_OTHER") {
             if let Some(limit) = parse_limit_config(&val) {
                 config.limits.auth_other = limit;
             }
         }
-        if let Ok(val) = std::env::var("RATE_LIMIT_WRITE") {
+        if let Ok(val) = vars.var("RATE_LIMIT_WRITE") {
             if let Some(limit) = parse_limit_config(&val) {
                 config.limits.write = limit;
             }
         }
-        if let Ok(val) = std::env::var("RATE_LIMIT_SOCIAL") {
+        if let Ok(val) = vars.var("RATE_LIMIT_SOCIAL") {
             if let Some(limit) = parse_limit_config(&val) {
                 config.limits.social = limit;
             }
         }
-        if let Ok(val) = std::env::var("RATE_LIMIT_READ") {
+        if let Ok(val) = vars.var("RATE_LIMIT_READ") {
             if let Some(limit) = parse_limit_config(&val) {
                 config.limits.read = limit;
             }
         }
-        if let Ok(val) = std::env::var("RATE_LIMIT_WS_CONNECT") {
+        if let Ok(val) = vars.var("RATE_LIMIT_WS_CONNECT") {
             if let Some(limit) = parse_limit_config(&val) {
                 config.limits.ws_connect = limit;
             }
         }
-        if let Ok(val) = std::env::var("RATE_LIMIT_WS_MESSAGE") {
+        if let Ok(val) = vars.var("RATE_LIMIT_WS_MESSAGE") {
             if let Some(limit) = parse_limit_config(&val) {
                 config.limits.ws_message = limit;
             }
         }
-        if let Ok(val) = std::env::var("RATE_LIMIT_VOICE_JOIN") {
+        if let Ok(val) = vars.var("RATE_LIMIT_VOICE_JOIN") {
             if let Some(limit) = parse_limit_config(&val) {
                 config.limits.voice_join = limit;
             }
         }
-        if let Ok(val) = std::env::var("RATE_LIMIT_SEARCH") {
+        if let Ok(val) = vars.var("RATE_LIMIT_SEARCH") {
             if let Some(limit) = parse_limit_config(&val) {
                 config.limits.search = limit;
             }
         }
-        if let Ok(val) = std::env::var("RATE_LIMIT_FAILED_AUTH") {
+        if let Ok(val) = vars.var("RATE_LIMIT_INCOMING_WEBHOOK") {
+            if let Some(limit) = parse_limit_config(&val) {
+                config.limits.incoming_webhook = limit;
+            }
+        }
+        if let Ok(val) = vars.var("RATE_LIMIT_FAILED_AUTH") {
             if let Some(limit) = parse_failed_auth_config(&val) {
                 config.limits.failed_auth = limit;
             }
@@ -279,6 +310,19 @@ fn parse_limit_config(val: &str) -> Option<LimitConfig> {
     }
 }
 
+/// Parses a token bucket config from "`capacity,refill_secs`" format.
+///
+/// Both values must be non-zero.
+pub fn parse_token_bucket_config(val: &str) -> Option<TokenBucketConfig> {
+    let (capacity, refill_secs) = val.split_once(',')?;
+    let capacity = capacity.trim().parse().ok().filter(|&c| c > 0)?;
+    let refill_secs = refill_secs.trim().parse().ok().filter(|&s| s > 0)?;
+    Some(TokenBucketConfig {
+        capacity,
+        refill_secs,
+    })
+}
+
 /// Parses a failed auth config from "`max_failures,block_duration_secs,window_secs`" format.
 fn parse_failed_auth_config(val: &str) -> Option<FailedAuthConfig> {
     let parts: Vec<&str> = val.split(',').collect();
@@ -338,6 +382,19 @@ mod tests {
         assert!(parse_limit_config("abc,60").is_none());
     }
 
+    #[test]
+    fn test_parse_token_bucket_config() {
+        let bucket = parse_token_bucket_config(" 20 , 30 ").unwrap();
+        assert_eq!(bucket.capacity, 20);
+        assert_eq!(bucket.refill_secs, 30);
+
+        // Invalid formats and empty buckets
+        assert!(parse_token_bucket_config("20").is_none());
+        assert!(parse_token_bucket_config("20,30,extra").is_none());
+        assert!(parse_token_bucket_config("0,30").is_none());
+        assert!(parse_token_bucket_config("20,0").is_none());
+    }
+
     #[test]
     fn test_parse_failed_auth_config() {
         let config = parse_failed_auth_config("5,300,120").unwrap();
@@ -64,9 +64,8 @@ impl IntoResponse for RateLimitError {
                 };
                 let mut response = (StatusCode::TOO_MANY_REQUESTS, Json(body)).into_response();
                 let headers = response.headers_mut();
-                if let Ok(v) = HeaderValue::from_str(&result.retry_after.to_string()) {
-                    headers.insert("Retry-After", v);
-                }
+                headers.insert("Retry-After", HeaderValue::from(result.retry_after));
+                result.insert_headers(headers);
                 response
             }
             Self::IpBlocked { retry_after } => {
@@ -2,13 +2,14 @@
 
 use std::sync::Arc;
 
+use arc_swap::ArcSwap;
 use fred::prelude::*;
 use tokio::sync::RwLock;
 use tracing::{debug, info, warn};
 
 use crate::ratelimit::{
-    LimitConfig, RateLimitCategory, RateLimitConfig, RateLimitError, RateLimitResult,
-    SCRIPT_ALLOWED,
+    LimitConfig, RateLimitCategory, RateLimitConfig, RateLimitError, RateLimitResult, RoutePolicy,
+    TokenBucketConfig, SCRIPT_ALLOWED,
 };
 
 /// Embedded Lua script for atomic rate limit check and increment.
@@ -17,11 +18,15 @@ const RATE_LIMIT_SCRIPT: &str = include_str!("rate_limit.lua");
 /// Embedded Lua script for atomic failed auth tracking and blocking.
 const FAILED_AUTH_SCRIPT: &str = include_str!("failed_auth.lua");
 
+/// Embedded Lua script for atomic token bucket refill and take.
+const TOKEN_BUCKET_SCRIPT: &str = include_str!("token_bucket.lua");
+
 /// Script SHAs for Lua scripts loaded in Redis.
 #[derive(Clone, Default)]
 struct ScriptShas {
     rate_limit: String,
     failed_auth: String,
+    token_bucket: String,
 }
 
 /// Core rate limiter service backed by Redis.
@@ -31,7 +36,7 @@ struct ScriptShas {
 #[derive(Clone)]
 pub struct RateLimiter {
     redis: Client,
-    config: Arc<RateLimitConfig>,
+    config: Arc<ArcSwap<RateLimitConfig>>,
     scripts: Arc<RwLock<ScriptShas>>,
 }
 
@@ -42,7 +47,7 @@ impl RateLimiter {
     pub fn new(redis: Client, config: RateLimitConfig) -> Self {
         Self {
             redis,
-            config: Arc::new(config),
+            config: Arc::new(ArcSwap::from_pointee(config)),
             scripts: Arc::new(RwLock::new(ScriptShas::default())),
         }
     }
@@ -60,16 +65,19 @@ impl RateLimiter {
     async fn load_scripts(&self) -> Result<(), Error> {
         let rate_limit_sha: String = self.redis.script_load(RATE_LIMIT_SCRIPT).await?;
         let failed_auth_sha: String = self.redis.script_load(FAILED_AUTH_SCRIPT).await?;
+        let token_bucket_sha: String = self.redis.script_load(TOKEN_BUCKET_SCRIPT).await?;
 
         info!(
             rate_limit_sha = %rate_limit_sha,
             failed_auth_sha = %failed_auth_sha,
+            token_bucket_sha = %token_bucket_sha,
             "Lua scripts loaded into Redis"
         );
 
         let mut scripts = self.scripts.write().await;
         scripts.rate_limit = rate_limit_sha;
         scripts.failed_auth = failed_auth_sha;
+        scripts.token_bucket = token_bucket_sha;
         Ok(())
     }
 
@@ -96,7 +104,7 @@ impl RateLimiter {
         identifier: &str,
     ) -> Result<RateLimitResult, RateLimitError> {
         // Skip rate limiting if disabled
-        if !self.config.enabled {
+        if !self.config.load().enabled {
             return Ok(RateLimitResult {
                 allowed: true,
                 limit: 0,
@@ -122,7 +130,7 @@ impl RateLimiter {
         let key = self.build_key(category.as_str(), identifier);
 
         // Execute Lua script atomically with NOSCRIPT retry
-        let result = self.execute_rate_limit_script(&key, limit_config).await?;
+        let result = self.execute_rate_limit_script(&key, &limit_config).await?;
 
         let count = result[0] as u32;
         let allowed = result[1] == SCRIPT_ALLOWED;
@@ -204,9 +212,100 @@ impl RateLimiter {
         }
     }
 
+    /// Takes a token from the bucket of a route policy for the given identifier.
+    ///
+    /// Unlike `check()`, limits come from the caller so they can live in the
+    /// server `Config`. `limit` is the bucket capacity, `remaining` the whole
+    /// tokens left and `reset_at` the time the bucket is full again.
+    ///
+    /// # Errors
+    /// Returns `RateLimitError::RedisUnavailable` if Redis is unreachable.
+    #[tracing::instrument(skip(self, bucket), fields(policy = %policy.as_str()))]
+    pub async fn check_bucket(
+        &self,
+        policy: RoutePolicy,
+        identifier: &str,
+        bucket: TokenBucketConfig,
+    ) -> Result<RateLimitResult, RateLimitError> {
+        if !self.config.load().enabled || self.is_allowed_by_config(identifier) {
+            return Ok(RateLimitResult {
+                allowed: true,
+                limit: 0,
+                remaining: 0,
+                reset_at: 0,
+                retry_after: 0,
+            });
+        }
+
+        let key = self.build_key(&format!("route:{}", policy.as_str()), identifier);
+        let result = self.execute_token_bucket_script(&key, bucket).await?;
+
+        let allowed = result[0] == SCRIPT_ALLOWED;
+        let remaining = u32::try_from(result[1].max(0)).unwrap_or(bucket.capacity);
+        let wait_secs = millis_to_secs(result[2]);
+        let full_secs = millis_to_secs(result[3]);
+
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+
+        Ok(RateLimitResult {
+            allowed,
+            limit: bucket.capacity,
+            remaining,
+            reset_at: now + full_secs,
+            // A denied request can always retry once the next token arrives
+            retry_after: if allowed { 0 } else { wait_secs.max(1) },
+        })
+    }
+
+    /// Executes the token bucket Lua script with NOSCRIPT retry.
+    async fn execute_token_bucket_script(
+        &self,
+        key: &str,
+        bucket: TokenBucketConfig,
+    ) -> Result<Vec<i64>, RateLimitError> {
+        let scripts = self.scripts.read().await;
+        let sha = scripts.token_bucket.clone();
+        drop(scripts);
+
+        let args = vec![bucket.capacity.to_string(), bucket.refill_secs.to_string()];
+
+        let result: Result<Vec<i64>, _> = self.redis.evalsha(&sha, vec![key], args.clone()).await;
+
+        match result {
+            Ok(r) => Ok(r),
+            Err(e) if Self::is_noscript_error(&e) => {
+                warn!("NOSCRIPT error in token_bucket, reloading Lua scripts");
+                self.load_scripts().await.map_err(|e| {
+                    warn!(error = %e, "Failed to reload scripts");
+                    RateLimitError::RedisUnavailable
+                })?;
+
+                // Retry with new SHA
+                let scripts = self.scripts.read().await;
+                let new_sha = scripts.token_bucket.clone();
+                drop(scripts);
+
+                self.redis
+                    .evalsha(&new_sha, vec![key], args)
+                    .await
+                    .map_err(|e| {
+                        warn!(error = %e, "Token bucket script failed after reload");
+                        RateLimitError::RedisUnavailable
+                    })
+            }
+            Err(e) => {
+                warn!(error = %e, "Redis token bucket check failed");
+                Err(RateLimitError::RedisUnavailable)
+            }
+        }
+    }
+
     /// Checks if the identifier is in the allowlist configuration.
     pub fn is_allowed_by_config(&self, identifier: &str) -> bool {
-        self.config.allowlist.contains(identifier)
+        self.config.load().allowlist.contains(identifier)
     }
 
     /// Records a failed authentication attempt for the given IP.
@@ -222,7 +321,7 @@ impl RateLimiter {
     /// Returns `RateLimitError::RedisUnavailable` if Redis is unreachable.
     #[tracing::instrument(skip(self))]
     pub async fn record_failed_auth(&self, ip: &str) -> Result<bool, RateLimitError> {
-        if !self.config.enabled {
+        if !self.config.load().enabled {
             return Ok(false);
         }
 
@@ -232,11 +331,11 @@ impl RateLimiter {
 
         let failed_key = self.build_key("failed_auth", ip);
         let block_key = self.build_key("blocked", ip);
-        let config = &self.config.limits.failed_auth;
+        let config = self.config.load().limits.failed_auth.clone();
 
         // Execute atomic Lua script with NOSCRIPT retry
         let result = self
-            .execute_failed_auth_script(&failed_key, &block_key, config)
+            .execute_failed_auth_script(&failed_key, &block_key, &config)
             .await?;
 
         let count = result[0];
@@ -324,7 +423,7 @@ impl RateLimiter {
     /// Returns `RateLimitError::RedisUnavailable` if Redis is unreachable.
     #[tracing::instrument(skip(self))]
     pub async fn is_blocked(&self, ip: &str) -> Result<bool, RateLimitError> {
-        if !self.config.enabled {
+        if !self.config.load().enabled {
             return Ok(false);
         }
 
@@ -350,7 +449,7 @@ impl RateLimiter {
     /// Returns `None` if the IP is not blocked or Redis is unavailable.
     #[tracing::instrument(skip(self))]
     pub async fn get_block_ttl(&self, ip: &str) -> Option<u64> {
-        if !self.config.enabled {
+        if !self.config.load().enabled {
             return None;
         }
 
@@ -381,43 +480,59 @@ impl RateLimiter {
     }
 
     /// Returns the configuration for this rate limiter.
-    pub fn config(&self) -> &RateLimitConfig {
-        &self.config
+    pub fn config(&self) -> Arc<RateLimitConfig> {
+        self.config.load_full()
+    }
+
+    /// Replaces the configuration after a reload; clones of this limiter
+    /// share it. Whether rate limiting exists at all is decided at startup.
+    pub fn set_config(&self, config: RateLimitConfig) {
+        self.config.store(Arc::new(config));
     }
 
     /// Builds a Redis key with the configured prefix.
     fn build_key(&self, category: &str, identifier: &str) -> String {
         format!(
             "{}:{}:{}",
-            self.config.redis_key_prefix, category, identifier
+            self.config.load().redis_key_prefix,
+            category,
+            identifier
         )
     }
 
     /// Returns the limit configuration for a given category.
-    fn get_limit_config(&self, category: RateLimitCategory) -> &LimitConfig {
-        match category {
-            RateLimitCategory::AuthLogin => &self.config.limits.auth_login,
-            RateLimitCategory::AuthRegister => &self.config.limits.auth_register,
-            RateLimitCategory::AuthPasswordReset => &self.config.limits.auth_password_reset,
-            RateLimitCategory::AuthOther => &self.config.limits.auth_other,
-            RateLimitCategory::Write => &self.config.limits.write,
-            RateLimitCategory::Social => &self.config.limits.social,
-            RateLimitCategory::Read => &self.config.limits.read,
-            RateLimitCategory::WsConnect => &self.config.limits.ws_connect,
-            RateLimitCategory::WsMessage => &self.config.limits.ws_message,
-            RateLimitCategory::VoiceJoin => &self.config.limits.voice_join,
-            RateLimitCategory::Search => &self.config.limits.search,
-            RateLimitCategory::DataGovernance => &self.config.limits.data_governance,
+    fn get_limit_config(&self, category: RateLimitCategory) -> LimitConfig {
+        let config = self.config.load();
+        let limit = match category {
+            RateLimitCategory::AuthLogin => &config.limits.auth_login,
+            RateLimitCategory::AuthRegister => &config.limits.auth_register,
+            RateLimitCategory::AuthPasswordReset => &config.limits.auth_password_reset,
+            RateLimitCategory::AuthOther => &config.limits.auth_other,
+            RateLimitCategory::Write => &config.limits.write,
+            RateLimitCategory::Social => &config.limits.social,
+            RateLimitCategory::Read => &config.limits.read,
+            RateLimitCategory::WsConnect => &config.limits.ws_connect,
+            RateLimitCategory::WsMessage => &config.limits.ws_message,
+            RateLimitCategory::VoiceJoin => &config.limits.voice_join,
+            RateLimitCategory::Search => &config.limits.search,
+            RateLimitCategory::DataGovernance => &config.limits.data_governance,
+            RateLimitCategory::IncomingWebhook => &config.limits.incoming_webhook,
             RateLimitCategory::FailedAuth => {
                 // FailedAuth uses max_failures as requests and window_secs from failed_auth config.
                 // Note: This category should not be used with check() - use record_failed_auth()
                 // instead. This exists for consistency in the type system.
-                &self.config.limits.failed_auth_as_limit
+                &config.limits.failed_auth_as_limit
             }
-        }
+        };
+        limit.clone()
     }
 }
 
+/// Rounds a script duration in milliseconds up to whole seconds.
+fn millis_to_secs(ms: i64) -> u64 {
+    u64::try_from(ms.max(0)).unwrap_or_default().div_ceil(1000)
+}
+
 #[cfg(test)]
 mod tests {
     use std::collections::HashSet;
@@ -438,7 +553,7 @@ mod tests {
     fn create_mock_limiter(config: RateLimitConfig) -> RateLimiter {
         RateLimiter {
             redis: create_mock_client(),
-            config: Arc::new(config),
+            config: Arc::new(ArcSwap::from_pointee(config)),
             scripts: Arc::new(RwLock::new(ScriptShas::default())),
         }
     }
@@ -471,6 +586,15 @@ mod tests {
         assert_eq!(read.requests, 200);
     }
 
+    #[test]
+    fn test_millis_to_secs_rounds_up() {
+        assert_eq!(millis_to_secs(0), 0);
+        assert_eq!(millis_to_secs(1), 1);
+        assert_eq!(millis_to_secs(1000), 1);
+        assert_eq!(millis_to_secs(1001), 2);
+        assert_eq!(millis_to_secs(-5), 0);
+    }
+
     /// Helper to create a mock Redis client for tests that don't need actual Redis.
     fn create_mock_client() -> Client {
         let config = Config::from_url("redis://localhost:6379").unwrap();
@@ -1,47 +1,33 @@
 //! Axum middleware for rate limiting.
 //!
 //! Provides middleware functions to enforce rate limits on incoming requests.
-//! Supports rate limiting by IP address (for unauthenticated endpoints) and
-//! by user ID (for authenticated endpoints).
+//! Supports rate limiting by IP address (for unauthenticated endpoints), by
+//! user ID (for authenticated endpoints), and by route policy (applied once
+//! around the whole router).
 
 use std::net::SocketAddr;
 
-use axum::extract::{ConnectInfo, Request, State};
-use axum::http::header::HeaderValue;
+use axum::extract::{ConnectInfo, MatchedPath, Request, State};
+use axum::http::header::AUTHORIZATION;
 use axum::middleware::Next;
 use axum::response::Response;
 use tracing::{debug, warn};
 
 use crate::api::AppState;
+use crate::auth::jwt::validate_access_token;
 use crate::auth::AuthUser;
+use crate::observability::metrics;
 use crate::ratelimit::{
-    extract_client_ip, normalize_ip, NormalizedIp, RateLimitCategory, RateLimitError,
-    RateLimitResult,
+    extract_client_ip, normalize_ip, NormalizedIp, RateLimitCategory, RateLimitError, RoutePolicy,
 };
 
-/// Standard rate limit header names.
-const HEADER_LIMIT: &str = "X-RateLimit-Limit";
-const HEADER_REMAINING: &str = "X-RateLimit-Remaining";
-const HEADER_RESET: &str = "X-RateLimit-Reset";
-
-/// Adds rate limit headers to a response.
-///
-/// Adds the following headers:
-/// - `X-RateLimit-Limit`: Maximum requests allowed in the window
-/// - `X-RateLimit-Remaining`: Requests remaining in the current window
-/// - `X-RateLimit-Reset`: Unix timestamp when the window resets
-fn add_rate_limit_headers(response: &mut Response, result: &RateLimitResult) {
-    let headers = response.headers_mut();
-
-    if let Ok(v) = HeaderValue::from_str(&result.limit.to_string()) {
-        headers.insert(HEADER_LIMIT, v);
-    }
-    if let Ok(v) = HeaderValue::from_str(&result.remaining.to_string()) {
-        headers.insert(HEADER_REMAINING, v);
-    }
-    if let Ok(v) = HeaderValue::from_str(&result.reset_at.to_string()) {
-        headers.insert(HEADER_RESET, v);
-    }
+/// Records a rejected request against its matched route template.
+fn record_rejection(request: &Request) {
+    let route = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map_or("unmatched", MatchedPath::as_str);
+    metrics::record_rate_limit_rejection(route);
 }
 
 /// Middleware to rate limit requests by client IP address.
@@ -134,12 +120,13 @@ pub async fn rate_limit_by_ip(
             retry_after = result.retry_after,
             "Rate limit exceeded"
         );
+        record_rejection(&request);
         return Err(RateLimitError::LimitExceeded(result));
     }
 
     // Run the request and add rate limit headers to response
     let mut response = next.run(request).await;
-    add_rate_limit_headers(&mut response, &result);
+    result.insert_headers(response.headers_mut());
     Ok(response)
 }
 
@@ -238,12 +225,13 @@ pub async fn rate_limit_by_user(
             retry_after = result.retry_after,
             "Rate limit exceeded"
         );
+        record_rejection(&request);
         return Err(RateLimitError::LimitExceeded(result));
     }
 
     // Run the request and add rate limit headers to response
     let mut response = next.run(request).await;
-    add_rate_limit_headers(&mut response, &result);
+    result.insert_headers(response.headers_mut());
     Ok(response)
 }
 
@@ -330,6 +318,94 @@ pub async fn check_ip_not_blocked(
     Ok(next.run(request).await)
 }
 
+/// Middleware enforcing the global per-route token buckets.
+///
+/// Applied once around the whole router in `create_router`. Requests whose
+/// matched route has a [`RoutePolicy`] take a token from the bucket of the
+/// caller: the user in a valid `Authorization: Bearer` access token, or the
+/// normalized client IP otherwise. Other requests pass straight through.
+///
+/// # Behavior
+///
+/// - If rate limiter is not configured, requests pass through.
+/// - If Redis is unavailable, follows `fail_open` like the other middleware.
+/// - If the bucket is empty, returns `429 Too Many Requests` with
+///   `Retry-After` and `X-RateLimit-*` headers and records the rejection.
+#[tracing::instrument(skip(state, request, next))]
+pub async fn rate_limit_by_route(
+    State(state): State<AppState>,
+    request: Request,
+    next: Next,
+) -> Result<Response, RateLimitError> {
+    let Some(ref rate_limiter) = state.rate_limiter else {
+        return Ok(next.run(request).await);
+    };
+
+    let Some(policy) = request
+        .extensions()
+        .get::<MatchedPath>()
+        .and_then(|path| RoutePolicy::for_route(request.method(), path.as_str()))
+    else {
+        return Ok(next.run(request).await);
+    };
+
+    let user_id = request
+        .headers()
+        .get(AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "))
+        .and_then(|token| validate_access_token(token, &state.config().jwt_public_key).ok())
+        .map(|claims| claims.sub);
+    let identifier = if let Some(user_id) = user_id {
+        format!("user:{user_id}")
+    } else {
+        let connect_info = request
+            .extensions()
+            .get::<ConnectInfo<SocketAddr>>()
+            .copied();
+        let trust_proxy = rate_limiter.config().trust_proxy;
+        normalize_ip(extract_client_ip(
+            request.headers(),
+            connect_info.as_ref(),
+            trust_proxy,
+        ))
+    };
+
+    let bucket = policy.bucket(&state.config().route_rate_limits);
+    let result = match rate_limiter.check_bucket(policy, &identifier, bucket).await {
+        Ok(result) => result,
+        Err(RateLimitError::RedisUnavailable) => {
+            // Fail open if configured - SECURITY WARNING: rate limiting is disabled!
+            if rate_limiter.config().fail_open {
+                warn!(
+                    policy = %policy.as_str(),
+                    identifier = %identifier,
+                    security_impact = "high",
+                    "SECURITY WARNING: Redis unavailable - RATE LIMITING DISABLED! API vulnerable to abuse/DoS attacks. Investigate Redis connectivity immediately."
+                );
+                return Ok(next.run(request).await);
+            }
+            return Err(RateLimitError::RedisUnavailable);
+        }
+        Err(e) => return Err(e),
+    };
+
+    if !result.allowed {
+        debug!(
+            policy = %policy.as_str(),
+            identifier = %identifier,
+            retry_after = result.retry_after,
+            "Route rate limit exceeded"
+        );
+        record_rejection(&request);
+        return Err(RateLimitError::LimitExceeded(result));
+    }
+
+    let mut response = next.run(request).await;
+    result.insert_headers(response.headers_mut());
+    Ok(response)
+}
+
 /// Sets the rate limit category for downstream middleware.
 ///
 /// This middleware should be applied before `rate_limit_by_ip` or `rate_limit_by_user`
@@ -371,6 +447,27 @@ mod tests {
         let _middleware = with_category(category);
     }
 
+    #[test]
+    fn test_limit_exceeded_sets_rate_limit_headers() {
+        use axum::response::IntoResponse;
+
+        let response = RateLimitError::LimitExceeded(crate::ratelimit::RateLimitResult {
+            allowed: false,
+            limit: 10,
+            remaining: 0,
+            reset_at: 1_700_000_060,
+            retry_after: 6,
+        })
+        .into_response();
+
+        assert_eq!(response.status(), axum::http::StatusCode::TOO_MANY_REQUESTS);
+        let headers = response.headers();
+        assert_eq!(headers["Retry-After"], "6");
+        assert_eq!(headers["X-RateLimit-Limit"], "10");
+        assert_eq!(headers["X-RateLimit-Remaining"], "0");
+        assert_eq!(headers["X-RateLimit-Reset"], "1700000060");
+    }
+
     #[test]
     fn test_normalized_ip_type() {
         let ip = NormalizedIp("192.168.1.1".to_string());
@@ -9,6 +9,7 @@ pub mod error;
 pub mod ip;
 pub mod limiter;
 pub mod middleware;
+pub mod policy;
 pub mod types;
 
 pub use config::*;
@@ -16,5 +17,8 @@ pub use constants::*;
 pub use error::*;
 pub use ip::*;
 pub use limiter::*;
-pub use middleware::{check_ip_not_blocked, rate_limit_by_ip, rate_limit_by_user, with_category};
+pub use middleware::{
+    check_ip_not_blocked, rate_limit_by_ip, rate_limit_by_route, rate_limit_by_user, with_category,
+};
+pub use policy::*;
 pub use types::*;
@@ -0,0 +1,99 @@
+//! Per-route rate limit policies for the global rate limit layer.
+
+use axum::http::Method;
+
+use crate::config::RoutePolicyConfig;
+use crate::ratelimit::TokenBucketConfig;
+
+/// Routes covered by a global token bucket.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RoutePolicy {
+    /// Password login
+    Login,
+    /// Account registration
+    Register,
+    /// Posting a channel message
+    MessageCreate,
+    /// File uploads (attachments, avatars, DM icons, emojis)
+    Upload,
+}
+
+impl RoutePolicy {
+    /// Returns the string identifier for this policy (used in Redis keys).
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Login => "login",
+            Self::Register => "register",
+            Self::MessageCreate => "message_create",
+            Self::Upload => "upload",
+        }
+    }
+
+    /// Finds the policy for a request, given its method and matched route
+    /// template (e.g. `/api/messages/channel/{channel_id}`).
+    pub fn for_route(method: &Method, route: &str) -> Option<Self> {
+        if method != Method::POST {
+            return None;
+        }
+        match route {
+            "/auth/login" => Some(Self::Login),
+            "/auth/register" => Some(Self::Register),
+            "/api/messages/channel/{channel_id}" => Some(Self::MessageCreate),
+            "/api/messages/upload"
+            | "/api/messages/channel/{channel_id}/upload"
+            | "/auth/me/avatar"
+            | "/api/dm/{id}/icon"
+            | "/api/guilds/{id}/emojis" => Some(Self::Upload),
+            _ => None,
+        }
+    }
+
+    /// Returns the configured bucket for this policy.
+    pub const fn bucket(self, config: &RoutePolicyConfig) -> TokenBucketConfig {
+        match self {
+            Self::Login => config.login,
+            Self::Register => config.register,
+            Self::MessageCreate => config.message_create,
+            Self::Upload => config.uploads,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_for_route_matches_templates() {
+        assert_eq!(
+            RoutePolicy::for_route(&Method::POST, "/auth/login"),
+            Some(RoutePolicy::Login)
+        );
+        assert_eq!(
+            RoutePolicy::for_route(&Method::POST, "/api/messages/channel/{channel_id}"),
+            Some(RoutePolicy::MessageCreate)
+        );
+        assert_eq!(
+            RoutePolicy::for_route(&Method::POST, "/api/guilds/{id}/emojis"),
+            Some(RoutePolicy::Upload)
+        );
+
+        // Listing messages shares the path but not the method
+        assert_eq!(
+            RoutePolicy::for_route(&Method::GET, "/api/messages/channel/{channel_id}"),
+            None
+        );
+        // Resolved paths never match a template
+        assert_eq!(
+            RoutePolicy::for_route(&Method::POST, "/api/messages/channel/123"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_bucket_uses_route_config() {
+        let config = RoutePolicyConfig::default();
+        assert_eq!(RoutePolicy::Upload.bucket(&config), config.uploads);
+        assert_eq!(RoutePolicy::Register.bucket(&config), config.register);
+    }
+}
@@ -0,0 +1,39 @@
+-- Atomic token bucket refill and take
+-- KEYS[1] = bucket key (hash with `tokens` and `ts` in milliseconds)
+-- ARGV[1] = capacity (max tokens)
+-- ARGV[2] = refill_secs (time for an empty bucket to refill completely)
+-- Returns: {allowed (1/0), remaining tokens, ms until next token, ms until full}
+
+local capacity = tonumber(ARGV[1])
+local rate = capacity / (tonumber(ARGV[2]) * 1000)
+
+-- Use the Redis clock so every server node shares one time source
+local time = redis.call('TIME')
+local now = tonumber(time[1]) * 1000 + math.floor(tonumber(time[2]) / 1000)
+
+local state = redis.call('HMGET', KEYS[1], 'tokens', 'ts')
+local tokens = tonumber(state[1])
+local ts = tonumber(state[2])
+if tokens == nil or ts == nil then
+    tokens = capacity
+    ts = now
+end
+
+tokens = math.min(capacity, tokens + math.max(0, now - ts) * rate)
+
+local allowed = 0
+local wait_ms = 0
+if tokens >= 1 then
+    tokens = tokens - 1
+    allowed = 1
+else
+    wait_ms = math.ceil((1 - tokens) / rate)
+end
+
+local full_ms = math.ceil((capacity - tokens) / rate)
+
+redis.call('HSET', KEYS[1], 'tokens', tostring(tokens), 'ts', now)
+-- A full bucket is the same as no bucket, so let the key expire then
+redis.call('PEXPIRE', KEYS[1], math.max(full_ms, 1))
+
+return {allowed, math.floor(tokens), wait_ms, full_ms}
@@ -1,7 +1,14 @@
 //! Rate limiting types.
 
+use axum::http::header::HeaderValue;
+use axum::http::HeaderMap;
 use serde::Serialize;
 
+/// Standard rate limit header names.
+const HEADER_LIMIT: &str = "X-RateLimit-Limit";
+const HEADER_REMAINING: &str = "X-RateLimit-Remaining";
+const HEADER_RESET: &str = "X-RateLimit-Reset";
+
 /// Categories for rate limiting with different thresholds.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum RateLimitCategory {
@@ -31,6 +38,8 @@ pub enum RateLimitCategory {
     Search,
     /// Data governance operations (export, deletion)
     DataGovernance,
+    /// Messages posted through one incoming webhook
+    IncomingWebhook,
 }
 
 impl RateLimitCategory {
@@ -50,6 +59,7 @@ impl RateLimitCategory {
             Self::VoiceJoin => "voice_join",
             Self::Search => "search",
             Self::DataGovernance => "data_governance",
+            Self::IncomingWebhook => "incoming_webhook",
         }
     }
 
@@ -68,6 +78,7 @@ impl RateLimitCategory {
             Self::VoiceJoin,
             Self::Search,
             Self::DataGovernance,
+            Self::IncomingWebhook,
         ]
     }
 }
@@ -87,6 +98,18 @@ pub struct RateLimitResult {
     pub retry_after: u64,
 }
 
+impl RateLimitResult {
+    /// Adds the standard rate limit headers:
+    /// - `X-RateLimit-Limit`: Maximum requests allowed in the window
+    /// - `X-RateLimit-Remaining`: Requests remaining in the current window
+    /// - `X-RateLimit-Reset`: Unix timestamp when the window resets
+    pub fn insert_headers(&self, headers: &mut HeaderMap) {
+        headers.insert(HEADER_LIMIT, HeaderValue::from(self.limit));
+        headers.insert(HEADER_REMAINING, HeaderValue::from(self.remaining));
+        headers.insert(HEADER_RESET, HeaderValue::from(self.reset_at));
+    }
+}
+
 /// Information about a blocked IP address.
 #[derive(Debug, Clone, Serialize)]
 pub struct BlockedIpInfo {
@@ -4,11 +4,34 @@ use uuid::Uuid;
 use validator::Validate;
 
 use super::block_cache;
-use super::types::{Friend, Friendship, FriendshipStatus, SendFriendRequestBody, SocialError};
+use super::types::{
+    Friend, Friendship, FriendshipStatus, Relationship, SendFriendRequestBody, SocialError,
+};
 use crate::api::AppState;
 use crate::auth::AuthUser;
 use crate::ws::{broadcast_to_user, ServerEvent};
 
+/// Send `RelationshipUpdate` to both users, each with their own view of the
+/// relationship.
+async fn publish_relationship(
+    state: &AppState,
+    (user_id, relationship): (Uuid, Option<Relationship>),
+    (other_id, other_relationship): (Uuid, Option<Relationship>),
+) {
+    for (recipient, target, relationship) in [
+        (user_id, other_id, relationship),
+        (other_id, user_id, other_relationship),
+    ] {
+        let event = ServerEvent::RelationshipUpdate {
+            user_id: target,
+            relationship,
+        };
+        if let Err(e) = broadcast_to_user(&state.redis, recipient, &event).await {
+            tracing::warn!("Failed to broadcast RelationshipUpdate event: {}", e);
+        }
+    }
+}
+
 /// POST /api/friends/request
 /// Send a friend request to another user
 #[utoipa::path(
@@ -52,10 +75,10 @@ pub async fn send_friend_request(
                 error = %e,
                 user_id = %auth.id,
                 %target_id,
-                fail_open = state.config.block_check_fail_open,
+                fail_open = state.config().block_check_fail_open,
                 "Redis block check failed, using failsafe policy"
             );
-            if !state.config.block_check_fail_open {
+            if !state.config().block_check_fail_open {
                 return Err(SocialError::Blocked);
             }
         }
@@ -114,6 +137,12 @@ pub async fn send_friend_request(
     if let Err(e) = broadcast_to_user(&state.redis, target_id, &event).await {
         tracing::warn!("Failed to send friend request notification: {}", e);
     }
+    publish_relationship(
+        &state,
+        (auth.id, Some(Relationship::OutgoingRequest)),
+        (target_id, Some(Relationship::IncomingRequest)),
+    )
+    .await;
 
     Ok(Json(friendship))
 }
@@ -316,6 +345,12 @@ pub async fn accept_friend_request(
     if let Err(e) = broadcast_to_user(&state.redis, friendship.requester_id, &event).await {
         tracing::warn!("Failed to send friend request accepted notification: {}", e);
     }
+    publish_relationship(
+        &state,
+        (auth.id, Some(Relationship::Friend)),
+        (friendship.requester_id, Some(Relationship::Friend)),
+    )
+    .await;
 
     Ok(Json(updated))
 }
@@ -359,6 +394,8 @@ pub async fn reject_friend_request(
         .execute(&state.db)
         .await?;
 
+    publish_relationship(&state, (auth.id, None), (friendship.requester_id, None)).await;
+
     Ok(Json(()))
 }
 
@@ -460,6 +497,12 @@ pub async fn block_user(
     if let Err(e) = broadcast_to_user(&state.redis, auth.id, &event).await {
         tracing::warn!("Failed to broadcast UserBlocked event: {}", e);
     }
+    publish_relationship(
+        &state,
+        (auth.id, Some(Relationship::Blocked)),
+        (user_id, None),
+    )
+    .await;
 
     Ok(Json(result))
 }
@@ -507,6 +550,7 @@ pub async fn unblock_user(
     if let Err(e) = broadcast_to_user(&state.redis, auth.id, &event).await {
         tracing::warn!("Failed to broadcast UserUnblocked event: {}", e);
     }
+    publish_relationship(&state, (auth.id, None), (user_id, None)).await;
 
     Ok(Json(()))
 }
@@ -550,5 +594,12 @@ pub async fn remove_friend(
         .execute(&state.db)
         .await?;
 
+    let other_id = if friendship.requester_id == auth.id {
+        friendship.addressee_id
+    } else {
+        friendship.requester_id
+    };
+    publish_relationship(&state, (auth.id, None), (other_id, None)).await;
+
     Ok(Json(()))
 }
@@ -14,6 +14,16 @@ pub enum FriendshipStatus {
     Blocked,
 }
 
+/// A user's relationship to another user, from their own point of view.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Relationship {
+    Friend,
+    IncomingRequest,
+    OutgoingRequest,
+    Blocked,
+}
+
 /// Friendship record from database
 #[derive(Debug, Clone, FromRow, Serialize, utoipa::ToSchema)]
 pub struct Friendship {
@@ -21,6 +21,24 @@ pub fn format_file_size(bytes: usize) -> String {
     }
 }
 
+/// Whether `headers` carry `Authorization: Bearer <token>`, compared in
+/// constant time.
+pub fn has_bearer_token(headers: &axum::http::HeaderMap, token: &str) -> bool {
+    let Some(provided) = headers
+        .get(axum::http::header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "))
+    else {
+        return false;
+    };
+    provided.len() == token.len()
+        && provided
+            .bytes()
+            .zip(token.bytes())
+            .fold(0u8, |acc, (a, b)| acc | (a ^ b))
+            == 0
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
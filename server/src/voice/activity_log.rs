@@ -0,0 +1,305 @@
+//! Voice Activity Log
+//!
+//! Posts compact "joined voice / left voice" system messages into a guild's
+//! configured log channel (`guilds.voice_log_channel_id`).
+//!
+//! Join/leave events are buffered in memory and flushed every
+//! [`VOICE_LOG_BATCH_WINDOW`]. Within a window each user's events are collapsed
+//! to their net effect, so reconnects and rapid channel hopping produce at most
+//! one message per user instead of a burst.
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use fred::clients::Client;
+use sqlx::PgPool;
+use tokio::sync::Mutex;
+use tracing::warn;
+use uuid::Uuid;
+
+use crate::chat::system_messages;
+use crate::db::MessageType;
+use crate::i18n::LocalizedText;
+
+/// How long events are buffered before being posted.
+pub const VOICE_LOG_BATCH_WINDOW: Duration = Duration::from_secs(15);
+
+/// A voice presence change.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VoiceActivityKind {
+    /// User joined the channel.
+    Joined,
+    /// User left the channel.
+    Left,
+}
+
+/// Net voice presence change of one user over a batch window.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VoiceTransition {
+    /// User whose presence changed.
+    pub user_id: Uuid,
+    /// Voice channel before the window (`None` = not in voice).
+    pub from: Option<Uuid>,
+    /// Voice channel after the window (`None` = not in voice).
+    pub to: Option<Uuid>,
+}
+
+/// Per-user state accumulated during a window.
+#[derive(Debug, Clone, Copy)]
+struct PendingUser {
+    from: Option<Uuid>,
+    to: Option<Uuid>,
+}
+
+/// Buffers voice join/leave events for batched posting.
+#[derive(Default)]
+pub struct VoiceActivityLog {
+    /// Users in first-event order with their pending state.
+    pending: Mutex<Vec<(Uuid, PendingUser)>>,
+}
+
+impl VoiceActivityLog {
+    /// Create an empty activity log.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a join or leave.
+    pub async fn record(&self, user_id: Uuid, channel_id: Uuid, kind: VoiceActivityKind) {
+        let mut pending = self.pending.lock().await;
+        record_event(&mut pending, user_id, channel_id, kind);
+    }
+
+    /// Take the buffered events, collapsed to net transitions.
+    pub async fn drain(&self) -> Vec<VoiceTransition> {
+        let pending = std::mem::take(&mut *self.pending.lock().await);
+        collapse(pending)
+    }
+
+    /// Flush buffered events into the configured guild log channels.
+    pub async fn flush(&self, pool: &PgPool, redis: &Client) {
+        let transitions = self.drain().await;
+        if transitions.is_empty() {
+            return;
+        }
+
+        let channel_ids: Vec<Uuid> = transitions
+            .iter()
+            .flat_map(|t| [t.from, t.to])
+            .flatten()
+            .collect();
+
+        // Voice channel -> (guild, channel name, guild log channel)
+        let rows: Vec<(Uuid, Uuid, String, Option<Uuid>)> = match sqlx::query_as(
+            r"SELECT c.id, g.id, c.name, g.voice_log_channel_id
+               FROM channels c
+               JOIN guilds g ON g.id = c.guild_id
+               WHERE c.id = ANY($1)",
+        )
+        .bind(&channel_ids)
+        .fetch_all(pool)
+        .await
+        {
+            Ok(rows) => rows,
+            Err(e) => {
+                warn!(error = %e, "Failed to resolve voice activity log channels");
+                return;
+            }
+        };
+        let channels: HashMap<Uuid, (Uuid, String, Option<Uuid>)> = rows
+            .into_iter()
+            .map(|(id, guild_id, name, log)| (id, (guild_id, name, log)))
+            .collect();
+
+        for transition in transitions {
+            for (log_channel_id, text) in render(&transition, &channels) {
+                system_messages::post(
+                    pool,
+                    redis,
+                    log_channel_id,
+                    transition.user_id,
+                    MessageType::SystemVoice,
+                    &text,
+                )
+                .await;
+            }
+        }
+    }
+}
+
+/// Apply one event to the pending state.
+fn record_event(
+    pending: &mut Vec<(Uuid, PendingUser)>,
+    user_id: Uuid,
+    channel_id: Uuid,
+    kind: VoiceActivityKind,
+) {
+    let to = match kind {
+        VoiceActivityKind::Joined => Some(channel_id),
+        VoiceActivityKind::Left => None,
+    };
+
+    if let Some((_, state)) = pending.iter_mut().find(|(id, _)| *id == user_id) {
+        state.to = to;
+    } else {
+        // The first event tells where the user was before the window
+        let from = match kind {
+            VoiceActivityKind::Joined => None,
+            VoiceActivityKind::Left => Some(channel_id),
+        };
+        pending.push((user_id, PendingUser { from, to }));
+    }
+}
+
+/// Drop users whose presence is unchanged after the window.
+fn collapse(pending: Vec<(Uuid, PendingUser)>) -> Vec<VoiceTransition> {
+    pending
+        .into_iter()
+        .filter(|(_, state)| state.from != state.to)
+        .map(|(user_id, state)| VoiceTransition {
+            user_id,
+            from: state.from,
+            to: state.to,
+        })
+        .collect()
+}
+
+/// Render a transition into `(log_channel_id, text)` messages.
+///
+/// Guilds without a log channel are skipped. A move within one guild is a
+/// single message; a move across guilds is a leave and a join.
+fn render(
+    transition: &VoiceTransition,
+    channels: &HashMap<Uuid, (Uuid, String, Option<Uuid>)>,
+) -> Vec<(Uuid, LocalizedText)> {
+    let from = transition.from.and_then(|id| channels.get(&id));
+    let to = transition.to.and_then(|id| channels.get(&id));
+
+    match (from, to) {
+        (Some((from_guild, from_name, log)), Some((to_guild, to_name, _)))
+            if from_guild == to_guild =>
+        {
+            match log {
+                Some(log) => vec![(
+                    *log,
+                    LocalizedText::new("system-voice-moved")
+                        .arg("from", from_name.as_str())
+                        .arg("to", to_name.as_str()),
+                )],
+                None => Vec::new(),
+            }
+        }
+        (from, to) => {
+            let mut out = Vec::new();
+            if let Some((_, name, Some(log))) = from {
+                out.push((
+                    *log,
+                    LocalizedText::new("system-voice-left").arg("channel", name.as_str()),
+                ));
+            }
+            if let Some((_, name, Some(log))) = to {
+                out.push((
+                    *log,
+                    LocalizedText::new("system-voice-joined").arg("channel", name.as_str()),
+                ));
+            }
+            out
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn collapse_events(events: &[(Uuid, Uuid, VoiceActivityKind)]) -> Vec<VoiceTransition> {
+        let mut pending = Vec::new();
+        for (user_id, channel_id, kind) in events {
+            record_event(&mut pending, *user_id, *channel_id, *kind);
+        }
+        collapse(pending)
+    }
+
+    #[test]
+    fn test_join_and_leave_in_window_collapse_to_nothing() {
+        let user = Uuid::new_v4();
+        let channel = Uuid::new_v4();
+
+        let transitions = collapse_events(&[
+            (user, channel, VoiceActivityKind::Joined),
+            (user, channel, VoiceActivityKind::Left),
+        ]);
+        assert!(transitions.is_empty());
+    }
+
+    #[test]
+    fn test_reconnect_collapses_to_nothing() {
+        let user = Uuid::new_v4();
+        let channel = Uuid::new_v4();
+
+        let transitions = collapse_events(&[
+            (user, channel, VoiceActivityKind::Left),
+            (user, channel, VoiceActivityKind::Joined),
+        ]);
+        assert!(transitions.is_empty());
+    }
+
+    #[test]
+    fn test_channel_hopping_collapses_to_move() {
+        let user = Uuid::new_v4();
+        let (a, b, c) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+
+        let transitions = collapse_events(&[
+            (user, a, VoiceActivityKind::Left),
+            (user, b, VoiceActivityKind::Joined),
+            (user, b, VoiceActivityKind::Left),
+            (user, c, VoiceActivityKind::Joined),
+        ]);
+        assert_eq!(
+            transitions,
+            vec![VoiceTransition {
+                user_id: user,
+                from: Some(a),
+                to: Some(c),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_render_skips_guilds_without_log_channel() {
+        let user = Uuid::new_v4();
+        let (guild, log) = (Uuid::new_v4(), Uuid::new_v4());
+        let (a, b) = (Uuid::new_v4(), Uuid::new_v4());
+        let mut channels = HashMap::new();
+        channels.insert(a, (guild, "General".to_string(), Some(log)));
+        channels.insert(b, (Uuid::new_v4(), "Other".to_string(), None));
+
+        let joined = VoiceTransition {
+            user_id: user,
+            from: None,
+            to: Some(a),
+        };
+        assert_eq!(
+            render(&joined, &channels),
+            vec![(
+                log,
+                LocalizedText::new("system-voice-joined").arg("channel", "General")
+            )]
+        );
+
+        // Moving to a guild without a log channel only reports the leave
+        let moved = VoiceTransition {
+            user_id: user,
+            from: Some(a),
+            to: Some(b),
+        };
+        assert_eq!(
+            render(&moved, &channels),
+            vec![(
+                log,
+                LocalizedText::new("system-voice-left").arg("channel", "General")
+            )]
+        );
+    }
+}
@@ -12,7 +12,9 @@ use uuid::Uuid;
 
 use crate::api::AppState;
 use crate::auth::AuthUser;
-use crate::db::{self, ChannelType};
+use crate::chat::system_messages;
+use crate::db::{self, ChannelType, MessageType};
+use crate::i18n::LocalizedText;
 use crate::social::block_cache;
 use crate::voice::call::CallState;
 use crate::voice::call_service::{CallError, CallService};
@@ -229,23 +231,37 @@ pub async fn start_call(
                     error = %e,
                     user_id = %auth.id,
                     %target_id,
-                    fail_open = state.config.block_check_fail_open,
+                    fail_open = state.config().block_check_fail_open,
                     "Redis block check failed, using failsafe policy"
                 );
-                if !state.config.block_check_fail_open {
+                if !state.config().block_check_fail_open {
                     return Err(CallHandlerError::Blocked);
                 }
             }
         }
     }
 
+    let recipients: Vec<Uuid> = target_users.iter().copied().collect();
     let call_service = CallService::new(state.redis.clone());
     let call_state = call_service
         .start_call(channel_id, auth.id, target_users)
         .await?;
 
-    // Broadcast IncomingCall to all participants (they're subscribed to the DM channel)
     let initiator_name = get_username(&state, auth.id).await?;
+
+    // Ring offline participants through push notifications
+    crate::push::worker::enqueue_or_warn(
+        &state.redis,
+        &crate::push::types::PushJob::Call {
+            channel_id,
+            initiator_id: auth.id,
+            initiator_name: initiator_name.clone(),
+            recipients,
+        },
+    )
+    .await;
+
+    // Broadcast IncomingCall to all participants (they're subscribed to the DM channel)
     // Default capabilities: audio only for now
     let capabilities = vec!["audio".to_string()];
     if let Err(e) = broadcast_to_channel(
@@ -263,6 +279,16 @@ pub async fn start_call(
         tracing::warn!(error = %e, %channel_id, "Failed to broadcast IncomingCall event");
     }
 
+    system_messages::post(
+        &state.db,
+        &state.redis,
+        channel_id,
+        auth.id,
+        MessageType::SystemCall,
+        &LocalizedText::new("system-call-started"),
+    )
+    .await;
+
     Ok((
         StatusCode::CREATED,
         Json(CallStateResponse {
@@ -309,10 +335,10 @@ pub async fn join_call(
                         error = %e,
                         user_id = %auth.id,
                         target_id = %participant_id,
-                        fail_open = state.config.block_check_fail_open,
+                        fail_open = state.config().block_check_fail_open,
                         "Redis block check failed, using failsafe policy"
                     );
-                    if !state.config.block_check_fail_open {
+                    if !state.config().block_check_fail_open {
                         return Err(CallHandlerError::Blocked);
                     }
                 }
@@ -405,6 +431,16 @@ pub async fn decline_call(
         {
             tracing::warn!(error = %e, %channel_id, "Failed to broadcast CallEnded event");
         }
+
+        system_messages::post(
+            &state.db,
+            &state.redis,
+            channel_id,
+            auth.id,
+            MessageType::SystemCall,
+            &LocalizedText::new("system-call-declined"),
+        )
+        .await;
     }
 
     Ok(Json(CallStateResponse {
@@ -478,6 +514,16 @@ pub async fn leave_call(
         {
             tracing::warn!(error = %e, %channel_id, "Failed to broadcast CallEnded event");
         }
+
+        system_messages::post(
+            &state.db,
+            &state.redis,
+            channel_id,
+            auth.id,
+            MessageType::SystemCall,
+            &system_messages::call_ended_text(*duration_secs),
+        )
+        .await;
     }
 
     Ok(Json(CallStateResponse {
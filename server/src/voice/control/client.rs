@@ -0,0 +1,127 @@
+//! SFU Control Client
+//!
+//! Used by server replicas to drive a remote media node.
+
+use axum::http::uri::PathAndQuery;
+use tonic::codec::{ProstCodec, Streaming};
+use tonic::metadata::MetadataValue;
+use tonic::transport::{Channel, Endpoint};
+use tonic::{IntoRequest, Request, Status};
+
+use super::{
+    AnswerRequest, EventsRequest, IceCandidateRequest, JoinReply, JoinRequest, LeaveReply,
+    LeaveRequest, NodeStatus, PeerEvent, SetMutedRequest, StatusRequest,
+};
+
+/// Timeout of unary calls; event streams are not limited.
+const CALL_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
+
+/// Client for one media node's `SfuControl` service.
+#[derive(Debug, Clone)]
+pub struct ControlClient {
+    grpc: tonic::client::Grpc<Channel>,
+    authorization: MetadataValue<tonic::metadata::Ascii>,
+}
+
+impl ControlClient {
+    /// Create a client for the node at `url`.
+    ///
+    /// The connection is established on the first call and re-established
+    /// after failures.
+    pub fn new(url: &str, token: &str) -> Result<Self, String> {
+        let channel = Endpoint::from_shared(url.to_string())
+            .map_err(|e| format!("Invalid SFU node URL '{url}': {e}"))?
+            .connect_lazy();
+        let authorization = format!("Bearer {token}")
+            .parse()
+            .map_err(|_| "SFU control token is not valid ASCII".to_string())?;
+        Ok(Self {
+            grpc: tonic::client::Grpc::new(channel),
+            authorization,
+        })
+    }
+
+    /// Build an authenticated request.
+    fn request<M>(&self, message: M, timeout: bool) -> Request<M> {
+        let mut request = message.into_request();
+        request
+            .metadata_mut()
+            .insert("authorization", self.authorization.clone());
+        if timeout {
+            request.set_timeout(CALL_TIMEOUT);
+        }
+        request
+    }
+
+    async fn unary<M1, M2>(&self, method: &'static str, message: M1) -> Result<M2, Status>
+    where
+        M1: prost::Message + Send + Sync + 'static,
+        M2: prost::Message + Default + Send + Sync + 'static,
+    {
+        let mut grpc = self.grpc.clone();
+        grpc.ready()
+            .await
+            .map_err(|e| Status::unavailable(format!("SFU node is not ready: {e}")))?;
+        let response = grpc
+            .unary(
+                self.request(message, true),
+                PathAndQuery::from_static(method),
+                ProstCodec::default(),
+            )
+            .await?;
+        Ok(response.into_inner())
+    }
+
+    /// Node identity and load.
+    pub async fn status(&self) -> Result<NodeStatus, Status> {
+        self.unary("/kaiku.sfu.v1.SfuControl/Status", StatusRequest {})
+            .await
+    }
+
+    /// Join a participant; the offer arrives on the events stream.
+    pub async fn join(&self, request: JoinRequest) -> Result<JoinReply, Status> {
+        self.unary("/kaiku.sfu.v1.SfuControl/Join", request).await
+    }
+
+    /// Apply a participant's SDP answer.
+    pub async fn answer(&self, request: AnswerRequest) -> Result<(), Status> {
+        self.unary::<_, ()>("/kaiku.sfu.v1.SfuControl/Answer", request)
+            .await
+    }
+
+    /// Apply a participant's ICE candidate.
+    pub async fn add_ice_candidate(&self, request: IceCandidateRequest) -> Result<(), Status> {
+        self.unary::<_, ()>("/kaiku.sfu.v1.SfuControl/AddIceCandidate", request)
+            .await
+    }
+
+    /// Mute or unmute a participant.
+    pub async fn set_muted(&self, request: SetMutedRequest) -> Result<(), Status> {
+        self.unary::<_, ()>("/kaiku.sfu.v1.SfuControl/SetMuted", request)
+            .await
+    }
+
+    /// Remove a participant.
+    pub async fn leave(&self, request: LeaveRequest) -> Result<LeaveReply, Status> {
+        self.unary("/kaiku.sfu.v1.SfuControl/Leave", request).await
+    }
+
+    /// Open the stream of events for the participants joined by `client_id`.
+    pub async fn events(&self, client_id: &str) -> Result<Streaming<PeerEvent>, Status> {
+        let mut grpc = self.grpc.clone();
+        grpc.ready()
+            .await
+            .map_err(|e| Status::unavailable(format!("SFU node is not ready: {e}")))?;
+        let request = EventsRequest {
+            client_id: client_id.to_string(),
+        };
+        let response = grpc
+            .server_streaming(
+                self.request(request, false),
+                PathAndQuery::from_static("/kaiku.sfu.v1.SfuControl/Events"),
+                ProstCodec::default(),
+            )
+            .await?;
+        Ok(response.into_inner())
+    }
+}
@@ -0,0 +1,270 @@
+//! SFU Control Protocol
+//!
+//! gRPC service (`kaiku.sfu.v1.SfuControl`) through which a server replica
+//! drives an SFU running on a remote media node (the `sfu-node` binary).
+//! The replica keeps everything that needs the database: permission checks,
+//! guild perks, session metrics and the room claim in the cluster registry.
+//! The node only hosts the WebRTC rooms.
+//!
+//! | RPC               | Purpose                                                  |
+//! |-------------------|----------------------------------------------------------|
+//! | `Status`          | Node identity and load, polled as the node's heartbeat  |
+//! | `Join`            | Create a peer in a room and send it the SDP offer       |
+//! | `Answer`          | Apply a participant's SDP answer                         |
+//! | `AddIceCandidate` | Apply a participant's ICE candidate                      |
+//! | `SetMuted`        | Mute or unmute a participant                             |
+//! | `Leave`           | Remove a participant and report its session              |
+//! | `Events`          | Stream of the `ServerEvent`s addressed to participants  |
+//!
+//! Events are the JSON encoding of [`ServerEvent`](crate::ws::ServerEvent),
+//! so the replica forwards them to the participant's WebSocket unchanged.
+//! Every call carries `authorization: Bearer <SFU_CONTROL_TOKEN>`.
+//!
+//! The messages are written out by hand with `prost` derives instead of
+//! being generated from a `.proto` file, so building the server does not
+//! need `protoc`.
+
+pub mod client;
+pub mod server;
+
+use std::collections::HashMap;
+
+pub use client::ControlClient;
+pub use server::ControlService;
+use tonic::{Code, Status};
+
+use super::error::VoiceError;
+
+/// Fully qualified gRPC service name.
+pub const SERVICE_NAME: &str = "kaiku.sfu.v1.SfuControl";
+
+/// Request for [`NodeStatus`].
+#[derive(Clone, PartialEq, Eq, prost::Message)]
+pub struct StatusRequest {}
+
+/// Identity and load of a media node.
+#[derive(Clone, PartialEq, Eq, prost::Message)]
+pub struct NodeStatus {
+    /// Node identifier (`NODE_ID` of the node).
+    #[prost(string, tag = "1")]
+    pub node_id: String,
+    /// Server version the node runs.
+    #[prost(string, tag = "2")]
+    pub version: String,
+    /// Region label (`NODE_REGION`), if configured.
+    #[prost(string, optional, tag = "3")]
+    pub region: Option<String>,
+    /// Public IP of the node (`NODE_PUBLIC_ADDRESS`), if configured.
+    #[prost(string, optional, tag = "4")]
+    pub public_address: Option<String>,
+    /// Hosted voice channels.
+    #[prost(uint64, tag = "5")]
+    pub voice_rooms: u64,
+    /// Participants across those voice channels.
+    #[prost(uint64, tag = "6")]
+    pub voice_participants: u64,
+    /// When the node started (Unix milliseconds).
+    #[prost(int64, tag = "7")]
+    pub started_at_ms: i64,
+}
+
+/// Join a participant to a room on the node.
+#[derive(Clone, PartialEq, Eq, prost::Message)]
+pub struct JoinRequest {
+    /// Node ID of the replica the participant is connected to; the node
+    /// sends the participant's events to that replica's `Events` stream.
+    #[prost(string, tag = "1")]
+    pub client_id: String,
+    #[prost(string, tag = "2")]
+    pub channel_id: String,
+    #[prost(string, tag = "3")]
+    pub user_id: String,
+    #[prost(string, tag = "4")]
+    pub username: String,
+    #[prost(string, tag = "5")]
+    pub display_name: String,
+    #[prost(string, optional, tag = "6")]
+    pub name_pronunciation: Option<String>,
+    /// Join receive-only.
+    #[prost(bool, tag = "7")]
+    pub listener: bool,
+    #[prost(bool, tag = "8")]
+    pub noise_suppression: bool,
+    /// Opus bitrate cap from the guild's perks.
+    #[prost(uint32, optional, tag = "9")]
+    pub max_audio_bitrate: Option<u32>,
+    /// RTT samples to the media nodes, as reported in `VoiceJoin`.
+    #[prost(map = "string, uint32", tag = "10")]
+    pub node_rtts: HashMap<String, u32>,
+}
+
+/// Reply to [`JoinRequest`]; the offer itself is sent as an event.
+#[derive(Clone, PartialEq, Eq, prost::Message)]
+pub struct JoinReply {}
+
+/// A participant's SDP answer.
+#[derive(Clone, PartialEq, Eq, prost::Message)]
+pub struct AnswerRequest {
+    #[prost(string, tag = "1")]
+    pub channel_id: String,
+    #[prost(string, tag = "2")]
+    pub user_id: String,
+    #[prost(string, tag = "3")]
+    pub sdp: String,
+}
+
+/// A participant's ICE candidate (JSON `RTCIceCandidateInit`).
+#[derive(Clone, PartialEq, Eq, prost::Message)]
+pub struct IceCandidateRequest {
+    #[prost(string, tag = "1")]
+    pub channel_id: String,
+    #[prost(string, tag = "2")]
+    pub user_id: String,
+    #[prost(string, tag = "3")]
+    pub candidate: String,
+}
+
+/// Mute or unmute a participant.
+#[derive(Clone, PartialEq, Eq, prost::Message)]
+pub struct SetMutedRequest {
+    #[prost(string, tag = "1")]
+    pub channel_id: String,
+    #[prost(string, tag = "2")]
+    pub user_id: String,
+    #[prost(bool, tag = "3")]
+    pub muted: bool,
+}
+
+/// Remove a participant from a room.
+#[derive(Clone, PartialEq, Eq, prost::Message)]
+pub struct LeaveRequest {
+    #[prost(string, tag = "1")]
+    pub channel_id: String,
+    #[prost(string, tag = "2")]
+    pub user_id: String,
+}
+
+/// The participant's session, for the replica to finalize, and whether the
+/// room is gone.
+#[derive(Clone, PartialEq, Eq, prost::Message)]
+pub struct LeaveReply {
+    /// Whether the participant was in the room.
+    #[prost(bool, tag = "1")]
+    pub found: bool,
+    #[prost(string, tag = "2")]
+    pub session_id: String,
+    /// Unix milliseconds.
+    #[prost(int64, tag = "3")]
+    pub connected_at_ms: i64,
+    #[prost(bool, tag = "4")]
+    pub noise_suppression: bool,
+    /// The room emptied and was removed; the replica releases its claim.
+    #[prost(bool, tag = "5")]
+    pub room_empty: bool,
+}
+
+/// Subscribe to the events of the participants a replica joined.
+#[derive(Clone, PartialEq, Eq, prost::Message)]
+pub struct EventsRequest {
+    /// Node ID of the replica, as sent in [`JoinRequest::client_id`].
+    #[prost(string, tag = "1")]
+    pub client_id: String,
+}
+
+/// An event for one participant.
+#[derive(Clone, PartialEq, Eq, prost::Message)]
+pub struct PeerEvent {
+    #[prost(string, tag = "1")]
+    pub channel_id: String,
+    #[prost(string, tag = "2")]
+    pub user_id: String,
+    /// JSON-encoded `ServerEvent`.
+    #[prost(string, tag = "3")]
+    pub event_json: String,
+}
+
+/// Status a node returns for `error`.
+pub(crate) fn status_from_error(error: &VoiceError) -> Status {
+    let code = match error {
+        VoiceError::Listener => Code::PermissionDenied,
+        VoiceError::AlreadyJoined => Code::AlreadyExists,
+        VoiceError::RoomNotFound(_) | VoiceError::ParticipantNotFound(_) => Code::NotFound,
+        VoiceError::ChannelFull { .. } => Code::ResourceExhausted,
+        VoiceError::Signaling(_) => Code::InvalidArgument,
+        _ => Code::Internal,
+    };
+    Status::new(code, error.to_string())
+}
+
+/// Error reported to the participant for a status returned by a node.
+///
+/// Transport and authentication failures are not passed on verbatim.
+pub(crate) fn error_from_status(status: &Status) -> VoiceError {
+    match status.code() {
+        Code::PermissionDenied => VoiceError::Listener,
+        Code::AlreadyExists => VoiceError::AlreadyJoined,
+        Code::NotFound => VoiceError::NotInChannel,
+        Code::InvalidArgument | Code::ResourceExhausted | Code::Internal => {
+            VoiceError::MediaNode(status.message().to_string())
+        }
+        _ => VoiceError::MediaNode("Voice media node is unavailable".to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use prost::Message;
+
+    use super::*;
+
+    #[test]
+    fn test_join_request_round_trip() {
+        let request = JoinRequest {
+            client_id: "node-a".to_string(),
+            channel_id: uuid::Uuid::new_v4().to_string(),
+            user_id: uuid::Uuid::new_v4().to_string(),
+            username: "alice".to_string(),
+            display_name: "Alice".to_string(),
+            name_pronunciation: None,
+            listener: true,
+            noise_suppression: false,
+            max_audio_bitrate: Some(128_000),
+            node_rtts: HashMap::from([("eu-1".to_string(), 24)]),
+        };
+        let decoded = JoinRequest::decode(request.encode_to_vec().as_slice()).unwrap();
+        assert_eq!(decoded, request);
+    }
+
+    #[test]
+    fn test_empty_optional_fields_stay_unset() {
+        let status = NodeStatus {
+            node_id: "sfu-1".to_string(),
+            ..Default::default()
+        };
+        let decoded = NodeStatus::decode(status.encode_to_vec().as_slice()).unwrap();
+        assert_eq!(decoded.region, None);
+        assert_eq!(decoded.public_address, None);
+    }
+
+    #[test]
+    fn test_voice_errors_survive_the_node_boundary() {
+        let listener = status_from_error(&VoiceError::Listener);
+        assert!(matches!(error_from_status(&listener), VoiceError::Listener));
+        let joined = status_from_error(&VoiceError::AlreadyJoined);
+        assert!(matches!(
+            error_from_status(&joined),
+            VoiceError::AlreadyJoined
+        ));
+        let missing = status_from_error(&VoiceError::ParticipantNotFound(uuid::Uuid::new_v4()));
+        assert!(matches!(
+            error_from_status(&missing),
+            VoiceError::NotInChannel
+        ));
+    }
+
+    #[test]
+    fn test_transport_errors_are_not_passed_on() {
+        let error = error_from_status(&Status::unavailable("tcp connect error: 10.0.0.7:50051"));
+        assert_eq!(error.to_string(), "Voice media node is unavailable");
+    }
+}
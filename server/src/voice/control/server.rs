@@ -0,0 +1,272 @@
+//! SFU Control Service
+//!
+//! Served by the `sfu-node` binary on `SFU_CONTROL_BIND_ADDRESS`.
+
+use std::convert::Infallible;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use axum::http;
+use chrono::{DateTime, Utc};
+use dashmap::DashMap;
+use futures::future::BoxFuture;
+use futures::stream::{self, BoxStream};
+use tokio::sync::mpsc;
+use tonic::body::BoxBody;
+use tonic::codec::ProstCodec;
+use tonic::server::{Grpc, NamedService};
+use tonic::{Request, Response, Status};
+use tracing::{debug, info, warn};
+use uuid::Uuid;
+
+use super::{
+    status_from_error, AnswerRequest, EventsRequest, IceCandidateRequest, JoinReply, JoinRequest,
+    LeaveReply, LeaveRequest, NodeStatus, PeerEvent, SetMutedRequest, StatusRequest, SERVICE_NAME,
+};
+use crate::util::has_bearer_token;
+use crate::voice::ws_handler::{self, HostJoin};
+use crate::voice::SfuServer;
+use crate::ws::ServerEvent;
+
+/// Buffered events per replica stream.
+const EVENTS_CAPACITY: usize = 256;
+
+/// Buffered events per participant before they are forwarded.
+const PEER_EVENTS_CAPACITY: usize = 100;
+
+/// The `SfuControl` service of a media node.
+#[derive(Clone)]
+pub struct ControlService {
+    sfu: Arc<SfuServer>,
+    token: Arc<str>,
+    started_at: DateTime<Utc>,
+    /// Events stream of each replica, by the replica's node ID.
+    replicas: Arc<DashMap<String, mpsc::Sender<PeerEvent>>>,
+}
+
+impl ControlService {
+    /// Serve `sfu` to replicas presenting `token`.
+    pub fn new(sfu: Arc<SfuServer>, token: &str) -> Self {
+        Self {
+            sfu,
+            token: token.into(),
+            started_at: Utc::now(),
+            replicas: Arc::new(DashMap::new()),
+        }
+    }
+
+    async fn status(&self, _request: StatusRequest) -> Result<NodeStatus, Status> {
+        let config = self.sfu.config();
+        let (voice_rooms, voice_participants) = self.sfu.load().await;
+        Ok(NodeStatus {
+            node_id: config.node_id.clone(),
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            region: config.node_region.clone(),
+            public_address: config.node_public_address.clone(),
+            voice_rooms: voice_rooms as u64,
+            voice_participants: voice_participants as u64,
+            started_at_ms: self.started_at.timestamp_millis(),
+        })
+    }
+
+    async fn join(&self, request: JoinRequest) -> Result<JoinReply, Status> {
+        let channel_id = parse_id(&request.channel_id)?;
+        let user_id = parse_id(&request.user_id)?;
+
+        let (tx, rx) = mpsc::channel(PEER_EVENTS_CAPACITY);
+        self.spawn_event_forwarder(request.client_id, channel_id, user_id, rx);
+
+        let join = HostJoin {
+            channel_id,
+            user_id,
+            username: request.username,
+            display_name: request.display_name,
+            name_pronunciation: request.name_pronunciation,
+            listener: request.listener,
+            noise_suppression: request.noise_suppression,
+            max_audio_bitrate: request.max_audio_bitrate,
+            node_rtts: request.node_rtts,
+        };
+        ws_handler::host_join(&self.sfu, join, &tx)
+            .await
+            .map_err(|e| status_from_error(&e))?;
+
+        info!(user_id = %user_id, channel_id = %channel_id, "User joined voice channel");
+        Ok(JoinReply {})
+    }
+
+    async fn answer(&self, request: AnswerRequest) -> Result<(), Status> {
+        let channel_id = parse_id(&request.channel_id)?;
+        let user_id = parse_id(&request.user_id)?;
+        ws_handler::handle_answer(&self.sfu, user_id, channel_id, &request.sdp)
+            .await
+            .map_err(|e| status_from_error(&e))
+    }
+
+    async fn add_ice_candidate(&self, request: IceCandidateRequest) -> Result<(), Status> {
+        let channel_id = parse_id(&request.channel_id)?;
+        let user_id = parse_id(&request.user_id)?;
+        ws_handler::handle_ice_candidate(&self.sfu, user_id, channel_id, &request.candidate)
+            .await
+            .map_err(|e| status_from_error(&e))
+    }
+
+    async fn set_muted(&self, request: SetMutedRequest) -> Result<(), Status> {
+        let channel_id = parse_id(&request.channel_id)?;
+        let user_id = parse_id(&request.user_id)?;
+        ws_handler::handle_mute(&self.sfu, user_id, channel_id, request.muted)
+            .await
+            .map_err(|e| status_from_error(&e))
+    }
+
+    async fn leave(&self, request: LeaveRequest) -> Result<LeaveReply, Status> {
+        let channel_id = parse_id(&request.channel_id)?;
+        let user_id = parse_id(&request.user_id)?;
+        let left = ws_handler::host_leave(&self.sfu, user_id, channel_id)
+            .await
+            .map_err(|e| status_from_error(&e))?;
+
+        info!(user_id = %user_id, channel_id = %channel_id, "User left voice channel");
+        let mut reply = LeaveReply {
+            room_empty: left.room_empty,
+            ..Default::default()
+        };
+        if let Some(session) = left.session {
+            reply.found = true;
+            reply.session_id = session.session_id.to_string();
+            reply.connected_at_ms = session.connected_at.timestamp_millis();
+            reply.noise_suppression = session.noise_suppression;
+        }
+        Ok(reply)
+    }
+
+    /// Route the events of the replica's participants to a new stream,
+    /// replacing the replica's previous one.
+    fn events(&self, request: EventsRequest) -> BoxStream<'static, Result<PeerEvent, Status>> {
+        let (tx, rx) = mpsc::channel(EVENTS_CAPACITY);
+        info!(client_id = %request.client_id, "Replica subscribed to events");
+        self.replicas.insert(request.client_id, tx);
+        Box::pin(stream::unfold(rx, |mut rx| async move {
+            rx.recv().await.map(|event| (Ok(event), rx))
+        }))
+    }
+
+    /// Forward a participant's events to the events stream of the replica
+    /// it joined through.
+    fn spawn_event_forwarder(
+        &self,
+        client_id: String,
+        channel_id: Uuid,
+        user_id: Uuid,
+        mut rx: mpsc::Receiver<ServerEvent>,
+    ) {
+        let replicas = self.replicas.clone();
+        tokio::spawn(async move {
+            while let Some(event) = rx.recv().await {
+                let event_json = match serde_json::to_string(&event) {
+                    Ok(json) => json,
+                    Err(e) => {
+                        warn!(error = %e, "Failed to serialize voice event");
+                        continue;
+                    }
+                };
+                let Some(tx) = replicas.get(&client_id).map(|tx| tx.clone()) else {
+                    debug!(client_id = %client_id, "No events stream for replica");
+                    continue;
+                };
+                let event = PeerEvent {
+                    channel_id: channel_id.to_string(),
+                    user_id: user_id.to_string(),
+                    event_json,
+                };
+                if tx.send(event).await.is_err() {
+                    debug!(client_id = %client_id, "Replica events stream closed");
+                }
+            }
+        });
+    }
+}
+
+#[allow(clippy::result_large_err)] // `Status` is what the handlers return
+fn parse_id(id: &str) -> Result<Uuid, Status> {
+    Uuid::parse_str(id).map_err(|_| Status::invalid_argument(format!("Invalid ID '{id}'")))
+}
+
+/// Decode a unary request, run `handler` on it and encode its reply.
+async fn unary<M1, M2, F, Fut>(
+    request: http::Request<BoxBody>,
+    handler: F,
+) -> http::Response<BoxBody>
+where
+    M1: prost::Message + Default + Send + 'static,
+    M2: prost::Message + Send + 'static,
+    F: FnOnce(M1) -> Fut + Clone + Send + 'static,
+    Fut: std::future::Future<Output = Result<M2, Status>> + Send + 'static,
+{
+    let service = tower::service_fn(move |request: Request<M1>| {
+        let handler = handler.clone();
+        async move { handler(request.into_inner()).await.map(Response::new) }
+    });
+    Grpc::new(ProstCodec::default())
+        .unary(service, request)
+        .await
+}
+
+impl NamedService for ControlService {
+    const NAME: &'static str = SERVICE_NAME;
+}
+
+impl tower::Service<http::Request<BoxBody>> for ControlService {
+    type Response = http::Response<BoxBody>;
+    type Error = Infallible;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: http::Request<BoxBody>) -> Self::Future {
+        let this = self.clone();
+        Box::pin(async move {
+            if !has_bearer_token(request.headers(), &this.token) {
+                return Ok(Status::unauthenticated("Invalid SFU control token").into_http());
+            }
+
+            let path = request
+                .uri()
+                .path()
+                .strip_prefix(&format!("/{SERVICE_NAME}/"));
+            let response = match path {
+                Some("Status") => {
+                    unary(request, move |r| async move { this.status(r).await }).await
+                }
+                Some("Join") => unary(request, move |r| async move { this.join(r).await }).await,
+                Some("Answer") => {
+                    unary(request, move |r| async move { this.answer(r).await }).await
+                }
+                Some("AddIceCandidate") => {
+                    unary(
+                        request,
+                        move |r| async move { this.add_ice_candidate(r).await },
+                    )
+                    .await
+                }
+                Some("SetMuted") => {
+                    unary(request, move |r| async move { this.set_muted(r).await }).await
+                }
+                Some("Leave") => unary(request, move |r| async move { this.leave(r).await }).await,
+                Some("Events") => {
+                    let service = tower::service_fn(move |request: Request<EventsRequest>| {
+                        let stream = this.events(request.into_inner());
+                        async move { Ok::<_, Status>(Response::new(stream)) }
+                    });
+                    Grpc::new(ProstCodec::default())
+                        .server_streaming(service, request)
+                        .await
+                }
+                _ => Status::unimplemented("Unknown SfuControl method").into_http(),
+            };
+            Ok(response)
+        })
+    }
+}
@@ -0,0 +1,181 @@
+//! Live Room Diagnostics
+//!
+//! Snapshots of the SFU state of the rooms hosted by this node, for the admin
+//! voice diagnostics API: per-peer connection and ICE state, the selected
+//! candidate pair, the bitrate each participant publishes and their latest
+//! stats reports from `connection_metrics`.
+
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use super::peer::Peer;
+use super::sfu::Room;
+use super::track_types::TrackSource;
+
+/// Stats reports returned per participant.
+const RECENT_STATS_LIMIT: i64 = 10;
+
+/// A hosted room in the room listing.
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct VoiceRoomSummary {
+    /// Voice channel.
+    pub channel_id: Uuid,
+    /// Connected participants.
+    pub participant_count: usize,
+    /// Participants whose ICE connection is not connected.
+    pub disconnected_count: usize,
+    /// Media node the channel is placed on (multi-node deployments only).
+    pub media_node: Option<String>,
+    /// Whether a server-side recording is running.
+    pub recording: bool,
+}
+
+/// One stats report a participant sent.
+#[derive(Debug, Serialize, sqlx::FromRow, utoipa::ToSchema)]
+pub struct RecentVoiceStats {
+    pub time: DateTime<Utc>,
+    pub latency_ms: i16,
+    pub packet_loss: f32,
+    pub jitter_ms: i16,
+    pub quality: i16,
+}
+
+/// SFU state of one participant.
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct PeerDiagnostics {
+    pub user_id: Uuid,
+    pub username: String,
+    /// Server-side session of this connection.
+    pub session_id: Uuid,
+    pub connected_at: DateTime<Utc>,
+    /// Peer connection state (e.g. "connected", "failed").
+    pub connection_state: String,
+    /// ICE connection state (e.g. "checking", "connected").
+    pub ice_connection_state: String,
+    /// Selected ICE candidate pair, local and remote candidate.
+    pub selected_candidate_pair: Option<String>,
+    /// RTP payload bitrate the participant currently publishes (bits per second).
+    pub inbound_bitrate: u64,
+    /// Opus bitrate cap announced to the client, if any.
+    pub audio_bitrate_cap: Option<u32>,
+    /// Whether the cap was lowered for sustained packet loss.
+    pub loss_capped: bool,
+    pub muted: bool,
+    pub listener: bool,
+    /// Tracks the participant publishes.
+    #[schema(value_type = Vec<String>)]
+    pub incoming_tracks: Vec<TrackSource>,
+    /// Tracks forwarded to the participant.
+    pub outgoing_track_count: usize,
+    /// Latest stats reports of the current connection, newest first.
+    pub recent_stats: Vec<RecentVoiceStats>,
+}
+
+/// SFU state of one hosted room.
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct VoiceRoomDiagnostics {
+    pub channel_id: Uuid,
+    /// Media node the channel is placed on (multi-node deployments only).
+    pub media_node: Option<String>,
+    /// Whether a server-side recording is running.
+    pub recording: bool,
+    /// Participants, ordered by join time.
+    pub participants: Vec<PeerDiagnostics>,
+}
+
+/// Summarize a hosted room.
+pub async fn room_summary(room: &Room) -> VoiceRoomSummary {
+    let peers: Vec<Arc<Peer>> = room.peers.read().await.values().cloned().collect();
+    VoiceRoomSummary {
+        channel_id: room.channel_id,
+        participant_count: peers.len(),
+        disconnected_count: peers.iter().filter(|peer| !peer.is_connected()).count(),
+        media_node: room.media_node.read().await.clone(),
+        recording: room.recording.read().await.is_some(),
+    }
+}
+
+/// Snapshot a hosted room and its participants.
+///
+/// Uses admin RLS bypass via transaction-scoped `app.admin_bypass` to read
+/// the participants' stats reports.
+pub async fn room_diagnostics(
+    pool: &PgPool,
+    room: &Room,
+) -> Result<VoiceRoomDiagnostics, sqlx::Error> {
+    let mut peers: Vec<Arc<Peer>> = room.peers.read().await.values().cloned().collect();
+    peers.sort_by_key(|peer| peer.connected_at);
+
+    let mut tx = pool.begin().await?;
+    crate::db::set_admin_bypass(&mut tx).await?;
+
+    let mut participants = Vec::with_capacity(peers.len());
+    for peer in peers {
+        participants.push(peer_diagnostics(&mut tx, &peer).await?);
+    }
+    tx.commit().await?;
+
+    Ok(VoiceRoomDiagnostics {
+        channel_id: room.channel_id,
+        media_node: room.media_node.read().await.clone(),
+        recording: room.recording.read().await.is_some(),
+        participants,
+    })
+}
+
+async fn peer_diagnostics(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    peer: &Peer,
+) -> Result<PeerDiagnostics, sqlx::Error> {
+    let selected_candidate_pair = peer
+        .peer_connection
+        .sctp()
+        .transport()
+        .ice_transport()
+        .get_selected_candidate_pair()
+        .await
+        .map(|pair| pair.to_string());
+
+    let mut incoming_tracks: Vec<TrackSource> =
+        peer.incoming_tracks.read().await.keys().copied().collect();
+    incoming_tracks.sort_by_key(|source| format!("{source:?}"));
+
+    // Stats reports sent since this connection was established
+    let recent_stats = sqlx::query_as::<_, RecentVoiceStats>(
+        r"
+        SELECT time, latency_ms, packet_loss, jitter_ms, quality
+        FROM connection_metrics
+        WHERE user_id = $1 AND channel_id = $2 AND time >= $3
+        ORDER BY time DESC
+        LIMIT $4
+        ",
+    )
+    .bind(peer.user_id)
+    .bind(peer.channel_id)
+    .bind(peer.connected_at)
+    .bind(RECENT_STATS_LIMIT)
+    .fetch_all(&mut **tx)
+    .await?;
+
+    Ok(PeerDiagnostics {
+        user_id: peer.user_id,
+        username: peer.username.clone(),
+        session_id: peer.session_id,
+        connected_at: peer.connected_at,
+        connection_state: peer.peer_connection.connection_state().to_string(),
+        ice_connection_state: peer.peer_connection.ice_connection_state().to_string(),
+        selected_candidate_pair,
+        inbound_bitrate: peer.inbound_bitrate.bitrate(),
+        audio_bitrate_cap: peer.effective_audio_bitrate().await,
+        loss_capped: peer.loss_audio_bitrate().await.is_some(),
+        muted: peer.is_muted().await,
+        listener: peer.is_listener().await,
+        incoming_tracks,
+        outgoing_track_count: peer.outgoing_tracks.read().await.len(),
+        recent_stats,
+    })
+}
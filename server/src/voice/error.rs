@@ -40,6 +40,14 @@ pub enum VoiceError {
     #[error("Not authorized to join this voice channel")]
     Unauthorized,
 
+    /// User is timed out in the channel's guild.
+    #[error("You are timed out in this server")]
+    TimedOut,
+
+    /// The channel's guild is suspended.
+    #[error("This server has been suspended")]
+    GuildSuspended,
+
     /// Channel not found.
     #[error("Channel not found: {0}")]
     ChannelNotFound(Uuid),
@@ -48,14 +56,38 @@ pub enum VoiceError {
     #[error("Already in voice channel")]
     AlreadyJoined,
 
+    /// The channel is hosted by another server node.
+    #[error("This voice channel is hosted on another server node ({0})")]
+    RoomOnOtherNode(String),
+
     /// Not in voice channel.
     #[error("Not in voice channel")]
     NotInChannel,
 
+    /// Listeners cannot send media until a moderator promotes them.
+    #[error("Listeners cannot speak in this voice channel")]
+    Listener,
+
     /// Rate limited.
     #[error("Rate limited: too many voice join requests")]
     RateLimited,
 
+    /// A recording is already running in this channel.
+    #[error("A recording is already in progress in this channel")]
+    RecordingInProgress,
+
+    /// No recording is running in this channel.
+    #[error("No active recording in this channel")]
+    NoActiveRecording,
+
+    /// Recording requires object storage, which is not configured.
+    #[error("Voice recording is not available on this server")]
+    RecordingUnavailable,
+
+    /// A remote media node reported an error or could not be reached.
+    #[error("{0}")]
+    MediaNode(String),
+
     /// Internal error.
     #[error("Internal error: {0}")]
     Internal(String),
@@ -83,16 +115,42 @@ impl IntoResponse for VoiceError {
             ),
             Self::ChannelFull { .. } => (StatusCode::CONFLICT, "CHANNEL_FULL", self.to_string()),
             Self::Unauthorized => (StatusCode::FORBIDDEN, "UNAUTHORIZED", self.to_string()),
+            Self::TimedOut => (StatusCode::FORBIDDEN, "TIMED_OUT", self.to_string()),
+            Self::GuildSuspended => (StatusCode::FORBIDDEN, "GUILD_SUSPENDED", self.to_string()),
             Self::ChannelNotFound(_) => {
                 (StatusCode::NOT_FOUND, "CHANNEL_NOT_FOUND", self.to_string())
             }
             Self::AlreadyJoined => (StatusCode::CONFLICT, "ALREADY_JOINED", self.to_string()),
+            Self::RoomOnOtherNode(_) => {
+                (StatusCode::CONFLICT, "ROOM_ON_OTHER_NODE", self.to_string())
+            }
             Self::NotInChannel => (StatusCode::BAD_REQUEST, "NOT_IN_CHANNEL", self.to_string()),
+            Self::Listener => (StatusCode::FORBIDDEN, "LISTENER", self.to_string()),
             Self::RateLimited => (
                 StatusCode::TOO_MANY_REQUESTS,
                 "RATE_LIMITED",
                 self.to_string(),
             ),
+            Self::RecordingInProgress => (
+                StatusCode::CONFLICT,
+                "RECORDING_IN_PROGRESS",
+                self.to_string(),
+            ),
+            Self::NoActiveRecording => (
+                StatusCode::NOT_FOUND,
+                "NO_ACTIVE_RECORDING",
+                self.to_string(),
+            ),
+            Self::RecordingUnavailable => (
+                StatusCode::SERVICE_UNAVAILABLE,
+                "RECORDING_UNAVAILABLE",
+                self.to_string(),
+            ),
+            Self::MediaNode(_) => (
+                StatusCode::BAD_GATEWAY,
+                "MEDIA_NODE_ERROR",
+                self.to_string(),
+            ),
             Self::Internal(_) => (
                 StatusCode::INTERNAL_SERVER_ERROR,
                 "INTERNAL_ERROR",
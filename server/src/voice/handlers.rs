@@ -45,18 +45,19 @@ pub struct IceServersResponse {
     security(("bearer_auth" = [])),
 )]
 pub async fn get_ice_servers(State(state): State<AppState>) -> Json<IceServersResponse> {
+    let config = state.config();
     let mut servers = vec![IceServer {
-        urls: vec![state.config.stun_server.clone()],
+        urls: vec![config.stun_server.clone()],
         username: None,
         credential: None,
     }];
 
     // Add TURN server if configured
-    if let Some(turn) = &state.config.turn_server {
+    if let Some(turn) = &config.turn_server {
         servers.push(IceServer {
             urls: vec![turn.clone()],
-            username: state.config.turn_username.clone(),
-            credential: state.config.turn_credential.clone(),
+            username: config.turn_username.clone(),
+            credential: config.turn_credential.clone(),
         });
     }
 
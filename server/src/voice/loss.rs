@@ -0,0 +1,165 @@
+//! Sustained Packet Loss
+//!
+//! Every stats report is stored in `connection_metrics`; right after storing
+//! it, the packet loss the participant reported over the last
+//! `voice_loss_window_secs` is averaged. At `voice_loss_warning_percent` or
+//! more the participant is sent `VoiceQualityWarning` and the SFU lowers what
+//! their client sends: the Opus cap in the next offer drops to
+//! `voice_loss_audio_bitrate` and a REMB caps their screen share and webcam.
+//! The SFU forwards media without re-encoding it, so relieving the
+//! participant's own uplink is what it can do for a congested connection.
+//!
+//! Once the average falls below half the threshold the caps are lifted and
+//! `VoiceQualityRecovered` is sent.
+
+use std::time::Duration;
+
+use sqlx::PgPool;
+use tracing::{info, warn};
+use uuid::Uuid;
+use webrtc::rtcp::payload_feedbacks::receiver_estimated_maximum_bitrate::ReceiverEstimatedMaximumBitrate;
+
+use super::metrics::recent_packet_loss;
+use super::peer::Peer;
+use super::quality::Quality;
+use super::sfu::SfuServer;
+use crate::observability::metrics;
+use crate::ws::ServerEvent;
+
+/// Reports needed in the window before loss counts as sustained.
+const MIN_SAMPLES: i64 = 3;
+
+/// Change to a participant's bitrate caps.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LossAction {
+    /// Warn and cap the bitrate.
+    Lower,
+    /// Lift the cap.
+    Lift,
+}
+
+/// Decide on a cap change from the average `loss`, with hysteresis.
+fn evaluate(loss: f32, threshold: f32, capped: bool) -> Option<LossAction> {
+    if !capped && loss >= threshold {
+        Some(LossAction::Lower)
+    } else if capped && loss < threshold / 2.0 {
+        Some(LossAction::Lift)
+    } else {
+        None
+    }
+}
+
+/// Check a participant's recent packet loss after a stats report and adjust
+/// their bitrate caps.
+pub async fn check_packet_loss(sfu: &SfuServer, pool: &PgPool, peer: &Peer, session_id: Uuid) {
+    let config = sfu.config();
+    let window = Duration::from_secs(config.voice_loss_window_secs);
+    let loss = match recent_packet_loss(pool, peer.user_id, session_id, window, MIN_SAMPLES).await {
+        Ok(Some(loss)) => loss,
+        Ok(None) => return,
+        Err(e) => {
+            warn!(user_id = %peer.user_id, error = %e, "Failed to read recent packet loss");
+            return;
+        }
+    };
+
+    let capped = peer.loss_audio_bitrate().await.is_some();
+    match evaluate(loss, config.voice_loss_warning_percent, capped) {
+        Some(LossAction::Lower) => {
+            let audio_bitrate = config.voice_loss_audio_bitrate;
+            if !peer.set_loss_audio_bitrate(Some(audio_bitrate)).await {
+                return;
+            }
+            info!(
+                user_id = %peer.user_id,
+                channel_id = %peer.channel_id,
+                packet_loss = loss,
+                "Sustained packet loss, lowering bitrate"
+            );
+            metrics::record_voice_quality_warning();
+            metrics::record_voice_bitrate_adjustment(true);
+            send(
+                peer,
+                ServerEvent::VoiceQualityWarning {
+                    channel_id: peer.channel_id,
+                    packet_loss: loss,
+                    audio_bitrate,
+                },
+            )
+            .await;
+            apply_caps(peer, Quality::Low.max_bitrate()).await;
+        }
+        Some(LossAction::Lift) => {
+            if !peer.set_loss_audio_bitrate(None).await {
+                return;
+            }
+            info!(
+                user_id = %peer.user_id,
+                channel_id = %peer.channel_id,
+                packet_loss = loss,
+                "Packet loss recovered, lifting bitrate cap"
+            );
+            metrics::record_voice_bitrate_adjustment(false);
+            send(
+                peer,
+                ServerEvent::VoiceQualityRecovered {
+                    channel_id: peer.channel_id,
+                },
+            )
+            .await;
+            apply_caps(peer, Quality::Premium.max_bitrate()).await;
+        }
+        None => {}
+    }
+}
+
+async fn send(peer: &Peer, event: ServerEvent) {
+    if let Err(e) = peer.signal_tx.send(event).await {
+        warn!(user_id = %peer.user_id, error = %e, "Failed to send voice quality event");
+    }
+}
+
+/// Renegotiate so the client picks up the Opus cap, and cap the peer's
+/// published video with a REMB.
+async fn apply_caps(peer: &Peer, video_bitrate: u32) {
+    if let Err(e) = SfuServer::renegotiate(peer).await {
+        warn!(user_id = %peer.user_id, error = %e, "Failed to renegotiate bitrate");
+    }
+
+    let ssrcs: Vec<u32> = peer
+        .incoming_tracks
+        .read()
+        .await
+        .iter()
+        .filter(|(source, _)| source.is_video())
+        .map(|(_, track)| track.ssrc())
+        .collect();
+    if ssrcs.is_empty() {
+        return;
+    }
+    let remb = ReceiverEstimatedMaximumBitrate {
+        sender_ssrc: 0,
+        bitrate: video_bitrate as f32,
+        ssrcs,
+    };
+    if let Err(e) = peer.peer_connection.write_rtcp(&[Box::new(remb)]).await {
+        warn!(user_id = %peer.user_id, error = %e, "Failed to send REMB");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_evaluate_hysteresis() {
+        assert_eq!(evaluate(6.0, 5.0, false), Some(LossAction::Lower));
+        assert_eq!(evaluate(5.0, 5.0, false), Some(LossAction::Lower));
+        assert_eq!(evaluate(4.9, 5.0, false), None);
+
+        // Capped participants keep the cap until loss falls below half the threshold
+        assert_eq!(evaluate(6.0, 5.0, true), None);
+        assert_eq!(evaluate(3.0, 5.0, true), None);
+        assert_eq!(evaluate(2.4, 5.0, true), Some(LossAction::Lift));
+    }
+}
@@ -24,8 +24,9 @@ pub async fn store_metrics(
     let result = sqlx::query(
         r"
         INSERT INTO connection_metrics
-        (time, user_id, session_id, channel_id, guild_id, latency_ms, packet_loss, jitter_ms, quality)
-        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+        (time, user_id, session_id, channel_id, guild_id, latency_ms, packet_loss, jitter_ms, quality,
+         concealment_events, jitter_buffer_ms, audio_level)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
         ",
     )
     .bind(Utc::now())
@@ -37,6 +38,9 @@ pub async fn store_metrics(
     .bind(stats.packet_loss)
     .bind(stats.jitter)
     .bind(i16::from(stats.quality))
+    .bind(stats.playout.concealment_events.map(i64::from))
+    .bind(stats.playout.jitter_buffer_delay)
+    .bind(stats.playout.audio_level)
     .execute(&pool)
     .await;
 
@@ -50,6 +54,32 @@ pub async fn store_metrics(
     }
 }
 
+/// Average packet loss a user reported for a session over the last `window`.
+///
+/// Returns `None` with fewer than `min_samples` reports in the window.
+pub async fn recent_packet_loss(
+    pool: &PgPool,
+    user_id: Uuid,
+    session_id: Uuid,
+    window: std::time::Duration,
+    min_samples: i64,
+) -> Result<Option<f32>, sqlx::Error> {
+    let (loss, samples): (Option<f32>, i64) = sqlx::query_as(
+        r"
+        SELECT AVG(packet_loss)::REAL, COUNT(*)
+        FROM connection_metrics
+        WHERE session_id = $1 AND user_id = $2 AND time >= NOW() - $3 * INTERVAL '1 second'
+        ",
+    )
+    .bind(session_id)
+    .bind(user_id)
+    .bind(window.as_secs_f64())
+    .fetch_one(pool)
+    .await?;
+
+    Ok(loss.filter(|_| samples >= min_samples))
+}
+
 /// Get `guild_id` from `channel_id`.
 ///
 /// Returns `None` if the channel doesn't exist or doesn't belong to a guild.
@@ -67,6 +97,7 @@ pub async fn get_guild_id(pool: &PgPool, channel_id: Uuid) -> Option<Uuid> {
 /// Creates a session record in `connection_sessions` with aggregated
 /// metrics from all connection metrics collected during the session.
 /// For very short calls with no metrics, NULL aggregates are stored.
+/// `noise_suppression` is the client's setting at join.
 pub async fn finalize_session(
     pool: &PgPool,
     user_id: Uuid,
@@ -74,6 +105,7 @@ pub async fn finalize_session(
     channel_id: Uuid,
     guild_id: Option<Uuid>,
     started_at: DateTime<Utc>,
+    noise_suppression: bool,
 ) -> Result<(), sqlx::Error> {
     // Check if any metrics exist for this session
     let has_metrics: bool =
@@ -88,13 +120,14 @@ pub async fn finalize_session(
             r"
             INSERT INTO connection_sessions
             (id, user_id, channel_id, guild_id, started_at, ended_at,
-             avg_latency, avg_loss, avg_jitter, worst_quality)
+             avg_latency, avg_loss, avg_jitter, worst_quality, noise_suppression)
             SELECT
                 $1, $2, $3, $4, $5, NOW(),
                 AVG(latency_ms)::SMALLINT,
                 AVG(packet_loss)::REAL,
                 AVG(jitter_ms)::SMALLINT,
-                MIN(quality)::SMALLINT
+                MIN(quality)::SMALLINT,
+                $6
             FROM connection_metrics
             WHERE session_id = $1
             ",
@@ -104,6 +137,7 @@ pub async fn finalize_session(
         .bind(channel_id)
         .bind(guild_id)
         .bind(started_at)
+        .bind(noise_suppression)
         .execute(pool)
         .await?;
     } else {
@@ -112,8 +146,8 @@ pub async fn finalize_session(
             r"
             INSERT INTO connection_sessions
             (id, user_id, channel_id, guild_id, started_at, ended_at,
-             avg_latency, avg_loss, avg_jitter, worst_quality)
-            VALUES ($1, $2, $3, $4, $5, NOW(), NULL, NULL, NULL, NULL)
+             avg_latency, avg_loss, avg_jitter, worst_quality, noise_suppression)
+            VALUES ($1, $2, $3, $4, $5, NOW(), NULL, NULL, NULL, NULL, $6)
             ",
         )
         .bind(session_id)
@@ -121,6 +155,7 @@ pub async fn finalize_session(
         .bind(channel_id)
         .bind(guild_id)
         .bind(started_at)
+        .bind(noise_suppression)
         .execute(pool)
         .await?;
     }
@@ -6,27 +6,42 @@
 //! This module provides:
 //! - SFU server for managing voice rooms and peer connections
 //! - Track routing for RTP packet forwarding
+//! - Server-side speaking detection from RTP audio levels
+//! - Bitrate caps for participants reporting sustained packet loss
 //! - HTTP endpoints for ICE server configuration
+//! - Live room snapshots for the admin diagnostics API
+//! - Media node listing and latency-based channel placement
+//! - gRPC control protocol for rooms hosted on remote SFU nodes
+//! - Server-side recording with participant consent
+//! - Batched join/leave posts to guild voice log channels
 //! - DM voice call signaling
 
+pub mod activity_log;
 pub mod call;
 pub mod call_handlers;
 pub mod call_service;
+pub mod control;
+pub mod diagnostics;
 pub mod error;
 pub(crate) mod handlers;
+mod loss;
 mod metrics;
 mod peer;
 mod quality;
 mod rate_limit;
+pub mod recording;
+pub mod regions;
+pub mod remote;
 pub mod screen_share;
 pub mod sfu;
+mod speaking;
 mod stats;
 mod track;
 mod track_types;
 pub mod webcam;
 pub mod ws_handler;
 
-use axum::routing::get;
+use axum::routing::{get, post};
 use axum::Router;
 // Re-exports
 pub use error::VoiceError;
@@ -35,7 +50,8 @@ pub use screen_share::{
     ScreenShareCheckResponse, ScreenShareError, ScreenShareInfo, ScreenShareStartRequest,
 };
 pub use sfu::{ParticipantInfo, Room, SfuServer};
-pub use stats::{UserStats, VoiceStats};
+pub use speaking::VoiceActivityMode;
+pub use stats::{PlayoutStats, UserStats, VoiceStats};
 pub use track_types::{TrackInfo, TrackKind, TrackSource};
 pub use webcam::WebcamInfo;
 
@@ -44,7 +60,19 @@ use crate::api::AppState;
 /// Create voice router.
 ///
 /// Note: Voice join/leave are handled via WebSocket events.
-/// This router only provides ICE server configuration.
+/// This router provides ICE server configuration, media node regions and
+/// recording control.
 pub fn router() -> Router<AppState> {
-    Router::new().route("/ice-servers", get(handlers::get_ice_servers))
+    Router::new()
+        .route("/ice-servers", get(handlers::get_ice_servers))
+        .route("/regions", get(regions::get_regions))
+        .route(
+            "/{channel_id}/recording/start",
+            post(recording::start_recording),
+        )
+        .route(
+            "/{channel_id}/recording/stop",
+            post(recording::stop_recording_handler),
+        )
+        .route("/{channel_id}/recordings", get(recording::list_recordings))
 }
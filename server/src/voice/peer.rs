@@ -3,7 +3,9 @@
 //! Wraps `RTCPeerConnection` for each participant in a voice channel.
 
 use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
+use std::time::Instant;
 
 use chrono::{DateTime, Utc};
 use tokio::sync::{mpsc, RwLock};
@@ -20,9 +22,76 @@ use webrtc::track::track_local::TrackLocal;
 use webrtc::track::track_remote::TrackRemote;
 
 use super::error::VoiceError;
+use super::speaking::VoiceActivityMode;
 use super::track_types::TrackSource;
 use crate::ws::ServerEvent;
 
+/// How long each bitrate measurement window lasts, in milliseconds.
+const BITRATE_WINDOW_MS: u64 = 1000;
+
+/// Lock-free meter of the RTP payload bitrate a peer publishes.
+///
+/// The forwarders of all the peer's tracks add to it; the bitrate of the
+/// last complete one-second window is kept.
+pub struct BitrateMeter {
+    started: Instant,
+    /// Start of the current window, in milliseconds since `started`.
+    window_start_ms: AtomicU64,
+    /// Bytes received in the current window.
+    bytes: AtomicU64,
+    /// Bitrate of the last complete window, in bits per second.
+    bps: AtomicU64,
+}
+
+impl Default for BitrateMeter {
+    fn default() -> Self {
+        Self {
+            started: Instant::now(),
+            window_start_ms: AtomicU64::new(0),
+            bytes: AtomicU64::new(0),
+            bps: AtomicU64::new(0),
+        }
+    }
+}
+
+impl BitrateMeter {
+    fn elapsed_ms(&self) -> u64 {
+        u64::try_from(self.started.elapsed().as_millis()).unwrap_or(u64::MAX)
+    }
+
+    /// Count `bytes` of received payload.
+    pub fn record(&self, bytes: usize) {
+        self.bytes.fetch_add(bytes as u64, Ordering::Relaxed);
+
+        let now = self.elapsed_ms();
+        let window_start = self.window_start_ms.load(Ordering::Relaxed);
+        let elapsed = now.saturating_sub(window_start);
+        if elapsed >= BITRATE_WINDOW_MS
+            && self
+                .window_start_ms
+                .compare_exchange(window_start, now, Ordering::Relaxed, Ordering::Relaxed)
+                .is_ok()
+        {
+            let bytes = self.bytes.swap(0, Ordering::Relaxed);
+            self.bps
+                .store(bytes * 8 * 1000 / elapsed, Ordering::Relaxed);
+        }
+    }
+
+    /// Bitrate of the last complete window in bits per second (0 when the
+    /// peer stopped sending).
+    pub fn bitrate(&self) -> u64 {
+        let idle = self
+            .elapsed_ms()
+            .saturating_sub(self.window_start_ms.load(Ordering::Relaxed));
+        if idle > 2 * BITRATE_WINDOW_MS {
+            0
+        } else {
+            self.bps.load(Ordering::Relaxed)
+        }
+    }
+}
+
 /// Represents a user's WebRTC connection to the SFU.
 pub struct Peer {
     /// User ID.
@@ -31,6 +100,8 @@ pub struct Peer {
     pub username: String,
     /// Display name.
     pub display_name: String,
+    /// How the user's name is pronounced, for screen-reader announcements.
+    pub name_pronunciation: RwLock<Option<String>>,
     /// Channel ID the peer is connected to.
     pub channel_id: Uuid,
     /// The WebRTC peer connection.
@@ -43,6 +114,23 @@ pub struct Peer {
     pub outgoing_tracks: RwLock<HashMap<(Uuid, TrackSource), Arc<TrackLocalStaticRTP>>>,
     /// Whether the user is muted.
     pub muted: RwLock<bool>,
+    /// Receive-only listener (joined without `VOICE_SPEAK`, or a stage audience member).
+    pub listener: RwLock<bool>,
+    /// Whether the listener has asked to speak.
+    pub hand_raised: RwLock<bool>,
+    /// How the user transmits audio (push-to-talk or voice activity).
+    pub activity_mode: RwLock<VoiceActivityMode>,
+    /// Opus bitrate cap announced in SDP offers (from guild perks).
+    pub max_audio_bitrate: RwLock<Option<u32>>,
+    /// Lower Opus bitrate cap while the client reports sustained packet loss.
+    pub loss_audio_bitrate: RwLock<Option<u32>>,
+    /// Client-measured RTT to each media node, in milliseconds.
+    /// Map: node ID -> RTT
+    pub node_rtts: RwLock<HashMap<String, u32>>,
+    /// Whether the client reported noise suppression at join.
+    pub noise_suppression: RwLock<bool>,
+    /// Bitrate of the media the peer publishes.
+    pub inbound_bitrate: Arc<BitrateMeter>,
     /// Channel to send signaling messages back to the user.
     pub signal_tx: mpsc::Sender<ServerEvent>,
     /// Unique session identifier for this connection.
@@ -72,11 +160,20 @@ impl Peer {
             user_id,
             username,
             display_name,
+            name_pronunciation: RwLock::new(None),
             channel_id,
             peer_connection: Arc::new(peer_connection),
             incoming_tracks: RwLock::new(HashMap::new()),
             outgoing_tracks: RwLock::new(HashMap::new()),
             muted: RwLock::new(false),
+            listener: RwLock::new(false),
+            hand_raised: RwLock::new(false),
+            activity_mode: RwLock::new(VoiceActivityMode::default()),
+            max_audio_bitrate: RwLock::new(None),
+            loss_audio_bitrate: RwLock::new(None),
+            node_rtts: RwLock::new(HashMap::new()),
+            noise_suppression: RwLock::new(false),
+            inbound_bitrate: Arc::new(BitrateMeter::default()),
             signal_tx,
             session_id: Uuid::now_v7(),
             connected_at: Utc::now(),
@@ -99,6 +196,13 @@ impl Peer {
         Ok(())
     }
 
+    /// Add the recvonly transceivers every speaking participant starts with:
+    /// audio for the mic and video to prepare m-lines for screen sharing.
+    pub async fn add_recv_transceivers(&self) -> Result<(), VoiceError> {
+        self.add_recv_transceiver(RTPCodecType::Audio).await?;
+        self.add_recv_transceiver(RTPCodecType::Video).await
+    }
+
     /// Set an incoming track from this peer.
     pub async fn set_incoming_track(&self, source: TrackSource, track: Arc<TrackRemote>) {
         let mut incoming = self.incoming_tracks.write().await;
@@ -194,6 +298,97 @@ impl Peer {
         *self.muted.read().await
     }
 
+    /// Set listener state.
+    pub async fn set_listener(&self, listener: bool) {
+        let mut l = self.listener.write().await;
+        *l = listener;
+    }
+
+    /// Whether the peer is a receive-only listener.
+    pub async fn is_listener(&self) -> bool {
+        *self.listener.read().await
+    }
+
+    /// Raise or lower the listener's hand, returning whether it changed.
+    pub async fn set_hand_raised(&self, raised: bool) -> bool {
+        let mut h = self.hand_raised.write().await;
+        std::mem::replace(&mut *h, raised) != raised
+    }
+
+    /// Whether the listener has asked to speak.
+    pub async fn is_hand_raised(&self) -> bool {
+        *self.hand_raised.read().await
+    }
+
+    /// Set activity mode.
+    pub async fn set_activity_mode(&self, mode: VoiceActivityMode) {
+        let mut m = self.activity_mode.write().await;
+        *m = mode;
+    }
+
+    /// Get activity mode.
+    pub async fn activity_mode(&self) -> VoiceActivityMode {
+        *self.activity_mode.read().await
+    }
+
+    /// Set how the user's name is pronounced.
+    pub async fn set_name_pronunciation(&self, pronunciation: Option<String>) {
+        *self.name_pronunciation.write().await = pronunciation;
+    }
+
+    /// Set the Opus bitrate cap. Returns whether it changed.
+    pub async fn set_max_audio_bitrate(&self, bitrate: u32) -> bool {
+        let mut b = self.max_audio_bitrate.write().await;
+        let changed = *b != Some(bitrate);
+        *b = Some(bitrate);
+        changed
+    }
+
+    /// Record the client's RTT samples to media nodes.
+    pub async fn set_node_rtts(&self, rtts: HashMap<String, u32>) {
+        *self.node_rtts.write().await = rtts;
+    }
+
+    /// Record whether the client noise-suppresses its microphone.
+    pub async fn set_noise_suppression(&self, enabled: bool) {
+        *self.noise_suppression.write().await = enabled;
+    }
+
+    /// Whether the client reported noise suppression at join.
+    pub async fn noise_suppression(&self) -> bool {
+        *self.noise_suppression.read().await
+    }
+
+    /// Get the Opus bitrate cap.
+    pub async fn max_audio_bitrate(&self) -> Option<u32> {
+        *self.max_audio_bitrate.read().await
+    }
+
+    /// Set or lift the packet-loss bitrate cap. Returns whether it changed.
+    pub async fn set_loss_audio_bitrate(&self, bitrate: Option<u32>) -> bool {
+        let mut b = self.loss_audio_bitrate.write().await;
+        let changed = *b != bitrate;
+        *b = bitrate;
+        changed
+    }
+
+    /// Get the packet-loss bitrate cap.
+    pub async fn loss_audio_bitrate(&self) -> Option<u32> {
+        *self.loss_audio_bitrate.read().await
+    }
+
+    /// Opus bitrate cap announced in offers: the lower of the perk and
+    /// packet-loss caps.
+    pub async fn effective_audio_bitrate(&self) -> Option<u32> {
+        match (
+            self.max_audio_bitrate().await,
+            self.loss_audio_bitrate().await,
+        ) {
+            (Some(max), Some(loss)) => Some(max.min(loss)),
+            (max, loss) => max.or(loss),
+        }
+    }
+
     /// Close the peer connection.
     pub async fn close(&self) -> Result<(), VoiceError> {
         self.peer_connection.close().await?;
@@ -0,0 +1,709 @@
+//! Server-Side Voice Recording
+//!
+//! Records consenting participants of a guild voice room.
+//!
+//! The SFU never decodes audio, so recordings are multitrack: each consenting
+//! participant's Opus stream is muxed into its own Ogg file, and the tracks are
+//! bundled into a single ZIP archive together with a `manifest.json` holding the
+//! per-track start offsets. Players mix the tracks on playback.
+//!
+//! Flow:
+//! 1. `POST /api/voice/{channel_id}/recording/start` (requires `RECORD_VOICE`)
+//! 2. Every participant receives `voice_recording_started` and answers with a
+//!    `voice_recording_consent` event. Only consenting participants are captured.
+//! 3. `POST /api/voice/{channel_id}/recording/stop` (or the room emptying) closes
+//!    all tracks, uploads the archive to S3, and completes the `voice_recordings` row.
+
+use std::collections::HashMap;
+use std::io::{BufWriter, Write};
+use std::sync::Arc;
+
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::Json;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sqlx::PgPool;
+use tokio::sync::{mpsc, Mutex};
+use tokio::task::JoinHandle;
+use tracing::{error, info, warn};
+use uuid::Uuid;
+use webrtc::media::io::ogg_writer::OggWriter;
+use webrtc::media::io::Writer;
+use webrtc::rtp::packet::Packet as RtpPacket;
+use zip::write::SimpleFileOptions;
+use zip::ZipWriter;
+
+use super::error::VoiceError;
+use super::sfu::Room;
+use crate::api::AppState;
+use crate::auth::AuthUser;
+use crate::chat::S3Client;
+use crate::permissions::GuildPermissions;
+use crate::ws::ServerEvent;
+
+/// Buffered RTP packets per participant before the tap starts dropping.
+///
+/// ~5 seconds of 20ms Opus frames, enough to absorb disk stalls.
+const RECORDING_TAP_CAPACITY: usize = 256;
+
+/// Opus clock rate used by the SFU media engine.
+const OPUS_SAMPLE_RATE: u32 = 48000;
+
+/// Opus channel count registered in the SFU media engine.
+const OPUS_CHANNELS: u8 = 2;
+
+/// A recording as stored in the database.
+#[derive(Debug, Clone, Serialize, sqlx::FromRow, utoipa::ToSchema)]
+pub struct VoiceRecording {
+    /// Recording ID.
+    pub id: Uuid,
+    /// Voice channel that was recorded.
+    pub channel_id: Uuid,
+    /// Guild owning the channel.
+    pub guild_id: Option<Uuid>,
+    /// User who started the recording.
+    pub started_by: Option<Uuid>,
+    /// `recording`, `processing`, `completed`, or `failed`.
+    pub status: String,
+    /// Archive object key (internal).
+    #[serde(skip)]
+    pub s3_key: Option<String>,
+    /// Archive size in bytes (once completed).
+    pub size_bytes: Option<i64>,
+    /// Recording duration in milliseconds (once completed).
+    pub duration_ms: Option<i64>,
+    /// Number of participant tracks in the archive.
+    pub track_count: i32,
+    /// When the recording started.
+    pub started_at: DateTime<Utc>,
+    /// When the recording stopped.
+    pub ended_at: Option<DateTime<Utc>>,
+}
+
+/// A completed recording with a short-lived download URL.
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct VoiceRecordingWithUrl {
+    #[serde(flatten)]
+    pub recording: VoiceRecording,
+    /// Presigned download URL (only for completed recordings).
+    pub download_url: Option<String>,
+}
+
+/// One participant's Ogg/Opus track being written.
+struct TrackRecorder {
+    user_id: Uuid,
+    /// Offset from the recording start.
+    offset_ms: i64,
+    /// Backing file (deleted when dropped).
+    file: tempfile::NamedTempFile,
+    /// Writer task, returns the number of packets written.
+    handle: JoinHandle<Result<u64, VoiceError>>,
+}
+
+/// A closed participant track ready for packaging.
+#[derive(Debug, Serialize)]
+struct TrackManifestEntry {
+    user_id: Uuid,
+    file: String,
+    offset_ms: i64,
+    packets: u64,
+}
+
+/// Archive manifest written next to the tracks.
+#[derive(Debug, Serialize)]
+struct RecordingManifest {
+    version: &'static str,
+    recording_id: Uuid,
+    channel_id: Uuid,
+    started_at: DateTime<Utc>,
+    ended_at: DateTime<Utc>,
+    codec: &'static str,
+    tracks: Vec<TrackManifestEntry>,
+}
+
+/// An active recording attached to a voice room.
+pub struct RecordingSession {
+    /// Recording ID (`voice_recordings.id`).
+    pub id: Uuid,
+    /// Recorded voice channel.
+    pub channel_id: Uuid,
+    /// User who started the recording.
+    pub started_by: Uuid,
+    /// When the recording started.
+    pub started_at: DateTime<Utc>,
+    /// Tracks currently being captured, keyed by participant.
+    active: Mutex<HashMap<Uuid, TrackRecorder>>,
+    /// Tracks closed mid-recording (consent withdrawn or participant left).
+    closed: Mutex<Vec<TrackRecorder>>,
+    pool: PgPool,
+    s3: S3Client,
+}
+
+impl RecordingSession {
+    /// Create a new recording session.
+    #[must_use]
+    pub fn new(id: Uuid, channel_id: Uuid, started_by: Uuid, pool: PgPool, s3: S3Client) -> Self {
+        Self {
+            id,
+            channel_id,
+            started_by,
+            started_at: Utc::now(),
+            active: Mutex::new(HashMap::new()),
+            closed: Mutex::new(Vec::new()),
+            pool,
+            s3,
+        }
+    }
+
+    /// Whether a participant is currently being captured.
+    pub async fn is_capturing(&self, user_id: Uuid) -> bool {
+        self.active.lock().await.contains_key(&user_id)
+    }
+}
+
+/// Spawn a blocking writer that muxes tapped Opus packets into an Ogg file.
+fn spawn_track_writer(
+    mut rx: mpsc::Receiver<RtpPacket>,
+    file: std::fs::File,
+) -> JoinHandle<Result<u64, VoiceError>> {
+    tokio::task::spawn_blocking(move || {
+        let mut writer = OggWriter::new(BufWriter::new(file), OPUS_SAMPLE_RATE, OPUS_CHANNELS)
+            .map_err(|e| VoiceError::Internal(format!("Failed to create Ogg writer: {e}")))?;
+        let mut packets = 0u64;
+
+        while let Some(packet) = rx.blocking_recv() {
+            match writer.write_rtp(&packet) {
+                Ok(()) => packets += 1,
+                Err(e) => warn!(error = %e, "Dropping unwritable RTP packet from recording"),
+            }
+        }
+
+        writer
+            .close()
+            .map_err(|e| VoiceError::Internal(format!("Failed to close Ogg writer: {e}")))?;
+        Ok(packets)
+    })
+}
+
+/// Start capturing a participant who consented.
+async fn start_track(
+    room: &Room,
+    session: &RecordingSession,
+    user_id: Uuid,
+) -> Result<(), VoiceError> {
+    let mut active = session.active.lock().await;
+    if active.contains_key(&user_id) {
+        return Ok(());
+    }
+
+    let file = tempfile::NamedTempFile::new()
+        .map_err(|e| VoiceError::Internal(format!("Failed to create track file: {e}")))?;
+    let handle_file = file
+        .reopen()
+        .map_err(|e| VoiceError::Internal(format!("Failed to open track file: {e}")))?;
+
+    let (tx, rx) = mpsc::channel(RECORDING_TAP_CAPACITY);
+    let handle = spawn_track_writer(rx, handle_file);
+    room.track_router.add_recording_tap(user_id, tx);
+
+    active.insert(
+        user_id,
+        TrackRecorder {
+            user_id,
+            offset_ms: (Utc::now() - session.started_at).num_milliseconds().max(0),
+            file,
+            handle,
+        },
+    );
+
+    Ok(())
+}
+
+/// Stop capturing a participant, keeping what was recorded so far.
+async fn stop_track(room: &Room, session: &RecordingSession, user_id: Uuid) {
+    // Removing the tap drops the sender, which ends the writer loop
+    room.track_router.remove_recording_tap(user_id);
+
+    let recorder = session.active.lock().await.remove(&user_id);
+    if let Some(recorder) = recorder {
+        session.closed.lock().await.push(recorder);
+    }
+}
+
+/// Record a participant's consent decision and start or stop their track.
+pub async fn set_consent(
+    room: &Room,
+    user_id: Uuid,
+    recording_id: Uuid,
+    consent: bool,
+) -> Result<(), VoiceError> {
+    let session = room
+        .recording
+        .read()
+        .await
+        .clone()
+        .filter(|s| s.id == recording_id)
+        .ok_or(VoiceError::NoActiveRecording)?;
+
+    if room.get_peer(user_id).await.is_none() {
+        return Err(VoiceError::NotInChannel);
+    }
+
+    sqlx::query(
+        r"INSERT INTO voice_recording_consents (recording_id, user_id, consented)
+           VALUES ($1, $2, $3)
+           ON CONFLICT (recording_id, user_id)
+           DO UPDATE SET consented = EXCLUDED.consented, responded_at = NOW()",
+    )
+    .bind(recording_id)
+    .bind(user_id)
+    .bind(consent)
+    .execute(&session.pool)
+    .await
+    .map_err(|e| VoiceError::Internal(format!("Failed to store consent: {e}")))?;
+
+    if consent {
+        start_track(room, &session, user_id).await?;
+    } else {
+        stop_track(room, &session, user_id).await;
+    }
+
+    room.broadcast_all(ServerEvent::VoiceRecordingConsentUpdated {
+        channel_id: room.channel_id,
+        recording_id,
+        user_id,
+        consented: consent,
+    })
+    .await;
+
+    Ok(())
+}
+
+/// Stop capturing a participant who left the room.
+pub async fn remove_participant(room: &Room, user_id: Uuid) {
+    let session = room.recording.read().await.clone();
+    if let Some(session) = session {
+        stop_track(room, &session, user_id).await;
+    }
+}
+
+/// Detach the active recording from the room and finalize it in the background.
+///
+/// Returns the recording ID if a recording was active.
+pub async fn stop_recording(room: &Room, reason: &str) -> Option<Uuid> {
+    let session = room.recording.write().await.take()?;
+    room.track_router.clear_recording_taps();
+
+    room.broadcast_all(ServerEvent::VoiceRecordingStopped {
+        channel_id: room.channel_id,
+        recording_id: session.id,
+        reason: reason.to_string(),
+    })
+    .await;
+
+    let recording_id = session.id;
+    tokio::spawn(async move {
+        if let Err(e) = finalize(&session).await {
+            error!(recording_id = %session.id, error = %e, "Failed to finalize voice recording");
+            let _ = sqlx::query(
+                "UPDATE voice_recordings SET status = 'failed', ended_at = NOW() WHERE id = $1",
+            )
+            .bind(session.id)
+            .execute(&session.pool)
+            .await;
+        }
+    });
+
+    Some(recording_id)
+}
+
+/// Close all tracks, package them, upload the archive, and complete the DB row.
+async fn finalize(session: &RecordingSession) -> Result<(), VoiceError> {
+    let ended_at = Utc::now();
+
+    sqlx::query("UPDATE voice_recordings SET status = 'processing', ended_at = $2 WHERE id = $1")
+        .bind(session.id)
+        .bind(ended_at)
+        .execute(&session.pool)
+        .await
+        .map_err(|e| VoiceError::Internal(e.to_string()))?;
+
+    let mut recorders: Vec<TrackRecorder> = std::mem::take(&mut *session.closed.lock().await);
+    recorders.extend(session.active.lock().await.drain().map(|(_, r)| r));
+
+    // Wait for every writer to flush its Ogg file
+    let mut tracks = Vec::with_capacity(recorders.len());
+    let mut per_user: HashMap<Uuid, usize> = HashMap::new();
+    for recorder in recorders {
+        let packets = match recorder.handle.await {
+            Ok(Ok(packets)) => packets,
+            Ok(Err(e)) => {
+                warn!(user_id = %recorder.user_id, error = %e, "Skipping failed recording track");
+                continue;
+            }
+            Err(e) => {
+                warn!(user_id = %recorder.user_id, error = %e, "Recording track writer panicked");
+                continue;
+            }
+        };
+        if packets == 0 {
+            continue;
+        }
+
+        let segment = per_user.entry(recorder.user_id).or_insert(0);
+        *segment += 1;
+        let name = if *segment == 1 {
+            format!("{}.ogg", recorder.user_id)
+        } else {
+            format!("{}-{}.ogg", recorder.user_id, segment)
+        };
+
+        tracks.push((
+            TrackManifestEntry {
+                user_id: recorder.user_id,
+                file: name,
+                offset_ms: recorder.offset_ms,
+                packets,
+            },
+            recorder.file,
+        ));
+    }
+
+    let manifest = RecordingManifest {
+        version: "1.0",
+        recording_id: session.id,
+        channel_id: session.channel_id,
+        started_at: session.started_at,
+        ended_at,
+        codec: "opus/ogg",
+        tracks: Vec::new(),
+    };
+    let track_count = tracks.len() as i32;
+
+    let archive = tokio::task::spawn_blocking(move || build_archive(manifest, tracks))
+        .await
+        .map_err(|e| VoiceError::Internal(format!("Archive task failed: {e}")))??;
+
+    let key = format!("recordings/{}/{}.zip", session.channel_id, session.id);
+    let size = session
+        .s3
+        .upload_from_path(&key, archive.path(), "application/zip")
+        .await
+        .map_err(|e| VoiceError::Internal(e.to_string()))?;
+
+    let duration_ms = (ended_at - session.started_at).num_milliseconds().max(0);
+    sqlx::query(
+        r"UPDATE voice_recordings
+           SET status = 'completed', s3_key = $2, size_bytes = $3, duration_ms = $4, track_count = $5
+           WHERE id = $1",
+    )
+    .bind(session.id)
+    .bind(&key)
+    .bind(size as i64)
+    .bind(duration_ms)
+    .bind(track_count)
+    .execute(&session.pool)
+    .await
+    .map_err(|e| VoiceError::Internal(e.to_string()))?;
+
+    info!(
+        recording_id = %session.id,
+        channel_id = %session.channel_id,
+        tracks = track_count,
+        size_bytes = size,
+        "Voice recording completed"
+    );
+
+    Ok(())
+}
+
+/// Bundle the Ogg tracks and manifest into a ZIP archive (blocking I/O).
+fn build_archive(
+    mut manifest: RecordingManifest,
+    tracks: Vec<(TrackManifestEntry, tempfile::NamedTempFile)>,
+) -> Result<tempfile::NamedTempFile, VoiceError> {
+    let io_err = |e: std::io::Error| VoiceError::Internal(format!("Archive I/O error: {e}"));
+    let zip_err = |e: zip::result::ZipError| VoiceError::Internal(format!("Archive error: {e}"));
+
+    let archive = tempfile::NamedTempFile::new().map_err(io_err)?;
+    let mut zip = ZipWriter::new(BufWriter::new(archive.reopen().map_err(io_err)?));
+    // Opus is already compressed; storing avoids burning CPU for nothing
+    let options = SimpleFileOptions::default().compression_method(zip::CompressionMethod::Stored);
+
+    for (entry, file) in tracks {
+        zip.start_file(entry.file.as_str(), options)
+            .map_err(zip_err)?;
+        let mut reader = file.reopen().map_err(io_err)?;
+        std::io::copy(&mut reader, &mut zip).map_err(io_err)?;
+        manifest.tracks.push(entry);
+    }
+
+    zip.start_file("manifest.json", options).map_err(zip_err)?;
+    serde_json::to_writer_pretty(&mut zip, &manifest)
+        .map_err(|e| VoiceError::Internal(e.to_string()))?;
+
+    let mut inner = zip.finish().map_err(zip_err)?;
+    inner.flush().map_err(io_err)?;
+
+    Ok(archive)
+}
+
+// ============================================================================
+// HTTP Handlers
+// ============================================================================
+
+/// Resolve the guild of a voice channel and require `RECORD_VOICE`.
+async fn require_record_permission(
+    pool: &PgPool,
+    user_id: Uuid,
+    channel_id: Uuid,
+) -> Result<Uuid, VoiceError> {
+    let guild_id = super::metrics::get_guild_id(pool, channel_id)
+        .await
+        .ok_or(VoiceError::ChannelNotFound(channel_id))?;
+
+    let ctx = crate::permissions::require_channel_access(pool, user_id, channel_id)
+        .await
+        .map_err(|_e: crate::permissions::PermissionError| VoiceError::Unauthorized)?;
+
+    if !ctx.has_permission(GuildPermissions::RECORD_VOICE) {
+        return Err(VoiceError::Unauthorized);
+    }
+
+    Ok(guild_id)
+}
+
+/// Start recording a voice channel.
+///
+/// POST `/api/voice/{channel_id}/recording/start`
+///
+/// Participants are asked for consent via `voice_recording_started`; only
+/// those who answer with `voice_recording_consent { consent: true }` are recorded.
+#[utoipa::path(
+    post,
+    path = "/api/voice/{channel_id}/recording/start",
+    tag = "voice",
+    params(("channel_id" = Uuid, Path, description = "Voice channel ID")),
+    responses(
+        (status = 201, description = "Recording started", body = VoiceRecording),
+        (status = 403, description = "Missing RECORD_VOICE permission"),
+        (status = 404, description = "No active voice room"),
+        (status = 409, description = "Recording already in progress"),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[tracing::instrument(skip(state))]
+pub async fn start_recording(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(channel_id): Path<Uuid>,
+) -> Result<(StatusCode, Json<VoiceRecording>), VoiceError> {
+    let s3 = state.s3.clone().ok_or(VoiceError::RecordingUnavailable)?;
+    let guild_id = require_record_permission(&state.db, auth.id, channel_id).await?;
+
+    let room = state
+        .sfu
+        .get_room(channel_id)
+        .await
+        .ok_or(VoiceError::RoomNotFound(channel_id))?;
+
+    let mut slot = room.recording.write().await;
+    if slot.is_some() {
+        return Err(VoiceError::RecordingInProgress);
+    }
+
+    let recording: VoiceRecording = sqlx::query_as(
+        r"INSERT INTO voice_recordings (channel_id, guild_id, started_by)
+           VALUES ($1, $2, $3)
+           RETURNING id, channel_id, guild_id, started_by, status, s3_key, size_bytes, duration_ms,
+                     track_count, started_at, ended_at",
+    )
+    .bind(channel_id)
+    .bind(guild_id)
+    .bind(auth.id)
+    .fetch_one(&state.db)
+    .await
+    .map_err(|e| match e {
+        sqlx::Error::Database(ref db) if db.is_unique_violation() => {
+            VoiceError::RecordingInProgress
+        }
+        other => VoiceError::Internal(other.to_string()),
+    })?;
+
+    let session = RecordingSession {
+        started_at: recording.started_at,
+        ..RecordingSession::new(recording.id, channel_id, auth.id, state.db.clone(), s3)
+    };
+    *slot = Some(Arc::new(session));
+    drop(slot);
+
+    room.broadcast_all(ServerEvent::VoiceRecordingStarted {
+        channel_id,
+        recording_id: recording.id,
+        started_by: auth.id,
+    })
+    .await;
+
+    info!(channel_id = %channel_id, recording_id = %recording.id, "Voice recording started");
+
+    Ok((StatusCode::CREATED, Json(recording)))
+}
+
+/// Stop the active recording of a voice channel.
+///
+/// POST `/api/voice/{channel_id}/recording/stop`
+///
+/// The archive is packaged and uploaded in the background; the returned
+/// recording is in the `processing` state.
+#[utoipa::path(
+    post,
+    path = "/api/voice/{channel_id}/recording/stop",
+    tag = "voice",
+    params(("channel_id" = Uuid, Path, description = "Voice channel ID")),
+    responses(
+        (status = 200, description = "Recording stopped", body = VoiceRecording),
+        (status = 403, description = "Missing RECORD_VOICE permission"),
+        (status = 404, description = "No active recording"),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[tracing::instrument(skip(state))]
+pub async fn stop_recording_handler(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(channel_id): Path<Uuid>,
+) -> Result<Json<VoiceRecording>, VoiceError> {
+    require_record_permission(&state.db, auth.id, channel_id).await?;
+
+    let room = state
+        .sfu
+        .get_room(channel_id)
+        .await
+        .ok_or(VoiceError::NoActiveRecording)?;
+
+    let recording_id = stop_recording(&room, "stopped")
+        .await
+        .ok_or(VoiceError::NoActiveRecording)?;
+
+    let mut recording = fetch_recording(&state.db, recording_id).await?;
+    // Finalization runs in the background; report it as in progress
+    if recording.status == "recording" {
+        recording.status = "processing".to_string();
+    }
+
+    Ok(Json(recording))
+}
+
+/// List recordings of a voice channel.
+///
+/// GET `/api/voice/{channel_id}/recordings`
+#[utoipa::path(
+    get,
+    path = "/api/voice/{channel_id}/recordings",
+    tag = "voice",
+    params(("channel_id" = Uuid, Path, description = "Voice channel ID")),
+    responses(
+        (status = 200, description = "Recordings", body = Vec<VoiceRecordingWithUrl>),
+        (status = 403, description = "Missing RECORD_VOICE permission"),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[tracing::instrument(skip(state))]
+pub async fn list_recordings(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(channel_id): Path<Uuid>,
+) -> Result<Json<Vec<VoiceRecordingWithUrl>>, VoiceError> {
+    require_record_permission(&state.db, auth.id, channel_id).await?;
+
+    let recordings: Vec<VoiceRecording> = sqlx::query_as(
+        r"SELECT id, channel_id, guild_id, started_by, status, s3_key, size_bytes, duration_ms,
+                 track_count, started_at, ended_at
+           FROM voice_recordings
+           WHERE channel_id = $1
+           ORDER BY started_at DESC
+           LIMIT 50",
+    )
+    .bind(channel_id)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|e| VoiceError::Internal(e.to_string()))?;
+
+    let mut result = Vec::with_capacity(recordings.len());
+    for recording in recordings {
+        let download_url = match (&state.s3, &recording.s3_key) {
+            (Some(s3), Some(key)) if recording.status == "completed" => {
+                s3.presign_get(key).await.ok()
+            }
+            _ => None,
+        };
+        result.push(VoiceRecordingWithUrl {
+            recording,
+            download_url,
+        });
+    }
+
+    Ok(Json(result))
+}
+
+async fn fetch_recording(pool: &PgPool, id: Uuid) -> Result<VoiceRecording, VoiceError> {
+    sqlx::query_as(
+        r"SELECT id, channel_id, guild_id, started_by, status, s3_key, size_bytes, duration_ms,
+                 track_count, started_at, ended_at
+           FROM voice_recordings WHERE id = $1",
+    )
+    .bind(id)
+    .fetch_one(pool)
+    .await
+    .map_err(|e| VoiceError::Internal(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Read;
+
+    use super::*;
+
+    #[test]
+    fn test_build_archive_contains_tracks_and_manifest() {
+        let user_id = Uuid::new_v4();
+        let mut track = tempfile::NamedTempFile::new().unwrap();
+        track.write_all(b"OggS-fake").unwrap();
+
+        let manifest = RecordingManifest {
+            version: "1.0",
+            recording_id: Uuid::new_v4(),
+            channel_id: Uuid::new_v4(),
+            started_at: Utc::now(),
+            ended_at: Utc::now(),
+            codec: "opus/ogg",
+            tracks: Vec::new(),
+        };
+        let entry = TrackManifestEntry {
+            user_id,
+            file: format!("{user_id}.ogg"),
+            offset_ms: 1500,
+            packets: 1,
+        };
+
+        let archive = build_archive(manifest, vec![(entry, track)]).unwrap();
+        let mut zip = zip::ZipArchive::new(archive.reopen().unwrap()).unwrap();
+
+        let mut contents = String::new();
+        zip.by_name(&format!("{user_id}.ogg"))
+            .unwrap()
+            .read_to_string(&mut contents)
+            .unwrap();
+        assert_eq!(contents, "OggS-fake");
+
+        let mut manifest_json = String::new();
+        zip.by_name("manifest.json")
+            .unwrap()
+            .read_to_string(&mut manifest_json)
+            .unwrap();
+        let manifest: serde_json::Value = serde_json::from_str(&manifest_json).unwrap();
+        assert_eq!(manifest["tracks"][0]["offset_ms"], 1500);
+        assert_eq!(manifest["codec"], "opus/ogg");
+    }
+}
@@ -0,0 +1,177 @@
+//! Media Node Regions
+//!
+//! Lists the SFU media nodes of a multi-node deployment and places voice
+//! channels on the node with the lowest round-trip time for its participants.
+//!
+//! Clients fetch `GET /api/voice/regions`, time a request to each node's
+//! probe URL and report the results in `VoiceJoin`. The server keeps the
+//! samples on the joining peer and re-evaluates the channel's node on every
+//! join.
+
+use std::collections::HashMap;
+
+use axum::extract::State;
+use axum::Json;
+use serde::Serialize;
+
+use crate::api::AppState;
+
+/// Highest RTT sample (in milliseconds) accepted from a client.
+///
+/// Anything above this is treated as a failed probe and dropped.
+pub const MAX_REPORTED_RTT_MS: u32 = 10_000;
+
+/// A media node clients can be placed on.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, utoipa::ToSchema)]
+pub struct MediaNode {
+    /// Stable node identifier, reported back by clients with their RTT.
+    pub id: String,
+    /// Human-readable region label (e.g., "eu-central").
+    pub region: String,
+    /// URL clients request to measure round-trip time to this node.
+    pub probe_url: String,
+}
+
+/// Parse media nodes from "`id|region|probe_url`" entries separated by commas.
+///
+/// Malformed entries and duplicate IDs are skipped.
+pub fn parse_media_nodes(value: &str) -> Vec<MediaNode> {
+    let mut nodes: Vec<MediaNode> = Vec::new();
+    for entry in value.split(',') {
+        let mut parts = entry.trim().splitn(3, '|').map(str::trim);
+        let (Some(id), Some(region), Some(probe_url)) = (parts.next(), parts.next(), parts.next())
+        else {
+            continue;
+        };
+        if id.is_empty() || probe_url.is_empty() || nodes.iter().any(|n| n.id == id) {
+            continue;
+        }
+        nodes.push(MediaNode {
+            id: id.to_string(),
+            region: region.to_string(),
+            probe_url: probe_url.to_string(),
+        });
+    }
+    nodes
+}
+
+/// Keep only samples for known nodes with a plausible RTT.
+#[allow(clippy::implicit_hasher)]
+pub fn sanitize_rtts(nodes: &[MediaNode], rtts: HashMap<String, u32>) -> HashMap<String, u32> {
+    rtts.into_iter()
+        .filter(|(id, rtt)| *rtt <= MAX_REPORTED_RTT_MS && nodes.iter().any(|n| &n.id == id))
+        .collect()
+}
+
+/// Pick the node with the lowest mean RTT across participants.
+///
+/// Each participant's samples are a map of node ID to RTT in milliseconds.
+/// Nodes nobody has measured are only chosen when no node has samples, in
+/// which case the first configured node wins. Ties keep configuration order.
+#[allow(clippy::implicit_hasher)]
+pub fn select_node<'a>(
+    nodes: &'a [MediaNode],
+    samples: &[HashMap<String, u32>],
+) -> Option<&'a MediaNode> {
+    let mut best: Option<(&MediaNode, f64)> = None;
+    for node in nodes {
+        let rtts: Vec<u32> = samples
+            .iter()
+            .filter_map(|s| s.get(&node.id).copied())
+            .collect();
+        if rtts.is_empty() {
+            continue;
+        }
+        let mean = f64::from(rtts.iter().sum::<u32>()) / rtts.len() as f64;
+        if best.is_none_or(|(_, best_mean)| mean < best_mean) {
+            best = Some((node, mean));
+        }
+    }
+    best.map(|(node, _)| node).or_else(|| nodes.first())
+}
+
+/// Response listing the available media nodes.
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct VoiceRegionsResponse {
+    /// Configured media nodes. Empty on single-node deployments.
+    pub nodes: Vec<MediaNode>,
+}
+
+/// List media nodes and their probe URLs.
+///
+/// GET /api/voice/regions
+///
+/// Clients measure RTT to each probe URL and send the results with
+/// `VoiceJoin` so the channel can be placed on the closest node.
+#[utoipa::path(
+    get,
+    path = "/api/voice/regions",
+    tag = "voice",
+    responses(
+        (status = 200, description = "Available media nodes", body = VoiceRegionsResponse),
+    ),
+    security(("bearer_auth" = [])),
+)]
+pub async fn get_regions(State(state): State<AppState>) -> Json<VoiceRegionsResponse> {
+    Json(VoiceRegionsResponse {
+        nodes: state.config().voice_media_nodes.clone(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn nodes() -> Vec<MediaNode> {
+        parse_media_nodes(
+            "eu1|eu-central|https://eu1.example.com/health,\
+             us1|us-east|https://us1.example.com/health",
+        )
+    }
+
+    fn sample(pairs: &[(&str, u32)]) -> HashMap<String, u32> {
+        pairs
+            .iter()
+            .map(|(id, rtt)| ((*id).to_string(), *rtt))
+            .collect()
+    }
+
+    #[test]
+    fn parse_skips_malformed_and_duplicate_entries() {
+        let nodes = parse_media_nodes("a|eu|https://a, broken, |us|https://b, a|us|https://c,");
+        assert_eq!(nodes.len(), 1);
+        assert_eq!(nodes[0].id, "a");
+        assert_eq!(nodes[0].region, "eu");
+        assert_eq!(nodes[0].probe_url, "https://a");
+    }
+
+    #[test]
+    fn sanitize_drops_unknown_nodes_and_outliers() {
+        let rtts = sanitize_rtts(
+            &nodes(),
+            sample(&[("eu1", 20), ("us1", MAX_REPORTED_RTT_MS + 1), ("ap1", 5)]),
+        );
+        assert_eq!(rtts, sample(&[("eu1", 20)]));
+    }
+
+    #[test]
+    fn select_prefers_lowest_mean_rtt() {
+        let nodes = nodes();
+        let samples = vec![
+            sample(&[("eu1", 20), ("us1", 110)]),
+            sample(&[("eu1", 150), ("us1", 30)]),
+            sample(&[("eu1", 25), ("us1", 100)]),
+        ];
+        assert_eq!(select_node(&nodes, &samples).unwrap().id, "eu1");
+
+        let samples = vec![sample(&[("eu1", 200), ("us1", 40)]), sample(&[("us1", 60)])];
+        assert_eq!(select_node(&nodes, &samples).unwrap().id, "us1");
+    }
+
+    #[test]
+    fn select_falls_back_to_first_node_without_samples() {
+        let nodes = nodes();
+        assert_eq!(select_node(&nodes, &[]).unwrap().id, "eu1");
+        assert!(select_node(&[], &[sample(&[("eu1", 10)])]).is_none());
+    }
+}
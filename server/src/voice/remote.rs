@@ -0,0 +1,398 @@
+//! Remote SFU Nodes
+//!
+//! Media nodes running the `sfu-node` binary host voice rooms on behalf of
+//! the server replicas, driven through the control protocol
+//! ([`super::control`]). A replica configured with `SFU_REMOTE_NODES` polls
+//! each node's status every [`HEARTBEAT_INTERVAL`] and writes the node's
+//! entry in the cluster registry, so remote nodes take part in region
+//! placement, room claims and failover like any replica.
+//!
+//! Participants keep their WebSocket on the replica: their signaling is
+//! forwarded to the node hosting the room, and the node's events for them
+//! come back over the node's `Events` stream.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+use dashmap::mapref::entry::Entry;
+use dashmap::DashMap;
+use fred::clients::Client;
+use tokio::sync::mpsc;
+use tokio::task::JoinSet;
+use tonic::codec::Streaming;
+use tracing::{debug, info, warn};
+use uuid::Uuid;
+
+use super::control::{
+    self, AnswerRequest, ControlClient, IceCandidateRequest, JoinRequest, LeaveRequest, NodeStatus,
+    PeerEvent, SetMutedRequest,
+};
+use super::error::VoiceError;
+use super::ws_handler::{HostJoin, HostLeave, LeftSession};
+use crate::cluster::registry::{self, NodeInfo, HEARTBEAT_INTERVAL};
+use crate::config::Config;
+use crate::ws::ServerEvent;
+
+/// Remote media nodes known to this replica, keyed by node ID.
+#[derive(Default)]
+pub struct RemoteNodes {
+    nodes: DashMap<String, Arc<RemoteNode>>,
+}
+
+impl RemoteNodes {
+    /// Node `node_id`, if it is a remote node this replica can reach.
+    pub fn get(&self, node_id: &str) -> Option<Arc<RemoteNode>> {
+        self.nodes
+            .get(node_id)
+            .map(|node| node.clone())
+            .filter(|node| node.is_connected())
+    }
+
+    /// Remote node hosting the participant's session, if any.
+    pub fn session(&self, channel_id: Uuid, user_id: Uuid) -> Option<Arc<RemoteNode>> {
+        self.nodes
+            .iter()
+            .find(|node| node.sessions.contains_key(&(channel_id, user_id)))
+            .map(|node| node.clone())
+    }
+
+    /// Forget the participant's session and return the node hosting it.
+    pub fn take_session(&self, channel_id: Uuid, user_id: Uuid) -> Option<Arc<RemoteNode>> {
+        let node = self.session(channel_id, user_id)?;
+        node.sessions.remove(&(channel_id, user_id));
+        Some(node)
+    }
+
+    /// Connect to the media nodes in `SFU_REMOTE_NODES` and keep their
+    /// registry entries fresh.
+    ///
+    /// Returns `None` when no remote node is configured.
+    pub fn spawn(
+        self: &Arc<Self>,
+        redis: Client,
+        config: &Config,
+    ) -> Option<tokio::task::JoinHandle<()>> {
+        let token = config.sfu_control_token.as_deref()?;
+        if config.sfu_remote_nodes.is_empty() {
+            return None;
+        }
+
+        let mut tasks = JoinSet::new();
+        for url in &config.sfu_remote_nodes {
+            match ControlClient::new(url, token) {
+                Ok(client) => {
+                    tasks.spawn(run_node(
+                        self.clone(),
+                        redis.clone(),
+                        url.clone(),
+                        client,
+                        config.node_id.clone(),
+                    ));
+                }
+                Err(e) => warn!(url = %url, error = %e, "Invalid remote SFU node"),
+            }
+        }
+        // Dropping the set when the task is aborted stops the node tasks
+        Some(tokio::spawn(async move {
+            while tasks.join_next().await.is_some() {}
+        }))
+    }
+}
+
+/// A media node hosting rooms for this replica.
+pub struct RemoteNode {
+    node_id: String,
+    /// This replica's node ID, which the node routes our events by.
+    client_id: String,
+    client: ControlClient,
+    /// Whether the node answers and its events stream is open.
+    connected: AtomicBool,
+    /// WebSocket senders of the participants joined through this replica.
+    sessions: DashMap<(Uuid, Uuid), mpsc::Sender<ServerEvent>>,
+}
+
+impl RemoteNode {
+    /// The node's ID in the cluster registry.
+    pub fn node_id(&self) -> &str {
+        &self.node_id
+    }
+
+    fn is_connected(&self) -> bool {
+        self.connected.load(Ordering::Relaxed)
+    }
+
+    /// Join a participant to a room on the node.
+    ///
+    /// The offer, room state and later events reach `tx` through the node's
+    /// events stream.
+    pub(crate) async fn join(
+        &self,
+        join: HostJoin,
+        tx: &mpsc::Sender<ServerEvent>,
+    ) -> Result<(), VoiceError> {
+        let key = (join.channel_id, join.user_id);
+        match self.sessions.entry(key) {
+            Entry::Occupied(_) => return Err(VoiceError::AlreadyJoined),
+            Entry::Vacant(entry) => {
+                entry.insert(tx.clone());
+            }
+        }
+
+        let request = JoinRequest {
+            client_id: self.client_id.clone(),
+            channel_id: join.channel_id.to_string(),
+            user_id: join.user_id.to_string(),
+            username: join.username,
+            display_name: join.display_name,
+            name_pronunciation: join.name_pronunciation,
+            listener: join.listener,
+            noise_suppression: join.noise_suppression,
+            max_audio_bitrate: join.max_audio_bitrate,
+            node_rtts: join.node_rtts,
+        };
+        if let Err(status) = self.client.join(request).await {
+            self.sessions.remove(&key);
+            return Err(self.error(status));
+        }
+        Ok(())
+    }
+
+    /// Apply a participant's SDP answer.
+    pub async fn answer(
+        &self,
+        channel_id: Uuid,
+        user_id: Uuid,
+        sdp: &str,
+    ) -> Result<(), VoiceError> {
+        self.client
+            .answer(AnswerRequest {
+                channel_id: channel_id.to_string(),
+                user_id: user_id.to_string(),
+                sdp: sdp.to_string(),
+            })
+            .await
+            .map_err(|status| self.error(status))
+    }
+
+    /// Apply a participant's ICE candidate.
+    pub async fn add_ice_candidate(
+        &self,
+        channel_id: Uuid,
+        user_id: Uuid,
+        candidate: &str,
+    ) -> Result<(), VoiceError> {
+        self.client
+            .add_ice_candidate(IceCandidateRequest {
+                channel_id: channel_id.to_string(),
+                user_id: user_id.to_string(),
+                candidate: candidate.to_string(),
+            })
+            .await
+            .map_err(|status| self.error(status))
+    }
+
+    /// Mute or unmute a participant.
+    pub async fn set_muted(
+        &self,
+        channel_id: Uuid,
+        user_id: Uuid,
+        muted: bool,
+    ) -> Result<(), VoiceError> {
+        self.client
+            .set_muted(SetMutedRequest {
+                channel_id: channel_id.to_string(),
+                user_id: user_id.to_string(),
+                muted,
+            })
+            .await
+            .map_err(|status| self.error(status))
+    }
+
+    /// Remove a participant from its room on the node.
+    pub(crate) async fn leave(
+        &self,
+        channel_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<HostLeave, VoiceError> {
+        let reply = self
+            .client
+            .leave(LeaveRequest {
+                channel_id: channel_id.to_string(),
+                user_id: user_id.to_string(),
+            })
+            .await
+            .map_err(|status| self.error(status))?;
+
+        let session = if !reply.found {
+            None
+        } else if let (Ok(session_id), Some(connected_at)) = (
+            Uuid::parse_str(&reply.session_id),
+            DateTime::<Utc>::from_timestamp_millis(reply.connected_at_ms),
+        ) {
+            Some(LeftSession {
+                session_id,
+                connected_at,
+                noise_suppression: reply.noise_suppression,
+            })
+        } else {
+            warn!(node_id = %self.node_id, "Remote SFU node returned an invalid session");
+            None
+        };
+        Ok(HostLeave {
+            session,
+            screen_share_stopped: false,
+            room_empty: reply.room_empty,
+        })
+    }
+
+    fn error(&self, status: tonic::Status) -> VoiceError {
+        let error = control::error_from_status(&status);
+        if matches!(error, VoiceError::MediaNode(_)) {
+            warn!(
+                node_id = %self.node_id,
+                code = ?status.code(),
+                error = %status.message(),
+                "Remote SFU node call failed"
+            );
+        }
+        error
+    }
+
+    /// Forward an event from the node to the participant's WebSocket.
+    async fn deliver(&self, event: PeerEvent) {
+        let (Ok(channel_id), Ok(user_id)) = (
+            Uuid::parse_str(&event.channel_id),
+            Uuid::parse_str(&event.user_id),
+        ) else {
+            warn!(node_id = %self.node_id, "Remote SFU node sent an event without valid IDs");
+            return;
+        };
+        let Some(tx) = self
+            .sessions
+            .get(&(channel_id, user_id))
+            .map(|tx| tx.clone())
+        else {
+            debug!(
+                node_id = %self.node_id,
+                channel_id = %channel_id,
+                user_id = %user_id,
+                "Dropping event for a participant not joined through this node"
+            );
+            return;
+        };
+        match serde_json::from_str::<ServerEvent>(&event.event_json) {
+            Ok(event) => {
+                if let Err(e) = tx.send(event).await {
+                    warn!(user_id = %user_id, error = %e, "Failed to send event to peer");
+                }
+            }
+            Err(e) => {
+                warn!(node_id = %self.node_id, error = %e, "Invalid event from remote SFU node");
+            }
+        }
+    }
+}
+
+/// Registry entry of a remote node, from its status.
+fn node_info(status: &NodeStatus) -> NodeInfo {
+    let now = Utc::now();
+    NodeInfo {
+        node_id: status.node_id.clone(),
+        version: status.version.clone(),
+        started_at: DateTime::from_timestamp_millis(status.started_at_ms).unwrap_or(now),
+        last_heartbeat: now,
+        ws_connections: 0,
+        voice_rooms: status.voice_rooms,
+        voice_participants: status.voice_participants,
+        region: status.region.clone(),
+        public_address: status.public_address.clone(),
+    }
+}
+
+/// Next event of the stream, or never when it is not open.
+async fn next_event(
+    events: &mut Option<Streaming<PeerEvent>>,
+) -> Result<Option<PeerEvent>, tonic::Status> {
+    match events {
+        Some(events) => events.message().await,
+        None => std::future::pending().await,
+    }
+}
+
+/// Poll one node's status, keep its registry entry fresh and pump its
+/// events to the participants.
+async fn run_node(
+    nodes: Arc<RemoteNodes>,
+    redis: Client,
+    url: String,
+    client: ControlClient,
+    client_id: String,
+) {
+    let mut node: Option<Arc<RemoteNode>> = None;
+    let mut events = None;
+    let mut interval = tokio::time::interval(HEARTBEAT_INTERVAL);
+    loop {
+        tokio::select! {
+            _ = interval.tick() => {
+                let status = match client.status().await {
+                    Ok(status) => status,
+                    Err(e) => {
+                        // The registry entry expires, and the rooms fail over
+                        warn!(url = %url, error = %e.message(), "Remote SFU node is unreachable");
+                        continue;
+                    }
+                };
+
+                // A node restarted under another ID is a different node
+                let current = match &node {
+                    Some(current) if current.node_id == status.node_id => current.clone(),
+                    _ => {
+                        if let Some(previous) = node.take() {
+                            previous.connected.store(false, Ordering::Relaxed);
+                        }
+                        events = None;
+                        let current = Arc::new(RemoteNode {
+                            node_id: status.node_id.clone(),
+                            client_id: client_id.clone(),
+                            client: client.clone(),
+                            connected: AtomicBool::new(false),
+                            sessions: DashMap::new(),
+                        });
+                        nodes.nodes.insert(status.node_id.clone(), current.clone());
+                        info!(url = %url, node_id = %status.node_id, "Connected to remote SFU node");
+                        current
+                    }
+                };
+                node = Some(current.clone());
+
+                // Joins go to the node only once its events can reach us
+                if events.is_none() {
+                    match client.events(&client_id).await {
+                        Ok(stream) => events = Some(stream),
+                        Err(e) => {
+                            warn!(node_id = %current.node_id, error = %e.message(), "Failed to open remote SFU node events");
+                        }
+                    }
+                }
+                current.connected.store(events.is_some(), Ordering::Relaxed);
+
+                if let Err(e) = registry::write_node_entry(&redis, &node_info(&status)).await {
+                    warn!(node_id = %current.node_id, error = %e, "Failed to register remote SFU node");
+                }
+            }
+            message = next_event(&mut events) => match (message, &node) {
+                (Ok(Some(event)), Some(node)) => node.deliver(event).await,
+                (Ok(Some(_)), None) => {}
+                (result, _) => {
+                    let error = result.err().map(|e| e.message().to_string());
+                    warn!(url = %url, error = ?error, "Remote SFU node events stream closed");
+                    events = None;
+                    if let Some(node) = &node {
+                        node.connected.store(false, Ordering::Relaxed);
+                    }
+                }
+            },
+        }
+    }
+}
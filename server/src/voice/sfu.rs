@@ -3,28 +3,38 @@
 //! Manages voice rooms and WebRTC peer connections for real-time audio.
 
 use std::collections::HashMap;
+use std::fmt::Write;
 use std::sync::Arc;
 
+use arc_swap::ArcSwap;
+use sqlx::PgPool;
 use tokio::sync::{mpsc, RwLock};
 use tracing::{debug, info, warn};
 use uuid::Uuid;
 use webrtc::api::interceptor_registry::register_default_interceptors;
 use webrtc::api::media_engine::MediaEngine;
+use webrtc::api::setting_engine::SettingEngine;
 use webrtc::api::{APIBuilder, API};
+use webrtc::ice_transport::ice_candidate_type::RTCIceCandidateType;
 use webrtc::ice_transport::ice_server::RTCIceServer;
 use webrtc::interceptor::registry::Registry;
 use webrtc::peer_connection::configuration::RTCConfiguration;
 use webrtc::peer_connection::peer_connection_state::RTCPeerConnectionState;
 use webrtc::peer_connection::sdp::session_description::RTCSessionDescription;
 use webrtc::rtp_transceiver::rtp_codec::{
-    RTCRtpCodecCapability, RTCRtpCodecParameters, RTPCodecType,
+    RTCRtpCodecCapability, RTCRtpCodecParameters, RTCRtpHeaderExtensionCapability, RTPCodecType,
 };
 use webrtc::rtp_transceiver::RTCPFeedback;
 
+use super::activity_log::{VoiceActivityLog, VOICE_LOG_BATCH_WINDOW};
 use super::error::VoiceError;
 use super::peer::Peer;
 use super::rate_limit::VoiceStatsLimiter;
+use super::recording::RecordingSession;
+use super::regions::{select_node, MediaNode};
+use super::remote::RemoteNodes;
 use super::screen_share::ScreenShareInfo;
+use super::speaking::{SpeakingMonitor, VoiceActivityMode, AUDIO_LEVEL_URI};
 use super::track::{spawn_rtp_forwarder, TrackRouter};
 use super::track_types::TrackSource;
 use super::webcam::WebcamInfo;
@@ -37,6 +47,7 @@ const DEFAULT_MAX_PARTICIPANTS: usize = 25;
 
 /// Participant info for room state.
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[allow(clippy::struct_excessive_bools)]
 pub struct ParticipantInfo {
     /// User ID.
     pub user_id: Uuid,
@@ -54,6 +65,15 @@ pub struct ParticipantInfo {
     /// Whether the user has their webcam active.
     #[serde(default)]
     pub webcam_active: bool,
+    /// How the user transmits audio.
+    #[serde(default)]
+    pub activity_mode: VoiceActivityMode,
+    /// Whether the user joined receive-only, without `VOICE_SPEAK`.
+    #[serde(default)]
+    pub listener: bool,
+    /// Whether the listener has asked to speak.
+    #[serde(default)]
+    pub hand_raised: bool,
 }
 
 /// Voice channel room with all participants.
@@ -70,6 +90,10 @@ pub struct Room {
     pub screen_shares: RwLock<HashMap<Uuid, ScreenShareInfo>>,
     /// Active webcams.
     pub webcams: RwLock<HashMap<Uuid, WebcamInfo>>,
+    /// Active server-side recording, if any.
+    pub recording: RwLock<Option<Arc<RecordingSession>>>,
+    /// Media node the channel is placed on (multi-node deployments only).
+    pub media_node: RwLock<Option<String>>,
 }
 
 impl Room {
@@ -83,6 +107,8 @@ impl Room {
             max_participants,
             screen_shares: RwLock::new(HashMap::new()),
             webcams: RwLock::new(HashMap::new()),
+            recording: RwLock::new(None),
+            media_node: RwLock::new(None),
         }
     }
 
@@ -185,6 +211,9 @@ impl Room {
                 muted: peer.is_muted().await,
                 screen_sharing: shares.contains_key(user_id),
                 webcam_active: webcams.contains_key(user_id),
+                activity_mode: peer.activity_mode().await,
+                listener: peer.is_listener().await,
+                hand_raised: peer.is_hand_raised().await,
             });
         }
 
@@ -235,6 +264,29 @@ impl Room {
         }
     }
 
+    /// Place the room on the media node with the lowest mean RTT for its
+    /// current participants and return the chosen node ID.
+    pub async fn place_media_node(&self, nodes: &[MediaNode]) -> Option<String> {
+        let peers: Vec<Arc<Peer>> = self.peers.read().await.values().cloned().collect();
+        let mut samples = Vec::with_capacity(peers.len());
+        for peer in peers {
+            samples.push(peer.node_rtts.read().await.clone());
+        }
+
+        let node_id = select_node(nodes, &samples).map(|node| node.id.clone());
+        let mut current = self.media_node.write().await;
+        if *current != node_id {
+            debug!(
+                channel_id = %self.channel_id,
+                from = ?*current,
+                to = ?node_id,
+                "Voice room placed on media node"
+            );
+            current.clone_from(&node_id);
+        }
+        node_id
+    }
+
     /// Get participant count.
     pub async fn participant_count(&self) -> usize {
         self.peers.read().await.len()
@@ -252,12 +304,16 @@ pub struct SfuServer {
     rooms: Arc<RwLock<HashMap<Uuid, Arc<Room>>>>,
     /// WebRTC API instance.
     api: Arc<API>,
-    /// Server configuration.
-    config: Arc<Config>,
+    /// Server configuration, replaced when it is reloaded.
+    config: ArcSwap<Config>,
     /// Rate limiter for voice operations (global/redis).
     rate_limiter: Option<Arc<RateLimiter>>,
     /// Rate limiter for voice stats (local/memory).
     stats_limiter: Arc<VoiceStatsLimiter>,
+    /// Buffered join/leave events for guild voice log channels.
+    activity_log: Arc<VoiceActivityLog>,
+    /// Remote media nodes hosting rooms for this server.
+    remote_nodes: Arc<RemoteNodes>,
 }
 
 impl SfuServer {
@@ -284,6 +340,17 @@ impl SfuServer {
             )
             .map_err(|e| VoiceError::WebRtc(e.to_string()))?;
 
+        // Negotiate the audio level extension so the SFU can detect speaking
+        media_engine
+            .register_header_extension(
+                RTCRtpHeaderExtensionCapability {
+                    uri: AUDIO_LEVEL_URI.to_string(),
+                },
+                RTPCodecType::Audio,
+                None,
+            )
+            .map_err(|e| VoiceError::WebRtc(e.to_string()))?;
+
         // Register VP9 video codec (preferred)
         media_engine
             .register_codec(
@@ -396,10 +463,17 @@ impl SfuServer {
         registry = register_default_interceptors(registry, &mut media_engine)
             .map_err(|e| VoiceError::WebRtc(e.to_string()))?;
 
+        // Advertise the node's public address when it sits behind 1:1 NAT
+        let mut setting_engine = SettingEngine::default();
+        if let Some(address) = &config.node_public_address {
+            setting_engine.set_nat_1to1_ips(vec![address.clone()], RTCIceCandidateType::Host);
+        }
+
         // Build WebRTC API
         let api = APIBuilder::new()
             .with_media_engine(media_engine)
             .with_interceptor_registry(registry)
+            .with_setting_engine(setting_engine)
             .build();
 
         info!("SFU server initialized");
@@ -407,9 +481,11 @@ impl SfuServer {
         Ok(Self {
             rooms: Arc::new(RwLock::new(HashMap::new())),
             api: Arc::new(api),
-            config,
+            config: ArcSwap::new(config),
             rate_limiter: rate_limiter.map(Arc::new),
             stats_limiter: Arc::new(VoiceStatsLimiter::default()),
+            activity_log: Arc::new(VoiceActivityLog::new()),
+            remote_nodes: Arc::new(RemoteNodes::default()),
         })
     }
 
@@ -420,20 +496,60 @@ impl SfuServer {
         self.stats_limiter.start_cleanup_task()
     }
 
+    /// Start background task posting batched join/leave events to guild voice log channels.
+    /// Returns a handle to the spawned task.
+    pub fn start_activity_log_task(
+        &self,
+        pool: PgPool,
+        redis: fred::clients::Client,
+    ) -> tokio::task::JoinHandle<()> {
+        let log = self.activity_log.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(VOICE_LOG_BATCH_WINDOW);
+            loop {
+                interval.tick().await;
+                log.flush(&pool, &redis).await;
+            }
+        })
+    }
+
+    /// Get the voice activity log.
+    pub const fn activity_log(&self) -> &Arc<VoiceActivityLog> {
+        &self.activity_log
+    }
+
+    /// Get the remote media nodes.
+    pub const fn remote_nodes(&self) -> &Arc<RemoteNodes> {
+        &self.remote_nodes
+    }
+
+    /// Get the server configuration.
+    #[must_use]
+    pub fn config(&self) -> Arc<Config> {
+        self.config.load_full()
+    }
+
+    /// Replace the server configuration after a reload. Peers already
+    /// connected keep their ICE servers.
+    pub fn set_config(&self, config: Arc<Config>) {
+        self.config.store(config);
+    }
+
     /// Get `RTCConfiguration` with ICE servers from config.
     #[must_use]
     pub fn rtc_config(&self) -> RTCConfiguration {
+        let config = self.config.load();
         let mut ice_servers = vec![RTCIceServer {
-            urls: vec![self.config.stun_server.clone()],
+            urls: vec![config.stun_server.clone()],
             ..Default::default()
         }];
 
         // Add TURN server if configured
-        if let Some(turn) = &self.config.turn_server {
+        if let Some(turn) = &config.turn_server {
             ice_servers.push(RTCIceServer {
                 urls: vec![turn.clone()],
-                username: self.config.turn_username.clone().unwrap_or_default(),
-                credential: self.config.turn_credential.clone().unwrap_or_default(),
+                username: config.turn_username.clone().unwrap_or_default(),
+                credential: config.turn_credential.clone().unwrap_or_default(),
                 ..Default::default()
             });
         }
@@ -466,19 +582,41 @@ impl SfuServer {
         rooms.get(&channel_id).cloned()
     }
 
-    /// Remove a room if empty.
-    pub async fn cleanup_room_if_empty(&self, channel_id: Uuid) {
+    /// Hosted rooms, ordered by channel ID.
+    pub async fn rooms(&self) -> Vec<Arc<Room>> {
+        let mut rooms: Vec<Arc<Room>> = self.rooms.read().await.values().cloned().collect();
+        rooms.sort_by_key(|room| room.channel_id);
+        rooms
+    }
+
+    /// Remove a room if empty. Returns `true` if the room was removed.
+    pub async fn cleanup_room_if_empty(&self, channel_id: Uuid) -> bool {
         let mut rooms = self.rooms.write().await;
 
         if let Some(room) = rooms.get(&channel_id) {
             if room.is_empty().await {
                 rooms.remove(&channel_id);
                 debug!(channel_id = %channel_id, "Removed empty voice room");
+                return true;
             }
         }
+        false
+    }
+
+    /// Number of hosted rooms and participants across them.
+    pub async fn load(&self) -> (usize, usize) {
+        let rooms: Vec<Arc<Room>> = self.rooms.read().await.values().cloned().collect();
+        let mut participants = 0;
+        for room in &rooms {
+            participants += room.participant_count().await;
+        }
+        (rooms.len(), participants)
     }
 
     /// Create a new peer connection for a user.
+    ///
+    /// Listeners get no recv transceivers, so they cannot send media until
+    /// promoted.
     pub async fn create_peer(
         &self,
         user_id: Uuid,
@@ -486,6 +624,7 @@ impl SfuServer {
         display_name: String,
         channel_id: Uuid,
         signal_tx: mpsc::Sender<ServerEvent>,
+        listener: bool,
     ) -> Result<Arc<Peer>, VoiceError> {
         let config = self.rtc_config();
         let peer = Peer::new(
@@ -500,11 +639,12 @@ impl SfuServer {
         .await?;
         let peer = Arc::new(peer);
 
-        // Add recvonly transceivers
-        // Always add Audio (mic)
-        peer.add_recv_transceiver(RTPCodecType::Audio).await?;
-        // Always add Video (screen) to prepare m-lines
-        peer.add_recv_transceiver(RTPCodecType::Video).await?;
+        if listener {
+            peer.set_listener(true).await;
+            peer.set_muted(true).await;
+        } else {
+            peer.add_recv_transceivers().await?;
+        }
 
         // Set up connection state handler
         let peer_weak = Arc::downgrade(&peer);
@@ -566,6 +706,13 @@ impl SfuServer {
                         _ => return,
                     };
 
+                    // Listeners (including a stage's audience) are receive-only;
+                    // only speakers publish, whatever the client negotiated
+                    if peer.is_listener().await {
+                        warn!(user_id = %uid, "Ignoring track from listener");
+                        return;
+                    }
+
                     // Determine source type: check pending queue first, fall back to defaults
                     let source_type = match track.kind() {
                         RTPCodecType::Audio => peer
@@ -585,8 +732,36 @@ impl SfuServer {
                     // Store incoming track
                     peer.set_incoming_track(source_type, track.clone()).await;
 
+                    // Detect speaking from the microphone's audio level extension
+                    let speaking = if source_type == TrackSource::Microphone {
+                        track
+                            .params()
+                            .header_extensions
+                            .iter()
+                            .find(|ext| ext.uri == AUDIO_LEVEL_URI)
+                            .and_then(|ext| u8::try_from(ext.id).ok())
+                            .map(|ext_id| {
+                                SpeakingMonitor::new(
+                                    ext_id,
+                                    Arc::downgrade(&peer),
+                                    Arc::downgrade(&room),
+                                    uid,
+                                    cid,
+                                )
+                            })
+                    } else {
+                        None
+                    };
+
                     // Start RTP forwarder
-                    spawn_rtp_forwarder(uid, source_type, track.clone(), room.track_router.clone());
+                    spawn_rtp_forwarder(
+                        uid,
+                        source_type,
+                        track.clone(),
+                        room.track_router.clone(),
+                        speaking,
+                        peer.inbound_bitrate.clone(),
+                    );
 
                     // Create subscriber tracks for all existing peers
                     let other_peers = room.get_other_peers(uid).await;
@@ -664,10 +839,7 @@ impl SfuServer {
     /// Trigger renegotiation by creating a new offer and sending it to the peer.
     /// Used after dynamically adding/removing tracks mid-session.
     pub async fn renegotiate(peer: &Peer) -> Result<(), VoiceError> {
-        let offer = peer.peer_connection.create_offer(None).await?;
-        peer.peer_connection
-            .set_local_description(offer.clone())
-            .await?;
+        let offer = Self::build_offer(peer).await?;
         peer.signal_tx
             .send(ServerEvent::VoiceOffer {
                 channel_id: peer.channel_id,
@@ -680,13 +852,40 @@ impl SfuServer {
 
     /// Create an offer for a peer.
     pub async fn create_offer(&self, peer: &Peer) -> Result<RTCSessionDescription, VoiceError> {
-        let offer = peer.peer_connection.create_offer(None).await?;
+        Self::build_offer(peer).await
+    }
+
+    /// Create and apply a local offer, announcing the peer's Opus bitrate cap.
+    async fn build_offer(peer: &Peer) -> Result<RTCSessionDescription, VoiceError> {
+        let mut offer = peer.peer_connection.create_offer(None).await?;
         peer.peer_connection
             .set_local_description(offer.clone())
             .await?;
+        if let Some(bitrate) = peer.effective_audio_bitrate().await {
+            offer.sdp = with_opus_max_bitrate(&offer.sdp, bitrate);
+        }
         Ok(offer)
     }
 
+    /// Apply a new Opus bitrate cap to everyone in the given voice channels.
+    ///
+    /// Peers whose cap changed are renegotiated so the client encoder picks it up.
+    pub async fn apply_audio_bitrate(&self, channel_ids: &[Uuid], bitrate: u32) {
+        for channel_id in channel_ids {
+            let Some(room) = self.get_room(*channel_id).await else {
+                continue;
+            };
+            let peers: Vec<Arc<Peer>> = room.peers.read().await.values().cloned().collect();
+            for peer in peers {
+                if peer.set_max_audio_bitrate(bitrate).await {
+                    if let Err(e) = Self::renegotiate(&peer).await {
+                        warn!(user_id = %peer.user_id, error = %e, "Failed to renegotiate bitrate");
+                    }
+                }
+            }
+        }
+    }
+
     /// Handle an answer from a peer.
     pub async fn handle_answer(&self, peer: &Peer, sdp: &str) -> Result<(), VoiceError> {
         let answer = RTCSessionDescription::answer(sdp.to_string())
@@ -737,3 +936,65 @@ impl SfuServer {
         self.rooms.read().await.len()
     }
 }
+
+/// Add `maxaveragebitrate` to the Opus `fmtp` lines of an SDP.
+///
+/// Per RFC 7587 this tells the remote encoder the highest average bitrate we
+/// want to receive; browsers apply it to their Opus encoder.
+pub fn with_opus_max_bitrate(sdp: &str, bitrate: u32) -> String {
+    let opus_payloads: Vec<&str> = sdp
+        .lines()
+        .filter_map(|line| line.strip_prefix("a=rtpmap:"))
+        .filter_map(|rest| rest.split_once(' '))
+        .filter(|(_, codec)| codec.to_ascii_lowercase().starts_with("opus/"))
+        .map(|(pt, _)| pt)
+        .collect();
+
+    let mut out = String::with_capacity(sdp.len() + 32);
+    for line in sdp.split_inclusive('\n') {
+        let body = line.trim_end_matches(['\r', '\n']);
+        let ending = &line[body.len()..];
+        let Some((pt, params)) = body
+            .strip_prefix("a=fmtp:")
+            .and_then(|rest| rest.split_once(' '))
+            .filter(|(pt, _)| opus_payloads.contains(pt))
+        else {
+            out.push_str(line);
+            continue;
+        };
+        let params: Vec<&str> = params
+            .split(';')
+            .filter(|p| !p.is_empty() && !p.trim_start().starts_with("maxaveragebitrate="))
+            .collect();
+        let sep = if params.is_empty() { "" } else { ";" };
+        write!(
+            out,
+            "a=fmtp:{pt} {}{sep}maxaveragebitrate={bitrate}{ending}",
+            params.join(";")
+        )
+        .expect("write to String is infallible");
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_with_opus_max_bitrate() {
+        let sdp = "v=0\r\n\
+                   m=audio 9 UDP/TLS/RTP/SAVPF 111 0\r\n\
+                   a=rtpmap:111 opus/48000/2\r\n\
+                   a=fmtp:111 minptime=10;useinbandfec=1;maxaveragebitrate=510000\r\n\
+                   a=rtpmap:0 PCMU/8000\r\n\
+                   a=fmtp:0 foo=1\r\n";
+
+        let munged = with_opus_max_bitrate(sdp, 128_000);
+        assert!(
+            munged.contains("a=fmtp:111 minptime=10;useinbandfec=1;maxaveragebitrate=128000\r\n")
+        );
+        assert!(munged.contains("a=fmtp:0 foo=1\r\n"));
+        assert_eq!(munged.matches("maxaveragebitrate").count(), 1);
+    }
+}
@@ -0,0 +1,324 @@
+//! Server-Side Speaking Detection
+//!
+//! Derives `VoiceSpeaking` events from the RFC 6464 client-to-mixer audio level
+//! header extension instead of trusting clients to self-report.
+//!
+//! Each microphone forwarder owns a [`SpeakingMonitor`]. Packets are inspected
+//! in the RTP hot path, so detection is a handful of integer comparisons; events
+//! are only emitted on state changes and are throttled per participant.
+
+use std::sync::Weak;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use webrtc::rtp::packet::Packet as RtpPacket;
+
+use super::peer::Peer;
+use super::sfu::Room;
+use crate::ws::ServerEvent;
+
+/// Header extension URI for the RFC 6464 audio level.
+pub const AUDIO_LEVEL_URI: &str = "urn:ietf:params:rtp-hdrext:ssrc-audio-level";
+
+/// Loudest level (in -dBov) still treated as silence in VAD mode.
+///
+/// Speech typically sits between -20 and -45 dBov; background noise is quieter.
+const VAD_LEVEL_THRESHOLD: u8 = 50;
+
+/// Loudest level (in -dBov) still treated as silence in PTT mode.
+///
+/// PTT clients only transmit while the key is held, so anything above the
+/// noise floor counts as speech.
+const PTT_LEVEL_THRESHOLD: u8 = 90;
+
+/// How long speech must be absent before a participant stops speaking.
+const SPEAKING_HANGOVER: Duration = Duration::from_millis(400);
+
+/// Minimum interval between two speaking broadcasts for one participant.
+const SPEAKING_BROADCAST_INTERVAL: Duration = Duration::from_millis(250);
+
+/// How often an idle forwarder re-evaluates the hangover (DTX sends no packets).
+pub const SPEAKING_IDLE_POLL: Duration = Duration::from_millis(200);
+
+/// How a participant transmits audio.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VoiceActivityMode {
+    /// Voice activity detection (open mic gated by level).
+    #[default]
+    Vad,
+    /// Push-to-talk.
+    Ptt,
+}
+
+/// Parse an RFC 6464 audio level extension payload.
+///
+/// Returns `(voice_activity_flag, level)` where level is 0 (loudest) to 127 (silence) in -dBov.
+#[must_use]
+pub fn parse_audio_level(data: &[u8]) -> Option<(bool, u8)> {
+    let byte = *data.first()?;
+    Some((byte & 0x80 != 0, byte & 0x7f))
+}
+
+/// Whether a packet at the given level counts as speech in the given mode.
+#[must_use]
+pub const fn is_voiced(level: u8, mode: VoiceActivityMode) -> bool {
+    match mode {
+        VoiceActivityMode::Vad => level <= VAD_LEVEL_THRESHOLD,
+        VoiceActivityMode::Ptt => level <= PTT_LEVEL_THRESHOLD,
+    }
+}
+
+/// Speaking state machine with hangover and broadcast throttling.
+#[derive(Debug)]
+pub struct SpeakingDetector {
+    /// Negotiated header extension ID for the audio level.
+    ext_id: u8,
+    /// Last broadcast state.
+    speaking: bool,
+    /// Last time a voiced packet was seen.
+    last_voice: Option<Instant>,
+    /// Last time the broadcast state changed.
+    last_change: Option<Instant>,
+}
+
+impl SpeakingDetector {
+    /// Create a detector for the negotiated audio level extension ID.
+    #[must_use]
+    pub const fn new(ext_id: u8) -> Self {
+        Self {
+            ext_id,
+            speaking: false,
+            last_voice: None,
+            last_change: None,
+        }
+    }
+
+    /// Feed a packet. Returns the new state when it should be broadcast.
+    pub fn observe(
+        &mut self,
+        packet: &RtpPacket,
+        mode: VoiceActivityMode,
+        muted: bool,
+        now: Instant,
+    ) -> Option<bool> {
+        let voiced = !muted
+            && packet
+                .header
+                .get_extension(self.ext_id)
+                .and_then(|data| parse_audio_level(&data))
+                .is_some_and(|(_, level)| is_voiced(level, mode));
+
+        if voiced {
+            self.last_voice = Some(now);
+        }
+
+        self.evaluate(now)
+    }
+
+    /// Re-evaluate without a packet (idle stream). Returns the new state when it should be broadcast.
+    pub fn tick(&mut self, now: Instant) -> Option<bool> {
+        self.evaluate(now)
+    }
+
+    fn evaluate(&mut self, now: Instant) -> Option<bool> {
+        let active = self
+            .last_voice
+            .is_some_and(|t| now.duration_since(t) < SPEAKING_HANGOVER);
+
+        if active == self.speaking {
+            return None;
+        }
+
+        // Throttle flapping; a suppressed transition is retried on the next packet or tick
+        if self
+            .last_change
+            .is_some_and(|t| now.duration_since(t) < SPEAKING_BROADCAST_INTERVAL)
+        {
+            return None;
+        }
+
+        self.speaking = active;
+        self.last_change = Some(now);
+        Some(active)
+    }
+}
+
+/// Speaking detector bound to a participant, broadcasting changes to its room.
+pub struct SpeakingMonitor {
+    user_id: Uuid,
+    channel_id: Uuid,
+    detector: SpeakingDetector,
+    peer: Weak<Peer>,
+    room: Weak<Room>,
+}
+
+impl SpeakingMonitor {
+    /// Create a monitor for a participant's microphone track.
+    #[must_use]
+    pub const fn new(
+        ext_id: u8,
+        peer: Weak<Peer>,
+        room: Weak<Room>,
+        user_id: Uuid,
+        channel_id: Uuid,
+    ) -> Self {
+        Self {
+            user_id,
+            channel_id,
+            detector: SpeakingDetector::new(ext_id),
+            peer,
+            room,
+        }
+    }
+
+    /// Inspect a forwarded packet.
+    pub async fn observe(&mut self, packet: &RtpPacket) {
+        let Some(peer) = self.peer.upgrade() else {
+            return;
+        };
+        let mode = peer.activity_mode().await;
+        let muted = peer.is_muted().await;
+
+        if let Some(speaking) = self.detector.observe(packet, mode, muted, Instant::now()) {
+            self.publish(speaking);
+        }
+    }
+
+    /// Re-evaluate after no packet arrived for [`SPEAKING_IDLE_POLL`].
+    pub fn tick(&mut self) {
+        if let Some(speaking) = self.detector.tick(Instant::now()) {
+            self.publish(speaking);
+        }
+    }
+
+    /// Broadcast a state change without blocking the forwarder.
+    fn publish(&self, speaking: bool) {
+        let Some(room) = self.room.upgrade() else {
+            return;
+        };
+        let event = ServerEvent::VoiceSpeaking {
+            channel_id: self.channel_id,
+            user_id: self.user_id,
+            speaking,
+        };
+        tokio::spawn(async move {
+            room.broadcast_all(event).await;
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXT_ID: u8 = 1;
+
+    fn packet_with_level(level: Option<u8>) -> RtpPacket {
+        let mut packet = RtpPacket::default();
+        if let Some(level) = level {
+            packet
+                .header
+                .set_extension(EXT_ID, bytes::Bytes::copy_from_slice(&[level]))
+                .unwrap();
+        }
+        packet
+    }
+
+    #[test]
+    fn test_parse_audio_level() {
+        assert_eq!(parse_audio_level(&[0x80 | 0x1e]), Some((true, 30)));
+        assert_eq!(parse_audio_level(&[127]), Some((false, 127)));
+        assert_eq!(parse_audio_level(&[]), None);
+    }
+
+    #[test]
+    fn test_is_voiced_depends_on_mode() {
+        assert!(is_voiced(30, VoiceActivityMode::Vad));
+        assert!(!is_voiced(70, VoiceActivityMode::Vad));
+        assert!(is_voiced(70, VoiceActivityMode::Ptt));
+        assert!(!is_voiced(127, VoiceActivityMode::Ptt));
+    }
+
+    #[test]
+    fn test_detector_start_and_hangover() {
+        let mut detector = SpeakingDetector::new(EXT_ID);
+        let start = Instant::now();
+        let loud = packet_with_level(Some(20));
+        let quiet = packet_with_level(Some(127));
+
+        assert_eq!(
+            detector.observe(&loud, VoiceActivityMode::Vad, false, start),
+            Some(true)
+        );
+        // Still within hangover
+        assert_eq!(
+            detector.observe(
+                &quiet,
+                VoiceActivityMode::Vad,
+                false,
+                start + Duration::from_millis(300)
+            ),
+            None
+        );
+        // Hangover expired (DTX: no packets, only ticks)
+        assert_eq!(
+            detector.tick(start + Duration::from_millis(500)),
+            Some(false)
+        );
+        assert_eq!(detector.tick(start + Duration::from_millis(900)), None);
+    }
+
+    #[test]
+    fn test_detector_throttles_flapping() {
+        let mut detector = SpeakingDetector::new(EXT_ID);
+        let start = Instant::now();
+        let loud = packet_with_level(Some(20));
+
+        assert_eq!(
+            detector.observe(&loud, VoiceActivityMode::Vad, false, start),
+            Some(true)
+        );
+        assert_eq!(
+            detector.tick(start + Duration::from_millis(450)),
+            Some(false)
+        );
+        // Speech resumes right after the stop broadcast: suppressed by the throttle
+        let resume = start + Duration::from_millis(500);
+        assert_eq!(
+            detector.observe(&loud, VoiceActivityMode::Vad, false, resume),
+            None
+        );
+        // Next packet after the interval goes through
+        assert_eq!(
+            detector.observe(
+                &loud,
+                VoiceActivityMode::Vad,
+                false,
+                start + Duration::from_millis(720)
+            ),
+            Some(true)
+        );
+    }
+
+    #[test]
+    fn test_detector_ignores_muted_and_missing_extension() {
+        let mut detector = SpeakingDetector::new(EXT_ID);
+        let now = Instant::now();
+
+        assert_eq!(
+            detector.observe(
+                &packet_with_level(Some(10)),
+                VoiceActivityMode::Vad,
+                true,
+                now
+            ),
+            None
+        );
+        assert_eq!(
+            detector.observe(&packet_with_level(None), VoiceActivityMode::Ptt, false, now),
+            None
+        );
+    }
+}
@@ -6,6 +6,41 @@
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+/// Audio playout metrics reported alongside network stats.
+///
+/// Concealment and jitter-buffer figures describe what the reporting client
+/// receives, while the audio level describes what it sends. Together with the
+/// network stats they let clients tell a local network problem apart from a
+/// remote participant's. All fields are optional for older clients.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserialize, Serialize)]
+pub struct PlayoutStats {
+    /// Concealment events on received audio since the previous report.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub concealment_events: Option<u32>,
+    /// Average jitter-buffer delay of received audio in milliseconds (0-10000).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub jitter_buffer_delay: Option<i16>,
+    /// Level of the outgoing microphone audio (0.0-1.0).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub audio_level: Option<f32>,
+}
+
+impl PlayoutStats {
+    /// Validate stats are within acceptable ranges.
+    pub fn validate(&self) -> Result<(), &'static str> {
+        if self
+            .jitter_buffer_delay
+            .is_some_and(|d| !(0..=10000).contains(&d))
+        {
+            return Err("jitter_buffer_delay out of range (0-10000ms)");
+        }
+        if self.audio_level.is_some_and(|l| !(0.0..=1.0).contains(&l)) {
+            return Err("audio_level out of range (0.0-1.0)");
+        }
+        Ok(())
+    }
+}
+
 /// Connection metrics reported by clients.
 ///
 /// These stats are collected periodically from each participant
@@ -24,6 +59,9 @@ pub struct VoiceStats {
     pub quality: u8,
     /// Unix timestamp in milliseconds when the stats were collected.
     pub timestamp: i64,
+    /// Concealment, jitter-buffer and audio-level stats.
+    #[serde(flatten)]
+    pub playout: PlayoutStats,
 }
 
 impl VoiceStats {
@@ -43,7 +81,7 @@ impl VoiceStats {
         if self.quality > 3 {
             return Err("quality must be 0-3");
         }
-        Ok(())
+        self.playout.validate()
     }
 }
 
@@ -63,6 +101,9 @@ pub struct UserStats {
     pub jitter: i16,
     /// Quality indicator (0=poor, 1=fair, 2=good, 3=excellent).
     pub quality: u8,
+    /// Concealment, jitter-buffer and audio-level stats.
+    #[serde(flatten)]
+    pub playout: PlayoutStats,
 }
 
 #[cfg(test)]
@@ -78,6 +119,7 @@ mod tests {
             jitter: 30,
             quality: 3,
             timestamp: 1234567890,
+            playout: PlayoutStats::default(),
         };
         assert!(stats.validate().is_ok());
     }
@@ -91,6 +133,7 @@ mod tests {
             jitter: 0,
             quality: 3,
             timestamp: 0,
+            playout: PlayoutStats::default(),
         };
         assert_eq!(stats.validate(), Err("latency out of range (0-10000ms)"));
 
@@ -101,6 +144,7 @@ mod tests {
             jitter: 0,
             quality: 3,
             timestamp: 0,
+            playout: PlayoutStats::default(),
         };
         assert_eq!(stats2.validate(), Err("latency out of range (0-10000ms)"));
     }
@@ -114,6 +158,7 @@ mod tests {
             jitter: 30,
             quality: 3,
             timestamp: 0,
+            playout: PlayoutStats::default(),
         };
         assert_eq!(stats.validate(), Err("packet_loss out of range (0-100%)"));
 
@@ -124,6 +169,7 @@ mod tests {
             jitter: 30,
             quality: 3,
             timestamp: 0,
+            playout: PlayoutStats::default(),
         };
         assert_eq!(stats2.validate(), Err("packet_loss out of range (0-100%)"));
     }
@@ -137,6 +183,7 @@ mod tests {
             jitter: -1,
             quality: 3,
             timestamp: 0,
+            playout: PlayoutStats::default(),
         };
         assert_eq!(stats.validate(), Err("jitter out of range (0-5000ms)"));
     }
@@ -150,7 +197,36 @@ mod tests {
             jitter: 30,
             quality: 4,
             timestamp: 0,
+            playout: PlayoutStats::default(),
         };
         assert_eq!(stats.validate(), Err("quality must be 0-3"));
     }
+
+    #[test]
+    fn test_playout_stats_out_of_range() {
+        let mut stats = VoiceStats {
+            session_id: Uuid::new_v4(),
+            latency: 100,
+            packet_loss: 1.0,
+            jitter: 30,
+            quality: 3,
+            timestamp: 0,
+            playout: PlayoutStats {
+                concealment_events: Some(12),
+                jitter_buffer_delay: Some(80),
+                audio_level: Some(0.4),
+            },
+        };
+        assert!(stats.validate().is_ok());
+
+        stats.playout.jitter_buffer_delay = Some(-1);
+        assert_eq!(
+            stats.validate(),
+            Err("jitter_buffer_delay out of range (0-10000ms)")
+        );
+
+        stats.playout.jitter_buffer_delay = None;
+        stats.playout.audio_level = Some(1.5);
+        assert_eq!(stats.validate(), Err("audio_level out of range (0.0-1.0)"));
+    }
 }
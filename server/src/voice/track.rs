@@ -6,6 +6,7 @@
 use std::sync::Arc;
 
 use dashmap::DashMap;
+use tokio::sync::mpsc;
 use tracing::{debug, warn};
 use uuid::Uuid;
 use webrtc::rtp::packet::Packet as RtpPacket;
@@ -15,7 +16,8 @@ use webrtc::track::track_local::TrackLocalWriter;
 use webrtc::track::track_remote::TrackRemote;
 
 use super::error::VoiceError;
-use super::peer::Peer;
+use super::peer::{BitrateMeter, Peer};
+use super::speaking::{SpeakingMonitor, SPEAKING_IDLE_POLL};
 use super::track_types::TrackSource;
 
 /// Subscription info for a track.
@@ -35,6 +37,9 @@ pub struct TrackRouter {
     /// Map: `(source_user_id, source_type)` -> list of subscriptions
     /// Using `DashMap` to avoid lock contention in the RTP forwarding hot path.
     subscriptions: DashMap<(Uuid, TrackSource), Vec<Subscription>>,
+    /// Map: `source_user_id` -> recorder input for consenting participants.
+    /// Only microphone packets are copied into a tap.
+    recording_taps: DashMap<Uuid, mpsc::Sender<RtpPacket>>,
 }
 
 impl TrackRouter {
@@ -42,6 +47,7 @@ impl TrackRouter {
     pub fn new() -> Self {
         Self {
             subscriptions: DashMap::new(),
+            recording_taps: DashMap::new(),
         }
     }
 
@@ -103,6 +109,15 @@ impl TrackRouter {
         source_type: TrackSource,
         rtp_packet: &RtpPacket,
     ) {
+        // Copy microphone audio into the recorder without ever blocking forwarding
+        if source_type == TrackSource::Microphone {
+            if let Some(tap) = self.recording_taps.get(&source_user_id) {
+                if tap.try_send(rtp_packet.clone()).is_err() {
+                    debug!(source = %source_user_id, "Recording tap full, dropping packet");
+                }
+            }
+        }
+
         // DashMap::get returns a guard that provides lock-free concurrent read access
         if let Some(subscribers) = self.subscriptions.get(&(source_user_id, source_type)) {
             crate::observability::metrics::record_rtp_packet_forwarded();
@@ -182,6 +197,23 @@ impl TrackRouter {
         debug!(subscriber = %subscriber_id, "Removed subscriber from all sources");
     }
 
+    /// Start copying a participant's microphone packets into a recorder.
+    pub fn add_recording_tap(&self, source_user_id: Uuid, tap: mpsc::Sender<RtpPacket>) {
+        self.recording_taps.insert(source_user_id, tap);
+    }
+
+    /// Stop copying a participant's microphone packets.
+    ///
+    /// Dropping the sender lets the recorder task flush and close its file.
+    pub fn remove_recording_tap(&self, source_user_id: Uuid) -> bool {
+        self.recording_taps.remove(&source_user_id).is_some()
+    }
+
+    /// Remove all recording taps (recording stopped).
+    pub fn clear_recording_taps(&self) {
+        self.recording_taps.clear();
+    }
+
     /// Get the number of subscribers for a source.
     pub async fn subscriber_count(&self, source_user_id: Uuid, source_type: TrackSource) -> usize {
         self.subscriptions
@@ -197,18 +229,42 @@ impl Default for TrackRouter {
 }
 
 /// Spawn a task to read RTP packets from a track and forward them.
+///
+/// When a speaking monitor is given, each packet is inspected for the audio
+/// level and the monitor is ticked while the track is idle (DTX).
 pub fn spawn_rtp_forwarder(
     source_user_id: Uuid,
     source_type: TrackSource,
     track: Arc<TrackRemote>,
     router: Arc<TrackRouter>,
+    mut speaking: Option<SpeakingMonitor>,
+    bitrate: Arc<BitrateMeter>,
 ) {
     tokio::spawn(async move {
         let mut buf = vec![0u8; 1500]; // MTU size
 
         loop {
-            match track.read(&mut buf).await {
+            let result = if speaking.is_some() {
+                let Ok(result) =
+                    tokio::time::timeout(SPEAKING_IDLE_POLL, track.read(&mut buf)).await
+                else {
+                    if let Some(monitor) = speaking.as_mut() {
+                        monitor.tick();
+                    }
+                    continue;
+                };
+                result
+            } else {
+                track.read(&mut buf).await
+            };
+
+            match result {
                 Ok((packet, _attributes)) => {
+                    bitrate.record(packet.payload.len());
+                    if let Some(monitor) = speaking.as_mut() {
+                        monitor.observe(&packet).await;
+                    }
+
                     // Forward the RTP packet to all subscribers
                     router
                         .forward_rtp(source_user_id, source_type, &packet)
@@ -366,6 +422,39 @@ mod tests {
             .await;
     }
 
+    #[tokio::test]
+    async fn test_recording_tap_receives_only_microphone_packets() {
+        let router = TrackRouter::new();
+        let source_id = Uuid::new_v4();
+        let (tx, mut rx) = mpsc::channel(8);
+        router.add_recording_tap(source_id, tx);
+
+        let rtp_packet = RtpPacket {
+            header: webrtc::rtp::header::Header {
+                version: 2,
+                payload_type: 111,
+                sequence_number: 7,
+                ssrc: 42,
+                ..Default::default()
+            },
+            payload: bytes::Bytes::from_static(&[0u8; 20]),
+        };
+
+        router
+            .forward_rtp(source_id, TrackSource::ScreenAudio, &rtp_packet)
+            .await;
+        router
+            .forward_rtp(source_id, TrackSource::Microphone, &rtp_packet)
+            .await;
+
+        let received = rx.try_recv().expect("microphone packet should be tapped");
+        assert_eq!(received.header.sequence_number, 7);
+        assert!(rx.try_recv().is_err());
+
+        assert!(router.remove_recording_tap(source_id));
+        assert!(!router.remove_recording_tap(source_id));
+    }
+
     // =========================================================================
     // Concurrent Access Tests (DashMap should handle these without deadlocks)
     // =========================================================================
@@ -2,6 +2,7 @@
 //!
 //! Handles voice signaling messages from WebSocket connections.
 
+use std::collections::HashMap;
 use std::sync::Arc;
 
 use fred::clients::Client;
@@ -12,8 +13,12 @@ use uuid::Uuid;
 use webrtc::rtcp::payload_feedbacks::picture_loss_indication::PictureLossIndication;
 use webrtc::rtp_transceiver::rtp_codec::RTPCodecType;
 
+use super::activity_log::VoiceActivityKind;
 use super::error::VoiceError;
+use super::loss;
 use super::metrics::{finalize_session, get_guild_id, store_metrics};
+use super::recording;
+use super::regions::sanitize_rtts;
 use super::screen_share::{
     stop_screen_share, try_start_screen_share, validate_source_label, ScreenShareError,
     ScreenShareInfo,
@@ -22,7 +27,8 @@ use super::sfu::SfuServer;
 use super::stats::VoiceStats;
 use super::track_types::TrackSource;
 use super::webcam::WebcamInfo;
-use super::Quality;
+use super::{Quality, VoiceActivityMode};
+use crate::cluster;
 use crate::ws::{ClientEvent, ServerEvent, VoiceParticipant};
 
 /// Handle a voice-related client event.
@@ -35,8 +41,24 @@ pub async fn handle_voice_event(
     tx: &mpsc::Sender<ServerEvent>,
 ) -> Result<(), VoiceError> {
     match event {
-        ClientEvent::VoiceJoin { channel_id } => {
-            let result = handle_join(sfu, pool, user_id, channel_id, tx).await;
+        ClientEvent::VoiceJoin {
+            channel_id,
+            node_rtts,
+            noise_suppression,
+        } => {
+            let result = handle_join(
+                sfu,
+                pool,
+                redis,
+                user_id,
+                HandleJoinParams {
+                    channel_id,
+                    node_rtts,
+                    noise_suppression,
+                },
+                tx,
+            )
+            .await;
             crate::observability::metrics::record_voice_join(result.is_ok());
             result
         }
@@ -62,6 +84,7 @@ pub async fn handle_voice_event(
             jitter,
             quality,
             timestamp,
+            playout,
         } => {
             let stats = VoiceStats {
                 session_id,
@@ -70,6 +93,7 @@ pub async fn handle_voice_event(
                 jitter,
                 quality,
                 timestamp,
+                playout,
             };
             handle_voice_stats(sfu, pool, user_id, channel_id, stats).await
         }
@@ -103,18 +127,113 @@ pub async fn handle_voice_event(
         ClientEvent::VoiceWebcamStop { channel_id } => {
             handle_webcam_stop(sfu, user_id, channel_id).await
         }
+        ClientEvent::VoiceSetActivityMode { channel_id, mode } => {
+            handle_activity_mode(sfu, user_id, channel_id, mode).await
+        }
+        ClientEvent::VoicePromoteListener {
+            channel_id,
+            user_id: listener_id,
+        } => handle_promote_listener(sfu, pool, user_id, channel_id, listener_id).await,
+        ClientEvent::VoiceRaiseHand { channel_id } => {
+            handle_hand(sfu, user_id, channel_id, true).await
+        }
+        ClientEvent::VoiceLowerHand { channel_id } => {
+            handle_hand(sfu, user_id, channel_id, false).await
+        }
+        ClientEvent::VoiceSetUserGain { target_user, gain } => {
+            crate::api::preferences::set_voice_user_gain(pool, redis, user_id, target_user, gain)
+                .await
+                .map_err(|e| VoiceError::Signaling(e.to_string()))
+        }
+        ClientEvent::VoiceRecordingConsent {
+            channel_id,
+            recording_id,
+            consent,
+        } => {
+            let room = sfu
+                .get_room(channel_id)
+                .await
+                .ok_or(VoiceError::RoomNotFound(channel_id))?;
+            recording::set_consent(&room, user_id, recording_id, consent).await
+        }
         _ => Ok(()), // Non-voice events handled elsewhere
     }
 }
 
+/// Parameters for joining a voice channel.
+struct HandleJoinParams {
+    channel_id: Uuid,
+    node_rtts: HashMap<String, u32>,
+    noise_suppression: bool,
+}
+
+/// Redirect the first join of a room to the guild's preferred voice region.
+///
+/// Rooms already hosted by a live node stay there ([`cluster::rooms`]), and
+/// when the region has no live node the room is hosted here. Cluster lookups
+/// failing never blocks the join.
+async fn check_preferred_region(
+    sfu: &SfuServer,
+    pool: &PgPool,
+    redis: &Client,
+    guild_id: Uuid,
+    channel_id: Uuid,
+) -> Result<(), VoiceError> {
+    let region: Option<String> =
+        sqlx::query_scalar("SELECT voice_region FROM guilds WHERE id = $1")
+            .bind(guild_id)
+            .fetch_optional(pool)
+            .await
+            .map_err(|e| VoiceError::Internal(format!("Failed to load voice region: {e}")))?
+            .flatten();
+    let Some(region) = region else {
+        return Ok(());
+    };
+    let config = sfu.config();
+    if config.node_region.as_deref() == Some(region.as_str()) {
+        return Ok(());
+    }
+
+    if let Ok(Some(owner)) = cluster::rooms::room_owner(redis, channel_id).await {
+        if cluster::registry::is_alive(redis, &owner)
+            .await
+            .unwrap_or(false)
+        {
+            return Ok(());
+        }
+    }
+
+    match cluster::rooms::preferred_node(redis, &region).await {
+        Ok(Some(node)) if node.node_id != config.node_id => {
+            Err(VoiceError::RoomOnOtherNode(node.node_id))
+        }
+        Ok(_) => Ok(()),
+        Err(e) => {
+            warn!(
+                guild_id = %guild_id,
+                region = %region,
+                error = %e,
+                "Failed to find preferred voice node"
+            );
+            Ok(())
+        }
+    }
+}
+
 /// Handle a user joining a voice channel.
 async fn handle_join(
     sfu: &Arc<SfuServer>,
     pool: &PgPool,
+    redis: &Client,
     user_id: Uuid,
-    channel_id: Uuid,
+    params: HandleJoinParams,
     tx: &mpsc::Sender<ServerEvent>,
 ) -> Result<(), VoiceError> {
+    let HandleJoinParams {
+        channel_id,
+        node_rtts,
+        noise_suppression,
+    } = params;
     info!(user_id = %user_id, channel_id = %channel_id, "User joining voice channel");
 
     // Check if user has VIEW_CHANNEL and VOICE_CONNECT permissions
@@ -126,13 +245,44 @@ async fn handle_join(
         return Err(VoiceError::Unauthorized);
     }
 
+    let channel = crate::db::get_channel_by_id(pool, channel_id)
+        .await
+        .map_err(|e| VoiceError::Internal(format!("Failed to load channel: {e}")))?;
+
+    // Without VOICE_SPEAK the user joins receive-only. On a stage only
+    // moderators speak; everyone else joins the audience and raises their hand.
+    let stage = channel
+        .as_ref()
+        .is_some_and(|channel| channel.channel_type == crate::db::ChannelType::Stage);
+    let listener = !ctx.has_permission(crate::permissions::GuildPermissions::VOICE_SPEAK)
+        || (stage && !ctx.has_permission(crate::permissions::GuildPermissions::VOICE_MUTE_OTHERS));
+
+    // Nobody joins voice in a suspended guild, and timed-out members cannot
+    // join voice
+    let guild_id = channel.and_then(|channel| channel.guild_id);
+    if let Some(guild_id) = guild_id {
+        let suspended = crate::db::is_guild_suspended(pool, guild_id)
+            .await
+            .map_err(|e| VoiceError::Internal(format!("Failed to check suspension: {e}")))?;
+        if suspended {
+            return Err(VoiceError::GuildSuspended);
+        }
+        let timeout = crate::db::get_member_timeout(pool, guild_id, user_id)
+            .await
+            .map_err(|e| VoiceError::Internal(format!("Failed to check timeout: {e}")))?;
+        if timeout.is_some() {
+            return Err(VoiceError::TimedOut);
+        }
+    }
+
     sfu.check_rate_limit(user_id).await?;
 
-    let user = sqlx::query("SELECT username, display_name FROM users WHERE id = $1")
-        .bind(user_id)
-        .fetch_one(pool)
-        .await
-        .map_err(|e| VoiceError::Signaling(format!("Failed to fetch user info: {e}")))?;
+    let user =
+        sqlx::query("SELECT username, display_name, name_pronunciation FROM users WHERE id = $1")
+            .bind(user_id)
+            .fetch_one(pool)
+            .await
+            .map_err(|e| VoiceError::Signaling(format!("Failed to fetch user info: {e}")))?;
 
     let username: String = user
         .try_get("username")
@@ -140,6 +290,117 @@ async fn handle_join(
     let display_name: String = user
         .try_get("display_name")
         .map_err(|e| VoiceError::Signaling(format!("Failed to get display_name: {e}")))?;
+    let name_pronunciation: Option<String> = user
+        .try_get("name_pronunciation")
+        .map_err(|e| VoiceError::Signaling(format!("Failed to get name_pronunciation: {e}")))?;
+
+    // A guild preferring another region is hosted there; rooms on a remote
+    // media node are joined through its control protocol
+    let mut remote = None;
+    if let Some(guild_id) = guild_id {
+        match check_preferred_region(sfu, pool, redis, guild_id, channel_id).await {
+            Err(VoiceError::RoomOnOtherNode(node_id)) => match sfu.remote_nodes().get(&node_id) {
+                Some(node) => remote = Some(node),
+                None => return Err(VoiceError::RoomOnOtherNode(node_id)),
+            },
+            result => result?,
+        }
+    }
+
+    // A channel is hosted by one node; joining through another would split the call
+    let config = sfu.config();
+    let host_id = remote
+        .as_ref()
+        .map_or(config.node_id.as_str(), |node| node.node_id());
+    match cluster::rooms::claim_room(redis, channel_id, host_id).await {
+        Ok(None) => {}
+        Ok(Some(node_id)) if node_id == config.node_id => remote = None,
+        Ok(Some(node_id)) => match sfu.remote_nodes().get(&node_id) {
+            Some(node) => remote = Some(node),
+            None => return Err(VoiceError::RoomOnOtherNode(node_id)),
+        },
+        Err(e) => warn!(channel_id = %channel_id, error = %e, "Failed to claim voice room"),
+    }
+
+    // Cap the Opus bitrate according to the guild's supporter perks
+    let max_audio_bitrate =
+        match crate::guild::perks::get_channel_perks(pool, &config, channel_id).await {
+            Ok(perks) => perks.map(|perks| perks.voice_bitrate),
+            Err(e) => {
+                warn!(channel_id = %channel_id, error = %e, "Failed to load guild perks");
+                None
+            }
+        };
+
+    let join = HostJoin {
+        channel_id,
+        user_id,
+        username,
+        display_name,
+        name_pronunciation,
+        listener,
+        noise_suppression,
+        max_audio_bitrate,
+        node_rtts,
+    };
+    match &remote {
+        Some(node) => node.join(join, tx).await?,
+        None => host_join(sfu, join, tx).await?,
+    }
+
+    sfu.activity_log()
+        .record(user_id, channel_id, VoiceActivityKind::Joined)
+        .await;
+
+    info!(
+        user_id = %user_id,
+        channel_id = %channel_id,
+        listener = listener,
+        media_node = remote.as_ref().map(|node| node.node_id()),
+        "User joined voice channel"
+    );
+    crate::observability::metrics::record_voice_session_start();
+
+    Ok(())
+}
+
+/// A participant to add to the room of the SFU hosting the channel.
+pub(crate) struct HostJoin {
+    pub channel_id: Uuid,
+    pub user_id: Uuid,
+    pub username: String,
+    pub display_name: String,
+    pub name_pronunciation: Option<String>,
+    /// Join receive-only.
+    pub listener: bool,
+    pub noise_suppression: bool,
+    /// Opus bitrate cap from the guild's perks.
+    pub max_audio_bitrate: Option<u32>,
+    /// RTT samples to the media nodes, as reported in `VoiceJoin`.
+    pub node_rtts: HashMap<String, u32>,
+}
+
+/// Create the participant's peer, send it the offer and the room state, and
+/// announce it to the room.
+///
+/// Runs on the node hosting the room: here, or on a remote media node
+/// through the control protocol ([`super::control`]).
+pub(crate) async fn host_join(
+    sfu: &SfuServer,
+    join: HostJoin,
+    tx: &mpsc::Sender<ServerEvent>,
+) -> Result<(), VoiceError> {
+    let HostJoin {
+        channel_id,
+        user_id,
+        username,
+        display_name,
+        name_pronunciation,
+        listener,
+        noise_suppression,
+        max_audio_bitrate,
+        node_rtts,
+    } = join;
 
     let room = sfu.get_or_create_room(channel_id).await;
 
@@ -150,13 +411,26 @@ async fn handle_join(
             display_name.clone(),
             channel_id,
             tx.clone(),
+            listener,
         )
         .await?;
+    peer.set_name_pronunciation(name_pronunciation.clone())
+        .await;
+    if let Some(bitrate) = max_audio_bitrate {
+        peer.set_max_audio_bitrate(bitrate).await;
+    }
+
+    let config = sfu.config();
+    let media_nodes = &config.voice_media_nodes;
+    peer.set_node_rtts(sanitize_rtts(media_nodes, node_rtts))
+        .await;
+    peer.set_noise_suppression(noise_suppression).await;
 
     sfu.setup_ice_handler(&peer);
     sfu.setup_track_handler(&peer, &room);
 
     room.add_peer(peer.clone()).await?;
+    let media_node = room.place_media_node(media_nodes).await;
 
     let other_peers = room.get_other_peers(user_id).await;
     for other_peer in other_peers {
@@ -211,6 +485,9 @@ async fn handle_join(
             muted: p.muted,
             screen_sharing: p.screen_sharing,
             webcam_active: p.webcam_active,
+            activity_mode: p.activity_mode,
+            listener: p.listener,
+            hand_raised: p.hand_raised,
         })
         .collect();
 
@@ -222,10 +499,24 @@ async fn handle_join(
         participants,
         screen_shares,
         webcams,
+        media_node,
+        media_address: config.node_public_address.clone(),
     })
     .await
     .map_err(|e| VoiceError::Signaling(e.to_string()))?;
 
+    // Ask the joining user for consent if a recording is running
+    let active_recording = room.recording.read().await.clone();
+    if let Some(session) = active_recording {
+        tx.send(ServerEvent::VoiceRecordingStarted {
+            channel_id,
+            recording_id: session.id,
+            started_by: session.started_by,
+        })
+        .await
+        .map_err(|e| VoiceError::Signaling(e.to_string()))?;
+    }
+
     room.broadcast_except(
         user_id,
         ServerEvent::VoiceUserJoined {
@@ -233,17 +524,12 @@ async fn handle_join(
             user_id,
             username,
             display_name,
+            name_pronunciation,
+            listener,
         },
     )
     .await;
 
-    info!(
-        user_id = %user_id,
-        channel_id = %channel_id,
-        "User joined voice channel"
-    );
-    crate::observability::metrics::record_voice_session_start();
-
     Ok(())
 }
 
@@ -262,43 +548,29 @@ async fn handle_leave(
         .await
         .map_err(|_e: crate::permissions::PermissionError| VoiceError::Unauthorized)?;
 
-    let room = sfu
-        .get_room(channel_id)
-        .await
-        .ok_or(VoiceError::RoomNotFound(channel_id))?;
+    let (left, host_id) = match sfu.remote_nodes().take_session(channel_id, user_id) {
+        Some(node) => (
+            node.leave(channel_id, user_id).await?,
+            node.node_id().to_string(),
+        ),
+        None => (
+            host_leave(sfu, user_id, channel_id).await?,
+            sfu.config().node_id.clone(),
+        ),
+    };
 
-    // Check if sharing screen and stop it
-    if room.remove_screen_share(user_id).await.is_some() {
+    if left.screen_share_stopped {
         stop_screen_share(redis, channel_id).await;
-
-        room.broadcast_except(
-            user_id,
-            ServerEvent::ScreenShareStopped {
-                channel_id,
-                user_id,
-                reason: "disconnected".to_string(),
-            },
-        )
-        .await;
-    }
-
-    // Check if webcam is active and stop it
-    if room.remove_webcam(user_id).await.is_some() {
-        room.broadcast_except(
-            user_id,
-            ServerEvent::WebcamStopped {
-                channel_id,
-                user_id,
-                reason: "disconnected".to_string(),
-            },
-        )
-        .await;
     }
 
-    // Remove peer from room
-    if let Some(peer) = room.remove_peer(user_id).await {
+    if let Some(LeftSession {
+        session_id,
+        connected_at,
+        noise_suppression,
+    }) = left.session
+    {
         // Record voice session end metric
-        let duration_s = (chrono::Utc::now() - peer.connected_at)
+        let duration_s = (chrono::Utc::now() - connected_at)
             .num_milliseconds()
             .max(0) as f64
             / 1000.0;
@@ -307,8 +579,6 @@ async fn handle_leave(
         // Finalize session in background
         let guild_id = get_guild_id(pool, channel_id).await;
         let pool_clone = pool.clone();
-        let session_id = peer.session_id;
-        let connected_at = peer.connected_at;
 
         tokio::spawn(async move {
             // Retry with exponential backoff (3 attempts: 100ms, 200ms, 400ms)
@@ -323,6 +593,7 @@ async fn handle_leave(
                     channel_id,
                     guild_id,
                     connected_at,
+                    noise_suppression,
                 )
                 .await
                 {
@@ -363,6 +634,104 @@ async fn handle_leave(
             }
         });
 
+        sfu.activity_log()
+            .record(user_id, channel_id, VoiceActivityKind::Left)
+            .await;
+    }
+
+    if left.room_empty {
+        if let Err(e) = cluster::rooms::release_room(redis, channel_id, &host_id).await {
+            warn!(channel_id = %channel_id, error = %e, "Failed to release voice room");
+        }
+    }
+
+    info!(
+        user_id = %user_id,
+        channel_id = %channel_id,
+        "User left voice channel"
+    );
+
+    Ok(())
+}
+
+/// Session of a participant that left, for the replica to finalize.
+pub(crate) struct LeftSession {
+    pub session_id: Uuid,
+    pub connected_at: chrono::DateTime<chrono::Utc>,
+    pub noise_suppression: bool,
+}
+
+/// Outcome of removing a participant from its room.
+pub(crate) struct HostLeave {
+    /// The participant's session, if it was in the room.
+    pub session: Option<LeftSession>,
+    /// The participant's screen share was stopped.
+    pub screen_share_stopped: bool,
+    /// The room emptied and was removed.
+    pub room_empty: bool,
+}
+
+/// Remove the participant from the room, close its peer connection and
+/// announce the departure to the room.
+///
+/// Runs on the node hosting the room, like [`host_join`].
+pub(crate) async fn host_leave(
+    sfu: &SfuServer,
+    user_id: Uuid,
+    channel_id: Uuid,
+) -> Result<HostLeave, VoiceError> {
+    let room = sfu
+        .get_room(channel_id)
+        .await
+        .ok_or(VoiceError::RoomNotFound(channel_id))?;
+
+    // Check if sharing screen and stop it
+    let screen_share_stopped = room.remove_screen_share(user_id).await.is_some();
+    if screen_share_stopped {
+        room.broadcast_except(
+            user_id,
+            ServerEvent::ScreenShareStopped {
+                channel_id,
+                user_id,
+                reason: "disconnected".to_string(),
+            },
+        )
+        .await;
+    }
+
+    // Check if webcam is active and stop it
+    if room.remove_webcam(user_id).await.is_some() {
+        room.broadcast_except(
+            user_id,
+            ServerEvent::WebcamStopped {
+                channel_id,
+                user_id,
+                reason: "disconnected".to_string(),
+            },
+        )
+        .await;
+    }
+
+    // Stop capturing the user if a recording is running
+    recording::remove_participant(&room, user_id).await;
+
+    // Remove peer from room
+    let peer = room.remove_peer(user_id).await;
+    let (display_name, name_pronunciation) = match &peer {
+        Some(peer) => (
+            peer.display_name.clone(),
+            peer.name_pronunciation.read().await.clone(),
+        ),
+        None => (String::new(), None),
+    };
+    let mut session = None;
+    if let Some(peer) = peer {
+        session = Some(LeftSession {
+            session_id: peer.session_id,
+            connected_at: peer.connected_at,
+            noise_suppression: peer.noise_suppression().await,
+        });
+
         // Close the peer connection
         if let Err(e) = peer.close().await {
             warn!(error = %e, "Error closing peer connection");
@@ -374,23 +743,26 @@ async fn handle_leave(
         ServerEvent::VoiceUserLeft {
             channel_id,
             user_id,
+            display_name,
+            name_pronunciation,
         },
     )
     .await;
 
-    sfu.cleanup_room_if_empty(channel_id).await;
-
-    info!(
-        user_id = %user_id,
-        channel_id = %channel_id,
-        "User left voice channel"
-    );
+    // Finalize the recording once the last participant is gone
+    if room.is_empty().await {
+        recording::stop_recording(&room, "empty").await;
+    }
 
-    Ok(())
+    Ok(HostLeave {
+        session,
+        screen_share_stopped,
+        room_empty: sfu.cleanup_room_if_empty(channel_id).await,
+    })
 }
 
 /// Handle an SDP answer from a client.
-async fn handle_answer(
+pub(crate) async fn handle_answer(
     sfu: &Arc<SfuServer>,
     user_id: Uuid,
     channel_id: Uuid,
@@ -398,6 +770,11 @@ async fn handle_answer(
 ) -> Result<(), VoiceError> {
     debug!(user_id = %user_id, channel_id = %channel_id, "Received SDP answer");
 
+    // Rooms hosted on a remote media node are signaled through the node
+    if let Some(node) = sfu.remote_nodes().session(channel_id, user_id) {
+        return node.answer(channel_id, user_id, sdp).await;
+    }
+
     let room = sfu
         .get_room(channel_id)
         .await
@@ -420,7 +797,7 @@ async fn handle_answer(
 }
 
 /// Handle an ICE candidate from a client.
-async fn handle_ice_candidate(
+pub(crate) async fn handle_ice_candidate(
     sfu: &Arc<SfuServer>,
     user_id: Uuid,
     channel_id: Uuid,
@@ -428,6 +805,10 @@ async fn handle_ice_candidate(
 ) -> Result<(), VoiceError> {
     debug!(user_id = %user_id, channel_id = %channel_id, "Received ICE candidate");
 
+    if let Some(node) = sfu.remote_nodes().session(channel_id, user_id) {
+        return node.add_ice_candidate(channel_id, user_id, candidate).await;
+    }
+
     let room = sfu
         .get_room(channel_id)
         .await
@@ -444,7 +825,7 @@ async fn handle_ice_candidate(
 }
 
 /// Handle mute/unmute.
-async fn handle_mute(
+pub(crate) async fn handle_mute(
     sfu: &Arc<SfuServer>,
     user_id: Uuid,
     channel_id: Uuid,
@@ -457,6 +838,10 @@ async fn handle_mute(
         "Mute state changed"
     );
 
+    if let Some(node) = sfu.remote_nodes().session(channel_id, user_id) {
+        return node.set_muted(channel_id, user_id, muted).await;
+    }
+
     let room = sfu
         .get_room(channel_id)
         .await
@@ -467,6 +852,10 @@ async fn handle_mute(
         .await
         .ok_or(VoiceError::ParticipantNotFound(user_id))?;
 
+    if !muted && peer.is_listener().await {
+        return Err(VoiceError::Listener);
+    }
+
     peer.set_muted(muted).await;
 
     // Notify other participants
@@ -487,6 +876,150 @@ async fn handle_mute(
     Ok(())
 }
 
+/// Handle a push-to-talk / voice activity mode change.
+async fn handle_activity_mode(
+    sfu: &Arc<SfuServer>,
+    user_id: Uuid,
+    channel_id: Uuid,
+    mode: VoiceActivityMode,
+) -> Result<(), VoiceError> {
+    let room = sfu
+        .get_room(channel_id)
+        .await
+        .ok_or(VoiceError::RoomNotFound(channel_id))?;
+
+    let peer = room
+        .get_peer(user_id)
+        .await
+        .ok_or(VoiceError::ParticipantNotFound(user_id))?;
+
+    if peer.activity_mode().await == mode {
+        return Ok(());
+    }
+    peer.set_activity_mode(mode).await;
+
+    debug!(
+        user_id = %user_id,
+        channel_id = %channel_id,
+        mode = ?mode,
+        "Activity mode changed"
+    );
+
+    room.broadcast_except(
+        user_id,
+        ServerEvent::VoiceActivityModeChanged {
+            channel_id,
+            user_id,
+            mode,
+        },
+    )
+    .await;
+
+    Ok(())
+}
+
+/// Handle a moderator promoting a listener so they can speak.
+///
+/// The listener gets the recv transceivers they joined without and a new
+/// offer; they stay muted until they unmute themselves.
+async fn handle_promote_listener(
+    sfu: &Arc<SfuServer>,
+    pool: &PgPool,
+    moderator_id: Uuid,
+    channel_id: Uuid,
+    listener_id: Uuid,
+) -> Result<(), VoiceError> {
+    let ctx = crate::permissions::require_channel_access(pool, moderator_id, channel_id)
+        .await
+        .map_err(|_e: crate::permissions::PermissionError| VoiceError::Unauthorized)?;
+
+    if !ctx.has_permission(crate::permissions::GuildPermissions::VOICE_MUTE_OTHERS) {
+        return Err(VoiceError::Unauthorized);
+    }
+
+    let room = sfu
+        .get_room(channel_id)
+        .await
+        .ok_or(VoiceError::RoomNotFound(channel_id))?;
+
+    let peer = room
+        .get_peer(listener_id)
+        .await
+        .ok_or(VoiceError::ParticipantNotFound(listener_id))?;
+
+    if !peer.is_listener().await {
+        return Ok(());
+    }
+
+    peer.add_recv_transceivers().await?;
+    peer.set_listener(false).await;
+    peer.set_hand_raised(false).await;
+    SfuServer::renegotiate(&peer).await?;
+
+    room.broadcast_all(ServerEvent::VoiceListenerPromoted {
+        channel_id,
+        user_id: listener_id,
+        promoted_by: moderator_id,
+    })
+    .await;
+
+    info!(
+        user_id = %listener_id,
+        channel_id = %channel_id,
+        promoted_by = %moderator_id,
+        "Listener promoted"
+    );
+
+    Ok(())
+}
+
+/// Handle a listener raising or lowering their hand to ask to speak.
+///
+/// Moderators answer a raised hand with `VoicePromoteListener`. Speakers have
+/// nothing to ask for, so their requests are ignored.
+async fn handle_hand(
+    sfu: &Arc<SfuServer>,
+    user_id: Uuid,
+    channel_id: Uuid,
+    raised: bool,
+) -> Result<(), VoiceError> {
+    let room = sfu
+        .get_room(channel_id)
+        .await
+        .ok_or(VoiceError::RoomNotFound(channel_id))?;
+
+    let peer = room
+        .get_peer(user_id)
+        .await
+        .ok_or(VoiceError::ParticipantNotFound(user_id))?;
+
+    if !peer.is_listener().await || !peer.set_hand_raised(raised).await {
+        return Ok(());
+    }
+
+    let event = if raised {
+        ServerEvent::VoiceHandRaised {
+            channel_id,
+            user_id,
+        }
+    } else {
+        ServerEvent::VoiceHandLowered {
+            channel_id,
+            user_id,
+        }
+    };
+    room.broadcast_all(event).await;
+
+    info!(
+        user_id = %user_id,
+        channel_id = %channel_id,
+        raised = raised,
+        "Listener hand changed"
+    );
+
+    Ok(())
+}
+
 /// Handle voice quality statistics from a client.
 ///
 /// This broadcasts the stats to other participants in the room
@@ -518,22 +1051,31 @@ async fn handle_voice_stats(
         packet_loss: stats.packet_loss,
         jitter: stats.jitter,
         quality: stats.quality,
+        playout: stats.playout,
     };
 
-    if let Some(room) = sfu.get_room(channel_id).await {
+    let peer = if let Some(room) = sfu.get_room(channel_id).await {
         // Verify user is actually in the room before broadcasting
-        if room.get_peer(user_id).await.is_none() {
+        let Some(peer) = room.get_peer(user_id).await else {
             warn!(user_id = %user_id, channel_id = %channel_id, "User attempted to broadcast stats to a room they are not in");
             return Ok(());
-        }
+        };
         room.broadcast_except(user_id, broadcast).await;
-    }
+        Some(peer)
+    } else {
+        None
+    };
 
-    // Store in database (fire-and-forget)
+    // Store in database, then react to sustained packet loss (fire-and-forget)
     let guild_id = get_guild_id(pool, channel_id).await;
     let pool_clone = pool.clone();
+    let sfu = sfu.clone();
     tokio::spawn(async move {
-        store_metrics(pool_clone, stats, user_id, channel_id, guild_id).await;
+        let session_id = stats.session_id;
+        store_metrics(pool_clone.clone(), stats, user_id, channel_id, guild_id).await;
+        if let Some(peer) = peer {
+            loss::check_packet_loss(&sfu, &pool_clone, &peer, session_id).await;
+        }
     });
 
     Ok(())
@@ -587,6 +1129,10 @@ async fn handle_screen_share_start(
         .await
         .ok_or(VoiceError::ParticipantNotFound(params.user_id))?;
 
+    if peer.is_listener().await {
+        return Err(VoiceError::Listener);
+    }
+
     // Check if user is already sharing
     {
         let shares = room.screen_shares.read().await;
@@ -776,6 +1322,10 @@ async fn handle_webcam_start(
         .await
         .ok_or(VoiceError::ParticipantNotFound(user_id))?;
 
+    if peer.is_listener().await {
+        return Err(VoiceError::Listener);
+    }
+
     // Check if user already has webcam active
     {
         let webcams = room.webcams.read().await;
@@ -2,6 +2,7 @@
 
 #[cfg(test)]
 mod tests {
+    use std::collections::HashMap;
     use std::sync::Arc;
 
     use fred::prelude::*;
@@ -10,7 +11,7 @@ mod tests {
     use uuid::Uuid;
 
     use crate::config::Config;
-    use crate::voice::{error, sfu, ws_handler};
+    use crate::voice::{error, sfu, ws_handler, VoiceActivityMode};
     use crate::ws::{ClientEvent, ServerEvent};
 
     /// Helper to create a test Redis client.
@@ -173,7 +174,11 @@ mod tests {
             &pool,
             &redis,
             user_id,
-            ClientEvent::VoiceJoin { channel_id },
+            ClientEvent::VoiceJoin {
+                channel_id,
+                node_rtts: HashMap::new(),
+                noise_suppression: false,
+            },
             &tx,
         )
         .await?;
@@ -196,6 +201,61 @@ mod tests {
         Ok(())
     }
 
+    #[sqlx::test]
+    async fn test_leave_records_noise_suppression_on_session(
+        pool: PgPool,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let user_id = create_test_user(&pool, "quietmic", "Quiet Mic").await?;
+        let guild_id = create_test_guild_with_voice_permissions(&pool, user_id).await?;
+        let channel_id = create_test_channel(&pool, "Quiet Room", guild_id).await?;
+
+        let config = Arc::new(Config::default_for_test());
+        let sfu = Arc::new(sfu::SfuServer::new(config, None)?);
+        let redis = create_test_redis().await;
+        let (tx, _rx) = mpsc::channel::<ServerEvent>(10);
+
+        ws_handler::handle_voice_event(
+            &sfu,
+            &pool,
+            &redis,
+            user_id,
+            ClientEvent::VoiceJoin {
+                channel_id,
+                node_rtts: HashMap::new(),
+                noise_suppression: true,
+            },
+            &tx,
+        )
+        .await?;
+        ws_handler::handle_voice_event(
+            &sfu,
+            &pool,
+            &redis,
+            user_id,
+            ClientEvent::VoiceLeave { channel_id },
+            &tx,
+        )
+        .await?;
+
+        // The session is finalized in the background
+        let mut recorded = None;
+        for _ in 0..20 {
+            recorded = sqlx::query_scalar::<_, Option<bool>>(
+                "SELECT noise_suppression FROM connection_sessions WHERE user_id = $1",
+            )
+            .bind(user_id)
+            .fetch_optional(&pool)
+            .await?;
+            if recorded.is_some() {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+        }
+        assert_eq!(recorded, Some(Some(true)));
+
+        Ok(())
+    }
+
     #[sqlx::test]
     #[ignore = "Requires Redis-backed rate limiter configuration"]
     async fn test_rate_limiting_blocks_rapid_joins(
@@ -226,7 +286,11 @@ mod tests {
             &pool,
             &redis,
             user_id,
-            ClientEvent::VoiceJoin { channel_id },
+            ClientEvent::VoiceJoin {
+                channel_id,
+                node_rtts: HashMap::new(),
+                noise_suppression: false,
+            },
             &tx,
         )
         .await?;
@@ -237,7 +301,11 @@ mod tests {
             &pool,
             &redis,
             user_id,
-            ClientEvent::VoiceJoin { channel_id },
+            ClientEvent::VoiceJoin {
+                channel_id,
+                node_rtts: HashMap::new(),
+                noise_suppression: false,
+            },
             &tx,
         )
         .await;
@@ -287,7 +355,11 @@ mod tests {
             &pool,
             &redis,
             user1_id,
-            ClientEvent::VoiceJoin { channel_id },
+            ClientEvent::VoiceJoin {
+                channel_id,
+                node_rtts: HashMap::new(),
+                noise_suppression: false,
+            },
             &tx1,
         )
         .await?;
@@ -297,7 +369,11 @@ mod tests {
             &pool,
             &redis,
             user2_id,
-            ClientEvent::VoiceJoin { channel_id },
+            ClientEvent::VoiceJoin {
+                channel_id,
+                node_rtts: HashMap::new(),
+                noise_suppression: false,
+            },
             &tx2,
         )
         .await?;
@@ -309,4 +385,571 @@ mod tests {
 
         Ok(())
     }
+
+    #[sqlx::test]
+    async fn test_activity_mode_change_is_broadcast(
+        pool: PgPool,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let user1_id = create_test_user(&pool, "pttuser1", "PTT One").await?;
+        let user2_id = create_test_user(&pool, "pttuser2", "PTT Two").await?;
+        let guild_id = create_test_guild_with_voice_permissions(&pool, user1_id).await?;
+        add_user_to_guild(&pool, guild_id, user2_id).await?;
+        let channel_id = create_test_channel(&pool, "PTT Test", guild_id).await?;
+
+        let config = Arc::new(Config::default_for_test());
+        let sfu = Arc::new(sfu::SfuServer::new(config, None)?);
+        let redis = create_test_redis().await;
+
+        let (tx1, mut rx1) = mpsc::channel::<ServerEvent>(10);
+        let (tx2, _rx2) = mpsc::channel::<ServerEvent>(10);
+
+        for (user_id, tx) in [(user1_id, &tx1), (user2_id, &tx2)] {
+            ws_handler::handle_voice_event(
+                &sfu,
+                &pool,
+                &redis,
+                user_id,
+                ClientEvent::VoiceJoin {
+                    channel_id,
+                    node_rtts: HashMap::new(),
+                    noise_suppression: false,
+                },
+                tx,
+            )
+            .await?;
+        }
+
+        ws_handler::handle_voice_event(
+            &sfu,
+            &pool,
+            &redis,
+            user2_id,
+            ClientEvent::VoiceSetActivityMode {
+                channel_id,
+                mode: VoiceActivityMode::Ptt,
+            },
+            &tx2,
+        )
+        .await?;
+
+        // user1 sees: VoiceOffer, VoiceRoomState, VoiceUserJoined, VoiceActivityModeChanged
+        let mut mode_event = None;
+        while let Ok(event) = rx1.try_recv() {
+            if let ServerEvent::VoiceActivityModeChanged { user_id, mode, .. } = event {
+                mode_event = Some((user_id, mode));
+            }
+        }
+        assert_eq!(mode_event, Some((user2_id, VoiceActivityMode::Ptt)));
+
+        let room = sfu.get_or_create_room(channel_id).await;
+        let participant = room
+            .get_participant_info()
+            .await
+            .into_iter()
+            .find(|p| p.user_id == user2_id)
+            .expect("user2 should be in the room");
+        assert_eq!(participant.activity_mode, VoiceActivityMode::Ptt);
+
+        Ok(())
+    }
+
+    #[sqlx::test]
+    async fn test_join_and_leave_carry_name_pronunciation(
+        pool: PgPool,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let user1_id = create_test_user(&pool, "ttsuser1", "TTS One").await?;
+        let user2_id = create_test_user(&pool, "ttsuser2", "Siobhan").await?;
+        sqlx::query("UPDATE users SET name_pronunciation = 'shi-vawn' WHERE id = $1")
+            .bind(user2_id)
+            .execute(&pool)
+            .await?;
+        let guild_id = create_test_guild_with_voice_permissions(&pool, user1_id).await?;
+        add_user_to_guild(&pool, guild_id, user2_id).await?;
+        let channel_id = create_test_channel(&pool, "TTS Test", guild_id).await?;
+
+        let config = Arc::new(Config::default_for_test());
+        let sfu = Arc::new(sfu::SfuServer::new(config, None)?);
+        let redis = create_test_redis().await;
+
+        let (tx1, mut rx1) = mpsc::channel::<ServerEvent>(10);
+        let (tx2, _rx2) = mpsc::channel::<ServerEvent>(10);
+
+        for (user_id, tx) in [(user1_id, &tx1), (user2_id, &tx2)] {
+            ws_handler::handle_voice_event(
+                &sfu,
+                &pool,
+                &redis,
+                user_id,
+                ClientEvent::VoiceJoin {
+                    channel_id,
+                    node_rtts: HashMap::new(),
+                    noise_suppression: false,
+                },
+                tx,
+            )
+            .await?;
+        }
+        ws_handler::handle_voice_event(
+            &sfu,
+            &pool,
+            &redis,
+            user2_id,
+            ClientEvent::VoiceLeave { channel_id },
+            &tx2,
+        )
+        .await?;
+
+        let (mut joined, mut left) = (None, None);
+        while let Ok(event) = rx1.try_recv() {
+            match event {
+                ServerEvent::VoiceUserJoined {
+                    display_name,
+                    name_pronunciation,
+                    ..
+                } => joined = Some((display_name, name_pronunciation)),
+                ServerEvent::VoiceUserLeft {
+                    display_name,
+                    name_pronunciation,
+                    ..
+                } => left = Some((display_name, name_pronunciation)),
+                _ => {}
+            }
+        }
+        let expected = Some(("Siobhan".to_string(), Some("shi-vawn".to_string())));
+        assert_eq!(joined, expected);
+        assert_eq!(left, expected);
+
+        Ok(())
+    }
+
+    #[sqlx::test]
+    async fn test_listener_joins_receive_only_until_promoted(
+        pool: PgPool,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        // The owner has every permission; @everyone lacks VOICE_SPEAK
+        let owner_id = create_test_user(&pool, "stageowner", "Stage Owner").await?;
+        let listener_id = create_test_user(&pool, "listener", "Listener").await?;
+        let guild_id = create_test_guild_with_voice_permissions(&pool, owner_id).await?;
+        add_user_to_guild(&pool, guild_id, listener_id).await?;
+        let channel_id = create_test_channel(&pool, "Stage", guild_id).await?;
+
+        let config = Arc::new(Config::default_for_test());
+        let sfu = Arc::new(sfu::SfuServer::new(config, None)?);
+        let redis = create_test_redis().await;
+
+        let (owner_tx, _owner_rx) = mpsc::channel::<ServerEvent>(10);
+        let (listener_tx, mut listener_rx) = mpsc::channel::<ServerEvent>(10);
+
+        for (user_id, tx) in [(owner_id, &owner_tx), (listener_id, &listener_tx)] {
+            ws_handler::handle_voice_event(
+                &sfu,
+                &pool,
+                &redis,
+                user_id,
+                ClientEvent::VoiceJoin {
+                    channel_id,
+                    node_rtts: HashMap::new(),
+                    noise_suppression: false,
+                },
+                tx,
+            )
+            .await?;
+        }
+
+        let room = sfu.get_or_create_room(channel_id).await;
+        let is_listener = |user_id: Uuid| {
+            let room = room.clone();
+            async move {
+                room.get_participant_info()
+                    .await
+                    .into_iter()
+                    .find(|p| p.user_id == user_id)
+                    .map(|p| p.listener)
+            }
+        };
+        assert_eq!(is_listener(owner_id).await, Some(false));
+        assert_eq!(is_listener(listener_id).await, Some(true));
+
+        let unmute = ws_handler::handle_voice_event(
+            &sfu,
+            &pool,
+            &redis,
+            listener_id,
+            ClientEvent::VoiceUnmute { channel_id },
+            &listener_tx,
+        )
+        .await;
+        assert!(matches!(unmute, Err(error::VoiceError::Listener)));
+
+        // Listeners cannot promote each other
+        let promote = || ClientEvent::VoicePromoteListener {
+            channel_id,
+            user_id: listener_id,
+        };
+        let result = ws_handler::handle_voice_event(
+            &sfu,
+            &pool,
+            &redis,
+            listener_id,
+            promote(),
+            &listener_tx,
+        )
+        .await;
+        assert!(matches!(result, Err(error::VoiceError::Unauthorized)));
+
+        while listener_rx.try_recv().is_ok() {}
+        ws_handler::handle_voice_event(&sfu, &pool, &redis, owner_id, promote(), &owner_tx).await?;
+        assert_eq!(is_listener(listener_id).await, Some(false));
+
+        // The promoted listener is renegotiated with a mic slot
+        let event = listener_rx.recv().await.expect("Should receive VoiceOffer");
+        assert!(matches!(event, ServerEvent::VoiceOffer { .. }));
+        let event = listener_rx
+            .recv()
+            .await
+            .expect("Should receive VoiceListenerPromoted");
+        assert!(matches!(
+            event,
+            ServerEvent::VoiceListenerPromoted { user_id, promoted_by, .. }
+                if user_id == listener_id && promoted_by == owner_id
+        ));
+
+        Ok(())
+    }
+
+    #[sqlx::test]
+    async fn test_stage_audience_raises_hand_to_speak(
+        pool: PgPool,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        // The owner moderates the stage; the member could speak in a voice channel
+        let owner_id = create_test_user(&pool, "stagehost", "Stage Host").await?;
+        let member_id = create_test_user(&pool, "audience", "Audience").await?;
+        let guild_id = create_test_guild_with_voice_permissions(&pool, owner_id).await?;
+        add_user_to_guild(&pool, guild_id, member_id).await?;
+        sqlx::query(
+            "UPDATE guild_roles SET permissions = permissions | (1::bigint << 6) WHERE guild_id = $1",
+        )
+        .bind(guild_id)
+        .execute(&pool)
+        .await?;
+        let channel_id = create_test_channel(&pool, "Town Hall", guild_id).await?;
+        sqlx::query("UPDATE channels SET channel_type = 'stage' WHERE id = $1")
+            .bind(channel_id)
+            .execute(&pool)
+            .await?;
+
+        let config = Arc::new(Config::default_for_test());
+        let sfu = Arc::new(sfu::SfuServer::new(config, None)?);
+        let redis = create_test_redis().await;
+
+        let (owner_tx, mut owner_rx) = mpsc::channel::<ServerEvent>(10);
+        let (member_tx, _member_rx) = mpsc::channel::<ServerEvent>(10);
+
+        for (user_id, tx) in [(owner_id, &owner_tx), (member_id, &member_tx)] {
+            ws_handler::handle_voice_event(
+                &sfu,
+                &pool,
+                &redis,
+                user_id,
+                ClientEvent::VoiceJoin {
+                    channel_id,
+                    node_rtts: HashMap::new(),
+                    noise_suppression: false,
+                },
+                tx,
+            )
+            .await?;
+        }
+
+        let room = sfu.get_or_create_room(channel_id).await;
+        let state_of = |user_id: Uuid| {
+            let room = room.clone();
+            async move {
+                room.get_participant_info()
+                    .await
+                    .into_iter()
+                    .find(|p| p.user_id == user_id)
+                    .map(|p| (p.listener, p.hand_raised))
+            }
+        };
+        assert_eq!(state_of(owner_id).await, Some((false, false)));
+        assert_eq!(state_of(member_id).await, Some((true, false)));
+
+        while owner_rx.try_recv().is_ok() {}
+        ws_handler::handle_voice_event(
+            &sfu,
+            &pool,
+            &redis,
+            member_id,
+            ClientEvent::VoiceRaiseHand { channel_id },
+            &member_tx,
+        )
+        .await?;
+        assert_eq!(state_of(member_id).await, Some((true, true)));
+        let event = owner_rx
+            .recv()
+            .await
+            .expect("Should receive VoiceHandRaised");
+        assert!(matches!(
+            event,
+            ServerEvent::VoiceHandRaised { user_id, .. } if user_id == member_id
+        ));
+
+        // Speakers have nothing to ask for
+        ws_handler::handle_voice_event(
+            &sfu,
+            &pool,
+            &redis,
+            owner_id,
+            ClientEvent::VoiceRaiseHand { channel_id },
+            &owner_tx,
+        )
+        .await?;
+        assert_eq!(state_of(owner_id).await, Some((false, false)));
+
+        ws_handler::handle_voice_event(
+            &sfu,
+            &pool,
+            &redis,
+            owner_id,
+            ClientEvent::VoicePromoteListener {
+                channel_id,
+                user_id: member_id,
+            },
+            &owner_tx,
+        )
+        .await?;
+        assert_eq!(state_of(member_id).await, Some((false, false)));
+
+        Ok(())
+    }
+
+    #[sqlx::test]
+    async fn test_user_gain_is_saved_in_preferences(
+        pool: PgPool,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let user_id = create_test_user(&pool, "listener", "Listener").await?;
+        let loud_id = create_test_user(&pool, "loudspeaker", "Loud Speaker").await?;
+
+        let config = Arc::new(Config::default_for_test());
+        let sfu = Arc::new(sfu::SfuServer::new(config, None)?);
+        let redis = create_test_redis().await;
+        let (tx, _rx) = mpsc::channel::<ServerEvent>(10);
+
+        let set_gain = |target_user: Uuid, gain: f32| {
+            ws_handler::handle_voice_event(
+                &sfu,
+                &pool,
+                &redis,
+                user_id,
+                ClientEvent::VoiceSetUserGain { target_user, gain },
+                &tx,
+            )
+        };
+        let pool_ref = &pool;
+        let saved_gains = || async move {
+            sqlx::query_scalar::<_, serde_json::Value>(
+                "SELECT preferences->'voice_user_gains' FROM user_preferences WHERE user_id = $1",
+            )
+            .bind(user_id)
+            .fetch_one(pool_ref)
+            .await
+        };
+
+        set_gain(loud_id, 0.4).await?;
+        assert_eq!(
+            saved_gains().await?,
+            serde_json::json!({ loud_id.to_string(): 0.4 })
+        );
+
+        // Out of range gains and the user's own audio are rejected
+        assert!(set_gain(loud_id, 2.5).await.is_err());
+        assert!(set_gain(loud_id, f32::NAN).await.is_err());
+        assert!(set_gain(user_id, 0.5).await.is_err());
+
+        // Back to 100% removes the entry
+        set_gain(loud_id, 1.0).await?;
+        assert_eq!(saved_gains().await?, serde_json::json!({}));
+
+        Ok(())
+    }
+
+    #[sqlx::test]
+    async fn test_join_redirects_to_preferred_region_until_node_fails(
+        pool: PgPool,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let user_id = create_test_user(&pool, "traveller", "Traveller").await?;
+        let guild_id = create_test_guild_with_voice_permissions(&pool, user_id).await?;
+        let channel_id = create_test_channel(&pool, "Regional", guild_id).await?;
+        let region = format!("test-{}", &Uuid::new_v4().simple().to_string()[..12]);
+        sqlx::query("UPDATE guilds SET voice_region = $1 WHERE id = $2")
+            .bind(&region)
+            .bind(guild_id)
+            .execute(&pool)
+            .await?;
+
+        let config = Arc::new(Config::default_for_test());
+        let sfu = Arc::new(sfu::SfuServer::new(config.clone(), None)?);
+        let redis = create_test_redis().await;
+        let (tx, _rx) = mpsc::channel::<ServerEvent>(10);
+        let join = || {
+            ws_handler::handle_voice_event(
+                &sfu,
+                &pool,
+                &redis,
+                user_id,
+                ClientEvent::VoiceJoin {
+                    channel_id,
+                    node_rtts: HashMap::new(),
+                    noise_suppression: false,
+                },
+                &tx,
+            )
+        };
+
+        // A live node in the guild's region hosts new rooms
+        let regional_node = format!("node-{region}");
+        let now = chrono::Utc::now();
+        let info = serde_json::json!({
+            "node_id": regional_node,
+            "version": "test",
+            "started_at": now,
+            "last_heartbeat": now,
+            "ws_connections": 0,
+            "voice_rooms": 0,
+            "voice_participants": 0,
+            "region": region,
+        });
+        redis
+            .set::<(), _, _>(
+                format!("cluster:node:{regional_node}"),
+                info.to_string(),
+                None,
+                None,
+                false,
+            )
+            .await?;
+        redis
+            .zadd::<(), _, _>(
+                "cluster:nodes",
+                None,
+                None,
+                false,
+                false,
+                (
+                    (now.timestamp_millis() + 60_000) as f64,
+                    regional_node.as_str(),
+                ),
+            )
+            .await?;
+
+        match join().await {
+            Err(error::VoiceError::RoomOnOtherNode(node)) => assert_eq!(node, regional_node),
+            other => panic!("Expected a redirect to the regional node, got {other:?}"),
+        }
+
+        // Once the node stops heartbeating the room is hosted here
+        redis
+            .zrem::<(), _, _>("cluster:nodes", regional_node.as_str())
+            .await?;
+        redis
+            .del::<(), _>(format!("cluster:node:{regional_node}"))
+            .await?;
+        join().await?;
+
+        crate::cluster::rooms::release_room(&redis, channel_id, &config.node_id).await?;
+        Ok(())
+    }
+
+    #[sqlx::test]
+    async fn test_sustained_packet_loss_warns_and_caps_bitrate(
+        pool: PgPool,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let user_id = create_test_user(&pool, "lossy", "Lossy Link").await?;
+        let guild_id = create_test_guild_with_voice_permissions(&pool, user_id).await?;
+        let channel_id = create_test_channel(&pool, "Lossy Room", guild_id).await?;
+
+        let config = Arc::new(Config::default_for_test());
+        let sfu = Arc::new(sfu::SfuServer::new(config.clone(), None)?);
+        let redis = create_test_redis().await;
+        let (tx, mut rx) = mpsc::channel::<ServerEvent>(20);
+
+        ws_handler::handle_voice_event(
+            &sfu,
+            &pool,
+            &redis,
+            user_id,
+            ClientEvent::VoiceJoin {
+                channel_id,
+                node_rtts: HashMap::new(),
+                noise_suppression: false,
+            },
+            &tx,
+        )
+        .await?;
+
+        // Earlier reports of the same session, already stored
+        let session_id = Uuid::now_v7();
+        for _ in 0..2 {
+            sqlx::query(
+                "INSERT INTO connection_metrics
+                 (time, user_id, session_id, channel_id, guild_id, latency_ms, packet_loss, jitter_ms, quality)
+                 VALUES (NOW(), $1, $2, $3, $4, 80, 12.0, 20, 1)",
+            )
+            .bind(user_id)
+            .bind(session_id)
+            .bind(channel_id)
+            .bind(guild_id)
+            .execute(&pool)
+            .await?;
+        }
+
+        ws_handler::handle_voice_event(
+            &sfu,
+            &pool,
+            &redis,
+            user_id,
+            ClientEvent::VoiceStats {
+                channel_id,
+                session_id,
+                latency: 80,
+                packet_loss: 15.0,
+                jitter: 20,
+                quality: 1,
+                timestamp: chrono::Utc::now().timestamp_millis(),
+                playout: crate::voice::PlayoutStats::default(),
+            },
+            &tx,
+        )
+        .await?;
+
+        // The loss is checked in the background after the report is stored
+        let warning = tokio::time::timeout(std::time::Duration::from_secs(5), async {
+            while let Some(event) = rx.recv().await {
+                if let ServerEvent::VoiceQualityWarning {
+                    packet_loss,
+                    audio_bitrate,
+                    ..
+                } = event
+                {
+                    return Some((packet_loss, audio_bitrate));
+                }
+            }
+            None
+        })
+        .await?;
+        let (packet_loss, audio_bitrate) = warning.expect("Should receive VoiceQualityWarning");
+        assert!((packet_loss - 13.0).abs() < 0.01);
+        assert_eq!(audio_bitrate, config.voice_loss_audio_bitrate);
+
+        // The lower cap applies to the participant's next offers
+        let room = sfu.get_room(channel_id).await.expect("Room should exist");
+        let peer = room.get_peer(user_id).await.expect("Peer should exist");
+        assert_eq!(
+            peer.effective_audio_bitrate().await,
+            Some(config.voice_loss_audio_bitrate)
+        );
+
+        Ok(())
+    }
 }
@@ -115,3 +115,43 @@ pub async fn dispatch_command_event(
         }
     }
 }
+
+/// Dispatch an audit.logged event to a guild's log stream, if it has one.
+pub async fn dispatch_guild_log_event(
+    db: &PgPool,
+    redis: &Client,
+    guild_id: Uuid,
+    event_id: Uuid,
+    payload: serde_json::Value,
+) {
+    let (webhook_id, url) = match queries::find_guild_log_stream(db, guild_id).await {
+        Ok(Some(stream)) => stream,
+        Ok(None) => return,
+        Err(e) => {
+            warn!(
+                guild_id = %guild_id,
+                error = %e,
+                "Failed to find guild log stream"
+            );
+            return;
+        }
+    };
+
+    let item = WebhookDeliveryItem {
+        webhook_id,
+        url,
+        event_type: BotEventType::AuditLogged,
+        event_id,
+        payload,
+        attempt: 0,
+        event_time: chrono::Utc::now(),
+    };
+
+    if let Err(e) = delivery::enqueue(redis, &item).await {
+        error!(
+            webhook_id = %webhook_id,
+            event_id = %event_id,
+            "Failed to enqueue guild log stream delivery: {}", e
+        );
+    }
+}
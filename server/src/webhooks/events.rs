@@ -26,6 +26,10 @@ pub enum BotEventType {
     #[serde(rename = "command.invoked")]
     #[sqlx(rename = "command.invoked")]
     CommandInvoked,
+    /// A guild audit log entry was written (guild log streams only).
+    #[serde(rename = "audit.logged")]
+    #[sqlx(rename = "audit.logged")]
+    AuditLogged,
 }
 
 impl BotEventType {
@@ -36,6 +40,7 @@ impl BotEventType {
             "member.joined" => Some(Self::MemberJoined),
             "member.left" => Some(Self::MemberLeft),
             "command.invoked" => Some(Self::CommandInvoked),
+            "audit.logged" => Some(Self::AuditLogged),
             _ => None,
         }
     }
@@ -47,6 +52,7 @@ impl BotEventType {
             Self::MemberJoined => "member.joined",
             Self::MemberLeft => "member.left",
             Self::CommandInvoked => "command.invoked",
+            Self::AuditLogged => "audit.logged",
         }
     }
 }
@@ -115,6 +121,8 @@ impl GatewayIntent {
                 // Commands are always permitted (default intent)
                 true
             }
+            // Only delivered to guild log streams, never to bots
+            BotEventType::AuditLogged => false,
         }
     }
 }
@@ -8,6 +8,7 @@ use axum::Json;
 use tracing::{info, instrument, warn};
 use uuid::Uuid;
 
+use super::events::BotEventType;
 use super::types::{
     CreateWebhookRequest, DeliveryLogEntry, TestDeliveryResult, UpdateWebhookRequest,
     WebhookCreatedResponse, WebhookError, WebhookResponse,
@@ -40,50 +41,58 @@ async fn verify_ownership(
 
 /// Get the MFA encryption key bytes from config, or return an error if not configured.
 fn get_encryption_key(state: &AppState) -> Result<Vec<u8>, WebhookError> {
-    let key_hex = state.config.mfa_encryption_key.as_ref().ok_or_else(|| {
-        WebhookError::Validation(
-            "Server encryption key not configured (MFA_ENCRYPTION_KEY)".to_string(),
-        )
-    })?;
-    let key = hex::decode(key_hex)
-        .map_err(|_| WebhookError::Validation("Server encryption key misconfigured".to_string()))?;
+    signing_secret_key(&state.config()).map_err(WebhookError::Validation)
+}
+
+/// The key signing secrets are encrypted with at rest (`MFA_ENCRYPTION_KEY`),
+/// or why it cannot be used.
+pub(crate) fn signing_secret_key(config: &crate::config::Config) -> Result<Vec<u8>, String> {
+    let key_hex = config
+        .mfa_encryption_key
+        .as_ref()
+        .ok_or("Server encryption key not configured (MFA_ENCRYPTION_KEY)")?;
+    let key = hex::decode(key_hex).map_err(|_| "Server encryption key misconfigured")?;
     if key.len() != 32 {
         tracing::error!(
             actual_len = key.len(),
             "MFA_ENCRYPTION_KEY has wrong length (expected 32 bytes)"
         );
-        return Err(WebhookError::Validation(
-            "Server encryption key misconfigured".to_string(),
-        ));
+        return Err("Server encryption key misconfigured".to_string());
     }
     Ok(key)
 }
 
-/// Validate a URL for webhook delivery (includes SSRF protection).
-fn validate_url(url: &str) -> Result<(), WebhookError> {
-    if url.len() < 10 || url.len() > 2048 {
+/// Validate the events a bot webhook subscribes to.
+fn validate_events(events: &[BotEventType]) -> Result<(), WebhookError> {
+    if events.is_empty() {
         return Err(WebhookError::Validation(
-            "URL must be between 10 and 2048 characters".to_string(),
+            "At least one subscribed event is required".to_string(),
         ));
     }
-    if !url.starts_with("http://") && !url.starts_with("https://") {
+    if events.contains(&BotEventType::AuditLogged) {
         return Err(WebhookError::Validation(
-            "URL must start with http:// or https://".to_string(),
+            "audit.logged is only delivered to guild log streams".to_string(),
         ));
     }
+    Ok(())
+}
+
+/// Validate a URL for webhook delivery (includes SSRF protection).
+pub(crate) fn validate_url(url: &str) -> Result<(), String> {
+    if url.len() < 10 || url.len() > 2048 {
+        return Err("URL must be between 10 and 2048 characters".to_string());
+    }
+    if !url.starts_with("http://") && !url.starts_with("https://") {
+        return Err("URL must start with http:// or https://".to_string());
+    }
 
     // SSRF protection: block private/reserved hostnames
-    let parsed = reqwest::Url::parse(url)
-        .map_err(|_| WebhookError::Validation("Invalid URL format".to_string()))?;
+    let parsed = reqwest::Url::parse(url).map_err(|_| "Invalid URL format")?;
 
-    let host = parsed
-        .host_str()
-        .ok_or_else(|| WebhookError::Validation("URL must contain a host".to_string()))?;
+    let host = parsed.host_str().ok_or("URL must contain a host")?;
 
     if super::ssrf::is_blocked_host(host) {
-        return Err(WebhookError::Validation(
-            "URL must not point to a private or reserved address".to_string(),
-        ));
+        return Err("URL must not point to a private or reserved address".to_string());
     }
 
     Ok(())
@@ -111,14 +120,9 @@ pub async fn create_webhook(
     verify_ownership(&state.db, app_id, claims.id).await?;
 
     // Validate
-    validate_url(&req.url)?;
+    validate_url(&req.url).map_err(WebhookError::Validation)?;
 
-    if req.subscribed_events.is_empty() {
-        return Err(WebhookError::Validation(
-            "At least one subscribed event is required".to_string(),
-        )
-        .into());
-    }
+    validate_events(&req.subscribed_events)?;
 
     if let Some(ref desc) = req.description {
         if desc.len() > 500 {
@@ -133,7 +137,7 @@ pub async fn create_webhook(
     let count = queries::count_webhooks(&state.db, app_id)
         .await
         .map_err(WebhookError::Database)?;
-    if count >= state.config.max_webhooks_per_app {
+    if count >= state.config().max_webhooks_per_app {
         return Err(WebhookError::MaxWebhooksReached.into());
     }
 
@@ -254,16 +258,11 @@ pub async fn update_webhook(
     verify_ownership(&state.db, app_id, claims.id).await?;
 
     if let Some(ref url) = req.url {
-        validate_url(url)?;
+        validate_url(url).map_err(WebhookError::Validation)?;
     }
 
     if let Some(ref events) = req.subscribed_events {
-        if events.is_empty() {
-            return Err(WebhookError::Validation(
-                "At least one subscribed event is required".to_string(),
-            )
-            .into());
-        }
+        validate_events(events)?;
     }
 
     if let Some(ref desc) = req.description {
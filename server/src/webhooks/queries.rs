@@ -10,7 +10,7 @@ use tracing::error;
 use uuid::Uuid;
 
 use super::events::BotEventType;
-use super::types::{DeliveryLogEntry, Webhook, WebhookResponse};
+use super::types::{DeadLetterEntry, DeliveryLogEntry, Webhook, WebhookResponse};
 
 /// Create a webhook.
 pub async fn create_webhook(
@@ -257,6 +257,93 @@ pub async fn insert_dead_letter(
     Ok(())
 }
 
+const DEAD_LETTER_COLUMNS: &str = r"
+    d.id, d.webhook_id, w.application_id, w.guild_id, w.url, w.active AS webhook_active,
+    d.event_type, d.event_id, d.payload, d.attempts, d.last_error,
+    d.event_time, d.created_at
+";
+
+/// Count dead letters, optionally for one webhook.
+pub async fn count_dead_letters(pool: &PgPool, webhook_id: Option<Uuid>) -> sqlx::Result<i64> {
+    let (count,): (i64,) = sqlx::query_as(
+        "SELECT COUNT(*) FROM webhook_dead_letters WHERE $1::uuid IS NULL OR webhook_id = $1",
+    )
+    .bind(webhook_id)
+    .fetch_one(pool)
+    .await?;
+    Ok(count)
+}
+
+/// List dead letters (newest first), optionally for one webhook.
+pub async fn list_dead_letters(
+    pool: &PgPool,
+    webhook_id: Option<Uuid>,
+    limit: i64,
+    offset: i64,
+) -> sqlx::Result<Vec<DeadLetterEntry>> {
+    sqlx::query_as::<_, DeadLetterEntry>(&format!(
+        r"
+        SELECT {DEAD_LETTER_COLUMNS}
+        FROM webhook_dead_letters d
+        JOIN webhooks w ON w.id = d.webhook_id
+        WHERE $1::uuid IS NULL OR d.webhook_id = $1
+        ORDER BY d.created_at DESC
+        LIMIT $2 OFFSET $3
+        "
+    ))
+    .bind(webhook_id)
+    .bind(limit)
+    .bind(offset)
+    .fetch_all(pool)
+    .await
+}
+
+/// Get a dead letter by ID.
+pub async fn get_dead_letter(pool: &PgPool, id: Uuid) -> sqlx::Result<Option<DeadLetterEntry>> {
+    sqlx::query_as::<_, DeadLetterEntry>(&format!(
+        r"
+        SELECT {DEAD_LETTER_COLUMNS}
+        FROM webhook_dead_letters d
+        JOIN webhooks w ON w.id = d.webhook_id
+        WHERE d.id = $1
+        "
+    ))
+    .bind(id)
+    .fetch_optional(pool)
+    .await
+}
+
+/// Oldest dead letters of a webhook, up to `limit`.
+pub async fn oldest_dead_letters(
+    pool: &PgPool,
+    webhook_id: Uuid,
+    limit: i64,
+) -> sqlx::Result<Vec<DeadLetterEntry>> {
+    sqlx::query_as::<_, DeadLetterEntry>(&format!(
+        r"
+        SELECT {DEAD_LETTER_COLUMNS}
+        FROM webhook_dead_letters d
+        JOIN webhooks w ON w.id = d.webhook_id
+        WHERE d.webhook_id = $1
+        ORDER BY d.created_at ASC
+        LIMIT $2
+        "
+    ))
+    .bind(webhook_id)
+    .bind(limit)
+    .fetch_all(pool)
+    .await
+}
+
+/// Delete a dead letter. Returns whether it existed.
+pub async fn delete_dead_letter(pool: &PgPool, id: Uuid) -> sqlx::Result<bool> {
+    let result = sqlx::query("DELETE FROM webhook_dead_letters WHERE id = $1")
+        .bind(id)
+        .execute(pool)
+        .await?;
+    Ok(result.rows_affected() > 0)
+}
+
 /// Look up the signing secret for a webhook by ID.
 pub async fn get_signing_secret(pool: &PgPool, webhook_id: Uuid) -> sqlx::Result<Option<String>> {
     let row: Option<(String,)> =
@@ -321,6 +408,17 @@ pub async fn find_guild_webhooks_for_event(
     })
 }
 
+/// Find a guild's active log stream, as its webhook ID and URL.
+pub async fn find_guild_log_stream(
+    pool: &PgPool,
+    guild_id: Uuid,
+) -> sqlx::Result<Option<(Uuid, String)>> {
+    sqlx::query_as("SELECT id, url FROM webhooks WHERE guild_id = $1 AND active = true")
+        .bind(guild_id)
+        .fetch_optional(pool)
+        .await
+}
+
 /// Find active webhooks for a specific application that subscribe to an event type.
 pub async fn find_app_webhooks_for_event(
     pool: &PgPool,
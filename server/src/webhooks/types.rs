@@ -82,6 +82,27 @@ pub struct DeliveryLogEntry {
     pub created_at: DateTime<Utc>,
 }
 
+/// Dead-lettered delivery, with the webhook it was addressed to.
+#[derive(Debug, Serialize, sqlx::FromRow, utoipa::ToSchema)]
+pub struct DeadLetterEntry {
+    pub id: Uuid,
+    pub webhook_id: Uuid,
+    /// Bot application owning the webhook (`None` for a guild log stream).
+    pub application_id: Option<Uuid>,
+    /// Guild owning the webhook when it is a guild log stream.
+    pub guild_id: Option<Uuid>,
+    pub url: String,
+    pub webhook_active: bool,
+    pub event_type: BotEventType,
+    pub event_id: Uuid,
+    #[schema(value_type = Object)]
+    pub payload: serde_json::Value,
+    pub attempts: i32,
+    pub last_error: Option<String>,
+    pub event_time: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+}
+
 /// Test delivery result.
 #[derive(Debug, Serialize, utoipa::ToSchema)]
 pub struct TestDeliveryResult {
@@ -82,7 +82,7 @@ pub async fn create_workspace(
     .bind(auth_user.id)
     .bind(&request.name)
     .bind(&request.icon)
-    .bind(state.config.max_workspaces_per_user)
+    .bind(state.config().max_workspaces_per_user)
     .fetch_optional(&mut *tx)
     .await?
     .ok_or(WorkspaceError::WorkspaceLimitExceeded)?;
@@ -413,7 +413,7 @@ pub async fn add_entry(
     .bind(workspace_id)
     .bind(request.guild_id)
     .bind(request.channel_id)
-    .bind(state.config.max_entries_per_workspace)
+    .bind(state.config().max_entries_per_workspace)
     .fetch_optional(&mut *tx)
     .await;
 
@@ -551,10 +551,10 @@ pub async fn reorder_entries(
     Path(workspace_id): Path<Uuid>,
     Json(request): Json<ReorderEntriesRequest>,
 ) -> Result<StatusCode, WorkspaceError> {
-    if request.entry_ids.len() > state.config.max_entries_per_workspace as usize {
+    if request.entry_ids.len() > state.config().max_entries_per_workspace as usize {
         return Err(WorkspaceError::Validation(format!(
             "entry_ids must contain at most {} items",
-            state.config.max_entries_per_workspace
+            state.config().max_entries_per_workspace
         )));
     }
 
@@ -648,10 +648,10 @@ pub async fn reorder_workspaces(
     auth_user: AuthUser,
     Json(request): Json<ReorderWorkspacesRequest>,
 ) -> Result<StatusCode, WorkspaceError> {
-    if request.workspace_ids.len() > state.config.max_workspaces_per_user as usize {
+    if request.workspace_ids.len() > state.config().max_workspaces_per_user as usize {
         return Err(WorkspaceError::Validation(format!(
             "workspace_ids must contain at most {} items",
-            state.config.max_workspaces_per_user
+            state.config().max_workspaces_per_user
         )));
     }
 
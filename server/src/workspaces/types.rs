@@ -128,6 +128,7 @@ impl From<WorkspaceEntryRow> for WorkspaceEntryResponse {
             channel_type: match row.channel_type {
                 ChannelType::Text => "text".to_string(),
                 ChannelType::Voice => "voice".to_string(),
+                ChannelType::Stage => "stage".to_string(),
                 ChannelType::Dm => "dm".to_string(),
             },
             created_at: row.created_at,
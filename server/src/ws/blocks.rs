@@ -0,0 +1,195 @@
+//! Per-connection Block Set
+//!
+//! Each connection keeps the users it must not hear from: everyone the user
+//! blocked and everyone who blocked the user. The set is loaded from the
+//! database when the connection opens and kept current by the events that
+//! announce block changes on the user's own topic: `UserBlocked` and
+//! `UserUnblocked` for the blocker's sessions, `RelationshipUpdate` for both
+//! sides. Unblocks and relationship updates are re-checked in the Redis block
+//! cache, since the other side may still block the user.
+
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use fred::prelude::*;
+use sqlx::PgPool;
+use tokio::sync::RwLock;
+use tracing::{debug, warn};
+use uuid::Uuid;
+
+use super::ServerEvent;
+use crate::social::block_cache;
+
+/// Users whose events are hidden from one connection.
+#[derive(Clone)]
+pub struct BlockSet {
+    users: Arc<RwLock<HashSet<Uuid>>>,
+}
+
+impl BlockSet {
+    /// Load everyone `user_id` blocked or is blocked by.
+    ///
+    /// A failed load is logged and leaves that direction empty.
+    pub async fn load(db: &PgPool, redis: &Client, user_id: Uuid) -> Self {
+        let blocked = block_cache::load_blocked_users(db, redis, user_id)
+            .await
+            .unwrap_or_else(|e| {
+                warn!("Failed to load blocked users for {}: {}", user_id, e);
+                HashSet::new()
+            });
+        let blocked_by = block_cache::load_blocked_by(db, redis, user_id)
+            .await
+            .unwrap_or_else(|e| {
+                warn!("Failed to load blocked-by for {}: {}", user_id, e);
+                HashSet::new()
+            });
+        debug!(
+            "User {} has blocked {} users and is blocked by {}",
+            user_id,
+            blocked.len(),
+            blocked_by.len()
+        );
+
+        Self::from_users(blocked.union(&blocked_by).copied().collect())
+    }
+
+    fn from_users(users: HashSet<Uuid>) -> Self {
+        Self {
+            users: Arc::new(RwLock::new(users)),
+        }
+    }
+
+    /// Whether `event` comes from a blocked user and must not be delivered.
+    pub async fn hides(&self, event: &ServerEvent) -> bool {
+        let Some(actor) = actor(event) else {
+            return false;
+        };
+        self.users.read().await.contains(&actor)
+    }
+
+    /// Update the set from a block change on the user's own topic.
+    pub async fn apply(&self, redis: &Client, user_id: Uuid, event: &ServerEvent) {
+        match event {
+            ServerEvent::UserBlocked {
+                user_id: blocked_id,
+            } => {
+                self.users.write().await.insert(*blocked_id);
+            }
+            // The other side may still block us, and the other side of a block
+            // only learns of it through the relationship update
+            ServerEvent::UserUnblocked { user_id: other_id }
+            | ServerEvent::RelationshipUpdate {
+                user_id: other_id, ..
+            } => {
+                self.sync(redis, user_id, *other_id).await;
+            }
+            _ => {}
+        }
+    }
+
+    /// Re-check whether either user blocks the other.
+    async fn sync(&self, redis: &Client, user_id: Uuid, other_id: Uuid) {
+        match block_cache::is_blocked_either_direction(redis, user_id, other_id).await {
+            Ok(true) => {
+                self.users.write().await.insert(other_id);
+            }
+            Ok(false) => {
+                self.users.write().await.remove(&other_id);
+            }
+            Err(e) => {
+                warn!(error = %e, %user_id, %other_id, "Failed to re-check block");
+            }
+        }
+    }
+}
+
+/// The user who caused an event, for events hidden across blocks.
+fn actor(event: &ServerEvent) -> Option<Uuid> {
+    match event {
+        ServerEvent::MessageNew { message, .. } | ServerEvent::ThreadReplyNew { message, .. } => {
+            message
+                .get("author")
+                .and_then(|a| a.get("id"))
+                .and_then(|id| id.as_str())
+                .and_then(|id| Uuid::parse_str(id).ok())
+        }
+        ServerEvent::TypingStart { user_id, .. }
+        | ServerEvent::TypingStop { user_id, .. }
+        | ServerEvent::VoiceUserJoined { user_id, .. }
+        | ServerEvent::VoiceUserLeft { user_id, .. }
+        | ServerEvent::CallParticipantJoined { user_id, .. }
+        | ServerEvent::CallParticipantLeft { user_id, .. }
+        | ServerEvent::PresenceUpdate { user_id, .. }
+        | ServerEvent::RichPresenceUpdate { user_id, .. } => Some(*user_id),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message_from(author_id: Uuid) -> serde_json::Value {
+        serde_json::json!({ "id": Uuid::new_v4(), "author": { "id": author_id } })
+    }
+
+    #[tokio::test]
+    async fn hides_events_from_blocked_users() {
+        let blocked = Uuid::new_v4();
+        let set = BlockSet::from_users(HashSet::from([blocked]));
+        let channel_id = Uuid::new_v4();
+
+        for event in [
+            ServerEvent::MessageNew {
+                channel_id,
+                message: message_from(blocked),
+            },
+            ServerEvent::ThreadReplyNew {
+                channel_id,
+                parent_id: Uuid::new_v4(),
+                message: message_from(blocked),
+                thread_info: serde_json::json!({}),
+            },
+            ServerEvent::TypingStart {
+                channel_id,
+                user_id: blocked,
+            },
+            ServerEvent::PresenceUpdate {
+                user_id: blocked,
+                status: "online".to_string(),
+            },
+        ] {
+            assert!(set.hides(&event).await, "{event:?} should be hidden");
+        }
+    }
+
+    #[tokio::test]
+    async fn delivers_events_from_other_users() {
+        let set = BlockSet::from_users(HashSet::from([Uuid::new_v4()]));
+        let other = Uuid::new_v4();
+        let channel_id = Uuid::new_v4();
+
+        assert!(
+            !set.hides(&ServerEvent::MessageNew {
+                channel_id,
+                message: message_from(other),
+            })
+            .await
+        );
+        assert!(
+            !set.hides(&ServerEvent::TypingStart {
+                channel_id,
+                user_id: other,
+            })
+            .await
+        );
+        // Messages without an author (system messages) always go through
+        assert!(
+            !set.hides(&ServerEvent::MessageNew {
+                channel_id,
+                message: serde_json::json!({ "id": Uuid::new_v4() }),
+            })
+            .await
+        );
+    }
+}
@@ -9,13 +9,14 @@ use axum::extract::ws::{Message, WebSocket};
 use axum::extract::{State, WebSocketUpgrade};
 use axum::http::{HeaderMap, StatusCode};
 use axum::response::Response;
-use fred::interfaces::{ClientLike, EventInterface, KeysInterface, PubsubInterface};
+use fred::interfaces::{ClientLike, EventInterface, PubsubInterface};
 use futures::{SinkExt, StreamExt};
 use serde::{Deserialize, Serialize};
 use sqlx::PgPool;
 use tracing::{error, info, instrument, warn};
 use uuid::Uuid;
 
+use crate::api::interactions::{self, InteractionError};
 use crate::api::AppState;
 use crate::ratelimit::RateLimitCategory;
 
@@ -116,7 +117,7 @@ pub enum BotServerEvent {
 ///
 /// Token format: `bot_user_id.secret` to enable indexed lookup
 #[instrument(skip(pool, token))]
-async fn authenticate_bot_token(
+pub(crate) async fn authenticate_bot_token(
     pool: &PgPool,
     token: &str,
 ) -> Result<(Uuid, Uuid), (StatusCode, String)> {
@@ -173,7 +174,7 @@ async fn authenticate_bot_token(
 }
 
 /// Extract bot token from WebSocket upgrade request.
-fn extract_bot_token(headers: &axum::http::HeaderMap) -> Option<String> {
+pub(crate) fn extract_bot_token(headers: &axum::http::HeaderMap) -> Option<String> {
     headers
         .get(axum::http::header::AUTHORIZATION)
         .and_then(|v| v.to_str().ok())
@@ -318,6 +319,11 @@ async fn handle_bot_socket(
             if let Message::Text(text) = msg {
                 match serde_json::from_str::<BotClientEvent>(&text) {
                     Ok(event) => {
+                        let channel_id = match &event {
+                            BotClientEvent::MessageCreate { channel_id, .. } => Some(*channel_id),
+                            BotClientEvent::CommandResponse { .. } => None,
+                        };
+                        crate::observability::consumers::record_ws_event(bot_user_id, channel_id);
                         if let Err(e) = handle_bot_event(event, &state_clone, bot_user_id).await {
                             error!("Error handling bot event: {}", e);
                             let _ = error_tx.send(BotServerEvent::Error {
@@ -483,206 +489,18 @@ async fn handle_bot_event(
             interaction_id,
             content,
             ephemeral,
-        } => {
-            // Validate content length
-            if let Err(e) = crate::chat::messages::validate_message_content(&content) {
-                return Err(e
-                    .message
-                    .as_ref()
-                    .map(|m| m.to_string())
-                    .unwrap_or_else(|| "Invalid response content".to_string()));
-            }
-
-            info!(
-                interaction_id = %interaction_id,
-                ephemeral = ephemeral,
-                "Bot responding to command"
-            );
-
-            let owner_key = format!("interaction:{interaction_id}:owner");
-            let expected_owner = state
-                .redis
-                .get::<Option<String>, _>(&owner_key)
-                .await
-                .map_err(|e| {
+        } => interactions::respond(state, bot_user_id, interaction_id, content, ephemeral)
+            .await
+            .map_err(|e| match e {
+                InteractionError::Database(_) | InteractionError::Redis(_) => {
                     error!(
                         interaction_id = %interaction_id,
                         error = %e,
-                        "Failed to fetch interaction owner"
+                        "Failed to deliver command response"
                     );
-                    "Failed to validate interaction ownership".to_string()
-                })?
-                .ok_or_else(|| "Interaction not found or expired".to_string())?;
-
-            let bot_user_id_str = bot_user_id.to_string();
-            if expected_owner != bot_user_id_str {
-                warn!(
-                    interaction_id = %interaction_id,
-                    bot_user_id = %bot_user_id,
-                    expected_owner = %expected_owner,
-                    "Bot attempted to respond to interaction it does not own"
-                );
-                return Err("Interaction does not belong to this bot".to_string());
-            }
-
-            // Store command response in Redis with expiry (5 minutes)
-            // The command invoker's WebSocket client will poll/listen for this
-            let response_key = format!("interaction:{interaction_id}:response");
-            let response_data = serde_json::json!({
-                "content": content,
-                "ephemeral": ephemeral,
-                "bot_user_id": bot_user_id,
-            });
-
-            let was_set: bool = state
-                .redis
-                .set(
-                    &response_key,
-                    response_data.to_string(),
-                    Some(fred::types::Expiration::EX(300)),
-                    Some(fred::types::SetOptions::NX),
-                    false,
-                )
-                .await
-                .map_err(|e| {
-                    error!("Failed to store command response: {e}");
-                    format!("Failed to store response: {e}")
-                })?;
-
-            if !was_set {
-                return Err("Response already provided for this interaction".to_string());
-            }
-
-            // Publish event to notify waiting clients
-            state
-                .redis
-                .publish::<(), _, _>(
-                    format!("interaction:{interaction_id}"),
-                    response_data.to_string(),
-                )
-                .await
-                .map_err(|e| {
-                    error!("Failed to publish command response: {e}");
-                    format!("Failed to publish response: {e}")
-                })?;
-
-            // Deliver response to the invoking user/channel
-            let context_key = format!("interaction:{interaction_id}:context");
-            let context_raw: Option<String> = state.redis.get(&context_key).await.map_err(|e| {
-                error!("Failed to fetch interaction context: {e}");
-                format!("Failed to fetch interaction context: {e}")
-            })?;
-
-            let Some(context_raw) = context_raw else {
-                warn!(interaction_id = %interaction_id, "Interaction context not found, skipping delivery");
-                return Ok(());
-            };
-
-            let context: serde_json::Value = serde_json::from_str(&context_raw).map_err(|e| {
-                error!("Failed to parse interaction context: {e}");
-                format!("Failed to parse interaction context: {e}")
-            })?;
-
-            let user_id = context["user_id"]
-                .as_str()
-                .and_then(|s| Uuid::parse_str(s).ok())
-                .ok_or("Invalid user_id in interaction context")?;
-            let channel_id = context["channel_id"]
-                .as_str()
-                .and_then(|s| Uuid::parse_str(s).ok())
-                .ok_or("Invalid channel_id in interaction context")?;
-            let command_name = context["command_name"]
-                .as_str()
-                .ok_or("Invalid command_name in interaction context")?
-                .to_string();
-
-            let bot_user = crate::db::find_user_by_id(&state.db, bot_user_id)
-                .await
-                .map_err(|e| format!("Failed to look up bot user: {e}"))?;
-            let bot_name = bot_user
-                .as_ref()
-                .map(|u| u.display_name.clone())
-                .unwrap_or_else(|| "Bot".to_string());
-
-            if ephemeral {
-                // Ephemeral: only deliver to the invoking user
-                crate::ws::broadcast_to_user(
-                    &state.redis,
-                    user_id,
-                    &crate::ws::ServerEvent::CommandResponse {
-                        interaction_id,
-                        content,
-                        command_name,
-                        bot_name,
-                        channel_id,
-                        ephemeral: true,
-                    },
-                )
-                .await
-                .map_err(|e| {
-                    warn!("Failed to deliver ephemeral command response: {e}");
-                    format!("Failed to deliver response: {e}")
-                })?;
-            } else {
-                // Non-ephemeral: insert a real message and broadcast to channel
-                let message = crate::db::create_message(
-                    &state.db,
-                    channel_id,
-                    bot_user_id,
-                    &content,
-                    false,
-                    None,
-                    None,
-                )
-                .await
-                .map_err(|e| {
-                    error!("Failed to create bot command response message: {e}");
-                    format!("Failed to create message: {e}")
-                })?;
-
-                let author_json = if let Some(ref u) = bot_user {
-                    serde_json::json!({
-                        "id": u.id,
-                        "username": u.username,
-                        "display_name": u.display_name,
-                        "avatar_url": u.avatar_url,
-                        "status": format!("{:?}", u.status).to_lowercase(),
-                    })
-                } else {
-                    serde_json::json!({
-                        "id": bot_user_id,
-                        "username": "bot",
-                        "display_name": "Bot",
-                        "avatar_url": null,
-                        "status": "offline",
-                    })
-                };
-
-                crate::ws::broadcast_to_channel(
-                    &state.redis,
-                    channel_id,
-                    &crate::ws::ServerEvent::MessageNew {
-                        channel_id,
-                        message: serde_json::json!({
-                            "id": message.id,
-                            "channel_id": channel_id,
-                            "author": author_json,
-                            "content": message.content,
-                            "encrypted": message.encrypted,
-                            "nonce": message.nonce,
-                            "reply_to": message.reply_to,
-                            "created_at": message.created_at.to_rfc3339(),
-                        }),
-                    },
-                )
-                .await
-                .map_err(|e| {
-                    warn!("Failed to broadcast bot command response: {e}");
-                    format!("Failed to broadcast: {e}")
-                })?;
-            }
-
-            Ok(())
-        }
+                    "Failed to deliver response".to_string()
+                }
+                e => e.to_string(),
+            }),
     }
 }
@@ -0,0 +1,381 @@
+//! Event Fan-out
+//!
+//! Every server event is published to a Redis pub/sub topic: `channel:{id}`,
+//! `guild:{id}`, `user:{id}`, `presence:{id}` or `admin:events` (see
+//! [`super::channels`]). Each server instance holds one shared subscriber
+//! connection, subscribed only to the topics its own WebSocket connections
+//! registered, and fans incoming messages out to those connections.
+//!
+//! Events published on any replica therefore reach every connection that
+//! needs them, while a node receives only the traffic its clients use.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::time::Instant;
+
+use fred::prelude::*;
+use fred::types::Message;
+use tokio::sync::broadcast::error::RecvError;
+use tokio::sync::mpsc::error::TrySendError;
+use tokio::sync::{broadcast, mpsc, Mutex, OnceCell, RwLock};
+use tracing::{debug, error, warn};
+use uuid::Uuid;
+
+/// A pub/sub message delivered to a connection: `(topic, payload)`.
+pub type TopicMessage = (Arc<str>, Arc<str>);
+
+/// Messages buffered per connection before new ones are dropped.
+///
+/// The dispatcher never waits on a single connection, so one slow client
+/// cannot hold up delivery to the rest of the node.
+const CONNECTION_BUFFER: usize = 256;
+
+/// Channels a single connection may be subscribed to at once.
+///
+/// Subscribing to one more evicts the channel the connection used least
+/// recently, which keeps per-node topic counts bounded for clients that
+/// subscribe to every channel they open and never unsubscribe.
+pub const MAX_CHANNEL_SUBSCRIPTIONS: usize = 500;
+
+/// Per-node topic registry and shared Redis subscriber.
+pub struct EventFanout {
+    redis: Client,
+    /// Dedicated subscriber connection, created on first use.
+    subscriber: OnceCell<Client>,
+    /// Topic -> local connections listening on it.
+    topics: RwLock<HashMap<String, HashMap<Uuid, mpsc::Sender<TopicMessage>>>>,
+    /// Serializes Redis subscribe/unsubscribe calls so they apply in order.
+    transitions: Mutex<()>,
+}
+
+impl EventFanout {
+    /// Create the registry. No Redis connection is opened until the first
+    /// topic is subscribed.
+    #[must_use]
+    pub fn new(redis: Client) -> Self {
+        Self {
+            redis,
+            subscriber: OnceCell::new(),
+            topics: RwLock::new(HashMap::new()),
+            transitions: Mutex::new(()),
+        }
+    }
+
+    /// Register a connection. Messages for its topics arrive on the receiver.
+    #[must_use]
+    pub fn register(self: &Arc<Self>) -> (ConnectionTopics, mpsc::Receiver<TopicMessage>) {
+        let (tx, rx) = mpsc::channel(CONNECTION_BUFFER);
+        let topics = ConnectionTopics {
+            fanout: Arc::clone(self),
+            id: Uuid::new_v4(),
+            tx,
+            topics: Arc::new(Mutex::new(HashSet::new())),
+        };
+        (topics, rx)
+    }
+
+    /// Number of topics this node is subscribed to.
+    pub async fn topic_count(&self) -> usize {
+        self.topics.read().await.len()
+    }
+
+    async fn subscriber(self: &Arc<Self>) -> Result<&Client, Error> {
+        self.subscriber
+            .get_or_try_init(|| async {
+                let subscriber = self.redis.clone_new();
+                let _connect_handle = subscriber.connect();
+                subscriber.wait_for_connect().await?;
+                tokio::spawn(Arc::clone(self).dispatch(subscriber.message_rx()));
+                Ok(subscriber)
+            })
+            .await
+    }
+
+    async fn add(
+        self: &Arc<Self>,
+        topic: &str,
+        id: Uuid,
+        tx: &mpsc::Sender<TopicMessage>,
+    ) -> Result<(), Error> {
+        let _guard = self.transitions.lock().await;
+
+        let first = {
+            let mut topics = self.topics.write().await;
+            let listeners = topics.entry(topic.to_string()).or_default();
+            listeners.insert(id, tx.clone());
+            listeners.len() == 1
+        };
+        if !first {
+            return Ok(());
+        }
+
+        let result: Result<(), Error> = match self.subscriber().await {
+            Ok(subscriber) => subscriber.subscribe(topic).await,
+            Err(e) => Err(e),
+        };
+        if let Err(e) = result {
+            self.forget(topic, id).await;
+            return Err(e);
+        }
+        debug!(topic, "Subscribed node to topic");
+        Ok(())
+    }
+
+    async fn remove(&self, topic: &str, id: Uuid) -> Result<(), Error> {
+        let _guard = self.transitions.lock().await;
+
+        if !self.forget(topic, id).await {
+            return Ok(());
+        }
+        if let Some(subscriber) = self.subscriber.get() {
+            let () = subscriber.unsubscribe(topic).await?;
+            debug!(topic, "Unsubscribed node from topic");
+        }
+        Ok(())
+    }
+
+    /// Drop a listener; returns whether it was the topic's last one.
+    async fn forget(&self, topic: &str, id: Uuid) -> bool {
+        let mut topics = self.topics.write().await;
+        let Some(listeners) = topics.get_mut(topic) else {
+            return false;
+        };
+        listeners.remove(&id);
+        if listeners.is_empty() {
+            topics.remove(topic);
+            true
+        } else {
+            false
+        }
+    }
+
+    async fn dispatch(self: Arc<Self>, mut messages: broadcast::Receiver<Message>) {
+        loop {
+            let message = match messages.recv().await {
+                Ok(message) => message,
+                Err(RecvError::Lagged(skipped)) => {
+                    warn!(skipped, "Event fan-out lagged behind Redis");
+                    continue;
+                }
+                Err(RecvError::Closed) => {
+                    error!("Event fan-out subscriber closed");
+                    break;
+                }
+            };
+
+            let Some(payload) = message.value.as_str() else {
+                continue;
+            };
+            let topic: Arc<str> = Arc::from(&*message.channel);
+            let payload: Arc<str> = Arc::from(payload.as_ref());
+
+            let topics = self.topics.read().await;
+            let Some(listeners) = topics.get(&*topic) else {
+                continue;
+            };
+            for tx in listeners.values() {
+                match tx.try_send((Arc::clone(&topic), Arc::clone(&payload))) {
+                    Ok(()) | Err(TrySendError::Closed(_)) => {}
+                    Err(TrySendError::Full(_)) => {
+                        warn!(topic = %topic, "Dropping event for slow WebSocket connection");
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// One WebSocket connection's topic subscriptions.
+///
+/// Cheap to clone; clones share the same subscriptions. Call
+/// [`ConnectionTopics::close`] when the connection ends.
+#[derive(Clone)]
+pub struct ConnectionTopics {
+    fanout: Arc<EventFanout>,
+    id: Uuid,
+    tx: mpsc::Sender<TopicMessage>,
+    topics: Arc<Mutex<HashSet<String>>>,
+}
+
+impl ConnectionTopics {
+    /// Start receiving messages published to `topic`.
+    pub async fn subscribe(&self, topic: &str) -> Result<(), Error> {
+        let mut topics = self.topics.lock().await;
+        if topics.contains(topic) {
+            return Ok(());
+        }
+        self.fanout.add(topic, self.id, &self.tx).await?;
+        topics.insert(topic.to_string());
+        Ok(())
+    }
+
+    /// Stop receiving messages published to `topic`.
+    pub async fn unsubscribe(&self, topic: &str) -> Result<(), Error> {
+        let mut topics = self.topics.lock().await;
+        if topics.remove(topic) {
+            self.fanout.remove(topic, self.id).await?;
+        }
+        Ok(())
+    }
+
+    /// Drop every subscription of this connection.
+    pub async fn close(&self) {
+        let topics: Vec<String> = self.topics.lock().await.drain().collect();
+        for topic in topics {
+            if let Err(e) = self.fanout.remove(&topic, self.id).await {
+                warn!(topic, error = %e, "Failed to unsubscribe topic on close");
+            }
+        }
+    }
+}
+
+/// Channels a connection is subscribed to, kept in sync with its topics.
+///
+/// `VIEW_CHANNEL` is checked before [`ChannelSubscriptions::insert`], so the
+/// set doubles as a permission cache (see `revalidate_subscriptions`).
+///
+/// At most `limit` channels are kept; each remembers when the client last
+/// used it (subscribed, typed, joined voice, ...) and the least recently used
+/// one is evicted to make room for a new subscription.
+#[derive(Clone)]
+pub struct ChannelSubscriptions {
+    channels: Arc<RwLock<HashMap<Uuid, Instant>>>,
+    topics: ConnectionTopics,
+    limit: usize,
+}
+
+impl ChannelSubscriptions {
+    /// Track channel subscriptions on top of a connection's topics, capped
+    /// at [`MAX_CHANNEL_SUBSCRIPTIONS`].
+    #[must_use]
+    pub fn new(topics: ConnectionTopics) -> Self {
+        Self::with_limit(topics, MAX_CHANNEL_SUBSCRIPTIONS)
+    }
+
+    /// Track channel subscriptions with a custom cap (at least one).
+    #[must_use]
+    pub fn with_limit(topics: ConnectionTopics, limit: usize) -> Self {
+        Self {
+            channels: Arc::new(RwLock::new(HashMap::new())),
+            topics,
+            limit: limit.max(1),
+        }
+    }
+
+    /// The connection's topics, for non-channel subscriptions.
+    #[must_use]
+    pub const fn topics(&self) -> &ConnectionTopics {
+        &self.topics
+    }
+
+    /// Subscribe to a channel's events, or mark it used if already subscribed.
+    ///
+    /// Returns the channel evicted to stay within the cap, if any.
+    pub async fn insert(&self, channel_id: Uuid) -> Result<Option<Uuid>, Error> {
+        let evicted = {
+            let mut channels = self.channels.write().await;
+            if let Some(last_used) = channels.get_mut(&channel_id) {
+                *last_used = Instant::now();
+                return Ok(None);
+            }
+            let evicted = if channels.len() >= self.limit {
+                least_recently_used(&channels)
+            } else {
+                None
+            };
+            if let Some(evicted) = evicted {
+                channels.remove(&evicted);
+            }
+            evicted
+        };
+
+        if let Some(evicted) = evicted {
+            crate::observability::metrics::record_ws_subscription_eviction();
+            crate::observability::metrics::record_ws_channel_subscriptions(-1);
+            if let Err(e) = self
+                .topics
+                .unsubscribe(&super::channels::channel_events(evicted))
+                .await
+            {
+                warn!(channel_id = %evicted, error = %e, "Failed to drop evicted channel topic");
+            }
+        }
+
+        self.topics
+            .subscribe(&super::channels::channel_events(channel_id))
+            .await?;
+        if self
+            .channels
+            .write()
+            .await
+            .insert(channel_id, Instant::now())
+            .is_none()
+        {
+            crate::observability::metrics::record_ws_channel_subscriptions(1);
+        }
+        Ok(evicted)
+    }
+
+    /// Unsubscribe from a channel's events.
+    pub async fn remove(&self, channel_id: Uuid) -> Result<(), Error> {
+        if self.channels.write().await.remove(&channel_id).is_some() {
+            crate::observability::metrics::record_ws_channel_subscriptions(-1);
+        }
+        self.topics
+            .unsubscribe(&super::channels::channel_events(channel_id))
+            .await
+    }
+
+    /// Mark a subscribed channel as used by the client.
+    pub async fn touch(&self, channel_id: Uuid) {
+        if let Some(last_used) = self.channels.write().await.get_mut(&channel_id) {
+            *last_used = Instant::now();
+        }
+    }
+
+    /// Whether the connection is subscribed to the channel.
+    pub async fn contains(&self, channel_id: Uuid) -> bool {
+        self.channels.read().await.contains_key(&channel_id)
+    }
+
+    /// Currently subscribed channels.
+    pub async fn snapshot(&self) -> Vec<Uuid> {
+        self.channels.read().await.keys().copied().collect()
+    }
+
+    /// Drop every subscription of the connection, channel or not.
+    pub async fn close(&self) {
+        let dropped = std::mem::take(&mut *self.channels.write().await).len();
+        crate::observability::metrics::record_ws_channel_subscriptions(-(dropped as i64));
+        self.topics.close().await;
+    }
+}
+
+/// The channel with the oldest last use.
+fn least_recently_used(channels: &HashMap<Uuid, Instant>) -> Option<Uuid> {
+    channels
+        .iter()
+        .min_by_key(|(_, last_used)| **last_used)
+        .map(|(channel_id, _)| *channel_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    #[test]
+    fn test_least_recently_used() {
+        assert_eq!(least_recently_used(&HashMap::new()), None);
+
+        let now = Instant::now();
+        let (stale, fresh, freshest) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let channels = HashMap::from([
+            (fresh, now + Duration::from_secs(5)),
+            (stale, now),
+            (freshest, now + Duration::from_secs(10)),
+        ]);
+        assert_eq!(least_recently_used(&channels), Some(stale));
+    }
+}
@@ -0,0 +1,106 @@
+//! Connection Lifecycle
+//!
+//! Liveness, flood protection, and shutdown for client gateway connections.
+//! The server pings every connection and closes it if nothing (not even a
+//! pong) arrives within the idle timeout, closes connections that exceed the
+//! per-connection message rate, and closes all connections with `1001 Going
+//! Away` on shutdown. Each way a connection can end is recorded as a
+//! [`DisconnectCause`](crate::observability::ws_disconnects::DisconnectCause).
+
+use std::sync::LazyLock;
+use std::time::{Duration, Instant};
+
+use tokio::sync::watch;
+
+/// How often the server sends a WebSocket ping.
+pub const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Close the connection if nothing is received for this long.
+///
+/// Three missed heartbeats; browsers and the desktop client answer pings
+/// automatically.
+pub const IDLE_TIMEOUT: Duration = Duration::from_secs(90);
+
+/// Window over which client messages are counted.
+const MESSAGE_RATE_WINDOW: Duration = Duration::from_secs(10);
+
+/// Maximum client messages per window before the connection is closed.
+const MESSAGE_RATE_LIMIT: u32 = 100;
+
+/// Set to `true` once the server starts shutting down.
+static SHUTDOWN: LazyLock<watch::Sender<bool>> = LazyLock::new(|| watch::channel(false).0);
+
+/// Signal all gateway connections to close.
+pub fn begin_shutdown() {
+    SHUTDOWN.send_replace(true);
+}
+
+/// Subscribe to the shutdown signal.
+///
+/// Each open connection holds one receiver until its cleanup has finished,
+/// which is what [`drain_connections`] waits on.
+pub fn shutdown_receiver() -> watch::Receiver<bool> {
+    SHUTDOWN.subscribe()
+}
+
+/// Wait (up to `timeout`) for all gateway connections to finish cleanup.
+///
+/// Returns the number of connections still open when the timeout elapsed.
+pub async fn drain_connections(timeout: Duration) -> usize {
+    let deadline = Instant::now() + timeout;
+    while SHUTDOWN.receiver_count() > 0 && Instant::now() < deadline {
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+    SHUTDOWN.receiver_count()
+}
+
+/// Fixed-window counter of messages received on one connection.
+#[derive(Debug)]
+pub struct MessageRateGuard {
+    window_start: Instant,
+    count: u32,
+}
+
+impl Default for MessageRateGuard {
+    fn default() -> Self {
+        Self {
+            window_start: Instant::now(),
+            count: 0,
+        }
+    }
+}
+
+impl MessageRateGuard {
+    /// Count a message; returns `false` once the limit for the window is exceeded.
+    pub fn allow(&mut self) -> bool {
+        self.allow_at(Instant::now())
+    }
+
+    fn allow_at(&mut self, now: Instant) -> bool {
+        if now.duration_since(self.window_start) >= MESSAGE_RATE_WINDOW {
+            self.window_start = now;
+            self.count = 0;
+        }
+        self.count += 1;
+        self.count <= MESSAGE_RATE_LIMIT
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_message_rate_guard_resets_each_window() {
+        let start = Instant::now();
+        let mut guard = MessageRateGuard {
+            window_start: start,
+            count: 0,
+        };
+        for _ in 0..MESSAGE_RATE_LIMIT {
+            assert!(guard.allow_at(start));
+        }
+        assert!(!guard.allow_at(start + Duration::from_secs(1)));
+        assert!(guard.allow_at(start + MESSAGE_RATE_WINDOW));
+    }
+}
@@ -18,14 +18,19 @@
 //! Sec-WebSocket-Protocol: access_token
 //! ```
 
+pub mod blocks;
 pub mod bot_events;
 pub mod bot_gateway;
+pub mod fanout;
+pub mod lifecycle;
+pub mod session;
+pub mod typing;
 
-use std::collections::HashSet;
+use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 
-use axum::extract::ws::{Message, WebSocket};
+use axum::extract::ws::{close_code, CloseFrame, Message, WebSocket};
 use axum::extract::{State, WebSocketUpgrade};
 use axum::http::HeaderMap;
 use axum::response::Response;
@@ -33,19 +38,29 @@ use chrono::{DateTime, Utc};
 use fred::prelude::*;
 use futures::{SinkExt, StreamExt};
 use serde::{Deserialize, Serialize};
-use tokio::sync::mpsc;
+use tokio::sync::{mpsc, oneshot, Notify};
 use tracing::{debug, error, info, warn};
 use uuid::Uuid;
 
 use crate::api::AppState;
 use crate::auth::jwt;
+use crate::chat::system_messages;
 use crate::db;
-use crate::social::block_cache;
-use crate::voice::{Quality, ScreenShareInfo, WebcamInfo};
+use crate::i18n::RequestLocale;
+use crate::observability::ws_disconnects::{self, Disconnect, DisconnectCause};
+use crate::presence::registry as presence_registry;
+use crate::social::types::Relationship;
+use crate::voice::{PlayoutStats, Quality, ScreenShareInfo, VoiceActivityMode, WebcamInfo};
+use blocks::BlockSet;
+use fanout::{ChannelSubscriptions, ConnectionTopics, TopicMessage};
+use session::{ResumeError, Resumption, Session};
 
 /// Minimum interval between activity updates (10 seconds).
 const ACTIVITY_UPDATE_INTERVAL: Duration = Duration::from_secs(10);
 
+/// Members per `guild_member_chunk` when the request sets no limit.
+const DEFAULT_MEMBER_CHUNK: i64 = 100;
+
 /// State for activity rate limiting and deduplication.
 ///
 /// **Internal:** Exposed for integration tests only.
@@ -85,6 +100,11 @@ fn extract_token_from_protocol(headers: &HeaderMap) -> Option<String> {
 pub enum ClientEvent {
     /// Ping for keepalive
     Ping,
+    /// Request a server clock reading (answered with `time_sync`)
+    TimeSync {
+        /// Client clock when the request was sent (Unix milliseconds).
+        client_time: i64,
+    },
     /// Subscribe to channel events
     Subscribe {
         /// Channel to subscribe to.
@@ -111,6 +131,14 @@ pub enum ClientEvent {
     VoiceJoin {
         /// Voice channel to join.
         channel_id: Uuid,
+        /// Measured RTT in milliseconds to each media node from
+        /// `GET /api/voice/regions`, keyed by node ID.
+        #[serde(default)]
+        node_rtts: HashMap<String, u32>,
+        /// Whether the client noise-suppresses its microphone. Recorded on the
+        /// connection session for diagnostics.
+        #[serde(default)]
+        noise_suppression: bool,
     },
     /// Leave a voice channel
     VoiceLeave {
@@ -157,6 +185,9 @@ pub enum ClientEvent {
         quality: u8,
         /// Timestamp when stats were collected (Unix epoch ms).
         timestamp: i64,
+        /// Concealment, jitter-buffer and audio-level stats.
+        #[serde(flatten)]
+        playout: PlayoutStats,
     },
     /// Start screen sharing in voice channel
     VoiceScreenShareStart {
@@ -187,6 +218,49 @@ pub enum ClientEvent {
         /// Voice channel.
         channel_id: Uuid,
     },
+    /// Set push-to-talk or voice activity mode in voice channel
+    VoiceSetActivityMode {
+        /// Voice channel.
+        channel_id: Uuid,
+        /// New activity mode.
+        mode: VoiceActivityMode,
+    },
+    /// Promote a listener in voice channel so they can speak (moderators)
+    VoicePromoteListener {
+        /// Voice channel.
+        channel_id: Uuid,
+        /// Listener to promote.
+        user_id: Uuid,
+    },
+    /// Ask the moderators of voice channel to let a listener speak
+    VoiceRaiseHand {
+        /// Voice channel.
+        channel_id: Uuid,
+    },
+    /// Withdraw a raised hand in voice channel
+    VoiceLowerHand {
+        /// Voice channel.
+        channel_id: Uuid,
+    },
+    /// Set how loud another user plays for this user (0.0-2.0, 1.0 = unchanged)
+    ///
+    /// Saved in the `voice_user_gains` preference, which the client applies
+    /// to that user's audio.
+    VoiceSetUserGain {
+        /// User whose audio is scaled.
+        target_user: Uuid,
+        /// Gain multiplier.
+        gain: f32,
+    },
+    /// Answer a recording consent request
+    VoiceRecordingConsent {
+        /// Voice channel.
+        channel_id: Uuid,
+        /// Recording being answered.
+        recording_id: Uuid,
+        /// Whether the user agrees to be recorded.
+        consent: bool,
+    },
 
     /// Set rich presence activity (game, music, etc).
     SetActivity {
@@ -196,10 +270,45 @@ pub enum ClientEvent {
     /// Set user status (online, away, busy, offline).
     SetStatus { status: crate::db::UserStatus },
 
+    /// Report that the user is active. Clients send this about once a minute
+    /// while their idle detector considers the user active; without it the
+    /// user is shown as away after `presence::registry::IDLE_AFTER`.
+    PresencePing,
+
+    /// Request a chunk of a guild's member list (answered with
+    /// `guild_member_chunk`). Filters and paging match `GET /members`.
+    RequestGuildMembers {
+        /// Guild whose members to list.
+        guild_id: Uuid,
+        /// Only members with this role.
+        #[serde(default)]
+        role_id: Option<Uuid>,
+        /// Only members who are not offline.
+        #[serde(default)]
+        online_only: bool,
+        /// Continue after this user ID (`next_cursor` of the previous chunk).
+        #[serde(default)]
+        after: Option<Uuid>,
+        /// Chunk size (1-1000, default 100).
+        #[serde(default)]
+        limit: Option<i64>,
+        /// Echoed back on the chunk so clients can match responses.
+        #[serde(default)]
+        nonce: Option<String>,
+    },
+
     /// Subscribe to admin events (requires elevated admin).
     AdminSubscribe,
     /// Unsubscribe from admin events.
     AdminUnsubscribe,
+    /// Resume an earlier session, replaying the events it missed. Only valid
+    /// as the first message on a connection (see [`session`]).
+    Resume {
+        /// Session to resume (from its `ready` event).
+        session_id: Uuid,
+        /// Sequence number of the last event the client received.
+        last_seq: u64,
+    },
 }
 
 impl ClientEvent {
@@ -207,6 +316,7 @@ impl ClientEvent {
     pub const fn variant_name(&self) -> &'static str {
         match self {
             Self::Ping => "ping",
+            Self::TimeSync { .. } => "time_sync",
             Self::Subscribe { .. } => "subscribe",
             Self::Unsubscribe { .. } => "unsubscribe",
             Self::Typing { .. } => "typing",
@@ -222,16 +332,62 @@ impl ClientEvent {
             Self::VoiceScreenShareStop { .. } => "voice_screen_share_stop",
             Self::VoiceWebcamStart { .. } => "voice_webcam_start",
             Self::VoiceWebcamStop { .. } => "voice_webcam_stop",
+            Self::VoiceSetActivityMode { .. } => "voice_set_activity_mode",
+            Self::VoicePromoteListener { .. } => "voice_promote_listener",
+            Self::VoiceRaiseHand { .. } => "voice_raise_hand",
+            Self::VoiceLowerHand { .. } => "voice_lower_hand",
+            Self::VoiceSetUserGain { .. } => "voice_set_user_gain",
+            Self::VoiceRecordingConsent { .. } => "voice_recording_consent",
             Self::SetActivity { .. } => "set_activity",
             Self::SetStatus { .. } => "set_status",
+            Self::PresencePing => "presence_ping",
+            Self::RequestGuildMembers { .. } => "request_guild_members",
             Self::AdminSubscribe => "admin_subscribe",
             Self::AdminUnsubscribe => "admin_unsubscribe",
+            Self::Resume { .. } => "resume",
+        }
+    }
+
+    /// Channel this event targets, if any (for per-guild usage accounting).
+    pub const fn channel_id(&self) -> Option<Uuid> {
+        match self {
+            Self::Subscribe { channel_id }
+            | Self::Unsubscribe { channel_id }
+            | Self::Typing { channel_id }
+            | Self::StopTyping { channel_id }
+            | Self::VoiceJoin { channel_id, .. }
+            | Self::VoiceLeave { channel_id }
+            | Self::VoiceAnswer { channel_id, .. }
+            | Self::VoiceIceCandidate { channel_id, .. }
+            | Self::VoiceMute { channel_id }
+            | Self::VoiceUnmute { channel_id }
+            | Self::VoiceStats { channel_id, .. }
+            | Self::VoiceScreenShareStart { channel_id, .. }
+            | Self::VoiceScreenShareStop { channel_id }
+            | Self::VoiceWebcamStart { channel_id, .. }
+            | Self::VoiceWebcamStop { channel_id }
+            | Self::VoiceSetActivityMode { channel_id, .. }
+            | Self::VoicePromoteListener { channel_id, .. }
+            | Self::VoiceRaiseHand { channel_id }
+            | Self::VoiceLowerHand { channel_id }
+            | Self::VoiceRecordingConsent { channel_id, .. } => Some(*channel_id),
+            Self::Ping
+            | Self::TimeSync { .. }
+            | Self::VoiceSetUserGain { .. }
+            | Self::SetActivity { .. }
+            | Self::SetStatus { .. }
+            | Self::PresencePing
+            | Self::RequestGuildMembers { .. }
+            | Self::AdminSubscribe
+            | Self::AdminUnsubscribe
+            | Self::Resume { .. } => None,
         }
     }
 }
 
 /// Participant info for voice room state.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[allow(clippy::struct_excessive_bools)]
 pub struct VoiceParticipant {
     /// User ID.
     pub user_id: Uuid,
@@ -249,6 +405,15 @@ pub struct VoiceParticipant {
     /// Whether this participant has their webcam active.
     #[serde(default)]
     pub webcam_active: bool,
+    /// How this participant transmits audio.
+    #[serde(default)]
+    pub activity_mode: VoiceActivityMode,
+    /// Whether this participant is a receive-only listener.
+    #[serde(default)]
+    pub listener: bool,
+    /// Whether this listener has asked to speak.
+    #[serde(default)]
+    pub hand_raised: bool,
 }
 
 /// Server-to-client events.
@@ -259,9 +424,57 @@ pub enum ServerEvent {
     Ready {
         /// Authenticated user ID.
         user_id: Uuid,
+        /// Session this connection's events are numbered in; absent if the
+        /// session could not be created and events are not resumable.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        session_id: Option<Uuid>,
+        /// The user's current profile; absent if it could not be loaded.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        profile: Option<vc_common::types::UserProfile>,
+    },
+    /// Session resumed. Missed events were replayed right before this one.
+    Resumed {
+        /// The resumed session, which this connection now continues.
+        session_id: Uuid,
+        /// Sequence number of the last event replayed.
+        last_seq: u64,
+        /// Number of events replayed.
+        replayed: usize,
+    },
+    /// Session could not be resumed; the connection continues on the session
+    /// from its `ready` event and the client should refetch state.
+    ResumeFailed {
+        /// Why: `unknown_session`, `invalid_seq`, `too_old` or `unavailable`.
+        reason: String,
+    },
+    /// An admin suspended the account or revoked its sessions. The server
+    /// closes the connection right after; the client should sign out.
+    SessionRevoked {
+        /// `suspended` or `revoked`.
+        reason: String,
+    },
+    /// The account was signed in to from a network it has not used before.
+    NewLoginNetwork {
+        /// Address of the new sign-in.
+        ip: String,
+        /// User agent of the new sign-in.
+        user_agent: Option<String>,
+        /// Sign-in method: `password`, `passkey`, `oidc` or `device_link`.
+        method: String,
     },
     /// Pong response
     Pong,
+    /// Server clock reading, sent after `ready` and in reply to `time_sync`.
+    ///
+    /// Clients use it to estimate clock skew: with `client_time` echoed back,
+    /// `offset = server_time - (client_time + received_at) / 2`.
+    TimeSync {
+        /// Server clock (Unix milliseconds).
+        server_time: i64,
+        /// The request's `client_time`; absent for the unsolicited reading after `ready`.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        client_time: Option<i64>,
+    },
     /// Subscribed to channel
     Subscribed {
         /// Channel subscribed to.
@@ -272,6 +485,14 @@ pub enum ServerEvent {
         /// Channel unsubscribed from.
         channel_id: Uuid,
     },
+    /// A channel subscription was dropped to make room for a new one; the
+    /// client subscribes again when it needs the channel.
+    SubscriptionEvicted {
+        /// Channel no longer subscribed to.
+        channel_id: Uuid,
+        /// Per-connection subscription cap.
+        limit: usize,
+    },
     /// New message in channel
     MessageNew {
         /// Channel containing the message.
@@ -287,6 +508,8 @@ pub enum ServerEvent {
         message_id: Uuid,
         /// New content.
         content: String,
+        /// Custom emojis referenced in the new content.
+        emojis: Vec<crate::guild::types::MessageEmoji>,
         /// Edit timestamp (RFC3339).
         edited_at: String,
     },
@@ -297,6 +520,42 @@ pub enum ServerEvent {
         /// Deleted message ID.
         message_id: Uuid,
     },
+    /// Message pinned to its channel
+    MessagePinned {
+        /// Channel the message is pinned in.
+        channel_id: Uuid,
+        /// Pinned message ID.
+        message_id: Uuid,
+        /// User who pinned the message.
+        pinned_by: Uuid,
+        /// Pin timestamp.
+        pinned_at: DateTime<Utc>,
+    },
+    /// Message unpinned from its channel
+    MessageUnpinned {
+        /// Channel the message was pinned in.
+        channel_id: Uuid,
+        /// Unpinned message ID.
+        message_id: Uuid,
+    },
+    /// Background image processing finished for an attachment
+    AttachmentProcessed {
+        /// Channel containing the message.
+        channel_id: Uuid,
+        /// Message the attachment belongs to.
+        message_id: Uuid,
+        /// Updated attachment (dimensions, blurhash, variant URLs).
+        attachment: serde_json::Value,
+    },
+    /// Link previews for a message were resolved or changed
+    MessageEmbedsUpdate {
+        /// Channel containing the message.
+        channel_id: Uuid,
+        /// Message the embeds belong to.
+        message_id: Uuid,
+        /// Full list of link embeds (replaces any previous embeds).
+        embeds: serde_json::Value,
+    },
     /// Reaction added to a message
     ReactionAdd {
         /// Channel containing the message.
@@ -319,6 +578,26 @@ pub enum ServerEvent {
         /// Emoji that was removed.
         emoji: String,
     },
+    /// Guild name, description, icon, banner or designated channels changed
+    GuildUpdate {
+        /// Guild ID.
+        guild_id: Uuid,
+        /// The updated guild.
+        guild: crate::guild::types::Guild,
+    },
+    /// A chunk of a guild's member list (after `request_guild_members`)
+    GuildMemberChunk {
+        /// Guild ID.
+        guild_id: Uuid,
+        /// Members in this chunk, ordered by user ID.
+        members: Vec<crate::guild::types::GuildMember>,
+        /// Pass as `after` to request the next chunk; `None` on the last one.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        next_cursor: Option<Uuid>,
+        /// Nonce from the request.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        nonce: Option<String>,
+    },
     /// Guild custom emojis updated
     GuildEmojiUpdated {
         /// Guild ID.
@@ -326,6 +605,68 @@ pub enum ServerEvent {
         /// Updated emojis list.
         emojis: Vec<crate::guild::types::GuildEmoji>,
     },
+    /// Guild supporter perks changed
+    GuildPerksUpdate {
+        /// Guild ID.
+        guild_id: Uuid,
+        /// New effective perks.
+        perks: crate::guild::perks::GuildPerks,
+    },
+    /// Guild role created
+    RoleCreate {
+        /// Guild ID.
+        guild_id: Uuid,
+        /// The new role.
+        role: crate::guild::types::RoleResponse,
+    },
+    /// Guild role updated (name, color, permissions or position)
+    RoleUpdate {
+        /// Guild ID.
+        guild_id: Uuid,
+        /// The updated role.
+        role: crate::guild::types::RoleResponse,
+    },
+    /// Guild role deleted
+    RoleDelete {
+        /// Guild ID.
+        guild_id: Uuid,
+        /// Deleted role ID.
+        role_id: Uuid,
+    },
+    /// Guild roles reordered
+    RolesReorder {
+        /// Guild ID.
+        guild_id: Uuid,
+        /// All roles with their new positions.
+        roles: Vec<crate::guild::types::RoleResponse>,
+    },
+    /// Channels and categories moved (one event per bulk reorder)
+    ChannelPositionsUpdate {
+        /// Guild ID.
+        guild_id: Uuid,
+        /// Moved channels with their new position and category.
+        channels: Vec<crate::guild::channel_positions::ChannelPlacement>,
+        /// Moved categories with their new position and parent.
+        categories: Vec<crate::guild::channel_positions::CategoryPlacement>,
+    },
+    /// A member's role assignments changed
+    MemberRolesUpdate {
+        /// Guild ID.
+        guild_id: Uuid,
+        /// Member whose roles changed.
+        user_id: Uuid,
+        /// Role IDs now assigned to the member.
+        role_ids: Vec<Uuid>,
+    },
+    /// A member was timed out or their timeout ended
+    MemberTimeoutUpdate {
+        /// Guild ID.
+        guild_id: Uuid,
+        /// Member whose timeout changed.
+        user_id: Uuid,
+        /// When the timeout ends (`None` = no longer timed out).
+        timeout_until: Option<DateTime<Utc>>,
+    },
     /// User typing
     TypingStart {
         /// Channel user is typing in.
@@ -380,6 +721,12 @@ pub enum ServerEvent {
         username: String,
         /// User's display name.
         display_name: String,
+        /// How the user's name is pronounced, for screen-reader announcements.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        name_pronunciation: Option<String>,
+        /// Whether the user joined as a receive-only listener.
+        #[serde(default)]
+        listener: bool,
     },
     /// User left voice channel
     VoiceUserLeft {
@@ -387,6 +734,12 @@ pub enum ServerEvent {
         channel_id: Uuid,
         /// User who left.
         user_id: Uuid,
+        /// User's display name.
+        #[serde(default)]
+        display_name: String,
+        /// How the user's name is pronounced, for screen-reader announcements.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        name_pronunciation: Option<String>,
     },
     /// User muted in voice channel
     VoiceUserMuted {
@@ -402,6 +755,56 @@ pub enum ServerEvent {
         /// User who unmuted.
         user_id: Uuid,
     },
+    /// Participant started or stopped speaking (detected server-side)
+    VoiceSpeaking {
+        /// Voice channel.
+        channel_id: Uuid,
+        /// Participant whose state changed.
+        user_id: Uuid,
+        /// Whether the participant is speaking.
+        speaking: bool,
+    },
+    /// Participant switched between push-to-talk and voice activity
+    VoiceActivityModeChanged {
+        /// Voice channel.
+        channel_id: Uuid,
+        /// Participant whose mode changed.
+        user_id: Uuid,
+        /// New activity mode.
+        mode: VoiceActivityMode,
+    },
+    /// A moderator promoted a listener, who can now speak
+    VoiceListenerPromoted {
+        /// Voice channel.
+        channel_id: Uuid,
+        /// Promoted participant.
+        user_id: Uuid,
+        /// Moderator who promoted them.
+        promoted_by: Uuid,
+    },
+    /// A listener asked to speak
+    VoiceHandRaised {
+        /// Voice channel.
+        channel_id: Uuid,
+        /// Listener asking to speak.
+        user_id: Uuid,
+    },
+    /// A listener withdrew their request to speak
+    VoiceHandLowered {
+        /// Voice channel.
+        channel_id: Uuid,
+        /// Listener who lowered their hand.
+        user_id: Uuid,
+    },
+    /// A message matched one of the user's highlight keywords
+    MessageHighlight {
+        /// Channel the message was posted in.
+        channel_id: Uuid,
+        /// Highlighted message.
+        message_id: Uuid,
+        /// Keyword that matched, as the user configured it.
+        keyword: String,
+    },
     /// Current voice room state (sent on join)
     VoiceRoomState {
         /// Voice channel.
@@ -414,6 +817,13 @@ pub enum ServerEvent {
         /// Active webcams.
         #[serde(default)]
         webcams: Vec<WebcamInfo>,
+        /// Media node the channel is placed on (multi-node deployments only).
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        media_node: Option<String>,
+        /// Public IP of the node hosting the channel, for ICE (multi-node
+        /// deployments only).
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        media_address: Option<String>,
     },
     /// Voice error
     VoiceError {
@@ -436,6 +846,23 @@ pub enum ServerEvent {
         jitter: i16,
         /// Quality score (0-100).
         quality: u8,
+        /// Concealment, jitter-buffer and audio-level stats.
+        #[serde(flatten)]
+        playout: PlayoutStats,
+    },
+    /// Sustained packet loss on the user's connection (sent to that user)
+    VoiceQualityWarning {
+        /// Voice channel.
+        channel_id: Uuid,
+        /// Average packet loss percentage over the detection window.
+        packet_loss: f32,
+        /// Opus bitrate cap applied until the loss recovers (bits per second).
+        audio_bitrate: u32,
+    },
+    /// Packet loss recovered and the bitrate cap was lifted (sent to that user)
+    VoiceQualityRecovered {
+        /// Voice channel.
+        channel_id: Uuid,
     },
 
     // Screen Share events
@@ -485,6 +912,37 @@ pub enum ServerEvent {
         reason: String,
     },
 
+    // Recording events
+    /// Recording started; participants should answer with `VoiceRecordingConsent`
+    VoiceRecordingStarted {
+        /// Voice channel.
+        channel_id: Uuid,
+        /// Recording ID.
+        recording_id: Uuid,
+        /// User who started the recording.
+        started_by: Uuid,
+    },
+    /// A participant answered the consent request
+    VoiceRecordingConsentUpdated {
+        /// Voice channel.
+        channel_id: Uuid,
+        /// Recording ID.
+        recording_id: Uuid,
+        /// Participant who answered.
+        user_id: Uuid,
+        /// Whether the participant is being recorded.
+        consented: bool,
+    },
+    /// Recording stopped
+    VoiceRecordingStopped {
+        /// Voice channel.
+        channel_id: Uuid,
+        /// Recording ID.
+        recording_id: Uuid,
+        /// Reason for stop (e.g. "stopped", "empty").
+        reason: String,
+    },
+
     /// Screen share quality changed
     ScreenShareQualityChanged {
         /// Channel ID.
@@ -564,6 +1022,14 @@ pub enum ServerEvent {
         last_read_message_id: Option<Uuid>,
     },
 
+    /// DM conversation state changed (sent to all sessions of the same user)
+    DmStateUpdate {
+        /// DM channel ID.
+        channel_id: Uuid,
+        /// The conversation's new state.
+        state: crate::chat::dm_state::DmConversationState,
+    },
+
     /// Guild channel read position updated (sent to other sessions of the same user)
     ChannelRead {
         /// Guild channel ID.
@@ -597,6 +1063,63 @@ pub enum ServerEvent {
         /// When the preferences were updated.
         updated_at: DateTime<Utc>,
     },
+    /// A message draft was saved or cleared on another device.
+    DraftUpdate {
+        /// Channel the draft belongs to.
+        channel_id: Uuid,
+        /// Draft text; empty when the draft was cleared.
+        content: String,
+        /// When the draft was last edited.
+        updated_at: DateTime<Utc>,
+    },
+    /// A requested data export archive is ready to download.
+    DataExportReady {
+        /// Export job ID.
+        job_id: Uuid,
+        /// Presigned archive URL.
+        download_url: String,
+        /// When `download_url` stops working.
+        download_url_expires_at: DateTime<Utc>,
+        /// Archive size in bytes.
+        file_size_bytes: i64,
+    },
+    /// A requested data export failed; a new one can be requested.
+    DataExportFailed {
+        /// Export job ID.
+        job_id: Uuid,
+        /// Why the export failed.
+        error_message: String,
+    },
+    /// A system admin reviewed one of the user's guilds for discovery, or
+    /// reports delisted it (sent to the guild owner).
+    GuildDiscoveryReviewed {
+        /// Guild ID.
+        guild_id: Uuid,
+        /// Guild name for display.
+        guild_name: String,
+        /// New review state: `approved`, `rejected` or `delisted`.
+        status: String,
+        /// Why the listing was rejected or delisted.
+        reason: Option<String>,
+    },
+    /// A system admin suspended one of the user's guilds (sent to the guild
+    /// owner). Nobody can post or join voice until it is lifted.
+    GuildSuspended {
+        /// Guild ID.
+        guild_id: Uuid,
+        /// Guild name for display.
+        guild_name: String,
+        /// Reason given by the admin.
+        reason: String,
+    },
+    /// A system admin lifted the suspension of one of the user's guilds
+    /// (sent to the guild owner).
+    GuildUnsuspended {
+        /// Guild ID.
+        guild_id: Uuid,
+        /// Guild name for display.
+        guild_name: String,
+    },
 
     // Friend events
     /// Friend request received (sent to the addressee).
@@ -637,6 +1160,14 @@ pub enum ServerEvent {
         /// Unblocked user ID.
         user_id: Uuid,
     },
+    /// Relationship to another user changed (sent to both users). Being
+    /// blocked is reported as no relationship.
+    RelationshipUpdate {
+        /// The other user.
+        user_id: Uuid,
+        /// Relationship to them, `None` if there is none.
+        relationship: Option<Relationship>,
+    },
 
     // Workspace events (broadcast to workspace owner's sessions)
     /// New workspace created.
@@ -870,6 +1401,23 @@ pub async fn broadcast_to_channel(
     Ok(())
 }
 
+/// Broadcast a server event to all members of a guild via Redis.
+#[tracing::instrument(skip(redis, event), fields(guild_id = %guild_id))]
+pub async fn broadcast_to_guild(
+    redis: &Client,
+    guild_id: Uuid,
+    event: &ServerEvent,
+) -> Result<(), Error> {
+    let payload = serde_json::to_string(event)
+        .map_err(|e| Error::new(ErrorKind::Parse, format!("JSON error: {e}")))?;
+
+    redis
+        .publish::<(), _, _>(channels::guild_events(guild_id), payload)
+        .await?;
+
+    Ok(())
+}
+
 /// Broadcast an admin event to all admin subscribers via Redis.
 #[tracing::instrument(skip(redis, event))]
 pub async fn broadcast_admin_event(redis: &Client, event: &ServerEvent) -> Result<(), Error> {
@@ -901,7 +1449,7 @@ pub async fn broadcast_to_user(
 }
 
 /// Broadcast a presence update to all users who should see it.
-async fn broadcast_presence_update(state: &AppState, user_id: Uuid, event: &ServerEvent) {
+pub async fn broadcast_presence_update(redis: &Client, user_id: Uuid, event: &ServerEvent) {
     let json = match serde_json::to_string(event) {
         Ok(j) => j,
         Err(e) => {
@@ -911,8 +1459,8 @@ async fn broadcast_presence_update(state: &AppState, user_id: Uuid, event: &Serv
     };
 
     // Broadcast on presence channel
-    let channel = format!("presence:{user_id}");
-    let result: Result<(), Error> = state.redis.publish(&channel, &json).await;
+    let channel = channels::user_presence(user_id);
+    let result: Result<(), Error> = redis.publish(&channel, &json).await;
     if let Err(e) = result {
         error!("Failed to broadcast presence update: {}", e);
     }
@@ -1052,8 +1600,22 @@ pub async fn handler(
     };
 
     // Validate token before upgrade
-    let claims = match jwt::validate_access_token(&token, &state.config.jwt_public_key) {
+    let claims = match jwt::validate_access_token(&token, &state.config().jwt_public_key) {
         Ok(claims) => claims,
+        Err(crate::auth::AuthError::TokenExpired) => {
+            // Clients retrying with a stale token are a common reconnect storm
+            ws_disconnects::record_disconnect(
+                &state.db,
+                Disconnect {
+                    user_id: None,
+                    cause: DisconnectCause::AuthExpired,
+                    close_code: None,
+                    session_secs: None,
+                },
+            )
+            .await;
+            return error_response(401, "Token expired");
+        }
         Err(_) => {
             return error_response(401, "Invalid token");
         }
@@ -1066,15 +1628,59 @@ pub async fn handler(
         }
     };
 
+    // Suspended accounts and revoked tokens cannot connect
+    match db::find_user_by_id(&state.db, user_id).await {
+        Ok(Some(user)) => match crate::auth::ensure_account_active(&user, Some(claims.iat)) {
+            Ok(()) => {}
+            Err(crate::auth::AuthError::AccountSuspended) => {
+                return error_response(403, "Account suspended");
+            }
+            Err(crate::auth::AuthError::AccountPendingApproval) => {
+                return error_response(403, "Account pending approval");
+            }
+            Err(_) => return error_response(401, "Invalid token"),
+        },
+        Ok(None) => return error_response(401, "Invalid token"),
+        Err(e) => {
+            warn!("Failed to load user for WebSocket handshake: {}", e);
+            return error_response(500, "Internal Server Error");
+        }
+    }
+
+    // System messages are rendered in the handshake's Accept-Language
+    let RequestLocale(locale) = RequestLocale::from_headers(&headers);
+
     // Respond with the protocol to confirm (required for WebSocket handshake)
     ws.protocols(["access_token"])
         .max_message_size(256 * 1024)
         .max_frame_size(64 * 1024)
-        .on_upgrade(move |socket| handle_socket(socket, state, user_id))
+        .on_upgrade(move |socket| handle_socket(socket, state, user_id, locale))
 }
 
 /// Handle WebSocket connection.
-async fn handle_socket(socket: WebSocket, state: AppState, user_id: Uuid) {
+/// Profile sent in the `ready` event.
+fn ready_profile(user: db::User) -> vc_common::types::UserProfile {
+    use vc_common::types::UserStatus;
+
+    vc_common::types::UserProfile {
+        id: user.id,
+        username: user.username,
+        display_name: user.display_name,
+        avatar_url: user.avatar_url,
+        banner_url: user.banner_url,
+        about_me: user.about_me,
+        pronouns: user.pronouns,
+        accent_color: user.accent_color,
+        status: match user.status {
+            db::UserStatus::Online => UserStatus::Online,
+            db::UserStatus::Away => UserStatus::Away,
+            db::UserStatus::Busy => UserStatus::Busy,
+            db::UserStatus::Offline => UserStatus::Offline,
+        },
+    }
+}
+
+async fn handle_socket(socket: WebSocket, state: AppState, user_id: Uuid, locale: &'static str) {
     use futures::stream::{SplitSink, SplitStream};
     let (mut ws_sender, mut ws_receiver): (SplitSink<WebSocket, Message>, SplitStream<WebSocket>) =
         socket.split();
@@ -1082,24 +1688,70 @@ async fn handle_socket(socket: WebSocket, state: AppState, user_id: Uuid) {
     // Channel for sending messages to the WebSocket
     let (tx, mut rx) = mpsc::channel::<ServerEvent>(100);
 
-    // Track subscribed channels
-    let subscribed_channels: Arc<tokio::sync::RwLock<HashSet<Uuid>>> =
-        Arc::new(tokio::sync::RwLock::new(HashSet::new()));
+    // Register with this node's event fan-out and track subscribed channels
+    let (connection_topics, topic_rx) = state.event_fanout.register();
+    let subscribed_channels = ChannelSubscriptions::new(connection_topics.clone());
 
     // Track admin event subscription
     let admin_subscribed: Arc<tokio::sync::RwLock<bool>> =
         Arc::new(tokio::sync::RwLock::new(false));
 
-    // Update user presence to online
-    if let Err(e) = update_presence(&state, user_id, "online").await {
-        warn!("Failed to update presence: {}", e);
+    // Register the connection in the presence registry
+    let connection_id = Uuid::new_v4();
+    match presence_registry::connect(&state.redis, user_id, connection_id).await {
+        Ok(Some(status)) => {
+            presence_registry::publish_change(&state.db, &state.redis, user_id, &status).await;
+        }
+        Ok(None) => {}
+        Err(e) => warn!("Failed to update presence: {}", e),
+    }
+    if let Err(e) = crate::cluster::registry::track_connection(
+        &state.redis,
+        &state.config().node_id,
+        user_id,
+        connection_id,
+    )
+    .await
+    {
+        warn!("Failed to track connection in cluster registry: {}", e);
     }
 
+    // Start a resumable session; events are still delivered without one
+    let session = match Session::start(&state.redis, user_id, connection_id).await {
+        Ok(session) => Some(session),
+        Err(e) => {
+            warn!("Failed to start resumable session: {}", e);
+            None
+        }
+    };
+
     info!("WebSocket connected: user={}", user_id);
     crate::observability::metrics::record_ws_connect();
+    let connected_at = Instant::now();
+    let mut shutdown_rx = lifecycle::shutdown_receiver();
 
-    // Send ready event
-    let _ = tx.send(ServerEvent::Ready { user_id }).await;
+    let profile = match db::find_user_by_id(&state.db, user_id).await {
+        Ok(user) => user.map(ready_profile),
+        Err(e) => {
+            warn!("Failed to load profile for ready event: {}", e);
+            None
+        }
+    };
+
+    // Send ready event, followed by a clock reading for skew estimation
+    let _ = tx
+        .send(ServerEvent::Ready {
+            user_id,
+            session_id: session.as_ref().map(Session::id),
+            profile,
+        })
+        .await;
+    let _ = tx
+        .send(ServerEvent::TimeSync {
+            server_time: Utc::now().timestamp_millis(),
+            client_time: None,
+        })
+        .await;
 
     // Fetch user's friends for presence subscriptions
     let friend_ids = match get_user_friends(&state.db, user_id).await {
@@ -1154,81 +1806,143 @@ async fn handle_socket(socket: WebSocket, state: AppState, user_id: Uuid) {
     };
 
     // Load block sets for event filtering
-    let blocked_ids = match block_cache::load_blocked_users(&state.db, &state.redis, user_id).await
-    {
-        Ok(ids) => {
-            debug!("User {} has blocked {} users", user_id, ids.len());
-            ids
-        }
-        Err(e) => {
-            warn!("Failed to load blocked users for {}: {}", user_id, e);
-            HashSet::new()
-        }
-    };
-    let blocked_by_ids = match block_cache::load_blocked_by(&state.db, &state.redis, user_id).await
-    {
-        Ok(ids) => {
-            debug!("User {} is blocked by {} users", user_id, ids.len());
-            ids
-        }
-        Err(e) => {
-            warn!("Failed to load blocked-by for {}: {}", user_id, e);
-            HashSet::new()
-        }
-    };
-
-    let blocked_users: Arc<tokio::sync::RwLock<HashSet<Uuid>>> = Arc::new(
-        tokio::sync::RwLock::new(blocked_ids.union(&blocked_by_ids).copied().collect()),
-    );
-
-    // Spawn task to handle Redis pub/sub
-    let redis_client = state.redis.clone();
-    let tx_clone = tx.clone();
-    let subscribed_clone = subscribed_channels.clone();
-    let admin_subscribed_clone = admin_subscribed.clone();
-    let blocked_clone = blocked_users.clone();
-    let pubsub_handle = tokio::spawn(async move {
-        handle_pubsub(
-            redis_client,
-            HandlePubsubParams {
-                tx: tx_clone,
-                subscribed_channels: subscribed_clone,
-                admin_subscribed: admin_subscribed_clone,
-                blocked_users: blocked_clone,
-                user_id,
-                friend_ids,
-                guild_ids,
-            },
-        )
-        .await;
-    });
+    let blocked_users = BlockSet::load(&state.db, &state.redis, user_id).await;
+    let revoked = Arc::new(Notify::new());
+
+    // Spawn task to handle pub/sub events fanned out to this connection. It
+    // outlives the connection while the session can still be resumed.
+    let (sequenced_tx, mut sequenced_rx) = mpsc::channel::<String>(100);
+    let (control_tx, control_rx) = mpsc::channel::<SessionCommand>(4);
+    tokio::spawn(handle_pubsub(
+        connection_topics,
+        topic_rx,
+        session,
+        control_rx,
+        HandlePubsubParams {
+            db: state.db.clone(),
+            redis: state.redis.clone(),
+            tx: tx.clone(),
+            sequenced_tx,
+            connection_id,
+            subscribed_channels: subscribed_channels.clone(),
+            admin_subscribed: admin_subscribed.clone(),
+            blocked_users,
+            revoked: revoked.clone(),
+            user_id,
+            locale,
+            friend_ids,
+            guild_ids,
+        },
+    ));
+
+    // Spawn task to forward events and heartbeats to WebSocket, and to send
+    // the close frame when the server ends the connection
+    let (close_tx, mut close_rx) = oneshot::channel::<CloseFrame>();
+    let session_db = state.db.clone();
+    let session_redis = state.redis.clone();
+    let mut sender_handle: tokio::task::JoinHandle<()> = tokio::spawn(async move {
+        let mut heartbeat = tokio::time::interval(lifecycle::HEARTBEAT_INTERVAL);
+        heartbeat.tick().await; // consume immediate first tick
+        loop {
+            tokio::select! {
+                event = rx.recv() => {
+                    let Some(event) = event else { break };
+                    let msg = match serde_json::to_string(&event) {
+                        Ok(json) => json,
+                        Err(e) => {
+                            error!("Failed to serialize event: {}", e);
+                            continue;
+                        }
+                    };
 
-    // Spawn task to forward events to WebSocket
-    let sender_handle: tokio::task::JoinHandle<()> = tokio::spawn(async move {
-        while let Some(event) = rx.recv().await {
-            let msg = match serde_json::to_string(&event) {
-                Ok(json) => json,
-                Err(e) => {
-                    error!("Failed to serialize event: {}", e);
-                    continue;
+                    let send_result: Result<(), axum::Error> =
+                        ws_sender.send(Message::Text(msg.into())).await;
+                    if send_result.is_err() {
+                        break;
+                    }
+                }
+                Some(msg) = sequenced_rx.recv() => {
+                    if ws_sender.send(Message::Text(msg.into())).await.is_err() {
+                        break;
+                    }
+                }
+                _ = heartbeat.tick() => {
+                    if ws_sender.send(Message::Ping(Vec::new().into())).await.is_err() {
+                        break;
+                    }
+                    match presence_registry::heartbeat(&session_redis, user_id, connection_id)
+                        .await
+                    {
+                        Ok(Some(status)) => {
+                            presence_registry::publish_change(
+                                &session_db,
+                                &session_redis,
+                                user_id,
+                                &status,
+                            )
+                            .await;
+                        }
+                        Ok(None) => {}
+                        Err(e) => warn!("Failed to refresh presence: {}", e),
+                    }
+                }
+                frame = &mut close_rx => {
+                    if let Ok(frame) = frame {
+                        let _ = ws_sender.send(Message::Close(Some(frame))).await;
+                    }
+                    break;
                 }
-            };
-
-            let send_result: Result<(), axum::Error> =
-                ws_sender.send(Message::Text(msg.into())).await;
-            if send_result.is_err() {
-                break;
             }
         }
     });
 
     // Activity rate limiting state
     let mut activity_state = ActivityState::default();
-
-    // Handle incoming messages
-    while let Some(msg) = ws_receiver.next().await {
+    let mut rate_guard = lifecycle::MessageRateGuard::default();
+    let mut first_message = true;
+
+    // Handle incoming messages until the connection ends, noting why
+    let (cause, frame_code) = loop {
+        let msg = tokio::select! {
+            _ = shutdown_rx.wait_for(|shutting_down| *shutting_down) => {
+                break (DisconnectCause::ServerShutdown, Some(close_code::AWAY));
+            }
+            () = revoked.notified() => {
+                info!("WebSocket session revoked: user={}", user_id);
+                break (DisconnectCause::SessionRevoked, Some(close_code::POLICY));
+            }
+            msg = tokio::time::timeout(lifecycle::IDLE_TIMEOUT, ws_receiver.next()) => msg,
+        };
+        let msg = match msg {
+            Err(_elapsed) => {
+                info!("WebSocket idle timeout: user={}", user_id);
+                break (DisconnectCause::Timeout, None);
+            }
+            Ok(None) => break (DisconnectCause::ConnectionLost, None),
+            Ok(Some(msg)) => msg,
+        };
         match msg {
             Ok(Message::Text(text)) => {
+                if !rate_guard.allow() {
+                    warn!("WebSocket message rate exceeded: user={}", user_id);
+                    break (DisconnectCause::RateLimited, Some(close_code::POLICY));
+                }
+                // A resume is handed to the forwarder, which owns the session
+                if std::mem::take(&mut first_message) {
+                    if let Ok(ClientEvent::Resume {
+                        session_id,
+                        last_seq,
+                    }) = serde_json::from_str(&text)
+                    {
+                        let _ = control_tx
+                            .send(SessionCommand::Resume {
+                                session_id,
+                                last_seq,
+                            })
+                            .await;
+                        continue;
+                    }
+                }
                 if let Err(e) = handle_client_message(
                     &text,
                     user_id,
@@ -1253,29 +1967,119 @@ async fn handle_socket(socket: WebSocket, state: AppState, user_id: Uuid) {
                 // Axum handles pong automatically, but we can respond too
                 debug!("Received ping from user={}", user_id);
             }
-            Ok(Message::Close(_)) => {
+            Ok(Message::Close(frame)) => {
                 info!("WebSocket closed: user={}", user_id);
-                break;
+                break (DisconnectCause::ClientClose, frame.map(|f| f.code));
             }
             Err(e) => {
                 warn!("WebSocket error: {}", e);
-                break;
+                break (DisconnectCause::ConnectionLost, None);
             }
             _ => {}
         }
-    }
+    };
 
-    // Cleanup
-    pubsub_handle.abort();
-    sender_handle.abort();
+    // Cleanup: keep buffering events for a resume unless the client left for
+    // good or this server is going away
+    let session_command = match cause {
+        DisconnectCause::ClientClose
+        | DisconnectCause::ServerShutdown
+        | DisconnectCause::SessionRevoked => SessionCommand::End,
+        _ => SessionCommand::Detach,
+    };
+    let _ = control_tx.send(session_command).await;
+    let server_close_reason = match cause {
+        DisconnectCause::RateLimited => Some("Message rate exceeded"),
+        DisconnectCause::ServerShutdown => Some("Server shutting down"),
+        DisconnectCause::SessionRevoked => Some("Session revoked"),
+        _ => None,
+    };
+    match (server_close_reason, frame_code) {
+        (Some(reason), Some(code)) => {
+            let _ = close_tx.send(CloseFrame {
+                code,
+                reason: reason.into(),
+            });
+            // Give the sender a moment to flush the close frame
+            if tokio::time::timeout(Duration::from_secs(1), &mut sender_handle)
+                .await
+                .is_err()
+            {
+                sender_handle.abort();
+            }
+        }
+        _ => sender_handle.abort(),
+    }
 
-    // Update user presence to offline
-    if let Err(e) = update_presence(&state, user_id, "offline").await {
-        warn!("Failed to update presence on disconnect: {}", e);
+    // Go offline unless the user still has connections elsewhere
+    match presence_registry::disconnect(&state.redis, user_id, connection_id).await {
+        Ok(Some(status)) => {
+            presence_registry::publish_change(&state.db, &state.redis, user_id, &status).await;
+        }
+        Ok(None) => {}
+        Err(e) => warn!("Failed to update presence on disconnect: {}", e),
+    }
+    if let Err(e) = crate::cluster::registry::untrack_connection(
+        &state.redis,
+        &state.config().node_id,
+        user_id,
+        connection_id,
+    )
+    .await
+    {
+        warn!("Failed to untrack connection in cluster registry: {}", e);
     }
 
-    info!("WebSocket disconnected: user={}", user_id);
+    info!(
+        "WebSocket disconnected: user={} cause={}",
+        user_id,
+        cause.as_str()
+    );
     crate::observability::metrics::record_ws_disconnect();
+    ws_disconnects::record_disconnect(
+        &state.db,
+        Disconnect {
+            user_id: Some(user_id),
+            cause,
+            close_code: frame_code,
+            session_secs: Some(connected_at.elapsed().as_secs_f64()),
+        },
+    )
+    .await;
+    drop(shutdown_rx);
+}
+
+/// Whether a user may show a typing indicator in a channel.
+///
+/// Requires the same access as posting: `VIEW_CHANNEL`, plus `SEND_MESSAGES`
+/// (`VOICE_TEXT_CHAT` in voice channels) and no active timeout in guild
+/// channels.
+async fn can_send_typing(
+    state: &AppState,
+    user_id: Uuid,
+    channel_id: Uuid,
+) -> Result<bool, sqlx::Error> {
+    let Ok(ctx) = crate::permissions::require_channel_access(&state.db, user_id, channel_id).await
+    else {
+        return Ok(false);
+    };
+    let Some(channel) = db::find_channel_by_id(&state.db, channel_id).await? else {
+        return Ok(false);
+    };
+    if let Some(guild_id) = channel.guild_id {
+        let send_permission = crate::permissions::send_permission_for(&channel.channel_type);
+        if !ctx.has_permission(send_permission) {
+            return Ok(false);
+        }
+        if db::is_guild_suspended(&state.db, guild_id).await?
+            || db::get_member_timeout(&state.db, guild_id, user_id)
+                .await?
+                .is_some()
+        {
+            return Ok(false);
+        }
+    }
+    Ok(true)
 }
 
 /// Handle a client message.
@@ -1291,18 +2095,30 @@ pub async fn handle_client_message(
     user_id: Uuid,
     state: &AppState,
     tx: &mpsc::Sender<ServerEvent>,
-    subscribed_channels: &Arc<tokio::sync::RwLock<HashSet<Uuid>>>,
+    subscribed_channels: &ChannelSubscriptions,
     admin_subscribed: &Arc<tokio::sync::RwLock<bool>>,
     activity_state: &mut ActivityState,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
     let event: ClientEvent = serde_json::from_str(text)?;
     crate::observability::metrics::record_ws_message(event.variant_name());
+    crate::observability::consumers::record_ws_event(user_id, event.channel_id());
+    if let Some(channel_id) = event.channel_id() {
+        subscribed_channels.touch(channel_id).await;
+    }
 
     match event {
         ClientEvent::Ping => {
             tx.send(ServerEvent::Pong).await?;
         }
 
+        ClientEvent::TimeSync { client_time } => {
+            tx.send(ServerEvent::TimeSync {
+                server_time: Utc::now().timestamp_millis(),
+                client_time: Some(client_time),
+            })
+            .await?;
+        }
+
         ClientEvent::Subscribe { channel_id } => {
             // Verify channel exists
             if db::find_channel_by_id(&state.db, channel_id)
@@ -1330,64 +2146,44 @@ pub async fn handle_client_message(
                 return Ok(());
             }
 
-            // Add to subscribed channels
-            subscribed_channels.write().await.insert(channel_id);
+            // Add to subscribed channels, making room if at the cap
+            if let Some(evicted) = subscribed_channels.insert(channel_id).await? {
+                tx.send(ServerEvent::SubscriptionEvicted {
+                    channel_id: evicted,
+                    limit: fanout::MAX_CHANNEL_SUBSCRIPTIONS,
+                })
+                .await?;
+                debug!(
+                    "User {} subscription to channel {} evicted",
+                    user_id, evicted
+                );
+            }
 
             tx.send(ServerEvent::Subscribed { channel_id }).await?;
             debug!("User {} subscribed to channel {}", user_id, channel_id);
         }
 
         ClientEvent::Unsubscribe { channel_id } => {
-            subscribed_channels.write().await.remove(&channel_id);
+            subscribed_channels.remove(channel_id).await?;
             tx.send(ServerEvent::Unsubscribed { channel_id }).await?;
             debug!("User {} unsubscribed from channel {}", user_id, channel_id);
         }
 
         ClientEvent::Typing { channel_id } => {
-            // Check if user has VIEW_CHANNEL permission
-            let permission_result: Result<_, crate::permissions::PermissionError> =
-                crate::permissions::require_channel_access(&state.db, user_id, channel_id).await;
-
-            if permission_result.is_err() {
-                warn!(
-                    "User {} attempted to send typing indicator for channel {} without permission",
+            if !can_send_typing(state, user_id, channel_id).await? {
+                debug!(
+                    "Ignoring typing indicator from user {} for channel {}",
                     user_id, channel_id
                 );
-                return Ok(()); // Silently ignore unauthorized typing indicator
+                return Ok(());
             }
 
-            // Broadcast typing indicator
-            broadcast_to_channel(
-                &state.redis,
-                channel_id,
-                &ServerEvent::TypingStart {
-                    channel_id,
-                    user_id,
-                },
-            )
-            .await?;
+            typing::start_typing(&state.redis, channel_id, user_id).await?;
         }
 
         ClientEvent::StopTyping { channel_id } => {
-            // Check if user has VIEW_CHANNEL permission
-            let permission_result: Result<_, crate::permissions::PermissionError> =
-                crate::permissions::require_channel_access(&state.db, user_id, channel_id).await;
-
-            if permission_result.is_err() {
-                warn!("User {} attempted to send stop typing indicator for channel {} without permission", user_id, channel_id);
-                return Ok(()); // Silently ignore unauthorized stop typing indicator
-            }
-
-            // Broadcast stop typing
-            broadcast_to_channel(
-                &state.redis,
-                channel_id,
-                &ServerEvent::TypingStop {
-                    channel_id,
-                    user_id,
-                },
-            )
-            .await?;
+            // Only clears the caller's own entry, so no permission check is needed
+            typing::stop_typing(&state.redis, channel_id, user_id).await?;
         }
 
         // Voice events - delegate to voice handler
@@ -1401,7 +2197,13 @@ pub async fn handle_client_message(
         | ClientEvent::VoiceScreenShareStart { .. }
         | ClientEvent::VoiceScreenShareStop { .. }
         | ClientEvent::VoiceWebcamStart { .. }
-        | ClientEvent::VoiceWebcamStop { .. } => {
+        | ClientEvent::VoiceWebcamStop { .. }
+        | ClientEvent::VoiceSetActivityMode { .. }
+        | ClientEvent::VoicePromoteListener { .. }
+        | ClientEvent::VoiceRaiseHand { .. }
+        | ClientEvent::VoiceLowerHand { .. }
+        | ClientEvent::VoiceSetUserGain { .. }
+        | ClientEvent::VoiceRecordingConsent { .. } => {
             if let Err(e) = crate::voice::ws_handler::handle_voice_event(
                 &state.sfu,
                 &state.db,
@@ -1462,24 +2264,67 @@ pub async fn handle_client_message(
 
             // Broadcast to user's presence subscribers
             let event = ServerEvent::RichPresenceUpdate { user_id, activity };
-            broadcast_presence_update(state, user_id, &event).await;
+            broadcast_presence_update(&state.redis, user_id, &event).await;
         }
 
         ClientEvent::SetStatus { status } => {
-            let status_str = match status {
-                crate::db::UserStatus::Online => "online",
-                crate::db::UserStatus::Away => "away",
-                crate::db::UserStatus::Busy => "busy",
-                crate::db::UserStatus::Offline => "offline",
-            };
-            update_presence(state, user_id, status_str).await?;
+            if let Some(effective) =
+                presence_registry::set_status(&state.redis, user_id, &status).await?
+            {
+                presence_registry::publish_change(&state.db, &state.redis, user_id, &effective)
+                    .await;
+            }
+            debug!("User {} set status to {}", user_id, status.as_str());
+        }
 
-            let event = ServerEvent::PresenceUpdate {
-                user_id,
-                status: status_str.to_string(),
+        ClientEvent::PresencePing => {
+            if let Some(effective) = presence_registry::activity_ping(&state.redis, user_id).await?
+            {
+                presence_registry::publish_change(&state.db, &state.redis, user_id, &effective)
+                    .await;
+            }
+        }
+
+        ClientEvent::RequestGuildMembers {
+            guild_id,
+            role_id,
+            online_only,
+            after,
+            limit,
+            nonce,
+        } => {
+            if !db::is_guild_member(&state.db, guild_id, user_id).await? {
+                tx.send(ServerEvent::Error {
+                    code: "forbidden".to_string(),
+                    message: "You are not a member of this guild".to_string(),
+                })
+                .await?;
+                return Ok(());
+            }
+
+            let limit = limit
+                .unwrap_or(DEFAULT_MEMBER_CHUNK)
+                .clamp(1, crate::guild::handlers::MAX_MEMBER_PAGE);
+            let query = crate::guild::types::ListMembersQuery {
+                limit: Some(limit),
+                after,
+                role_id,
+                online: online_only,
+            };
+            let members =
+                crate::guild::handlers::query_members(&state.db, guild_id, &query).await?;
+            let next_cursor = if members.len() as i64 == limit {
+                members.last().map(|member| member.user_id)
+            } else {
+                None
             };
-            broadcast_presence_update(state, user_id, &event).await;
-            debug!("User {} set status to {}", user_id, status_str);
+            tx.send(ServerEvent::GuildMemberChunk {
+                guild_id,
+                members,
+                next_cursor,
+                nonce,
+            })
+            .await?;
         }
 
         ClientEvent::AdminSubscribe => {
@@ -1495,12 +2340,28 @@ pub async fn handle_client_message(
                 return Ok(());
             }
 
+            subscribed_channels
+                .topics()
+                .subscribe(channels::ADMIN_EVENTS)
+                .await?;
             *admin_subscribed.write().await = true;
             debug!("Admin {} subscribed to admin events", user_id);
         }
 
+        ClientEvent::Resume { .. } => {
+            tx.send(ServerEvent::Error {
+                code: "invalid_resume".to_string(),
+                message: "Resume must be the first message on a connection".to_string(),
+            })
+            .await?;
+        }
+
         ClientEvent::AdminUnsubscribe => {
             *admin_subscribed.write().await = false;
+            subscribed_channels
+                .topics()
+                .unsubscribe(channels::ADMIN_EVENTS)
+                .await?;
             debug!("Admin {} unsubscribed from admin events", user_id);
         }
     }
@@ -1508,58 +2369,63 @@ pub async fn handle_client_message(
     Ok(())
 }
 
-/// Parameters for the Redis pub/sub handler.
+/// Instructions from a connection to its pub/sub forwarder.
+enum SessionCommand {
+    /// Resume an earlier session (the client's first message).
+    Resume { session_id: Uuid, last_seq: u64 },
+    /// The connection dropped; keep buffering events for a resume.
+    Detach,
+    /// The connection ended for good.
+    End,
+}
+
+/// Parameters for the pub/sub handler.
 struct HandlePubsubParams {
+    db: sqlx::PgPool,
+    redis: Client,
     tx: mpsc::Sender<ServerEvent>,
-    subscribed_channels: Arc<tokio::sync::RwLock<HashSet<Uuid>>>,
+    /// Session-numbered events, already serialized.
+    sequenced_tx: mpsc::Sender<String>,
+    connection_id: Uuid,
+    subscribed_channels: ChannelSubscriptions,
     admin_subscribed: Arc<tokio::sync::RwLock<bool>>,
-    blocked_users: Arc<tokio::sync::RwLock<HashSet<Uuid>>>,
+    blocked_users: BlockSet,
+    /// Notified when `SessionRevoked` arrives, to close the connection.
+    revoked: Arc<Notify>,
     user_id: Uuid,
+    /// Locale system messages are rendered in.
+    locale: &'static str,
     friend_ids: Vec<Uuid>,
     guild_ids: Vec<Uuid>,
 }
 
-/// Handle Redis pub/sub messages.
-async fn handle_pubsub(redis: Client, params: HandlePubsubParams) {
-    // Create a subscriber client
-    let subscriber = redis.clone_new();
-
-    // Connect (fred 8.x returns JoinHandle)
-    let _connect_handle = subscriber.connect();
-
-    if let Err(e) = subscriber.wait_for_connect().await {
-        error!("Subscriber connection failed: {}", e);
-        return;
-    }
-
-    // Subscribe to pattern for all channel events
-    let mut pubsub_stream = subscriber.message_rx();
-
-    // Subscribe to channel pattern
-    if let Err(e) = subscriber.psubscribe("channel:*").await {
-        error!("Failed to psubscribe: {}", e);
-        return;
-    }
-
+/// Handle pub/sub messages fanned out to this connection.
+///
+/// Subscribes the connection's standing topics (own user events, friends'
+/// presence, guild events); channel and admin topics follow `Subscribe` and
+/// `AdminSubscribe` requests. Forwarded events are numbered in the
+/// connection's session. After the connection drops the task keeps buffering
+/// them for [`session::RESUME_WINDOW`], until another connection resumes the
+/// session.
+async fn handle_pubsub(
+    topics: ConnectionTopics,
+    mut messages: mpsc::Receiver<TopicMessage>,
+    mut session: Option<Session>,
+    mut control: mpsc::Receiver<SessionCommand>,
+    params: HandlePubsubParams,
+) {
     // Subscribe to user's own events channel (for preferences sync, etc.)
     let user_channel = channels::user_events(params.user_id);
-    if let Err(e) = subscriber.subscribe(&user_channel).await {
+    if let Err(e) = topics.subscribe(&user_channel).await {
         warn!("Failed to subscribe to user events channel: {}", e);
     } else {
         debug!("Subscribed to user events channel: {}", user_channel);
     }
 
-    // Subscribe to admin events channel
-    if let Err(e) = subscriber.subscribe(channels::ADMIN_EVENTS).await {
-        warn!("Failed to subscribe to admin events: {}", e);
-    } else {
-        debug!("Subscribed to admin events channel");
-    }
-
     // Subscribe to friends' presence channels
     for friend_id in &params.friend_ids {
         let presence_channel = channels::user_presence(*friend_id);
-        if let Err(e) = subscriber.subscribe(&presence_channel).await {
+        if let Err(e) = topics.subscribe(&presence_channel).await {
             warn!(
                 "Failed to subscribe to presence channel for friend {}: {}",
                 friend_id, e
@@ -1572,7 +2438,7 @@ async fn handle_pubsub(redis: Client, params: HandlePubsubParams) {
     // Subscribe to guild event channels for state sync
     for guild_id in &params.guild_ids {
         let guild_channel = channels::guild_events(*guild_id);
-        if let Err(e) = subscriber.subscribe(&guild_channel).await {
+        if let Err(e) = topics.subscribe(&guild_channel).await {
             warn!(
                 "Failed to subscribe to guild events channel for guild {}: {}",
                 guild_id, e
@@ -1582,142 +2448,365 @@ async fn handle_pubsub(redis: Client, params: HandlePubsubParams) {
         }
     }
 
-    while let Ok(message) = pubsub_stream.recv().await {
-        let channel_name = message.channel.to_string();
-
-        // Handle channel events (channel:{uuid})
-        if let Some(uuid_str) = channel_name.strip_prefix("channel:") {
-            if let Ok(channel_id) = Uuid::parse_str(uuid_str) {
-                // Check if we're subscribed to this channel
-                if params
-                    .subscribed_channels
-                    .read()
-                    .await
-                    .contains(&channel_id)
-                {
-                    // Parse and forward the event (with block filtering)
-                    if let Some(payload) = message.value.as_str() {
-                        if let Ok(event) = serde_json::from_str::<ServerEvent>(&payload) {
-                            // Filter events from blocked users
-                            let blocked = params.blocked_users.read().await;
-                            let should_filter = match &event {
-                                ServerEvent::MessageNew { message, .. } => message
-                                    .get("author")
-                                    .and_then(|a| a.get("id"))
-                                    .and_then(|id| id.as_str())
-                                    .and_then(|id| Uuid::parse_str(id).ok())
-                                    .is_some_and(|author_id| blocked.contains(&author_id)),
-                                ServerEvent::TypingStart { user_id: uid, .. }
-                                | ServerEvent::TypingStop { user_id: uid, .. }
-                                | ServerEvent::VoiceUserJoined { user_id: uid, .. }
-                                | ServerEvent::VoiceUserLeft { user_id: uid, .. }
-                                | ServerEvent::CallParticipantJoined { user_id: uid, .. }
-                                | ServerEvent::CallParticipantLeft { user_id: uid, .. } => {
-                                    blocked.contains(uid)
-                                }
-                                _ => false,
-                            };
-                            drop(blocked);
-
-                            if !should_filter && params.tx.send(event).await.is_err() {
-                                break;
-                            }
-                        }
-                    }
+    let mut touch = tokio::time::interval(session::TOUCH_INTERVAL);
+    touch.tick().await; // consume immediate first tick
+    let mut detached_until: Option<tokio::time::Instant> = None;
+    let mut control_open = true;
+
+    loop {
+        tokio::select! {
+            message = messages.recv() => {
+                let Some((topic, payload)) = message else { break };
+                if !route_event(&params, &user_channel, session.as_ref(), &topic, &payload).await {
+                    break;
                 }
             }
-        }
-        // Handle user events (user:{uuid}) - for preferences sync across devices
-        else if channel_name == user_channel {
-            if let Some(payload) = message.value.as_str() {
-                if let Ok(event) = serde_json::from_str::<ServerEvent>(&payload) {
-                    // Handle block/unblock events to update in-memory set
-                    match &event {
-                        ServerEvent::UserBlocked {
-                            user_id: blocked_id,
-                        } => {
-                            params.blocked_users.write().await.insert(*blocked_id);
-                        }
-                        ServerEvent::UserUnblocked {
-                            user_id: unblocked_id,
-                        } => {
-                            params.blocked_users.write().await.remove(unblocked_id);
-                        }
-                        _ => {}
-                    }
-
-                    if params.tx.send(event).await.is_err() {
+            command = control.recv(), if control_open => match command {
+                Some(SessionCommand::Resume { session_id, last_seq }) => {
+                    resume_session(&params, &mut session, session_id, last_seq).await;
+                }
+                Some(SessionCommand::Detach) => {
+                    if session.is_none() || !touch_session(&params, session.as_ref()).await {
                         break;
                     }
+                    detached_until = Some(tokio::time::Instant::now() + session::RESUME_WINDOW);
                 }
-            }
-        }
-        // Handle admin events
-        else if channel_name == channels::ADMIN_EVENTS {
-            // Only forward if user is subscribed to admin events
-            if *params.admin_subscribed.read().await {
-                if let Some(payload) = message.value.as_str() {
-                    if let Ok(event) = serde_json::from_str::<ServerEvent>(&payload) {
-                        if params.tx.send(event).await.is_err() {
-                            break;
-                        }
+                Some(SessionCommand::End) => break,
+                None => {
+                    control_open = false;
+                    if detached_until.is_none() {
+                        break;
                     }
                 }
+            },
+            _ = touch.tick() => {
+                if !touch_session(&params, session.as_ref()).await {
+                    break;
+                }
             }
+            () = tokio::time::sleep_until(detached_until.unwrap_or_else(tokio::time::Instant::now)),
+                if detached_until.is_some() => break,
         }
-        // Handle presence events (presence:{uuid})
-        else if channel_name.starts_with("presence:") {
-            // Forward presence updates from friends (filter blocked users)
-            if let Some(payload) = message.value.as_str() {
-                if let Ok(event) = serde_json::from_str::<ServerEvent>(&payload) {
-                    let should_filter = match &event {
-                        ServerEvent::PresenceUpdate { user_id: uid, .. }
-                        | ServerEvent::RichPresenceUpdate { user_id: uid, .. } => {
-                            params.blocked_users.read().await.contains(uid)
+    }
+
+    if let Some(session) = &session {
+        if let Err(e) = session.end().await {
+            warn!(session_id = %session.id(), error = %e, "Failed to end session");
+        }
+    }
+    params.subscribed_channels.close().await;
+}
+
+/// Forward one pub/sub message if this connection should see it.
+///
+/// Returns `false` once the forwarder should stop.
+async fn route_event(
+    params: &HandlePubsubParams,
+    user_channel: &str,
+    session: Option<&Session>,
+    channel_name: &str,
+    payload: &str,
+) -> bool {
+    // Handle channel events (channel:{uuid})
+    if let Some(uuid_str) = channel_name.strip_prefix("channel:") {
+        if let Ok(channel_id) = Uuid::parse_str(uuid_str) {
+            // Check if we're subscribed to this channel
+            if params.subscribed_channels.contains(channel_id).await {
+                // Parse and forward the event (with block filtering)
+                if let Ok(mut event) = serde_json::from_str::<ServerEvent>(payload) {
+                    // Filter events from blocked users
+                    let should_filter = params.blocked_users.hides(&event).await;
+
+                    // Re-render system messages in the connection's locale
+                    let localized = match &mut event {
+                        ServerEvent::MessageNew { message, .. } => {
+                            system_messages::localize_json(message, params.locale)
                         }
                         _ => false,
                     };
+                    let localized = localized
+                        .then(|| serde_json::to_string(&event).ok())
+                        .flatten();
+                    let payload = localized.as_deref().unwrap_or(payload);
 
-                    if !should_filter && params.tx.send(event).await.is_err() {
-                        break;
+                    if !should_filter && !deliver(params, session, event, payload).await {
+                        return false;
                     }
                 }
             }
         }
-        // Handle user events (user:{uuid}) for cross-device sync
-        else if channel_name.starts_with("user:") {
-            // Forward all user-targeted events (read sync, etc.)
-            if let Some(payload) = message.value.as_str() {
-                if let Ok(event) = serde_json::from_str::<ServerEvent>(&payload) {
-                    if params.tx.send(event).await.is_err() {
-                        break;
-                    }
+    }
+    // Handle user events (user:{uuid}) - for preferences sync across devices
+    else if channel_name == user_channel {
+        if let Ok(event) = serde_json::from_str::<ServerEvent>(payload) {
+            // Keep the block set current
+            params
+                .blocked_users
+                .apply(&params.redis, params.user_id, &event)
+                .await;
+
+            let revoked = matches!(event, ServerEvent::SessionRevoked { .. });
+            if !deliver(params, session, event, payload).await {
+                return false;
+            }
+            // A revoked session ends here and cannot be resumed
+            if revoked {
+                params.revoked.notify_one();
+                return false;
+            }
+        }
+    }
+    // Handle admin events
+    else if channel_name == channels::ADMIN_EVENTS {
+        // Only forward if user is subscribed to admin events
+        if *params.admin_subscribed.read().await {
+            if let Ok(event) = serde_json::from_str::<ServerEvent>(payload) {
+                if !deliver(params, session, event, payload).await {
+                    return false;
                 }
             }
         }
-        // Handle guild events (guild:{uuid}) for state sync
-        else if channel_name.starts_with("guild:") {
-            // Forward guild/member patch events to all guild members
-            if let Some(payload) = message.value.as_str() {
-                if let Ok(event) = serde_json::from_str::<ServerEvent>(&payload) {
-                    if params.tx.send(event).await.is_err() {
-                        break;
-                    }
+    }
+    // Handle presence events (presence:{uuid})
+    else if channel_name.starts_with("presence:") {
+        // Forward presence updates from friends (filter blocked users)
+        if let Ok(event) = serde_json::from_str::<ServerEvent>(payload) {
+            let should_filter = params.blocked_users.hides(&event).await;
+
+            if !should_filter && !deliver(params, session, event, payload).await {
+                return false;
+            }
+        }
+    }
+    // Handle user events (user:{uuid}) for cross-device sync
+    else if channel_name.starts_with("user:") {
+        // Forward all user-targeted events (read sync, etc.)
+        if let Ok(event) = serde_json::from_str::<ServerEvent>(payload) {
+            if !deliver(params, session, event, payload).await {
+                return false;
+            }
+        }
+    }
+    // Handle guild events (guild:{uuid}) for state sync
+    else if channel_name.starts_with("guild:") {
+        // Forward guild/member patch events to all guild members
+        if let Ok(event) = serde_json::from_str::<ServerEvent>(payload) {
+            // Role changes can revoke VIEW_CHANNEL on subscribed channels
+            if let Some(guild_id) = permission_change_guild(&event, params.user_id) {
+                tokio::spawn(revalidate_subscriptions(
+                    params.db.clone(),
+                    params.user_id,
+                    guild_id,
+                    params.subscribed_channels.clone(),
+                    params.tx.clone(),
+                ));
+            }
+
+            if !deliver(params, session, event, payload).await {
+                return false;
+            }
+        }
+    }
+    true
+}
+
+/// Send an event to the client, numbered in the session if there is one.
+///
+/// Returns `false` once another connection resumed the session. A closed
+/// connection is not an error: events are still buffered for a resume.
+async fn deliver(
+    params: &HandlePubsubParams,
+    session: Option<&Session>,
+    event: ServerEvent,
+    payload: &str,
+) -> bool {
+    let Some(session) = session else {
+        let _ = params.tx.send(event).await;
+        return true;
+    };
+    match session.append(payload).await {
+        Ok(Some(sequenced)) => {
+            let _ = params.sequenced_tx.send(sequenced).await;
+            true
+        }
+        Ok(None) => false,
+        Err(e) => {
+            warn!(session_id = %session.id(), error = %e, "Failed to buffer session event");
+            let _ = params.tx.send(event).await;
+            true
+        }
+    }
+}
+
+/// Refresh the session. Returns `false` once another connection resumed it.
+async fn touch_session(params: &HandlePubsubParams, session: Option<&Session>) -> bool {
+    let Some(session) = session else {
+        return true;
+    };
+    let channels = params.subscribed_channels.snapshot().await;
+    match session.touch(&channels).await {
+        Ok(owned) => owned,
+        Err(e) => {
+            warn!(session_id = %session.id(), error = %e, "Failed to refresh session");
+            true
+        }
+    }
+}
+
+/// Resume `session_id` on this connection, replaying what the client missed
+/// and answering with `resumed` or `resume_failed`.
+async fn resume_session(
+    params: &HandlePubsubParams,
+    current: &mut Option<Session>,
+    session_id: Uuid,
+    last_seq: u64,
+) {
+    let reply = match take_over_session(params, session_id, last_seq).await {
+        Ok(resumption) => {
+            let replayed = resumption.events.len();
+            for event in resumption.events {
+                let _ = params.sequenced_tx.send(event).await;
+            }
+            if let Some(fresh) = current.replace(resumption.session) {
+                if let Err(e) = fresh.end().await {
+                    warn!(session_id = %fresh.id(), error = %e, "Failed to end session");
                 }
             }
+            debug!(
+                "User {} resumed session {} ({} events replayed)",
+                params.user_id, session_id, replayed
+            );
+            ServerEvent::Resumed {
+                session_id,
+                last_seq: resumption.last_seq,
+                replayed,
+            }
+        }
+        Err(e) => {
+            debug!(
+                "User {} could not resume session {}: {}",
+                params.user_id, session_id, e
+            );
+            ServerEvent::ResumeFailed {
+                reason: e.reason().to_string(),
+            }
+        }
+    };
+
+    match serde_json::to_string(&reply) {
+        Ok(json) => {
+            let _ = params.sequenced_tx.send(json).await;
         }
+        Err(e) => error!("Failed to serialize event: {}", e),
     }
 }
 
-/// Update user presence in the database.
-async fn update_presence(state: &AppState, user_id: Uuid, status: &str) -> Result<(), sqlx::Error> {
-    sqlx::query("UPDATE users SET status = $1::user_status WHERE id = $2")
-        .bind(status)
-        .bind(user_id)
-        .execute(&state.db)
-        .await?;
+/// Subscribe the session's channels (those the user can still view), then
+/// take the session over.
+async fn take_over_session(
+    params: &HandlePubsubParams,
+    session_id: Uuid,
+    last_seq: u64,
+) -> Result<Resumption, ResumeError> {
+    let channels = Session::peek_channels(&params.redis, session_id, params.user_id).await?;
+    for channel_id in channels {
+        if crate::permissions::require_channel_access(&params.db, params.user_id, channel_id)
+            .await
+            .is_err()
+        {
+            continue;
+        }
+        match params.subscribed_channels.insert(channel_id).await {
+            Ok(Some(evicted)) => {
+                let _ = params
+                    .tx
+                    .send(ServerEvent::SubscriptionEvicted {
+                        channel_id: evicted,
+                        limit: fanout::MAX_CHANNEL_SUBSCRIPTIONS,
+                    })
+                    .await;
+            }
+            Ok(None) => {}
+            Err(e) => {
+                warn!(
+                    channel_id = %channel_id,
+                    error = %e,
+                    "Failed to restore channel subscription"
+                );
+            }
+        }
+    }
+    Session::resume(
+        &params.redis,
+        session_id,
+        params.user_id,
+        params.connection_id,
+        last_seq,
+    )
+    .await
+}
 
-    Ok(())
+/// Guild in which `user_id`'s permissions may have changed because of `event`.
+fn permission_change_guild(event: &ServerEvent, user_id: Uuid) -> Option<Uuid> {
+    match event {
+        ServerEvent::RoleUpdate { guild_id, .. } | ServerEvent::RoleDelete { guild_id, .. } => {
+            Some(*guild_id)
+        }
+        ServerEvent::MemberRolesUpdate {
+            guild_id,
+            user_id: member_id,
+            ..
+        } if *member_id == user_id => Some(*guild_id),
+        _ => None,
+    }
+}
+
+/// Drop subscriptions to guild channels the user can no longer view.
+///
+/// `VIEW_CHANNEL` is only checked on `Subscribe`, so the subscription set acts
+/// as a permission cache that must be re-checked after role changes.
+async fn revalidate_subscriptions(
+    db: sqlx::PgPool,
+    user_id: Uuid,
+    guild_id: Uuid,
+    subscribed_channels: ChannelSubscriptions,
+    tx: mpsc::Sender<ServerEvent>,
+) {
+    let subscribed = subscribed_channels.snapshot().await;
+    if subscribed.is_empty() {
+        return;
+    }
+
+    let guild_channels: Vec<Uuid> = match sqlx::query_scalar(
+        "SELECT id FROM channels WHERE guild_id = $1 AND id = ANY($2)",
+    )
+    .bind(guild_id)
+    .bind(&subscribed)
+    .fetch_all(&db)
+    .await
+    {
+        Ok(ids) => ids,
+        Err(e) => {
+            warn!(guild_id = %guild_id, error = %e, "Failed to revalidate channel subscriptions");
+            return;
+        }
+    };
+
+    for channel_id in guild_channels {
+        if crate::permissions::require_channel_access(&db, user_id, channel_id)
+            .await
+            .is_err()
+        {
+            if let Err(e) = subscribed_channels.remove(channel_id).await {
+                warn!(channel_id = %channel_id, error = %e, "Failed to drop channel topic");
+            }
+            let _ = tx.send(ServerEvent::Unsubscribed { channel_id }).await;
+            debug!(
+                "User {} lost access to channel {}, unsubscribed",
+                user_id, channel_id
+            );
+        }
+    }
 }
 
 /// Get list of user's accepted friend IDs.
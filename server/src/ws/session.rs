@@ -0,0 +1,320 @@
+//! Resumable Sessions
+//!
+//! Every gateway connection runs a session that numbers the pub/sub events
+//! it forwards (`seq`, from 1) and keeps the last [`REPLAY_BUFFER_LEN`] of
+//! them in Redis. When a connection drops, its forwarder keeps buffering for
+//! [`RESUME_WINDOW`]; a client that reconnects within the window sends
+//! `resume { session_id, last_seq }` as its first message and is sent the
+//! events it missed instead of refetching all state.
+//!
+//! Per session:
+//! - `ws:session:{id}` — hash with the owning user (`user_id`), the
+//!   connection feeding the session (`owner`), the last sequence number
+//!   (`seq`), when the owner last refreshed it (`touched`, Unix ms) and the
+//!   subscribed channels (`channels`, JSON array).
+//! - `ws:session:{id}:events` — the last events as sent, oldest first.
+//!
+//! Resuming hands the session to the new connection in one script; the old
+//! forwarder's appends are rejected from then on and it stops. Replay is
+//! at-least-once: an event delivered on the new connection just before the
+//! handover can appear again in the replay.
+
+use std::time::Duration;
+
+use fred::interfaces::LuaInterface;
+use fred::prelude::*;
+use fred::types::FromValue;
+use uuid::Uuid;
+
+/// Events kept per session for replay.
+pub const REPLAY_BUFFER_LEN: usize = 500;
+
+/// How long a dropped connection keeps buffering events for a resume.
+pub const RESUME_WINDOW: Duration = Duration::from_secs(2 * 60);
+
+/// How often the forwarder refreshes its session.
+pub const TOUCH_INTERVAL: Duration = Duration::from_secs(10);
+
+/// A session not refreshed for this long lost its forwarder (e.g. the server
+/// holding it died) and may be missing events, so it cannot be resumed.
+const STALE_AFTER: Duration = Duration::from_secs(TOUCH_INTERVAL.as_secs() * 3);
+
+/// Applies one session operation.
+///
+/// KEYS: session hash, event list.
+/// ARGV: op, now (ms), connection ID, key TTL (ms), then per op:
+/// - `start`: user ID
+/// - `append`: event JSON, buffer length; returns the event as sent (with
+///   `seq`), or false if another connection owns the session
+/// - `touch`: channels JSON; returns 1, or 0 if not the owner
+/// - `end`: deletes the session if still the owner
+/// - `peek`: user ID, stale cutoff (ms); returns the channels JSON, or false
+/// - `resume`: user ID, stale cutoff (ms), last seq; returns
+///   `{status, seq, channels, events...}`
+const SESSION_LUA: &str = r#"
+local op = ARGV[1]
+local now = tonumber(ARGV[2])
+local ttl = tonumber(ARGV[4])
+
+local function refresh()
+    redis.call('HSET', KEYS[1], 'touched', now)
+    redis.call('PEXPIRE', KEYS[1], ttl)
+    redis.call('PEXPIRE', KEYS[2], ttl)
+end
+
+local function resumable()
+    if redis.call('HGET', KEYS[1], 'user_id') ~= ARGV[5] then
+        return false
+    end
+    local touched = tonumber(redis.call('HGET', KEYS[1], 'touched') or '0')
+    return now - touched <= tonumber(ARGV[6])
+end
+
+if op == 'start' then
+    redis.call('DEL', KEYS[2])
+    redis.call('HSET', KEYS[1], 'user_id', ARGV[5], 'owner', ARGV[3], 'seq', 0, 'channels', '[]')
+    refresh()
+    return 1
+end
+
+local owner = redis.call('HGET', KEYS[1], 'owner')
+
+if op == 'append' then
+    if owner ~= ARGV[3] then
+        return false
+    end
+    local seq = redis.call('HINCRBY', KEYS[1], 'seq', 1)
+    local event = '{"seq":' .. seq .. ',' .. string.sub(ARGV[5], 2)
+    redis.call('RPUSH', KEYS[2], event)
+    redis.call('LTRIM', KEYS[2], -tonumber(ARGV[6]), -1)
+    refresh()
+    return event
+elseif op == 'touch' then
+    if owner ~= ARGV[3] then
+        return 0
+    end
+    redis.call('HSET', KEYS[1], 'channels', ARGV[5])
+    refresh()
+    return 1
+elseif op == 'end' then
+    if owner == ARGV[3] then
+        redis.call('DEL', KEYS[1], KEYS[2])
+    end
+    return 1
+elseif op == 'peek' then
+    if not owner or not resumable() then
+        return false
+    end
+    return redis.call('HGET', KEYS[1], 'channels')
+elseif op == 'resume' then
+    if not owner or not resumable() then
+        return {'unknown_session'}
+    end
+    local seq = tonumber(redis.call('HGET', KEYS[1], 'seq'))
+    local missed = seq - tonumber(ARGV[7])
+    if missed < 0 then
+        return {'invalid_seq'}
+    end
+    if missed > redis.call('LLEN', KEYS[2]) then
+        return {'too_old'}
+    end
+    redis.call('HSET', KEYS[1], 'owner', ARGV[3])
+    refresh()
+    local result = {'ok', tostring(seq), redis.call('HGET', KEYS[1], 'channels')}
+    if missed > 0 then
+        for _, event in ipairs(redis.call('LRANGE', KEYS[2], -missed, -1)) do
+            table.insert(result, event)
+        end
+    end
+    return result
+end
+return false
+"#;
+
+fn session_key(session_id: Uuid) -> String {
+    format!("ws:session:{session_id}")
+}
+
+fn events_key(session_id: Uuid) -> String {
+    format!("ws:session:{session_id}:events")
+}
+
+fn now_millis() -> i64 {
+    chrono::Utc::now().timestamp_millis()
+}
+
+/// Why a session could not be resumed.
+#[derive(Debug, thiserror::Error)]
+pub enum ResumeError {
+    /// The session does not exist, expired, lost its forwarder, or belongs to
+    /// another user.
+    #[error("Unknown or expired session")]
+    UnknownSession,
+    /// `last_seq` is ahead of the session.
+    #[error("Sequence number is ahead of the session")]
+    InvalidSeq,
+    /// Some of the missed events are no longer buffered.
+    #[error("Missed events are no longer buffered")]
+    TooOld,
+    /// Redis error.
+    #[error("Session store unavailable: {0}")]
+    Redis(#[from] Error),
+}
+
+impl ResumeError {
+    /// Reason code sent to the client in `resume_failed`.
+    #[must_use]
+    pub const fn reason(&self) -> &'static str {
+        match self {
+            Self::UnknownSession => "unknown_session",
+            Self::InvalidSeq => "invalid_seq",
+            Self::TooOld => "too_old",
+            Self::Redis(_) => "unavailable",
+        }
+    }
+}
+
+/// A resumed session and what the client missed.
+#[derive(Debug)]
+pub struct Resumption {
+    /// The session, now fed by the resuming connection.
+    pub session: Session,
+    /// Sequence number of the last buffered event.
+    pub last_seq: u64,
+    /// Events after the client's `last_seq`, as sent.
+    pub events: Vec<String>,
+}
+
+/// One connection's handle on a session.
+#[derive(Debug)]
+pub struct Session {
+    redis: Client,
+    id: Uuid,
+    connection_id: Uuid,
+}
+
+impl Session {
+    /// Start a new session fed by `connection_id`.
+    pub async fn start(redis: &Client, user_id: Uuid, connection_id: Uuid) -> Result<Self, Error> {
+        let session = Self {
+            redis: redis.clone(),
+            id: Uuid::new_v4(),
+            connection_id,
+        };
+        let _: i64 = session.apply("start", &[user_id.to_string()]).await?;
+        Ok(session)
+    }
+
+    /// Session ID, as sent to the client.
+    #[must_use]
+    pub const fn id(&self) -> Uuid {
+        self.id
+    }
+
+    /// Number and buffer an event (pub/sub payload JSON). Returns the event
+    /// as it must be sent, or `None` once another connection resumed the
+    /// session.
+    pub async fn append(&self, payload: &str) -> Result<Option<String>, Error> {
+        self.apply(
+            "append",
+            &[payload.to_string(), REPLAY_BUFFER_LEN.to_string()],
+        )
+        .await
+    }
+
+    /// Refresh the session and record the subscribed channels. Returns
+    /// `false` once another connection resumed the session.
+    pub async fn touch(&self, channels: &[Uuid]) -> Result<bool, Error> {
+        let channels = serde_json::to_string(channels).unwrap_or_else(|_| "[]".to_string());
+        let owned: i64 = self.apply("touch", &[channels]).await?;
+        Ok(owned == 1)
+    }
+
+    /// Delete the session unless another connection resumed it.
+    pub async fn end(&self) -> Result<(), Error> {
+        let _: i64 = self.apply("end", &[]).await?;
+        Ok(())
+    }
+
+    /// Channels subscribed in a resumable session of `user_id`, without
+    /// taking it over.
+    ///
+    /// The resuming connection subscribes these before [`Session::resume`],
+    /// so no event published during the handover is lost.
+    pub async fn peek_channels(
+        redis: &Client,
+        session_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<Vec<Uuid>, ResumeError> {
+        let probe = Self {
+            redis: redis.clone(),
+            id: session_id,
+            connection_id: Uuid::nil(),
+        };
+        let channels: Option<String> = probe
+            .apply(
+                "peek",
+                &[user_id.to_string(), STALE_AFTER.as_millis().to_string()],
+            )
+            .await?;
+        let channels = channels.ok_or(ResumeError::UnknownSession)?;
+        Ok(serde_json::from_str(&channels).unwrap_or_default())
+    }
+
+    /// Take over `session_id` for `connection_id` and fetch the events after
+    /// `last_seq`.
+    pub async fn resume(
+        redis: &Client,
+        session_id: Uuid,
+        user_id: Uuid,
+        connection_id: Uuid,
+        last_seq: u64,
+    ) -> Result<Resumption, ResumeError> {
+        let session = Self {
+            redis: redis.clone(),
+            id: session_id,
+            connection_id,
+        };
+        let mut reply: Vec<String> = session
+            .apply(
+                "resume",
+                &[
+                    user_id.to_string(),
+                    STALE_AFTER.as_millis().to_string(),
+                    last_seq.to_string(),
+                ],
+            )
+            .await?;
+
+        match reply.first().map(String::as_str) {
+            Some("ok") if reply.len() >= 3 => {}
+            Some("invalid_seq") => return Err(ResumeError::InvalidSeq),
+            Some("too_old") => return Err(ResumeError::TooOld),
+            _ => return Err(ResumeError::UnknownSession),
+        }
+        let events = reply.split_off(3);
+        let last_seq = reply[1].parse().unwrap_or(last_seq);
+        Ok(Resumption {
+            session,
+            last_seq,
+            events,
+        })
+    }
+
+    async fn apply<R: FromValue>(&self, op: &str, args: &[String]) -> Result<R, Error> {
+        let mut argv = vec![
+            op.to_string(),
+            now_millis().to_string(),
+            self.connection_id.to_string(),
+            RESUME_WINDOW.as_millis().to_string(),
+        ];
+        argv.extend_from_slice(args);
+        self.redis
+            .eval(
+                SESSION_LUA,
+                vec![session_key(self.id), events_key(self.id)],
+                argv,
+            )
+            .await
+    }
+}
@@ -0,0 +1,193 @@
+//! Typing Registry
+//!
+//! Tracks who is typing in which channel in a Redis sorted set (score = expiry
+//! in Unix milliseconds). Broadcasts are coalesced: the first `Typing` from a
+//! user emits `TypingStart`, repeats only extend the 8-second TTL and re-emit
+//! `TypingStart` as a heartbeat at most every 5 seconds, and `TypingStop` is
+//! emitted once — on `StopTyping`, when the user sends a message, or when the
+//! sweeper finds the entry expired. All updates run as Lua scripts, so with
+//! several server instances only one of them broadcasts each change.
+
+use std::time::Duration;
+
+use fred::interfaces::LuaInterface;
+use fred::prelude::*;
+use tracing::warn;
+use uuid::Uuid;
+
+use super::{broadcast_to_channel, ServerEvent};
+
+/// Redis sorted set of `{channel_id}:{user_id}` entries.
+const TYPING_ZSET_KEY: &str = "typing:active";
+
+/// Redis hash of entry -> time of the last `TypingStart` broadcast.
+const TYPING_ANNOUNCED_KEY: &str = "typing:announced";
+
+/// How long a typing indicator lasts without a refresh.
+pub const TYPING_TTL: Duration = Duration::from_secs(8);
+
+/// Minimum time between `TypingStart` broadcasts for an ongoing indicator.
+///
+/// Lets clients expire indicators locally if a `TypingStop` is ever missed.
+const TYPING_HEARTBEAT: Duration = Duration::from_secs(5);
+
+/// How often expired indicators are swept.
+const SWEEP_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Registers or refreshes an entry; returns 1 if `TypingStart` should be broadcast.
+const START_TYPING_LUA: &str = r"
+local now = tonumber(ARGV[1])
+local added = redis.call('ZADD', KEYS[1], now + tonumber(ARGV[2]), ARGV[4])
+local last = tonumber(redis.call('HGET', KEYS[2], ARGV[4]) or '0')
+if added == 1 or now - last >= tonumber(ARGV[3]) then
+    redis.call('HSET', KEYS[2], ARGV[4], now)
+    return 1
+end
+return 0
+";
+
+/// Removes an entry; returns 1 if it existed.
+const STOP_TYPING_LUA: &str = r"
+redis.call('HDEL', KEYS[2], ARGV[1])
+return redis.call('ZREM', KEYS[1], ARGV[1])
+";
+
+/// Atomically removes and returns expired entries.
+const TAKE_EXPIRED_LUA: &str = r"
+local items = redis.call('ZRANGEBYSCORE', KEYS[1], '-inf', ARGV[1], 'LIMIT', 0, 500)
+if #items > 0 then
+    redis.call('ZREM', KEYS[1], unpack(items))
+    redis.call('HDEL', KEYS[2], unpack(items))
+end
+return items
+";
+
+fn entry(channel_id: Uuid, user_id: Uuid) -> String {
+    format!("{channel_id}:{user_id}")
+}
+
+fn parse_entry(entry: &str) -> Option<(Uuid, Uuid)> {
+    let (channel_id, user_id) = entry.split_once(':')?;
+    Some((
+        Uuid::parse_str(channel_id).ok()?,
+        Uuid::parse_str(user_id).ok()?,
+    ))
+}
+
+fn now_millis() -> i64 {
+    chrono::Utc::now().timestamp_millis()
+}
+
+/// Register (or refresh) a user as typing.
+///
+/// Broadcasts `TypingStart` if the user was not already typing, or as a
+/// heartbeat once `TYPING_HEARTBEAT` has passed since the last broadcast.
+pub async fn start_typing(redis: &Client, channel_id: Uuid, user_id: Uuid) -> Result<(), Error> {
+    let announce: i64 = redis
+        .eval(
+            START_TYPING_LUA,
+            vec![TYPING_ZSET_KEY, TYPING_ANNOUNCED_KEY],
+            vec![
+                now_millis().to_string(),
+                TYPING_TTL.as_millis().to_string(),
+                TYPING_HEARTBEAT.as_millis().to_string(),
+                entry(channel_id, user_id),
+            ],
+        )
+        .await?;
+
+    if announce == 1 {
+        broadcast_to_channel(
+            redis,
+            channel_id,
+            &ServerEvent::TypingStart {
+                channel_id,
+                user_id,
+            },
+        )
+        .await?;
+    }
+    Ok(())
+}
+
+/// Clear a user's typing indicator.
+///
+/// Broadcasts `TypingStop` only if the user was typing.
+pub async fn stop_typing(redis: &Client, channel_id: Uuid, user_id: Uuid) -> Result<(), Error> {
+    let removed: i64 = redis
+        .eval(
+            STOP_TYPING_LUA,
+            vec![TYPING_ZSET_KEY, TYPING_ANNOUNCED_KEY],
+            vec![entry(channel_id, user_id)],
+        )
+        .await?;
+
+    if removed == 1 {
+        broadcast_to_channel(
+            redis,
+            channel_id,
+            &ServerEvent::TypingStop {
+                channel_id,
+                user_id,
+            },
+        )
+        .await?;
+    }
+    Ok(())
+}
+
+/// Remove expired indicators and broadcast `TypingStop` for each.
+async fn sweep_expired(redis: &Client) -> Result<usize, Error> {
+    let expired: Vec<String> = redis
+        .eval(
+            TAKE_EXPIRED_LUA,
+            vec![TYPING_ZSET_KEY, TYPING_ANNOUNCED_KEY],
+            vec![now_millis().to_string()],
+        )
+        .await?;
+
+    for (channel_id, user_id) in expired.iter().filter_map(|e| parse_entry(e)) {
+        if let Err(e) = broadcast_to_channel(
+            redis,
+            channel_id,
+            &ServerEvent::TypingStop {
+                channel_id,
+                user_id,
+            },
+        )
+        .await
+        {
+            warn!(channel_id = %channel_id, error = %e, "Failed to broadcast typing expiry");
+        }
+    }
+    Ok(expired.len())
+}
+
+/// Spawn the background task that expires stale typing indicators.
+pub fn spawn_typing_sweeper(redis: Client) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(SWEEP_INTERVAL);
+        loop {
+            interval.tick().await;
+            if let Err(e) = sweep_expired(&redis).await {
+                warn!(error = %e, "Failed to sweep expired typing indicators");
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_entry_round_trip() {
+        let (channel_id, user_id) = (Uuid::new_v4(), Uuid::new_v4());
+        assert_eq!(
+            parse_entry(&entry(channel_id, user_id)),
+            Some((channel_id, user_id))
+        );
+        assert_eq!(parse_entry("not-an-entry"), None);
+        assert_eq!(parse_entry(&format!("{channel_id}:nope")), None);
+    }
+}
@@ -6,6 +6,7 @@
 #![allow(clippy::items_after_statements)]
 //! - Elevation required for ban/suspend operations
 //! - Elevation cache behavior
+//! - Step-up confirmation via `POST /api/admin/elevate`
 //!
 //! Run with: `cargo test --test integration admin_elevation`
 //! Run ignored (integration) tests: `cargo test --test integration admin_elevation -- --ignored`
@@ -55,8 +56,8 @@ fn test_system_admin_user_struct_clone() {
 #[test]
 fn test_elevation_duration_is_15_minutes() {
     // Verify the elevation duration constant
-    // The system uses 15 minutes as the elevation duration
-    let duration_minutes = 15;
+    let duration_minutes = vc_server::admin::ELEVATION_TTL_MINUTES;
+    assert_eq!(duration_minutes, 15);
     let now = Utc::now();
     let expires_at = now + Duration::minutes(duration_minutes);
 
@@ -82,6 +83,9 @@ fn test_admin_error_variants() {
     let invalid_mfa = AdminError::InvalidMfaCode;
     assert_eq!(invalid_mfa.to_string(), "Invalid MFA code");
 
+    let invalid_password = AdminError::InvalidPassword;
+    assert_eq!(invalid_password.to_string(), "Invalid password");
+
     let not_found = AdminError::NotFound("User".to_string());
     assert_eq!(not_found.to_string(), "User not found");
 
@@ -672,3 +676,94 @@ async fn test_elevation_ip_address_stored() {
     // Cleanup
     cleanup_test_user(&pool, admin_user.id).await;
 }
+
+// ============================================================================
+// HTTP Tests: step-up confirmation
+// ============================================================================
+
+fn elevate_request(token: &str, body: serde_json::Value) -> axum::http::Request<axum::body::Body> {
+    super::helpers::TestApp::request(axum::http::Method::POST, "/api/admin/elevate")
+        .header("Authorization", format!("Bearer {token}"))
+        .header("Content-Type", "application/json")
+        .extension(axum::extract::ConnectInfo(std::net::SocketAddr::from((
+            [127, 0, 0, 1],
+            0,
+        ))))
+        .body(axum::body::Body::from(body.to_string()))
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_elevate_requires_step_up_confirmation() {
+    use axum::http::StatusCode;
+
+    use super::helpers::{body_to_json, generate_access_token, TestApp};
+
+    let app = TestApp::new().await;
+    let admin = create_test_user(&app.pool, &format!("test_stepup_{}", Uuid::new_v4())).await;
+    let admin_id = admin.id;
+    let mut guard = app.cleanup_guard();
+    guard.add(move |pool| async move {
+        cleanup_test_user(&pool, admin_id).await;
+    });
+    grant_system_admin(&app.pool, admin.id, admin.id).await;
+    create_session(&app.pool, admin.id).await;
+    let token = generate_access_token(&app.config, admin.id);
+
+    // Some confirmation is required
+    let resp = app
+        .oneshot(elevate_request(&token, serde_json::json!({})))
+        .await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+    // TOTP codes need MFA to be enabled
+    let resp = app
+        .oneshot(elevate_request(
+            &token,
+            serde_json::json!({ "mfa_code": "123456" }),
+        ))
+        .await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(body_to_json(resp).await["error"], "mfa_required");
+
+    let resp = app
+        .oneshot(elevate_request(
+            &token,
+            serde_json::json!({ "password": "wrong" }),
+        ))
+        .await;
+    assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+    assert_eq!(body_to_json(resp).await["error"], "invalid_password");
+
+    let failures: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM system_audit_log WHERE actor_id = $1 AND action = 'admin.session.elevation_failed'",
+    )
+    .bind(admin.id)
+    .fetch_one(&app.pool)
+    .await
+    .unwrap();
+    assert_eq!(failures, 1);
+
+    // Still not elevated
+    let retention = || {
+        TestApp::request(axum::http::Method::GET, "/api/admin/retention")
+            .header("Authorization", format!("Bearer {token}"))
+            .body(axum::body::Body::empty())
+            .unwrap()
+    };
+    let resp = app.oneshot(retention()).await;
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+
+    let resp = app
+        .oneshot(elevate_request(
+            &token,
+            serde_json::json!({ "password": "Test123!@#", "reason": "step-up test" }),
+        ))
+        .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let body = body_to_json(resp).await;
+    assert_eq!(body["elevated"], true);
+
+    let resp = app.oneshot(retention()).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+}
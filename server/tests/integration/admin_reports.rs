@@ -8,9 +8,12 @@
 use axum::body::Body;
 use axum::http::Method;
 
+use vc_server::permissions::GuildPermissions;
+
 use super::helpers::{
-    body_to_json, create_elevated_session, create_test_report, create_test_user, delete_user,
-    generate_access_token, make_admin, TestApp,
+    body_to_json, create_channel, create_elevated_session, create_guild_with_default_role,
+    create_test_report, create_test_user, delete_guild, delete_user, generate_access_token,
+    insert_message, make_admin, TestApp,
 };
 
 // ============================================================================
@@ -383,3 +386,289 @@ async fn test_report_stats_success() {
     // Cleanup
     delete_user(&app.pool, admin).await;
 }
+
+// ============================================================================
+// Triage Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_claimed_report_is_locked_to_claimer() {
+    let app = TestApp::new().await;
+    let (admin, _) = create_test_user(&app.pool).await;
+    make_admin(&app.pool, admin).await;
+    create_elevated_session(&app.pool, admin).await;
+    let token = generate_access_token(&app.config, admin);
+    let (other_admin, _) = create_test_user(&app.pool).await;
+    make_admin(&app.pool, other_admin).await;
+    create_elevated_session(&app.pool, other_admin).await;
+    let other_token = generate_access_token(&app.config, other_admin);
+
+    let (reporter, _) = create_test_user(&app.pool).await;
+    let (target, _) = create_test_user(&app.pool).await;
+    let report_id = create_test_report(&app.pool, reporter, target).await;
+
+    let req = TestApp::request(
+        Method::POST,
+        &format!("/api/admin/reports/{report_id}/claim"),
+    )
+    .header("Authorization", format!("Bearer {token}"))
+    .body(Body::empty())
+    .unwrap();
+    let resp = app.oneshot(req).await;
+    assert_eq!(resp.status(), 200);
+    let json = body_to_json(resp).await;
+    assert!(json["claimed_at"].is_string());
+
+    // Another admin can neither resolve nor release the claim
+    let req = TestApp::request(
+        Method::POST,
+        &format!("/api/admin/reports/{report_id}/resolve"),
+    )
+    .header("Content-Type", "application/json")
+    .header("Authorization", format!("Bearer {other_token}"))
+    .body(Body::from(
+        serde_json::json!({ "resolution_action": "dismissed" }).to_string(),
+    ))
+    .unwrap();
+    let resp = app.oneshot(req).await;
+    assert_eq!(resp.status(), 409);
+    assert_eq!(body_to_json(resp).await["error"], "REPORT_CLAIMED");
+
+    let req = TestApp::request(
+        Method::DELETE,
+        &format!("/api/admin/reports/{report_id}/claim"),
+    )
+    .header("Authorization", format!("Bearer {other_token}"))
+    .body(Body::empty())
+    .unwrap();
+    let resp = app.oneshot(req).await;
+    assert_eq!(resp.status(), 409);
+
+    // ...unless forced, which returns the report to the open queue
+    let req = TestApp::request(
+        Method::DELETE,
+        &format!("/api/admin/reports/{report_id}/claim?force=true"),
+    )
+    .header("Authorization", format!("Bearer {other_token}"))
+    .body(Body::empty())
+    .unwrap();
+    let resp = app.oneshot(req).await;
+    assert_eq!(resp.status(), 200);
+    let json = body_to_json(resp).await;
+    assert_eq!(json["status"], "pending");
+    assert!(json["assigned_admin_id"].is_null());
+    assert!(json["claimed_at"].is_null());
+
+    // Resolving an open report claims it on the way
+    let req = TestApp::request(
+        Method::POST,
+        &format!("/api/admin/reports/{report_id}/resolve"),
+    )
+    .header("Content-Type", "application/json")
+    .header("Authorization", format!("Bearer {other_token}"))
+    .body(Body::from(
+        serde_json::json!({ "resolution_action": "dismissed" }).to_string(),
+    ))
+    .unwrap();
+    let resp = app.oneshot(req).await;
+    assert_eq!(resp.status(), 200);
+    let json = body_to_json(resp).await;
+    assert_eq!(json["status"], "dismissed");
+    assert_eq!(json["assigned_admin_id"], other_admin.to_string());
+    assert_eq!(json["resolved_by"], other_admin.to_string());
+
+    // Cleanup
+    delete_user(&app.pool, reporter).await;
+    delete_user(&app.pool, target).await;
+    delete_user(&app.pool, admin).await;
+    delete_user(&app.pool, other_admin).await;
+}
+
+#[tokio::test]
+async fn test_report_notes() {
+    let app = TestApp::new().await;
+    let (admin, admin_name) = create_test_user(&app.pool).await;
+    make_admin(&app.pool, admin).await;
+    create_elevated_session(&app.pool, admin).await;
+    let token = generate_access_token(&app.config, admin);
+
+    let (reporter, _) = create_test_user(&app.pool).await;
+    let (target, _) = create_test_user(&app.pool).await;
+    let report_id = create_test_report(&app.pool, reporter, target).await;
+
+    let req = TestApp::request(
+        Method::POST,
+        &format!("/api/admin/reports/{report_id}/notes"),
+    )
+    .header("Content-Type", "application/json")
+    .header("Authorization", format!("Bearer {token}"))
+    .body(Body::from(
+        serde_json::json!({ "body": "Asked the reporter for context" }).to_string(),
+    ))
+    .unwrap();
+    let resp = app.oneshot(req).await;
+    assert_eq!(resp.status(), 201);
+
+    let req = TestApp::request(
+        Method::POST,
+        &format!("/api/admin/reports/{report_id}/notes"),
+    )
+    .header("Content-Type", "application/json")
+    .header("Authorization", format!("Bearer {token}"))
+    .body(Body::from(serde_json::json!({ "body": "" }).to_string()))
+    .unwrap();
+    let resp = app.oneshot(req).await;
+    assert_eq!(resp.status(), 400);
+
+    let req = TestApp::request(
+        Method::GET,
+        &format!("/api/admin/reports/{report_id}/notes"),
+    )
+    .header("Authorization", format!("Bearer {token}"))
+    .body(Body::empty())
+    .unwrap();
+    let resp = app.oneshot(req).await;
+    assert_eq!(resp.status(), 200);
+    let json = body_to_json(resp).await;
+    let notes = json.as_array().expect("notes should be an array");
+    assert_eq!(notes.len(), 1);
+    assert_eq!(notes[0]["body"], "Asked the reporter for context");
+    assert_eq!(notes[0]["author_username"], admin_name);
+
+    // Cleanup
+    delete_user(&app.pool, reporter).await;
+    delete_user(&app.pool, target).await;
+    delete_user(&app.pool, admin).await;
+}
+
+#[tokio::test]
+async fn test_report_evidence_survives_message_deletion() {
+    let app = TestApp::new().await;
+    let (admin, _) = create_test_user(&app.pool).await;
+    make_admin(&app.pool, admin).await;
+    create_elevated_session(&app.pool, admin).await;
+    let token = generate_access_token(&app.config, admin);
+
+    let (reporter, _) = create_test_user(&app.pool).await;
+    let reporter_token = generate_access_token(&app.config, reporter);
+    let (target, _) = create_test_user(&app.pool).await;
+    let guild_id =
+        create_guild_with_default_role(&app.pool, target, GuildPermissions::empty()).await;
+    let channel_id = create_channel(&app.pool, guild_id, "general").await;
+    let context_id = insert_message(&app.pool, channel_id, target, "context message").await;
+    let message_id = insert_message(&app.pool, channel_id, target, "reported message").await;
+
+    // Reporting a message captures it as evidence
+    let req = TestApp::request(Method::POST, "/api/reports")
+        .header("Content-Type", "application/json")
+        .header("Authorization", format!("Bearer {reporter_token}"))
+        .body(Body::from(
+            serde_json::json!({
+                "target_type": "message",
+                "target_user_id": target,
+                "target_message_id": message_id,
+                "category": "harassment",
+            })
+            .to_string(),
+        ))
+        .unwrap();
+    let resp = app.oneshot(req).await;
+    assert_eq!(resp.status(), 200);
+    let report_id = body_to_json(resp).await["id"].as_str().unwrap().to_string();
+
+    // Admins can link further messages, once each
+    for expected in [201, 409] {
+        let req = TestApp::request(
+            Method::POST,
+            &format!("/api/admin/reports/{report_id}/evidence"),
+        )
+        .header("Content-Type", "application/json")
+        .header("Authorization", format!("Bearer {token}"))
+        .body(Body::from(
+            serde_json::json!({ "message_id": context_id }).to_string(),
+        ))
+        .unwrap();
+        assert_eq!(app.oneshot(req).await.status(), expected);
+    }
+
+    // The author deletes the message, then the channel goes away entirely
+    sqlx::query("UPDATE messages SET deleted_at = NOW(), content = '[deleted]' WHERE id = $1")
+        .bind(message_id)
+        .execute(&app.pool)
+        .await
+        .unwrap();
+    delete_guild(&app.pool, guild_id).await;
+
+    let req = TestApp::request(
+        Method::GET,
+        &format!("/api/admin/reports/{report_id}/evidence"),
+    )
+    .header("Authorization", format!("Bearer {token}"))
+    .body(Body::empty())
+    .unwrap();
+    let resp = app.oneshot(req).await;
+    assert_eq!(resp.status(), 200);
+    let json = body_to_json(resp).await;
+    let evidence = json.as_array().expect("evidence should be an array");
+    assert_eq!(evidence.len(), 2);
+    assert_eq!(evidence[0]["content"], "reported message");
+    assert!(evidence[0]["added_by"].is_null());
+    assert!(evidence[0]["message_id"].is_null());
+    assert_eq!(evidence[0]["channel_id"], channel_id.to_string());
+    assert_eq!(evidence[1]["content"], "context message");
+    assert_eq!(evidence[1]["added_by"], admin.to_string());
+
+    // Cleanup
+    delete_user(&app.pool, reporter).await;
+    delete_user(&app.pool, target).await;
+    delete_user(&app.pool, admin).await;
+}
+
+#[tokio::test]
+async fn test_report_sla_metrics() {
+    let app = TestApp::new().await;
+    let (admin, _) = create_test_user(&app.pool).await;
+    make_admin(&app.pool, admin).await;
+    create_elevated_session(&app.pool, admin).await;
+    let token = generate_access_token(&app.config, admin);
+
+    let (reporter, _) = create_test_user(&app.pool).await;
+    let (target, _) = create_test_user(&app.pool).await;
+    let report_id = create_test_report(&app.pool, reporter, target).await;
+
+    let req = TestApp::request(
+        Method::POST,
+        &format!("/api/admin/reports/{report_id}/resolve"),
+    )
+    .header("Content-Type", "application/json")
+    .header("Authorization", format!("Bearer {token}"))
+    .body(Body::from(
+        serde_json::json!({ "resolution_action": "warned" }).to_string(),
+    ))
+    .unwrap();
+    assert_eq!(app.oneshot(req).await.status(), 200);
+
+    let req = TestApp::request(
+        Method::GET,
+        "/api/admin/observability/report-sla?range=24h&sla_hours=4",
+    )
+    .header("Authorization", format!("Bearer {token}"))
+    .body(Body::empty())
+    .unwrap();
+    let resp = app.oneshot(req).await;
+    assert_eq!(resp.status(), 200);
+
+    let json = body_to_json(resp).await;
+    assert_eq!(json["sla_secs"], 4 * 3600);
+    assert!(json["backlog"]["open"].is_number());
+    assert!(json["created"].as_i64().unwrap() >= 1);
+    assert!(json["time_to_claim"]["count"].as_i64().unwrap() >= 1);
+    assert!(json["time_to_resolve"]["count"].as_i64().unwrap() >= 1);
+    assert!(json["time_to_resolve"]["p50_secs"].is_number());
+    assert!(json["closed_within_sla"].as_i64().unwrap() >= 1);
+
+    // Cleanup
+    delete_user(&app.pool, reporter).await;
+    delete_user(&app.pool, target).await;
+    delete_user(&app.pool, admin).await;
+}
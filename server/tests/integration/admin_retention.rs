@@ -0,0 +1,86 @@
+//! HTTP Integration Tests for Instance Data Retention Settings
+//!
+//! Tests `/api/admin/retention`, which requires system admin + elevated session.
+//! Only updates that keep the defaults are sent, since the settings are
+//! shared by every test running against the database.
+//!
+//! Run with: `cargo test --test integration admin_retention -- --nocapture`
+
+use axum::body::Body;
+use axum::http::{Method, StatusCode};
+
+use super::helpers::{
+    body_to_json, create_elevated_session, create_test_user, generate_access_token, make_admin,
+    TestApp,
+};
+
+fn patch_retention(token: &str, body: serde_json::Value) -> axum::http::Request<Body> {
+    TestApp::request(Method::PATCH, "/api/admin/retention")
+        .header("Authorization", format!("Bearer {token}"))
+        .header("Content-Type", "application/json")
+        .extension(axum::extract::ConnectInfo(std::net::SocketAddr::from((
+            [127, 0, 0, 1],
+            0,
+        ))))
+        .body(Body::from(body.to_string()))
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_retention_settings_require_elevation() {
+    let app = TestApp::new().await;
+    let (admin, _) = create_test_user(&app.pool).await;
+    let mut guard = app.cleanup_guard();
+    guard.delete_user(admin);
+    make_admin(&app.pool, admin).await;
+    let token = generate_access_token(&app.config, admin);
+
+    let req = TestApp::request(Method::GET, "/api/admin/retention")
+        .header("Authorization", format!("Bearer {token}"))
+        .body(Body::empty())
+        .unwrap();
+    let resp = app.oneshot(req).await;
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+    assert_eq!(body_to_json(resp).await["error"], "elevation_required");
+}
+
+#[tokio::test]
+async fn test_get_and_validate_retention_settings() {
+    let app = TestApp::new().await;
+    let (admin, _) = create_test_user(&app.pool).await;
+    let mut guard = app.cleanup_guard();
+    guard.delete_user(admin);
+    make_admin(&app.pool, admin).await;
+    create_elevated_session(&app.pool, admin).await;
+    let token = generate_access_token(&app.config, admin);
+
+    let req = TestApp::request(Method::GET, "/api/admin/retention")
+        .header("Authorization", format!("Bearer {token}"))
+        .body(Body::empty())
+        .unwrap();
+    let resp = app.oneshot(req).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let settings = body_to_json(resp).await;
+    assert_eq!(settings["telemetry_days"], 30);
+    assert_eq!(settings["connection_metrics_days"], 7);
+
+    // Out of range values and a default above the maximum are rejected
+    for body in [
+        serde_json::json!({ "telemetry_days": 0 }),
+        serde_json::json!({ "connection_metrics_days": 400 }),
+        serde_json::json!({ "message_default_days": 90, "message_max_days": 30 }),
+    ] {
+        let resp = app.oneshot(patch_retention(&token, body)).await;
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(body_to_json(resp).await["error"], "validation");
+    }
+
+    let resp = app
+        .oneshot(patch_retention(
+            &token,
+            serde_json::json!({ "soft_delete_purge_days": 30 }),
+        ))
+        .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(body_to_json(resp).await["soft_delete_purge_days"], 30);
+}
@@ -0,0 +1,173 @@
+//! HTTP Integration Tests for Admin Server Settings
+//!
+//! Tests `/api/admin/settings` and `/api/admin/config/reload`, which require
+//! system admin + elevated session.
+//! The settings are shared by every test running against the database, so
+//! updates share `#[serial(setup)]` with the setup tests, restore the defaults
+//! afterwards and only override values with their environment value.
+//!
+//! Run with: `cargo test --test integration admin_settings -- --nocapture`
+
+use axum::body::Body;
+use axum::http::{Method, StatusCode};
+use serial_test::serial;
+
+use super::helpers::{
+    body_to_json, create_elevated_session, create_test_user, generate_access_token, make_admin,
+    TestApp,
+};
+
+fn admin_request(
+    method: Method,
+    path: &str,
+    token: &str,
+    body: Option<serde_json::Value>,
+) -> axum::http::Request<Body> {
+    TestApp::request(method, path)
+        .header("Authorization", format!("Bearer {token}"))
+        .header("Content-Type", "application/json")
+        .extension(axum::extract::ConnectInfo(std::net::SocketAddr::from((
+            [127, 0, 0, 1],
+            0,
+        ))))
+        .body(body.map_or_else(Body::empty, |b| Body::from(b.to_string())))
+        .unwrap()
+}
+
+fn settings_request(
+    method: Method,
+    token: &str,
+    body: Option<serde_json::Value>,
+) -> axum::http::Request<Body> {
+    admin_request(method, "/api/admin/settings", token, body)
+}
+
+#[tokio::test]
+async fn test_server_settings_require_elevation() {
+    let app = TestApp::new().await;
+    let (admin, _) = create_test_user(&app.pool).await;
+    let mut guard = app.cleanup_guard();
+    guard.delete_user(admin);
+    make_admin(&app.pool, admin).await;
+    let token = generate_access_token(&app.config, admin);
+
+    let resp = app
+        .oneshot(settings_request(Method::GET, &token, None))
+        .await;
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+    assert_eq!(body_to_json(resp).await["error"], "elevation_required");
+}
+
+#[tokio::test]
+async fn test_server_settings_validation() {
+    let app = TestApp::new().await;
+    let (admin, _) = create_test_user(&app.pool).await;
+    let mut guard = app.cleanup_guard();
+    guard.delete_user(admin);
+    make_admin(&app.pool, admin).await;
+    create_elevated_session(&app.pool, admin).await;
+    let token = generate_access_token(&app.config, admin);
+
+    // Limits above the environment value (the request body limit) or below
+    // 1 KiB, unknown policies and blank names are rejected
+    for body in [
+        serde_json::json!({ "max_upload_size": app.config.max_upload_size + 1 }),
+        serde_json::json!({ "max_emoji_size": 100 }),
+        serde_json::json!({ "registration_policy": "everyone" }),
+        serde_json::json!({ "server_name": "  " }),
+        serde_json::json!({ "retention": { "telemetry_days": 0 } }),
+    ] {
+        let resp = app
+            .oneshot(settings_request(Method::PATCH, &token, Some(body)))
+            .await;
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(body_to_json(resp).await["error"], "validation");
+    }
+}
+
+#[tokio::test]
+#[serial(setup)]
+async fn test_update_server_settings() {
+    let app = TestApp::new().await;
+    let (admin, _) = create_test_user(&app.pool).await;
+    let mut guard = app.cleanup_guard();
+    guard.delete_user(admin);
+    guard.restore_config_defaults();
+    make_admin(&app.pool, admin).await;
+    create_elevated_session(&app.pool, admin).await;
+    let token = generate_access_token(&app.config, admin);
+
+    let resp = app
+        .oneshot(settings_request(
+            Method::PATCH,
+            &token,
+            Some(serde_json::json!({
+                "server_name": "  Settings Test  ",
+                "guild_discovery_enabled": app.config.enable_guild_discovery,
+                "max_avatar_size": app.config.max_avatar_size,
+            })),
+        ))
+        .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let settings = body_to_json(resp).await;
+    assert_eq!(settings["server_name"], "Settings Test");
+    assert_eq!(settings["max_avatar_size"], app.config.max_avatar_size);
+    assert_eq!(
+        settings["overrides"]["guild_discovery_enabled"],
+        app.config.enable_guild_discovery
+    );
+    assert!(settings["overrides"]["max_upload_size"].is_null());
+
+    let action: String = sqlx::query_scalar(
+        "SELECT action FROM system_audit_log WHERE actor_id = $1 ORDER BY created_at DESC LIMIT 1",
+    )
+    .bind(admin)
+    .fetch_one(&app.pool)
+    .await
+    .unwrap();
+    assert_eq!(action, "admin.settings.update");
+
+    // `null` clears an override
+    let resp = app
+        .oneshot(settings_request(
+            Method::PATCH,
+            &token,
+            Some(serde_json::json!({ "guild_discovery_enabled": null })),
+        ))
+        .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let settings = body_to_json(resp).await;
+    assert!(settings["overrides"]["guild_discovery_enabled"].is_null());
+    assert_eq!(
+        settings["overrides"]["max_avatar_size"],
+        app.config.max_avatar_size
+    );
+
+    let resp = app
+        .oneshot(settings_request(Method::GET, &token, None))
+        .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(body_to_json(resp).await["server_name"], "Settings Test");
+}
+
+#[tokio::test]
+async fn test_reload_config_requires_elevation() {
+    let app = TestApp::new().await;
+    let (admin, _) = create_test_user(&app.pool).await;
+    let mut guard = app.cleanup_guard();
+    guard.delete_user(admin);
+    make_admin(&app.pool, admin).await;
+    let token = generate_access_token(&app.config, admin);
+
+    // Only the elevation check: a reload would re-read this process's `.env`
+    let resp = app
+        .oneshot(admin_request(
+            Method::POST,
+            "/api/admin/config/reload",
+            &token,
+            None,
+        ))
+        .await;
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+    assert_eq!(body_to_json(resp).await["error"], "elevation_required");
+}
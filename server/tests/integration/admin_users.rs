@@ -0,0 +1,234 @@
+//! HTTP Integration Tests for Admin User Actions
+//!
+//! Tests suspension, forced session revocation and system admin grants under
+//! `/api/admin/users/{id}`. All of them require system admin + elevated session.
+//!
+//! Run with: `cargo test --test integration admin_users -- --nocapture`
+
+use axum::body::Body;
+use axum::http::{Method, StatusCode};
+use uuid::Uuid;
+
+use super::helpers::{
+    body_to_json, create_elevated_session, create_test_user, generate_access_token, make_admin,
+    TestApp,
+};
+
+fn admin_action(
+    method: Method,
+    token: &str,
+    user_id: Uuid,
+    action: &str,
+) -> axum::http::Request<Body> {
+    TestApp::request(method, &format!("/api/admin/users/{user_id}/{action}"))
+        .header("Authorization", format!("Bearer {token}"))
+        .extension(axum::extract::ConnectInfo(std::net::SocketAddr::from((
+            [127, 0, 0, 1],
+            0,
+        ))))
+        .body(Body::empty())
+        .unwrap()
+}
+
+fn suspend(token: &str, user_id: Uuid, reason: &str) -> axum::http::Request<Body> {
+    TestApp::request(Method::POST, &format!("/api/admin/users/{user_id}/suspend"))
+        .header("Authorization", format!("Bearer {token}"))
+        .header("Content-Type", "application/json")
+        .extension(axum::extract::ConnectInfo(std::net::SocketAddr::from((
+            [127, 0, 0, 1],
+            0,
+        ))))
+        .body(Body::from(
+            serde_json::json!({ "reason": reason }).to_string(),
+        ))
+        .unwrap()
+}
+
+fn get_me(token: &str) -> axum::http::Request<Body> {
+    TestApp::request(Method::GET, "/auth/me")
+        .header("Authorization", format!("Bearer {token}"))
+        .body(Body::empty())
+        .unwrap()
+}
+
+/// Move the revocation cutoff into the past so tokens minted in the same
+/// second as the revocation are accepted again.
+async fn backdate_token_revocation(pool: &sqlx::PgPool, user_id: Uuid) {
+    sqlx::query("UPDATE users SET tokens_revoked_at = NOW() - INTERVAL '1 minute' WHERE id = $1")
+        .bind(user_id)
+        .execute(pool)
+        .await
+        .expect("Failed to backdate token revocation");
+}
+
+#[tokio::test]
+async fn test_user_actions_require_elevation() {
+    let app = TestApp::new().await;
+    let (admin, _) = create_test_user(&app.pool).await;
+    let (target, _) = create_test_user(&app.pool).await;
+    let mut guard = app.cleanup_guard();
+    guard.delete_user(admin);
+    guard.delete_user(target);
+    make_admin(&app.pool, admin).await;
+    let token = generate_access_token(&app.config, admin);
+
+    let resp = app.oneshot(suspend(&token, target, "spam")).await;
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+    assert_eq!(body_to_json(resp).await["error"], "elevation_required");
+
+    let resp = app
+        .oneshot(admin_action(
+            Method::POST,
+            &token,
+            target,
+            "revoke-sessions",
+        ))
+        .await;
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+
+    let resp = app
+        .oneshot(admin_action(Method::POST, &token, target, "system-admin"))
+        .await;
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+}
+
+#[tokio::test]
+async fn test_suspend_and_unsuspend_user() {
+    let app = TestApp::new().await;
+    let (admin, _) = create_test_user(&app.pool).await;
+    let (target, _) = create_test_user(&app.pool).await;
+    let mut guard = app.cleanup_guard();
+    guard.delete_user(admin);
+    guard.delete_user(target);
+    make_admin(&app.pool, admin).await;
+    create_elevated_session(&app.pool, admin).await;
+    let token = generate_access_token(&app.config, admin);
+    let target_token = generate_access_token(&app.config, target);
+
+    let resp = app.oneshot(get_me(&target_token)).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    // Reason is required, and admins cannot suspend themselves
+    let resp = app.oneshot(suspend(&token, target, "   ")).await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    let resp = app.oneshot(suspend(&token, admin, "spam")).await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+    let resp = app.oneshot(suspend(&token, target, "spam")).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let body = body_to_json(resp).await;
+    assert_eq!(body["suspended"], true);
+    assert_eq!(body["reason"], "spam");
+
+    // Existing tokens stop working
+    let resp = app.oneshot(get_me(&target_token)).await;
+    assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+
+    // Fresh tokens are rejected while the account is suspended
+    backdate_token_revocation(&app.pool, target).await;
+    let fresh_token = generate_access_token(&app.config, target);
+    let resp = app.oneshot(get_me(&fresh_token)).await;
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+    assert_eq!(body_to_json(resp).await["error"], "ACCOUNT_SUSPENDED");
+
+    let resp = app
+        .oneshot(admin_action(Method::DELETE, &token, target, "suspend"))
+        .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(body_to_json(resp).await["suspended"], false);
+
+    let resp = app.oneshot(get_me(&fresh_token)).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    // Lifting a suspension twice is a 404
+    let resp = app
+        .oneshot(admin_action(Method::DELETE, &token, target, "suspend"))
+        .await;
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+
+    let action: Option<String> = sqlx::query_scalar(
+        "SELECT action FROM system_audit_log WHERE actor_id = $1 AND target_id = $2 ORDER BY created_at LIMIT 1",
+    )
+    .bind(admin)
+    .bind(target)
+    .fetch_optional(&app.pool)
+    .await
+    .unwrap();
+    assert_eq!(action.as_deref(), Some("admin.users.suspend"));
+}
+
+#[tokio::test]
+async fn test_revoke_sessions_invalidates_tokens() {
+    let app = TestApp::new().await;
+    let (admin, _) = create_test_user(&app.pool).await;
+    let (target, _) = create_test_user(&app.pool).await;
+    let mut guard = app.cleanup_guard();
+    guard.delete_user(admin);
+    guard.delete_user(target);
+    make_admin(&app.pool, admin).await;
+    create_elevated_session(&app.pool, admin).await;
+    let token = generate_access_token(&app.config, admin);
+    let target_token = generate_access_token(&app.config, target);
+
+    let resp = app
+        .oneshot(admin_action(
+            Method::POST,
+            &token,
+            target,
+            "revoke-sessions",
+        ))
+        .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(body_to_json(resp).await["user_id"], target.to_string());
+
+    let resp = app.oneshot(get_me(&target_token)).await;
+    assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+
+    // Tokens issued after the revocation are accepted
+    backdate_token_revocation(&app.pool, target).await;
+    let fresh_token = generate_access_token(&app.config, target);
+    let resp = app.oneshot(get_me(&fresh_token)).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_grant_and_revoke_system_admin() {
+    let app = TestApp::new().await;
+    let (admin, _) = create_test_user(&app.pool).await;
+    let (target, _) = create_test_user(&app.pool).await;
+    let mut guard = app.cleanup_guard();
+    guard.delete_user(admin);
+    guard.delete_user(target);
+    make_admin(&app.pool, admin).await;
+    create_elevated_session(&app.pool, admin).await;
+    let token = generate_access_token(&app.config, admin);
+
+    let resp = app
+        .oneshot(admin_action(Method::POST, &token, target, "system-admin"))
+        .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(body_to_json(resp).await["is_admin"], true);
+
+    // Granting twice is rejected
+    let resp = app
+        .oneshot(admin_action(Method::POST, &token, target, "system-admin"))
+        .await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+    // Admins cannot revoke themselves
+    let resp = app
+        .oneshot(admin_action(Method::DELETE, &token, admin, "system-admin"))
+        .await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+    let resp = app
+        .oneshot(admin_action(Method::DELETE, &token, target, "system-admin"))
+        .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(body_to_json(resp).await["is_admin"], false);
+
+    let resp = app
+        .oneshot(admin_action(Method::DELETE, &token, target, "system-admin"))
+        .await;
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+}
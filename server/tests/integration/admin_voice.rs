@@ -0,0 +1,54 @@
+//! Admin Voice Diagnostics Integration Tests
+//!
+//! Run with: `cargo test --test integration admin_voice -- --nocapture`
+
+use axum::body::Body;
+use axum::http::{Method, StatusCode};
+use uuid::Uuid;
+
+use super::helpers::{
+    body_to_json, create_test_user, delete_user, generate_access_token, make_admin, TestApp,
+};
+
+fn get(uri: &str, token: &str) -> axum::http::Request<Body> {
+    TestApp::request(Method::GET, uri)
+        .header("authorization", format!("Bearer {token}"))
+        .body(Body::empty())
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_voice_rooms_require_system_admin() {
+    let app = TestApp::new().await;
+    let (user_id, _) = create_test_user(&app.pool).await;
+    let mut guard = app.cleanup_guard();
+    guard.add(move |pool| async move { delete_user(&pool, user_id).await });
+
+    let token = generate_access_token(&app.config, user_id);
+    let resp = app.oneshot(get("/api/admin/voice/rooms", &token)).await;
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+
+    let uri = format!("/api/admin/voice/rooms/{}", Uuid::new_v4());
+    let resp = app.oneshot(get(&uri, &token)).await;
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+}
+
+#[tokio::test]
+async fn test_voice_rooms_list_and_unknown_room() {
+    let app = TestApp::new().await;
+    let (admin_id, _) = create_test_user(&app.pool).await;
+    let mut guard = app.cleanup_guard();
+    guard.add(move |pool| async move { delete_user(&pool, admin_id).await });
+    make_admin(&app.pool, admin_id).await;
+    let token = generate_access_token(&app.config, admin_id);
+
+    let resp = app.oneshot(get("/api/admin/voice/rooms", &token)).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let body = body_to_json(resp).await;
+    assert_eq!(body["node_id"], app.config.node_id.as_str());
+    assert_eq!(body["rooms"].as_array().unwrap().len(), 0);
+
+    let uri = format!("/api/admin/voice/rooms/{}", Uuid::new_v4());
+    let resp = app.oneshot(get(&uri, &token)).await;
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+}
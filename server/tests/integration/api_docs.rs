@@ -0,0 +1,63 @@
+//! API Documentation Integration Tests
+//!
+//! Run with: `cargo test --test integration api_docs -- --nocapture`
+
+use axum::body::Body;
+use axum::http::{Method, StatusCode};
+use vc_server::config::Config;
+
+use super::helpers::{body_to_json, TestApp};
+
+fn get(uri: &str) -> axum::http::Request<Body> {
+    TestApp::request(Method::GET, uri)
+        .body(Body::empty())
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_openapi_spec_covers_all_routers() {
+    let app = TestApp::new().await;
+
+    let resp = app.oneshot(get("/api/openapi.json")).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let spec = body_to_json(resp).await;
+    assert_eq!(spec["info"]["title"], "Kaiku API");
+
+    let paths = spec["paths"].as_object().unwrap();
+    for path in [
+        "/health",
+        "/auth/login",
+        "/auth/me/password",
+        "/auth/device-link/{code}/claim",
+        "/api/messages/channel/{channel_id}",
+        "/api/guilds/{id}/filters",
+        "/api/guilds/{id}/pages/{page_id}/revisions",
+        "/api/guilds/{id}/page-categories",
+        "/api/admin/observability/summary",
+        "/api/admin/guilds/{id}/page-limits",
+    ] {
+        assert!(paths.contains_key(path), "missing {path} in OpenAPI spec");
+    }
+
+    let schemes = &spec["components"]["securitySchemes"];
+    assert!(schemes["bearer_auth"].is_object());
+
+    // Swagger UI loads the same spec
+    let resp = app.oneshot(get("/api/docs/")).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_api_docs_hidden_when_disabled() {
+    let mut config = Config::default_for_test();
+    config.enable_api_docs = false;
+    let app = TestApp::with_config(config).await;
+
+    for uri in ["/api/openapi.json", "/api/docs/"] {
+        assert_eq!(
+            app.oneshot(get(uri)).await.status(),
+            StatusCode::NOT_FOUND,
+            "{uri} should not be served"
+        );
+    }
+}
@@ -0,0 +1,138 @@
+//! HTTP integration tests for attachments held by the malware scanner.
+//!
+//! Run with: `cargo test --test integration attachment_scanning -- --nocapture`
+
+use axum::body::Body;
+use axum::http::{Method, StatusCode};
+use serde_json::Value;
+use uuid::Uuid;
+use vc_server::permissions::GuildPermissions;
+
+use super::helpers::{
+    add_guild_member, authed, body_to_json, create_channel, create_elevated_session,
+    create_guild_with_default_role, create_test_user, delete_guild, delete_user,
+    generate_access_token, insert_attachment, insert_message, make_admin, TestApp,
+};
+
+async fn get_json(app: &TestApp, uri: &str, token: &str) -> Value {
+    let resp = app
+        .oneshot(authed(Method::GET, uri, token).body(Body::empty()).unwrap())
+        .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    body_to_json(resp).await
+}
+
+fn message_ids(body: &Value) -> Vec<String> {
+    body["items"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|m| m["id"].as_str().unwrap().to_string())
+        .collect()
+}
+
+#[tokio::test]
+async fn test_quarantined_attachment_hidden_until_released() {
+    let app = TestApp::new().await;
+    let (author_id, _) = create_test_user(&app.pool).await;
+    let (member_id, _) = create_test_user(&app.pool).await;
+    let (admin_id, _) = create_test_user(&app.pool).await;
+    let guild_id =
+        create_guild_with_default_role(&app.pool, author_id, GuildPermissions::VIEW_CHANNEL).await;
+    let mut guard = app.cleanup_guard();
+    guard.add(move |pool| async move {
+        delete_guild(&pool, guild_id).await;
+        delete_user(&pool, author_id).await;
+        delete_user(&pool, member_id).await;
+        delete_user(&pool, admin_id).await;
+    });
+    add_guild_member(&app.pool, guild_id, member_id).await;
+    make_admin(&app.pool, admin_id).await;
+    let channel_id = create_channel(&app.pool, guild_id, "scanned").await;
+
+    let visible_msg = insert_message(&app.pool, channel_id, member_id, "hello").await;
+    let held_msg = insert_message(&app.pool, channel_id, author_id, "totally safe").await;
+    insert_attachment(&app.pool, held_msg).await;
+    let attachment_id: Uuid = sqlx::query_scalar(
+        "UPDATE file_attachments SET scan_status = 'quarantined', scan_signature = 'Eicar-Test-Signature', scanned_at = NOW() \
+         WHERE message_id = $1 RETURNING id",
+    )
+    .bind(held_msg)
+    .fetch_one(&app.pool)
+    .await
+    .unwrap();
+
+    let author_token = generate_access_token(&app.config, author_id);
+    let member_token = generate_access_token(&app.config, member_id);
+    let admin_token = generate_access_token(&app.config, admin_id);
+    let messages_uri = format!("/api/messages/channel/{channel_id}");
+    let gallery_uri = format!("/api/channels/{channel_id}/attachments");
+
+    // Other members see neither the message nor the attachment
+    let body = get_json(&app, &messages_uri, &member_token).await;
+    assert_eq!(message_ids(&body), vec![visible_msg.to_string()]);
+    let body = get_json(&app, &gallery_uri, &member_token).await;
+    assert!(body["items"].as_array().unwrap().is_empty());
+
+    // The author still sees their message, with the scan state
+    let body = get_json(&app, &messages_uri, &author_token).await;
+    let held = body["items"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|m| m["id"] == held_msg.to_string())
+        .expect("author sees held message");
+    assert_eq!(held["attachments"][0]["scan_status"], "quarantined");
+
+    // Listing is open to system admins, releasing needs an elevated session
+    let body = get_json(
+        &app,
+        "/api/admin/attachments/held?status=quarantined",
+        &admin_token,
+    )
+    .await;
+    let entry = body["items"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|a| a["id"] == attachment_id.to_string())
+        .expect("held attachment listed");
+    assert_eq!(entry["scan_signature"], "Eicar-Test-Signature");
+    assert_eq!(entry["uploader_id"], author_id.to_string());
+
+    let release_uri = format!("/api/admin/attachments/{attachment_id}/release");
+    let release = || {
+        authed(Method::POST, &release_uri, &admin_token)
+            .body(Body::empty())
+            .unwrap()
+    };
+    assert_eq!(app.oneshot(release()).await.status(), StatusCode::FORBIDDEN);
+
+    create_elevated_session(&app.pool, admin_id).await;
+    assert_eq!(
+        app.oneshot(release()).await.status(),
+        StatusCode::NO_CONTENT
+    );
+    // Only held attachments can be released
+    assert_eq!(app.oneshot(release()).await.status(), StatusCode::NOT_FOUND);
+
+    let body = get_json(&app, &messages_uri, &member_token).await;
+    let released = body["items"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|m| m["id"] == held_msg.to_string())
+        .expect("released message visible");
+    assert!(released["attachments"][0].get("scan_status").is_none());
+    let body = get_json(&app, &gallery_uri, &member_token).await;
+    assert_eq!(body["items"].as_array().unwrap().len(), 1);
+
+    let audit: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM system_audit_log WHERE action = 'admin.attachments.release' AND target_id = $1",
+    )
+    .bind(attachment_id)
+    .fetch_one(&app.pool)
+    .await
+    .unwrap();
+    assert_eq!(audit, 1);
+}
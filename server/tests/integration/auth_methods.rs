@@ -0,0 +1,320 @@
+//! Linked Sign-in Method Integration Tests
+//!
+//! Run with: `cargo test --test integration auth_methods -- --nocapture`
+
+use axum::body::Body;
+use axum::http::{Method, StatusCode};
+use uuid::Uuid;
+use vc_server::auth::hash_password;
+
+use super::helpers::{
+    authed, body_to_json, create_test_user, delete_user, error_code, generate_access_token,
+    json_request, seed_reauth_token, TestApp,
+};
+
+const PASSWORD: &str = "correct-horse-battery";
+
+async fn set_password(pool: &sqlx::PgPool, user_id: Uuid, password: Option<&str>) {
+    let hash = password.map(|p| hash_password(p).unwrap());
+    sqlx::query("UPDATE users SET password_hash = $1 WHERE id = $2")
+        .bind(hash)
+        .bind(user_id)
+        .execute(pool)
+        .await
+        .unwrap();
+}
+
+async fn insert_identity(pool: &sqlx::PgPool, user_id: Uuid) -> Uuid {
+    sqlx::query_scalar(
+        "INSERT INTO user_auth_methods (user_id, method, provider, external_id)
+         VALUES ($1, 'oidc', 'test-idp', $2)
+         RETURNING id",
+    )
+    .bind(user_id)
+    .bind(format!("test-idp:{}", Uuid::new_v4()))
+    .fetch_one(pool)
+    .await
+    .unwrap()
+}
+
+#[tokio::test]
+async fn test_link_requires_reauthentication() {
+    let app = TestApp::new().await;
+    let (user_id, _) = create_test_user(&app.pool).await;
+    let mut guard = app.cleanup_guard();
+    guard.delete_user(user_id);
+    set_password(&app.pool, user_id, Some(PASSWORD)).await;
+    let token = generate_access_token(&app.config, user_id);
+
+    let resp = app
+        .oneshot(
+            authed(Method::GET, "/api/me/auth-methods", &token)
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let methods = body_to_json(resp).await;
+    assert_eq!(methods["has_password"], true);
+    assert_eq!(methods["identities"].as_array().unwrap().len(), 0);
+
+    let link = |current_password: Option<&str>| {
+        serde_json::json!({
+            "method": "oidc",
+            "provider": "test-idp",
+            "current_password": current_password,
+        })
+    };
+
+    // Missing or wrong current password is refused before anything else
+    let resp = app
+        .oneshot(json_request(
+            Method::POST,
+            "/api/me/auth-methods/link",
+            &token,
+            link(None),
+        ))
+        .await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    let resp = app
+        .oneshot(json_request(
+            Method::POST,
+            "/api/me/auth-methods/link",
+            &token,
+            link(Some("wrong-password")),
+        ))
+        .await;
+    assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+    assert_eq!(error_code(resp).await, "INVALID_CREDENTIALS");
+
+    // OIDC is disabled in the default server config
+    let resp = app
+        .oneshot(json_request(
+            Method::POST,
+            "/api/me/auth-methods/link",
+            &token,
+            link(Some(PASSWORD)),
+        ))
+        .await;
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+    assert_eq!(error_code(resp).await, "AUTH_METHOD_DISABLED");
+
+    // A second password cannot be linked
+    let resp = app
+        .oneshot(json_request(
+            Method::POST,
+            "/api/me/auth-methods/link",
+            &token,
+            serde_json::json!({
+                "method": "local",
+                "current_password": PASSWORD,
+                "new_password": "another-password",
+            }),
+        ))
+        .await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_passwordless_account_links_password() {
+    let app = TestApp::new().await;
+    let (user_id, _) = create_test_user(&app.pool).await;
+    let (other_id, _) = create_test_user(&app.pool).await;
+    let mut guard = app.cleanup_guard();
+    guard.add(move |pool| async move {
+        delete_user(&pool, user_id).await;
+        delete_user(&pool, other_id).await;
+    });
+    set_password(&app.pool, user_id, None).await;
+    let identity_id = insert_identity(&app.pool, user_id).await;
+    let token = generate_access_token(&app.config, user_id);
+
+    let resp = app
+        .oneshot(json_request(
+            Method::POST,
+            "/api/me/auth-methods/link",
+            &token,
+            serde_json::json!({ "method": "local", "new_password": "short" }),
+        ))
+        .await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+    // Without password or MFA, the session alone cannot add a password or
+    // drop the identity
+    let resp = app
+        .oneshot(json_request(
+            Method::POST,
+            "/api/me/auth-methods/link",
+            &token,
+            serde_json::json!({ "method": "local", "new_password": PASSWORD }),
+        ))
+        .await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    let resp = app
+        .oneshot(json_request(
+            Method::DELETE,
+            &format!("/api/me/auth-methods/{identity_id}"),
+            &token,
+            serde_json::json!({}),
+        ))
+        .await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+    // Unknown re-auth tokens and another account's are refused
+    let other_reauth = seed_reauth_token(&app.config, other_id).await;
+    for reauth_token in ["not-a-token", other_reauth.as_str()] {
+        let resp = app
+            .oneshot(json_request(
+                Method::POST,
+                "/api/me/auth-methods/link",
+                &token,
+                serde_json::json!({
+                    "method": "local",
+                    "new_password": PASSWORD,
+                    "reauth_token": reauth_token,
+                }),
+            ))
+            .await;
+        assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+        assert_eq!(error_code(resp).await, "INVALID_CREDENTIALS");
+    }
+
+    // A fresh provider sign-in confirms the change
+    let reauth_token = seed_reauth_token(&app.config, user_id).await;
+    let resp = app
+        .oneshot(json_request(
+            Method::POST,
+            "/api/me/auth-methods/link",
+            &token,
+            serde_json::json!({
+                "method": "local",
+                "new_password": PASSWORD,
+                "reauth_token": reauth_token,
+            }),
+        ))
+        .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let linked = body_to_json(resp).await;
+    assert!(linked["authorize_url"].is_null());
+    assert_eq!(linked["methods"]["has_password"], true);
+    let identities = linked["methods"]["identities"].as_array().unwrap();
+    assert_eq!(identities.len(), 1);
+    assert_eq!(identities[0]["method"], "oidc");
+    assert!(identities[0].get("external_id").is_none());
+}
+
+#[tokio::test]
+async fn test_unlink_keeps_a_usable_method() {
+    let app = TestApp::new().await;
+    let (user_id, _) = create_test_user(&app.pool).await;
+    let (other_id, _) = create_test_user(&app.pool).await;
+    let mut guard = app.cleanup_guard();
+    guard.add(move |pool| async move {
+        delete_user(&pool, user_id).await;
+        delete_user(&pool, other_id).await;
+    });
+    set_password(&app.pool, user_id, Some(PASSWORD)).await;
+    let token = generate_access_token(&app.config, user_id);
+    let identity_id = insert_identity(&app.pool, user_id).await;
+    let other_identity_id = insert_identity(&app.pool, other_id).await;
+    let reauth = || serde_json::json!({ "current_password": PASSWORD });
+
+    // Another user's identity is not found
+    let resp = app
+        .oneshot(json_request(
+            Method::DELETE,
+            &format!("/api/me/auth-methods/{other_identity_id}"),
+            &token,
+            reauth(),
+        ))
+        .await;
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+
+    // With OIDC disabled, the identity cannot stand in for the password
+    let resp = app
+        .oneshot(json_request(
+            Method::DELETE,
+            "/api/me/auth-methods/local",
+            &token,
+            reauth(),
+        ))
+        .await;
+    assert_eq!(resp.status(), StatusCode::CONFLICT);
+    assert_eq!(error_code(resp).await, "LAST_AUTH_METHOD");
+
+    // The password remains, so the identity can go
+    let resp = app
+        .oneshot(json_request(
+            Method::DELETE,
+            &format!("/api/me/auth-methods/{identity_id}"),
+            &token,
+            reauth(),
+        ))
+        .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let methods = body_to_json(resp).await;
+    assert_eq!(methods["has_password"], true);
+    assert_eq!(methods["identities"].as_array().unwrap().len(), 0);
+
+    // Now the password is the only method left
+    let resp = app
+        .oneshot(json_request(
+            Method::DELETE,
+            "/api/me/auth-methods/local",
+            &token,
+            reauth(),
+        ))
+        .await;
+    assert_eq!(resp.status(), StatusCode::CONFLICT);
+    assert_eq!(error_code(resp).await, "LAST_AUTH_METHOD");
+}
+
+#[tokio::test]
+async fn test_unlink_requires_reauthentication() {
+    let app = TestApp::new().await;
+    let (user_id, _) = create_test_user(&app.pool).await;
+    let mut guard = app.cleanup_guard();
+    guard.delete_user(user_id);
+    set_password(&app.pool, user_id, Some(PASSWORD)).await;
+    let token = generate_access_token(&app.config, user_id);
+    let identity_id = insert_identity(&app.pool, user_id).await;
+
+    for uri in [
+        "/api/me/auth-methods/local".to_string(),
+        format!("/api/me/auth-methods/{identity_id}"),
+    ] {
+        // A session alone is not enough to remove a sign-in method
+        let resp = app
+            .oneshot(json_request(
+                Method::DELETE,
+                &uri,
+                &token,
+                serde_json::json!({}),
+            ))
+            .await;
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+        let resp = app
+            .oneshot(json_request(
+                Method::DELETE,
+                &uri,
+                &token,
+                serde_json::json!({ "current_password": "wrong-password" }),
+            ))
+            .await;
+        assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+        assert_eq!(error_code(resp).await, "INVALID_CREDENTIALS");
+    }
+
+    // Nothing was removed
+    let resp = app
+        .oneshot(
+            authed(Method::GET, "/api/me/auth-methods", &token)
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+    let methods = body_to_json(resp).await;
+    assert_eq!(methods["has_password"], true);
+    assert_eq!(methods["identities"].as_array().unwrap().len(), 1);
+}
@@ -0,0 +1,167 @@
+//! Integration tests for the billing entitlement webhook.
+//!
+//! Run with: `cargo test --test integration billing_entitlements -- --nocapture`
+
+use axum::body::Body;
+use axum::http::{Method, StatusCode};
+use uuid::Uuid;
+use vc_server::config::Config;
+use vc_server::guild::perks::{set_supporter, SupporterSource};
+use vc_server::webhooks::signing::sign_payload;
+
+use super::helpers::{
+    add_guild_member, body_to_json, create_guild, create_test_user, delete_guild, delete_user,
+    TestApp,
+};
+
+const SECRET: &str = "billing_test_secret";
+
+fn billing_config() -> Config {
+    let mut config = Config::default_for_test();
+    config.billing_webhook_secret = Some(SECRET.to_string());
+    config.perk_tier_thresholds = vec![1];
+    config
+}
+
+async fn post_webhook(
+    app: &TestApp,
+    payload: &serde_json::Value,
+    signature: Option<String>,
+) -> axum::response::Response {
+    let body = payload.to_string();
+    let mut request = TestApp::request(Method::POST, "/api/admin/entitlements/webhook")
+        .header("content-type", "application/json");
+    if let Some(signature) = signature {
+        request = request.header("x-webhook-signature", signature);
+    }
+    app.oneshot(request.body(Body::from(body)).unwrap()).await
+}
+
+fn sign(payload: &serde_json::Value) -> Option<String> {
+    Some(format!(
+        "sha256={}",
+        sign_payload(SECRET, payload.to_string().as_bytes())
+    ))
+}
+
+async fn supporter_source(app: &TestApp, guild_id: Uuid, user_id: Uuid) -> Option<String> {
+    sqlx::query_scalar(
+        "SELECT supporter_source FROM guild_members WHERE guild_id = $1 AND user_id = $2",
+    )
+    .bind(guild_id)
+    .bind(user_id)
+    .fetch_one(&app.pool)
+    .await
+    .expect("Failed to fetch supporter source")
+}
+
+#[tokio::test]
+async fn test_webhook_requires_configuration_and_signature() {
+    let payload = serde_json::json!({ "event_id": "evt_unsigned", "guild_id": Uuid::new_v4() });
+
+    // Disabled without a configured secret
+    let app = TestApp::new().await;
+    let resp = post_webhook(&app, &payload, sign(&payload)).await;
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+
+    let app = TestApp::with_config(billing_config()).await;
+    let resp = post_webhook(&app, &payload, None).await;
+    assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+
+    let resp = post_webhook(&app, &payload, Some("sha256=deadbeef".to_string())).await;
+    assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+    let body = body_to_json(resp).await;
+    assert_eq!(body["error"], "invalid_signature");
+}
+
+#[tokio::test]
+async fn test_webhook_applies_entitlements_once() {
+    let app = TestApp::with_config(billing_config()).await;
+    let (owner_id, _) = create_test_user(&app.pool).await;
+    let (member_id, _) = create_test_user(&app.pool).await;
+    let (outsider_id, _) = create_test_user(&app.pool).await;
+    let guild_id = create_guild(&app.pool, owner_id).await;
+    add_guild_member(&app.pool, guild_id, member_id).await;
+    let mut guard = app.cleanup_guard();
+    guard.add(move |pool| async move {
+        delete_guild(&pool, guild_id).await;
+        delete_user(&pool, owner_id).await;
+        delete_user(&pool, member_id).await;
+        delete_user(&pool, outsider_id).await;
+    });
+
+    let payload = serde_json::json!({
+        "event_id": format!("evt_{}", Uuid::new_v4()),
+        "guild_id": guild_id,
+        "plan": "pro",
+        "quotas": { "max_pages": 100 },
+        "supporters": [
+            { "user_id": member_id, "active": true },
+            { "user_id": outsider_id, "active": true },
+        ],
+    });
+
+    let resp = post_webhook(&app, &payload, sign(&payload)).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let body = body_to_json(resp).await;
+    assert_eq!(body["applied"], true);
+    assert_eq!(body["skipped_user_ids"], serde_json::json!([outsider_id]));
+    assert_eq!(body["perks"]["tier"], 1);
+
+    let (plan, max_pages): (String, Option<i32>) =
+        sqlx::query_as("SELECT plan, max_pages FROM guilds WHERE id = $1")
+            .bind(guild_id)
+            .fetch_one(&app.pool)
+            .await
+            .unwrap();
+    assert_eq!(plan, "pro");
+    assert_eq!(max_pages, Some(100));
+    assert_eq!(
+        supporter_source(&app, guild_id, member_id).await.as_deref(),
+        Some("billing")
+    );
+
+    // Redelivery of the same event is a no-op
+    let resp = post_webhook(&app, &payload, sign(&payload)).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let body = body_to_json(resp).await;
+    assert_eq!(body["applied"], false);
+}
+
+#[tokio::test]
+async fn test_billing_revocation_keeps_manual_supporters() {
+    let app = TestApp::with_config(billing_config()).await;
+    let (owner_id, _) = create_test_user(&app.pool).await;
+    let (member_id, _) = create_test_user(&app.pool).await;
+    let guild_id = create_guild(&app.pool, owner_id).await;
+    add_guild_member(&app.pool, guild_id, member_id).await;
+    let mut guard = app.cleanup_guard();
+    guard.add(move |pool| async move {
+        delete_guild(&pool, guild_id).await;
+        delete_user(&pool, owner_id).await;
+        delete_user(&pool, member_id).await;
+    });
+
+    set_supporter(&app.pool, guild_id, owner_id, Some(SupporterSource::Manual))
+        .await
+        .unwrap();
+
+    for (active, event) in [(true, "grant"), (false, "revoke")] {
+        let payload = serde_json::json!({
+            "event_id": format!("evt_{event}_{}", Uuid::new_v4()),
+            "guild_id": guild_id,
+            "supporters": [
+                { "user_id": owner_id, "active": active },
+                { "user_id": member_id, "active": active },
+            ],
+        });
+        let resp = post_webhook(&app, &payload, sign(&payload)).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+    }
+
+    assert_eq!(
+        supporter_source(&app, guild_id, owner_id).await.as_deref(),
+        Some("manual")
+    );
+    assert_eq!(supporter_source(&app, guild_id, member_id).await, None);
+}
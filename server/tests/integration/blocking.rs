@@ -5,8 +5,12 @@
 //!
 //! Run with: `cargo test --test integration blocking -- --nocapture`
 
+use std::time::Duration;
+
 use axum::body::Body;
 use axum::http::Method;
+use fred::prelude::*;
+use vc_server::ws::channels;
 
 use super::helpers::{
     body_to_json, create_dm_channel, create_test_user, delete_user, generate_access_token, TestApp,
@@ -128,6 +132,64 @@ async fn test_unblock_user_success() {
     delete_user(&app.pool, user_b).await;
 }
 
+#[tokio::test]
+async fn test_block_sends_relationship_update_to_both_users() {
+    let app = TestApp::new().await;
+    let (user_a, _) = create_test_user(&app.pool).await;
+    let (user_b, _) = create_test_user(&app.pool).await;
+    let token = generate_access_token(&app.config, user_a);
+
+    let subscriber = vc_server::db::create_redis_client(&app.config.redis_url)
+        .await
+        .unwrap();
+    let _connect_handle = subscriber.connect();
+    subscriber.wait_for_connect().await.unwrap();
+    let mut pubsub_stream = subscriber.message_rx();
+    subscriber
+        .subscribe(vec![
+            channels::user_events(user_a),
+            channels::user_events(user_b),
+        ])
+        .await
+        .unwrap();
+
+    let req = TestApp::request(Method::POST, &format!("/api/friends/{user_b}/block"))
+        .header("Authorization", format!("Bearer {token}"))
+        .body(Body::empty())
+        .unwrap();
+    let resp = app.oneshot(req).await;
+    assert_eq!(resp.status(), 200);
+
+    let (mut for_a, mut for_b) = (None, None);
+    while for_a.is_none() || for_b.is_none() {
+        let message = tokio::time::timeout(Duration::from_secs(2), pubsub_stream.recv())
+            .await
+            .expect("timed out waiting for relationship update")
+            .expect("pubsub stream closed unexpectedly");
+        let payload = String::from_utf8(message.value.as_bytes().unwrap().to_vec()).unwrap();
+        let event: serde_json::Value = serde_json::from_str(&payload).unwrap();
+        if event["type"] != "relationship_update" {
+            continue;
+        }
+        if message.channel == channels::user_events(user_a) {
+            for_a = Some(event);
+        } else {
+            for_b = Some(event);
+        }
+    }
+
+    let (for_a, for_b) = (for_a.unwrap(), for_b.unwrap());
+    assert_eq!(for_a["user_id"], user_b.to_string());
+    assert_eq!(for_a["relationship"], "blocked");
+    // The blocked user is not told they were blocked
+    assert_eq!(for_b["user_id"], user_a.to_string());
+    assert!(for_b["relationship"].is_null());
+
+    // Cleanup
+    delete_user(&app.pool, user_a).await;
+    delete_user(&app.pool, user_b).await;
+}
+
 #[tokio::test]
 async fn test_unblock_nonexistent_fails() {
     let app = TestApp::new().await;
@@ -218,18 +280,10 @@ async fn test_block_prevents_dm_creation() {
         ))
         .unwrap();
     let resp = app.oneshot(req).await;
-    assert_eq!(resp.status(), 400);
+    assert_eq!(resp.status(), 403);
 
     let json = body_to_json(resp).await;
-    assert_eq!(json["error"], "VALIDATION_ERROR");
-    assert!(
-        json["message"]
-            .as_str()
-            .unwrap_or("")
-            .contains("Cannot create DM"),
-        "Expected block-related validation message, got: {}",
-        json["message"]
-    );
+    assert_eq!(json["error"], "BLOCKED");
 
     // Cleanup
     delete_user(&app.pool, user_a).await;
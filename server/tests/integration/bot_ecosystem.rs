@@ -1769,3 +1769,223 @@ async fn test_builtin_ping_returns_pong() {
         .unwrap();
     delete_user(&app.pool, user_id).await;
 }
+
+/// Create an application with a bot user; returns `(bot_user_id, bot_token)`.
+async fn create_bot_with_token(app: &TestApp, owner_token: &str) -> (uuid::Uuid, String) {
+    let create_req = TestApp::request(Method::POST, "/api/applications")
+        .header("Authorization", format!("Bearer {owner_token}"))
+        .header("Content-Type", "application/json")
+        .body(Body::from(
+            serde_json::to_string(&json!({ "name": "Interaction Bot" })).unwrap(),
+        ))
+        .unwrap();
+    let create_resp = app.oneshot(create_req).await;
+    assert_eq!(create_resp.status(), 201);
+    let body = create_resp.into_body().collect().await.unwrap().to_bytes();
+    let app_data: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    let app_id = app_data["id"].as_str().unwrap();
+
+    let bot_req = TestApp::request(Method::POST, &format!("/api/applications/{app_id}/bot"))
+        .header("Authorization", format!("Bearer {owner_token}"))
+        .body(Body::empty())
+        .unwrap();
+    let bot_resp = app.oneshot(bot_req).await;
+    assert_eq!(bot_resp.status(), 201);
+    let body = bot_resp.into_body().collect().await.unwrap().to_bytes();
+    let bot_data: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    let bot_user_id = uuid::Uuid::parse_str(bot_data["bot_user_id"].as_str().unwrap()).unwrap();
+    (bot_user_id, bot_data["token"].as_str().unwrap().to_string())
+}
+
+/// Store the Redis state a routed slash command invocation leaves behind.
+async fn seed_interaction(
+    redis: &fred::clients::Client,
+    interaction_id: uuid::Uuid,
+    bot_user_id: uuid::Uuid,
+    user_id: uuid::Uuid,
+    channel_id: uuid::Uuid,
+) {
+    redis
+        .set::<(), _, _>(
+            format!("interaction:{interaction_id}:owner"),
+            bot_user_id.to_string(),
+            Some(fred::types::Expiration::EX(300)),
+            None,
+            false,
+        )
+        .await
+        .unwrap();
+    redis
+        .set::<(), _, _>(
+            format!("interaction:{interaction_id}:context"),
+            json!({
+                "user_id": user_id,
+                "channel_id": channel_id,
+                "guild_id": uuid::Uuid::new_v4(),
+                "command_name": "hello",
+            })
+            .to_string(),
+            Some(fred::types::Expiration::EX(300)),
+            None,
+            false,
+        )
+        .await
+        .unwrap();
+}
+
+fn interaction_response_request(
+    interaction_id: uuid::Uuid,
+    bot_token: Option<&str>,
+    content: &str,
+) -> axum::http::Request<Body> {
+    let mut req = TestApp::request(
+        Method::POST,
+        &format!("/api/interactions/{interaction_id}/response"),
+    )
+    .header("Content-Type", "application/json");
+    if let Some(bot_token) = bot_token {
+        req = req.header("Authorization", format!("Bot {bot_token}"));
+    }
+    req.body(Body::from(
+        serde_json::to_string(&json!({ "content": content, "ephemeral": true })).unwrap(),
+    ))
+    .unwrap()
+}
+
+/// Test that a webhook bot can answer an interaction over HTTP and that the
+/// ephemeral reply reaches only the invoking user.
+#[tokio::test]
+async fn test_interaction_response_http_delivers_ephemeral_reply() {
+    let app = TestApp::new().await;
+    let (user_id, _) = create_test_user(&app.pool).await;
+    let token = generate_access_token(&app.config, user_id);
+    let (bot_user_id, bot_token) = create_bot_with_token(&app, &token).await;
+
+    let redis = db::create_redis_client(&app.config.redis_url)
+        .await
+        .unwrap();
+    let interaction_id = uuid::Uuid::new_v4();
+    let channel_id = uuid::Uuid::new_v4();
+    seed_interaction(&redis, interaction_id, bot_user_id, user_id, channel_id).await;
+
+    let subscriber = db::create_redis_client(&app.config.redis_url)
+        .await
+        .unwrap();
+    let _connect_handle = subscriber.connect();
+    subscriber.wait_for_connect().await.unwrap();
+    let mut pubsub_stream = subscriber.message_rx();
+    subscriber
+        .subscribe(format!("user:{user_id}"))
+        .await
+        .unwrap();
+
+    let resp = app
+        .oneshot(interaction_response_request(
+            interaction_id,
+            Some(&bot_token),
+            "Only you can see this",
+        ))
+        .await;
+    assert_eq!(resp.status(), 204);
+
+    let message = tokio::time::timeout(Duration::from_secs(2), pubsub_stream.recv())
+        .await
+        .expect("timed out waiting for ephemeral response")
+        .expect("user pubsub stream closed unexpectedly");
+    let payload = String::from_utf8(message.value.as_bytes().unwrap().to_vec()).unwrap();
+    let event: serde_json::Value = serde_json::from_str(&payload).unwrap();
+    assert_eq!(event["type"], "command_response");
+    assert_eq!(event["interaction_id"], interaction_id.to_string());
+    assert_eq!(event["channel_id"], channel_id.to_string());
+    assert_eq!(event["content"], "Only you can see this");
+    assert_eq!(event["ephemeral"], true);
+
+    // A second response to the same interaction is rejected
+    let resp = app
+        .oneshot(interaction_response_request(
+            interaction_id,
+            Some(&bot_token),
+            "Again",
+        ))
+        .await;
+    assert_eq!(resp.status(), 409);
+
+    let persisted_count = sqlx::query_scalar!(
+        "SELECT COUNT(*) as \"count!\" FROM messages WHERE channel_id = $1",
+        channel_id
+    )
+    .fetch_one(&app.pool)
+    .await
+    .unwrap();
+    assert_eq!(persisted_count, 0, "ephemeral replies must not be stored");
+
+    for suffix in ["owner", "context", "response"] {
+        redis
+            .del::<(), _>(format!("interaction:{interaction_id}:{suffix}"))
+            .await
+            .unwrap();
+    }
+    delete_user(&app.pool, user_id).await;
+}
+
+/// Test that the HTTP interaction response requires the owning bot's token.
+#[tokio::test]
+async fn test_interaction_response_http_requires_owning_bot() {
+    let app = TestApp::new().await;
+    let (user_id, _) = create_test_user(&app.pool).await;
+    let token = generate_access_token(&app.config, user_id);
+    let (_, bot_token) = create_bot_with_token(&app, &token).await;
+
+    let redis = db::create_redis_client(&app.config.redis_url)
+        .await
+        .unwrap();
+    let interaction_id = uuid::Uuid::new_v4();
+    seed_interaction(
+        &redis,
+        interaction_id,
+        uuid::Uuid::new_v4(),
+        user_id,
+        uuid::Uuid::new_v4(),
+    )
+    .await;
+
+    let resp = app
+        .oneshot(interaction_response_request(interaction_id, None, "Hi"))
+        .await;
+    assert_eq!(resp.status(), 401);
+
+    let resp = app
+        .oneshot(interaction_response_request(
+            interaction_id,
+            Some(&format!("{}.not-the-secret", uuid::Uuid::new_v4())),
+            "Hi",
+        ))
+        .await;
+    assert_eq!(resp.status(), 401);
+
+    let resp = app
+        .oneshot(interaction_response_request(
+            interaction_id,
+            Some(&bot_token),
+            "Hi",
+        ))
+        .await;
+    assert_eq!(resp.status(), 403, "interaction belongs to another bot");
+
+    let resp = app
+        .oneshot(interaction_response_request(
+            uuid::Uuid::new_v4(),
+            Some(&bot_token),
+            "Hi",
+        ))
+        .await;
+    assert_eq!(resp.status(), 404);
+
+    for suffix in ["owner", "context"] {
+        redis
+            .del::<(), _>(format!("interaction:{interaction_id}:{suffix}"))
+            .await
+            .unwrap();
+    }
+    delete_user(&app.pool, user_id).await;
+}
@@ -0,0 +1,103 @@
+//! Integration tests for the capacity report job and admin endpoint.
+//!
+//! Run with: `cargo test --test integration capacity_reports -- --nocapture`
+
+use axum::body::Body;
+use axum::http::{Method, StatusCode};
+use chrono::{NaiveTime, Utc};
+use vc_server::observability::capacity;
+
+use super::helpers::{
+    body_to_json, create_test_user, delete_user, generate_access_token, make_admin, TestApp,
+};
+
+#[tokio::test]
+async fn test_capacity_report_generation_and_export() {
+    let app = TestApp::new().await;
+    let (admin, _) = create_test_user(&app.pool).await;
+    make_admin(&app.pool, admin).await;
+    let token = generate_access_token(&app.config, admin);
+
+    // A day no other test writes to, so the report is deterministic to find
+    let date = Utc::now().date_naive() - chrono::Duration::days(200);
+    let mut guard = app.cleanup_guard();
+    guard.add(move |pool| async move {
+        sqlx::query("DELETE FROM capacity_reports WHERE report_date = $1")
+            .bind(date)
+            .execute(&pool)
+            .await
+            .ok();
+        delete_user(&pool, admin).await;
+    });
+    sqlx::query("DELETE FROM capacity_reports WHERE report_date = $1")
+        .bind(date)
+        .execute(&app.pool)
+        .await
+        .unwrap();
+
+    let noon = date
+        .and_time(NaiveTime::from_hms_opt(12, 0, 0).unwrap())
+        .and_utc();
+    sqlx::query(
+        "INSERT INTO sessions (user_id, token_hash, expires_at, created_at) VALUES ($1, $2, $3, $3)",
+    )
+    .bind(admin)
+    .bind(format!("capacity_test_{admin}"))
+    .bind(noon)
+    .execute(&app.pool)
+    .await
+    .unwrap();
+
+    assert!(capacity::generate_report(&app.pool, date).await.unwrap());
+    // Already generated: no second row
+    assert!(!capacity::generate_report(&app.pool, date).await.unwrap());
+
+    let resp = app
+        .oneshot(
+            TestApp::request(Method::GET, "/api/admin/reports/capacity?days=365")
+                .header("authorization", format!("Bearer {token}"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let body = body_to_json(resp).await;
+    let report = body
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|r| r["report_date"] == date.to_string())
+        .expect("report listed");
+    assert!(report["daily_active_users"].as_i64().unwrap() >= 1);
+    assert!(report["db_size_bytes"].as_i64().unwrap() > 0);
+
+    let resp = app
+        .oneshot(
+            TestApp::request(
+                Method::GET,
+                "/api/admin/reports/capacity?days=365&format=csv",
+            )
+            .header("authorization", format!("Bearer {token}"))
+            .body(Body::empty())
+            .unwrap(),
+        )
+        .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(resp.headers()["content-type"], "text/csv; charset=utf-8");
+    let bytes = axum::body::to_bytes(resp.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let csv = String::from_utf8(bytes.to_vec()).unwrap();
+    assert!(csv.starts_with("date,daily_active_users,"));
+    assert!(csv.contains(&format!("\n{date},")));
+
+    let resp = app
+        .oneshot(
+            TestApp::request(Method::GET, "/api/admin/reports/capacity?days=0")
+                .header("authorization", format!("Bearer {token}"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+}
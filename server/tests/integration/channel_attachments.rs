@@ -0,0 +1,119 @@
+//! HTTP integration tests for the channel attachment gallery.
+//!
+//! Run with: `cargo test --test integration channel_attachments -- --nocapture`
+
+use axum::body::Body;
+use axum::http::{Method, StatusCode};
+use sqlx::PgPool;
+use uuid::Uuid;
+use vc_server::permissions::GuildPermissions;
+
+use super::helpers::{
+    add_guild_member, authed, body_to_json, create_channel, create_guild_with_default_role,
+    create_test_user, delete_guild, delete_user, generate_access_token, insert_attachment,
+    insert_message, TestApp,
+};
+
+async fn insert_file(pool: &PgPool, message_id: Uuid, filename: &str, mime_type: &str) {
+    sqlx::query(
+        "INSERT INTO file_attachments (message_id, filename, mime_type, size_bytes, s3_key) VALUES ($1, $2, $3, 2048, 'uploads/test.bin')",
+    )
+    .bind(message_id)
+    .bind(filename)
+    .bind(mime_type)
+    .execute(pool)
+    .await
+    .expect("Failed to insert attachment");
+}
+
+#[tokio::test]
+async fn test_channel_attachments_page_and_filter() {
+    let app = TestApp::new().await;
+    let (owner_id, _) = create_test_user(&app.pool).await;
+    let (member_id, _) = create_test_user(&app.pool).await;
+    let (outsider_id, _) = create_test_user(&app.pool).await;
+    let guild_id =
+        create_guild_with_default_role(&app.pool, owner_id, GuildPermissions::VIEW_CHANNEL).await;
+    let mut guard = app.cleanup_guard();
+    guard.add(move |pool| async move {
+        delete_guild(&pool, guild_id).await;
+        delete_user(&pool, owner_id).await;
+        delete_user(&pool, member_id).await;
+        delete_user(&pool, outsider_id).await;
+    });
+    add_guild_member(&app.pool, guild_id, member_id).await;
+    let channel_id = create_channel(&app.pool, guild_id, "gallery").await;
+
+    let image_msg = insert_message(&app.pool, channel_id, owner_id, "look").await;
+    insert_attachment(&app.pool, image_msg).await;
+    let doc_msg = insert_message(&app.pool, channel_id, member_id, "notes").await;
+    insert_file(&app.pool, doc_msg, "notes.pdf", "application/pdf").await;
+    let deleted_msg = insert_message(&app.pool, channel_id, member_id, "oops").await;
+    insert_file(&app.pool, deleted_msg, "clip.mp4", "video/mp4").await;
+    sqlx::query("UPDATE messages SET deleted_at = NOW() WHERE id = $1")
+        .bind(deleted_msg)
+        .execute(&app.pool)
+        .await
+        .unwrap();
+
+    // channel_id is filled in from the parent message on insert
+    let stored: Uuid =
+        sqlx::query_scalar("SELECT channel_id FROM file_attachments WHERE message_id = $1")
+            .bind(image_msg)
+            .fetch_one(&app.pool)
+            .await
+            .unwrap();
+    assert_eq!(stored, channel_id);
+
+    let member_token = generate_access_token(&app.config, member_id);
+    let list = |query: &str, token: &str| {
+        authed(
+            Method::GET,
+            &format!("/api/channels/{channel_id}/attachments{query}"),
+            token,
+        )
+        .body(Body::empty())
+        .unwrap()
+    };
+
+    let outsider_token = generate_access_token(&app.config, outsider_id);
+    assert_eq!(
+        app.oneshot(list("", &outsider_token)).await.status(),
+        StatusCode::FORBIDDEN
+    );
+
+    let resp = app.oneshot(list("", &member_token)).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let body = body_to_json(resp).await;
+    let items = body["items"].as_array().unwrap();
+    // Deleted message attachments are hidden; newest first
+    assert_eq!(items.len(), 2);
+    assert_eq!(body["has_more"], false);
+    assert_eq!(items[0]["filename"], "notes.pdf");
+    assert_eq!(items[0]["kind"], "file");
+    assert_eq!(items[0]["uploader"]["id"], member_id.to_string());
+    assert_eq!(items[1]["kind"], "image");
+    assert_eq!(items[1]["message_id"], image_msg.to_string());
+    assert_eq!(items[1]["uploader"]["id"], owner_id.to_string());
+    assert!(items[1]["url"]
+        .as_str()
+        .unwrap()
+        .starts_with("/api/messages/attachments/"));
+
+    let resp = app.oneshot(list("?kind=image", &member_token)).await;
+    let body = body_to_json(resp).await;
+    assert_eq!(body["items"].as_array().unwrap().len(), 1);
+    assert_eq!(body["items"][0]["kind"], "image");
+
+    // Cursor pagination
+    let resp = app.oneshot(list("?limit=1", &member_token)).await;
+    let body = body_to_json(resp).await;
+    assert_eq!(body["has_more"], true);
+    let cursor = body["next_cursor"].as_str().unwrap().to_string();
+    let resp = app
+        .oneshot(list(&format!("?limit=1&before={cursor}"), &member_token))
+        .await;
+    let body = body_to_json(resp).await;
+    assert_eq!(body["has_more"], false);
+    assert_eq!(body["items"][0]["filename"], "test.png");
+}
@@ -0,0 +1,120 @@
+//! HTTP integration tests for channel transcript exports.
+//!
+//! Run with: `cargo test --test integration channel_exports -- --nocapture`
+
+use axum::body::Body;
+use axum::http::{Method, StatusCode};
+use uuid::Uuid;
+use vc_server::permissions::GuildPermissions;
+
+use super::helpers::{
+    add_guild_member, authed, body_to_json, create_channel, create_guild_with_default_role,
+    create_test_user, delete_guild, delete_user, generate_access_token, TestApp,
+};
+
+fn export_request(
+    channel_id: Uuid,
+    token: &str,
+    body: serde_json::Value,
+) -> axum::http::Request<Body> {
+    authed(
+        Method::POST,
+        &format!("/api/channels/{channel_id}/export"),
+        token,
+    )
+    .header("content-type", "application/json")
+    .body(Body::from(body.to_string()))
+    .unwrap()
+}
+
+#[tokio::test]
+async fn test_channel_export_requires_manage_guild_and_storage() {
+    let app = TestApp::new().await;
+    let (owner_id, _) = create_test_user(&app.pool).await;
+    let (member_id, _) = create_test_user(&app.pool).await;
+    let guild_id =
+        create_guild_with_default_role(&app.pool, owner_id, GuildPermissions::VIEW_CHANNEL).await;
+    let mut guard = app.cleanup_guard();
+    guard.add(move |pool| async move {
+        delete_guild(&pool, guild_id).await;
+        delete_user(&pool, owner_id).await;
+        delete_user(&pool, member_id).await;
+    });
+    add_guild_member(&app.pool, guild_id, member_id).await;
+    let channel_id = create_channel(&app.pool, guild_id, "records").await;
+    let owner_token = generate_access_token(&app.config, owner_id);
+    let member_token = generate_access_token(&app.config, member_id);
+
+    // Plain members lack MANAGE_GUILD
+    let resp = app
+        .oneshot(export_request(
+            channel_id,
+            &member_token,
+            serde_json::json!({}),
+        ))
+        .await;
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+    let list = authed(
+        Method::GET,
+        &format!("/api/channels/{channel_id}/exports"),
+        &member_token,
+    )
+    .body(Body::empty())
+    .unwrap();
+    assert_eq!(app.oneshot(list).await.status(), StatusCode::FORBIDDEN);
+
+    // Empty or inverted ranges are rejected
+    let resp = app
+        .oneshot(export_request(
+            channel_id,
+            &owner_token,
+            serde_json::json!({
+                "format": "html",
+                "from": "2026-03-02T00:00:00Z",
+                "to": "2026-03-01T00:00:00Z",
+            }),
+        ))
+        .await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(body_to_json(resp).await["error"], "validation");
+
+    let resp = app
+        .oneshot(export_request(
+            channel_id,
+            &owner_token,
+            serde_json::json!({"format": "pdf"}),
+        ))
+        .await;
+    assert!(resp.status().is_client_error());
+
+    // Tests run without S3, so no job can be queued
+    let resp = app
+        .oneshot(export_request(
+            channel_id,
+            &owner_token,
+            serde_json::json!({"format": "jsonl", "attachments": "bundled"}),
+        ))
+        .await;
+    assert_eq!(resp.status(), StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(body_to_json(resp).await["error"], "storage_not_configured");
+
+    let list = authed(
+        Method::GET,
+        &format!("/api/channels/{channel_id}/exports"),
+        &owner_token,
+    )
+    .body(Body::empty())
+    .unwrap();
+    let resp = app.oneshot(list).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(body_to_json(resp).await, serde_json::json!([]));
+
+    let get = authed(
+        Method::GET,
+        &format!("/api/channels/{channel_id}/exports/{}", Uuid::new_v4()),
+        &owner_token,
+    )
+    .body(Body::empty())
+    .unwrap();
+    assert_eq!(app.oneshot(get).await.status(), StatusCode::NOT_FOUND);
+}
@@ -0,0 +1,171 @@
+//! HTTP integration tests for channel feeds and feed tokens.
+//!
+//! Run with: `cargo test --test integration channel_feeds -- --nocapture`
+
+use axum::body::Body;
+use axum::http::{Method, StatusCode};
+use http_body_util::BodyExt;
+use vc_server::permissions::GuildPermissions;
+
+use super::helpers::{
+    add_guild_member, authed, body_to_json, create_channel, create_guild_with_default_role,
+    create_test_user, delete_guild, delete_user, generate_access_token, insert_message, TestApp,
+};
+
+#[tokio::test]
+async fn test_feed_tokens_grant_read_only_feed() {
+    let app = TestApp::new().await;
+    let (owner_id, _) = create_test_user(&app.pool).await;
+    let (member_id, _) = create_test_user(&app.pool).await;
+    let guild_id =
+        create_guild_with_default_role(&app.pool, owner_id, GuildPermissions::VIEW_CHANNEL).await;
+    let mut guard = app.cleanup_guard();
+    guard.add(move |pool| async move {
+        delete_guild(&pool, guild_id).await;
+        delete_user(&pool, owner_id).await;
+        delete_user(&pool, member_id).await;
+    });
+    add_guild_member(&app.pool, guild_id, member_id).await;
+    let channel_id = create_channel(&app.pool, guild_id, "announcements").await;
+    let owner_token = generate_access_token(&app.config, owner_id);
+    let member_token = generate_access_token(&app.config, member_id);
+
+    insert_message(
+        &app.pool,
+        channel_id,
+        owner_id,
+        "v1.0 released\nChangelog below",
+    )
+    .await;
+    let reply_to = insert_message(&app.pool, channel_id, owner_id, "<b>Tom & Jerry</b>").await;
+    let reply = insert_message(&app.pool, channel_id, member_id, "thread reply").await;
+    sqlx::query("UPDATE messages SET parent_id = $1 WHERE id = $2")
+        .bind(reply_to)
+        .bind(reply)
+        .execute(&app.pool)
+        .await
+        .unwrap();
+
+    let create = |token: &str, name: &str| {
+        authed(
+            Method::POST,
+            &format!("/api/channels/{channel_id}/feed-tokens"),
+            token,
+        )
+        .header("content-type", "application/json")
+        .body(Body::from(serde_json::json!({ "name": name }).to_string()))
+        .unwrap()
+    };
+
+    // Plain members lack MANAGE_CHANNELS
+    assert_eq!(
+        app.oneshot(create(&member_token, "dashboard"))
+            .await
+            .status(),
+        StatusCode::FORBIDDEN
+    );
+    assert_eq!(
+        app.oneshot(create(&owner_token, "  ")).await.status(),
+        StatusCode::BAD_REQUEST
+    );
+
+    let resp = app.oneshot(create(&owner_token, "dashboard")).await;
+    assert_eq!(resp.status(), StatusCode::CREATED);
+    let created = body_to_json(resp).await;
+    let feed_token = created["token"].as_str().unwrap().to_string();
+    let token_id = created["id"].as_str().unwrap().to_string();
+    assert_eq!(created["name"], "dashboard");
+
+    // The raw token is never listed
+    let resp = app
+        .oneshot(
+            authed(
+                Method::GET,
+                &format!("/api/channels/{channel_id}/feed-tokens"),
+                &owner_token,
+            )
+            .body(Body::empty())
+            .unwrap(),
+        )
+        .await;
+    let listed = body_to_json(resp).await;
+    assert_eq!(listed.as_array().unwrap().len(), 1);
+    assert!(listed[0].get("token").is_none());
+    assert!(listed[0].get("token_hash").is_none());
+
+    let feed = |query: &str| {
+        TestApp::request(
+            Method::GET,
+            &format!("/api/channels/{channel_id}/feed?{query}"),
+        )
+        .body(Body::empty())
+        .unwrap()
+    };
+
+    let resp = app.oneshot(feed(&format!("token={feed_token}"))).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(
+        resp.headers().get("content-type").unwrap(),
+        "application/feed+json"
+    );
+    let body = body_to_json(resp).await;
+    assert_eq!(body["version"], "https://jsonfeed.org/version/1.1");
+    assert_eq!(body["title"], "#announcements");
+    // Thread replies are left out; newest first
+    let items = body["items"].as_array().unwrap();
+    assert_eq!(items.len(), 2);
+    assert_eq!(items[0]["content_text"], "<b>Tom & Jerry</b>");
+    assert_eq!(items[1]["title"], "v1.0 released");
+
+    let resp = app
+        .oneshot(feed(&format!("token={feed_token}&format=atom")))
+        .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(
+        resp.headers().get("content-type").unwrap(),
+        "application/atom+xml; charset=utf-8"
+    );
+    let bytes = resp.into_body().collect().await.unwrap().to_bytes();
+    let xml = String::from_utf8(bytes.to_vec()).unwrap();
+    assert!(xml.contains("<feed xmlns=\"http://www.w3.org/2005/Atom\">"));
+    assert!(xml.contains("&lt;b&gt;Tom &amp; Jerry&lt;/b&gt;"));
+    assert!(!xml.contains("thread reply"));
+
+    // Wrong token, or a valid token for another channel, is rejected
+    assert_eq!(
+        app.oneshot(feed("token=not-a-real-token")).await.status(),
+        StatusCode::UNAUTHORIZED
+    );
+    let other_channel = create_channel(&app.pool, guild_id, "other").await;
+    let resp = app
+        .oneshot(
+            TestApp::request(
+                Method::GET,
+                &format!("/api/channels/{other_channel}/feed?token={feed_token}"),
+            )
+            .body(Body::empty())
+            .unwrap(),
+        )
+        .await;
+    assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+
+    // Revoking the token disables the feed
+    let resp = app
+        .oneshot(
+            authed(
+                Method::DELETE,
+                &format!("/api/channels/{channel_id}/feed-tokens/{token_id}"),
+                &owner_token,
+            )
+            .body(Body::empty())
+            .unwrap(),
+        )
+        .await;
+    assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+    assert_eq!(
+        app.oneshot(feed(&format!("token={feed_token}")))
+            .await
+            .status(),
+        StatusCode::UNAUTHORIZED
+    );
+}
@@ -0,0 +1,185 @@
+//! HTTP integration tests for channel pins.
+//!
+//! Run with: `cargo test --test integration channel_pins -- --nocapture`
+
+use axum::body::Body;
+use axum::http::{Method, StatusCode};
+use uuid::Uuid;
+use vc_server::permissions::GuildPermissions;
+
+use super::helpers::{
+    add_guild_member, body_to_json, create_channel, create_guild_with_default_role,
+    create_test_user, delete_guild, delete_user, generate_access_token, insert_message, TestApp,
+};
+
+fn pins_request(method: Method, path: &str, token: &str) -> axum::http::Request<Body> {
+    TestApp::request(method, path)
+        .header("authorization", format!("Bearer {token}"))
+        .body(Body::empty())
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_pin_and_unpin_message() {
+    let app = TestApp::new().await;
+    let (owner_id, _) = create_test_user(&app.pool).await;
+    let (member_id, _) = create_test_user(&app.pool).await;
+    let guild_id = create_guild_with_default_role(
+        &app.pool,
+        owner_id,
+        GuildPermissions::VIEW_CHANNEL | GuildPermissions::SEND_MESSAGES,
+    )
+    .await;
+    let mut guard = app.cleanup_guard();
+    guard.add(move |pool| async move {
+        delete_guild(&pool, guild_id).await;
+        delete_user(&pool, owner_id).await;
+        delete_user(&pool, member_id).await;
+    });
+    add_guild_member(&app.pool, guild_id, member_id).await;
+    let channel_id = create_channel(&app.pool, guild_id, "pins").await;
+    let message_id = insert_message(&app.pool, channel_id, member_id, "pin me").await;
+    let owner_token = generate_access_token(&app.config, owner_id);
+    let member_token = generate_access_token(&app.config, member_id);
+    let pin_path = format!("/api/channels/{channel_id}/pins/{message_id}");
+    let list_path = format!("/api/channels/{channel_id}/pins");
+
+    // Members without MANAGE_MESSAGES cannot pin
+    let resp = app
+        .oneshot(pins_request(Method::PUT, &pin_path, &member_token))
+        .await;
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+
+    let resp = app
+        .oneshot(pins_request(Method::PUT, &pin_path, &owner_token))
+        .await;
+    assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+
+    // Pinning again is a no-op
+    let resp = app
+        .oneshot(pins_request(Method::PUT, &pin_path, &owner_token))
+        .await;
+    assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+
+    // Everyone who can view the channel sees the pin
+    let resp = app
+        .oneshot(pins_request(Method::GET, &list_path, &member_token))
+        .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let body = body_to_json(resp).await;
+    let pins = body.as_array().unwrap();
+    assert_eq!(pins.len(), 1);
+    assert_eq!(pins[0]["message"]["id"], message_id.to_string());
+    assert_eq!(pins[0]["message"]["content"], "pin me");
+    assert_eq!(pins[0]["pinned_by"], owner_id.to_string());
+
+    // Pinning announces itself with a system message
+    let system_pins: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM messages WHERE channel_id = $1 AND message_type = 'system_pin'",
+    )
+    .bind(channel_id)
+    .fetch_one(&app.pool)
+    .await
+    .unwrap();
+    assert_eq!(system_pins, 1);
+
+    let resp = app
+        .oneshot(pins_request(Method::DELETE, &pin_path, &member_token))
+        .await;
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+
+    let resp = app
+        .oneshot(pins_request(Method::DELETE, &pin_path, &owner_token))
+        .await;
+    assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+
+    let resp = app
+        .oneshot(pins_request(Method::DELETE, &pin_path, &owner_token))
+        .await;
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+
+    let body = body_to_json(
+        app.oneshot(pins_request(Method::GET, &list_path, &member_token))
+            .await,
+    )
+    .await;
+    assert!(body.as_array().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_pin_limit_and_foreign_messages() {
+    let app = TestApp::new().await;
+    let (owner_id, _) = create_test_user(&app.pool).await;
+    let guild_id =
+        create_guild_with_default_role(&app.pool, owner_id, GuildPermissions::VIEW_CHANNEL).await;
+    let mut guard = app.cleanup_guard();
+    guard.add(move |pool| async move {
+        delete_guild(&pool, guild_id).await;
+        delete_user(&pool, owner_id).await;
+    });
+    let channel_id = create_channel(&app.pool, guild_id, "full-pins").await;
+    let other_channel_id = create_channel(&app.pool, guild_id, "elsewhere").await;
+    let token = generate_access_token(&app.config, owner_id);
+
+    // A message from another channel cannot be pinned here
+    let foreign_id = insert_message(&app.pool, other_channel_id, owner_id, "elsewhere").await;
+    let resp = app
+        .oneshot(pins_request(
+            Method::PUT,
+            &format!("/api/channels/{channel_id}/pins/{foreign_id}"),
+            &token,
+        ))
+        .await;
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+
+    // Fill the channel up to the cap directly
+    for i in 0..50 {
+        let message_id = insert_message(&app.pool, channel_id, owner_id, &format!("#{i}")).await;
+        sqlx::query(
+            "INSERT INTO channel_pins (channel_id, message_id, pinned_by) VALUES ($1, $2, $3)",
+        )
+        .bind(channel_id)
+        .bind(message_id)
+        .bind(owner_id)
+        .execute(&app.pool)
+        .await
+        .unwrap();
+    }
+
+    let extra_id = insert_message(&app.pool, channel_id, owner_id, "one too many").await;
+    let resp = app
+        .oneshot(pins_request(
+            Method::PUT,
+            &format!("/api/channels/{channel_id}/pins/{extra_id}"),
+            &token,
+        ))
+        .await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+    // Deleted messages no longer count towards the cap
+    sqlx::query(
+        "UPDATE messages SET deleted_at = NOW()
+         WHERE id = (SELECT message_id FROM channel_pins WHERE channel_id = $1 LIMIT 1)",
+    )
+    .bind(channel_id)
+    .execute(&app.pool)
+    .await
+    .unwrap();
+    let resp = app
+        .oneshot(pins_request(
+            Method::PUT,
+            &format!("/api/channels/{channel_id}/pins/{extra_id}"),
+            &token,
+        ))
+        .await;
+    assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+
+    let resp = app
+        .oneshot(pins_request(
+            Method::PUT,
+            &format!("/api/channels/{channel_id}/pins/{}", Uuid::new_v4()),
+            &token,
+        ))
+        .await;
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+}
@@ -0,0 +1,165 @@
+//! Integration tests for bulk channel and category reordering.
+//!
+//! Run with: `cargo test --test integration channel_positions -- --nocapture`
+
+use axum::body::Body;
+use axum::http::{Method, StatusCode};
+use sqlx::PgPool;
+use uuid::Uuid;
+use vc_server::permissions::GuildPermissions;
+
+use super::helpers::{
+    add_guild_member, body_to_json, create_channel, create_guild_with_default_role,
+    create_test_user, delete_guild, delete_user, generate_access_token, TestApp,
+};
+
+async fn create_category(pool: &PgPool, guild_id: Uuid, parent_id: Option<Uuid>) -> Uuid {
+    sqlx::query_scalar(
+        "INSERT INTO channel_categories (guild_id, name, parent_id) VALUES ($1, 'Category', $2) RETURNING id",
+    )
+    .bind(guild_id)
+    .bind(parent_id)
+    .fetch_one(pool)
+    .await
+    .expect("Failed to create category")
+}
+
+async fn channel_placement(pool: &PgPool, channel_id: Uuid) -> (i32, Option<Uuid>) {
+    sqlx::query_as("SELECT position, category_id FROM channels WHERE id = $1")
+        .bind(channel_id)
+        .fetch_one(pool)
+        .await
+        .expect("Failed to fetch channel placement")
+}
+
+async fn update_positions(
+    app: &TestApp,
+    token: &str,
+    guild_id: Uuid,
+    body: serde_json::Value,
+) -> axum::response::Response {
+    app.oneshot(
+        TestApp::request(
+            Method::PATCH,
+            &format!("/api/guilds/{guild_id}/channels/positions"),
+        )
+        .header("authorization", format!("Bearer {token}"))
+        .header("content-type", "application/json")
+        .body(Body::from(body.to_string()))
+        .unwrap(),
+    )
+    .await
+}
+
+#[tokio::test]
+async fn test_update_channel_positions() {
+    let app = TestApp::new().await;
+    let (owner_id, _) = create_test_user(&app.pool).await;
+    let token = generate_access_token(&app.config, owner_id);
+    let guild_id =
+        create_guild_with_default_role(&app.pool, owner_id, GuildPermissions::empty()).await;
+    let general = create_channel(&app.pool, guild_id, "general").await;
+    let random = create_channel(&app.pool, guild_id, "random").await;
+    let top = create_category(&app.pool, guild_id, None).await;
+    let other = create_category(&app.pool, guild_id, None).await;
+    let mut guard = app.cleanup_guard();
+    guard.add(move |pool| async move {
+        delete_guild(&pool, guild_id).await;
+        delete_user(&pool, owner_id).await;
+    });
+
+    let resp = update_positions(
+        &app,
+        &token,
+        guild_id,
+        serde_json::json!({
+            "channels": [
+                { "id": general, "position": 1, "category_id": other },
+                { "id": random, "position": 0, "category_id": other },
+            ],
+            "categories": [{ "id": other, "position": 0, "parent_id": top }],
+        }),
+    )
+    .await;
+    assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+    assert_eq!(
+        channel_placement(&app.pool, general).await,
+        (1, Some(other))
+    );
+    assert_eq!(channel_placement(&app.pool, random).await, (0, Some(other)));
+
+    // Nesting `top` under `other` would make three levels
+    let resp = update_positions(
+        &app,
+        &token,
+        guild_id,
+        serde_json::json!({
+            "categories": [{ "id": top, "position": 0, "parent_id": other }],
+        }),
+    )
+    .await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    let body = body_to_json(resp).await;
+    assert_eq!(body["error"], "VALIDATION_ERROR");
+}
+
+#[tokio::test]
+async fn test_update_channel_positions_stays_in_guild() {
+    let app = TestApp::new().await;
+    let (owner_id, _) = create_test_user(&app.pool).await;
+    let (member_id, _) = create_test_user(&app.pool).await;
+    let token = generate_access_token(&app.config, owner_id);
+    let guild_id =
+        create_guild_with_default_role(&app.pool, owner_id, GuildPermissions::empty()).await;
+    let other_guild_id =
+        create_guild_with_default_role(&app.pool, owner_id, GuildPermissions::empty()).await;
+    add_guild_member(&app.pool, guild_id, member_id).await;
+    let general = create_channel(&app.pool, guild_id, "general").await;
+    let foreign_channel = create_channel(&app.pool, other_guild_id, "general").await;
+    let foreign_category = create_category(&app.pool, other_guild_id, None).await;
+    let mut guard = app.cleanup_guard();
+    guard.add(move |pool| async move {
+        delete_guild(&pool, guild_id).await;
+        delete_guild(&pool, other_guild_id).await;
+        delete_user(&pool, owner_id).await;
+        delete_user(&pool, member_id).await;
+    });
+
+    // Category from another guild
+    let resp = update_positions(
+        &app,
+        &token,
+        guild_id,
+        serde_json::json!({
+            "channels": [{ "id": general, "position": 0, "category_id": foreign_category }],
+        }),
+    )
+    .await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+    // Channel from another guild; the whole batch is rolled back
+    let resp = update_positions(
+        &app,
+        &token,
+        guild_id,
+        serde_json::json!({
+            "channels": [
+                { "id": general, "position": 5 },
+                { "id": foreign_channel, "position": 0 },
+            ],
+        }),
+    )
+    .await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(channel_placement(&app.pool, general).await.0, 0);
+
+    // Members without MANAGE_CHANNELS cannot reorder
+    let resp = update_positions(
+        &app,
+        &generate_access_token(&app.config, member_id),
+        guild_id,
+        serde_json::json!({ "channels": [{ "id": general, "position": 1 }] }),
+    )
+    .await;
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+}
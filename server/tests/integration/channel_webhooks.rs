@@ -0,0 +1,261 @@
+//! HTTP integration tests for incoming channel webhooks.
+//!
+//! Run with: `cargo test --test integration channel_webhooks -- --nocapture`
+
+use axum::body::Body;
+use axum::http::{Method, StatusCode};
+use vc_server::permissions::GuildPermissions;
+
+use super::helpers::{
+    add_guild_member, authed, body_to_json, create_channel, create_guild_with_default_role,
+    create_test_user, delete_guild, delete_user, generate_access_token, json_request, TestApp,
+};
+
+#[tokio::test]
+async fn test_incoming_webhook_posts_messages_until_revoked() {
+    let app = TestApp::new().await;
+    let (owner_id, _) = create_test_user(&app.pool).await;
+    let (member_id, _) = create_test_user(&app.pool).await;
+    let guild_id = create_guild_with_default_role(
+        &app.pool,
+        owner_id,
+        GuildPermissions::VIEW_CHANNEL | GuildPermissions::SEND_MESSAGES,
+    )
+    .await;
+    let mut guard = app.cleanup_guard();
+    guard.add(move |pool| async move {
+        delete_guild(&pool, guild_id).await;
+        delete_user(&pool, owner_id).await;
+        delete_user(&pool, member_id).await;
+    });
+    add_guild_member(&app.pool, guild_id, member_id).await;
+    let channel_id = create_channel(&app.pool, guild_id, "deploys").await;
+    let owner_token = generate_access_token(&app.config, owner_id);
+    let member_token = generate_access_token(&app.config, member_id);
+
+    let create = |token: &str, body: serde_json::Value| {
+        json_request(
+            Method::POST,
+            &format!("/api/channels/{channel_id}/webhooks"),
+            token,
+            body,
+        )
+    };
+
+    // Plain members lack MANAGE_CHANNELS
+    assert_eq!(
+        app.oneshot(create(&member_token, serde_json::json!({ "name": "CI" })))
+            .await
+            .status(),
+        StatusCode::FORBIDDEN
+    );
+    assert_eq!(
+        app.oneshot(create(
+            &owner_token,
+            serde_json::json!({ "name": "CI", "avatar_url": "http://ci.example.com/a.png" }),
+        ))
+        .await
+        .status(),
+        StatusCode::BAD_REQUEST
+    );
+
+    let resp = app
+        .oneshot(create(
+            &owner_token,
+            serde_json::json!({ "name": "CI", "avatar_url": "https://ci.example.com/a.png" }),
+        ))
+        .await;
+    assert_eq!(resp.status(), StatusCode::CREATED);
+    let created = body_to_json(resp).await;
+    let webhook_id = created["id"].as_str().unwrap().to_string();
+    let webhook_token = created["token"].as_str().unwrap().to_string();
+    assert_eq!(created["name"], "CI");
+
+    // The raw token is never listed
+    let resp = app
+        .oneshot(
+            authed(
+                Method::GET,
+                &format!("/api/channels/{channel_id}/webhooks"),
+                &owner_token,
+            )
+            .body(Body::empty())
+            .unwrap(),
+        )
+        .await;
+    let listed = body_to_json(resp).await;
+    assert_eq!(listed.as_array().unwrap().len(), 1);
+    assert!(listed[0].get("token").is_none());
+    assert!(listed[0].get("token_hash").is_none());
+
+    let execute = |token: &str, body: serde_json::Value| {
+        TestApp::request(
+            Method::POST,
+            &format!("/api/channels/{channel_id}/webhooks/{webhook_id}/{token}"),
+        )
+        .header("Content-Type", "application/json")
+        .body(Body::from(body.to_string()))
+        .unwrap()
+    };
+
+    let resp = app
+        .oneshot(execute(
+            &webhook_token,
+            serde_json::json!({ "content": "Deployed v1.2" }),
+        ))
+        .await;
+    assert_eq!(resp.status(), StatusCode::CREATED);
+    let message = body_to_json(resp).await;
+    assert_eq!(message["webhook"], true);
+    assert_eq!(message["author"]["display_name"], "CI");
+    assert_eq!(
+        message["author"]["avatar_url"],
+        "https://ci.example.com/a.png"
+    );
+
+    // Name and avatar can be overridden per message
+    let resp = app
+        .oneshot(execute(
+            &webhook_token,
+            serde_json::json!({ "content": "Rolled back", "username": "Release Bot" }),
+        ))
+        .await;
+    assert_eq!(resp.status(), StatusCode::CREATED);
+
+    assert_eq!(
+        app.oneshot(execute(
+            &webhook_token,
+            serde_json::json!({ "content": "" })
+        ))
+        .await
+        .status(),
+        StatusCode::BAD_REQUEST
+    );
+    assert_eq!(
+        app.oneshot(execute(
+            "not-a-real-token",
+            serde_json::json!({ "content": "spoofed" }),
+        ))
+        .await
+        .status(),
+        StatusCode::UNAUTHORIZED
+    );
+
+    // Messages render with the webhook's identity in the channel history
+    let resp = app
+        .oneshot(
+            authed(
+                Method::GET,
+                &format!("/api/messages/channel/{channel_id}"),
+                &member_token,
+            )
+            .body(Body::empty())
+            .unwrap(),
+        )
+        .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let history = body_to_json(resp).await;
+    let items = history["items"].as_array().unwrap();
+    assert_eq!(items.len(), 2);
+    assert!(items.iter().all(|m| m["webhook"] == true));
+    let names: Vec<&str> = items
+        .iter()
+        .map(|m| m["author"]["display_name"].as_str().unwrap())
+        .collect();
+    assert!(names.contains(&"CI"));
+    assert!(names.contains(&"Release Bot"));
+
+    // Revoking the webhook disables its URL; posted messages keep their author
+    let resp = app
+        .oneshot(
+            authed(
+                Method::DELETE,
+                &format!("/api/channels/{channel_id}/webhooks/{webhook_id}"),
+                &owner_token,
+            )
+            .body(Body::empty())
+            .unwrap(),
+        )
+        .await;
+    assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+    assert_eq!(
+        app.oneshot(execute(
+            &webhook_token,
+            serde_json::json!({ "content": "after revoke" }),
+        ))
+        .await
+        .status(),
+        StatusCode::UNAUTHORIZED
+    );
+
+    let kept: Vec<String> = sqlx::query_scalar(
+        "SELECT webhook_name FROM messages WHERE channel_id = $1 AND webhook_id IS NULL",
+    )
+    .bind(channel_id)
+    .fetch_all(&app.pool)
+    .await
+    .unwrap();
+    assert_eq!(kept.len(), 2);
+}
+
+#[tokio::test]
+async fn test_incoming_webhook_follows_creator_posting_rights() {
+    let app = TestApp::new().await;
+    let (owner_id, _) = create_test_user(&app.pool).await;
+    let (manager_id, _) = create_test_user(&app.pool).await;
+    let guild_id = create_guild_with_default_role(
+        &app.pool,
+        owner_id,
+        GuildPermissions::VIEW_CHANNEL
+            | GuildPermissions::SEND_MESSAGES
+            | GuildPermissions::MANAGE_CHANNELS,
+    )
+    .await;
+    let mut guard = app.cleanup_guard();
+    guard.add(move |pool| async move {
+        delete_guild(&pool, guild_id).await;
+        delete_user(&pool, owner_id).await;
+        delete_user(&pool, manager_id).await;
+    });
+    add_guild_member(&app.pool, guild_id, manager_id).await;
+    let channel_id = create_channel(&app.pool, guild_id, "alerts").await;
+    let manager_token = generate_access_token(&app.config, manager_id);
+
+    let resp = app
+        .oneshot(json_request(
+            Method::POST,
+            &format!("/api/channels/{channel_id}/webhooks"),
+            &manager_token,
+            serde_json::json!({ "name": "Pager" }),
+        ))
+        .await;
+    assert_eq!(resp.status(), StatusCode::CREATED);
+    let created = body_to_json(resp).await;
+    let webhook_id = created["id"].as_str().unwrap().to_string();
+    let webhook_token = created["token"].as_str().unwrap().to_string();
+
+    let execute = || {
+        TestApp::request(
+            Method::POST,
+            &format!("/api/channels/{channel_id}/webhooks/{webhook_id}/{webhook_token}"),
+        )
+        .header("Content-Type", "application/json")
+        .body(Body::from(
+            serde_json::json!({ "content": "Disk almost full" }).to_string(),
+        ))
+        .unwrap()
+    };
+    assert_eq!(app.oneshot(execute()).await.status(), StatusCode::CREATED);
+
+    // A timed-out creator's webhook cannot post either
+    sqlx::query(
+        "UPDATE guild_members SET timeout_until = NOW() + INTERVAL '1 hour'
+         WHERE guild_id = $1 AND user_id = $2",
+    )
+    .bind(guild_id)
+    .bind(manager_id)
+    .execute(&app.pool)
+    .await
+    .unwrap();
+    assert_eq!(app.oneshot(execute()).await.status(), StatusCode::FORBIDDEN);
+}
@@ -0,0 +1,190 @@
+//! Cluster Coordination Integration Tests
+//!
+//! Run with: `cargo test --test integration cluster -- --nocapture`
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum::body::Body;
+use axum::http::{Method, StatusCode};
+use chrono::Utc;
+use fred::prelude::*;
+use uuid::Uuid;
+use vc_server::cluster::{registry, rooms};
+use vc_server::db;
+use vc_server::presence::registry as presence_registry;
+use vc_server::voice::SfuServer;
+
+use super::helpers::{
+    body_to_json, create_test_user, delete_user, generate_access_token, make_admin, TestApp,
+};
+
+async fn redis(app: &TestApp) -> Client {
+    db::create_redis_client(&app.config.redis_url)
+        .await
+        .expect("Failed to connect to Redis")
+}
+
+/// Register a node entry that expires `expires_in_ms` from now.
+async fn register_fake_node(redis: &Client, node_id: &str, expires_in_ms: i64) {
+    redis
+        .zadd::<(), _, _>(
+            "cluster:nodes",
+            None,
+            None,
+            false,
+            false,
+            (
+                (Utc::now().timestamp_millis() + expires_in_ms) as f64,
+                node_id,
+            ),
+        )
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn test_dead_node_is_reaped() {
+    let app = TestApp::new().await;
+    let redis = redis(&app).await;
+    let node_id = format!("dead-node-{}", Uuid::new_v4().simple());
+    let (user_id, connection_id) = (Uuid::new_v4(), Uuid::new_v4());
+    let channel_id = Uuid::new_v4();
+
+    presence_registry::connect(&redis, user_id, connection_id)
+        .await
+        .unwrap();
+    registry::track_connection(&redis, &node_id, user_id, connection_id)
+        .await
+        .unwrap();
+    register_fake_node(&redis, &node_id, 60_000).await;
+    assert_eq!(
+        rooms::claim_room(&redis, channel_id, &node_id)
+            .await
+            .unwrap(),
+        None
+    );
+
+    // The node stops sending heartbeats
+    register_fake_node(&redis, &node_id, -1_000).await;
+    assert!(!registry::is_alive(&redis, &node_id).await.unwrap());
+
+    registry::reap_dead_nodes(&app.pool, &redis).await.unwrap();
+
+    assert!(!presence_registry::has_active_connection(&redis, user_id)
+        .await
+        .unwrap());
+    assert_eq!(rooms::room_owner(&redis, channel_id).await.unwrap(), None);
+}
+
+#[tokio::test]
+async fn test_voice_room_has_single_owner() {
+    let app = TestApp::new().await;
+    let redis = redis(&app).await;
+    let (owner, other) = (
+        format!("owner-node-{}", Uuid::new_v4().simple()),
+        format!("other-node-{}", Uuid::new_v4().simple()),
+    );
+    let channel_id = Uuid::new_v4();
+    register_fake_node(&redis, &owner, 60_000).await;
+    register_fake_node(&redis, &other, 60_000).await;
+
+    assert_eq!(
+        rooms::claim_room(&redis, channel_id, &owner).await.unwrap(),
+        None
+    );
+    // Claiming again from the owner is fine, another node is told who hosts it
+    assert_eq!(
+        rooms::claim_room(&redis, channel_id, &owner).await.unwrap(),
+        None
+    );
+    assert_eq!(
+        rooms::claim_room(&redis, channel_id, &other).await.unwrap(),
+        Some(owner.clone())
+    );
+
+    // Once the owner is gone the room can move
+    register_fake_node(&redis, &owner, -1_000).await;
+    assert_eq!(
+        rooms::claim_room(&redis, channel_id, &other).await.unwrap(),
+        None
+    );
+    assert_eq!(
+        rooms::room_owner(&redis, channel_id).await.unwrap(),
+        Some(other.clone())
+    );
+
+    rooms::release_room(&redis, channel_id, &other)
+        .await
+        .unwrap();
+    assert_eq!(rooms::room_owner(&redis, channel_id).await.unwrap(), None);
+
+    registry::deregister(&app.pool, &redis, &owner)
+        .await
+        .unwrap();
+    registry::deregister(&app.pool, &redis, &other)
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn test_admin_lists_cluster_nodes() {
+    let app = TestApp::new().await;
+    let redis = redis(&app).await;
+    let (admin_id, _) = create_test_user(&app.pool).await;
+    let (user_id, _) = create_test_user(&app.pool).await;
+    let mut guard = app.cleanup_guard();
+    guard.add(move |pool| async move {
+        delete_user(&pool, admin_id).await;
+        delete_user(&pool, user_id).await;
+    });
+    make_admin(&app.pool, admin_id).await;
+
+    let node_id = app.config.node_id.clone();
+    let sfu = Arc::new(SfuServer::new(app.config.clone(), None).unwrap());
+    let heartbeat =
+        registry::spawn_node_heartbeat(app.pool.clone(), redis.clone(), node_id.clone(), sfu);
+
+    // The first heartbeat is sent right away
+    let mut registered = false;
+    for _ in 0..50 {
+        if registry::is_alive(&redis, &node_id).await.unwrap() {
+            registered = true;
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    assert!(registered, "Node never registered");
+
+    let list = |token: String| {
+        TestApp::request(Method::GET, "/api/admin/cluster/nodes")
+            .header("authorization", format!("Bearer {token}"))
+            .body(Body::empty())
+            .unwrap()
+    };
+
+    let resp = app
+        .oneshot(list(generate_access_token(&app.config, user_id)))
+        .await;
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+
+    let resp = app
+        .oneshot(list(generate_access_token(&app.config, admin_id)))
+        .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let body = body_to_json(resp).await;
+    assert_eq!(body["current_node_id"], node_id);
+    let node = body["nodes"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|node| node["node_id"] == node_id.as_str())
+        .expect("Current node missing from listing");
+    assert_eq!(node["voice_rooms"], 0);
+
+    heartbeat.abort();
+    registry::deregister(&app.pool, &redis, &node_id)
+        .await
+        .unwrap();
+    assert!(!registry::is_alive(&redis, &node_id).await.unwrap());
+}
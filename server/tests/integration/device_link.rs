@@ -0,0 +1,260 @@
+//! Device Linking Integration Tests
+//!
+//! Run with: `cargo test --test integration device_link -- --nocapture`
+
+use std::net::SocketAddr;
+
+use axum::body::Body;
+use axum::extract::ConnectInfo;
+use axum::http::{Method, StatusCode};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+
+use super::helpers::{
+    body_to_json, create_test_user, generate_access_token, shared_config, TestApp,
+};
+
+fn new_device(method: Method, uri: &str, body: &serde_json::Value) -> axum::http::Request<Body> {
+    TestApp::request(method, uri)
+        .header("Content-Type", "application/json")
+        .header("User-Agent", "Kaiku Desktop/1.0")
+        .extension(ConnectInfo(SocketAddr::from(([203, 0, 113, 7], 40000))))
+        .body(Body::from(body.to_string()))
+        .unwrap()
+}
+
+fn existing_device(method: Method, uri: &str, token: &str) -> axum::http::request::Builder {
+    TestApp::request(method, uri).header("Authorization", format!("Bearer {token}"))
+}
+
+async fn start_link(app: &TestApp, body: serde_json::Value) -> (String, String) {
+    let resp = app
+        .oneshot(new_device(Method::POST, "/auth/device-link", &body))
+        .await;
+    assert_eq!(resp.status(), StatusCode::CREATED);
+    let json = body_to_json(resp).await;
+    (
+        json["code"].as_str().unwrap().to_string(),
+        json["secret"].as_str().unwrap().to_string(),
+    )
+}
+
+async fn claim(app: &TestApp, code: &str, secret: &str) -> axum::response::Response {
+    app.oneshot(new_device(
+        Method::POST,
+        &format!("/auth/device-link/{code}/claim"),
+        &serde_json::json!({ "secret": secret }),
+    ))
+    .await
+}
+
+#[tokio::test]
+async fn test_device_link_issues_session_after_approval() {
+    let app = TestApp::new().await;
+    let (user_id, _) = create_test_user(&app.pool).await;
+    let mut guard = app.cleanup_guard();
+    guard.delete_user(user_id);
+    let token = generate_access_token(&app.config, user_id);
+
+    let public_key = STANDARD.encode([9u8; 32]);
+    let (code, secret) = start_link(&app, serde_json::json!({ "public_key": public_key })).await;
+
+    // Nothing to claim until the existing device approves
+    assert_eq!(
+        claim(&app, &code, &secret).await.status(),
+        StatusCode::ACCEPTED
+    );
+    assert_eq!(
+        claim(&app, &code, "wrong-secret").await.status(),
+        StatusCode::UNAUTHORIZED
+    );
+
+    // The approving device sees who is asking, even for a hand-typed code
+    let typed = format!("{}-{}", &code[..5], &code[5..]).to_lowercase();
+    let resp = app
+        .oneshot(
+            existing_device(Method::GET, &format!("/auth/device-link/{typed}"), &token)
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let details = body_to_json(resp).await;
+    assert_eq!(details["code"], code.as_str());
+    assert_eq!(details["user_agent"], "Kaiku Desktop/1.0");
+    assert_eq!(details["ip_address"], "203.0.113.7");
+    assert_eq!(details["accepts_key_transfer"], true);
+
+    let key_transfer = STANDARD.encode(b"sealed recovery key");
+    let approve = || {
+        existing_device(
+            Method::POST,
+            &format!("/auth/device-link/{code}/approve"),
+            &token,
+        )
+        .header("Content-Type", "application/json")
+        .body(Body::from(
+            serde_json::json!({ "key_transfer": key_transfer }).to_string(),
+        ))
+        .unwrap()
+    };
+    assert_eq!(
+        app.oneshot(approve()).await.status(),
+        StatusCode::NO_CONTENT
+    );
+    assert_eq!(app.oneshot(approve()).await.status(), StatusCode::CONFLICT);
+
+    let resp = claim(&app, &code, &secret).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let session = body_to_json(resp).await;
+    assert_eq!(session["key_transfer"], key_transfer.as_str());
+    assert!(session["refresh_token"].is_string());
+    let access_token = session["access_token"].as_str().unwrap();
+
+    let resp = app
+        .oneshot(
+            existing_device(Method::GET, "/auth/me", access_token)
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(body_to_json(resp).await["id"], user_id.to_string());
+
+    // The linked device got a session of its own
+    let sessions: i64 =
+        sqlx::query_scalar("SELECT COUNT(*) FROM sessions WHERE user_id = $1 AND user_agent = $2")
+            .bind(user_id)
+            .bind("Kaiku Desktop/1.0")
+            .fetch_one(&app.pool)
+            .await
+            .unwrap();
+    assert_eq!(sessions, 1);
+
+    // A link can only be claimed once
+    assert_eq!(
+        claim(&app, &code, &secret).await.status(),
+        StatusCode::NOT_FOUND
+    );
+}
+
+#[tokio::test]
+async fn test_device_link_rejection_and_key_transfer_checks() {
+    let app = TestApp::new().await;
+    let (user_id, _) = create_test_user(&app.pool).await;
+    let mut guard = app.cleanup_guard();
+    guard.delete_user(user_id);
+    let token = generate_access_token(&app.config, user_id);
+
+    // Without a public key there is nowhere to seal a key transfer to
+    let (code, secret) = start_link(&app, serde_json::json!({})).await;
+    let resp = app
+        .oneshot(
+            existing_device(
+                Method::POST,
+                &format!("/auth/device-link/{code}/approve"),
+                &token,
+            )
+            .header("Content-Type", "application/json")
+            .body(Body::from(
+                serde_json::json!({ "key_transfer": STANDARD.encode(b"key") }).to_string(),
+            ))
+            .unwrap(),
+        )
+        .await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+    // Rejecting removes the request entirely
+    let resp = app
+        .oneshot(
+            existing_device(Method::DELETE, &format!("/auth/device-link/{code}"), &token)
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+    assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+    assert_eq!(
+        claim(&app, &code, &secret).await.status(),
+        StatusCode::NOT_FOUND
+    );
+
+    // Approving needs a signed-in device
+    let (code, _) = start_link(&app, serde_json::json!({})).await;
+    let resp = app
+        .oneshot(
+            TestApp::request(Method::POST, &format!("/auth/device-link/{code}/approve"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+    assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+
+    let resp = app
+        .oneshot(new_device(
+            Method::POST,
+            "/auth/device-link",
+            &serde_json::json!({ "public_key": STANDARD.encode([1u8; 16]) }),
+        ))
+        .await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_device_link_is_not_challenged_from_new_network() {
+    let mut config = shared_config().await.clone();
+    config.new_network_require_totp = true;
+    let app = TestApp::with_config(config).await;
+    let (user_id, _) = create_test_user(&app.pool).await;
+    let mut guard = app.cleanup_guard();
+    guard.delete_user(user_id);
+    guard.add(move |pool| async move {
+        let _ = sqlx::query("DELETE FROM system_audit_log WHERE target_id = $1")
+            .bind(user_id)
+            .execute(&pool)
+            .await;
+    });
+    let token = generate_access_token(&app.config, user_id);
+
+    // A TOTP account that has only signed in from another network
+    sqlx::query("UPDATE users SET mfa_secret = 'encrypted-secret' WHERE id = $1")
+        .bind(user_id)
+        .execute(&app.pool)
+        .await
+        .unwrap();
+    sqlx::query(
+        "INSERT INTO user_login_networks (user_id, network, last_ip)
+         VALUES ($1, '198.51.100.0/24', '198.51.100.5')",
+    )
+    .bind(user_id)
+    .execute(&app.pool)
+    .await
+    .unwrap();
+
+    // Approval from the signed-in device is the only check
+    let (code, secret) = start_link(&app, serde_json::json!({})).await;
+    let resp = app
+        .oneshot(
+            existing_device(
+                Method::POST,
+                &format!("/auth/device-link/{code}/approve"),
+                &token,
+            )
+            .body(Body::empty())
+            .unwrap(),
+        )
+        .await;
+    assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+    assert_eq!(claim(&app, &code, &secret).await.status(), StatusCode::OK);
+
+    let alerts: Vec<serde_json::Value> = sqlx::query_scalar(
+        "SELECT details FROM system_audit_log
+         WHERE target_id = $1 AND action = 'auth.login.new_network'",
+    )
+    .bind(user_id)
+    .fetch_all(&app.pool)
+    .await
+    .unwrap();
+    assert_eq!(alerts.len(), 1);
+    assert_eq!(alerts[0]["method"], "device_link");
+    assert_eq!(alerts[0]["network"], "203.0.113.0/24");
+}
@@ -1,15 +1,18 @@
 //! HTTP Integration Tests for Direct Messages
 //!
 //! Tests DM creation (with idempotency), listing, access control,
-//! and leave behavior.
+//! leave behavior, and per-user conversation state.
 //!
 //! Run with: `cargo test --test integration dm_http -- --nocapture`
 
 use axum::body::Body;
 use axum::http::Method;
+use serde_json::json;
 use uuid::Uuid;
 
-use super::helpers::{body_to_json, create_test_user, generate_access_token, TestApp};
+use super::helpers::{
+    body_to_json, create_test_user, generate_access_token, insert_message, TestApp,
+};
 
 // ============================================================================
 // Test Helpers
@@ -46,6 +49,42 @@ async fn list_dms(app: &TestApp, token: &str) -> serde_json::Value {
     body_to_json(resp).await
 }
 
+/// Update a DM's conversation state, returning the status and response JSON.
+async fn patch_dm_state(
+    app: &TestApp,
+    token: &str,
+    dm_id: Uuid,
+    body: serde_json::Value,
+) -> (u16, serde_json::Value) {
+    let req = TestApp::request(Method::PATCH, &format!("/api/dm/{dm_id}/state"))
+        .header("Authorization", format!("Bearer {token}"))
+        .header("Content-Type", "application/json")
+        .body(Body::from(body.to_string()))
+        .unwrap();
+
+    let resp = app.oneshot(req).await;
+    let status = resp.status().as_u16();
+    (status, body_to_json(resp).await)
+}
+
+/// IDs of the DMs listed at `path`, in order.
+async fn listed_dm_ids(app: &TestApp, token: &str, path: &str) -> Vec<String> {
+    let req = TestApp::request(Method::GET, path)
+        .header("Authorization", format!("Bearer {token}"))
+        .body(Body::empty())
+        .unwrap();
+
+    let resp = app.oneshot(req).await;
+    assert_eq!(resp.status(), 200);
+    body_to_json(resp)
+        .await
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|dm| dm["id"].as_str().unwrap().to_string())
+        .collect()
+}
+
 // ============================================================================
 // DM CRUD
 // ============================================================================
@@ -223,3 +262,102 @@ async fn test_leave_dm() {
         resp.status()
     );
 }
+
+// ============================================================================
+// Conversation State
+// ============================================================================
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_dm_conversation_state() {
+    let app = TestApp::new().await;
+    let (user_a, _) = create_test_user(&app.pool).await;
+    let (user_b, _) = create_test_user(&app.pool).await;
+    let (user_c, _) = create_test_user(&app.pool).await;
+    let token_a = generate_access_token(&app.config, user_a);
+    let token_c = generate_access_token(&app.config, user_c);
+
+    let mut guard = app.cleanup_guard();
+    guard.delete_user(user_a);
+    guard.delete_user(user_b);
+    guard.delete_user(user_c);
+
+    let (_, dm_ab) = create_dm_via_api(&app, &token_a, &[user_b]).await;
+    let dm_ab = Uuid::parse_str(dm_ab["id"].as_str().unwrap()).unwrap();
+    guard.add(move |pool| async move { super::helpers::delete_dm_channel(&pool, dm_ab).await });
+    let (_, dm_ac) = create_dm_via_api(&app, &token_a, &[user_c]).await;
+    let dm_ac = Uuid::parse_str(dm_ac["id"].as_str().unwrap()).unwrap();
+    guard.add(move |pool| async move { super::helpers::delete_dm_channel(&pool, dm_ac).await });
+
+    insert_message(&app.pool, dm_ac, user_c, "older").await;
+    insert_message(&app.pool, dm_ab, user_b, "newer").await;
+    assert_eq!(
+        listed_dm_ids(&app, &token_a, "/api/dm").await,
+        [dm_ab.to_string(), dm_ac.to_string()]
+    );
+
+    // Pinning moves a conversation to the top
+    let (status, state) = patch_dm_state(&app, &token_a, dm_ac, json!({ "pinned": true })).await;
+    assert_eq!(status, 200);
+    assert_eq!(state["pinned"], true);
+    assert_eq!(state["archived"], false);
+    assert_eq!(
+        listed_dm_ids(&app, &token_a, "/api/dm").await,
+        [dm_ac.to_string(), dm_ab.to_string()]
+    );
+
+    // Hidden conversations drop out of the list until a new message arrives
+    let (_, state) = patch_dm_state(&app, &token_a, dm_ab, json!({ "hidden": true })).await;
+    assert_eq!(state["hidden"], true);
+    assert_eq!(
+        listed_dm_ids(&app, &token_a, "/api/dm").await,
+        [dm_ac.to_string()]
+    );
+    assert_eq!(
+        listed_dm_ids(&app, &token_a, "/api/dm?include_hidden=true")
+            .await
+            .len(),
+        2
+    );
+    insert_message(&app.pool, dm_ab, user_b, "are you there?").await;
+    assert_eq!(listed_dm_ids(&app, &token_a, "/api/dm").await.len(), 2);
+
+    // A conversation kept unread survives read-all until it is read directly
+    let (_, state) = patch_dm_state(&app, &token_a, dm_ac, json!({ "unread_kept": true })).await;
+    assert_eq!(state["unread_kept"], true);
+    assert_eq!(state["pinned"], true, "Omitted fields are left unchanged");
+    let req = TestApp::request(Method::POST, "/api/dm/read-all")
+        .header("Authorization", format!("Bearer {token_a}"))
+        .body(Body::empty())
+        .unwrap();
+    assert_eq!(app.oneshot(req).await.status(), 204);
+    let dms = list_dms(&app, &token_a).await;
+    let find = |dms: &serde_json::Value, id: Uuid| {
+        dms.as_array()
+            .unwrap()
+            .iter()
+            .find(|dm| dm["id"] == id.to_string())
+            .cloned()
+            .unwrap()
+    };
+    assert!(find(&dms, dm_ac)["unread_count"].as_i64().unwrap() >= 1);
+    assert_eq!(find(&dms, dm_ab)["unread_count"], 0);
+
+    let req = TestApp::request(Method::POST, &format!("/api/dm/{dm_ac}/read"))
+        .header("Authorization", format!("Bearer {token_a}"))
+        .header("Content-Type", "application/json")
+        .body(Body::from(
+            json!({ "last_read_message_id": null }).to_string(),
+        ))
+        .unwrap();
+    assert_eq!(app.oneshot(req).await.status(), 200);
+    let dm = find(&list_dms(&app, &token_a).await, dm_ac);
+    assert_eq!(dm["unread_count"], 0);
+    assert_eq!(dm["state"]["unread_kept"], false);
+
+    // State is per user, and only participants can set it
+    let (status, _) = patch_dm_state(&app, &token_c, dm_ab, json!({ "archived": true })).await;
+    assert_eq!(status, 403);
+    let (status, state) = patch_dm_state(&app, &token_c, dm_ac, json!({ "archived": true })).await;
+    assert_eq!(status, 200);
+    assert_eq!(state["pinned"], false);
+}
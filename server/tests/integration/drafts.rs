@@ -0,0 +1,137 @@
+//! HTTP integration tests for message draft sync.
+//!
+//! Run with: `cargo test --test integration drafts -- --nocapture`
+
+use axum::body::Body;
+use axum::http::{Method, StatusCode};
+use chrono::{Duration, Utc};
+use serde_json::json;
+use uuid::Uuid;
+use vc_server::permissions::GuildPermissions;
+
+use super::helpers::{
+    body_to_json, create_channel, create_guild_with_default_role, create_test_user, delete_guild,
+    delete_user, generate_access_token, TestApp,
+};
+
+fn put_draft(channel_id: Uuid, token: &str, body: serde_json::Value) -> axum::http::Request<Body> {
+    TestApp::request(Method::PUT, &format!("/api/me/drafts/{channel_id}"))
+        .header("authorization", format!("Bearer {token}"))
+        .header("content-type", "application/json")
+        .body(Body::from(body.to_string()))
+        .unwrap()
+}
+
+fn list_drafts(token: &str) -> axum::http::Request<Body> {
+    TestApp::request(Method::GET, "/api/me/drafts")
+        .header("authorization", format!("Bearer {token}"))
+        .body(Body::empty())
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_newest_draft_wins() {
+    let app = TestApp::new().await;
+    let (user_id, _) = create_test_user(&app.pool).await;
+    let guild_id =
+        create_guild_with_default_role(&app.pool, user_id, GuildPermissions::VIEW_CHANNEL).await;
+    let mut guard = app.cleanup_guard();
+    guard.add(move |pool| async move {
+        delete_guild(&pool, guild_id).await;
+        delete_user(&pool, user_id).await;
+    });
+    let channel_id = create_channel(&app.pool, guild_id, "drafts").await;
+    let token = generate_access_token(&app.config, user_id);
+
+    let edited_at = Utc::now() - Duration::minutes(5);
+    let resp = app
+        .oneshot(put_draft(
+            channel_id,
+            &token,
+            json!({ "content": "from desktop", "updated_at": edited_at }),
+        ))
+        .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(body_to_json(resp).await["content"], "from desktop");
+
+    // An older edit from another device loses and gets the stored draft back
+    let resp = app
+        .oneshot(put_draft(
+            channel_id,
+            &token,
+            json!({ "content": "stale phone edit", "updated_at": edited_at - Duration::minutes(1) }),
+        ))
+        .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(body_to_json(resp).await["content"], "from desktop");
+
+    let resp = app.oneshot(list_drafts(&token)).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let body = body_to_json(resp).await;
+    let drafts = body["drafts"].as_array().unwrap();
+    assert_eq!(drafts.len(), 1);
+    assert_eq!(drafts[0]["channel_id"], channel_id.to_string());
+    assert_eq!(drafts[0]["content"], "from desktop");
+
+    // Clearing hides the draft, and the stale edit still cannot bring it back
+    let resp = app
+        .oneshot(put_draft(channel_id, &token, json!({ "content": "" })))
+        .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let resp = app
+        .oneshot(put_draft(
+            channel_id,
+            &token,
+            json!({ "content": "from desktop", "updated_at": edited_at }),
+        ))
+        .await;
+    assert_eq!(body_to_json(resp).await["content"], "");
+
+    let body = body_to_json(app.oneshot(list_drafts(&token)).await).await;
+    assert!(body["drafts"].as_array().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_draft_validation_and_access() {
+    let app = TestApp::new().await;
+    let (owner_id, _) = create_test_user(&app.pool).await;
+    let (outsider_id, _) = create_test_user(&app.pool).await;
+    let guild_id =
+        create_guild_with_default_role(&app.pool, owner_id, GuildPermissions::VIEW_CHANNEL).await;
+    let mut guard = app.cleanup_guard();
+    guard.add(move |pool| async move {
+        delete_guild(&pool, guild_id).await;
+        delete_user(&pool, owner_id).await;
+        delete_user(&pool, outsider_id).await;
+    });
+    let channel_id = create_channel(&app.pool, guild_id, "private-drafts").await;
+    let owner_token = generate_access_token(&app.config, owner_id);
+    let outsider_token = generate_access_token(&app.config, outsider_id);
+
+    let resp = app
+        .oneshot(put_draft(
+            channel_id,
+            &owner_token,
+            json!({ "content": "a".repeat(10_001) }),
+        ))
+        .await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+    let resp = app
+        .oneshot(put_draft(
+            channel_id,
+            &outsider_token,
+            json!({ "content": "hello" }),
+        ))
+        .await;
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+
+    let resp = app
+        .oneshot(put_draft(
+            Uuid::new_v4(),
+            &owner_token,
+            json!({ "content": "hello" }),
+        ))
+        .await;
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+}
@@ -0,0 +1,164 @@
+//! HTTP Integration Tests for Email Verification
+//!
+//! Tests resending and confirming verification codes with the console email
+//! backend, and that changing the address clears the verified state. Codes
+//! only reach the log, so the tests store a known one directly.
+//!
+//! Run with: `cargo test --test integration email_verification -- --nocapture`
+
+use axum::body::Body;
+use axum::http::{Method, StatusCode};
+use chrono::{Duration, Utc};
+use uuid::Uuid;
+use vc_server::config::Config;
+
+use super::helpers::{authed, body_to_json, create_test_user, generate_access_token, TestApp};
+
+async fn console_email_app() -> TestApp {
+    let mut config = Config::default_for_test();
+    config.email_backend = "console".into();
+    TestApp::with_config(config).await
+}
+
+fn verify(code: &str) -> axum::http::Request<Body> {
+    TestApp::request(Method::POST, "/auth/email/verify")
+        .header("Content-Type", "application/json")
+        .extension(axum::extract::ConnectInfo(std::net::SocketAddr::from((
+            [127, 0, 0, 1],
+            0,
+        ))))
+        .body(Body::from(serde_json::json!({ "token": code }).to_string()))
+        .unwrap()
+}
+
+async fn store_code(pool: &sqlx::PgPool, user_id: Uuid, email: &str, code: &str) {
+    vc_server::db::create_email_verification_token(
+        pool,
+        user_id,
+        email,
+        &vc_server::auth::hash_token(code),
+        Utc::now() + Duration::hours(1),
+    )
+    .await
+    .expect("Failed to store verification code");
+}
+
+async fn email_verified(app: &TestApp, token: &str) -> bool {
+    let resp = app
+        .oneshot(
+            authed(Method::GET, "/auth/me", token)
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    body_to_json(resp).await["email_verified"]
+        .as_bool()
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_verify_email_with_code() {
+    let app = console_email_app().await;
+    let (user_id, _) = create_test_user(&app.pool).await;
+    let mut guard = app.cleanup_guard();
+    guard.delete_user(user_id);
+    let email = format!("verify_{}@example.com", user_id.simple());
+    sqlx::query("UPDATE users SET email = $2 WHERE id = $1")
+        .bind(user_id)
+        .bind(&email)
+        .execute(&app.pool)
+        .await
+        .unwrap();
+    let token = generate_access_token(&app.config, user_id);
+    assert!(!email_verified(&app, &token).await);
+
+    let resp = app
+        .oneshot(
+            authed(Method::POST, "/auth/email/verification", &token)
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    // Again right away
+    let resp = app
+        .oneshot(
+            authed(Method::POST, "/auth/email/verification", &token)
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+    assert_eq!(resp.status(), StatusCode::TOO_MANY_REQUESTS);
+
+    let resp = app.oneshot(verify("not-a-real-code")).await;
+    assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+
+    store_code(&app.pool, user_id, &email, "known-code").await;
+    let resp = app.oneshot(verify("known-code")).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert!(email_verified(&app, &token).await);
+
+    // Codes are single-use, and a verified address needs no new one
+    let resp = app.oneshot(verify("known-code")).await;
+    assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+    let resp = app
+        .oneshot(
+            authed(Method::POST, "/auth/email/verification", &token)
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+    assert_eq!(resp.status(), StatusCode::CONFLICT);
+}
+
+#[tokio::test]
+async fn test_changing_email_clears_verification() {
+    let app = console_email_app().await;
+    let (user_id, _) = create_test_user(&app.pool).await;
+    let mut guard = app.cleanup_guard();
+    guard.delete_user(user_id);
+    let old_email = format!("old_{}@example.com", user_id.simple());
+    let new_email = format!("new_{}@example.com", user_id.simple());
+    sqlx::query("UPDATE users SET email = $2, email_verified_at = NOW() WHERE id = $1")
+        .bind(user_id)
+        .bind(&old_email)
+        .execute(&app.pool)
+        .await
+        .unwrap();
+    let token = generate_access_token(&app.config, user_id);
+    store_code(&app.pool, user_id, &old_email, "old-address-code").await;
+
+    // Saving the same address keeps it verified
+    let resp = app
+        .oneshot(
+            authed(Method::POST, "/auth/me", &token)
+                .header("Content-Type", "application/json")
+                .body(Body::from(
+                    serde_json::json!({ "email": old_email }).to_string(),
+                ))
+                .unwrap(),
+        )
+        .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert!(email_verified(&app, &token).await);
+
+    let resp = app
+        .oneshot(
+            authed(Method::POST, "/auth/me", &token)
+                .header("Content-Type", "application/json")
+                .body(Body::from(
+                    serde_json::json!({ "email": new_email }).to_string(),
+                ))
+                .unwrap(),
+        )
+        .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert!(!email_verified(&app, &token).await);
+
+    // A code sent to the old address does not verify the new one
+    let resp = app.oneshot(verify("old-address-code")).await;
+    assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+    assert!(!email_verified(&app, &token).await);
+}
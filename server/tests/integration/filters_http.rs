@@ -1,7 +1,8 @@
 //! HTTP Integration Tests for Content Filters
 //!
 //! Tests filter configuration CRUD, custom patterns, message blocking,
-//! moderation log, and edge cases (encrypted, DM, cache invalidation).
+//! moderation log, escalation policies, and edge cases (encrypted, DM,
+//! cache invalidation).
 //!
 //! Run with: `cargo test --test integration filters_http -- --nocapture`
 
@@ -595,3 +596,585 @@ async fn test_cache_invalidation_on_config_change() {
     );
     assert_eq!(json["error"], "CONTENT_FILTERED");
 }
+
+// ============================================================================
+// Channel Language Rules
+// ============================================================================
+
+const ENGLISH_TEXT: &str = "The quick brown fox jumps over the lazy dog while everyone watches";
+const GERMAN_TEXT: &str =
+    "Der schnelle braune Fuchs springt über den faulen Hund und alle schauen zu";
+
+/// Set a channel language rule via the API and return the response.
+async fn set_language_rule(
+    app: &TestApp,
+    guild_id: Uuid,
+    channel_id: Uuid,
+    token: &str,
+    body: serde_json::Value,
+) -> axum::response::Response {
+    let req = TestApp::request(
+        Method::PUT,
+        &format!("/api/guilds/{guild_id}/filters/languages/{channel_id}"),
+    )
+    .header("Authorization", format!("Bearer {token}"))
+    .header("Content-Type", "application/json")
+    .body(Body::from(serde_json::to_string(&body).unwrap()))
+    .unwrap();
+
+    app.oneshot(req).await
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_language_rule_blocks_other_languages() {
+    let app = TestApp::new().await;
+    let (user_id, guild_id, channel_id, token) = setup_guild_with_filters(&app).await;
+    let other_channel = super::helpers::create_channel(&app.pool, guild_id, "anything").await;
+
+    let mut guard = app.cleanup_guard();
+    guard.add(move |pool| async move { super::helpers::delete_guild(&pool, guild_id).await });
+    guard.delete_user(user_id);
+
+    let resp = set_language_rule(
+        &app,
+        guild_id,
+        channel_id,
+        &token,
+        serde_json::json!({ "allowed_languages": ["ENG", "eng"], "action": "block" }),
+    )
+    .await;
+    assert_eq!(resp.status(), 200);
+    let rule = body_to_json(resp).await;
+    assert_eq!(rule["allowed_languages"], serde_json::json!(["eng"]));
+
+    let (status, json) = send_message_raw(&app, channel_id, &token, GERMAN_TEXT).await;
+    assert_eq!(status, 403, "Message in a disallowed language is blocked");
+    assert_eq!(json["error"], "CONTENT_FILTERED");
+
+    let (status, _) = send_message_raw(&app, channel_id, &token, ENGLISH_TEXT).await;
+    assert_eq!(status, 201);
+    // Too short to classify
+    let (status, _) = send_message_raw(&app, channel_id, &token, "danke!").await;
+    assert_eq!(status, 201);
+    // Other channels are unaffected
+    let (status, _) = send_message_raw(&app, other_channel, &token, GERMAN_TEXT).await;
+    assert_eq!(status, 201);
+
+    // Dry run with a channel applies its rule
+    let body = serde_json::json!({ "content": GERMAN_TEXT, "channel_id": channel_id });
+    let req = TestApp::request(
+        Method::POST,
+        &format!("/api/guilds/{guild_id}/filters/test"),
+    )
+    .header("Authorization", format!("Bearer {token}"))
+    .header("Content-Type", "application/json")
+    .body(Body::from(serde_json::to_string(&body).unwrap()))
+    .unwrap();
+    let json = body_to_json(app.oneshot(req).await).await;
+    assert_eq!(json["blocked"], true);
+    assert_eq!(json["matches"][0]["category"], "language");
+    assert_eq!(json["matches"][0]["matched_pattern"], "language:deu");
+
+    // Deleting the rule lets the message through again
+    let req = TestApp::request(
+        Method::DELETE,
+        &format!("/api/guilds/{guild_id}/filters/languages/{channel_id}"),
+    )
+    .header("Authorization", format!("Bearer {token}"))
+    .body(Body::empty())
+    .unwrap();
+    assert_eq!(app.oneshot(req).await.status(), 204);
+
+    let (status, _) = send_message_raw(&app, channel_id, &token, GERMAN_TEXT).await;
+    assert_eq!(status, 201);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_language_rule_warn_logs_and_validates() {
+    let app = TestApp::new().await;
+    let (user_id, guild_id, channel_id, token) = setup_guild_with_filters(&app).await;
+
+    let mut guard = app.cleanup_guard();
+    guard.add(move |pool| async move { super::helpers::delete_guild(&pool, guild_id).await });
+    guard.delete_user(user_id);
+
+    let resp = set_language_rule(
+        &app,
+        guild_id,
+        channel_id,
+        &token,
+        serde_json::json!({ "allowed_languages": ["english"] }),
+    )
+    .await;
+    assert_eq!(resp.status(), 400, "Unknown language codes are rejected");
+
+    let resp = set_language_rule(
+        &app,
+        guild_id,
+        channel_id,
+        &token,
+        serde_json::json!({ "allowed_languages": [] }),
+    )
+    .await;
+    assert_eq!(resp.status(), 400);
+
+    // Default action is warn
+    let resp = set_language_rule(
+        &app,
+        guild_id,
+        channel_id,
+        &token,
+        serde_json::json!({ "allowed_languages": ["eng"] }),
+    )
+    .await;
+    assert_eq!(resp.status(), 200);
+    assert_eq!(body_to_json(resp).await["action"], "warn");
+
+    let (status, _) = send_message_raw(&app, channel_id, &token, GERMAN_TEXT).await;
+    assert_eq!(status, 201, "Warn action lets the message through");
+
+    let req = TestApp::request(Method::GET, &format!("/api/guilds/{guild_id}/filters/log"))
+        .header("Authorization", format!("Bearer {token}"))
+        .body(Body::empty())
+        .unwrap();
+    let json = body_to_json(app.oneshot(req).await).await;
+    assert_eq!(json["total"], 1);
+    assert_eq!(json["items"][0]["category"], "language");
+    assert_eq!(json["items"][0]["action"], "warn");
+
+    // Language is not a toggleable category
+    let body = serde_json::json!({
+        "configs": [{ "category": "language", "enabled": true, "action": "block" }]
+    });
+    let req = TestApp::request(Method::PUT, &format!("/api/guilds/{guild_id}/filters"))
+        .header("Authorization", format!("Bearer {token}"))
+        .header("Content-Type", "application/json")
+        .body(Body::from(serde_json::to_string(&body).unwrap()))
+        .unwrap();
+    assert_eq!(app.oneshot(req).await.status(), 400);
+}
+
+// ============================================================================
+// Obfuscation Normalization
+// ============================================================================
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_normalization_toggle_catches_obfuscated_keywords() {
+    let app = TestApp::new().await;
+    let (user_id, guild_id, channel_id, token) = setup_guild_with_filters(&app).await;
+
+    let mut guard = app.cleanup_guard();
+    guard.add(move |pool| async move { super::helpers::delete_guild(&pool, guild_id).await });
+    guard.delete_user(user_id);
+
+    create_pattern(&app, guild_id, &token, "forbidden", false).await;
+
+    // All stages start off
+    let req = TestApp::request(
+        Method::GET,
+        &format!("/api/guilds/{guild_id}/filters/normalization"),
+    )
+    .header("Authorization", format!("Bearer {token}"))
+    .body(Body::empty())
+    .unwrap();
+    let json = body_to_json(app.oneshot(req).await).await;
+    assert_eq!(json["strip_invisible"], false);
+    assert_eq!(json["fold_confusables"], false);
+    assert_eq!(json["substitute_leetspeak"], false);
+
+    let (status, _) = send_message_raw(&app, channel_id, &token, "this is f0rb1dd3n").await;
+    assert_eq!(
+        status, 201,
+        "Obfuscated keyword passes without normalization"
+    );
+
+    let body = serde_json::json!({
+        "strip_invisible": true,
+        "fold_confusables": true,
+        "substitute_leetspeak": true,
+    });
+    let req = TestApp::request(
+        Method::PUT,
+        &format!("/api/guilds/{guild_id}/filters/normalization"),
+    )
+    .header("Authorization", format!("Bearer {token}"))
+    .header("Content-Type", "application/json")
+    .body(Body::from(serde_json::to_string(&body).unwrap()))
+    .unwrap();
+    let resp = app.oneshot(req).await;
+    assert_eq!(resp.status(), 200);
+    assert_eq!(body_to_json(resp).await, body);
+
+    // The cached engine was invalidated, so the new settings apply at once
+    for content in [
+        "this is f0rb1dd3n",
+        "this is for\u{200B}bidden",
+        "this is fоrbіdden",
+        "this is ｆｏｒｂｉｄｄｅｎ",
+        "this is fórbiddén",
+    ] {
+        let (status, json) = send_message_raw(&app, channel_id, &token, content).await;
+        assert_eq!(status, 403, "{content:?} should be blocked");
+        assert_eq!(json["error"], "CONTENT_FILTERED");
+    }
+    let (status, _) = send_message_raw(&app, channel_id, &token, "this is fine").await;
+    assert_eq!(status, 201);
+}
+
+// ============================================================================
+// Link Filtering
+// ============================================================================
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_links_category_modes_and_domain_lists() {
+    let app = TestApp::new().await;
+    let (user_id, guild_id, channel_id, token) = setup_guild_with_filters(&app).await;
+
+    let mut guard = app.cleanup_guard();
+    guard.add(move |pool| async move { super::helpers::delete_guild(&pool, guild_id).await });
+    guard.delete_user(user_id);
+
+    // Defaults to invites mode
+    enable_filter_category(&app, guild_id, &token, "links", "block").await;
+    let req = TestApp::request(Method::GET, &format!("/api/guilds/{guild_id}/filters"))
+        .header("Authorization", format!("Bearer {token}"))
+        .body(Body::empty())
+        .unwrap();
+    let json = body_to_json(app.oneshot(req).await).await;
+    assert_eq!(json[0]["category"], "links");
+    assert_eq!(json[0]["link_mode"], "invites");
+
+    let (status, json) =
+        send_message_raw(&app, channel_id, &token, "join us at discord.gg/abc123").await;
+    assert_eq!(status, 403, "External invites are blocked");
+    assert_eq!(json["error"], "CONTENT_FILTERED");
+    let (status, _) =
+        send_message_raw(&app, channel_id, &token, "docs: https://example.com/guide").await;
+    assert_eq!(status, 201);
+
+    // The guild's own invites pass
+    let req = TestApp::request(Method::POST, &format!("/api/guilds/{guild_id}/invites"))
+        .header("Authorization", format!("Bearer {token}"))
+        .header("Content-Type", "application/json")
+        .body(Body::from(r#"{"expires_in":"never"}"#))
+        .unwrap();
+    let resp = app.oneshot(req).await;
+    assert_eq!(resp.status(), 200);
+    let code = body_to_json(resp).await["code"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    let own = format!("https://chat.example.org/invite/{code}");
+    let (status, _) = send_message_raw(&app, channel_id, &token, &own).await;
+    assert_eq!(status, 201, "Own invites are not external");
+
+    // link_mode only applies to links
+    let body = serde_json::json!({
+        "configs": [{ "category": "spam", "enabled": true, "action": "block", "link_mode": "all" }]
+    });
+    let req = TestApp::request(Method::PUT, &format!("/api/guilds/{guild_id}/filters"))
+        .header("Authorization", format!("Bearer {token}"))
+        .header("Content-Type", "application/json")
+        .body(Body::from(serde_json::to_string(&body).unwrap()))
+        .unwrap();
+    assert_eq!(app.oneshot(req).await.status(), 400);
+
+    // Allowlist mode
+    let body = serde_json::json!({
+        "configs": [{ "category": "links", "enabled": true, "action": "block", "link_mode": "allowlist" }]
+    });
+    let req = TestApp::request(Method::PUT, &format!("/api/guilds/{guild_id}/filters"))
+        .header("Authorization", format!("Bearer {token}"))
+        .header("Content-Type", "application/json")
+        .body(Body::from(serde_json::to_string(&body).unwrap()))
+        .unwrap();
+    let resp = app.oneshot(req).await;
+    assert_eq!(resp.status(), 200);
+    assert_eq!(body_to_json(resp).await[0]["link_mode"], "allowlist");
+
+    let (status, _) =
+        send_message_raw(&app, channel_id, &token, "docs: https://example.com/guide").await;
+    assert_eq!(status, 403, "Unlisted domains are blocked");
+
+    let add_domain = |domain: &'static str, list: &'static str| {
+        TestApp::request(
+            Method::POST,
+            &format!("/api/guilds/{guild_id}/filters/domains"),
+        )
+        .header("Authorization", format!("Bearer {token}"))
+        .header("Content-Type", "application/json")
+        .body(Body::from(
+            serde_json::json!({ "domain": domain, "list": list }).to_string(),
+        ))
+        .unwrap()
+    };
+
+    let resp = app.oneshot(add_domain("not a domain", "allow")).await;
+    assert_eq!(resp.status(), 400);
+
+    let resp = app
+        .oneshot(add_domain("https://www.Example.com/", "allow"))
+        .await;
+    assert_eq!(resp.status(), 201);
+    let allowed = body_to_json(resp).await;
+    assert_eq!(allowed["domain"], "example.com");
+    assert_eq!(allowed["list"], "allow");
+
+    let (status, _) = send_message_raw(
+        &app,
+        channel_id,
+        &token,
+        "docs: https://docs.example.com/guide",
+    )
+    .await;
+    assert_eq!(status, 201, "Allowlisted domains and subdomains pass");
+
+    // Denylisted domains match even without a scheme
+    let resp = app.oneshot(add_domain("evil.net", "deny")).await;
+    assert_eq!(resp.status(), 201);
+    let body = serde_json::json!({ "content": "claim at evil.net/free" });
+    let req = TestApp::request(
+        Method::POST,
+        &format!("/api/guilds/{guild_id}/filters/test"),
+    )
+    .header("Authorization", format!("Bearer {token}"))
+    .header("Content-Type", "application/json")
+    .body(Body::from(serde_json::to_string(&body).unwrap()))
+    .unwrap();
+    let json = body_to_json(app.oneshot(req).await).await;
+    assert_eq!(json["blocked"], true);
+    assert_eq!(json["matches"][0]["category"], "links");
+    assert_eq!(json["matches"][0]["matched_pattern"], "domain:evil.net");
+
+    // Removing the allowlist entry blocks the domain again
+    let allowed_id = allowed["id"].as_str().unwrap();
+    let req = TestApp::request(
+        Method::DELETE,
+        &format!("/api/guilds/{guild_id}/filters/domains/{allowed_id}"),
+    )
+    .header("Authorization", format!("Bearer {token}"))
+    .body(Body::empty())
+    .unwrap();
+    assert_eq!(app.oneshot(req).await.status(), 204);
+
+    let req = TestApp::request(
+        Method::GET,
+        &format!("/api/guilds/{guild_id}/filters/domains"),
+    )
+    .header("Authorization", format!("Bearer {token}"))
+    .body(Body::empty())
+    .unwrap();
+    let json = body_to_json(app.oneshot(req).await).await;
+    assert_eq!(json.as_array().unwrap().len(), 1);
+    assert_eq!(json[0]["domain"], "evil.net");
+
+    let (status, _) =
+        send_message_raw(&app, channel_id, &token, "docs: https://example.com/guide").await;
+    assert_eq!(status, 403);
+}
+
+// ============================================================================
+// Escalation Policies
+// ============================================================================
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_escalation_policy_times_out_repeat_offender() {
+    let app = TestApp::new().await;
+    let (owner_id, _) = create_test_user(&app.pool).await;
+    let (member_id, _) = create_test_user(&app.pool).await;
+    let owner_token = generate_access_token(&app.config, owner_id);
+    let member_token = generate_access_token(&app.config, member_id);
+
+    let perms = GuildPermissions::VIEW_CHANNEL | GuildPermissions::SEND_MESSAGES;
+    let guild_id = super::helpers::create_guild_with_default_role(&app.pool, owner_id, perms).await;
+    let channel_id = super::helpers::create_channel(&app.pool, guild_id, "filter-test").await;
+    super::helpers::add_guild_member(&app.pool, guild_id, member_id).await;
+
+    let mut guard = app.cleanup_guard();
+    guard.add(move |pool| async move { super::helpers::delete_guild(&pool, guild_id).await });
+    guard.delete_user(owner_id);
+    guard.delete_user(member_id);
+
+    let create_policy = |token: &str, body: serde_json::Value| {
+        TestApp::request(
+            Method::POST,
+            &format!("/api/guilds/{guild_id}/filters/escalations"),
+        )
+        .header("Authorization", format!("Bearer {token}"))
+        .header("Content-Type", "application/json")
+        .body(Body::from(body.to_string()))
+        .unwrap()
+    };
+    let evaluate = |user_id: Uuid| {
+        TestApp::request(
+            Method::POST,
+            &format!("/api/guilds/{guild_id}/filters/escalations/evaluate"),
+        )
+        .header("Authorization", format!("Bearer {owner_token}"))
+        .header("Content-Type", "application/json")
+        .body(Body::from(
+            serde_json::json!({ "user_id": user_id }).to_string(),
+        ))
+        .unwrap()
+    };
+
+    // Members without MANAGE_GUILD cannot manage policies
+    let body = serde_json::json!({
+        "name": "Mute", "threshold": 2, "window_seconds": 3600,
+        "action": "timeout", "timeout_seconds": 600,
+    });
+    let resp = app.oneshot(create_policy(&member_token, body)).await;
+    assert_eq!(resp.status(), 403);
+
+    // Validation
+    for body in [
+        serde_json::json!({ "name": "Mute", "threshold": 2, "window_seconds": 3600, "action": "timeout" }),
+        serde_json::json!({ "name": "Warn", "threshold": 2, "window_seconds": 3600, "action": "warn", "timeout_seconds": 600 }),
+        serde_json::json!({ "name": "Ban", "threshold": 0, "window_seconds": 3600, "action": "ban" }),
+        serde_json::json!({ "name": "Ban", "threshold": 2, "window_seconds": 10, "action": "ban" }),
+        serde_json::json!({ "name": "Ban", "threshold": 2, "window_seconds": 3600, "action": "ban", "notify_channel_id": Uuid::new_v4() }),
+    ] {
+        let resp = app.oneshot(create_policy(&owner_token, body)).await;
+        assert_eq!(resp.status(), 400);
+    }
+
+    let body = serde_json::json!({
+        "name": "Mute", "threshold": 2, "window_seconds": 3600,
+        "action": "timeout", "timeout_seconds": 600, "notify_channel_id": channel_id,
+    });
+    let resp = app.oneshot(create_policy(&owner_token, body)).await;
+    assert_eq!(resp.status(), 201);
+    let timeout_policy = body_to_json(resp).await;
+    let timeout_policy_id = timeout_policy["id"].as_str().unwrap().to_string();
+    assert_eq!(timeout_policy["enabled"], true);
+
+    let body = serde_json::json!({
+        "name": "Ban", "threshold": 10, "window_seconds": 86400, "action": "ban",
+    });
+    let resp = app.oneshot(create_policy(&owner_token, body)).await;
+    assert_eq!(resp.status(), 201);
+    let ban_policy_id = body_to_json(resp).await["id"].as_str().unwrap().to_string();
+
+    create_pattern(&app, guild_id, &owner_token, "forbidden", false).await;
+
+    // One violation: below every threshold
+    let (status, _) = send_message_raw(&app, channel_id, &member_token, "forbidden").await;
+    assert_eq!(status, 403);
+    let json = body_to_json(app.oneshot(evaluate(member_id)).await).await;
+    assert_eq!(json["exempt"], false);
+    assert!(json["triggered_policy_id"].is_null());
+    assert_eq!(json["policies"][0]["violations"], 1);
+
+    // The owner is never escalated
+    let json = body_to_json(app.oneshot(evaluate(owner_id)).await).await;
+    assert_eq!(json["exempt"], true);
+
+    // Second violation reaches the timeout policy
+    let (status, _) = send_message_raw(&app, channel_id, &member_token, "forbidden").await;
+    assert_eq!(status, 403);
+
+    let mut timeout_until: Option<chrono::DateTime<chrono::Utc>> = None;
+    for _ in 0..50 {
+        timeout_until = sqlx::query_scalar(
+            "SELECT timeout_until FROM guild_members WHERE guild_id = $1 AND user_id = $2",
+        )
+        .bind(guild_id)
+        .bind(member_id)
+        .fetch_one(&app.pool)
+        .await
+        .unwrap();
+        if timeout_until.is_some() {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+    }
+    let timeout_until = timeout_until.expect("Member should be timed out");
+    assert!(timeout_until > chrono::Utc::now() + chrono::Duration::seconds(500));
+
+    // Audit entry and moderator notice (written after the timeout)
+    let mut audited = 0;
+    for _ in 0..50 {
+        audited = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM guild_audit_log
+             WHERE guild_id = $1 AND action = 'guild.automod.timed_out' AND target_id = $2",
+        )
+        .bind(guild_id)
+        .bind(member_id)
+        .fetch_one(&app.pool)
+        .await
+        .unwrap();
+        if audited > 0 {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+    }
+    assert_eq!(audited, 1_i64);
+
+    let mut notices = 0;
+    for _ in 0..50 {
+        notices = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM messages
+             WHERE channel_id = $1 AND message_type = 'system_automod'",
+        )
+        .bind(channel_id)
+        .fetch_one(&app.pool)
+        .await
+        .unwrap();
+        if notices > 0 {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+    }
+    assert_eq!(notices, 1_i64);
+
+    // Violations acted on are not counted again
+    let json = body_to_json(app.oneshot(evaluate(member_id)).await).await;
+    assert_eq!(json["policies"][0]["policy_id"], timeout_policy_id);
+    assert_eq!(json["policies"][0]["violations"], 0);
+    assert_eq!(json["policies"][1]["policy_id"], ban_policy_id);
+    assert_eq!(json["policies"][1]["violations"], 2);
+
+    // Update and delete
+    let req = TestApp::request(
+        Method::PUT,
+        &format!("/api/guilds/{guild_id}/filters/escalations/{timeout_policy_id}"),
+    )
+    .header("Authorization", format!("Bearer {owner_token}"))
+    .header("Content-Type", "application/json")
+    .body(Body::from(
+        serde_json::json!({
+            "name": "Warn", "enabled": false, "threshold": 3,
+            "window_seconds": 3600, "action": "warn",
+        })
+        .to_string(),
+    ))
+    .unwrap();
+    let resp = app.oneshot(req).await;
+    assert_eq!(resp.status(), 200);
+    let json = body_to_json(resp).await;
+    assert_eq!(json["action"], "warn");
+    assert!(json["timeout_seconds"].is_null());
+    assert!(json["notify_channel_id"].is_null());
+
+    let delete = || {
+        TestApp::request(
+            Method::DELETE,
+            &format!("/api/guilds/{guild_id}/filters/escalations/{ban_policy_id}"),
+        )
+        .header("Authorization", format!("Bearer {owner_token}"))
+        .body(Body::empty())
+        .unwrap()
+    };
+    assert_eq!(app.oneshot(delete()).await.status(), 204);
+    assert_eq!(app.oneshot(delete()).await.status(), 404);
+
+    let req = TestApp::request(
+        Method::GET,
+        &format!("/api/guilds/{guild_id}/filters/escalations"),
+    )
+    .header("Authorization", format!("Bearer {owner_token}"))
+    .body(Body::empty())
+    .unwrap();
+    let json = body_to_json(app.oneshot(req).await).await;
+    assert_eq!(json.as_array().unwrap().len(), 1);
+}
@@ -6,9 +6,17 @@
 
 use axum::body::Body;
 use axum::http::Method;
+use uuid::Uuid;
 use vc_server::auth::hash_password;
+use vc_server::config::DeletedUserContent;
+use vc_server::governance::deletion::process_pending_deletions;
+use vc_server::permissions::GuildPermissions;
 
-use super::helpers::{body_to_json, create_test_user, generate_access_token, TestApp};
+use super::helpers::{
+    add_guild_member, body_to_json, create_channel, create_dm_channel, create_friendship,
+    create_guild_with_default_role, create_test_user, delete_guild, generate_access_token,
+    insert_message, TestApp,
+};
 
 // ============================================================================
 // Helpers
@@ -120,6 +128,65 @@ async fn test_request_export_recovers_stale_pending_job() {
     assert_eq!(status.as_deref(), Some("failed"));
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_request_export_rejects_second_active_job() {
+    let app = TestApp::new().await;
+    let (user_id, _) = create_test_user(&app.pool).await;
+    let token = generate_access_token(&app.config, user_id);
+
+    let mut guard = app.cleanup_guard();
+    guard.delete_user(user_id);
+
+    sqlx::query("INSERT INTO data_export_jobs (user_id, status) VALUES ($1, 'processing')")
+        .bind(user_id)
+        .execute(&app.pool)
+        .await
+        .unwrap();
+
+    let req = TestApp::request(Method::POST, "/api/me/data-export")
+        .header("Authorization", format!("Bearer {token}"))
+        .body(Body::empty())
+        .unwrap();
+
+    let resp = app.oneshot(req).await;
+    assert_eq!(
+        resp.status(),
+        409,
+        "Only one active export per user is allowed"
+    );
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_get_export_status_pending_has_no_download_url() {
+    let app = TestApp::new().await;
+    let (user_id, _) = create_test_user(&app.pool).await;
+    let token = generate_access_token(&app.config, user_id);
+
+    let mut guard = app.cleanup_guard();
+    guard.delete_user(user_id);
+
+    let job_id: uuid::Uuid = sqlx::query_scalar(
+        "INSERT INTO data_export_jobs (user_id, status) VALUES ($1, 'pending') RETURNING id",
+    )
+    .bind(user_id)
+    .fetch_one(&app.pool)
+    .await
+    .unwrap();
+
+    let req = TestApp::request(Method::GET, "/api/me/data-export")
+        .header("Authorization", format!("Bearer {token}"))
+        .body(Body::empty())
+        .unwrap();
+
+    let resp = app.oneshot(req).await;
+    assert_eq!(resp.status(), 200);
+    let json = body_to_json(resp).await;
+    assert_eq!(json["id"], job_id.to_string());
+    assert_eq!(json["status"], "pending");
+    assert!(json["download_url"].is_null());
+    assert!(json["download_url_expires_at"].is_null());
+}
+
 // ============================================================================
 // Account Deletion Tests
 // ============================================================================
@@ -430,3 +497,162 @@ async fn test_profile_shows_deletion_scheduled() {
         "Profile should include deletion_scheduled_at after deletion request"
     );
 }
+
+// ============================================================================
+// Deletion Worker (tombstones)
+// ============================================================================
+
+/// Make a user's deletion due now.
+async fn schedule_deletion_now(pool: &sqlx::PgPool, user_id: Uuid) {
+    sqlx::query(
+        "UPDATE users SET deletion_requested_at = NOW() - INTERVAL '30 days',
+                          deletion_scheduled_at = NOW() - INTERVAL '1 minute'
+         WHERE id = $1",
+    )
+    .bind(user_id)
+    .execute(pool)
+    .await
+    .unwrap();
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_deleted_user_messages_are_tombstoned() {
+    let app = TestApp::new().await;
+    let (deleted_id, _) = create_test_user(&app.pool).await;
+    let (reader_id, _) = create_test_user(&app.pool).await;
+    let guild_id =
+        create_guild_with_default_role(&app.pool, reader_id, GuildPermissions::VIEW_CHANNEL).await;
+    add_guild_member(&app.pool, guild_id, deleted_id).await;
+    let channel_id = create_channel(&app.pool, guild_id, "tombstones").await;
+    let shared_dm = create_dm_channel(&app.pool, deleted_id, reader_id).await;
+    let orphaned_dm = create_dm_channel(&app.pool, deleted_id, reader_id).await;
+    sqlx::query("DELETE FROM dm_participants WHERE channel_id = $1 AND user_id = $2")
+        .bind(orphaned_dm)
+        .bind(reader_id)
+        .execute(&app.pool)
+        .await
+        .unwrap();
+    create_friendship(&app.pool, deleted_id, reader_id).await;
+
+    let mut guard = app.cleanup_guard();
+    guard.add(move |pool| async move {
+        delete_guild(&pool, guild_id).await;
+        sqlx::query("DELETE FROM channels WHERE id = ANY($1)")
+            .bind(vec![shared_dm, orphaned_dm])
+            .execute(&pool)
+            .await
+            .ok();
+    });
+    guard.delete_user(deleted_id);
+    guard.delete_user(reader_id);
+
+    let first = insert_message(&app.pool, channel_id, deleted_id, "still here").await;
+    let second = insert_message(&app.pool, channel_id, deleted_id, "me too").await;
+    insert_message(&app.pool, orphaned_dm, deleted_id, "nobody reads this").await;
+
+    schedule_deletion_now(&app.pool, deleted_id).await;
+    process_pending_deletions(&app.pool, &None, DeletedUserContent::Anonymize)
+        .await
+        .unwrap();
+
+    let user_exists: bool = sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM users WHERE id = $1)")
+        .bind(deleted_id)
+        .fetch_one(&app.pool)
+        .await
+        .unwrap();
+    assert!(!user_exists);
+
+    let channels_left: Vec<Uuid> = sqlx::query_scalar("SELECT id FROM channels WHERE id = ANY($1)")
+        .bind(vec![shared_dm, orphaned_dm])
+        .fetch_all(&app.pool)
+        .await
+        .unwrap();
+    assert_eq!(channels_left, vec![shared_dm]);
+
+    let friendships: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM friendships WHERE requester_id = $1 OR addressee_id = $1",
+    )
+    .bind(reader_id)
+    .fetch_one(&app.pool)
+    .await
+    .unwrap();
+    assert_eq!(friendships, 0);
+
+    // Both messages keep their content under one stable tombstone author
+    let token = generate_access_token(&app.config, reader_id);
+    let req = TestApp::request(Method::GET, &format!("/api/messages/channel/{channel_id}"))
+        .header("Authorization", format!("Bearer {token}"))
+        .body(Body::empty())
+        .unwrap();
+    let resp = app.oneshot(req).await;
+    assert_eq!(resp.status(), 200);
+    let body = body_to_json(resp).await;
+    let items = body["items"].as_array().unwrap();
+    let by_id = |id: Uuid| {
+        items
+            .iter()
+            .find(|m| m["id"] == id.to_string())
+            .expect("message kept")
+    };
+    let (first, second) = (by_id(first), by_id(second));
+    assert_eq!(first["content"], "still here");
+    assert_eq!(first["author"]["display_name"], "Deleted User");
+    assert_ne!(first["author"]["id"], Uuid::nil().to_string());
+    assert_ne!(first["author"]["id"], deleted_id.to_string());
+    assert_eq!(first["author"]["id"], second["author"]["id"]);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_deleted_user_content_policy_delete() {
+    let app = TestApp::new().await;
+    let (deleted_id, _) = create_test_user(&app.pool).await;
+    let (reader_id, _) = create_test_user(&app.pool).await;
+    let guild_id =
+        create_guild_with_default_role(&app.pool, reader_id, GuildPermissions::VIEW_CHANNEL).await;
+    add_guild_member(&app.pool, guild_id, deleted_id).await;
+    let channel_id = create_channel(&app.pool, guild_id, "forget-me").await;
+
+    let mut guard = app.cleanup_guard();
+    guard.add(move |pool| async move { delete_guild(&pool, guild_id).await });
+    guard.delete_user(deleted_id);
+    guard.delete_user(reader_id);
+
+    let plain = insert_message(&app.pool, channel_id, deleted_id, "delete me").await;
+    let parent = insert_message(&app.pool, channel_id, deleted_id, "thread start").await;
+    let reply = insert_message(&app.pool, channel_id, reader_id, "a reply").await;
+    sqlx::query("UPDATE messages SET parent_id = $1 WHERE id = $2")
+        .bind(parent)
+        .bind(reply)
+        .execute(&app.pool)
+        .await
+        .unwrap();
+    sqlx::query("UPDATE messages SET thread_reply_count = 1 WHERE id = $1")
+        .bind(parent)
+        .execute(&app.pool)
+        .await
+        .unwrap();
+
+    schedule_deletion_now(&app.pool, deleted_id).await;
+    process_pending_deletions(&app.pool, &None, DeletedUserContent::Delete)
+        .await
+        .unwrap();
+
+    let rows: Vec<(Uuid, String, bool, Option<Uuid>)> = sqlx::query_as(
+        "SELECT id, content, deleted_at IS NOT NULL, author_tombstone_id
+         FROM messages WHERE channel_id = $1",
+    )
+    .bind(channel_id)
+    .fetch_all(&app.pool)
+    .await
+    .unwrap();
+    assert!(!rows.iter().any(|(id, ..)| *id == plain));
+
+    let (_, content, deleted, tombstone) = rows.iter().find(|(id, ..)| *id == parent).unwrap();
+    assert_eq!(content, "");
+    assert!(*deleted);
+    assert!(tombstone.is_some());
+
+    let (_, content, deleted, _) = rows.iter().find(|(id, ..)| *id == reply).unwrap();
+    assert_eq!(content, "a reply");
+    assert!(!*deleted);
+}
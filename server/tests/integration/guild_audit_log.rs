@@ -0,0 +1,126 @@
+//! HTTP integration tests for the guild audit log.
+//!
+//! Run with: `cargo test --test integration guild_audit_log -- --nocapture`
+
+use axum::body::Body;
+use axum::http::{Method, StatusCode};
+
+use super::helpers::{
+    add_guild_member, authed, body_to_json, create_guild, create_test_user, delete_guild,
+    delete_user, generate_access_token, json_request, TestApp,
+};
+
+#[tokio::test]
+async fn test_audit_log_records_and_filters_actions() {
+    let app = TestApp::new().await;
+    let (owner_id, _) = create_test_user(&app.pool).await;
+    let (member_id, _) = create_test_user(&app.pool).await;
+    let guild_id = create_guild(&app.pool, owner_id).await;
+    let mut guard = app.cleanup_guard();
+    guard.add(move |pool| async move {
+        delete_guild(&pool, guild_id).await;
+        delete_user(&pool, owner_id).await;
+        delete_user(&pool, member_id).await;
+    });
+    add_guild_member(&app.pool, guild_id, member_id).await;
+    let owner_token = generate_access_token(&app.config, owner_id);
+    let member_token = generate_access_token(&app.config, member_id);
+
+    let resp = app
+        .oneshot(json_request(
+            Method::PATCH,
+            &format!("/api/guilds/{guild_id}/settings"),
+            &owner_token,
+            serde_json::json!({ "threads_enabled": false }),
+        ))
+        .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let resp = app
+        .oneshot(json_request(
+            Method::POST,
+            &format!("/api/guilds/{guild_id}/roles"),
+            &owner_token,
+            serde_json::json!({ "name": "Helpers" }),
+        ))
+        .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let role_id = body_to_json(resp).await["id"].as_str().unwrap().to_string();
+
+    let resp = app
+        .oneshot(
+            authed(
+                Method::POST,
+                &format!("/api/guilds/{guild_id}/members/{member_id}/roles/{role_id}"),
+                &owner_token,
+            )
+            .body(Body::empty())
+            .unwrap(),
+        )
+        .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let list = |query: &str, token: &str| {
+        authed(
+            Method::GET,
+            &format!("/api/guilds/{guild_id}/audit-log{query}"),
+            token,
+        )
+        .body(Body::empty())
+        .unwrap()
+    };
+
+    // Plain members lack VIEW_AUDIT_LOG
+    assert_eq!(
+        app.oneshot(list("", &member_token)).await.status(),
+        StatusCode::FORBIDDEN
+    );
+
+    let resp = app.oneshot(list("", &owner_token)).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let body = body_to_json(resp).await;
+    assert_eq!(body["total"], 3);
+    // Newest first
+    assert_eq!(body["items"][0]["action"], "guild.members.role_added");
+    assert_eq!(body["items"][0]["target_id"], member_id.to_string());
+    assert_eq!(body["items"][0]["changes"]["role_id"], role_id);
+    assert_eq!(body["items"][2]["action"], "guild.settings.updated");
+    assert_eq!(body["items"][2]["changes"]["threads_enabled"], false);
+    assert!(body["items"][2]["changes"].get("tags").is_none());
+
+    let resp = app
+        .oneshot(list("?action=guild.roles.", &owner_token))
+        .await;
+    let body = body_to_json(resp).await;
+    assert_eq!(body["total"], 1);
+    assert_eq!(body["items"][0]["action"], "guild.roles.created");
+    assert_eq!(body["items"][0]["changes"]["name"], "Helpers");
+
+    let resp = app
+        .oneshot(list(
+            "?action_type=guild.members.role_added&limit=1",
+            &owner_token,
+        ))
+        .await;
+    let body = body_to_json(resp).await;
+    assert_eq!(body["total"], 1);
+    assert_eq!(body["limit"], 1);
+
+    let resp = app
+        .oneshot(list(&format!("?actor_id={member_id}"), &owner_token))
+        .await;
+    assert_eq!(body_to_json(resp).await["total"], 0);
+
+    let resp = app
+        .oneshot(list("?from_date=2100-01-01T00:00:00Z", &owner_token))
+        .await;
+    assert_eq!(body_to_json(resp).await["total"], 0);
+
+    let resp = app
+        .oneshot(list(
+            "?from_date=2030-01-02T00:00:00Z&to_date=2030-01-01T00:00:00Z",
+            &owner_token,
+        ))
+        .await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+}
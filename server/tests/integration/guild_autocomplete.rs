@@ -0,0 +1,161 @@
+//! HTTP integration tests for mention autocomplete.
+//!
+//! Run with: `cargo test --test integration guild_autocomplete -- --nocapture`
+
+use axum::body::Body;
+use axum::http::{Method, StatusCode};
+use uuid::Uuid;
+use vc_server::permissions::GuildPermissions;
+
+use super::helpers::{
+    add_guild_member, body_to_json, create_channel, create_guild_with_default_role,
+    create_test_user, delete_guild, delete_user, generate_access_token, insert_message, TestApp,
+};
+
+async fn autocomplete(
+    app: &TestApp,
+    token: &str,
+    guild_id: Uuid,
+    query: &str,
+) -> (StatusCode, serde_json::Value) {
+    let req = TestApp::request(
+        Method::GET,
+        &format!("/api/guilds/{guild_id}/autocomplete?{query}"),
+    )
+    .header("authorization", format!("Bearer {token}"))
+    .body(Body::empty())
+    .unwrap();
+    let resp = app.oneshot(req).await;
+    let status = resp.status();
+    if status != StatusCode::OK {
+        // Query rejections are plain text
+        return (status, serde_json::Value::Null);
+    }
+    (status, body_to_json(resp).await)
+}
+
+fn names(body: &serde_json::Value, field: &str) -> Vec<String> {
+    body["results"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|r| r[field].as_str().unwrap().to_string())
+        .collect()
+}
+
+#[tokio::test]
+async fn test_autocomplete_users_ranked_by_recent_activity() {
+    let app = TestApp::new().await;
+    let (owner_id, _) = create_test_user(&app.pool).await;
+    let (alice_id, _) = create_test_user(&app.pool).await;
+    let (bob_id, _) = create_test_user(&app.pool).await;
+    let (outsider_id, _) = create_test_user(&app.pool).await;
+    let guild_id =
+        create_guild_with_default_role(&app.pool, owner_id, GuildPermissions::VIEW_CHANNEL).await;
+    let mut guard = app.cleanup_guard();
+    guard.add(move |pool| async move {
+        delete_guild(&pool, guild_id).await;
+        for id in [owner_id, alice_id, bob_id, outsider_id] {
+            delete_user(&pool, id).await;
+        }
+    });
+    add_guild_member(&app.pool, guild_id, alice_id).await;
+    add_guild_member(&app.pool, guild_id, bob_id).await;
+
+    let tag = format!("ac{}", &Uuid::new_v4().simple().to_string()[..8]);
+    for (id, name) in [(alice_id, "alpha"), (bob_id, "beta")] {
+        sqlx::query("UPDATE users SET display_name = $1 WHERE id = $2")
+            .bind(format!("{tag}{name}"))
+            .bind(id)
+            .execute(&app.pool)
+            .await
+            .unwrap();
+    }
+    let channel_id = create_channel(&app.pool, guild_id, "general").await;
+    insert_message(&app.pool, channel_id, bob_id, "hello").await;
+    let owner_token = generate_access_token(&app.config, owner_id);
+
+    // Bob posted recently, so he ranks above Alice
+    let (status, body) = autocomplete(
+        &app,
+        &owner_token,
+        guild_id,
+        &format!("type=user&q=%40{tag}"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(
+        names(&body, "display_name"),
+        vec![format!("{tag}beta"), format!("{tag}alpha")]
+    );
+    assert_eq!(body["results"][0]["type"], "user");
+
+    // Prefix matching is case-insensitive
+    let (_, body) = autocomplete(
+        &app,
+        &owner_token,
+        guild_id,
+        &format!("type=user&q={}", tag.to_uppercase()),
+    )
+    .await;
+    assert_eq!(body["results"].as_array().unwrap().len(), 2);
+    let (_, body) = autocomplete(
+        &app,
+        &owner_token,
+        guild_id,
+        &format!("type=user&q={tag}al"),
+    )
+    .await;
+    assert_eq!(names(&body, "display_name"), vec![format!("{tag}alpha")]);
+
+    // Non-members are rejected, and the type must be known
+    let outsider_token = generate_access_token(&app.config, outsider_id);
+    let (status, _) = autocomplete(&app, &outsider_token, guild_id, "type=user&q=a").await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    let (status, _) = autocomplete(&app, &owner_token, guild_id, "type=emoji&q=a").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_autocomplete_channels_and_roles_respect_permissions() {
+    let app = TestApp::new().await;
+    let (owner_id, _) = create_test_user(&app.pool).await;
+    let (member_id, _) = create_test_user(&app.pool).await;
+    // @everyone has no permissions: members can't view channels or mention everyone
+    let guild_id =
+        create_guild_with_default_role(&app.pool, owner_id, GuildPermissions::empty()).await;
+    let mut guard = app.cleanup_guard();
+    guard.add(move |pool| async move {
+        delete_guild(&pool, guild_id).await;
+        delete_user(&pool, owner_id).await;
+        delete_user(&pool, member_id).await;
+    });
+    add_guild_member(&app.pool, guild_id, member_id).await;
+    create_channel(&app.pool, guild_id, "general").await;
+    create_channel(&app.pool, guild_id, "gaming").await;
+    sqlx::query(
+        "INSERT INTO guild_roles (id, guild_id, name, permissions, position) VALUES ($1, $2, 'Moderators', 0, 10)",
+    )
+    .bind(Uuid::now_v7())
+    .bind(guild_id)
+    .execute(&app.pool)
+    .await
+    .unwrap();
+    let owner_token = generate_access_token(&app.config, owner_id);
+    let member_token = generate_access_token(&app.config, member_id);
+
+    let (status, body) = autocomplete(&app, &owner_token, guild_id, "type=channel&q=%23gen").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(names(&body, "name"), vec!["general"]);
+    assert_eq!(body["results"][0]["channel_type"], "text");
+
+    let (status, body) = autocomplete(&app, &member_token, guild_id, "type=channel&q=g").await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(body["results"].as_array().unwrap().is_empty());
+
+    // @everyone is only suggested to members who may mention it
+    let (_, body) = autocomplete(&app, &member_token, guild_id, "type=role").await;
+    assert_eq!(names(&body, "name"), vec!["Moderators"]);
+    let (_, body) = autocomplete(&app, &owner_token, guild_id, "type=role&q=%40every").await;
+    assert_eq!(names(&body, "name"), vec!["@everyone"]);
+}
@@ -0,0 +1,220 @@
+//! HTTP integration tests for guild bans and kicks.
+//!
+//! Run with: `cargo test --test integration guild_bans -- --nocapture`
+
+use axum::body::Body;
+use axum::http::{Method, StatusCode};
+use uuid::Uuid;
+use vc_server::permissions::GuildPermissions;
+
+use super::helpers::{
+    add_guild_member, authed, body_to_json, create_channel, create_guild, create_test_user,
+    delete_guild, delete_user, generate_access_token, insert_message, TestApp,
+};
+
+/// Give `user_id` a role with `perms` at position 1.
+async fn grant_role(app: &TestApp, guild_id: Uuid, user_id: Uuid, perms: GuildPermissions) {
+    let role_id = Uuid::now_v7();
+    sqlx::query(
+        "INSERT INTO guild_roles (id, guild_id, name, permissions, position) VALUES ($1, $2, 'Mod', $3, 1)",
+    )
+    .bind(role_id)
+    .bind(guild_id)
+    .bind(perms.to_db())
+    .execute(&app.pool)
+    .await
+    .unwrap();
+    sqlx::query("INSERT INTO guild_member_roles (guild_id, user_id, role_id) VALUES ($1, $2, $3)")
+        .bind(guild_id)
+        .bind(user_id)
+        .bind(role_id)
+        .execute(&app.pool)
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn test_ban_prune_list_and_unban() {
+    let app = TestApp::new().await;
+    let (owner_id, _) = create_test_user(&app.pool).await;
+    let (member_id, _) = create_test_user(&app.pool).await;
+    let guild_id = create_guild(&app.pool, owner_id).await;
+    let mut guard = app.cleanup_guard();
+    guard.add(move |pool| async move {
+        delete_guild(&pool, guild_id).await;
+        delete_user(&pool, owner_id).await;
+        delete_user(&pool, member_id).await;
+    });
+    add_guild_member(&app.pool, guild_id, member_id).await;
+    let channel_id = create_channel(&app.pool, guild_id, "general").await;
+    let message_id = insert_message(&app.pool, channel_id, member_id, "spam").await;
+    let owner_token = generate_access_token(&app.config, owner_id);
+    let member_token = generate_access_token(&app.config, member_id);
+
+    // Plain members cannot ban
+    let resp = app
+        .oneshot(
+            authed(
+                Method::POST,
+                &format!("/api/guilds/{guild_id}/bans"),
+                &member_token,
+            )
+            .header("content-type", "application/json")
+            .body(Body::from(format!(r#"{{"user_id": "{owner_id}"}}"#)))
+            .unwrap(),
+        )
+        .await;
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+
+    let resp = app
+        .oneshot(
+            authed(
+                Method::POST,
+                &format!("/api/guilds/{guild_id}/bans"),
+                &owner_token,
+            )
+            .header("content-type", "application/json")
+            .body(Body::from(format!(
+                r#"{{"user_id": "{member_id}", "reason": "spam", "delete_message_seconds": 3600}}"#
+            )))
+            .unwrap(),
+        )
+        .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let body = body_to_json(resp).await;
+    assert_eq!(body["removed"], true);
+    assert_eq!(body["deleted_messages"], 1);
+
+    let deleted: bool =
+        sqlx::query_scalar("SELECT deleted_at IS NOT NULL FROM messages WHERE id = $1")
+            .bind(message_id)
+            .fetch_one(&app.pool)
+            .await
+            .unwrap();
+    assert!(deleted);
+
+    let resp = app
+        .oneshot(
+            authed(
+                Method::GET,
+                &format!("/api/guilds/{guild_id}/bans?limit=10"),
+                &owner_token,
+            )
+            .body(Body::empty())
+            .unwrap(),
+        )
+        .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let body = body_to_json(resp).await;
+    assert_eq!(body["total"], 1);
+    assert_eq!(body["items"][0]["user_id"], member_id.to_string());
+    assert_eq!(body["items"][0]["reason"], "spam");
+
+    // Banned users cannot rejoin via invite
+    let resp = app
+        .oneshot(
+            authed(
+                Method::POST,
+                &format!("/api/guilds/{guild_id}/invites"),
+                &owner_token,
+            )
+            .header("content-type", "application/json")
+            .body(Body::from(r#"{"expires_in": "1d"}"#))
+            .unwrap(),
+        )
+        .await;
+    let code = body_to_json(resp).await["code"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    let resp = app
+        .oneshot(
+            authed(
+                Method::POST,
+                &format!("/api/invites/{code}/join"),
+                &member_token,
+            )
+            .body(Body::empty())
+            .unwrap(),
+        )
+        .await;
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+
+    let unban = || {
+        authed(
+            Method::DELETE,
+            &format!("/api/guilds/{guild_id}/bans/{member_id}"),
+            &owner_token,
+        )
+        .body(Body::empty())
+        .unwrap()
+    };
+    assert_eq!(app.oneshot(unban()).await.status(), StatusCode::NO_CONTENT);
+    assert_eq!(app.oneshot(unban()).await.status(), StatusCode::NOT_FOUND);
+
+    let audited: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM guild_audit_log WHERE guild_id = $1 AND action IN ('guild.members.banned', 'guild.members.unbanned')",
+    )
+    .bind(guild_id)
+    .fetch_one(&app.pool)
+    .await
+    .unwrap();
+    assert_eq!(audited, 2);
+}
+
+#[tokio::test]
+async fn test_kick_respects_permissions_and_hierarchy() {
+    let app = TestApp::new().await;
+    let (owner_id, _) = create_test_user(&app.pool).await;
+    let (mod_id, _) = create_test_user(&app.pool).await;
+    let (member_id, _) = create_test_user(&app.pool).await;
+    let guild_id = create_guild(&app.pool, owner_id).await;
+    let mut guard = app.cleanup_guard();
+    guard.add(move |pool| async move {
+        delete_guild(&pool, guild_id).await;
+        delete_user(&pool, owner_id).await;
+        delete_user(&pool, mod_id).await;
+        delete_user(&pool, member_id).await;
+    });
+    add_guild_member(&app.pool, guild_id, mod_id).await;
+    add_guild_member(&app.pool, guild_id, member_id).await;
+    let mod_token = generate_access_token(&app.config, mod_id);
+
+    let kick = |target: Uuid| {
+        authed(
+            Method::DELETE,
+            &format!("/api/guilds/{guild_id}/members/{target}"),
+            &mod_token,
+        )
+        .body(Body::empty())
+        .unwrap()
+    };
+
+    // No KICK_MEMBERS yet
+    assert_eq!(
+        app.oneshot(kick(member_id)).await.status(),
+        StatusCode::FORBIDDEN
+    );
+
+    grant_role(&app, guild_id, mod_id, GuildPermissions::KICK_MEMBERS).await;
+
+    // The owner can never be kicked
+    assert_eq!(
+        app.oneshot(kick(owner_id)).await.status(),
+        StatusCode::FORBIDDEN
+    );
+
+    assert_eq!(
+        app.oneshot(kick(member_id)).await.status(),
+        StatusCode::NO_CONTENT
+    );
+    let is_member: bool = sqlx::query_scalar(
+        "SELECT EXISTS(SELECT 1 FROM guild_members WHERE guild_id = $1 AND user_id = $2)",
+    )
+    .bind(guild_id)
+    .bind(member_id)
+    .fetch_one(&app.pool)
+    .await
+    .unwrap();
+    assert!(!is_member);
+}
@@ -0,0 +1,338 @@
+//! Integration tests for discovery: trending and recommended guilds, and the
+//! admin review queue.
+
+use axum::body::Body;
+use axum::http::{Method, StatusCode};
+use chrono::Utc;
+use serde_json::Value;
+use uuid::Uuid;
+use vc_server::discovery::trending;
+
+use super::helpers::{
+    add_guild_member, authed, body_to_json, create_channel, create_elevated_session, create_guild,
+    create_test_user, delete_guild, delete_user, generate_access_token, insert_message,
+    json_request, make_admin, TestApp,
+};
+
+async fn make_discoverable(app: &TestApp, guild_id: Uuid, tag: &str) {
+    sqlx::query("UPDATE guilds SET discoverable = true, discovery_status = 'approved', tags = ARRAY[$2] WHERE id = $1")
+        .bind(guild_id)
+        .bind(tag)
+        .execute(&app.pool)
+        .await
+        .unwrap();
+}
+
+fn guild_ids(body: &Value) -> Vec<Uuid> {
+    body["guilds"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|g| g["id"].as_str().unwrap().parse().unwrap())
+        .collect()
+}
+
+#[tokio::test]
+async fn test_trending_sort_and_recommendations() {
+    let app = TestApp::new().await;
+    let (owner_id, _) = create_test_user(&app.pool).await;
+    let (viewer_id, _) = create_test_user(&app.pool).await;
+    let viewer_token = generate_access_token(&app.config, viewer_id);
+    let mut members = Vec::new();
+    for _ in 0..5 {
+        members.push(create_test_user(&app.pool).await.0);
+    }
+    let quiet_id = create_guild(&app.pool, owner_id).await;
+    let busy_id = create_guild(&app.pool, owner_id).await;
+    let banned_id = create_guild(&app.pool, owner_id).await;
+    let home_id = create_guild(&app.pool, owner_id).await;
+    let cleanup_members = members.clone();
+    let mut guard = app.cleanup_guard();
+    guard.add(move |pool| async move {
+        for guild_id in [quiet_id, busy_id, banned_id, home_id] {
+            delete_guild(&pool, guild_id).await;
+        }
+        for user_id in cleanup_members {
+            delete_user(&pool, user_id).await;
+        }
+        delete_user(&pool, viewer_id).await;
+        delete_user(&pool, owner_id).await;
+    });
+
+    let tag = format!("t{}", &Uuid::new_v4().simple().to_string()[..12]);
+    for guild_id in [quiet_id, busy_id, banned_id] {
+        make_discoverable(&app, guild_id, &tag).await;
+    }
+    sqlx::query("UPDATE guilds SET tags = ARRAY[$2] WHERE id = $1")
+        .bind(home_id)
+        .bind(&tag)
+        .execute(&app.pool)
+        .await
+        .unwrap();
+    add_guild_member(&app.pool, home_id, viewer_id).await;
+    sqlx::query(
+        "INSERT INTO guild_bans (guild_id, user_id, banned_by, reason) VALUES ($1, $2, $3, 'spam')",
+    )
+    .bind(banned_id)
+    .bind(viewer_id)
+    .bind(owner_id)
+    .execute(&app.pool)
+    .await
+    .unwrap();
+
+    // The quiet guild is bigger but nobody joined or posted this week.
+    for &user_id in &members {
+        add_guild_member(&app.pool, quiet_id, user_id).await;
+    }
+    sqlx::query(
+        "UPDATE guild_members SET joined_at = NOW() - INTERVAL '30 days' WHERE guild_id = $1",
+    )
+    .bind(quiet_id)
+    .execute(&app.pool)
+    .await
+    .unwrap();
+
+    // The busy guild gained members and is chatting.
+    let channel_id = create_channel(&app.pool, busy_id, "general").await;
+    for &user_id in &members[..3] {
+        add_guild_member(&app.pool, busy_id, user_id).await;
+        insert_message(&app.pool, channel_id, user_id, "hello").await;
+    }
+
+    let scored = trending::refresh_activity_stats(&app.pool, Utc::now().date_naive())
+        .await
+        .unwrap();
+    assert!(scored >= 3);
+    let (growth, messages): (i32, i64) = sqlx::query_as(
+        "SELECT member_growth_7d, messages_7d FROM guild_activity_stats WHERE guild_id = $1",
+    )
+    .bind(busy_id)
+    .fetch_one(&app.pool)
+    .await
+    .unwrap();
+    assert_eq!((growth, messages), (4, 3));
+    assert_eq!(
+        trending::refresh_if_stale(&app.pool, Utc::now().date_naive())
+            .await
+            .unwrap(),
+        None
+    );
+
+    let browse = |sort: &'static str| {
+        TestApp::request(
+            Method::GET,
+            &format!("/api/discover/guilds?sort={sort}&tags={tag}"),
+        )
+        .body(Body::empty())
+        .unwrap()
+    };
+
+    let resp = app.oneshot(browse("members")).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(guild_ids(&body_to_json(resp).await)[0], quiet_id);
+
+    let resp = app.oneshot(browse("trending")).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let body = body_to_json(resp).await;
+    assert_eq!(body["total"], 3);
+    assert_eq!(guild_ids(&body)[0], busy_id);
+
+    // Recommendations share the tag of the viewer's guild, skip guilds they
+    // are in or banned from, and rank by trending score.
+    let resp = app
+        .oneshot(
+            TestApp::request(Method::GET, "/api/discover/guilds/recommended?limit=25")
+                .header("authorization", format!("Bearer {viewer_token}"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let ids: Vec<Uuid> = guild_ids(&body_to_json(resp).await)
+        .into_iter()
+        .filter(|id| [quiet_id, busy_id, banned_id, home_id].contains(id))
+        .collect();
+    assert_eq!(ids, vec![busy_id, quiet_id]);
+
+    let resp = app
+        .oneshot(
+            TestApp::request(Method::GET, "/api/discover/guilds/recommended")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+    assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+}
+
+/// Number of listed guilds with `tag`.
+async fn listed(app: &TestApp, tag: &str) -> i64 {
+    let resp = app
+        .oneshot(
+            TestApp::request(Method::GET, &format!("/api/discover/guilds?tags={tag}"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+    body_to_json(resp).await["total"].as_i64().unwrap()
+}
+
+/// Mark a guild discoverable through its settings; returns the settings.
+async fn submit(app: &TestApp, token: &str, guild_id: Uuid, tag: &str) -> Value {
+    let resp = app
+        .oneshot(json_request(
+            Method::PATCH,
+            &format!("/api/guilds/{guild_id}/settings"),
+            token,
+            serde_json::json!({ "discoverable": true, "tags": [tag] }),
+        ))
+        .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    body_to_json(resp).await
+}
+
+/// The guild's entry in the admin review queue, if queued.
+async fn queue_entry(app: &TestApp, token: &str, guild_id: Uuid) -> Option<Value> {
+    let resp = app
+        .oneshot(
+            authed(Method::GET, "/api/admin/discovery/queue?limit=100", token)
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    body_to_json(resp).await["items"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|item| item["guild_id"] == guild_id.to_string())
+        .cloned()
+}
+
+#[tokio::test]
+async fn test_discovery_review_queue() {
+    let mut config = vc_server::config::Config::default_for_test();
+    config.discovery_report_threshold = 2;
+    let app = TestApp::with_config(config).await;
+    let (owner_id, _) = create_test_user(&app.pool).await;
+    let (admin_id, _) = create_test_user(&app.pool).await;
+    let (reporter1_id, _) = create_test_user(&app.pool).await;
+    let (reporter2_id, _) = create_test_user(&app.pool).await;
+    make_admin(&app.pool, admin_id).await;
+    create_elevated_session(&app.pool, admin_id).await;
+    let owner_token = generate_access_token(&app.config, owner_id);
+    let admin_token = generate_access_token(&app.config, admin_id);
+    let guild_id = create_guild(&app.pool, owner_id).await;
+    let mut guard = app.cleanup_guard();
+    guard.add(move |pool| async move {
+        delete_guild(&pool, guild_id).await;
+        for user_id in [owner_id, admin_id, reporter1_id, reporter2_id] {
+            delete_user(&pool, user_id).await;
+        }
+    });
+
+    let tag = format!("t{}", &Uuid::new_v4().simple().to_string()[..12]);
+    let review = |action: &'static str, body: Value| {
+        json_request(
+            Method::POST,
+            &format!("/api/admin/discovery/queue/{guild_id}/{action}"),
+            &admin_token,
+            body,
+        )
+    };
+
+    // Marking the guild discoverable queues it instead of listing it.
+    let settings = submit(&app, &owner_token, guild_id, &tag).await;
+    assert_eq!(settings["discovery_status"], "pending");
+    assert_eq!(listed(&app, &tag).await, 0);
+    assert_eq!(
+        queue_entry(&app, &admin_token, guild_id).await.unwrap()["status"],
+        "pending"
+    );
+
+    // Rejection needs a reason, which the owner sees in the settings.
+    let resp = app
+        .oneshot(review("reject", serde_json::json!({ "reason": " " })))
+        .await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    let resp = app
+        .oneshot(review(
+            "reject",
+            serde_json::json!({ "reason": "Add a description" }),
+        ))
+        .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(body_to_json(resp).await["status"], "rejected");
+    assert!(queue_entry(&app, &admin_token, guild_id).await.is_none());
+    let resp = app
+        .oneshot(
+            authed(
+                Method::GET,
+                &format!("/api/guilds/{guild_id}/settings"),
+                &owner_token,
+            )
+            .body(Body::empty())
+            .unwrap(),
+        )
+        .await;
+    let settings = body_to_json(resp).await;
+    assert_eq!(settings["discovery_status"], "rejected");
+    assert_eq!(settings["discovery_rejection_reason"], "Add a description");
+
+    // Resubmitting clears the reason; approval lists the guild.
+    let settings = submit(&app, &owner_token, guild_id, &tag).await;
+    assert_eq!(settings["discovery_status"], "pending");
+    assert!(settings["discovery_rejection_reason"].is_null());
+    let resp = app.oneshot(review("approve", serde_json::json!({}))).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(listed(&app, &tag).await, 1);
+    let resp = app.oneshot(review("approve", serde_json::json!({}))).await;
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+
+    // Saving the settings again keeps an approved guild listed.
+    assert_eq!(
+        submit(&app, &owner_token, guild_id, &tag).await["discovery_status"],
+        "approved"
+    );
+
+    // Enough reports delist it and put it back in the queue.
+    for reporter_id in [reporter1_id, reporter1_id, reporter2_id] {
+        let token = generate_access_token(&app.config, reporter_id);
+        let resp = app
+            .oneshot(json_request(
+                Method::POST,
+                &format!("/api/discover/guilds/{guild_id}/report"),
+                &token,
+                serde_json::json!({ "reason": "Scam links" }),
+            ))
+            .await;
+        assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+        // Reporting twice counts once.
+        if reporter_id == reporter1_id {
+            assert_eq!(listed(&app, &tag).await, 1);
+        }
+    }
+    assert_eq!(listed(&app, &tag).await, 0);
+    let entry = queue_entry(&app, &admin_token, guild_id).await.unwrap();
+    assert_eq!(entry["status"], "delisted");
+    assert_eq!(entry["report_count"], 2);
+
+    // Delisted guilds cannot be reported or joined.
+    let token = generate_access_token(&app.config, reporter1_id);
+    let resp = app
+        .oneshot(
+            authed(
+                Method::POST,
+                &format!("/api/discover/guilds/{guild_id}/join"),
+                &token,
+            )
+            .body(Body::empty())
+            .unwrap(),
+        )
+        .await;
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+
+    // Re-approval starts a fresh report count.
+    let resp = app.oneshot(review("approve", serde_json::json!({}))).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(listed(&app, &tag).await, 1);
+}
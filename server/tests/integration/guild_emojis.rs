@@ -0,0 +1,146 @@
+//! HTTP integration tests for custom emoji use in messages and emoji search.
+//!
+//! Run with: `cargo test --test integration guild_emojis -- --nocapture`
+
+use axum::body::Body;
+use axum::http::{Method, StatusCode};
+use uuid::Uuid;
+use vc_server::permissions::GuildPermissions;
+
+use super::helpers::{
+    body_to_json, create_channel, create_guild_with_default_role, create_test_user, delete_guild,
+    delete_user, generate_access_token, TestApp,
+};
+
+async fn insert_emoji(app: &TestApp, guild_id: Uuid, user_id: Uuid, name: &str) -> Uuid {
+    let emoji_id = Uuid::now_v7();
+    sqlx::query(
+        "INSERT INTO guild_emojis (id, guild_id, name, image_url, animated, uploaded_by)
+         VALUES ($1, $2, $3, $4, FALSE, $5)",
+    )
+    .bind(emoji_id)
+    .bind(guild_id)
+    .bind(name)
+    .bind(format!("/api/guilds/{guild_id}/emojis/{emoji_id}/image"))
+    .bind(user_id)
+    .execute(&app.pool)
+    .await
+    .expect("Failed to insert emoji");
+    emoji_id
+}
+
+fn get(path: &str, token: &str) -> axum::http::Request<Body> {
+    TestApp::request(Method::GET, path)
+        .header("authorization", format!("Bearer {token}"))
+        .body(Body::empty())
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_message_resolves_guild_emojis() {
+    let app = TestApp::new().await;
+    let (user_id, _) = create_test_user(&app.pool).await;
+    let perms = GuildPermissions::VIEW_CHANNEL | GuildPermissions::SEND_MESSAGES;
+    let guild_id = create_guild_with_default_role(&app.pool, user_id, perms).await;
+    let other_guild_id = create_guild_with_default_role(&app.pool, user_id, perms).await;
+    let mut guard = app.cleanup_guard();
+    guard.add(move |pool| async move {
+        delete_guild(&pool, guild_id).await;
+        delete_guild(&pool, other_guild_id).await;
+        delete_user(&pool, user_id).await;
+    });
+    let channel_id = create_channel(&app.pool, guild_id, "emoji-chat").await;
+    let token = generate_access_token(&app.config, user_id);
+
+    let party_id = insert_emoji(&app, guild_id, user_id, "party").await;
+    // Another guild's emoji cannot be used here
+    insert_emoji(&app, other_guild_id, user_id, "elsewhere").await;
+
+    let resp = app
+        .oneshot(
+            TestApp::request(Method::POST, &format!("/api/messages/channel/{channel_id}"))
+                .header("authorization", format!("Bearer {token}"))
+                .header("content-type", "application/json")
+                .body(Body::from(
+                    serde_json::json!({ "content": ":party: :elsewhere: :unknown: :party:" })
+                        .to_string(),
+                ))
+                .unwrap(),
+        )
+        .await;
+    assert_eq!(resp.status(), StatusCode::CREATED);
+    let message = body_to_json(resp).await;
+    assert_eq!(message["content"], ":party: :elsewhere: :unknown: :party:");
+    let emojis = message["emojis"].as_array().unwrap();
+    assert_eq!(emojis.len(), 1);
+    assert_eq!(emojis[0]["id"], party_id.to_string());
+    assert_eq!(emojis[0]["name"], "party");
+    assert_eq!(emojis[0]["animated"], false);
+    assert_eq!(
+        emojis[0]["url"],
+        format!("/api/guilds/{guild_id}/emojis/{party_id}/image")
+    );
+
+    // The listing resolves the same emojis
+    let resp = app
+        .oneshot(get(&format!("/api/messages/channel/{channel_id}"), &token))
+        .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let body = body_to_json(resp).await;
+    let listed = &body["items"][0];
+    assert_eq!(listed["id"], message["id"]);
+    assert_eq!(listed["emojis"], message["emojis"]);
+}
+
+#[tokio::test]
+async fn test_emoji_search_prefix() {
+    let app = TestApp::new().await;
+    let (owner_id, _) = create_test_user(&app.pool).await;
+    let (outsider_id, _) = create_test_user(&app.pool).await;
+    let guild_id =
+        create_guild_with_default_role(&app.pool, owner_id, GuildPermissions::VIEW_CHANNEL).await;
+    let mut guard = app.cleanup_guard();
+    guard.add(move |pool| async move {
+        delete_guild(&pool, guild_id).await;
+        delete_user(&pool, owner_id).await;
+        delete_user(&pool, outsider_id).await;
+    });
+    let token = generate_access_token(&app.config, owner_id);
+
+    for name in ["cat_wave", "cat", "catjam", "dog", "c_a_t"] {
+        insert_emoji(&app, guild_id, owner_id, name).await;
+    }
+
+    let search = |q: &str| {
+        get(
+            &format!("/api/guilds/{guild_id}/emojis/search?q={q}"),
+            &token,
+        )
+    };
+
+    let resp = app.oneshot(search("CAT")).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let names: Vec<String> = body_to_json(resp)
+        .await
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|e| e["name"].as_str().unwrap().to_string())
+        .collect();
+    assert_eq!(names, ["cat", "catjam", "cat_wave"]);
+
+    // `_` is matched literally, and a leading `:` is ignored
+    let resp = app.oneshot(search(":c_")).await;
+    let body = body_to_json(resp).await;
+    let results = body.as_array().unwrap();
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0]["name"], "c_a_t");
+
+    let resp = app
+        .oneshot(get(
+            &format!("/api/guilds/{guild_id}/emojis/search?q=cat"),
+            &generate_access_token(&app.config, outsider_id),
+        ))
+        .await;
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+}
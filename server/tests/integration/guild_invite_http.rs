@@ -0,0 +1,129 @@
+//! HTTP integration tests for limited-use guild invites.
+//!
+//! Run with: `cargo test --test integration guild_invite_http -- --nocapture`
+
+use axum::body::Body;
+use axum::http::{Method, StatusCode};
+use uuid::Uuid;
+
+use super::helpers::{
+    body_to_json, create_guild, create_test_user, delete_guild, delete_user, generate_access_token,
+    TestApp,
+};
+
+async fn join(app: &TestApp, token: &str, code: &str) -> axum::response::Response {
+    app.oneshot(
+        TestApp::request(Method::POST, &format!("/api/invites/{code}/join"))
+            .header("authorization", format!("Bearer {token}"))
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await
+}
+
+async fn preview(app: &TestApp, code: &str) -> axum::response::Response {
+    app.oneshot(
+        TestApp::request(Method::GET, &format!("/api/invites/{code}"))
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await
+}
+
+#[tokio::test]
+async fn test_limited_use_invite_lifecycle() {
+    let app = TestApp::new().await;
+    let (owner_id, _) = create_test_user(&app.pool).await;
+    let (first_id, _) = create_test_user(&app.pool).await;
+    let (second_id, _) = create_test_user(&app.pool).await;
+    let guild_id = create_guild(&app.pool, owner_id).await;
+    let mut guard = app.cleanup_guard();
+    guard.add(move |pool| async move {
+        delete_guild(&pool, guild_id).await;
+        delete_user(&pool, owner_id).await;
+        delete_user(&pool, first_id).await;
+        delete_user(&pool, second_id).await;
+    });
+    let owner_token = generate_access_token(&app.config, owner_id);
+
+    let resp = app
+        .oneshot(
+            TestApp::request(Method::POST, &format!("/api/guilds/{guild_id}/invites"))
+                .header("authorization", format!("Bearer {owner_token}"))
+                .header("content-type", "application/json")
+                .body(Body::from(r#"{"expires_in": "1d", "max_uses": 1}"#))
+                .unwrap(),
+        )
+        .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let invite = body_to_json(resp).await;
+    assert_eq!(invite["max_uses"], 1);
+    let code = invite["code"].as_str().unwrap().to_string();
+
+    // Public preview needs no token
+    let resp = preview(&app, &code).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let body = body_to_json(resp).await;
+    assert_eq!(body["guild_id"], guild_id.to_string());
+    assert_eq!(body["member_count"], 1);
+    assert_eq!(body["uses_remaining"], 1);
+
+    let first_token = generate_access_token(&app.config, first_id);
+    let resp = join(&app, &first_token, &code).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(body_to_json(resp).await["use_count"], 1);
+
+    // Used up: second user is rejected and the invite no longer previews
+    let second_token = generate_access_token(&app.config, second_id);
+    let resp = join(&app, &second_token, &code).await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    let resp = preview(&app, &code).await;
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+
+    let is_member: bool = sqlx::query_scalar(
+        "SELECT EXISTS(SELECT 1 FROM guild_members WHERE guild_id = $1 AND user_id = $2)",
+    )
+    .bind(guild_id)
+    .bind(second_id)
+    .fetch_one(&app.pool)
+    .await
+    .unwrap();
+    assert!(!is_member);
+
+    let audited: bool = sqlx::query_scalar(
+        "SELECT EXISTS(SELECT 1 FROM guild_audit_log WHERE action = 'guild.invites.used' AND actor_id = $1 AND guild_id = $2)",
+    )
+    .bind(first_id)
+    .bind(guild_id)
+    .fetch_one(&app.pool)
+    .await
+    .unwrap();
+    assert!(audited);
+}
+
+#[tokio::test]
+async fn test_invite_max_uses_validated() {
+    let app = TestApp::new().await;
+    let (owner_id, _) = create_test_user(&app.pool).await;
+    let guild_id = create_guild(&app.pool, owner_id).await;
+    let mut guard = app.cleanup_guard();
+    guard.add(move |pool| async move {
+        delete_guild(&pool, guild_id).await;
+        delete_user(&pool, owner_id).await;
+    });
+    let token = generate_access_token(&app.config, owner_id);
+
+    let resp = app
+        .oneshot(
+            TestApp::request(Method::POST, &format!("/api/guilds/{guild_id}/invites"))
+                .header("authorization", format!("Bearer {token}"))
+                .header("content-type", "application/json")
+                .body(Body::from(r#"{"expires_in": "1d", "max_uses": 0}"#))
+                .unwrap(),
+        )
+        .await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+    let resp = preview(&app, &Uuid::new_v4().to_string()[..8]).await;
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+}
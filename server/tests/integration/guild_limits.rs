@@ -396,11 +396,13 @@ async fn test_member_limit_on_discovery_join() {
     });
 
     // Make guild discoverable
-    sqlx::query("UPDATE guilds SET discoverable = true WHERE id = $1")
-        .bind(guild_id)
-        .execute(&app.pool)
-        .await
-        .unwrap();
+    sqlx::query(
+        "UPDATE guilds SET discoverable = true, discovery_status = 'approved' WHERE id = $1",
+    )
+    .bind(guild_id)
+    .execute(&app.pool)
+    .await
+    .unwrap();
 
     // Owner is member (1/2). Add user2 (2/2).
     add_guild_member(&app.pool, guild_id, user2_id).await;
@@ -438,11 +440,13 @@ async fn test_globally_banned_user_cannot_join_via_discovery() {
         delete_user(&pool, banned_user_id).await;
     });
 
-    sqlx::query("UPDATE guilds SET discoverable = true WHERE id = $1")
-        .bind(guild_id)
-        .execute(&app.pool)
-        .await
-        .unwrap();
+    sqlx::query(
+        "UPDATE guilds SET discoverable = true, discovery_status = 'approved' WHERE id = $1",
+    )
+    .bind(guild_id)
+    .execute(&app.pool)
+    .await
+    .unwrap();
 
     sqlx::query("INSERT INTO global_bans (user_id, banned_by, reason) VALUES ($1, $2, $3)")
         .bind(banned_user_id)
@@ -0,0 +1,143 @@
+//! HTTP integration tests for guild moderation log streams.
+//!
+//! Run with: `cargo test --test integration guild_log_stream -- --nocapture`
+
+use axum::body::Body;
+use axum::http::{Method, StatusCode};
+use vc_server::config::Config;
+use vc_server::permissions::GuildPermissions;
+
+use super::helpers::{
+    add_guild_member, body_to_json, create_guild_with_default_role, create_test_user, delete_guild,
+    delete_user, generate_access_token, TestApp,
+};
+
+async fn log_stream_test_app() -> TestApp {
+    let mut config = Config::default_for_test();
+    config.mfa_encryption_key =
+        Some("000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f".to_string());
+    TestApp::with_config(config).await
+}
+
+fn request(
+    method: Method,
+    uri: &str,
+    token: &str,
+    body: Option<serde_json::Value>,
+) -> axum::http::Request<Body> {
+    let builder = TestApp::request(method, uri)
+        .header("authorization", format!("Bearer {token}"))
+        .header("content-type", "application/json");
+    match body {
+        Some(body) => builder.body(Body::from(body.to_string())).unwrap(),
+        None => builder.body(Body::empty()).unwrap(),
+    }
+}
+
+#[tokio::test]
+async fn test_log_stream_lifecycle() {
+    let app = log_stream_test_app().await;
+    let (owner_id, _) = create_test_user(&app.pool).await;
+    let (member_id, _) = create_test_user(&app.pool).await;
+    let guild_id =
+        create_guild_with_default_role(&app.pool, owner_id, GuildPermissions::VIEW_CHANNEL).await;
+    add_guild_member(&app.pool, guild_id, member_id).await;
+    let mut guard = app.cleanup_guard();
+    guard.add(move |pool| async move {
+        delete_guild(&pool, guild_id).await;
+        delete_user(&pool, owner_id).await;
+        delete_user(&pool, member_id).await;
+    });
+    let owner_token = generate_access_token(&app.config, owner_id);
+    let member_token = generate_access_token(&app.config, member_id);
+    let uri = format!("/api/guilds/{guild_id}/log-stream");
+    let config =
+        |url: &str, rotate: bool| Some(serde_json::json!({ "url": url, "rotate_secret": rotate }));
+
+    // Plain members lack MANAGE_GUILD
+    let resp = app
+        .oneshot(request(
+            Method::PUT,
+            &uri,
+            &member_token,
+            config("https://example.com/log", false),
+        ))
+        .await;
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+
+    // Private addresses are rejected
+    let resp = app
+        .oneshot(request(
+            Method::PUT,
+            &uri,
+            &owner_token,
+            config("http://127.0.0.1/log", false),
+        ))
+        .await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+    // Creating reveals the secret once
+    let resp = app
+        .oneshot(request(
+            Method::PUT,
+            &uri,
+            &owner_token,
+            config("https://example.com/log", false),
+        ))
+        .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let created = body_to_json(resp).await;
+    let secret = created["signing_secret"].as_str().unwrap().to_string();
+    assert_eq!(secret.len(), 64);
+    assert_eq!(created["active"], true);
+
+    let resp = app
+        .oneshot(request(Method::GET, &uri, &owner_token, None))
+        .await;
+    let stream = body_to_json(resp).await;
+    assert_eq!(stream["url"], "https://example.com/log");
+    assert!(stream.get("signing_secret").is_none());
+
+    // Updating keeps the secret unless it is rotated
+    let resp = app
+        .oneshot(request(
+            Method::PUT,
+            &uri,
+            &owner_token,
+            config("https://example.com/v2", false),
+        ))
+        .await;
+    let updated = body_to_json(resp).await;
+    assert_eq!(updated["url"], "https://example.com/v2");
+    assert!(updated.get("signing_secret").is_none());
+
+    let resp = app
+        .oneshot(request(
+            Method::PUT,
+            &uri,
+            &owner_token,
+            config("https://example.com/v2", true),
+        ))
+        .await;
+    let rotated = body_to_json(resp).await;
+    assert_ne!(rotated["signing_secret"].as_str().unwrap(), secret);
+
+    // Configuration changes are audited, and therefore streamed too
+    let audited: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM guild_audit_log WHERE guild_id = $1 AND action = 'guild.log_stream.updated'",
+    )
+    .bind(guild_id)
+    .fetch_one(&app.pool)
+    .await
+    .unwrap();
+    assert_eq!(audited, 3);
+
+    let resp = app
+        .oneshot(request(Method::DELETE, &uri, &owner_token, None))
+        .await;
+    assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+    let resp = app
+        .oneshot(request(Method::GET, &uri, &owner_token, None))
+        .await;
+    assert!(body_to_json(resp).await.is_null());
+}
@@ -0,0 +1,146 @@
+//! HTTP integration tests for CSV member role imports.
+//!
+//! Run with: `cargo test --test integration guild_member_import -- --nocapture`
+
+use std::time::Duration;
+
+use axum::body::Body;
+use axum::http::{Method, StatusCode};
+use http_body_util::BodyExt;
+use uuid::Uuid;
+use vc_server::permissions::GuildPermissions;
+
+use super::helpers::{
+    add_guild_member, authed, body_to_json, create_guild_with_default_role, create_test_user,
+    delete_guild, delete_user, generate_access_token, TestApp,
+};
+
+#[tokio::test]
+async fn test_csv_import_assigns_roles_and_reports_rows() {
+    let app = TestApp::new().await;
+    let (owner_id, _) = create_test_user(&app.pool).await;
+    let (alice_id, alice_name) = create_test_user(&app.pool).await;
+    let (bob_id, _) = create_test_user(&app.pool).await;
+    let guild_id =
+        create_guild_with_default_role(&app.pool, owner_id, GuildPermissions::empty()).await;
+    let mut guard = app.cleanup_guard();
+    guard.add(move |pool| async move {
+        delete_guild(&pool, guild_id).await;
+        delete_user(&pool, owner_id).await;
+        delete_user(&pool, alice_id).await;
+        delete_user(&pool, bob_id).await;
+    });
+    add_guild_member(&app.pool, guild_id, alice_id).await;
+    add_guild_member(&app.pool, guild_id, bob_id).await;
+    let bob_email = format!("bob-{bob_id}@example.com");
+    sqlx::query("UPDATE users SET email = $1 WHERE id = $2")
+        .bind(&bob_email)
+        .bind(bob_id)
+        .execute(&app.pool)
+        .await
+        .unwrap();
+    let role_id = Uuid::now_v7();
+    sqlx::query(
+        "INSERT INTO guild_roles (id, guild_id, name, permissions, position) VALUES ($1, $2, 'Helper', 0, 100)",
+    )
+    .bind(role_id)
+    .bind(guild_id)
+    .execute(&app.pool)
+    .await
+    .unwrap();
+    let owner_token = generate_access_token(&app.config, owner_id);
+    let alice_token = generate_access_token(&app.config, alice_id);
+
+    let csv = format!(
+        "user,role,action\n{alice_name},helper\n{},Helper,add\nnobody_here,Helper\n{alice_name},Missing\n",
+        bob_email.to_uppercase()
+    );
+    let import = |token: &str, body: String| {
+        authed(
+            Method::POST,
+            &format!("/api/guilds/{guild_id}/members/import"),
+            token,
+        )
+        .header("content-type", "text/csv")
+        .body(Body::from(body))
+        .unwrap()
+    };
+
+    assert_eq!(
+        app.oneshot(import(&alice_token, csv.clone()))
+            .await
+            .status(),
+        StatusCode::FORBIDDEN
+    );
+    assert_eq!(
+        app.oneshot(import(&owner_token, "user,role\n".to_string()))
+            .await
+            .status(),
+        StatusCode::BAD_REQUEST
+    );
+
+    let resp = app.oneshot(import(&owner_token, csv)).await;
+    assert_eq!(resp.status(), StatusCode::ACCEPTED);
+    let job = body_to_json(resp).await;
+    let job_id = job["id"].as_str().unwrap().to_string();
+    assert_eq!(job["total_rows"], 4);
+
+    let mut job = job;
+    for _ in 0..50 {
+        let resp = app
+            .oneshot(
+                authed(
+                    Method::GET,
+                    &format!("/api/guilds/{guild_id}/members/import/{job_id}"),
+                    &owner_token,
+                )
+                .body(Body::empty())
+                .unwrap(),
+            )
+            .await;
+        job = body_to_json(resp).await;
+        if job["status"] == "completed" || job["status"] == "failed" {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    assert_eq!(job["status"], "completed");
+    assert_eq!(job["succeeded_rows"], 2);
+    assert_eq!(job["failed_rows"], 2);
+
+    let assigned: Vec<Uuid> = sqlx::query_scalar(
+        "SELECT user_id FROM guild_member_roles WHERE role_id = $1 ORDER BY user_id",
+    )
+    .bind(role_id)
+    .fetch_all(&app.pool)
+    .await
+    .unwrap();
+    let mut expected = vec![alice_id, bob_id];
+    expected.sort();
+    assert_eq!(assigned, expected);
+
+    let resp = app
+        .oneshot(
+            authed(
+                Method::GET,
+                &format!("/api/guilds/{guild_id}/members/import/{job_id}/report"),
+                &owner_token,
+            )
+            .body(Body::empty())
+            .unwrap(),
+        )
+        .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(
+        resp.headers().get("content-type").unwrap(),
+        "text/csv; charset=utf-8"
+    );
+    let bytes = resp.into_body().collect().await.unwrap().to_bytes();
+    let report = String::from_utf8(bytes.to_vec()).unwrap();
+    let lines: Vec<&str> = report.lines().collect();
+    assert_eq!(lines[0], "line,user,role,action,status,detail");
+    assert_eq!(lines[1], format!("2,{alice_name},helper,,added,"));
+    assert!(lines[2].ends_with(",Helper,add,added,"));
+    assert!(lines[3].starts_with("4,nobody_here,Helper,,error,"));
+    assert!(lines[4].ends_with(",error,Role not found"));
+}
@@ -0,0 +1,124 @@
+//! HTTP integration tests for member list filters and pagination.
+//!
+//! Run with: `cargo test --test integration guild_member_list -- --nocapture`
+
+use axum::body::Body;
+use axum::http::{Method, StatusCode};
+use uuid::Uuid;
+
+use super::helpers::{
+    add_guild_member, body_to_json, create_guild, create_test_user, delete_guild, delete_user,
+    generate_access_token, TestApp,
+};
+
+async fn list_members(app: &TestApp, token: &str, guild_id: Uuid, query: &str) -> Vec<Uuid> {
+    let resp = app
+        .oneshot(
+            TestApp::request(
+                Method::GET,
+                &format!("/api/guilds/{guild_id}/members{query}"),
+            )
+            .header("authorization", format!("Bearer {token}"))
+            .body(Body::empty())
+            .unwrap(),
+        )
+        .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    body_to_json(resp)
+        .await
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|m| m["user_id"].as_str().unwrap().parse().unwrap())
+        .collect()
+}
+
+#[tokio::test]
+async fn test_member_list_filters_and_pages() {
+    let app = TestApp::new().await;
+    let (owner_id, _) = create_test_user(&app.pool).await;
+    let (moderator_id, _) = create_test_user(&app.pool).await;
+    let (offline_id, _) = create_test_user(&app.pool).await;
+    let guild_id = create_guild(&app.pool, owner_id).await;
+    let mut guard = app.cleanup_guard();
+    guard.add(move |pool| async move {
+        delete_guild(&pool, guild_id).await;
+        delete_user(&pool, owner_id).await;
+        delete_user(&pool, moderator_id).await;
+        delete_user(&pool, offline_id).await;
+    });
+    add_guild_member(&app.pool, guild_id, moderator_id).await;
+    add_guild_member(&app.pool, guild_id, offline_id).await;
+    let token = generate_access_token(&app.config, owner_id);
+
+    let role_id = Uuid::now_v7();
+    sqlx::query(
+        "INSERT INTO guild_roles (id, guild_id, name, permissions, position) VALUES ($1, $2, 'Moderators', 0, 1)",
+    )
+    .bind(role_id)
+    .bind(guild_id)
+    .execute(&app.pool)
+    .await
+    .unwrap();
+    sqlx::query("INSERT INTO guild_member_roles (guild_id, user_id, role_id) VALUES ($1, $2, $3)")
+        .bind(guild_id)
+        .bind(moderator_id)
+        .bind(role_id)
+        .execute(&app.pool)
+        .await
+        .unwrap();
+    sqlx::query("UPDATE users SET status = 'online' WHERE id = ANY($1)")
+        .bind(vec![owner_id, moderator_id])
+        .execute(&app.pool)
+        .await
+        .unwrap();
+    sqlx::query("UPDATE users SET status = 'offline' WHERE id = $1")
+        .bind(offline_id)
+        .execute(&app.pool)
+        .await
+        .unwrap();
+
+    // Without parameters everyone is listed, with their roles
+    let resp = app
+        .oneshot(
+            TestApp::request(Method::GET, &format!("/api/guilds/{guild_id}/members"))
+                .header("authorization", format!("Bearer {token}"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let members = body_to_json(resp).await;
+    let members = members.as_array().unwrap();
+    assert_eq!(members.len(), 3);
+    let moderator = members
+        .iter()
+        .find(|m| m["user_id"] == moderator_id.to_string())
+        .unwrap();
+    assert_eq!(moderator["role_ids"], serde_json::json!([role_id]));
+
+    // Role and online filters
+    assert_eq!(
+        list_members(&app, &token, guild_id, &format!("?role_id={role_id}")).await,
+        vec![moderator_id]
+    );
+    let mut online = list_members(&app, &token, guild_id, "?online=true").await;
+    online.sort();
+    let mut expected = vec![owner_id, moderator_id];
+    expected.sort();
+    assert_eq!(online, expected);
+
+    // Cursor pagination walks the list in user ID order
+    let mut all = [owner_id, moderator_id, offline_id];
+    all.sort();
+    let first = list_members(&app, &token, guild_id, "?limit=2").await;
+    assert_eq!(first, all[..2]);
+    let rest = list_members(
+        &app,
+        &token,
+        guild_id,
+        &format!("?limit=2&after={}", first[1]),
+    )
+    .await;
+    assert_eq!(rest, all[2..]);
+}
@@ -0,0 +1,173 @@
+//! HTTP integration tests for guild nicknames and guild avatars.
+//!
+//! Run with: `cargo test --test integration guild_member_profiles -- --nocapture`
+
+use axum::body::Body;
+use axum::http::{Method, StatusCode};
+use uuid::Uuid;
+
+use super::helpers::{
+    add_guild_member, authed, body_to_json, create_channel, create_guild, create_test_user,
+    delete_guild, delete_user, generate_access_token, TestApp,
+};
+
+async fn update_member(
+    app: &TestApp,
+    token: &str,
+    guild_id: Uuid,
+    user_id: Uuid,
+    body: serde_json::Value,
+) -> axum::response::Response {
+    app.oneshot(
+        authed(
+            Method::PATCH,
+            &format!("/api/guilds/{guild_id}/members/{user_id}"),
+            token,
+        )
+        .header("content-type", "application/json")
+        .body(Body::from(body.to_string()))
+        .unwrap(),
+    )
+    .await
+}
+
+#[tokio::test]
+async fn test_nicknames_are_gated_and_shown_on_messages() {
+    let app = TestApp::new().await;
+    let (owner_id, _) = create_test_user(&app.pool).await;
+    let (member_id, _) = create_test_user(&app.pool).await;
+    let guild_id = create_guild(&app.pool, owner_id).await;
+    let mut guard = app.cleanup_guard();
+    guard.add(move |pool| async move {
+        delete_guild(&pool, guild_id).await;
+        delete_user(&pool, owner_id).await;
+        delete_user(&pool, member_id).await;
+    });
+    add_guild_member(&app.pool, guild_id, member_id).await;
+    let channel_id = create_channel(&app.pool, guild_id, "general").await;
+    let owner_token = generate_access_token(&app.config, owner_id);
+    let member_token = generate_access_token(&app.config, member_id);
+
+    // Members set their own nickname
+    let resp = update_member(
+        &app,
+        &member_token,
+        guild_id,
+        member_id,
+        serde_json::json!({ "nickname": "  Captain  " }),
+    )
+    .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(body_to_json(resp).await["nickname"], "Captain");
+
+    // ...but need MANAGE_NICKNAMES for anyone else's
+    let resp = update_member(
+        &app,
+        &member_token,
+        guild_id,
+        owner_id,
+        serde_json::json!({ "nickname": "Boss" }),
+    )
+    .await;
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+
+    // Invalid nicknames and avatar URLs are rejected
+    let resp = update_member(
+        &app,
+        &owner_token,
+        guild_id,
+        member_id,
+        serde_json::json!({ "nickname": "x".repeat(33) }),
+    )
+    .await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    let resp = update_member(
+        &app,
+        &owner_token,
+        guild_id,
+        member_id,
+        serde_json::json!({ "guild_avatar_url": "https://example.com/a.png" }),
+    )
+    .await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+    // Message authorship carries the guild identity
+    sqlx::query(
+        "UPDATE guild_members SET avatar_url = 'https://cdn.example/guild.webp' \
+         WHERE guild_id = $1 AND user_id = $2",
+    )
+    .bind(guild_id)
+    .bind(member_id)
+    .execute(&app.pool)
+    .await
+    .unwrap();
+
+    let resp = app
+        .oneshot(
+            authed(
+                Method::POST,
+                &format!("/api/messages/channel/{channel_id}"),
+                &member_token,
+            )
+            .header("content-type", "application/json")
+            .body(Body::from(r#"{"content": "hello"}"#))
+            .unwrap(),
+        )
+        .await;
+    assert_eq!(resp.status(), StatusCode::CREATED);
+    let message = body_to_json(resp).await;
+    assert_eq!(message["author"]["display_name"], "Captain");
+    assert_eq!(
+        message["author"]["avatar_url"],
+        "https://cdn.example/guild.webp"
+    );
+
+    let resp = app
+        .oneshot(
+            authed(
+                Method::GET,
+                &format!("/api/messages/channel/{channel_id}"),
+                &owner_token,
+            )
+            .body(Body::empty())
+            .unwrap(),
+        )
+        .await;
+    let listed = body_to_json(resp).await;
+    assert_eq!(listed["items"][0]["author"]["display_name"], "Captain");
+
+    // Moderators can clear the nickname and reset the guild avatar
+    let resp = update_member(
+        &app,
+        &owner_token,
+        guild_id,
+        member_id,
+        serde_json::json!({ "nickname": null, "guild_avatar_url": null }),
+    )
+    .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let member = body_to_json(resp).await;
+    assert!(member["nickname"].is_null());
+    assert!(member["guild_avatar_url"].is_null());
+
+    let resp = app
+        .oneshot(
+            authed(
+                Method::GET,
+                &format!("/api/guilds/{guild_id}/members"),
+                &owner_token,
+            )
+            .body(Body::empty())
+            .unwrap(),
+        )
+        .await;
+    let members = body_to_json(resp).await;
+    let member = members
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|m| m["user_id"] == member_id.to_string())
+        .unwrap();
+    assert!(member["nickname"].is_null());
+    assert!(member["guild_avatar_url"].is_null());
+}
@@ -0,0 +1,189 @@
+//! HTTP integration tests for guild onboarding.
+//!
+//! Run with: `cargo test --test integration guild_onboarding -- --nocapture`
+
+use axum::body::Body;
+use axum::http::{Method, StatusCode};
+use uuid::Uuid;
+use vc_server::permissions::GuildPermissions;
+
+use super::helpers::{
+    body_to_json, create_channel, create_guild_with_default_role, create_test_user, delete_guild,
+    delete_user, generate_access_token, TestApp,
+};
+
+async fn send(
+    app: &TestApp,
+    method: Method,
+    uri: &str,
+    token: &str,
+    body: Option<serde_json::Value>,
+) -> axum::response::Response {
+    let request = TestApp::request(method, uri).header("authorization", format!("Bearer {token}"));
+    let request = match body {
+        Some(body) => request
+            .header("content-type", "application/json")
+            .body(Body::from(body.to_string())),
+        None => request.body(Body::empty()),
+    };
+    app.oneshot(request.unwrap()).await
+}
+
+#[tokio::test]
+async fn test_onboarding_gates_new_members_until_completed() {
+    let app = TestApp::new().await;
+    let (owner_id, _) = create_test_user(&app.pool).await;
+    let (joiner_id, _) = create_test_user(&app.pool).await;
+    let guild_id = create_guild_with_default_role(
+        &app.pool,
+        owner_id,
+        GuildPermissions::VIEW_CHANNEL | GuildPermissions::SEND_MESSAGES,
+    )
+    .await;
+    let mut guard = app.cleanup_guard();
+    guard.add(move |pool| async move {
+        delete_guild(&pool, guild_id).await;
+        delete_user(&pool, owner_id).await;
+        delete_user(&pool, joiner_id).await;
+    });
+    let channel_id = create_channel(&app.pool, guild_id, "welcome").await;
+    let owner_token = generate_access_token(&app.config, owner_id);
+    let joiner_token = generate_access_token(&app.config, joiner_id);
+
+    let rules_page_id: Uuid = sqlx::query_scalar(
+        r"INSERT INTO pages (guild_id, title, slug, content, content_hash, requires_acceptance, created_by, updated_by)
+          VALUES ($1, 'Rules', 'rules', 'Be nice', 'hash-v1', true, $2, $2)
+          RETURNING id",
+    )
+    .bind(guild_id)
+    .bind(owner_id)
+    .fetch_one(&app.pool)
+    .await
+    .unwrap();
+    let role_id = Uuid::now_v7();
+    sqlx::query(
+        "INSERT INTO guild_roles (id, guild_id, name, permissions, position) VALUES ($1, $2, 'Member', 0, 5)",
+    )
+    .bind(role_id)
+    .bind(guild_id)
+    .execute(&app.pool)
+    .await
+    .unwrap();
+    let code = Uuid::new_v4().simple().to_string()[..8].to_string();
+    sqlx::query("INSERT INTO guild_invites (guild_id, code, created_by) VALUES ($1, $2, $3)")
+        .bind(guild_id)
+        .bind(&code)
+        .bind(owner_id)
+        .execute(&app.pool)
+        .await
+        .unwrap();
+
+    let onboarding_uri = format!("/api/guilds/{guild_id}/onboarding");
+    let config = serde_json::json!({
+        "enabled": true,
+        "welcome_description": "  Welcome aboard!  ",
+        "welcome_channels": [{ "channel_id": channel_id, "description": "Say hi" }],
+        "rules_page_id": rules_page_id,
+        "default_role_ids": [role_id],
+    });
+
+    // Channels must belong to the guild
+    let mut invalid = config.clone();
+    invalid["welcome_channels"][0]["channel_id"] = serde_json::json!(Uuid::new_v4());
+    let resp = send(
+        &app,
+        Method::PUT,
+        &onboarding_uri,
+        &owner_token,
+        Some(invalid),
+    )
+    .await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+    let resp = send(
+        &app,
+        Method::PUT,
+        &onboarding_uri,
+        &owner_token,
+        Some(config),
+    )
+    .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(
+        body_to_json(resp).await["welcome_description"],
+        "Welcome aboard!"
+    );
+
+    // Non-members cannot read it
+    let resp = send(&app, Method::GET, &onboarding_uri, &joiner_token, None).await;
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+
+    // Joining returns the onboarding and leaves the member pending
+    let resp = send(
+        &app,
+        Method::POST,
+        &format!("/api/invites/{code}/join"),
+        &joiner_token,
+        None,
+    )
+    .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let joined = body_to_json(resp).await;
+    assert_eq!(joined["onboarding"]["enabled"], true);
+    assert_eq!(
+        joined["onboarding"]["rules_page_id"],
+        rules_page_id.to_string()
+    );
+
+    let message_uri = format!("/api/messages/channel/{channel_id}");
+    let message = serde_json::json!({ "content": "Hello!" });
+    let resp = send(
+        &app,
+        Method::POST,
+        &message_uri,
+        &joiner_token,
+        Some(message.clone()),
+    )
+    .await;
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+    assert_eq!(body_to_json(resp).await["error"], "ONBOARDING_PENDING");
+
+    // Completing accepts the rules and assigns the default roles
+    let complete_uri = format!("/api/guilds/{guild_id}/onboarding/complete");
+    let resp = send(&app, Method::POST, &complete_uri, &joiner_token, None).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(
+        body_to_json(resp).await["role_ids"],
+        serde_json::json!([role_id])
+    );
+    let accepted: bool = sqlx::query_scalar(
+        "SELECT EXISTS(SELECT 1 FROM page_acceptances WHERE user_id = $1 AND page_id = $2 AND content_hash = 'hash-v1')",
+    )
+    .bind(joiner_id)
+    .bind(rules_page_id)
+    .fetch_one(&app.pool)
+    .await
+    .unwrap();
+    assert!(accepted);
+
+    let resp = send(
+        &app,
+        Method::POST,
+        &message_uri,
+        &joiner_token,
+        Some(message),
+    )
+    .await;
+    assert_eq!(resp.status(), StatusCode::CREATED);
+
+    // Roles are only handed out once
+    sqlx::query("DELETE FROM guild_member_roles WHERE guild_id = $1 AND user_id = $2")
+        .bind(guild_id)
+        .bind(joiner_id)
+        .execute(&app.pool)
+        .await
+        .unwrap();
+    let resp = send(&app, Method::POST, &complete_uri, &joiner_token, None).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(body_to_json(resp).await["role_ids"], serde_json::json!([]));
+}
@@ -0,0 +1,115 @@
+//! Integration tests for guild supporter perks.
+//!
+//! Run with: `cargo test --test integration guild_perks -- --nocapture`
+
+use axum::body::Body;
+use axum::http::{Method, StatusCode};
+use uuid::Uuid;
+use vc_server::config::Config;
+use vc_server::guild::perks::{set_supporter, SupporterSource};
+
+use super::helpers::{
+    add_guild_member, body_to_json, create_guild, create_test_user, delete_guild, delete_user,
+    generate_access_token, TestApp,
+};
+
+fn perks_config() -> Config {
+    let mut config = Config::default_for_test();
+    config.max_emojis_per_guild = 10;
+    config.perk_tier_thresholds = vec![1, 2];
+    config.perk_emoji_slots_per_tier = 5;
+    config
+}
+
+async fn get_json(app: &TestApp, token: &str, uri: &str) -> serde_json::Value {
+    let resp = app
+        .oneshot(
+            TestApp::request(Method::GET, uri)
+                .header("authorization", format!("Bearer {token}"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    body_to_json(resp).await
+}
+
+#[tokio::test]
+async fn test_perks_follow_supporters() {
+    let app = TestApp::with_config(perks_config()).await;
+    let (owner_id, _) = create_test_user(&app.pool).await;
+    let (member_id, _) = create_test_user(&app.pool).await;
+    let token = generate_access_token(&app.config, owner_id);
+    let guild_id = create_guild(&app.pool, owner_id).await;
+    add_guild_member(&app.pool, guild_id, member_id).await;
+    let mut guard = app.cleanup_guard();
+    guard.add(move |pool| async move {
+        delete_guild(&pool, guild_id).await;
+        delete_user(&pool, owner_id).await;
+        delete_user(&pool, member_id).await;
+    });
+
+    let perks_uri = format!("/api/guilds/{guild_id}/perks");
+    let body = get_json(&app, &token, &perks_uri).await;
+    assert_eq!(body["tier"], 0);
+    assert_eq!(body["emoji_slots"], 10);
+    assert_eq!(body["next_tier_at"], 1);
+
+    for user_id in [owner_id, member_id] {
+        assert!(
+            set_supporter(&app.pool, guild_id, user_id, Some(SupporterSource::Manual))
+                .await
+                .unwrap()
+        );
+    }
+
+    let body = get_json(&app, &token, &perks_uri).await;
+    assert_eq!(body["tier"], 2);
+    assert_eq!(body["supporter_count"], 2);
+    assert_eq!(body["emoji_slots"], 20);
+    assert!(body["next_tier_at"].is_null());
+
+    // The usage endpoint reports the raised emoji limit
+    let usage = get_json(&app, &token, &format!("/api/guilds/{guild_id}/usage")).await;
+    assert_eq!(usage["emojis"]["limit"], 20);
+
+    // Revoking drops the guild back a tier; non-members cannot be flagged
+    assert!(set_supporter(&app.pool, guild_id, member_id, None)
+        .await
+        .unwrap());
+    assert!(!set_supporter(
+        &app.pool,
+        guild_id,
+        Uuid::new_v4(),
+        Some(SupporterSource::Manual)
+    )
+    .await
+    .unwrap());
+    let body = get_json(&app, &token, &perks_uri).await;
+    assert_eq!(body["tier"], 1);
+}
+
+#[tokio::test]
+async fn test_perks_requires_membership() {
+    let app = TestApp::new().await;
+    let (owner_id, _) = create_test_user(&app.pool).await;
+    let (outsider_id, _) = create_test_user(&app.pool).await;
+    let outsider_token = generate_access_token(&app.config, outsider_id);
+    let guild_id = create_guild(&app.pool, owner_id).await;
+    let mut guard = app.cleanup_guard();
+    guard.add(move |pool| async move {
+        delete_guild(&pool, guild_id).await;
+        delete_user(&pool, owner_id).await;
+        delete_user(&pool, outsider_id).await;
+    });
+
+    let resp = app
+        .oneshot(
+            TestApp::request(Method::GET, &format!("/api/guilds/{guild_id}/perks"))
+                .header("authorization", format!("Bearer {outsider_token}"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+}
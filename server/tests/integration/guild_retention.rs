@@ -0,0 +1,245 @@
+//! HTTP integration tests for guild message retention policies.
+//!
+//! Run with: `cargo test --test integration guild_retention -- --nocapture`
+
+use axum::body::Body;
+use axum::http::{Method, StatusCode};
+use uuid::Uuid;
+use vc_server::permissions::GuildPermissions;
+
+use super::helpers::{
+    add_guild_member, body_to_json, create_channel, create_guild_with_default_role,
+    create_test_user, delete_guild, delete_user, generate_access_token, insert_deleted_message,
+    insert_message, TestApp,
+};
+
+fn put_retention(uri: &str, token: &str, retention_days: Option<i32>) -> axum::http::Request<Body> {
+    TestApp::request(Method::PUT, uri)
+        .header("authorization", format!("Bearer {token}"))
+        .header("content-type", "application/json")
+        .body(Body::from(
+            serde_json::json!({ "retention_days": retention_days }).to_string(),
+        ))
+        .unwrap()
+}
+
+fn get(uri: &str, token: &str) -> axum::http::Request<Body> {
+    TestApp::request(Method::GET, uri)
+        .header("authorization", format!("Bearer {token}"))
+        .body(Body::empty())
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_retention_settings_surface_in_channel_metadata() {
+    let app = TestApp::new().await;
+    let (owner_id, _) = create_test_user(&app.pool).await;
+    let (member_id, _) = create_test_user(&app.pool).await;
+    let guild_id =
+        create_guild_with_default_role(&app.pool, owner_id, GuildPermissions::VIEW_CHANNEL).await;
+    let mut guard = app.cleanup_guard();
+    guard.add(move |pool| async move {
+        delete_guild(&pool, guild_id).await;
+        delete_user(&pool, owner_id).await;
+        delete_user(&pool, member_id).await;
+    });
+    add_guild_member(&app.pool, guild_id, member_id).await;
+    let channel_id = create_channel(&app.pool, guild_id, "ephemeral").await;
+    let owner_token = generate_access_token(&app.config, owner_id);
+    let member_token = generate_access_token(&app.config, member_id);
+    let retention_uri = format!("/api/guilds/{guild_id}/retention");
+    let channel_retention_uri = format!("{retention_uri}/channels/{channel_id}");
+
+    // Plain members lack MANAGE_GUILD
+    assert_eq!(
+        app.oneshot(get(&retention_uri, &member_token))
+            .await
+            .status(),
+        StatusCode::FORBIDDEN
+    );
+    assert_eq!(
+        app.oneshot(put_retention(&retention_uri, &member_token, Some(30)))
+            .await
+            .status(),
+        StatusCode::FORBIDDEN
+    );
+
+    for days in [0, 3651] {
+        let resp = app
+            .oneshot(put_retention(&retention_uri, &owner_token, Some(days)))
+            .await;
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    }
+
+    let resp = app
+        .oneshot(put_retention(&retention_uri, &owner_token, Some(30)))
+        .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let policy = body_to_json(resp).await;
+    assert_eq!(policy["retention_days"], 30);
+    assert_eq!(
+        policy["channels"][0]["retention_days"],
+        serde_json::Value::Null
+    );
+    assert_eq!(policy["channels"][0]["effective_retention_days"], 30);
+
+    // Members see the policy in effect on the channel
+    let resp = app
+        .oneshot(get(&format!("/api/channels/{channel_id}"), &member_token))
+        .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(body_to_json(resp).await["retention_days"], 30);
+
+    let resp = app
+        .oneshot(put_retention(&channel_retention_uri, &owner_token, Some(7)))
+        .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let policy = body_to_json(resp).await;
+    assert_eq!(policy["channels"][0]["effective_retention_days"], 7);
+
+    let resp = app
+        .oneshot(get(
+            &format!("/api/guilds/{guild_id}/channels"),
+            &member_token,
+        ))
+        .await;
+    let channels = body_to_json(resp).await;
+    let channel = channels
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|c| c["id"] == channel_id.to_string())
+        .unwrap();
+    assert_eq!(channel["retention_days"], 7);
+
+    // Clearing both keeps messages forever
+    app.oneshot(put_retention(&channel_retention_uri, &owner_token, None))
+        .await;
+    app.oneshot(put_retention(&retention_uri, &owner_token, None))
+        .await;
+    let resp = app
+        .oneshot(get(&format!("/api/channels/{channel_id}"), &member_token))
+        .await;
+    assert_eq!(
+        body_to_json(resp).await["retention_days"],
+        serde_json::Value::Null
+    );
+
+    // Channels of other guilds cannot be targeted
+    let resp = app
+        .oneshot(put_retention(
+            &format!("{retention_uri}/channels/{}", Uuid::new_v4()),
+            &owner_token,
+            Some(7),
+        ))
+        .await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_purge_deletes_only_expired_messages() {
+    let app = TestApp::new().await;
+    let (owner_id, _) = create_test_user(&app.pool).await;
+    let guild_id =
+        create_guild_with_default_role(&app.pool, owner_id, GuildPermissions::VIEW_CHANNEL).await;
+    let mut guard = app.cleanup_guard();
+    guard.add(move |pool| async move {
+        delete_guild(&pool, guild_id).await;
+        delete_user(&pool, owner_id).await;
+    });
+    let channel_id = create_channel(&app.pool, guild_id, "short-lived").await;
+    let kept_channel_id = create_channel(&app.pool, guild_id, "archive").await;
+
+    sqlx::query("UPDATE guilds SET retention_days = 30 WHERE id = $1")
+        .bind(guild_id)
+        .execute(&app.pool)
+        .await
+        .unwrap();
+    sqlx::query("UPDATE channels SET retention_days = 3650 WHERE id = $1")
+        .bind(kept_channel_id)
+        .execute(&app.pool)
+        .await
+        .unwrap();
+
+    let expired = insert_message(&app.pool, channel_id, owner_id, "old news").await;
+    let recent = insert_message(&app.pool, channel_id, owner_id, "fresh").await;
+    let overridden = insert_message(&app.pool, kept_channel_id, owner_id, "keep me").await;
+    // A thread with a recent reply is kept as a whole
+    let thread_parent = insert_message(&app.pool, channel_id, owner_id, "old thread").await;
+    let thread_reply = insert_message(&app.pool, channel_id, owner_id, "recent reply").await;
+    sqlx::query(
+        "UPDATE messages SET created_at = NOW() - INTERVAL '31 days'
+         WHERE id = ANY($1)",
+    )
+    .bind(vec![expired, overridden, thread_parent])
+    .execute(&app.pool)
+    .await
+    .unwrap();
+    sqlx::query("UPDATE messages SET parent_id = $1 WHERE id = $2")
+        .bind(thread_parent)
+        .bind(thread_reply)
+        .execute(&app.pool)
+        .await
+        .unwrap();
+    sqlx::query("UPDATE messages SET thread_last_reply_at = NOW() WHERE id = $1")
+        .bind(thread_parent)
+        .execute(&app.pool)
+        .await
+        .unwrap();
+
+    vc_server::guild::retention::purge_expired_messages(&app.pool, &None)
+        .await
+        .unwrap();
+
+    let remaining: Vec<Uuid> =
+        sqlx::query_scalar("SELECT id FROM messages WHERE channel_id = ANY($1)")
+            .bind(vec![channel_id, kept_channel_id])
+            .fetch_all(&app.pool)
+            .await
+            .unwrap();
+    assert!(!remaining.contains(&expired));
+    for id in [recent, overridden, thread_parent, thread_reply] {
+        assert!(remaining.contains(&id));
+    }
+}
+
+#[tokio::test]
+async fn test_purge_deleted_messages_after_grace_period() {
+    let app = TestApp::new().await;
+    let (owner_id, _) = create_test_user(&app.pool).await;
+    let guild_id =
+        create_guild_with_default_role(&app.pool, owner_id, GuildPermissions::VIEW_CHANNEL).await;
+    let mut guard = app.cleanup_guard();
+    guard.add(move |pool| async move {
+        delete_guild(&pool, guild_id).await;
+        delete_user(&pool, owner_id).await;
+    });
+    let channel_id = create_channel(&app.pool, guild_id, "general").await;
+
+    let purged = insert_deleted_message(&app.pool, channel_id, owner_id, "gone").await;
+    let recent = insert_deleted_message(&app.pool, channel_id, owner_id, "just gone").await;
+    let live = insert_message(&app.pool, channel_id, owner_id, "still here").await;
+    sqlx::query("UPDATE messages SET deleted_at = NOW() - INTERVAL '31 days' WHERE id = $1")
+        .bind(purged)
+        .execute(&app.pool)
+        .await
+        .unwrap();
+    sqlx::query("UPDATE messages SET created_at = NOW() - INTERVAL '31 days' WHERE id = $1")
+        .bind(live)
+        .execute(&app.pool)
+        .await
+        .unwrap();
+
+    vc_server::guild::retention::purge_deleted_messages(&app.pool, &None)
+        .await
+        .unwrap();
+
+    let remaining: Vec<Uuid> = sqlx::query_scalar("SELECT id FROM messages WHERE channel_id = $1")
+        .bind(channel_id)
+        .fetch_all(&app.pool)
+        .await
+        .unwrap();
+    assert!(!remaining.contains(&purged));
+    assert!(remaining.contains(&recent));
+    assert!(remaining.contains(&live));
+}
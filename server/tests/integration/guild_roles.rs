@@ -0,0 +1,249 @@
+//! Integration tests for guild role reordering and member role listing.
+//!
+//! Run with: `cargo test --test integration guild_roles -- --nocapture`
+
+use axum::body::Body;
+use axum::http::{Method, StatusCode};
+use sqlx::PgPool;
+use uuid::Uuid;
+use vc_server::permissions::GuildPermissions;
+
+use super::helpers::{
+    add_guild_member, body_to_json, create_guild_with_default_role, create_test_user, delete_guild,
+    delete_user, generate_access_token, TestApp,
+};
+
+async fn create_role(pool: &PgPool, guild_id: Uuid, name: &str, position: i32) -> Uuid {
+    let role_id = Uuid::now_v7();
+    sqlx::query(
+        "INSERT INTO guild_roles (id, guild_id, name, permissions, position) VALUES ($1, $2, $3, $4, $5)",
+    )
+    .bind(role_id)
+    .bind(guild_id)
+    .bind(name)
+    .bind(GuildPermissions::MANAGE_ROLES.to_db())
+    .bind(position)
+    .execute(pool)
+    .await
+    .expect("Failed to create role");
+    role_id
+}
+
+async fn role_position(pool: &PgPool, role_id: Uuid) -> i32 {
+    sqlx::query_scalar("SELECT position FROM guild_roles WHERE id = $1")
+        .bind(role_id)
+        .fetch_one(pool)
+        .await
+        .expect("Failed to fetch role position")
+}
+
+async fn reorder(
+    app: &TestApp,
+    token: &str,
+    guild_id: Uuid,
+    role_ids: &[Uuid],
+) -> axum::response::Response {
+    app.oneshot(
+        TestApp::request(
+            Method::POST,
+            &format!("/api/guilds/{guild_id}/roles/reorder"),
+        )
+        .header("authorization", format!("Bearer {token}"))
+        .header("content-type", "application/json")
+        .body(Body::from(
+            serde_json::json!({ "role_ids": role_ids }).to_string(),
+        ))
+        .unwrap(),
+    )
+    .await
+}
+
+#[tokio::test]
+async fn test_reorder_roles_reuses_positions() {
+    let app = TestApp::new().await;
+    let (owner_id, _) = create_test_user(&app.pool).await;
+    let token = generate_access_token(&app.config, owner_id);
+    let guild_id =
+        create_guild_with_default_role(&app.pool, owner_id, GuildPermissions::empty()).await;
+    let officer = create_role(&app.pool, guild_id, "Officer", 50).await;
+    let moderator = create_role(&app.pool, guild_id, "Moderator", 100).await;
+    let mut guard = app.cleanup_guard();
+    guard.add(move |pool| async move {
+        delete_guild(&pool, guild_id).await;
+        delete_user(&pool, owner_id).await;
+    });
+
+    let resp = reorder(&app, &token, guild_id, &[moderator, officer]).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let body = body_to_json(resp).await;
+    let ids: Vec<&str> = body
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter(|role| role["is_default"] == false)
+        .map(|role| role["id"].as_str().unwrap())
+        .collect();
+    assert_eq!(ids, vec![moderator.to_string(), officer.to_string()]);
+
+    assert_eq!(role_position(&app.pool, moderator).await, 50);
+    assert_eq!(role_position(&app.pool, officer).await, 100);
+
+    // Incomplete list is rejected
+    let resp = reorder(&app, &token, guild_id, &[moderator]).await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    let body = body_to_json(resp).await;
+    assert_eq!(body["error"], "VALIDATION_ERROR");
+}
+
+#[tokio::test]
+async fn test_reorder_roles_respects_hierarchy() {
+    let app = TestApp::new().await;
+    let (owner_id, _) = create_test_user(&app.pool).await;
+    let (manager_id, _) = create_test_user(&app.pool).await;
+    let token = generate_access_token(&app.config, manager_id);
+    let guild_id =
+        create_guild_with_default_role(&app.pool, owner_id, GuildPermissions::empty()).await;
+    add_guild_member(&app.pool, guild_id, manager_id).await;
+    let manager = create_role(&app.pool, guild_id, "Manager", 50).await;
+    let helper = create_role(&app.pool, guild_id, "Helper", 100).await;
+    let member = create_role(&app.pool, guild_id, "Member", 150).await;
+    sqlx::query("INSERT INTO guild_member_roles (guild_id, user_id, role_id) VALUES ($1, $2, $3)")
+        .bind(guild_id)
+        .bind(manager_id)
+        .bind(manager)
+        .execute(&app.pool)
+        .await
+        .expect("Failed to assign role");
+    let mut guard = app.cleanup_guard();
+    guard.add(move |pool| async move {
+        delete_guild(&pool, guild_id).await;
+        delete_user(&pool, owner_id).await;
+        delete_user(&pool, manager_id).await;
+    });
+
+    // Roles below the actor can be swapped
+    let resp = reorder(&app, &token, guild_id, &[manager, member, helper]).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(role_position(&app.pool, member).await, 100);
+
+    // Moving a role above the actor's own role is forbidden
+    let resp = reorder(&app, &token, guild_id, &[helper, manager, member]).await;
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+    let body = body_to_json(resp).await;
+    assert_eq!(body["error"], "ROLE_HIERARCHY");
+    assert_eq!(role_position(&app.pool, manager).await, 50);
+}
+
+#[tokio::test]
+async fn test_member_roles_lists_assignments() {
+    let app = TestApp::new().await;
+    let (owner_id, _) = create_test_user(&app.pool).await;
+    let (member_id, _) = create_test_user(&app.pool).await;
+    let token = generate_access_token(&app.config, owner_id);
+    let guild_id =
+        create_guild_with_default_role(&app.pool, owner_id, GuildPermissions::empty()).await;
+    add_guild_member(&app.pool, guild_id, member_id).await;
+    let role_id = create_role(&app.pool, guild_id, "Helper", 100).await;
+    let mut guard = app.cleanup_guard();
+    guard.add(move |pool| async move {
+        delete_guild(&pool, guild_id).await;
+        delete_user(&pool, owner_id).await;
+        delete_user(&pool, member_id).await;
+    });
+
+    let resp = app
+        .oneshot(
+            TestApp::request(
+                Method::POST,
+                &format!("/api/guilds/{guild_id}/members/{member_id}/roles/{role_id}"),
+            )
+            .header("authorization", format!("Bearer {token}"))
+            .body(Body::empty())
+            .unwrap(),
+        )
+        .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let resp = app
+        .oneshot(
+            TestApp::request(Method::GET, &format!("/api/guilds/{guild_id}/member-roles"))
+                .header("authorization", format!("Bearer {token}"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let body = body_to_json(resp).await;
+    assert_eq!(
+        body[member_id.to_string()],
+        serde_json::json!([role_id.to_string()])
+    );
+    assert!(body.get(owner_id.to_string()).is_none());
+}
+
+#[tokio::test]
+async fn test_bulk_member_roles_add_and_remove() {
+    let app = TestApp::new().await;
+    let (owner_id, _) = create_test_user(&app.pool).await;
+    let (alice_id, _) = create_test_user(&app.pool).await;
+    let (bob_id, _) = create_test_user(&app.pool).await;
+    let (outsider_id, _) = create_test_user(&app.pool).await;
+    let token = generate_access_token(&app.config, owner_id);
+    let guild_id =
+        create_guild_with_default_role(&app.pool, owner_id, GuildPermissions::empty()).await;
+    add_guild_member(&app.pool, guild_id, alice_id).await;
+    add_guild_member(&app.pool, guild_id, bob_id).await;
+    let role_id = create_role(&app.pool, guild_id, "Helper", 100).await;
+    let mut guard = app.cleanup_guard();
+    guard.add(move |pool| async move {
+        delete_guild(&pool, guild_id).await;
+        delete_user(&pool, owner_id).await;
+        delete_user(&pool, alice_id).await;
+        delete_user(&pool, bob_id).await;
+        delete_user(&pool, outsider_id).await;
+    });
+
+    let bulk = |action: &str, user_ids: &[Uuid]| {
+        TestApp::request(
+            Method::POST,
+            &format!("/api/guilds/{guild_id}/members/bulk-roles"),
+        )
+        .header("authorization", format!("Bearer {token}"))
+        .header("content-type", "application/json")
+        .body(Body::from(
+            serde_json::json!({ "role_id": role_id, "action": action, "user_ids": user_ids })
+                .to_string(),
+        ))
+        .unwrap()
+    };
+
+    let resp = app
+        .oneshot(bulk("add", &[alice_id, bob_id, outsider_id, alice_id]))
+        .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let body = body_to_json(resp).await;
+    assert_eq!(body["updated"].as_array().unwrap().len(), 2);
+    assert_eq!(body["unchanged"], serde_json::json!([]));
+    assert_eq!(body["failed"][0]["user_id"], outsider_id.to_string());
+
+    let resp = app.oneshot(bulk("remove", &[alice_id])).await;
+    let body = body_to_json(resp).await;
+    assert_eq!(body["updated"], serde_json::json!([alice_id.to_string()]));
+
+    let resp = app.oneshot(bulk("remove", &[alice_id])).await;
+    let body = body_to_json(resp).await;
+    assert_eq!(body["unchanged"], serde_json::json!([alice_id.to_string()]));
+
+    let assigned: Vec<Uuid> = sqlx::query_scalar(
+        "SELECT user_id FROM guild_member_roles WHERE guild_id = $1 AND role_id = $2",
+    )
+    .bind(guild_id)
+    .bind(role_id)
+    .fetch_all(&app.pool)
+    .await
+    .unwrap();
+    assert_eq!(assigned, vec![bob_id]);
+
+    let resp = app.oneshot(bulk("add", &[])).await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+}
@@ -0,0 +1,405 @@
+//! Integration tests for guild settings.
+//!
+//! Run with: `cargo test --test integration guild_settings -- --nocapture`
+
+use axum::body::Body;
+use axum::http::{Method, StatusCode};
+use uuid::Uuid;
+
+use super::helpers::{
+    add_guild_member, body_to_json, create_channel, create_guild, create_test_user, delete_guild,
+    delete_user, generate_access_token, TestApp,
+};
+
+async fn patch_settings(
+    app: &TestApp,
+    token: &str,
+    guild_id: Uuid,
+    body: serde_json::Value,
+) -> axum::response::Response {
+    app.oneshot(
+        TestApp::request(Method::PATCH, &format!("/api/guilds/{guild_id}/settings"))
+            .header("authorization", format!("Bearer {token}"))
+            .header("content-type", "application/json")
+            .body(Body::from(body.to_string()))
+            .unwrap(),
+    )
+    .await
+}
+
+#[tokio::test]
+async fn test_voice_log_channel_set_and_clear() {
+    let app = TestApp::new().await;
+    let (owner_id, _) = create_test_user(&app.pool).await;
+    let token = generate_access_token(&app.config, owner_id);
+    let guild_id = create_guild(&app.pool, owner_id).await;
+    let log_channel_id = create_channel(&app.pool, guild_id, "voice-log").await;
+    let mut guard = app.cleanup_guard();
+    guard.add(move |pool| async move {
+        delete_guild(&pool, guild_id).await;
+        delete_user(&pool, owner_id).await;
+    });
+
+    let resp = patch_settings(
+        &app,
+        &token,
+        guild_id,
+        serde_json::json!({ "voice_log_channel_id": log_channel_id }),
+    )
+    .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let body = body_to_json(resp).await;
+    assert_eq!(body["voice_log_channel_id"], log_channel_id.to_string());
+
+    // Unrelated update leaves the log channel untouched
+    let resp = patch_settings(
+        &app,
+        &token,
+        guild_id,
+        serde_json::json!({ "threads_enabled": true }),
+    )
+    .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let body = body_to_json(resp).await;
+    assert_eq!(body["voice_log_channel_id"], log_channel_id.to_string());
+
+    // Explicit null disables the log
+    let resp = patch_settings(
+        &app,
+        &token,
+        guild_id,
+        serde_json::json!({ "voice_log_channel_id": null }),
+    )
+    .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let body = body_to_json(resp).await;
+    assert!(body["voice_log_channel_id"].is_null());
+}
+
+#[tokio::test]
+async fn test_voice_log_channel_rejects_foreign_or_voice_channel() {
+    let app = TestApp::new().await;
+    let (owner_id, _) = create_test_user(&app.pool).await;
+    let token = generate_access_token(&app.config, owner_id);
+    let guild_id = create_guild(&app.pool, owner_id).await;
+    let other_guild_id = create_guild(&app.pool, owner_id).await;
+    let foreign_channel_id = create_channel(&app.pool, other_guild_id, "elsewhere").await;
+    let voice_channel_id = Uuid::now_v7();
+    sqlx::query(
+        "INSERT INTO channels (id, guild_id, name, channel_type) VALUES ($1, $2, 'Lounge', 'voice')",
+    )
+    .bind(voice_channel_id)
+    .bind(guild_id)
+    .execute(&app.pool)
+    .await
+    .expect("Failed to create voice channel");
+    let mut guard = app.cleanup_guard();
+    guard.add(move |pool| async move {
+        delete_guild(&pool, guild_id).await;
+        delete_guild(&pool, other_guild_id).await;
+        delete_user(&pool, owner_id).await;
+    });
+
+    for channel_id in [foreign_channel_id, voice_channel_id] {
+        let resp = patch_settings(
+            &app,
+            &token,
+            guild_id,
+            serde_json::json!({ "voice_log_channel_id": channel_id }),
+        )
+        .await;
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+        let body = body_to_json(resp).await;
+        assert_eq!(body["error"], "VALIDATION_ERROR");
+    }
+}
+
+#[tokio::test]
+async fn test_voice_region_set_validate_and_clear() {
+    let app = TestApp::new().await;
+    let (owner_id, _) = create_test_user(&app.pool).await;
+    let token = generate_access_token(&app.config, owner_id);
+    let guild_id = create_guild(&app.pool, owner_id).await;
+    let mut guard = app.cleanup_guard();
+    guard.add(move |pool| async move {
+        delete_guild(&pool, guild_id).await;
+        delete_user(&pool, owner_id).await;
+    });
+
+    let resp = patch_settings(
+        &app,
+        &token,
+        guild_id,
+        serde_json::json!({ "voice_region": "eu-central" }),
+    )
+    .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let body = body_to_json(resp).await;
+    assert_eq!(body["voice_region"], "eu-central");
+
+    for region in ["", "eu central", "a".repeat(33).as_str()] {
+        let resp = patch_settings(
+            &app,
+            &token,
+            guild_id,
+            serde_json::json!({ "voice_region": region }),
+        )
+        .await;
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    }
+
+    let resp = patch_settings(
+        &app,
+        &token,
+        guild_id,
+        serde_json::json!({ "voice_region": null }),
+    )
+    .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let body = body_to_json(resp).await;
+    assert!(body["voice_region"].is_null());
+}
+
+async fn patch_guild(
+    app: &TestApp,
+    token: &str,
+    guild_id: Uuid,
+    body: serde_json::Value,
+) -> axum::response::Response {
+    app.oneshot(
+        TestApp::request(Method::PATCH, &format!("/api/guilds/{guild_id}"))
+            .header("authorization", format!("Bearer {token}"))
+            .header("content-type", "application/json")
+            .body(Body::from(body.to_string()))
+            .unwrap(),
+    )
+    .await
+}
+
+#[tokio::test]
+async fn test_system_and_rules_channels_set_and_clear() {
+    let app = TestApp::new().await;
+    let (owner_id, _) = create_test_user(&app.pool).await;
+    let token = generate_access_token(&app.config, owner_id);
+    let guild_id = create_guild(&app.pool, owner_id).await;
+    let system_channel_id = create_channel(&app.pool, guild_id, "welcome").await;
+    let rules_channel_id = create_channel(&app.pool, guild_id, "rules").await;
+    let mut guard = app.cleanup_guard();
+    guard.add(move |pool| async move {
+        delete_guild(&pool, guild_id).await;
+        delete_user(&pool, owner_id).await;
+    });
+
+    let resp = patch_guild(
+        &app,
+        &token,
+        guild_id,
+        serde_json::json!({
+            "system_channel_id": system_channel_id,
+            "rules_channel_id": rules_channel_id,
+        }),
+    )
+    .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let body = body_to_json(resp).await;
+    assert_eq!(body["system_channel_id"], system_channel_id.to_string());
+    assert_eq!(body["rules_channel_id"], rules_channel_id.to_string());
+
+    // Unrelated update leaves both untouched; explicit null clears one
+    let resp = patch_guild(
+        &app,
+        &token,
+        guild_id,
+        serde_json::json!({ "description": "Hi", "rules_channel_id": null }),
+    )
+    .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let body = body_to_json(resp).await;
+    assert_eq!(body["system_channel_id"], system_channel_id.to_string());
+    assert!(body["rules_channel_id"].is_null());
+
+    // Deleting the channel clears the designation
+    sqlx::query("DELETE FROM channels WHERE id = $1")
+        .bind(system_channel_id)
+        .execute(&app.pool)
+        .await
+        .expect("Failed to delete channel");
+    let resp = app
+        .oneshot(
+            TestApp::request(Method::GET, &format!("/api/guilds/{guild_id}"))
+                .header("authorization", format!("Bearer {token}"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let body = body_to_json(resp).await;
+    assert!(body["system_channel_id"].is_null());
+}
+
+#[tokio::test]
+async fn test_system_channel_rejects_foreign_channel() {
+    let app = TestApp::new().await;
+    let (owner_id, _) = create_test_user(&app.pool).await;
+    let token = generate_access_token(&app.config, owner_id);
+    let guild_id = create_guild(&app.pool, owner_id).await;
+    let other_guild_id = create_guild(&app.pool, owner_id).await;
+    let foreign_channel_id = create_channel(&app.pool, other_guild_id, "elsewhere").await;
+    let mut guard = app.cleanup_guard();
+    guard.add(move |pool| async move {
+        delete_guild(&pool, guild_id).await;
+        delete_guild(&pool, other_guild_id).await;
+        delete_user(&pool, owner_id).await;
+    });
+
+    for field in ["system_channel_id", "rules_channel_id"] {
+        let resp = patch_guild(
+            &app,
+            &token,
+            guild_id,
+            serde_json::json!({ field: foreign_channel_id }),
+        )
+        .await;
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+        let body = body_to_json(resp).await;
+        assert_eq!(body["error"], "VALIDATION_ERROR");
+    }
+}
+
+fn image_upload(token: &str, guild_id: Uuid, kind: &str) -> axum::http::Request<Body> {
+    let boundary = "----TestBoundary";
+    let body = format!(
+        "--{boundary}\r\nContent-Disposition: form-data; name=\"file\"; filename=\"icon.png\"\r\nContent-Type: image/png\r\n\r\nnot-a-png\r\n--{boundary}--\r\n"
+    );
+    TestApp::request(Method::POST, &format!("/api/guilds/{guild_id}/{kind}"))
+        .header("authorization", format!("Bearer {token}"))
+        .header(
+            "content-type",
+            format!("multipart/form-data; boundary={boundary}"),
+        )
+        .body(Body::from(body))
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_guild_image_upload_requires_manage_guild() {
+    let app = TestApp::new().await;
+    let (owner_id, _) = create_test_user(&app.pool).await;
+    let (member_id, _) = create_test_user(&app.pool).await;
+    let owner_token = generate_access_token(&app.config, owner_id);
+    let member_token = generate_access_token(&app.config, member_id);
+    let guild_id = create_guild(&app.pool, owner_id).await;
+    add_guild_member(&app.pool, guild_id, member_id).await;
+    let mut guard = app.cleanup_guard();
+    guard.add(move |pool| async move {
+        delete_guild(&pool, guild_id).await;
+        delete_user(&pool, owner_id).await;
+        delete_user(&pool, member_id).await;
+    });
+
+    for kind in ["icon", "banner"] {
+        let resp = app
+            .oneshot(image_upload(&member_token, guild_id, kind))
+            .await;
+        assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+
+        // S3 is not configured in tests, so a permitted upload stops there
+        let resp = app
+            .oneshot(image_upload(&owner_token, guild_id, kind))
+            .await;
+        assert_eq!(resp.status(), StatusCode::SERVICE_UNAVAILABLE);
+        let body = body_to_json(resp).await;
+        assert_eq!(body["error"], "STORAGE_NOT_CONFIGURED");
+    }
+
+    let resp = app
+        .oneshot(image_upload(&owner_token, Uuid::new_v4(), "icon"))
+        .await;
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_guild_images_are_not_settable_by_url() {
+    let app = TestApp::new().await;
+    let (owner_id, _) = create_test_user(&app.pool).await;
+    let token = generate_access_token(&app.config, owner_id);
+    let guild_id = create_guild(&app.pool, owner_id).await;
+    let mut guard = app.cleanup_guard();
+    guard.add(move |pool| async move {
+        delete_guild(&pool, guild_id).await;
+        delete_user(&pool, owner_id).await;
+    });
+
+    let resp = patch_guild(
+        &app,
+        &token,
+        guild_id,
+        serde_json::json!({ "icon_url": "https://example.com/icon.png" }),
+    )
+    .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert!(body_to_json(resp).await["icon_url"].is_null());
+
+    let resp = patch_settings(
+        &app,
+        &token,
+        guild_id,
+        serde_json::json!({ "banner_url": "https://example.com/banner.png" }),
+    )
+    .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert!(body_to_json(resp).await["banner_url"].is_null());
+}
+
+#[tokio::test]
+async fn test_guild_image_delete_clears_url() {
+    let app = TestApp::new().await;
+    let (owner_id, _) = create_test_user(&app.pool).await;
+    let (member_id, _) = create_test_user(&app.pool).await;
+    let owner_token = generate_access_token(&app.config, owner_id);
+    let member_token = generate_access_token(&app.config, member_id);
+    let guild_id = create_guild(&app.pool, owner_id).await;
+    add_guild_member(&app.pool, guild_id, member_id).await;
+    let mut guard = app.cleanup_guard();
+    guard.add(move |pool| async move {
+        delete_guild(&pool, guild_id).await;
+        delete_user(&pool, owner_id).await;
+        delete_user(&pool, member_id).await;
+    });
+
+    sqlx::query("UPDATE guilds SET icon_url = $1, banner_url = $1 WHERE id = $2")
+        .bind(format!("/voicechat/guilds/{guild_id}/icons/x/256.webp"))
+        .bind(guild_id)
+        .execute(&app.pool)
+        .await
+        .expect("Failed to set guild images");
+
+    let delete = |token: &str, kind: &str| {
+        TestApp::request(Method::DELETE, &format!("/api/guilds/{guild_id}/{kind}"))
+            .header("authorization", format!("Bearer {token}"))
+            .body(Body::empty())
+            .unwrap()
+    };
+
+    let resp = app.oneshot(delete(&member_token, "icon")).await;
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+
+    let resp = app.oneshot(delete(&owner_token, "icon")).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let body = body_to_json(resp).await;
+    assert!(body["icon_url"].is_null());
+    assert!(body["banner_url"].is_string());
+
+    let resp = app.oneshot(delete(&owner_token, "banner")).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert!(body_to_json(resp).await["banner_url"].is_null());
+
+    let action_count: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM guild_audit_log WHERE guild_id = $1 AND action = 'guild.updated'",
+    )
+    .bind(guild_id)
+    .fetch_one(&app.pool)
+    .await
+    .unwrap();
+    assert_eq!(action_count, 2);
+}
@@ -0,0 +1,134 @@
+//! HTTP Integration Tests for Admin Guild Suspension
+//!
+//! Tests `/api/admin/guilds/{id}/suspend`: reason validation, posting being
+//! blocked while suspended, and eviction from discovery trending stats.
+//!
+//! Run with: `cargo test --test integration guild_suspension -- --nocapture`
+
+use axum::body::Body;
+use axum::http::{Method, StatusCode};
+use uuid::Uuid;
+
+use super::helpers::{
+    authed, body_to_json, create_channel, create_elevated_session, create_guild, create_test_user,
+    delete_guild, delete_user, generate_access_token, make_admin, TestApp,
+};
+
+async fn suspend(
+    app: &TestApp,
+    token: &str,
+    guild_id: Uuid,
+    reason: &str,
+) -> axum::response::Response {
+    app.oneshot(
+        authed(
+            Method::POST,
+            &format!("/api/admin/guilds/{guild_id}/suspend"),
+            token,
+        )
+        .header("content-type", "application/json")
+        .body(Body::from(
+            serde_json::json!({ "reason": reason }).to_string(),
+        ))
+        .unwrap(),
+    )
+    .await
+}
+
+async fn post_message(app: &TestApp, token: &str, channel_id: Uuid) -> axum::response::Response {
+    app.oneshot(
+        authed(
+            Method::POST,
+            &format!("/api/messages/channel/{channel_id}"),
+            token,
+        )
+        .header("content-type", "application/json")
+        .body(Body::from(r#"{"content": "hello"}"#))
+        .unwrap(),
+    )
+    .await
+}
+
+#[tokio::test]
+async fn test_suspended_guild_blocks_posting_until_lifted() {
+    let app = TestApp::new().await;
+    let (admin_id, _) = create_test_user(&app.pool).await;
+    let (owner_id, _) = create_test_user(&app.pool).await;
+    let guild_id = create_guild(&app.pool, owner_id).await;
+    let mut guard = app.cleanup_guard();
+    guard.add(move |pool| async move {
+        delete_guild(&pool, guild_id).await;
+        delete_user(&pool, owner_id).await;
+        delete_user(&pool, admin_id).await;
+    });
+    make_admin(&app.pool, admin_id).await;
+    create_elevated_session(&app.pool, admin_id).await;
+    let channel_id = create_channel(&app.pool, guild_id, "general").await;
+    let admin_token = generate_access_token(&app.config, admin_id);
+    let owner_token = generate_access_token(&app.config, owner_id);
+
+    sqlx::query(
+        r"INSERT INTO guild_activity_stats
+              (guild_id, member_growth_7d, messages_7d, trending_score, computed_on, computed_at)
+          VALUES ($1, 1, 1, 1.0, CURRENT_DATE, NOW())",
+    )
+    .bind(guild_id)
+    .execute(&app.pool)
+    .await
+    .unwrap();
+
+    let resp = post_message(&app, &owner_token, channel_id).await;
+    assert_eq!(resp.status(), StatusCode::CREATED);
+
+    // A reason is required
+    let resp = suspend(&app, &admin_token, guild_id, "  ").await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    let resp = suspend(&app, &admin_token, Uuid::new_v4(), "spam").await;
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+
+    let resp = suspend(&app, &admin_token, guild_id, "  spam  ").await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(body_to_json(resp).await["suspended"], true);
+
+    let (reason, suspended_by): (Option<String>, Option<Uuid>) =
+        sqlx::query_as("SELECT suspension_reason, suspended_by FROM guilds WHERE id = $1")
+            .bind(guild_id)
+            .fetch_one(&app.pool)
+            .await
+            .unwrap();
+    assert_eq!(reason.as_deref(), Some("spam"));
+    assert_eq!(suspended_by, Some(admin_id));
+
+    // Evicted from trending
+    let stats: i64 =
+        sqlx::query_scalar("SELECT COUNT(*) FROM guild_activity_stats WHERE guild_id = $1")
+            .bind(guild_id)
+            .fetch_one(&app.pool)
+            .await
+            .unwrap();
+    assert_eq!(stats, 0);
+
+    // Even the owner cannot post
+    let resp = post_message(&app, &owner_token, channel_id).await;
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+    assert_eq!(body_to_json(resp).await["error"], "GUILD_SUSPENDED");
+
+    let resp = suspend(&app, &admin_token, guild_id, "again").await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+    let resp = app
+        .oneshot(
+            authed(
+                Method::DELETE,
+                &format!("/api/admin/guilds/{guild_id}/suspend"),
+                &admin_token,
+            )
+            .body(Body::empty())
+            .unwrap(),
+        )
+        .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let resp = post_message(&app, &owner_token, channel_id).await;
+    assert_eq!(resp.status(), StatusCode::CREATED);
+}
@@ -0,0 +1,242 @@
+//! HTTP integration tests for member timeouts.
+//!
+//! Run with: `cargo test --test integration guild_timeouts -- --nocapture`
+
+use axum::body::Body;
+use axum::http::{Method, StatusCode};
+use uuid::Uuid;
+use vc_server::db;
+use vc_server::permissions::GuildPermissions;
+
+use super::helpers::{
+    add_guild_member, authed, body_to_json, create_channel, create_guild, create_test_user,
+    delete_guild, delete_user, generate_access_token, TestApp,
+};
+
+async fn set_timeout(
+    app: &TestApp,
+    token: &str,
+    guild_id: Uuid,
+    user_id: Uuid,
+    body: serde_json::Value,
+) -> axum::response::Response {
+    app.oneshot(
+        authed(
+            Method::PATCH,
+            &format!("/api/guilds/{guild_id}/members/{user_id}/timeout"),
+            token,
+        )
+        .header("content-type", "application/json")
+        .body(Body::from(body.to_string()))
+        .unwrap(),
+    )
+    .await
+}
+
+async fn post_message(app: &TestApp, token: &str, channel_id: Uuid) -> axum::response::Response {
+    app.oneshot(
+        authed(
+            Method::POST,
+            &format!("/api/messages/channel/{channel_id}"),
+            token,
+        )
+        .header("content-type", "application/json")
+        .body(Body::from(r#"{"content": "hello"}"#))
+        .unwrap(),
+    )
+    .await
+}
+
+#[tokio::test]
+async fn test_timeout_blocks_messages_until_lifted() {
+    let app = TestApp::new().await;
+    let (owner_id, _) = create_test_user(&app.pool).await;
+    let (member_id, _) = create_test_user(&app.pool).await;
+    let guild_id = create_guild(&app.pool, owner_id).await;
+    let mut guard = app.cleanup_guard();
+    guard.add(move |pool| async move {
+        delete_guild(&pool, guild_id).await;
+        delete_user(&pool, owner_id).await;
+        delete_user(&pool, member_id).await;
+    });
+    add_guild_member(&app.pool, guild_id, member_id).await;
+    let channel_id = create_channel(&app.pool, guild_id, "general").await;
+    let owner_token = generate_access_token(&app.config, owner_id);
+    let member_token = generate_access_token(&app.config, member_id);
+
+    // Plain members cannot time out others
+    let until = chrono::Utc::now() + chrono::Duration::hours(1);
+    let resp = set_timeout(
+        &app,
+        &member_token,
+        guild_id,
+        owner_id,
+        serde_json::json!({ "timeout_until": until }),
+    )
+    .await;
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+
+    // Longer than 28 days is rejected
+    let resp = set_timeout(
+        &app,
+        &owner_token,
+        guild_id,
+        member_id,
+        serde_json::json!({ "timeout_until": chrono::Utc::now() + chrono::Duration::days(29) }),
+    )
+    .await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+    let resp = set_timeout(
+        &app,
+        &owner_token,
+        guild_id,
+        member_id,
+        serde_json::json!({ "timeout_until": until, "reason": "cool off" }),
+    )
+    .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert!(body_to_json(resp).await["timeout_until"].is_string());
+
+    let resp = post_message(&app, &member_token, channel_id).await;
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+    assert_eq!(body_to_json(resp).await["error"], "TIMED_OUT");
+
+    let resp = app
+        .oneshot(
+            authed(
+                Method::GET,
+                &format!("/api/guilds/{guild_id}/members"),
+                &owner_token,
+            )
+            .body(Body::empty())
+            .unwrap(),
+        )
+        .await;
+    let members = body_to_json(resp).await;
+    let member = members
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|m| m["user_id"] == member_id.to_string())
+        .unwrap();
+    assert!(member["timeout_until"].is_string());
+
+    let resp = set_timeout(
+        &app,
+        &owner_token,
+        guild_id,
+        member_id,
+        serde_json::json!({ "timeout_until": null }),
+    )
+    .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(
+        post_message(&app, &member_token, channel_id).await.status(),
+        StatusCode::CREATED
+    );
+
+    let audited: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM guild_audit_log WHERE guild_id = $1 AND action IN ('guild.members.timed_out', 'guild.members.timeout_removed')",
+    )
+    .bind(guild_id)
+    .fetch_one(&app.pool)
+    .await
+    .unwrap();
+    assert_eq!(audited, 2);
+}
+
+#[tokio::test]
+async fn test_timeout_respects_hierarchy_and_sweeper_clears_expired() {
+    let app = TestApp::new().await;
+    let (owner_id, _) = create_test_user(&app.pool).await;
+    let (mod_id, _) = create_test_user(&app.pool).await;
+    let (member_id, _) = create_test_user(&app.pool).await;
+    let guild_id = create_guild(&app.pool, owner_id).await;
+    let mut guard = app.cleanup_guard();
+    guard.add(move |pool| async move {
+        delete_guild(&pool, guild_id).await;
+        delete_user(&pool, owner_id).await;
+        delete_user(&pool, mod_id).await;
+        delete_user(&pool, member_id).await;
+    });
+    add_guild_member(&app.pool, guild_id, mod_id).await;
+    add_guild_member(&app.pool, guild_id, member_id).await;
+
+    let role_id = Uuid::now_v7();
+    sqlx::query(
+        "INSERT INTO guild_roles (id, guild_id, name, permissions, position) VALUES ($1, $2, 'Mod', $3, 1)",
+    )
+    .bind(role_id)
+    .bind(guild_id)
+    .bind(GuildPermissions::TIMEOUT_MEMBERS.to_db())
+    .execute(&app.pool)
+    .await
+    .unwrap();
+    sqlx::query("INSERT INTO guild_member_roles (guild_id, user_id, role_id) VALUES ($1, $2, $3)")
+        .bind(guild_id)
+        .bind(mod_id)
+        .bind(role_id)
+        .execute(&app.pool)
+        .await
+        .unwrap();
+    let mod_token = generate_access_token(&app.config, mod_id);
+    let until = chrono::Utc::now() + chrono::Duration::minutes(10);
+
+    // The owner can never be timed out
+    let resp = set_timeout(
+        &app,
+        &mod_token,
+        guild_id,
+        owner_id,
+        serde_json::json!({ "timeout_until": until }),
+    )
+    .await;
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+
+    let resp = set_timeout(
+        &app,
+        &mod_token,
+        guild_id,
+        member_id,
+        serde_json::json!({ "timeout_until": until }),
+    )
+    .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert!(db::get_member_timeout(&app.pool, guild_id, member_id)
+        .await
+        .unwrap()
+        .is_some());
+
+    // Expired timeouts are ignored by enforcement and cleared by the sweeper
+    sqlx::query(
+        "UPDATE guild_members SET timeout_until = NOW() - INTERVAL '1 second' WHERE guild_id = $1 AND user_id = $2",
+    )
+    .bind(guild_id)
+    .bind(member_id)
+    .execute(&app.pool)
+    .await
+    .unwrap();
+    assert!(db::get_member_timeout(&app.pool, guild_id, member_id)
+        .await
+        .unwrap()
+        .is_none());
+
+    let redis = db::create_redis_client(&app.config.redis_url)
+        .await
+        .unwrap();
+    let cleared = vc_server::guild::timeouts::sweep_expired_timeouts(&app.pool, &redis)
+        .await
+        .unwrap();
+    assert!(cleared >= 1);
+
+    let remaining: Option<chrono::DateTime<chrono::Utc>> = sqlx::query_scalar(
+        "SELECT timeout_until FROM guild_members WHERE guild_id = $1 AND user_id = $2",
+    )
+    .bind(guild_id)
+    .bind(member_id)
+    .fetch_one(&app.pool)
+    .await
+    .unwrap();
+    assert!(remaining.is_none());
+}
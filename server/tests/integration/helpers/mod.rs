@@ -17,6 +17,8 @@
 //! (rate limiting, request IDs, etc.) instead of `tower::ServiceExt::oneshot`.
 #![allow(dead_code)]
 
+pub mod soft_passkey;
+
 use std::future::Future;
 use std::net::SocketAddr;
 use std::pin::Pin;
@@ -24,6 +26,7 @@ use std::sync::Arc;
 use std::time::Duration;
 
 use axum::body::Body;
+use axum::extract::ConnectInfo;
 use axum::http::{self, Method, Request, Response};
 use axum::Router;
 use http_body_util::BodyExt;
@@ -34,9 +37,11 @@ use tower::ServiceExt;
 use uuid::Uuid;
 use vc_server::api::{create_router, AppState, AppStateConfig};
 use vc_server::auth::jwt;
+use vc_server::auth::oidc::OidcProviderManager;
 use vc_server::chat::S3Client;
 use vc_server::config::Config;
 use vc_server::db;
+use vc_server::email::EmailService;
 use vc_server::permissions::GuildPermissions;
 use vc_server::voice::sfu::SfuServer;
 
@@ -146,6 +151,7 @@ impl CleanupGuard {
                 ("registration_policy", serde_json::json!("open")),
                 ("terms_url", serde_json::Value::Null),
                 ("privacy_url", serde_json::Value::Null),
+                ("instance_settings", serde_json::json!({})),
             ] {
                 let _ = sqlx::query(
                     "UPDATE server_config SET value = $1, updated_by = NULL WHERE key = $2",
@@ -235,6 +241,8 @@ impl TestApp {
     }
 
     /// Create a test app with a custom config (for limit testing).
+    ///
+    /// Gets an email service if the config has one, e.g. `EMAIL_BACKEND=console`.
     pub async fn with_config(config: Config) -> Self {
         let pool = shared_pool().await.clone();
         let redis = db::create_redis_client(&config.redis_url)
@@ -250,7 +258,9 @@ impl TestApp {
             s3: None,
             sfu,
             rate_limiter: None,
-            email: None,
+            email: config
+                .has_email()
+                .then(|| EmailService::new(&config).expect("Failed to create EmailService")),
             oidc_manager: None,
         });
         let router = create_router(state);
@@ -263,6 +273,36 @@ impl TestApp {
         }
     }
 
+    /// Create a test app with a custom config that signs in through
+    /// `oidc_manager`'s providers.
+    pub async fn with_oidc_manager(config: Config, oidc_manager: OidcProviderManager) -> Self {
+        let pool = shared_pool().await.clone();
+        let redis = db::create_redis_client(&config.redis_url)
+            .await
+            .expect("Failed to connect to test Redis");
+        let sfu =
+            SfuServer::new(Arc::new(config.clone()), None).expect("Failed to create SfuServer");
+
+        let state = AppState::new(AppStateConfig {
+            db: pool.clone(),
+            redis,
+            config: config.clone(),
+            s3: None,
+            sfu,
+            rate_limiter: None,
+            email: None,
+            oidc_manager: Some(oidc_manager),
+        });
+        let router = create_router(state);
+        let config = Arc::new(config);
+
+        Self {
+            router,
+            pool,
+            config,
+        }
+    }
+
     /// Build an HTTP request with the given method and URI.
     pub fn request(method: Method, uri: &str) -> http::request::Builder {
         Request::builder().method(method).uri(uri)
@@ -515,6 +555,25 @@ pub async fn create_elevated_session(pool: &PgPool, user_id: Uuid) {
     .expect("Failed to create elevated session");
 }
 
+/// Store a re-auth token as a fresh provider sign-in would, and return it.
+pub async fn seed_reauth_token(config: &Config, user_id: Uuid) -> String {
+    use fred::interfaces::KeysInterface;
+
+    let redis = db::create_redis_client(&config.redis_url).await.unwrap();
+    let token = Uuid::new_v4().to_string();
+    redis
+        .set::<(), _, _>(
+            format!("auth:reauth:{}", vc_server::auth::hash_token(&token)),
+            user_id.to_string(),
+            Some(fred::types::Expiration::EX(300)),
+            None,
+            false,
+        )
+        .await
+        .unwrap();
+    token
+}
+
 /// Create a test report and return the report ID.
 pub async fn create_test_report(pool: &PgPool, reporter_id: Uuid, target_id: Uuid) -> Uuid {
     let row: (Uuid,) = sqlx::query_as(
@@ -542,6 +601,37 @@ pub async fn body_to_json(response: Response<Body>) -> serde_json::Value {
     })
 }
 
+/// Read the `error` code from an error response body.
+pub async fn error_code(response: Response<Body>) -> String {
+    body_to_json(response).await["error"]
+        .as_str()
+        .expect("Error response has no code")
+        .to_string()
+}
+
+/// Start a request authenticated with `token`.
+///
+/// Carries a loopback `ConnectInfo`, so routes behind the per-IP rate limiter
+/// accept it too.
+pub fn authed(method: Method, uri: &str, token: &str) -> http::request::Builder {
+    TestApp::request(method, uri)
+        .header("Authorization", format!("Bearer {token}"))
+        .extension(ConnectInfo(SocketAddr::from(([127, 0, 0, 1], 0))))
+}
+
+/// Build an authenticated request with a JSON body.
+pub fn json_request(
+    method: Method,
+    uri: &str,
+    token: &str,
+    body: serde_json::Value,
+) -> Request<Body> {
+    authed(method, uri, token)
+        .header("Content-Type", "application/json")
+        .body(Body::from(body.to_string()))
+        .unwrap()
+}
+
 // ============================================================================
 // Data helpers (guilds, channels, messages)
 // ============================================================================
@@ -0,0 +1,161 @@
+//! Software passkey for driving `WebAuthn` ceremonies in tests.
+//!
+//! Answers the server's challenges the way a platform authenticator would:
+//! a P-256 key with `none` attestation, user presence and verification always
+//! asserted, and a zero signature counter. Only the `localhost` relying party
+//! used by the passkey tests is supported.
+
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use openssl::bn::{BigNum, BigNumContext};
+use openssl::ec::{EcGroup, EcKey};
+use openssl::hash::MessageDigest;
+use openssl::nid::Nid;
+use openssl::pkey::{PKey, Private};
+use openssl::sign::Signer;
+use sha2::{Digest, Sha256};
+
+/// Relying party ID the passkey is bound to.
+const RP_ID: &str = "localhost";
+
+/// Origin the ceremonies claim to run on.
+const ORIGIN: &str = "http://localhost:8080";
+
+/// User present, user verified.
+const FLAGS_UP_UV: u8 = 0x01 | 0x04;
+
+/// Attested credential data included.
+const FLAG_AT: u8 = 0x40;
+
+pub struct SoftPasskey {
+    credential_id: [u8; 16],
+    key: PKey<Private>,
+}
+
+impl SoftPasskey {
+    pub fn new() -> Self {
+        let group = EcGroup::from_curve_name(Nid::X9_62_PRIME256V1).unwrap();
+        let key = EcKey::generate(&group).unwrap();
+        Self {
+            credential_id: rand::random(),
+            key: PKey::from_ec_key(key).unwrap(),
+        }
+    }
+
+    /// Registration response for `options` from `/auth/webauthn/register/start`.
+    pub fn register(&self, options: &serde_json::Value) -> serde_json::Value {
+        let mut auth_data = authenticator_data(FLAGS_UP_UV | FLAG_AT);
+        auth_data.extend_from_slice(&[0; 16]); // AAGUID
+        auth_data.extend_from_slice(
+            &u16::try_from(self.credential_id.len())
+                .unwrap()
+                .to_be_bytes(),
+        );
+        auth_data.extend_from_slice(&self.credential_id);
+        auth_data.extend_from_slice(&self.cose_public_key());
+
+        // {"fmt": "none", "attStmt": {}, "authData": auth_data}
+        let mut attestation = vec![0xa3];
+        cbor_text(&mut attestation, "fmt");
+        cbor_text(&mut attestation, "none");
+        cbor_text(&mut attestation, "attStmt");
+        attestation.push(0xa0);
+        cbor_text(&mut attestation, "authData");
+        cbor_bytes(&mut attestation, &auth_data);
+
+        let client_data = client_data("webauthn.create", options);
+        serde_json::json!({
+            "id": URL_SAFE_NO_PAD.encode(self.credential_id),
+            "rawId": URL_SAFE_NO_PAD.encode(self.credential_id),
+            "type": "public-key",
+            "response": {
+                "attestationObject": URL_SAFE_NO_PAD.encode(attestation),
+                "clientDataJSON": URL_SAFE_NO_PAD.encode(client_data),
+            },
+        })
+    }
+
+    /// Assertion for `options` from `/auth/webauthn/login/start`.
+    pub fn sign(&self, options: &serde_json::Value) -> serde_json::Value {
+        let auth_data = authenticator_data(FLAGS_UP_UV);
+        let client_data = client_data("webauthn.get", options);
+
+        let mut signer = Signer::new(MessageDigest::sha256(), &self.key).unwrap();
+        signer.update(&auth_data).unwrap();
+        signer.update(&Sha256::digest(&client_data)).unwrap();
+        let signature = signer.sign_to_vec().unwrap();
+
+        serde_json::json!({
+            "id": URL_SAFE_NO_PAD.encode(self.credential_id),
+            "rawId": URL_SAFE_NO_PAD.encode(self.credential_id),
+            "type": "public-key",
+            "response": {
+                "authenticatorData": URL_SAFE_NO_PAD.encode(auth_data),
+                "clientDataJSON": URL_SAFE_NO_PAD.encode(client_data),
+                "signature": URL_SAFE_NO_PAD.encode(signature),
+            },
+        })
+    }
+
+    /// ES256 public key as a `COSE_Key` map.
+    fn cose_public_key(&self) -> Vec<u8> {
+        let key = self.key.ec_key().unwrap();
+        let mut x = BigNum::new().unwrap();
+        let mut y = BigNum::new().unwrap();
+        key.public_key()
+            .affine_coordinates(
+                key.group(),
+                &mut x,
+                &mut y,
+                &mut BigNumContext::new().unwrap(),
+            )
+            .unwrap();
+
+        // {1: 2 (EC2), 3: -7 (ES256), -1: 1 (P-256), -2: x, -3: y}
+        let mut cose = vec![0xa5, 0x01, 0x02, 0x03, 0x26, 0x20, 0x01, 0x21];
+        cbor_bytes(&mut cose, &x.to_vec_padded(32).unwrap());
+        cose.push(0x22);
+        cbor_bytes(&mut cose, &y.to_vec_padded(32).unwrap());
+        cose
+    }
+}
+
+/// RP ID hash, `flags` and a zero signature counter.
+fn authenticator_data(flags: u8) -> Vec<u8> {
+    let mut data = Sha256::digest(RP_ID.as_bytes()).to_vec();
+    data.push(flags);
+    data.extend_from_slice(&[0; 4]);
+    data
+}
+
+fn client_data(kind: &str, options: &serde_json::Value) -> Vec<u8> {
+    serde_json::json!({
+        "type": kind,
+        "challenge": options["publicKey"]["challenge"],
+        "origin": ORIGIN,
+        "crossOrigin": false,
+    })
+    .to_string()
+    .into_bytes()
+}
+
+fn cbor_text(out: &mut Vec<u8>, text: &str) {
+    cbor_header(out, 0x60, text.len());
+    out.extend_from_slice(text.as_bytes());
+}
+
+fn cbor_bytes(out: &mut Vec<u8>, bytes: &[u8]) {
+    cbor_header(out, 0x40, bytes.len());
+    out.extend_from_slice(bytes);
+}
+
+fn cbor_header(out: &mut Vec<u8>, major: u8, len: usize) {
+    match u8::try_from(len) {
+        Ok(len) if len < 24 => out.push(major | len),
+        Ok(len) => out.extend_from_slice(&[major | 0x18, len]),
+        Err(_) => {
+            out.push(major | 0x19);
+            out.extend_from_slice(&u16::try_from(len).unwrap().to_be_bytes());
+        }
+    }
+}
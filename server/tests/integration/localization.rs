@@ -0,0 +1,102 @@
+//! HTTP Integration Tests for Server-Side Localization
+//!
+//! Tests `Accept-Language` negotiation for error envelopes and system
+//! message content.
+//!
+//! Run with: `cargo test --test integration localization -- --nocapture`
+
+use axum::body::Body;
+use axum::http::{Method, StatusCode};
+use uuid::Uuid;
+use vc_server::db::{self, MessageType};
+use vc_server::i18n::LocalizedText;
+use vc_server::permissions::GuildPermissions;
+
+use super::helpers::{body_to_json, create_test_user, generate_access_token, TestApp};
+
+fn unauthenticated_get(accept_language: Option<&str>) -> axum::http::Request<Body> {
+    let mut builder = TestApp::request(
+        Method::GET,
+        &format!("/api/messages/channel/{}", Uuid::new_v4()),
+    );
+    if let Some(accept_language) = accept_language {
+        builder = builder.header("Accept-Language", accept_language);
+    }
+    builder.body(Body::empty()).unwrap()
+}
+
+#[tokio::test]
+async fn test_error_envelope_follows_accept_language() {
+    let app = TestApp::new().await;
+
+    let resp = app
+        .oneshot(unauthenticated_get(Some("fi-FI,fi;q=0.9,en;q=0.8")))
+        .await;
+    assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+    assert_eq!(resp.headers()["content-language"], "fi");
+
+    let body = body_to_json(resp).await;
+    assert_eq!(body["error"], "MISSING_AUTH");
+    assert_eq!(body["message"], "Authorization-otsake puuttuu");
+    assert_eq!(body["locale"], "fi");
+}
+
+#[tokio::test]
+async fn test_error_envelope_defaults_to_english() {
+    let app = TestApp::new().await;
+
+    for accept_language in [None, Some("ja")] {
+        let resp = app.oneshot(unauthenticated_get(accept_language)).await;
+        assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+
+        let body = body_to_json(resp).await;
+        assert_eq!(body["message"], "Missing authorization header");
+        assert_eq!(body["locale"], "en");
+    }
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_system_messages_render_in_reader_locale() {
+    let app = TestApp::new().await;
+    let (user_id, _) = create_test_user(&app.pool).await;
+    let token = generate_access_token(&app.config, user_id);
+    let guild_id = super::helpers::create_guild_with_default_role(
+        &app.pool,
+        user_id,
+        GuildPermissions::VIEW_CHANNEL,
+    )
+    .await;
+    let channel_id = super::helpers::create_channel(&app.pool, guild_id, "i18n-test").await;
+
+    let mut guard = app.cleanup_guard();
+    guard.add(move |pool| async move { super::helpers::delete_guild(&pool, guild_id).await });
+    guard.delete_user(user_id);
+
+    let text = LocalizedText::new("system-voice-joined").arg("channel", "Lobby");
+    db::create_system_message(
+        &app.pool,
+        channel_id,
+        user_id,
+        MessageType::SystemVoice,
+        "joined voice channel Lobby",
+        &text,
+    )
+    .await
+    .unwrap();
+
+    let req = TestApp::request(Method::GET, &format!("/api/messages/channel/{channel_id}"))
+        .header("Authorization", format!("Bearer {token}"))
+        .header("Accept-Language", "de-DE")
+        .body(Body::empty())
+        .unwrap();
+    let resp = app.oneshot(req).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let body = body_to_json(resp).await;
+    let message = &body["items"][0];
+    assert_eq!(message["message_type"], "system_voice");
+    assert_eq!(message["content"], "ist dem Sprachkanal Lobby beigetreten");
+    assert_eq!(message["content_locale"], "de");
+    assert_eq!(message["system_text"]["id"], "system-voice-joined");
+    assert_eq!(message["system_text"]["args"]["channel"], "Lobby");
+}
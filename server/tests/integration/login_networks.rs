@@ -0,0 +1,331 @@
+//! HTTP Integration Tests for New Network Sign-In Alerts
+//!
+//! Tests that sign-ins remember their network, that only one from a network
+//! the account has not used before is recorded as `auth.login.new_network`,
+//! and that `NEW_NETWORK_REQUIRE_TOTP` keeps passkeys from replacing the TOTP
+//! code there.
+//!
+//! Run with: `cargo test --test integration login_networks -- --nocapture`
+
+use axum::body::Body;
+use axum::http::{Method, StatusCode};
+use totp_rs::{Algorithm, Secret, TOTP};
+use uuid::Uuid;
+use vc_server::auth::mfa_crypto::encrypt_mfa_secret;
+
+use super::helpers::soft_passkey::SoftPasskey;
+use super::helpers::{
+    body_to_json, create_test_user, error_code, generate_access_token, json_request, shared_config,
+    CleanupGuard, TestApp,
+};
+
+const PASSWORD: &str = "Network-Test-Pass-1";
+
+/// Key for the TOTP secret.
+const ENCRYPTION_KEY: &str = "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f";
+
+const TOTP_SECRET: &str = "JBSWY3DPEHPK3PXPJBSWY3DPEHPK3PXP";
+
+fn post_from(uri: &str, body: &serde_json::Value, ip: [u8; 4]) -> axum::http::Request<Body> {
+    TestApp::request(Method::POST, uri)
+        .header("Content-Type", "application/json")
+        .extension(axum::extract::ConnectInfo(std::net::SocketAddr::from((
+            ip, 40000,
+        ))))
+        .body(Body::from(body.to_string()))
+        .unwrap()
+}
+
+fn login_from(username: &str, ip: [u8; 4]) -> axum::http::Request<Body> {
+    post_from(
+        "/auth/login",
+        &serde_json::json!({ "username": username, "password": PASSWORD }),
+        ip,
+    )
+}
+
+/// A user signing in with `PASSWORD`, removed with its audit trail.
+async fn password_user(app: &TestApp) -> (Uuid, String, CleanupGuard) {
+    let (user_id, username) = create_test_user(&app.pool).await;
+    let mut guard = app.cleanup_guard();
+    guard.delete_user(user_id);
+    guard.add(move |pool| async move {
+        let _ = sqlx::query("DELETE FROM system_audit_log WHERE target_id = $1")
+            .bind(user_id)
+            .execute(&pool)
+            .await;
+    });
+    sqlx::query("UPDATE users SET password_hash = $2 WHERE id = $1")
+        .bind(user_id)
+        .bind(vc_server::auth::hash_password(PASSWORD).unwrap())
+        .execute(&app.pool)
+        .await
+        .unwrap();
+    (user_id, username, guard)
+}
+
+/// App with passkeys configured and `NEW_NETWORK_REQUIRE_TOTP` on.
+async fn require_totp_app() -> TestApp {
+    let mut config = shared_config().await.clone();
+    config.webauthn_rp_id = Some("localhost".to_string());
+    config.webauthn_rp_origin = Some("http://localhost:8080".to_string());
+    config.mfa_encryption_key = Some(ENCRYPTION_KEY.to_string());
+    config.new_network_require_totp = true;
+    TestApp::with_config(config).await
+}
+
+async fn enable_totp(app: &TestApp, user_id: Uuid) {
+    let key = hex::decode(ENCRYPTION_KEY).unwrap();
+    sqlx::query("UPDATE users SET mfa_secret = $2 WHERE id = $1")
+        .bind(user_id)
+        .bind(encrypt_mfa_secret(TOTP_SECRET, &key).unwrap())
+        .execute(&app.pool)
+        .await
+        .unwrap();
+}
+
+fn totp_code(username: &str) -> String {
+    TOTP::new(
+        Algorithm::SHA1,
+        6,
+        1,
+        30,
+        Secret::Encoded(TOTP_SECRET.to_string()).to_bytes().unwrap(),
+        Some("Kaiku".to_string()),
+        username.to_string(),
+    )
+    .unwrap()
+    .generate_current()
+    .unwrap()
+}
+
+/// Register `passkey` on the account.
+async fn register_passkey(app: &TestApp, user_id: Uuid, passkey: &SoftPasskey) {
+    let token = generate_access_token(&app.config, user_id);
+    let resp = app
+        .oneshot(json_request(
+            Method::POST,
+            "/auth/webauthn/register/start",
+            &token,
+            serde_json::json!({ "current_password": PASSWORD }),
+        ))
+        .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let challenge = body_to_json(resp).await;
+
+    let resp = app
+        .oneshot(json_request(
+            Method::POST,
+            "/auth/webauthn/register/finish",
+            &token,
+            serde_json::json!({
+                "challenge_id": challenge["challenge_id"],
+                "name": "Soft passkey",
+                "credential": passkey.register(&challenge["options"]),
+            }),
+        ))
+        .await;
+    assert_eq!(resp.status(), StatusCode::CREATED);
+}
+
+/// A signed passkey assertion for the account, as sent to `/login/finish`
+/// or with a password in place of the TOTP code.
+async fn passkey_assertion(
+    app: &TestApp,
+    username: &str,
+    passkey: &SoftPasskey,
+) -> serde_json::Value {
+    let resp = app
+        .oneshot(post_from(
+            "/auth/webauthn/login/start",
+            &serde_json::json!({ "username": username }),
+            [203, 0, 113, 9],
+        ))
+        .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let challenge = body_to_json(resp).await;
+    serde_json::json!({
+        "challenge_id": challenge["challenge_id"],
+        "credential": passkey.sign(&challenge["options"]),
+    })
+}
+
+async fn new_network_alerts(pool: &sqlx::PgPool, user_id: Uuid) -> Vec<serde_json::Value> {
+    sqlx::query_scalar(
+        r"SELECT details FROM system_audit_log
+          WHERE target_id = $1 AND action = 'auth.login.new_network'
+          ORDER BY created_at",
+    )
+    .bind(user_id)
+    .fetch_all(pool)
+    .await
+    .unwrap()
+}
+
+#[tokio::test]
+async fn test_sign_in_from_new_network_is_recorded() {
+    let app = TestApp::new().await;
+    let (user_id, username, _guard) = password_user(&app).await;
+
+    // The first sign-in is never new, and the same /24 is the same network
+    for ip in [[203, 0, 113, 9], [203, 0, 113, 77]] {
+        let resp = app.oneshot(login_from(&username, ip)).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+    }
+    assert!(new_network_alerts(&app.pool, user_id).await.is_empty());
+
+    let last_ip: Option<String> = sqlx::query_scalar(
+        "SELECT host(last_ip) FROM user_login_networks WHERE user_id = $1 AND network = '203.0.113.0/24'",
+    )
+    .bind(user_id)
+    .fetch_optional(&app.pool)
+    .await
+    .unwrap();
+    assert_eq!(last_ip.as_deref(), Some("203.0.113.77"));
+
+    let resp = app.oneshot(login_from(&username, [198, 51, 100, 5])).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let alerts = new_network_alerts(&app.pool, user_id).await;
+    assert_eq!(alerts.len(), 1);
+    assert_eq!(alerts[0]["method"], "password");
+    assert_eq!(alerts[0]["network"], "198.51.100.0/24");
+
+    // Known from now on
+    let resp = app.oneshot(login_from(&username, [198, 51, 100, 6])).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(new_network_alerts(&app.pool, user_id).await.len(), 1);
+
+    let networks: i64 =
+        sqlx::query_scalar("SELECT COUNT(*) FROM user_login_networks WHERE user_id = $1")
+            .bind(user_id)
+            .fetch_one(&app.pool)
+            .await
+            .unwrap();
+    assert_eq!(networks, 2);
+}
+
+#[tokio::test]
+async fn test_new_network_requires_totp_instead_of_passkey() {
+    let app = require_totp_app().await;
+    let (user_id, username, _guard) = password_user(&app).await;
+    let passkey = SoftPasskey::new();
+    register_passkey(&app, user_id, &passkey).await;
+    enable_totp(&app, user_id).await;
+
+    // The first sign-in is never new, so the passkey is enough
+    let assertion = passkey_assertion(&app, &username, &passkey).await;
+    let resp = app
+        .oneshot(post_from(
+            "/auth/webauthn/login/finish",
+            &assertion,
+            [203, 0, 113, 9],
+        ))
+        .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    // Passkey sign-in from a new network
+    let assertion = passkey_assertion(&app, &username, &passkey).await;
+    let resp = app
+        .oneshot(post_from(
+            "/auth/webauthn/login/finish",
+            &assertion,
+            [198, 51, 100, 5],
+        ))
+        .await;
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+    assert_eq!(error_code(resp).await, "NEW_NETWORK_TOTP_REQUIRED");
+
+    // Password with a passkey in place of the code, from the same network
+    let assertion = passkey_assertion(&app, &username, &passkey).await;
+    let password_and_passkey = serde_json::json!({
+        "username": username,
+        "password": PASSWORD,
+        "passkey": assertion,
+    });
+    let resp = app
+        .oneshot(post_from(
+            "/auth/login",
+            &password_and_passkey,
+            [198, 51, 100, 5],
+        ))
+        .await;
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+    assert_eq!(error_code(resp).await, "NEW_NETWORK_TOTP_REQUIRED");
+
+    let reasons: Vec<String> = sqlx::query_scalar(
+        "SELECT details->>'reason' FROM system_audit_log
+         WHERE target_id = $1 AND action = 'auth.login_failed'",
+    )
+    .bind(user_id)
+    .fetch_all(&app.pool)
+    .await
+    .unwrap();
+    assert_eq!(
+        reasons,
+        ["new_network_totp_required", "new_network_totp_required"]
+    );
+    assert!(new_network_alerts(&app.pool, user_id).await.is_empty());
+
+    // From a known network the passkey still stands in for the code
+    let assertion = passkey_assertion(&app, &username, &passkey).await;
+    let password_and_passkey = serde_json::json!({
+        "username": username,
+        "password": PASSWORD,
+        "passkey": assertion,
+    });
+    let resp = app
+        .oneshot(post_from(
+            "/auth/login",
+            &password_and_passkey,
+            [203, 0, 113, 20],
+        ))
+        .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    // The authenticator code gets through and raises the alert
+    let resp = app
+        .oneshot(post_from(
+            "/auth/login",
+            &serde_json::json!({
+                "username": username,
+                "password": PASSWORD,
+                "mfa_code": totp_code(&username),
+            }),
+            [198, 51, 100, 5],
+        ))
+        .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let alerts = new_network_alerts(&app.pool, user_id).await;
+    assert_eq!(alerts.len(), 1);
+    assert_eq!(alerts[0]["method"], "password");
+}
+
+#[tokio::test]
+async fn test_require_totp_leaves_accounts_without_totp_alone() {
+    let app = require_totp_app().await;
+    let (user_id, username, _guard) = password_user(&app).await;
+    let passkey = SoftPasskey::new();
+    register_passkey(&app, user_id, &passkey).await;
+
+    let resp = app.oneshot(login_from(&username, [203, 0, 113, 9])).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    // Neither a password nor a passkey is challenged from a new network
+    let resp = app.oneshot(login_from(&username, [198, 51, 100, 5])).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let assertion = passkey_assertion(&app, &username, &passkey).await;
+    let resp = app
+        .oneshot(post_from(
+            "/auth/webauthn/login/finish",
+            &assertion,
+            [192, 0, 2, 44],
+        ))
+        .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let alerts = new_network_alerts(&app.pool, user_id).await;
+    assert_eq!(alerts.len(), 2);
+    assert_eq!(alerts[0]["method"], "password");
+    assert_eq!(alerts[1]["method"], "passkey");
+}
@@ -2,42 +2,92 @@ mod helpers;
 
 mod admin_elevation;
 mod admin_reports;
+mod admin_retention;
+mod admin_settings;
+mod admin_users;
+mod admin_voice;
+mod api_docs;
+mod attachment_scanning;
 mod auth;
+mod auth_methods;
+mod billing_entitlements;
 mod blocking;
 mod bot_ecosystem;
 mod bot_intents;
+mod capacity_reports;
+mod channel_attachments;
+mod channel_exports;
+mod channel_feeds;
 mod channel_permissions;
+mod channel_pins;
+mod channel_positions;
+mod channel_webhooks;
 mod channels_http;
+mod cluster;
 mod connectivity_http;
+mod device_link;
 mod dm_http;
+mod drafts;
 mod e2ee_keys;
 mod e2ee_settings;
+mod email_verification;
 mod favorites;
 mod filters_http;
 mod global_search_http;
 mod governance;
+mod guild_audit_log;
+mod guild_autocomplete;
+mod guild_bans;
+mod guild_discovery;
+mod guild_emojis;
 mod guild_invite;
+mod guild_invite_http;
 mod guild_limits;
+mod guild_log_stream;
+mod guild_member_import;
+mod guild_member_list;
+mod guild_member_profiles;
+mod guild_onboarding;
+mod guild_perks;
+mod guild_retention;
+mod guild_roles;
+mod guild_settings;
+mod guild_suspension;
+mod guild_timeouts;
+mod localization;
+mod login_networks;
 mod media_processing;
 mod mention_permission;
 mod messages_http;
 mod oidc;
+mod oidc_callback;
 mod pages;
+mod passkeys;
+mod presence;
+mod profile_customization;
+mod push_devices;
 mod ratelimit;
 mod ratelimit_http;
+mod registration_policies;
 mod reports;
 mod roles_security;
 mod screenshare;
 mod search;
 mod search_http;
+mod settings_versions;
+mod sfu_control;
 mod setup;
 mod setup_concurrent_http;
 mod setup_http;
 mod setup_integration;
+mod system_audit_log;
 mod threads;
 mod upload_limits;
 mod uploads_http;
+mod username_change;
 mod voice_sfu;
 mod webhooks;
 mod websocket_integration;
 mod workspaces;
+mod ws_disconnects;
+mod ws_sessions;
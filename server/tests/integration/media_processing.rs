@@ -55,6 +55,28 @@ fn build_upload_multipart(
     (boundary.to_string(), body)
 }
 
+/// Poll attachment metadata until background image processing has finished.
+async fn wait_for_processing(app: &TestApp, token: &str, attachment_id: &str) -> serde_json::Value {
+    for _ in 0..50 {
+        let req = TestApp::request(
+            Method::GET,
+            &format!("/api/messages/attachments/{attachment_id}"),
+        )
+        .header("Authorization", format!("Bearer {token}"))
+        .body(Body::empty())
+        .unwrap();
+        let resp = app.oneshot(req).await;
+        assert_eq!(resp.status(), 200);
+        let body_bytes = resp.into_body().collect().await.unwrap().to_bytes();
+        let json: serde_json::Value = serde_json::from_slice(&body_bytes).unwrap();
+        if json["processing_status"] != "pending" {
+            return json;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+    }
+    panic!("Attachment {attachment_id} was still pending after 5s");
+}
+
 // ============================================================================
 // Auth & Error Path Tests (no S3 required)
 // ============================================================================
@@ -151,10 +173,17 @@ async fn test_image_upload_generates_metadata() {
     let body_bytes = resp.into_body().collect().await.unwrap().to_bytes();
     let json: serde_json::Value = serde_json::from_slice(&body_bytes).unwrap();
 
-    // Verify media metadata is present in the attachment
+    // Dimensions are read from the header before the upload returns
     let attachment = &json["attachments"][0];
     assert_eq!(attachment["width"], 500, "Should have width");
     assert_eq!(attachment["height"], 400, "Should have height");
+
+    // Blurhash and variants are filled in by the background task
+    let attachment_id = attachment["id"].as_str().unwrap();
+    let attachment = wait_for_processing(&app, &token, attachment_id).await;
+    assert_eq!(attachment["processing_status"], "processed");
+    assert_eq!(attachment["width"], 500);
+    assert_eq!(attachment["height"], 400);
     assert!(
         attachment["blurhash"].is_string(),
         "Should have blurhash string"
@@ -267,6 +296,7 @@ async fn test_variant_download_returns_webp() {
     let body_bytes = resp.into_body().collect().await.unwrap().to_bytes();
     let json: serde_json::Value = serde_json::from_slice(&body_bytes).unwrap();
     let attachment_id = json["attachments"][0]["id"].as_str().unwrap();
+    wait_for_processing(&app, &token, attachment_id).await;
 
     // Download the thumbnail variant
     let req = TestApp::request(
@@ -334,8 +364,10 @@ async fn test_variant_fallback_to_original() {
     let attachment_id = json["attachments"][0]["id"].as_str().unwrap();
 
     // No thumbnail was generated, so variant=thumbnail should fall back to original
+    let attachment = wait_for_processing(&app, &token, attachment_id).await;
+    assert_eq!(attachment["processing_status"], "processed");
     assert!(
-        json["attachments"][0]["thumbnail_url"].is_null(),
+        attachment["thumbnail_url"].is_null(),
         "50px image should not have thumbnail_url"
     );
 
@@ -1,7 +1,7 @@
 //! HTTP Integration Tests for Message CRUD
 //!
 //! Tests message creation, validation, pagination, editing, deletion,
-//! and nonexistent channel handling.
+//! forwarding, and nonexistent channel handling.
 //!
 //! Run with: `cargo test --test integration messages_http -- --nocapture`
 
@@ -321,3 +321,188 @@ async fn test_create_message_nonexistent_channel() {
         "Posting to nonexistent channel should return 404"
     );
 }
+
+// ============================================================================
+// Forwarding
+// ============================================================================
+
+/// Forward a message via the API, returning the raw response.
+async fn forward_message(
+    app: &TestApp,
+    message_id: &str,
+    channel_id: Uuid,
+    token: &str,
+) -> axum::response::Response {
+    let body = serde_json::json!({ "channel_id": channel_id });
+    let req = TestApp::request(Method::POST, &format!("/api/messages/{message_id}/forward"))
+        .header("Authorization", format!("Bearer {token}"))
+        .header("Content-Type", "application/json")
+        .body(Body::from(serde_json::to_string(&body).unwrap()))
+        .unwrap();
+    app.oneshot(req).await
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_forward_message_references_original() {
+    let app = TestApp::new().await;
+    let (user_id, _) = create_test_user(&app.pool).await;
+    let token = generate_access_token(&app.config, user_id);
+    let perms = GuildPermissions::VIEW_CHANNEL | GuildPermissions::SEND_MESSAGES;
+    let guild_id = super::helpers::create_guild_with_default_role(&app.pool, user_id, perms).await;
+    let source = super::helpers::create_channel(&app.pool, guild_id, "fwd-source").await;
+    let target = super::helpers::create_channel(&app.pool, guild_id, "fwd-target").await;
+
+    let mut guard = app.cleanup_guard();
+    guard.add(move |pool| async move { super::helpers::delete_guild(&pool, guild_id).await });
+    guard.delete_user(user_id);
+
+    let original = send_message(&app, source, &token, "Worth sharing").await;
+    let original_id = original["id"].as_str().unwrap();
+
+    let resp = forward_message(&app, original_id, target, &token).await;
+    assert_eq!(resp.status(), 201);
+    let forwarded = body_to_json(resp).await;
+    assert_eq!(forwarded["channel_id"], target.to_string());
+    assert_eq!(forwarded["content"], "Worth sharing");
+    assert_eq!(forwarded["forwarded_from"]["message_id"], original_id);
+    assert_eq!(
+        forwarded["forwarded_from"]["channel_id"],
+        source.to_string()
+    );
+    assert_eq!(
+        forwarded["forwarded_from"]["author"]["id"],
+        user_id.to_string()
+    );
+    assert_eq!(
+        forwarded["forwarded_from"]["created_at"],
+        original["created_at"]
+    );
+
+    // The header survives in the message history
+    let listed = list_messages(&app, target, &token, "").await;
+    let item = &listed["items"][0];
+    assert_eq!(item["forwarded_from"]["message_id"], original_id);
+
+    // Forwarding a forward still points at the first original
+    let resp = forward_message(&app, forwarded["id"].as_str().unwrap(), source, &token).await;
+    assert_eq!(resp.status(), 201);
+    let again = body_to_json(resp).await;
+    assert_eq!(again["forwarded_from"]["message_id"], original_id);
+    assert_eq!(again["forwarded_from"]["channel_id"], source.to_string());
+
+    // Regular messages carry no forward header
+    assert!(original.get("forwarded_from").is_none());
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_forward_message_checks_both_channels() {
+    let app = TestApp::new().await;
+    let (user_id, _) = create_test_user(&app.pool).await;
+    let (other_id, _) = create_test_user(&app.pool).await;
+    let token = generate_access_token(&app.config, user_id);
+    let perms = GuildPermissions::VIEW_CHANNEL | GuildPermissions::SEND_MESSAGES;
+    let guild_id = super::helpers::create_guild_with_default_role(&app.pool, user_id, perms).await;
+    let source = super::helpers::create_channel(&app.pool, guild_id, "fwd-checks").await;
+    // A guild where the user may read but not post
+    let read_only_guild = super::helpers::create_guild_with_default_role(
+        &app.pool,
+        other_id,
+        GuildPermissions::VIEW_CHANNEL,
+    )
+    .await;
+    super::helpers::add_guild_member(&app.pool, read_only_guild, user_id).await;
+    let read_only = super::helpers::create_channel(&app.pool, read_only_guild, "read-only").await;
+    let readable = super::helpers::insert_message(&app.pool, read_only, other_id, "hi").await;
+    // A guild the user is not in
+    let foreign_guild =
+        super::helpers::create_guild_with_default_role(&app.pool, other_id, perms).await;
+    let foreign = super::helpers::create_channel(&app.pool, foreign_guild, "foreign").await;
+    let secret = super::helpers::insert_message(&app.pool, foreign, other_id, "secret").await;
+
+    let mut guard = app.cleanup_guard();
+    guard.add(move |pool| async move {
+        super::helpers::delete_guild(&pool, guild_id).await;
+        super::helpers::delete_guild(&pool, read_only_guild).await;
+        super::helpers::delete_guild(&pool, foreign_guild).await;
+    });
+    guard.delete_user(user_id);
+    guard.delete_user(other_id);
+
+    let msg = send_message(&app, source, &token, "Forward me").await;
+    let msg_id = msg["id"].as_str().unwrap();
+
+    // Destination without SEND_MESSAGES
+    let resp = forward_message(&app, msg_id, read_only, &token).await;
+    assert_eq!(resp.status(), 403);
+
+    // Destination the user cannot see
+    let resp = forward_message(&app, msg_id, foreign, &token).await;
+    assert_eq!(resp.status(), 403);
+
+    // Unknown destination
+    let resp = forward_message(&app, msg_id, Uuid::now_v7(), &token).await;
+    assert_eq!(resp.status(), 404);
+
+    // Source the user cannot see looks missing
+    let resp = forward_message(&app, &secret.to_string(), source, &token).await;
+    assert_eq!(resp.status(), 404);
+
+    // Readable source, allowed destination
+    let resp = forward_message(&app, &readable.to_string(), source, &token).await;
+    assert_eq!(resp.status(), 201);
+
+    // Encrypted messages stay where they were sent
+    sqlx::query("UPDATE messages SET encrypted = true, nonce = 'n' WHERE id = $1")
+        .bind(Uuid::parse_str(msg_id).unwrap())
+        .execute(&app.pool)
+        .await
+        .unwrap();
+    let resp = forward_message(&app, msg_id, source, &token).await;
+    assert_eq!(resp.status(), 400);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_voice_channel_chat_requires_voice_text_chat() {
+    let app = TestApp::new().await;
+    let (owner_id, _) = create_test_user(&app.pool).await;
+    let (member_id, _) = create_test_user(&app.pool).await;
+    let token = generate_access_token(&app.config, member_id);
+    // SEND_MESSAGES alone does not cover voice channel chat
+    let perms = GuildPermissions::VIEW_CHANNEL | GuildPermissions::SEND_MESSAGES;
+    let guild_id = super::helpers::create_guild_with_default_role(&app.pool, owner_id, perms).await;
+    super::helpers::add_guild_member(&app.pool, guild_id, member_id).await;
+    let voice_id: Uuid = sqlx::query_scalar(
+        "INSERT INTO channels (guild_id, name, channel_type) VALUES ($1, 'voice-chat', 'voice') RETURNING id",
+    )
+    .bind(guild_id)
+    .fetch_one(&app.pool)
+    .await
+    .unwrap();
+
+    let mut guard = app.cleanup_guard();
+    guard.add(move |pool| async move { super::helpers::delete_guild(&pool, guild_id).await });
+    guard.delete_user(owner_id);
+    guard.delete_user(member_id);
+
+    let post = || {
+        TestApp::request(Method::POST, &format!("/api/messages/channel/{voice_id}"))
+            .header("Authorization", format!("Bearer {token}"))
+            .header("Content-Type", "application/json")
+            .body(Body::from(r#"{"content":"can you hear me?"}"#))
+            .unwrap()
+    };
+    let resp = app.oneshot(post()).await;
+    assert_eq!(resp.status(), 403);
+
+    sqlx::query("UPDATE guild_roles SET permissions = $2 WHERE guild_id = $1 AND is_default")
+        .bind(guild_id)
+        .bind((perms | GuildPermissions::VOICE_TEXT_CHAT).to_db())
+        .execute(&app.pool)
+        .await
+        .unwrap();
+    let resp = app.oneshot(post()).await;
+    assert_eq!(resp.status(), 201);
+
+    let messages = list_messages(&app, voice_id, &token, "").await;
+    assert_eq!(messages["items"][0]["content"], "can you hear me?");
+}
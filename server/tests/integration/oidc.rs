@@ -125,6 +125,7 @@ fn test_username_from_preferred_username() {
     let info = OidcUserInfo {
         subject: "sub-123".into(),
         email: Some("user@example.com".into()),
+        email_verified: false,
         name: Some("Full Name".into()),
         preferred_username: Some("myuser".into()),
         avatar_url: None,
@@ -137,6 +138,7 @@ fn test_username_preferred_with_hyphens_normalized() {
     let info = OidcUserInfo {
         subject: "sub-123".into(),
         email: None,
+        email_verified: false,
         name: None,
         preferred_username: Some("my-cool-user".into()),
         avatar_url: None,
@@ -150,6 +152,7 @@ fn test_username_preferred_uppercase_normalized() {
     let info = OidcUserInfo {
         subject: "sub-123".into(),
         email: None,
+        email_verified: false,
         name: None,
         preferred_username: Some("MyUser".into()),
         avatar_url: None,
@@ -162,6 +165,7 @@ fn test_username_from_name_fallback() {
     let info = OidcUserInfo {
         subject: "sub-123".into(),
         email: Some("user@example.com".into()),
+        email_verified: false,
         name: Some("John Doe".into()),
         preferred_username: None,
         avatar_url: None,
@@ -174,6 +178,7 @@ fn test_username_from_name_with_special_chars() {
     let info = OidcUserInfo {
         subject: "sub-123".into(),
         email: None,
+        email_verified: false,
         name: Some("María García".into()),
         preferred_username: None,
         avatar_url: None,
@@ -193,6 +198,7 @@ fn test_username_from_email_fallback() {
     let info = OidcUserInfo {
         subject: "sub-123".into(),
         email: Some("jane.doe@example.com".into()),
+        email_verified: false,
         name: None,
         preferred_username: None,
         avatar_url: None,
@@ -205,6 +211,7 @@ fn test_username_from_email_with_plus() {
     let info = OidcUserInfo {
         subject: "sub-123".into(),
         email: Some("user+tag@example.com".into()),
+        email_verified: false,
         name: None,
         preferred_username: None,
         avatar_url: None,
@@ -220,6 +227,7 @@ fn test_username_random_fallback() {
     let info = OidcUserInfo {
         subject: "sub-123".into(),
         email: None,
+        email_verified: false,
         name: None,
         preferred_username: None,
         avatar_url: None,
@@ -237,6 +245,7 @@ fn test_username_too_short_preferred_falls_through() {
     let info = OidcUserInfo {
         subject: "sub-123".into(),
         email: Some("ok@example.com".into()),
+        email_verified: false,
         name: None,
         preferred_username: Some("ab".into()), // Too short (< 3 chars)
         avatar_url: None,
@@ -254,6 +263,7 @@ fn test_username_too_long_preferred_falls_through() {
     let info = OidcUserInfo {
         subject: "sub-123".into(),
         email: Some("long@example.com".into()),
+        email_verified: false,
         name: None,
         preferred_username: Some("a".repeat(33)), // Too long (> 32 chars)
         avatar_url: None,
@@ -316,6 +326,8 @@ fn test_flow_state_serialization_roundtrip() {
         nonce: "abc123nonce".to_string(),
         redirect_uri: "http://127.0.0.1:12345/callback".to_string(),
         created_at: 1706745600,
+        link_user_id: None,
+        reauth_user_id: None,
     };
 
     let json = serde_json::to_string(&state).expect("Serialization should succeed");
@@ -337,6 +349,8 @@ fn test_flow_state_json_format() {
         nonce: "nonce".to_string(),
         redirect_uri: "https://example.com/callback".to_string(),
         created_at: 0,
+        link_user_id: None,
+        reauth_user_id: None,
     };
 
     let json: serde_json::Value = serde_json::to_value(&state).unwrap();
@@ -349,6 +363,49 @@ fn test_flow_state_json_format() {
     assert!(json.get("created_at").is_some());
 }
 
+#[test]
+fn test_flow_state_link_user_roundtrip() {
+    let user_id = uuid::Uuid::new_v4();
+    let state = OidcFlowState {
+        slug: "gitlab".to_string(),
+        pkce_verifier: "verifier".to_string(),
+        nonce: "nonce".to_string(),
+        redirect_uri: "https://example.com/callback".to_string(),
+        created_at: 0,
+        link_user_id: Some(user_id),
+        reauth_user_id: None,
+    };
+
+    let json = serde_json::to_string(&state).unwrap();
+    let deserialized: OidcFlowState = serde_json::from_str(&json).unwrap();
+    assert_eq!(deserialized.link_user_id, Some(user_id));
+
+    // States stored before linking existed still parse as sign-in flows
+    let legacy = r#"{"slug":"gitlab","pkce_verifier":"v","nonce":"n","redirect_uri":"https://example.com/callback","created_at":0}"#;
+    let deserialized: OidcFlowState = serde_json::from_str(legacy).unwrap();
+    assert!(deserialized.link_user_id.is_none());
+}
+
+#[test]
+fn test_flow_state_reauth_user_roundtrip() {
+    let user_id = uuid::Uuid::new_v4();
+    let state = OidcFlowState {
+        slug: "gitlab".to_string(),
+        pkce_verifier: "verifier".to_string(),
+        nonce: "nonce".to_string(),
+        redirect_uri: "https://example.com/callback".to_string(),
+        created_at: 0,
+        link_user_id: None,
+        reauth_user_id: Some(user_id),
+    };
+
+    let json: serde_json::Value = serde_json::to_value(&state).unwrap();
+    assert!(json.get("link_user_id").is_none());
+    let deserialized: OidcFlowState = serde_json::from_value(json).unwrap();
+    assert_eq!(deserialized.reauth_user_id, Some(user_id));
+    assert!(deserialized.link_user_id.is_none());
+}
+
 // ============================================================================
 // Preset Configuration Tests
 // ============================================================================
@@ -425,7 +482,7 @@ async fn test_manager_generate_auth_url_unknown_provider() {
     let manager = OidcProviderManager::new(key);
 
     let result = manager
-        .generate_auth_url("nonexistent", "http://localhost/callback")
+        .generate_auth_url("nonexistent", "http://localhost/callback", false)
         .await;
     assert!(result.is_err());
     assert!(result
@@ -434,6 +491,18 @@ async fn test_manager_generate_auth_url_unknown_provider() {
         .contains("Provider not found"),);
 }
 
+#[tokio::test]
+async fn test_manager_fresh_login_unknown_provider() {
+    let key = test_encryption_key();
+    let manager = OidcProviderManager::new(key);
+
+    assert!(!manager.supports_fresh_login("nonexistent").await);
+    let result = manager
+        .generate_auth_url("nonexistent", "http://localhost/callback", true)
+        .await;
+    assert!(result.is_err());
+}
+
 #[tokio::test]
 async fn test_manager_exchange_code_unknown_provider() {
     let key = test_encryption_key();
@@ -512,6 +581,8 @@ async fn test_oidc_provider_crud() {
             client_id: "test-client-id",
             client_secret_encrypted: &encrypted_secret,
             scopes: "openid profile email",
+            provisioning: "registration_policy",
+            link_by_email: false,
             created_by: user_id,
         },
     )
@@ -527,6 +598,32 @@ async fn test_oidc_provider_crud() {
         .expect("Provider lookup should succeed");
     assert_eq!(found.slug, slug);
     assert_eq!(found.client_id, "test-client-id");
+    assert_eq!(found.provisioning, "registration_policy");
+    assert!(!found.link_by_email);
+
+    // Sign-in policy is kept unless the update sets it
+    let updated = vc_server::db::update_oidc_provider(
+        &pool,
+        vc_server::db::UpdateOidcProviderParams {
+            id: provider.id,
+            display_name: "Test Provider",
+            icon_hint: Some("key"),
+            issuer_url: None,
+            authorization_url: Some("https://example.com/auth"),
+            token_url: Some("https://example.com/token"),
+            userinfo_url: Some("https://example.com/userinfo"),
+            client_id: "test-client-id",
+            client_secret_encrypted: None,
+            scopes: "openid profile email",
+            enabled: true,
+            provisioning: Some("never"),
+            link_by_email: None,
+        },
+    )
+    .await
+    .expect("Provider update should succeed");
+    assert_eq!(updated.provisioning, "never");
+    assert!(!updated.link_by_email);
 
     // Verify secret decryption
     let decrypted = manager
@@ -609,6 +706,8 @@ async fn test_load_providers_from_database() {
             client_id: "client-id",
             client_secret_encrypted: &encrypted_secret,
             scopes: "openid",
+            provisioning: "registration_policy",
+            link_by_email: false,
             created_by: user_id,
         },
     )
@@ -0,0 +1,299 @@
+//! OIDC Callback Integration Tests
+//!
+//! Drives `/auth/oidc/callback` against a plain `OAuth2` provider served from a
+//! local port, with the flow state seeded the way `/auth/oidc/authorize`
+//! stores it.
+//!
+//! Run with: `cargo test --test integration oidc_callback -- --nocapture`
+
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+
+use axum::body::Body;
+use axum::extract::ConnectInfo;
+use axum::http::{header, Method, StatusCode};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use fred::interfaces::KeysInterface;
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+use vc_server::auth::mfa_crypto::encrypt_mfa_secret;
+use vc_server::auth::oidc::{OidcFlowState, OidcProviderManager};
+use vc_server::db;
+
+use super::helpers::{
+    create_test_user, generate_access_token, json_request, shared_config, shared_pool,
+    spawn_test_server, CleanupGuard, TestApp, TestServer,
+};
+
+/// Desktop-app redirect the callback sends its tokens to; never contacted.
+const REDIRECT_URI: &str = "http://127.0.0.1:9/callback";
+
+const USER_AGENT: &str = "oidc-callback-test";
+
+/// Key for the flow state and provider secrets.
+const ENCRYPTION_KEY: &str = "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f";
+
+/// A plain `OAuth2` provider that accepts any code and answers userinfo
+/// requests with whatever the test last set.
+struct FakeProvider {
+    app: TestApp,
+    slug: String,
+    creator_id: Uuid,
+    userinfo: Arc<Mutex<serde_json::Value>>,
+    _server: TestServer,
+}
+
+impl FakeProvider {
+    async fn start() -> Self {
+        let userinfo = Arc::new(Mutex::new(serde_json::Value::Null));
+        let served = userinfo.clone();
+        let router = Router::new()
+            .route(
+                "/token",
+                post(|| async {
+                    Json(serde_json::json!({
+                        "access_token": "fake-access-token",
+                        "token_type": "bearer",
+                    }))
+                }),
+            )
+            .route(
+                "/userinfo",
+                get(move || async move { Json(served.lock().unwrap().clone()) }),
+            );
+        let server = spawn_test_server(router).await;
+
+        let mut config = shared_config().await.clone();
+        config.mfa_encryption_key = Some(ENCRYPTION_KEY.to_string());
+        let manager = OidcProviderManager::new(hex::decode(ENCRYPTION_KEY).unwrap());
+        let pool = shared_pool().await;
+        let (creator_id, _) = create_test_user(pool).await;
+        let slug = format!("fake-{}", &Uuid::new_v4().simple().to_string()[..8]);
+        db::create_oidc_provider(
+            pool,
+            db::CreateOidcProviderParams {
+                slug: &slug,
+                display_name: "Fake Provider",
+                icon_hint: None,
+                provider_type: "custom",
+                issuer_url: None,
+                authorization_url: Some(&format!("{}/authorize", server.url)),
+                token_url: Some(&format!("{}/token", server.url)),
+                userinfo_url: Some(&format!("{}/userinfo", server.url)),
+                client_id: "client-id",
+                client_secret_encrypted: &manager.encrypt_secret("client-secret").unwrap(),
+                scopes: "openid",
+                provisioning: "always",
+                link_by_email: false,
+                created_by: creator_id,
+            },
+        )
+        .await
+        .unwrap();
+        manager.load_providers(pool).await.unwrap();
+
+        Self {
+            app: TestApp::with_oidc_manager(config, manager).await,
+            slug,
+            creator_id,
+            userinfo,
+            _server: server,
+        }
+    }
+
+    /// Remove the provider, its creator and every account it signed in.
+    fn cleanup_guard(&self) -> CleanupGuard {
+        let mut guard = self.app.cleanup_guard();
+        let slug = self.slug.clone();
+        guard.add(move |pool| async move {
+            let _ = sqlx::query(
+                "DELETE FROM system_audit_log WHERE target_id IN
+                     (SELECT user_id FROM user_auth_methods WHERE provider = $1)",
+            )
+            .bind(&slug)
+            .execute(&pool)
+            .await;
+            let _ = sqlx::query(
+                "DELETE FROM users WHERE id IN
+                     (SELECT user_id FROM user_auth_methods WHERE provider = $1)",
+            )
+            .bind(&slug)
+            .execute(&pool)
+            .await;
+            let _ = sqlx::query("DELETE FROM oidc_providers WHERE slug = $1")
+                .bind(&slug)
+                .execute(&pool)
+                .await;
+        });
+        guard.delete_user(self.creator_id);
+        guard
+    }
+
+    /// Sign in from `ip` as the provider account described by `userinfo`.
+    async fn sign_in(
+        &self,
+        userinfo: serde_json::Value,
+        ip: [u8; 4],
+    ) -> axum::http::Response<Body> {
+        *self.userinfo.lock().unwrap() = userinfo;
+        let state = self.seed_flow_state().await;
+        self.app
+            .oneshot(
+                TestApp::request(
+                    Method::GET,
+                    &format!("/auth/oidc/callback?code=fake-code&state={state}"),
+                )
+                .header(header::USER_AGENT, USER_AGENT)
+                .extension(ConnectInfo(SocketAddr::from((ip, 40000))))
+                .body(Body::empty())
+                .unwrap(),
+            )
+            .await
+    }
+
+    /// Store a sign-in flow state as `/auth/oidc/authorize` would and return
+    /// its CSRF state.
+    async fn seed_flow_state(&self) -> String {
+        let csrf_state = Uuid::new_v4().to_string();
+        let flow = OidcFlowState {
+            slug: self.slug.clone(),
+            pkce_verifier: "verifier".to_string(),
+            nonce: "nonce".to_string(),
+            redirect_uri: REDIRECT_URI.to_string(),
+            created_at: chrono::Utc::now().timestamp(),
+            link_user_id: None,
+            reauth_user_id: None,
+        };
+        let key = hex::decode(ENCRYPTION_KEY).unwrap();
+        let encrypted = encrypt_mfa_secret(&serde_json::to_string(&flow).unwrap(), &key).unwrap();
+
+        let redis = db::create_redis_client(&self.app.config.redis_url)
+            .await
+            .unwrap();
+        redis
+            .set::<(), _, _>(
+                format!(
+                    "oidc:state:{}",
+                    hex::encode(Sha256::digest(csrf_state.as_bytes()))
+                ),
+                encrypted,
+                Some(fred::types::Expiration::EX(600)),
+                None,
+                false,
+            )
+            .await
+            .unwrap();
+        csrf_state
+    }
+
+    /// The account signed in as `subject`, with its username.
+    async fn user(&self, subject: &str) -> (Uuid, String) {
+        sqlx::query_as(
+            "SELECT u.id, u.username FROM users u
+             JOIN user_auth_methods m ON m.user_id = u.id
+             WHERE m.external_id = $1",
+        )
+        .bind(format!("{}:{subject}", self.slug))
+        .fetch_one(&self.app.pool)
+        .await
+        .unwrap()
+    }
+}
+
+fn assert_signed_in(resp: &axum::http::Response<Body>) {
+    assert_eq!(resp.status(), StatusCode::TEMPORARY_REDIRECT);
+    let location = resp.headers()[header::LOCATION].to_str().unwrap();
+    assert!(location.starts_with(REDIRECT_URI), "{location}");
+    assert!(location.contains("access_token="), "{location}");
+}
+
+#[tokio::test]
+async fn test_callback_records_sign_in_network() {
+    let provider = FakeProvider::start().await;
+    let _guard = provider.cleanup_guard();
+    let subject = Uuid::new_v4().to_string();
+    let userinfo = serde_json::json!({ "sub": subject });
+
+    let resp = provider.sign_in(userinfo.clone(), [203, 0, 113, 9]).await;
+    assert_signed_in(&resp);
+    let (user_id, _) = provider.user(&subject).await;
+
+    let (ip, user_agent): (Option<String>, Option<String>) =
+        sqlx::query_as("SELECT host(ip_address), user_agent FROM sessions WHERE user_id = $1")
+            .bind(user_id)
+            .fetch_one(&provider.app.pool)
+            .await
+            .unwrap();
+    assert_eq!(ip.as_deref(), Some("203.0.113.9"));
+    assert_eq!(user_agent.as_deref(), Some(USER_AGENT));
+
+    let login_ip: Option<String> = sqlx::query_scalar(
+        "SELECT host(ip_address) FROM system_audit_log
+         WHERE target_id = $1 AND action = 'auth.login'",
+    )
+    .bind(user_id)
+    .fetch_one(&provider.app.pool)
+    .await
+    .unwrap();
+    assert_eq!(login_ip.as_deref(), Some("203.0.113.9"));
+
+    let networks: Vec<String> =
+        sqlx::query_scalar("SELECT network::text FROM user_login_networks WHERE user_id = $1")
+            .bind(user_id)
+            .fetch_all(&provider.app.pool)
+            .await
+            .unwrap();
+    assert_eq!(networks, ["203.0.113.0/24"]);
+
+    // The first sign-in is never new; one from another network is
+    let resp = provider.sign_in(userinfo, [198, 51, 100, 5]).await;
+    assert_signed_in(&resp);
+    let alerts: Vec<serde_json::Value> = sqlx::query_scalar(
+        "SELECT details FROM system_audit_log
+         WHERE target_id = $1 AND action = 'auth.login.new_network'",
+    )
+    .bind(user_id)
+    .fetch_all(&provider.app.pool)
+    .await
+    .unwrap();
+    assert_eq!(alerts.len(), 1);
+    assert_eq!(alerts[0]["method"], "oidc");
+    assert_eq!(alerts[0]["network"], "198.51.100.0/24");
+}
+
+#[tokio::test]
+async fn test_provisioning_skips_held_and_reserved_usernames() {
+    let provider = FakeProvider::start().await;
+    let mut guard = provider.cleanup_guard();
+    let (user_id, old_username) = create_test_user(&provider.app.pool).await;
+    guard.delete_user(user_id);
+
+    // The old name stays held for its previous owner after a rename
+    let token = generate_access_token(&provider.app.config, user_id);
+    let resp = provider
+        .app
+        .oneshot(json_request(
+            Method::PATCH,
+            "/api/me/username",
+            &token,
+            serde_json::json!({ "username": format!("renamed_{}", &old_username[9..]) }),
+        ))
+        .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    for claimed in [old_username.as_str(), "support"] {
+        let subject = Uuid::new_v4().to_string();
+        let resp = provider
+            .sign_in(
+                serde_json::json!({ "sub": subject, "preferred_username": claimed }),
+                [203, 0, 113, 9],
+            )
+            .await;
+        assert_signed_in(&resp);
+
+        let (_, username) = provider.user(&subject).await;
+        assert_ne!(username, claimed);
+        assert!(username.starts_with(&format!("{claimed}_")), "{username}");
+    }
+}
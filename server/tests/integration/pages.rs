@@ -160,6 +160,7 @@ fn test_update_page_request_structure() {
         content: Some("Updated content".to_string()),
         requires_acceptance: Some(true),
         category_id: None,
+        version: None,
     };
 
     assert_eq!(request.title, Some("Updated Title".to_string()));
@@ -0,0 +1,203 @@
+//! Passkey (`WebAuthn`) Integration Tests
+//!
+//! These cover configuration, re-authentication and challenge handling; full
+//! ceremonies against `helpers::soft_passkey` run in `login_networks`.
+//!
+//! Run with: `cargo test --test integration passkeys -- --nocapture`
+
+use std::net::SocketAddr;
+
+use axum::body::Body;
+use axum::extract::ConnectInfo;
+use axum::http::{Method, StatusCode};
+use uuid::Uuid;
+use vc_server::auth::hash_password;
+
+use super::helpers::{
+    body_to_json, create_test_user, error_code, generate_access_token, json_request,
+    seed_reauth_token, shared_config, TestApp,
+};
+
+const PASSWORD: &str = "correct-horse-battery";
+
+async fn passkey_app() -> TestApp {
+    let mut config = shared_config().await.clone();
+    config.webauthn_rp_id = Some("localhost".to_string());
+    config.webauthn_rp_origin = Some("http://localhost:8080".to_string());
+    TestApp::with_config(config).await
+}
+
+/// Unauthenticated JSON request, as sent by the login page.
+fn login_request(uri: &str, body: &serde_json::Value) -> axum::http::Request<Body> {
+    TestApp::request(Method::POST, uri)
+        .header("Content-Type", "application/json")
+        .extension(ConnectInfo(SocketAddr::from(([203, 0, 113, 9], 40000))))
+        .body(Body::from(body.to_string()))
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_passkeys_unavailable_without_configuration() {
+    let app = TestApp::new().await;
+    let (user_id, _) = create_test_user(&app.pool).await;
+    let mut guard = app.cleanup_guard();
+    guard.delete_user(user_id);
+    let token = generate_access_token(&app.config, user_id);
+
+    let resp = app
+        .oneshot(json_request(
+            Method::POST,
+            "/auth/webauthn/register/start",
+            &token,
+            serde_json::json!({}),
+        ))
+        .await;
+    assert_eq!(resp.status(), StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(error_code(resp).await, "PASSKEYS_NOT_CONFIGURED");
+
+    let resp = app
+        .oneshot(
+            TestApp::request(Method::GET, "/api/settings")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+    assert_eq!(body_to_json(resp).await["passkeys_enabled"], false);
+}
+
+#[tokio::test]
+async fn test_registration_requires_reauthentication() {
+    let app = passkey_app().await;
+    let (user_id, username) = create_test_user(&app.pool).await;
+    let mut guard = app.cleanup_guard();
+    guard.delete_user(user_id);
+    sqlx::query("UPDATE users SET password_hash = $1 WHERE id = $2")
+        .bind(hash_password(PASSWORD).unwrap())
+        .bind(user_id)
+        .execute(&app.pool)
+        .await
+        .unwrap();
+    let token = generate_access_token(&app.config, user_id);
+
+    let start = |current_password: &str| {
+        json_request(
+            Method::POST,
+            "/auth/webauthn/register/start",
+            &token,
+            serde_json::json!({ "current_password": current_password }),
+        )
+    };
+
+    let resp = app.oneshot(start("wrong-password")).await;
+    assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+
+    let resp = app.oneshot(start(PASSWORD)).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let challenge = body_to_json(resp).await;
+    assert!(challenge["challenge_id"].as_str().is_some());
+    let public_key = &challenge["options"]["publicKey"];
+    assert_eq!(public_key["rp"]["id"], "localhost");
+    assert_eq!(public_key["user"]["name"], username);
+    assert!(public_key["challenge"].as_str().is_some());
+
+    let resp = app
+        .oneshot(
+            TestApp::request(Method::GET, "/auth/webauthn/credentials")
+                .header("Authorization", format!("Bearer {token}"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(body_to_json(resp).await, serde_json::json!([]));
+}
+
+#[tokio::test]
+async fn test_registration_without_password_or_mfa_requires_fresh_sign_in() {
+    let app = passkey_app().await;
+    let (user_id, _) = create_test_user(&app.pool).await;
+    let mut guard = app.cleanup_guard();
+    guard.delete_user(user_id);
+    sqlx::query("UPDATE users SET password_hash = NULL WHERE id = $1")
+        .bind(user_id)
+        .execute(&app.pool)
+        .await
+        .unwrap();
+    let token = generate_access_token(&app.config, user_id);
+
+    let start = |body: serde_json::Value| {
+        json_request(Method::POST, "/auth/webauthn/register/start", &token, body)
+    };
+
+    // A session alone is not enough
+    let resp = app.oneshot(start(serde_json::json!({}))).await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(error_code(resp).await, "VALIDATION_ERROR");
+
+    let resp = app
+        .oneshot(start(serde_json::json!({ "reauth_token": "not-a-token" })))
+        .await;
+    assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+
+    let reauth_token = seed_reauth_token(&app.config, user_id).await;
+    let resp = app
+        .oneshot(start(serde_json::json!({ "reauth_token": reauth_token })))
+        .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert!(body_to_json(resp).await["challenge_id"].as_str().is_some());
+
+    // The token is single-use
+    let resp = app
+        .oneshot(start(serde_json::json!({ "reauth_token": reauth_token })))
+        .await;
+    assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn test_unknown_challenges_are_rejected() {
+    let app = passkey_app().await;
+    let (user_id, username) = create_test_user(&app.pool).await;
+    let mut guard = app.cleanup_guard();
+    guard.delete_user(user_id);
+    let token = generate_access_token(&app.config, user_id);
+
+    // No passkey registered: nothing to challenge
+    let resp = app
+        .oneshot(login_request(
+            "/auth/webauthn/login/start",
+            &serde_json::json!({ "username": username }),
+        ))
+        .await;
+    assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+
+    let credential = serde_json::json!({
+        "id": "AAAA",
+        "rawId": "AAAA",
+        "type": "public-key",
+        "extensions": {},
+        "response": {
+            "authenticatorData": "AAAA",
+            "clientDataJSON": "AAAA",
+            "signature": "AAAA",
+            "userHandle": null
+        }
+    });
+    let resp = app
+        .oneshot(login_request(
+            "/auth/webauthn/login/finish",
+            &serde_json::json!({ "challenge_id": Uuid::new_v4(), "credential": credential }),
+        ))
+        .await;
+    assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+    assert_eq!(error_code(resp).await, "INVALID_PASSKEY");
+
+    let resp = app
+        .oneshot(json_request(
+            Method::DELETE,
+            &format!("/auth/webauthn/credentials/{}", Uuid::new_v4()),
+            &token,
+            serde_json::json!({}),
+        ))
+        .await;
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+}
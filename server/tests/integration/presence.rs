@@ -0,0 +1,129 @@
+//! Presence Registry Integration Tests
+//!
+//! Run with: `cargo test --test integration presence -- --nocapture`
+
+use axum::body::Body;
+use axum::http::{Method, StatusCode};
+use uuid::Uuid;
+use vc_server::config::Config;
+use vc_server::db::{self, UserStatus};
+use vc_server::presence::registry;
+
+use super::helpers::{body_to_json, create_test_user, generate_access_token, TestApp};
+
+async fn redis() -> fred::prelude::Client {
+    let config = Config::default_for_test();
+    db::create_redis_client(&config.redis_url)
+        .await
+        .expect("Failed to connect to Redis")
+}
+
+#[tokio::test]
+async fn test_presence_counts_connections_across_instances() {
+    let redis = redis().await;
+    let user_id = Uuid::new_v4();
+    let (first, second) = (Uuid::new_v4(), Uuid::new_v4());
+
+    let status = registry::connect(&redis, user_id, first).await.unwrap();
+    assert_eq!(status.as_deref(), Some("online"));
+
+    // A second connection (e.g. on another replica) is not a transition
+    let status = registry::connect(&redis, user_id, second).await.unwrap();
+    assert_eq!(status, None);
+
+    let status = registry::disconnect(&redis, user_id, first).await.unwrap();
+    assert_eq!(status, None, "Still connected through the other session");
+    assert!(registry::has_active_connection(&redis, user_id)
+        .await
+        .unwrap());
+
+    let status = registry::disconnect(&redis, user_id, second).await.unwrap();
+    assert_eq!(status.as_deref(), Some("offline"));
+    assert!(!registry::has_active_connection(&redis, user_id)
+        .await
+        .unwrap());
+}
+
+#[tokio::test]
+async fn test_presence_chosen_status_survives_activity() {
+    let redis = redis().await;
+    let user_id = Uuid::new_v4();
+    let connection_id = Uuid::new_v4();
+
+    registry::connect(&redis, user_id, connection_id)
+        .await
+        .unwrap();
+
+    let status = registry::set_status(&redis, user_id, &UserStatus::Busy)
+        .await
+        .unwrap();
+    assert_eq!(status.as_deref(), Some("busy"));
+
+    // Activity pings do not override a chosen status
+    let status = registry::activity_ping(&redis, user_id).await.unwrap();
+    assert_eq!(status, None);
+
+    let status = registry::set_status(&redis, user_id, &UserStatus::Online)
+        .await
+        .unwrap();
+    assert_eq!(status.as_deref(), Some("online"));
+
+    registry::disconnect(&redis, user_id, connection_id)
+        .await
+        .unwrap();
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_profile_update_persists_status_message() {
+    let app = TestApp::new().await;
+    let (user_id, _) = create_test_user(&app.pool).await;
+    let token = generate_access_token(&app.config, user_id);
+
+    let mut guard = app.cleanup_guard();
+    guard.delete_user(user_id);
+
+    let update = |body: serde_json::Value| {
+        TestApp::request(Method::POST, "/auth/me")
+            .header("Authorization", format!("Bearer {token}"))
+            .header("Content-Type", "application/json")
+            .body(Body::from(body.to_string()))
+            .unwrap()
+    };
+
+    let resp = app
+        .oneshot(update(
+            serde_json::json!({ "status_message": "  In queue " }),
+        ))
+        .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let json = body_to_json(resp).await;
+    assert_eq!(json["updated"], serde_json::json!(["status_message"]));
+
+    let stored: Option<String> =
+        sqlx::query_scalar("SELECT status_message FROM users WHERE id = $1")
+            .bind(user_id)
+            .fetch_one(&app.pool)
+            .await
+            .unwrap();
+    assert_eq!(stored.as_deref(), Some("In queue"));
+
+    let resp = app
+        .oneshot(update(
+            serde_json::json!({ "status_message": "x".repeat(129) }),
+        ))
+        .await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+    let resp = app
+        .oneshot(update(serde_json::json!({ "status_message": null })))
+        .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let stored: Option<String> =
+        sqlx::query_scalar("SELECT status_message FROM users WHERE id = $1")
+            .bind(user_id)
+            .fetch_one(&app.pool)
+            .await
+            .unwrap();
+    assert_eq!(stored, None);
+}
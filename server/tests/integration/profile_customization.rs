@@ -0,0 +1,111 @@
+//! Profile Customization Integration Tests
+//!
+//! Run with: `cargo test --test integration profile_customization -- --nocapture`
+
+use axum::body::Body;
+use axum::http::{Method, StatusCode};
+
+use super::helpers::{body_to_json, create_test_user, generate_access_token, TestApp};
+
+fn update_profile(token: &str, body: serde_json::Value) -> axum::http::Request<Body> {
+    TestApp::request(Method::PATCH, "/api/me/profile")
+        .header("Authorization", format!("Bearer {token}"))
+        .header("Content-Type", "application/json")
+        .body(Body::from(body.to_string()))
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_profile_customization_validates_and_sanitizes() {
+    let app = TestApp::new().await;
+    let (user_id, _) = create_test_user(&app.pool).await;
+    let mut guard = app.cleanup_guard();
+    guard.delete_user(user_id);
+    let token = generate_access_token(&app.config, user_id);
+
+    // Invalid values and empty updates are refused
+    let resp = app
+        .oneshot(update_profile(&token, serde_json::json!({})))
+        .await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    let resp = app
+        .oneshot(update_profile(
+            &token,
+            serde_json::json!({ "accent_color": "red" }),
+        ))
+        .await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    let resp = app
+        .oneshot(update_profile(
+            &token,
+            serde_json::json!({ "pronouns": "x".repeat(41) }),
+        ))
+        .await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+    let resp = app
+        .oneshot(update_profile(
+            &token,
+            serde_json::json!({
+                "about_me": "**Hello** <img src=x onerror=alert(1)> world",
+                "pronouns": " they/them ",
+                "accent_color": "#5865F2",
+            }),
+        ))
+        .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let profile = body_to_json(resp).await;
+    assert_eq!(profile["pronouns"], "they/them");
+    assert_eq!(profile["accent_color"], "#5865f2");
+    let about_me = profile["about_me"].as_str().unwrap();
+    assert!(about_me.starts_with("**Hello**"));
+    assert!(!about_me.contains("<img"));
+
+    // Only the given fields change; null clears
+    let resp = app
+        .oneshot(update_profile(
+            &token,
+            serde_json::json!({ "accent_color": null }),
+        ))
+        .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let resp = app
+        .oneshot(
+            TestApp::request(Method::GET, "/auth/me")
+                .header("Authorization", format!("Bearer {token}"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+    let me = body_to_json(resp).await;
+    assert_eq!(me["pronouns"], "they/them");
+    assert!(me["accent_color"].is_null());
+    assert!(me["banner_url"].is_null());
+}
+
+#[tokio::test]
+async fn test_profile_banner_removal() {
+    let app = TestApp::new().await;
+    let (user_id, _) = create_test_user(&app.pool).await;
+    let mut guard = app.cleanup_guard();
+    guard.delete_user(user_id);
+    let token = generate_access_token(&app.config, user_id);
+
+    sqlx::query("UPDATE users SET banner_url = 'https://cdn.example/banner.png' WHERE id = $1")
+        .bind(user_id)
+        .execute(&app.pool)
+        .await
+        .unwrap();
+
+    let resp = app
+        .oneshot(
+            TestApp::request(Method::DELETE, "/api/me/profile/banner")
+                .header("Authorization", format!("Bearer {token}"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert!(body_to_json(resp).await["banner_url"].is_null());
+}
@@ -0,0 +1,140 @@
+//! Push Device Registration Integration Tests
+//!
+//! Run with: `cargo test --test integration push_devices -- --nocapture`
+
+use axum::body::Body;
+use axum::http::{Method, StatusCode};
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use vc_server::config::Config;
+
+use super::helpers::{body_to_json, create_test_user, generate_access_token, TestApp};
+
+fn push_config() -> Config {
+    let mut config = Config::default_for_test();
+    config.push_vapid_private_key = Some(URL_SAFE_NO_PAD.encode([3u8; 32]));
+    config.push_vapid_subject = Some("mailto:admin@example.com".to_string());
+    config
+}
+
+fn web_push_body(endpoint: &str) -> serde_json::Value {
+    serde_json::json!({
+        "platform": "web_push",
+        "token": endpoint,
+        "p256dh": "BNcRdreALRFXTkOOUHK1EtK2wtaz5Ry4YfYCA_0QTpQtUbVlUls0VJXg7A8u-Ts1XbjhazAkj7I99e8QcYP7DkM",
+        "auth": "tBHItJI5svbpez7KI4CCXg",
+        "device_name": "Firefox on Linux",
+    })
+}
+
+async fn post_device(
+    app: &TestApp,
+    token: &str,
+    body: &serde_json::Value,
+) -> axum::response::Response {
+    let req = TestApp::request(Method::POST, "/api/me/push-devices")
+        .header("Authorization", format!("Bearer {token}"))
+        .header("Content-Type", "application/json")
+        .body(Body::from(body.to_string()))
+        .unwrap();
+    app.oneshot(req).await
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_push_config_reports_enabled_backends() {
+    let app = TestApp::with_config(push_config()).await;
+    let (user_id, _) = create_test_user(&app.pool).await;
+    let token = generate_access_token(&app.config, user_id);
+
+    let mut guard = app.cleanup_guard();
+    guard.delete_user(user_id);
+
+    let req = TestApp::request(Method::GET, "/api/me/push-devices/config")
+        .header("Authorization", format!("Bearer {token}"))
+        .body(Body::empty())
+        .unwrap();
+    let resp = app.oneshot(req).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let json = body_to_json(resp).await;
+    let key = URL_SAFE_NO_PAD
+        .decode(
+            json["vapid_public_key"]
+                .as_str()
+                .expect("VAPID key present"),
+        )
+        .unwrap();
+    assert_eq!(key.len(), 65, "Uncompressed P-256 point");
+    assert_eq!(json["fcm"], false);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_register_list_and_delete_device() {
+    let app = TestApp::with_config(push_config()).await;
+    let (user_id, _) = create_test_user(&app.pool).await;
+    let token = generate_access_token(&app.config, user_id);
+
+    let mut guard = app.cleanup_guard();
+    guard.delete_user(user_id);
+
+    let endpoint = format!("https://push.example.com/send/{user_id}");
+    let resp = post_device(&app, &token, &web_push_body(&endpoint)).await;
+    assert_eq!(resp.status(), StatusCode::CREATED);
+    let created = body_to_json(resp).await;
+    assert_eq!(created["platform"], "web_push");
+    assert_eq!(created["device_name"], "Firefox on Linux");
+
+    // Re-registering the same subscription updates it instead of duplicating
+    let resp = post_device(&app, &token, &web_push_body(&endpoint)).await;
+    assert_eq!(resp.status(), StatusCode::CREATED);
+    assert_eq!(body_to_json(resp).await["id"], created["id"]);
+
+    let req = TestApp::request(Method::GET, "/api/me/push-devices")
+        .header("Authorization", format!("Bearer {token}"))
+        .body(Body::empty())
+        .unwrap();
+    let list = body_to_json(app.oneshot(req).await).await;
+    assert_eq!(list.as_array().unwrap().len(), 1);
+    assert!(list[0].get("token").is_none(), "Tokens are never returned");
+
+    let id = created["id"].as_str().unwrap();
+    let req = TestApp::request(Method::DELETE, &format!("/api/me/push-devices/{id}"))
+        .header("Authorization", format!("Bearer {token}"))
+        .body(Body::empty())
+        .unwrap();
+    assert_eq!(app.oneshot(req).await.status(), StatusCode::NO_CONTENT);
+
+    let req = TestApp::request(Method::DELETE, &format!("/api/me/push-devices/{id}"))
+        .header("Authorization", format!("Bearer {token}"))
+        .body(Body::empty())
+        .unwrap();
+    assert_eq!(app.oneshot(req).await.status(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_register_rejects_disabled_platform_and_bad_subscription() {
+    let app = TestApp::with_config(push_config()).await;
+    let (user_id, _) = create_test_user(&app.pool).await;
+    let token = generate_access_token(&app.config, user_id);
+
+    let mut guard = app.cleanup_guard();
+    guard.delete_user(user_id);
+
+    // FCM is not configured
+    let fcm = serde_json::json!({ "platform": "fcm", "token": "registration-token" });
+    let resp = post_device(&app, &token, &fcm).await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(body_to_json(resp).await["error"], "PLATFORM_DISABLED");
+
+    // Plain-http endpoints are refused
+    let resp = post_device(&app, &token, &web_push_body("http://push.example.com/x")).await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+    // Web Push needs the subscription keys
+    let missing_keys = serde_json::json!({
+        "platform": "web_push",
+        "token": "https://push.example.com/send/abc",
+    });
+    let resp = post_device(&app, &token, &missing_keys).await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+}
@@ -78,6 +78,10 @@ async fn create_test_limiter(redis: fred::clients::Client) -> RateLimiter {
                 requests: 2,
                 window_secs: 60,
             },
+            incoming_webhook: LimitConfig {
+                requests: 30,
+                window_secs: 60,
+            },
             failed_auth: FailedAuthConfig {
                 max_failures: 3,
                 block_duration_secs: 60,
@@ -0,0 +1,248 @@
+//! HTTP Integration Tests for Registration Policies
+//!
+//! Tests the `invite_only` policy with admin-minted registration codes and the
+//! `approval` policy with `/api/admin/registrations`. Both change the
+//! server-wide `registration_policy`, so they share `#[serial(setup)]` with the
+//! setup tests and restore the default on cleanup.
+//!
+//! Run with: `cargo test --test integration registration_policies -- --nocapture`
+
+use axum::body::Body;
+use axum::http::{Method, StatusCode};
+use serial_test::serial;
+use uuid::Uuid;
+
+use super::helpers::{
+    body_to_json, create_elevated_session, create_test_user, generate_access_token, make_admin,
+    TestApp,
+};
+
+const PASSWORD: &str = "Policy-Test-Pass-1";
+
+fn with_ip(builder: axum::http::request::Builder) -> axum::http::request::Builder {
+    builder.extension(axum::extract::ConnectInfo(std::net::SocketAddr::from((
+        [127, 0, 0, 1],
+        0,
+    ))))
+}
+
+fn register(username: &str, code: Option<&str>) -> axum::http::Request<Body> {
+    with_ip(TestApp::request(Method::POST, "/auth/register"))
+        .header("Content-Type", "application/json")
+        .body(Body::from(
+            serde_json::json!({
+                "username": username,
+                "password": PASSWORD,
+                "registration_code": code,
+            })
+            .to_string(),
+        ))
+        .unwrap()
+}
+
+fn login(username: &str) -> axum::http::Request<Body> {
+    with_ip(TestApp::request(Method::POST, "/auth/login"))
+        .header("Content-Type", "application/json")
+        .body(Body::from(
+            serde_json::json!({ "username": username, "password": PASSWORD }).to_string(),
+        ))
+        .unwrap()
+}
+
+fn admin_request(method: Method, uri: &str, token: &str) -> axum::http::request::Builder {
+    with_ip(TestApp::request(method, uri)).header("Authorization", format!("Bearer {token}"))
+}
+
+async fn set_policy(pool: &sqlx::PgPool, policy: &str) {
+    sqlx::query("UPDATE server_config SET value = $1 WHERE key = 'registration_policy'")
+        .bind(serde_json::json!(policy))
+        .execute(pool)
+        .await
+        .expect("Failed to set registration_policy");
+}
+
+async fn user_id_of(pool: &sqlx::PgPool, username: &str) -> Option<Uuid> {
+    sqlx::query_scalar("SELECT id FROM users WHERE username = $1")
+        .bind(username)
+        .fetch_optional(pool)
+        .await
+        .unwrap()
+}
+
+fn unique_username(prefix: &str) -> String {
+    format!("{prefix}_{}", &Uuid::new_v4().simple().to_string()[..12])
+}
+
+#[tokio::test]
+#[serial(setup)]
+async fn test_invite_only_requires_a_registration_code() {
+    let app = TestApp::new().await;
+    let (admin_id, _) = create_test_user(&app.pool).await;
+    let first = unique_username("code_a");
+    let second = unique_username("code_b");
+    let mut guard = app.cleanup_guard();
+    guard.restore_config_defaults();
+    guard.delete_user(admin_id);
+    for username in [first.clone(), second.clone()] {
+        guard.add(move |pool| async move {
+            let _ = sqlx::query("DELETE FROM users WHERE username = $1")
+                .bind(username)
+                .execute(&pool)
+                .await;
+        });
+    }
+    make_admin(&app.pool, admin_id).await;
+    create_elevated_session(&app.pool, admin_id).await;
+    let token = generate_access_token(&app.config, admin_id);
+    set_policy(&app.pool, "invite_only").await;
+
+    let resp = app.oneshot(register(&first, None)).await;
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+    assert_eq!(
+        body_to_json(resp).await["error"],
+        "INVALID_REGISTRATION_CODE"
+    );
+
+    let resp = app
+        .oneshot(
+            admin_request(Method::POST, "/api/admin/registration-codes", &token)
+                .header("Content-Type", "application/json")
+                .body(Body::from(r#"{"max_uses": 1, "note": "test"}"#))
+                .unwrap(),
+        )
+        .await;
+    assert_eq!(resp.status(), StatusCode::CREATED);
+    let code = body_to_json(resp).await;
+    let code_id = code["id"].as_str().unwrap().to_string();
+    guard.add(move |pool| async move {
+        let _ = sqlx::query("DELETE FROM registration_codes WHERE id = $1::uuid")
+            .bind(code_id)
+            .execute(&pool)
+            .await;
+    });
+    let code = code["code"].as_str().unwrap().to_string();
+
+    // Codes are case-insensitive
+    let resp = app
+        .oneshot(register(&first, Some(&code.to_lowercase())))
+        .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    // Used up
+    let resp = app.oneshot(register(&second, Some(&code))).await;
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+    assert!(user_id_of(&app.pool, &second).await.is_none());
+}
+
+#[tokio::test]
+#[serial(setup)]
+async fn test_approval_queue() {
+    let app = TestApp::new().await;
+    let (admin_id, _) = create_test_user(&app.pool).await;
+    let approved = unique_username("appr_a");
+    let rejected = unique_username("appr_b");
+    let mut guard = app.cleanup_guard();
+    guard.restore_config_defaults();
+    guard.delete_user(admin_id);
+    for username in [approved.clone(), rejected.clone()] {
+        guard.add(move |pool| async move {
+            let _ = sqlx::query("DELETE FROM users WHERE username = $1")
+                .bind(username)
+                .execute(&pool)
+                .await;
+        });
+    }
+    make_admin(&app.pool, admin_id).await;
+    create_elevated_session(&app.pool, admin_id).await;
+    let token = generate_access_token(&app.config, admin_id);
+    set_policy(&app.pool, "approval").await;
+
+    for username in [&approved, &rejected] {
+        let resp = app.oneshot(register(username, None)).await;
+        assert_eq!(resp.status(), StatusCode::ACCEPTED);
+        assert_eq!(body_to_json(resp).await["pending_approval"], true);
+    }
+    let approved_id = user_id_of(&app.pool, &approved).await.unwrap();
+    let rejected_id = user_id_of(&app.pool, &rejected).await.unwrap();
+
+    // Pending accounts can neither sign in nor use a token
+    let resp = app.oneshot(login(&approved)).await;
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+    assert_eq!(
+        body_to_json(resp).await["error"],
+        "ACCOUNT_PENDING_APPROVAL"
+    );
+    let pending_token = generate_access_token(&app.config, approved_id);
+    let resp = app
+        .oneshot(
+            TestApp::request(Method::GET, "/auth/me")
+                .header("Authorization", format!("Bearer {pending_token}"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+
+    let resp = app
+        .oneshot(
+            admin_request(Method::GET, "/api/admin/registrations?limit=100", &token)
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let queue = body_to_json(resp).await;
+    let queued: Vec<&str> = queue["items"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|e| e["username"].as_str().unwrap())
+        .collect();
+    assert!(queued.contains(&approved.as_str()));
+    assert!(queued.contains(&rejected.as_str()));
+
+    let resp = app
+        .oneshot(
+            admin_request(
+                Method::POST,
+                &format!("/api/admin/registrations/{approved_id}/approve"),
+                &token,
+            )
+            .body(Body::empty())
+            .unwrap(),
+        )
+        .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(body_to_json(resp).await["status"], "approved");
+
+    let resp = app.oneshot(login(&approved)).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let resp = app
+        .oneshot(
+            admin_request(
+                Method::POST,
+                &format!("/api/admin/registrations/{rejected_id}/reject"),
+                &token,
+            )
+            .body(Body::empty())
+            .unwrap(),
+        )
+        .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert!(user_id_of(&app.pool, &rejected).await.is_none());
+
+    // Approved accounts are no longer in the queue
+    let resp = app
+        .oneshot(
+            admin_request(
+                Method::POST,
+                &format!("/api/admin/registrations/{approved_id}/approve"),
+                &token,
+            )
+            .body(Body::empty())
+            .unwrap(),
+        )
+        .await;
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+}
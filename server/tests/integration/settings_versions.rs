@@ -0,0 +1,134 @@
+//! HTTP integration tests for optimistic concurrency on guild, channel, role
+//! and page updates.
+//!
+//! Run with: `cargo test --test integration settings_versions -- --nocapture`
+
+use axum::body::Body;
+use axum::http::{Method, StatusCode};
+use serde_json::json;
+use uuid::Uuid;
+use vc_server::permissions::GuildPermissions;
+
+use super::helpers::{
+    body_to_json, create_channel, create_guild_with_default_role, create_test_user, delete_guild,
+    generate_access_token, TestApp,
+};
+
+fn request(
+    method: Method,
+    path: &str,
+    token: &str,
+    body: &serde_json::Value,
+) -> axum::http::Request<Body> {
+    TestApp::request(method, path)
+        .header("authorization", format!("Bearer {token}"))
+        .header("content-type", "application/json")
+        .body(Body::from(body.to_string()))
+        .unwrap()
+}
+
+/// PATCH `path` twice from the same starting version: the first update wins,
+/// the second gets 409 with the current state, and an update without a
+/// version still applies.
+async fn assert_stale_update_conflicts(app: &TestApp, path: &str, token: &str, field: &str) {
+    let resp = app
+        .oneshot(request(
+            Method::PATCH,
+            path,
+            token,
+            &json!({ field: "first-edit", "version": 1 }),
+        ))
+        .await;
+    assert_eq!(resp.status(), StatusCode::OK, "first update of {path}");
+    let updated = body_to_json(resp).await;
+    assert_eq!(updated["version"], 2);
+
+    let resp = app
+        .oneshot(request(
+            Method::PATCH,
+            path,
+            token,
+            &json!({ field: "stale-edit", "version": 1 }),
+        ))
+        .await;
+    assert_eq!(
+        resp.status(),
+        StatusCode::CONFLICT,
+        "stale update of {path}"
+    );
+    let body = body_to_json(resp).await;
+    assert_eq!(body["error"], "VERSION_CONFLICT");
+    assert_eq!(body["current"][field], "first-edit");
+    assert_eq!(body["current"]["version"], 2);
+
+    let resp = app
+        .oneshot(request(
+            Method::PATCH,
+            path,
+            token,
+            &json!({ field: "unchecked-edit" }),
+        ))
+        .await;
+    assert_eq!(
+        resp.status(),
+        StatusCode::OK,
+        "unversioned update of {path}"
+    );
+    let body = body_to_json(resp).await;
+    assert_eq!(body[field], "unchecked-edit");
+    assert_eq!(body["version"], 3);
+}
+
+#[tokio::test]
+async fn test_stale_updates_conflict() {
+    let app = TestApp::new().await;
+    let (owner_id, _) = create_test_user(&app.pool).await;
+    let guild_id =
+        create_guild_with_default_role(&app.pool, owner_id, GuildPermissions::VIEW_CHANNEL).await;
+    let mut guard = app.cleanup_guard();
+    guard.add(move |pool| async move { delete_guild(&pool, guild_id).await });
+    guard.delete_user(owner_id);
+    let channel_id = create_channel(&app.pool, guild_id, "versioned").await;
+    let token = generate_access_token(&app.config, owner_id);
+
+    let role_id = Uuid::now_v7();
+    sqlx::query(
+        "INSERT INTO guild_roles (id, guild_id, name, permissions, position) VALUES ($1, $2, 'mods', 0, 1)",
+    )
+    .bind(role_id)
+    .bind(guild_id)
+    .execute(&app.pool)
+    .await
+    .unwrap();
+
+    let resp = app
+        .oneshot(request(
+            Method::POST,
+            &format!("/api/guilds/{guild_id}/pages"),
+            &token,
+            &json!({ "title": "Rules", "content": "Be nice" }),
+        ))
+        .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let page = body_to_json(resp).await;
+    assert_eq!(page["version"], 1);
+    let page_id = page["id"].as_str().unwrap().to_string();
+
+    assert_stale_update_conflicts(&app, &format!("/api/guilds/{guild_id}"), &token, "name").await;
+    assert_stale_update_conflicts(&app, &format!("/api/channels/{channel_id}"), &token, "name")
+        .await;
+    assert_stale_update_conflicts(
+        &app,
+        &format!("/api/guilds/{guild_id}/roles/{role_id}"),
+        &token,
+        "name",
+    )
+    .await;
+    assert_stale_update_conflicts(
+        &app,
+        &format!("/api/guilds/{guild_id}/pages/{page_id}"),
+        &token,
+        "content",
+    )
+    .await;
+}
@@ -43,12 +43,14 @@ async fn test_first_user_detection_works() {
     // Create user (note: admin grant happens in registration handler, not here)
     let user = sqlx::query_as!(
         db::User,
-        r#"INSERT INTO users (username, display_name, password_hash, auth_method)
-           VALUES ($1, $2, $3, 'local')
+        r#"INSERT INTO users (username, display_name, password_hash)
+           VALUES ($1, $2, $3)
            RETURNING id, username, display_name, email, password_hash,
-                     auth_method as "auth_method: _", external_id, avatar_url,
+                     avatar_url, banner_url, about_me, pronouns, accent_color,
                      status as "status: _", mfa_secret, is_bot, bot_owner_id,
                      deletion_requested_at, deletion_scheduled_at,
+                     suspended_at, suspension_reason, tokens_revoked_at,
+                     pending_approval_at, email_verified_at,
                      created_at, updated_at"#,
         username,
         "First User",
@@ -141,12 +143,14 @@ async fn test_server_config_operations() {
 
     let test_user = sqlx::query_as!(
         db::User,
-        r#"INSERT INTO users (username, display_name, password_hash, auth_method)
-           VALUES ($1, $2, $3, 'local')
+        r#"INSERT INTO users (username, display_name, password_hash)
+           VALUES ($1, $2, $3)
            RETURNING id, username, display_name, email, password_hash,
-                     auth_method as "auth_method: _", external_id, avatar_url,
+                     avatar_url, banner_url, about_me, pronouns, accent_color,
                      status as "status: _", mfa_secret, is_bot, bot_owner_id,
                      deletion_requested_at, deletion_scheduled_at,
+                     suspended_at, suspension_reason, tokens_revoked_at,
+                     pending_approval_at, email_verified_at,
                      created_at, updated_at"#,
         test_username,
         "Config Test",
@@ -214,12 +218,14 @@ async fn test_mark_setup_complete() {
 
     let test_user = sqlx::query_as!(
         db::User,
-        r#"INSERT INTO users (username, display_name, password_hash, auth_method)
-           VALUES ($1, $2, $3, 'local')
+        r#"INSERT INTO users (username, display_name, password_hash)
+           VALUES ($1, $2, $3)
            RETURNING id, username, display_name, email, password_hash,
-                     auth_method as "auth_method: _", external_id, avatar_url,
+                     avatar_url, banner_url, about_me, pronouns, accent_color,
                      status as "status: _", mfa_secret, is_bot, bot_owner_id,
                      deletion_requested_at, deletion_scheduled_at,
+                     suspended_at, suspension_reason, tokens_revoked_at,
+                     pending_approval_at, email_verified_at,
                      created_at, updated_at"#,
         test_username,
         "Setup Test",
@@ -329,12 +335,14 @@ async fn test_config_validation() {
 
     let test_user = sqlx::query_as!(
         db::User,
-        r#"INSERT INTO users (username, display_name, password_hash, auth_method)
-           VALUES ($1, $2, $3, 'local')
+        r#"INSERT INTO users (username, display_name, password_hash)
+           VALUES ($1, $2, $3)
            RETURNING id, username, display_name, email, password_hash,
-                     auth_method as "auth_method: _", external_id, avatar_url,
+                     avatar_url, banner_url, about_me, pronouns, accent_color,
                      status as "status: _", mfa_secret, is_bot, bot_owner_id,
                      deletion_requested_at, deletion_scheduled_at,
+                     suspended_at, suspension_reason, tokens_revoked_at,
+                     pending_approval_at, email_verified_at,
                      created_at, updated_at"#,
         test_username,
         "Validation Test",
@@ -444,8 +452,8 @@ async fn test_second_user_not_admin() {
 
     // Create first user
     sqlx::query(
-        "INSERT INTO users (username, display_name, password_hash, auth_method)
-         VALUES ($1, 'User 1', 'hash', 'local')",
+        "INSERT INTO users (username, display_name, password_hash)
+         VALUES ($1, 'User 1', 'hash')",
     )
     .bind(&first_username)
     .execute(&mut *tx)
@@ -455,12 +463,14 @@ async fn test_second_user_not_admin() {
     // Create second user
     let user2 = sqlx::query_as!(
         db::User,
-        r#"INSERT INTO users (username, display_name, password_hash, auth_method)
-           VALUES ($1, $2, $3, 'local')
+        r#"INSERT INTO users (username, display_name, password_hash)
+           VALUES ($1, $2, $3)
            RETURNING id, username, display_name, email, password_hash,
-                     auth_method as "auth_method: _", external_id, avatar_url,
+                     avatar_url, banner_url, about_me, pronouns, accent_color,
                      status as "status: _", mfa_secret, is_bot, bot_owner_id,
                      deletion_requested_at, deletion_scheduled_at,
+                     suspended_at, suspension_reason, tokens_revoked_at,
+                     pending_approval_at, email_verified_at,
                      created_at, updated_at"#,
         second_username,
         "User 2",
@@ -53,12 +53,14 @@ async fn test_first_user_receives_admin_sequential(pool: PgPool) {
     // Create first user
     let user1 = sqlx::query_as!(
         db::User,
-        r#"INSERT INTO users (username, display_name, password_hash, auth_method)
-           VALUES ($1, $2, $3, 'local')
+        r#"INSERT INTO users (username, display_name, password_hash)
+           VALUES ($1, $2, $3)
            RETURNING id, username, display_name, email, password_hash,
-                     auth_method as "auth_method: _", external_id, avatar_url,
+                     avatar_url, banner_url, about_me, pronouns, accent_color,
                      status as "status: _", mfa_secret, is_bot, bot_owner_id,
                      deletion_requested_at, deletion_scheduled_at,
+                     suspended_at, suspension_reason, tokens_revoked_at,
+                     pending_approval_at, email_verified_at,
                      created_at, updated_at"#,
         username1,
         "First User",
@@ -115,12 +117,14 @@ async fn test_first_user_receives_admin_sequential(pool: PgPool) {
     // Create second user (NO admin grant since user_count > 0)
     let user2 = sqlx::query_as!(
         db::User,
-        r#"INSERT INTO users (username, display_name, password_hash, auth_method)
-           VALUES ($1, $2, $3, 'local')
+        r#"INSERT INTO users (username, display_name, password_hash)
+           VALUES ($1, $2, $3)
            RETURNING id, username, display_name, email, password_hash,
-                     auth_method as "auth_method: _", external_id, avatar_url,
+                     avatar_url, banner_url, about_me, pronouns, accent_color,
                      status as "status: _", mfa_secret, is_bot, bot_owner_id,
                      deletion_requested_at, deletion_scheduled_at,
+                     suspended_at, suspension_reason, tokens_revoked_at,
+                     pending_approval_at, email_verified_at,
                      created_at, updated_at"#,
         username2,
         "Second User",
@@ -194,12 +198,14 @@ async fn test_concurrent_registrations_only_one_gets_admin(pool: PgPool) {
             // Create user
             let user = sqlx::query_as!(
                 db::User,
-                r#"INSERT INTO users (username, display_name, password_hash, auth_method)
-                   VALUES ($1, $2, $3, 'local')
+                r#"INSERT INTO users (username, display_name, password_hash)
+                   VALUES ($1, $2, $3)
                    RETURNING id, username, display_name, email, password_hash,
-                             auth_method as "auth_method: _", external_id, avatar_url,
+                             avatar_url, banner_url, about_me, pronouns, accent_color,
                              status as "status: _", mfa_secret, is_bot, bot_owner_id,
                              deletion_requested_at, deletion_scheduled_at,
+                             suspended_at, suspension_reason, tokens_revoked_at,
+                             pending_approval_at, email_verified_at,
                              created_at, updated_at"#,
                 username.clone(),
                 format!("User {}", i),
@@ -0,0 +1,355 @@
+//! SFU Control Protocol Integration Tests
+//!
+//! Serves a media node's `SfuControl` service on a local port and drives it
+//! from a replica configured with `SFU_REMOTE_NODES`, the way the `sfu-node`
+//! binary and the server replicas talk in production.
+//!
+//! Run with: `cargo test --test integration sfu_control -- --nocapture`
+
+use std::net::Shutdown;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use chrono::Utc;
+use fred::prelude::*;
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::mpsc;
+use tonic::Code;
+use uuid::Uuid;
+use vc_server::cluster::{registry, rooms};
+use vc_server::db;
+use vc_server::permissions::GuildPermissions;
+use vc_server::voice::control::{ControlClient, ControlService};
+use vc_server::voice::remote::RemoteNodes;
+use vc_server::voice::{ws_handler, SfuServer};
+use vc_server::ws::{ClientEvent, ServerEvent};
+
+use super::helpers::{
+    add_guild_member, create_guild_with_default_role, create_test_user, delete_guild, delete_user,
+    shared_config, shared_pool, CleanupGuard,
+};
+
+const TOKEN: &str = "sfu-control-test-token";
+
+/// A media node serving its control protocol on a local port.
+struct MediaNode {
+    node_id: String,
+    url: String,
+    sfu: Arc<SfuServer>,
+    server: tokio::task::JoinHandle<Result<(), tonic::transport::Error>>,
+    /// Accepted connections, kept to cut them when the node drops.
+    connections: Arc<Mutex<Vec<std::net::TcpStream>>>,
+}
+
+impl MediaNode {
+    async fn start(region: &str) -> Self {
+        let mut config = shared_config().await.clone();
+        config.node_id = format!("sfu-node-{}", Uuid::new_v4().simple());
+        config.node_region = Some(region.to_string());
+        let node_id = config.node_id.clone();
+        let sfu = Arc::new(SfuServer::new(Arc::new(config), None).unwrap());
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let connections = Arc::new(Mutex::new(Vec::new()));
+        let incoming = futures::stream::unfold(
+            (listener, connections.clone()),
+            |(listener, connections)| async move {
+                let accepted = listener.accept().await.and_then(|(stream, _)| {
+                    let stream = stream.into_std()?;
+                    connections.lock().unwrap().push(stream.try_clone()?);
+                    TcpStream::from_std(stream)
+                });
+                Some((accepted, (listener, connections)))
+            },
+        );
+        let server = tokio::spawn(
+            tonic::transport::Server::builder()
+                .add_service(ControlService::new(sfu.clone(), TOKEN))
+                .serve_with_incoming(incoming),
+        );
+
+        Self {
+            node_id,
+            url,
+            sfu,
+            server,
+            connections,
+        }
+    }
+
+    /// Take the node off the network: stop accepting and cut every open
+    /// connection, as when its machine goes away.
+    fn drop_off(&self) {
+        self.server.abort();
+        for connection in self.connections.lock().unwrap().drain(..) {
+            let _ = connection.shutdown(Shutdown::Both);
+        }
+    }
+}
+
+/// A replica driving `node` through the control protocol.
+struct Replica {
+    node_id: String,
+    sfu: Arc<SfuServer>,
+    redis: Client,
+    remote: tokio::task::JoinHandle<()>,
+}
+
+impl Replica {
+    async fn start(node: &MediaNode) -> Self {
+        let mut config = shared_config().await.clone();
+        config.node_id = format!("replica-{}", Uuid::new_v4().simple());
+        config.sfu_remote_nodes = vec![node.url.clone()];
+        config.sfu_control_token = Some(TOKEN.to_string());
+        let redis = db::create_redis_client(&config.redis_url)
+            .await
+            .expect("Failed to connect to Redis");
+        let sfu = Arc::new(SfuServer::new(Arc::new(config.clone()), None).unwrap());
+        let remote = RemoteNodes::spawn(sfu.remote_nodes(), redis.clone(), &config)
+            .expect("Remote node not configured");
+
+        // The first status poll registers the node and opens its events
+        let mut connected = false;
+        for _ in 0..50 {
+            if sfu.remote_nodes().get(&node.node_id).is_some()
+                && registry::is_alive(&redis, &node.node_id).await.unwrap()
+            {
+                connected = true;
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+        assert!(connected, "Replica never connected to the media node");
+
+        Self {
+            node_id: config.node_id,
+            sfu,
+            redis,
+            remote,
+        }
+    }
+
+    async fn send(
+        &self,
+        user_id: Uuid,
+        event: ClientEvent,
+        tx: &mpsc::Sender<ServerEvent>,
+    ) -> Result<(), vc_server::voice::VoiceError> {
+        ws_handler::handle_voice_event(
+            &self.sfu,
+            shared_pool().await,
+            &self.redis,
+            user_id,
+            event,
+            tx,
+        )
+        .await
+    }
+
+    async fn join(&self, user_id: Uuid, channel_id: Uuid) -> mpsc::Receiver<ServerEvent> {
+        let (tx, mut rx) = mpsc::channel(100);
+        self.send(
+            user_id,
+            ClientEvent::VoiceJoin {
+                channel_id,
+                node_rtts: std::collections::HashMap::new(),
+                noise_suppression: false,
+            },
+            &tx,
+        )
+        .await
+        .unwrap();
+
+        // The offer reaches the participant wherever the room is hosted
+        let offer = tokio::time::timeout(Duration::from_secs(5), async {
+            loop {
+                match rx.recv().await {
+                    Some(ServerEvent::VoiceOffer { channel_id: id, .. }) => break id,
+                    Some(_) => {}
+                    None => panic!("Events closed before the offer"),
+                }
+            }
+        })
+        .await
+        .expect("No voice offer");
+        assert_eq!(offer, channel_id);
+        rx
+    }
+
+    /// Stop driving the node and remove both from the registry.
+    fn cleanup(&self, guard: &mut CleanupGuard, node: &MediaNode) {
+        let remote = self.remote.abort_handle();
+        let (redis, ids) = (
+            self.redis.clone(),
+            [self.node_id.clone(), node.node_id.clone()],
+        );
+        guard.add(move |pool| async move {
+            remote.abort();
+            for id in ids {
+                let _ = registry::deregister(&pool, &redis, &id).await;
+            }
+        });
+    }
+}
+
+/// A guild preferring `region` with a voice channel, owned by a new user.
+///
+/// Returns the guild, the channel and the owner.
+async fn voice_channel(guard: &mut CleanupGuard, region: &str) -> (Uuid, Uuid, Uuid) {
+    let pool = shared_pool().await;
+    let (owner_id, _) = create_test_user(pool).await;
+    let guild_id =
+        create_guild_with_default_role(pool, owner_id, GuildPermissions::EVERYONE_DEFAULT).await;
+    sqlx::query("UPDATE guilds SET voice_region = $2 WHERE id = $1")
+        .bind(guild_id)
+        .bind(region)
+        .execute(pool)
+        .await
+        .unwrap();
+    let channel_id = Uuid::now_v7();
+    sqlx::query(
+        "INSERT INTO channels (id, guild_id, name, channel_type) VALUES ($1, $2, 'Lounge', 'voice')",
+    )
+    .bind(channel_id)
+    .bind(guild_id)
+    .execute(pool)
+    .await
+    .expect("Failed to create voice channel");
+    guard.add(move |pool| async move {
+        delete_guild(&pool, guild_id).await;
+        delete_user(&pool, owner_id).await;
+    });
+    (guild_id, channel_id, owner_id)
+}
+
+fn test_region() -> String {
+    format!("test-{}", &Uuid::new_v4().simple().to_string()[..8])
+}
+
+#[tokio::test]
+async fn test_control_requires_token() {
+    let node = MediaNode::start(&test_region()).await;
+
+    let client = ControlClient::new(&node.url, "wrong-token").unwrap();
+    let status = client.status().await.unwrap_err();
+    assert_eq!(status.code(), Code::Unauthenticated);
+    let status = client.events("replica").await.unwrap_err();
+    assert_eq!(status.code(), Code::Unauthenticated);
+
+    let client = ControlClient::new(&node.url, TOKEN).unwrap();
+    let status = client.status().await.unwrap();
+    assert_eq!(status.node_id, node.node_id);
+    assert_eq!(status.voice_rooms, 0);
+
+    node.drop_off();
+}
+
+#[tokio::test]
+async fn test_replica_joins_and_leaves_through_remote_node() {
+    let region = test_region();
+    let node = MediaNode::start(&region).await;
+    let replica = Replica::start(&node).await;
+    let mut guard = CleanupGuard::new(shared_pool().await.clone());
+    replica.cleanup(&mut guard, &node);
+    let (_, channel_id, user_id) = voice_channel(&mut guard, &region).await;
+
+    // The guild prefers the node's region, so the room is hosted there
+    let _events = replica.join(user_id, channel_id).await;
+    assert!(node.sfu.get_room(channel_id).await.is_some());
+    assert!(replica.sfu.get_room(channel_id).await.is_none());
+    assert_eq!(
+        rooms::room_owner(&replica.redis, channel_id).await.unwrap(),
+        Some(node.node_id.clone())
+    );
+    assert!(replica
+        .sfu
+        .remote_nodes()
+        .session(channel_id, user_id)
+        .is_some());
+
+    let (tx, _rx) = mpsc::channel(1);
+    replica
+        .send(user_id, ClientEvent::VoiceLeave { channel_id }, &tx)
+        .await
+        .unwrap();
+    assert!(node.sfu.get_room(channel_id).await.is_none());
+    assert_eq!(
+        rooms::room_owner(&replica.redis, channel_id).await.unwrap(),
+        None
+    );
+    assert!(replica
+        .sfu
+        .remote_nodes()
+        .session(channel_id, user_id)
+        .is_none());
+
+    node.drop_off();
+}
+
+#[tokio::test]
+async fn test_room_fails_over_when_remote_node_drops() {
+    let region = test_region();
+    let node = MediaNode::start(&region).await;
+    let replica = Replica::start(&node).await;
+    let mut guard = CleanupGuard::new(shared_pool().await.clone());
+    replica.cleanup(&mut guard, &node);
+    let (guild_id, channel_id, owner_id) = voice_channel(&mut guard, &region).await;
+    let _events = replica.join(owner_id, channel_id).await;
+    assert_eq!(
+        rooms::room_owner(&replica.redis, channel_id).await.unwrap(),
+        Some(node.node_id.clone())
+    );
+
+    node.drop_off();
+
+    // The replica stops routing to the node as soon as its events close
+    let mut disconnected = false;
+    for _ in 0..50 {
+        if replica.sfu.remote_nodes().get(&node.node_id).is_none() {
+            disconnected = true;
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    assert!(disconnected, "Replica still routes to the dropped node");
+
+    // Its registry entry lapses once heartbeats stop; expire it rather than
+    // wait out NODE_TTL
+    replica
+        .redis
+        .zadd::<(), _, _>(
+            "cluster:nodes",
+            None,
+            None,
+            false,
+            false,
+            (
+                (Utc::now().timestamp_millis() - 1_000) as f64,
+                node.node_id.as_str(),
+            ),
+        )
+        .await
+        .unwrap();
+    assert!(!registry::is_alive(&replica.redis, &node.node_id)
+        .await
+        .unwrap());
+
+    // The next participant reopens the room on the replica itself
+    let pool = shared_pool().await;
+    let (user_id, _) = create_test_user(pool).await;
+    guard.delete_user(user_id);
+    add_guild_member(pool, guild_id, user_id).await;
+    let _events = replica.join(user_id, channel_id).await;
+    assert!(replica.sfu.get_room(channel_id).await.is_some());
+    assert_eq!(
+        rooms::room_owner(&replica.redis, channel_id).await.unwrap(),
+        Some(replica.node_id.clone())
+    );
+
+    let (tx, _rx) = mpsc::channel(1);
+    replica
+        .send(user_id, ClientEvent::VoiceLeave { channel_id }, &tx)
+        .await
+        .unwrap();
+}
@@ -0,0 +1,167 @@
+//! HTTP Integration Tests for the System Audit Log
+//!
+//! Tests that sign-ins and failed sign-ins are recorded with IP and user
+//! agent, and the cursor pagination and filters of `/api/admin/audit-log`.
+//!
+//! Run with: `cargo test --test integration system_audit_log -- --nocapture`
+
+use axum::body::Body;
+use axum::http::{Method, StatusCode};
+use uuid::Uuid;
+use vc_server::permissions::queries::{write_audit_event, AuditEvent};
+
+use super::helpers::{body_to_json, create_test_user, generate_access_token, make_admin, TestApp};
+
+const PASSWORD: &str = "Audit-Test-Pass-1";
+
+fn login(username: &str, password: &str) -> axum::http::Request<Body> {
+    TestApp::request(Method::POST, "/auth/login")
+        .header("Content-Type", "application/json")
+        .header("User-Agent", "audit-test/1.0")
+        .extension(axum::extract::ConnectInfo(std::net::SocketAddr::from((
+            [203, 0, 113, 9],
+            40000,
+        ))))
+        .body(Body::from(
+            serde_json::json!({ "username": username, "password": password }).to_string(),
+        ))
+        .unwrap()
+}
+
+fn audit_log(token: &str, query: &str) -> axum::http::Request<Body> {
+    TestApp::request(Method::GET, &format!("/api/admin/audit-log?{query}"))
+        .header("Authorization", format!("Bearer {token}"))
+        .body(Body::empty())
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_logins_are_audited() {
+    let app = TestApp::new().await;
+    let (user_id, username) = create_test_user(&app.pool).await;
+    let mut guard = app.cleanup_guard();
+    guard.delete_user(user_id);
+    guard.add(move |pool| async move {
+        let _ = sqlx::query("DELETE FROM system_audit_log WHERE target_id = $1")
+            .bind(user_id)
+            .execute(&pool)
+            .await;
+    });
+    sqlx::query("UPDATE users SET password_hash = $2 WHERE id = $1")
+        .bind(user_id)
+        .bind(vc_server::auth::hash_password(PASSWORD).unwrap())
+        .execute(&app.pool)
+        .await
+        .unwrap();
+
+    let resp = app.oneshot(login(&username, "wrong-password")).await;
+    assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+    let resp = app.oneshot(login(&username, PASSWORD)).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let rows: Vec<(
+        String,
+        Option<Uuid>,
+        Option<String>,
+        Option<String>,
+        serde_json::Value,
+    )> = sqlx::query_as(
+        r"SELECT action, actor_id, host(ip_address), user_agent, details
+          FROM system_audit_log WHERE target_id = $1 ORDER BY created_at",
+    )
+    .bind(user_id)
+    .fetch_all(&app.pool)
+    .await
+    .unwrap();
+    assert_eq!(rows.len(), 2);
+
+    let (action, actor, ip, user_agent, details) = &rows[0];
+    assert_eq!(action, "auth.login_failed");
+    assert_eq!(*actor, None);
+    assert_eq!(ip.as_deref(), Some("203.0.113.9"));
+    assert_eq!(user_agent.as_deref(), Some("audit-test/1.0"));
+    assert_eq!(details["reason"], "invalid_password");
+
+    let (action, actor, _, _, details) = &rows[1];
+    assert_eq!(action, "auth.login");
+    assert_eq!(*actor, Some(user_id));
+    assert_eq!(details["method"], "password");
+}
+
+#[tokio::test]
+async fn test_audit_log_cursor_pagination_and_filters() {
+    let app = TestApp::new().await;
+    let (admin_id, _) = create_test_user(&app.pool).await;
+    let target_id = Uuid::new_v4();
+    let mut guard = app.cleanup_guard();
+    guard.delete_user(admin_id);
+    guard.add(move |pool| async move {
+        let _ = sqlx::query("DELETE FROM system_audit_log WHERE target_id = $1")
+            .bind(target_id)
+            .execute(&pool)
+            .await;
+    });
+    make_admin(&app.pool, admin_id).await;
+    let token = generate_access_token(&app.config, admin_id);
+
+    for action in ["auth.login", "auth.mfa.enabled", "auth.login"] {
+        write_audit_event(
+            &app.pool,
+            AuditEvent {
+                actor_id: Some(admin_id),
+                action,
+                target_type: Some("user"),
+                target_id: Some(target_id),
+                ..AuditEvent::default()
+            },
+        )
+        .await
+        .unwrap();
+    }
+
+    let resp = app
+        .oneshot(audit_log(&token, &format!("target_id={target_id}&limit=2")))
+        .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let page = body_to_json(resp).await;
+    assert_eq!(page["total"], 3);
+    assert_eq!(page["items"].as_array().unwrap().len(), 2);
+    let cursor = page["next_cursor"].as_str().unwrap().to_string();
+    let first_ids: Vec<String> = page["items"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|e| e["id"].as_str().unwrap().to_string())
+        .collect();
+
+    let resp = app
+        .oneshot(audit_log(
+            &token,
+            &format!("target_id={target_id}&limit=2&cursor={cursor}"),
+        ))
+        .await;
+    let page = body_to_json(resp).await;
+    let items = page["items"].as_array().unwrap();
+    assert_eq!(items.len(), 1);
+    assert!(!first_ids.contains(&items[0]["id"].as_str().unwrap().to_string()));
+    assert!(page["next_cursor"].is_null());
+
+    // Exact action and actor filters
+    let resp = app
+        .oneshot(audit_log(
+            &token,
+            &format!("target_id={target_id}&action_type=auth.login&actor_id={admin_id}"),
+        ))
+        .await;
+    let page = body_to_json(resp).await;
+    assert_eq!(page["total"], 2);
+    assert_eq!(page["items"][0]["actor_id"], admin_id.to_string());
+
+    let resp = app
+        .oneshot(audit_log(
+            &token,
+            "from_date=2026-02-01T00:00:00Z&to_date=2026-01-01T00:00:00Z",
+        ))
+        .await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+}
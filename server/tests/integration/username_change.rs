@@ -0,0 +1,131 @@
+//! Username Change Integration Tests
+//!
+//! Run with: `cargo test --test integration username_change -- --nocapture`
+
+use axum::body::Body;
+use axum::http::{Method, StatusCode};
+use uuid::Uuid;
+
+use super::helpers::{
+    body_to_json, create_test_user, delete_user, error_code, generate_access_token, make_admin,
+    TestApp,
+};
+
+fn change_username(token: &str, username: &str) -> axum::http::Request<Body> {
+    TestApp::request(Method::PATCH, "/api/me/username")
+        .header("Authorization", format!("Bearer {token}"))
+        .header("Content-Type", "application/json")
+        .body(Body::from(
+            serde_json::json!({ "username": username }).to_string(),
+        ))
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_username_change_cooldown_and_hold() {
+    let app = TestApp::new().await;
+    let (user_id, old_username) = create_test_user(&app.pool).await;
+    let (other_id, other_username) = create_test_user(&app.pool).await;
+    let mut guard = app.cleanup_guard();
+    guard.delete_user(user_id);
+    guard.delete_user(other_id);
+    let token = generate_access_token(&app.config, user_id);
+    let other_token = generate_access_token(&app.config, other_id);
+
+    let suffix = Uuid::new_v4().simple().to_string()[..8].to_string();
+    let new_username = format!("renamed_{suffix}");
+
+    // Reserved, malformed and taken names are refused up front
+    let resp = app.oneshot(change_username(&token, "Admin")).await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    let resp = app.oneshot(change_username(&token, "no spaces")).await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    let resp = app.oneshot(change_username(&token, &old_username)).await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    let resp = app.oneshot(change_username(&token, &other_username)).await;
+    assert_eq!(resp.status(), StatusCode::CONFLICT);
+    assert_eq!(error_code(resp).await, "USERNAME_TAKEN");
+
+    let resp = app
+        .oneshot(change_username(&token, &new_username.to_uppercase()))
+        .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let changed = body_to_json(resp).await;
+    assert_eq!(changed["username"], new_username.as_str());
+    assert_eq!(changed["previous_username"], old_username.as_str());
+
+    let resp = app
+        .oneshot(
+            TestApp::request(Method::GET, "/auth/me")
+                .header("Authorization", format!("Bearer {token}"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+    assert_eq!(body_to_json(resp).await["username"], new_username.as_str());
+
+    // A second rename has to wait out the cooldown
+    let resp = app
+        .oneshot(change_username(&token, &format!("again_{suffix}")))
+        .await;
+    assert_eq!(resp.status(), StatusCode::TOO_MANY_REQUESTS);
+    assert_eq!(error_code(resp).await, "USERNAME_CHANGE_COOLDOWN");
+
+    // The released name stays held for its previous owner
+    let resp = app
+        .oneshot(change_username(&other_token, &old_username))
+        .await;
+    assert_eq!(resp.status(), StatusCode::CONFLICT);
+    assert_eq!(error_code(resp).await, "USERNAME_TAKEN");
+
+    // Once the hold and cooldown have passed, the name is free again
+    sqlx::query(
+        "UPDATE username_history SET changed_at = NOW() - INTERVAL '31 days' WHERE user_id = $1",
+    )
+    .bind(user_id)
+    .execute(&app.pool)
+    .await
+    .unwrap();
+    let resp = app
+        .oneshot(change_username(&other_token, &old_username))
+        .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let resp = app
+        .oneshot(change_username(&token, &format!("again_{suffix}")))
+        .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_username_history_visible_to_admins() {
+    let app = TestApp::new().await;
+    let (admin_id, _) = create_test_user(&app.pool).await;
+    let (user_id, old_username) = create_test_user(&app.pool).await;
+    let mut guard = app.cleanup_guard();
+    guard.add(move |pool| async move {
+        delete_user(&pool, admin_id).await;
+        delete_user(&pool, user_id).await;
+    });
+    make_admin(&app.pool, admin_id).await;
+    let admin_token = generate_access_token(&app.config, admin_id);
+    let token = generate_access_token(&app.config, user_id);
+
+    let new_username = format!("history_{}", &Uuid::new_v4().simple().to_string()[..8]);
+    let resp = app.oneshot(change_username(&token, &new_username)).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let resp = app
+        .oneshot(
+            TestApp::request(Method::GET, &format!("/api/admin/users/{user_id}/details"))
+                .header("Authorization", format!("Bearer {admin_token}"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let details = body_to_json(resp).await;
+    let history = details["username_history"].as_array().unwrap();
+    assert_eq!(history.len(), 1);
+    assert_eq!(history[0]["old_username"], old_username.as_str());
+    assert_eq!(history[0]["new_username"], new_username.as_str());
+}
@@ -25,6 +25,7 @@ const DEFAULT_MAX_PARTICIPANTS: usize = 25;
 #[test]
 fn test_participant_info_fields() {
     use vc_server::voice::sfu::ParticipantInfo;
+    use vc_server::voice::VoiceActivityMode;
 
     let info = ParticipantInfo {
         user_id: Uuid::new_v4(),
@@ -33,6 +34,9 @@ fn test_participant_info_fields() {
         muted: false,
         screen_sharing: false,
         webcam_active: false,
+        activity_mode: VoiceActivityMode::default(),
+        listener: false,
+        hand_raised: false,
     };
 
     assert!(!info.muted);
@@ -44,6 +48,7 @@ fn test_participant_info_fields() {
 #[test]
 fn test_participant_info_serialization() {
     use vc_server::voice::sfu::ParticipantInfo;
+    use vc_server::voice::VoiceActivityMode;
 
     let info = ParticipantInfo {
         user_id: Uuid::parse_str("550e8400-e29b-41d4-a716-446655440000").unwrap(),
@@ -52,6 +57,9 @@ fn test_participant_info_serialization() {
         muted: true,
         screen_sharing: true,
         webcam_active: false,
+        activity_mode: VoiceActivityMode::default(),
+        listener: false,
+        hand_raised: false,
     };
 
     let json = serde_json::to_string(&info).expect("Should serialize");
@@ -64,6 +72,7 @@ fn test_participant_info_serialization() {
 #[test]
 fn test_participant_info_muted_default() {
     use vc_server::voice::sfu::ParticipantInfo;
+    use vc_server::voice::VoiceActivityMode;
 
     // Test that a new participant starts unmuted
     let info = ParticipantInfo {
@@ -73,6 +82,9 @@ fn test_participant_info_muted_default() {
         muted: false,
         screen_sharing: false,
         webcam_active: false,
+        activity_mode: VoiceActivityMode::default(),
+        listener: false,
+        hand_raised: false,
     };
 
     assert!(!info.muted, "New participants should start unmuted");
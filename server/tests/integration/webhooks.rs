@@ -275,6 +275,33 @@ async fn empty_events_rejected() {
     assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
 }
 
+#[tokio::test]
+async fn guild_log_stream_event_rejected() {
+    let app = webhook_test_app().await;
+    let (user_id, _) = create_test_user(&app.pool).await;
+    let (app_id, _, _) = create_bot_application(&app.pool, user_id).await;
+    let token = generate_access_token(&app.config, user_id);
+    let mut guard = app.cleanup_guard();
+    guard.delete_user(user_id);
+
+    let body = serde_json::json!({
+        "url": "https://example.com/webhook",
+        "subscribed_events": ["audit.logged"],
+    });
+
+    let req = TestApp::request(
+        Method::POST,
+        &format!("/api/applications/{app_id}/webhooks"),
+    )
+    .header("Authorization", format!("Bearer {token}"))
+    .header("Content-Type", "application/json")
+    .body(Body::from(serde_json::to_string(&body).unwrap()))
+    .unwrap();
+
+    let resp = app.oneshot(req).await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+}
+
 #[tokio::test]
 async fn max_5_webhooks_enforced() {
     let app = webhook_test_app().await;
@@ -349,3 +376,165 @@ async fn delivery_log_initially_empty() {
     let json = body_to_json(resp).await;
     assert_eq!(json.as_array().unwrap().len(), 0);
 }
+
+// ============================================================================
+// Dead-Letter Admin Tests
+// ============================================================================
+
+async fn insert_dead_letter(pool: &sqlx::PgPool, webhook_id: uuid::Uuid) -> uuid::Uuid {
+    sqlx::query_scalar(
+        r"
+        INSERT INTO webhook_dead_letters
+            (webhook_id, event_type, event_id, payload, attempts, last_error, event_time)
+        VALUES ($1, 'message.created', $2, $3, 5, 'HTTP 503', NOW())
+        RETURNING id
+        ",
+    )
+    .bind(webhook_id)
+    .bind(uuid::Uuid::new_v4())
+    .bind(serde_json::json!({ "content": "hello" }))
+    .fetch_one(pool)
+    .await
+    .unwrap()
+}
+
+fn admin_request(method: Method, uri: &str, token: &str) -> axum::http::request::Builder {
+    TestApp::request(method, uri)
+        .header("Authorization", format!("Bearer {token}"))
+        .extension(axum::extract::ConnectInfo(std::net::SocketAddr::from((
+            [127, 0, 0, 1],
+            0,
+        ))))
+}
+
+#[tokio::test]
+async fn admin_lists_and_replays_dead_letters() {
+    let app = webhook_test_app().await;
+    let (owner_id, _) = create_test_user(&app.pool).await;
+    let (admin_id, _) = create_test_user(&app.pool).await;
+    let (app_id, _, _) = create_bot_application(&app.pool, owner_id).await;
+    make_admin(&app.pool, admin_id).await;
+    create_elevated_session(&app.pool, admin_id).await;
+    let token = generate_access_token(&app.config, admin_id);
+    let mut guard = app.cleanup_guard();
+    guard.delete_user(owner_id);
+    guard.delete_user(admin_id);
+
+    let wh_id = create_test_webhook(
+        &app.pool,
+        app_id,
+        "https://example.com/dead",
+        &["message.created"],
+    )
+    .await;
+    let first = insert_dead_letter(&app.pool, wh_id).await;
+    insert_dead_letter(&app.pool, wh_id).await;
+    insert_dead_letter(&app.pool, wh_id).await;
+
+    let req = admin_request(
+        Method::GET,
+        &format!("/api/admin/webhooks/dead-letters?webhook_id={wh_id}"),
+        &token,
+    )
+    .body(Body::empty())
+    .unwrap();
+    let resp = app.oneshot(req).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let json = body_to_json(resp).await;
+    assert_eq!(json["total"], 3);
+    assert_eq!(json["items"][0]["webhook_id"], wh_id.to_string());
+    assert_eq!(json["items"][0]["application_id"], app_id.to_string());
+    assert_eq!(json["items"][0]["last_error"], "HTTP 503");
+
+    let req = admin_request(
+        Method::POST,
+        &format!("/api/admin/webhooks/dead-letters/{first}/replay"),
+        &token,
+    )
+    .body(Body::empty())
+    .unwrap();
+    let resp = app.oneshot(req).await;
+    assert_eq!(resp.status(), StatusCode::ACCEPTED);
+    let json = body_to_json(resp).await;
+    assert_eq!(json["replayed"], 1);
+    assert_eq!(json["remaining"], 2);
+
+    let req = admin_request(
+        Method::POST,
+        "/api/admin/webhooks/dead-letters/replay",
+        &token,
+    )
+    .header("Content-Type", "application/json")
+    .body(Body::from(
+        serde_json::json!({ "webhook_id": wh_id }).to_string(),
+    ))
+    .unwrap();
+    let resp = app.oneshot(req).await;
+    assert_eq!(resp.status(), StatusCode::ACCEPTED);
+    let json = body_to_json(resp).await;
+    assert_eq!(json["replayed"], 2);
+    assert_eq!(json["remaining"], 0);
+
+    let remaining: i64 =
+        sqlx::query_scalar("SELECT COUNT(*) FROM webhook_dead_letters WHERE webhook_id = $1")
+            .bind(wh_id)
+            .fetch_one(&app.pool)
+            .await
+            .unwrap();
+    assert_eq!(remaining, 0);
+}
+
+#[tokio::test]
+async fn dead_letter_replay_requires_elevation_and_active_webhook() {
+    let app = webhook_test_app().await;
+    let (owner_id, _) = create_test_user(&app.pool).await;
+    let (admin_id, _) = create_test_user(&app.pool).await;
+    let (app_id, _, _) = create_bot_application(&app.pool, owner_id).await;
+    make_admin(&app.pool, admin_id).await;
+    let token = generate_access_token(&app.config, admin_id);
+    let mut guard = app.cleanup_guard();
+    guard.delete_user(owner_id);
+    guard.delete_user(admin_id);
+
+    let wh_id = create_test_webhook(
+        &app.pool,
+        app_id,
+        "https://example.com/inactive",
+        &["message.created"],
+    )
+    .await;
+    let dead_letter = insert_dead_letter(&app.pool, wh_id).await;
+    let uri = format!("/api/admin/webhooks/dead-letters/{dead_letter}/replay");
+
+    let req = admin_request(Method::POST, &uri, &token)
+        .body(Body::empty())
+        .unwrap();
+    let resp = app.oneshot(req).await;
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+
+    create_elevated_session(&app.pool, admin_id).await;
+    sqlx::query("UPDATE webhooks SET active = false WHERE id = $1")
+        .bind(wh_id)
+        .execute(&app.pool)
+        .await
+        .unwrap();
+
+    let req = admin_request(Method::POST, &uri, &token)
+        .body(Body::empty())
+        .unwrap();
+    let resp = app.oneshot(req).await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+    let req = admin_request(
+        Method::POST,
+        &format!(
+            "/api/admin/webhooks/dead-letters/{}/replay",
+            uuid::Uuid::new_v4()
+        ),
+        &token,
+    )
+    .body(Body::empty())
+    .unwrap();
+    let resp = app.oneshot(req).await;
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+}
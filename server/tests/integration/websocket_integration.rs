@@ -5,6 +5,7 @@ use sqlx::PgPool;
 use vc_server::api::{AppState, AppStateConfig};
 use vc_server::config::Config;
 use vc_server::db;
+use vc_server::ws::fanout::ChannelSubscriptions;
 use vc_server::ws::ServerEvent;
 
 // Mock WebSocket connection logic for testing
@@ -369,7 +370,8 @@ async fn test_websocket_subscribe_denied_without_permission() {
     let ctx = PermissionTestContext::setup().await;
 
     let (tx, mut rx) = mpsc::channel(10);
-    let subscribed_channels = Arc::new(tokio::sync::RwLock::new(std::collections::HashSet::new()));
+    let (topics, _topic_rx) = ctx.state.event_fanout.register();
+    let subscribed_channels = ChannelSubscriptions::new(topics);
     let admin_subscribed = Arc::new(tokio::sync::RwLock::new(false));
     let mut activity_state = vc_server::ws::ActivityState::default();
 
@@ -393,9 +395,8 @@ async fn test_websocket_subscribe_denied_without_permission() {
     assert!(result.is_ok(), "Handler should not crash");
 
     // Check that user was NOT added to subscribed channels
-    let subscribed = subscribed_channels.read().await;
     assert!(
-        !subscribed.contains(&ctx.channel.id),
+        !subscribed_channels.contains(ctx.channel.id).await,
         "User without VIEW_CHANNEL should NOT be subscribed"
     );
 
@@ -425,7 +426,8 @@ async fn test_websocket_subscribe_allowed_with_permission() {
     let ctx = PermissionTestContext::setup().await;
 
     let (tx, mut rx) = mpsc::channel(10);
-    let subscribed_channels = Arc::new(tokio::sync::RwLock::new(std::collections::HashSet::new()));
+    let (topics, _topic_rx) = ctx.state.event_fanout.register();
+    let subscribed_channels = ChannelSubscriptions::new(topics);
     let admin_subscribed = Arc::new(tokio::sync::RwLock::new(false));
     let mut activity_state = vc_server::ws::ActivityState::default();
 
@@ -448,9 +450,8 @@ async fn test_websocket_subscribe_allowed_with_permission() {
     assert!(result.is_ok(), "Handler should succeed");
 
     // Check that user WAS added to subscribed channels
-    let subscribed = subscribed_channels.read().await;
     assert!(
-        subscribed.contains(&ctx.channel.id),
+        subscribed_channels.contains(ctx.channel.id).await,
         "User with VIEW_CHANNEL should be subscribed"
     );
 
@@ -479,7 +480,8 @@ async fn test_websocket_subscribe_owner_bypass() {
     let ctx = PermissionTestContext::setup().await;
 
     let (tx, mut rx) = mpsc::channel(10);
-    let subscribed_channels = Arc::new(tokio::sync::RwLock::new(std::collections::HashSet::new()));
+    let (topics, _topic_rx) = ctx.state.event_fanout.register();
+    let subscribed_channels = ChannelSubscriptions::new(topics);
     let admin_subscribed = Arc::new(tokio::sync::RwLock::new(false));
     let mut activity_state = vc_server::ws::ActivityState::default();
 
@@ -502,9 +504,8 @@ async fn test_websocket_subscribe_owner_bypass() {
     assert!(result.is_ok(), "Handler should succeed for owner");
 
     // Check that owner WAS added to subscribed channels
-    let subscribed = subscribed_channels.read().await;
     assert!(
-        subscribed.contains(&ctx.channel.id),
+        subscribed_channels.contains(ctx.channel.id).await,
         "Guild owner should be able to subscribe"
     );
 
@@ -524,3 +525,279 @@ async fn test_websocket_subscribe_owner_bypass() {
     ctx.cleanup().await;
     println!("✅ WebSocket Subscribe owner bypass test passed.");
 }
+
+/// Test that typing events are coalesced and suppressed for members who cannot send
+#[tokio::test]
+async fn test_websocket_typing_coalesced_and_suppressed() {
+    use tokio::sync::mpsc;
+
+    let ctx = PermissionTestContext::setup().await;
+
+    let subscriber = ctx.state.redis.clone_new();
+    let _ = subscriber.connect();
+    subscriber
+        .wait_for_connect()
+        .await
+        .expect("Redis subscriber connect failed");
+    let () = subscriber
+        .subscribe(vc_server::ws::channels::channel_events(ctx.channel.id))
+        .await
+        .expect("Subscribe failed");
+    let mut message_stream = subscriber.message_rx();
+
+    let (tx, _rx) = mpsc::channel(10);
+    let (topics, _topic_rx) = ctx.state.event_fanout.register();
+    let subscribed_channels = ChannelSubscriptions::new(topics);
+    let admin_subscribed = Arc::new(tokio::sync::RwLock::new(false));
+    let mut activity_state = vc_server::ws::ActivityState::default();
+
+    let typing_event = serde_json::json!({
+        "type": "typing",
+        "channel_id": ctx.channel.id.to_string()
+    })
+    .to_string();
+
+    // user_with_perm can view the channel but lacks SEND_MESSAGES; the owner
+    // types twice in a row.
+    for user_id in [ctx.user_with_perm.id, ctx.owner.id, ctx.owner.id] {
+        vc_server::ws::handle_client_message(
+            &typing_event,
+            user_id,
+            &ctx.state,
+            &tx,
+            &subscribed_channels,
+            &admin_subscribed,
+            &mut activity_state,
+        )
+        .await
+        .expect("Typing should not error");
+    }
+
+    let stop_event = serde_json::json!({
+        "type": "stop_typing",
+        "channel_id": ctx.channel.id.to_string()
+    })
+    .to_string();
+    vc_server::ws::handle_client_message(
+        &stop_event,
+        ctx.owner.id,
+        &ctx.state,
+        &tx,
+        &subscribed_channels,
+        &admin_subscribed,
+        &mut activity_state,
+    )
+    .await
+    .expect("Stop typing should not error");
+
+    let mut received = Vec::new();
+    while let Ok(Ok(message)) = tokio::time::timeout(
+        tokio::time::Duration::from_millis(500),
+        message_stream.recv(),
+    )
+    .await
+    {
+        let payload = message.value.as_str().expect("Payload not string");
+        received.push(serde_json::from_str::<ServerEvent>(payload.as_ref()).unwrap());
+    }
+
+    assert_eq!(
+        received.len(),
+        2,
+        "Expected one start and one stop: {received:?}"
+    );
+    assert!(matches!(
+        received[0],
+        ServerEvent::TypingStart { user_id, .. } if user_id == ctx.owner.id
+    ));
+    assert!(matches!(
+        received[1],
+        ServerEvent::TypingStop { user_id, .. } if user_id == ctx.owner.id
+    ));
+
+    ctx.cleanup().await;
+    println!("✅ WebSocket typing coalescing test passed.");
+}
+
+/// Test that connections on one node share a topic subscription and each
+/// receive events published to it
+#[tokio::test]
+async fn test_websocket_fanout_shares_node_subscription() {
+    let ctx = PermissionTestContext::setup().await;
+    let topic = vc_server::ws::channels::channel_events(ctx.channel.id);
+
+    let (first, mut first_rx) = ctx.state.event_fanout.register();
+    let (second, mut second_rx) = ctx.state.event_fanout.register();
+    first.subscribe(&topic).await.expect("Subscribe failed");
+    second.subscribe(&topic).await.expect("Subscribe failed");
+    assert_eq!(ctx.state.event_fanout.topic_count().await, 1);
+
+    let event = ServerEvent::TypingStart {
+        channel_id: ctx.channel.id,
+        user_id: ctx.owner.id,
+    };
+    vc_server::ws::broadcast_to_channel(&ctx.state.redis, ctx.channel.id, &event)
+        .await
+        .expect("Broadcast failed");
+
+    for rx in [&mut first_rx, &mut second_rx] {
+        let (received_topic, payload) =
+            tokio::time::timeout(tokio::time::Duration::from_secs(2), rx.recv())
+                .await
+                .expect("Timed out waiting for fan-out")
+                .expect("Fan-out closed");
+        assert_eq!(&*received_topic, topic);
+        assert!(matches!(
+            serde_json::from_str::<ServerEvent>(&payload).unwrap(),
+            ServerEvent::TypingStart { user_id, .. } if user_id == ctx.owner.id
+        ));
+    }
+
+    first.close().await;
+    assert_eq!(ctx.state.event_fanout.topic_count().await, 1);
+    second.close().await;
+    assert_eq!(ctx.state.event_fanout.topic_count().await, 0);
+
+    ctx.cleanup().await;
+    println!("✅ WebSocket fan-out test passed.");
+}
+
+/// Test that a connection at its subscription cap evicts the channel it used
+/// least recently
+#[tokio::test]
+async fn test_websocket_subscription_cap_evicts_least_recently_used() {
+    let ctx = PermissionTestContext::setup().await;
+    let (topics, _topic_rx) = ctx.state.event_fanout.register();
+    let subscribed_channels = ChannelSubscriptions::with_limit(topics, 2);
+    let (first, second, third) = (
+        uuid::Uuid::new_v4(),
+        uuid::Uuid::new_v4(),
+        uuid::Uuid::new_v4(),
+    );
+
+    assert_eq!(subscribed_channels.insert(first).await.unwrap(), None);
+    assert_eq!(subscribed_channels.insert(second).await.unwrap(), None);
+    // Using the first channel again makes the second the stalest
+    subscribed_channels.touch(first).await;
+    assert_eq!(
+        subscribed_channels.insert(third).await.unwrap(),
+        Some(second)
+    );
+
+    assert!(subscribed_channels.contains(first).await);
+    assert!(!subscribed_channels.contains(second).await);
+    assert!(subscribed_channels.contains(third).await);
+    assert_eq!(ctx.state.event_fanout.topic_count().await, 2);
+
+    // Re-subscribing to a current channel evicts nothing
+    assert_eq!(subscribed_channels.insert(first).await.unwrap(), None);
+
+    subscribed_channels.close().await;
+    assert_eq!(ctx.state.event_fanout.topic_count().await, 0);
+
+    ctx.cleanup().await;
+    println!("✅ WebSocket subscription cap test passed.");
+}
+
+/// Send one client event and return the server's reply.
+async fn request_reply(
+    ctx: &PermissionTestContext,
+    user_id: uuid::Uuid,
+    event: serde_json::Value,
+) -> ServerEvent {
+    let (tx, mut rx) = tokio::sync::mpsc::channel(10);
+    let (topics, _topic_rx) = ctx.state.event_fanout.register();
+    let subscribed_channels = ChannelSubscriptions::new(topics);
+    let admin_subscribed = Arc::new(tokio::sync::RwLock::new(false));
+    let mut activity_state = vc_server::ws::ActivityState::default();
+
+    vc_server::ws::handle_client_message(
+        &event.to_string(),
+        user_id,
+        &ctx.state,
+        &tx,
+        &subscribed_channels,
+        &admin_subscribed,
+        &mut activity_state,
+    )
+    .await
+    .expect("Handler should not fail");
+
+    tokio::time::timeout(tokio::time::Duration::from_secs(1), rx.recv())
+        .await
+        .expect("Should receive a reply")
+        .expect("Channel should not be closed")
+}
+
+/// Test that member list chunks page through the guild and require membership
+#[tokio::test]
+async fn test_websocket_guild_member_chunks() {
+    let ctx = PermissionTestContext::setup().await;
+
+    // First chunk of two members, ordered by user ID
+    let event = request_reply(
+        &ctx,
+        ctx.owner.id,
+        serde_json::json!({
+            "type": "request_guild_members",
+            "guild_id": ctx.guild_id,
+            "limit": 2,
+            "nonce": "page-1"
+        }),
+    )
+    .await;
+    let ServerEvent::GuildMemberChunk {
+        guild_id,
+        members,
+        next_cursor,
+        nonce,
+    } = event
+    else {
+        panic!("Expected GuildMemberChunk event, got {event:?}");
+    };
+    assert_eq!(guild_id, ctx.guild_id);
+    assert_eq!(nonce.as_deref(), Some("page-1"));
+    assert_eq!(members.len(), 2);
+    assert!(members[0].user_id < members[1].user_id);
+    let cursor = next_cursor.expect("A full chunk should have a cursor");
+    assert_eq!(cursor, members[1].user_id);
+
+    // The last chunk has the remaining member and no cursor
+    let event = request_reply(
+        &ctx,
+        ctx.owner.id,
+        serde_json::json!({
+            "type": "request_guild_members",
+            "guild_id": ctx.guild_id,
+            "after": cursor,
+            "limit": 2
+        }),
+    )
+    .await;
+    let ServerEvent::GuildMemberChunk {
+        members,
+        next_cursor,
+        ..
+    } = event
+    else {
+        panic!("Expected GuildMemberChunk event, got {event:?}");
+    };
+    assert_eq!(members.len(), 1);
+    assert!(members[0].user_id > cursor);
+    assert_eq!(next_cursor, None);
+
+    // Non-members get an error
+    let event = request_reply(
+        &ctx,
+        uuid::Uuid::new_v4(),
+        serde_json::json!({ "type": "request_guild_members", "guild_id": ctx.guild_id }),
+    )
+    .await;
+    match event {
+        ServerEvent::Error { code, .. } => assert_eq!(code, "forbidden"),
+        _ => panic!("Expected Error event, got {event:?}"),
+    }
+
+    ctx.cleanup().await;
+    println!("✅ WebSocket guild member chunk test passed.");
+}
@@ -0,0 +1,74 @@
+//! Integration tests for the WebSocket disconnect audit.
+//!
+//! Run with: `cargo test --test integration ws_disconnects -- --nocapture`
+
+use chrono::{Duration, Utc};
+use vc_server::observability::ws_disconnects::{
+    query_disconnect_breakdown, record_disconnect, Disconnect, DisconnectCause,
+};
+
+use super::helpers::{create_test_user, delete_user, TestApp};
+
+#[tokio::test]
+async fn test_ws_disconnect_breakdown_counts_causes_and_users() {
+    let app = TestApp::new().await;
+    let (user_id, username) = create_test_user(&app.pool).await;
+    let mut guard = app.cleanup_guard();
+    guard.add(move |pool| async move {
+        sqlx::query("DELETE FROM telemetry_ws_disconnects WHERE user_id = $1")
+            .bind(user_id)
+            .execute(&pool)
+            .await
+            .ok();
+        delete_user(&pool, user_id).await;
+    });
+
+    let from = Utc::now() - Duration::seconds(1);
+    for (cause, close_code) in [
+        (DisconnectCause::ClientClose, Some(1000)),
+        (DisconnectCause::ClientClose, Some(1001)),
+        (DisconnectCause::RateLimited, Some(1008)),
+        (DisconnectCause::Timeout, None),
+    ] {
+        record_disconnect(
+            &app.pool,
+            Disconnect {
+                user_id: Some(user_id),
+                cause,
+                close_code,
+                session_secs: Some(12.0),
+            },
+        )
+        .await;
+    }
+
+    let breakdown = query_disconnect_breakdown(&app.pool, from, Utc::now(), 50)
+        .await
+        .unwrap();
+
+    let user = breakdown
+        .top_users
+        .iter()
+        .find(|u| u.user_id == user_id)
+        .expect("user should be among the top disconnecting users");
+    assert_eq!(user.count, 4);
+    assert_eq!(user.username.as_deref(), Some(username.as_str()));
+
+    let cause_count = |cause: &str| {
+        breakdown
+            .by_cause
+            .iter()
+            .find(|c| c.cause == cause)
+            .map_or(0, |c| c.count)
+    };
+    assert!(cause_count("client_close") >= 2);
+    assert!(cause_count("rate_limited") >= 1);
+    assert!(cause_count("timeout") >= 1);
+    assert!(breakdown
+        .by_close_code
+        .iter()
+        .any(|c| c.close_code == Some(1008)));
+    assert!(breakdown.total >= 4);
+    assert_eq!(breakdown.bucket_secs, 60);
+    assert!(!breakdown.timeline.is_empty());
+}
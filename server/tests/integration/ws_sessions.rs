@@ -0,0 +1,112 @@
+//! Resumable WebSocket Session Integration Tests
+//!
+//! Run with: `cargo test --test integration ws_sessions -- --nocapture`
+
+use uuid::Uuid;
+use vc_server::config::Config;
+use vc_server::db;
+use vc_server::ws::session::{ResumeError, Session, REPLAY_BUFFER_LEN};
+
+async fn redis() -> fred::prelude::Client {
+    let config = Config::default_for_test();
+    db::create_redis_client(&config.redis_url)
+        .await
+        .expect("Failed to connect to Redis")
+}
+
+fn typing_event(n: usize) -> String {
+    format!(
+        r#"{{"type":"typing_start","channel_id":"{}","user_id":"{n}"}}"#,
+        Uuid::nil()
+    )
+}
+
+#[tokio::test]
+async fn test_session_resume_replays_missed_events() {
+    let redis = redis().await;
+    let user_id = Uuid::new_v4();
+    let session = Session::start(&redis, user_id, Uuid::new_v4())
+        .await
+        .unwrap();
+
+    let mut sent = Vec::new();
+    for n in 1..=5 {
+        sent.push(session.append(&typing_event(n)).await.unwrap().unwrap());
+    }
+    let first: serde_json::Value = serde_json::from_str(&sent[0]).unwrap();
+    assert_eq!(first["seq"], 1);
+    assert_eq!(first["type"], "typing_start");
+
+    // The client saw events 1-3 before the connection dropped
+    let new_connection = Uuid::new_v4();
+    let resumption = Session::resume(&redis, session.id(), user_id, new_connection, 3)
+        .await
+        .unwrap();
+    assert_eq!(resumption.last_seq, 5);
+    assert_eq!(resumption.events, sent[3..]);
+
+    // The old connection lost the session; the new one continues numbering
+    assert!(session.append(&typing_event(6)).await.unwrap().is_none());
+    let next = resumption
+        .session
+        .append(&typing_event(6))
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(next.starts_with(r#"{"seq":6,"#));
+
+    // Ending the old connection's handle leaves the resumed session alone
+    session.end().await.unwrap();
+    assert!(resumption.session.touch(&[]).await.unwrap());
+    resumption.session.end().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_session_resume_rejections() {
+    let redis = redis().await;
+    let user_id = Uuid::new_v4();
+    let session = Session::start(&redis, user_id, Uuid::new_v4())
+        .await
+        .unwrap();
+    for n in 0..REPLAY_BUFFER_LEN + 10 {
+        session.append(&typing_event(n)).await.unwrap();
+    }
+
+    // Another user cannot resume it
+    let result = Session::resume(&redis, session.id(), Uuid::new_v4(), Uuid::new_v4(), 0).await;
+    assert!(matches!(result, Err(ResumeError::UnknownSession)));
+
+    // Ahead of the session
+    let result = Session::resume(&redis, session.id(), user_id, Uuid::new_v4(), 1_000).await;
+    assert!(matches!(result, Err(ResumeError::InvalidSeq)));
+
+    // The first events have been trimmed from the buffer
+    let result = Session::resume(&redis, session.id(), user_id, Uuid::new_v4(), 5).await;
+    assert!(matches!(result, Err(ResumeError::TooOld)));
+
+    // Ended sessions are gone
+    session.end().await.unwrap();
+    let result = Session::resume(&redis, session.id(), user_id, Uuid::new_v4(), 0).await;
+    assert!(matches!(result, Err(ResumeError::UnknownSession)));
+}
+
+#[tokio::test]
+async fn test_session_peek_channels() {
+    let redis = redis().await;
+    let user_id = Uuid::new_v4();
+    let session = Session::start(&redis, user_id, Uuid::new_v4())
+        .await
+        .unwrap();
+    let channel_id = Uuid::new_v4();
+    assert!(session.touch(&[channel_id]).await.unwrap());
+
+    let channels = Session::peek_channels(&redis, session.id(), user_id)
+        .await
+        .unwrap();
+    assert_eq!(channels, vec![channel_id]);
+
+    let result = Session::peek_channels(&redis, session.id(), Uuid::new_v4()).await;
+    assert!(matches!(result, Err(ResumeError::UnknownSession)));
+
+    session.end().await.unwrap();
+}
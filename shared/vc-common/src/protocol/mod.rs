@@ -14,6 +14,12 @@ pub enum ClientEvent {
     /// Ping for keepalive
     Ping,
 
+    /// Request a server clock reading
+    TimeSync {
+        /// Client clock when the request was sent (Unix milliseconds).
+        client_time: i64,
+    },
+
     /// Subscribe to channel events
     Subscribe {
         /// Channel to subscribe to.
@@ -85,6 +91,17 @@ pub enum ClientEvent {
         /// Voice channel.
         channel_id: Uuid,
     },
+
+    /// Report that the user is active (sent periodically while not idle)
+    PresencePing,
+
+    /// Resume an earlier session; only valid as the first message
+    Resume {
+        /// Session to resume.
+        session_id: Uuid,
+        /// Sequence number of the last event received.
+        last_seq: u64,
+    },
 }
 
 /// Server-to-client WebSocket events.
@@ -94,12 +111,37 @@ pub enum ServerEvent {
     /// Pong response
     Pong,
 
+    /// Server clock reading, sent after `Ready` and in reply to `TimeSync`
+    TimeSync {
+        /// Server clock (Unix milliseconds).
+        server_time: i64,
+        /// The request's `client_time`; absent for the unsolicited reading.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        client_time: Option<i64>,
+    },
+
     /// Connection ready with user info
     Ready {
         /// Authenticated user profile.
         user: UserProfile,
     },
 
+    /// Session resumed after replaying missed events
+    Resumed {
+        /// The resumed session.
+        session_id: Uuid,
+        /// Sequence number of the last event replayed.
+        last_seq: u64,
+        /// Number of events replayed.
+        replayed: usize,
+    },
+
+    /// Session could not be resumed
+    ResumeFailed {
+        /// Reason code.
+        reason: String,
+    },
+
     /// New message
     MessageCreate {
         /// New message.
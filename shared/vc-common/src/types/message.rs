@@ -6,6 +6,36 @@ use uuid::Uuid;
 
 use super::UserProfile;
 
+/// Message kind.
+///
+/// System messages are generated by the server for lifecycle events and are
+/// rendered distinctly from user content.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MessageType {
+    /// Regular user message.
+    #[default]
+    Default,
+    /// A member joined the guild.
+    SystemJoin,
+    /// A message was pinned.
+    SystemPin,
+    /// A DM call started or ended.
+    SystemCall,
+    /// The guild gained or lost supporter perks.
+    SystemBoost,
+    /// Voice channel join/leave activity.
+    SystemVoice,
+}
+
+impl MessageType {
+    /// Whether this is a server-generated system message.
+    #[must_use]
+    pub const fn is_system(self) -> bool {
+        !matches!(self, Self::Default)
+    }
+}
+
 /// Message data.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Message {
@@ -13,8 +43,11 @@ pub struct Message {
     pub id: Uuid,
     /// Channel containing message.
     pub channel_id: Uuid,
-    /// Message author.
+    /// Message author (for system messages, the user the event is about).
     pub author: UserProfile,
+    /// Message kind.
+    #[serde(default)]
+    pub message_type: MessageType,
     /// Message content.
     pub content: String,
     /// Whether E2EE encrypted.
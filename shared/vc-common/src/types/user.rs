@@ -31,6 +31,18 @@ pub struct UserProfile {
     pub display_name: String,
     /// Avatar image URL.
     pub avatar_url: Option<String>,
+    /// Profile banner image URL.
+    #[serde(default)]
+    pub banner_url: Option<String>,
+    /// About-me text (markdown, raw HTML stripped).
+    #[serde(default)]
+    pub about_me: Option<String>,
+    /// Pronouns.
+    #[serde(default)]
+    pub pronouns: Option<String>,
+    /// Profile accent colour (`#rrggbb`).
+    #[serde(default)]
+    pub accent_color: Option<String>,
     /// Current status.
     pub status: UserStatus,
 }
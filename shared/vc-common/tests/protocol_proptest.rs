@@ -0,0 +1,314 @@
+//! Property-based serde tests for the WebSocket protocol.
+//!
+//! Every generated `ClientEvent`/`ServerEvent` must survive a JSON round trip
+//! unchanged, and deserialization must tolerate fields it does not know about
+//! (newer servers and clients add fields without bumping the protocol).
+//!
+//! Run with: `cargo test -p vc-common --test protocol_proptest`
+
+use chrono::{DateTime, TimeZone, Utc};
+use proptest::prelude::*;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use uuid::Uuid;
+use vc_common::protocol::{ClientEvent, ServerEvent, WsMessage};
+use vc_common::{Attachment, Message, MessageType, UserProfile, UserStatus};
+
+fn uuid() -> impl Strategy<Value = Uuid> {
+    any::<u128>().prop_map(Uuid::from_u128)
+}
+
+fn timestamp() -> impl Strategy<Value = DateTime<Utc>> {
+    // 1970 .. 2100, with sub-second precision
+    (0_i64..4_102_444_800, 0_u32..1_000_000_000).prop_map(|(secs, nanos)| {
+        Utc.timestamp_opt(secs, nanos)
+            .single()
+            .expect("timestamp in range")
+    })
+}
+
+fn status() -> impl Strategy<Value = UserStatus> {
+    prop_oneof![
+        Just(UserStatus::Online),
+        Just(UserStatus::Away),
+        Just(UserStatus::Busy),
+        Just(UserStatus::Offline),
+    ]
+}
+
+fn message_type() -> impl Strategy<Value = MessageType> {
+    prop_oneof![
+        Just(MessageType::Default),
+        Just(MessageType::SystemJoin),
+        Just(MessageType::SystemPin),
+        Just(MessageType::SystemCall),
+        Just(MessageType::SystemBoost),
+        Just(MessageType::SystemVoice),
+    ]
+}
+
+fn profile() -> impl Strategy<Value = UserProfile> {
+    (
+        uuid(),
+        "[a-z0-9_]{3,32}",
+        any::<String>(),
+        proptest::option::of(any::<String>()),
+        proptest::option::of(any::<String>()),
+        proptest::option::of(any::<String>()),
+        proptest::option::of(any::<String>()),
+        proptest::option::of("#[0-9a-f]{6}"),
+        status(),
+    )
+        .prop_map(
+            |(
+                id,
+                username,
+                display_name,
+                avatar_url,
+                banner_url,
+                about_me,
+                pronouns,
+                accent_color,
+                status,
+            )| UserProfile {
+                id,
+                username,
+                display_name,
+                avatar_url,
+                banner_url,
+                about_me,
+                pronouns,
+                accent_color,
+                status,
+            },
+        )
+}
+
+fn attachment() -> impl Strategy<Value = Attachment> {
+    (
+        uuid(),
+        any::<String>(),
+        "[a-z]+/[a-z0-9.+-]+",
+        any::<u64>(),
+        any::<String>(),
+    )
+        .prop_map(|(id, filename, mime_type, size, url)| Attachment {
+            id,
+            filename,
+            mime_type,
+            size,
+            url,
+        })
+}
+
+fn message() -> impl Strategy<Value = Message> {
+    (
+        (uuid(), uuid(), profile(), message_type()),
+        (
+            any::<String>(),
+            any::<bool>(),
+            proptest::collection::vec(attachment(), 0..3),
+            proptest::option::of(uuid()),
+            proptest::option::of(timestamp()),
+            timestamp(),
+        ),
+    )
+        .prop_map(
+            |(
+                (id, channel_id, author, message_type),
+                (content, encrypted, attachments, reply_to, edited_at, created_at),
+            )| Message {
+                id,
+                channel_id,
+                author,
+                message_type,
+                content,
+                encrypted,
+                attachments,
+                reply_to,
+                edited_at,
+                created_at,
+            },
+        )
+}
+
+fn client_event() -> impl Strategy<Value = ClientEvent> {
+    prop_oneof![
+        Just(ClientEvent::Ping),
+        any::<i64>().prop_map(|client_time| ClientEvent::TimeSync { client_time }),
+        uuid().prop_map(|channel_id| ClientEvent::Subscribe { channel_id }),
+        uuid().prop_map(|channel_id| ClientEvent::Unsubscribe { channel_id }),
+        uuid().prop_map(|channel_id| ClientEvent::Typing { channel_id }),
+        uuid().prop_map(|channel_id| ClientEvent::StopTyping { channel_id }),
+        uuid().prop_map(|channel_id| ClientEvent::VoiceJoin { channel_id }),
+        uuid().prop_map(|channel_id| ClientEvent::VoiceLeave { channel_id }),
+        (uuid(), any::<String>())
+            .prop_map(|(channel_id, sdp)| ClientEvent::VoiceOffer { channel_id, sdp }),
+        (uuid(), any::<String>())
+            .prop_map(|(channel_id, sdp)| ClientEvent::VoiceAnswer { channel_id, sdp }),
+        (uuid(), any::<String>()).prop_map(|(channel_id, candidate)| ClientEvent::VoiceIce {
+            channel_id,
+            candidate
+        }),
+        uuid().prop_map(|channel_id| ClientEvent::VoiceMute { channel_id }),
+        uuid().prop_map(|channel_id| ClientEvent::VoiceUnmute { channel_id }),
+        Just(ClientEvent::PresencePing),
+        (uuid(), any::<u64>()).prop_map(|(session_id, last_seq)| ClientEvent::Resume {
+            session_id,
+            last_seq
+        }),
+    ]
+}
+
+fn server_event() -> impl Strategy<Value = ServerEvent> {
+    prop_oneof![
+        Just(ServerEvent::Pong),
+        (any::<i64>(), proptest::option::of(any::<i64>())).prop_map(
+            |(server_time, client_time)| ServerEvent::TimeSync {
+                server_time,
+                client_time
+            }
+        ),
+        profile().prop_map(|user| ServerEvent::Ready { user }),
+        (uuid(), any::<u64>(), any::<usize>()).prop_map(|(session_id, last_seq, replayed)| {
+            ServerEvent::Resumed {
+                session_id,
+                last_seq,
+                replayed,
+            }
+        }),
+        any::<String>().prop_map(|reason| ServerEvent::ResumeFailed { reason }),
+        message().prop_map(|message| ServerEvent::MessageCreate { message }),
+        (uuid(), uuid(), any::<String>()).prop_map(|(channel_id, message_id, content)| {
+            ServerEvent::MessageUpdate {
+                channel_id,
+                message_id,
+                content,
+            }
+        }),
+        (uuid(), uuid()).prop_map(|(channel_id, message_id)| ServerEvent::MessageDelete {
+            channel_id,
+            message_id
+        }),
+        (uuid(), profile())
+            .prop_map(|(channel_id, user)| ServerEvent::TypingStart { channel_id, user }),
+        (uuid(), uuid()).prop_map(|(channel_id, user_id)| ServerEvent::TypingStop {
+            channel_id,
+            user_id
+        }),
+        (uuid(), status())
+            .prop_map(|(user_id, status)| ServerEvent::PresenceUpdate { user_id, status }),
+        (uuid(), profile())
+            .prop_map(|(channel_id, user)| ServerEvent::VoiceUserJoined { channel_id, user }),
+        (uuid(), uuid()).prop_map(|(channel_id, user_id)| ServerEvent::VoiceUserLeft {
+            channel_id,
+            user_id
+        }),
+        (uuid(), uuid(), any::<String>()).prop_map(|(channel_id, user_id, sdp)| {
+            ServerEvent::VoiceOffer {
+                channel_id,
+                user_id,
+                sdp,
+            }
+        }),
+        (uuid(), uuid(), any::<String>()).prop_map(|(channel_id, user_id, sdp)| {
+            ServerEvent::VoiceAnswer {
+                channel_id,
+                user_id,
+                sdp,
+            }
+        }),
+        (uuid(), uuid(), any::<String>()).prop_map(|(channel_id, user_id, candidate)| {
+            ServerEvent::VoiceIce {
+                channel_id,
+                user_id,
+                candidate,
+            }
+        }),
+        (uuid(), uuid(), any::<bool>()).prop_map(|(channel_id, user_id, speaking)| {
+            ServerEvent::VoiceSpeaking {
+                channel_id,
+                user_id,
+                speaking,
+            }
+        }),
+        (any::<String>(), any::<String>())
+            .prop_map(|(code, message)| ServerEvent::Error { code, message }),
+    ]
+}
+
+/// Field name no protocol type uses.
+fn unknown_field() -> impl Strategy<Value = String> {
+    "x_[a-z_]{1,16}"
+}
+
+/// Serialize, deserialize and serialize again; both encodings must match.
+fn assert_round_trip<T: Serialize + DeserializeOwned>(value: &T) -> Result<(), TestCaseError> {
+    let json = serde_json::to_value(value).expect("serialize");
+    let decoded: T = serde_json::from_value(json.clone())
+        .map_err(|e| TestCaseError::fail(format!("deserialize {json}: {e}")))?;
+    prop_assert_eq!(serde_json::to_value(&decoded).expect("serialize"), json);
+    Ok(())
+}
+
+/// Add an unknown top-level field; decoding must ignore it.
+fn assert_ignores_unknown<T: Serialize + DeserializeOwned>(
+    value: &T,
+    field: &str,
+    extra: serde_json::Value,
+) -> Result<(), TestCaseError> {
+    let json = serde_json::to_value(value).expect("serialize");
+    let mut extended = json.clone();
+    extended
+        .as_object_mut()
+        .expect("events serialize to objects")
+        .insert(field.to_string(), extra);
+    let decoded: T = serde_json::from_value(extended)
+        .map_err(|e| TestCaseError::fail(format!("unknown field rejected: {e}")))?;
+    prop_assert_eq!(serde_json::to_value(&decoded).expect("serialize"), json);
+    Ok(())
+}
+
+proptest! {
+    #[test]
+    fn client_event_round_trips(event in client_event()) {
+        assert_round_trip(&event)?;
+    }
+
+    #[test]
+    fn server_event_round_trips(event in server_event()) {
+        assert_round_trip(&event)?;
+    }
+
+    #[test]
+    fn client_event_ignores_unknown_fields(
+        event in client_event(),
+        field in unknown_field(),
+        extra in any::<i64>(),
+    ) {
+        assert_ignores_unknown(&event, &field, extra.into())?;
+    }
+
+    #[test]
+    fn server_event_ignores_unknown_fields(
+        event in server_event(),
+        field in unknown_field(),
+        extra in any::<String>(),
+    ) {
+        assert_ignores_unknown(&event, &field, extra.into())?;
+    }
+
+    #[test]
+    fn ws_message_round_trips(
+        id in proptest::option::of("[a-zA-Z0-9-]{1,36}"),
+        event in client_event(),
+    ) {
+        assert_round_trip(&WsMessage { id, event })?;
+    }
+
+    #[test]
+    fn arbitrary_json_never_panics(input in any::<String>()) {
+        let _ = serde_json::from_str::<ClientEvent>(&input);
+        let _ = serde_json::from_str::<ServerEvent>(&input);
+    }
+}